@@ -0,0 +1,71 @@
+use core::hint::black_box;
+
+use benches::bench;
+use bevy_animation::compression::{AnimationCompression, QuantizedRotationCurve};
+use bevy_math::{curve::*, ops, EulerRot, Quat};
+use criterion::{criterion_group, BenchmarkId, Criterion};
+
+criterion_group!(benches, reduce_keyframes, sample_rotations);
+
+/// One minute of rotation keyframes sampled at 60 frames per second, as exported for cinematics.
+fn cinematic_rotations() -> (Vec<f32>, Vec<Quat>) {
+    (0..3600)
+        .map(|frame| {
+            let t = frame as f32 / 60.0;
+            let rotation = Quat::from_euler(
+                EulerRot::YXZ,
+                t * 0.5,
+                ops::sin(t * 0.7) * 0.3,
+                ops::cos(t * 1.3) * 0.1,
+            );
+            (t, rotation)
+        })
+        .unzip()
+}
+
+fn reduce_keyframes(c: &mut Criterion) {
+    let (times, rotations) = cinematic_rotations();
+    let compression = AnimationCompression::default();
+
+    c.bench_function(bench!("reduce_rotations"), |b| {
+        b.iter(|| compression.reduce_rotations(black_box(&times), black_box(&rotations)));
+    });
+}
+
+fn sample_rotations(c: &mut Criterion) {
+    let (times, rotations) = cinematic_rotations();
+    let (reduced_times, reduced_rotations) =
+        AnimationCompression::default().reduce_rotations(&times, &rotations);
+
+    let raw =
+        UnevenSampleAutoCurve::new(times.iter().copied().zip(rotations.iter().copied())).unwrap();
+    let reduced = UnevenSampleAutoCurve::new(
+        reduced_times
+            .iter()
+            .copied()
+            .zip(reduced_rotations.iter().copied()),
+    )
+    .unwrap();
+    let quantized =
+        QuantizedRotationCurve::new(reduced_times.into_iter().zip(reduced_rotations)).unwrap();
+
+    let mut group = c.benchmark_group(bench!("sample_rotations"));
+    group.bench_with_input(BenchmarkId::from_parameter("raw"), &raw, |b, curve| {
+        b.iter(|| curve.sample_clamped(black_box(31.7)));
+    });
+    group.bench_with_input(
+        BenchmarkId::from_parameter("reduced"),
+        &reduced,
+        |b, curve| {
+            b.iter(|| curve.sample_clamped(black_box(31.7)));
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::from_parameter("reduced_quantized"),
+        &quantized,
+        |b, curve| {
+            b.iter(|| curve.sample_clamped(black_box(31.7)));
+        },
+    );
+    group.finish();
+}
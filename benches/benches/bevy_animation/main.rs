@@ -0,0 +1,5 @@
+use criterion::criterion_main;
+
+mod compression;
+
+criterion_main!(compression::benches);
@@ -1,7 +1,7 @@
 //! Traits and type for interpolating between values.
 
 use crate::util;
-use bevy_color::{Laba, LinearRgba, Oklaba, Srgba, Xyza};
+use bevy_color::{Color, Laba, LinearRgba, Oklaba, Srgba, Xyza};
 use bevy_math::*;
 use bevy_reflect::Reflect;
 use bevy_transform::prelude::Transform;
@@ -95,6 +95,23 @@ impl_color_animatable!(Oklaba);
 impl_color_animatable!(Srgba);
 impl_color_animatable!(Xyza);
 
+// Color is blended in linear RGB, whichever color space its inputs are in
+impl Animatable for Color {
+    #[inline]
+    fn interpolate(a: &Self, b: &Self, t: f32) -> Self {
+        Color::LinearRgba(LinearRgba::interpolate(&a.to_linear(), &b.to_linear(), t))
+    }
+
+    #[inline]
+    fn blend(inputs: impl Iterator<Item = BlendInput<Self>>) -> Self {
+        Color::LinearRgba(LinearRgba::blend(inputs.map(|input| BlendInput {
+            weight: input.weight,
+            value: input.value.to_linear(),
+            additive: input.additive,
+        })))
+    }
+}
+
 // Vec3 is special cased to use Vec3A internally for blending
 impl Animatable for Vec3 {
     #[inline]
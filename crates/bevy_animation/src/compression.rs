@@ -0,0 +1,304 @@
+//! Compression of animation keyframes, trading a bounded error for smaller clips.
+//!
+//! Two techniques are provided:
+//! - keyframe reduction, with [`reduce_keyframes`], removes the keyframes which can be
+//!   interpolated from their neighbors within a tolerance;
+//! - rotation quantization, with [`QuantizedRotationCurve`], stores each rotation in 8 bytes
+//!   instead of 16.
+//!
+//! [`AnimationCompression`] combines them for the curves of transforms. The glTF loader applies
+//! it to linearly interpolated curves when its `animation_compression` setting is set; other
+//! curves are kept uncompressed.
+
+use bevy_math::{
+    curve::{cores::*, *},
+    Quat, Vec3, Vec4,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use serde::{Deserialize, Serialize};
+
+/// Settings for compressing the keyframes of transform animations.
+///
+/// The tolerances are the largest error allowed at the removed keyframes.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Default)]
+pub struct AnimationCompression {
+    /// The largest distance between an interpolated and an original translation.
+    pub translation_tolerance: f32,
+    /// The largest angle, in radians, between an interpolated and an original rotation.
+    pub rotation_tolerance: f32,
+    /// The largest distance between an interpolated and an original scale.
+    pub scale_tolerance: f32,
+    /// Whether rotations are stored in a [`QuantizedRotationCurve`].
+    pub quantize_rotations: bool,
+}
+
+impl Default for AnimationCompression {
+    fn default() -> Self {
+        Self {
+            translation_tolerance: 1e-4,
+            rotation_tolerance: 1e-3,
+            scale_tolerance: 1e-4,
+            quantize_rotations: true,
+        }
+    }
+}
+
+impl AnimationCompression {
+    /// Settings which don't remove keyframes or quantize rotations.
+    pub const LOSSLESS: Self = Self {
+        translation_tolerance: 0.0,
+        rotation_tolerance: 0.0,
+        scale_tolerance: 0.0,
+        quantize_rotations: false,
+    };
+
+    /// Removes the translation keyframes which can be linearly interpolated from their neighbors.
+    pub fn reduce_translations(
+        &self,
+        times: &[f32],
+        translations: &[Vec3],
+    ) -> (Vec<f32>, Vec<Vec3>) {
+        reduce_keyframes(
+            times,
+            translations,
+            self.translation_tolerance,
+            |a, b, t| a.lerp(*b, t),
+            |a, b| a.distance(*b),
+        )
+    }
+
+    /// Removes the rotation keyframes which can be spherically interpolated from their neighbors.
+    pub fn reduce_rotations(&self, times: &[f32], rotations: &[Quat]) -> (Vec<f32>, Vec<Quat>) {
+        reduce_keyframes(
+            times,
+            rotations,
+            self.rotation_tolerance,
+            |a, b, t| a.slerp(*b, t),
+            |a, b| a.angle_between(*b),
+        )
+    }
+
+    /// Removes the scale keyframes which can be linearly interpolated from their neighbors.
+    pub fn reduce_scales(&self, times: &[f32], scales: &[Vec3]) -> (Vec<f32>, Vec<Vec3>) {
+        reduce_keyframes(
+            times,
+            scales,
+            self.scale_tolerance,
+            |a, b, t| a.lerp(*b, t),
+            |a, b| a.distance(*b),
+        )
+    }
+}
+
+/// Removes the keyframes which can be interpolated from the remaining keyframes with an error of at
+/// most `tolerance`.
+///
+/// The first and last keyframes are always kept. `interpolate` must match the interpolation of
+/// the curve the keyframes are used in, and `error` measures the difference between two values.
+/// `times` must be sorted, and have the same length as `values`.
+pub fn reduce_keyframes<T: Clone>(
+    times: &[f32],
+    values: &[T],
+    tolerance: f32,
+    interpolate: impl Fn(&T, &T, f32) -> T,
+    error: impl Fn(&T, &T) -> f32,
+) -> (Vec<f32>, Vec<T>) {
+    let len = times.len().min(values.len());
+    if len <= 2 {
+        return (times[..len].to_vec(), values[..len].to_vec());
+    }
+
+    // Douglas-Peucker: keep the keyframe furthest from the interpolation of the segment, and
+    // recurse on both sides, until all the keyframes are within the tolerance.
+    let mut kept = vec![false; len];
+    kept[0] = true;
+    kept[len - 1] = true;
+    let mut segments = vec![(0, len - 1)];
+    while let Some((start, end)) = segments.pop() {
+        let duration = times[end] - times[start];
+        let mut furthest = None;
+        let mut max_error = tolerance;
+        for index in start + 1..end {
+            let t = if duration > 0.0 {
+                (times[index] - times[start]) / duration
+            } else {
+                0.0
+            };
+            let interpolated = interpolate(&values[start], &values[end], t);
+            let error = error(&interpolated, &values[index]);
+            if error > max_error {
+                max_error = error;
+                furthest = Some(index);
+            }
+        }
+        if let Some(index) = furthest {
+            kept[index] = true;
+            segments.push((start, index));
+            segments.push((index, end));
+        }
+    }
+
+    (0..len)
+        .filter(|index| kept[*index])
+        .map(|index| (times[index], values[index].clone()))
+        .unzip()
+}
+
+/// A keyframe-defined curve of rotations, interpolated spherically, storing each rotation in
+/// 8 bytes.
+///
+/// Rotations are stored with the "smallest three" encoding: the largest component of the
+/// quaternion is dropped, since it can be computed from the others, and the three others are
+/// quantized to 20 bits each. The error is below `1e-5` radians.
+#[derive(Debug, Clone, Reflect)]
+pub struct QuantizedRotationCurve {
+    core: UnevenCore<u64>,
+}
+
+impl Curve<Quat> for QuantizedRotationCurve {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.core.domain()
+    }
+
+    #[inline]
+    fn sample_clamped(&self, t: f32) -> Quat {
+        match self.core.sample_interp(t) {
+            InterpolationDatum::Exact(rotation)
+            | InterpolationDatum::LeftTail(rotation)
+            | InterpolationDatum::RightTail(rotation) => dequantize_rotation(*rotation),
+            InterpolationDatum::Between(start, end, t) => {
+                dequantize_rotation(*start).slerp(dequantize_rotation(*end), t)
+            }
+        }
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> Quat {
+        self.sample_clamped(t)
+    }
+}
+
+impl QuantizedRotationCurve {
+    /// Create a new [`QuantizedRotationCurve`]. If the curve could not be constructed from the
+    /// given data, an error is returned.
+    #[inline]
+    pub fn new(
+        timed_rotations: impl IntoIterator<Item = (f32, Quat)>,
+    ) -> Result<Self, UnevenCoreError> {
+        Ok(Self {
+            core: UnevenCore::new(
+                timed_rotations
+                    .into_iter()
+                    .map(|(time, rotation)| (time, quantize_rotation(rotation))),
+            )?,
+        })
+    }
+}
+
+const COMPONENT_BITS: u32 = 20;
+const COMPONENT_MAX: f32 = ((1 << COMPONENT_BITS) - 1) as f32;
+const COMPONENT_MASK: u64 = (1 << COMPONENT_BITS) - 1;
+
+fn quantize_rotation(rotation: Quat) -> u64 {
+    let mut components = Vec4::from(rotation.normalize()).to_array();
+    let largest = (0..4)
+        .max_by(|a, b| components[*a].abs().total_cmp(&components[*b].abs()))
+        .unwrap_or(3);
+    // `q` and `-q` are the same rotation, so the largest component can be made positive.
+    if components[largest] < 0.0 {
+        components = components.map(|component| -component);
+    }
+
+    let mut packed = largest as u64;
+    for (index, component) in components.into_iter().enumerate() {
+        if index != largest {
+            // The other components are in `[-1/sqrt(2), 1/sqrt(2)]`.
+            let normalized = (component * core::f32::consts::SQRT_2 + 1.0) * 0.5;
+            let quantized = (normalized.clamp(0.0, 1.0) * COMPONENT_MAX).round() as u64;
+            packed = (packed << COMPONENT_BITS) | quantized;
+        }
+    }
+    packed
+}
+
+fn dequantize_rotation(packed: u64) -> Quat {
+    let largest = (packed >> (3 * COMPONENT_BITS)) as usize & 3;
+    let mut components = [0.0; 4];
+    let mut shift = 3 * COMPONENT_BITS;
+    let mut sum_squares = 0.0;
+    for (index, component) in components.iter_mut().enumerate() {
+        if index != largest {
+            shift -= COMPONENT_BITS;
+            let quantized = ((packed >> shift) & COMPONENT_MASK) as f32;
+            *component = (quantized / COMPONENT_MAX * 2.0 - 1.0) * core::f32::consts::FRAC_1_SQRT_2;
+            sum_squares += *component * *component;
+        }
+    }
+    components[largest] = (1.0 - sum_squares).max(0.0).sqrt();
+    Quat::from_array(components).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{vec3, EulerRot};
+
+    #[test]
+    fn reduce_linear_keyframes() {
+        let times = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let translations = [
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(2.0, 0.0, 0.0),
+            vec3(2.0, 1.0, 0.0),
+            vec3(2.0, 2.0, 0.0),
+        ];
+        let (times, translations) =
+            AnimationCompression::default().reduce_translations(&times, &translations);
+        assert_eq!(times, [0.0, 2.0, 4.0]);
+        assert_eq!(
+            translations,
+            [
+                vec3(0.0, 0.0, 0.0),
+                vec3(2.0, 0.0, 0.0),
+                vec3(2.0, 2.0, 0.0)
+            ]
+        );
+
+        // Nothing is removed without tolerance, unless it's exactly interpolated.
+        let times = [0.0, 1.0, 2.0];
+        let scales = [Vec3::ONE, Vec3::splat(1.5), Vec3::splat(3.0)];
+        let (reduced, _) = AnimationCompression::LOSSLESS.reduce_scales(&times, &scales);
+        assert_eq!(reduced, times);
+    }
+
+    /// Asserts that two rotations are equal up to the quantization error.
+    ///
+    /// Each packed component has a step of `sqrt(2) / (2^20 - 1)`, so after renormalization
+    /// every component is off by well under `1e-5`. The angle is not compared directly
+    /// because `acos` is too imprecise in `f32` near a dot product of one.
+    fn assert_rotations_close(a: Quat, b: Quat) {
+        // `q` and `-q` are the same rotation.
+        let b = if a.dot(b) < 0.0 { -b } else { b };
+        let difference = (Vec4::from(a) - Vec4::from(b)).abs().max_element();
+        assert!(difference < 1e-5, "{a} and {b} differ by {difference}");
+        assert!(a.dot(b) > 1.0 - 1e-5);
+    }
+
+    #[test]
+    fn quantized_rotations() {
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, -2.0, 0.5), (-3.0, 0.2, 2.9)] {
+            let rotation = Quat::from_euler(EulerRot::XYZ, x, y, z);
+            let dequantized = dequantize_rotation(quantize_rotation(rotation));
+            assert_rotations_close(rotation, dequantized);
+        }
+
+        let start = Quat::from_rotation_y(0.0);
+        let end = Quat::from_rotation_y(2.0);
+        let curve = QuantizedRotationCurve::new([(0.0, start), (1.0, end)]).unwrap();
+        let sample = curve.sample_clamped(0.5);
+        assert_rotations_close(sample, start.slerp(end, 0.5));
+    }
+}
@@ -11,6 +11,7 @@ extern crate alloc;
 
 pub mod animatable;
 pub mod animation_curves;
+pub mod compression;
 pub mod gltf_curves;
 pub mod graph;
 pub mod transition;
@@ -9,15 +9,15 @@ use alloc::{
 };
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
-    component::RequiredComponentsError,
-    event::{event_update_system, EventCursor},
+    component::{Immutable, RequiredComponentsError},
+    event::{event_update_system, EventCursor, EventRetention},
     intern::Interned,
     prelude::*,
     schedule::{ScheduleBuildSettings, ScheduleLabel},
     system::{IntoObserverSystem, SystemId, SystemInput},
 };
 use bevy_platform_support::collections::HashMap;
-use core::{fmt::Debug, num::NonZero, panic::AssertUnwindSafe};
+use core::{fmt::Debug, hash::Hash, num::NonZero, panic::AssertUnwindSafe};
 use log::debug;
 use thiserror::Error;
 
@@ -364,6 +364,59 @@ impl App {
         self
     }
 
+    /// Initializes `T` event handling like [`add_event`](Self::add_event), and sets how long the
+    /// events are kept by the [`event_update_system`].
+    ///
+    /// See [`EventRetention`] for the available policies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{prelude::*, event::EventRetention};
+    /// #
+    /// # #[derive(Event)]
+    /// # struct MyEvent;
+    /// # let mut app = App::new();
+    /// #
+    /// // Keep the events for 10 frames, for systems that only run every few frames.
+    /// app.set_event_retention::<MyEvent>(EventRetention::Updates(10));
+    /// ```
+    pub fn set_event_retention<T>(&mut self, retention: EventRetention) -> &mut Self
+    where
+        T: Event,
+    {
+        self.main_mut().set_event_retention::<T>(retention);
+        self
+    }
+
+    /// Initializes a channel of consumable `T` events by inserting a
+    /// [`ConsumableEvents::<T>`](bevy_ecs::event::ConsumableEvents) resource, updated by the
+    /// [`event_update_system`] in [`First`].
+    ///
+    /// Unlike the events added with [`add_event`](Self::add_event), each consumable event is
+    /// handled by at most one [`EventConsumer`](bevy_ecs::event::EventConsumer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Event)]
+    /// # struct MyEvent;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_consumable_event::<MyEvent>();
+    /// ```
+    pub fn add_consumable_event<T>(&mut self) -> &mut Self
+    where
+        T: Event,
+    {
+        self.main_mut().add_consumable_event::<T>();
+        self
+    }
+
     /// Inserts the [`Resource`] into the app, overwriting any existing resource of the same type.
     ///
     /// There is also an [`init_resource`](Self::init_resource) for resources that have
@@ -1324,6 +1377,18 @@ impl App {
         self.world_mut().add_observer(observer);
         self
     }
+
+    /// Creates the [`Index`](bevy_ecs::index::Index) of the component `C`, which maps its values to the entities holding them.
+    ///
+    /// Calling this again for the same component does nothing, so plugins can declare the indexes they use
+    /// without coordinating. See [`World::init_index`] for more details.
+    pub fn init_index<C>(&mut self) -> &mut Self
+    where
+        C: Component<Mutability = Immutable> + Eq + Hash + Clone,
+    {
+        self.world_mut().init_index::<C>();
+        self
+    }
 }
 
 type RunnerFn = Box<dyn FnOnce(App) -> AppExit>;
@@ -0,0 +1,178 @@
+//! Catches panics in the schedules of an [`App`], to clean up before exiting.
+
+use crate::{App, AppExit, Plugin};
+use alloc::string::{String, ToString};
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    world::World,
+};
+use core::{any::Any, fmt, num::NonZero, panic::AssertUnwindSafe};
+use log::error;
+use std::panic::catch_unwind;
+
+/// Catches the panics of the schedules run by [`Main`](crate::Main), and runs the [`OnCrash`]
+/// schedule before exiting the app.
+///
+/// Without this plugin, a panic in a system unwinds through [`App::update`] and tears the app
+/// down without any cleanup. With it, when a schedule panics:
+/// 1. the remaining schedules of the frame are skipped,
+/// 2. a [`CrashReport`] resource describing the panic is inserted,
+/// 3. the [`OnCrash`] schedule runs, where systems can save the game, flush logs or dump the
+///    world to a scene,
+/// 4. the report is logged, and an [`AppExit::Error`] is sent so that the runner exits the app.
+///
+/// ```no_run
+/// # use bevy_app::{prelude::*, CrashHandlerPlugin, CrashReport, OnCrash};
+/// # use bevy_ecs::prelude::*;
+/// fn save_game(report: Res<CrashReport>) {
+///     // Save the game before exiting...
+/// }
+///
+/// App::new()
+///     .add_plugins(CrashHandlerPlugin)
+///     .add_systems(OnCrash, save_game)
+///     .run();
+/// ```
+///
+/// Panics are caught per schedule, where possible: the schedule which panicked may have left
+/// the world partially updated, and isn't run again. Panics can't be caught when the app is
+/// built with `panic = "abort"`, and the schedules of sub-apps, like the render app, aren't
+/// covered.
+#[derive(Default)]
+pub struct CrashHandlerPlugin;
+
+impl Plugin for CrashHandlerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CrashHandler>().init_schedule(OnCrash);
+    }
+}
+
+/// Configures the [`CrashHandlerPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct CrashHandler {
+    /// Whether panics are caught. When `false`, panics unwind through [`App::update`] as if the
+    /// plugin wasn't added.
+    pub enabled: bool,
+    /// The code of the [`AppExit::Error`] sent after a crash.
+    pub exit_code: NonZero<u8>,
+}
+
+impl Default for CrashHandler {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            exit_code: NonZero::<u8>::MIN,
+        }
+    }
+}
+
+/// The schedule run when a schedule panics, with the [`CrashHandlerPlugin`].
+///
+/// The [`CrashReport`] resource describes the panic. Panics in this schedule are logged and
+/// ignored.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnCrash;
+
+/// Describes a panic caught by the [`CrashHandlerPlugin`].
+///
+/// This resource is available to the systems of the [`OnCrash`] schedule.
+#[derive(Resource, Debug, Clone)]
+pub struct CrashReport {
+    /// The schedule which panicked.
+    pub schedule: InternedScheduleLabel,
+    /// The message of the panic.
+    pub message: String,
+}
+
+impl fmt::Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the {:?} schedule panicked: {}",
+            self.schedule, self.message
+        )
+    }
+}
+
+/// Runs the schedule `label`, catching its panics if the [`CrashHandler`] is enabled.
+///
+/// Returns `false` if the schedule panicked and the panic was handled.
+pub(crate) fn run_schedule(world: &mut World, label: InternedScheduleLabel) -> bool {
+    let catch_panics = world
+        .get_resource::<CrashHandler>()
+        .is_some_and(|handler| handler.enabled);
+    // A second crash isn't handled, so that it isn't hidden if the app keeps running.
+    if !catch_panics || world.contains_resource::<CrashReport>() {
+        let _ = world.try_run_schedule(label);
+        return true;
+    }
+
+    match catch_unwind(AssertUnwindSafe(|| world.try_run_schedule(label))) {
+        Ok(_) => true,
+        Err(payload) => {
+            handle_crash(world, label, payload.as_ref());
+            false
+        }
+    }
+}
+
+fn handle_crash(world: &mut World, schedule: InternedScheduleLabel, payload: &(dyn Any + Send)) {
+    let report = CrashReport {
+        schedule,
+        message: panic_message(payload),
+    };
+    world.insert_resource(report.clone());
+
+    if let Err(payload) = catch_unwind(AssertUnwindSafe(|| world.try_run_schedule(OnCrash))) {
+        error!(
+            "The OnCrash schedule panicked: {}",
+            panic_message(payload.as_ref())
+        );
+    }
+
+    error!("The app crashed: {report}");
+    let exit_code = world.resource::<CrashHandler>().exit_code;
+    world.send_event(AppExit::Error(exit_code));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Update;
+    use bevy_ecs::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct Saved(bool);
+
+    #[test]
+    fn crash_runs_on_crash_and_exits() {
+        fn crash() {
+            panic!("boom");
+        }
+
+        let mut app = App::new();
+        app.add_plugins(CrashHandlerPlugin)
+            .init_resource::<Saved>()
+            .add_systems(Update, crash)
+            .add_systems(OnCrash, |mut saved: ResMut<Saved>| saved.0 = true);
+
+        app.update();
+
+        assert!(app.world().resource::<Saved>().0);
+        let report = app.world().resource::<CrashReport>();
+        assert_eq!(report.schedule, Update.intern());
+        assert_eq!(report.message, "boom");
+        assert_eq!(app.should_exit(), Some(AppExit::error()));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{App, First, Plugin};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    resource::Resource,
+    system::ResMut,
+};
+use bevy_tasks::{JobGraph, JobId, JobStatus};
+
+/// Adds the [`Jobs`] resource, and sends a [`JobFinished`] event whenever one of its jobs
+/// finishes.
+///
+/// This plugin is included in the [`TaskPoolPlugin`](crate::TaskPoolPlugin).
+#[derive(Default)]
+pub struct JobsPlugin;
+
+impl Plugin for JobsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Jobs>()
+            .add_event::<JobFinished>()
+            .add_systems(First, update_jobs);
+    }
+}
+
+/// The [`JobGraph`] of the app, whose jobs run on the
+/// [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool) across frames.
+///
+/// Jobs can depend on other jobs, report their progress and be canceled, see [`JobGraph`]. When
+/// a job finishes, a [`JobFinished`] event is sent in the [`First`] schedule, after which its
+/// output can be retrieved with [`JobGraph::take_output`]:
+///
+/// ```
+/// # use bevy_app::{Jobs, JobFinished};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_tasks::JobId;
+/// #[derive(Resource)]
+/// struct NavMeshJob(JobId);
+///
+/// fn bake_nav_mesh(mut commands: Commands, mut jobs: ResMut<Jobs>) {
+///     let geometry = jobs.submit(&[], |_| vec![0.0_f32; 1024]);
+///     let nav_mesh = jobs.submit(&[geometry], |progress| {
+///         progress.set(1.0);
+///         42_u32
+///     });
+///     commands.insert_resource(NavMeshJob(nav_mesh));
+/// }
+///
+/// fn receive_nav_mesh(
+///     mut events: EventReader<JobFinished>,
+///     mut jobs: ResMut<Jobs>,
+///     nav_mesh_job: Res<NavMeshJob>,
+/// ) {
+///     for event in events.read() {
+///         if event.job == nav_mesh_job.0 {
+///             let nav_mesh = jobs.take_output::<u32>(event.job);
+///             // ...
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(bake_nav_mesh);
+/// # bevy_ecs::system::assert_is_system(receive_nav_mesh);
+/// ```
+///
+/// Finished jobs stay in the graph until their output is taken or they are
+/// [removed](JobGraph::remove).
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Jobs(pub JobGraph);
+
+/// An [`Event`] sent when a job of the [`Jobs`] resource finishes.
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct JobFinished {
+    /// The job which finished.
+    pub job: JobId,
+    /// Whether the job [completed](JobStatus::Completed), was [canceled](JobStatus::Canceled),
+    /// or [failed](JobStatus::Failed).
+    pub status: JobStatus,
+}
+
+impl From<bevy_tasks::JobFinished> for JobFinished {
+    fn from(finished: bevy_tasks::JobFinished) -> Self {
+        Self {
+            job: finished.job,
+            status: finished.status,
+        }
+    }
+}
+
+/// Starts the jobs of the [`Jobs`] resource whose dependencies are completed, and sends a
+/// [`JobFinished`] event for each finished job.
+pub fn update_jobs(mut jobs: ResMut<Jobs>, mut events: EventWriter<JobFinished>) {
+    events.send_batch(jobs.update().map(JobFinished::from));
+}
@@ -21,6 +21,10 @@ extern crate std;
 extern crate alloc;
 
 mod app;
+#[cfg(feature = "std")]
+mod crash_handler;
+#[cfg(all(feature = "bevy_tasks", feature = "std"))]
+mod jobs;
 mod main_schedule;
 mod panic_handler;
 mod plugin;
@@ -31,8 +35,13 @@ mod sub_app;
 mod task_pool_plugin;
 #[cfg(all(any(unix, windows), feature = "std"))]
 mod terminal_ctrl_c_handler;
+mod test_app;
 
 pub use app::*;
+#[cfg(feature = "std")]
+pub use crash_handler::*;
+#[cfg(all(feature = "bevy_tasks", feature = "std"))]
+pub use jobs::*;
 pub use main_schedule::*;
 pub use panic_handler::*;
 pub use plugin::*;
@@ -43,6 +52,7 @@ pub use sub_app::*;
 pub use task_pool_plugin::*;
 #[cfg(all(any(unix, windows), feature = "std"))]
 pub use terminal_ctrl_c_handler::*;
+pub use test_app::*;
 
 /// The app prelude.
 ///
@@ -268,22 +268,46 @@ impl Main {
     /// A system that runs the "main schedule"
     pub fn run_main(world: &mut World, mut run_at_least_once: Local<bool>) {
         if !*run_at_least_once {
-            world.resource_scope(|world, order: Mut<MainScheduleOrder>| {
-                for &label in &order.startup_labels {
-                    let _ = world.try_run_schedule(label);
-                }
-            });
             *run_at_least_once = true;
+            let completed = world.resource_scope(|world, order: Mut<MainScheduleOrder>| {
+                order
+                    .startup_labels
+                    .iter()
+                    .all(|&label| run_schedule(world, label))
+            });
+            if !completed {
+                return;
+            }
         }
 
         world.resource_scope(|world, order: Mut<MainScheduleOrder>| {
             for &label in &order.labels {
-                let _ = world.try_run_schedule(label);
+                if !run_schedule(world, label) {
+                    break;
+                }
             }
         });
     }
 }
 
+/// Runs the schedule `label` if it exists.
+///
+/// Returns `false` if the schedule panicked and the panic was handled by the
+/// [`CrashHandlerPlugin`](crate::CrashHandlerPlugin), in which case the following schedules
+/// are skipped.
+fn run_schedule(world: &mut World, label: InternedScheduleLabel) -> bool {
+    #[cfg(feature = "std")]
+    {
+        crate::crash_handler::run_schedule(world, label)
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = world.try_run_schedule(label);
+        true
+    }
+}
+
 /// Initializes the [`Main`] schedule, sub schedules, and resources for a given [`App`].
 pub struct MainSchedulePlugin;
 
@@ -1,7 +1,7 @@
 use crate::{App, AppLabel, InternedAppLabel, Plugin, Plugins, PluginsState};
 use alloc::{boxed::Box, string::String, vec::Vec};
 use bevy_ecs::{
-    event::EventRegistry,
+    event::{ConsumableEvents, EventRegistry, EventRetention},
     prelude::*,
     schedule::{InternedScheduleLabel, ScheduleBuildSettings, ScheduleLabel},
     system::{SystemId, SystemInput},
@@ -347,6 +347,31 @@ impl SubApp {
         self
     }
 
+    /// See [`App::set_event_retention`].
+    pub fn set_event_retention<T>(&mut self, retention: EventRetention) -> &mut Self
+    where
+        T: Event,
+    {
+        self.add_event::<T>();
+        self.world
+            .resource_mut::<Events<T>>()
+            .set_retention(retention);
+
+        self
+    }
+
+    /// See [`App::add_consumable_event`].
+    pub fn add_consumable_event<T>(&mut self) -> &mut Self
+    where
+        T: Event,
+    {
+        if !self.world.contains_resource::<ConsumableEvents<T>>() {
+            EventRegistry::register_consumable_event::<T>(self.world_mut());
+        }
+
+        self
+    }
+
     /// See [`App::add_plugins`].
     pub fn add_plugins<M>(&mut self, plugins: impl Plugins<M>) -> &mut Self {
         self.run_as_app(|app| plugins.add_to_app(app));
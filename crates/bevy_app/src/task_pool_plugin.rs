@@ -21,6 +21,8 @@ use {crate::Last, bevy_ecs::prelude::NonSend};
 use bevy_tasks::tick_global_task_pools_on_main_thread;
 
 /// Setup of default task pools: [`AsyncComputeTaskPool`], [`ComputeTaskPool`], [`IoTaskPool`].
+///
+/// With the `std` feature, this also adds the [`JobsPlugin`](crate::JobsPlugin).
 #[derive(Default)]
 pub struct TaskPoolPlugin {
     /// Options for the [`TaskPool`](bevy_tasks::TaskPool) created at application start.
@@ -34,6 +36,9 @@ impl Plugin for TaskPoolPlugin {
 
         #[cfg(not(target_arch = "wasm32"))]
         _app.add_systems(Last, tick_global_task_pools);
+
+        #[cfg(feature = "std")]
+        _app.add_plugins(crate::JobsPlugin);
     }
 }
 /// A dummy type that is [`!Send`](Send), to force systems to run on the main thread.
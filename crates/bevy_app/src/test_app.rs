@@ -0,0 +1,245 @@
+use crate::{App, AppExit, Plugins, PluginsState};
+use alloc::vec::Vec;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    event::Event,
+    query::{QueryData, QueryFilter, ReadOnlyQueryData},
+    resource::Resource,
+    world::World,
+};
+use core::fmt::Debug;
+
+/// An [`App`] driven step by step from tests, without a runner.
+///
+/// Each call to [`step`](Self::step) runs one frame of the app, after finishing the plugins on the
+/// first call. Between steps, tests inject events with [`send_event`](Self::send_event) and check
+/// the state of the world with methods like [`assert_resource`](Self::assert_resource).
+///
+/// The plugins of Bevy extend the harness with traits implemented for [`TestApp`], to advance
+/// time by a fixed delta every frame, press keys and buttons, or wait for assets to load. The
+/// [`App`] itself is reachable through [`Deref`](core::ops::Deref).
+///
+/// ```
+/// # use bevy_app::{prelude::*, TestApp};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Resource, Default, PartialEq, Debug)]
+/// struct Score(u32);
+///
+/// #[derive(Event)]
+/// struct Scored(u32);
+///
+/// fn count_score(mut score: ResMut<Score>, mut events: EventReader<Scored>) {
+///     for Scored(points) in events.read() {
+///         score.0 += points;
+///     }
+/// }
+///
+/// let mut app = TestApp::new(|app: &mut App| {
+///     app.init_resource::<Score>()
+///         .add_event::<Scored>()
+///         .add_systems(Update, count_score);
+/// });
+///
+/// app.send_event(Scored(2)).send_event(Scored(3)).step();
+/// app.assert_resource(&Score(5));
+///
+/// app.step_frames(10);
+/// app.assert_resource(&Score(5));
+/// ```
+#[derive(Deref, DerefMut)]
+pub struct TestApp(pub App);
+
+impl TestApp {
+    /// Creates an [`App`] with the given plugins.
+    ///
+    /// Tasks only make progress if the [`TaskPoolPlugin`](crate::TaskPoolPlugin) is added, for
+    /// example with the `MinimalPlugins`.
+    pub fn new<M>(plugins: impl Plugins<M>) -> Self {
+        let mut app = App::new();
+        app.add_plugins(plugins);
+        Self(app)
+    }
+
+    /// Waits for the plugins to be ready, and runs [`App::finish`] and [`App::cleanup`], if it
+    /// wasn't done already.
+    ///
+    /// This is called by the first [`step`](Self::step), but can be called earlier to access the
+    /// resources inserted by the plugins when they finish.
+    pub fn finish(&mut self) -> &mut Self {
+        while self.0.plugins_state() == PluginsState::Adding {
+            tick_task_pools();
+        }
+        if self.0.plugins_state() == PluginsState::Ready {
+            self.0.finish();
+        }
+        if self.0.plugins_state() == PluginsState::Finished {
+            self.0.cleanup();
+        }
+        self
+    }
+
+    /// Runs one frame of the app.
+    pub fn step(&mut self) -> &mut Self {
+        self.finish();
+        self.0.update();
+        self
+    }
+
+    /// Runs `frames` frames of the app.
+    pub fn step_frames(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            self.step();
+        }
+        self
+    }
+
+    /// Runs frames of the app until `condition` returns `true`, or `max_frames` frames ran.
+    ///
+    /// Returns the number of frames which ran, or `None` if the condition never returned `true`.
+    pub fn step_until(
+        &mut self,
+        max_frames: u32,
+        mut condition: impl FnMut(&mut World) -> bool,
+    ) -> Option<u32> {
+        for frame in 1..=max_frames {
+            self.step();
+            if condition(self.0.world_mut()) {
+                return Some(frame);
+            }
+        }
+        None
+    }
+
+    /// Sends an event, which is read by the systems of the next frames.
+    pub fn send_event<E: Event>(&mut self, event: E) -> &mut Self {
+        self.0.world_mut().send_event(event);
+        self
+    }
+
+    /// Returns the resource of type `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.0.world().resource::<R>()
+    }
+
+    /// Returns the items of the query, in an unspecified order.
+    pub fn query<D: ReadOnlyQueryData, F: QueryFilter>(&mut self) -> Vec<D::Item<'_>> {
+        let mut query = self.0.world_mut().query_filtered::<D, F>();
+        query.iter(self.0.world()).collect()
+    }
+
+    /// Returns the number of entities matching the query.
+    pub fn count<D: QueryData, F: QueryFilter>(&mut self) -> usize {
+        let world = self.0.world_mut();
+        let mut query = world.query_filtered::<D, F>();
+        query.iter(world).count()
+    }
+
+    /// Returns the [`AppExit`] sent by the systems of the app, if any.
+    pub fn exit(&self) -> Option<AppExit> {
+        self.0.should_exit()
+    }
+
+    /// Asserts that the resource of type `R` is equal to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist, or isn't equal to `expected`.
+    #[track_caller]
+    pub fn assert_resource<R: Resource + PartialEq + Debug>(&self, expected: &R) -> &Self {
+        assert_eq!(self.resource::<R>(), expected);
+        self
+    }
+
+    /// Asserts that `expected` entities match the query.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of entities matching the query isn't `expected`.
+    #[track_caller]
+    pub fn assert_count<D: QueryData, F: QueryFilter>(&mut self, expected: usize) -> &mut Self {
+        let count = self.count::<D, F>();
+        assert_eq!(
+            count,
+            expected,
+            "expected {expected} entities matching `Query<{}, {}>`, found {count}",
+            core::any::type_name::<D>(),
+            core::any::type_name::<F>(),
+        );
+        self
+    }
+}
+
+impl From<App> for TestApp {
+    fn from(app: App) -> Self {
+        Self(app)
+    }
+}
+
+/// Ticks the global task pools while the plugins aren't ready, since the app doesn't run yet.
+fn tick_task_pools() {
+    #[cfg(all(feature = "bevy_tasks", not(target_arch = "wasm32")))]
+    if bevy_tasks::ComputeTaskPool::try_get().is_some()
+        && bevy_tasks::AsyncComputeTaskPool::try_get().is_some()
+        && bevy_tasks::IoTaskPool::try_get().is_some()
+    {
+        bevy_tasks::tick_global_task_pools_on_main_thread();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{App, Plugin, TestApp, Update};
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader},
+        query::With,
+        system::Commands,
+    };
+
+    #[derive(Component)]
+    struct Spawned;
+
+    #[derive(Event)]
+    struct Spawn(u32);
+
+    struct SpawnPlugin;
+
+    impl Plugin for SpawnPlugin {
+        fn build(&self, app: &mut App) {
+            app.add_event::<Spawn>().add_systems(
+                Update,
+                |mut commands: Commands, mut events: EventReader<Spawn>| {
+                    for Spawn(count) in events.read() {
+                        for _ in 0..*count {
+                            commands.spawn(Spawned);
+                        }
+                    }
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn step_and_assert() {
+        let mut app = TestApp::new(SpawnPlugin);
+        app.step().assert_count::<Entity, With<Spawned>>(0);
+
+        app.send_event(Spawn(3))
+            .step()
+            .assert_count::<Entity, With<Spawned>>(3);
+        assert_eq!(app.query::<Entity, With<Spawned>>().len(), 3);
+
+        app.send_event(Spawn(1));
+        let frames = app.step_until(5, |world| {
+            world.query::<&Spawned>().iter(world).count() == 4
+        });
+        assert_eq!(frames, Some(1));
+        assert_eq!(app.step_until(3, |_| false), None);
+        assert!(app.exit().is_none());
+    }
+}
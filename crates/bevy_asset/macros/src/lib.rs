@@ -4,13 +4,14 @@
 use bevy_macro_utils::BevyManifest;
 use proc_macro::{Span, TokenStream};
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, Data, DeriveInput, Path};
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Fields, LitStr, Path};
 
 pub(crate) fn bevy_asset_path() -> Path {
     BevyManifest::shared().get_path("bevy_asset")
 }
 
 const DEPENDENCY_ATTRIBUTE: &str = "dependency";
+const ASSET_ATTRIBUTE: &str = "asset";
 
 #[proc_macro_derive(Asset, attributes(dependency))]
 pub fn derive_asset(input: TokenStream) -> TokenStream {
@@ -122,3 +123,93 @@ fn derive_dependency_visitor_internal(
         }
     })
 }
+
+#[proc_macro_derive(AssetCollection, attributes(asset))]
+pub fn derive_asset_collection(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let bevy_asset_path: Path = bevy_asset_path();
+    match derive_asset_collection_internal(&ast, &bevy_asset_path) {
+        Ok(asset_collection) => TokenStream::from(asset_collection),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn derive_asset_collection_internal(
+    ast: &DeriveInput,
+    bevy_asset_path: &Path,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    let Data::Struct(DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = &ast.data
+    else {
+        return Err(syn::Error::new_spanned(
+            struct_name,
+            "AssetCollection can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut loads = Vec::new();
+    let mut visitors = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let mut path: Option<LitStr> = None;
+        let mut folder = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident(ASSET_ATTRIBUTE))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("path") {
+                    path = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("folder") {
+                    folder = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported asset attribute, expected `path` or `folder`"))
+                }
+            })?;
+        }
+        let Some(path) = path else {
+            return Err(syn::Error::new_spanned(
+                field,
+                "missing the path of the asset, add `#[asset(path = \"...\")]`",
+            ));
+        };
+
+        loads.push(if folder {
+            quote!(#ident: asset_server.load_folder(#path))
+        } else {
+            quote!(#ident: asset_server.load(#path))
+        });
+        visitors.push(
+            quote!(#bevy_asset_path::VisitAssetDependencies::visit_dependencies(&self.#ident, visit);),
+        );
+    }
+
+    // prevent unused variable warning in case there are no assets
+    let visit = if visitors.is_empty() {
+        quote! { _visit }
+    } else {
+        quote! { visit }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #bevy_asset_path::AssetCollection for #struct_name #type_generics #where_clause {
+            fn load(asset_server: &#bevy_asset_path::AssetServer) -> Self {
+                Self {
+                    #(#loads,)*
+                }
+            }
+
+            fn visit_handles(&self, #visit: &mut impl FnMut(#bevy_asset_path::UntypedAssetId)) {
+                #(#visitors)*
+            }
+        }
+    })
+}
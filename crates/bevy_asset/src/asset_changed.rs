@@ -3,14 +3,16 @@
 //! Like [`Changed`](bevy_ecs::prelude::Changed), but for [`Asset`]s,
 //! and triggers whenever the handle or the underlying asset changes.
 
-use crate::{AsAssetId, Asset, AssetId};
+use crate::{AsAssetId, AsUntypedAssetId, Asset, AssetHolders, AssetId};
+use alloc::vec::Vec;
 use bevy_ecs::component::Components;
 use bevy_ecs::{
     archetype::Archetype,
     component::{ComponentId, Tick},
-    prelude::{Entity, Resource, World},
-    query::{FilteredAccess, QueryData, QueryFilter, ReadFetch, WorldQuery},
+    prelude::{Entity, Query, Res, Resource, World},
+    query::{FilteredAccess, QueryData, QueryFilter, QueryState, ReadFetch, WorldQuery},
     storage::{Table, TableRow},
+    system::{ReadOnlySystemParam, SystemChangeTick, SystemMeta, SystemParam},
     world::unsafe_world_cell::UnsafeWorldCell,
 };
 use bevy_platform_support::collections::HashMap;
@@ -29,16 +31,51 @@ use tracing::error;
 #[derive(Resource)]
 pub(crate) struct AssetChanges<A: Asset> {
     change_ticks: HashMap<AssetId<A>, Tick>,
+    /// The changes in the order they happened, to find the assets changed since a tick without
+    /// going through all the assets. The entries of assets which changed again since, or were
+    /// removed, are stale and skipped.
+    log: Vec<(Tick, AssetId<A>)>,
     last_change_tick: Tick,
 }
 
 impl<A: Asset> AssetChanges<A> {
     pub(crate) fn insert(&mut self, asset_id: AssetId<A>, tick: Tick) {
         self.last_change_tick = tick;
-        self.change_ticks.insert(asset_id, tick);
+        if self.change_ticks.insert(asset_id, tick) != Some(tick) {
+            self.log.push((tick, asset_id));
+            self.compact();
+        }
     }
+
     pub(crate) fn remove(&mut self, asset_id: &AssetId<A>) {
         self.change_ticks.remove(asset_id);
+        self.compact();
+    }
+
+    /// Returns the assets which changed after `last_run`, from the most recent change.
+    ///
+    /// This only goes through the assets which changed, however many assets there are.
+    pub(crate) fn changed_since(
+        &self,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> impl Iterator<Item = AssetId<A>> + '_ {
+        self.log
+            .iter()
+            .rev()
+            .take_while(move |(tick, _)| tick.is_newer_than(last_run, this_run))
+            .filter(|(tick, asset_id)| self.change_ticks.get(asset_id) == Some(tick))
+            .map(|(_, asset_id)| *asset_id)
+    }
+
+    /// Drops the stale entries of the log once they outnumber the live ones, so that the log
+    /// doesn't grow with each change.
+    fn compact(&mut self) {
+        if self.log.len() > 2 * self.change_ticks.len() + 64 {
+            let change_ticks = &self.change_ticks;
+            self.log
+                .retain(|(tick, asset_id)| change_ticks.get(asset_id) == Some(tick));
+        }
     }
 }
 
@@ -46,29 +83,30 @@ impl<A: Asset> Default for AssetChanges<A> {
     fn default() -> Self {
         Self {
             change_ticks: Default::default(),
+            log: Vec::new(),
             last_change_tick: Tick::new(0),
         }
     }
 }
 
-struct AssetChangeCheck<'w, A: AsAssetId> {
+struct AssetChangeCheck<'w, A: Asset> {
     // This should never be `None` in practice, but we need to handle the case
     // where the `AssetChanges` resource was removed.
-    change_ticks: Option<&'w HashMap<AssetId<A::Asset>, Tick>>,
+    change_ticks: Option<&'w HashMap<AssetId<A>, Tick>>,
     last_run: Tick,
     this_run: Tick,
 }
 
-impl<A: AsAssetId> Clone for AssetChangeCheck<'_, A> {
+impl<A: Asset> Clone for AssetChangeCheck<'_, A> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<A: AsAssetId> Copy for AssetChangeCheck<'_, A> {}
+impl<A: Asset> Copy for AssetChangeCheck<'_, A> {}
 
-impl<'w, A: AsAssetId> AssetChangeCheck<'w, A> {
-    fn new(changes: &'w AssetChanges<A::Asset>, last_run: Tick, this_run: Tick) -> Self {
+impl<'w, A: Asset> AssetChangeCheck<'w, A> {
+    fn new(changes: &'w AssetChanges<A>, last_run: Tick, this_run: Tick) -> Self {
         Self {
             change_ticks: Some(&changes.change_ticks),
             last_run,
@@ -77,28 +115,35 @@ impl<'w, A: AsAssetId> AssetChangeCheck<'w, A> {
     }
     // TODO(perf): some sort of caching? Each check has two levels of indirection,
     // which is not optimal.
-    fn has_changed(&self, handle: &A) -> bool {
+    fn has_changed<C: AsUntypedAssetId>(&self, handle: &C) -> bool {
         let is_newer = |tick: &Tick| tick.is_newer_than(self.last_run, self.this_run);
-        let id = handle.as_asset_id();
+        // Untyped handles to assets of another type never match.
+        let Ok(id) = handle.as_untyped_asset_id().try_typed::<A>() else {
+            return false;
+        };
 
         self.change_ticks
             .is_some_and(|change_ticks| change_ticks.get(&id).is_some_and(is_newer))
     }
 }
 
-/// Filter that selects entities with an `A` for an asset that changed
-/// after the system last ran, where `A` is a component that implements
-/// [`AsAssetId`].
+/// Filter that selects entities with a `C` for an asset of type `A` that changed
+/// after the system last ran, where `C` is a component that implements
+/// [`AsUntypedAssetId`], like every [`AsAssetId`] handle wrapper.
+///
+/// For [`AsAssetId`] components, `A` defaults to their [`AsAssetId::Asset`]. Components holding
+/// untyped handles need to name the asset type, e.g. `AssetChanged<MyUntypedHandle, Image>`, and
+/// only match the entities whose handle points to an `A`.
 ///
-/// Unlike `Changed<A>`, this is true whenever the asset for the `A`
+/// Unlike `Changed<C>`, this is true whenever the asset for the `C`
 /// in `ResMut<Assets<A>>` changed. For example, when a mesh changed through the
-/// [`Assets<Mesh>::get_mut`] method, `AssetChanged<Mesh>` will iterate over all
-/// entities with the `Handle<Mesh>` for that mesh. Meanwhile, `Changed<Handle<Mesh>>`
+/// [`Assets<Mesh>::get_mut`] method, `AssetChanged<Mesh3d>` will iterate over all
+/// entities with the `Mesh3d` for that mesh. Meanwhile, `Changed<Mesh3d>`
 /// will iterate over no entities.
 ///
-/// Swapping the actual `A` component is a common pattern. So you
-/// should check for _both_ `AssetChanged<A>` and `Changed<A>` with
-/// `Or<(Changed<A>, AssetChanged<A>)>`.
+/// Swapping the actual `C` component is a common pattern. So you
+/// should check for _both_ `AssetChanged<C>` and `Changed<C>` with
+/// `Or<(Changed<C>, AssetChanged<C>)>`.
 ///
 /// # Quirks
 ///
@@ -113,25 +158,30 @@ impl<'w, A: AsAssetId> AssetChangeCheck<'w, A> {
 ///
 /// # Performance
 ///
-/// When at least one `A` is updated, this will
-/// read a hashmap once per entity with an `A` component. The
-/// runtime of the query is proportional to how many entities with an `A`
+/// When at least one `A` asset is updated, this will
+/// read a hashmap once per entity with a `C` component. The
+/// runtime of the query is proportional to how many entities with a `C`
 /// it matches.
 ///
 /// If no `A` asset updated since the last time the system ran, then no lookups occur.
 ///
+/// When few of many entities are expected to match, prefer [`AssetChangedEntities`], whose
+/// runtime is proportional to the number of changed assets instead.
+///
 /// [`AssetEvents`]: crate::AssetEvents
 /// [`Assets<Mesh>::get_mut`]: crate::Assets::get_mut
-pub struct AssetChanged<A: AsAssetId>(PhantomData<A>);
+pub struct AssetChanged<C: AsUntypedAssetId, A: Asset = <C as AsAssetId>::Asset>(
+    PhantomData<(C, A)>,
+);
 
 /// [`WorldQuery`] fetch for [`AssetChanged`].
 #[doc(hidden)]
-pub struct AssetChangedFetch<'w, A: AsAssetId> {
-    inner: Option<ReadFetch<'w, A>>,
+pub struct AssetChangedFetch<'w, C: AsUntypedAssetId, A: Asset> {
+    inner: Option<ReadFetch<'w, C>>,
     check: AssetChangeCheck<'w, A>,
 }
 
-impl<'w, A: AsAssetId> Clone for AssetChangedFetch<'w, A> {
+impl<'w, C: AsUntypedAssetId, A: Asset> Clone for AssetChangedFetch<'w, C, A> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner,
@@ -142,18 +192,18 @@ impl<'w, A: AsAssetId> Clone for AssetChangedFetch<'w, A> {
 
 /// [`WorldQuery`] state for [`AssetChanged`].
 #[doc(hidden)]
-pub struct AssetChangedState<A: AsAssetId> {
+pub struct AssetChangedState<C: AsUntypedAssetId, A: Asset> {
     asset_id: ComponentId,
     resource_id: ComponentId,
-    _asset: PhantomData<fn(A)>,
+    _asset: PhantomData<fn(C, A)>,
 }
 
 #[expect(unsafe_code, reason = "WorldQuery is an unsafe trait.")]
 /// SAFETY: `ROQueryFetch<Self>` is the same as `QueryFetch<Self>`
-unsafe impl<A: AsAssetId> WorldQuery for AssetChanged<A> {
-    type Fetch<'w> = AssetChangedFetch<'w, A>;
+unsafe impl<C: AsUntypedAssetId, A: Asset> WorldQuery for AssetChanged<C, A> {
+    type Fetch<'w> = AssetChangedFetch<'w, C, A>;
 
-    type State = AssetChangedState<A>;
+    type State = AssetChangedState<C, A>;
 
     fn shrink_fetch<'wlong: 'wshort, 'wshort>(fetch: Self::Fetch<'wlong>) -> Self::Fetch<'wshort> {
         fetch
@@ -167,16 +217,17 @@ unsafe impl<A: AsAssetId> WorldQuery for AssetChanged<A> {
     ) -> Self::Fetch<'w> {
         // SAFETY:
         // - `AssetChanges` is private and only accessed mutably in the `AssetEvents` schedule
-        // - `resource_id` was obtained from the type ID of `AssetChanges<A::Asset>`.
+        // - `resource_id` was obtained from the type ID of `AssetChanges<A>`.
         let Some(changes) = (unsafe {
             world
                 .get_resource_by_id(state.resource_id)
-                .map(|ptr| ptr.deref::<AssetChanges<A::Asset>>())
+                .map(|ptr| ptr.deref::<AssetChanges<A>>())
         }) else {
             error!(
                 "AssetChanges<{ty}> resource was removed, please do not remove \
-                AssetChanges<{ty}> when using the AssetChanged<{ty}> world query",
-                ty = ShortName::of::<A>()
+                AssetChanges<{ty}> when using the AssetChanged<{component}, {ty}> world query",
+                ty = ShortName::of::<A>(),
+                component = ShortName::of::<C>()
             );
 
             return AssetChangedFetch {
@@ -192,15 +243,15 @@ unsafe impl<A: AsAssetId> WorldQuery for AssetChanged<A> {
 
         AssetChangedFetch {
             inner: has_updates.then(||
-                    // SAFETY: We delegate to the inner `init_fetch` for `A`
+                    // SAFETY: We delegate to the inner `init_fetch` for `C`
                     unsafe {
-                        <&A>::init_fetch(world, &state.asset_id, last_run, this_run)
+                        <&C>::init_fetch(world, &state.asset_id, last_run, this_run)
                     }),
             check: AssetChangeCheck::new(changes, last_run, this_run),
         }
     }
 
-    const IS_DENSE: bool = <&A>::IS_DENSE;
+    const IS_DENSE: bool = <&C>::IS_DENSE;
 
     unsafe fn set_archetype<'w>(
         fetch: &mut Self::Fetch<'w>,
@@ -209,31 +260,31 @@ unsafe impl<A: AsAssetId> WorldQuery for AssetChanged<A> {
         table: &'w Table,
     ) {
         if let Some(inner) = &mut fetch.inner {
-            // SAFETY: We delegate to the inner `set_archetype` for `A`
+            // SAFETY: We delegate to the inner `set_archetype` for `C`
             unsafe {
-                <&A>::set_archetype(inner, &state.asset_id, archetype, table);
+                <&C>::set_archetype(inner, &state.asset_id, archetype, table);
             }
         }
     }
 
     unsafe fn set_table<'w>(fetch: &mut Self::Fetch<'w>, state: &Self::State, table: &'w Table) {
         if let Some(inner) = &mut fetch.inner {
-            // SAFETY: We delegate to the inner `set_table` for `A`
+            // SAFETY: We delegate to the inner `set_table` for `C`
             unsafe {
-                <&A>::set_table(inner, &state.asset_id, table);
+                <&C>::set_table(inner, &state.asset_id, table);
             }
         }
     }
 
     #[inline]
     fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
-        <&A>::update_component_access(&state.asset_id, access);
+        <&C>::update_component_access(&state.asset_id, access);
         access.add_resource_read(state.resource_id);
     }
 
-    fn init_state(world: &mut World) -> AssetChangedState<A> {
-        let resource_id = world.init_resource::<AssetChanges<A::Asset>>();
-        let asset_id = world.register_component::<A>();
+    fn init_state(world: &mut World) -> AssetChangedState<C, A> {
+        let resource_id = world.init_resource::<AssetChanges<A>>();
+        let asset_id = world.register_component::<C>();
         AssetChangedState {
             asset_id,
             resource_id,
@@ -242,8 +293,8 @@ unsafe impl<A: AsAssetId> WorldQuery for AssetChanged<A> {
     }
 
     fn get_state(components: &Components) -> Option<Self::State> {
-        let resource_id = components.resource_id::<AssetChanges<A::Asset>>()?;
-        let asset_id = components.component_id::<A>()?;
+        let resource_id = components.resource_id::<AssetChanges<A>>()?;
+        let asset_id = components.component_id::<C>()?;
         Some(AssetChangedState {
             asset_id,
             resource_id,
@@ -261,7 +312,7 @@ unsafe impl<A: AsAssetId> WorldQuery for AssetChanged<A> {
 
 #[expect(unsafe_code, reason = "QueryFilter is an unsafe trait.")]
 /// SAFETY: read-only access
-unsafe impl<A: AsAssetId> QueryFilter for AssetChanged<A> {
+unsafe impl<C: AsUntypedAssetId, A: Asset> QueryFilter for AssetChanged<C, A> {
     const IS_ARCHETYPAL: bool = false;
 
     #[inline]
@@ -271,18 +322,133 @@ unsafe impl<A: AsAssetId> QueryFilter for AssetChanged<A> {
         table_row: TableRow,
     ) -> bool {
         fetch.inner.as_mut().is_some_and(|inner| {
-            // SAFETY: We delegate to the inner `fetch` for `A`
+            // SAFETY: We delegate to the inner `fetch` for `C`
             unsafe {
-                let handle = <&A>::fetch(inner, entity, table_row);
+                let handle = <&C>::fetch(inner, entity, table_row);
                 fetch.check.has_changed(handle)
             }
         })
     }
 }
 
+/// System parameter listing the entities with a `C` component for an asset of type `A` that
+/// changed after the system last ran.
+///
+/// This selects the same entities as the [`AssetChanged<C, A>`] filter, but instead of checking
+/// every entity with a `C`, it goes through the changed `A` assets and the entities holding them.
+/// Its runtime is proportional to the number of assets which changed, rather than to the number
+/// of entities with a `C`.
+///
+/// The entities are found in the [`AssetHolders`], so `C` must be tracked with
+/// [`AssetApp::track_asset_holders`](crate::AssetApp::track_asset_holders). Entities holding an
+/// asset through an untracked component aren't listed.
+pub struct AssetChangedEntities<'w, 's, C: AsUntypedAssetId, A: Asset = <C as AsAssetId>::Asset> {
+    changes: Res<'w, AssetChanges<A>>,
+    holders: Res<'w, AssetHolders>,
+    components: Query<'w, 's, &'static C>,
+    ticks: SystemChangeTick,
+}
+
+impl<C: AsUntypedAssetId, A: Asset> AssetChangedEntities<'_, '_, C, A> {
+    /// Returns the entities with a `C` for an asset that changed after the system last ran,
+    /// from the most recently changed asset.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.changes
+            .changed_since(self.ticks.last_run(), self.ticks.this_run())
+            .flat_map(move |id| {
+                let id = id.untyped();
+                self.holders.get(id).filter(move |entity| {
+                    self.components
+                        .get(*entity)
+                        .is_ok_and(|component| component.as_untyped_asset_id() == id)
+                })
+            })
+    }
+
+    /// Returns `true` if no asset held through a `C` changed after the system last ran.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+type AssetChangedEntitiesState<C, A> = (
+    Res<'static, AssetChanges<A>>,
+    Res<'static, AssetHolders>,
+    Query<'static, 'static, &'static C>,
+    SystemChangeTick,
+);
+
+#[expect(
+    unsafe_code,
+    reason = "We cannot implement SystemParam without using unsafe code."
+)]
+// SAFETY: All methods are delegated to existing `SystemParam` implementations
+unsafe impl<C: AsUntypedAssetId, A: Asset> SystemParam for AssetChangedEntities<'_, '_, C, A> {
+    type State = (ComponentId, ComponentId, QueryState<&'static C>, ());
+    type Item<'w, 's> = AssetChangedEntities<'w, 's, C, A>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        // The changes of `A` are only recorded once something reads them.
+        world.init_resource::<AssetChanges<A>>();
+        AssetChangedEntitiesState::<C, A>::init_state(world, system_meta)
+    }
+
+    unsafe fn new_archetype(
+        state: &mut Self::State,
+        archetype: &Archetype,
+        system_meta: &mut SystemMeta,
+    ) {
+        // SAFETY: The caller ensures that `archetype` is from the World the state was initialized from in `init_state`.
+        unsafe {
+            AssetChangedEntitiesState::<C, A>::new_archetype(state, archetype, system_meta);
+        };
+    }
+
+    #[inline]
+    unsafe fn validate_param(
+        state: &Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell,
+    ) -> bool {
+        // SAFETY: Delegated to existing `SystemParam` implementations.
+        unsafe { AssetChangedEntitiesState::<C, A>::validate_param(state, system_meta, world) }
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: Delegated to existing `SystemParam` implementations.
+        let (changes, holders, components, ticks) = unsafe {
+            AssetChangedEntitiesState::<C, A>::get_param(state, system_meta, world, change_tick)
+        };
+        AssetChangedEntities {
+            changes,
+            holders,
+            components,
+            ticks,
+        }
+    }
+}
+
+#[expect(
+    unsafe_code,
+    reason = "We cannot implement ReadOnlySystemParam without using unsafe code."
+)]
+// SAFETY: Each field is `ReadOnlySystemParam`.
+unsafe impl<C: AsUntypedAssetId, A: Asset> ReadOnlySystemParam
+    for AssetChangedEntities<'_, '_, C, A>
+{
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{self as bevy_asset, AssetEvents, AssetPlugin, Handle};
+    use crate::{
+        self as bevy_asset, AssetEvents, AssetPlugin, Handle, UntypedAssetId, UntypedHandle,
+    };
     use alloc::{vec, vec::Vec};
     use core::num::NonZero;
     use std::println;
@@ -297,6 +463,7 @@ mod tests {
         system::{Commands, IntoSystem, Local, Query, Res, ResMut},
     };
     use bevy_reflect::TypePath;
+    use uuid::Uuid;
 
     use super::*;
 
@@ -314,6 +481,15 @@ mod tests {
         }
     }
 
+    #[derive(Component)]
+    struct MyUntypedComponent(UntypedHandle);
+
+    impl AsUntypedAssetId for MyUntypedComponent {
+        fn as_untyped_asset_id(&self) -> UntypedAssetId {
+            self.0.id()
+        }
+    }
+
     fn run_app<Marker>(system: impl IntoSystem<(), (), Marker>) {
         let mut app = App::new();
         app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
@@ -468,4 +644,93 @@ mod tests {
         app.update(); // run_count == 5
         assert_counter(&app, Counter(vec![6, 12]));
     }
+
+    #[test]
+    fn change_log() {
+        let ids: Vec<AssetId<MyAsset>> = (0..3)
+            .map(|i| AssetId::Uuid {
+                uuid: Uuid::from_u128(i),
+            })
+            .collect();
+        let mut changes = AssetChanges::default();
+        for frame in 1..=100 {
+            changes.insert(ids[frame as usize % 2], Tick::new(frame));
+        }
+        changes.insert(ids[2], Tick::new(101));
+        changes.remove(&ids[1]);
+
+        // The stale changes of assets which changed again, or were removed, are skipped.
+        let changed: Vec<_> = changes
+            .changed_since(Tick::new(98), Tick::new(102))
+            .collect();
+        assert_eq!(changed, [ids[2], ids[0]]);
+        assert_eq!(
+            changes
+                .changed_since(Tick::new(101), Tick::new(102))
+                .count(),
+            0
+        );
+        assert!(changes.log.len() <= 2 * changes.change_ticks.len() + 64);
+    }
+
+    #[derive(Asset, TypePath)]
+    struct OtherAsset;
+
+    #[derive(Default, Resource)]
+    struct Detected(Vec<(Vec<Entity>, Vec<Entity>)>);
+
+    #[test]
+    fn untyped_and_indexed() {
+        fn detect(
+            filtered: Query<Entity, AssetChanged<MyUntypedComponent, MyAsset>>,
+            indexed: AssetChangedEntities<MyUntypedComponent, MyAsset>,
+            mut detected: ResMut<Detected>,
+        ) {
+            let mut filtered: Vec<_> = filtered.iter().collect();
+            let mut indexed: Vec<_> = indexed.iter().collect();
+            filtered.sort();
+            indexed.sort();
+            detected.0.push((filtered, indexed));
+        }
+
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_asset::<MyAsset>()
+            .init_asset::<OtherAsset>()
+            .track_asset_holders::<MyUntypedComponent>()
+            .init_resource::<Detected>()
+            .add_systems(PostUpdate, detect.after(AssetEvents));
+
+        let mut assets = app.world_mut().resource_mut::<Assets<MyAsset>>();
+        let a = assets.add(MyAsset(0, "init")).untyped();
+        let b = assets.add(MyAsset(1, "init")).untyped();
+        let e0 = app.world_mut().spawn(MyUntypedComponent(a.clone())).id();
+        let e1 = app.world_mut().spawn(MyUntypedComponent(a)).id();
+        let e2 = app.world_mut().spawn(MyUntypedComponent(b.clone())).id();
+        // Handles to assets of another type are never listed.
+        let other = app
+            .world_mut()
+            .resource_mut::<Assets<OtherAsset>>()
+            .add(OtherAsset)
+            .untyped();
+        app.world_mut().spawn(MyUntypedComponent(other.clone()));
+
+        app.update();
+        app.update();
+        app.world_mut()
+            .resource_mut::<Assets<MyAsset>>()
+            .get_mut(b.id().typed::<MyAsset>())
+            .unwrap()
+            .1 = "new_value";
+        app.world_mut()
+            .resource_mut::<Assets<OtherAsset>>()
+            .get_mut(other.id().typed::<OtherAsset>())
+            .unwrap();
+        app.update();
+
+        let detected = &app.world().resource::<Detected>().0;
+        assert_eq!(detected[0], (vec![e0, e1, e2], vec![e0, e1, e2]));
+        assert_eq!(detected[1], (vec![], vec![]));
+        assert_eq!(detected[2], (vec![e2], vec![e2]));
+    }
 }
@@ -545,12 +545,20 @@ impl<A: Asset> Assets<A> {
 
             if drop_event.asset_server_managed {
                 let untyped_id = id.untyped();
+                let path = infos.get(untyped_id).and_then(|info| info.path.clone());
 
                 // the process_handle_drop call checks whether new handles have been created since the drop event was fired, before removing the asset
                 if !infos.process_handle_drop(untyped_id) {
                     // a new handle has been created, or the asset doesn't exist
                     continue;
                 }
+
+                // Forget the load priority once no asset of any type is loaded from the path.
+                if let Some(path) = path.filter(|path| {
+                    path.label().is_none() && infos.get_path_ids(path).next().is_none()
+                }) {
+                    asset_server.forget_load_priority(&path);
+                }
             }
 
             assets.queued_events.push(AssetEvent::Unused { id });
@@ -0,0 +1,163 @@
+use core::any::type_name;
+
+use bevy_ecs::{
+    resource::Resource,
+    system::{Commands, Res, ResMut},
+    world::World,
+};
+use tracing::error;
+
+use crate::{AssetServer, RecursiveDependencyLoadState, UntypedAssetId};
+
+/// A group of assets loaded together, and inserted as a resource once they're all loaded.
+///
+/// This trait is usually derived, on a [`Resource`] struct with a handle for each asset. Each field
+/// declares the path of its asset with `#[asset(path = "...")]`, or the path of a folder to load
+/// with [`AssetServer::load_folder`] with `#[asset(path = "...", folder)]`.
+///
+/// Collections are loaded by [`AssetApp::load_asset_collection`](crate::AssetApp::load_asset_collection):
+/// while the assets and their dependencies load, the collection is in a
+/// [`LoadingAssetCollection`] resource. Once they're all loaded, the collection itself is inserted
+/// as a resource.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_asset::{prelude::*, AssetCollection, LoadedFolder};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # #[derive(Asset, TypePath)]
+/// # struct Sprite;
+/// #[derive(AssetCollection, Resource)]
+/// struct PlayerAssets {
+///     #[asset(path = "player/idle.sprite")]
+///     idle: Handle<Sprite>,
+///     #[asset(path = "player/sounds", folder)]
+///     sounds: Handle<LoadedFolder>,
+/// }
+///
+/// fn build(app: &mut App) {
+///     app.load_asset_collection::<PlayerAssets>()
+///         .add_systems(Update, spawn_player.run_if(resource_added::<PlayerAssets>));
+/// }
+///
+/// fn spawn_player(assets: Res<PlayerAssets>) {
+///     // Every asset of `PlayerAssets` is loaded...
+/// }
+/// ```
+pub trait AssetCollection: Resource + Sized {
+    /// Starts loading the assets of the collection.
+    fn load(asset_server: &AssetServer) -> Self;
+
+    /// Visits the id of each asset of the collection.
+    fn visit_handles(&self, visit: &mut impl FnMut(UntypedAssetId));
+
+    /// Returns the combined [`RecursiveDependencyLoadState`] of the assets of the collection.
+    ///
+    /// The collection is [`Loaded`](RecursiveDependencyLoadState::Loaded) once all its assets and their
+    /// dependencies are loaded, and [`Failed`](RecursiveDependencyLoadState::Failed) as soon as one
+    /// of them failed to load.
+    fn load_state(&self, asset_server: &AssetServer) -> RecursiveDependencyLoadState {
+        let mut state = RecursiveDependencyLoadState::Loaded;
+        self.visit_handles(
+            &mut |id| match asset_server.recursive_dependency_load_state(id) {
+                RecursiveDependencyLoadState::Loaded => {}
+                RecursiveDependencyLoadState::Failed(err) => {
+                    if !state.is_failed() {
+                        state = RecursiveDependencyLoadState::Failed(err);
+                    }
+                }
+                RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading => {
+                    if state.is_loaded() {
+                        state = RecursiveDependencyLoadState::Loading;
+                    }
+                }
+            },
+        );
+        state
+    }
+
+    /// Returns the fraction of the assets of the collection that are loaded along with
+    /// their dependencies, between `0.0` and `1.0`.
+    fn progress(&self, asset_server: &AssetServer) -> f32 {
+        let mut loaded = 0;
+        let mut total = 0;
+        self.visit_handles(&mut |id| {
+            total += 1;
+            if asset_server.is_loaded_with_dependencies(id) {
+                loaded += 1;
+            }
+        });
+        if total == 0 {
+            1.0
+        } else {
+            loaded as f32 / total as f32
+        }
+    }
+}
+
+/// An [`AssetCollection`] whose assets are loading.
+///
+/// Once all the assets are loaded, this resource is removed and the collection is inserted
+/// as a resource instead. If an asset fails to load, this resource is kept, and the error
+/// can be found with [`LoadingAssetCollection::load_state`].
+#[derive(Resource)]
+pub struct LoadingAssetCollection<C: AssetCollection> {
+    collection: C,
+    failed: bool,
+}
+
+impl<C: AssetCollection> LoadingAssetCollection<C> {
+    /// Returns the loading collection.
+    pub fn collection(&self) -> &C {
+        &self.collection
+    }
+
+    /// Returns the combined load state of the assets of the collection.
+    pub fn load_state(&self, asset_server: &AssetServer) -> RecursiveDependencyLoadState {
+        self.collection.load_state(asset_server)
+    }
+
+    /// Returns the fraction of the assets of the collection that are loaded, between `0.0` and `1.0`.
+    pub fn progress(&self, asset_server: &AssetServer) -> f32 {
+        self.collection.progress(asset_server)
+    }
+}
+
+/// Starts loading the [`AssetCollection`] `C`.
+pub(crate) fn start_loading_asset_collection<C: AssetCollection>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(LoadingAssetCollection {
+        collection: C::load(&asset_server),
+        failed: false,
+    });
+}
+
+/// Inserts the [`AssetCollection`] `C` as a resource once all its assets are loaded.
+pub(crate) fn finish_loading_asset_collection<C: AssetCollection>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<LoadingAssetCollection<C>>,
+) {
+    if loading.failed {
+        return;
+    }
+    match loading.load_state(&asset_server) {
+        RecursiveDependencyLoadState::Loaded => {
+            commands.queue(|world: &mut World| {
+                if let Some(loading) = world.remove_resource::<LoadingAssetCollection<C>>() {
+                    world.insert_resource(loading.collection);
+                }
+            });
+        }
+        RecursiveDependencyLoadState::Failed(err) => {
+            error!(
+                "Failed to load the asset collection {}: {err}",
+                type_name::<C>()
+            );
+            loading.failed = true;
+        }
+        RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading => {}
+    }
+}
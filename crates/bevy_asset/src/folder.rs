@@ -1,7 +1,12 @@
 use alloc::vec::Vec;
 
 use crate as bevy_asset;
-use crate::{Asset, UntypedHandle};
+use crate::{
+    Asset, AssetId, AssetLoadError, AssetPath, AssetServer, Assets, Handle, LoadState,
+    UntypedAssetId, UntypedAssetLoadFailedEvent, UntypedHandle,
+};
+use bevy_ecs::prelude::*;
+use bevy_platform_support::collections::HashSet;
 use bevy_reflect::TypePath;
 
 /// A "loaded folder" containing handles for all assets stored in a given [`AssetPath`].
@@ -12,3 +17,83 @@ pub struct LoadedFolder {
     #[dependency]
     pub handles: Vec<UntypedHandle>,
 }
+
+impl LoadedFolder {
+    /// Returns the handles of the assets of type `A` in the folder, in the order of
+    /// [`handles`](Self::handles).
+    pub fn handles_of<A: Asset>(&self) -> impl Iterator<Item = Handle<A>> + '_ {
+        self.handles
+            .iter()
+            .filter_map(|handle| handle.clone().try_typed::<A>().ok())
+    }
+}
+
+/// An event sent when a file of a [`LoadedFolder`] fails to load.
+///
+/// This is mostly useful with [`AssetServer::load_folder_matching`], whose folders load even
+/// when some of their files fail to.
+#[derive(Event, Clone, Debug)]
+pub struct LoadedFolderFileFailedEvent {
+    /// The folder containing the file.
+    pub folder: AssetId<LoadedFolder>,
+    /// The id of the asset that failed to load.
+    pub id: UntypedAssetId,
+    /// The path of the file.
+    pub path: AssetPath<'static>,
+    /// Why the file failed to load.
+    pub error: AssetLoadError,
+}
+
+/// Sends a [`LoadedFolderFileFailedEvent`] for each file of a [`LoadedFolder`] that fails to load.
+pub(crate) fn send_loaded_folder_file_failed_events(
+    mut failures: EventReader<UntypedAssetLoadFailedEvent>,
+    mut folder_failures: EventWriter<LoadedFolderFileFailedEvent>,
+    folders: Res<Assets<LoadedFolder>>,
+    asset_server: Res<AssetServer>,
+    mut seen_folders: Local<HashSet<AssetId<LoadedFolder>>>,
+) {
+    let failures: Vec<_> = failures.read().collect();
+
+    // The files of new folders may have failed before the folder itself was loaded.
+    seen_folders.retain(|id| folders.contains(*id));
+    let mut new_folders: HashSet<_> = HashSet::default();
+    for (id, folder) in folders.iter() {
+        if seen_folders.contains(&id) {
+            continue;
+        }
+        new_folders.insert(id);
+        for handle in &folder.handles {
+            if let (Some(LoadState::Failed(error)), Some(path)) =
+                (asset_server.get_load_state(handle.id()), handle.path())
+            {
+                folder_failures.send(LoadedFolderFileFailedEvent {
+                    folder: id,
+                    id: handle.id(),
+                    path: path.clone(),
+                    error: (*error).clone(),
+                });
+            }
+        }
+    }
+
+    for failure in failures {
+        for id in seen_folders.iter() {
+            let Some(folder) = folders.get(*id) else {
+                continue;
+            };
+            if folder
+                .handles
+                .iter()
+                .any(|handle| handle.id() == failure.id)
+            {
+                folder_failures.send(LoadedFolderFileFailedEvent {
+                    folder: *id,
+                    id: failure.id,
+                    path: failure.path.clone(),
+                    error: failure.error.clone(),
+                });
+            }
+        }
+    }
+    seen_folders.extend(new_folders);
+}
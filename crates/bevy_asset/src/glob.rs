@@ -0,0 +1,223 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use std::path::{Component, Path, PathBuf};
+
+/// A glob pattern matched against the paths of the files of an asset source, used by
+/// [`AssetServer::load_folder_matching`](crate::AssetServer::load_folder_matching).
+///
+/// The pattern is made of `/`-separated components, which support the following syntax:
+/// - `*` matches any sequence of characters within a component.
+/// - `?` matches any single character.
+/// - `**`, as a whole component, matches any number of nested folders, including none.
+/// - `{a,b}` matches either of the comma-separated alternatives.
+#[derive(Clone, Debug)]
+pub(crate) struct GlobPattern {
+    /// The components of each alternative of the pattern, after expanding the braces.
+    alternatives: Vec<Vec<String>>,
+}
+
+impl GlobPattern {
+    pub(crate) fn new(pattern: &str) -> Self {
+        let alternatives = expand_braces(pattern)
+            .into_iter()
+            .map(|alternative| {
+                alternative
+                    .split('/')
+                    .filter(|component| !component.is_empty() && *component != ".")
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .collect();
+        Self { alternatives }
+    }
+
+    /// Returns the deepest folder containing all the paths matched by the pattern, which is where
+    /// the search for matching files starts.
+    pub(crate) fn base_folder(&self) -> PathBuf {
+        let literal_prefix = |components: &[String]| {
+            let folders = components.len().saturating_sub(1);
+            components[..folders]
+                .iter()
+                .take_while(|component| !is_wildcard(component))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let mut alternatives = self.alternatives.iter();
+        let Some(first) = alternatives.next() else {
+            return PathBuf::new();
+        };
+        let mut prefix = literal_prefix(first);
+        for alternative in alternatives {
+            let other = literal_prefix(alternative);
+            let common = prefix
+                .iter()
+                .zip(&other)
+                .take_while(|(a, b)| a == b)
+                .count();
+            prefix.truncate(common);
+        }
+        prefix.iter().collect()
+    }
+
+    /// Returns true if `path`, relative to the root of its asset source, matches the pattern.
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        let Some(components) = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(component) => Some(component.to_str()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+        self.alternatives
+            .iter()
+            .any(|pattern| matches_components(pattern, &components))
+    }
+}
+
+fn is_wildcard(component: &str) -> bool {
+    component.contains(['*', '?'])
+}
+
+/// Expands the first `{a,b}` group of `pattern` into one pattern per alternative, recursively.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let mut depth = 0;
+    let mut separators = Vec::new();
+    let mut close = None;
+    for (index, c) in pattern[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + index);
+                    break;
+                }
+            }
+            ',' if depth == 1 => separators.push(open + index),
+            _ => {}
+        }
+    }
+    // An unbalanced brace is matched literally.
+    let Some(close) = close else {
+        return vec![pattern.to_string()];
+    };
+
+    let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+    let mut bounds = vec![open];
+    bounds.extend(separators);
+    bounds.push(close);
+    bounds
+        .windows(2)
+        .flat_map(|bounds| {
+            let choice = &pattern[bounds[0] + 1..bounds[1]];
+            expand_braces(&[prefix, choice, suffix].concat())
+        })
+        .collect()
+}
+
+fn matches_components(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skipped| matches_components(rest, &path[skipped..]))
+        }
+        Some((first, rest)) => path.split_first().is_some_and(|(component, path)| {
+            matches_component(first, component) && matches_components(rest, path)
+        }),
+    }
+}
+
+/// Matches a single path component against a pattern with `*` and `?` wildcards.
+fn matches_component(pattern: &str, component: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let component: Vec<char> = component.chars().collect();
+    let (mut p, mut c) = (0, 0);
+    // The position of the last `*` in the pattern, and of the component when it was reached.
+    let mut backtrack = None;
+    while c < component.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, c));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                c += 1;
+            }
+            Some(&expected) if expected == component[c] => {
+                p += 1;
+                c += 1;
+            }
+            _ => {
+                // Let the last `*` match one more character, if there was one.
+                let Some((star, matched)) = backtrack else {
+                    return false;
+                };
+                p = star + 1;
+                c = matched + 1;
+                backtrack = Some((star, matched + 1));
+            }
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        GlobPattern::new(pattern).matches(Path::new(path))
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("textures/*.png", "textures/grass.png"));
+        assert!(!matches("textures/*.png", "textures/grass.jpg"));
+        assert!(!matches("textures/*.png", "textures/terrain/grass.png"));
+        assert!(matches("textures/gr?ss.png", "textures/grass.png"));
+        assert!(matches("textures/*a*s*.png", "textures/grass.png"));
+        assert!(!matches("textures/*a*z*.png", "textures/grass.png"));
+    }
+
+    #[test]
+    fn recursive_wildcards() {
+        assert!(matches("textures/**/*.png", "textures/grass.png"));
+        assert!(matches(
+            "textures/**/*.png",
+            "textures/terrain/rock/cliff.png"
+        ));
+        assert!(!matches("textures/**/*.png", "models/grass.png"));
+        assert!(matches("**/normal.png", "textures/terrain/normal.png"));
+    }
+
+    #[test]
+    fn alternatives() {
+        let pattern = GlobPattern::new("textures/{terrain,props/*}/*.{png,ktx2}");
+        assert!(pattern.matches(Path::new("textures/terrain/grass.ktx2")));
+        assert!(pattern.matches(Path::new("textures/props/crate/wood.png")));
+        assert!(!pattern.matches(Path::new("textures/props/wood.png")));
+        assert_eq!(pattern.base_folder(), Path::new("textures"));
+    }
+
+    #[test]
+    fn base_folder() {
+        let base = |pattern: &str| GlobPattern::new(pattern).base_folder();
+        assert_eq!(
+            base("textures/terrain/*.png"),
+            Path::new("textures/terrain")
+        );
+        assert_eq!(base("textures/**/grass.png"), Path::new("textures"));
+        assert_eq!(base("*.png"), Path::new(""));
+        assert_eq!(base("textures/grass.png"), Path::new("textures"));
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{AsUntypedAssetId, AssetPath, LoadState, UntypedAssetId};
+use bevy_ecs::prelude::*;
+use bevy_platform_support::collections::{HashMap, HashSet};
+
+/// The entities holding handles to each asset, through the components registered with
+/// [`AssetApp::track_asset_holders`](crate::AssetApp::track_asset_holders).
+///
+/// Tracking is opt-in: only the components registered for tracking are recorded here.
+#[derive(Resource, Default, Debug)]
+pub struct AssetHolders {
+    holders: HashMap<UntypedAssetId, HashSet<Entity>>,
+}
+
+impl AssetHolders {
+    /// Returns the entities holding a handle to the asset `id`.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> impl Iterator<Item = Entity> + '_ {
+        self.holders.get(&id.into()).into_iter().flatten().copied()
+    }
+
+    /// Returns the number of entities holding a handle to the asset `id`.
+    pub fn count(&self, id: impl Into<UntypedAssetId>) -> usize {
+        self.holders.get(&id.into()).map_or(0, HashSet::len)
+    }
+
+    /// Returns the assets held by at least one entity, with the entities holding them.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (UntypedAssetId, impl Iterator<Item = Entity> + '_)> {
+        self.holders
+            .iter()
+            .map(|(id, entities)| (*id, entities.iter().copied()))
+    }
+
+    fn insert(&mut self, id: UntypedAssetId, entity: Entity) {
+        self.holders.entry(id).or_default().insert(entity);
+    }
+
+    fn remove(&mut self, id: UntypedAssetId, entity: Entity) {
+        if let Some(entities) = self.holders.get_mut(&id) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.holders.remove(&id);
+            }
+        }
+    }
+}
+
+/// Records the asset held by a newly inserted `C` component in [`AssetHolders`].
+pub(crate) fn track_asset_holder_insert<C: AsUntypedAssetId>(
+    trigger: Trigger<OnInsert, C>,
+    components: Query<&C>,
+    mut holders: ResMut<AssetHolders>,
+) {
+    if let Ok(component) = components.get(trigger.target()) {
+        holders.insert(component.as_untyped_asset_id(), trigger.target());
+    }
+}
+
+/// Forgets the asset held by a `C` component that is about to be replaced or removed.
+pub(crate) fn track_asset_holder_replace<C: AsUntypedAssetId>(
+    trigger: Trigger<OnReplace, C>,
+    components: Query<&C>,
+    mut holders: ResMut<AssetHolders>,
+) {
+    if let Ok(component) = components.get(trigger.target()) {
+        holders.remove(component.as_untyped_asset_id(), trigger.target());
+    }
+}
+
+/// A snapshot of the assets managed by an [`AssetServer`](crate::AssetServer), with their handle
+/// counts and dependencies, returned by [`AssetServer::report`](crate::AssetServer::report).
+///
+/// The report can be printed with its [`Display`](fmt::Display) implementation to debug leaking
+/// or unexpectedly loaded assets.
+#[derive(Clone, Debug, Default)]
+pub struct AssetReport {
+    /// The assets in the report, sorted by id.
+    pub assets: Vec<AssetReportEntry>,
+}
+
+/// The state of a single asset in an [`AssetReport`].
+#[derive(Clone, Debug)]
+pub struct AssetReportEntry {
+    /// The id of the asset.
+    pub id: UntypedAssetId,
+    /// The path of the asset, if it was loaded from one.
+    pub path: Option<AssetPath<'static>>,
+    /// The load state of the asset.
+    pub load_state: LoadState,
+    /// The number of strong handles to the asset that are alive.
+    pub strong_handles: usize,
+    /// The assets this asset directly depends on.
+    pub dependencies: Vec<UntypedAssetId>,
+    /// The assets directly depending on this asset.
+    pub dependents: Vec<UntypedAssetId>,
+    /// The entities holding a handle to this asset, filled in by [`AssetReport::with_holders`].
+    pub holders: Vec<Entity>,
+}
+
+impl AssetReport {
+    /// Fills in the [`holders`](AssetReportEntry::holders) of each asset of the report.
+    pub fn with_holders(mut self, holders: &AssetHolders) -> Self {
+        for entry in &mut self.assets {
+            entry.holders = holders.get(entry.id).collect();
+            entry.holders.sort();
+        }
+        self
+    }
+
+    /// Returns the entry of the asset `id`, if it's in the report.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetReportEntry> {
+        let id = id.into();
+        self.assets.iter().find(|entry| entry.id == id)
+    }
+
+    fn name(&self, id: UntypedAssetId) -> AssetName<'_> {
+        AssetName {
+            id,
+            path: self.get(id).and_then(|entry| entry.path.as_ref()),
+        }
+    }
+}
+
+impl fmt::Display for AssetReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} assets", self.assets.len())?;
+        for entry in &self.assets {
+            writeln!(
+                f,
+                "{} [{:?}, {} strong handles]",
+                self.name(entry.id),
+                entry.load_state,
+                entry.strong_handles
+            )?;
+            for dependency in &entry.dependencies {
+                writeln!(f, "    depends on {}", self.name(*dependency))?;
+            }
+            for dependent in &entry.dependents {
+                writeln!(f, "    required by {}", self.name(*dependent))?;
+            }
+            for holder in &entry.holders {
+                writeln!(f, "    held by {holder}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays the path of an asset, or its id for assets without a path.
+struct AssetName<'a> {
+    id: UntypedAssetId,
+    path: Option<&'a AssetPath<'static>>,
+}
+
+impl fmt::Display for AssetName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path {
+            Some(path) => write!(f, "{path}"),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
@@ -15,13 +15,17 @@ pub mod memory;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
+#[cfg(all(target_arch = "wasm32", feature = "web_asset_cache"))]
+pub mod web_cache;
+#[cfg(any(test, all(target_arch = "wasm32", feature = "web_asset_cache")))]
+mod web_cache_index;
 
 mod source;
 
 pub use futures_lite::AsyncWriteExt;
 pub use source::*;
 
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
 use bevy_tasks::{BoxedFuture, ConditionalSendFuture};
 use core::future::Future;
 use core::{
@@ -762,6 +766,93 @@ impl Reader for SliceReader<'_> {
     }
 }
 
+/// A [`Reader`] which reads the first bytes of another [`Reader`] ahead of time, so they can be
+/// inspected before the whole content is read.
+pub(crate) struct PeekReader<'a> {
+    peeked: Vec<u8>,
+    peeked_read: usize,
+    inner: Box<dyn Reader + 'a>,
+}
+
+impl<'a> PeekReader<'a> {
+    /// Create a new [`PeekReader`], reading up to `len` bytes ahead from `inner`.
+    pub(crate) async fn new(mut inner: Box<dyn Reader + 'a>, len: usize) -> std::io::Result<Self> {
+        let mut peeked = vec![0; len];
+        let mut filled = 0;
+        while filled < len {
+            let n = futures_lite::AsyncReadExt::read(&mut inner, &mut peeked[filled..]).await?;
+            if n == 0 {
+                break;
+            }
+            filled += n;
+        }
+        peeked.truncate(filled);
+        Ok(Self {
+            peeked,
+            peeked_read: 0,
+            inner,
+        })
+    }
+
+    /// The bytes read ahead, which are fewer than requested if the content is shorter.
+    pub(crate) fn peeked(&self) -> &[u8] {
+        &self.peeked
+    }
+}
+
+impl AsyncRead for PeekReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let remaining = &this.peeked[this.peeked_read..];
+        if remaining.is_empty() {
+            return Pin::new(&mut this.inner).poll_read(cx, buf);
+        }
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        this.peeked_read += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncSeekForward for PeekReader<'_> {
+    fn poll_seek_forward(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        offset: u64,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        let remaining = (this.peeked.len() - this.peeked_read) as u64;
+        if offset <= remaining {
+            this.peeked_read += offset as usize;
+            return Poll::Ready(Ok(this.peeked_read as u64));
+        }
+        // The inner reader is already past the peeked bytes, so its position is the right one.
+        let position = ready!(Pin::new(&mut this.inner).poll_seek_forward(cx, offset - remaining))?;
+        this.peeked_read = this.peeked.len();
+        Poll::Ready(Ok(position))
+    }
+}
+
+impl Reader for PeekReader<'_> {
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> StackFuture<'a, std::io::Result<usize>, STACK_FUTURE_SIZE> {
+        StackFuture::from(async {
+            let peeked = self.peeked.len() - self.peeked_read;
+            buf.extend_from_slice(&self.peeked[self.peeked_read..]);
+            self.peeked_read = self.peeked.len();
+            // The inner future is as large as this one, so it has to be boxed.
+            let n = Box::pin(self.inner.read_to_end(buf)).await?;
+            Ok(peeked + n)
+        })
+    }
+}
+
 /// Appends `.meta` to the given path.
 pub(crate) fn get_meta_path(path: &Path) -> PathBuf {
     let mut meta_path = path.to_path_buf();
@@ -783,3 +874,38 @@ impl Stream for EmptyPathStream {
         Poll::Ready(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tasks::block_on;
+
+    #[test]
+    fn peek_reader_replays_peeked_bytes() {
+        let bytes: Vec<u8> = (0..10).collect();
+
+        let mut reader =
+            block_on(PeekReader::new(Box::new(VecReader::new(bytes.clone())), 4)).unwrap();
+        assert_eq!(reader.peeked(), &bytes[..4]);
+        let mut buf = Vec::new();
+        assert_eq!(block_on(reader.read_to_end(&mut buf)).unwrap(), 10);
+        assert_eq!(buf, bytes);
+
+        let mut reader =
+            block_on(PeekReader::new(Box::new(VecReader::new(bytes.clone())), 4)).unwrap();
+        assert_eq!(block_on(reader.seek_forward(2)).unwrap(), 2);
+        assert_eq!(block_on(reader.seek_forward(3)).unwrap(), 5);
+        let mut buf = Vec::new();
+        block_on(futures_lite::AsyncReadExt::read_to_end(
+            &mut reader,
+            &mut buf,
+        ))
+        .unwrap();
+        assert_eq!(buf, &bytes[5..]);
+
+        // Peeking more than the content is fine.
+        let reader =
+            block_on(PeekReader::new(Box::new(VecReader::new(bytes.clone())), 20)).unwrap();
+        assert_eq!(reader.peeked(), &bytes[..]);
+    }
+}
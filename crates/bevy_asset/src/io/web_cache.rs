@@ -0,0 +1,491 @@
+//! A persistent browser cache for assets read on the web.
+
+use crate::{
+    io::{
+        get_meta_path,
+        web_cache_index::{entry_name, CacheEntry, CacheIndex},
+        AssetReader, AssetReaderError, ErasedAssetReader, PathStream, Reader, VecReader,
+    },
+    meta::{AssetHash, ProcessedInfoMinimal},
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_platform_support::{collections::HashMap, sync::Arc};
+use core::sync::atomic::{AtomicBool, Ordering};
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// The default maximum size of a [`WebCachedAssetReader`], in bytes (256 MiB).
+pub const DEFAULT_WEB_CACHE_BUDGET: u64 = 256 * 1024 * 1024;
+
+/// The default name of the directory used by a [`WebCachedAssetReader`].
+pub const DEFAULT_WEB_CACHE_NAME: &str = "bevy_asset_cache";
+
+const INDEX_FILE_NAME: &str = "index.ron";
+
+/// How long changes to the cache index are batched before it is written to storage, in milliseconds.
+const INDEX_FLUSH_DELAY_MS: i32 = 100;
+
+/// An [`AssetReader`] that stores the bytes read by another reader in the browser's
+/// [Origin Private File System] (OPFS), so that refreshing the page does not download every asset again,
+/// and previously loaded assets remain available while offline.
+///
+/// Entries are keyed by asset path and, for processed assets, by the `full_hash` found in the asset's
+/// `.meta` file. When the meta of a processed asset reports a new hash, the cached bytes are invalidated
+/// and downloaded again. Assets without a processed hash are always requested from the wrapped reader,
+/// and the cached copy is only used when that request fails for a reason other than the asset not existing.
+///
+/// The total size of the cache is kept under a budget (see [`WebCachedAssetReader::with_budget`])
+/// by evicting the least recently used entries. The index of the entries is written to storage
+/// shortly after it changes, once for all the assets stored in the meantime.
+///
+/// If the browser does not support OPFS, this reader forwards every request to the wrapped reader.
+///
+/// ```no_run
+/// # use bevy_asset::{io::{AssetSource, AssetSourceId, web_cache::WebCachedAssetReader, wasm::HttpWasmAssetReader}, AssetApp};
+/// # use bevy_app::App;
+/// # let mut app = App::new();
+/// app.register_asset_source(
+///     AssetSourceId::Default,
+///     AssetSource::build().with_reader(|| {
+///         Box::new(WebCachedAssetReader::new(HttpWasmAssetReader::new("assets")))
+///     }),
+/// );
+/// ```
+///
+/// [Origin Private File System]: https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system
+pub struct WebCachedAssetReader {
+    reader: Box<dyn ErasedAssetReader>,
+    cache_name: String,
+    budget: u64,
+    /// Shared with the task writing the index to storage.
+    state: Arc<Mutex<CacheState>>,
+    unavailable: AtomicBool,
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// The cache index, loaded from storage on first use.
+    index: Option<CacheIndex>,
+    /// The processed hash of each asset whose meta has been read, if it has one.
+    processed_hashes: HashMap<PathBuf, Option<AssetHash>>,
+    /// The index changed since it was last written to storage.
+    index_dirty: bool,
+    /// A task is waiting to write the index to storage.
+    flush_scheduled: bool,
+}
+
+impl WebCachedAssetReader {
+    /// Creates a new [`WebCachedAssetReader`] caching the assets read by `reader`.
+    pub fn new(reader: impl AssetReader) -> Self {
+        Self {
+            reader: Box::new(reader),
+            cache_name: DEFAULT_WEB_CACHE_NAME.to_string(),
+            budget: DEFAULT_WEB_CACHE_BUDGET,
+            state: Arc::new(Mutex::new(CacheState::default())),
+            unavailable: AtomicBool::new(false),
+        }
+    }
+
+    /// Sets the maximum total size of the cached assets, in bytes.
+    ///
+    /// Defaults to [`DEFAULT_WEB_CACHE_BUDGET`].
+    pub fn with_budget(mut self, budget: u64) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Sets the name of the OPFS directory the cache is stored in.
+    ///
+    /// Readers for different asset sources should use different names.
+    /// Defaults to [`DEFAULT_WEB_CACHE_NAME`].
+    pub fn with_cache_name(mut self, cache_name: impl Into<String>) -> Self {
+        self.cache_name = cache_name.into();
+        self
+    }
+
+    /// Removes every cached asset.
+    pub async fn clear(&self) {
+        let Some(directory) = self.directory().await else {
+            return;
+        };
+        let names: Vec<String> = {
+            let mut state = self.state.lock();
+            let index = state.index.get_or_insert_with(CacheIndex::default);
+            let names = index.entries.keys().cloned().collect();
+            index.entries.clear();
+            state.processed_hashes.clear();
+            names
+        };
+        for name in names {
+            // Missing files are fine, they may have been removed by the browser.
+            let _ = remove_file(&directory, &name).await;
+        }
+        self.schedule_index_flush(&directory);
+    }
+
+    /// Returns the cache directory, loading the cache index on first use.
+    /// Returns `None` if OPFS is not available.
+    async fn directory(&self) -> Option<JsValue> {
+        if self.unavailable.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let directory = match cache_directory(&self.cache_name).await {
+            Ok(directory) => directory,
+            Err(err) => {
+                if !self.unavailable.swap(true, Ordering::Relaxed) {
+                    warn!(
+                        "The browser asset cache is not available, assets will not be cached: {}",
+                        js_error_message(&err)
+                    );
+                }
+                return None;
+            }
+        };
+
+        if self.state.lock().index.is_none() {
+            let index = match read_file(&directory, INDEX_FILE_NAME).await {
+                Ok(Some(bytes)) => ron::de::from_bytes(&bytes).unwrap_or_default(),
+                _ => CacheIndex::default(),
+            };
+            self.state.lock().index.get_or_insert(index);
+        }
+
+        Some(directory)
+    }
+
+    /// Marks the cache index as changed, and schedules writing it to storage.
+    ///
+    /// Writing the whole index after each change would take quadratic time when loading many
+    /// assets, so the changes made until the write starts are written at once.
+    fn schedule_index_flush(&self, directory: &JsValue) {
+        {
+            let mut state = self.state.lock();
+            state.index_dirty = true;
+            if core::mem::replace(&mut state.flush_scheduled, true) {
+                return;
+            }
+        }
+
+        let state = self.state.clone();
+        let directory = directory.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            sleep(INDEX_FLUSH_DELAY_MS).await;
+            state.lock().flush_scheduled = false;
+            persist_index(&state, &directory).await;
+        });
+    }
+
+    /// Reads the cache entry `name`, marking it as recently used.
+    async fn lookup(&self, directory: &JsValue, name: &str) -> Option<Vec<u8>> {
+        if !self
+            .state
+            .lock()
+            .index
+            .as_ref()
+            .is_some_and(|index| index.entries.contains_key(name))
+        {
+            return None;
+        }
+
+        match read_file(directory, name).await {
+            Ok(Some(bytes)) => {
+                if let Some(entry) = self
+                    .state
+                    .lock()
+                    .index
+                    .as_mut()
+                    .and_then(|index| index.entries.get_mut(name))
+                {
+                    entry.last_used = now();
+                }
+                Some(bytes)
+            }
+            _ => {
+                // The browser may clear storage at any time, forget about the missing entry.
+                if let Some(index) = self.state.lock().index.as_mut() {
+                    index.entries.remove(name);
+                }
+                None
+            }
+        }
+    }
+
+    /// Reads the most recently used entry for `path`, regardless of its hash.
+    async fn lookup_latest(&self, directory: &JsValue, path: &str) -> Option<Vec<u8>> {
+        let name = self
+            .state
+            .lock()
+            .index
+            .as_ref()
+            .and_then(|index| index.latest(path))?;
+        self.lookup(directory, &name).await
+    }
+
+    /// Stores `bytes` for `path`, invalidating entries for other hashes of the same path and
+    /// evicting old entries to stay within the budget.
+    async fn store(&self, directory: &JsValue, path: &str, hash: Option<&AssetHash>, bytes: &[u8]) {
+        let name = entry_name(path, hash);
+        if let Err(err) = write_file(directory, &name, bytes).await {
+            warn!(
+                "Failed to store {path} in the browser asset cache: {}",
+                js_error_message(&err)
+            );
+            return;
+        }
+
+        let removed: Vec<String> = {
+            let mut state = self.state.lock();
+            let index = state.index.get_or_insert_with(CacheIndex::default);
+            let entry = CacheEntry::new(path, hash, bytes.len() as u64, now());
+            index.insert(name, entry, self.budget)
+        };
+
+        for name in removed {
+            let _ = remove_file(directory, &name).await;
+        }
+        self.schedule_index_flush(directory);
+    }
+
+    /// Forgets every entry for `path`, used when the wrapped reader reports that it no longer exists.
+    async fn forget(&self, directory: &JsValue, path: &str) {
+        let removed: Vec<String> = {
+            let mut state = self.state.lock();
+            let Some(index) = state.index.as_mut() else {
+                return;
+            };
+            index.remove_path(path)
+        };
+
+        if removed.is_empty() {
+            return;
+        }
+        for name in removed {
+            let _ = remove_file(directory, &name).await;
+        }
+        self.schedule_index_flush(directory);
+    }
+
+    /// Reads `path` through the cache. Entries are keyed by `hash` if there is one, otherwise
+    /// the wrapped reader is always queried and the cache only serves as an offline fallback.
+    async fn read_cached(
+        &self,
+        path: &Path,
+        hash: Option<AssetHash>,
+        fetch: impl core::future::Future<Output = Result<Vec<u8>, AssetReaderError>>,
+    ) -> Result<Vec<u8>, AssetReaderError> {
+        let Some(directory) = self.directory().await else {
+            return fetch.await;
+        };
+        let key = path.to_string_lossy();
+
+        if let Some(hash) = hash.as_ref() {
+            if let Some(bytes) = self.lookup(&directory, &entry_name(&key, Some(hash))).await {
+                return Ok(bytes);
+            }
+        }
+
+        match fetch.await {
+            Ok(bytes) => {
+                self.store(&directory, &key, hash.as_ref(), &bytes).await;
+                Ok(bytes)
+            }
+            Err(AssetReaderError::NotFound(path)) => {
+                self.forget(&directory, &key).await;
+                Err(AssetReaderError::NotFound(path))
+            }
+            Err(err) => match self.lookup_latest(&directory, &key).await {
+                Some(bytes) => Ok(bytes),
+                None => Err(err),
+            },
+        }
+    }
+
+    /// Returns the processed hash of the asset at `path`, reading its meta if it hasn't been read yet.
+    async fn processed_hash(&self, path: &Path) -> Option<AssetHash> {
+        if let Some(hash) = self.state.lock().processed_hashes.get(path) {
+            return *hash;
+        }
+        // This records the hash as a side effect.
+        let _ = self.read_meta_bytes_cached(path).await;
+        self.state
+            .lock()
+            .processed_hashes
+            .get(path)
+            .copied()
+            .flatten()
+    }
+
+    async fn read_meta_bytes_cached(&self, path: &Path) -> Result<Vec<u8>, AssetReaderError> {
+        let meta_path = get_meta_path(path);
+        let bytes = self
+            .read_cached(&meta_path, None, self.reader.read_meta_bytes(path))
+            .await;
+
+        let hash = bytes.as_ref().ok().and_then(|bytes| {
+            ron::de::from_bytes::<ProcessedInfoMinimal>(bytes)
+                .ok()
+                .and_then(|minimal| minimal.processed_info)
+                .map(|info| info.full_hash)
+        });
+        self.state
+            .lock()
+            .processed_hashes
+            .insert(path.to_path_buf(), hash);
+        bytes
+    }
+
+    async fn fetch(&self, path: &Path) -> Result<Vec<u8>, AssetReaderError> {
+        let mut reader = self.reader.read(path).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(bytes)
+    }
+}
+
+impl AssetReader for WebCachedAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let hash = self.processed_hash(path).await;
+        let bytes = self.read_cached(path, hash, self.fetch(path)).await?;
+        Ok(VecReader::new(bytes))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let bytes = self.read_meta_bytes_cached(path).await?;
+        Ok(VecReader::new(bytes))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        self.reader.read_directory(path).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.reader.is_directory(path).await
+    }
+
+    async fn read_meta_bytes<'a>(&'a self, path: &'a Path) -> Result<Vec<u8>, AssetReaderError> {
+        self.read_meta_bytes_cached(path).await
+    }
+}
+
+/// Writes the cache index to storage if it changed since it was last written.
+async fn persist_index(state: &Mutex<CacheState>, directory: &JsValue) {
+    let serialized = {
+        let mut state = state.lock();
+        let Some(index) = state.index.as_ref().filter(|_| state.index_dirty) else {
+            return;
+        };
+        let serialized = ron::ser::to_string(index);
+        state.index_dirty = false;
+        serialized
+    };
+    let result = match serialized {
+        Ok(serialized) => write_file(directory, INDEX_FILE_NAME, serialized.as_bytes()).await,
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    };
+    if let Err(err) = result {
+        warn!(
+            "Failed to write the browser asset cache index: {}",
+            js_error_message(&err)
+        );
+    }
+}
+
+fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+/// Waits for `milliseconds` with `setTimeout`.
+async fn sleep(milliseconds: i32) {
+    let promise = Promise::new(&mut |resolve, _reject| {
+        let global = js_sys::global();
+        let set_timeout = Reflect::get(&global, &JsValue::from_str("setTimeout"))
+            .and_then(|set_timeout| set_timeout.dyn_into::<Function>());
+        let scheduled = set_timeout.and_then(|set_timeout| {
+            set_timeout.call2(&global, &resolve, &JsValue::from(milliseconds))
+        });
+        if scheduled.is_err() {
+            let _ = resolve.call0(&JsValue::UNDEFINED);
+        }
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+fn js_error_message(value: &JsValue) -> String {
+    js_sys::JSON::stringify(value)
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| "unknown error".to_string())
+}
+
+/// Calls the asynchronous method `method` of `target` and awaits the returned promise.
+async fn call_async(target: &JsValue, method: &str, args: &[&JsValue]) -> Result<JsValue, JsValue> {
+    let function: Function = Reflect::get(target, &JsValue::from_str(method))?.dyn_into()?;
+    let args: Array = args.iter().copied().collect();
+    let promise: Promise = function.apply(target, &args)?.dyn_into()?;
+    JsFuture::from(promise).await
+}
+
+fn create_option() -> Result<JsValue, JsValue> {
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from_str("create"), &JsValue::TRUE)?;
+    Ok(options.into())
+}
+
+/// Opens (creating it if needed) the OPFS directory `name`.
+async fn cache_directory(name: &str) -> Result<JsValue, JsValue> {
+    let navigator = Reflect::get(&js_sys::global(), &JsValue::from_str("navigator"))?;
+    let storage = Reflect::get(&navigator, &JsValue::from_str("storage"))?;
+    if storage.is_undefined() {
+        return Err(JsValue::from_str("navigator.storage is not available"));
+    }
+    let root = call_async(&storage, "getDirectory", &[]).await?;
+    call_async(
+        &root,
+        "getDirectoryHandle",
+        &[&JsValue::from_str(name), &create_option()?],
+    )
+    .await
+}
+
+/// Reads the file `name` from `directory`, returning `None` if it doesn't exist.
+async fn read_file(directory: &JsValue, name: &str) -> Result<Option<Vec<u8>>, JsValue> {
+    let Ok(handle) = call_async(directory, "getFileHandle", &[&JsValue::from_str(name)]).await
+    else {
+        return Ok(None);
+    };
+    let file = call_async(&handle, "getFile", &[]).await?;
+    let buffer = call_async(&file, "arrayBuffer", &[]).await?;
+    Ok(Some(Uint8Array::new(&buffer).to_vec()))
+}
+
+/// Writes `bytes` to the file `name` in `directory`, replacing any previous content.
+async fn write_file(directory: &JsValue, name: &str, bytes: &[u8]) -> Result<(), JsValue> {
+    let handle = call_async(
+        directory,
+        "getFileHandle",
+        &[&JsValue::from_str(name), &create_option()?],
+    )
+    .await?;
+    let writable = call_async(&handle, "createWritable", &[]).await?;
+    call_async(&writable, "write", &[&Uint8Array::from(bytes).into()]).await?;
+    call_async(&writable, "close", &[]).await?;
+    Ok(())
+}
+
+async fn remove_file(directory: &JsValue, name: &str) -> Result<(), JsValue> {
+    call_async(directory, "removeEntry", &[&JsValue::from_str(name)])
+        .await
+        .map(drop)
+}
@@ -0,0 +1,198 @@
+//! The index of the entries stored by a `WebCachedAssetReader`.
+//!
+//! This is kept apart from the reader, which only builds for the web, so that it can be tested natively.
+
+use crate::meta::AssetHash;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub(crate) struct CacheIndex {
+    /// Cache entries, keyed by the name of the file storing their bytes.
+    pub(crate) entries: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct CacheEntry {
+    pub(crate) path: String,
+    pub(crate) hash: Option<String>,
+    pub(crate) size: u64,
+    pub(crate) last_used: u64,
+}
+
+impl CacheEntry {
+    pub(crate) fn new(path: &str, hash: Option<&AssetHash>, size: u64, last_used: u64) -> Self {
+        Self {
+            path: path.to_string(),
+            hash: hash.map(|hash| blake3::Hash::from(*hash).to_hex().to_string()),
+            size,
+            last_used,
+        }
+    }
+}
+
+impl CacheIndex {
+    pub(crate) fn total_size(&self) -> u64 {
+        self.entries.values().map(|entry| entry.size).sum()
+    }
+
+    /// Adds the entry `name`, invalidating the entries for other hashes of the same path and
+    /// evicting old entries to stay within `budget`. Returns the names of the removed entries.
+    pub(crate) fn insert(&mut self, name: String, entry: CacheEntry, budget: u64) -> Vec<String> {
+        let mut removed: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(other, other_entry)| other_entry.path == entry.path && **other != name)
+            .map(|(other, _)| other.clone())
+            .collect();
+        for other in &removed {
+            self.entries.remove(other);
+        }
+        self.entries.insert(name, entry);
+        removed.extend(self.evict_to(budget));
+        removed
+    }
+
+    /// Removes every entry for `path`, returning their names.
+    pub(crate) fn remove_path(&mut self, path: &str) -> Vec<String> {
+        let removed: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.path == path)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &removed {
+            self.entries.remove(name);
+        }
+        removed
+    }
+
+    /// Removes the least recently used entries until the cache fits in `budget`,
+    /// returning the names of the removed entries.
+    pub(crate) fn evict_to(&mut self, budget: u64) -> Vec<String> {
+        let mut total_size = self.total_size();
+        if total_size <= budget {
+            return Vec::new();
+        }
+
+        let mut by_age: Vec<(u64, String)> = self
+            .entries
+            .iter()
+            .map(|(name, entry)| (entry.last_used, name.clone()))
+            .collect();
+        by_age.sort_unstable();
+
+        let mut evicted = Vec::new();
+        for (_, name) in by_age {
+            if total_size <= budget {
+                break;
+            }
+            if let Some(entry) = self.entries.remove(&name) {
+                total_size -= entry.size;
+                evicted.push(name);
+            }
+        }
+        evicted
+    }
+
+    /// Returns the name of the most recently used entry for `path`.
+    pub(crate) fn latest(&self, path: &str) -> Option<String> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| entry.path == path)
+            .max_by_key(|(_, entry)| entry.last_used)
+            .map(|(name, _)| name.clone())
+    }
+}
+
+/// Returns the name of the file storing the entry for `path` at `hash`.
+pub(crate) fn entry_name(path: &str, hash: Option<&AssetHash>) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(path.as_bytes());
+    if let Some(hash) = hash {
+        hasher.update(hash);
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_name, CacheEntry, CacheIndex};
+    use alloc::{string::String, vec, vec::Vec};
+
+    /// Inserts an entry for `path`, with a hash made of `hash` bytes, without a budget.
+    fn insert(
+        index: &mut CacheIndex,
+        path: &str,
+        hash: Option<u8>,
+        size: u64,
+        time: u64,
+    ) -> Vec<String> {
+        let hash = hash.map(|byte| [byte; 32]);
+        let name = entry_name(path, hash.as_ref());
+        index.insert(
+            name,
+            CacheEntry::new(path, hash.as_ref(), size, time),
+            u64::MAX,
+        )
+    }
+
+    #[test]
+    fn lookup_latest_entry() {
+        let mut index = CacheIndex::default();
+        insert(&mut index, "a.png", Some(1), 10, 1);
+        insert(&mut index, "b.png", None, 10, 2);
+
+        assert_eq!(
+            index.latest("a.png"),
+            Some(entry_name("a.png", Some(&[1; 32])))
+        );
+        assert_eq!(index.latest("b.png"), Some(entry_name("b.png", None)));
+        assert_eq!(index.latest("c.png"), None);
+        assert_eq!(index.total_size(), 20);
+    }
+
+    #[test]
+    fn new_hash_invalidates_entry() {
+        let mut index = CacheIndex::default();
+        insert(&mut index, "a.png", Some(1), 10, 1);
+        insert(&mut index, "b.png", Some(1), 10, 2);
+
+        let removed = insert(&mut index, "a.png", Some(2), 10, 3);
+        assert_eq!(removed, vec![entry_name("a.png", Some(&[1; 32]))]);
+        assert_eq!(
+            index.latest("a.png"),
+            Some(entry_name("a.png", Some(&[2; 32])))
+        );
+        assert_eq!(index.entries.len(), 2);
+
+        // Storing the same hash again replaces the entry in place.
+        assert!(insert(&mut index, "a.png", Some(2), 10, 4).is_empty());
+        assert_eq!(index.entries.len(), 2);
+
+        let removed = index.remove_path("a.png");
+        assert_eq!(removed, vec![entry_name("a.png", Some(&[2; 32]))]);
+        assert_eq!(index.latest("a.png"), None);
+        assert!(index.latest("b.png").is_some());
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut index = CacheIndex::default();
+        insert(&mut index, "a.png", None, 10, 3);
+        insert(&mut index, "b.png", None, 10, 1);
+        insert(&mut index, "c.png", None, 10, 2);
+
+        assert!(index.evict_to(30).is_empty());
+        assert_eq!(
+            index.evict_to(15),
+            vec![entry_name("b.png", None), entry_name("c.png", None)]
+        );
+        assert_eq!(index.latest("a.png"), Some(entry_name("a.png", None)));
+        assert_eq!(index.total_size(), 10);
+    }
+}
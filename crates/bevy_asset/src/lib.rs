@@ -160,37 +160,44 @@ pub mod transformer;
 /// This includes the most common types in this crate, re-exported for your convenience.
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::asset_changed::AssetChanged;
+    pub use crate::asset_changed::{AssetChanged, AssetChangedEntities};
 
     #[doc(hidden)]
     pub use crate::{
-        Asset, AssetApp, AssetEvent, AssetId, AssetMode, AssetPlugin, AssetServer, Assets,
-        DirectAssetAccessExt, Handle, UntypedHandle,
+        AsAssetId, AsUntypedAssetId, Asset, AssetApp, AssetCollection, AssetEvent, AssetHolders,
+        AssetId, AssetMode, AssetPlugin, AssetServer, Assets, DirectAssetAccessExt, Handle,
+        UntypedHandle,
     };
 }
 
 mod asset_changed;
 mod assets;
+mod collection;
 mod direct_access_ext;
 mod event;
 mod folder;
+mod glob;
 mod handle;
 mod id;
+mod introspection;
 mod loader;
 mod loader_builders;
 mod path;
 mod reflect;
 mod render_asset;
 mod server;
+mod test_app;
 
 pub use assets::*;
-pub use bevy_asset_macros::Asset;
+pub use bevy_asset_macros::{Asset, AssetCollection};
+pub use collection::{AssetCollection, LoadingAssetCollection};
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
 pub use futures_lite::{AsyncReadExt, AsyncWriteExt};
 pub use handle::*;
 pub use id::*;
+pub use introspection::*;
 pub use loader::*;
 pub use loader_builders::{
     Deferred, DynamicTyped, Immediate, NestedLoader, StaticTyped, UnknownTyped,
@@ -199,6 +206,7 @@ pub use path::*;
 pub use reflect::*;
 pub use render_asset::*;
 pub use server::*;
+pub use test_app::*;
 
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy assets.
 pub use ron;
@@ -213,11 +221,13 @@ use alloc::{
     sync::Arc,
     vec::Vec,
 };
-use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate, Startup};
 use bevy_ecs::prelude::Component;
 use bevy_ecs::{
     reflect::AppTypeRegistry,
-    schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet},
+    schedule::{
+        common_conditions::resource_exists, IntoSystemConfigs, IntoSystemSetConfigs, SystemSet,
+    },
     world::FromWorld,
 };
 use bevy_platform_support::collections::HashSet;
@@ -255,6 +265,16 @@ pub struct AssetPlugin {
     pub mode: AssetMode,
     /// How/If asset meta files should be checked.
     pub meta_check: AssetMetaCheck,
+    /// The maximum number of assets loading at the same time, or [`None`] for no limit. The loads requested
+    /// past this limit wait, and start by order of [`LoadPriority`] as the running loads complete.
+    ///
+    /// Loads started by [`AssetLoader`]s with [`NestedLoader::immediate`] are not limited, as the loader
+    /// waits for them. A load keeps its slot until it completes, so loaders which wait for other loads in
+    /// another way, such as with [`AssetServer::wait_for_asset`], can deadlock once every slot is taken by
+    /// such loaders.
+    ///
+    /// Defaults to [`None`].
+    pub max_concurrent_loads: Option<usize>,
 }
 
 /// Controls whether or not assets are pre-processed before being loaded.
@@ -308,6 +328,7 @@ impl Default for AssetPlugin {
             processed_file_path: Self::DEFAULT_PROCESSED_FILE_PATH.to_string(),
             watch_for_changes_override: None,
             meta_check: AssetMetaCheck::default(),
+            max_concurrent_loads: None,
         }
     }
 }
@@ -382,17 +403,26 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        app.world()
+            .resource::<AssetServer>()
+            .set_max_concurrent_loads(self.max_concurrent_loads);
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
             .init_asset::<()>()
             .add_event::<UntypedAssetLoadFailedEvent>()
+            .add_event::<LoadedFolderFileFailedEvent>()
+            .init_resource::<AssetHolders>()
             .configure_sets(PreUpdate, TrackAssets.after(handle_internal_asset_events))
             // `handle_internal_asset_events` requires the use of `&mut World`,
             // and as a result has ambiguous system ordering with all other systems in `PreUpdate`.
             // This is virtually never a real problem: asset loading is async and so anything that interacts directly with it
             // needs to be robust to stochastic delays anyways.
             .add_systems(PreUpdate, handle_internal_asset_events.ambiguous_with_all())
+            .add_systems(
+                PreUpdate,
+                send_loaded_folder_file_failed_events.after(handle_internal_asset_events),
+            )
             .register_type::<AssetPath>();
     }
 }
@@ -420,6 +450,21 @@ pub trait AsAssetId: Component {
     fn as_asset_id(&self) -> AssetId<Self::Asset>;
 }
 
+/// A trait for components that can be used as identifiers of assets of any type, e.g.
+/// [`UntypedHandle`] wrappers.
+///
+/// This is implemented for every [`AsAssetId`] component.
+pub trait AsUntypedAssetId: Component {
+    /// Retrieves the untyped asset id from this component.
+    fn as_untyped_asset_id(&self) -> UntypedAssetId;
+}
+
+impl<C: AsAssetId> AsUntypedAssetId for C {
+    fn as_untyped_asset_id(&self) -> UntypedAssetId {
+        self.as_asset_id().untyped()
+    }
+}
+
 /// This trait defines how to visit the dependencies of an asset.
 /// For example, a 3D model might require both textures and meshes to be loaded.
 ///
@@ -509,6 +554,12 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Loads the [`AssetCollection`] `C` on startup, and inserts it as a resource once all its
+    /// assets are loaded. Until then, it's in a [`LoadingAssetCollection`] resource.
+    fn load_asset_collection<C: AssetCollection>(&mut self) -> &mut Self;
+    /// Records the entities holding an asset through a `C` component in the [`AssetHolders`]
+    /// resource, to find out which entities keep an asset alive.
+    fn track_asset_holders<C: AsUntypedAssetId>(&mut self) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -612,6 +663,21 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn load_asset_collection<C: AssetCollection>(&mut self) -> &mut Self {
+        self.add_systems(Startup, collection::start_loading_asset_collection::<C>)
+            .add_systems(
+                PreUpdate,
+                collection::finish_loading_asset_collection::<C>
+                    .run_if(resource_exists::<LoadingAssetCollection<C>>)
+                    .after(handle_internal_asset_events),
+            )
+    }
+
+    fn track_asset_holders<C: AsUntypedAssetId>(&mut self) -> &mut Self {
+        self.add_observer(track_asset_holder_insert::<C>)
+            .add_observer(track_asset_holder_replace::<C>)
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -636,8 +702,10 @@ mod tests {
             AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets,
+        AsAssetId, Asset, AssetApp, AssetCollection, AssetEvent, AssetHolders, AssetId,
+        AssetLoadError, AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetServer, Assets,
+        LoadPriority, LoadedFolderFileFailedEvent, LoadingAssetCollection,
+        RecursiveDependencyLoadState,
     };
     use alloc::{
         boxed::Box,
@@ -1437,6 +1505,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_priority_is_forgotten_when_unloaded() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+        let path = "a.cool.ron";
+        dir.insert_asset_text(Path::new(path), SIMPLE_TEXT);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+
+        let server = app.world().resource::<AssetServer>().clone();
+        let handle = server.load_with_priority::<CoolText>(path, LoadPriority::High);
+        gate_opener.open(path);
+        run_app_until(&mut app, |_| server.is_loaded(&handle).then_some(()));
+        assert_eq!(server.load_priority(path), LoadPriority::High);
+
+        drop(handle);
+        app.update();
+        assert_eq!(server.load_priority(path), LoadPriority::Normal);
+    }
+
     #[test]
     fn manual_asset_management() {
         // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
@@ -1632,6 +1726,195 @@ mod tests {
         });
     }
 
+    #[test]
+    fn load_folder_matching() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+        let ron = |text: &str| {
+            format!(
+                "(text: \"{text}\", dependencies: [], embedded_dependencies: [], sub_texts: [])"
+            )
+        };
+        let c_path = "text/nested/c.cool.ron";
+        let a_path = "text/a.cool.ron";
+        let broken_path = "text/broken.cool.ron";
+        dir.insert_asset_text(Path::new(c_path), &ron("c"));
+        dir.insert_asset_text(Path::new(a_path), &ron("a"));
+        dir.insert_asset_text(Path::new(broken_path), "(text: ");
+        dir.insert_asset_text(Path::new("text/ignored.txt"), "ignored");
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle = asset_server.load_folder_matching::<CoolText>("text/**/*.cool.ron");
+        gate_opener.open(a_path);
+        gate_opener.open(broken_path);
+        gate_opener.open(c_path);
+
+        let mut failures = Vec::new();
+        run_app_until(&mut app, |world| {
+            let events = world.resource::<Events<LoadedFolderFileFailedEvent>>();
+            failures.extend(
+                events
+                    .iter_current_update_events()
+                    .map(|event| (event.folder, event.path.clone())),
+            );
+            // The broken file fails the recursive dependencies of the folder.
+            let state = world
+                .resource::<AssetServer>()
+                .get_recursive_dependency_load_state(&handle);
+            matches!(state, Some(RecursiveDependencyLoadState::Failed(_))).then_some(())
+        });
+
+        let loaded_folder = app
+            .world()
+            .resource::<Assets<LoadedFolder>>()
+            .get(&handle)
+            .unwrap();
+        let paths: Vec<_> = loaded_folder
+            .handles_of::<CoolText>()
+            .map(|handle| handle.path().unwrap().to_string())
+            .collect();
+        assert_eq!(paths, [a_path, broken_path, c_path]);
+        assert_eq!(failures, [(handle.id(), AssetPath::from(broken_path))]);
+    }
+
+    #[test]
+    fn asset_introspection() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        #[derive(Component)]
+        struct TextHolder(Handle<CoolText>);
+
+        impl AsAssetId for TextHolder {
+            type Asset = CoolText;
+
+            fn as_asset_id(&self) -> AssetId<CoolText> {
+                self.0.id()
+            }
+        }
+
+        let dir = Dir::default();
+        let a_path = "a.cool.ron";
+        let b_path = "b.cool.ron";
+        dir.insert_asset_text(
+            Path::new(a_path),
+            r#"(text: "a", dependencies: ["b.cool.ron"], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        dir.insert_asset_text(
+            Path::new(b_path),
+            r#"(text: "b", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .track_asset_holders::<TextHolder>();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load(a_path);
+        let holder = app.world_mut().spawn(TextHolder(a.clone())).id();
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+
+        // `a` is held by the test and the holder, and `b` by the test and `a`, once the load tasks
+        // have dropped their handles.
+        let b = asset_server.load::<CoolText>(b_path);
+        run_app_until(&mut app, |world| {
+            let asset_server = world.resource::<AssetServer>();
+            (asset_server.is_loaded_with_dependencies(&a)
+                && asset_server.get_strong_handle_count(&a) == Some(2)
+                && asset_server.get_strong_handle_count(&b) == Some(2))
+            .then_some(())
+        });
+
+        assert_eq!(
+            asset_server.get_dependencies(&a),
+            Some(vec![b.id().untyped()])
+        );
+        assert_eq!(asset_server.get_dependents(&b), [a.id().untyped()]);
+        assert!(asset_server.get_dependents(&a).is_empty());
+
+        let holders = app.world().resource::<AssetHolders>();
+        assert_eq!(holders.get(&a).collect::<Vec<_>>(), [holder]);
+        let report = asset_server.report().with_holders(holders);
+        let entry = report.get(&a).unwrap();
+        assert_eq!(entry.path, Some(AssetPath::from(a_path)));
+        assert_eq!(entry.holders, [holder]);
+        assert!(report.to_string().contains("a.cool.ron"));
+
+        app.world_mut().despawn(holder);
+        assert_eq!(app.world().resource::<AssetHolders>().count(&a), 0);
+        assert_eq!(asset_server.get_strong_handle_count(&a), Some(1));
+    }
+
+    #[test]
+    fn loader_preference_is_scoped_to_the_asset() {
+        /// Loads any `.txt` file as a [`CoolText`] holding `ID`, so the loader that was used can
+        /// be told from the asset.
+        struct IdLoader<const ID: u8>;
+
+        impl<const ID: u8> AssetLoader for IdLoader<ID> {
+            type Asset = CoolText;
+
+            type Settings = ();
+
+            type Error = std::io::Error;
+
+            async fn load(
+                &self,
+                _reader: &mut dyn Reader,
+                _settings: &Self::Settings,
+                _load_context: &mut LoadContext<'_>,
+            ) -> Result<Self::Asset, Self::Error> {
+                Ok(CoolText {
+                    text: ID.to_string(),
+                    ..Default::default()
+                })
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["txt"]
+            }
+        }
+
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("a.txt"), "a");
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<CoolText>()
+        .register_asset_loader(IdLoader::<1>)
+        .register_asset_loader(IdLoader::<2>);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+
+        let handle = asset_server.load_with_loader::<IdLoader<1>>("a.txt", |_| {});
+        run_app_until(&mut app, |world| get(world, handle.id()).map(|_| ()));
+        assert_eq!(get(app.world(), handle.id()).unwrap().text, "1");
+
+        // The preference is dropped with the asset, so later loads use the loader registered last.
+        let id = handle.id();
+        drop(handle);
+        run_app_until(&mut app, |_| {
+            asset_server.get_load_state(id).is_none().then_some(())
+        });
+        let handle = asset_server.load::<CoolText>("a.txt");
+        run_app_until(&mut app, |world| get(world, handle.id()).map(|_| ()));
+        assert_eq!(get(app.world(), handle.id()).unwrap().text, "2");
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
@@ -1776,6 +2059,70 @@ mod tests {
         app.world_mut().run_schedule(Update);
     }
 
+    #[test]
+    fn load_asset_collection() {
+        #[derive(AssetCollection, Resource)]
+        struct Texts {
+            #[asset(path = "a.cool.ron")]
+            a: Handle<CoolText>,
+            #[asset(path = "c.cool.ron")]
+            c: Handle<CoolText>,
+        }
+
+        let dir = Dir::default();
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .load_asset_collection::<Texts>();
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+        gate_opener.open(c_path);
+
+        run_app_until(&mut app, |world| {
+            world.contains_resource::<Texts>().then_some(())
+        });
+
+        let world = app.world();
+        assert!(!world.contains_resource::<LoadingAssetCollection<Texts>>());
+        let texts = world.resource::<Texts>();
+        let asset_server = world.resource::<AssetServer>();
+        assert!(asset_server.is_loaded_with_dependencies(texts.a.id()));
+        assert_eq!(get(world, texts.a.id()).unwrap().text, "a");
+        assert_eq!(get(world, texts.c.id()).unwrap().text, "c");
+        assert_eq!(texts.progress(asset_server), 1.0);
+    }
+
     // validate the Asset derive macro for various asset types
     #[derive(Asset, TypePath)]
     pub struct TestAsset;
@@ -48,6 +48,33 @@ pub trait AssetLoader: Send + Sync + 'static {
     fn extensions(&self) -> &[&str] {
         &[]
     }
+
+    /// Inspects the first bytes of an asset to tell whether this [`AssetLoader`] can load it.
+    ///
+    /// This is used to choose between several loaders matching the type and extension of an asset,
+    /// when neither its `.meta` file nor the code loading it select one. `header` holds the first
+    /// [`SNIFF_LENGTH`] bytes of the asset, or fewer if the asset is shorter.
+    ///
+    /// The default implementation returns [`SniffResult::Unknown`].
+    fn sniff(&self, _header: &[u8]) -> SniffResult {
+        SniffResult::Unknown
+    }
+}
+
+/// The maximum number of bytes passed to [`AssetLoader::sniff`].
+pub const SNIFF_LENGTH: usize = 512;
+
+/// Whether an [`AssetLoader`] recognizes the content of an asset, as returned by [`AssetLoader::sniff`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SniffResult {
+    /// The content is in a format the loader can load, for example it starts with the magic number
+    /// of that format.
+    Accept,
+    /// The content is in a format the loader can't load.
+    Reject,
+    /// The loader can't tell from the first bytes of the content.
+    #[default]
+    Unknown,
 }
 
 /// Provides type-erased access to an [`AssetLoader`].
@@ -65,6 +92,8 @@ pub trait ErasedAssetLoader: Send + Sync + 'static {
 
     /// Returns a list of extensions supported by this asset loader, without the preceding dot.
     fn extensions(&self) -> &[&str];
+    /// Inspects the first bytes of an asset to tell whether this asset loader can load it.
+    fn sniff(&self, header: &[u8]) -> SniffResult;
     /// Deserializes metadata from the input `meta` bytes into the appropriate type (erased as [`Box<dyn AssetMetaDyn>`]).
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the default meta value for the [`AssetLoader`] (erased as [`Box<dyn AssetMetaDyn>`]).
@@ -110,6 +139,10 @@ where
         <L as AssetLoader>::extensions(self)
     }
 
+    fn sniff(&self, header: &[u8]) -> SniffResult {
+        <L as AssetLoader>::sniff(self, header)
+    }
+
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError> {
         let meta = AssetMeta::<L, ()>::deserialize(meta)?;
         Ok(Box::new(meta))
@@ -305,6 +305,9 @@ impl NestedLoader<'_, '_, StaticTyped, Deferred> {
     pub fn load<'c, A: Asset>(self, path: impl Into<AssetPath<'c>>) -> Handle<A> {
         let path = path.into().to_owned();
         let handle = if self.load_context.should_load_dependencies {
+            self.load_context
+                .asset_server
+                .inherit_load_priority(self.load_context.asset_path(), &path);
             self.load_context
                 .asset_server
                 .load_with_meta_transform(path, self.meta_transform, ())
@@ -329,6 +332,9 @@ impl NestedLoader<'_, '_, DynamicTyped, Deferred> {
     pub fn load<'p>(self, path: impl Into<AssetPath<'p>>) -> UntypedHandle {
         let path = path.into().to_owned();
         let handle = if self.load_context.should_load_dependencies {
+            self.load_context
+                .asset_server
+                .inherit_load_priority(self.load_context.asset_path(), &path);
             self.load_context
                 .asset_server
                 .load_erased_with_meta_transform(
@@ -359,6 +365,9 @@ impl NestedLoader<'_, '_, UnknownTyped, Deferred> {
     pub fn load<'p>(self, path: impl Into<AssetPath<'p>>) -> Handle<LoadedUntypedAsset> {
         let path = path.into().to_owned();
         let handle = if self.load_context.should_load_dependencies {
+            self.load_context
+                .asset_server
+                .inherit_load_priority(self.load_context.asset_path(), &path);
             self.load_context
                 .asset_server
                 .load_unknown_type_with_meta_transform(path, self.meta_transform)
@@ -413,7 +422,7 @@ impl<'builder, 'reader, T> NestedLoader<'_, '_, T, Immediate<'builder, 'reader>>
             let (meta, loader, reader) = self
                 .load_context
                 .asset_server
-                .get_meta_loader_and_reader(path, asset_type_id)
+                .get_meta_loader_and_reader(path, asset_type_id, None)
                 .await
                 .map_err(|error| LoadDirectError {
                     dependency: path.clone(),
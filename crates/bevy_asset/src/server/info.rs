@@ -1,5 +1,6 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
+    server::LoaderPreference,
     Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
     Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
     UntypedAssetId, UntypedHandle,
@@ -27,6 +28,8 @@ pub(crate) struct AssetInfo {
     pub(crate) load_state: LoadState,
     pub(crate) dep_load_state: DependencyLoadState,
     pub(crate) rec_dep_load_state: RecursiveDependencyLoadState,
+    /// The direct dependencies of the asset, as of its last load.
+    pub(crate) dependencies: HashSet<UntypedAssetId>,
     loading_dependencies: HashSet<UntypedAssetId>,
     failed_dependencies: HashSet<UntypedAssetId>,
     loading_rec_dependencies: HashSet<UntypedAssetId>,
@@ -45,6 +48,9 @@ pub(crate) struct AssetInfo {
     handle_drops_to_skip: usize,
     /// List of tasks waiting for this asset to complete loading
     pub(crate) waiting_tasks: Vec<Waker>,
+    /// The [`AssetLoader`](crate::AssetLoader) requested by the load which started loading this
+    /// asset, used when several loaders match it. Reloads keep using it.
+    pub(crate) loader_preference: Option<LoaderPreference>,
 }
 
 impl AssetInfo {
@@ -55,6 +61,7 @@ impl AssetInfo {
             load_state: LoadState::NotLoaded,
             dep_load_state: DependencyLoadState::NotLoaded,
             rec_dep_load_state: RecursiveDependencyLoadState::NotLoaded,
+            dependencies: HashSet::default(),
             loading_dependencies: HashSet::default(),
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
@@ -64,8 +71,19 @@ impl AssetInfo {
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
             waiting_tasks: Vec::new(),
+            loader_preference: None,
         }
     }
+
+    /// Returns the number of strong handles to the asset that are alive.
+    pub(crate) fn strong_handle_count(&self) -> usize {
+        self.weak_handle.strong_count()
+    }
+
+    /// Returns the direct dependencies of the asset which are still loading.
+    pub(crate) fn loading_dependencies(&self) -> &HashSet<UntypedAssetId> {
+        &self.loading_dependencies
+    }
 }
 
 #[derive(Default)]
@@ -283,6 +301,10 @@ impl AssetInfos {
         self.infos.get(&id)
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (UntypedAssetId, &AssetInfo)> {
+        self.infos.iter().map(|(id, info)| (*id, info))
+    }
+
     pub(crate) fn contains_key(&self, id: UntypedAssetId) -> bool {
         self.infos.contains_key(&id)
     }
@@ -397,6 +419,7 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_id, world);
+        let dependencies = loaded_asset.dependencies.clone();
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
@@ -493,6 +516,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_id)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
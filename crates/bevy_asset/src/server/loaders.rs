@@ -1,5 +1,5 @@
 use crate::{
-    loader::{AssetLoader, ErasedAssetLoader},
+    loader::{AssetLoader, ErasedAssetLoader, SniffResult},
     path::AssetPath,
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
@@ -33,6 +33,13 @@ impl AssetLoaders {
         self.loaders.get(index).cloned()
     }
 
+    /// Get the [`AssetLoader`]s stored at the specific indices
+    fn get_all_by_index(&self, indices: impl Iterator<Item = usize>) -> Vec<MaybeAssetLoader> {
+        indices
+            .filter_map(|index| self.get_by_index(index))
+            .collect()
+    }
+
     /// Registers a new [`AssetLoader`]. [`AssetLoader`]s must be registered before they can be used.
     pub(crate) fn push<L: AssetLoader>(&mut self, loader: L) {
         let type_name = core::any::type_name::<L>();
@@ -74,7 +81,7 @@ impl AssetLoaders {
             }
             if !duplicate_extensions.is_empty() {
                 warn!("Duplicate AssetLoader registered for Asset type `{loader_asset_type_name}` with extensions `{duplicate_extensions:?}`. \
-                Loader must be specified in a .meta file in order to load assets of this type with these extensions.");
+                Unless the loaders recognize their assets with `AssetLoader::sniff`, the loader must be specified in a .meta file or with `AssetServer::load_with_loader` in order to load assets of this type with these extensions.");
             }
 
             self.type_name_to_loader.insert(type_name, loader_index);
@@ -140,7 +147,7 @@ impl AssetLoaders {
         }
         if !duplicate_extensions.is_empty() {
             warn!("Duplicate AssetLoader preregistered for Asset type `{loader_asset_type_name}` with extensions `{duplicate_extensions:?}`. \
-            Loader must be specified in a .meta file in order to load assets of this type with these extensions.");
+            Unless the loaders recognize their assets with `AssetLoader::sniff`, the loader must be specified in a .meta file or with `AssetServer::load_with_loader` in order to load assets of this type with these extensions.");
         }
 
         self.type_id_to_loaders
@@ -174,91 +181,81 @@ impl AssetLoaders {
             return self.get_by_name(type_name);
         }
 
+        self.find_candidates(asset_type_id, extension, asset_path)
+            .into_iter()
+            .next()
+    }
+
+    /// Find all the [`AssetLoader`]s matching the provided search criteria, from the most to the
+    /// least recently registered.
+    ///
+    /// Every loader matching the first conclusive resolution step is returned, so the first one is
+    /// the loader [`find`](Self::find) would return.
+    pub(crate) fn find_candidates(
+        &self,
+        asset_type_id: Option<TypeId>,
+        extension: Option<&str>,
+        asset_path: Option<&AssetPath<'_>>,
+    ) -> Vec<MaybeAssetLoader> {
         // The presence of a label will affect loader choice
         let label = asset_path.as_ref().and_then(|path| path.label());
 
         // Try by asset type
-        let candidates = if let Some(type_id) = asset_type_id {
-            if label.is_none() {
-                Some(self.type_id_to_loaders.get(&type_id)?)
-            } else {
-                None
+        let candidates = match asset_type_id {
+            Some(type_id) if label.is_none() => {
+                let Some(candidates) = self.type_id_to_loaders.get(&type_id) else {
+                    return Vec::new();
+                };
+                if candidates.len() <= 1 {
+                    return self.get_all_by_index(candidates.iter().copied());
+                }
+                Some(candidates)
             }
-        } else {
-            None
+            _ => None,
         };
 
-        if let Some(candidates) = candidates {
-            if candidates.is_empty() {
-                return None;
-            } else if candidates.len() == 1 {
-                let index = candidates.first().copied().unwrap();
-                return self.get_by_index(index);
-            }
-        }
-
         // Asset type is insufficient, use extension information
-        let try_extension = |extension| {
-            if let Some(indices) = self.extension_to_loaders.get(extension) {
-                if let Some(candidates) = candidates {
-                    if candidates.is_empty() {
-                        indices.last()
-                    } else {
-                        indices
-                            .iter()
-                            .rev()
-                            .find(|index| candidates.contains(index))
-                    }
-                } else {
-                    indices.last()
-                }
-            } else {
-                None
-            }
+        let try_extension = |extension: &str| {
+            let Some(indices) = self.extension_to_loaders.get(extension) else {
+                return Vec::new();
+            };
+            self.get_all_by_index(
+                indices
+                    .iter()
+                    .rev()
+                    .filter(|index| candidates.is_none_or(|candidates| candidates.contains(index)))
+                    .copied(),
+            )
         };
 
         // Try the provided extension
         if let Some(extension) = extension {
-            if let Some(&index) = try_extension(extension) {
-                return self.get_by_index(index);
+            let loaders = try_extension(extension);
+            if !loaders.is_empty() {
+                return loaders;
             }
         }
 
-        // Try extracting the extension from the path
+        // Try extracting the extension from the path, then secondary extensions
         if let Some(full_extension) = asset_path.and_then(AssetPath::get_full_extension) {
-            if let Some(&index) = try_extension(full_extension.as_str()) {
-                return self.get_by_index(index);
-            }
-
-            // Try secondary extensions from the path
-            for extension in AssetPath::iter_secondary_extensions(&full_extension) {
-                if let Some(&index) = try_extension(extension) {
-                    return self.get_by_index(index);
-                }
+            let loaders = core::iter::once(full_extension.as_str())
+                .chain(AssetPath::iter_secondary_extensions(&full_extension))
+                .map(try_extension)
+                .find(|loaders| !loaders.is_empty());
+            if let Some(loaders) = loaders {
+                return loaders;
             }
         }
 
         // Fallback if no resolution step was conclusive
-        match candidates?
-            .last()
-            .copied()
-            .and_then(|index| self.get_by_index(index))
-        {
-            Some(loader) => {
-                warn!(
-                    "Multiple AssetLoaders found for Asset: {:?}; Path: {:?}; Extension: {:?}",
-                    asset_type_id, asset_path, extension
-                );
-                Some(loader)
-            }
-            None => {
-                warn!(
-                    "No AssetLoader found for Asset: {:?}; Path: {:?}; Extension: {:?}",
-                    asset_type_id, asset_path, extension
-                );
-                None
-            }
-        }
+        let Some(candidates) = candidates else {
+            return Vec::new();
+        };
+        warn!(
+            "Multiple AssetLoaders found for Asset: {:?}; Path: {:?}; Extension: {:?}",
+            asset_type_id, asset_path, extension
+        );
+        self.get_all_by_index(candidates.iter().rev().copied())
     }
 
     /// Get the [`AssetLoader`] for a given asset type
@@ -288,6 +285,50 @@ impl AssetLoaders {
     }
 }
 
+/// Keeps the `loaders` for which `keep` returns `true`, unless it returns `false` for all of them.
+pub(crate) fn retain_if_any(
+    loaders: &mut Vec<Arc<dyn ErasedAssetLoader>>,
+    keep: impl Fn(&dyn ErasedAssetLoader) -> bool,
+) {
+    if loaders.iter().any(|loader| keep(&**loader)) {
+        loaders.retain(|loader| keep(&**loader));
+    }
+}
+
+/// Narrows `loaders` down to the ones recognizing the `header` of an asset.
+///
+/// Loaders accepting the header are preferred, and loaders rejecting it are discarded, unless all
+/// of them do.
+pub(crate) fn retain_sniffed(loaders: &mut Vec<Arc<dyn ErasedAssetLoader>>, header: &[u8]) {
+    retain_if_any(loaders, |loader| {
+        loader.sniff(header) == SniffResult::Accept
+    });
+    retain_if_any(loaders, |loader| {
+        loader.sniff(header) != SniffResult::Reject
+    });
+}
+
+/// Chooses between the `loaders` matching an asset, ordered from the most to the least preferred.
+///
+/// If the loaders all load the same asset type, the most preferred one is chosen, as more recently
+/// registered loaders shadow older ones. Otherwise the choice is ambiguous, and the type names of
+/// the loaders are returned.
+pub(crate) fn choose_loader(
+    loaders: Vec<Arc<dyn ErasedAssetLoader>>,
+) -> Result<Option<Arc<dyn ErasedAssetLoader>>, Vec<&'static str>> {
+    let Some(first) = loaders.first() else {
+        return Ok(None);
+    };
+    if loaders
+        .iter()
+        .all(|loader| loader.asset_type_id() == first.asset_type_id())
+    {
+        Ok(loaders.into_iter().next())
+    } else {
+        Err(loaders.iter().map(|loader| loader.type_name()).collect())
+    }
+}
+
 #[derive(Error, Debug, Clone)]
 pub(crate) enum GetLoaderError {
     #[error(transparent)]
@@ -338,11 +379,15 @@ impl<T: AssetLoader> AssetLoader for InstrumentedAssetLoader<T> {
     fn extensions(&self) -> &[&str] {
         self.0.extensions()
     }
+
+    fn sniff(&self, header: &[u8]) -> SniffResult {
+        self.0.sniff(header)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use alloc::{format, string::String};
+    use alloc::{format, string::String, vec};
     use core::marker::PhantomData;
     use std::{
         path::Path,
@@ -857,4 +902,104 @@ mod tests {
         assert!(rx_a2_a.try_recv().is_err());
         assert!(rx_a3_a.try_recv().is_ok());
     }
+
+    /// An [`AssetLoader`] for the `json` extension, recognizing assets starting with `MAGIC`.
+    struct SniffingLoader<T: Asset, const MAGIC: u8>(PhantomData<T>);
+
+    impl<T: Asset, const MAGIC: u8> AssetLoader for SniffingLoader<T, MAGIC> {
+        type Asset = T;
+
+        type Settings = ();
+
+        type Error = String;
+
+        async fn load(
+            &self,
+            _: &mut dyn crate::io::Reader,
+            _: &Self::Settings,
+            _: &mut crate::LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            Err(format!("Loaded {}", MAGIC))
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["json"]
+        }
+
+        fn sniff(&self, header: &[u8]) -> SniffResult {
+            match header.first() {
+                Some(&byte) if byte == MAGIC => SniffResult::Accept,
+                Some(_) => SniffResult::Reject,
+                None => SniffResult::Unknown,
+            }
+        }
+    }
+
+    /// Ensure that all the [`AssetLoader`]s matching an extension are candidates, and that sniffing chooses between them.
+    #[test]
+    fn sniffing_resolution() {
+        let mut loaders = AssetLoaders::default();
+
+        loaders.push(SniffingLoader::<A, 1>(PhantomData));
+        loaders.push(SniffingLoader::<B, 2>(PhantomData));
+
+        let path = AssetPath::from_path(Path::new("asset.json"));
+        let choose = |header: &[u8]| {
+            let mut candidates: Vec<_> = loaders
+                .find_candidates(None, None, Some(&path))
+                .into_iter()
+                .map(|loader| block_on(loader.get()).unwrap())
+                .collect();
+            retain_sniffed(&mut candidates, header);
+            choose_loader(candidates).map(|loader| loader.unwrap().type_name())
+        };
+
+        assert_eq!(
+            choose(&[1, 0]),
+            Ok(core::any::type_name::<SniffingLoader<A, 1>>())
+        );
+        assert_eq!(
+            choose(&[2, 0]),
+            Ok(core::any::type_name::<SniffingLoader<B, 2>>())
+        );
+        // Without any conclusive sniffing, loaders of different asset types are ambiguous.
+        let headers: [&[u8]; 2] = [&[], &[3]];
+        for header in headers {
+            assert_eq!(
+                choose(header),
+                Err(vec![
+                    core::any::type_name::<SniffingLoader<B, 2>>(),
+                    core::any::type_name::<SniffingLoader<A, 1>>(),
+                ])
+            );
+        }
+
+        // Requesting an asset type narrows the candidates down.
+        let candidates = loaders.find_candidates(Some(TypeId::of::<A>()), None, Some(&path));
+        assert_eq!(candidates.len(), 1);
+    }
+
+    /// Ensure that loaders of the same asset type aren't ambiguous, and the most recently registered one is chosen.
+    #[test]
+    fn sniffing_shadow() {
+        let mut loaders = AssetLoaders::default();
+
+        loaders.push(SniffingLoader::<A, 1>(PhantomData));
+        loaders.push(SniffingLoader::<A, 2>(PhantomData));
+
+        let candidates: Vec<_> = loaders
+            .find_candidates(
+                None,
+                None,
+                Some(&AssetPath::from_path(Path::new("asset.json"))),
+            )
+            .into_iter()
+            .map(|loader| block_on(loader.get()).unwrap())
+            .collect();
+
+        assert_eq!(
+            choose_loader(candidates).map(|loader| loader.unwrap().type_name()),
+            Ok(core::any::type_name::<SniffingLoader<A, 2>>())
+        );
+    }
 }
@@ -1,13 +1,19 @@
 mod info;
 mod loaders;
+mod scheduler;
+
+pub use scheduler::LoadPriority;
 
 use crate::{
     folder::LoadedFolder,
+    glob::GlobPattern,
+    introspection::{AssetReport, AssetReportEntry},
     io::{
         AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
-        ErasedAssetReader, MissingAssetSourceError, MissingProcessedAssetReaderError, Reader,
+        ErasedAssetReader, MissingAssetSourceError, MissingProcessedAssetReaderError, PeekReader,
+        Reader,
     },
-    loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
+    loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset, SNIFF_LENGTH},
     meta::{
         loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn, AssetMetaMinimal,
         MetaTransform, Settings,
@@ -25,15 +31,21 @@ use alloc::{
 };
 use atomicow::CowArc;
 use bevy_ecs::prelude::*;
-use bevy_platform_support::collections::HashSet;
+use bevy_platform_support::collections::{HashMap, HashSet};
 use bevy_tasks::IoTaskPool;
-use core::{any::TypeId, future::Future, panic::AssertUnwindSafe, task::Poll};
+use core::{
+    any::{Any, TypeId},
+    future::Future,
+    panic::AssertUnwindSafe,
+    task::Poll,
+};
 use crossbeam_channel::{Receiver, Sender};
 use either::Either;
 use futures_lite::{FutureExt, StreamExt};
 use info::*;
 use loaders::*;
 use parking_lot::{RwLock, RwLockWriteGuard};
+use scheduler::LoadScheduler;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::{error, info};
@@ -66,6 +78,17 @@ pub(crate) struct AssetServerData {
     sources: AssetSources,
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
+    load_scheduler: LoadScheduler,
+}
+
+/// The [`AssetLoader`] requested when loading an asset, used when several loaders match it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LoaderPreference {
+    /// The loader with the given type name, requested by [`AssetServer::load_with_loader`].
+    Loader(&'static str),
+    /// The loaders whose settings have the given type, requested by
+    /// [`AssetServer::load_with_settings`].
+    Settings(TypeId),
 }
 
 /// The "asset mode" the server is currently in.
@@ -126,6 +149,7 @@ impl AssetServer {
                 asset_event_receiver,
                 loaders,
                 infos: RwLock::new(infos),
+                load_scheduler: Default::default(),
             }),
         }
     }
@@ -336,6 +360,85 @@ impl AssetServer {
         self.load_with_meta_transform(path, None, guard)
     }
 
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given [`LoadPriority`].
+    ///
+    /// When the number of concurrent loads is limited, as set by [`AssetServer::set_max_concurrent_loads`],
+    /// the loads of higher priority start first, and the dependencies loaded by the asset's [`AssetLoader`]
+    /// inherit its priority. The priority is kept for future loads of the asset while it stays loaded, such
+    /// as hot reloads, and can be changed while it loads with [`AssetServer::set_load_priority`].
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        let path = path.into();
+        self.data.load_scheduler.set_priority(&path, priority);
+        self.load_with_meta_transform(path, None, ())
+    }
+
+    /// Changes the [`LoadPriority`] of the asset with the given `id`, which applies to its load if it
+    /// hasn't started yet, and to its future loads.
+    ///
+    /// The dependencies of the asset which are still loading are raised to the new priority if it is
+    /// higher than theirs.
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: LoadPriority) {
+        let id = id.into();
+        let infos = self.data.infos.read();
+        let Some(path) = infos.get(id).and_then(|info| info.path.as_ref()) else {
+            return;
+        };
+        self.data.load_scheduler.set_priority(path, priority);
+
+        let mut visited: HashSet<_> = HashSet::default();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let Some(info) = infos.get(id) else {
+                continue;
+            };
+            for &dependency in info.loading_dependencies() {
+                if !visited.insert(dependency) {
+                    continue;
+                }
+                if let Some(path) = infos.get(dependency).and_then(|info| info.path.as_ref()) {
+                    self.data.load_scheduler.raise_priority(path, priority);
+                }
+                stack.push(dependency);
+            }
+        }
+    }
+
+    /// Returns the [`LoadPriority`] of the asset at `path`.
+    pub fn load_priority<'a>(&self, path: impl Into<AssetPath<'a>>) -> LoadPriority {
+        self.data.load_scheduler.priority(&path.into())
+    }
+
+    /// Returns the maximum number of assets loading at the same time, or [`None`] if there is no limit.
+    pub fn max_concurrent_loads(&self) -> Option<usize> {
+        self.data.load_scheduler.max_concurrent_loads()
+    }
+
+    /// Sets the maximum number of assets loading at the same time. The loads requested past this limit
+    /// wait, and start by order of [`LoadPriority`] as the running loads complete.
+    ///
+    /// This defaults to [`AssetPlugin::max_concurrent_loads`](crate::AssetPlugin::max_concurrent_loads).
+    pub fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        self.data
+            .load_scheduler
+            .set_max_concurrent_loads(max_concurrent_loads);
+    }
+
+    /// Gives the dependency at `path` the [`LoadPriority`] of the asset at `dependent`, unless it has a
+    /// higher one.
+    pub(crate) fn inherit_load_priority(&self, dependent: &AssetPath<'_>, path: &AssetPath<'_>) {
+        self.data.load_scheduler.inherit_priority(dependent, path);
+    }
+
+    /// Forgets the [`LoadPriority`] of the asset at `path`, once no asset is loaded from it anymore.
+    pub(crate) fn forget_load_priority(&self, path: &AssetPath<'_>) {
+        self.data.load_scheduler.remove_priority(path);
+    }
+
     /// Begins loading an [`Asset`] of type `A` stored at `path`. The given `settings` function will override the asset's
     /// [`AssetLoader`] settings. The type `S` _must_ match the configured [`AssetLoader::Settings`] or `settings` changes
     /// will be ignored and an error will be printed to the log.
@@ -345,7 +448,32 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         settings: impl Fn(&mut S) + Send + Sync + 'static,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, Some(loader_settings_meta_transform(settings)), ())
+        self.load_with_loader_preference(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            Some(LoaderPreference::Settings(TypeId::of::<S>())),
+            (),
+        )
+    }
+
+    /// Begins loading an [`Asset`] stored at `path` with the [`AssetLoader`] `L`, instead of the loader
+    /// matching the asset's extension. The given `settings` function will override the loader's settings.
+    ///
+    /// This is useful when several loaders are registered for the same extension. The loader selected in
+    /// the asset's `.meta` file still takes precedence, if there is one. An asset which is already loaded
+    /// or loading keeps the loader it was loaded with.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_loader<'a, L: AssetLoader>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        settings: impl Fn(&mut L::Settings) + Send + Sync + 'static,
+    ) -> Handle<L::Asset> {
+        self.load_with_loader_preference(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            Some(LoaderPreference::Loader(core::any::type_name::<L>())),
+            (),
+        )
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path` while holding a guard item.
@@ -364,7 +492,12 @@ impl AssetServer {
         settings: impl Fn(&mut S) + Send + Sync + 'static,
         guard: G,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, Some(loader_settings_meta_transform(settings)), guard)
+        self.load_with_loader_preference(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            Some(LoaderPreference::Settings(TypeId::of::<S>())),
+            guard,
+        )
     }
 
     pub(crate) fn load_with_meta_transform<'a, A: Asset, G: Send + Sync + 'static>(
@@ -372,6 +505,21 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
         guard: G,
+    ) -> Handle<A> {
+        self.load_with_loader_preference(path, meta_transform, None, guard)
+    }
+
+    /// Like [`load_with_meta_transform`](Self::load_with_meta_transform), and records the
+    /// [`AssetLoader`] requested for the asset if this starts loading it.
+    ///
+    /// The preference belongs to the asset, so it is kept for reloads and forgotten once the asset
+    /// is dropped. Loading an asset which is already loaded or loading doesn't change its loader.
+    fn load_with_loader_preference<'a, A: Asset, G: Send + Sync + 'static>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        meta_transform: Option<MetaTransform>,
+        loader_preference: Option<LoaderPreference>,
+        guard: G,
     ) -> Handle<A> {
         let path = path.into().into_owned();
         let mut infos = self.data.infos.write();
@@ -382,6 +530,9 @@ impl AssetServer {
         );
 
         if should_load {
+            if let Some(info) = infos.get_mut(handle.id().untyped()) {
+                info.loader_preference = loader_preference;
+            }
             self.spawn_load_task(handle.clone().untyped(), path, infos, guard);
         }
 
@@ -554,11 +705,20 @@ impl AssetServer {
         meta_transform: Option<MetaTransform>,
     ) -> Result<UntypedHandle, AssetLoadError> {
         let asset_type_id = input_handle.as_ref().map(UntypedHandle::type_id);
+        let loader_preference = input_handle.as_ref().and_then(|handle| {
+            self.data
+                .infos
+                .read()
+                .get(handle.id())
+                .and_then(|info| info.loader_preference)
+        });
 
         let path = path.into_owned();
+        // Wait for the loads of higher priority, and hold the permit until this load completes.
+        let _permit = self.data.load_scheduler.acquire(&path).await;
         let path_clone = path.clone();
         let (mut meta, loader, mut reader) = self
-            .get_meta_loader_and_reader(&path_clone, asset_type_id)
+            .get_meta_loader_and_reader(&path_clone, asset_type_id, loader_preference)
             .await
             .inspect_err(|e| {
                 // if there was an input handle, a "load" operation has already started, so we must produce a "failure" event, if
@@ -875,8 +1035,11 @@ impl AssetServer {
         ) -> Result<(), AssetLoadError> {
             let is_dir = reader.is_directory(path).await?;
             if is_dir {
-                let mut path_stream = reader.read_directory(path.as_ref()).await?;
-                while let Some(child_path) = path_stream.next().await {
+                let mut child_paths: Vec<PathBuf> =
+                    reader.read_directory(path.as_ref()).await?.collect().await;
+                // Sort the paths so that the handles of the folder have a stable order.
+                child_paths.sort();
+                for child_path in child_paths {
                     if reader.is_directory(&child_path).await? {
                         Box::pin(load_folder(
                             source.clone(),
@@ -948,6 +1111,111 @@ impl AssetServer {
             .detach();
     }
 
+    /// Loads the assets of type `A` whose paths match the glob `pattern`, recursively. The
+    /// [`LoadedFolder`] asset (when it loads) will contain handles to all the matching assets, sorted
+    /// by path, which can be retrieved with [`LoadedFolder::handles_of`].
+    ///
+    /// The pattern supports the following syntax, in each `/`-separated part of the path:
+    /// - `*` matches any sequence of characters, except `/`.
+    /// - `?` matches any single character.
+    /// - `**`, as a whole part, matches any number of nested folders, including none.
+    /// - `{a,b}` matches either of the comma-separated alternatives.
+    ///
+    /// ```no_run
+    /// # use bevy_asset::{AssetServer, Handle, LoadedFolder};
+    /// # #[derive(bevy_asset::Asset, bevy_reflect::TypePath)]
+    /// # struct Image;
+    /// # fn system(asset_server: &AssetServer) {
+    /// let textures: Handle<LoadedFolder> =
+    ///     asset_server.load_folder_matching::<Image>("textures/**/*.{png,ktx2}");
+    /// # }
+    /// ```
+    ///
+    /// Unlike [`AssetServer::load_folder`], a file that fails to load doesn't fail the whole folder:
+    /// a [`LoadedFolderFileFailedEvent`] is sent for each file that fails to load, and the folder
+    /// is available as soon as the matching files are found. Wait for the [`LoadedFolder`]'s
+    /// [`RecursiveDependencyLoadState`] to know when all the files are loaded.
+    ///
+    /// Loading the same pattern with the same asset type multiple times will return the same
+    /// handle. Folders loaded from a pattern are not reloaded when files are added or removed.
+    ///
+    /// [`LoadedFolderFileFailedEvent`]: crate::LoadedFolderFileFailedEvent
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the assets"]
+    pub fn load_folder_matching<A: Asset>(&self, pattern: &str) -> Handle<LoadedFolder> {
+        // The type of the assets is part of the key, so that the same pattern can be loaded as
+        // different asset types.
+        let path = AssetPath::parse(pattern)
+            .into_owned()
+            .with_label(A::type_path());
+        let (handle, should_load) = self
+            .data
+            .infos
+            .write()
+            .get_or_create_path_handle::<LoadedFolder>(
+                path.clone(),
+                HandleLoadingMode::Request,
+                None,
+            );
+        if !should_load {
+            return handle;
+        }
+
+        let id = handle.id().untyped();
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let Ok(source) = server.get_source(path.source()) else {
+                    error!(
+                        "Failed to load {path}. AssetSource {} does not exist",
+                        path.source()
+                    );
+                    return;
+                };
+                let pattern = GlobPattern::new(&path.path().to_string_lossy());
+                let asset_reader = match server.data.mode {
+                    AssetServerMode::Unprocessed => source.reader(),
+                    AssetServerMode::Processed => match source.processed_reader() {
+                        Ok(reader) => reader,
+                        Err(_) => {
+                            error!(
+                                "Failed to load {path}. AssetSource {} does not have a processed AssetReader",
+                                path.source()
+                            );
+                            return;
+                        }
+                    },
+                };
+
+                let mut paths = Vec::new();
+                match find_matching_files(&pattern.base_folder(), &pattern, asset_reader, &mut paths).await {
+                    Ok(()) => {
+                        paths.sort();
+                        let handles = paths
+                            .into_iter()
+                            .map(|file| {
+                                server
+                                    .load::<A>(AssetPath::from(file).with_source(source.id()))
+                                    .untyped()
+                            })
+                            .collect();
+                        server.send_asset_event(InternalAssetEvent::Loaded {
+                            id,
+                            loaded_asset: LoadedAsset::new_with_dependencies(LoadedFolder { handles })
+                                .into(),
+                        });
+                    }
+                    Err(err) => {
+                        error!("Failed to load folder. {err}");
+                        let error = AssetLoadError::from(err);
+                        server.send_asset_event(InternalAssetEvent::Failed { id, error, path });
+                    }
+                }
+            })
+            .detach();
+
+        handle
+    }
+
     fn send_asset_event(&self, event: InternalAssetEvent) {
         self.data.asset_event_sender.send(event).unwrap();
     }
@@ -1066,6 +1334,73 @@ impl AssetServer {
         )
     }
 
+    /// Returns the number of strong handles to the asset `id` that are alive, or `None` if the asset
+    /// isn't managed by this [`AssetServer`].
+    pub fn get_strong_handle_count(&self, id: impl Into<UntypedAssetId>) -> Option<usize> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .map(AssetInfo::strong_handle_count)
+    }
+
+    /// Returns the assets the asset `id` directly depends on, as of its last load, or `None` if the
+    /// asset isn't managed by this [`AssetServer`].
+    pub fn get_dependencies(&self, id: impl Into<UntypedAssetId>) -> Option<Vec<UntypedAssetId>> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .map(|info| info.dependencies.iter().copied().collect())
+    }
+
+    /// Returns the assets managed by this [`AssetServer`] that directly depend on the asset `id`.
+    pub fn get_dependents(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        let id = id.into();
+        self.data
+            .infos
+            .read()
+            .iter()
+            .filter(|(_, info)| info.dependencies.contains(&id))
+            .map(|(dependent, _)| dependent)
+            .collect()
+    }
+
+    /// Returns an [`AssetReport`] of all the assets managed by this [`AssetServer`], with their
+    /// strong handle counts and dependencies.
+    ///
+    /// Assets added directly to [`Assets`] aren't managed by the [`AssetServer`], and aren't part
+    /// of the report.
+    pub fn report(&self) -> AssetReport {
+        let infos = self.data.infos.read();
+        let mut dependents = <HashMap<UntypedAssetId, Vec<UntypedAssetId>>>::default();
+        for (id, info) in infos.iter() {
+            for dependency in &info.dependencies {
+                dependents.entry(*dependency).or_default().push(id);
+            }
+        }
+        let mut assets: Vec<_> = infos
+            .iter()
+            .map(|(id, info)| {
+                let mut dependencies: Vec<_> = info.dependencies.iter().copied().collect();
+                dependencies.sort();
+                let mut dependents = dependents.remove(&id).unwrap_or_default();
+                dependents.sort();
+                AssetReportEntry {
+                    id,
+                    path: info.path.clone(),
+                    load_state: info.load_state.clone(),
+                    strong_handles: info.strong_handle_count(),
+                    dependencies,
+                    dependents,
+                    holders: Vec::new(),
+                }
+            })
+            .collect();
+        assets.sort_by_key(|entry| entry.id);
+        AssetReport { assets }
+    }
+
     /// Returns an active handle for the given path, if the asset at the given path has already started loading,
     /// or is still "alive".
     pub fn get_handle<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Option<Handle<A>> {
@@ -1215,6 +1550,7 @@ impl AssetServer {
         &'a self,
         asset_path: &'a AssetPath<'_>,
         asset_type_id: Option<TypeId>,
+        loader_preference: Option<LoaderPreference>,
     ) -> Result<
         (
             Box<dyn AssetMetaDyn>,
@@ -1275,47 +1611,85 @@ impl AssetServer {
                 }
                 Err(AssetReaderError::NotFound(_)) => {
                     // TODO: Handle error transformation
-                    let loader = {
-                        self.data
-                            .loaders
-                            .read()
-                            .find(None, asset_type_id, None, Some(asset_path))
-                    };
-
-                    let error = || AssetLoadError::MissingAssetLoader {
-                        loader_name: None,
-                        asset_type_id,
-                        extension: None,
-                        asset_path: Some(asset_path.to_string()),
-                    };
-
-                    let loader = loader.ok_or_else(error)?.get().await.map_err(|_| error())?;
-
+                    let (loader, reader) = self
+                        .select_loader(asset_path, asset_type_id, loader_preference, reader)
+                        .await?;
                     let meta = loader.default_meta();
                     Ok((meta, loader, reader))
                 }
                 Err(err) => Err(err.into()),
             }
         } else {
-            let loader = {
-                self.data
-                    .loaders
-                    .read()
-                    .find(None, asset_type_id, None, Some(asset_path))
-            };
+            let (loader, reader) = self
+                .select_loader(asset_path, asset_type_id, loader_preference, reader)
+                .await?;
+            let meta = loader.default_meta();
+            Ok((meta, loader, reader))
+        }
+    }
 
-            let error = || AssetLoadError::MissingAssetLoader {
-                loader_name: None,
-                asset_type_id,
-                extension: None,
-                asset_path: Some(asset_path.to_string()),
-            };
+    /// Chooses the [`AssetLoader`] for the asset at `asset_path`, when no `.meta` file selects one.
+    ///
+    /// If several loaders match the type and extension of the asset, the one requested by
+    /// [`AssetServer::load_with_loader`] is used, or the ones whose settings were provided to
+    /// [`AssetServer::load_with_settings`] are preferred. Any remaining ambiguity is resolved by
+    /// [sniffing](AssetLoader::sniff) the first bytes of the asset, in which case the returned
+    /// reader replays them.
+    async fn select_loader<'a>(
+        &self,
+        asset_path: &AssetPath<'_>,
+        asset_type_id: Option<TypeId>,
+        preference: Option<LoaderPreference>,
+        reader: Box<dyn Reader + 'a>,
+    ) -> Result<(Arc<dyn ErasedAssetLoader>, Box<dyn Reader + 'a>), AssetLoadError> {
+        let candidates = {
+            let loaders = self.data.loaders.read();
+            match preference {
+                Some(LoaderPreference::Loader(type_name)) => loaders
+                    .find(Some(type_name), asset_type_id, None, Some(asset_path))
+                    .into_iter()
+                    .collect(),
+                _ => loaders.find_candidates(asset_type_id, None, Some(asset_path)),
+            }
+        };
 
-            let loader = loader.ok_or_else(error)?.get().await.map_err(|_| error())?;
+        let error = || AssetLoadError::MissingAssetLoader {
+            loader_name: None,
+            asset_type_id,
+            extension: None,
+            asset_path: Some(asset_path.to_string()),
+        };
 
-            let meta = loader.default_meta();
-            Ok((meta, loader, reader))
+        let mut loaders = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            loaders.push(candidate.get().await.map_err(|_| error())?);
+        }
+
+        if let Some(LoaderPreference::Settings(settings_type_id)) = preference {
+            retain_if_any(&mut loaders, |loader| {
+                loader
+                    .default_meta()
+                    .loader_settings()
+                    .is_some_and(|settings| Any::type_id(settings.as_any()) == settings_type_id)
+            });
+        }
+
+        let mut reader = reader;
+        if loaders.len() > 1 {
+            let peek_reader = PeekReader::new(reader, SNIFF_LENGTH)
+                .await
+                .map_err(|e| AssetReaderError::Io(Arc::new(e)))?;
+            retain_sniffed(&mut loaders, peek_reader.peeked());
+            reader = Box::new(peek_reader);
         }
+
+        let loader = choose_loader(loaders)
+            .map_err(|candidates| AssetLoadError::AmbiguousAssetLoader {
+                path: asset_path.clone_owned(),
+                candidates,
+            })?
+            .ok_or_else(error)?;
+        Ok((loader, reader))
     }
 
     pub(crate) async fn load_with_meta_loader_and_reader(
@@ -1607,6 +1981,27 @@ pub fn handle_internal_asset_events(world: &mut World) {
     });
 }
 
+/// Collects the paths of the files in `folder` and its subfolders matching `pattern`.
+async fn find_matching_files(
+    folder: &Path,
+    pattern: &GlobPattern,
+    reader: &dyn ErasedAssetReader,
+    paths: &mut Vec<PathBuf>,
+) -> Result<(), AssetReaderError> {
+    if !reader.is_directory(folder).await? {
+        return Ok(());
+    }
+    let mut children = reader.read_directory(folder).await?;
+    while let Some(child) = children.next().await {
+        if reader.is_directory(&child).await? {
+            Box::pin(find_matching_files(&child, pattern, reader, paths)).await?;
+        } else if pattern.matches(&child) {
+            paths.push(child);
+        }
+    }
+    Ok(())
+}
+
 /// Internal events for asset load results
 pub(crate) enum InternalAssetEvent {
     Loaded {
@@ -1758,6 +2153,13 @@ pub enum AssetLoadError {
     MissingAssetSourceError(#[from] MissingAssetSourceError),
     #[error(transparent)]
     MissingProcessedAssetReaderError(#[from] MissingProcessedAssetReaderError),
+    #[error("Multiple asset loaders of different asset types can load '{path}', and none of them recognized its content: {}. \
+    Select one with the `loader` of the asset's .meta file, or load it with `AssetServer::load_with_loader`.",
+            candidates.join(", "))]
+    AmbiguousAssetLoader {
+        path: AssetPath<'static>,
+        candidates: Vec<&'static str>,
+    },
     #[error("Encountered an error while reading asset metadata bytes")]
     AssetMetaReadError,
     #[error("Failed to deserialize meta for asset {path}: {error}")]
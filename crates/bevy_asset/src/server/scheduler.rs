@@ -0,0 +1,277 @@
+use crate::path::AssetPath;
+use alloc::vec::Vec;
+use bevy_platform_support::collections::{HashMap, HashSet};
+use core::{future::poll_fn, task::Poll, task::Waker};
+use parking_lot::Mutex;
+
+/// The priority of an asset load, deciding which loads start first when the number of concurrent
+/// loads is limited by [`AssetPlugin::max_concurrent_loads`](crate::AssetPlugin::max_concurrent_loads).
+///
+/// Loads of the same priority start in the order they were requested. The dependencies loaded by
+/// an [`AssetLoader`](crate::AssetLoader) inherit the priority of the asset being loaded, unless
+/// they already have a higher one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoadPriority {
+    /// For assets which are not needed soon, such as the content of areas the player hasn't
+    /// reached yet.
+    Low,
+    /// The priority of assets loaded with [`AssetServer::load`](crate::AssetServer::load).
+    #[default]
+    Normal,
+    /// For assets which are needed soon, such as the content of the next level.
+    High,
+    /// For assets which are needed right away, such as the fonts and icons of the UI.
+    Critical,
+}
+
+/// Schedules the asset loads of an [`AssetServer`](crate::AssetServer), starting the loads of
+/// highest [`LoadPriority`] first once the maximum number of concurrent loads is reached.
+#[derive(Default)]
+pub(crate) struct LoadScheduler {
+    state: Mutex<SchedulerState>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    max_concurrent_loads: Option<usize>,
+    running: usize,
+    next_ticket: u64,
+    waiting: Vec<WaitingLoad>,
+    granted: HashSet<u64>,
+    /// The priorities of assets, by path without label. Missing assets have the default priority.
+    priorities: HashMap<AssetPath<'static>, LoadPriority>,
+}
+
+struct WaitingLoad {
+    ticket: u64,
+    path: AssetPath<'static>,
+    waker: Option<Waker>,
+}
+
+impl SchedulerState {
+    fn priority(&self, path: &AssetPath<'static>) -> LoadPriority {
+        self.priorities.get(path).copied().unwrap_or_default()
+    }
+
+    fn has_capacity(&self) -> bool {
+        self.max_concurrent_loads
+            .is_none_or(|max_concurrent_loads| self.running < max_concurrent_loads)
+    }
+
+    /// Starts the waiting loads of highest priority, as long as there is capacity for them.
+    fn dispatch(&mut self) {
+        while self.has_capacity() {
+            let Some(index) = (0..self.waiting.len()).max_by(|&a, &b| {
+                let (a, b) = (&self.waiting[a], &self.waiting[b]);
+                self.priority(&a.path)
+                    .cmp(&self.priority(&b.path))
+                    .then(b.ticket.cmp(&a.ticket))
+            }) else {
+                return;
+            };
+            let load = self.waiting.swap_remove(index);
+            self.running += 1;
+            self.granted.insert(load.ticket);
+            if let Some(waker) = load.waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl LoadScheduler {
+    pub(crate) fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        let mut state = self.state.lock();
+        state.max_concurrent_loads = max_concurrent_loads;
+        state.dispatch();
+    }
+
+    pub(crate) fn max_concurrent_loads(&self) -> Option<usize> {
+        self.state.lock().max_concurrent_loads
+    }
+
+    /// Returns the priority of the asset at `path`.
+    pub(crate) fn priority(&self, path: &AssetPath<'_>) -> LoadPriority {
+        self.state
+            .lock()
+            .priority(&path.without_label().into_owned())
+    }
+
+    /// Sets the priority of the asset at `path`, which applies to its pending and future loads.
+    pub(crate) fn set_priority(&self, path: &AssetPath<'_>, priority: LoadPriority) {
+        let path = path.without_label().into_owned();
+        let mut state = self.state.lock();
+        if priority == LoadPriority::default() {
+            state.priorities.remove(&path);
+        } else {
+            state.priorities.insert(path, priority);
+        }
+    }
+
+    /// Forgets the priority of the asset at `path`, once it is unloaded.
+    pub(crate) fn remove_priority(&self, path: &AssetPath<'_>) {
+        let path = path.without_label().into_owned();
+        self.state.lock().priorities.remove(&path);
+    }
+
+    /// Raises the priority of the asset at `path` to `priority`, if it is lower.
+    pub(crate) fn raise_priority(&self, path: &AssetPath<'_>, priority: LoadPriority) {
+        let path = path.without_label().into_owned();
+        let mut state = self.state.lock();
+        if state.priority(&path) < priority {
+            state.priorities.insert(path, priority);
+        }
+    }
+
+    /// Gives the dependency at `path` the priority of the asset at `dependent`, unless it was
+    /// given a higher one.
+    pub(crate) fn inherit_priority(&self, dependent: &AssetPath<'_>, path: &AssetPath<'_>) {
+        let dependent = dependent.without_label().into_owned();
+        let path = path.without_label().into_owned();
+        let mut state = self.state.lock();
+        let priority = state.priority(&dependent);
+        match state.priorities.get(&path) {
+            Some(&existing) if existing >= priority => {}
+            None if priority == LoadPriority::default() => {}
+            Some(_) if priority == LoadPriority::default() => {
+                state.priorities.remove(&path);
+            }
+            _ => {
+                state.priorities.insert(path, priority);
+            }
+        }
+    }
+
+    /// Waits until the asset at `path` can start loading, according to its priority.
+    ///
+    /// The returned [`LoadPermit`] must be held for the duration of the load.
+    pub(crate) async fn acquire(&self, path: &AssetPath<'_>) -> LoadPermit<'_> {
+        let ticket = {
+            let mut state = self.state.lock();
+            if state.waiting.is_empty() && state.has_capacity() {
+                state.running += 1;
+                return LoadPermit(self);
+            }
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            state.waiting.push(WaitingLoad {
+                ticket,
+                path: path.without_label().into_owned(),
+                waker: None,
+            });
+            ticket
+        };
+
+        let mut waiting = WaitingTicket {
+            scheduler: self,
+            ticket,
+            granted: false,
+        };
+        poll_fn(|cx| {
+            let mut state = self.state.lock();
+            if state.granted.remove(&ticket) {
+                waiting.granted = true;
+                return Poll::Ready(());
+            }
+            if let Some(load) = state.waiting.iter_mut().find(|load| load.ticket == ticket) {
+                load.waker = Some(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await;
+        LoadPermit(self)
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock();
+        state.running -= 1;
+        state.dispatch();
+    }
+}
+
+/// A running asset load, which frees its slot in the [`LoadScheduler`] when dropped.
+pub(crate) struct LoadPermit<'a>(&'a LoadScheduler);
+
+impl Drop for LoadPermit<'_> {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+/// Removes a waiting load from the [`LoadScheduler`] if its task is dropped.
+struct WaitingTicket<'a> {
+    scheduler: &'a LoadScheduler,
+    ticket: u64,
+    granted: bool,
+}
+
+impl Drop for WaitingTicket<'_> {
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+        let mut state = self.scheduler.state.lock();
+        state.waiting.retain(|load| load.ticket != self.ticket);
+        if state.granted.remove(&self.ticket) {
+            state.running -= 1;
+            state.dispatch();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{sync::Arc, task::Wake};
+    use core::{
+        future::Future,
+        pin::pin,
+        sync::atomic::{AtomicBool, Ordering},
+        task::Context,
+    };
+
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn loads_start_by_priority() {
+        let scheduler = LoadScheduler::default();
+        scheduler.set_max_concurrent_loads(Some(1));
+        scheduler.set_priority(&AssetPath::from("high.png"), LoadPriority::High);
+
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let first_path = AssetPath::from("first.png");
+        let low_path = AssetPath::from("low.png");
+        let high_path = AssetPath::from("high.png");
+        let first = bevy_tasks::block_on(scheduler.acquire(&first_path));
+        let mut low = pin!(scheduler.acquire(&low_path));
+        let mut high = pin!(scheduler.acquire(&high_path));
+        assert!(low.as_mut().poll(&mut cx).is_pending());
+        assert!(high.as_mut().poll(&mut cx).is_pending());
+
+        // The load requested last starts first, as it has a higher priority.
+        drop(first);
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert!(low.as_mut().poll(&mut cx).is_pending());
+        let Poll::Ready(high_permit) = high.as_mut().poll(&mut cx) else {
+            panic!("the high priority load should have started");
+        };
+
+        // Raising the priority of a waiting load applies right away.
+        let other_path = AssetPath::from("other.png");
+        let mut other = pin!(scheduler.acquire(&other_path));
+        assert!(other.as_mut().poll(&mut cx).is_pending());
+        scheduler.raise_priority(&other_path, LoadPriority::Critical);
+        drop(high_permit);
+        assert!(low.as_mut().poll(&mut cx).is_pending());
+        assert!(other.as_mut().poll(&mut cx).is_ready());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{Asset, AssetPath, AssetServer, Handle};
+use bevy_app::TestApp;
+
+/// The number of frames [`TestAppAssetExt::flush_asset_loads`] runs before giving up.
+const MAX_FLUSH_FRAMES: u32 = 10_000;
+
+/// Extends [`TestApp`] to wait for assets to load.
+///
+/// Assets load in tasks, so the [`TaskPoolPlugin`](bevy_app::TaskPoolPlugin) must be added.
+/// Tests usually read their assets from a
+/// [`MemoryAssetReader`](crate::io::memory::MemoryAssetReader), registered as an asset source
+/// before the [`AssetPlugin`](crate::AssetPlugin) is added, so that loading doesn't depend on the
+/// file system.
+pub trait TestAppAssetExt {
+    /// Runs frames until none of the assets of the [`AssetServer`] are loading anymore, which
+    /// includes their dependencies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the assets are still loading after 10000 frames.
+    fn flush_asset_loads(&mut self) -> &mut Self;
+
+    /// Loads the asset at `path`, and runs frames until it and its dependencies have loaded or
+    /// failed to load.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the assets are still loading after 10000 frames.
+    fn load_now<'a, A: Asset>(&mut self, path: impl Into<AssetPath<'a>>) -> Handle<A>;
+}
+
+impl TestAppAssetExt for TestApp {
+    fn flush_asset_loads(&mut self) -> &mut Self {
+        let loaded = self.step_until(MAX_FLUSH_FRAMES, |world| {
+            !world
+                .resource::<AssetServer>()
+                .report()
+                .assets
+                .iter()
+                .any(|entry| entry.load_state.is_loading())
+        });
+        if loaded.is_none() {
+            panic!("assets were still loading after {MAX_FLUSH_FRAMES} frames");
+        }
+        self
+    }
+
+    fn load_now<'a, A: Asset>(&mut self, path: impl Into<AssetPath<'a>>) -> Handle<A> {
+        self.finish();
+        let handle = self.world().resource::<AssetServer>().load(path);
+        self.flush_asset_loads();
+        handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        tests::{CoolText, CoolTextLoader},
+        AssetApp, AssetPlugin, AssetServer, Assets, TestAppAssetExt,
+    };
+    use alloc::boxed::Box;
+    use bevy_app::{App, TaskPoolPlugin, TestApp};
+    use std::path::Path;
+
+    #[test]
+    fn load_now() {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"(text: "a", dependencies: ["b.cool.ron"], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(text: "b", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+
+        let mut app = TestApp::from(App::new());
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let handle = app.load_now::<CoolText>("a.cool.ron");
+        let assets = app.resource::<Assets<CoolText>>();
+        let a = assets.get(&handle).unwrap();
+        assert_eq!(a.text, "a");
+        assert_eq!(assets.get(&a.dependencies[0]).unwrap().text, "b");
+        assert!(app
+            .resource::<AssetServer>()
+            .is_loaded_with_dependencies(&handle));
+    }
+}
@@ -0,0 +1,519 @@
+use crate::{AudioSink, AudioSinkPlayback, SpatialAudioSink};
+use alloc::{borrow::Cow, collections::VecDeque, sync::Arc};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::ops;
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::prelude::*;
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+use rodio::{Sample, Source};
+use std::sync::Mutex;
+
+/// The lowest level reported by the [`AudioMeter`]s, in decibels.
+pub const MIN_DECIBELS: f32 = -120.0;
+
+/// The number of samples collected by a sound before they are handed over to the analysis.
+const TAP_CHUNK_SIZE: usize = 256;
+
+/// Adds [`AudioAnalysis`], which measures the loudness and the spectrum of the sounds being
+/// played, in total and for each [`AudioBus`].
+///
+/// Only the sounds which start playing after this plugin is added are analyzed.
+#[derive(Default)]
+pub struct AudioAnalysisPlugin {
+    /// The initial settings of the analysis.
+    pub settings: AudioAnalysisSettings,
+}
+
+impl Plugin for AudioAnalysisPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AudioBus>()
+            .register_type::<AudioAnalysisSettings>()
+            .insert_resource(self.settings.clone())
+            .init_resource::<AudioAnalysis>()
+            .add_systems(PostUpdate, update_audio_analysis);
+    }
+}
+
+/// Groups sounds to measure them together in [`AudioAnalysis::buses`].
+///
+/// Add this component next to an [`AudioPlayer`](crate::AudioPlayer) before it starts playing.
+/// Every sound is also measured in [`AudioAnalysis::master`], whether it's on a bus or not.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Debug, PartialEq, Hash)]
+pub struct AudioBus(pub Cow<'static, str>);
+
+impl AudioBus {
+    /// Creates a bus with the given name.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+}
+
+/// Settings of the [`AudioAnalysis`].
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct AudioAnalysisSettings {
+    /// The duration over which [`AudioMeter::rms`], [`AudioMeter::peak`] and
+    /// [`AudioMeter::loudness`] are measured.
+    ///
+    /// Defaults to 400 milliseconds, the window of momentary loudness.
+    pub window: Duration,
+    /// The number of samples transformed to compute the [`AudioMeter::spectrum`], rounded up to
+    /// a power of two. Larger sizes have a finer frequency resolution but react slower.
+    ///
+    /// Defaults to 2048.
+    pub fft_size: usize,
+    /// The number of frequency bands in the [`AudioMeter::spectrum`], spaced logarithmically
+    /// between [`AudioAnalysisSettings::min_frequency`] and [`AudioAnalysisSettings::max_frequency`].
+    ///
+    /// Defaults to 32.
+    pub bands: usize,
+    /// The lowest frequency of the spectrum, in hertz.
+    ///
+    /// Defaults to 20 Hz.
+    pub min_frequency: f32,
+    /// The highest frequency of the spectrum, in hertz.
+    ///
+    /// Defaults to 20 kHz.
+    pub max_frequency: f32,
+}
+
+impl Default for AudioAnalysisSettings {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_millis(400),
+            fft_size: 2048,
+            bands: 32,
+            min_frequency: 20.0,
+            max_frequency: 20_000.0,
+        }
+    }
+}
+
+/// The loudness and spectrum of the sounds being played, updated every frame in [`PostUpdate`].
+///
+/// The sounds are measured after their volume is applied, but before spatialization and any
+/// processing of the audio device. They're downmixed to mono, and the measures of a bus add up
+/// the power of its sounds, which matches the loudness of their mix as long as they aren't
+/// correlated, such as the same sound played twice in sync.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::AudioAnalysis;
+/// fn print_music_loudness(analysis: Res<AudioAnalysis>) {
+///     if let Some(music) = analysis.bus("music") {
+///         println!("music: {:.1} LUFS", music.loudness);
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AudioAnalysis {
+    /// The measures of all the sounds.
+    pub master: AudioMeter,
+    /// The measures of the sounds of each [`AudioBus`] which has sounds playing.
+    pub buses: HashMap<AudioBus, AudioMeter>,
+}
+
+impl AudioAnalysis {
+    /// Returns the measures of the bus with the given name, if it has sounds playing.
+    pub fn bus(&self, name: &str) -> Option<&AudioMeter> {
+        self.buses.get(&AudioBus::new(Cow::Owned(name.into())))
+    }
+}
+
+/// Measures of a group of sounds, see [`AudioAnalysis`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioMeter {
+    /// The root mean square of the samples, from 0 for silence to 1 for a full-scale square wave.
+    pub rms: f32,
+    /// The largest absolute sample of a single sound.
+    pub peak: f32,
+    /// The loudness in LUFS, without the K-weighting filter nor the gating of the full
+    /// measurement, so it's only an approximation.
+    pub loudness: f32,
+    /// The power of each frequency band, in decibels relative to full scale, from the lowest
+    /// frequencies to the highest.
+    pub spectrum: Vec<f32>,
+}
+
+impl Default for AudioMeter {
+    fn default() -> Self {
+        Self {
+            rms: 0.0,
+            peak: 0.0,
+            loudness: MIN_DECIBELS,
+            spectrum: Vec::new(),
+        }
+    }
+}
+
+/// The power measured for a group of sounds, before it's converted to an [`AudioMeter`].
+#[derive(Default)]
+struct Power {
+    mean_square: f32,
+    peak: f32,
+    bands: Vec<f32>,
+}
+
+impl Power {
+    fn add(&mut self, other: &Power) {
+        self.mean_square += other.mean_square;
+        self.peak = self.peak.max(other.peak);
+        if self.bands.len() < other.bands.len() {
+            self.bands.resize(other.bands.len(), 0.0);
+        }
+        for (band, power) in self.bands.iter_mut().zip(&other.bands) {
+            *band += power;
+        }
+    }
+
+    fn to_meter(&self) -> AudioMeter {
+        AudioMeter {
+            rms: ops::sqrt(self.mean_square),
+            peak: self.peak,
+            loudness: (-0.691 + to_decibels(self.mean_square)).max(MIN_DECIBELS),
+            spectrum: self.bands.iter().map(|&power| to_decibels(power)).collect(),
+        }
+    }
+}
+
+fn to_decibels(power: f32) -> f32 {
+    if power <= 0.0 {
+        return MIN_DECIBELS;
+    }
+    (10.0 * ops::log10(power)).max(MIN_DECIBELS)
+}
+
+/// The latest samples of a sound, downmixed to mono, shared with the audio thread.
+pub(crate) struct TapBuffer {
+    samples: Mutex<VecDeque<f32>>,
+    sample_rate: AtomicU32,
+    window: Duration,
+    fft_size: usize,
+}
+
+impl TapBuffer {
+    /// The number of samples to keep at `sample_rate`.
+    fn capacity(&self, sample_rate: u32) -> usize {
+        let window = (self.window.as_secs_f64() * sample_rate as f64).ceil() as usize;
+        window.max(self.fft_size)
+    }
+}
+
+/// Collects the samples of a sound for the [`AudioAnalysis`].
+#[derive(Component, Clone)]
+pub(crate) struct AnalysisTap(Arc<TapBuffer>);
+
+impl AnalysisTap {
+    /// Creates a tap which keeps enough samples for the `settings`.
+    pub(crate) fn new(settings: &AudioAnalysisSettings) -> Self {
+        Self(Arc::new(TapBuffer {
+            samples: Mutex::new(VecDeque::new()),
+            sample_rate: AtomicU32::new(0),
+            window: settings.window,
+            fft_size: settings.fft_size.next_power_of_two(),
+        }))
+    }
+
+    /// Wraps `source` so that its samples are copied to this tap as it plays.
+    pub(crate) fn wrap<S>(&self, source: S) -> Tapped<S>
+    where
+        S: Source,
+        S::Item: Sample,
+    {
+        Tapped {
+            inner: source,
+            tap: self.0.clone(),
+            pending: Vec::with_capacity(TAP_CHUNK_SIZE),
+            frame_sum: 0.0,
+            frame_channel: 0,
+        }
+    }
+
+    /// Measures the latest samples of the sound, played at `gain`.
+    fn measure(&self, settings: &AudioAnalysisSettings, gain: f32) -> Power {
+        let sample_rate = self.0.sample_rate.load(Ordering::Relaxed);
+        let samples = self.0.samples.lock().unwrap();
+        if sample_rate == 0 || samples.is_empty() || gain <= 0.0 {
+            return Power::default();
+        }
+
+        let window =
+            ((settings.window.as_secs_f64() * sample_rate as f64) as usize).clamp(1, samples.len());
+        let (mean_square, peak) = samples
+            .iter()
+            .skip(samples.len() - window)
+            .fold((0.0, 0.0f32), |(sum, peak), &sample| {
+                (sum + sample * sample, peak.max(sample.abs()))
+            });
+
+        let fft_size = settings.fft_size.next_power_of_two();
+        let start = samples.len().saturating_sub(fft_size);
+        let latest: Vec<f32> = samples.iter().skip(start).copied().collect();
+        drop(samples);
+        let mut bands = spectrum(&latest, fft_size, sample_rate, settings);
+        for band in &mut bands {
+            *band *= gain * gain;
+        }
+
+        Power {
+            mean_square: mean_square / window as f32 * gain * gain,
+            peak: peak * gain,
+            bands,
+        }
+    }
+}
+
+/// Computes the power of each band of the `settings` in the latest `samples` of a sound.
+///
+/// The samples are zero-padded at the start if there are fewer than `fft_size`.
+fn spectrum(
+    samples: &[f32],
+    fft_size: usize,
+    sample_rate: u32,
+    settings: &AudioAnalysisSettings,
+) -> Vec<f32> {
+    let mut bands = vec![0.0; settings.bands];
+    if settings.bands == 0 || fft_size < 2 {
+        return bands;
+    }
+
+    // Apply a Hann window, normalized so that a full-scale sine has a power of 0.5 like its mean square.
+    let offset = fft_size - samples.len();
+    let mut re = vec![0.0; fft_size];
+    let mut im = vec![0.0; fft_size];
+    for (i, &sample) in samples.iter().enumerate() {
+        let n = i + offset;
+        re[n] = sample * 0.5 * (1.0 - ops::cos(TAU * n as f32 / fft_size as f32));
+    }
+    fft(&mut re, &mut im);
+
+    let min = settings.min_frequency.max(f32::EPSILON);
+    let ratio = settings.max_frequency.max(min) / min;
+    let scale = 2.0 / (0.375 * fft_size as f32 * fft_size as f32);
+    for bin in 1..fft_size / 2 {
+        let frequency = bin as f32 * sample_rate as f32 / fft_size as f32;
+        let position = ops::ln(frequency / min) / ops::ln(ratio);
+        if !(0.0..1.0).contains(&position) {
+            continue;
+        }
+        let band = (position * settings.bands as f32) as usize;
+        bands[band] += (re[bin] * re[bin] + im[bin] * im[bin]) * scale;
+    }
+    bands
+}
+
+/// An in-place radix-2 fast Fourier transform. The length of the slices must be a power of two.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let (sin, cos) = ops::sin_cos(-TAU / len as f32);
+        for start in (0..n).step_by(len) {
+            let (mut w_re, mut w_im) = (1.0, 0.0);
+            for k in 0..len / 2 {
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+                (w_re, w_im) = (w_re * cos - w_im * sin, w_re * sin + w_im * cos);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// A [`Source`] that copies its samples, downmixed to mono, to an [`AnalysisTap`].
+///
+/// The samples are handed over in chunks, and dropped rather than blocking the audio thread if
+/// the analysis is reading them at the same time.
+pub(crate) struct Tapped<S> {
+    inner: S,
+    tap: Arc<TapBuffer>,
+    pending: Vec<f32>,
+    frame_sum: f32,
+    frame_channel: u16,
+}
+
+impl<S> Tapped<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn flush(&mut self) {
+        let sample_rate = self.inner.sample_rate();
+        let capacity = self.tap.capacity(sample_rate);
+        let Ok(mut samples) = self.tap.samples.try_lock() else {
+            if self.pending.len() >= capacity {
+                self.pending.clear();
+            }
+            return;
+        };
+        let overflow = (samples.len() + self.pending.len()).saturating_sub(capacity);
+        let n = overflow.min(samples.len());
+        samples.drain(..n);
+        samples.extend(self.pending.drain(..));
+        self.tap.sample_rate.store(sample_rate, Ordering::Relaxed);
+    }
+}
+
+impl<S> Iterator for Tapped<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.inner.next()?;
+        let channels = self.inner.channels().max(1);
+        self.frame_sum += sample.to_f32();
+        self.frame_channel += 1;
+        if self.frame_channel >= channels {
+            self.pending.push(self.frame_sum / channels as f32);
+            self.frame_sum = 0.0;
+            self.frame_channel = 0;
+            if self.pending.len() >= TAP_CHUNK_SIZE {
+                self.flush();
+            }
+        }
+        Some(sample)
+    }
+}
+
+impl<S> Source for Tapped<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+/// Returns the gain at which a sink is heard, or zero if it isn't playing.
+fn sink_gain(sink: &impl AudioSinkPlayback) -> f32 {
+    if sink.is_paused() || sink.is_muted() || sink.empty() {
+        0.0
+    } else {
+        sink.volume()
+    }
+}
+
+/// Updates the [`AudioAnalysis`] from the latest samples of the sounds being played.
+fn update_audio_analysis(
+    settings: Res<AudioAnalysisSettings>,
+    mut analysis: ResMut<AudioAnalysis>,
+    taps: Query<(
+        &AnalysisTap,
+        Option<&AudioBus>,
+        Option<&AudioSink>,
+        Option<&SpatialAudioSink>,
+    )>,
+) {
+    let mut master = Power::default();
+    let mut buses: HashMap<AudioBus, Power> = HashMap::default();
+    for (tap, bus, sink, spatial_sink) in &taps {
+        let gain = match (sink, spatial_sink) {
+            (Some(sink), _) => sink_gain(sink),
+            (None, Some(sink)) => sink_gain(sink),
+            (None, None) => continue,
+        };
+        let power = tap.measure(&settings, gain);
+        master.add(&power);
+        if let Some(bus) = bus {
+            buses.entry(bus.clone()).or_default().add(&power);
+        }
+    }
+
+    let analysis = analysis.as_mut();
+    analysis.master = master.to_meter();
+    analysis
+        .master
+        .spectrum
+        .resize(settings.bands, MIN_DECIBELS);
+    analysis.buses = buses
+        .into_iter()
+        .map(|(bus, power)| {
+            let mut meter = power.to_meter();
+            meter.spectrum.resize(settings.bands, MIN_DECIBELS);
+            (bus, meter)
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn sine_is_measured_in_its_band() {
+        let settings = AudioAnalysisSettings {
+            window: Duration::from_millis(100),
+            fft_size: 1024,
+            bands: 10,
+            min_frequency: 10.0,
+            max_frequency: 10_000.0,
+        };
+        let sample_rate = 8_000;
+        // A full-scale 1 kHz sine, in stereo.
+        let samples: Vec<f32> = (0..sample_rate)
+            .flat_map(|i| {
+                let sample = ops::sin(TAU * 1_000.0 * i as f32 / sample_rate as f32);
+                [sample, sample]
+            })
+            .collect();
+        let tap = AnalysisTap::new(&settings);
+        let played: Vec<f32> = tap
+            .wrap(SamplesBuffer::new(2, sample_rate, samples.clone()))
+            .collect();
+        assert_eq!(played, samples);
+
+        let power = tap.measure(&settings, 0.5);
+        let meter = power.to_meter();
+        assert!((meter.rms - 0.5 / 2.0f32.sqrt()).abs() < 1e-3);
+        assert!((meter.peak - 0.5).abs() < 1e-3);
+        // 1 kHz is in the 7th of 10 bands spanning 3 decades.
+        let loudest = (0..10)
+            .max_by(|&a, &b| meter.spectrum[a].total_cmp(&meter.spectrum[b]))
+            .unwrap();
+        assert_eq!(loudest, 6);
+        // The band holds about all the power of the sine.
+        assert!((meter.spectrum[6] - to_decibels(0.125)).abs() < 0.5);
+    }
+}
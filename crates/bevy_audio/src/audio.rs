@@ -1,4 +1,4 @@
-use crate::{AudioSource, Decodable, Volume};
+use crate::{AudioSource, AudioTimestamp, Decodable, Volume};
 use bevy_asset::{Asset, Handle};
 use bevy_ecs::prelude::*;
 use bevy_math::Vec3;
@@ -56,6 +56,11 @@ pub struct PlaybackSettings {
     /// Optional scale factor applied to the positions of this audio source and the listener,
     /// overriding the default value configured on [`AudioPlugin::default_spatial_scale`](crate::AudioPlugin::default_spatial_scale).
     pub spatial_scale: Option<SpatialScale>,
+    /// Optional position on the [`AudioClock`](crate::AudioClock) timeline at which to start playing.
+    ///
+    /// The sound plays silence until then, so it starts on the exact frame regardless of when
+    /// the entity was spawned. If the timestamp has already passed, the sound starts right away.
+    pub start_at: Option<AudioTimestamp>,
 }
 
 impl Default for PlaybackSettings {
@@ -80,6 +85,7 @@ impl PlaybackSettings {
         muted: false,
         spatial: false,
         spatial_scale: None,
+        start_at: None,
     };
 
     /// Will play the associated audio source in a loop.
@@ -135,6 +141,12 @@ impl PlaybackSettings {
         self.spatial_scale = Some(spatial_scale);
         self
     }
+
+    /// Helper to start playing at a position on the [`AudioClock`](crate::AudioClock) timeline.
+    pub const fn start_at(mut self, timestamp: AudioTimestamp) -> Self {
+        self.start_at = Some(timestamp);
+        self
+    }
 }
 
 /// Settings for the listener for spatial audio sources.
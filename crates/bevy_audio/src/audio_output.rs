@@ -1,12 +1,18 @@
 use crate::{
-    AudioPlayer, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode, PlaybackSettings,
-    SpatialAudioSink, SpatialListener,
+    AnalysisTap, AudioAnalysisSettings, AudioClock, AudioPlayer, Decodable, DefaultSpatialScale,
+    GlobalVolume, PlaybackMode, PlaybackSettings, SpatialAudioSink, SpatialListener,
 };
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_math::Vec3;
 use bevy_transform::prelude::GlobalTransform;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
+use rodio::{
+    cpal::{
+        traits::{DeviceTrait, HostTrait},
+        FromSample,
+    },
+    OutputStream, OutputStreamHandle, Sample, Sink, Source, SpatialSink,
+};
 use tracing::warn;
 
 use crate::{AudioSink, AudioSinkPlayback};
@@ -26,6 +32,7 @@ use crate::{AudioSink, AudioSinkPlayback};
 #[derive(Resource)]
 pub(crate) struct AudioOutput {
     stream_handle: Option<OutputStreamHandle>,
+    pub(crate) clock: AudioClock,
 }
 
 impl Default for AudioOutput {
@@ -33,18 +40,111 @@ impl Default for AudioOutput {
         if let Ok((stream, stream_handle)) = OutputStream::try_default() {
             // We leak `OutputStream` to prevent the audio from stopping.
             core::mem::forget(stream);
+
+            // `OutputStream::try_default` uses the default config of the default device,
+            // so the clock source is mixed without any conversion.
+            let clock = rodio::cpal::default_host()
+                .default_output_device()
+                .and_then(|device| device.default_output_config().ok())
+                .map(|config| AudioClock::new(config.sample_rate().0, config.channels()))
+                .unwrap_or_default();
+            if let Err(err) = stream_handle.play_raw(clock.source()) {
+                warn!("Error starting the audio clock: {err:?}");
+            }
+
             Self {
                 stream_handle: Some(stream_handle),
+                clock,
             }
         } else {
             warn!("No audio device found.");
             Self {
                 stream_handle: None,
+                clock: AudioClock::default(),
             }
         }
     }
 }
 
+/// Abstracts over [`Sink`] and [`SpatialSink`] to queue audio sources.
+trait AppendSource {
+    fn append_source<S>(&self, source: S)
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+        S::Item: Sample + Send;
+}
+
+impl AppendSource for Sink {
+    fn append_source<S>(&self, source: S)
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+        S::Item: Sample + Send,
+    {
+        self.append(source);
+    }
+}
+
+impl AppendSource for SpatialSink {
+    fn append_source<S>(&self, source: S)
+    where
+        S: Source + Send + 'static,
+        f32: FromSample<S::Item>,
+        S::Item: Sample + Send,
+    {
+        self.append(source);
+    }
+}
+
+/// Appends the decoder of `audio_source` to `sink`, looping and scheduling it according to `settings`.
+///
+/// The samples are copied to `tap` as they play, if any.
+fn append_decoder<T: Decodable>(
+    sink: &impl AppendSource,
+    audio_source: &T,
+    settings: &PlaybackSettings,
+    clock: &AudioClock,
+    tap: Option<&AnalysisTap>,
+) where
+    f32: FromSample<T::DecoderItem>,
+{
+    match settings.mode {
+        PlaybackMode::Loop => match audio_source.looping_decoder() {
+            Some(decoder) => append_scheduled(sink, decoder, settings, clock, tap),
+            None => append_scheduled(
+                sink,
+                audio_source.decoder().repeat_infinite(),
+                settings,
+                clock,
+                tap,
+            ),
+        },
+        PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
+            append_scheduled(sink, audio_source.decoder(), settings, clock, tap);
+        }
+    }
+}
+
+fn append_scheduled<S>(
+    sink: &impl AppendSource,
+    source: S,
+    settings: &PlaybackSettings,
+    clock: &AudioClock,
+    tap: Option<&AnalysisTap>,
+) where
+    S: Source + Send + 'static,
+    f32: FromSample<S::Item>,
+    S::Item: Sample + Send,
+{
+    match (settings.start_at, tap) {
+        (Some(start), Some(tap)) => sink.append_source(tap.wrap(clock.schedule(source, start))),
+        (Some(start), None) => sink.append_source(clock.schedule(source, start)),
+        (None, Some(tap)) => sink.append_source(tap.wrap(source)),
+        (None, None) => sink.append_source(source),
+    }
+}
+
 /// Marker for internal use, to despawn entities when playback finishes.
 #[derive(Component, Default)]
 pub struct PlaybackDespawnMarker;
@@ -108,6 +208,7 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
     >,
     ear_positions: EarPositions,
     default_spatial_scale: Res<DefaultSpatialScale>,
+    analysis_settings: Option<Res<AudioAnalysisSettings>>,
     mut commands: Commands,
 ) where
     f32: rodio::cpal::FromSample<Source::DecoderItem>,
@@ -121,6 +222,10 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
         let Some(audio_source) = audio_sources.get(&source_handle.0) else {
             continue;
         };
+        let tap = analysis_settings.as_deref().map(AnalysisTap::new);
+        if let Some(tap) = &tap {
+            commands.entity(entity).insert(tap.clone());
+        }
         // audio data is available (has loaded), begin playback and insert sink component
         if settings.spatial {
             let (left_ear, right_ear) = ear_positions.get();
@@ -156,12 +261,13 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 }
             };
 
-            match settings.mode {
-                PlaybackMode::Loop => sink.append(audio_source.decoder().repeat_infinite()),
-                PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
-                }
-            };
+            append_decoder(
+                &sink,
+                audio_source,
+                settings,
+                &audio_output.clock,
+                tap.as_ref(),
+            );
 
             let mut sink = SpatialAudioSink::new(sink);
 
@@ -196,12 +302,13 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
                 }
             };
 
-            match settings.mode {
-                PlaybackMode::Loop => sink.append(audio_source.decoder().repeat_infinite()),
-                PlaybackMode::Once | PlaybackMode::Despawn | PlaybackMode::Remove => {
-                    sink.append(audio_source.decoder());
-                }
-            };
+            append_decoder(
+                &sink,
+                audio_source,
+                settings,
+                &audio_output.clock,
+                tap.as_ref(),
+            );
 
             let mut sink = AudioSink::new(sink);
 
@@ -267,6 +374,7 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
                 AudioSink,
                 PlaybackSettings,
                 PlaybackRemoveMarker,
+                AnalysisTap,
             )>();
         }
     }
@@ -277,6 +385,7 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
                 SpatialAudioSink,
                 PlaybackSettings,
                 PlaybackRemoveMarker,
+                AnalysisTap,
             )>();
         }
     }
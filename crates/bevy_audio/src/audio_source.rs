@@ -92,6 +92,14 @@ pub trait Decodable: Send + Sync + 'static {
 
     /// Build and return a [`Self::Decoder`] of the implementing type
     fn decoder(&self) -> Self::Decoder;
+
+    /// Build and return a [`Self::Decoder`] that repeats forever, used for [`PlaybackMode::Loop`](crate::PlaybackMode::Loop).
+    ///
+    /// If this returns `None`, which is the default, the [`Self::Decoder`] returned by [`Decodable::decoder`]
+    /// is repeated instead, which keeps all of its decoded samples in memory.
+    fn looping_decoder(&self) -> Option<Self::Decoder> {
+        None
+    }
 }
 
 impl Decodable for AudioSource {
@@ -0,0 +1,315 @@
+use alloc::sync::Arc;
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use rodio::{Sample, Source};
+
+/// A position on the timeline of the audio output, in frames since the output started.
+///
+/// A frame holds one sample for each output channel, so the timeline advances by
+/// [`AudioClock::sample_rate`] frames per second. Use [`AudioClock::now`] to get the current position,
+/// and [`PlaybackSettings::start_at`](crate::PlaybackSettings::start_at) to schedule a sound.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub struct AudioTimestamp(pub u64);
+
+impl AudioTimestamp {
+    /// The start of the audio output timeline.
+    pub const ZERO: Self = Self(0);
+
+    /// Returns the timestamp `duration` after the start of the timeline, at `sample_rate`.
+    pub fn from_duration(duration: Duration, sample_rate: u32) -> Self {
+        Self(duration_to_frames(duration, sample_rate))
+    }
+
+    /// Returns the time between the start of the timeline and this timestamp, at `sample_rate`.
+    pub fn as_duration(self, sample_rate: u32) -> Duration {
+        Duration::from_secs_f64(self.0 as f64 / sample_rate as f64)
+    }
+
+    /// Returns the first timestamp at or after `self` on a grid of `interval` frames starting at `origin`.
+    ///
+    /// Timestamps before `origin` are quantized to `origin`. An `interval` of zero returns `self` unchanged.
+    pub fn quantize(self, interval: u64, origin: AudioTimestamp) -> Self {
+        if interval == 0 {
+            return self;
+        }
+        let Some(elapsed) = self.0.checked_sub(origin.0) else {
+            return origin;
+        };
+        Self(
+            elapsed
+                .div_ceil(interval)
+                .saturating_mul(interval)
+                .saturating_add(origin.0),
+        )
+    }
+
+    /// Returns the timestamp `frames` after this one.
+    pub const fn add_frames(self, frames: u64) -> Self {
+        Self(self.0.saturating_add(frames))
+    }
+}
+
+/// The clock of the audio output, counting the frames mixed since the output started.
+///
+/// Unlike [`Time`](https://docs.rs/bevy/latest/bevy/time/struct.Time.html), this clock advances
+/// with the audio hardware rather than with the frame rate, which makes it suitable to schedule sounds
+/// that need to line up exactly, such as in rhythm games.
+///
+/// The clock counts frames as they are mixed, which happens slightly ahead of them being heard,
+/// due to the output buffer of the audio device. If no audio device is available, the clock never advances.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::AssetServer;
+/// # use bevy_audio::{AudioClock, AudioPlayer, PlaybackSettings};
+/// fn play_on_next_beat(clock: Res<AudioClock>, asset_server: Res<AssetServer>, mut commands: Commands) {
+///     let start = clock.next_beat(120.0, clock.origin());
+///     commands.spawn((
+///         AudioPlayer::new(asset_server.load("kick.ogg")),
+///         PlaybackSettings::ONCE.start_at(start),
+///     ));
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct AudioClock {
+    /// The number of samples mixed, across all channels.
+    samples: Arc<AtomicU64>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl Default for AudioClock {
+    fn default() -> Self {
+        Self::new(44_100, 2)
+    }
+}
+
+impl AudioClock {
+    pub(crate) fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            samples: Arc::new(AtomicU64::new(0)),
+            sample_rate,
+            channels: channels.max(1),
+        }
+    }
+
+    /// The current position of the audio output.
+    pub fn now(&self) -> AudioTimestamp {
+        AudioTimestamp(self.samples.load(Ordering::Acquire) / self.channels as u64)
+    }
+
+    /// The start of the audio output timeline, usable as the origin of a quantization grid.
+    pub const fn origin(&self) -> AudioTimestamp {
+        AudioTimestamp::ZERO
+    }
+
+    /// The number of frames per second of the audio output.
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The number of frames in `duration`.
+    pub fn frames(&self, duration: Duration) -> u64 {
+        duration_to_frames(duration, self.sample_rate)
+    }
+
+    /// Returns the timestamp `duration` from now.
+    pub fn after(&self, duration: Duration) -> AudioTimestamp {
+        self.now().add_frames(self.frames(duration))
+    }
+
+    /// Returns the first timestamp from now that is a multiple of `interval` after `origin`.
+    pub fn quantize(&self, interval: Duration, origin: AudioTimestamp) -> AudioTimestamp {
+        self.now().quantize(self.frames(interval), origin)
+    }
+
+    /// Returns the timestamp of the next beat at `bpm` beats per minute, for a song whose first beat is at `origin`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bpm` isn't a positive, finite number.
+    pub fn next_beat(&self, bpm: f64, origin: AudioTimestamp) -> AudioTimestamp {
+        assert!(
+            bpm > 0.0 && bpm.is_finite(),
+            "the tempo must be positive and finite, got {bpm} bpm"
+        );
+        let frames_per_beat = (self.sample_rate as f64 * 60.0 / bpm).round() as u64;
+        self.now().quantize(frames_per_beat, origin)
+    }
+
+    /// Returns a silent [`Source`] that advances this clock as it is mixed.
+    pub(crate) fn source(&self) -> ClockSource {
+        ClockSource {
+            samples: Arc::clone(&self.samples),
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+        }
+    }
+
+    /// Delays `source` so that it starts playing at `start`.
+    pub(crate) fn schedule<S>(&self, source: S, start: AudioTimestamp) -> Scheduled<S>
+    where
+        S: Source,
+        S::Item: Sample,
+    {
+        Scheduled {
+            inner: source,
+            clock: self.clone(),
+            start,
+            silence: None,
+        }
+    }
+}
+
+fn duration_to_frames(duration: Duration, sample_rate: u32) -> u64 {
+    (duration.as_secs_f64() * sample_rate as f64).round() as u64
+}
+
+/// A silent, infinite [`Source`] in the format of the output stream, which counts the samples mixed.
+pub(crate) struct ClockSource {
+    samples: Arc<AtomicU64>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl Iterator for ClockSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.samples.fetch_add(1, Ordering::Release);
+        Some(0.0)
+    }
+}
+
+impl Source for ClockSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A [`Source`] that plays silence until the [`AudioClock`] reaches its start timestamp.
+///
+/// The delay is computed the first time the source is mixed, so it doesn't depend on when the
+/// sound was queued during the frame.
+pub(crate) struct Scheduled<S> {
+    inner: S,
+    clock: AudioClock,
+    start: AudioTimestamp,
+    /// The number of silent samples left to play, computed on the first sample.
+    silence: Option<u64>,
+}
+
+impl<S> Iterator for Scheduled<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let silence = self.silence.get_or_insert_with(|| {
+            let frames = self.start.0.saturating_sub(self.clock.now().0);
+            // Convert from output frames to frames of the inner source.
+            let frames = (frames as f64 * self.inner.sample_rate() as f64
+                / self.clock.sample_rate as f64)
+                .round() as u64;
+            frames * self.inner.channels() as u64
+        });
+        if *silence > 0 {
+            *silence -= 1;
+            return Some(S::Item::zero_value());
+        }
+        self.inner.next()
+    }
+}
+
+impl<S> Source for Scheduled<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        match self.silence {
+            Some(silence) if silence > 0 => Some(silence as usize),
+            _ => self.inner.current_frame_len(),
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn quantize_rounds_up_to_grid() {
+        let origin = AudioTimestamp(100);
+        assert_eq!(
+            AudioTimestamp(100).quantize(48, origin),
+            AudioTimestamp(100)
+        );
+        assert_eq!(
+            AudioTimestamp(101).quantize(48, origin),
+            AudioTimestamp(148)
+        );
+        assert_eq!(
+            AudioTimestamp(148).quantize(48, origin),
+            AudioTimestamp(148)
+        );
+        assert_eq!(AudioTimestamp(10).quantize(48, origin), origin);
+        assert_eq!(AudioTimestamp(7).quantize(0, origin), AudioTimestamp(7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn next_beat_at_zero_bpm() {
+        AudioClock::new(48_000, 2).next_beat(0.0, AudioTimestamp::ZERO);
+    }
+
+    #[test]
+    fn scheduled_source_waits_for_clock() {
+        let clock = AudioClock::new(4, 1);
+        let mut clock_source = clock.source();
+        // Advance the clock by 2 frames.
+        clock_source.next();
+        clock_source.next();
+
+        let source = SamplesBuffer::new(1, 4, vec![1.0f32, 1.0]);
+        let samples: Vec<f32> = clock.schedule(source, AudioTimestamp(5)).collect();
+        assert_eq!(samples, vec![0.0, 0.0, 0.0, 1.0, 1.0]);
+
+        // Sounds scheduled in the past start right away.
+        let source = SamplesBuffer::new(1, 4, vec![1.0f32]);
+        let samples: Vec<f32> = clock.schedule(source, AudioTimestamp(1)).collect();
+        assert_eq!(samples, vec![1.0]);
+    }
+}
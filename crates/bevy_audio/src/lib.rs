@@ -29,11 +29,15 @@
 
 extern crate alloc;
 
+mod analysis;
 mod audio;
 mod audio_output;
 mod audio_source;
+mod clock;
 mod pitch;
 mod sinks;
+#[cfg(not(target_arch = "wasm32"))]
+mod streaming;
 mod volume;
 
 /// The audio prelude.
@@ -42,14 +46,18 @@ mod volume;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, Decodable, GlobalVolume, Pitch,
-        PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioClock, AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, AudioTimestamp,
+        Decodable, GlobalVolume, Pitch, PlaybackSettings, SpatialAudioSink, SpatialListener,
     };
 }
 
+pub use analysis::*;
 pub use audio::*;
 pub use audio_source::*;
+pub use clock::*;
 pub use pitch::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use streaming::*;
 pub use volume::*;
 
 pub use rodio::{cpal::Sample as CpalSample, source::Source, Sample};
@@ -86,6 +94,7 @@ impl Plugin for AudioPlugin {
             .register_type::<DefaultSpatialScale>()
             .register_type::<PlaybackMode>()
             .register_type::<PlaybackSettings>()
+            .register_type::<AudioTimestamp>()
             .insert_resource(self.global_volume)
             .insert_resource(DefaultSpatialScale(self.default_spatial_scale))
             .configure_sets(
@@ -100,10 +109,19 @@ impl Plugin for AudioPlugin {
             )
             .init_resource::<AudioOutput>();
 
+        let clock = app.world().resource::<AudioOutput>().clock.clone();
+        app.insert_resource(clock);
+
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         {
             app.add_audio_source::<AudioSource>();
             app.init_asset_loader::<AudioLoader>();
+
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                app.add_audio_source::<StreamingAudioSource>();
+                app.init_asset_loader::<StreamingAudioLoader>();
+            }
         }
 
         app.add_audio_source::<Pitch>();
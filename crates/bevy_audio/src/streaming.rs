@@ -0,0 +1,686 @@
+use crate::Decodable;
+use alloc::{collections::VecDeque, sync::Arc};
+use bevy_asset::{
+    io::{AsyncSeekForwardExt, Reader},
+    Asset, AssetLoader, AssetPath, AssetServer, AssetServerMode, AsyncReadExt, LoadContext,
+};
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypePath;
+use bevy_tasks::IoTaskPool;
+use core::{
+    future::poll_fn,
+    task::{Poll, Waker},
+    time::Duration,
+};
+use rodio::{source::SeekError, Source};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    sync::{Condvar, Mutex},
+};
+
+/// The default amount of encoded audio data a [`StreamingAudioSource`] reads ahead of playback, in bytes.
+pub const DEFAULT_READ_AHEAD: usize = 256 * 1024;
+
+/// The size of the chunks read from the [`AssetReader`](bevy_asset::io::AssetReader) at once.
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// The amount of encoded data buffered before the decoder is created, so that reading the header of
+/// the file doesn't wait for the storage.
+const HEADER_READ_AHEAD: usize = 64 * 1024;
+
+/// A source of audio data that is decoded while it plays, instead of being loaded into memory up front.
+///
+/// Only a small ring buffer of encoded data (see [`StreamingAudioSettings::read_ahead`]) is kept in memory,
+/// which makes this a good fit for long music tracks. Each playback reads the file again from its
+/// asset source in a task of the [`IoTaskPool`], and stays silent until the header of the file is
+/// buffered and decoded.
+///
+/// [`StreamingAudioLoader`] doesn't claim any file extension, so that audio files are loaded as
+/// [`AudioSource`](crate::AudioSource) by default. Streaming audio sources are loaded by requesting
+/// this asset type explicitly:
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::AssetServer;
+/// # use bevy_audio::{AudioPlayer, PlaybackSettings, StreamingAudioSource};
+/// fn play_music(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     commands.spawn((
+///         AudioPlayer::<StreamingAudioSource>(asset_server.load("music.ogg")),
+///         PlaybackSettings::LOOP,
+///     ));
+/// }
+/// ```
+///
+/// When played with [`PlaybackMode::Loop`](crate::PlaybackMode::Loop), the source seeks back to
+/// [`StreamingAudioSettings::loop_start`] instead of keeping the decoded audio around.
+#[derive(Asset, TypePath, Clone)]
+pub struct StreamingAudioSource {
+    asset_server: AssetServer,
+    path: AssetPath<'static>,
+    settings: StreamingAudioSettings,
+}
+
+impl StreamingAudioSource {
+    /// The path of the streamed audio file.
+    pub fn path(&self) -> &AssetPath<'static> {
+        &self.path
+    }
+
+    /// The settings this source was loaded with.
+    pub fn settings(&self) -> &StreamingAudioSettings {
+        &self.settings
+    }
+
+    fn streaming_decoder(&self, looping: bool) -> io::Result<StreamingDecoder> {
+        let reader = StreamingReader::new(
+            self.asset_server.clone(),
+            self.path.clone(),
+            self.settings.read_ahead,
+        )?;
+        Ok(StreamingDecoder {
+            path: self.path.clone(),
+            pending: Some(reader),
+            pending_seek: None,
+            inner: None,
+            looping,
+            loop_start: self.settings.loop_start,
+            loop_end: self.settings.loop_end,
+            loop_end_position: None,
+            position: 0,
+        })
+    }
+
+    /// Returns a decoder of this source, or a silent one if the file can't be read.
+    fn decoder_or_silence(&self, looping: bool) -> StreamingDecoder {
+        self.streaming_decoder(looping).unwrap_or_else(|err| {
+            tracing::error!("Failed to stream audio source {}: {err}", self.path);
+            StreamingDecoder {
+                path: self.path.clone(),
+                pending: None,
+                pending_seek: None,
+                inner: None,
+                looping: false,
+                loop_start: Duration::ZERO,
+                loop_end: None,
+                loop_end_position: None,
+                position: 0,
+            }
+        })
+    }
+}
+
+/// Settings for loading a [`StreamingAudioSource`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StreamingAudioSettings {
+    /// How many bytes of encoded audio are read ahead of playback.
+    ///
+    /// Larger values are more tolerant of slow storage, at the cost of memory.
+    /// Defaults to [`DEFAULT_READ_AHEAD`].
+    pub read_ahead: usize,
+    /// The position playback returns to when the source loops.
+    ///
+    /// This allows a track to have an intro that is only played once.
+    pub loop_start: Duration,
+    /// The position at which the source loops back to [`loop_start`](Self::loop_start).
+    ///
+    /// If `None`, the whole track is played before looping.
+    pub loop_end: Option<Duration>,
+}
+
+impl Default for StreamingAudioSettings {
+    fn default() -> Self {
+        Self {
+            read_ahead: DEFAULT_READ_AHEAD,
+            loop_start: Duration::ZERO,
+            loop_end: None,
+        }
+    }
+}
+
+/// Loads files as [`StreamingAudioSource`] [`Assets`](bevy_asset::Assets).
+///
+/// This supports the same formats as [`AudioLoader`](crate::AudioLoader), but doesn't register any
+/// extension: it's only used for loads of the [`StreamingAudioSource`] type, or when selected with
+/// [`AssetServer::load_with_loader`] or in a `.meta` file.
+pub struct StreamingAudioLoader {
+    asset_server: AssetServer,
+}
+
+impl FromWorld for StreamingAudioLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            asset_server: world.resource::<AssetServer>().clone(),
+        }
+    }
+}
+
+impl AssetLoader for StreamingAudioLoader {
+    type Asset = StreamingAudioSource;
+    type Settings = StreamingAudioSettings;
+    type Error = io::Error;
+
+    async fn load(
+        &self,
+        _reader: &mut dyn Reader,
+        settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<StreamingAudioSource, Self::Error> {
+        // The data is read when the source is played, not when it is loaded.
+        Ok(StreamingAudioSource {
+            asset_server: self.asset_server.clone(),
+            path: load_context.asset_path().clone(),
+            settings: settings.clone(),
+        })
+    }
+}
+
+impl Decodable for StreamingAudioSource {
+    type DecoderItem = <StreamingDecoder as Iterator>::Item;
+    type Decoder = StreamingDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        self.decoder_or_silence(false)
+    }
+
+    fn looping_decoder(&self) -> Option<Self::Decoder> {
+        Some(self.decoder_or_silence(true))
+    }
+}
+
+/// Converts `duration` into a number of interleaved samples of `source`.
+fn duration_to_samples<S: Source>(duration: Duration, source: &S) -> u64
+where
+    S::Item: rodio::Sample,
+{
+    let frames = (duration.as_secs_f64() * source.sample_rate() as f64).round() as u64;
+    frames * source.channels() as u64
+}
+
+/// The [`Decodable::Decoder`] of a [`StreamingAudioSource`].
+///
+/// The decoder is created by the audio output once enough of the file is buffered, and this is silent
+/// until then, so that playing a source never waits for the storage. When looping, it seeks back to the
+/// loop start once it reaches the loop end or the end of the stream. If the file couldn't be decoded,
+/// this is silent.
+pub struct StreamingDecoder {
+    path: AssetPath<'static>,
+    /// The reader of the file, until enough of it is buffered to create the decoder.
+    pending: Option<StreamingReader>,
+    /// A seek requested before the decoder was created.
+    pending_seek: Option<Duration>,
+    inner: Option<rodio::Decoder<StreamingReader>>,
+    looping: bool,
+    loop_start: Duration,
+    loop_end: Option<Duration>,
+    /// [`Self::loop_end`] in interleaved samples, known once the decoder is created.
+    loop_end_position: Option<u64>,
+    /// The number of interleaved samples since the start of the track.
+    position: u64,
+}
+
+impl StreamingDecoder {
+    /// Creates the decoder once its reader has buffered the header of the file.
+    ///
+    /// Returns `false` if the decoder isn't ready yet.
+    fn poll_decoder(&mut self) -> bool {
+        let Some(reader) = &self.pending else {
+            return true;
+        };
+        if !reader.is_ready() {
+            return false;
+        }
+
+        let reader = self.pending.take().unwrap();
+        match rodio::Decoder::new(reader) {
+            Ok(mut inner) => {
+                self.loop_end_position = self
+                    .loop_end
+                    .map(|loop_end| duration_to_samples(loop_end, &inner));
+                if let Some(pos) = self.pending_seek.take() {
+                    match inner.try_seek(pos) {
+                        Ok(()) => self.position = duration_to_samples(pos, &inner),
+                        Err(err) => tracing::warn!("Failed to seek streaming audio source: {err}"),
+                    }
+                }
+                self.inner = Some(inner);
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Failed to decode streaming audio source {}: {err}",
+                    self.path
+                );
+                self.looping = false;
+            }
+        }
+        true
+    }
+
+    fn seek_to_loop_start(&mut self) -> bool {
+        let Some(inner) = &mut self.inner else {
+            return false;
+        };
+        match inner.try_seek(self.loop_start) {
+            Ok(()) => {
+                self.position = duration_to_samples(self.loop_start, inner);
+                true
+            }
+            Err(err) => {
+                tracing::warn!("Failed to loop streaming audio source: {err}");
+                self.looping = false;
+                false
+            }
+        }
+    }
+}
+
+impl Iterator for StreamingDecoder {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.poll_decoder() {
+            // Play silence while the header of the file is being read.
+            return Some(0);
+        }
+
+        if self.looping
+            && self
+                .loop_end_position
+                .is_some_and(|loop_end| self.position >= loop_end)
+            && !self.seek_to_loop_start()
+        {
+            return None;
+        }
+
+        let sample = match self.inner.as_mut()?.next() {
+            Some(sample) => sample,
+            None if self.looping && self.seek_to_loop_start() => self.inner.as_mut()?.next()?,
+            None => return None,
+        };
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl Source for StreamingDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        match &self.inner {
+            Some(inner) => inner.current_frame_len(),
+            // Frames of a single sample, so that the format of the decoder is picked up once it is created.
+            None if self.pending.is_some() => Some(1),
+            None => Some(0),
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.as_ref().map_or(1, Source::channels)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.as_ref().map_or(44100, Source::sample_rate)
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        match &self.inner {
+            _ if self.looping || self.pending.is_some() => None,
+            Some(inner) => inner.total_duration(),
+            None => Some(Duration::ZERO),
+        }
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        if self.pending.is_some() {
+            self.pending_seek = Some(pos);
+            return Ok(());
+        }
+        let Some(inner) = &mut self.inner else {
+            return Ok(());
+        };
+        inner.try_seek(pos)?;
+        self.position = duration_to_samples(pos, inner);
+        Ok(())
+    }
+}
+
+/// The state shared between a [`StreamingReader`] and the task filling it.
+struct StreamState {
+    /// Encoded bytes read ahead of the current position.
+    buffer: VecDeque<u8>,
+    /// The position in the file of the first byte of `buffer`.
+    position: u64,
+    /// A position requested by [`StreamingReader::seek`] that the task hasn't handled yet.
+    seek: Option<u64>,
+    /// Incremented on every seek, so that data read before the seek is discarded.
+    generation: u64,
+    /// The task reached the end of the file.
+    eof: bool,
+    /// The task failed to read the file.
+    error: Option<io::Error>,
+    /// The task stopped, so no more data will be read.
+    done: bool,
+    /// The [`StreamingReader`] was dropped.
+    closed: bool,
+    /// Wakes the task once the reader consumed data, seeked or was dropped.
+    waker: Option<Waker>,
+}
+
+struct Shared {
+    state: Mutex<StreamState>,
+    /// Notifies the reader once the task read data or stopped.
+    condvar: Condvar,
+    capacity: usize,
+}
+
+impl Shared {
+    /// Wakes the task filling the buffer.
+    fn wake_task(state: &mut StreamState) {
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A [`Read`] + [`Seek`] view of an asset, filled by a task of the [`IoTaskPool`] through the
+/// [`AssetServer`]'s asset reader.
+struct StreamingReader {
+    shared: Arc<Shared>,
+}
+
+impl StreamingReader {
+    /// Starts reading the asset at `path`.
+    ///
+    /// Returns an error if the [`IoTaskPool`] isn't initialized.
+    fn new(
+        asset_server: AssetServer,
+        path: AssetPath<'static>,
+        read_ahead: usize,
+    ) -> io::Result<Self> {
+        let task_pool = IoTaskPool::try_get()
+            .ok_or_else(|| io::Error::other("the IO task pool is not initialized"))?;
+        let shared = Arc::new(Shared {
+            state: Mutex::new(StreamState {
+                buffer: VecDeque::with_capacity(read_ahead),
+                position: 0,
+                seek: None,
+                generation: 0,
+                eof: false,
+                error: None,
+                done: false,
+                closed: false,
+                waker: None,
+            }),
+            condvar: Condvar::new(),
+            capacity: read_ahead.max(CHUNK_SIZE),
+        });
+
+        let task_shared = Arc::clone(&shared);
+        task_pool
+            .spawn(async move {
+                let result = fill_stream(&asset_server, &path, &task_shared).await;
+                let mut state = task_shared.state.lock().unwrap();
+                if let Err(err) = result {
+                    state.error = Some(err);
+                }
+                state.done = true;
+                task_shared.condvar.notify_all();
+            })
+            .detach();
+
+        Ok(Self { shared })
+    }
+
+    /// Returns `true` once enough of the file is buffered to read its header without waiting, or
+    /// no more data will be read.
+    fn is_ready(&self) -> bool {
+        let state = self.shared.state.lock().unwrap();
+        state.buffer.len() >= self.shared.capacity.min(HEADER_READ_AHEAD)
+            || state.eof
+            || state.error.is_some()
+            || state.done
+    }
+}
+
+impl Drop for StreamingReader {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.closed = true;
+        Shared::wake_task(&mut state);
+    }
+}
+
+impl Read for StreamingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut state = self.shared.state.lock().unwrap();
+        loop {
+            if !state.buffer.is_empty() && state.seek.is_none() {
+                let len = buf.len().min(state.buffer.len());
+                for (byte, value) in buf.iter_mut().zip(state.buffer.drain(..len)) {
+                    *byte = value;
+                }
+                state.position += len as u64;
+                Shared::wake_task(&mut state);
+                return Ok(len);
+            }
+            if let Some(err) = state.error.take() {
+                return Err(err);
+            }
+            if (state.eof && state.seek.is_none()) || state.done {
+                return Ok(0);
+            }
+            state = self.shared.condvar.wait(state).unwrap();
+        }
+    }
+}
+
+impl Seek for StreamingReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let mut state = self.shared.state.lock().unwrap();
+        let current = state.seek.unwrap_or(state.position);
+        let target = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::Current(offset) => current.checked_add_signed(offset).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position")
+            })?,
+            SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "streaming audio sources cannot seek from the end",
+                ));
+            }
+        };
+
+        let buffered_end = state.position + state.buffer.len() as u64;
+        if state.seek.is_none() && target >= state.position && target <= buffered_end {
+            // The target is already buffered, skip to it.
+            let skip = (target - state.position) as usize;
+            state.buffer.drain(..skip);
+            state.position = target;
+        } else {
+            state.buffer.clear();
+            state.position = target;
+            state.seek = Some(target);
+            state.generation += 1;
+            state.eof = false;
+            state.error = None;
+        }
+        Shared::wake_task(&mut state);
+        Ok(target)
+    }
+}
+
+/// Reads `path` into the stream buffer until the [`StreamingReader`] is dropped.
+async fn fill_stream(
+    asset_server: &AssetServer,
+    path: &AssetPath<'static>,
+    shared: &Shared,
+) -> io::Result<()> {
+    let source = asset_server
+        .get_source(path.source())
+        .map_err(io::Error::other)?;
+    let asset_reader = match asset_server.mode() {
+        AssetServerMode::Unprocessed => source.reader(),
+        AssetServerMode::Processed => source.processed_reader().map_err(io::Error::other)?,
+    };
+    let open = || async {
+        asset_reader
+            .read(path.path())
+            .await
+            .map_err(io::Error::other)
+    };
+
+    let mut reader = open().await?;
+    // The position of `reader` in the file.
+    let mut reader_position = 0;
+    let mut chunk = vec![0; CHUNK_SIZE];
+
+    loop {
+        // Wait for the reader to make room in the buffer, or to seek.
+        let next_read = poll_fn(|cx| {
+            let mut state = shared.state.lock().unwrap();
+            if state.closed {
+                return Poll::Ready(None);
+            }
+            let space = shared.capacity.saturating_sub(state.buffer.len());
+            if state.seek.is_some() || (!state.eof && space > 0) {
+                return Poll::Ready(Some((
+                    state.generation,
+                    state.seek.take(),
+                    space.min(CHUNK_SIZE),
+                )));
+            }
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await;
+        let Some((generation, seek, len)) = next_read else {
+            return Ok(());
+        };
+
+        if let Some(target) = seek {
+            if target < reader_position {
+                // Readers can only seek forward, start over from the beginning of the file.
+                reader = open().await?;
+                reader_position = 0;
+            }
+            reader_position = reader.seek_forward(target - reader_position).await?;
+        }
+
+        let read = reader.read(&mut chunk[..len]).await?;
+        reader_position += read as u64;
+
+        let mut state = shared.state.lock().unwrap();
+        if state.generation != generation {
+            // The reader seeked while this chunk was read, so it starts at the wrong position.
+            continue;
+        }
+        if read == 0 {
+            state.eof = true;
+        } else {
+            state.buffer.extend(&chunk[..read]);
+        }
+        shared.condvar.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        StreamingAudioLoader, StreamingAudioSettings, StreamingAudioSource, StreamingReader,
+    };
+    use crate::Decodable;
+    use alloc::{boxed::Box, vec::Vec};
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceBuilders, AssetSourceId,
+        },
+        AssetServer, AssetServerMode,
+    };
+    use bevy_tasks::{IoTaskPool, TaskPool};
+    use std::{
+        io::{Read, Seek, SeekFrom},
+        path::Path,
+    };
+
+    fn asset_server(files: &[(&str, Vec<u8>)]) -> AssetServer {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let dir = Dir::default();
+        for (path, data) in files {
+            dir.insert_asset(Path::new(path), data.clone());
+        }
+        let mut builders = AssetSourceBuilders::default();
+        builders.insert(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        );
+        AssetServer::new(
+            builders.build_sources(false, false),
+            AssetServerMode::Unprocessed,
+            false,
+        )
+    }
+
+    #[test]
+    fn reader_reads_and_seeks() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let asset_server = asset_server(&[("long.bin", data.clone())]);
+        let mut reader = StreamingReader::new(asset_server, "long.bin".into(), 0).unwrap();
+
+        let mut read = Vec::new();
+        reader.read_to_end(&mut read).unwrap();
+        assert_eq!(read, data);
+
+        // Seeking backwards reopens the file.
+        assert_eq!(reader.seek(SeekFrom::Start(1000)).unwrap(), 1000);
+        let mut buf = [0; 100];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf[..], data[1000..1100]);
+
+        // Seeking forwards within the buffered data skips it.
+        assert_eq!(reader.seek(SeekFrom::Current(50)).unwrap(), 1150);
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf[..], data[1150..1250]);
+
+        assert!(reader.seek(SeekFrom::End(0)).is_err());
+    }
+
+    #[test]
+    fn undecodable_source_is_silent() {
+        let asset_server = asset_server(&[("noise.ogg", vec![7; 1024])]);
+        let source = StreamingAudioSource {
+            asset_server,
+            path: "noise.ogg".into(),
+            settings: StreamingAudioSettings::default(),
+        };
+        // The decoder is silent until the file is read, then stops since it can't decode it.
+        assert!(source.decoder().all(|sample| sample == 0));
+        assert!(source.looping_decoder().unwrap().all(|sample| sample == 0));
+    }
+
+    #[test]
+    fn loader_is_only_used_for_streaming_sources() {
+        let asset_server = asset_server(&[]);
+        asset_server.register_loader(crate::AudioLoader);
+        asset_server.register_loader(StreamingAudioLoader {
+            asset_server: asset_server.clone(),
+        });
+
+        let streaming_loader = bevy_tasks::block_on(
+            asset_server.get_asset_loader_with_asset_type::<StreamingAudioSource>(),
+        )
+        .unwrap();
+        assert_eq!(
+            streaming_loader.type_name(),
+            core::any::type_name::<StreamingAudioLoader>()
+        );
+
+        let path_loader = bevy_tasks::block_on(asset_server.get_path_asset_loader("music.ogg"))
+            .ok()
+            .map(|loader| loader.type_name());
+        assert_ne!(
+            path_loader,
+            Some(core::any::type_name::<StreamingAudioLoader>())
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{impl_componentwise_vector_space, Alpha, Color, ColorToComponents, LinearRgba, Mix};
+use bevy_math::{Vec3, Vec4};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::*;
+
+/// Linear RGB color in the [ACEScg](https://en.wikipedia.org/wiki/Academy_Color_Encoding_System#ACEScg)
+/// working space, with an alpha channel.
+///
+/// `ACEScg` uses the wide AP1 primaries and a D60 white point. Its gamut covers most of the colors
+/// that can be displayed, so grading operations such as exposure and contrast adjustments can be
+/// applied in it without clipping saturated colors. Convert from and to [`LinearRgba`] (in the
+/// sRGB primaries) with [`From`].
+///
+/// Unlike the other color types of this crate, [`AcesCg`] is a working space for processing
+/// colors rather than a way to specify them, and is not a variant of [`Color`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(PartialEq, Default))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AcesCg {
+    /// The red channel. [0.0, 1.0] for colors within the sRGB gamut.
+    pub red: f32,
+    /// The green channel. [0.0, 1.0] for colors within the sRGB gamut.
+    pub green: f32,
+    /// The blue channel. [0.0, 1.0] for colors within the sRGB gamut.
+    pub blue: f32,
+    /// The alpha channel. [0.0, 1.0]
+    pub alpha: f32,
+}
+
+impl_componentwise_vector_space!(AcesCg, [red, green, blue, alpha]);
+
+impl AcesCg {
+    /// Construct a new [`AcesCg`] color from components.
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Construct a new [`AcesCg`] color from (r, g, b) components, with the default alpha (1.0).
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::new(red, green, blue, 1.0)
+    }
+
+    /// Returns the luminance of the color, from the Y row of the AP1 to XYZ matrix.
+    pub fn luminance(&self) -> f32 {
+        self.red * 0.2722287 + self.green * 0.6740818 + self.blue * 0.0536895
+    }
+}
+
+impl Default for AcesCg {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Alpha for AcesCg {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+
+    #[inline]
+    fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    #[inline]
+    fn set_alpha(&mut self, alpha: f32) {
+        self.alpha = alpha;
+    }
+}
+
+impl Mix for AcesCg {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            red: self.red * n_factor + other.red * factor,
+            green: self.green * n_factor + other.green * factor,
+            blue: self.blue * n_factor + other.blue * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl ColorToComponents for AcesCg {
+    fn to_f32_array(self) -> [f32; 4] {
+        [self.red, self.green, self.blue, self.alpha]
+    }
+
+    fn to_f32_array_no_alpha(self) -> [f32; 3] {
+        [self.red, self.green, self.blue]
+    }
+
+    fn to_vec4(self) -> Vec4 {
+        Vec4::new(self.red, self.green, self.blue, self.alpha)
+    }
+
+    fn to_vec3(self) -> Vec3 {
+        Vec3::new(self.red, self.green, self.blue)
+    }
+
+    fn from_f32_array(color: [f32; 4]) -> Self {
+        Self::new(color[0], color[1], color[2], color[3])
+    }
+
+    fn from_f32_array_no_alpha(color: [f32; 3]) -> Self {
+        Self::rgb(color[0], color[1], color[2])
+    }
+
+    fn from_vec4(color: Vec4) -> Self {
+        Self::new(color.x, color.y, color.z, color.w)
+    }
+
+    fn from_vec3(color: Vec3) -> Self {
+        Self::rgb(color.x, color.y, color.z)
+    }
+}
+
+impl From<LinearRgba> for AcesCg {
+    fn from(
+        LinearRgba {
+            red,
+            green,
+            blue,
+            alpha,
+        }: LinearRgba,
+    ) -> Self {
+        // Linear sRGB (D65) to ACEScg (D60), with a Bradford chromatic adaptation
+        // https://www.colour-science.org/ (RGB_to_RGB, sRGB to ACEScg)
+        Self::new(
+            red * 0.6130974 + green * 0.3395231 + blue * 0.0473793,
+            red * 0.0701937 + green * 0.9163539 + blue * 0.0134523,
+            red * 0.0206156 + green * 0.1095698 + blue * 0.8698151,
+            alpha,
+        )
+    }
+}
+
+impl From<AcesCg> for LinearRgba {
+    fn from(
+        AcesCg {
+            red,
+            green,
+            blue,
+            alpha,
+        }: AcesCg,
+    ) -> Self {
+        // ACEScg (D60) to linear sRGB (D65), the inverse of the matrix above
+        LinearRgba::new(
+            red * 1.705051 + green * -0.6217921 + blue * -0.0832589,
+            red * -0.1302564 + green * 1.1408048 + blue * -0.0105485,
+            red * -0.0240034 + green * -0.128969 + blue * 1.1529724,
+            alpha,
+        )
+    }
+}
+
+impl From<Color> for AcesCg {
+    fn from(value: Color) -> Self {
+        LinearRgba::from(value).into()
+    }
+}
+
+impl From<AcesCg> for Color {
+    fn from(value: AcesCg) -> Self {
+        Color::LinearRgba(value.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::assert_approx_eq, Srgba};
+
+    #[test]
+    fn white_and_black() {
+        let white = AcesCg::from(LinearRgba::WHITE);
+        assert_approx_eq!(white.red, 1.0, 0.0001);
+        assert_approx_eq!(white.green, 1.0, 0.0001);
+        assert_approx_eq!(white.blue, 1.0, 0.0001);
+        assert_approx_eq!(white.luminance(), 1.0, 0.0001);
+        assert_eq!(AcesCg::from(LinearRgba::BLACK), AcesCg::default());
+    }
+
+    #[test]
+    fn to_from_linear_rgba() {
+        let color = Srgba::new(0.9, 0.2, 0.4, 0.5);
+        let aces = AcesCg::from(Color::from(color));
+        let linear = LinearRgba::from(color);
+        // Saturated sRGB colors are less saturated in the wider AP1 gamut.
+        assert!(aces.red < linear.red && aces.green > linear.green);
+
+        let back = LinearRgba::from(aces);
+        assert_approx_eq!(back.red, linear.red, 0.0001);
+        assert_approx_eq!(back.green, linear.green, 0.0001);
+        assert_approx_eq!(back.blue, linear.blue, 0.0001);
+        assert_eq!(back.alpha, 0.5);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{AcesCg, LinearRgba, Oklcha};
+use bevy_math::ops;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::*;
+
+/// A set of grading adjustments applied to individual colors.
+///
+/// Exposure and contrast are applied in the [`AcesCg`] working space, so that saturated colors
+/// keep their hue when they are brightened. Saturation and hue shifts are applied in [`Oklcha`],
+/// which keeps the perceived lightness of the color unchanged.
+///
+/// The default value leaves colors untouched.
+///
+/// ```
+/// # use bevy_color::{ColorGrade, LinearRgba};
+/// let grade = ColorGrade {
+///     exposure: 1.0,
+///     saturation: 0.5,
+///     ..Default::default()
+/// };
+/// let graded = grade.apply(LinearRgba::rgb(0.5, 0.2, 0.1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(PartialEq, Default))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ColorGrade {
+    /// The exposure adjustment, in stops. Each stop doubles the intensity of the color.
+    pub exposure: f32,
+    /// The contrast around [`pivot`](Self::pivot). Values above 1.0 increase the contrast.
+    pub contrast: f32,
+    /// The intensity which is left unchanged by [`contrast`](Self::contrast), in linear units.
+    pub pivot: f32,
+    /// The factor applied to the chroma of the color. 0.0 produces a gray.
+    pub saturation: f32,
+    /// The rotation of the hue, in degrees.
+    pub hue_shift: f32,
+}
+
+impl Default for ColorGrade {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ColorGrade {
+    /// The grade which leaves colors untouched.
+    pub const IDENTITY: Self = Self {
+        exposure: 0.0,
+        contrast: 1.0,
+        pivot: 0.18,
+        saturation: 1.0,
+        hue_shift: 0.0,
+    };
+
+    /// Applies the grade to `color`, returning the graded color in linear sRGB.
+    pub fn apply(&self, color: impl Into<LinearRgba>) -> LinearRgba {
+        let mut aces = AcesCg::from(color.into());
+        let exposure = ops::exp2(self.exposure);
+        for channel in [&mut aces.red, &mut aces.green, &mut aces.blue] {
+            let exposed = *channel * exposure;
+            *channel = if self.contrast != 1.0 && exposed > 0.0 {
+                self.pivot * ops::powf(exposed / self.pivot, self.contrast)
+            } else {
+                exposed
+            };
+        }
+        let linear = LinearRgba::from(aces);
+        if self.saturation == 1.0 && self.hue_shift == 0.0 {
+            return linear;
+        }
+
+        let mut oklcha = Oklcha::from(linear);
+        oklcha.chroma *= self.saturation.max(0.0);
+        oklcha.hue = ops::rem_euclid(oklcha.hue + self.hue_shift, 360.0);
+        oklcha.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::assert_approx_eq, Gray, Srgba};
+
+    #[test]
+    fn identity() {
+        let color = LinearRgba::new(0.7, 0.3, 0.1, 0.5);
+        let graded = ColorGrade::default().apply(color);
+        assert_approx_eq!(graded.red, color.red, 0.0001);
+        assert_approx_eq!(graded.green, color.green, 0.0001);
+        assert_approx_eq!(graded.blue, color.blue, 0.0001);
+        assert_eq!(graded.alpha, 0.5);
+    }
+
+    #[test]
+    fn exposure_and_contrast() {
+        let gray = LinearRgba::gray(0.25);
+        let graded = ColorGrade {
+            exposure: 1.0,
+            ..Default::default()
+        }
+        .apply(gray);
+        assert_approx_eq!(graded.green, 0.5, 0.0001);
+
+        let contrast = ColorGrade {
+            contrast: 2.0,
+            ..Default::default()
+        };
+        assert_approx_eq!(contrast.apply(LinearRgba::gray(0.18)).red, 0.18, 0.0001);
+        assert!(contrast.apply(gray).red > 0.25);
+        assert!(contrast.apply(LinearRgba::gray(0.1)).red < 0.1);
+    }
+
+    #[test]
+    fn saturation_and_hue() {
+        let red = Srgba::new(0.8, 0.2, 0.2, 1.0);
+        let desaturated = Oklcha::from(
+            ColorGrade {
+                saturation: 0.0,
+                ..Default::default()
+            }
+            .apply(red),
+        );
+        assert_approx_eq!(desaturated.chroma, 0.0, 0.0001);
+        assert_approx_eq!(desaturated.lightness, Oklcha::from(red).lightness, 0.0001);
+
+        let shifted = Oklcha::from(
+            ColorGrade {
+                hue_shift: 180.0,
+                ..Default::default()
+            }
+            .apply(red),
+        );
+        let hue = Oklcha::from(red).hue;
+        assert_approx_eq!(shifted.hue, ops::rem_euclid(hue + 180.0, 360.0), 0.01);
+    }
+}
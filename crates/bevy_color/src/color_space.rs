@@ -0,0 +1,107 @@
+use crate::{AcesCg, ColorToComponents, LinearRgba, Srgba};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::*;
+
+/// The color space in which the components of a color, or the texels of an image, are encoded.
+///
+/// Color properties and image assets that don't carry their own color type can be tagged with a
+/// [`ColorSpace`] so that they are converted to the linear working space of the renderer when
+/// they are used. With the `bevy_reflect` feature, it can be attached to fields as a custom
+/// reflection attribute:
+///
+/// ```
+/// # use bevy_color::ColorSpace;
+/// # use bevy_reflect::Reflect;
+/// #[derive(Reflect)]
+/// struct Palette {
+///     #[reflect(@ColorSpace::Srgb)]
+///     albedo: [f32; 4],
+///     #[reflect(@ColorSpace::AcesCg)]
+///     emission: [f32; 4],
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ColorSpace {
+    /// Gamma-encoded sRGB, as used by most image files and color pickers. See [`Srgba`].
+    #[default]
+    Srgb,
+    /// Linear sRGB, the working space of the renderer. See [`LinearRgba`].
+    LinearSrgb,
+    /// Linear `ACEScg`, a wide gamut working space used for grading. See [`AcesCg`].
+    AcesCg,
+}
+
+impl ColorSpace {
+    /// Returns true if the components in this color space are proportional to light intensity.
+    pub const fn is_linear(self) -> bool {
+        !matches!(self, Self::Srgb)
+    }
+
+    /// Interprets `components` (red, green, blue and alpha) in this color space, and converts
+    /// them to [`LinearRgba`].
+    pub fn decode(self, components: [f32; 4]) -> LinearRgba {
+        match self {
+            Self::Srgb => Srgba::from_f32_array(components).into(),
+            Self::LinearSrgb => LinearRgba::from_f32_array(components),
+            Self::AcesCg => AcesCg::from_f32_array(components).into(),
+        }
+    }
+
+    /// Converts `color` to the components (red, green, blue and alpha) of this color space.
+    pub fn encode(self, color: LinearRgba) -> [f32; 4] {
+        match self {
+            Self::Srgb => Srgba::from(color).to_f32_array(),
+            Self::LinearSrgb => color.to_f32_array(),
+            Self::AcesCg => AcesCg::from(color).to_f32_array(),
+        }
+    }
+
+    /// Converts `components` (red, green, blue and alpha) from this color space to `target`.
+    pub fn convert(self, components: [f32; 4], target: ColorSpace) -> [f32; 4] {
+        if self == target {
+            return components;
+        }
+        target.encode(self.decode(components))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_approx_eq;
+
+    #[test]
+    fn convert_between_spaces() {
+        let srgb = [0.5, 0.25, 1.0, 0.75];
+        let linear = ColorSpace::Srgb.convert(srgb, ColorSpace::LinearSrgb);
+        assert_eq!(
+            linear,
+            LinearRgba::from(Srgba::from_f32_array(srgb)).to_f32_array()
+        );
+        assert_eq!(linear[3], 0.75);
+
+        let aces = ColorSpace::Srgb.convert(srgb, ColorSpace::AcesCg);
+        let back = ColorSpace::AcesCg.convert(aces, ColorSpace::Srgb);
+        for (a, b) in srgb.into_iter().zip(back) {
+            assert_approx_eq!(a, b, 0.0001);
+        }
+        assert_eq!(ColorSpace::AcesCg.convert(aces, ColorSpace::AcesCg), aces);
+    }
+
+    #[test]
+    fn linearity() {
+        assert!(!ColorSpace::Srgb.is_linear());
+        assert!(ColorSpace::LinearSrgb.is_linear());
+        assert!(ColorSpace::AcesCg.is_linear());
+    }
+}
@@ -20,6 +20,7 @@
 //! - [`Oklaba`] (lightness, a-axis, b-axis, alpha)
 //! - [`Oklcha`] (lightness, chroma, hue, alpha)
 //! - [`Xyza`] (x-axis, y-axis, z-axis, alpha)
+//! - [`AcesCg`] (red, green, blue, alpha in the `ACEScg` working space)
 //!
 //! Each of these color spaces is represented as a distinct Rust type.
 //!
@@ -96,12 +97,15 @@ extern crate std;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod acescg;
 mod color;
 pub mod color_difference;
+mod color_grade;
 #[cfg(feature = "alloc")]
 mod color_gradient;
 mod color_ops;
 mod color_range;
+mod color_space;
 mod hsla;
 mod hsva;
 mod hwba;
@@ -128,11 +132,14 @@ pub mod prelude {
     };
 }
 
+pub use acescg::*;
 pub use color::*;
+pub use color_grade::*;
 #[cfg(feature = "alloc")]
 pub use color_gradient::*;
 pub use color_ops::*;
 pub use color_range::*;
+pub use color_space::*;
 pub use hsla::*;
 pub use hsva::*;
 pub use hwba::*;
@@ -0,0 +1,359 @@
+//! Selection of the anti-aliasing method of a camera from a quality level.
+//!
+//! Bevy provides several anti-aliasing methods, each with its own component:
+//! [`Msaa`], [`Fxaa`], [`Smaa`] and [`TemporalAntiAliasing`]. They can't be
+//! combined, and each has its own requirements: MSAA doesn't work with
+//! deferred rendering, SMAA needs the `smaa_luts` feature, and the temporal
+//! methods need a 3D camera with a perspective projection. The
+//! [`AntiAliasing`] component picks the best method supported by a camera for
+//! the requested quality, and inserts or removes the components accordingly.
+
+use crate::{
+    core_3d::Camera3d,
+    fxaa::Fxaa,
+    prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass},
+    smaa::{Smaa, SmaaMode},
+    taa::{TemporalAntiAliasPlugin, TemporalAntiAliasing},
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::Has,
+    reflect::ReflectComponent,
+    resource::Resource,
+    system::{Commands, Query, Res},
+};
+use bevy_image::BevyDefault as _;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Projection, TemporalJitter},
+    render_resource::TextureFormat,
+    renderer::RenderAdapter,
+    view::{Msaa, ViewTarget},
+};
+use bevy_utils::prelude::default;
+
+/// Adds support for the [`AntiAliasing`] component.
+pub struct AntiAliasingPlugin;
+
+impl Plugin for AntiAliasingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AntiAliasing>()
+            .init_resource::<AntiAliasingCapabilities>()
+            .add_systems(PostUpdate, select_anti_aliasing);
+    }
+
+    fn finish(&self, app: &mut App) {
+        // All the plugins and the GPU are known by now.
+        let msaa = app
+            .world()
+            .get_resource::<RenderAdapter>()
+            .is_none_or(|render_adapter| {
+                [
+                    ViewTarget::TEXTURE_FORMAT_HDR,
+                    TextureFormat::bevy_default(),
+                ]
+                .into_iter()
+                .all(|format| {
+                    render_adapter
+                        .get_texture_format_features(format)
+                        .flags
+                        .sample_count_supported(Msaa::Sample4.samples())
+                })
+            });
+        let taa = app.is_plugin_added::<TemporalAntiAliasPlugin>();
+        app.insert_resource(AntiAliasingCapabilities {
+            msaa,
+            taa,
+            ..default()
+        });
+    }
+}
+
+/// Selects the anti-aliasing method of a camera from a quality level,
+/// depending on what the camera and the platform support.
+///
+/// | Quality  | Method                                           | Fallback                           |
+/// |----------|--------------------------------------------------|------------------------------------|
+/// | `Off`    | None                                             |                                    |
+/// | `Low`    | FXAA                                             |                                    |
+/// | `Medium` | 4x MSAA                                          | SMAA, or FXAA without SMAA support |
+/// | `High`   | SMAA T2x, or SMAA on 2D and orthographic cameras | `Medium` without SMAA support      |
+/// | `Ultra`  | TAA on 3D perspective cameras                    | `High`                             |
+///
+/// MSAA isn't used on cameras with a [`DeferredPrepass`], nor when the GPU
+/// doesn't support it for the formats of the main textures. SMAA requires the
+/// `smaa_luts` feature. See [`AntiAliasingCapabilities`] for what the platform
+/// supports.
+///
+/// The method selected for a camera is available in its
+/// [`SelectedAntiAliasing`] component. Whenever it changes, the [`Msaa`],
+/// [`Fxaa`], [`Smaa`], [`TemporalAntiAliasing`] and [`TemporalJitter`]
+/// components of the camera are replaced, so they shouldn't be modified by hand
+/// on a camera with this component. The [`DepthPrepass`] and
+/// [`MotionVectorPrepass`] needed by the temporal methods are added as needed,
+/// and removed when no longer needed unless the camera had them before.
+#[derive(Component, Reflect, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component, Default)]
+pub enum AntiAliasing {
+    /// Disables anti-aliasing.
+    Off,
+    /// The cheapest method, which blurs edges slightly.
+    Low,
+    /// A method anti-aliasing the edges of meshes, without blurring textures.
+    ///
+    /// This is the default.
+    #[default]
+    Medium,
+    /// A method also reducing the aliasing of thin features and shading, with
+    /// little ghosting in motion.
+    High,
+    /// The method smoothing out most kinds of aliasing, at the cost of some
+    /// ghosting and blurring in motion.
+    Ultra,
+}
+
+/// An anti-aliasing method selected by [`AntiAliasing`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AntiAliasingMethod {
+    /// No anti-aliasing.
+    None,
+    /// 4x multisample anti-aliasing, see [`Msaa`].
+    Msaa,
+    /// Fast approximate anti-aliasing, see [`Fxaa`].
+    Fxaa,
+    /// Subpixel morphological anti-aliasing, see [`Smaa`].
+    Smaa,
+    /// Subpixel morphological anti-aliasing with temporal supersampling, see
+    /// [`SmaaMode::T2x`].
+    SmaaT2x,
+    /// Temporal anti-aliasing, see [`TemporalAntiAliasing`].
+    Taa,
+}
+
+/// The anti-aliasing methods the platform supports, used by [`AntiAliasing`]
+/// to select a method.
+///
+/// This is computed by the [`AntiAliasingPlugin`] once all the plugins are
+/// added, and can be overridden to disable some methods.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AntiAliasingCapabilities {
+    /// Whether the GPU supports 4x MSAA for the formats of the main textures.
+    pub msaa: bool,
+    /// Whether SMAA can be used, which requires the `smaa_luts` feature.
+    pub smaa: bool,
+    /// Whether TAA can be used, which requires the [`TemporalAntiAliasPlugin`].
+    pub taa: bool,
+}
+
+impl Default for AntiAliasingCapabilities {
+    fn default() -> Self {
+        Self {
+            msaa: true,
+            smaa: cfg!(feature = "smaa_luts"),
+            taa: false,
+        }
+    }
+}
+
+/// The anti-aliasing method selected for a camera by its [`AntiAliasing`]
+/// component.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelectedAntiAliasing {
+    method: AntiAliasingMethod,
+    /// Whether the [`DepthPrepass`] was added for the selected method.
+    inserted_depth_prepass: bool,
+    /// Whether the [`MotionVectorPrepass`] was added for the selected method.
+    inserted_motion_vector_prepass: bool,
+}
+
+impl SelectedAntiAliasing {
+    /// The anti-aliasing method used by the camera.
+    pub fn method(&self) -> AntiAliasingMethod {
+        self.method
+    }
+}
+
+impl AntiAliasing {
+    /// Returns the anti-aliasing method of this quality on a camera.
+    ///
+    /// `perspective_3d` tells whether the camera is a 3D camera with a
+    /// perspective projection, which the temporal methods require, and
+    /// `deferred` whether it uses deferred rendering, which MSAA doesn't
+    /// support.
+    pub fn method(
+        self,
+        capabilities: &AntiAliasingCapabilities,
+        perspective_3d: bool,
+        deferred: bool,
+    ) -> AntiAliasingMethod {
+        match self {
+            AntiAliasing::Off => AntiAliasingMethod::None,
+            AntiAliasing::Medium if capabilities.msaa && !deferred => AntiAliasingMethod::Msaa,
+            AntiAliasing::Medium if capabilities.smaa => AntiAliasingMethod::Smaa,
+            AntiAliasing::Low | AntiAliasing::Medium => AntiAliasingMethod::Fxaa,
+            AntiAliasing::High if !capabilities.smaa => {
+                AntiAliasing::Medium.method(capabilities, perspective_3d, deferred)
+            }
+            AntiAliasing::High if perspective_3d => AntiAliasingMethod::SmaaT2x,
+            AntiAliasing::High => AntiAliasingMethod::Smaa,
+            AntiAliasing::Ultra if capabilities.taa && perspective_3d => AntiAliasingMethod::Taa,
+            AntiAliasing::Ultra => {
+                AntiAliasing::High.method(capabilities, perspective_3d, deferred)
+            }
+        }
+    }
+}
+
+/// Replaces the anti-aliasing components of the cameras whose
+/// [`AntiAliasing`] selects another method.
+fn select_anti_aliasing(
+    mut commands: Commands,
+    capabilities: Res<AntiAliasingCapabilities>,
+    mut cameras: Query<(
+        Entity,
+        &AntiAliasing,
+        Option<&Projection>,
+        Has<Camera3d>,
+        Has<DeferredPrepass>,
+        Has<DepthPrepass>,
+        Has<MotionVectorPrepass>,
+        &mut Msaa,
+        Option<&SelectedAntiAliasing>,
+    )>,
+) {
+    for (
+        entity,
+        anti_aliasing,
+        projection,
+        camera_3d,
+        deferred_prepass,
+        depth_prepass,
+        motion_vector_prepass,
+        mut msaa,
+        selected,
+    ) in &mut cameras
+    {
+        let perspective_3d = camera_3d && matches!(projection, Some(Projection::Perspective(_)));
+        let method = anti_aliasing.method(&capabilities, perspective_3d, deferred_prepass);
+        if selected.is_some_and(|selected| selected.method == method) {
+            continue;
+        }
+
+        let temporal = matches!(
+            method,
+            AntiAliasingMethod::SmaaT2x | AntiAliasingMethod::Taa
+        );
+        let needs_depth_prepass = method == AntiAliasingMethod::Taa;
+        let inserted_depth_prepass = needs_depth_prepass
+            && (!depth_prepass || selected.is_some_and(|selected| selected.inserted_depth_prepass));
+        let inserted_motion_vector_prepass = temporal
+            && (!motion_vector_prepass
+                || selected.is_some_and(|selected| selected.inserted_motion_vector_prepass));
+
+        let mut entity_commands = commands.entity(entity);
+        if let Some(selected) = selected {
+            if selected.inserted_depth_prepass && !needs_depth_prepass {
+                entity_commands.remove::<DepthPrepass>();
+            }
+            if selected.inserted_motion_vector_prepass && !temporal {
+                entity_commands.remove::<MotionVectorPrepass>();
+            }
+        }
+        entity_commands.remove::<(Fxaa, Smaa, TemporalAntiAliasing)>();
+        if !temporal {
+            entity_commands.remove::<TemporalJitter>();
+        }
+
+        match method {
+            AntiAliasingMethod::None | AntiAliasingMethod::Msaa => {}
+            AntiAliasingMethod::Fxaa => {
+                entity_commands.insert(Fxaa::default());
+            }
+            AntiAliasingMethod::Smaa => {
+                entity_commands.insert(Smaa::default());
+            }
+            AntiAliasingMethod::SmaaT2x => {
+                entity_commands.insert((
+                    Smaa {
+                        mode: SmaaMode::T2x,
+                        ..default()
+                    },
+                    TemporalJitter::default(),
+                    MotionVectorPrepass,
+                ));
+            }
+            AntiAliasingMethod::Taa => {
+                // The jitter and the prepasses are required components.
+                entity_commands.insert(TemporalAntiAliasing::default());
+            }
+        }
+        *msaa = if method == AntiAliasingMethod::Msaa {
+            Msaa::Sample4
+        } else {
+            Msaa::Off
+        };
+
+        entity_commands.insert(SelectedAntiAliasing {
+            method,
+            inserted_depth_prepass,
+            inserted_motion_vector_prepass,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn methods_fall_back_to_supported_ones() {
+        let all = AntiAliasingCapabilities {
+            msaa: true,
+            smaa: true,
+            taa: true,
+        };
+        let none = AntiAliasingCapabilities {
+            msaa: false,
+            smaa: false,
+            taa: false,
+        };
+
+        // A forward rendered 3D camera gets the preferred method of each quality.
+        let methods = [
+            AntiAliasing::Off,
+            AntiAliasing::Low,
+            AntiAliasing::Medium,
+            AntiAliasing::High,
+            AntiAliasing::Ultra,
+        ]
+        .map(|anti_aliasing| anti_aliasing.method(&all, true, false));
+        assert_eq!(
+            methods,
+            [
+                AntiAliasingMethod::None,
+                AntiAliasingMethod::Fxaa,
+                AntiAliasingMethod::Msaa,
+                AntiAliasingMethod::SmaaT2x,
+                AntiAliasingMethod::Taa,
+            ]
+        );
+
+        // 2D cameras can't use the temporal methods, and deferred ones can't use MSAA.
+        assert_eq!(
+            AntiAliasing::Ultra.method(&all, false, false),
+            AntiAliasingMethod::Smaa
+        );
+        assert_eq!(
+            AntiAliasing::Medium.method(&all, true, true),
+            AntiAliasingMethod::Smaa
+        );
+
+        // FXAA is always supported.
+        assert_eq!(
+            AntiAliasing::Ultra.method(&none, true, true),
+            AntiAliasingMethod::Fxaa
+        );
+    }
+}
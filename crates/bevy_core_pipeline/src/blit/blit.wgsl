@@ -5,5 +5,13 @@
 
 @fragment
 fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(in_texture, in_sampler, in.uv);
+    let color = textureSample(in_texture, in_sampler, in.uv);
+
+#ifdef HDR_OUTPUT_SCRGB
+    // scRGB is linear, with 1.0 at 80 nits.
+    let nits = min(max(color.rgb, vec3(0.0)) * f32(#HDR_OUTPUT_PAPER_WHITE_NITS), vec3(f32(#HDR_OUTPUT_PEAK_NITS)));
+    return vec4(nits / 80.0, color.a);
+#else
+    return color;
+#endif
 }
@@ -7,6 +7,7 @@ use bevy_render::{
         *,
     },
     renderer::RenderDevice,
+    view::{ActiveHdrOutput, HdrOutputFormat},
     RenderApp,
 };
 
@@ -71,19 +72,36 @@ pub struct BlitPipelineKey {
     pub texture_format: TextureFormat,
     pub blend_state: Option<BlendState>,
     pub samples: u32,
+    /// The HDR output to encode the texture for, if any.
+    pub hdr_output: Option<ActiveHdrOutput>,
 }
 
 impl SpecializedRenderPipeline for BlitPipeline {
     type Key = BlitPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if let Some(hdr_output) = key.hdr_output {
+            shader_defs.push(match hdr_output.format {
+                HdrOutputFormat::ScRgb => "HDR_OUTPUT_SCRGB".into(),
+            });
+            shader_defs.push(ShaderDefVal::UInt(
+                "HDR_OUTPUT_PAPER_WHITE_NITS".into(),
+                hdr_output.paper_white_nits,
+            ));
+            shader_defs.push(ShaderDefVal::UInt(
+                "HDR_OUTPUT_PEAK_NITS".into(),
+                hdr_output.peak_nits,
+            ));
+        }
+
         RenderPipelineDescriptor {
             label: Some("blit pipeline".into()),
             layout: vec![self.texture_bind_group.clone()],
             vertex: fullscreen_shader_vertex_state(),
             fragment: Some(FragmentState {
                 shader: BLIT_SHADER_HANDLE,
-                shader_defs: vec![],
+                shader_defs,
                 entry_point: "fs_main".into(),
                 targets: vec![Some(ColorTargetState {
                     format: key.texture_format,
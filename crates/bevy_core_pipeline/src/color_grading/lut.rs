@@ -0,0 +1,200 @@
+use bevy_color::Color;
+use bevy_image::{Image, ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor};
+use bevy_math::{ops, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    view::{ColorGrading, ColorGradingUniform},
+};
+use bevy_utils::default;
+use core::f32::consts::{FRAC_PI_3, TAU};
+
+/// The intensity at the center of the log encoding of a [`ColorGradingLut`].
+const MIDDLE_GRAY: f32 = 0.18;
+
+/// Half the size of the crossfade region between shadows and midtones and
+/// between midtones and highlights, matching the tonemapping shader.
+const LEVEL_MARGIN: f32 = 0.1;
+
+/// Bakes [`ColorGrading`] values into 3D LUT (look up table) images.
+///
+/// The baked LUT maps HDR linear colors, before tonemapping, to the same colors
+/// with the hue rotation, white balance, sectional grading and exposure of the
+/// [`ColorGrading`] applied. The `post_saturation` value is applied after
+/// tonemapping and isn't part of the LUT.
+///
+/// Since HDR colors aren't bounded, the LUT is indexed with a log2 encoding of
+/// the colors between [`min_ev`](Self::min_ev) and [`max_ev`](Self::max_ev),
+/// see [`ColorGradingLut::encode`]. The texels store linear colors.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub struct ColorGradingLut {
+    /// The number of texels along each axis of the LUT.
+    ///
+    /// The default value is 33.
+    pub size: u32,
+
+    /// The lowest exposure covered by the LUT, in stops relative to middle gray
+    /// (0.18). Darker colors are clamped.
+    ///
+    /// The default value is -10.0.
+    pub min_ev: f32,
+
+    /// The highest exposure covered by the LUT, in stops relative to middle
+    /// gray (0.18). Brighter colors are clamped.
+    ///
+    /// The default value is 6.5.
+    pub max_ev: f32,
+}
+
+impl Default for ColorGradingLut {
+    fn default() -> Self {
+        Self {
+            size: 33,
+            min_ev: -10.0,
+            max_ev: 6.5,
+        }
+    }
+}
+
+impl ColorGradingLut {
+    /// Converts a linear color to the coordinates at which it's looked up in
+    /// the LUT, in the `[0, 1]` range.
+    pub fn encode(&self, color: Vec3) -> Vec3 {
+        let range = self.max_ev - self.min_ev;
+        Vec3::from_array(color.to_array().map(|channel| {
+            let ev = ops::log2(channel.max(f32::MIN_POSITIVE) / MIDDLE_GRAY);
+            (ev.clamp(self.min_ev, self.max_ev) - self.min_ev) / range
+        }))
+    }
+
+    /// Converts coordinates in the LUT, in the `[0, 1]` range, back to the
+    /// linear color they represent.
+    pub fn decode(&self, coordinates: Vec3) -> Vec3 {
+        let range = self.max_ev - self.min_ev;
+        Vec3::from_array(
+            coordinates
+                .to_array()
+                .map(|channel| MIDDLE_GRAY * ops::exp2(self.min_ev + channel * range)),
+        )
+    }
+
+    /// Bakes `color_grading` into a new 3D [`Image`].
+    ///
+    /// The red channel of the input varies along the *x* axis of the image, the
+    /// green channel along *y*, and the blue channel along *z*.
+    pub fn bake(&self, color_grading: &ColorGrading) -> Image {
+        let size = self.size.max(2);
+        let uniform = ColorGradingUniform::from(color_grading.clone());
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: size,
+            },
+            TextureDimension::D3,
+            &[0; 8],
+            TextureFormat::Rgba16Float,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+            label: Some("Color grading LUT sampler".to_string()),
+            address_mode_u: ImageAddressMode::ClampToEdge,
+            address_mode_v: ImageAddressMode::ClampToEdge,
+            address_mode_w: ImageAddressMode::ClampToEdge,
+            mag_filter: ImageFilterMode::Linear,
+            min_filter: ImageFilterMode::Linear,
+            ..default()
+        });
+
+        let scale = 1.0 / (size - 1) as f32;
+        for z in 0..size {
+            for y in 0..size {
+                for x in 0..size {
+                    let coordinates = Vec3::new(x as f32, y as f32, z as f32) * scale;
+                    let graded = grade(&uniform, self.decode(coordinates));
+                    image
+                        .set_color_at_3d(x, y, z, Color::linear_rgb(graded.x, graded.y, graded.z))
+                        .expect("the LUT image is a 3D image in a supported format");
+                }
+            }
+        }
+        image
+    }
+}
+
+/// Applies the color grading performed before tonemapping to `color`, the same
+/// way the tonemapping shader does.
+fn grade(color_grading: &ColorGradingUniform, color: Vec3) -> Vec3 {
+    let mut color = color.max(Vec3::ZERO);
+
+    if color_grading.hue != 0.0 {
+        let [hue, saturation, value] = rgb_to_hsv(color);
+        color = hsv_to_rgb(
+            ops::rem_euclid(hue + color_grading.hue, TAU),
+            saturation,
+            value,
+        );
+    }
+
+    color = (color_grading.balance * color).max(Vec3::ZERO);
+
+    // Blend between the shadows, midtones and highlights sections around the
+    // cutoff points.
+    let level = (color.x + color.y + color.z) / 3.0;
+    let range = color_grading.midtone_range;
+    let levels = if level < range.x - LEVEL_MARGIN {
+        Vec3::X
+    } else if level < range.x + LEVEL_MARGIN {
+        let midtones = (level - range.x) * (0.5 / LEVEL_MARGIN) + 0.5;
+        Vec3::new(0.0, midtones, 1.0 - midtones)
+    } else if level < range.y - LEVEL_MARGIN {
+        Vec3::Y
+    } else if level < range.y + LEVEL_MARGIN {
+        let highlights = (level - range.y) * (0.5 / LEVEL_MARGIN) + 0.5;
+        Vec3::new(0.0, 1.0 - highlights, highlights)
+    } else {
+        Vec3::Z
+    };
+
+    let contrast = levels.dot(color_grading.contrast);
+    let saturation = levels.dot(color_grading.saturation);
+    let gamma = levels.dot(color_grading.gamma);
+    let gain = levels.dot(color_grading.gain);
+    let lift = levels.dot(color_grading.lift);
+
+    let luma = color.dot(Vec3::new(0.2126, 0.7152, 0.0722));
+    color = luma + saturation * (color - luma);
+    color = 0.5 + (color - 0.5) * contrast;
+    color = (color * gain + lift).map(|channel| powsafe(channel, 1.0 / gamma));
+    color *= ops::exp2(color_grading.exposure);
+    color.max(Vec3::ZERO)
+}
+
+fn powsafe(value: f32, power: f32) -> f32 {
+    ops::powf(ops::abs(value), power).copysign(value)
+}
+
+fn rgb_to_hsv(rgb: Vec3) -> [f32; 3] {
+    let max = rgb.max_element();
+    let chroma = max - rgb.min_element();
+    let hue = if chroma == 0.0 {
+        0.0
+    } else if max == rgb.x {
+        FRAC_PI_3 * ops::rem_euclid((rgb.y - rgb.z) / chroma, 6.0)
+    } else if max == rgb.y {
+        FRAC_PI_3 * ((rgb.z - rgb.x) / chroma + 2.0)
+    } else {
+        FRAC_PI_3 * ((rgb.x - rgb.y) / chroma + 4.0)
+    };
+    let saturation = if max > 0.0 { chroma / max } else { 0.0 };
+    [hue, saturation, max]
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> Vec3 {
+    Vec3::new(5.0, 3.0, 1.0).map(|n| {
+        let k = ops::rem_euclid(n + hue / FRAC_PI_3, 6.0);
+        value - value * saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+    })
+}
@@ -0,0 +1,131 @@
+//! Color grading that varies with the position of the camera.
+//!
+//! [`ColorGradingVolume`]s are regions of space with their own
+//! [`ColorGrading`]. Cameras with a [`BaseColorGrading`] get their
+//! [`ColorGrading`] blended from the volumes they are in, or near, every frame.
+//! [`ColorGradingLut`] bakes color grading values into 3D LUT images.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3A;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::ColorGrading;
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+mod lut;
+
+pub use lut::ColorGradingLut;
+
+/// Adds support for [`ColorGradingVolume`]s.
+pub struct ColorGradingVolumePlugin;
+
+impl Plugin for ColorGradingVolumePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ColorGradingVolume>()
+            .register_type::<BaseColorGrading>()
+            .add_systems(
+                PostUpdate,
+                blend_color_grading_volumes.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// A region of space that applies its own [`ColorGrading`] to the cameras
+/// inside it.
+///
+/// The volume is a cube that spans from -0.5 to 0.5 on each axis, transformed
+/// by the [`GlobalTransform`] of the entity. Cameras outside the volume but
+/// within [`blend_distance`](Self::blend_distance) of it are partially
+/// affected, so that the grading smoothly changes when moving between an
+/// interior and an exterior, for example.
+///
+/// Only cameras with a [`BaseColorGrading`] are affected by volumes.
+#[derive(Clone, Component, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform)]
+pub struct ColorGradingVolume {
+    /// The color grading applied to cameras inside the volume.
+    pub color_grading: ColorGrading,
+
+    /// The distance from the volume, in world units, over which the color
+    /// grading fades in as a camera approaches it.
+    ///
+    /// The default value is 1.0.
+    pub blend_distance: f32,
+
+    /// The influence of the volume on cameras inside it, from 0.0 to 1.0.
+    ///
+    /// The default value is 1.0.
+    pub weight: f32,
+
+    /// The order in which overlapping volumes are blended. Volumes with a
+    /// higher priority are blended over the ones with a lower priority.
+    ///
+    /// The default value is 0.
+    pub priority: i32,
+}
+
+impl Default for ColorGradingVolume {
+    fn default() -> Self {
+        Self {
+            color_grading: ColorGrading::default(),
+            blend_distance: 1.0,
+            weight: 1.0,
+            priority: 0,
+        }
+    }
+}
+
+impl ColorGradingVolume {
+    /// Returns the influence of the volume at `position`, from 0.0 to
+    /// [`weight`](Self::weight).
+    pub fn weight_at(&self, transform: &GlobalTransform, position: Vec3A) -> f32 {
+        let affine = transform.affine();
+        let local = affine.inverse().transform_point3a(position);
+        let closest = affine.transform_point3a(local.clamp(Vec3A::splat(-0.5), Vec3A::splat(0.5)));
+        let distance = position.distance(closest);
+        let falloff = if distance == 0.0 {
+            1.0
+        } else if self.blend_distance > 0.0 {
+            1.0 - (distance / self.blend_distance).min(1.0)
+        } else {
+            0.0
+        };
+        falloff * self.weight.clamp(0.0, 1.0)
+    }
+}
+
+/// The [`ColorGrading`] of a camera outside of all [`ColorGradingVolume`]s.
+///
+/// Adding this component to a camera makes its [`ColorGrading`] component
+/// driven by the volumes: it's overwritten every frame with this base grading,
+/// blended with the volumes affecting the camera.
+#[derive(Clone, Component, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(ColorGrading)]
+pub struct BaseColorGrading(pub ColorGrading);
+
+/// Blends the [`ColorGrading`] of cameras with a [`BaseColorGrading`] from the
+/// [`ColorGradingVolume`]s affecting them.
+pub fn blend_color_grading_volumes(
+    mut cameras: Query<(&GlobalTransform, &BaseColorGrading, &mut ColorGrading)>,
+    volumes: Query<(&ColorGradingVolume, &GlobalTransform)>,
+) {
+    let mut sorted_volumes: Vec<_> = volumes.iter().collect();
+    sorted_volumes.sort_by_key(|(volume, _)| volume.priority);
+
+    for (camera_transform, base, mut color_grading) in &mut cameras {
+        let position = camera_transform.translation_vec3a();
+        let mut blended = base.0.clone();
+        for (volume, volume_transform) in &sorted_volumes {
+            let weight = volume.weight_at(volume_transform, position);
+            if weight > 0.0 {
+                blended = blended.lerp(&volume.color_grading, weight);
+            }
+        }
+        *color_grading = blended;
+    }
+}
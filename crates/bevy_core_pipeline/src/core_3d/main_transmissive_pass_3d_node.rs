@@ -2,7 +2,7 @@ use super::{Camera3d, ViewTransmissionTexture};
 use crate::core_3d::Transmissive3d;
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, ViewRenderScale},
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_phase::ViewSortedRenderPhases,
     render_resource::{Extent3d, RenderPassDescriptor, StoreOp},
@@ -27,13 +27,16 @@ impl ViewNode for MainTransmissivePass3dNode {
         &'static ViewTarget,
         Option<&'static ViewTransmissionTexture>,
         &'static ViewDepthTexture,
+        Option<&'static ViewRenderScale>,
     );
 
     fn run(
         &self,
         graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (camera, view, camera_3d, target, transmission, depth): QueryItem<Self::ViewQuery>,
+        (camera, view, camera_3d, target, transmission, depth, render_scale): QueryItem<
+            Self::ViewQuery,
+        >,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.view_entity();
@@ -95,8 +98,8 @@ impl ViewNode for MainTransmissivePass3dNode {
                     let mut render_pass =
                         render_context.begin_tracked_render_pass(render_pass_descriptor.clone());
 
-                    if let Some(viewport) = camera.viewport.as_ref() {
-                        render_pass.set_camera_viewport(viewport);
+                    if let Some(viewport) = camera.main_pass_viewport(render_scale) {
+                        render_pass.set_camera_viewport(&viewport);
                     }
 
                     // render items in range
@@ -110,8 +113,8 @@ impl ViewNode for MainTransmissivePass3dNode {
                 let mut render_pass =
                     render_context.begin_tracked_render_pass(render_pass_descriptor);
 
-                if let Some(viewport) = camera.viewport.as_ref() {
-                    render_pass.set_camera_viewport(viewport);
+                if let Some(viewport) = camera.main_pass_viewport(render_scale) {
+                    render_pass.set_camera_viewport(&viewport);
                 }
 
                 if let Err(err) = transmissive_phase.render(&mut render_pass, world, view_entity) {
@@ -26,6 +26,7 @@ pub mod graph {
         MainOpaquePass,
         MainTransmissivePass,
         MainTransparentPass,
+        MainPassUpscaling,
         EndMainPass,
         LateDownsampleDepth,
         Taa,
@@ -117,6 +118,7 @@ use crate::{
         DEFERRED_PREPASS_FORMAT,
     },
     dof::DepthOfFieldNode,
+    main_pass_upscaling::MainPassUpscalingNode,
     prepass::{
         node::{EarlyPrepassNode, LatePrepassNode},
         AlphaMask3dPrepass, DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass,
@@ -201,6 +203,10 @@ impl Plugin for Core3dPlugin {
                 Core3d,
                 Node3d::MainTransparentPass,
             )
+            .add_render_graph_node::<ViewNodeRunner<MainPassUpscalingNode>>(
+                Core3d,
+                Node3d::MainPassUpscaling,
+            )
             .add_render_graph_node::<EmptyNode>(Core3d, Node3d::EndMainPass)
             .add_render_graph_node::<ViewNodeRunner<DepthOfFieldNode>>(Core3d, Node3d::DepthOfField)
             .add_render_graph_node::<ViewNodeRunner<TonemappingNode>>(Core3d, Node3d::Tonemapping)
@@ -218,6 +224,7 @@ impl Plugin for Core3dPlugin {
                     Node3d::MainOpaquePass,
                     Node3d::MainTransmissivePass,
                     Node3d::MainTransparentPass,
+                    Node3d::MainPassUpscaling,
                     Node3d::EndMainPass,
                     Node3d::Tonemapping,
                     Node3d::EndMainPassPostProcessing,
@@ -3,7 +3,7 @@ use bevy_render::render_graph::ViewNode;
 
 use bevy_render::view::ExtractedView;
 use bevy_render::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, ViewRenderScale},
     render_graph::{NodeRunError, RenderGraphContext},
     render_phase::{TrackedRenderPass, ViewBinnedRenderPhases},
     render_resource::{CommandEncoderDescriptor, RenderPassDescriptor, StoreOp},
@@ -30,13 +30,14 @@ impl ViewNode for DeferredGBufferPrepassNode {
         &'static ExtractedView,
         &'static ViewDepthTexture,
         &'static ViewPrepassTextures,
+        Option<&'static ViewRenderScale>,
     );
 
     fn run<'w>(
         &self,
         graph: &mut RenderGraphContext,
         render_context: &mut RenderContext<'w>,
-        (camera, extracted_view, view_depth_texture, view_prepass_textures): QueryItem<
+        (camera, extracted_view, view_depth_texture, view_prepass_textures, render_scale): QueryItem<
             'w,
             Self::ViewQuery,
         >,
@@ -142,8 +143,8 @@ impl ViewNode for DeferredGBufferPrepassNode {
                 occlusion_query_set: None,
             });
             let mut render_pass = TrackedRenderPass::new(&render_device, render_pass);
-            if let Some(viewport) = camera.viewport.as_ref() {
-                render_pass.set_camera_viewport(viewport);
+            if let Some(viewport) = camera.main_pass_viewport(render_scale) {
+                render_pass.set_camera_viewport(&viewport);
             }
 
             // Opaque draws
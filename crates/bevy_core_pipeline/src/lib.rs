@@ -6,9 +6,11 @@
     html_favicon_url = "https://bevyengine.org/assets/icon.png"
 )]
 
+pub mod anti_aliasing;
 pub mod auto_exposure;
 pub mod blit;
 pub mod bloom;
+pub mod color_grading;
 pub mod contrast_adaptive_sharpening;
 pub mod core_2d;
 pub mod core_3d;
@@ -17,6 +19,7 @@ pub mod dof;
 pub mod experimental;
 pub mod fullscreen_vertex_shader;
 pub mod fxaa;
+pub mod main_pass_upscaling;
 pub mod motion_blur;
 pub mod msaa_writeback;
 pub mod oit;
@@ -39,8 +42,10 @@ pub mod prelude {
 }
 
 use crate::{
+    anti_aliasing::AntiAliasingPlugin,
     blit::BlitPlugin,
     bloom::BloomPlugin,
+    color_grading::ColorGradingVolumePlugin,
     contrast_adaptive_sharpening::CasPlugin,
     core_2d::Core2dPlugin,
     core_3d::Core3dPlugin,
@@ -49,6 +54,7 @@ use crate::{
     experimental::mip_generation::MipGenerationPlugin,
     fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE,
     fxaa::FxaaPlugin,
+    main_pass_upscaling::MainPassUpscalingPlugin,
     motion_blur::MotionBlurPlugin,
     msaa_writeback::MsaaWritebackPlugin,
     post_process::PostProcessingPlugin,
@@ -84,7 +90,9 @@ impl Plugin for CorePipelinePlugin {
                 MsaaWritebackPlugin,
                 TonemappingPlugin,
                 UpscalingPlugin,
+                MainPassUpscalingPlugin,
                 BloomPlugin,
+                ColorGradingVolumePlugin,
                 FxaaPlugin,
                 CasPlugin,
                 MotionBlurPlugin,
@@ -93,6 +101,7 @@ impl Plugin for CorePipelinePlugin {
                 PostProcessingPlugin,
                 OrderIndependentTransparencyPlugin,
                 MipGenerationPlugin,
-            ));
+            ))
+            .add_plugins(AntiAliasingPlugin);
     }
 }
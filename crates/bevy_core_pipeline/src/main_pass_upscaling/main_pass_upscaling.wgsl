@@ -0,0 +1,22 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct MainPassUpscalingUniform {
+    uv_offset: vec2<f32>,
+    uv_scale: vec2<f32>,
+};
+
+@group(0) @binding(0) var source_texture: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var<uniform> upscaling: MainPassUpscalingUniform;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Keep the bilinear filter within the area rendered by the main passes.
+    let half_texel = 0.5 / vec2<f32>(textureDimensions(source_texture));
+    let uv = clamp(
+        upscaling.uv_offset + in.uv * upscaling.uv_scale,
+        upscaling.uv_offset + half_texel,
+        upscaling.uv_offset + upscaling.uv_scale - half_texel,
+    );
+    return textureSample(source_texture, source_sampler, uv);
+}
@@ -0,0 +1,233 @@
+//! Upscales the main passes of 3D cameras rendered at a lower resolution with a [`RenderScale`].
+//!
+//! The main passes render to the top left of the viewport of the camera, and the
+//! [`Node3d::MainPassUpscaling`] node upscales the result to the whole viewport, before
+//! post-processing. By default, the upscaling is bilinear.
+//!
+//! To use another upscaler, like a temporal upsampler, add [`CustomMainPassUpscaler`] to the
+//! camera and add a render graph node between [`Node3d::MainTransparentPass`] and
+//! [`Node3d::MainPassUpscaling`]. The node can read the [`ViewRenderScale`] of the view to find
+//! the area rendered by the main passes, and must write the upscaled image to the whole viewport
+//! of the main texture, for example with [`ViewTarget::post_process_write`].
+//!
+//! The effects running after the main passes which read the depth or prepass textures, like
+//! temporal anti-aliasing, screen space ambient occlusion and screen space reflections, aren't
+//! aware of the render scale yet.
+//!
+//! [`Node3d::MainPassUpscaling`]: crate::core_3d::graph::Node3d::MainPassUpscaling
+//! [`Node3d::MainTransparentPass`]: crate::core_3d::graph::Node3d::MainTransparentPass
+//! [`ViewRenderScale`]: bevy_render::camera::ViewRenderScale
+
+use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_ecs::prelude::*;
+use bevy_image::BevyDefault as _;
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, RenderScale},
+    extract_component::UniformComponentPlugin,
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        *,
+    },
+    renderer::RenderDevice,
+    sync_world::RenderEntity,
+    view::{ExtractedView, ViewTarget},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+mod node;
+
+pub use node::MainPassUpscalingNode;
+
+const MAIN_PASS_UPSCALING_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3c7d5b0e-8a41-4f6b-9e2d-71c0f4a8b953");
+
+/// Disables the default upscaling of the main passes of a camera with a [`RenderScale`], so that
+/// another upscaler can replace it.
+///
+/// See the [module docs](self) for how to add an upscaler.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct CustomMainPassUpscaler;
+
+/// Adds the default upscaling of the main passes of 3D cameras with a [`RenderScale`].
+pub struct MainPassUpscalingPlugin;
+
+impl Plugin for MainPassUpscalingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            MAIN_PASS_UPSCALING_SHADER_HANDLE,
+            "main_pass_upscaling.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<CustomMainPassUpscaler>()
+            .add_plugins(UniformComponentPlugin::<MainPassUpscalingUniform>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<MainPassUpscalingPipeline>>()
+            .add_systems(ExtractSchedule, extract_main_pass_upscaling)
+            .add_systems(
+                Render,
+                prepare_main_pass_upscaling_pipelines.in_set(RenderSet::Prepare),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<MainPassUpscalingPipeline>();
+    }
+}
+
+/// The area of the main texture rendered by the main passes, in UV coordinates.
+#[doc(hidden)]
+#[derive(Component, ShaderType, Clone)]
+pub struct MainPassUpscalingUniform {
+    uv_offset: Vec2,
+    uv_scale: Vec2,
+}
+
+fn extract_main_pass_upscaling(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<(
+            RenderEntity,
+            &Camera,
+            Option<&RenderScale>,
+            Has<CustomMainPassUpscaler>,
+        )>,
+    >,
+) {
+    for (render_entity, camera, render_scale, custom_upscaler) in &cameras {
+        let uniform = camera
+            .physical_viewport_rect()
+            .zip(camera.physical_target_size())
+            .zip(render_scale)
+            .filter(|_| camera.is_active && !custom_upscaler)
+            .and_then(|((viewport, target_size), render_scale)| {
+                let main_pass_size = render_scale.main_pass_size(viewport.size());
+                (main_pass_size != viewport.size()).then(|| {
+                    let target_size = target_size.as_vec2();
+                    MainPassUpscalingUniform {
+                        uv_offset: viewport.min.as_vec2() / target_size,
+                        uv_scale: main_pass_size.as_vec2() / target_size,
+                    }
+                })
+            });
+
+        match uniform {
+            Some(uniform) => {
+                commands.entity(render_entity).insert(uniform);
+            }
+            None => {
+                commands
+                    .entity(render_entity)
+                    .remove::<(MainPassUpscalingUniform, ViewMainPassUpscalingPipeline)>();
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct MainPassUpscalingPipeline {
+    bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for MainPassUpscalingPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let bind_group_layout = render_device.create_bind_group_layout(
+            "main_pass_upscaling_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<MainPassUpscalingUniform>(true),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        MainPassUpscalingPipeline {
+            bind_group_layout,
+            sampler,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct MainPassUpscalingPipelineKey {
+    texture_format: TextureFormat,
+}
+
+impl SpecializedRenderPipeline for MainPassUpscalingPipeline {
+    type Key = MainPassUpscalingPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("main_pass_upscaling".into()),
+            layout: vec![self.bind_group_layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: MAIN_PASS_UPSCALING_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+fn prepare_main_pass_upscaling_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<MainPassUpscalingPipeline>>,
+    upscaling_pipeline: Res<MainPassUpscalingPipeline>,
+    views: Query<(Entity, &ExtractedView), With<MainPassUpscalingUniform>>,
+) {
+    for (entity, view) in &views {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &upscaling_pipeline,
+            MainPassUpscalingPipelineKey {
+                texture_format: if view.hdr {
+                    ViewTarget::TEXTURE_FORMAT_HDR
+                } else {
+                    TextureFormat::bevy_default()
+                },
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewMainPassUpscalingPipeline(pipeline_id));
+    }
+}
+
+#[derive(Component)]
+pub struct ViewMainPassUpscalingPipeline(CachedRenderPipelineId);
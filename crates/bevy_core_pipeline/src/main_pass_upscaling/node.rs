@@ -0,0 +1,106 @@
+use std::sync::Mutex;
+
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_render::{
+    camera::ExtractedCamera,
+    extract_component::{ComponentUniforms, DynamicUniformIndex},
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        BindGroup, BindGroupEntries, Buffer, BufferId, LoadOp, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor, StoreOp, TextureViewId,
+    },
+    renderer::RenderContext,
+    view::ViewTarget,
+};
+
+use super::{MainPassUpscalingPipeline, MainPassUpscalingUniform, ViewMainPassUpscalingPipeline};
+
+/// Upscales the area rendered by the main passes of a view with a render scale to its whole
+/// viewport.
+#[derive(Default)]
+pub struct MainPassUpscalingNode {
+    cached_bind_group: Mutex<Option<(BufferId, TextureViewId, BindGroup)>>,
+}
+
+impl ViewNode for MainPassUpscalingNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ViewMainPassUpscalingPipeline,
+        &'static DynamicUniformIndex<MainPassUpscalingUniform>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, target, pipeline, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let upscaling_pipeline = world.resource::<MainPassUpscalingPipeline>();
+        let uniforms = world.resource::<ComponentUniforms<MainPassUpscalingUniform>>();
+
+        let Some(uniforms_id) = uniforms.buffer().map(Buffer::id) else {
+            return Ok(());
+        };
+        let Some(uniforms) = uniforms.binding() else {
+            return Ok(());
+        };
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline.0) else {
+            return Ok(());
+        };
+
+        let post_process = target.post_process_write();
+        let source = post_process.source;
+        let destination = post_process.destination;
+
+        let mut cached_bind_group = self.cached_bind_group.lock().unwrap();
+        let bind_group = match &mut *cached_bind_group {
+            Some((buffer_id, texture_id, bind_group))
+                if source.id() == *texture_id && uniforms_id == *buffer_id =>
+            {
+                bind_group
+            }
+            cached_bind_group => {
+                let bind_group = render_context.render_device().create_bind_group(
+                    "main_pass_upscaling_bind_group",
+                    &upscaling_pipeline.bind_group_layout,
+                    &BindGroupEntries::sequential((source, &upscaling_pipeline.sampler, uniforms)),
+                );
+
+                let (_, _, bind_group) =
+                    cached_bind_group.insert((uniforms_id, source.id(), bind_group));
+                bind_group
+            }
+        };
+
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("main_pass_upscaling"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: destination,
+                resolve_target: None,
+                // Only the viewport is written, the rest of the texture is kept for the
+                // following cameras rendering to the same target.
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(pass_descriptor);
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -135,6 +135,7 @@ fn prepare_msaa_writeback_pipelines(
                 texture_format: view_target.main_texture_format(),
                 samples: msaa.samples(),
                 blend_state: None,
+                hdr_output: None,
             };
 
             let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
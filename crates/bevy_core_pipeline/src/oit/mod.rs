@@ -135,7 +135,7 @@ impl Plugin for OrderIndependentTransparencyPlugin {
                 (
                     Node3d::MainTransparentPass,
                     OitResolvePass,
-                    Node3d::EndMainPass,
+                    Node3d::MainPassUpscaling,
                 ),
             );
     }
@@ -17,11 +17,14 @@
 //! likely want set [`bevy_render::view::Msaa`] to [`bevy_render::view::Msaa::Off`]
 //! for every camera using SMAA.
 //!
+//! The temporal variant, SMAA T2x, can be enabled with [`SmaaMode::T2x`] on 3D
+//! cameras. It jitters the camera every other frame and blends each frame with
+//! the previous one, which also smooths out the aliasing that SMAA alone can't
+//! detect, such as subpixel features, at the cost of some blurring in motion.
+//!
 //! Those who have used SMAA in other engines should be aware that Bevy doesn't
 //! yet support the following more advanced features of SMAA:
 //!
-//! * The temporal variant.
-//!
 //! * Depth- and chroma-based edge detection.
 //!
 //! * Predicated thresholding.
@@ -34,16 +37,18 @@ use crate::tonemapping::lut_placeholder;
 use crate::{
     core_2d::graph::{Core2d, Node2d},
     core_3d::graph::{Core3d, Node3d},
+    prepass::{MotionVectorPrepass, ViewPrepassTextures},
 };
 use bevy_app::{App, Plugin};
 #[cfg(feature = "smaa_luts")]
 use bevy_asset::load_internal_binary_asset;
 use bevy_asset::{load_internal_asset, weak_handle, Handle};
 use bevy_derive::{Deref, DerefMut};
+use bevy_diagnostic::FrameCount;
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::{QueryItem, With},
+    query::{Has, QueryItem, With},
     reflect::ReflectComponent,
     resource::Resource,
     schedule::IntoSystemConfigs as _,
@@ -51,10 +56,10 @@ use bevy_ecs::{
     world::{FromWorld, World},
 };
 use bevy_image::{BevyDefault, Image};
-use bevy_math::{vec4, Vec4};
+use bevy_math::{vec4, Vec2, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, TemporalJitter},
     extract_component::{ExtractComponent, ExtractComponentPlugin},
     render_asset::RenderAssets,
     render_graph::{
@@ -75,7 +80,7 @@ use bevy_render::{
     },
     renderer::{RenderContext, RenderDevice, RenderQueue},
     texture::{CachedTexture, GpuImage, TextureCache},
-    view::{ExtractedView, ViewTarget},
+    view::{ExtractedView, Msaa, ViewTarget},
     Render, RenderApp, RenderSet,
 };
 use bevy_utils::prelude::default;
@@ -102,6 +107,10 @@ pub struct Smaa {
     ///
     /// Generally, you can leave this at its default level.
     pub preset: SmaaPreset,
+
+    /// Whether each frame is anti-aliased on its own, or blended with the
+    /// previous one.
+    pub mode: SmaaMode,
 }
 
 /// A preset quality level for SMAA.
@@ -128,9 +137,44 @@ pub enum SmaaPreset {
     Ultra,
 }
 
+/// The number of subsamples SMAA combines for each pixel.
+///
+/// The default value is *1x*.
+#[derive(Clone, Copy, Reflect, Default, PartialEq, Eq, Hash)]
+#[reflect(Default)]
+pub enum SmaaMode {
+    /// Anti-aliases each frame on its own.
+    ///
+    /// This is the default.
+    #[default]
+    Smaa1x,
+
+    /// Jitters the camera by a quarter of a pixel in alternating directions
+    /// every frame, and blends each anti-aliased frame with the previous one,
+    /// reprojected with motion vectors.
+    ///
+    /// This requires a 3D camera with a perspective projection, a
+    /// [`TemporalJitter`] and a [`MotionVectorPrepass`]. SMAA falls back to
+    /// [`SmaaMode::Smaa1x`] when any of them is missing. SMAA T2x can't be
+    /// combined with temporal anti-aliasing, as both jitter the camera.
+    #[doc(alias = "temporal")]
+    T2x,
+}
+
+/// The camera jitter and the subsample indices of the two frames of SMAA T2x,
+/// as given in the `@SUBSAMPLE_INDICES` table of `smaa.wgsl`.
+///
+/// The table assumes a bottom-to-top y axis, so the y jitters are flipped for
+/// [`TemporalJitter`].
+const SMAA_T2X_SUBSAMPLES: [(Vec2, Vec4); 2] = [
+    (Vec2::new(0.25, 0.25), Vec4::new(1.0, 1.0, 1.0, 0.0)),
+    (Vec2::new(-0.25, -0.25), Vec4::new(2.0, 2.0, 2.0, 0.0)),
+];
+
 /// A render world resource that holds all render pipeline data needed for SMAA.
 ///
-/// There are three separate passes, so we need three separate pipelines.
+/// There are three separate passes, so we need three separate pipelines, plus
+/// a fourth one for the temporal resolve of SMAA T2x.
 #[derive(Resource)]
 pub struct SmaaPipelines {
     /// Pass 1: Edge detection.
@@ -139,6 +183,8 @@ pub struct SmaaPipelines {
     blending_weight_calculation: SmaaBlendingWeightCalculationPipeline,
     /// Pass 3: Neighborhood blending.
     neighborhood_blending: SmaaNeighborhoodBlendingPipeline,
+    /// Pass 4, for SMAA T2x only: Temporal resolve.
+    resolve: SmaaResolvePipeline,
 }
 
 /// The pipeline data for phase 1 of SMAA: edge detection.
@@ -165,6 +211,14 @@ struct SmaaNeighborhoodBlendingPipeline {
     neighborhood_blending_bind_group_layout: BindGroupLayout,
 }
 
+/// The pipeline data for the temporal resolve of SMAA T2x.
+struct SmaaResolvePipeline {
+    /// The bind group layout common to all passes.
+    postprocess_bind_group_layout: BindGroupLayout,
+    /// The bind group layout for data specific to this pass.
+    resolve_bind_group_layout: BindGroupLayout,
+}
+
 /// A unique identifier for a set of SMAA pipelines.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct SmaaNeighborhoodBlendingPipelineKey {
@@ -186,6 +240,8 @@ pub struct ViewSmaaPipelines {
     blending_weight_calculation_pipeline_id: CachedRenderPipelineId,
     /// The pipeline ID for neighborhood blending (phase 3).
     neighborhood_blending_pipeline_id: CachedRenderPipelineId,
+    /// The pipeline ID for the temporal resolve, if SMAA T2x is used.
+    resolve_pipeline_id: Option<CachedRenderPipelineId>,
 }
 
 /// The render graph node that performs subpixel morphological antialiasing
@@ -195,10 +251,10 @@ pub struct SmaaNode;
 
 /// Values supplied to the GPU for SMAA.
 ///
-/// Currently, this just contains the render target metrics and values derived
-/// from them. These could be computed by the shader itself, but the original
-/// SMAA HLSL code supplied them in a uniform, so we do the same for
-/// consistency.
+/// This contains the render target metrics and values derived from them, and
+/// the subsample indices of the current frame. The metrics could be computed by
+/// the shader itself, but the original SMAA HLSL code supplied them in a
+/// uniform, so we do the same for consistency.
 #[derive(Clone, Copy, ShaderType)]
 pub struct SmaaInfoUniform {
     /// Information about the width and height of the framebuffer.
@@ -211,6 +267,12 @@ pub struct SmaaInfoUniform {
     ///
     /// * *w*: The pixel height of the framebuffer.
     pub rt_metrics: Vec4,
+
+    /// The indices selecting the area texture lookups for the subsample
+    /// rendered this frame, for SMAA T2x.
+    ///
+    /// This is zero for SMAA 1x.
+    pub subsample_indices: Vec4,
 }
 
 /// A render world component that stores the offset of each [`SmaaInfoUniform`]
@@ -252,6 +314,18 @@ pub struct SmaaTextures {
     pub blend_texture: CachedTexture,
 }
 
+/// A render world component that holds the anti-aliased frames of SMAA T2x.
+///
+/// The temporal resolve reads the previous frame from one texture, and writes
+/// the current frame to the other one. They're swapped every frame.
+#[derive(Component)]
+pub struct SmaaHistoryTextures {
+    /// The texture the current frame is written to.
+    pub write: CachedTexture,
+    /// The texture holding the previous frame.
+    pub read: CachedTexture,
+}
+
 /// A render world component that stores the bind groups necessary to perform
 /// SMAA.
 ///
@@ -264,6 +338,8 @@ pub struct SmaaBindGroups {
     pub blending_weight_calculation_bind_group: BindGroup,
     /// The bind group for the final pass (neighborhood blending).
     pub neighborhood_blending_bind_group: BindGroup,
+    /// The bind group for the temporal resolve, if SMAA T2x is used.
+    pub resolve_bind_group: Option<BindGroup>,
 }
 
 /// Stores the specialized render pipelines for SMAA.
@@ -282,6 +358,9 @@ pub struct SmaaSpecializedRenderPipelines {
     /// Specialized render pipelines for the third phase (neighborhood
     /// blending).
     neighborhood_blending: SpecializedRenderPipelines<SmaaNeighborhoodBlendingPipeline>,
+
+    /// Specialized render pipelines for the temporal resolve of SMAA T2x.
+    resolve: SpecializedRenderPipelines<SmaaResolvePipeline>,
 }
 
 impl Plugin for SmaaPlugin {
@@ -350,9 +429,11 @@ impl Plugin for SmaaPlugin {
             .add_systems(
                 Render,
                 (
+                    prepare_smaa_jitter.in_set(RenderSet::ManageViews),
                     prepare_smaa_pipelines.in_set(RenderSet::Prepare),
                     prepare_smaa_uniforms.in_set(RenderSet::PrepareResources),
                     prepare_smaa_textures.in_set(RenderSet::PrepareResources),
+                    prepare_smaa_history_textures.in_set(RenderSet::PrepareResources),
                     prepare_smaa_bind_groups.in_set(RenderSet::PrepareBindGroups),
                 ),
             )
@@ -435,6 +516,19 @@ impl FromWorld for SmaaPipelines {
             ),
         );
 
+        // Create the resolve bind group layout (pass 4, bind group 1).
+        let resolve_bind_group_layout = render_device.create_bind_group_layout(
+            "SMAA resolve bind group layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }), // history texture
+                    texture_2d(TextureSampleType::Float { filterable: true }), // motion vectors
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
         SmaaPipelines {
             edge_detection: SmaaEdgeDetectionPipeline {
                 postprocess_bind_group_layout: postprocess_bind_group_layout.clone(),
@@ -445,9 +539,13 @@ impl FromWorld for SmaaPipelines {
                 blending_weight_calculation_bind_group_layout,
             },
             neighborhood_blending: SmaaNeighborhoodBlendingPipeline {
-                postprocess_bind_group_layout,
+                postprocess_bind_group_layout: postprocess_bind_group_layout.clone(),
                 neighborhood_blending_bind_group_layout,
             },
+            resolve: SmaaResolvePipeline {
+                postprocess_bind_group_layout,
+                resolve_bind_group_layout,
+            },
         }
     }
 }
@@ -608,16 +706,98 @@ impl SpecializedRenderPipeline for SmaaNeighborhoodBlendingPipeline {
     }
 }
 
-/// A system, part of the render app, that specializes the three pipelines
-/// needed for SMAA according to each view's SMAA settings.
+// Phase 4, for SMAA T2x only: temporal resolve.
+impl SpecializedRenderPipeline for SmaaResolvePipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, texture_format: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = vec!["SMAA_RESOLVE".into()];
+
+        // The current frame is written both to the view target and to the
+        // history, for the next frame to blend with.
+        let target = ColorTargetState {
+            format: texture_format,
+            blend: None,
+            write_mask: ColorWrites::ALL,
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("SMAA resolve".into()),
+            layout: vec![
+                self.postprocess_bind_group_layout.clone(),
+                self.resolve_bind_group_layout.clone(),
+            ],
+            vertex: VertexState {
+                shader: SMAA_SHADER_HANDLE,
+                shader_defs: shader_defs.clone(),
+                entry_point: "resolve_vertex_main".into(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: SMAA_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "resolve_fragment_main".into(),
+                targets: vec![Some(target.clone()), Some(target)],
+            }),
+            push_constant_ranges: vec![],
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl Smaa {
+    /// Returns true if SMAA T2x is used on a view, which requires it to be
+    /// jittered and to have single-sampled motion vectors.
+    fn is_temporal(&self, jittered: bool, has_motion_vectors: bool, msaa: &Msaa) -> bool {
+        self.mode == SmaaMode::T2x && jittered && has_motion_vectors && *msaa == Msaa::Off
+    }
+}
+
+/// Returns the camera jitter and subsample indices of the SMAA T2x subsample
+/// rendered on the given frame.
+fn smaa_t2x_subsample(frame_count: &FrameCount) -> (Vec2, Vec4) {
+    SMAA_T2X_SUBSAMPLES[frame_count.0 as usize % SMAA_T2X_SUBSAMPLES.len()]
+}
+
+/// A system, part of the render app, that jitters the views using SMAA T2x.
+fn prepare_smaa_jitter(
+    frame_count: Res<FrameCount>,
+    mut views: Query<(&Smaa, &mut TemporalJitter, Has<MotionVectorPrepass>, &Msaa)>,
+) {
+    let (offset, _) = smaa_t2x_subsample(&frame_count);
+    for (smaa, mut jitter, motion_vector_prepass, msaa) in &mut views {
+        if smaa.is_temporal(true, motion_vector_prepass, msaa) {
+            jitter.offset = offset;
+        }
+    }
+}
+
+/// A system, part of the render app, that specializes the pipelines needed for
+/// SMAA according to each view's SMAA settings.
 fn prepare_smaa_pipelines(
     mut commands: Commands,
     pipeline_cache: Res<PipelineCache>,
     mut specialized_render_pipelines: ResMut<SmaaSpecializedRenderPipelines>,
     smaa_pipelines: Res<SmaaPipelines>,
-    view_targets: Query<(Entity, &ExtractedView, &Smaa)>,
+    view_targets: Query<(
+        Entity,
+        &ExtractedView,
+        &Smaa,
+        Has<TemporalJitter>,
+        Has<MotionVectorPrepass>,
+        &Msaa,
+    )>,
 ) {
-    for (entity, view, smaa) in &view_targets {
+    for (entity, view, smaa, jittered, motion_vector_prepass, msaa) in &view_targets {
+        let texture_format = if view.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
         let edge_detection_pipeline_id = specialized_render_pipelines.edge_detection.specialize(
             &pipeline_cache,
             &smaa_pipelines.edge_detection,
@@ -638,19 +818,26 @@ fn prepare_smaa_pipelines(
                 &pipeline_cache,
                 &smaa_pipelines.neighborhood_blending,
                 SmaaNeighborhoodBlendingPipelineKey {
-                    texture_format: if view.hdr {
-                        ViewTarget::TEXTURE_FORMAT_HDR
-                    } else {
-                        TextureFormat::bevy_default()
-                    },
+                    texture_format,
                     preset: smaa.preset,
                 },
             );
 
+        let resolve_pipeline_id = smaa
+            .is_temporal(jittered, motion_vector_prepass, msaa)
+            .then(|| {
+                specialized_render_pipelines.resolve.specialize(
+                    &pipeline_cache,
+                    &smaa_pipelines.resolve,
+                    texture_format,
+                )
+            });
+
         commands.entity(entity).insert(ViewSmaaPipelines {
             edge_detection_pipeline_id,
             blending_weight_calculation_pipeline_id,
             neighborhood_blending_pipeline_id,
+            resolve_pipeline_id,
         });
     }
 }
@@ -661,11 +848,24 @@ fn prepare_smaa_uniforms(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
-    view_targets: Query<(Entity, &ExtractedView), With<Smaa>>,
+    frame_count: Res<FrameCount>,
+    view_targets: Query<(
+        Entity,
+        &ExtractedView,
+        &Smaa,
+        Has<TemporalJitter>,
+        Has<MotionVectorPrepass>,
+        &Msaa,
+    )>,
     mut smaa_info_buffer: ResMut<SmaaInfoUniformBuffer>,
 ) {
     smaa_info_buffer.clear();
-    for (entity, view) in &view_targets {
+    for (entity, view, smaa, jittered, motion_vector_prepass, msaa) in &view_targets {
+        let subsample_indices = if smaa.is_temporal(jittered, motion_vector_prepass, msaa) {
+            smaa_t2x_subsample(&frame_count).1
+        } else {
+            Vec4::ZERO
+        };
         let offset = smaa_info_buffer.push(&SmaaInfoUniform {
             rt_metrics: vec4(
                 1.0 / view.viewport.z as f32,
@@ -673,6 +873,7 @@ fn prepare_smaa_uniforms(
                 view.viewport.z as f32,
                 view.viewport.w as f32,
             ),
+            subsample_indices,
         });
         commands
             .entity(entity)
@@ -759,6 +960,68 @@ fn prepare_smaa_textures(
     }
 }
 
+/// A system, part of the render app, that builds the history textures for each
+/// view using SMAA T2x.
+fn prepare_smaa_history_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    frame_count: Res<FrameCount>,
+    view_targets: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ExtractedView,
+        &Smaa,
+        Has<TemporalJitter>,
+        Has<MotionVectorPrepass>,
+        &Msaa,
+    )>,
+) {
+    for (entity, camera, view, smaa, jittered, motion_vector_prepass, msaa) in &view_targets {
+        let Some(texture_size) = camera.physical_target_size else {
+            continue;
+        };
+        if !smaa.is_temporal(jittered, motion_vector_prepass, msaa) {
+            commands.entity(entity).remove::<SmaaHistoryTextures>();
+            continue;
+        }
+
+        let mut texture_descriptor = TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: texture_size.x,
+                height: texture_size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: if view.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            },
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        };
+
+        texture_descriptor.label = Some("SMAA history 1 texture");
+        let history_1_texture = texture_cache.get(&render_device, texture_descriptor.clone());
+
+        texture_descriptor.label = Some("SMAA history 2 texture");
+        let history_2_texture = texture_cache.get(&render_device, texture_descriptor);
+
+        let (write, read) = if frame_count.0 % 2 == 0 {
+            (history_1_texture, history_2_texture)
+        } else {
+            (history_2_texture, history_1_texture)
+        };
+        commands
+            .entity(entity)
+            .insert(SmaaHistoryTextures { write, read });
+    }
+}
+
 /// A system, part of the render app, that builds the SMAA bind groups for each
 /// view with SMAA enabled.
 fn prepare_smaa_bind_groups(
@@ -766,7 +1029,15 @@ fn prepare_smaa_bind_groups(
     render_device: Res<RenderDevice>,
     smaa_pipelines: Res<SmaaPipelines>,
     images: Res<RenderAssets<GpuImage>>,
-    view_targets: Query<(Entity, &SmaaTextures), (With<ExtractedView>, With<Smaa>)>,
+    view_targets: Query<
+        (
+            Entity,
+            &SmaaTextures,
+            Option<&SmaaHistoryTextures>,
+            Option<&ViewPrepassTextures>,
+        ),
+        (With<ExtractedView>, With<Smaa>),
+    >,
 ) {
     // Fetch the two lookup textures. These are bundled in this library.
     let (Some(search_texture), Some(area_texture)) = (
@@ -776,7 +1047,7 @@ fn prepare_smaa_bind_groups(
         return;
     };
 
-    for (entity, smaa_textures) in &view_targets {
+    for (entity, smaa_textures, history_textures, prepass_textures) in &view_targets {
         // We use the same sampler settings for all textures, so we can build
         // only one and reuse it.
         let sampler = render_device.create_sampler(&SamplerDescriptor {
@@ -789,6 +1060,22 @@ fn prepare_smaa_bind_groups(
             ..default()
         });
 
+        // The temporal resolve needs both the previous frame and the motion
+        // vectors to reproject it.
+        let resolve_bind_group = history_textures
+            .zip(prepass_textures.and_then(ViewPrepassTextures::motion_vectors_view))
+            .map(|(history_textures, motion_vectors)| {
+                render_device.create_bind_group(
+                    Some("SMAA resolve bind group"),
+                    &smaa_pipelines.resolve.resolve_bind_group_layout,
+                    &BindGroupEntries::sequential((
+                        &history_textures.read.default_view,
+                        motion_vectors,
+                        &sampler,
+                    )),
+                )
+            });
+
         commands.entity(entity).insert(SmaaBindGroups {
             edge_detection_bind_group: render_device.create_bind_group(
                 Some("SMAA edge detection bind group"),
@@ -819,6 +1106,7 @@ fn prepare_smaa_bind_groups(
                     &sampler,
                 )),
             ),
+            resolve_bind_group,
         });
     }
 }
@@ -830,6 +1118,7 @@ impl ViewNode for SmaaNode {
         Read<SmaaInfoUniformOffset>,
         Read<SmaaTextures>,
         Read<SmaaBindGroups>,
+        Option<Read<SmaaHistoryTextures>>,
     );
 
     fn run<'w>(
@@ -842,6 +1131,7 @@ impl ViewNode for SmaaNode {
             view_smaa_uniform_offset,
             smaa_textures,
             view_smaa_bind_groups,
+            history_textures,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
@@ -904,6 +1194,29 @@ impl ViewNode for SmaaNode {
             destination,
         );
 
+        // Stage 4, for SMAA T2x only: Temporal resolve pass.
+        let resolve = view_pipelines
+            .resolve_pipeline_id
+            .and_then(|id| pipeline_cache.get_render_pipeline(id));
+        if let (Some(resolve_pipeline), Some(history_textures), Some(resolve_bind_group)) = (
+            resolve,
+            history_textures,
+            &view_smaa_bind_groups.resolve_bind_group,
+        ) {
+            let postprocess = view_target.post_process_write();
+            perform_resolve(
+                render_context,
+                smaa_pipelines,
+                resolve_bind_group,
+                smaa_info_uniform_buffer,
+                view_smaa_uniform_offset,
+                resolve_pipeline,
+                history_textures,
+                postprocess.source,
+                postprocess.destination,
+            );
+        }
+
         Ok(())
     }
 }
@@ -1072,6 +1385,57 @@ fn perform_neighborhood_blending(
     neighborhood_blending_render_pass.draw(0..3, 0..1);
 }
 
+/// Performs the temporal resolve (phase 4), for SMAA T2x.
+///
+/// This runs as part of the [`SmaaNode`], after neighborhood blending. It
+/// blends the anti-aliased frame with the previous one, read from the history
+/// texture, and writes the anti-aliased frame to the history for the next
+/// frame.
+fn perform_resolve(
+    render_context: &mut RenderContext,
+    smaa_pipelines: &SmaaPipelines,
+    resolve_bind_group: &BindGroup,
+    smaa_info_uniform_buffer: &SmaaInfoUniformBuffer,
+    view_smaa_uniform_offset: &SmaaInfoUniformOffset,
+    resolve_pipeline: &RenderPipeline,
+    history_textures: &SmaaHistoryTextures,
+    source: &TextureView,
+    destination: &TextureView,
+) {
+    let postprocess_bind_group = render_context.render_device().create_bind_group(
+        None,
+        &smaa_pipelines.resolve.postprocess_bind_group_layout,
+        &BindGroupEntries::sequential((source, &**smaa_info_uniform_buffer)),
+    );
+
+    let pass_descriptor = RenderPassDescriptor {
+        label: Some("SMAA resolve pass"),
+        color_attachments: &[
+            Some(RenderPassColorAttachment {
+                view: destination,
+                resolve_target: None,
+                ops: default(),
+            }),
+            Some(RenderPassColorAttachment {
+                view: &history_textures.write.default_view,
+                resolve_target: None,
+                ops: default(),
+            }),
+        ],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    };
+
+    let mut resolve_render_pass = render_context
+        .command_encoder()
+        .begin_render_pass(&pass_descriptor);
+    resolve_render_pass.set_pipeline(resolve_pipeline);
+    resolve_render_pass.set_bind_group(0, &postprocess_bind_group, &[**view_smaa_uniform_offset]);
+    resolve_render_pass.set_bind_group(1, resolve_bind_group, &[]);
+    resolve_render_pass.draw(0..3, 0..1);
+}
+
 impl SmaaPreset {
     /// Returns the `#define` in the shader corresponding to this quality
     /// preset.
@@ -294,6 +294,7 @@
 
 struct SmaaInfo {
     rt_metrics: vec4<f32>,
+    subsample_indices: vec4<f32>,
 }
 
 struct VertexVaryings {
@@ -323,6 +324,16 @@ struct NeighborhoodBlendingVaryings {
     @location(1) tex_coord: vec2<f32>,
 }
 
+struct ResolveVaryings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coord: vec2<f32>,
+}
+
+struct ResolveOutput {
+    @location(0) view_target: vec4<f32>,
+    @location(1) history: vec4<f32>,
+}
+
 @group(0) @binding(0) var color_texture: texture_2d<f32>;
 @group(0) @binding(1) var<uniform> smaa_info: SmaaInfo;
 
@@ -342,6 +353,12 @@ struct NeighborhoodBlendingVaryings {
 @group(1) @binding(1) var blend_sampler: sampler;
 #endif  // SMAA_NEIGHBORHOOD_BLENDING
 
+#ifdef SMAA_RESOLVE
+@group(1) @binding(0) var history_texture: texture_2d<f32>;
+@group(1) @binding(1) var motion_vectors_texture: texture_2d<f32>;
+@group(1) @binding(2) var resolve_sampler: sampler;
+#endif  // SMAA_RESOLVE
+
 //-----------------------------------------------------------------------------
 // SMAA Presets
 
@@ -557,6 +574,19 @@ fn neighborhood_blending_vertex_main(@builtin(vertex_index) vertex_index: u32)
 
 #endif  // SMAA_NEIGHBORHOOD_BLENDING
 
+#ifdef SMAA_RESOLVE
+
+/**
+ * Resolve Vertex Shader
+ */
+@vertex
+fn resolve_vertex_main(@builtin(vertex_index) vertex_index: u32) -> ResolveVaryings {
+    let varyings = calculate_vertex_varyings(vertex_index);
+    return ResolveVaryings(vec4(varyings.clip_coord, 0.0, 1.0), varyings.tex_coord);
+}
+
+#endif  // SMAA_RESOLVE
+
 //-----------------------------------------------------------------------------
 // Edge Detection Pixel Shaders (First Pass)
 
@@ -954,7 +984,7 @@ fn detect_vertical_corner_pattern(weights: vec2<f32>, tex_coord: vec4<f32>, d: v
 @fragment
 fn blending_weight_calculation_fragment_main(in: BlendingWeightCalculationVaryings)
         -> @location(0) vec4<f32> {
-    let subsample_indices = vec4(0.0);  // Just pass zero for SMAA 1x, see @SUBSAMPLE_INDICES.
+    let subsample_indices = smaa_info.subsample_indices;  // Zero for SMAA 1x, see @SUBSAMPLE_INDICES.
 
     var weights = vec4(0.0);
 
@@ -1104,3 +1134,55 @@ fn neighborhood_blending_fragment_main(in: NeighborhoodBlendingVaryings) -> @loc
 }
 
 #endif  // SMAA_NEIGHBORHOOD_BLENDING
+
+#ifdef SMAA_RESOLVE
+
+//-----------------------------------------------------------------------------
+// Temporal Resolve Pixel Shader (Optional Fourth Pass)
+
+/**
+ * Blends the anti-aliased current frame with the previous one, for SMAA T2x.
+ *
+ * Each of the two frames was rendered with a different subpixel jitter, so
+ * averaging them gives two samples per pixel. The previous frame is
+ * reprojected with the motion vectors, and clamped to the colors around the
+ * current pixel to avoid ghosting where the reprojection fails, in place of
+ * the velocity weighting of the original SMAA_REPROJECTION.
+ */
+@fragment
+fn resolve_fragment_main(in: ResolveVaryings) -> ResolveOutput {
+    let current = textureSampleLevel(color_texture, resolve_sampler, in.tex_coord, 0.0);
+
+    // Find the range of colors around the current pixel.
+    var color_min = current.rgb;
+    var color_max = current.rgb;
+    for (var x = -1; x <= 1; x += 1) {
+        for (var y = -1; y <= 1; y += 1) {
+            let offset = vec2(f32(x), f32(y)) * smaa_info.rt_metrics.xy;
+            let color = textureSampleLevel(
+                color_texture,
+                resolve_sampler,
+                in.tex_coord + offset,
+                0.0
+            ).rgb;
+            color_min = min(color_min, color);
+            color_max = max(color_max, color);
+        }
+    }
+
+    // Reproject the previous frame.
+    let motion_vector =
+        textureSampleLevel(motion_vectors_texture, resolve_sampler, in.tex_coord, 0.0).rg;
+    let history_coord = in.tex_coord - motion_vector;
+    let previous = textureSampleLevel(history_texture, resolve_sampler, history_coord, 0.0);
+    let history = vec4(clamp(previous.rgb, color_min, color_max), previous.a);
+
+    // Blend both subsamples equally, unless the previous one was off screen.
+    let on_screen = all(history_coord >= vec2(0.0)) && all(history_coord <= vec2(1.0));
+    let color = mix(current, history, select(0.0, 0.5, on_screen));
+
+    // Only the current subsample is kept for the next frame.
+    return ResolveOutput(color, current);
+}
+
+#endif  // SMAA_RESOLVE
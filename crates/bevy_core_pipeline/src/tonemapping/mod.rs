@@ -15,7 +15,7 @@ use bevy_render::{
     },
     renderer::RenderDevice,
     texture::{FallbackImage, GpuImage},
-    view::{ExtractedView, ViewTarget, ViewUniform},
+    view::{ActiveHdrOutput, ExtractedView, ViewTarget, ViewUniform},
     Render, RenderApp, RenderSet,
 };
 use bitflags::bitflags;
@@ -215,6 +215,7 @@ pub struct TonemappingPipelineKey {
     deband_dither: DebandDither,
     tonemapping: Tonemapping,
     flags: TonemappingPipelineKeyFlags,
+    hdr_output: Option<ActiveHdrOutput>,
 }
 
 impl SpecializedRenderPipeline for TonemappingPipeline {
@@ -236,6 +237,19 @@ impl SpecializedRenderPipeline for TonemappingPipeline {
             shader_defs.push("DEBAND_DITHER".into());
         }
 
+        // Tonemap to the range of the HDR display, relative to SDR white.
+        if let Some(hdr_output) = key.hdr_output {
+            shader_defs.push("HDR_OUTPUT".into());
+            shader_defs.push(ShaderDefVal::UInt(
+                "HDR_OUTPUT_PAPER_WHITE_NITS".into(),
+                hdr_output.paper_white_nits,
+            ));
+            shader_defs.push(ShaderDefVal::UInt(
+                "HDR_OUTPUT_PEAK_NITS".into(),
+                hdr_output.peak_nits,
+            ));
+        }
+
         // Define shader flags depending on the color grading options in use.
         if key.flags.contains(TonemappingPipelineKeyFlags::HUE_ROTATE) {
             shader_defs.push("HUE_ROTATE".into());
@@ -352,17 +366,15 @@ pub fn prepare_view_tonemapping_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<TonemappingPipeline>>,
     upscaling_pipeline: Res<TonemappingPipeline>,
-    view_targets: Query<
-        (
-            Entity,
-            &ExtractedView,
-            Option<&Tonemapping>,
-            Option<&DebandDither>,
-        ),
-        With<ViewTarget>,
-    >,
+    view_targets: Query<(
+        Entity,
+        &ExtractedView,
+        &ViewTarget,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+    )>,
 ) {
-    for (entity, view, tonemapping, dither) in view_targets.iter() {
+    for (entity, view, view_target, tonemapping, dither) in view_targets.iter() {
         // As an optimization, we omit parts of the shader that are unneeded.
         let mut flags = TonemappingPipelineKeyFlags::empty();
         flags.set(
@@ -384,6 +396,10 @@ pub fn prepare_view_tonemapping_pipelines(
             deband_dither: *dither.unwrap_or(&DebandDither::Disabled),
             tonemapping: *tonemapping.unwrap_or(&Tonemapping::None),
             flags,
+            // SDR main textures can't hold the extended range.
+            hdr_output: view_target
+                .out_texture_hdr_output()
+                .filter(|_| view_target.is_hdr()),
         };
         let pipeline = pipelines.specialize(&pipeline_cache, &upscaling_pipeline, key);
 
@@ -368,6 +368,13 @@ fn tone_mapping(in: vec4<f32>, in_color_grading: ColorGrading) -> vec4<f32> {
     color = color * powsafe(vec3(2.0), color_grading.exposure);
 #endif
 
+#ifdef HDR_OUTPUT
+    // Scale the tonemapping curve so that it reaches the peak brightness of the HDR display,
+    // with 1.0 still being SDR white.
+    let hdr_headroom = max(f32(#HDR_OUTPUT_PEAK_NITS) / f32(#HDR_OUTPUT_PAPER_WHITE_NITS), 1.0);
+    color = color / hdr_headroom;
+#endif
+
     // tone_mapping
 #ifdef TONEMAP_METHOD_NONE
     color = color;
@@ -388,6 +395,10 @@ fn tone_mapping(in: vec4<f32>, in_color_grading: ColorGrading) -> vec4<f32> {
     color = sample_blender_filmic_lut(color.rgb);
 #endif
 
+#ifdef HDR_OUTPUT
+    color = color * hdr_headroom;
+#endif
+
     // Perceptual post tonemapping grading
     color = saturation(color, color_grading.post_saturation);
 
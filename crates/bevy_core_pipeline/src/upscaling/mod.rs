@@ -77,6 +77,7 @@ fn prepare_view_upscaling_pipelines(
             texture_format: view_target.out_texture_format(),
             blend_state,
             samples: 1,
+            hdr_output: view_target.out_texture_hdr_output(),
         };
         let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
 
@@ -0,0 +1,282 @@
+//! Module containing logic for suggesting which textures to pack into texture atlases.
+
+use core::{hash::Hash, time::Duration};
+
+use bevy_app::{Plugin, Update};
+use bevy_asset::{AssetId, AssetServer, Assets};
+use bevy_ecs::{
+    prelude::Local,
+    resource::Resource,
+    schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+    system::{Res, ResMut},
+};
+use bevy_image::Image;
+use bevy_platform_support::collections::HashMap;
+use bevy_render::diagnostic::{Batching2dDiagnosticsPlugin, Batching2dReport};
+use bevy_time::Time;
+use tracing::info;
+
+/// A plugin that suggests which textures to pack into texture atlases, based on how often sprites
+/// using them break each other's batches.
+///
+/// This plugin will add the [`Batching2dDiagnosticsPlugin`] if it wasn't added before. The
+/// texture switches it reports are accumulated over the [`AtlasSuggestionsConfig::refresh_interval`],
+/// then the textures which switch most often are grouped, as long as they would fit together in
+/// an atlas of [`AtlasSuggestionsConfig::max_atlas_size`]. The result is available in the
+/// [`AtlasSuggestions`] resource, and logged if [`AtlasSuggestionsConfig::log`] is set.
+///
+/// Packing a group of textures into an atlas lets the sprites using them be drawn in a single
+/// batch, as long as no other kind of item is sorted between them.
+#[derive(Default)]
+pub struct AtlasSuggestionsPlugin {
+    /// Starting configuration of the suggestions, this can be later be changed through the
+    /// [`AtlasSuggestionsConfig`] resource.
+    pub config: AtlasSuggestionsConfig,
+}
+
+impl Plugin for AtlasSuggestionsPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        // TODO: Use plugin dependencies, see https://github.com/bevyengine/bevy/issues/69
+        if !app.is_plugin_added::<Batching2dDiagnosticsPlugin>() {
+            app.add_plugins(Batching2dDiagnosticsPlugin);
+        }
+        app.insert_resource(self.config.clone())
+            .init_resource::<AtlasSuggestions>()
+            .init_resource::<TextureSwitches>()
+            .add_systems(
+                Update,
+                (
+                    accumulate_texture_switches.run_if(resource_changed::<Batching2dReport>),
+                    update_atlas_suggestions,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Configuration options for the [`AtlasSuggestionsPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct AtlasSuggestionsConfig {
+    /// The maximum width and height of a suggested atlas, in pixels.
+    ///
+    /// Defaults to 4096.
+    pub max_atlas_size: u32,
+    /// The maximum number of suggestions.
+    ///
+    /// Defaults to 3.
+    pub max_suggestions: usize,
+    /// The period over which texture switches are accumulated before updating the suggestions.
+    ///
+    /// Defaults to once every 5 seconds.
+    pub refresh_interval: Duration,
+    /// Logs the suggestions each time they are updated if true.
+    pub log: bool,
+}
+
+impl Default for AtlasSuggestionsConfig {
+    fn default() -> Self {
+        Self {
+            max_atlas_size: 4096,
+            max_suggestions: 3,
+            refresh_interval: Duration::from_secs(5),
+            log: true,
+        }
+    }
+}
+
+/// The textures suggested for atlasing by the [`AtlasSuggestionsPlugin`], from the one saving the
+/// most draw calls to the one saving the fewest.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AtlasSuggestions {
+    /// The suggestions computed over the last [`AtlasSuggestionsConfig::refresh_interval`].
+    pub suggestions: Vec<AtlasSuggestion>,
+}
+
+/// A group of textures which would reduce the number of 2D draw calls if packed into an atlas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasSuggestion {
+    /// The textures to pack into the same atlas.
+    pub textures: Vec<AssetId<Image>>,
+    /// The number of draw calls per frame, on average, which are caused by switching between
+    /// these textures.
+    pub draw_calls_saved: f32,
+}
+
+/// The texture switches accumulated since the suggestions were last updated.
+#[derive(Resource, Default)]
+struct TextureSwitches {
+    pairs: HashMap<(AssetId<Image>, AssetId<Image>), u32>,
+    frames: u32,
+}
+
+fn accumulate_texture_switches(
+    report: Res<Batching2dReport>,
+    mut switches: ResMut<TextureSwitches>,
+) {
+    switches.frames += 1;
+    for (pair, count) in &report.texture_pairs {
+        *switches.pairs.entry(*pair).or_default() += count;
+    }
+}
+
+fn update_atlas_suggestions(
+    time: Res<Time>,
+    config: Res<AtlasSuggestionsConfig>,
+    images: Res<Assets<Image>>,
+    asset_server: Option<Res<AssetServer>>,
+    mut switches: ResMut<TextureSwitches>,
+    mut suggestions: ResMut<AtlasSuggestions>,
+    mut time_since_update: Local<Duration>,
+) {
+    *time_since_update += time.delta();
+    if *time_since_update < config.refresh_interval {
+        return;
+    }
+    *time_since_update = Duration::ZERO;
+
+    let frames = core::mem::take(&mut switches.frames);
+    let mut pairs: Vec<_> = switches.pairs.drain().collect();
+    if frames == 0 {
+        return;
+    }
+    pairs.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let size = |id: AssetId<Image>| images.get(id).map(|image| (image.width(), image.height()));
+    suggestions.suggestions = group_textures(&pairs, size, config.max_atlas_size)
+        .into_iter()
+        .take(config.max_suggestions)
+        .map(|(textures, switches)| AtlasSuggestion {
+            textures,
+            draw_calls_saved: switches as f32 / frames as f32,
+        })
+        .collect();
+
+    if !config.log {
+        return;
+    }
+    for suggestion in &suggestions.suggestions {
+        let textures = suggestion
+            .textures
+            .iter()
+            .map(|&id| {
+                asset_server
+                    .as_ref()
+                    .and_then(|asset_server| asset_server.get_path(id))
+                    .map_or_else(|| format!("{id:?}"), |path| path.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        info!(
+            "Packing these textures into an atlas would save {:.1} draw calls per frame: {}",
+            suggestion.draw_calls_saved, textures
+        );
+    }
+}
+
+/// Groups the textures which switch most often, as long as each group fits in an atlas of
+/// `max_size` pixels.
+///
+/// `pairs` holds the number of switches between pairs of textures, from most to least frequent.
+/// Returns the groups with the number of switches between their textures, from most to least
+/// switches. The textures are assumed to pack without wasted space, so the suggested atlases
+/// may need to be slightly larger in practice.
+fn group_textures<K: Copy + Ord + Hash>(
+    pairs: &[((K, K), u32)],
+    size: impl Fn(K) -> Option<(u32, u32)>,
+    max_size: u32,
+) -> Vec<(Vec<K>, u32)> {
+    let max_area = max_size as u64 * max_size as u64;
+    let area = |texture: K| {
+        size(texture)
+            .filter(|&(width, height)| width <= max_size && height <= max_size)
+            .map(|(width, height)| width as u64 * height as u64)
+    };
+
+    let mut groups: Vec<(Vec<K>, u64)> = Vec::new();
+    let mut group_of: HashMap<K, usize> = HashMap::default();
+    for &((a, b), _) in pairs {
+        let (Some(area_a), Some(area_b)) = (area(a), area(b)) else {
+            continue;
+        };
+        match (group_of.get(&a).copied(), group_of.get(&b).copied()) {
+            (Some(group_a), Some(group_b)) if group_a != group_b => {
+                if groups[group_a].1 + groups[group_b].1 <= max_area {
+                    let (textures, area) = core::mem::take(&mut groups[group_b]);
+                    for &texture in &textures {
+                        group_of.insert(texture, group_a);
+                    }
+                    groups[group_a].0.extend(textures);
+                    groups[group_a].1 += area;
+                }
+            }
+            (Some(_), Some(_)) => {}
+            (Some(group), None) | (None, Some(group)) => {
+                let (texture, texture_area) = if group_of.contains_key(&a) {
+                    (b, area_b)
+                } else {
+                    (a, area_a)
+                };
+                if groups[group].1 + texture_area <= max_area {
+                    group_of.insert(texture, group);
+                    groups[group].0.push(texture);
+                    groups[group].1 += texture_area;
+                }
+            }
+            (None, None) => {
+                if area_a + area_b <= max_area {
+                    group_of.insert(a, groups.len());
+                    group_of.insert(b, groups.len());
+                    groups.push((vec![a, b], area_a + area_b));
+                }
+            }
+        }
+    }
+
+    let mut switches = vec![0; groups.len()];
+    for &((a, b), count) in pairs {
+        if let (Some(group_a), Some(group_b)) = (group_of.get(&a), group_of.get(&b)) {
+            if group_a == group_b {
+                switches[*group_a] += count;
+            }
+        }
+    }
+    let mut result: Vec<_> = groups
+        .into_iter()
+        .zip(switches)
+        .filter(|((textures, _), _)| !textures.is_empty())
+        .map(|((mut textures, _), switches)| {
+            textures.sort();
+            (textures, switches)
+        })
+        .collect();
+    result.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn textures_switching_most_are_grouped() {
+        let pairs = [((0, 1), 10), ((2, 3), 6), ((1, 2), 4), ((3, 4), 1)];
+        // Texture 4 is too large for any atlas.
+        let size = |texture| Some(if texture == 4 { (64, 8) } else { (16, 16) });
+
+        // Only two textures fit in an atlas.
+        assert_eq!(
+            group_textures(&pairs, size, 24),
+            vec![(vec![0, 1], 10), (vec![2, 3], 6)]
+        );
+        // All the textures fit, except for the large one.
+        assert_eq!(
+            group_textures(&pairs, size, 48),
+            vec![(vec![0, 1, 2, 3], 20)]
+        );
+        // Textures of unknown size are ignored.
+        assert_eq!(
+            group_textures(&pairs, |texture| (texture != 1).then_some((1, 1)), 48),
+            vec![(vec![2, 3, 4], 7)]
+        );
+    }
+}
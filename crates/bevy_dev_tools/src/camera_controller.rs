@@ -0,0 +1,650 @@
+//! Orbit and fly camera controllers, for inspecting a scene while developing.
+//!
+//! Add the [`CameraControllerPlugin`], then insert an [`OrbitCameraController`] or a
+//! [`FlyCameraController`] on a camera entity:
+//!
+//! ```no_run
+//! # use bevy_app::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_render::prelude::*;
+//! # use bevy_dev_tools::camera_controller::{CameraControllerPlugin, OrbitCameraController};
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((Camera::default(), OrbitCameraController::default()));
+//! }
+//!
+//! App::new()
+//!     .add_plugins(CameraControllerPlugin)
+//!     .add_systems(Startup, setup);
+//! ```
+//!
+//! Both controllers read their input through the [`ButtonInput`], [`AccumulatedMouseMotion`]
+//! and [`AccumulatedMouseScroll`] resources of `bevy_input`, and smooth their motion using
+//! real time, so they keep working while the virtual time is paused.
+
+use core::f32::consts::FRAC_PI_2;
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit},
+    prelude::*,
+};
+use bevy_math::prelude::*;
+use bevy_math::{EulerRot, StableInterpolate};
+use bevy_reflect::prelude::*;
+use bevy_time::{Real, Time};
+use bevy_transform::{prelude::*, TransformSystem};
+
+/// The largest absolute pitch of a controlled camera, in radians.
+///
+/// Keeping the pitch slightly away from the poles keeps the up direction of the camera
+/// well-defined.
+pub const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+
+/// The number of pixels corresponding to one line of mouse scrolling.
+const PIXELS_PER_LINE: f32 = 16.0;
+
+/// Adds the systems driving [`OrbitCameraController`] and [`FlyCameraController`] cameras.
+#[derive(Default)]
+pub struct CameraControllerPlugin;
+
+impl Plugin for CameraControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<OrbitCameraController>()
+            .register_type::<FlyCameraController>()
+            .add_systems(
+                PostUpdate,
+                (orbit_camera_controller, fly_camera_controller)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// A button that can be bound to an action of a camera controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum CameraControllerBinding {
+    /// A key of the keyboard.
+    Key(KeyCode),
+    /// A button of the mouse.
+    Mouse(MouseButton),
+}
+
+impl CameraControllerBinding {
+    /// Returns `true` if the bound button is currently pressed.
+    pub fn pressed(&self, keys: &ButtonInput<KeyCode>, mouse: &ButtonInput<MouseButton>) -> bool {
+        match *self {
+            Self::Key(key) => keys.pressed(key),
+            Self::Mouse(button) => mouse.pressed(button),
+        }
+    }
+}
+
+impl From<KeyCode> for CameraControllerBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for CameraControllerBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+/// The bindings of an [`OrbitCameraController`].
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default, Debug)]
+pub struct OrbitCameraBindings {
+    /// Rotates the camera around its focus while held.
+    pub orbit: CameraControllerBinding,
+    /// Moves the focus of the camera in its view plane while held.
+    pub pan: CameraControllerBinding,
+}
+
+impl Default for OrbitCameraBindings {
+    fn default() -> Self {
+        Self {
+            orbit: MouseButton::Left.into(),
+            pan: MouseButton::Right.into(),
+        }
+    }
+}
+
+/// A camera rotating around a focus point, or around an entity.
+///
+/// Dragging with the [`orbit`](OrbitCameraBindings::orbit) binding rotates the camera,
+/// dragging with the [`pan`](OrbitCameraBindings::pan) binding moves the focus, and scrolling
+/// zooms in and out.
+///
+/// When the controller is added, the camera keeps its position and turns towards the focus.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform)]
+pub struct OrbitCameraController {
+    /// Whether the controller reacts to input and updates the [`Transform`] of the camera.
+    pub enabled: bool,
+    /// The point the camera rotates around, in world space.
+    pub focus: Vec3,
+    /// The entity the camera rotates around.
+    ///
+    /// While set, [`focus`](Self::focus) follows the [`GlobalTransform`] translation of the
+    /// entity.
+    pub target: Option<Entity>,
+    /// The rotation of the camera around the Y axis, in radians.
+    pub yaw: f32,
+    /// The rotation of the camera around its local X axis, in radians.
+    ///
+    /// This is clamped to [`MAX_PITCH`] in both directions.
+    pub pitch: f32,
+    /// The distance between the camera and its focus.
+    pub radius: f32,
+    /// The smallest allowed [`radius`](Self::radius).
+    pub min_radius: f32,
+    /// The largest allowed [`radius`](Self::radius).
+    pub max_radius: f32,
+    /// The rotation per pixel of mouse motion, in radians.
+    pub orbit_sensitivity: f32,
+    /// The panning per pixel of mouse motion, relative to the radius.
+    pub pan_sensitivity: f32,
+    /// The relative change of the radius per pixel of scrolling.
+    pub zoom_sensitivity: f32,
+    /// How quickly the camera catches up with its target position, as a decay rate.
+    ///
+    /// Lower values give the camera more inertia, and [`f32::INFINITY`] disables smoothing.
+    pub decay_rate: f32,
+    /// The bindings of the controller.
+    pub bindings: OrbitCameraBindings,
+    #[reflect(ignore)]
+    state: Option<OrbitState>,
+}
+
+impl Default for OrbitCameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            focus: Vec3::ZERO,
+            target: None,
+            yaw: 0.0,
+            pitch: 0.0,
+            radius: 5.0,
+            min_radius: 0.05,
+            max_radius: 1000.0,
+            orbit_sensitivity: 0.005,
+            pan_sensitivity: 0.001,
+            zoom_sensitivity: 0.01,
+            decay_rate: 20.0,
+            bindings: OrbitCameraBindings::default(),
+            state: None,
+        }
+    }
+}
+
+impl OrbitCameraController {
+    /// Moves the focus of the camera to `point`, keeping the current yaw, pitch and radius.
+    ///
+    /// This also clears the [`target`](Self::target) of the controller.
+    pub fn focus_on(&mut self, point: Vec3) {
+        self.target = None;
+        self.focus = point;
+    }
+
+    /// Makes the camera follow `entity`, keeping the current yaw, pitch and radius.
+    pub fn focus_on_entity(&mut self, entity: Entity) {
+        self.target = Some(entity);
+    }
+
+    /// The rotation of the camera for the given yaw and pitch.
+    fn rotation(yaw: f32, pitch: f32) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, yaw, pitch, 0.0)
+    }
+
+    /// Sets the yaw, pitch and radius so that a camera at `position` looks at the focus.
+    fn look_from(&mut self, position: Vec3) {
+        let offset = position - self.focus;
+        let radius = offset.length();
+        if !radius.is_finite() || radius <= f32::EPSILON {
+            return;
+        }
+        self.yaw = ops::atan2(offset.x, offset.z);
+        self.pitch = ops::asin((-offset.y / radius).clamp(-1.0, 1.0));
+        self.radius = radius;
+    }
+
+    /// Clamps the pitch and radius, replacing non-finite values with their previous ones.
+    fn sanitize(&mut self, previous: &OrbitState) {
+        if !self.yaw.is_finite() {
+            self.yaw = previous.yaw;
+        }
+        if !self.pitch.is_finite() {
+            self.pitch = previous.pitch;
+        }
+        if !self.radius.is_finite() {
+            self.radius = previous.radius;
+        }
+        if !self.focus.is_finite() {
+            self.focus = previous.focus;
+        }
+        self.pitch = self.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+        let min_radius = self.min_radius.max(f32::EPSILON);
+        self.radius = self
+            .radius
+            .clamp(min_radius, self.max_radius.max(min_radius));
+    }
+}
+
+/// The smoothed state of an [`OrbitCameraController`].
+#[derive(Clone, Copy, Debug)]
+struct OrbitState {
+    focus: Vec3,
+    yaw: f32,
+    pitch: f32,
+    radius: f32,
+}
+
+/// The bindings of a [`FlyCameraController`].
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default, Debug)]
+pub struct FlyCameraBindings {
+    /// Moves the camera forward while held.
+    pub forward: CameraControllerBinding,
+    /// Moves the camera backward while held.
+    pub back: CameraControllerBinding,
+    /// Moves the camera to the left while held.
+    pub left: CameraControllerBinding,
+    /// Moves the camera to the right while held.
+    pub right: CameraControllerBinding,
+    /// Moves the camera up while held.
+    pub up: CameraControllerBinding,
+    /// Moves the camera down while held.
+    pub down: CameraControllerBinding,
+    /// Multiplies the speed of the camera by [`FlyCameraController::run_multiplier`] while held.
+    pub run: CameraControllerBinding,
+    /// Rotates the camera with the mouse while held.
+    pub look: CameraControllerBinding,
+}
+
+impl Default for FlyCameraBindings {
+    fn default() -> Self {
+        Self {
+            forward: KeyCode::KeyW.into(),
+            back: KeyCode::KeyS.into(),
+            left: KeyCode::KeyA.into(),
+            right: KeyCode::KeyD.into(),
+            up: KeyCode::KeyE.into(),
+            down: KeyCode::KeyQ.into(),
+            run: KeyCode::ShiftLeft.into(),
+            look: MouseButton::Right.into(),
+        }
+    }
+}
+
+/// A free-flying camera.
+///
+/// The camera moves with the movement bindings and rotates with the mouse while the
+/// [`look`](FlyCameraBindings::look) binding is held. Scrolling changes its speed.
+///
+/// When the controller is added, the yaw and pitch are taken from the [`Transform`] of the
+/// camera.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform)]
+pub struct FlyCameraController {
+    /// Whether the controller reacts to input and updates the [`Transform`] of the camera.
+    pub enabled: bool,
+    /// The rotation of the camera around the Y axis, in radians.
+    pub yaw: f32,
+    /// The rotation of the camera around its local X axis, in radians.
+    ///
+    /// This is clamped to [`MAX_PITCH`] in both directions.
+    pub pitch: f32,
+    /// The speed of the camera, in units per second.
+    pub speed: f32,
+    /// The factor applied to [`speed`](Self::speed) while the
+    /// [`run`](FlyCameraBindings::run) binding is held.
+    pub run_multiplier: f32,
+    /// The relative change of the speed per pixel of scrolling.
+    pub speed_sensitivity: f32,
+    /// The rotation per pixel of mouse motion, in radians.
+    pub look_sensitivity: f32,
+    /// How quickly the camera reaches its target velocity and rotation, as a decay rate.
+    ///
+    /// Lower values give the camera more inertia, and [`f32::INFINITY`] disables smoothing.
+    pub decay_rate: f32,
+    /// The bindings of the controller.
+    pub bindings: FlyCameraBindings,
+    #[reflect(ignore)]
+    state: Option<FlyState>,
+}
+
+impl Default for FlyCameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            yaw: 0.0,
+            pitch: 0.0,
+            speed: 5.0,
+            run_multiplier: 3.0,
+            speed_sensitivity: 0.01,
+            look_sensitivity: 0.003,
+            decay_rate: 10.0,
+            bindings: FlyCameraBindings::default(),
+            state: None,
+        }
+    }
+}
+
+impl FlyCameraController {
+    /// Turns a camera at `position` towards `target`.
+    ///
+    /// Nothing changes if both points are the same.
+    pub fn look_at(&mut self, position: Vec3, target: Vec3) {
+        let Ok(direction) = Dir3::new(target - position) else {
+            return;
+        };
+        self.yaw = ops::atan2(-direction.x, -direction.z);
+        self.pitch = ops::asin(direction.y).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+}
+
+/// The smoothed state of a [`FlyCameraController`].
+#[derive(Clone, Copy, Debug)]
+struct FlyState {
+    yaw: f32,
+    pitch: f32,
+    velocity: Vec3,
+}
+
+/// Moves `value` towards `target`, or snaps it to `target` if `decay_rate` isn't finite.
+fn nudge<T: StableInterpolate>(value: &mut T, target: &T, decay_rate: f32, delta_time: f32) {
+    if decay_rate.is_finite() {
+        value.smooth_nudge(target, decay_rate, delta_time);
+    } else {
+        *value = target.clone();
+    }
+}
+
+/// Returns the accumulated mouse scrolling of this frame, in pixels.
+fn scroll_pixels(scroll: &AccumulatedMouseScroll) -> f32 {
+    match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta.y * PIXELS_PER_LINE,
+        MouseScrollUnit::Pixel => scroll.delta.y,
+    }
+}
+
+/// Returns the mouse motion of this frame, or zero if it isn't finite.
+fn mouse_delta(motion: &AccumulatedMouseMotion) -> Vec2 {
+    if motion.delta.is_finite() {
+        motion.delta
+    } else {
+        Vec2::ZERO
+    }
+}
+
+/// Updates the [`Transform`] of [`OrbitCameraController`] cameras.
+pub fn orbit_camera_controller(
+    time: Res<Time<Real>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    targets: Query<&GlobalTransform>,
+    mut cameras: Query<(&mut Transform, &mut OrbitCameraController)>,
+) {
+    let delta_time = time.delta_secs();
+    let mouse_delta = mouse_delta(&mouse_motion);
+    let scroll = scroll_pixels(&mouse_scroll);
+
+    for (mut transform, mut controller) in &mut cameras {
+        if !controller.enabled {
+            continue;
+        }
+        let controller = &mut *controller;
+
+        if let Some(target) = controller.target {
+            if let Ok(target_transform) = targets.get(target) {
+                controller.focus = target_transform.translation();
+            }
+        }
+
+        let previous = match controller.state {
+            Some(state) => state,
+            None => {
+                controller.look_from(transform.translation);
+                OrbitState {
+                    focus: controller.focus,
+                    yaw: controller.yaw,
+                    pitch: controller.pitch,
+                    radius: controller.radius,
+                }
+            }
+        };
+
+        if controller.bindings.orbit.pressed(&keys, &mouse_buttons) {
+            controller.yaw -= mouse_delta.x * controller.orbit_sensitivity;
+            controller.pitch -= mouse_delta.y * controller.orbit_sensitivity;
+        } else if controller.bindings.pan.pressed(&keys, &mouse_buttons) {
+            let rotation = OrbitCameraController::rotation(controller.yaw, controller.pitch);
+            let pan = rotation * Vec3::new(-mouse_delta.x, mouse_delta.y, 0.0);
+            controller.focus += pan * controller.pan_sensitivity * controller.radius;
+            controller.target = None;
+        }
+        if scroll.is_finite() {
+            controller.radius *= ops::exp(-scroll * controller.zoom_sensitivity);
+        }
+        controller.sanitize(&previous);
+
+        let mut state = previous;
+        let decay_rate = controller.decay_rate;
+        nudge(&mut state.focus, &controller.focus, decay_rate, delta_time);
+        nudge(&mut state.yaw, &controller.yaw, decay_rate, delta_time);
+        nudge(&mut state.pitch, &controller.pitch, decay_rate, delta_time);
+        nudge(
+            &mut state.radius,
+            &controller.radius,
+            decay_rate,
+            delta_time,
+        );
+        controller.state = Some(state);
+
+        let rotation = OrbitCameraController::rotation(state.yaw, state.pitch);
+        transform.rotation = rotation;
+        transform.translation = state.focus + rotation * Vec3::Z * state.radius;
+    }
+}
+
+/// Updates the [`Transform`] of [`FlyCameraController`] cameras.
+pub fn fly_camera_controller(
+    time: Res<Time<Real>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    mut cameras: Query<(&mut Transform, &mut FlyCameraController)>,
+) {
+    let delta_time = time.delta_secs();
+    let mouse_delta = mouse_delta(&mouse_motion);
+    let scroll = scroll_pixels(&mouse_scroll);
+
+    for (mut transform, mut controller) in &mut cameras {
+        if !controller.enabled {
+            continue;
+        }
+        let controller = &mut *controller;
+
+        let previous = match controller.state {
+            Some(state) => state,
+            None => {
+                let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+                controller.yaw = yaw;
+                controller.pitch = pitch;
+                FlyState {
+                    yaw,
+                    pitch,
+                    velocity: Vec3::ZERO,
+                }
+            }
+        };
+
+        if controller.bindings.look.pressed(&keys, &mouse_buttons) {
+            controller.yaw -= mouse_delta.x * controller.look_sensitivity;
+            controller.pitch -= mouse_delta.y * controller.look_sensitivity;
+        }
+        if !controller.yaw.is_finite() {
+            controller.yaw = previous.yaw;
+        }
+        if !controller.pitch.is_finite() {
+            controller.pitch = previous.pitch;
+        }
+        controller.pitch = controller.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+        if scroll.is_finite() {
+            controller.speed *= ops::exp(scroll * controller.speed_sensitivity);
+        }
+        if !controller.speed.is_finite() || controller.speed <= 0.0 {
+            controller.speed = FlyCameraController::default().speed;
+        }
+
+        let bindings = &controller.bindings;
+        let axis = |positive: CameraControllerBinding, negative: CameraControllerBinding| {
+            positive.pressed(&keys, &mouse_buttons) as i8 as f32
+                - negative.pressed(&keys, &mouse_buttons) as i8 as f32
+        };
+        let input = Vec3::new(
+            axis(bindings.right, bindings.left),
+            axis(bindings.up, bindings.down),
+            axis(bindings.back, bindings.forward),
+        );
+        let mut speed = controller.speed;
+        if bindings.run.pressed(&keys, &mouse_buttons) {
+            speed *= controller.run_multiplier;
+        }
+
+        let mut state = previous;
+        let decay_rate = controller.decay_rate;
+        nudge(&mut state.yaw, &controller.yaw, decay_rate, delta_time);
+        nudge(&mut state.pitch, &controller.pitch, decay_rate, delta_time);
+        let rotation = Quat::from_euler(EulerRot::YXZ, state.yaw, state.pitch, 0.0);
+        let target_velocity = rotation * input.normalize_or_zero() * speed;
+        nudge(
+            &mut state.velocity,
+            &target_velocity,
+            decay_rate,
+            delta_time,
+        );
+        controller.state = Some(state);
+
+        transform.rotation = rotation;
+        transform.translation += state.velocity * delta_time;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time<Real>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<AccumulatedMouseMotion>()
+            .init_resource::<AccumulatedMouseScroll>()
+            .add_systems(Update, (orbit_camera_controller, fly_camera_controller));
+        app
+    }
+
+    fn advance(app: &mut App) {
+        let mut time = app.world_mut().resource_mut::<Time<Real>>();
+        let last_update = time.last_update().unwrap_or_else(|| time.startup());
+        time.update_with_instant(last_update + Duration::from_millis(16));
+        app.update();
+    }
+
+    #[test]
+    fn orbit_controller_stays_finite_at_the_poles() {
+        let mut app = test_app();
+        let camera = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 5.0),
+                OrbitCameraController {
+                    decay_rate: f32::INFINITY,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world_mut()
+            .resource_mut::<AccumulatedMouseMotion>()
+            .delta = Vec2::new(0.0, 1.0e6);
+
+        for _ in 0..3 {
+            advance(&mut app);
+        }
+
+        let controller = app.world().get::<OrbitCameraController>(camera).unwrap();
+        assert_eq!(controller.pitch, -MAX_PITCH);
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert!(transform.translation.is_finite());
+        assert!(transform.rotation.is_finite());
+        assert!((transform.translation.length() - 5.0).abs() < 1.0e-3);
+        // The camera is above its focus, and still looks at it.
+        assert!(transform.translation.y > 4.9);
+        let forward = transform.forward();
+        assert!(forward.dot(-transform.translation.normalize()) > 0.999);
+    }
+
+    #[test]
+    fn orbit_controller_follows_target() {
+        let mut app = test_app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(1.0, 2.0, 3.0))
+            .id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 5.0),
+                OrbitCameraController {
+                    target: Some(target),
+                    decay_rate: f32::INFINITY,
+                    ..Default::default()
+                },
+            ))
+            .id();
+
+        advance(&mut app);
+
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        let to_target = Vec3::new(1.0, 2.0, 3.0) - transform.translation;
+        assert!(transform.forward().dot(to_target.normalize()) > 0.999);
+    }
+
+    #[test]
+    fn fly_controller_has_inertia() {
+        let mut app = test_app();
+        let camera = app
+            .world_mut()
+            .spawn((Transform::default(), FlyCameraController::default()))
+            .id();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyW);
+
+        advance(&mut app);
+        advance(&mut app);
+        let first = app.world().get::<Transform>(camera).unwrap().translation;
+        assert!(first.z < 0.0);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::KeyW);
+        advance(&mut app);
+        let second = app.world().get::<Transform>(camera).unwrap().translation;
+        // The camera keeps moving forward for a while after the key is released.
+        assert!(second.z < first.z);
+    }
+}
@@ -3,7 +3,7 @@
 use bevy_app::{Plugin, Startup, Update};
 use bevy_asset::Handle;
 use bevy_color::Color;
-use bevy_diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy_diagnostic::{Diagnostic, DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     component::Component,
@@ -14,7 +14,7 @@ use bevy_ecs::{
     schedule::{common_conditions::resource_changed, IntoSystemConfigs},
     system::{Commands, Query, Res},
 };
-use bevy_render::view::Visibility;
+use bevy_render::{diagnostic::RenderDiagnosticsPlugin, view::Visibility};
 use bevy_text::{Font, TextColor, TextFont, TextSpan};
 use bevy_time::Time;
 use bevy_ui::{
@@ -31,6 +31,8 @@ pub const FPS_OVERLAY_ZINDEX: i32 = i32::MAX - 32;
 /// A plugin that adds an FPS overlay to the Bevy application.
 ///
 /// This plugin will add the [`FrameTimeDiagnosticsPlugin`] if it wasn't added before.
+/// If the [`RenderDiagnosticsPlugin`] was added and the platform supports it, the GPU frame time
+/// is displayed as well.
 ///
 /// Note: It is recommended to use native overlay of rendering statistics when possible for lower overhead and more accurate results.
 /// The correct way to do this will vary by platform:
@@ -126,11 +128,19 @@ fn update_text(
     if *time_since_rerender >= config.refresh_interval {
         *time_since_rerender = Duration::ZERO;
         for entity in &query {
-            if let Some(fps) = diagnostic.get(&FrameTimeDiagnosticsPlugin::FPS) {
-                if let Some(value) = fps.smoothed() {
-                    *writer.text(entity, 1) = format!("{value:.2}");
-                }
-            }
+            let Some(fps) = diagnostic
+                .get(&FrameTimeDiagnosticsPlugin::FPS)
+                .and_then(Diagnostic::smoothed)
+            else {
+                continue;
+            };
+            let gpu_frame_time = diagnostic
+                .get(&RenderDiagnosticsPlugin::GPU_FRAME_TIME)
+                .and_then(Diagnostic::smoothed);
+            *writer.text(entity, 1) = match gpu_frame_time {
+                Some(gpu_frame_time) => format!("{fps:.2} (GPU: {gpu_frame_time:.2} ms)"),
+                None => format!("{fps:.2}"),
+            };
         }
     }
 }
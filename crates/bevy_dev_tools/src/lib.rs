@@ -10,13 +10,21 @@
 
 use bevy_app::prelude::*;
 
+pub mod atlas_suggestions;
+
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+#[cfg(feature = "camera_controller")]
+pub mod camera_controller;
+
 pub mod fps_overlay;
 
 pub mod picking_debug;
 
+#[cfg(feature = "screenshot_testing")]
+pub mod screenshot_testing;
+
 pub mod states;
 
 /// Enables developer tools in an [`App`]. This plugin is added automatically with `bevy_dev_tools`
@@ -0,0 +1,443 @@
+//! Screenshot comparison tests, runnable with `cargo test`.
+//!
+//! A [`ScreenshotTest`] renders a few frames of an [`App`], captures a screenshot with the
+//! [`Screenshot`] API, and compares it against a golden image with a [`ScreenshotTolerance`].
+//! When they differ, the captured image and an image highlighting the differences are written to
+//! the output directory, so CI can upload them as artifacts.
+//!
+//! Golden images are created or updated by running the tests with the `BEVY_UPDATE_SCREENSHOTS`
+//! environment variable set.
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_dev_tools::screenshot_testing::{ScreenshotTest, ScreenshotTolerance};
+//! # use bevy_render::camera::RenderTarget;
+//! # fn build_scene(app: &mut App, target: RenderTarget) {}
+//! #[test]
+//! fn red_cube() {
+//!     let mut app = App::new();
+//!     // Add the `DefaultPlugins`, without the `WinitPlugin` to render headlessly.
+//!     let target = ScreenshotTest::render_target(&mut app, 256, 256);
+//!     // Spawn a camera rendering to `target`, and the scene.
+//!     build_scene(&mut app, target.clone());
+//!
+//!     ScreenshotTest::new("red_cube")
+//!         .with_target(target)
+//!         .with_tolerance(ScreenshotTolerance::Perceptual { max_distance: 0.02 })
+//!         .assert(app);
+//! }
+//! ```
+
+use bevy_app::{App, PluginsState};
+use bevy_asset::{Assets, RenderAssetUsages};
+use bevy_color::{color_difference::EuclideanDistance, Oklaba, Srgba};
+use bevy_ecs::observer::Trigger;
+use bevy_image::{Image, IntoDynamicImageError};
+use bevy_render::{
+    camera::RenderTarget,
+    render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    view::screenshot::{Screenshot, ScreenshotCaptured},
+};
+use bevy_window::WindowRef;
+use image::{Rgb, RgbImage};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use tracing::info;
+
+/// The environment variable which, when set, makes [`ScreenshotTest`]s save their screenshots as
+/// the new golden images instead of comparing them.
+pub const UPDATE_SCREENSHOTS_ENV: &str = "BEVY_UPDATE_SCREENSHOTS";
+
+/// The environment variable overriding the default output directory of [`ScreenshotTest`]s.
+pub const SCREENSHOT_OUTPUT_ENV: &str = "BEVY_SCREENSHOT_TEST_OUTPUT";
+
+/// The number of frames rendered after the screenshot is requested, before giving up.
+const MAX_CAPTURE_FRAMES: u32 = 60;
+
+/// A test rendering an [`App`] and comparing a screenshot of it against a golden image.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Debug)]
+pub struct ScreenshotTest {
+    name: String,
+    frames: u32,
+    target: RenderTarget,
+    tolerance: ScreenshotTolerance,
+    max_differing_pixels: f32,
+    golden_dir: PathBuf,
+    output_dir: PathBuf,
+}
+
+impl ScreenshotTest {
+    /// Creates a test named `name`, whose golden image is `tests/screenshots/{name}.png`.
+    ///
+    /// By default, the test renders 10 frames of the primary window, and the output directory is
+    /// `screenshot-test-output`, unless the `BEVY_SCREENSHOT_TEST_OUTPUT` environment variable is
+    /// set. Both directories are relative to the working directory, which is the root of the
+    /// package under `cargo test`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            frames: 10,
+            target: RenderTarget::Window(WindowRef::Primary),
+            tolerance: ScreenshotTolerance::default(),
+            max_differing_pixels: 0.0,
+            golden_dir: PathBuf::from("tests/screenshots"),
+            output_dir: std::env::var_os(SCREENSHOT_OUTPUT_ENV)
+                .map_or_else(|| PathBuf::from("screenshot-test-output"), PathBuf::from),
+        }
+    }
+
+    /// Sets the number of frames rendered before taking the screenshot.
+    ///
+    /// This should leave enough time for the assets of the scene to load.
+    pub fn with_frames(mut self, frames: u32) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// Sets the render target of the screenshot.
+    ///
+    /// Windows aren't rendered without an event loop, so tests running the app directly should
+    /// render to an image created with [`ScreenshotTest::render_target`].
+    pub fn with_target(mut self, target: RenderTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets how much each pixel can differ from the golden image.
+    pub fn with_tolerance(mut self, tolerance: ScreenshotTolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the fraction of pixels, between 0 and 1, which can exceed the tolerance.
+    pub fn with_max_differing_pixels(mut self, fraction: f32) -> Self {
+        self.max_differing_pixels = fraction;
+        self
+    }
+
+    /// Sets the directory of the golden images.
+    pub fn with_golden_dir(mut self, golden_dir: impl Into<PathBuf>) -> Self {
+        self.golden_dir = golden_dir.into();
+        self
+    }
+
+    /// Sets the directory where the screenshots and differences of failed tests are written.
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = output_dir.into();
+        self
+    }
+
+    /// Creates an image which cameras can render to, to take screenshots without a window.
+    ///
+    /// This must be called after the `AssetPlugin` and the `ImagePlugin` are added.
+    pub fn render_target(app: &mut App, width: u32, height: u32) -> RenderTarget {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0; 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage |= TextureUsages::COPY_SRC
+            | TextureUsages::RENDER_ATTACHMENT
+            | TextureUsages::TEXTURE_BINDING;
+        let handle = app.world_mut().resource_mut::<Assets<Image>>().add(image);
+        RenderTarget::Image(handle.into())
+    }
+
+    /// Runs the test, and panics if it fails.
+    pub fn assert(self, app: App) {
+        let name = self.name.clone();
+        if let Err(error) = self.run(app) {
+            panic!("screenshot test `{name}` failed: {error}");
+        }
+    }
+
+    /// Runs the test.
+    ///
+    /// If the `BEVY_UPDATE_SCREENSHOTS` environment variable is set, the screenshot is saved as
+    /// the golden image instead.
+    pub fn run(self, mut app: App) -> Result<(), ScreenshotTestError> {
+        let screenshot = self.capture(&mut app)?;
+        let golden_path = self.golden_dir.join(format!("{}.png", self.name));
+
+        if std::env::var_os(UPDATE_SCREENSHOTS_ENV).is_some() {
+            std::fs::create_dir_all(&self.golden_dir)?;
+            screenshot.save(&golden_path)?;
+            info!("Updated golden image {}", golden_path.display());
+            return Ok(());
+        }
+
+        if !golden_path.exists() {
+            let actual = self.write_output("actual", &screenshot)?;
+            return Err(ScreenshotTestError::MissingGolden {
+                golden: golden_path,
+                actual,
+            });
+        }
+
+        let golden = image::open(&golden_path)?.to_rgb8();
+        let comparison = compare_images(&screenshot, &golden, self.tolerance)?;
+        let differing_fraction =
+            comparison.differing_pixels as f32 / comparison.total_pixels as f32;
+        if differing_fraction <= self.max_differing_pixels {
+            return Ok(());
+        }
+
+        let actual = self.write_output("actual", &screenshot)?;
+        let diff = self.write_output("diff", &comparison.diff)?;
+        Err(ScreenshotTestError::Mismatch {
+            differing_pixels: comparison.differing_pixels,
+            total_pixels: comparison.total_pixels,
+            max_difference: comparison.max_difference,
+            actual,
+            diff,
+        })
+    }
+
+    /// Renders the frames of the app, and captures the screenshot.
+    fn capture(&self, app: &mut App) -> Result<RgbImage, ScreenshotTestError> {
+        if app.plugins_state() != PluginsState::Cleaned {
+            while app.plugins_state() == PluginsState::Adding {
+                bevy_tasks::tick_global_task_pools_on_main_thread();
+            }
+            app.finish();
+            app.cleanup();
+        }
+
+        for _ in 0..self.frames {
+            app.update();
+        }
+
+        let captured = Arc::new(Mutex::new(None));
+        let captured_in_observer = captured.clone();
+        app.world_mut()
+            .spawn(Screenshot(self.target.clone()))
+            .observe(move |trigger: Trigger<ScreenshotCaptured>| {
+                *captured_in_observer.lock().unwrap() = Some(trigger.event().0.clone());
+            });
+
+        for _ in 0..MAX_CAPTURE_FRAMES {
+            app.update();
+            if let Some(image) = captured.lock().unwrap().take() {
+                // The alpha channel stores brightness values when HDR is enabled, so it's
+                // discarded like in screenshots saved to disk.
+                return Ok(image.try_into_dynamic()?.to_rgb8());
+            }
+        }
+        Err(ScreenshotTestError::NotCaptured)
+    }
+
+    fn write_output(&self, kind: &str, image: &RgbImage) -> Result<PathBuf, ScreenshotTestError> {
+        std::fs::create_dir_all(&self.output_dir)?;
+        let path = self.output_dir.join(format!("{}.{kind}.png", self.name));
+        image.save(&path)?;
+        Ok(path)
+    }
+}
+
+/// How much the pixels of a screenshot can differ from the golden image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScreenshotTolerance {
+    /// Each channel of a pixel can differ by at most `max_difference`, out of 255.
+    PerChannel {
+        /// The maximum difference of a channel.
+        max_difference: u8,
+    },
+    /// The colors of a pixel can be at most `max_distance` apart in the Oklab color space, where
+    /// a distance of about `0.02` is barely noticeable.
+    ///
+    /// This is more forgiving of differences which are hard to see, like in dark areas.
+    Perceptual {
+        /// The maximum distance between the colors.
+        max_distance: f32,
+    },
+}
+
+impl Default for ScreenshotTolerance {
+    fn default() -> Self {
+        Self::PerChannel { max_difference: 2 }
+    }
+}
+
+impl ScreenshotTolerance {
+    /// Returns the difference between two pixels, and whether it's tolerated.
+    fn difference(self, actual: &Rgb<u8>, expected: &Rgb<u8>) -> (f32, bool) {
+        match self {
+            Self::PerChannel { max_difference } => {
+                let difference = actual
+                    .0
+                    .iter()
+                    .zip(expected.0)
+                    .map(|(actual, expected)| actual.abs_diff(expected))
+                    .max()
+                    .unwrap_or(0);
+                (difference as f32, difference <= max_difference)
+            }
+            Self::Perceptual { max_distance } => {
+                let [r, g, b] = actual.0;
+                let actual = Oklaba::from(Srgba::rgb_u8(r, g, b));
+                let [r, g, b] = expected.0;
+                let expected = Oklaba::from(Srgba::rgb_u8(r, g, b));
+                let distance = actual.distance(&expected);
+                (distance, distance <= max_distance)
+            }
+        }
+    }
+}
+
+/// The result of [`compare_images`].
+#[derive(Clone, Debug)]
+pub struct ImageComparison {
+    /// The number of pixels exceeding the tolerance.
+    pub differing_pixels: usize,
+    /// The number of pixels of the images.
+    pub total_pixels: usize,
+    /// The largest difference between two pixels, in the unit of the tolerance.
+    pub max_difference: f32,
+    /// An image showing the pixels exceeding the tolerance in red, over a faded copy of the
+    /// expected image.
+    pub diff: RgbImage,
+}
+
+/// Compares two images pixel by pixel.
+pub fn compare_images(
+    actual: &RgbImage,
+    expected: &RgbImage,
+    tolerance: ScreenshotTolerance,
+) -> Result<ImageComparison, ScreenshotTestError> {
+    if actual.dimensions() != expected.dimensions() {
+        return Err(ScreenshotTestError::SizeMismatch {
+            actual: actual.dimensions(),
+            expected: expected.dimensions(),
+        });
+    }
+
+    let mut differing_pixels = 0;
+    let mut max_difference: f32 = 0.0;
+    let mut diff = RgbImage::new(expected.width(), expected.height());
+    for ((actual, expected), diff) in actual
+        .pixels()
+        .zip(expected.pixels())
+        .zip(diff.pixels_mut())
+    {
+        let (difference, tolerated) = tolerance.difference(actual, expected);
+        max_difference = max_difference.max(difference);
+        *diff = if tolerated {
+            Rgb(expected.0.map(|channel| channel / 4))
+        } else {
+            differing_pixels += 1;
+            Rgb([255, 0, 0])
+        };
+    }
+
+    Ok(ImageComparison {
+        differing_pixels,
+        total_pixels: (expected.width() * expected.height()) as usize,
+        max_difference,
+        diff,
+    })
+}
+
+/// An error returned by a [`ScreenshotTest`].
+#[derive(Error, Debug)]
+pub enum ScreenshotTestError {
+    /// The screenshot wasn't captured, usually because nothing renders to its target.
+    #[error("the screenshot wasn't captured after {MAX_CAPTURE_FRAMES} frames")]
+    NotCaptured,
+    /// The captured image couldn't be converted.
+    #[error("the screenshot couldn't be converted: {0}")]
+    Conversion(#[from] IntoDynamicImageError),
+    /// An image couldn't be read or written.
+    #[error("image error: {0}")]
+    Image(#[from] image::ImageError),
+    /// A directory couldn't be created.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    /// There is no golden image to compare the screenshot with.
+    #[error(
+        "missing golden image {}, the screenshot was written to {}; run with {UPDATE_SCREENSHOTS_ENV}=1 to accept it",
+        golden.display(),
+        actual.display()
+    )]
+    MissingGolden {
+        /// The path of the missing golden image.
+        golden: PathBuf,
+        /// The path where the screenshot was written.
+        actual: PathBuf,
+    },
+    /// The screenshot and the golden image have different sizes.
+    #[error("the screenshot is {actual:?} pixels, but the golden image is {expected:?}")]
+    SizeMismatch {
+        /// The width and height of the screenshot.
+        actual: (u32, u32),
+        /// The width and height of the golden image.
+        expected: (u32, u32),
+    },
+    /// Too many pixels of the screenshot differ from the golden image.
+    #[error(
+        "{differing_pixels} of {total_pixels} pixels differ, by up to {max_difference}; see {} and {}",
+        actual.display(),
+        diff.display()
+    )]
+    Mismatch {
+        /// The number of pixels exceeding the tolerance.
+        differing_pixels: usize,
+        /// The number of pixels of the images.
+        total_pixels: usize,
+        /// The largest difference between two pixels.
+        max_difference: f32,
+        /// The path where the screenshot was written.
+        actual: PathBuf,
+        /// The path where the image of the differences was written.
+        diff: PathBuf,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare() {
+        let expected = RgbImage::from_pixel(4, 4, Rgb([100, 100, 100]));
+        let mut actual = expected.clone();
+        actual.put_pixel(0, 0, Rgb([101, 100, 100]));
+        actual.put_pixel(1, 0, Rgb([200, 100, 100]));
+
+        let comparison = compare_images(
+            &actual,
+            &expected,
+            ScreenshotTolerance::PerChannel { max_difference: 1 },
+        )
+        .unwrap();
+        assert_eq!(comparison.differing_pixels, 1);
+        assert_eq!(comparison.total_pixels, 16);
+        assert_eq!(comparison.max_difference, 100.0);
+        assert_eq!(*comparison.diff.get_pixel(1, 0), Rgb([255, 0, 0]));
+        assert_eq!(*comparison.diff.get_pixel(0, 0), Rgb([25, 25, 25]));
+
+        let comparison = compare_images(
+            &actual,
+            &expected,
+            ScreenshotTolerance::Perceptual { max_distance: 0.02 },
+        )
+        .unwrap();
+        assert_eq!(comparison.differing_pixels, 1);
+
+        let smaller = RgbImage::new(2, 2);
+        assert!(matches!(
+            compare_images(&smaller, &expected, ScreenshotTolerance::default()),
+            Err(ScreenshotTestError::SizeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{pool::EntityPools, system::Res};
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds diagnostics on the utilization of the entity pools of [`bevy_ecs::pool`] to an App.
+///
+/// The measurements add up all pools: use [`EntityPools::stats`] for the utilization of a single
+/// pool.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct EntityPoolDiagnosticsPlugin;
+
+impl Plugin for EntityPoolDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ACTIVE))
+            .register_diagnostic(Diagnostic::new(Self::IDLE))
+            .register_diagnostic(Diagnostic::new(Self::UTILIZATION).with_suffix("%"))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl EntityPoolDiagnosticsPlugin {
+    /// The number of pooled entities which are spawned.
+    pub const ACTIVE: DiagnosticPath = DiagnosticPath::const_new("entity_pools/active");
+    /// The number of pooled entities waiting to be spawned again.
+    pub const IDLE: DiagnosticPath = DiagnosticPath::const_new("entity_pools/idle");
+    /// The percentage of the pooled entities which are spawned.
+    pub const UTILIZATION: DiagnosticPath = DiagnosticPath::const_new("entity_pools/utilization");
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, pools: Option<Res<EntityPools>>) {
+        let (active, idle) = pools
+            .iter()
+            .flat_map(|pools| pools.iter())
+            .fold((0, 0), |(active, idle), (_, stats)| {
+                (active + stats.active, idle + stats.idle)
+            });
+        diagnostics.add_measurement(&Self::ACTIVE, || active as f64);
+        diagnostics.add_measurement(&Self::IDLE, || idle as f64);
+        diagnostics.add_measurement(&Self::UTILIZATION, || {
+            if active + idle == 0 {
+                0.0
+            } else {
+                active as f64 / (active + idle) as f64 * 100.0
+            }
+        });
+    }
+}
@@ -0,0 +1,42 @@
+use bevy_app::prelude::*;
+use bevy_ecs::system::{FrameArenas, Res};
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds diagnostics on the memory used by the [`FrameArena`](bevy_ecs::system::FrameArena)s of
+/// the systems to an App.
+///
+/// The measurements add up all arenas: use [`FrameArenas::iter`] for the memory used by the arena
+/// of a single system. An allocated size that keeps growing means that some system allocates more
+/// memory every frame.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct FrameArenaDiagnosticsPlugin;
+
+impl Plugin for FrameArenaDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ALLOCATED_BYTES).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(Self::HIGH_WATER_MARK).with_suffix(" B"))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl FrameArenaDiagnosticsPlugin {
+    /// The number of bytes currently allocated by the arenas.
+    pub const ALLOCATED_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("frame_arenas/allocated_bytes");
+    /// The largest number of bytes allocated by each arena, added up.
+    pub const HIGH_WATER_MARK: DiagnosticPath =
+        DiagnosticPath::const_new("frame_arenas/high_water_mark");
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, arenas: Option<Res<FrameArenas>>) {
+        let (allocated_bytes, high_water_mark) = arenas
+            .map(|arenas| (arenas.allocated_bytes(), arenas.high_water_mark()))
+            .unwrap_or_default();
+        diagnostics.add_measurement(&Self::ALLOCATED_BYTES, || allocated_bytes as f64);
+        diagnostics.add_measurement(&Self::HIGH_WATER_MARK, || high_water_mark as f64);
+    }
+}
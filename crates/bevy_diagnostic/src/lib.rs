@@ -18,6 +18,8 @@ extern crate alloc;
 
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
+mod entity_pool_diagnostics_plugin;
+mod frame_arena_diagnostics_plugin;
 mod frame_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
@@ -27,6 +29,8 @@ mod system_information_diagnostics_plugin;
 pub use diagnostic::*;
 
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+pub use entity_pool_diagnostics_plugin::EntityPoolDiagnosticsPlugin;
+pub use frame_arena_diagnostics_plugin::FrameArenaDiagnosticsPlugin;
 pub use frame_count_diagnostics_plugin::{update_frame_count, FrameCount, FrameCountPlugin};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
@@ -1892,6 +1892,30 @@ mod tests {
         assert_eq!(4, world.resource::<R>().0);
     }
 
+    #[test]
+    fn component_hook_order_deferred() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _| world.resource_mut::<R>().assert_order(0))
+            .on_add_deferred(|world, context| {
+                world.resource_mut::<R>().assert_order(3);
+                world.entity_mut(context.entity).insert(D);
+            })
+            .deferred_order(1);
+
+        world
+            .register_component_hooks::<B>()
+            .on_add(|mut world, _| world.resource_mut::<R>().assert_order(1))
+            .on_add_deferred(|world, _| world.resource_mut::<R>().assert_order(2))
+            .deferred_order(-1);
+
+        let entity = world.spawn((A, B)).flush();
+        assert_eq!(4, world.resource::<R>().0);
+        assert!(world.entity(entity).contains::<D>());
+    }
+
     #[test]
     fn insert_if_new() {
         let mut world = World::new();
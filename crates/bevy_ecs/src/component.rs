@@ -531,7 +531,14 @@ pub enum StorageType {
 /// The type used for [`Component`] lifecycle hooks such as `on_add`, `on_insert` or `on_remove`.
 pub type ComponentHook = for<'w> fn(DeferredWorld<'w>, HookContext);
 
-/// Context provided to a [`ComponentHook`].
+/// The type used for deferred [`Component`] lifecycle hooks such as `on_add_deferred`.
+///
+/// Unlike a [`ComponentHook`], a deferred hook has full access to the [`World`], but doesn't run
+/// immediately: it's queued as a command, and runs the next time the world's commands are applied.
+/// By then, the entity may have been despawned or lost the component, which the hook must handle.
+pub type DeferredComponentHook = fn(&mut World, HookContext);
+
+/// Context provided to a [`ComponentHook`] or a [`DeferredComponentHook`].
 #[derive(Clone, Copy, Debug)]
 pub struct HookContext {
     /// The [`Entity`] this hook was invoked for.
@@ -558,6 +565,12 @@ pub struct HookContext {
 /// 1. Defining the [`Component::register_component_hooks`] method (see [`Component`])
 /// 2. Using the [`World::register_component_hooks`] method
 ///
+/// Hooks run immediately, with a [`DeferredWorld`] that can't make structural changes. When a hook
+/// needs full access to the world, for example to maintain an invariant spanning several
+/// components, a [`DeferredComponentHook`] can be registered instead with methods like
+/// [`ComponentHooks::on_add_deferred`]. Deferred hooks are queued as commands once all the immediate
+/// hooks of an operation ran, in the order set by [`ComponentHooks::deferred_order`].
+///
 /// # Example 2
 ///
 /// ```
@@ -602,6 +615,12 @@ pub struct ComponentHooks {
     pub(crate) on_replace: Option<ComponentHook>,
     pub(crate) on_remove: Option<ComponentHook>,
     pub(crate) on_despawn: Option<ComponentHook>,
+    pub(crate) on_add_deferred: Option<DeferredComponentHook>,
+    pub(crate) on_insert_deferred: Option<DeferredComponentHook>,
+    pub(crate) on_replace_deferred: Option<DeferredComponentHook>,
+    pub(crate) on_remove_deferred: Option<DeferredComponentHook>,
+    pub(crate) on_despawn_deferred: Option<DeferredComponentHook>,
+    pub(crate) deferred_order: i32,
 }
 
 impl ComponentHooks {
@@ -762,6 +781,147 @@ impl ComponentHooks {
         self.on_despawn = Some(hook);
         Some(self)
     }
+
+    /// Register a [`DeferredComponentHook`] that will be queued when this component is added to an entity.
+    ///
+    /// This is the deferred version of [`Self::on_add`]: the hook runs once the commands of
+    /// the world are applied, after the `on_add` hook.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has a deferred `on_add` hook
+    pub fn on_add_deferred(&mut self, hook: DeferredComponentHook) -> &mut Self {
+        self.try_on_add_deferred(hook)
+            .expect("Component already has a deferred on_add hook")
+    }
+
+    /// Attempt to register a [`DeferredComponentHook`] that will be queued when this component is added to an entity.
+    ///
+    /// This is a fallible version of [`Self::on_add_deferred`].
+    ///
+    /// Returns `None` if the component already has a deferred `on_add` hook.
+    pub fn try_on_add_deferred(&mut self, hook: DeferredComponentHook) -> Option<&mut Self> {
+        if self.on_add_deferred.is_some() {
+            return None;
+        }
+        self.on_add_deferred = Some(hook);
+        Some(self)
+    }
+
+    /// Register a [`DeferredComponentHook`] that will be queued when this component is added (with `.insert`) or replaced.
+    ///
+    /// This is the deferred version of [`Self::on_insert`]: the hook runs once the commands of
+    /// the world are applied, after the `on_insert` hook.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has a deferred `on_insert` hook
+    pub fn on_insert_deferred(&mut self, hook: DeferredComponentHook) -> &mut Self {
+        self.try_on_insert_deferred(hook)
+            .expect("Component already has a deferred on_insert hook")
+    }
+
+    /// Attempt to register a [`DeferredComponentHook`] that will be queued when this component is added (with `.insert`) or replaced.
+    ///
+    /// This is a fallible version of [`Self::on_insert_deferred`].
+    ///
+    /// Returns `None` if the component already has a deferred `on_insert` hook.
+    pub fn try_on_insert_deferred(&mut self, hook: DeferredComponentHook) -> Option<&mut Self> {
+        if self.on_insert_deferred.is_some() {
+            return None;
+        }
+        self.on_insert_deferred = Some(hook);
+        Some(self)
+    }
+
+    /// Register a [`DeferredComponentHook`] that will be queued when this component is about to be replaced (with `.insert`) or removed.
+    ///
+    /// This is the deferred version of [`Self::on_replace`]: the hook runs once the commands of
+    /// the world are applied, after the `on_replace` hook.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has a deferred `on_replace` hook
+    pub fn on_replace_deferred(&mut self, hook: DeferredComponentHook) -> &mut Self {
+        self.try_on_replace_deferred(hook)
+            .expect("Component already has a deferred on_replace hook")
+    }
+
+    /// Attempt to register a [`DeferredComponentHook`] that will be queued when this component is about to be replaced (with `.insert`) or removed.
+    ///
+    /// This is a fallible version of [`Self::on_replace_deferred`].
+    ///
+    /// Returns `None` if the component already has a deferred `on_replace` hook.
+    pub fn try_on_replace_deferred(&mut self, hook: DeferredComponentHook) -> Option<&mut Self> {
+        if self.on_replace_deferred.is_some() {
+            return None;
+        }
+        self.on_replace_deferred = Some(hook);
+        Some(self)
+    }
+
+    /// Register a [`DeferredComponentHook`] that will be queued when this component is removed from an entity.
+    ///
+    /// This is the deferred version of [`Self::on_remove`]: the hook runs once the commands of
+    /// the world are applied, after the `on_remove` hook.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has a deferred `on_remove` hook
+    pub fn on_remove_deferred(&mut self, hook: DeferredComponentHook) -> &mut Self {
+        self.try_on_remove_deferred(hook)
+            .expect("Component already has a deferred on_remove hook")
+    }
+
+    /// Attempt to register a [`DeferredComponentHook`] that will be queued when this component is removed from an entity.
+    ///
+    /// This is a fallible version of [`Self::on_remove_deferred`].
+    ///
+    /// Returns `None` if the component already has a deferred `on_remove` hook.
+    pub fn try_on_remove_deferred(&mut self, hook: DeferredComponentHook) -> Option<&mut Self> {
+        if self.on_remove_deferred.is_some() {
+            return None;
+        }
+        self.on_remove_deferred = Some(hook);
+        Some(self)
+    }
+
+    /// Register a [`DeferredComponentHook`] that will be queued when an entity with this component is despawned.
+    ///
+    /// This is the deferred version of [`Self::on_despawn`]: the hook runs once the commands of
+    /// the world are applied, after the `on_despawn` hook.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has a deferred `on_despawn` hook
+    pub fn on_despawn_deferred(&mut self, hook: DeferredComponentHook) -> &mut Self {
+        self.try_on_despawn_deferred(hook)
+            .expect("Component already has a deferred on_despawn hook")
+    }
+
+    /// Attempt to register a [`DeferredComponentHook`] that will be queued when an entity with this component is despawned.
+    ///
+    /// This is a fallible version of [`Self::on_despawn_deferred`].
+    ///
+    /// Returns `None` if the component already has a deferred `on_despawn` hook.
+    pub fn try_on_despawn_deferred(&mut self, hook: DeferredComponentHook) -> Option<&mut Self> {
+        if self.on_despawn_deferred.is_some() {
+            return None;
+        }
+        self.on_despawn_deferred = Some(hook);
+        Some(self)
+    }
+
+    /// Set the order of the deferred hooks of this component relative to the deferred hooks of
+    /// other components, for the same operation on the same entity.
+    ///
+    /// When several components of an entity have deferred hooks for the same lifecycle event, for
+    /// example when spawning a bundle, their hooks run in increasing order. Hooks with the same
+    /// order run in the order the components were processed. The default order is `0`.
+    pub fn deferred_order(&mut self, order: i32) -> &mut Self {
+        self.deferred_order = order;
+        self
+    }
 }
 
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
@@ -845,19 +1005,19 @@ impl ComponentInfo {
     /// Update the given flags to include any [`ComponentHook`] registered to self
     #[inline]
     pub(crate) fn update_archetype_flags(&self, flags: &mut ArchetypeFlags) {
-        if self.hooks().on_add.is_some() {
+        if self.hooks().on_add.is_some() || self.hooks().on_add_deferred.is_some() {
             flags.insert(ArchetypeFlags::ON_ADD_HOOK);
         }
-        if self.hooks().on_insert.is_some() {
+        if self.hooks().on_insert.is_some() || self.hooks().on_insert_deferred.is_some() {
             flags.insert(ArchetypeFlags::ON_INSERT_HOOK);
         }
-        if self.hooks().on_replace.is_some() {
+        if self.hooks().on_replace.is_some() || self.hooks().on_replace_deferred.is_some() {
             flags.insert(ArchetypeFlags::ON_REPLACE_HOOK);
         }
-        if self.hooks().on_remove.is_some() {
+        if self.hooks().on_remove.is_some() || self.hooks().on_remove_deferred.is_some() {
             flags.insert(ArchetypeFlags::ON_REMOVE_HOOK);
         }
-        if self.hooks().on_despawn.is_some() {
+        if self.hooks().on_despawn.is_some() || self.hooks().on_despawn_deferred.is_some() {
             flags.insert(ArchetypeFlags::ON_DESPAWN_HOOK);
         }
     }
@@ -0,0 +1,422 @@
+use alloc::vec::Vec;
+use bevy_platform_support::collections::{HashMap, HashSet};
+use core::any::TypeId;
+use thiserror::Error;
+
+use crate::{
+    component::{Component, ComponentInfo},
+    entity::{hash_map::EntityHashMap, Entity, EntityMapper, MapEntities},
+    hierarchy::{ChildOf, Children},
+    world::{EntityRef, EntityWorldMut, World},
+};
+
+/// A function cloning a component of a source entity into a target entity in another world.
+type WorldCloneFn = fn(&EntityRef, &mut EntityWorldMut, &mut WorldCloneMapper);
+
+/// Clones entities from a [`World`] into another [`World`], without going through a scene.
+///
+/// Components are cloned with the fast paths registered with
+/// [`clone_with`](Self::clone_with) and [`clone_with_mapping`](Self::clone_with_mapping), or
+/// with reflection otherwise: the `AppTypeRegistry` of the source world must then contain the
+/// `ReflectComponent` type data of the component, and its `ReflectMapEntities` type data if it
+/// references entities. Components which can't be cloned either way are skipped.
+///
+/// The entities referenced by the cloned components are mapped to their clones in the target
+/// world. Components referencing entities which aren't cloned and aren't in the entity map are
+/// skipped, so that they don't point to unrelated entities of the target world. For example, the
+/// [`ChildOf`] component of a cloned root is skipped, unless its parent is mapped to an entity of
+/// the target world beforehand.
+///
+/// [`ChildOf`] is cloned with a fast path by default. [`Children`] is never cloned: the
+/// relationship hooks rebuild it in the target world when the [`ChildOf`] components of the
+/// children are inserted. Other
+/// [`RelationshipTarget`](crate::relationship::RelationshipTarget) components should be
+/// [denied](Self::deny) for the same reason.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::entity::{hash_map::EntityHashMap, WorldEntityCloner};
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Health(u32);
+///
+/// #[derive(Component, Clone)]
+/// struct EditorOnly;
+///
+/// let mut editor_world = World::new();
+/// let player = editor_world
+///     .spawn((Health(10), EditorOnly))
+///     .with_child(Health(5))
+///     .id();
+///
+/// let mut play_world = World::new();
+/// let mut entity_map = EntityHashMap::default();
+/// WorldEntityCloner::default()
+///     .recursive(true)
+///     .deny::<EditorOnly>()
+///     .clone_with::<Health>()
+///     .clone_entities(&editor_world, &[player], &mut play_world, &mut entity_map)
+///     .unwrap();
+///
+/// let player_clone = play_world.entity(entity_map[&player]);
+/// assert_eq!(player_clone.get::<Health>(), Some(&Health(10)));
+/// assert!(!player_clone.contains::<EditorOnly>());
+/// assert_eq!(player_clone.get::<Children>().unwrap().len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct WorldEntityCloner {
+    filter_allows_components: bool,
+    filter: HashSet<TypeId>,
+    clone_fns: HashMap<TypeId, WorldCloneFn>,
+    recursive: bool,
+}
+
+impl Default for WorldEntityCloner {
+    fn default() -> Self {
+        let mut cloner = Self {
+            filter_allows_components: false,
+            filter: HashSet::default(),
+            clone_fns: HashMap::default(),
+            recursive: false,
+        };
+        cloner.clone_with_mapping::<ChildOf>();
+        cloner
+    }
+}
+
+impl WorldEntityCloner {
+    /// Sets the option to clone the descendants of the entities, following their [`Children`].
+    pub fn recursive(&mut self, recursive: bool) -> &mut Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Clones components of type `C` with their [`Clone`] implementation, instead of reflection.
+    ///
+    /// `C` must not reference entities, see [`clone_with_mapping`](Self::clone_with_mapping).
+    pub fn clone_with<C: Component + Clone>(&mut self) -> &mut Self {
+        self.clone_fns
+            .insert(TypeId::of::<C>(), |source, target, _| {
+                if let Some(component) = source.get::<C>() {
+                    target.insert(component.clone());
+                }
+            });
+        self
+    }
+
+    /// Clones components of type `C` with their [`Clone`] implementation, instead of reflection,
+    /// and maps the entities they reference with [`MapEntities`].
+    pub fn clone_with_mapping<C: Component + Clone + MapEntities>(&mut self) -> &mut Self {
+        self.clone_fns
+            .insert(TypeId::of::<C>(), |source, target, mapper| {
+                let Some(component) = source.get::<C>() else {
+                    return;
+                };
+                let mut component = component.clone();
+                mapper.unmapped = false;
+                component.map_entities(mapper);
+                if !mapper.unmapped {
+                    target.insert(component);
+                }
+            });
+        self
+    }
+
+    /// Disallows components of type `C` from being cloned.
+    ///
+    /// If [`Self::deny_all`] was called, this removes the component from the allowed list.
+    pub fn deny<C: Component>(&mut self) -> &mut Self {
+        self.deny_by_type_ids([TypeId::of::<C>()])
+    }
+
+    /// Disallows the components with the given [`TypeId`]s from being cloned.
+    pub fn deny_by_type_ids(&mut self, ids: impl IntoIterator<Item = TypeId>) -> &mut Self {
+        for id in ids {
+            if self.filter_allows_components {
+                self.filter.remove(&id);
+            } else {
+                self.filter.insert(id);
+            }
+        }
+        self
+    }
+
+    /// Allows components of type `C` to be cloned.
+    ///
+    /// Unless [`Self::deny_all`] was called, all components are already allowed, and this only
+    /// reverts previous calls to [`Self::deny`].
+    pub fn allow<C: Component>(&mut self) -> &mut Self {
+        self.allow_by_type_ids([TypeId::of::<C>()])
+    }
+
+    /// Allows the components with the given [`TypeId`]s to be cloned.
+    pub fn allow_by_type_ids(&mut self, ids: impl IntoIterator<Item = TypeId>) -> &mut Self {
+        for id in ids {
+            if self.filter_allows_components {
+                self.filter.insert(id);
+            } else {
+                self.filter.remove(&id);
+            }
+        }
+        self
+    }
+
+    /// Resets the filter to allow all components to be cloned.
+    pub fn allow_all(&mut self) -> &mut Self {
+        self.filter_allows_components = false;
+        self.filter.clear();
+        self
+    }
+
+    /// Resets the filter to deny all components, so that only the components allowed afterwards
+    /// are cloned.
+    pub fn deny_all(&mut self) -> &mut Self {
+        self.filter_allows_components = true;
+        self.filter.clear();
+        self
+    }
+
+    /// Clones `entities` of `source_world`, and their descendants if [`recursive`](Self::recursive),
+    /// into `target_world`.
+    ///
+    /// `entity_map` maps the entities of `source_world` to the entities of `target_world`. Cloned
+    /// entities which are already in the map are cloned into their mapped entity, which makes it
+    /// possible to mirror the same entities repeatedly; the others are spawned, and added to the
+    /// map. Mapping other entities beforehand, like the parent of a cloned root, preserves the
+    /// components referencing them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of `entities` doesn't exist in `source_world`, before cloning any
+    /// entity.
+    pub fn clone_entities(
+        &self,
+        source_world: &World,
+        entities: &[Entity],
+        target_world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) -> Result<(), WorldCloneError> {
+        let mut sources = Vec::with_capacity(entities.len());
+        let mut stack: Vec<Entity> = entities.iter().rev().copied().collect();
+        while let Some(entity) = stack.pop() {
+            let source = source_world
+                .get_entity(entity)
+                .map_err(WorldCloneError::NoSuchEntity)?;
+            sources.push(entity);
+            if self.recursive {
+                if let Some(children) = source.get::<Children>() {
+                    stack.extend(children.iter().rev());
+                }
+            }
+        }
+
+        for &source in &sources {
+            entity_map
+                .entry(source)
+                .or_insert_with(|| target_world.spawn_empty().id());
+        }
+
+        #[cfg(feature = "bevy_reflect")]
+        let type_registry = source_world
+            .get_resource::<crate::reflect::AppTypeRegistry>()
+            .map(|registry| registry.read());
+
+        let mut mapper = WorldCloneMapper {
+            entity_map,
+            unmapped: false,
+        };
+        for source in sources {
+            let source = source_world.entity(source);
+            let mut target = target_world.entity_mut(mapper.entity_map[&source.id()]);
+            for component_id in source.archetype().components() {
+                let Some(type_id) = source_world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(ComponentInfo::type_id)
+                else {
+                    continue;
+                };
+                if type_id == TypeId::of::<Children>() || !self.is_cloning_allowed(&type_id) {
+                    continue;
+                }
+
+                if let Some(clone_fn) = self.clone_fns.get(&type_id) {
+                    clone_fn(&source, &mut target, &mut mapper);
+                    continue;
+                }
+
+                #[cfg(feature = "bevy_reflect")]
+                if let Some(registry) = &type_registry {
+                    reflect_clone(registry, type_id, &source, &mut target, &mut mapper);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_cloning_allowed(&self, type_id: &TypeId) -> bool {
+        self.filter_allows_components == self.filter.contains(type_id)
+    }
+}
+
+/// Clones a component with its `ReflectComponent` type data, mapping its entities with its
+/// `ReflectMapEntities` type data.
+#[cfg(feature = "bevy_reflect")]
+fn reflect_clone(
+    registry: &bevy_reflect::TypeRegistry,
+    type_id: TypeId,
+    source: &EntityRef,
+    target: &mut EntityWorldMut,
+    mapper: &mut WorldCloneMapper,
+) {
+    use crate::reflect::{ReflectComponent, ReflectMapEntities};
+
+    let Some(registration) = registry.get(type_id) else {
+        return;
+    };
+    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+        return;
+    };
+    let Some(component) = reflect_component.reflect(*source) else {
+        return;
+    };
+    let mut component = component.clone_value();
+    if let Some(reflect_map_entities) = registration.data::<ReflectMapEntities>() {
+        mapper.unmapped = false;
+        reflect_map_entities.map_entities(component.as_mut(), mapper);
+        if mapper.unmapped {
+            return;
+        }
+    }
+    reflect_component.insert(target, component.as_ref(), registry);
+}
+
+/// Maps the entities of the source world to their clones, and records whether an entity wasn't
+/// mapped.
+struct WorldCloneMapper<'m> {
+    entity_map: &'m EntityHashMap<Entity>,
+    unmapped: bool,
+}
+
+impl EntityMapper for WorldCloneMapper<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        match self.entity_map.get(&entity) {
+            Some(&mapped) => mapped,
+            None => {
+                self.unmapped = true;
+                Entity::PLACEHOLDER
+            }
+        }
+    }
+}
+
+/// An error returned by [`WorldEntityCloner::clone_entities`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldCloneError {
+    /// The entity doesn't exist in the source world.
+    #[error("The entity {0} doesn't exist in the source world")]
+    NoSuchEntity(Entity),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::{
+            hash_map::EntityHashMap, Entity, VisitEntities, VisitEntitiesMut, WorldEntityCloner,
+        },
+        hierarchy::{ChildOf, Children},
+        world::World,
+    };
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct A(u32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct B;
+
+    #[derive(Component, Clone, VisitEntities, VisitEntitiesMut, PartialEq, Debug)]
+    struct Target(Entity);
+
+    #[test]
+    fn clone_hierarchy_between_worlds() {
+        let mut source = World::new();
+        let root = source.spawn((A(1), B)).id();
+        let child = source.spawn((A(2), ChildOf(root), Target(root))).id();
+        let outside = source.spawn_empty().id();
+        let grandchild = source.spawn((ChildOf(child), Target(outside))).id();
+
+        let mut target = World::new();
+        let mut entity_map = EntityHashMap::default();
+        WorldEntityCloner::default()
+            .recursive(true)
+            .deny::<B>()
+            .clone_with::<A>()
+            .clone_with_mapping::<Target>()
+            .clone_entities(&source, &[root], &mut target, &mut entity_map)
+            .unwrap();
+
+        assert_eq!(entity_map.len(), 3);
+        let (root_clone, child_clone, grandchild_clone) = (
+            entity_map[&root],
+            entity_map[&child],
+            entity_map[&grandchild],
+        );
+        assert_eq!(target.get::<A>(root_clone), Some(&A(1)));
+        assert!(target.get::<B>(root_clone).is_none());
+        assert_eq!(target.get::<A>(child_clone), Some(&A(2)));
+        assert_eq!(target.get::<Target>(child_clone), Some(&Target(root_clone)));
+        // The referenced entity wasn't cloned.
+        assert!(target.get::<Target>(grandchild_clone).is_none());
+        assert_eq!(
+            &**target.get::<Children>(root_clone).unwrap(),
+            &[child_clone]
+        );
+        assert_eq!(
+            target.get::<ChildOf>(grandchild_clone),
+            Some(&ChildOf(child_clone))
+        );
+
+        // Cloning again updates the same entities.
+        source.get_mut::<A>(root).unwrap().0 = 10;
+        WorldEntityCloner::default()
+            .clone_with::<A>()
+            .clone_entities(&source, &[root], &mut target, &mut entity_map)
+            .unwrap();
+        assert_eq!(target.get::<A>(root_clone), Some(&A(10)));
+        assert_eq!(target.entities().len(), 3);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn clone_with_reflection() {
+        use crate::reflect::{AppTypeRegistry, ReflectComponent};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct C(u32);
+
+        let mut source = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<C>();
+        registry.write().register::<ChildOf>();
+        source.insert_resource(registry);
+        let parent = source.spawn(C(1)).id();
+        let entity = source.spawn((C(2), ChildOf(parent))).id();
+
+        let mut target = World::new();
+        let target_parent = target.spawn_empty().id();
+        let mut entity_map = EntityHashMap::default();
+        entity_map.insert(parent, target_parent);
+        WorldEntityCloner::default()
+            .clone_entities(&source, &[entity], &mut target, &mut entity_map)
+            .unwrap();
+
+        let clone = entity_map[&entity];
+        assert_eq!(target.get::<C>(clone), Some(&C(2)));
+        assert_eq!(target.get::<ChildOf>(clone), Some(&ChildOf(target_parent)));
+        assert!(target.get::<C>(target_parent).is_none());
+    }
+}
@@ -37,6 +37,7 @@
 //! [`EntityWorldMut::remove`]: crate::world::EntityWorldMut::remove
 
 mod clone_entities;
+mod clone_world;
 mod entity_set;
 mod map_entities;
 mod visit_entities;
@@ -46,6 +47,7 @@ use bevy_reflect::Reflect;
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
 pub use clone_entities::*;
+pub use clone_world::*;
 pub use entity_set::*;
 pub use map_entities::*;
 pub use visit_entities::*;
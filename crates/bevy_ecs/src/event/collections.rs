@@ -1,9 +1,10 @@
 use crate as bevy_ecs;
-use alloc::vec::Vec;
+use alloc::{collections::VecDeque, vec::Vec};
 use bevy_ecs::{
     event::{Event, EventCursor, EventId, EventInstance},
     resource::Resource,
 };
+use bevy_platform_support::sync::atomic::{AtomicUsize, Ordering};
 #[cfg(feature = "track_location")]
 use core::panic::Location;
 use core::{
@@ -37,7 +38,8 @@ use {
 /// Events will persist across a single frame boundary and so ordering of event producers and
 /// consumers is not critical (although poorly-planned ordering may cause accumulating lag).
 /// If events are not handled by the end of the frame after they are updated, they will be
-/// dropped silently.
+/// dropped silently, unless a longer [`EventRetention`] is configured with
+/// [`Events::set_retention`].
 ///
 /// # Example
 /// ```
@@ -79,6 +81,9 @@ use {
 ///
 /// The buffers in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
 ///
+/// With an [`EventRetention`] other than the default, the events of both buffers are merged on
+/// [`update`](Events::update), and only the expired events are removed from the front.
+///
 /// An alternative call pattern would be to call [`update`](Events::update)
 /// manually across frames to control when events are cleared.
 /// This complicates consumption and risks ever-expanding memory usage if not cleaned up,
@@ -100,6 +105,15 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     pub(crate) events_b: EventSequence<E>,
     pub(crate) event_count: usize,
+    /// How long the events are kept by [`Events::update`].
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    retention: EventRetention,
+    /// The `event_count` at each of the last [`Events::update`] calls, used by [`EventRetention::Updates`].
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    update_event_counts: VecDeque<usize>,
+    /// The highest `event_count` that a reader has read up to, used by [`EventRetention::UntilRead`].
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    read_event_count: AtomicUsize,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -109,11 +123,64 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            retention: Default::default(),
+            update_event_counts: Default::default(),
+            read_event_count: Default::default(),
         }
     }
 }
 
+/// Controls how long [`Events`] keeps the events it received, see [`Events::set_retention`].
+///
+/// Events are never dropped outside of [`Events::update`], so an event is always visible to
+/// readers until at least the next update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventRetention {
+    /// Events are dropped by the given number of [`Events::update`] calls after they were sent.
+    ///
+    /// The default is two updates, which lets readers running once per frame see every event
+    /// regardless of system ordering. Readers running at a lower rate, for example every
+    /// few frames, need a higher value.
+    Updates(usize),
+    /// Events are kept until any reader has read them, and are dropped by the next
+    /// [`Events::update`] after that.
+    ///
+    /// An event counts as read once a reader has started iterating over its unread events, or
+    /// has [cleared](super::EventReader::clear) them.
+    ///
+    /// Events that are never read are never dropped, so this should only be used for events
+    /// that always have a reader.
+    UntilRead,
+}
+
+impl Default for EventRetention {
+    fn default() -> Self {
+        Self::Updates(2)
+    }
+}
+
 impl<E: Event> Events<E> {
+    /// Creates an empty [`Events`] keeping its events according to `retention`.
+    pub fn with_retention(retention: EventRetention) -> Self {
+        Self {
+            retention,
+            ..Default::default()
+        }
+    }
+
+    /// Returns how long the events are kept by [`Events::update`].
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Sets how long the events are kept by [`Events::update`].
+    ///
+    /// This only changes which events are dropped by the following updates; the events that
+    /// were already dropped are not restored.
+    pub fn set_retention(&mut self, retention: EventRetention) {
+        self.retention = retention;
+    }
+
     /// Returns the index of the oldest event stored in the event buffer.
     pub fn oldest_event_count(&self) -> usize {
         self.events_a.start_event_count
@@ -196,15 +263,12 @@ impl<E: Event> Events<E> {
     /// Swaps the event buffers and clears the oldest event buffer. In general, this should be
     /// called once per frame/update.
     ///
+    /// With an [`EventRetention`] other than the default, only the events that expired are
+    /// removed, and the remaining events are moved to the oldest event buffer.
+    ///
     /// If you need access to the events that were removed, consider using [`Events::update_drain`].
     pub fn update(&mut self) {
-        core::mem::swap(&mut self.events_a, &mut self.events_b);
-        self.events_b.clear();
-        self.events_b.start_event_count = self.event_count;
-        debug_assert_eq!(
-            self.events_a.start_event_count + self.events_a.len(),
-            self.events_b.start_event_count
-        );
+        let _ = self.update_drain();
     }
 
     /// Swaps the event buffers and drains the oldest event buffer, returning an iterator
@@ -213,17 +277,69 @@ impl<E: Event> Events<E> {
     /// If you do not need to take ownership of the removed events, use [`Events::update`] instead.
     #[must_use = "If you do not need the returned events, call .update() instead."]
     pub fn update_drain(&mut self) -> impl Iterator<Item = E> + '_ {
-        core::mem::swap(&mut self.events_a, &mut self.events_b);
-        let iter = self.events_b.events.drain(..);
+        let oldest_kept = self.oldest_kept_event_count();
+        let iter = if oldest_kept == self.events_b.start_event_count {
+            // All of the oldest events expired: reuse their buffer for the new events.
+            core::mem::swap(&mut self.events_a, &mut self.events_b);
+            debug_assert_eq!(
+                self.events_a.start_event_count + self.events_a.len(),
+                self.event_count
+            );
+            self.events_b.events.drain(..)
+        } else {
+            // Some of the oldest events are kept: move the newer events after them, and remove
+            // the expired events from the front.
+            let newer_events = core::mem::take(&mut self.events_b.events);
+            self.events_a.events.extend(newer_events);
+            let expired = oldest_kept
+                .saturating_sub(self.events_a.start_event_count)
+                .min(self.events_a.len());
+            self.events_a.start_event_count += expired;
+            debug_assert_eq!(
+                self.events_a.start_event_count + self.events_a.len() - expired,
+                self.event_count
+            );
+            self.events_a.events.drain(..expired)
+        };
         self.events_b.start_event_count = self.event_count;
-        debug_assert_eq!(
-            self.events_a.start_event_count + self.events_a.len(),
-            self.events_b.start_event_count
-        );
 
         iter.map(|e| e.event)
     }
 
+    /// Returns the `event_count` of the oldest event that should be kept by the update
+    /// currently being run, according to the [`EventRetention`].
+    fn oldest_kept_event_count(&mut self) -> usize {
+        match self.retention {
+            EventRetention::Updates(updates) => {
+                // The events sent since the update `updates - 1` updates ago are kept.
+                self.update_event_counts.push_back(self.event_count);
+                while self.update_event_counts.len() > updates {
+                    self.update_event_counts.pop_front();
+                }
+                if updates == 0 {
+                    self.event_count
+                } else if self.update_event_counts.len() == updates {
+                    self.update_event_counts[0]
+                } else {
+                    0
+                }
+            }
+            EventRetention::UntilRead => {
+                self.update_event_counts.clear();
+                *self.read_event_count.get_mut()
+            }
+        }
+    }
+
+    /// Records that a reader has read all of the events up to `event_count`.
+    #[inline]
+    pub(crate) fn mark_read(&self, event_count: usize) {
+        if self.retention == EventRetention::UntilRead {
+            self.read_event_count
+                .fetch_max(event_count, Ordering::Relaxed);
+        }
+    }
+
     #[inline]
     fn reset_start_event_count(&mut self) {
         self.events_a.start_event_count = self.event_count;
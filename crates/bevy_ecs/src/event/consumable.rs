@@ -0,0 +1,246 @@
+use crate as bevy_ecs;
+use alloc::collections::VecDeque;
+use bevy_ecs::{
+    event::{Event, EventId, EventInstance, EventRetention},
+    resource::Resource,
+    system::{ResMut, SystemParam},
+};
+use core::marker::PhantomData;
+#[cfg(feature = "track_location")]
+use core::panic::Location;
+
+/// A channel of events that are removed once they are handled, so that each event is handled
+/// by at most one [`EventConsumer`].
+///
+/// Unlike [`Events`](super::Events), where every [`EventReader`](super::EventReader) sees every
+/// event, the consumers of a [`ConsumableEvents`] run one after the other, in the order of the
+/// systems, and each consumer only sees the events that the previous consumers did not handle.
+/// This is useful when several systems compete to handle an event, like an input that should only
+/// be handled by the topmost UI element.
+///
+/// Events are sent by mutating the resource directly, and are kept according to the
+/// [`EventRetention`] of the channel until they are consumed. The default retention drops events
+/// that were not consumed by two [`update`](Self::update) calls after being sent; with
+/// [`EventRetention::UntilRead`], events are kept until they are consumed.
+///
+/// The channel is typically initialized, and updated once per frame, using
+/// [`add_consumable_event`](https://docs.rs/bevy/*/bevy/app/struct.App.html#method.add_consumable_event).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, event::{ConsumableEvents, EventConsumer}};
+/// #[derive(Event)]
+/// struct Click {
+///     x: f32,
+/// }
+///
+/// fn send_clicks(mut clicks: ResMut<ConsumableEvents<Click>>) {
+///     clicks.send(Click { x: 10.0 });
+/// }
+///
+/// fn handle_left_panel_clicks(mut clicks: EventConsumer<Click>) {
+///     // Only the clicks handled here are removed.
+///     clicks.consume(|click| click.x < 100.0);
+/// }
+///
+/// fn handle_remaining_clicks(mut clicks: EventConsumer<Click>) {
+///     for click in clicks.consume_all() {
+///         // Never receives the clicks handled by `handle_left_panel_clicks`.
+///         assert!(click.x >= 100.0);
+///     }
+/// }
+///
+/// # let mut world = World::new();
+/// # world.init_resource::<ConsumableEvents<Click>>();
+/// # let mut schedule = Schedule::default();
+/// # schedule.add_systems((send_clicks, handle_left_panel_clicks, handle_remaining_clicks).chain());
+/// # schedule.run(&mut world);
+/// ```
+#[derive(Debug, Resource)]
+pub struct ConsumableEvents<E: Event> {
+    events: VecDeque<ConsumableEvent<E>>,
+    event_count: usize,
+    update_count: usize,
+    retention: EventRetention,
+}
+
+#[derive(Debug)]
+struct ConsumableEvent<E: Event> {
+    instance: EventInstance<E>,
+    /// The `update_count` when the event was sent.
+    sent_update: usize,
+}
+
+// Derived Default impl would incorrectly require E: Default
+impl<E: Event> Default for ConsumableEvents<E> {
+    fn default() -> Self {
+        Self {
+            events: Default::default(),
+            event_count: Default::default(),
+            update_count: Default::default(),
+            retention: Default::default(),
+        }
+    }
+}
+
+impl<E: Event> ConsumableEvents<E> {
+    /// Creates an empty channel keeping its unconsumed events according to `retention`.
+    pub fn with_retention(retention: EventRetention) -> Self {
+        Self {
+            retention,
+            ..Default::default()
+        }
+    }
+
+    /// Returns how long the unconsumed events are kept by [`ConsumableEvents::update`].
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Sets how long the unconsumed events are kept by [`ConsumableEvents::update`].
+    ///
+    /// [`EventRetention::UntilRead`] keeps the events until they are consumed.
+    pub fn set_retention(&mut self, retention: EventRetention) {
+        self.retention = retention;
+    }
+
+    /// Sends an `event`, which can then be consumed by an [`EventConsumer`].
+    /// This method returns the [ID](`EventId`) of the sent `event`.
+    #[track_caller]
+    pub fn send(&mut self, event: E) -> EventId<E> {
+        let event_id = EventId {
+            id: self.event_count,
+            #[cfg(feature = "track_location")]
+            caller: Location::caller(),
+            _marker: PhantomData,
+        };
+        self.events.push_back(ConsumableEvent {
+            instance: EventInstance { event_id, event },
+            sent_update: self.update_count,
+        });
+        self.event_count += 1;
+        event_id
+    }
+
+    /// Sends the default value of the event. Useful when the event is an empty struct.
+    /// This method returns the [ID](`EventId`) of the sent `event`.
+    #[track_caller]
+    pub fn send_default(&mut self) -> EventId<E>
+    where
+        E: Default,
+    {
+        self.send(Default::default())
+    }
+
+    /// Drops the events that were not consumed within the [`EventRetention`] of the channel.
+    /// In general, this should be called once per frame/update.
+    pub fn update(&mut self) {
+        self.update_count += 1;
+        if let EventRetention::Updates(updates) = self.retention {
+            while self
+                .events
+                .front()
+                .is_some_and(|event| self.update_count - event.sent_update >= updates)
+            {
+                self.events.pop_front();
+            }
+        }
+    }
+
+    /// Iterates over the events that were not consumed yet, from the oldest to the newest,
+    /// without consuming them.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &E> {
+        self.events.iter().map(|event| &event.instance.event)
+    }
+
+    /// Iterates over the events that were not consumed yet, with their [IDs](`EventId`),
+    /// without consuming them.
+    pub fn iter_with_id(&self) -> impl ExactSizeIterator<Item = (&E, EventId<E>)> {
+        self.events
+            .iter()
+            .map(|event| (&event.instance.event, event.instance.event_id))
+    }
+
+    /// Calls `handler` on each event that was not consumed yet, from the oldest to the newest,
+    /// and consumes the events for which it returns `true`.
+    ///
+    /// Returns the number of consumed events.
+    pub fn consume(&mut self, mut handler: impl FnMut(&E) -> bool) -> usize {
+        let len = self.events.len();
+        self.events.retain(|event| !handler(&event.instance.event));
+        len - self.events.len()
+    }
+
+    /// Consumes all of the events, from the oldest to the newest.
+    pub fn consume_all(&mut self) -> impl Iterator<Item = E> + '_ {
+        self.events.drain(..).map(|event| event.instance.event)
+    }
+
+    /// Returns the number of events that were not consumed yet.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if all of the events were consumed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<E: Event> Extend<E> for ConsumableEvents<E> {
+    #[track_caller]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = E>,
+    {
+        for event in iter {
+            self.send(event);
+        }
+    }
+}
+
+/// Reads and consumes events from a [`ConsumableEvents`] channel.
+///
+/// Consumed events are removed from the channel, so the consumers running later see only the
+/// events that were not consumed yet. Two systems with an [`EventConsumer`] of the same event
+/// type are never run concurrently, and should be ordered to control which one handles the
+/// events first.
+///
+/// See [`ConsumableEvents`] for an example.
+#[derive(SystemParam)]
+pub struct EventConsumer<'w, E: Event> {
+    events: ResMut<'w, ConsumableEvents<E>>,
+}
+
+impl<'w, E: Event> EventConsumer<'w, E> {
+    /// Iterates over the events that were not consumed yet, without consuming them.
+    pub fn read(&self) -> impl ExactSizeIterator<Item = &E> {
+        self.events.iter()
+    }
+
+    /// Calls `handler` on each event that was not consumed yet, and consumes the events for
+    /// which it returns `true`.
+    ///
+    /// See [`ConsumableEvents::consume`].
+    pub fn consume(&mut self, handler: impl FnMut(&E) -> bool) -> usize {
+        self.events.consume(handler)
+    }
+
+    /// Consumes all of the events that were not consumed yet.
+    pub fn consume_all(&mut self) -> impl Iterator<Item = E> + '_ {
+        self.events.consume_all()
+    }
+
+    /// Returns the number of events that were not consumed yet.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if all of the events were consumed.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
@@ -136,6 +136,7 @@ impl<E: Event> EventCursor<E> {
 
     /// See [`EventReader::clear()`](super::EventReader::clear)
     pub fn clear(&mut self, events: &Events<E>) {
+        events.mark_read(events.event_count);
         self.last_event_count = events.event_count;
     }
 }
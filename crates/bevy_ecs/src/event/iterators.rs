@@ -1,5 +1,7 @@
 use crate as bevy_ecs;
 #[cfg(feature = "multi_threaded")]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "multi_threaded")]
 use bevy_ecs::batching::BatchingStrategy;
 use bevy_ecs::event::{Event, EventCursor, EventId, EventInstance, Events};
 use core::{iter::Chain, slice::Iter};
@@ -53,6 +55,7 @@ pub struct EventIteratorWithId<'a, E: Event> {
 impl<'a, E: Event> EventIteratorWithId<'a, E> {
     /// Creates a new iterator that yields any `events` that have not yet been seen by `reader`.
     pub fn new(reader: &'a mut EventCursor<E>, events: &'a Events<E>) -> Self {
+        events.mark_read(events.event_count);
         let a_index = reader
             .last_event_count
             .saturating_sub(events.events_a.start_event_count);
@@ -139,6 +142,16 @@ impl<'a, E: Event> ExactSizeIterator for EventIteratorWithId<'a, E> {
 }
 
 /// A parallel iterator over `Event`s.
+///
+/// # Ordering
+///
+/// The unread events are split into batches, whose size is controlled by the
+/// [`BatchingStrategy`]. Each batch is a contiguous run of events, and is processed by a single
+/// task in send order. Batches are processed concurrently, so events of different batches may be
+/// processed in any order.
+///
+/// [`fold_batches`](Self::fold_batches) returns the result of each batch in send order, which
+/// allows combining them in a deterministic way.
 #[cfg(feature = "multi_threaded")]
 #[derive(Debug)]
 pub struct EventParIter<'a, E: Event> {
@@ -153,6 +166,7 @@ pub struct EventParIter<'a, E: Event> {
 impl<'a, E: Event> EventParIter<'a, E> {
     /// Creates a new parallel iterator over `events` that have not yet been seen by `reader`.
     pub fn new(reader: &'a mut EventCursor<E>, events: &'a Events<E>) -> Self {
+        events.mark_read(events.event_count);
         let a_index = reader
             .last_event_count
             .saturating_sub(events.events_a.start_event_count);
@@ -226,14 +240,8 @@ impl<'a, E: Event> EventParIter<'a, E> {
                 return self.into_iter().for_each(|(e, i)| func(e, i));
             }
 
-            let batch_size = self
-                .batching_strategy
-                .calc_batch_size(|| self.len(), thread_count);
-            let chunks = self.slices.map(|s| s.chunks_exact(batch_size));
-            let remainders = chunks.each_ref().map(core::slice::ChunksExact::remainder);
-
             pool.scope(|scope| {
-                for batch in chunks.into_iter().flatten().chain(remainders) {
+                for batch in self.batches(thread_count) {
                     let func = func.clone();
                     scope.spawn(async move {
                         for event in batch {
@@ -249,6 +257,105 @@ impl<'a, E: Event> EventParIter<'a, E> {
         }
     }
 
+    /// Folds the unread events of each batch in parallel, and returns the results of the batches
+    /// in send order.
+    ///
+    /// Each batch starts from a value returned by `init`, which lets `func` accumulate state local
+    /// to the batch without any synchronization. Batches are contiguous runs of events, whose size
+    /// is controlled by the [`BatchingStrategy`], and each one is folded in send order.
+    ///
+    /// No result is returned for empty batches, so the returned [`Vec`] is empty if there are no
+    /// unread events.
+    ///
+    /// # Panics
+    /// If the [`ComputeTaskPool`] is not initialized. If using this from an event reader that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::ComputeTaskPool
+    #[cfg_attr(
+        target_arch = "wasm32",
+        expect(unused_mut, reason = "not mutated on this target")
+    )]
+    pub fn fold_batches<T, INIT, FN>(mut self, init: INIT, func: FN) -> Vec<T>
+    where
+        T: Send + 'static,
+        INIT: Fn() -> T + Send + Sync + Clone,
+        FN: Fn(T, &'a E, EventId<E>) -> T + Send + Sync + Clone,
+    {
+        #[cfg(target_arch = "wasm32")]
+        {
+            if self.is_empty() {
+                return Vec::new();
+            }
+            vec![self.into_iter().fold(init(), |acc, (e, i)| func(acc, e, i))]
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let pool = bevy_tasks::ComputeTaskPool::get();
+            let thread_count = pool.thread_num();
+            if thread_count <= 1 {
+                if self.is_empty() {
+                    return Vec::new();
+                }
+                return vec![self.into_iter().fold(init(), |acc, (e, i)| func(acc, e, i))];
+            }
+
+            let results = pool.scope(|scope| {
+                for batch in self.batches(thread_count) {
+                    let init = init.clone();
+                    let func = func.clone();
+                    scope.spawn(async move {
+                        batch
+                            .iter()
+                            .fold(init(), |acc, event| func(acc, &event.event, event.event_id))
+                    });
+                }
+            });
+
+            // Events are guaranteed to be read at this point.
+            self.reader.last_event_count += self.unread;
+            self.unread = 0;
+            results
+        }
+    }
+
+    /// Folds the unread events in parallel, like [`fold_batches`](Self::fold_batches), then
+    /// combines the results of the batches with `reduce`, in send order.
+    ///
+    /// As batches are combined in send order, `reduce` only needs to be associative, not
+    /// commutative, for the result to be deterministic. Returns `init()` if there are no unread
+    /// events.
+    ///
+    /// # Panics
+    /// If the [`ComputeTaskPool`] is not initialized. If using this from an event reader that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::ComputeTaskPool
+    pub fn fold_reduce<T, INIT, FN, R>(self, init: INIT, func: FN, reduce: R) -> T
+    where
+        T: Send + 'static,
+        INIT: Fn() -> T + Send + Sync + Clone,
+        FN: Fn(T, &'a E, EventId<E>) -> T + Send + Sync + Clone,
+        R: FnMut(T, T) -> T,
+    {
+        self.fold_batches(init.clone(), func)
+            .into_iter()
+            .reduce(reduce)
+            .unwrap_or_else(init)
+    }
+
+    /// Splits the unread events into batches, in send order.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn batches(&self, thread_count: usize) -> impl Iterator<Item = &'a [EventInstance<E>]> {
+        let batch_size = self
+            .batching_strategy
+            .calc_batch_size(|| self.len(), thread_count);
+        self.slices
+            .into_iter()
+            .flat_map(move |slice| slice.chunks(batch_size))
+    }
+
     /// Returns the number of [`Event`]s to be iterated.
     pub fn len(&self) -> usize {
         self.slices.iter().map(|s| s.len()).sum()
@@ -1,6 +1,7 @@
 //! Event handling types.
 mod base;
 mod collections;
+mod consumable;
 mod event_cursor;
 mod iterators;
 mod mut_iterators;
@@ -13,7 +14,8 @@ mod writer;
 pub(crate) use base::EventInstance;
 pub use base::{Event, EventId};
 pub use bevy_ecs_macros::Event;
-pub use collections::{Events, SendBatchIds};
+pub use collections::{EventRetention, Events, SendBatchIds};
+pub use consumable::{ConsumableEvents, EventConsumer};
 pub use event_cursor::EventCursor;
 #[cfg(feature = "multi_threaded")]
 pub use iterators::EventParIter;
@@ -460,6 +462,62 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "multi_threaded")]
+    #[test]
+    fn test_event_cursor_par_read_fold_reduce() {
+        use crate::{batching::BatchingStrategy, prelude::*};
+        use alloc::vec::Vec;
+
+        #[derive(Resource, Default)]
+        struct Received(Vec<usize>);
+
+        let mut world = World::new();
+        world.init_resource::<Events<TestEvent>>();
+        world.init_resource::<Received>();
+        for i in 0..50 {
+            world.send_event(TestEvent { i });
+        }
+        world.resource_mut::<Events<TestEvent>>().update();
+        for i in 50..100 {
+            world.send_event(TestEvent { i });
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            |mut cursor: Local<EventCursor<TestEvent>>,
+             events: Res<Events<TestEvent>>,
+             mut received: ResMut<Received>| {
+                received.0 = cursor
+                    .par_read(&events)
+                    .batching_strategy(BatchingStrategy::fixed(7))
+                    .fold_reduce(
+                        Vec::new,
+                        |mut batch, event, _| {
+                            batch.push(event.i);
+                            batch
+                        },
+                        |mut a, b| {
+                            a.extend(b);
+                            a
+                        },
+                    );
+            },
+        );
+
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<Received>().0,
+            (0..100).collect::<Vec<_>>(),
+            "batches should be combined in send order"
+        );
+
+        schedule.run(&mut world);
+        assert!(
+            world.resource::<Received>().0.is_empty(),
+            "par_read should have consumed events but didn't"
+        );
+    }
+
     #[cfg(feature = "multi_threaded")]
     #[test]
     fn test_event_cursor_par_read_mut() {
@@ -619,4 +677,162 @@ mod tests {
         });
         schedule.run(&mut world);
     }
+
+    #[test]
+    fn test_events_retention_updates() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::Updates(3));
+        let mut reader = events.get_cursor();
+
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.send(TestEvent { i: 1 });
+        events.update();
+        events.send(TestEvent { i: 2 });
+        assert_eq!(events.len(), 3);
+        events.update();
+        events.send(TestEvent { i: 3 });
+
+        // The first event was sent three updates ago and expired.
+        assert_eq!(events.oldest_event_count(), 1);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 1 }, TestEvent { i: 2 }, TestEvent { i: 3 }]
+        );
+        assert_eq!(reader.missed_events(&events), 0);
+
+        events.update();
+        events.update();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events.get_event(3).map(|(event, _)| event.i), Some(3));
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_events_retention_until_read() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::UntilRead);
+        let mut reader = events.get_cursor();
+
+        events.send(TestEvent { i: 0 });
+        events.send(TestEvent { i: 1 });
+        for _ in 0..5 {
+            events.update();
+        }
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(get_events(&events, &mut reader).len(), 2);
+        events.send(TestEvent { i: 2 });
+        events.update();
+
+        // Only the unread event is kept.
+        assert_eq!(events.len(), 1);
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 2 }]);
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_events_registry_updates_until_expired() {
+        use crate::world::{Mut, World};
+
+        let mut world = World::new();
+        EventRegistry::register_event::<TestEvent>(&mut world);
+        world
+            .resource_mut::<Events<TestEvent>>()
+            .set_retention(EventRetention::Updates(4));
+        let mut last_change_tick = world.change_tick();
+        world.increment_change_tick();
+        world.send_event(TestEvent { i: 0 });
+
+        // The events must keep being updated after they stopped changing to expire.
+        for len in [1, 1, 1, 0] {
+            world.resource_scope(|world, mut registry: Mut<EventRegistry>| {
+                registry.run_updates(world, last_change_tick);
+            });
+            assert_eq!(world.resource::<Events<TestEvent>>().len(), len);
+            last_change_tick = world.change_tick();
+            world.increment_change_tick();
+        }
+    }
+
+    #[test]
+    fn test_consumable_events() {
+        let mut events = ConsumableEvents::<TestEvent>::default();
+        events.extend([TestEvent { i: 0 }, TestEvent { i: 1 }, TestEvent { i: 2 }]);
+
+        // The first consumer only handles the even events.
+        assert_eq!(events.consume(|event| event.i % 2 == 0), 2);
+        assert_eq!(
+            events.iter().copied().collect::<Vec<_>>(),
+            vec![TestEvent { i: 1 }]
+        );
+
+        // The second consumer sees only what's left.
+        assert_eq!(
+            events.consume_all().collect::<Vec<_>>(),
+            vec![TestEvent { i: 1 }]
+        );
+        assert!(events.is_empty());
+
+        events.send(TestEvent { i: 3 });
+        events.update();
+        assert_eq!(events.len(), 1);
+        events.update();
+        assert!(events.is_empty());
+
+        events.set_retention(EventRetention::UntilRead);
+        events.send(TestEvent { i: 4 });
+        for _ in 0..5 {
+            events.update();
+        }
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_event_consumer_systems() {
+        use crate::{
+            resource::Resource,
+            schedule::{IntoSystemConfigs, Schedule},
+            system::ResMut,
+            world::World,
+        };
+
+        #[derive(Resource, Default)]
+        struct Handled(Vec<(usize, &'static str)>);
+
+        let mut world = World::new();
+        world.init_resource::<ConsumableEvents<TestEvent>>();
+        world.init_resource::<Handled>();
+        world
+            .resource_mut::<ConsumableEvents<TestEvent>>()
+            .extend((0..4).map(|i| TestEvent { i }));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                |mut consumer: EventConsumer<TestEvent>, mut handled: ResMut<Handled>| {
+                    consumer.consume(|event| {
+                        let consumed = event.i < 2;
+                        if consumed {
+                            handled.0.push((event.i, "first"));
+                        }
+                        consumed
+                    });
+                },
+                |mut consumer: EventConsumer<TestEvent>, mut handled: ResMut<Handled>| {
+                    for event in consumer.consume_all() {
+                        handled.0.push((event.i, "second"));
+                    }
+                },
+            )
+                .chain(),
+        );
+        schedule.run(&mut world);
+
+        assert_eq!(
+            world.resource::<Handled>().0,
+            vec![(0, "first"), (1, "first"), (2, "second"), (3, "second")]
+        );
+        assert!(world.resource::<ConsumableEvents<TestEvent>>().is_empty());
+    }
 }
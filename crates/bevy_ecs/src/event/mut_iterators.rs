@@ -57,6 +57,7 @@ pub struct EventMutIteratorWithId<'a, E: Event> {
 impl<'a, E: Event> EventMutIteratorWithId<'a, E> {
     /// Creates a new iterator that yields any `events` that have not yet been seen by `mutator`.
     pub fn new(mutator: &'a mut EventCursor<E>, events: &'a mut Events<E>) -> Self {
+        events.mark_read(events.event_count);
         let a_index = mutator
             .last_event_count
             .saturating_sub(events.events_a.start_event_count);
@@ -156,6 +157,7 @@ pub struct EventMutParIter<'a, E: Event> {
 impl<'a, E: Event> EventMutParIter<'a, E> {
     /// Creates a new parallel iterator over `events` that have not yet been seen by `mutator`.
     pub fn new(mutator: &'a mut EventCursor<E>, events: &'a mut Events<E>) -> Self {
+        events.mark_read(events.event_count);
         let a_index = mutator
             .last_event_count
             .saturating_sub(events.events_a.start_event_count);
@@ -34,7 +34,8 @@ impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     }
 
     /// Returns a parallel iterator over the events this [`EventReader`] has not seen yet.
-    /// See also [`for_each`](EventParIter::for_each).
+    /// See also [`for_each`](EventParIter::for_each), and [`fold_reduce`](EventParIter::fold_reduce)
+    /// to accumulate state per batch of events.
     ///
     /// # Example
     /// ```
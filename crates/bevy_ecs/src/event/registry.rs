@@ -3,7 +3,7 @@ use alloc::vec::Vec;
 use bevy_ecs::{
     change_detection::{DetectChangesMut, MutUntyped},
     component::{ComponentId, Tick},
-    event::{Event, Events},
+    event::{ConsumableEvents, Event, Events},
     resource::Resource,
     world::World,
 };
@@ -11,11 +11,13 @@ use bevy_ecs::{
 #[doc(hidden)]
 struct RegisteredEvent {
     component_id: ComponentId,
-    // Required to flush the secondary buffer and drop events even if left unchanged.
+    // Required to drop the remaining events even if left unchanged.
     previously_updated: bool,
-    // SAFETY: The component ID and the function must be used to fetch the Events<T> resource
-    // of the same type initialized in `register_event`, or improper type casts will occur.
-    update: unsafe fn(MutUntyped),
+    // SAFETY: The component ID and the function must be used to fetch the Events<T> or
+    // ConsumableEvents<T> resource of the same type initialized in `register_event` or
+    // `register_consumable_event`, or improper type casts will occur.
+    // Returns true if events remain after the update.
+    update: unsafe fn(MutUntyped) -> bool,
 }
 
 /// A registry of all of the [`Events`] in the [`World`], used by [`event_update_system`](crate::event::update::event_update_system)
@@ -56,9 +58,30 @@ impl EventRegistry {
             previously_updated: false,
             update: |ptr| {
                 // SAFETY: The resource was initialized with the type Events<T>.
-                unsafe { ptr.with_type::<Events<T>>() }
-                    .bypass_change_detection()
-                    .update();
+                let mut events = unsafe { ptr.with_type::<Events<T>>() };
+                let events = events.bypass_change_detection();
+                events.update();
+                !events.is_empty()
+            },
+        });
+    }
+
+    /// Registers a [`ConsumableEvents`] channel to be updated in a given [`World`]
+    ///
+    /// If no instance of the [`EventRegistry`] exists in the world, this will add one - otherwise it will use
+    /// the existing instance.
+    pub fn register_consumable_event<T: Event>(world: &mut World) {
+        let component_id = world.init_resource::<ConsumableEvents<T>>();
+        let mut registry = world.get_resource_or_init::<Self>();
+        registry.event_updates.push(RegisteredEvent {
+            component_id,
+            previously_updated: false,
+            update: |ptr| {
+                // SAFETY: The resource was initialized with the type ConsumableEvents<T>.
+                let mut events = unsafe { ptr.with_type::<ConsumableEvents<T>>() };
+                let events = events.bypass_change_detection();
+                events.update();
+                !events.is_empty()
             },
         });
     }
@@ -72,11 +95,10 @@ impl EventRegistry {
                 if registered_event.previously_updated || has_changed {
                     // SAFETY: The update function pointer is called with the resource
                     // fetched from the same component ID.
-                    unsafe { (registered_event.update)(events) };
-                    // Always set to true if the events have changed, otherwise disable running on the second invocation
-                    // to wait for more changes.
-                    registered_event.previously_updated =
-                        has_changed || !registered_event.previously_updated;
+                    let has_events = unsafe { (registered_event.update)(events) };
+                    // Keep updating while events remain, so that they are dropped according to
+                    // their retention, otherwise wait for more changes.
+                    registered_event.previously_updated = has_events;
                 }
             }
         }
@@ -0,0 +1,236 @@
+//! Provides the [`Index`] resource, used to find entities by the value of one of their components.
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, Immutable},
+    entity::Entity,
+    entity_disabling::Disabled,
+    observer::Trigger,
+    query::Has,
+    resource::Resource,
+    world::{DeferredWorld, OnInsert, OnReplace, World},
+};
+
+use bevy_platform_support::collections::HashMap;
+use core::{hash::Hash, marker::PhantomData};
+use smallvec::SmallVec;
+
+/// A secondary index mapping the values of the component `C` to the entities holding them.
+///
+/// Finding an entity by the value of one of its components otherwise requires iterating over
+/// a [`Query`](crate::system::Query). Once created with [`World::init_index`], the index is kept up to date
+/// by observers as `C` is inserted, replaced and removed, so lookups only cost a hash map access.
+///
+/// Only [immutable](Immutable) components can be indexed: a mutable component could be changed in place
+/// without the index noticing. Values don't need to be unique; use [`Index::get_all`] to find every entity
+/// holding a value.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, index::Index};
+/// #[derive(Component, Clone, PartialEq, Eq, Hash)]
+/// #[component(immutable)]
+/// struct NetworkId(u64);
+///
+/// let mut world = World::new();
+/// world.init_index::<NetworkId>();
+/// let player = world.spawn(NetworkId(42)).id();
+///
+/// fn find_player(index: Res<Index<NetworkId>>) {
+///     assert!(index.get(&NetworkId(42)).is_some());
+/// }
+/// # world.run_system_cached(find_player).unwrap();
+/// assert_eq!(world.resource::<Index<NetworkId>>().get(&NetworkId(42)), Some(player));
+/// ```
+#[derive(Resource)]
+pub struct Index<C>
+where
+    C: Component<Mutability = Immutable> + Eq + Hash + Clone,
+{
+    entities: HashMap<C, SmallVec<[Entity; 1]>>,
+}
+
+impl<C> Default for Index<C>
+where
+    C: Component<Mutability = Immutable> + Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+        }
+    }
+}
+
+impl<C> Index<C>
+where
+    C: Component<Mutability = Immutable> + Eq + Hash + Clone,
+{
+    /// Returns an entity holding `value`, if any.
+    ///
+    /// If several entities hold `value`, the one that received it first is returned.
+    pub fn get(&self, value: &C) -> Option<Entity> {
+        self.get_all(value).first().copied()
+    }
+
+    /// Returns all entities holding `value`, in the order they received it.
+    pub fn get_all(&self, value: &C) -> &[Entity] {
+        self.entities
+            .get(value)
+            .map(SmallVec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if any entity holds `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the number of distinct indexed values.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity holds `C`.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Iterates over the indexed values and the entities holding them.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &[Entity])> {
+        self.entities
+            .iter()
+            .map(|(value, entities)| (value, entities.as_slice()))
+    }
+
+    fn insert(&mut self, value: C, entity: Entity) {
+        self.entities.entry(value).or_default().push(entity);
+    }
+
+    fn remove(&mut self, value: &C, entity: Entity) {
+        let Some(entities) = self.entities.get_mut(value) else {
+            return;
+        };
+        entities.retain(|e| *e != entity);
+        if entities.is_empty() {
+            self.entities.remove(value);
+        }
+    }
+
+    fn on_insert(trigger: Trigger<OnInsert, C>, mut world: DeferredWorld) {
+        let entity = trigger.target();
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        // The index may have been removed from the world.
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.insert(value, entity);
+        }
+    }
+
+    fn on_replace(trigger: Trigger<OnReplace, C>, mut world: DeferredWorld) {
+        let entity = trigger.target();
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        // The index may have been removed from the world.
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.remove(&value, entity);
+        }
+    }
+}
+
+/// Marks that the observers keeping [`Index<C>`] up to date were added to the world.
+///
+/// The index resource may be removed and created again, but the observers must only be added once.
+#[derive(Resource)]
+struct IndexObservers<C: Component>(PhantomData<fn() -> C>);
+
+impl World {
+    /// Creates the [`Index`] of the component `C`, making it available as a resource.
+    ///
+    /// Entities already holding `C` are added to the index. Calling this again for the same component
+    /// does nothing, so plugins can declare the indexes they use without coordinating.
+    pub fn init_index<C>(&mut self)
+    where
+        C: Component<Mutability = Immutable> + Eq + Hash + Clone,
+    {
+        if self.contains_resource::<Index<C>>() {
+            return;
+        }
+
+        let mut index = Index::<C>::default();
+        // Mentioning `Disabled` makes disabled entities visible to the query.
+        let mut query = self.query::<(Entity, &C, Has<Disabled>)>();
+        for (entity, value, _) in query.iter(self) {
+            index.insert(value.clone(), entity);
+        }
+        self.insert_resource(index);
+
+        if !self.contains_resource::<IndexObservers<C>>() {
+            self.insert_resource(IndexObservers::<C>(PhantomData));
+            self.add_observer(Index::<C>::on_insert);
+            self.add_observer(Index::<C>::on_replace);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
+    #[component(immutable)]
+    struct NetworkId(u64);
+
+    #[test]
+    fn index_tracks_component_values() {
+        let mut world = World::new();
+        let a = world.spawn(NetworkId(1)).id();
+        world.init_index::<NetworkId>();
+        world.init_index::<NetworkId>();
+
+        let b = world.spawn(NetworkId(2)).id();
+        let c = world.spawn(NetworkId(2)).id();
+        let index = world.resource::<Index<NetworkId>>();
+        assert_eq!(index.get(&NetworkId(1)), Some(a));
+        assert_eq!(index.get_all(&NetworkId(2)), &[b, c]);
+        assert_eq!(index.len(), 2);
+
+        world.entity_mut(a).insert(NetworkId(3));
+        world.entity_mut(b).remove::<NetworkId>();
+        world.despawn(c);
+        let index = world.resource::<Index<NetworkId>>();
+        assert_eq!(index.get(&NetworkId(1)), None);
+        assert_eq!(index.get(&NetworkId(2)), None);
+        assert_eq!(index.get(&NetworkId(3)), Some(a));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn removed_index_is_not_updated() {
+        let mut world = World::new();
+        world.init_index::<NetworkId>();
+        let a = world.spawn(NetworkId(1)).id();
+        world.remove_resource::<Index<NetworkId>>();
+
+        world.entity_mut(a).insert(NetworkId(2));
+        world.spawn(NetworkId(3));
+        assert!(!world.contains_resource::<Index<NetworkId>>());
+    }
+
+    #[test]
+    fn recreated_index_is_updated_once() {
+        let mut world = World::new();
+        world.init_index::<NetworkId>();
+        world.remove_resource::<Index<NetworkId>>();
+        let a = world.spawn(NetworkId(1)).id();
+        world.init_index::<NetworkId>();
+
+        let b = world.spawn(NetworkId(1)).id();
+        let index = world.resource::<Index<NetworkId>>();
+        assert_eq!(index.get_all(&NetworkId(1)), &[a, b]);
+
+        world.entity_mut(a).remove::<NetworkId>();
+        let index = world.resource::<Index<NetworkId>>();
+        assert_eq!(index.get_all(&NetworkId(1)), &[b]);
+    }
+}
@@ -43,10 +43,14 @@ pub mod entity_disabling;
 pub mod event;
 pub mod hierarchy;
 pub mod identifier;
+pub mod index;
 pub mod intern;
 pub mod label;
 pub mod name;
 pub mod observer;
+#[cfg(feature = "persistent_id")]
+pub mod persistent_id;
+pub mod pool;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
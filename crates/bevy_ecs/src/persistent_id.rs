@@ -0,0 +1,168 @@
+//! Provides the [`PersistentEntityId`] [`Component`], identifying an [`Entity`] across despawns,
+//! app runs and serialization.
+
+use crate::{
+    self as bevy_ecs,
+    component::Component,
+    entity::{hash_map::EntityHashMap, Entity},
+    index::Index,
+    query::{QueryFilter, Without},
+    world::World,
+};
+
+use alloc::vec::Vec;
+use core::fmt;
+use uuid::Uuid;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::reflect::ReflectComponent,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A universally unique identifier for an entity, which stays the same when the entity is saved
+/// and loaded again, sent over the network, or despawned and spawned again.
+///
+/// An [`Entity`] is only unique within its [`World`], and only while it is alive. A persistent id
+/// can be used to refer to the same entity from other worlds, files or machines. Use
+/// [`PersistentEntityIds`] to find the entity holding an id.
+///
+/// The [`Default`] value is a new random id, so ids can be assigned automatically by requiring
+/// this component from other components, or to existing entities with
+/// [`World::assign_persistent_entity_ids`].
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, persistent_id::{PersistentEntityId, PersistentEntityIds}};
+/// #[derive(Component)]
+/// #[require(PersistentEntityId)]
+/// struct Player;
+///
+/// let mut world = World::new();
+/// world.init_index::<PersistentEntityId>();
+/// let player = world.spawn(Player).id();
+///
+/// let id = *world.get::<PersistentEntityId>(player).unwrap();
+/// assert_eq!(world.resource::<PersistentEntityIds>().get(&id), Some(player));
+/// ```
+///
+/// This component is [immutable](crate::component::Immutable): insert a new id to change it.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[component(immutable)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug, PartialEq, Hash)
+)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PersistentEntityId(pub Uuid);
+
+impl PersistentEntityId {
+    /// Creates a new random id.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for PersistentEntityId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Uuid> for PersistentEntityId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl fmt::Display for PersistentEntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The [`Index`] of [`PersistentEntityId`], finding the entity holding an id.
+///
+/// Created by [`World::init_index`], which the `ScenePlugin` of `bevy_scene` calls.
+pub type PersistentEntityIds = Index<PersistentEntityId>;
+
+impl Index<PersistentEntityId> {
+    /// Maps `entities` of another world, or of a serialized scene, to the entities of this world
+    /// holding the same [`PersistentEntityId`].
+    ///
+    /// Entities whose id isn't held by any entity of this world are left out of the map. The map
+    /// can seed a [`SceneEntityMapper`](crate::entity::SceneEntityMapper), so that the references
+    /// to these entities are remapped to the existing entities, and so that a scene written to the
+    /// world updates them instead of spawning them again.
+    pub fn entity_map<'a>(
+        &self,
+        entities: impl IntoIterator<Item = (Entity, &'a PersistentEntityId)>,
+    ) -> EntityHashMap<Entity> {
+        entities
+            .into_iter()
+            .filter_map(|(entity, id)| Some((entity, self.get(id)?)))
+            .collect()
+    }
+}
+
+impl World {
+    /// Inserts a new [`PersistentEntityId`] on the entities matching the filter `F` which don't
+    /// have one yet, for example before saving them to a scene.
+    pub fn assign_persistent_entity_ids<F: QueryFilter>(&mut self) {
+        let entities = self
+            .query_filtered::<Entity, (F, Without<PersistentEntityId>)>()
+            .iter(self)
+            .collect::<Vec<_>>();
+        for entity in entities {
+            self.entity_mut(entity).insert(PersistentEntityId::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::With;
+
+    #[derive(Component)]
+    struct Saved;
+
+    #[test]
+    fn assign_and_map_persistent_ids() {
+        let mut world = World::new();
+        world.init_index::<PersistentEntityId>();
+        let kept = PersistentEntityId::new();
+        let a = world.spawn((Saved, kept)).id();
+        let b = world.spawn(Saved).id();
+        let c = world.spawn_empty().id();
+
+        world.assign_persistent_entity_ids::<With<Saved>>();
+        assert_eq!(world.get::<PersistentEntityId>(a), Some(&kept));
+        assert!(world.get::<PersistentEntityId>(b).is_some());
+        assert!(world.get::<PersistentEntityId>(c).is_none());
+
+        // Entities of another world are mapped to the entities with the same id.
+        let mut other = World::new();
+        let other_a = other.spawn(kept).id();
+        let other_d = other.spawn(PersistentEntityId::new()).id();
+        let ids = [
+            (other_a, *other.get::<PersistentEntityId>(other_a).unwrap()),
+            (other_d, *other.get::<PersistentEntityId>(other_d).unwrap()),
+        ];
+        let map = world
+            .resource::<PersistentEntityIds>()
+            .entity_map(ids.iter().map(|(entity, id)| (*entity, id)));
+        assert_eq!(map.get(&other_a), Some(&a));
+        assert_eq!(map.get(&other_d), None);
+    }
+}
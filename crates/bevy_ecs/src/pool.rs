@@ -0,0 +1,477 @@
+//! Entity pools, recycling the entities of short-lived objects instead of despawning them.
+//!
+//! Spawning and despawning many entities every frame, like bullets or particles, allocates new
+//! entities and builds their components from scratch. An entity returned to a pool with
+//! [`EntityCommands::despawn_to_pool`] keeps its components and is [`Disabled`] instead, hiding it
+//! from queries. The next [`Commands::spawn_pooled`] on the same pool enables it again, inserting
+//! the new bundle over its old components, and only spawns a new entity when the pool is empty.
+//!
+//! Note that inserting and removing [`Disabled`] still moves the entity between two archetypes,
+//! copying its components, each time it enters and leaves the pool. Pools save the entity
+//! allocation and whatever the components allocate, like the buffers of a `Vec`, not the archetype
+//! moves, so they mostly pay off for entities with expensive components.
+//!
+//! ```
+//! # use bevy_ecs::{prelude::*, pool::{EntityPool, PoolId}};
+//! #[derive(Component, Default)]
+//! struct Velocity(f32);
+//!
+//! #[derive(Component, Default)]
+//! struct Hits(u32);
+//!
+//! const BULLETS: PoolId = PoolId("bullets");
+//!
+//! let mut world = World::new();
+//! // Returned bullets have their hit count reset.
+//! world.register_entity_pool(BULLETS, EntityPool::new().with_reset::<Hits>());
+//!
+//! fn fire(mut commands: Commands) {
+//!     commands.spawn_pooled(BULLETS, Velocity(10.0));
+//! }
+//!
+//! fn hit(mut commands: Commands, bullets: Query<(Entity, &Hits)>) {
+//!     for (bullet, hits) in &bullets {
+//!         if hits.0 > 0 {
+//!             commands.entity(bullet).despawn_to_pool(BULLETS);
+//!         }
+//!     }
+//! }
+//! # world.run_system_cached(fire).unwrap();
+//! # world.run_system_cached(hit).unwrap();
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    bundle::Bundle,
+    component::{Component, HookContext},
+    entity::Entity,
+    entity_disabling::Disabled,
+    resource::Resource,
+    system::{Commands, EntityCommands},
+    world::{DeferredWorld, EntityWorldMut, World},
+};
+
+use alloc::{boxed::Box, vec::Vec};
+use bevy_platform_support::{collections::HashMap, sync::Arc};
+use core::fmt;
+
+/// Identifies an entity pool, see the [module docs](crate::pool).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PoolId(pub &'static str);
+
+impl From<&'static str> for PoolId {
+    fn from(name: &'static str) -> Self {
+        Self(name)
+    }
+}
+
+impl fmt::Display for PoolId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+type ResetHook = Box<dyn Fn(&mut EntityWorldMut) + Send + Sync>;
+
+/// The configuration of an entity pool, registered with [`World::register_entity_pool`].
+///
+/// Pools which aren't registered are created on their first use with the default configuration,
+/// which keeps the components of returned entities as they are, and has no capacity limit.
+#[derive(Default)]
+pub struct EntityPool {
+    resets: Vec<ResetHook>,
+    capacity: Option<usize>,
+}
+
+impl EntityPool {
+    /// Creates a pool configuration with no reset logic and no capacity limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resets the component `C` of the returned entities to its default value, if they have it.
+    pub fn with_reset<C: Component + Default>(self) -> Self {
+        self.with_reset_hook(|entity| {
+            if entity.contains::<C>() {
+                entity.insert(C::default());
+            }
+        })
+    }
+
+    /// Runs `hook` on the entities returned to the pool, before they are disabled.
+    ///
+    /// Hooks run in the order they are added, after the resets of [`EntityPool::with_reset`]
+    /// added before them.
+    pub fn with_reset_hook(
+        mut self,
+        hook: impl Fn(&mut EntityWorldMut) + Send + Sync + 'static,
+    ) -> Self {
+        self.resets.push(Box::new(hook));
+        self
+    }
+
+    /// Limits the number of idle entities kept in the pool. Entities returned to a full pool are
+    /// despawned.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+}
+
+/// The utilization of an entity pool, see [`EntityPools::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The number of entities waiting in the pool to be spawned again.
+    pub idle: usize,
+    /// The number of entities of the pool which are spawned and haven't been returned.
+    pub active: usize,
+    /// The number of pooled spawns which spawned a new entity, as the pool was empty.
+    pub allocated: u64,
+    /// The number of pooled spawns which reused an idle entity.
+    pub reused: u64,
+    /// The number of entities returned to the pool.
+    pub returned: u64,
+    /// The number of entities despawned instead of being returned, as the pool was full.
+    pub discarded: u64,
+}
+
+impl PoolStats {
+    /// Returns the fraction of the entities of the pool which are active, from `0.0` to `1.0`.
+    pub fn utilization(&self) -> f32 {
+        let total = self.active + self.idle;
+        if total == 0 {
+            return 0.0;
+        }
+        self.active as f32 / total as f32
+    }
+
+    /// Returns the fraction of the pooled spawns which reused an idle entity, from `0.0` to `1.0`.
+    pub fn reuse_ratio(&self) -> f32 {
+        let spawns = self.allocated + self.reused;
+        if spawns == 0 {
+            return 0.0;
+        }
+        self.reused as f32 / spawns as f32
+    }
+}
+
+#[derive(Default)]
+struct PoolState {
+    config: Arc<EntityPool>,
+    idle: Vec<Entity>,
+    stats: PoolStats,
+}
+
+/// The entity pools of a [`World`] and their idle entities.
+#[derive(Resource, Default)]
+pub struct EntityPools {
+    pools: HashMap<PoolId, PoolState>,
+}
+
+impl EntityPools {
+    /// Returns the utilization of a pool, or `None` if it was never used or registered.
+    pub fn stats(&self, pool: PoolId) -> Option<PoolStats> {
+        self.pools.get(&pool).map(PoolState::stats)
+    }
+
+    /// Iterates over the pools and their utilization.
+    pub fn iter(&self) -> impl Iterator<Item = (PoolId, PoolStats)> + '_ {
+        self.pools.iter().map(|(id, pool)| (*id, pool.stats()))
+    }
+
+    /// Returns the idle entities of a pool.
+    pub fn idle(&self, pool: PoolId) -> &[Entity] {
+        self.pools
+            .get(&pool)
+            .map(|pool| pool.idle.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn pool_mut(&mut self, pool: PoolId) -> &mut PoolState {
+        self.pools.entry(pool).or_default()
+    }
+}
+
+impl PoolState {
+    fn stats(&self) -> PoolStats {
+        PoolStats {
+            idle: self.idle.len(),
+            ..self.stats
+        }
+    }
+}
+
+/// Marks the entities belonging to an entity pool, which are tracked by its [`PoolStats`].
+///
+/// Inserted by [`World::spawn_pooled`] and [`EntityWorldMut::despawn_to_pool`].
+#[derive(Component, Clone, Copy, Debug)]
+#[component(on_remove = Pooled::on_remove)]
+pub struct Pooled {
+    pool: PoolId,
+    /// The index of the entity in the idle entities of the pool, if it's idle.
+    idle_index: Option<usize>,
+}
+
+impl Pooled {
+    /// Returns the pool the entity belongs to.
+    pub fn pool(&self) -> PoolId {
+        self.pool
+    }
+
+    fn on_remove(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(&Pooled { pool, idle_index }) = world.get::<Pooled>(entity) else {
+            return;
+        };
+        let Some(mut pools) = world.get_resource_mut::<EntityPools>() else {
+            return;
+        };
+        let state = pools.pool_mut(pool);
+        let Some(index) = idle_index else {
+            state.stats.active = state.stats.active.saturating_sub(1);
+            return;
+        };
+        state.idle.swap_remove(index);
+        // The last idle entity took the place of the removed one.
+        if let Some(&moved) = state.idle.get(index) {
+            if let Some(mut pooled) = world.get_mut::<Pooled>(moved) {
+                pooled.idle_index = Some(index);
+            }
+        }
+    }
+}
+
+impl World {
+    /// Registers the configuration of an entity pool, replacing its previous configuration.
+    ///
+    /// The idle entities of the pool, if it was already used, are kept.
+    pub fn register_entity_pool(&mut self, pool: impl Into<PoolId>, config: EntityPool) {
+        self.get_resource_or_init::<EntityPools>()
+            .pool_mut(pool.into())
+            .config = Arc::new(config);
+    }
+
+    /// Spawns an entity with `bundle` from an entity pool.
+    ///
+    /// If the pool has an idle entity, it is enabled again and `bundle` is inserted over its
+    /// components, which are otherwise kept as they were when it was returned to the pool.
+    /// Otherwise, a new entity is spawned.
+    pub fn spawn_pooled<B: Bundle>(
+        &mut self,
+        pool: impl Into<PoolId>,
+        bundle: B,
+    ) -> EntityWorldMut {
+        let pool = pool.into();
+        let reused = {
+            let mut pools = self.get_resource_or_init::<EntityPools>();
+            let state = pools.pool_mut(pool);
+            state.stats.active += 1;
+            let reused = state.idle.pop();
+            if reused.is_some() {
+                state.stats.reused += 1;
+            } else {
+                state.stats.allocated += 1;
+            }
+            reused
+        };
+        let Some(entity) = reused else {
+            return self.spawn((
+                bundle,
+                Pooled {
+                    pool,
+                    idle_index: None,
+                },
+            ));
+        };
+
+        let mut entity = self.entity_mut(entity);
+        if let Some(mut pooled) = entity.get_mut::<Pooled>() {
+            pooled.idle_index = None;
+        }
+        entity.insert(bundle).remove::<Disabled>();
+        entity
+    }
+}
+
+impl EntityWorldMut<'_> {
+    /// Returns the entity to an entity pool, to be spawned again by [`World::spawn_pooled`],
+    /// instead of despawning it.
+    ///
+    /// The reset logic of the pool runs on the entity, then it is [`Disabled`]. If the pool is full,
+    /// the entity is despawned instead. Entities which weren't spawned from a pool can be returned
+    /// to one as well. Returning an idle entity again does nothing.
+    pub fn despawn_to_pool(mut self, pool: impl Into<PoolId>) {
+        let pool = pool.into();
+        let entity = self.id();
+        let previous = self.get::<Pooled>().copied();
+        if previous.is_some_and(|previous| previous.idle_index.is_some()) {
+            return;
+        }
+        let previous_pool = previous.map(|previous| previous.pool);
+
+        let (config, full) = self.world_scope(|world| {
+            let mut pools = world.get_resource_or_init::<EntityPools>();
+            let state = pools.pool_mut(pool);
+            let full = state
+                .config
+                .capacity
+                .is_some_and(|capacity| state.idle.len() >= capacity);
+            state.stats.discarded += u64::from(full);
+            (state.config.clone(), full)
+        });
+        if full {
+            // The hook of `Pooled` deactivates the entity when it's despawned.
+            self.despawn();
+            return;
+        }
+
+        for reset in &config.resets {
+            reset(&mut self);
+            if self.is_despawned() {
+                return;
+            }
+        }
+        let idle_index = self.world_scope(|world| {
+            let mut pools = world.resource_mut::<EntityPools>();
+            if let Some(previous_pool) = previous_pool {
+                let previous = pools.pool_mut(previous_pool);
+                previous.stats.active = previous.stats.active.saturating_sub(1);
+            }
+            let state = pools.pool_mut(pool);
+            state.idle.push(entity);
+            state.stats.returned += 1;
+            state.idle.len() - 1
+        });
+        // Replacing `Pooled`, rather than removing it, doesn't run its hook.
+        self.insert((
+            Pooled {
+                pool,
+                idle_index: Some(idle_index),
+            },
+            Disabled,
+        ));
+    }
+}
+
+impl Commands<'_, '_> {
+    /// Spawns an entity with `bundle` from an entity pool, reusing an idle entity of the pool if
+    /// there is one.
+    ///
+    /// As the entity is only known once the command is applied, it isn't returned: use
+    /// [`World::spawn_pooled`] to access it, for example in a custom command.
+    pub fn spawn_pooled<B: Bundle>(&mut self, pool: impl Into<PoolId>, bundle: B) {
+        let pool = pool.into();
+        self.queue(move |world: &mut World| {
+            world.spawn_pooled(pool, bundle);
+        });
+    }
+}
+
+impl EntityCommands<'_> {
+    /// Returns the entity to an entity pool instead of despawning it.
+    ///
+    /// See [`EntityWorldMut::despawn_to_pool`] for more details.
+    pub fn despawn_to_pool(&mut self, pool: impl Into<PoolId>) {
+        let pool = pool.into();
+        self.queue(move |entity: EntityWorldMut| entity.despawn_to_pool(pool));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    const BULLETS: PoolId = PoolId("bullets");
+
+    #[derive(Component, Default, PartialEq, Debug)]
+    struct Hits(u32);
+
+    #[derive(Component)]
+    struct Velocity(f32);
+
+    #[test]
+    fn pooled_entities_are_reused() {
+        let mut world = World::new();
+        world.register_entity_pool(BULLETS, EntityPool::new().with_reset::<Hits>());
+
+        let bullet = world.spawn_pooled(BULLETS, (Velocity(1.0), Hits(0))).id();
+        world.entity_mut(bullet).insert(Hits(3));
+        world.entity_mut(bullet).despawn_to_pool(BULLETS);
+
+        // The idle bullet is hidden from queries, and its hits are reset.
+        assert_eq!(world.query::<&Velocity>().iter(&world).count(), 0);
+        assert_eq!(world.get::<Hits>(bullet), Some(&Hits(0)));
+        assert_eq!(world.resource::<EntityPools>().idle(BULLETS), &[bullet]);
+
+        let reused = world.spawn_pooled(BULLETS, Velocity(2.0)).id();
+        assert_eq!(reused, bullet);
+        assert_eq!(world.query::<&Velocity>().single(&world).0, 2.0);
+
+        let stats = world.resource::<EntityPools>().stats(BULLETS).unwrap();
+        assert_eq!(
+            stats,
+            PoolStats {
+                idle: 0,
+                active: 1,
+                allocated: 1,
+                reused: 1,
+                returned: 1,
+                discarded: 0,
+            }
+        );
+        assert_eq!(stats.utilization(), 1.0);
+        assert_eq!(stats.reuse_ratio(), 0.5);
+    }
+
+    #[test]
+    fn full_pools_despawn_entities() {
+        let mut world = World::new();
+        world.register_entity_pool(BULLETS, EntityPool::new().with_capacity(1));
+
+        let a = world.spawn_pooled(BULLETS, Velocity(1.0)).id();
+        let b = world.spawn_pooled(BULLETS, Velocity(1.0)).id();
+        world.entity_mut(a).despawn_to_pool(BULLETS);
+        world.entity_mut(b).despawn_to_pool(BULLETS);
+        assert!(world.get_entity(a).is_ok());
+        assert!(world.get_entity(b).is_err());
+
+        // Despawning an idle entity removes it from the pool.
+        world.despawn(a);
+        let stats = world.resource::<EntityPools>().stats(BULLETS).unwrap();
+        assert_eq!((stats.idle, stats.active, stats.discarded), (0, 0, 1));
+    }
+
+    #[test]
+    fn despawning_idle_entities_keeps_the_pool_consistent() {
+        let mut world = World::new();
+        let bullets: Vec<_> = (0..3)
+            .map(|_| world.spawn_pooled(BULLETS, Velocity(1.0)).id())
+            .collect();
+        for &bullet in &bullets {
+            world.entity_mut(bullet).despawn_to_pool(BULLETS);
+        }
+
+        // The last idle entity takes the place of the despawned one.
+        world.despawn(bullets[0]);
+        world.despawn(bullets[2]);
+        assert_eq!(world.resource::<EntityPools>().idle(BULLETS), &[bullets[1]]);
+        assert_eq!(world.spawn_pooled(BULLETS, Velocity(2.0)).id(), bullets[1]);
+    }
+
+    #[test]
+    fn commands_use_pools() {
+        let mut world = World::new();
+        world.commands().spawn_pooled(BULLETS, Velocity(1.0));
+        world.flush();
+        let bullet = world
+            .query_filtered::<Entity, With<Velocity>>()
+            .single(&world);
+
+        world.commands().entity(bullet).despawn_to_pool(BULLETS);
+        // Returning an idle entity again does nothing.
+        world.commands().entity(bullet).despawn_to_pool(BULLETS);
+        world.flush();
+        let stats = world.resource::<EntityPools>().stats(BULLETS).unwrap();
+        assert_eq!((stats.idle, stats.active, stats.returned), (1, 0, 1));
+    }
+}
@@ -109,6 +109,16 @@ pub enum QuerySingleError {
     MultipleEntities(&'static str),
 }
 
+/// An error that occurs when zipping two queries with [`Query::zip`](crate::system::Query::zip).
+#[derive(Debug, Error)]
+pub enum QueryZipError {
+    /// The fetches of the queries conflict, like `&mut A` and `&A`.
+    ///
+    /// Filters are not taken into account, so this happens even if no entity can match both queries.
+    #[error("The fetches of the queries {0} and {1} conflict")]
+    ConflictingFetches(&'static str, &'static str),
+}
+
 #[cfg(test)]
 mod test {
     use crate as bevy_ecs;
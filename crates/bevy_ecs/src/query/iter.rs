@@ -810,6 +810,53 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryIter<'w, 's, D, F> {
         self.sort_impl::<L>(move |keyed_query| keyed_query.sort_by_cached_key(|(lens, _)| f(lens)))
     }
 
+    /// Sorts all query items into a new iterator, ordered by [`Entity::index`].
+    ///
+    /// The default iteration order depends on how entities are laid out in tables and archetypes,
+    /// which in turn depends on the order in which components were inserted and removed.
+    /// This order only depends on the entities themselves, which makes it suitable for
+    /// simulations that need to produce identical results between runs and across platforms.
+    ///
+    /// The sort is not cached across system runs.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `next` has been called on `QueryIter` before, unless the underlying `Query` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # let mut world = World::new();
+    /// #
+    /// #[derive(Component)]
+    /// struct Velocity(f32);
+    ///
+    /// fn integrate(mut query: Query<&mut Velocity>) {
+    ///     for mut velocity in query.iter_mut().sort_by_entity_index() {
+    ///         velocity.0 *= 0.99;
+    ///     }
+    /// }
+    /// #
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_systems(integrate);
+    /// # schedule.run(&mut world);
+    /// ```
+    pub fn sort_by_entity_index(
+        self,
+    ) -> QuerySortedIter<
+        'w,
+        's,
+        D,
+        F,
+        impl ExactSizeIterator<Item = Entity> + DoubleEndedIterator + FusedIterator + 'w,
+    > {
+        // Live entities never share an index, so an unstable sort still yields a total order.
+        self.sort_impl::<Entity>(|keyed_query| {
+            keyed_query.sort_unstable_by_key(|(entity, _)| entity.index());
+        })
+    }
+
     /// Shared implementation for the various `sort` methods.
     /// This uses the lens to collect the items for sorting, but delegates the actual sorting to the provided closure.
     ///
@@ -2658,6 +2705,35 @@ mod tests {
         assert_eq!(sort_by_cached_key, sort_by_cached_key_v2);
     }
 
+    #[test]
+    fn query_iter_sort_by_entity_index() {
+        #[derive(Component)]
+        struct B;
+
+        let mut world = World::new();
+        let e0 = world.spawn(A(0.)).id();
+        let e1 = world.spawn(A(1.)).id();
+        let e2 = world.spawn(A(2.)).id();
+        // Moving `e0` out of its table swap-removes it, which moves `e2` to its row, and moving
+        // it back places it at the end of the table.
+        world.entity_mut(e0).insert(B);
+        world.entity_mut(e0).remove::<B>();
+
+        let mut query = world.query::<(Entity, &A)>();
+        let unsorted = query
+            .iter(&world)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        assert_eq!(unsorted, [e2, e1, e0]);
+
+        let sorted = query
+            .iter(&world)
+            .sort_by_entity_index()
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, [e0, e1, e2]);
+    }
+
     #[test]
     #[should_panic]
     fn query_iter_sort_after_next() {
@@ -1,13 +1,43 @@
 use crate::{
+    component::ComponentId,
     entity::Entity,
     prelude::Mut,
-    reflect::{AppTypeRegistry, ReflectBundle, ReflectComponent},
+    reflect::{AppTypeRegistry, ReflectBundle, ReflectComponent, ReflectFromWorld},
     resource::Resource,
     system::EntityCommands,
     world::{EntityWorldMut, World},
 };
-use alloc::{borrow::Cow, boxed::Box};
-use bevy_reflect::{PartialReflect, TypeRegistry};
+use alloc::{alloc::dealloc, borrow::Cow, boxed::Box, string::String, vec::Vec};
+use bevy_ptr::OwningPtr;
+use bevy_reflect::{
+    std_traits::ReflectDefault, PartialReflect, Reflect, ReflectFromReflect, ReflectRef,
+    TypeRegistration, TypeRegistry,
+};
+use core::{alloc::Layout, ptr::NonNull};
+use thiserror::Error;
+
+/// An error that occurs when inserting a reflected bundle with
+/// [`insert_reflect_bundle`](EntityWorldMut::insert_reflect_bundle).
+#[derive(Error, Debug)]
+pub enum ReflectInsertError {
+    /// A dynamic value doesn't represent a type, so its component type is unknown.
+    #[error("the dynamic value `{0}` doesn't represent a type")]
+    MissingRepresentedType(String),
+    /// The type isn't registered in the type registry.
+    #[error("`{0}` should be registered in the type registry via `App::register_type`")]
+    NotRegistered(&'static str),
+    /// The type is neither a reflected component nor a reflected bundle.
+    #[error("`{0}` should have #[reflect(Component)] or #[reflect(Bundle)]")]
+    NotComponentOrBundle(&'static str),
+    /// The bundle isn't a struct or a tuple, so its components can't be found.
+    #[error("expected bundle `{0}` to be a named struct or tuple")]
+    InvalidBundle(&'static str),
+    /// The component couldn't be created from the reflected value.
+    #[error(
+        "couldn't create an instance of `{0}` using the reflected `FromReflect`, `Default` or `FromWorld` traits"
+    )]
+    InvalidComponent(&'static str),
+}
 
 /// An extension trait for [`EntityCommands`] for reflection related functions
 pub trait ReflectCommandExt {
@@ -102,6 +132,20 @@ pub trait ReflectCommandExt {
         component: Box<dyn PartialReflect>,
     ) -> &mut Self;
 
+    /// Adds all the given boxed reflect components and bundles to the entity at once, using the
+    /// reflection data in [`AppTypeRegistry`].
+    ///
+    /// See [`EntityWorldMut::insert_reflect_bundle`] for more details. Errors are handled by the
+    /// default error handler.
+    fn insert_reflect_bundle(&mut self, bundle: Vec<Box<dyn PartialReflect>>) -> &mut Self;
+
+    /// Same as [`insert_reflect_bundle`](ReflectCommandExt::insert_reflect_bundle), but using the
+    /// `T` resource as type registry instead of [`AppTypeRegistry`].
+    fn insert_reflect_bundle_with_registry<T: Resource + AsRef<TypeRegistry>>(
+        &mut self,
+        bundle: Vec<Box<dyn PartialReflect>>,
+    ) -> &mut Self;
+
     /// Removes from the entity the component or bundle with the given type name registered in [`AppTypeRegistry`].
     ///
     /// If the type is a bundle, it will remove any components in that bundle regardless if the entity
@@ -184,6 +228,27 @@ impl ReflectCommandExt for EntityCommands<'_> {
         })
     }
 
+    fn insert_reflect_bundle(&mut self, bundle: Vec<Box<dyn PartialReflect>>) -> &mut Self {
+        self.queue(
+            move |mut entity: EntityWorldMut| -> Result<(), ReflectInsertError> {
+                entity.insert_reflect_bundle(bundle)?;
+                Ok(())
+            },
+        )
+    }
+
+    fn insert_reflect_bundle_with_registry<T: Resource + AsRef<TypeRegistry>>(
+        &mut self,
+        bundle: Vec<Box<dyn PartialReflect>>,
+    ) -> &mut Self {
+        self.queue(
+            move |mut entity: EntityWorldMut| -> Result<(), ReflectInsertError> {
+                entity.insert_reflect_bundle_with_registry::<T>(bundle)?;
+                Ok(())
+            },
+        )
+    }
+
     fn remove_reflect(&mut self, component_type_path: impl Into<Cow<'static, str>>) -> &mut Self {
         let component_type_path: Cow<'static, str> = component_type_path.into();
         self.queue(move |mut entity: EntityWorldMut| {
@@ -263,6 +328,66 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Adds all the given boxed reflect components and bundles to the entity at once, using the
+    /// reflection data in [`AppTypeRegistry`].
+    ///
+    /// Unlike calling [`insert_reflect`](EntityWorldMut::insert_reflect) for each value, this
+    /// moves the entity to its new archetype only once. Each value is converted to its concrete
+    /// type with the reflected `FromReflect`, `Default` or `FromWorld` traits, and the fields of
+    /// bundles are inserted as if they were given individually. If several values are of the
+    /// same component type, the last one is inserted.
+    ///
+    /// This will overwrite any previous component(s) of the same type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, without inserting anything, if a value doesn't represent a
+    /// [`Component`](crate::component::Component) or [`Bundle`](crate::bundle::Bundle) registered
+    /// in [`AppTypeRegistry`], or if it can't be converted to its concrete type.
+    ///
+    /// # Panics
+    ///
+    /// - If the entity has been despawned while this `EntityWorldMut` is still alive.
+    /// - If [`AppTypeRegistry`] is not present in the [`World`].
+    pub fn insert_reflect_bundle(
+        &mut self,
+        bundle: Vec<Box<dyn PartialReflect>>,
+    ) -> Result<&mut Self, ReflectInsertError> {
+        self.assert_not_despawned();
+        let entity_id = self.id();
+        let result = self.world_scope(|world| {
+            world.resource_scope(|world, registry: Mut<AppTypeRegistry>| {
+                let type_registry = &registry.as_ref().read();
+                insert_reflect_bundle_with_registry_ref(world, entity_id, type_registry, bundle)
+            })
+        });
+        self.update_location();
+        result.map(|()| self)
+    }
+
+    /// Same as [`insert_reflect_bundle`](EntityWorldMut::insert_reflect_bundle), but using
+    /// the `T` resource as type registry instead of [`AppTypeRegistry`].
+    ///
+    /// # Panics
+    ///
+    /// - If the entity has been despawned while this `EntityWorldMut` is still alive.
+    /// - If the given [`Resource`] is not present in the [`World`].
+    pub fn insert_reflect_bundle_with_registry<T: Resource + AsRef<TypeRegistry>>(
+        &mut self,
+        bundle: Vec<Box<dyn PartialReflect>>,
+    ) -> Result<&mut Self, ReflectInsertError> {
+        self.assert_not_despawned();
+        let entity_id = self.id();
+        let result = self.world_scope(|world| {
+            world.resource_scope(|world, registry: Mut<T>| {
+                let type_registry = registry.as_ref().as_ref();
+                insert_reflect_bundle_with_registry_ref(world, entity_id, type_registry, bundle)
+            })
+        });
+        self.update_location();
+        result.map(|()| self)
+    }
+
     /// Removes from the entity the component or bundle with the given type name registered in [`AppTypeRegistry`].
     ///
     /// If the type is a bundle, it will remove any components in that bundle regardless if the entity
@@ -365,6 +490,128 @@ fn insert_reflect_with_registry_ref(
     }
 }
 
+/// Helper function to add reflect components and bundles to a given entity in a single insertion
+fn insert_reflect_bundle_with_registry_ref(
+    world: &mut World,
+    entity: Entity,
+    type_registry: &TypeRegistry,
+    bundle: Vec<Box<dyn PartialReflect>>,
+) -> Result<(), ReflectInsertError> {
+    let mut components = Vec::with_capacity(bundle.len());
+    for value in &bundle {
+        collect_reflect_components(
+            world,
+            type_registry,
+            value.as_partial_reflect(),
+            &mut components,
+        )?;
+    }
+    drop(bundle);
+
+    // Keep the last value of each component, with the ids sorted to maximize caching.
+    components.reverse();
+    components.sort_by_key(|(component_id, _)| *component_id);
+    components.dedup_by_key(|(component_id, _)| *component_id);
+    if components.is_empty() {
+        return Ok(());
+    }
+
+    let Ok(mut entity) = world.get_entity_mut(entity) else {
+        panic!("error[B0003]: Could not insert a reflected bundle for entity {entity}, which {}. See: https://bevyengine.org/learn/errors/b0003",
+        world.entities().entity_does_not_exist_error_details(entity));
+    };
+    let component_ids: Vec<ComponentId> = components.iter().map(|(id, _)| *id).collect();
+    let values: Vec<(NonNull<u8>, Layout)> = components
+        .into_iter()
+        .map(|(_, value)| {
+            let layout = Layout::for_value::<dyn Reflect>(&*value);
+            (NonNull::from(Box::leak(value)).cast::<u8>(), layout)
+        })
+        .collect();
+
+    // SAFETY:
+    // - Each component id was registered in this world by the `ReflectComponent` of the
+    //   concrete type of its value.
+    // - The values were leaked from their boxes, so they are only read once by the insertion.
+    unsafe {
+        entity.insert_by_ids(
+            &component_ids,
+            values.iter().map(|&(value, _)| OwningPtr::new(value)),
+        );
+    }
+
+    for (value, layout) in values {
+        if layout.size() != 0 {
+            // SAFETY: The memory was allocated by a `Box` with this layout, and its value
+            // was moved out by the insertion.
+            unsafe { dealloc(value.as_ptr(), layout) };
+        }
+    }
+    Ok(())
+}
+
+/// Converts a reflected component to its concrete type, or the fields of a reflected bundle
+/// recursively, and adds them to `components` along with their [`ComponentId`].
+fn collect_reflect_components(
+    world: &mut World,
+    type_registry: &TypeRegistry,
+    value: &dyn PartialReflect,
+    components: &mut Vec<(ComponentId, Box<dyn Reflect>)>,
+) -> Result<(), ReflectInsertError> {
+    let type_info = value.get_represented_type_info().ok_or_else(|| {
+        ReflectInsertError::MissingRepresentedType(value.reflect_type_path().into())
+    })?;
+    let type_path = type_info.type_path();
+    let type_registration = type_registry
+        .get(type_info.type_id())
+        .ok_or(ReflectInsertError::NotRegistered(type_path))?;
+
+    if let Some(reflect_component) = type_registration.data::<ReflectComponent>() {
+        let component = from_reflect_boxed(value, type_registration, world)
+            .ok_or(ReflectInsertError::InvalidComponent(type_path))?;
+        components.push((reflect_component.register_component(world), component));
+        Ok(())
+    } else if type_registration.data::<ReflectBundle>().is_some() {
+        match value.reflect_ref() {
+            ReflectRef::Struct(bundle) => bundle.iter_fields().try_for_each(|field| {
+                collect_reflect_components(world, type_registry, field, components)
+            }),
+            ReflectRef::Tuple(bundle) => bundle.iter_fields().try_for_each(|field| {
+                collect_reflect_components(world, type_registry, field, components)
+            }),
+            _ => Err(ReflectInsertError::InvalidBundle(type_path)),
+        }
+    } else {
+        Err(ReflectInsertError::NotComponentOrBundle(type_path))
+    }
+}
+
+/// Creates a value of the type of `type_registration` from `reflected`, with the same strategies
+/// as [`from_reflect_with_fallback`](super::from_reflect_with_fallback).
+fn from_reflect_boxed(
+    reflected: &dyn PartialReflect,
+    type_registration: &TypeRegistration,
+    world: &mut World,
+) -> Option<Box<dyn Reflect>> {
+    let value = if let Some(value) = type_registration
+        .data::<ReflectFromReflect>()
+        .and_then(|reflect_from_reflect| reflect_from_reflect.from_reflect(reflected))
+    {
+        value
+    } else {
+        let mut value = if let Some(reflect_default) = type_registration.data::<ReflectDefault>() {
+            reflect_default.default()
+        } else {
+            type_registration
+                .data::<ReflectFromWorld>()?
+                .from_world(world)
+        };
+        value.try_apply(reflected).ok()?;
+        value
+    };
+    (value.as_any().type_id() == type_registration.type_id()).then_some(value)
+}
+
 /// Helper function to remove a reflect component or bundle from a given entity
 fn remove_reflect_with_registry_ref(
     world: &mut World,
@@ -392,11 +639,17 @@ mod tests {
         bundle::Bundle,
         component::Component,
         prelude::{AppTypeRegistry, ReflectComponent},
-        reflect::{ReflectBundle, ReflectCommandExt},
+        reflect::{ReflectBundle, ReflectCommandExt, ReflectInsertError},
         system::{Commands, SystemState},
         world::World,
     };
-    use alloc::{borrow::ToOwned, boxed::Box};
+    use alloc::{
+        borrow::ToOwned,
+        boxed::Box,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
     use bevy_ecs_macros::Resource;
     use bevy_reflect::{PartialReflect, Reflect, TypeRegistry};
 
@@ -686,4 +939,57 @@ mod tests {
         assert_eq!(world.entity(entity).get::<ComponentA>(), None);
         assert_eq!(world.entity(entity).get::<ComponentB>(), None);
     }
+
+    #[test]
+    fn insert_reflect_bundle_at_once() {
+        #[derive(Component, Reflect, Default, PartialEq, Eq, Debug)]
+        #[reflect(Component)]
+        struct Label(String);
+
+        let mut world = World::new();
+
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<BundleA>();
+            registry.register::<Label>();
+        }
+        world.insert_resource(type_registry);
+
+        let entity = world.spawn_empty().id();
+        let archetype_count = world.archetypes().len();
+
+        let bundle: Vec<Box<dyn PartialReflect>> = vec![
+            Box::new(Label("first".to_string())),
+            // Dynamic values are converted to their concrete type.
+            BundleA {
+                a: ComponentA(31),
+                b: ComponentB(20),
+            }
+            .clone_value(),
+            Box::new(Label("last".to_string())),
+        ];
+        world
+            .entity_mut(entity)
+            .insert_reflect_bundle(bundle)
+            .unwrap();
+
+        // The entity was moved to its final archetype directly.
+        assert_eq!(world.archetypes().len(), archetype_count + 1);
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(31)));
+        assert_eq!(world.get::<ComponentB>(entity), Some(&ComponentB(20)));
+        assert_eq!(world.get::<Label>(entity), Some(&Label("last".to_string())));
+
+        #[derive(Component, Reflect)]
+        struct Unregistered;
+
+        let bundle: Vec<Box<dyn PartialReflect>> =
+            vec![Box::new(ComponentA(1)), Box::new(Unregistered)];
+        let result = world
+            .entity_mut(entity)
+            .insert_reflect_bundle(bundle)
+            .map(|_| ());
+        assert!(matches!(result, Err(ReflectInsertError::NotRegistered(_))));
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(31)));
+    }
 }
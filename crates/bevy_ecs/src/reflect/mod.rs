@@ -18,14 +18,18 @@ mod entity_commands;
 mod from_world;
 mod map_entities;
 mod resource;
+mod resource_history;
 mod visit_entities;
 
 pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
-pub use entity_commands::ReflectCommandExt;
+pub use entity_commands::{ReflectCommandExt, ReflectInsertError};
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
 pub use map_entities::ReflectMapEntities;
 pub use resource::{ReflectResource, ReflectResourceFns};
+pub use resource_history::{
+    record_resource_history, ResourceHistory, ResourceHistoryError, ResourceSnapshot,
+};
 pub use visit_entities::{ReflectVisitEntities, ReflectVisitEntitiesMut};
 
 /// A [`Resource`] storing [`TypeRegistry`] for
@@ -0,0 +1,349 @@
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use core::any::TypeId;
+
+use bevy_reflect::{PartialReflect, TypeRegistry};
+use bevy_utils::TypeIdMap;
+use thiserror::Error;
+
+use crate as bevy_ecs;
+use crate::{
+    change_detection::Mut,
+    reflect::{AppTypeRegistry, ReflectResource},
+    resource::Resource,
+    world::World,
+};
+
+/// Records the values of selected reflected resources over the last frames, to inspect them or to
+/// go back to a past frame while debugging.
+///
+/// Only the resources passed to [`track`](Self::track) are recorded, when the
+/// [`record_resource_history`] system runs, usually once per frame. They must be registered in the
+/// [`AppTypeRegistry`] with [`ReflectResource`]. Once [`capacity`](Self::capacity) snapshots are
+/// recorded, the oldest snapshot is dropped for each new one.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{record_resource_history, AppTypeRegistry, ReflectResource, ResourceHistory};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Resource, Reflect, Default)]
+/// #[reflect(Resource)]
+/// struct Score(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Score>();
+/// world.init_resource::<Score>();
+///
+/// let mut history = ResourceHistory::new(100);
+/// history.track::<Score>();
+/// world.insert_resource(history);
+///
+/// for score in 0..5 {
+///     world.resource_mut::<Score>().0 = score;
+///     record_resource_history(&mut world);
+/// }
+///
+/// // Go back to the frame where the score was 2.
+/// world.restore_resource_history(2).unwrap();
+/// assert_eq!(world.resource::<Score>().0, 2);
+/// ```
+#[derive(Resource)]
+pub struct ResourceHistory {
+    /// Whether [`record_resource_history`] is paused, for example to step through the recorded
+    /// frames without recording new ones.
+    pub paused: bool,
+    capacity: usize,
+    tracked: Vec<TypeId>,
+    snapshots: VecDeque<ResourceSnapshot>,
+    next_frame: u64,
+}
+
+impl Default for ResourceHistory {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl ResourceHistory {
+    /// The number of snapshots kept by default.
+    pub const DEFAULT_CAPACITY: usize = 300;
+
+    /// Creates a history keeping the last `capacity` snapshots.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            paused: false,
+            capacity,
+            tracked: Vec::new(),
+            snapshots: VecDeque::new(),
+            next_frame: 0,
+        }
+    }
+
+    /// Records the history of the resource `R`.
+    pub fn track<R: Resource>(&mut self) -> &mut Self {
+        self.track_by_id(TypeId::of::<R>())
+    }
+
+    /// Records the history of the resource with the given [`TypeId`].
+    pub fn track_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        if !self.tracked.contains(&type_id) {
+            self.tracked.push(type_id);
+        }
+        self
+    }
+
+    /// Stops recording the history of the resource `R`. Its past values are kept.
+    pub fn untrack<R: Resource>(&mut self) -> &mut Self {
+        self.untrack_by_id(TypeId::of::<R>())
+    }
+
+    /// Stops recording the history of the resource with the given [`TypeId`]. Its past values are
+    /// kept.
+    pub fn untrack_by_id(&mut self, type_id: TypeId) -> &mut Self {
+        self.tracked.retain(|tracked| *tracked != type_id);
+        self
+    }
+
+    /// Returns `true` if the history of the resource `R` is recorded.
+    pub fn is_tracked<R: Resource>(&self) -> bool {
+        self.tracked.contains(&TypeId::of::<R>())
+    }
+
+    /// Returns the maximum number of snapshots kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of snapshots kept, dropping the oldest snapshots if there are more.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.snapshots.len().saturating_sub(capacity);
+        self.snapshots.drain(..excess);
+    }
+
+    /// Returns the recorded snapshots, from the oldest to the most recent.
+    pub fn snapshots(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &ResourceSnapshot> + ExactSizeIterator {
+        self.snapshots.iter()
+    }
+
+    /// Returns the snapshot of the given frame, if it is still kept.
+    pub fn snapshot(&self, frame: u64) -> Option<&ResourceSnapshot> {
+        let oldest = self.snapshots.front()?.frame;
+        let index = usize::try_from(frame.checked_sub(oldest)?).ok()?;
+        self.snapshots.get(index)
+    }
+
+    /// Returns the most recent snapshot.
+    pub fn latest(&self) -> Option<&ResourceSnapshot> {
+        self.snapshots.back()
+    }
+
+    /// Removes the recorded snapshots. The next snapshots keep increasing frame numbers.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Records a snapshot of the tracked resources of `world`, returning its frame number.
+    ///
+    /// Tracked resources which don't exist in `world`, or aren't registered with
+    /// [`ReflectResource`], are left out of the snapshot.
+    pub fn record(&mut self, world: &World, registry: &TypeRegistry) -> u64 {
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        if self.capacity == 0 {
+            return frame;
+        }
+
+        let resources = self
+            .tracked
+            .iter()
+            .filter_map(|type_id| {
+                let value = registry
+                    .get_type_data::<ReflectResource>(*type_id)?
+                    .reflect(world)?;
+                Some((*type_id, value.clone_value()))
+            })
+            .collect();
+
+        if self.snapshots.len() >= self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots
+            .push_back(ResourceSnapshot { frame, resources });
+        frame
+    }
+}
+
+/// The values of the resources tracked by a [`ResourceHistory`] at a given frame.
+pub struct ResourceSnapshot {
+    frame: u64,
+    resources: TypeIdMap<Box<dyn PartialReflect>>,
+}
+
+impl ResourceSnapshot {
+    /// Returns the frame number of this snapshot, counting the snapshots recorded by the
+    /// [`ResourceHistory`].
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Returns the recorded value of the resource `R`.
+    pub fn get<R: Resource>(&self) -> Option<&dyn PartialReflect> {
+        self.get_by_id(TypeId::of::<R>())
+    }
+
+    /// Returns the recorded value of the resource with the given [`TypeId`].
+    pub fn get_by_id(&self, type_id: TypeId) -> Option<&dyn PartialReflect> {
+        self.resources.get(&type_id).map(AsRef::as_ref)
+    }
+
+    /// Returns the recorded resources, with their [`TypeId`].
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (TypeId, &dyn PartialReflect)> {
+        self.resources
+            .iter()
+            .map(|(type_id, value)| (*type_id, value.as_ref()))
+    }
+
+    /// Sets the resources of `world` to their values in this snapshot, inserting them if they
+    /// don't exist. Resources missing from the snapshot are left unchanged.
+    pub fn restore(&self, world: &mut World, registry: &TypeRegistry) {
+        for (type_id, value) in &self.resources {
+            if let Some(reflect_resource) = registry.get_type_data::<ReflectResource>(*type_id) {
+                reflect_resource.apply_or_insert(world, value.as_ref(), registry);
+            }
+        }
+    }
+}
+
+/// An error returned by [`World::restore_resource_history`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceHistoryError {
+    /// The world has no [`ResourceHistory`].
+    #[error("the world has no `ResourceHistory`")]
+    MissingHistory,
+    /// The snapshot of the frame isn't kept by the [`ResourceHistory`].
+    #[error("no snapshot of frame {0} in the resource history")]
+    MissingSnapshot(u64),
+}
+
+/// Records a snapshot of the resources tracked by the [`ResourceHistory`], unless it is paused.
+///
+/// Add this system at the end of the frame, for example in the `Last` schedule. It does nothing if
+/// the world has no [`ResourceHistory`] or no [`AppTypeRegistry`].
+pub fn record_resource_history(world: &mut World) {
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+    world.try_resource_scope(|world, mut history: Mut<ResourceHistory>| {
+        if !history.paused {
+            history.record(world, &registry.read());
+        }
+    });
+}
+
+impl World {
+    /// Sets the resources tracked by the [`ResourceHistory`] to their values in the given frame.
+    ///
+    /// Restoring a frame doesn't remove the more recent snapshots: the history can be stepped
+    /// through back and forth, preferably while [paused](ResourceHistory::paused).
+    ///
+    /// The resources are restored with the [`ReflectResource`] registered in the
+    /// [`AppTypeRegistry`].
+    pub fn restore_resource_history(&mut self, frame: u64) -> Result<(), ResourceHistoryError> {
+        let registry = self
+            .get_resource::<AppTypeRegistry>()
+            .cloned()
+            .unwrap_or_default();
+        self.try_resource_scope(|world, history: Mut<ResourceHistory>| {
+            let snapshot = history
+                .snapshot(frame)
+                .ok_or(ResourceHistoryError::MissingSnapshot(frame))?;
+            snapshot.restore(world, &registry.read());
+            Ok(())
+        })
+        .unwrap_or(Err(ResourceHistoryError::MissingHistory))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_reflect::Reflect;
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    enum State {
+        #[default]
+        Menu,
+        Playing,
+    }
+
+    #[test]
+    fn record_and_restore_history() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Score>();
+            registry.register::<State>();
+        }
+        world.init_resource::<Score>();
+
+        let mut history = ResourceHistory::new(3);
+        history.track::<Score>().track::<State>();
+        world.insert_resource(history);
+
+        for score in 0..4 {
+            world.resource_mut::<Score>().0 = score;
+            if score == 2 {
+                world.insert_resource(State::Playing);
+            }
+            record_resource_history(&mut world);
+        }
+
+        let history = world.resource::<ResourceHistory>();
+        assert_eq!(
+            history
+                .snapshots()
+                .map(ResourceSnapshot::frame)
+                .collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert!(history.snapshot(0).is_none());
+        let snapshot = history.snapshot(1).unwrap();
+        assert!(snapshot.get::<State>().is_none());
+        assert_eq!(
+            snapshot
+                .get::<Score>()
+                .unwrap()
+                .reflect_partial_eq(&Score(1)),
+            Some(true)
+        );
+
+        // Paused histories don't record.
+        world.resource_mut::<ResourceHistory>().paused = true;
+        record_resource_history(&mut world);
+        assert_eq!(
+            world
+                .resource::<ResourceHistory>()
+                .latest()
+                .unwrap()
+                .frame(),
+            3
+        );
+
+        world.restore_resource_history(2).unwrap();
+        assert_eq!(world.resource::<Score>(), &Score(2));
+        assert_eq!(world.resource::<State>(), &State::Playing);
+        assert_eq!(
+            world.restore_resource_history(0),
+            Err(ResourceHistoryError::MissingSnapshot(0))
+        );
+    }
+}
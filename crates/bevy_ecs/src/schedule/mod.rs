@@ -724,6 +724,45 @@ mod tests {
             let result = schedule.initialize(&mut world);
             assert!(matches!(result, Err(ScheduleBuildError::Ambiguity(_))));
         }
+
+        #[test]
+        fn parallel_determinism_rejects_ambiguity() {
+            #[derive(Resource)]
+            struct X;
+
+            fn res_ref(_x: Res<X>) {}
+            fn res_mut(_x: ResMut<X>) {}
+
+            let mut world = World::new();
+            let mut schedule = Schedule::default();
+
+            schedule.set_build_settings(ScheduleBuildSettings {
+                determinism: Determinism::Parallel,
+                ..Default::default()
+            });
+
+            schedule.add_systems((res_ref, res_mut));
+            let result = schedule.initialize(&mut world);
+            assert!(matches!(result, Err(ScheduleBuildError::Ambiguity(_))));
+
+            let mut schedule = Schedule::default();
+            schedule.set_build_settings(ScheduleBuildSettings {
+                determinism: Determinism::Parallel,
+                ..Default::default()
+            });
+            schedule.add_systems((res_ref, res_mut.after(res_ref)));
+            assert!(schedule.initialize(&mut world).is_ok());
+        }
+
+        #[test]
+        fn single_threaded_determinism_sets_executor() {
+            let mut schedule = Schedule::default();
+            schedule.set_build_settings(ScheduleBuildSettings {
+                determinism: Determinism::SingleThreaded,
+                ..Default::default()
+            });
+            assert_eq!(schedule.get_executor_kind(), ExecutorKind::SingleThreaded);
+        }
     }
 
     mod system_ambiguity {
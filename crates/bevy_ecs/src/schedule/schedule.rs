@@ -391,12 +391,21 @@ impl Schedule {
     }
 
     /// Changes miscellaneous build settings.
+    ///
+    /// If [`ScheduleBuildSettings::determinism`] is [`Determinism::SingleThreaded`], this also
+    /// switches the schedule to the [`ExecutorKind::SingleThreaded`] executor.
     pub fn set_build_settings(&mut self, settings: ScheduleBuildSettings) -> &mut Self {
         if settings.auto_insert_apply_deferred {
             self.add_build_pass(passes::AutoInsertApplyDeferredPass::default());
         } else {
             self.remove_build_pass::<passes::AutoInsertApplyDeferredPass>();
         }
+        if settings.determinism == Determinism::SingleThreaded {
+            self.set_executor_kind(ExecutorKind::SingleThreaded);
+        }
+        if settings.determinism != self.graph.settings.determinism {
+            self.graph.changed = true;
+        }
         self.graph.settings = settings;
         self
     }
@@ -1810,18 +1819,25 @@ impl ScheduleGraph {
     }
 
     /// if [`ScheduleBuildSettings::ambiguity_detection`] is [`LogLevel::Ignore`], this check is skipped
+    /// (unless [`ScheduleBuildSettings::determinism`] is [`Determinism::Parallel`], which always errors)
     fn optionally_check_conflicts(
         &self,
         conflicts: &[(NodeId, NodeId, Vec<ComponentId>)],
         components: &Components,
         schedule_label: InternedScheduleLabel,
     ) -> Result<(), ScheduleBuildError> {
-        if self.settings.ambiguity_detection == LogLevel::Ignore || conflicts.is_empty() {
+        let level = if self.settings.determinism == Determinism::Parallel {
+            &LogLevel::Error
+        } else {
+            &self.settings.ambiguity_detection
+        };
+
+        if *level == LogLevel::Ignore || conflicts.is_empty() {
             return Ok(());
         }
 
         let message = self.get_conflicts_error_message(conflicts, components);
-        match self.settings.ambiguity_detection {
+        match level {
             LogLevel::Ignore => Ok(()),
             LogLevel::Warn => {
                 warn!("Schedule {schedule_label:?} has ambiguities.\n{}", message);
@@ -1987,6 +2003,43 @@ pub struct ScheduleBuildSettings {
     ///
     /// Defaults to `true`.
     pub report_sets: bool,
+    /// Determines whether running the schedule on identical worlds must produce identical results.
+    ///
+    /// Defaults to [`Determinism::Relaxed`].
+    pub determinism: Determinism,
+}
+
+/// Specifies how strictly a schedule guarantees that running it on identical worlds
+/// produces identical results.
+///
+/// Systems whose data access conflicts but which have no ordering relationship
+/// (see [`ScheduleBuildSettings::ambiguity_detection`]) may run in a different order
+/// each time the schedule runs when using a multi-threaded executor.
+/// Simulations that need to be reproducible between runs or across machines
+/// (lockstep networking, replays, tests) should opt in to one of the stricter modes.
+///
+/// Note that this only covers system ordering: iteration order within a query follows the
+/// storage layout, and should be made explicit with
+/// [`QueryIter::sort_by_entity_index`](crate::query::QueryIter::sort_by_entity_index)
+/// where it matters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Determinism {
+    /// No guarantees beyond the explicit ordering constraints between systems.
+    #[default]
+    Relaxed,
+    /// Runs every system on a single thread, in the order given by the schedule's topological sort.
+    ///
+    /// This switches the schedule to [`ExecutorKind::SingleThreaded`].
+    /// Since the topological sort only depends on the order in which systems and their constraints
+    /// were added, the same app always runs its systems in the same order.
+    SingleThreaded,
+    /// Keeps running systems in parallel, but refuses to build schedules that contain ambiguities.
+    ///
+    /// Every pair of systems with conflicting access is then ordered, so parallel execution can only
+    /// interleave systems whose relative order cannot be observed. Ambiguities are reported as a
+    /// [`ScheduleBuildError::Ambiguity`] regardless of [`ScheduleBuildSettings::ambiguity_detection`],
+    /// and can still be resolved using `ambiguous_with` or by ordering the systems.
+    Parallel,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -2005,6 +2058,7 @@ impl ScheduleBuildSettings {
             auto_insert_apply_deferred: true,
             use_shortnames: true,
             report_sets: true,
+            determinism: Determinism::Relaxed,
         }
     }
 }
@@ -0,0 +1,242 @@
+use crate::{
+    self as bevy_ecs,
+    component::Tick,
+    prelude::World,
+    resource::Resource,
+    system::{ReadOnlySystemParam, SystemMeta, SystemParam},
+    world::unsafe_world_cell::UnsafeWorldCell,
+};
+use alloc::{borrow::Cow, vec::Vec};
+use bevy_platform_support::{
+    hash::FixedHasher,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use bevy_utils::synccell::SyncCell;
+use bumpalo::Bump;
+use core::ops::Deref;
+
+/// A [`Vec`] allocated in a [`FrameArena`].
+pub type ArenaVec<'a, T> = bumpalo::collections::Vec<'a, T>;
+
+/// A [`HashMap`](bevy_platform_support::collections::HashMap) allocated in a [`FrameArena`].
+pub type ArenaHashMap<'a, K, V> = hashbrown::HashMap<K, V, FixedHasher, &'a Bump>;
+
+/// [`SystemParam`] providing a bump allocator for temporary allocations, which is reset every
+/// time the system runs.
+///
+/// Systems often build temporary collections which are dropped at the end of each run. Collections
+/// allocated in a [`FrameArena`] reuse the memory of the previous runs instead: once the arena has
+/// grown to fit the largest run of the system, running it doesn't allocate anymore.
+///
+/// Everything allocated in the arena is freed without being dropped when the arena is reset, so
+/// values owning resources allocated elsewhere, like a [`Vec`] or a `String`, leak their memory.
+/// Use [`ArenaVec`] and [`ArenaHashMap`] instead.
+///
+/// Each system has its own arena, which isn't shared with other systems. The memory used by the
+/// arenas can be inspected with the [`FrameArenas`] resource.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::FrameArena;
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn lowest_health(arena: FrameArena, query: Query<(Entity, &Health)>) {
+///     let mut entities = arena.vec_with_capacity(query.iter().len());
+///     entities.extend(query.iter());
+///     entities.sort_by(|(_, a), (_, b)| a.0.total_cmp(&b.0));
+///     // ...
+/// }
+/// # bevy_ecs::system::assert_is_system(lowest_health);
+/// ```
+pub struct FrameArena<'s> {
+    bump: &'s Bump,
+    usage: &'s FrameArenaUsage,
+}
+
+impl<'s> FrameArena<'s> {
+    /// Creates an empty [`ArenaVec`].
+    pub fn vec<T>(&self) -> ArenaVec<'s, T> {
+        ArenaVec::new_in(self.bump)
+    }
+
+    /// Creates an empty [`ArenaVec`] with room for `capacity` elements.
+    pub fn vec_with_capacity<T>(&self, capacity: usize) -> ArenaVec<'s, T> {
+        ArenaVec::with_capacity_in(capacity, self.bump)
+    }
+
+    /// Creates an empty [`ArenaHashMap`].
+    pub fn hash_map<K, V>(&self) -> ArenaHashMap<'s, K, V> {
+        ArenaHashMap::with_hasher_in(FixedHasher, self.bump)
+    }
+
+    /// Creates an empty [`ArenaHashMap`] with room for `capacity` entries.
+    pub fn hash_map_with_capacity<K, V>(&self, capacity: usize) -> ArenaHashMap<'s, K, V> {
+        ArenaHashMap::with_capacity_and_hasher_in(capacity, FixedHasher, self.bump)
+    }
+
+    /// Returns the underlying bump allocator.
+    pub fn bump(&self) -> &'s Bump {
+        self.bump
+    }
+}
+
+impl Deref for FrameArena<'_> {
+    type Target = Bump;
+
+    fn deref(&self) -> &Self::Target {
+        self.bump
+    }
+}
+
+impl Drop for FrameArena<'_> {
+    fn drop(&mut self) {
+        self.usage.record(self.bump.allocated_bytes());
+    }
+}
+
+#[doc(hidden)]
+pub struct FrameArenaState {
+    bump: SyncCell<Bump>,
+    usage: Arc<FrameArenaUsage>,
+}
+
+// SAFETY: only local state is accessed
+unsafe impl SystemParam for FrameArena<'_> {
+    type State = FrameArenaState;
+    type Item<'w, 's> = FrameArena<'s>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let usage = Arc::new(FrameArenaUsage::default());
+        world
+            .get_resource_or_init::<FrameArenas>()
+            .arenas
+            .push((system_meta.name.clone(), usage.clone()));
+        FrameArenaState {
+            bump: SyncCell::new(Bump::new()),
+            usage,
+        }
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        _world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        let bump = state.bump.get();
+        // Nothing allocated in the previous run can still be borrowed.
+        bump.reset();
+        FrameArena {
+            bump,
+            usage: &state.usage,
+        }
+    }
+}
+
+// SAFETY: Only reads internal system state
+unsafe impl<'s> ReadOnlySystemParam for FrameArena<'s> {}
+
+/// The memory used by the [`FrameArena`] of a system.
+#[derive(Debug, Default)]
+pub struct FrameArenaUsage {
+    allocated_bytes: AtomicUsize,
+    high_water_mark: AtomicUsize,
+}
+
+impl FrameArenaUsage {
+    /// The number of bytes the arena allocated from the global allocator, at the end of the last
+    /// run of the system.
+    ///
+    /// The arena keeps this memory across runs, so it only grows when a run needs more memory than
+    /// the previous ones.
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocated_bytes.load(Ordering::Relaxed)
+    }
+
+    /// The largest [`allocated_bytes`](Self::allocated_bytes) of all the runs of the system.
+    pub fn high_water_mark(&self) -> usize {
+        self.high_water_mark.load(Ordering::Relaxed)
+    }
+
+    fn record(&self, allocated_bytes: usize) {
+        self.allocated_bytes
+            .store(allocated_bytes, Ordering::Relaxed);
+        self.high_water_mark
+            .fetch_max(allocated_bytes, Ordering::Relaxed);
+    }
+}
+
+/// The [`FrameArena`]s of the systems of a [`World`], with the memory they use.
+#[derive(Resource, Default)]
+pub struct FrameArenas {
+    arenas: Vec<(Cow<'static, str>, Arc<FrameArenaUsage>)>,
+}
+
+impl FrameArenas {
+    /// Iterates over the name of the system owning each arena, with the memory it uses.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FrameArenaUsage)> {
+        self.arenas
+            .iter()
+            .map(|(name, usage)| (name.as_ref(), usage.as_ref()))
+    }
+
+    /// The sum of the [`FrameArenaUsage::allocated_bytes`] of all arenas.
+    pub fn allocated_bytes(&self) -> usize {
+        self.iter().map(|(_, usage)| usage.allocated_bytes()).sum()
+    }
+
+    /// The sum of the [`FrameArenaUsage::high_water_mark`] of all arenas.
+    pub fn high_water_mark(&self) -> usize {
+        self.iter().map(|(_, usage)| usage.high_water_mark()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        system::{FrameArena, FrameArenas, RunSystemOnce},
+        world::World,
+    };
+    use core::mem::size_of;
+
+    #[test]
+    fn frame_arena_is_reused() {
+        fn allocate(arena: FrameArena) -> usize {
+            let mut vec = arena.vec();
+            vec.extend(0..1000_u32);
+            let mut map = arena.hash_map();
+            map.extend(vec.iter().map(|i| (*i, *i)));
+            map.len()
+        }
+
+        fn no_allocation(_arena: FrameArena) {}
+
+        let mut world = World::new();
+        let id = world.register_system(allocate);
+        assert_eq!(world.run_system(id).unwrap(), 1000);
+        let arenas = world.resource::<FrameArenas>();
+        let (name, usage) = arenas.iter().next().unwrap();
+        assert!(name.ends_with("allocate"));
+        assert!(usage.allocated_bytes() >= 1000 * size_of::<u32>());
+
+        // The arena keeps its largest chunk across runs, until it fits a whole run.
+        for _ in 0..10 {
+            world.run_system(id).unwrap();
+        }
+        let allocated_bytes = world.resource::<FrameArenas>().allocated_bytes();
+        world.run_system(id).unwrap();
+        let arenas = world.resource::<FrameArenas>();
+        assert_eq!(arenas.allocated_bytes(), allocated_bytes);
+        assert!(arenas.high_water_mark() >= allocated_bytes);
+
+        world.run_system_once(no_allocation).unwrap();
+        assert_eq!(world.resource::<FrameArenas>().iter().count(), 2);
+    }
+}
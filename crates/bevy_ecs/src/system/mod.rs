@@ -125,6 +125,7 @@ mod combinator;
 mod commands;
 mod exclusive_function_system;
 mod exclusive_system_param;
+mod frame_arena;
 mod function_system;
 mod input;
 mod observer_system;
@@ -143,6 +144,7 @@ pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
+pub use frame_arena::*;
 pub use function_system::*;
 pub use input::*;
 pub use observer_system::*;
@@ -785,6 +787,61 @@ mod tests {
         run_system(&mut world, sys);
     }
 
+    #[test]
+    fn zipped_queries() {
+        fn sys(
+            mut ran: ResMut<SystemRan>,
+            mut q1: Query<&mut W<usize>, Without<C>>,
+            mut q2: Query<&W<u32>, Changed<W<u32>>>,
+        ) {
+            let mut zipped = q1.zip(&mut q2).unwrap();
+            for (mut a, b) in &mut zipped.query() {
+                a.0 += b.0 as usize;
+            }
+            *ran = SystemRan::Yes;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(SystemRan::No);
+        let both = world.spawn((W(1usize), W(2u32))).id();
+        let filtered = world.spawn((W(1usize), W(2u32), C)).id();
+        let single = world.spawn(W(1usize)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sys);
+        schedule.run(&mut world);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+        assert_eq!(world.get::<W<usize>>(both).unwrap().0, 3);
+        assert_eq!(world.get::<W<usize>>(filtered).unwrap().0, 1);
+        assert_eq!(world.get::<W<usize>>(single).unwrap().0, 1);
+
+        // `Changed<W<u32>>` no longer matches.
+        schedule.run(&mut world);
+        assert_eq!(world.get::<W<usize>>(both).unwrap().0, 3);
+    }
+
+    #[test]
+    fn zipped_queries_conflicting_fetches() {
+        fn sys(
+            mut ran: ResMut<SystemRan>,
+            mut q1: Query<&mut A, With<B>>,
+            mut q2: Query<&A, Without<B>>,
+            mut q3: Query<&B, With<A>>,
+        ) {
+            assert!(matches!(
+                q1.zip(&mut q2),
+                Err(crate::query::QueryZipError::ConflictingFetches(..))
+            ));
+            assert!(q1.zip(&mut q3).is_ok());
+            *ran = SystemRan::Yes;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(SystemRan::No);
+        run_system(&mut world, sys);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+    }
+
     #[test]
     #[should_panic]
     fn conflicting_query_immut_system() {
@@ -3,9 +3,9 @@ use crate::{
     component::Tick,
     entity::{Entity, EntityBorrow, EntitySet},
     query::{
-        QueryCombinationIter, QueryData, QueryEntityError, QueryFilter, QueryIter, QueryManyIter,
-        QueryManyUniqueIter, QueryParIter, QuerySingleError, QueryState, ROQueryItem,
-        ReadOnlyQueryData,
+        FilteredAccess, QueryCombinationIter, QueryData, QueryEntityError, QueryFilter, QueryIter,
+        QueryManyIter, QueryManyUniqueIter, QueryParIter, QuerySingleError, QueryState,
+        QueryZipError, ROQueryItem, ReadOnlyQueryData,
     },
     world::unsafe_world_cell::UnsafeWorldCell,
 };
@@ -288,6 +288,13 @@ use core::{
 /// Optional components increase the number of entities a query has to match against.
 /// This can hurt iteration performance, especially if the query solely consists of only optional components, since the query would iterate over each entity in the world.
 ///
+/// The archetypes matched by a query are cached in its [`QueryState`], which only checks the archetypes created since its last use.
+/// Archetypal filters like [`With`], [`Without`] and [`Or`] combinations of them are thus never evaluated per entity, however many there are,
+/// and there is nothing to gain from caching the matching entities between runs.
+/// Change detection filters are evaluated per entity, as their result depends on the last run of the system.
+/// To avoid checking the same rarely changing entities every frame, track them in a [`Resource`](crate::resource::Resource) updated by
+/// [observers](crate::observer::Observer) or [component hooks](crate::component::ComponentHooks) instead.
+///
 /// The following table compares the computational complexity of the various methods and operations, where:
 ///
 /// - **n** is the number of entities that match the query,
@@ -2019,6 +2026,80 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
             this_run: self.this_run,
         }
     }
+
+    /// Returns a [`QueryLens`] yielding the items of this query and of `other` together, for
+    /// the entities matched by both.
+    ///
+    /// For example, this can take a `Query<&mut A, With<C>>` and a `Query<&B, Changed<B>>` and
+    /// return a `Query<(&mut A, &B), (With<C>, Changed<B>)>`. This is much faster than calling
+    /// [`get`](Self::get) on one query for each entity of the other, as only the archetypes
+    /// matched by both queries are iterated, once. Unlike [`join`](Self::join), the combined
+    /// fetch and filters are known at compile time, and the filters of both queries are
+    /// respected.
+    ///
+    /// Both queries stay mutably borrowed as long as the lens is alive, so their items can't
+    /// alias those of the lens.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryZipError::ConflictingFetches`] if the fetches of the two queries conflict,
+    /// like `&mut A` and `&A`. Filters are not taken into account, so this is an error even if
+    /// their filters are disjoint and no entity could match both.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Velocity(f32);
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Frozen;
+    /// #
+    /// # let mut world = World::default();
+    /// # world.spawn((Position(0.0), Velocity(1.0)));
+    ///
+    /// fn system(
+    ///     mut positions: Query<&mut Position, Without<Frozen>>,
+    ///     mut velocities: Query<&Velocity>,
+    /// ) {
+    ///     let mut moving = positions.zip(&mut velocities).unwrap();
+    ///     for (mut position, velocity) in &mut moving.query() {
+    ///         position.0 += velocity.0;
+    ///     }
+    /// }
+    ///
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_systems(system);
+    /// # schedule.run(&mut world);
+    /// ```
+    ///
+    /// ## Performance
+    ///
+    /// Creating the lens takes time proportional to the number of archetypes in the world, like
+    /// [`join`](Self::join), so it should be reused when iterating several times in a system.
+    pub fn zip<'a, OtherD: QueryData, OtherF: QueryFilter>(
+        &'a mut self,
+        other: &'a mut Query<OtherD, OtherF>,
+    ) -> Result<QueryLens<'a, (D, OtherD), (F, OtherF)>, QueryZipError> {
+        // Joining the fetches asserts that they don't conflict, so check it first.
+        let mut access = FilteredAccess::default();
+        D::update_component_access(&self.state.fetch_state, &mut access);
+        let mut other_access = FilteredAccess::default();
+        OtherD::update_component_access(&other.state.fetch_state, &mut other_access);
+        if !access.access().is_compatible(other_access.access()) {
+            return Err(QueryZipError::ConflictingFetches(
+                core::any::type_name::<D>(),
+                core::any::type_name::<OtherD>(),
+            ));
+        }
+
+        Ok(self.reborrow().join_filtered_inner(other))
+    }
 }
 
 impl<'w, 's, D: QueryData, F: QueryFilter> IntoIterator for Query<'w, 's, D, F> {
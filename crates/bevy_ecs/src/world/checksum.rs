@@ -0,0 +1,207 @@
+//! Helpers for comparing [`World`] state between runs.
+
+use crate::{component::Component, entity::Entity, resource::Resource, world::World};
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+
+/// Computes a platform-independent hash over a selected part of a [`World`].
+///
+/// This is intended to verify that a simulation is deterministic: record the checksum at the
+/// same point in two runs (or on two machines in a lockstep session) and compare the results.
+/// Only the components and resources that are explicitly added contribute to the checksum.
+///
+/// Components are visited in order of [`Entity::index`], so the result does not depend on
+/// the layout of the world's storage. Both the entity and the component value are hashed.
+///
+/// The checksum is built on FNV-1a and hashes `usize` and `isize` values as 64-bit integers,
+/// so it stays the same across targets with different pointer widths. Note that the result
+/// still depends on the [`Hash`] implementations of the included types.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::WorldChecksum;
+/// #[derive(Component, Hash)]
+/// struct Health(u32);
+///
+/// #[derive(Resource, Hash)]
+/// struct Turn(u64);
+///
+/// let mut world = World::new();
+/// world.spawn(Health(10));
+/// world.insert_resource(Turn(3));
+///
+/// let checksum = WorldChecksum::new()
+///     .with_component::<Health>(&world)
+///     .with_resource::<Turn>(&world)
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct WorldChecksum {
+    hasher: ChecksumHasher,
+}
+
+impl Default for WorldChecksum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorldChecksum {
+    /// Creates an empty checksum.
+    pub const fn new() -> Self {
+        Self {
+            hasher: ChecksumHasher::new(),
+        }
+    }
+
+    /// Adds every entity with a `C` component, along with the value of that component.
+    pub fn with_component<C: Component + Hash>(mut self, world: &World) -> Self {
+        self.add_component::<C>(world);
+        self
+    }
+
+    /// Adds the `R` resource. A missing resource is hashed differently from any present value.
+    pub fn with_resource<R: Resource + Hash>(mut self, world: &World) -> Self {
+        self.add_resource::<R>(world);
+        self
+    }
+
+    /// Adds every entity with a `C` component, along with the value of that component.
+    pub fn add_component<C: Component + Hash>(&mut self, world: &World) -> &mut Self {
+        let mut items: Vec<(Entity, &C)> = match world.try_query::<(Entity, &C)>() {
+            Some(mut state) => state.iter(world).collect(),
+            None => Vec::new(),
+        };
+        items.sort_unstable_by_key(|(entity, _)| entity.index());
+
+        self.hasher.write_usize(items.len());
+        for (entity, component) in items {
+            entity.hash(&mut self.hasher);
+            component.hash(&mut self.hasher);
+        }
+        self
+    }
+
+    /// Adds the `R` resource. A missing resource is hashed differently from any present value.
+    pub fn add_resource<R: Resource + Hash>(&mut self, world: &World) -> &mut Self {
+        world.get_resource::<R>().hash(&mut self.hasher);
+        self
+    }
+
+    /// Adds an arbitrary value, such as a frame number or a random number generator state.
+    pub fn add<T: Hash + ?Sized>(&mut self, value: &T) -> &mut Self {
+        value.hash(&mut self.hasher);
+        self
+    }
+
+    /// Returns the checksum of everything added so far.
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+
+/// A 64-bit FNV-1a hasher that produces the same output on every platform.
+#[derive(Debug, Clone)]
+struct ChecksumHasher(u64);
+
+impl ChecksumHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for ChecksumHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as i64 as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorldChecksum;
+    use crate::{self as bevy_ecs, component::Component, resource::Resource, world::World};
+
+    #[derive(Component, Hash)]
+    struct A(u32);
+
+    #[derive(Component, Hash)]
+    struct B(u32);
+
+    #[derive(Resource, Hash)]
+    struct R(u64);
+
+    #[test]
+    fn checksum_ignores_storage_layout() {
+        let mut first = World::new();
+        let e0 = first.spawn(A(0)).id();
+        first.spawn(A(1));
+        // Moving `e0` to another archetype changes its position in the iteration order.
+        first.entity_mut(e0).insert(B(0));
+        first.entity_mut(e0).remove::<B>();
+
+        let mut second = World::new();
+        second.spawn(A(0));
+        second.spawn(A(1));
+
+        assert_eq!(
+            WorldChecksum::new().with_component::<A>(&first).finish(),
+            WorldChecksum::new().with_component::<A>(&second).finish(),
+        );
+    }
+
+    #[test]
+    fn checksum_detects_changes() {
+        let mut world = World::new();
+        let entity = world.spawn(A(0)).id();
+        world.insert_resource(R(0));
+
+        let checksum = |world: &World| {
+            WorldChecksum::new()
+                .with_component::<A>(world)
+                .with_resource::<R>(world)
+                .finish()
+        };
+
+        let initial = checksum(&world);
+        world.get_mut::<A>(entity).unwrap().0 = 1;
+        let changed_component = checksum(&world);
+        assert_ne!(initial, changed_component);
+
+        world.remove_resource::<R>();
+        assert_ne!(changed_component, checksum(&world));
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::ops::Deref;
 #[cfg(feature = "track_location")]
 use core::panic::Location;
@@ -5,7 +6,7 @@ use core::panic::Location;
 use crate::{
     archetype::Archetype,
     change_detection::MutUntyped,
-    component::{ComponentId, HookContext, Mutable},
+    component::{ComponentId, DeferredComponentHook, HookContext, Mutable},
     entity::Entity,
     event::{Event, EventId, Events, SendBatchIds},
     observer::{Observers, TriggerTargets},
@@ -522,23 +523,26 @@ impl<'w> DeferredWorld<'w> {
         #[cfg(feature = "track_location")] caller: &'static Location<'static>,
     ) {
         if archetype.has_add_hook() {
+            let mut deferred = Vec::new();
             for component_id in targets {
                 // SAFETY: Caller ensures that these components exist
                 let hooks = unsafe { self.components().get_info_unchecked(component_id) }.hooks();
+                let context = HookContext {
+                    entity,
+                    component_id,
+                    #[cfg(feature = "track_location")]
+                    caller: Some(caller),
+                    #[cfg(not(feature = "track_location"))]
+                    caller: None,
+                };
+                if let Some(hook) = hooks.on_add_deferred {
+                    deferred.push((hooks.deferred_order, hook, context));
+                }
                 if let Some(hook) = hooks.on_add {
-                    hook(
-                        DeferredWorld { world: self.world },
-                        HookContext {
-                            entity,
-                            component_id,
-                            #[cfg(feature = "track_location")]
-                            caller: Some(caller),
-                            #[cfg(not(feature = "track_location"))]
-                            caller: None,
-                        },
-                    );
+                    hook(DeferredWorld { world: self.world }, context);
                 }
             }
+            self.queue_deferred_hooks(deferred);
         }
     }
 
@@ -555,23 +559,26 @@ impl<'w> DeferredWorld<'w> {
         #[cfg(feature = "track_location")] caller: &'static Location<'static>,
     ) {
         if archetype.has_insert_hook() {
+            let mut deferred = Vec::new();
             for component_id in targets {
                 // SAFETY: Caller ensures that these components exist
                 let hooks = unsafe { self.components().get_info_unchecked(component_id) }.hooks();
+                let context = HookContext {
+                    entity,
+                    component_id,
+                    #[cfg(feature = "track_location")]
+                    caller: Some(caller),
+                    #[cfg(not(feature = "track_location"))]
+                    caller: None,
+                };
+                if let Some(hook) = hooks.on_insert_deferred {
+                    deferred.push((hooks.deferred_order, hook, context));
+                }
                 if let Some(hook) = hooks.on_insert {
-                    hook(
-                        DeferredWorld { world: self.world },
-                        HookContext {
-                            entity,
-                            component_id,
-                            #[cfg(feature = "track_location")]
-                            caller: Some(caller),
-                            #[cfg(not(feature = "track_location"))]
-                            caller: None,
-                        },
-                    );
+                    hook(DeferredWorld { world: self.world }, context);
                 }
             }
+            self.queue_deferred_hooks(deferred);
         }
     }
 
@@ -588,23 +595,26 @@ impl<'w> DeferredWorld<'w> {
         #[cfg(feature = "track_location")] caller: &'static Location<'static>,
     ) {
         if archetype.has_replace_hook() {
+            let mut deferred = Vec::new();
             for component_id in targets {
                 // SAFETY: Caller ensures that these components exist
                 let hooks = unsafe { self.components().get_info_unchecked(component_id) }.hooks();
+                let context = HookContext {
+                    entity,
+                    component_id,
+                    #[cfg(feature = "track_location")]
+                    caller: Some(caller),
+                    #[cfg(not(feature = "track_location"))]
+                    caller: None,
+                };
+                if let Some(hook) = hooks.on_replace_deferred {
+                    deferred.push((hooks.deferred_order, hook, context));
+                }
                 if let Some(hook) = hooks.on_replace {
-                    hook(
-                        DeferredWorld { world: self.world },
-                        HookContext {
-                            entity,
-                            component_id,
-                            #[cfg(feature = "track_location")]
-                            caller: Some(caller),
-                            #[cfg(not(feature = "track_location"))]
-                            caller: None,
-                        },
-                    );
+                    hook(DeferredWorld { world: self.world }, context);
                 }
             }
+            self.queue_deferred_hooks(deferred);
         }
     }
 
@@ -621,23 +631,26 @@ impl<'w> DeferredWorld<'w> {
         #[cfg(feature = "track_location")] caller: &'static Location<'static>,
     ) {
         if archetype.has_remove_hook() {
+            let mut deferred = Vec::new();
             for component_id in targets {
                 // SAFETY: Caller ensures that these components exist
                 let hooks = unsafe { self.components().get_info_unchecked(component_id) }.hooks();
+                let context = HookContext {
+                    entity,
+                    component_id,
+                    #[cfg(feature = "track_location")]
+                    caller: Some(caller),
+                    #[cfg(not(feature = "track_location"))]
+                    caller: None,
+                };
+                if let Some(hook) = hooks.on_remove_deferred {
+                    deferred.push((hooks.deferred_order, hook, context));
+                }
                 if let Some(hook) = hooks.on_remove {
-                    hook(
-                        DeferredWorld { world: self.world },
-                        HookContext {
-                            entity,
-                            component_id,
-                            #[cfg(feature = "track_location")]
-                            caller: Some(caller),
-                            #[cfg(not(feature = "track_location"))]
-                            caller: None,
-                        },
-                    );
+                    hook(DeferredWorld { world: self.world }, context);
                 }
             }
+            self.queue_deferred_hooks(deferred);
         }
     }
 
@@ -654,23 +667,41 @@ impl<'w> DeferredWorld<'w> {
         #[cfg(feature = "track_location")] caller: &'static Location<'static>,
     ) {
         if archetype.has_despawn_hook() {
+            let mut deferred = Vec::new();
             for component_id in targets {
                 // SAFETY: Caller ensures that these components exist
                 let hooks = unsafe { self.components().get_info_unchecked(component_id) }.hooks();
+                let context = HookContext {
+                    entity,
+                    component_id,
+                    #[cfg(feature = "track_location")]
+                    caller: Some(caller),
+                    #[cfg(not(feature = "track_location"))]
+                    caller: None,
+                };
+                if let Some(hook) = hooks.on_despawn_deferred {
+                    deferred.push((hooks.deferred_order, hook, context));
+                }
                 if let Some(hook) = hooks.on_despawn {
-                    hook(
-                        DeferredWorld { world: self.world },
-                        HookContext {
-                            entity,
-                            component_id,
-                            #[cfg(feature = "track_location")]
-                            caller: Some(caller),
-                            #[cfg(not(feature = "track_location"))]
-                            caller: None,
-                        },
-                    );
+                    hook(DeferredWorld { world: self.world }, context);
                 }
             }
+            self.queue_deferred_hooks(deferred);
+        }
+    }
+
+    /// Queues `hooks` as commands, sorted by their [`ComponentHooks::deferred_order`].
+    ///
+    /// [`ComponentHooks::deferred_order`]: crate::component::ComponentHooks::deferred_order
+    fn queue_deferred_hooks(&mut self, mut hooks: Vec<(i32, DeferredComponentHook, HookContext)>) {
+        if hooks.is_empty() {
+            return;
+        }
+        // The sort is stable, so hooks with the same order keep the order of their components.
+        hooks.sort_by_key(|(order, ..)| *order);
+        let mut commands = self.commands();
+        for (_, hook, context) in hooks {
+            commands.queue(move |world: &mut World| hook(world, context));
         }
     }
 
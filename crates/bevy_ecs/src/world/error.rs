@@ -53,6 +53,17 @@ pub enum EntityComponentError {
     AliasedMutability(ComponentId),
 }
 
+/// An error returned when borrowing from a [`WorldSplit`](crate::world::WorldSplit).
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldSplitError {
+    /// The resource with the given type name does not exist in the world.
+    #[error("The resource {0} does not exist in the world.")]
+    MissingResource(&'static str),
+    /// The access to the resource or query data with the given type name conflicts with a previous borrow.
+    #[error("The access to {0} conflicts with a previous borrow from the same world split.")]
+    Conflict(&'static str),
+}
+
 /// An error that occurs when fetching entities mutably from a world.
 #[derive(Error, Debug, Clone, Copy)]
 pub enum EntityFetchError {
@@ -1,5 +1,6 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+mod checksum;
 pub(crate) mod command_queue;
 mod component_constants;
 mod deferred_world;
@@ -9,6 +10,7 @@ pub mod error;
 mod filtered_resource;
 mod identifier;
 mod spawn_batch;
+mod split;
 pub mod unsafe_world_cell;
 
 #[cfg(feature = "bevy_reflect")]
@@ -19,6 +21,7 @@ pub use crate::{
     world::command_queue::CommandQueue,
 };
 pub use bevy_ecs_macros::FromWorld;
+pub use checksum::WorldChecksum;
 pub use component_constants::*;
 pub use deferred_world::DeferredWorld;
 pub use entity_fetch::WorldEntityFetch;
@@ -29,6 +32,7 @@ pub use entity_ref::{
 pub use filtered_resource::*;
 pub use identifier::WorldId;
 pub use spawn_batch::*;
+pub use split::WorldSplit;
 
 use crate::{
     archetype::{ArchetypeId, ArchetypeRow, Archetypes},
@@ -0,0 +1,223 @@
+use crate::{
+    change_detection::{Mut, Ref},
+    component::ComponentId,
+    query::{FilteredAccess, FilteredAccessSet, QueryData, QueryFilter, QueryState},
+    resource::Resource,
+    system::Query,
+    world::{error::WorldSplitError, unsafe_world_cell::UnsafeWorldCell, World},
+};
+use bevy_platform_support::sync::{Mutex, PoisonError};
+
+/// Splits the mutable borrow of a [`World`] into several borrows of disjoint data, checked at runtime.
+///
+/// Created with [`World::split`]. Each call to [`WorldSplit::resource`], [`WorldSplit::resource_mut`]
+/// or [`WorldSplit::query`] returns an error if the requested access conflicts with a borrow previously
+/// handed out by the same split, the same way conflicting system parameters are rejected.
+/// Borrows are only released when the split and everything borrowed from it is dropped.
+///
+/// As a `WorldSplit` can be shared between threads, this lets an exclusive system process several
+/// independent parts of the world in parallel.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Resource, Default)]
+/// struct Score(u32);
+///
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<Score>();
+/// world.spawn(Health(10.0));
+/// let mut health_query = world.query::<&mut Health>();
+///
+/// let split = world.split();
+/// let mut score = split.resource_mut::<Score>().unwrap();
+/// let mut healths = split.query(&mut health_query).unwrap();
+/// // `Score` is already borrowed mutably.
+/// assert!(split.resource::<Score>().is_err());
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| score.0 += 1);
+///     scope.spawn(|| {
+///         for mut health in &mut healths {
+///             health.0 -= 1.0;
+///         }
+///     });
+/// });
+/// ```
+pub struct WorldSplit<'w> {
+    world: UnsafeWorldCell<'w>,
+    /// The access of all borrows handed out so far.
+    borrowed: Mutex<FilteredAccessSet<ComponentId>>,
+}
+
+impl<'w> WorldSplit<'w> {
+    /// Borrows the resource `R`.
+    ///
+    /// Returns an error if `R` doesn't exist, or was already borrowed mutably from this split.
+    pub fn resource<R: Resource>(&self) -> Result<Ref<'w, R>, WorldSplitError> {
+        let name = core::any::type_name::<R>();
+        let component_id = self.resource_id::<R>()?;
+        let mut access = FilteredAccess::default();
+        access.add_resource_read(component_id);
+        self.borrow(access, name, || {
+            // SAFETY: `borrow` checked that no conflicting borrow was handed out, and records this one.
+            unsafe { self.world.get_resource_ref::<R>() }
+                .ok_or(WorldSplitError::MissingResource(name))
+        })
+    }
+
+    /// Borrows the resource `R` mutably.
+    ///
+    /// Returns an error if `R` doesn't exist, or was already borrowed from this split.
+    pub fn resource_mut<R: Resource>(&self) -> Result<Mut<'w, R>, WorldSplitError> {
+        let name = core::any::type_name::<R>();
+        let component_id = self.resource_id::<R>()?;
+        let mut access = FilteredAccess::default();
+        access.add_resource_write(component_id);
+        self.borrow(access, name, || {
+            // SAFETY: `borrow` checked that no conflicting borrow was handed out, and records this one.
+            unsafe { self.world.get_resource_mut::<R>() }
+                .ok_or(WorldSplitError::MissingResource(name))
+        })
+    }
+
+    /// Creates a [`Query`] from `state`.
+    ///
+    /// Returns an error if the query's access conflicts with a query or resource already borrowed
+    /// from this split. As with system parameters, queries made disjoint with [`With`](crate::query::With)
+    /// and [`Without`](crate::query::Without) filters don't conflict.
+    ///
+    /// # Panics
+    ///
+    /// If `state` was created for another world.
+    pub fn query<'s, D: QueryData, F: QueryFilter>(
+        &self,
+        state: &'s mut QueryState<D, F>,
+    ) -> Result<Query<'w, 's, D, F>, WorldSplitError> {
+        let access = state.component_access().clone();
+        self.borrow(access, core::any::type_name::<D>(), move || {
+            // SAFETY: `borrow` checked that no conflicting borrow was handed out, and records this one.
+            Ok(unsafe { state.query_unchecked(self.world) })
+        })
+    }
+
+    fn resource_id<R: Resource>(&self) -> Result<ComponentId, WorldSplitError> {
+        self.world
+            .components()
+            .resource_id::<R>()
+            .ok_or(WorldSplitError::MissingResource(core::any::type_name::<R>()))
+    }
+
+    /// Calls `fetch` if `access` doesn't conflict with the previous borrows, and records it if `fetch` succeeds.
+    fn borrow<T>(
+        &self,
+        access: FilteredAccess<ComponentId>,
+        name: &'static str,
+        fetch: impl FnOnce() -> Result<T, WorldSplitError>,
+    ) -> Result<T, WorldSplitError> {
+        let mut borrowed = self.borrowed.lock().unwrap_or_else(PoisonError::into_inner);
+        if !borrowed.get_conflicts_single(&access).is_empty() {
+            return Err(WorldSplitError::Conflict(name));
+        }
+        let value = fetch()?;
+        borrowed.add(access);
+        Ok(value)
+    }
+}
+
+impl World {
+    /// Splits the mutable borrow of this world into several borrows of disjoint resources and queries,
+    /// checked at runtime.
+    ///
+    /// See [`WorldSplit`].
+    pub fn split(&mut self) -> WorldSplit<'_> {
+        WorldSplit {
+            world: self.as_unsafe_world_cell(),
+            borrowed: Mutex::new(FilteredAccessSet::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        prelude::{With, Without},
+        resource::Resource,
+        world::{error::WorldSplitError, World},
+    };
+
+    #[derive(Resource)]
+    struct A(u32);
+
+    #[derive(Resource)]
+    struct B(u32);
+
+    #[derive(Component)]
+    struct Position(f32);
+
+    #[derive(Component)]
+    struct Player;
+
+    #[test]
+    fn split_borrows_are_checked() {
+        let mut world = World::new();
+        world.insert_resource(A(0));
+        world.insert_resource(B(0));
+        world.spawn((Position(0.0), Player));
+        world.spawn(Position(0.0));
+        let mut players = world.query_filtered::<&mut Position, With<Player>>();
+        let mut others = world.query_filtered::<&mut Position, Without<Player>>();
+        let mut all = world.query::<&Position>();
+
+        let split = world.split();
+        let mut a = split.resource_mut::<A>().unwrap();
+        let b1 = split.resource::<B>().unwrap();
+        let b2 = split.resource::<B>().unwrap();
+        assert!(matches!(
+            split.resource::<A>(),
+            Err(WorldSplitError::Conflict(_))
+        ));
+        assert!(matches!(
+            split.resource_mut::<B>(),
+            Err(WorldSplitError::Conflict(_))
+        ));
+
+        let mut players = split.query(&mut players).unwrap();
+        let mut others = split.query(&mut others).unwrap();
+        assert!(split.query(&mut all).is_err());
+
+        a.0 += b1.0 + b2.0 + 1;
+        players.single_mut().0 = 1.0;
+        others.single_mut().0 = 2.0;
+
+        assert_eq!(world.resource::<A>().0, 1);
+        let mut positions: Vec<f32> = world
+            .query::<&Position>()
+            .iter(&world)
+            .map(|p| p.0)
+            .collect();
+        positions.sort_by(f32::total_cmp);
+        assert_eq!(positions, [1.0, 2.0]);
+    }
+
+    #[test]
+    fn missing_resource_is_not_borrowed() {
+        let mut world = World::new();
+        world.register_resource::<A>();
+        let split = world.split();
+        assert!(matches!(
+            split.resource_mut::<A>(),
+            Err(WorldSplitError::MissingResource(_))
+        ));
+        assert!(matches!(
+            split.resource::<B>(),
+            Err(WorldSplitError::MissingResource(_))
+        ));
+    }
+}
@@ -1,14 +1,14 @@
 //! Additional [`GizmoBuffer`] Functions -- Arrows
 //!
-//! Includes the implementation of [`GizmoBuffer::arrow`] and [`GizmoBuffer::arrow_2d`],
-//! and assorted support items.
+//! Includes the implementation of [`GizmoBuffer::arrow`], [`GizmoBuffer::arrow_2d`],
+//! [`GizmoBuffer::arrow_head`] and [`GizmoBuffer::arrow_head_2d`], and assorted support items.
 
 use crate::{gizmos::GizmoBuffer, prelude::GizmoConfigGroup};
 use bevy_color::{
     palettes::basic::{BLUE, GREEN, RED},
     Color,
 };
-use bevy_math::{Quat, Vec2, Vec3, Vec3Swizzles};
+use bevy_math::{Dir2, Dir3, Quat, Vec2, Vec3, Vec3Swizzles};
 use bevy_transform::TransformPoint;
 
 /// A builder returned by [`GizmoBuffer::arrow`] and [`GizmoBuffer::arrow_2d`]
@@ -70,33 +70,16 @@ where
         }
         // first, draw the body of the arrow
         self.gizmos.line(self.start, self.end, self.color);
-        // now the hard part is to draw the head in a sensible way
-        // put us in a coordinate system where the arrow is pointing towards +x and ends at the origin
-        let pointing_end = (self.end - self.start).normalize();
-        let rotation_end = Quat::from_rotation_arc(Vec3::X, pointing_end);
-        let tips = [
-            Vec3::new(-1., 1., 0.),
-            Vec3::new(-1., 0., 1.),
-            Vec3::new(-1., -1., 0.),
-            Vec3::new(-1., 0., -1.),
-        ];
-        // - extend the vectors so their length is `tip_length`
-        // - rotate the world so +x is facing in the same direction as the arrow
-        // - translate over to the tip of the arrow
-        let tips_end = tips.map(|v| rotation_end * (v.normalize() * self.tip_length) + self.end);
-        for v in tips_end {
-            // then actually draw the tips
-            self.gizmos.line(self.end, v, self.color);
-        }
+        // then the tips
+        self.gizmos
+            .arrow_head(self.end, self.end - self.start, self.tip_length, self.color);
         if self.double_ended {
-            let pointing_start = (self.start - self.end).normalize();
-            let rotation_start = Quat::from_rotation_arc(Vec3::X, pointing_start);
-            let tips_start =
-                tips.map(|v| rotation_start * (v.normalize() * self.tip_length) + self.start);
-            for v in tips_start {
-                // draw the start points tips
-                self.gizmos.line(self.start, v, self.color);
-            }
+            self.gizmos.arrow_head(
+                self.start,
+                self.start - self.end,
+                self.tip_length,
+                self.color,
+            );
         }
     }
 }
@@ -159,6 +142,93 @@ where
     ) -> ArrowBuilder<'_, Config, Clear> {
         self.arrow(start.extend(0.), end.extend(0.), color)
     }
+
+    /// Draw an arrowhead in 3D, with its tip at `tip` and pointing towards `direction`.
+    /// Has four barbs of length `length` for convenient viewing from any direction.
+    ///
+    /// This is useful to mark the end of line strips and curves. Nothing is drawn if
+    /// `direction` is zero.
+    ///
+    /// This should be called for each frame the arrowhead needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let points = [Vec3::ZERO, Vec3::X, Vec3::ONE];
+    ///     gizmos.linestrip(points, GREEN);
+    ///     gizmos.arrow_head(points[2], points[2] - points[1], 0.2, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn arrow_head(&mut self, tip: Vec3, direction: Vec3, length: f32, color: impl Into<Color>) {
+        if !self.enabled {
+            return;
+        }
+        let Ok(direction) = Dir3::new(direction) else {
+            return;
+        };
+        // put us in a coordinate system where the arrow is pointing towards +x and ends at the origin
+        let rotation = Quat::from_rotation_arc(Vec3::X, *direction);
+        let color = color.into();
+        let barbs = [
+            Vec3::new(-1., 1., 0.),
+            Vec3::new(-1., 0., 1.),
+            Vec3::new(-1., -1., 0.),
+            Vec3::new(-1., 0., -1.),
+        ];
+        for barb in barbs {
+            // - extend the vectors so their length is `length`
+            // - rotate the world so +x is facing in the same direction as the arrow
+            // - translate over to the tip of the arrow
+            self.line(tip, tip + rotation * (barb.normalize() * length), color);
+        }
+    }
+
+    /// Draw an arrowhead in 2D (on the xy plane), with its tip at `tip` and pointing towards
+    /// `direction`. Has two barbs of length `length`.
+    ///
+    /// This is useful to mark the end of line strips and curves. Nothing is drawn if
+    /// `direction` is zero.
+    ///
+    /// This should be called for each frame the arrowhead needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let points = [Vec2::ZERO, Vec2::X, Vec2::ONE];
+    ///     gizmos.linestrip_2d(points, GREEN);
+    ///     gizmos.arrow_head_2d(points[2], points[2] - points[1], 0.2, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn arrow_head_2d(
+        &mut self,
+        tip: Vec2,
+        direction: Vec2,
+        length: f32,
+        color: impl Into<Color>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let Ok(direction) = Dir2::new(direction) else {
+            return;
+        };
+        let color = color.into();
+        for barb in [Vec2::new(-1., 1.), Vec2::new(-1., -1.)] {
+            self.line_2d(
+                tip,
+                tip + direction.rotate(barb.normalize() * length),
+                color,
+            );
+        }
+    }
 }
 
 impl<Config, Clear> GizmoBuffer<Config, Clear>
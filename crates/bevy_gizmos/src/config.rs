@@ -36,6 +36,31 @@ pub enum GizmoLineJoint {
     Bevel,
 }
 
+/// An enum configuring how the ends of gizmo lines will be drawn.
+#[derive(Debug, Default, Copy, Clone, Reflect, PartialEq, Eq, Hash)]
+pub enum GizmoLineCap {
+    /// Ends the line exactly at its end points.
+    #[default]
+    Butt,
+    /// Extends the line past its end points by half its width.
+    Square,
+    /// Extends the line past its end points with a half circle.
+    Round,
+}
+
+/// An enum configuring the space in which the width of gizmo lines is measured.
+#[derive(Debug, Default, Copy, Clone, Reflect, PartialEq, Eq, Hash)]
+pub enum GizmoLineWidthSpace {
+    /// The width is measured in pixels, so lines keep the same width on screen
+    /// regardless of their distance to the camera or of its zoom.
+    #[default]
+    Screen,
+    /// The width is measured in world units, so lines scale like the rest of the scene.
+    ///
+    /// This is mostly useful in 2D, to keep lines in proportion with sprites when zooming.
+    World,
+}
+
 /// An enum used to configure the style of gizmo lines, similar to CSS line-style
 #[derive(Copy, Clone, Debug, Default, PartialEq, Reflect)]
 #[non_exhaustive]
@@ -210,15 +235,21 @@ impl Default for GizmoConfig {
 /// A struct that stores configuration for gizmos.
 #[derive(Clone, Reflect, Debug)]
 pub struct GizmoLineConfig {
-    /// Line width specified in pixels.
+    /// Line width specified in pixels, or in world units if `width_space` is
+    /// [`GizmoLineWidthSpace::World`].
     ///
     /// If `perspective` is `true` then this is the size in pixels at the camera's near plane.
     ///
     /// Defaults to `2.0`.
     pub width: f32,
+    /// Determine the space in which `width` is measured.
+    ///
+    /// Defaults to [`GizmoLineWidthSpace::Screen`].
+    pub width_space: GizmoLineWidthSpace,
     /// Apply perspective to gizmo lines.
     ///
-    /// This setting only affects 3D, non-orthographic cameras.
+    /// This setting only affects 3D, non-orthographic cameras, and has no effect
+    /// when `width_space` is [`GizmoLineWidthSpace::World`].
     ///
     /// Defaults to `false`.
     pub perspective: bool,
@@ -226,15 +257,22 @@ pub struct GizmoLineConfig {
     pub style: GizmoLineStyle,
     /// Describe how lines should join.
     pub joints: GizmoLineJoint,
+    /// Describe how the ends of lines should be drawn.
+    ///
+    /// Caps are drawn at both ends of every segment, including the inner points of line strips,
+    /// where they overlap with the adjacent segments.
+    pub cap: GizmoLineCap,
 }
 
 impl Default for GizmoLineConfig {
     fn default() -> Self {
         Self {
             width: 2.,
+            width_space: GizmoLineWidthSpace::Screen,
             perspective: false,
             style: GizmoLineStyle::Solid,
             joints: GizmoLineJoint::None,
+            cap: GizmoLineCap::Butt,
         }
     }
 }
@@ -246,8 +284,10 @@ impl Default for GizmoLineConfig {
 #[derive(Component)]
 pub(crate) struct GizmoMeshConfig {
     pub line_perspective: bool,
+    pub line_width_space: GizmoLineWidthSpace,
     pub line_style: GizmoLineStyle,
     pub line_joints: GizmoLineJoint,
+    pub line_cap: GizmoLineCap,
     pub render_layers: bevy_render::view::RenderLayers,
     pub handle: Handle<GizmoAsset>,
 }
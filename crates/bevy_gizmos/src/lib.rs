@@ -62,8 +62,8 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         config::{
-            DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore,
-            GizmoLineConfig, GizmoLineJoint, GizmoLineStyle,
+            DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore, GizmoLineCap,
+            GizmoLineConfig, GizmoLineJoint, GizmoLineStyle, GizmoLineWidthSpace,
         },
         gizmos::Gizmos,
         primitives::{dim2::GizmoPrimitive2d, dim3::GizmoPrimitive3d},
@@ -142,6 +142,9 @@ const LINE_SHADER_HANDLE: Handle<Shader> = weak_handle!("15dc5869-ad30-4664-b35a
 #[cfg(feature = "bevy_render")]
 const LINE_JOINT_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("7b5bdda5-df81-4711-a6cf-e587700de6f2");
+#[cfg(feature = "bevy_render")]
+const LINE_WIDTH_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3c1f0f52-8e4b-4a54-9d1e-5b27a6c0d4e8");
 
 /// A [`Plugin`] that provides an immediate mode drawing api for visual debugging.
 ///
@@ -154,6 +157,12 @@ impl Plugin for GizmoPlugin {
         #[cfg(feature = "bevy_render")]
         {
             use bevy_asset::load_internal_asset;
+            load_internal_asset!(
+                app,
+                LINE_WIDTH_SHADER_HANDLE,
+                "line_width.wgsl",
+                Shader::from_wgsl
+            );
             load_internal_asset!(app, LINE_SHADER_HANDLE, "lines.wgsl", Shader::from_wgsl);
             load_internal_asset!(
                 app,
@@ -474,8 +483,10 @@ fn extract_gizmo_data(
             #[cfg(any(feature = "bevy_pbr", feature = "bevy_sprite"))]
             GizmoMeshConfig {
                 line_perspective: config.line.perspective,
+                line_width_space: config.line.width_space,
                 line_style: config.line.style,
                 line_joints: config.line.joints,
+                line_cap: config.line.cap,
                 render_layers: config.render_layers.clone(),
                 handle: handle.clone(),
             },
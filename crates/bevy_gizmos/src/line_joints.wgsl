@@ -1,4 +1,5 @@
 #import bevy_render::{view::View, maths::affine3_to_square}
+#import bevy_gizmos::line_width::world_to_screen_width
 
 @group(0) @binding(0) var<uniform> view: View;
 
@@ -60,7 +61,9 @@ fn vertex_bevel(vertex: VertexInput) -> VertexOutput {
     var color = vertex.color;
     var line_width = joints_gizmo.line_width;
 
-#ifdef PERSPECTIVE
+#ifdef WORLD_SPACE_WIDTH
+    line_width *= world_to_screen_width(view, clip_b);
+#else ifdef PERSPECTIVE
     line_width /= clip_b.w;
 #endif
 
@@ -121,7 +124,9 @@ fn vertex_miter(vertex: VertexInput) -> VertexOutput {
     var color = vertex.color;
     var line_width = joints_gizmo.line_width;
 
-#ifdef PERSPECTIVE
+#ifdef WORLD_SPACE_WIDTH
+    line_width *= world_to_screen_width(view, clip_b);
+#else ifdef PERSPECTIVE
     line_width /= clip_b.w;
 #endif
 
@@ -173,7 +178,9 @@ fn vertex_round(vertex: VertexInput) -> VertexOutput {
     var color = vertex.color;
     var line_width = joints_gizmo.line_width;
 
-#ifdef PERSPECTIVE
+#ifdef WORLD_SPACE_WIDTH
+    line_width *= world_to_screen_width(view, clip_b);
+#else ifdef PERSPECTIVE
     line_width /= clip_b.w;
 #endif
 
@@ -0,0 +1,8 @@
+#define_import_path bevy_gizmos::line_width
+
+#import bevy_render::view::View
+
+// Returns the number of pixels covered by a world unit at the depth of `clip`.
+fn world_to_screen_width(view: View, clip: vec4<f32>) -> f32 {
+    return 0.5 * view.viewport.w * view.clip_from_view[1][1] / clip.w;
+}
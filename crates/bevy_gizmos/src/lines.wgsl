@@ -1,5 +1,6 @@
 // TODO use common view binding
 #import bevy_render::{view::View, maths::affine3_to_square}
+#import bevy_gizmos::line_width::world_to_screen_width
 
 @group(0) @binding(0) var<uniform> view: View;
 
@@ -32,6 +33,9 @@ struct VertexOutput {
     @location(0) color: vec4<f32>,
     @location(1) uv: f32,
     @location(2) line_fraction: f32,
+    // The position of the vertex relative to the start of the line, the length of the line and
+    // half its width, in pixels, multiplied by the clip w of the vertex. Only used by round caps.
+    @location(3) cap: vec4<f32>,
 };
 
 const EPSILON: f32 = 4.88e-04;
@@ -72,7 +76,19 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     var alpha = 1.;
 
     var uv: f32;
-#ifdef PERSPECTIVE
+#ifdef WORLD_SPACE_WIDTH
+    line_width *= world_to_screen_width(view, clip);
+
+    // We can't use vertex.position_X because we may have changed the clip positions with clip_near_plane
+    let position_a = view.world_from_clip * clip_a;
+    let position_b = view.world_from_clip * clip_b;
+    let world_distance = length(position_a.xyz - position_b.xyz);
+
+    // Offset to compensate for moved clip positions.
+    let clipped_offset = length(position_a.xyz - (world_from_local * vec4(vertex.position_a, 1.)).xyz);
+
+    uv = (clipped_offset + position.y * world_distance) / line_gizmo.line_width;
+#else ifdef PERSPECTIVE
     line_width /= clip.w;
 
     // get height of near clipping plane in world space
@@ -112,7 +128,19 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     }
 
     let x_offset = line_width * position.x * x_basis;
-    let screen = mix(screen_a, screen_b, position.y) + x_offset;
+    var screen = mix(screen_a, screen_b, position.y) + x_offset;
+
+    let line_length = length(screen_b - screen_a);
+    var along = position.y * line_length;
+#ifdef LINE_CAPS
+    // Extend the line past its end points by half its width to make room for the caps.
+    let cap_offset = (position.y - 0.5) * line_width;
+    screen += cap_offset * y_basis;
+    along += cap_offset;
+#endif
+    // Pre-multiplied by clip w, so that the fragment shader can undo the perspective correct
+    // interpolation and get the values interpolated in screen space.
+    let cap = vec4(position.x * line_width, along, line_length, 0.5 * line_width) * clip.w;
 
     var depth: f32;
     if line_gizmo.depth_bias >= 0. {
@@ -132,7 +160,7 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
 
     let line_fraction = 2.0 * line_gizmo.line_scale / (line_gizmo.gap_scale + line_gizmo.line_scale);
     uv /= (line_gizmo.gap_scale + line_gizmo.line_scale) / 2.0;
-    return VertexOutput(clip_position, color, uv, line_fraction);
+    return VertexOutput(clip_position, color, uv, line_fraction, cap);
 }
 
 fn clip_near_plane(a: vec4<f32>, b: vec4<f32>) -> vec4<f32> {
@@ -154,36 +182,54 @@ struct FragmentInput {
     @location(0) color: vec4<f32>,
     @location(1) uv: f32,
     @location(2) line_fraction: f32,
+    @location(3) cap: vec4<f32>,
 };
 
 struct FragmentOutput {
     @location(0) color: vec4<f32>,
 };
 
+// Discards the fragments past the end points of the line which are outside of its round caps.
+fn discard_outside_cap(in: FragmentInput) {
+#ifdef LINE_CAP_ROUND
+    // `position.w` is the reciprocal of the interpolated clip w.
+    let cap = in.cap * in.position.w;
+    let past_end = max(-cap.y, cap.y - cap.z);
+    if past_end > 0.0 && length(vec2(cap.x, past_end)) > cap.w {
+        discard;
+    }
+#endif
+}
+
+// Returns the distance along the line, in line widths, used to draw dots and dashes.
+fn pattern_uv(in: FragmentInput) -> f32 {
+#ifdef WORLD_SPACE_WIDTH
+    return in.uv;
+#else ifdef PERSPECTIVE
+    return in.uv;
+#else
+    return in.uv * in.position.w;
+#endif
+}
+
 @fragment
 fn fragment_solid(in: FragmentInput) -> FragmentOutput {
+    discard_outside_cap(in);
     return FragmentOutput(in.color);
 }
 @fragment
 fn fragment_dotted(in: FragmentInput) -> FragmentOutput {
-    var alpha: f32;
-#ifdef PERSPECTIVE
-    alpha = 1 - floor(in.uv % 2.0);
-#else
-    alpha = 1 - floor((in.uv * in.position.w) % 2.0);
-#endif
-    
+    discard_outside_cap(in);
+    let alpha = 1 - floor(pattern_uv(in) % 2.0);
+
     return FragmentOutput(vec4(in.color.xyz, in.color.w * alpha));
 }
 
 @fragment
 fn fragment_dashed(in: FragmentInput) -> FragmentOutput {
-#ifdef PERSPECTIVE
-    let uv = in.uv;
-#else
-    let uv = in.uv * in.position.w;
-#endif
+    discard_outside_cap(in);
+    let uv = pattern_uv(in);
     let alpha = 1.0 - floor(min((uv % 2.0) / in.line_fraction, 1.0));
-    
+
     return FragmentOutput(vec4(in.color.xyz, in.color.w * alpha));
 }
@@ -1,5 +1,5 @@
 use crate::{
-    config::{GizmoLineJoint, GizmoLineStyle, GizmoMeshConfig},
+    config::{GizmoLineCap, GizmoLineJoint, GizmoLineStyle, GizmoLineWidthSpace, GizmoMeshConfig},
     line_gizmo_vertex_buffer_layouts, line_joint_gizmo_vertex_buffer_layouts, DrawLineGizmo,
     DrawLineJointGizmo, GizmoRenderSystem, GpuLineGizmo, LineGizmoUniformBindgroupLayout,
     SetLineGizmoBindGroup, LINE_JOINT_SHADER_HANDLE, LINE_SHADER_HANDLE,
@@ -93,7 +93,9 @@ impl FromWorld for LineGizmoPipeline {
 struct LineGizmoPipelineKey {
     mesh_key: Mesh2dPipelineKey,
     strip: bool,
+    width_space: GizmoLineWidthSpace,
     line_style: GizmoLineStyle,
+    line_cap: GizmoLineCap,
 }
 
 impl SpecializedRenderPipeline for LineGizmoPipeline {
@@ -106,11 +108,23 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             TextureFormat::bevy_default()
         };
 
-        let shader_defs = vec![
+        let mut shader_defs = vec![
             #[cfg(feature = "webgl")]
             "SIXTEEN_BYTE_ALIGNMENT".into(),
         ];
 
+        if key.width_space == GizmoLineWidthSpace::World {
+            shader_defs.push("WORLD_SPACE_WIDTH".into());
+        }
+
+        if key.line_cap != GizmoLineCap::Butt {
+            shader_defs.push("LINE_CAPS".into());
+        }
+
+        if key.line_cap == GizmoLineCap::Round {
+            shader_defs.push("LINE_CAP_ROUND".into());
+        }
+
         let layout = vec![
             self.mesh_pipeline.view_layout.clone(),
             self.uniform_layout.clone(),
@@ -190,6 +204,7 @@ impl FromWorld for LineJointGizmoPipeline {
 #[derive(PartialEq, Eq, Hash, Clone)]
 struct LineJointGizmoPipelineKey {
     mesh_key: Mesh2dPipelineKey,
+    width_space: GizmoLineWidthSpace,
     joints: GizmoLineJoint,
 }
 
@@ -203,11 +218,15 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             TextureFormat::bevy_default()
         };
 
-        let shader_defs = vec![
+        let mut shader_defs = vec![
             #[cfg(feature = "webgl")]
             "SIXTEEN_BYTE_ALIGNMENT".into(),
         ];
 
+        if key.width_space == GizmoLineWidthSpace::World {
+            shader_defs.push("WORLD_SPACE_WIDTH".into());
+        }
+
         let layout = vec![
             self.mesh_pipeline.view_layout.clone(),
             self.uniform_layout.clone(),
@@ -331,7 +350,9 @@ fn queue_line_gizmos_2d(
                     LineGizmoPipelineKey {
                         mesh_key,
                         strip: false,
+                        width_space: config.line_width_space,
                         line_style: config.line_style,
+                        line_cap: config.line_cap,
                     },
                 );
                 transparent_phase.add(Transparent2d {
@@ -352,7 +373,9 @@ fn queue_line_gizmos_2d(
                     LineGizmoPipelineKey {
                         mesh_key,
                         strip: true,
+                        width_space: config.line_width_space,
                         line_style: config.line_style,
+                        line_cap: config.line_cap,
                     },
                 );
                 transparent_phase.add(Transparent2d {
@@ -411,6 +434,7 @@ fn queue_line_joint_gizmos_2d(
                 &pipeline,
                 LineJointGizmoPipelineKey {
                     mesh_key,
+                    width_space: config.line_width_space,
                     joints: config.line_joints,
                 },
             );
@@ -1,5 +1,5 @@
 use crate::{
-    config::{GizmoLineJoint, GizmoLineStyle, GizmoMeshConfig},
+    config::{GizmoLineCap, GizmoLineJoint, GizmoLineStyle, GizmoLineWidthSpace, GizmoMeshConfig},
     line_gizmo_vertex_buffer_layouts, line_joint_gizmo_vertex_buffer_layouts, DrawLineGizmo,
     DrawLineJointGizmo, GizmoRenderSystem, GpuLineGizmo, LineGizmoUniformBindgroupLayout,
     SetLineGizmoBindGroup, LINE_JOINT_SHADER_HANDLE, LINE_SHADER_HANDLE,
@@ -93,7 +93,9 @@ struct LineGizmoPipelineKey {
     view_key: MeshPipelineKey,
     strip: bool,
     perspective: bool,
+    width_space: GizmoLineWidthSpace,
     line_style: GizmoLineStyle,
+    line_cap: GizmoLineCap,
 }
 
 impl SpecializedRenderPipeline for LineGizmoPipeline {
@@ -109,6 +111,18 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             shader_defs.push("PERSPECTIVE".into());
         }
 
+        if key.width_space == GizmoLineWidthSpace::World {
+            shader_defs.push("WORLD_SPACE_WIDTH".into());
+        }
+
+        if key.line_cap != GizmoLineCap::Butt {
+            shader_defs.push("LINE_CAPS".into());
+        }
+
+        if key.line_cap == GizmoLineCap::Round {
+            shader_defs.push("LINE_CAP_ROUND".into());
+        }
+
         let format = if key.view_key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -188,6 +202,7 @@ impl FromWorld for LineJointGizmoPipeline {
 struct LineJointGizmoPipelineKey {
     view_key: MeshPipelineKey,
     perspective: bool,
+    width_space: GizmoLineWidthSpace,
     joints: GizmoLineJoint,
 }
 
@@ -204,6 +219,10 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             shader_defs.push("PERSPECTIVE".into());
         }
 
+        if key.width_space == GizmoLineWidthSpace::World {
+            shader_defs.push("WORLD_SPACE_WIDTH".into());
+        }
+
         let format = if key.view_key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -360,7 +379,9 @@ fn queue_line_gizmos_3d(
                         view_key,
                         strip: false,
                         perspective: config.line_perspective,
+                        width_space: config.line_width_space,
                         line_style: config.line_style,
+                        line_cap: config.line_cap,
                     },
                 );
                 transparent_phase.add(Transparent3d {
@@ -382,7 +403,9 @@ fn queue_line_gizmos_3d(
                         view_key,
                         strip: true,
                         perspective: config.line_perspective,
+                        width_space: config.line_width_space,
                         line_style: config.line_style,
+                        line_cap: config.line_cap,
                     },
                 );
                 transparent_phase.add(Transparent3d {
@@ -476,6 +499,7 @@ fn queue_line_joint_gizmos_3d(
                 LineJointGizmoPipelineKey {
                     view_key,
                     perspective: config.line_perspective,
+                    width_space: config.line_width_space,
                     joints: config.line_joints,
                 },
             );
@@ -149,8 +149,10 @@ pub(crate) fn extract_linegizmos(
             #[cfg(any(feature = "bevy_pbr", feature = "bevy_sprite"))]
             crate::config::GizmoMeshConfig {
                 line_perspective: gizmo.line_config.perspective,
+                line_width_space: gizmo.line_config.width_space,
                 line_style: gizmo.line_config.style,
                 line_joints: gizmo.line_config.joints,
+                line_cap: gizmo.line_config.cap,
                 render_layers: render_layers.cloned().unwrap_or_default(),
                 handle: gizmo.handle.clone_weak(),
             },
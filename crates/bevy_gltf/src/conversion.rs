@@ -0,0 +1,253 @@
+use bevy_math::{Mat4, Quat, Vec3};
+use bevy_platform_support::collections::HashSet;
+use bevy_render::{
+    mesh::{morph::MorphAttributes, Mesh, VertexAttributeValues},
+    primitives::Aabb,
+};
+use bevy_transform::components::Transform;
+use gltf::Node;
+use serde::{Deserialize, Serialize};
+
+use crate::loader::node_transform;
+
+/// How the [`GltfLoader`](crate::GltfLoader) converts the coordinates and units of glTF files.
+///
+/// The default for all files is set with
+/// [`GltfPlugin::coordinate_conversion`](crate::GltfPlugin::coordinate_conversion), and can be
+/// overridden per file with [`GltfLoaderSettings::convert_coordinates`](crate::GltfLoaderSettings::convert_coordinates)
+/// and [`GltfLoaderSettings::unit_scale`](crate::GltfLoaderSettings::unit_scale), for example in
+/// the `.meta` file of the asset.
+///
+/// The conversion is applied to the data of the file, consistently for meshes, morph targets,
+/// nodes, cameras, lights, skins and animation curves, so the loaded assets don't need any extra
+/// transform.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GltfCoordinateConversion {
+    /// If true, the scenes are turned around so that the front of glTF models faces Bevy's forward
+    /// direction.
+    ///
+    /// The front of a glTF model faces +Z, while Bevy's forward direction is -Z. Both use +Y as
+    /// up, so this is a half turn around the Y axis: glTF's +X becomes -X and +Z becomes -Z.
+    /// Cameras and lights keep looking in the same direction relative to the scene.
+    pub convert_coordinates: bool,
+    /// The length of a unit of the file, in meters.
+    ///
+    /// glTF files are specified in meters, but some exporters write other units: a file in
+    /// centimeters is loaded with a scale of `0.01`. Positions and distances are multiplied by
+    /// this scale, including the ranges of lights and the clipping planes of cameras.
+    pub unit_scale: f32,
+}
+
+impl Default for GltfCoordinateConversion {
+    fn default() -> Self {
+        Self {
+            convert_coordinates: false,
+            unit_scale: 1.0,
+        }
+    }
+}
+
+/// A half turn around the Y axis.
+const HALF_TURN_Y: Quat = Quat::from_xyzw(0.0, 1.0, 0.0, 0.0);
+
+/// Applies a [`GltfCoordinateConversion`] to the data of a glTF file.
+pub(crate) struct CoordinateConverter {
+    conversion: GltfCoordinateConversion,
+    /// The nodes with a camera, which are turned around once more to keep looking forward.
+    camera_nodes: HashSet<usize>,
+    /// The children of the nodes with a camera, which are turned around to compensate.
+    camera_children: HashSet<usize>,
+}
+
+impl CoordinateConverter {
+    pub(crate) fn new(conversion: GltfCoordinateConversion, gltf: &gltf::Gltf) -> Self {
+        let mut camera_nodes = HashSet::default();
+        let mut camera_children = HashSet::default();
+        if conversion.convert_coordinates {
+            for node in gltf.nodes().filter(|node| node.camera().is_some()) {
+                camera_nodes.insert(node.index());
+                camera_children.extend(node.children().map(|child| child.index()));
+            }
+        }
+        Self {
+            conversion,
+            camera_nodes,
+            camera_children,
+        }
+    }
+
+    /// Converts a direction, such as a normal.
+    pub(crate) fn direction(&self, direction: Vec3) -> Vec3 {
+        if self.conversion.convert_coordinates {
+            Vec3::new(-direction.x, direction.y, -direction.z)
+        } else {
+            direction
+        }
+    }
+
+    /// Converts a position, or any other vector measured in the units of the file.
+    pub(crate) fn position(&self, position: Vec3) -> Vec3 {
+        self.direction(position) * self.conversion.unit_scale
+    }
+
+    /// Converts a distance measured in the units of the file.
+    pub(crate) fn distance(&self, distance: f32) -> f32 {
+        distance * self.conversion.unit_scale
+    }
+
+    fn rotation(&self, rotation: Quat) -> Quat {
+        if self.conversion.convert_coordinates {
+            Quat::from_xyzw(-rotation.x, rotation.y, -rotation.z, rotation.w)
+        } else {
+            rotation
+        }
+    }
+
+    /// The rotations applied before and after the converted rotation of a node.
+    ///
+    /// Cameras look towards -Z in the space of their node, which the conversion turns into +Z, so
+    /// camera nodes are turned around, and their children are turned back.
+    fn node_corrections(&self, node_index: usize) -> (Quat, Quat) {
+        let before = if self.camera_children.contains(&node_index) {
+            HALF_TURN_Y
+        } else {
+            Quat::IDENTITY
+        };
+        let after = if self.camera_nodes.contains(&node_index) {
+            HALF_TURN_Y
+        } else {
+            Quat::IDENTITY
+        };
+        (before, after)
+    }
+
+    /// Converts the translation of a node, or a keyframe of its translation.
+    pub(crate) fn node_translation(&self, node_index: usize, translation: Vec3) -> Vec3 {
+        let (before, _) = self.node_corrections(node_index);
+        before * self.position(translation)
+    }
+
+    /// Converts the rotation of a node, or a keyframe of its rotation.
+    pub(crate) fn node_rotation(&self, node_index: usize, rotation: Quat) -> Quat {
+        let (before, after) = self.node_corrections(node_index);
+        before * self.rotation(rotation) * after
+    }
+
+    /// Returns the converted transform of a node.
+    ///
+    /// The scale of nodes doesn't need to be converted: a half turn around the Y axis commutes
+    /// with any scale along the axes.
+    pub(crate) fn node_transform(&self, node: &Node) -> Transform {
+        let transform = node_transform(node);
+        Transform {
+            translation: self.node_translation(node.index(), transform.translation),
+            rotation: self.node_rotation(node.index(), transform.rotation),
+            scale: transform.scale,
+        }
+    }
+
+    /// Returns the transform of the light of a node, relative to the node.
+    ///
+    /// Like cameras, lights look towards -Z in the space of their node, so they're turned around.
+    pub(crate) fn light_transform(&self) -> Transform {
+        if self.conversion.convert_coordinates {
+            Transform::from_rotation(HALF_TURN_Y)
+        } else {
+            Transform::IDENTITY
+        }
+    }
+
+    /// Converts the inverse bind matrix of a joint.
+    pub(crate) fn inverse_bind_matrix(&self, joint_index: usize, matrix: Mat4) -> Mat4 {
+        let conversion = Mat4::from_scale(self.position(Vec3::ONE));
+        let (_, after) = self.node_corrections(joint_index);
+        Mat4::from_quat(after.inverse()) * conversion * matrix * conversion.inverse()
+    }
+
+    /// Converts the positions, normals and tangents of a mesh.
+    pub(crate) fn mesh(&self, mesh: &mut Mesh) {
+        if self.conversion == GltfCoordinateConversion::default() {
+            return;
+        }
+        if let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+        {
+            for position in positions {
+                *position = self.position(Vec3::from(*position)).to_array();
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for normal in normals {
+                *normal = self.direction(Vec3::from(*normal)).to_array();
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x4(tangents)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_TANGENT)
+        {
+            for tangent in tangents {
+                let [x, y, z, w] = *tangent;
+                let [x, y, z] = self.direction(Vec3::new(x, y, z)).to_array();
+                *tangent = [x, y, z, w];
+            }
+        }
+    }
+
+    /// Converts the displacements of a morph target.
+    pub(crate) fn morph_attributes(&self, attributes: MorphAttributes) -> MorphAttributes {
+        MorphAttributes {
+            position: self.position(attributes.position),
+            normal: self.direction(attributes.normal),
+            tangent: self.direction(attributes.tangent),
+        }
+    }
+
+    /// Converts the bounding box of a primitive.
+    pub(crate) fn aabb(&self, min: Vec3, max: Vec3) -> Aabb {
+        let (min, max) = (self.position(min), self.position(max));
+        Aabb::from_min_max(min.min(max), min.max(max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_conversion() {
+        let converter = CoordinateConverter {
+            conversion: GltfCoordinateConversion {
+                convert_coordinates: true,
+                unit_scale: 0.01,
+            },
+            camera_nodes: [0].into_iter().collect(),
+            camera_children: [1].into_iter().collect(),
+        };
+
+        assert_eq!(
+            converter.position(Vec3::new(100.0, 200.0, 300.0)),
+            Vec3::new(-1.0, 2.0, -3.0)
+        );
+
+        // A camera keeps looking in the same direction relative to the scene.
+        let camera = Transform::from_rotation(converter.node_rotation(0, Quat::IDENTITY));
+        assert!((camera.rotation * Vec3::NEG_Z).abs_diff_eq(converter.direction(Vec3::NEG_Z), 1e-6));
+
+        // The children of the camera are where they would be without the camera's correction.
+        let child = Transform {
+            translation: converter.node_translation(1, Vec3::new(100.0, 0.0, 0.0)),
+            rotation: converter.node_rotation(1, Quat::IDENTITY),
+            scale: Vec3::ONE,
+        };
+        let global = camera * child;
+        assert!(global
+            .translation
+            .abs_diff_eq(Vec3::new(-1.0, 0.0, 0.0), 1e-6));
+        assert!((global.rotation * Vec3::X).abs_diff_eq(Vec3::X, 1e-6));
+
+        let aabb = converter.aabb(Vec3::new(0.0, 0.0, 0.0), Vec3::new(100.0, 100.0, 100.0));
+        assert_eq!(Vec3::from(aabb.min()), Vec3::new(-1.0, 0.0, -1.0));
+        assert_eq!(Vec3::from(aabb.max()), Vec3::new(0.0, 1.0, 0.0));
+    }
+}
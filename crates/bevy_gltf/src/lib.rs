@@ -96,8 +96,10 @@ extern crate alloc;
 use bevy_animation::AnimationClip;
 use bevy_platform_support::collections::HashMap;
 
+mod conversion;
 mod loader;
 mod vertex_attributes;
+pub use conversion::GltfCoordinateConversion;
 pub use loader::*;
 
 use bevy_app::prelude::*;
@@ -123,6 +125,23 @@ pub mod prelude {
 /// Adds support for glTF file loading to the app.
 #[derive(Default)]
 pub struct GltfPlugin {
+    /// The coordinate conversion applied to all glTF files, unless overridden by their
+    /// [`GltfLoaderSettings`].
+    ///
+    /// For example, to load all files as if their models were facing Bevy's forward direction,
+    /// and their units were centimeters:
+    ///
+    /// ```
+    /// # use bevy_gltf::{GltfCoordinateConversion, GltfPlugin};
+    /// let plugin = GltfPlugin {
+    ///     coordinate_conversion: GltfCoordinateConversion {
+    ///         convert_coordinates: true,
+    ///         unit_scale: 0.01,
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// ```
+    pub coordinate_conversion: GltfCoordinateConversion,
     custom_vertex_attributes: HashMap<Box<str>, MeshVertexAttribute>,
 }
 
@@ -165,6 +184,7 @@ impl Plugin for GltfPlugin {
         app.register_asset_loader(GltfLoader {
             supported_compressed_formats,
             custom_vertex_attributes: self.custom_vertex_attributes.clone(),
+            default_coordinate_conversion: self.coordinate_conversion,
         });
     }
 }
@@ -1,13 +1,14 @@
 use crate::{
-    vertex_attributes::convert_attribute, Gltf, GltfAssetLabel, GltfExtras, GltfMaterialExtras,
-    GltfMaterialName, GltfMeshExtras, GltfNode, GltfSceneExtras, GltfSkin,
+    conversion::CoordinateConverter, vertex_attributes::convert_attribute, Gltf, GltfAssetLabel,
+    GltfCoordinateConversion, GltfExtras, GltfMaterialExtras, GltfMaterialName, GltfMeshExtras,
+    GltfNode, GltfSceneExtras, GltfSkin,
 };
 
 use bevy_asset::{
     io::Reader, AssetLoadError, AssetLoader, Handle, LoadContext, ReadAssetBytesError,
 };
 use bevy_color::{Color, LinearRgba};
-use bevy_core_pipeline::prelude::Camera3d;
+use bevy_core_pipeline::{dof::DepthOfField, prelude::Camera3d};
 use bevy_ecs::{
     entity::{hash_map::EntityHashMap, Entity},
     hierarchy::ChildSpawner,
@@ -26,13 +27,15 @@ use bevy_pbr::{
 use bevy_platform_support::collections::{HashMap, HashSet};
 use bevy_render::{
     alpha::AlphaMode,
-    camera::{Camera, OrthographicProjection, PerspectiveProjection, Projection, ScalingMode},
+    camera::{
+        Camera, Exposure, OrthographicProjection, PerspectiveProjection, PhysicalCameraParameters,
+        Projection, ScalingMode,
+    },
     mesh::{
         morph::{MeshMorphWeights, MorphAttributes, MorphTargetImage, MorphWeights},
         skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
         Indices, Mesh, Mesh3d, MeshVertexAttribute, VertexAttributeValues,
     },
-    primitives::Aabb,
     render_asset::RenderAssetUsages,
     render_resource::{Face, PrimitiveTopology},
     view::Visibility,
@@ -133,6 +136,9 @@ pub struct GltfLoader {
     /// See [this section of the glTF specification](https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#meshes-overview)
     /// for additional details on custom attributes.
     pub custom_vertex_attributes: HashMap<Box<str>, MeshVertexAttribute>,
+    /// The coordinate conversion applied to the files which don't override it in their
+    /// [`GltfLoaderSettings`].
+    pub default_coordinate_conversion: GltfCoordinateConversion,
 }
 
 /// Specifies optional settings for processing gltfs at load time. By default, all recognized contents of
@@ -168,6 +174,32 @@ pub struct GltfLoaderSettings {
     pub load_lights: bool,
     /// If true, the loader will include the root of the gltf root node.
     pub include_source: bool,
+    /// How the intensities of `KHR_lights_punctual` lights are interpreted.
+    pub light_intensity_units: GltfLightIntensityUnits,
+    /// If true, the loader will read the physical parameters of cameras from their extras.
+    ///
+    /// glTF doesn't define physical camera parameters, so they are read from the `extras` of
+    /// the camera, using the names of the fields of [`PhysicalCameraParameters`]:
+    /// `aperture_f_stops`, `shutter_speed_s`, `sensitivity_iso` and `sensor_height`. Missing
+    /// parameters take their default value. If any of them is present, an [`Exposure`] is added
+    /// to the camera. If a `focal_distance` (in meters) is present as well, a [`DepthOfField`] is
+    /// added too.
+    pub load_physical_cameras: bool,
+    /// How the keyframes of linearly interpolated transform animations are compressed.
+    ///
+    /// If `None`, the keyframes are kept as they are in the file.
+    #[cfg(feature = "bevy_animation")]
+    pub animation_compression: Option<bevy_animation::compression::AnimationCompression>,
+    /// Overrides [`GltfCoordinateConversion::convert_coordinates`] for this file.
+    ///
+    /// If `None`, the default of the [`GltfLoader`] is used, which is set with
+    /// [`GltfPlugin::coordinate_conversion`](crate::GltfPlugin::coordinate_conversion).
+    pub convert_coordinates: Option<bool>,
+    /// Overrides [`GltfCoordinateConversion::unit_scale`] for this file.
+    ///
+    /// If `None`, the default of the [`GltfLoader`] is used, which is set with
+    /// [`GltfPlugin::coordinate_conversion`](crate::GltfPlugin::coordinate_conversion).
+    pub unit_scale: Option<f32>,
 }
 
 impl Default for GltfLoaderSettings {
@@ -178,10 +210,96 @@ impl Default for GltfLoaderSettings {
             load_cameras: true,
             load_lights: true,
             include_source: false,
+            light_intensity_units: GltfLightIntensityUnits::default(),
+            load_physical_cameras: true,
+            #[cfg(feature = "bevy_animation")]
+            animation_compression: None,
+            convert_coordinates: None,
+            unit_scale: None,
+        }
+    }
+}
+
+/// How the intensities of `KHR_lights_punctual` lights are interpreted by the [`GltfLoader`].
+///
+/// Bevy measures the intensity of point and spot lights as luminous power in lumens, and the
+/// illuminance of directional lights in lux.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum GltfLightIntensityUnits {
+    /// The units of the `KHR_lights_punctual` specification: point and spot lights are in candela,
+    /// and directional lights are in lux.
+    #[default]
+    Photometric,
+    /// Point and spot lights are in lumens, and directional lights are in lux.
+    ///
+    /// Some exporters write the luminous power of point and spot lights instead of their
+    /// luminous intensity.
+    Lumens,
+    /// Point and spot lights are in watts, and directional lights are in watts per square meter.
+    ///
+    /// This matches files exported with unitless or "raw" lighting, as Blender can do. The
+    /// intensities are converted with the given luminous efficacy, in lumens per watt. The luminous
+    /// efficacy of a perfect light source at 555nm is `683.0`.
+    Watts {
+        /// The number of lumens emitted per watt.
+        luminous_efficacy: f32,
+    },
+}
+
+impl GltfLightIntensityUnits {
+    /// Converts the intensity of a point or spot light to luminous power, in lumens.
+    pub fn to_lumens(self, intensity: f32) -> f32 {
+        match self {
+            // For a point light, luminous power = 4 * pi * luminous intensity. Spot lights are
+            // treated as point lights with their light masked, so they use the same conversion.
+            Self::Photometric => intensity * core::f32::consts::PI * 4.0,
+            Self::Lumens => intensity,
+            Self::Watts { luminous_efficacy } => intensity * luminous_efficacy,
+        }
+    }
+
+    /// Converts the intensity of a directional light to illuminance, in lux.
+    pub fn to_lux(self, intensity: f32) -> f32 {
+        match self {
+            Self::Photometric | Self::Lumens => intensity,
+            Self::Watts { luminous_efficacy } => intensity * luminous_efficacy,
         }
     }
 }
 
+/// The physical camera parameters read from the extras of a glTF camera.
+#[derive(Deserialize, Default)]
+struct PhysicalCameraExtras {
+    aperture_f_stops: Option<f32>,
+    shutter_speed_s: Option<f32>,
+    sensitivity_iso: Option<f32>,
+    sensor_height: Option<f32>,
+    focal_distance: Option<f32>,
+}
+
+impl PhysicalCameraExtras {
+    /// Returns the components to add to a camera with these extras.
+    fn components(&self) -> (Option<Exposure>, Option<DepthOfField>) {
+        let defaults = PhysicalCameraParameters::default();
+        let parameters = PhysicalCameraParameters {
+            aperture_f_stops: self.aperture_f_stops.unwrap_or(defaults.aperture_f_stops),
+            shutter_speed_s: self.shutter_speed_s.unwrap_or(defaults.shutter_speed_s),
+            sensitivity_iso: self.sensitivity_iso.unwrap_or(defaults.sensitivity_iso),
+            sensor_height: self.sensor_height.unwrap_or(defaults.sensor_height),
+        };
+        let exposure = (self.aperture_f_stops.is_some()
+            || self.shutter_speed_s.is_some()
+            || self.sensitivity_iso.is_some()
+            || self.sensor_height.is_some())
+        .then(|| Exposure::from_physical_camera(parameters));
+        let depth_of_field = self.focal_distance.map(|focal_distance| DepthOfField {
+            focal_distance,
+            ..DepthOfField::from_physical_camera(&parameters)
+        });
+        (exposure, depth_of_field)
+    }
+}
+
 impl AssetLoader for GltfLoader {
     type Asset = Gltf;
     type Settings = GltfLoaderSettings;
@@ -221,6 +339,17 @@ async fn load_gltf<'a, 'b, 'c>(
         .to_string();
     let buffer_data = load_buffers(&gltf, load_context).await?;
 
+    let default_conversion = loader.default_coordinate_conversion;
+    let converter = CoordinateConverter::new(
+        GltfCoordinateConversion {
+            convert_coordinates: settings
+                .convert_coordinates
+                .unwrap_or(default_conversion.convert_coordinates),
+            unit_scale: settings.unit_scale.unwrap_or(default_conversion.unit_scale),
+        },
+        &gltf,
+    );
+
     let mut linear_textures = <HashSet<_>>::default();
 
     for material in gltf.materials() {
@@ -275,7 +404,10 @@ async fn load_gltf<'a, 'b, 'c>(
 
     #[cfg(feature = "bevy_animation")]
     let (animations, named_animations, animation_roots) = {
-        use bevy_animation::{animated_field, animation_curves::*, gltf_curves::*, VariableCurve};
+        use bevy_animation::{
+            animated_field, animation_curves::*, compression::QuantizedRotationCurve,
+            gltf_curves::*, VariableCurve,
+        };
         use bevy_math::{
             curve::{ConstantCurve, Interval, UnevenSampleAutoCurve},
             Quat, Vec4,
@@ -314,7 +446,11 @@ async fn load_gltf<'a, 'b, 'c>(
                     match outputs {
                         ReadOutputs::Translations(tr) => {
                             let translation_property = animated_field!(Transform::translation);
-                            let translations: Vec<Vec3> = tr.map(Vec3::from).collect();
+                            let translations: Vec<Vec3> = tr
+                                .map(|translation| {
+                                    converter.node_translation(node.index(), translation.into())
+                                })
+                                .collect();
                             if keyframe_timestamps.len() == 1 {
                                 Some(VariableCurve::new(AnimatableCurve::new(
                                     translation_property,
@@ -323,6 +459,15 @@ async fn load_gltf<'a, 'b, 'c>(
                             } else {
                                 match interpolation {
                                     gltf::animation::Interpolation::Linear => {
+                                        let (keyframe_timestamps, translations) = match &settings
+                                            .animation_compression
+                                        {
+                                            Some(compression) => compression.reduce_translations(
+                                                &keyframe_timestamps,
+                                                &translations,
+                                            ),
+                                            None => (keyframe_timestamps, translations),
+                                        };
                                         UnevenSampleAutoCurve::new(
                                             keyframe_timestamps.into_iter().zip(translations),
                                         )
@@ -361,8 +506,13 @@ async fn load_gltf<'a, 'b, 'c>(
                         }
                         ReadOutputs::Rotations(rots) => {
                             let rotation_property = animated_field!(Transform::rotation);
-                            let rotations: Vec<Quat> =
-                                rots.into_f32().map(Quat::from_array).collect();
+                            let rotations: Vec<Quat> = rots
+                                .into_f32()
+                                .map(|rotation| {
+                                    converter
+                                        .node_rotation(node.index(), Quat::from_array(rotation))
+                                })
+                                .collect();
                             if keyframe_timestamps.len() == 1 {
                                 Some(VariableCurve::new(AnimatableCurve::new(
                                     rotation_property,
@@ -371,16 +521,50 @@ async fn load_gltf<'a, 'b, 'c>(
                             } else {
                                 match interpolation {
                                     gltf::animation::Interpolation::Linear => {
-                                        UnevenSampleAutoCurve::new(
-                                            keyframe_timestamps.into_iter().zip(rotations),
-                                        )
-                                        .ok()
-                                        .map(|curve| {
-                                            VariableCurve::new(AnimatableCurve::new(
-                                                rotation_property,
-                                                curve,
-                                            ))
-                                        })
+                                        match &settings.animation_compression {
+                                            Some(compression) => {
+                                                let (keyframe_timestamps, rotations) = compression
+                                                    .reduce_rotations(
+                                                        &keyframe_timestamps,
+                                                        &rotations,
+                                                    );
+                                                let timed_rotations =
+                                                    keyframe_timestamps.into_iter().zip(rotations);
+                                                if compression.quantize_rotations {
+                                                    QuantizedRotationCurve::new(timed_rotations)
+                                                        .ok()
+                                                        .map(|curve| {
+                                                            VariableCurve::new(
+                                                                AnimatableCurve::new(
+                                                                    rotation_property,
+                                                                    curve,
+                                                                ),
+                                                            )
+                                                        })
+                                                } else {
+                                                    UnevenSampleAutoCurve::new(timed_rotations)
+                                                        .ok()
+                                                        .map(|curve| {
+                                                            VariableCurve::new(
+                                                                AnimatableCurve::new(
+                                                                    rotation_property,
+                                                                    curve,
+                                                                ),
+                                                            )
+                                                        })
+                                                }
+                                            }
+                                            None => UnevenSampleAutoCurve::new(
+                                                keyframe_timestamps.into_iter().zip(rotations),
+                                            )
+                                            .ok()
+                                            .map(|curve| {
+                                                VariableCurve::new(AnimatableCurve::new(
+                                                    rotation_property,
+                                                    curve,
+                                                ))
+                                            }),
+                                        }
                                     }
                                     gltf::animation::Interpolation::Step => {
                                         SteppedKeyframeCurve::new(
@@ -421,6 +605,12 @@ async fn load_gltf<'a, 'b, 'c>(
                             } else {
                                 match interpolation {
                                     gltf::animation::Interpolation::Linear => {
+                                        let (keyframe_timestamps, scales) =
+                                            match &settings.animation_compression {
+                                                Some(compression) => compression
+                                                    .reduce_scales(&keyframe_timestamps, &scales),
+                                                None => (keyframe_timestamps, scales),
+                                            };
                                         UnevenSampleAutoCurve::new(
                                             keyframe_timestamps.into_iter().zip(scales),
                                         )
@@ -691,7 +881,10 @@ async fn load_gltf<'a, 'b, 'c>(
                         primitive: primitive.index(),
                     };
                     let morph_target_image = MorphTargetImage::new(
-                        morph_target_reader.map(PrimitiveMorphAttributesIter),
+                        morph_target_reader.map(|attributes| {
+                            PrimitiveMorphAttributesIter(attributes)
+                                .map(|attributes| converter.morph_attributes(attributes))
+                        }),
                         mesh.count_vertices(),
                         RenderAssetUsages::default(),
                     )?;
@@ -749,6 +942,8 @@ async fn load_gltf<'a, 'b, 'c>(
                 });
             }
 
+            converter.mesh(&mut mesh);
+
             let mesh_handle = load_context.add_labeled_asset(primitive_label.to_string(), mesh);
             primitives.push(super::GltfPrimitive::new(
                 &gltf_mesh,
@@ -780,7 +975,10 @@ async fn load_gltf<'a, 'b, 'c>(
             let local_to_bone_bind_matrices: Vec<Mat4> = reader
                 .read_inverse_bind_matrices()
                 .unwrap()
-                .map(|mat| Mat4::from_cols_array_2d(&mat))
+                .zip(gltf_skin.joints())
+                .map(|(mat, joint)| {
+                    converter.inverse_bind_matrix(joint.index(), Mat4::from_cols_array_2d(&mat))
+                })
                 .collect();
 
             load_context.add_labeled_asset(
@@ -855,7 +1053,7 @@ async fn load_gltf<'a, 'b, 'c>(
             &node,
             children,
             mesh,
-            node_transform(&node),
+            converter.node_transform(&node),
             skin,
             get_gltf_extras(node.extras()),
         );
@@ -897,6 +1095,7 @@ async fn load_gltf<'a, 'b, 'c>(
                         load_context,
                         &mut scene_load_context,
                         settings,
+                        &converter,
                         &mut node_index_to_entity_map,
                         &mut entity_to_skin_index_map,
                         &mut active_camera_found,
@@ -999,7 +1198,7 @@ fn get_gltf_extras(extras: &json::Extras) -> Option<GltfExtras> {
 /// on [`Node::transform()`] directly because it uses optimized glam types and
 /// if `libm` feature of `bevy_math` crate is enabled also handles cross
 /// platform determinism properly.
-fn node_transform(node: &Node) -> Transform {
+pub(crate) fn node_transform(node: &Node) -> Transform {
     match node.transform() {
         gltf::scene::Transform::Matrix { matrix } => {
             Transform::from_matrix(Mat4::from_cols_array_2d(&matrix))
@@ -1429,6 +1628,7 @@ fn load_node(
     root_load_context: &LoadContext,
     load_context: &mut LoadContext,
     settings: &GltfLoaderSettings,
+    converter: &CoordinateConverter,
     node_index_to_entity_map: &mut HashMap<usize, Entity>,
     entity_to_skin_index_map: &mut EntityHashMap<usize>,
     active_camera_found: &mut bool,
@@ -1438,7 +1638,7 @@ fn load_node(
     document: &Document,
 ) -> Result<(), GltfError> {
     let mut gltf_error = None;
-    let transform = node_transform(gltf_node);
+    let transform = converter.node_transform(gltf_node);
     let world_transform = *parent_transform * transform;
     // according to https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#instantiation,
     // if the determinant of the transform is negative we must invert the winding order of
@@ -1482,10 +1682,10 @@ fn load_node(
         if let Some(camera) = gltf_node.camera() {
             let projection = match camera.projection() {
                 gltf::camera::Projection::Orthographic(orthographic) => {
-                    let xmag = orthographic.xmag();
+                    let xmag = converter.distance(orthographic.xmag());
                     let orthographic_projection = OrthographicProjection {
-                        near: orthographic.znear(),
-                        far: orthographic.zfar(),
+                        near: converter.distance(orthographic.znear()),
+                        far: converter.distance(orthographic.zfar()),
                         scaling_mode: ScalingMode::FixedHorizontal {
                             viewport_width: xmag,
                         },
@@ -1497,11 +1697,11 @@ fn load_node(
                 gltf::camera::Projection::Perspective(perspective) => {
                     let mut perspective_projection: PerspectiveProjection = PerspectiveProjection {
                         fov: perspective.yfov(),
-                        near: perspective.znear(),
+                        near: converter.distance(perspective.znear()),
                         ..Default::default()
                     };
                     if let Some(zfar) = perspective.zfar() {
-                        perspective_projection.far = zfar;
+                        perspective_projection.far = converter.distance(zfar);
                     }
                     if let Some(aspect_ratio) = perspective.aspect_ratio() {
                         perspective_projection.aspect_ratio = aspect_ratio;
@@ -1519,6 +1719,25 @@ fn load_node(
                 },
             ));
 
+            if settings.load_physical_cameras {
+                if let Some(extras) = camera.extras() {
+                    match serde_json::from_str::<PhysicalCameraExtras>(extras.get()) {
+                        Ok(extras) => {
+                            let (exposure, depth_of_field) = extras.components();
+                            if let Some(exposure) = exposure {
+                                node.insert(exposure);
+                            }
+                            if let Some(depth_of_field) = depth_of_field {
+                                node.insert(depth_of_field);
+                            }
+                        }
+                        Err(err) => {
+                            warn!("Ignoring the physical parameters of a glTF camera: {err}");
+                        }
+                    }
+                }
+            }
+
             *active_camera_found = true;
         }
     }
@@ -1580,10 +1799,10 @@ fn load_node(
                         // > the accessors of the original primitive.
                         mesh_entity.insert(MeshMorphWeights::new(weights).unwrap());
                     }
-                    mesh_entity.insert(Aabb::from_min_max(
-                        Vec3::from_slice(&bounds.min),
-                        Vec3::from_slice(&bounds.max),
-                    ));
+                    mesh_entity.insert(
+                        converter
+                            .aabb(Vec3::from_slice(&bounds.min), Vec3::from_slice(&bounds.max)),
+                    );
 
                     if let Some(extras) = primitive.extras() {
                         mesh_entity.insert(GltfExtras {
@@ -1620,13 +1839,16 @@ fn load_node(
             if let Some(light) = gltf_node.light() {
                 match light.kind() {
                     gltf::khr_lights_punctual::Kind::Directional => {
-                        let mut entity = parent.spawn(DirectionalLight {
-                            color: Color::srgb_from_array(light.color()),
-                            // NOTE: KHR_punctual_lights defines the intensity units for directional
-                            // lights in lux (lm/m^2) which is what we need.
-                            illuminance: light.intensity(),
-                            ..Default::default()
-                        });
+                        let mut entity = parent.spawn((
+                            DirectionalLight {
+                                color: Color::srgb_from_array(light.color()),
+                                illuminance: settings
+                                    .light_intensity_units
+                                    .to_lux(light.intensity()),
+                                ..Default::default()
+                            },
+                            converter.light_transform(),
+                        ));
                         if let Some(name) = light.name() {
                             entity.insert(Name::new(name.to_string()));
                         }
@@ -1637,16 +1859,20 @@ fn load_node(
                         }
                     }
                     gltf::khr_lights_punctual::Kind::Point => {
-                        let mut entity = parent.spawn(PointLight {
-                            color: Color::srgb_from_array(light.color()),
-                            // NOTE: KHR_punctual_lights defines the intensity units for point lights in
-                            // candela (lm/sr) which is luminous intensity and we need luminous power.
-                            // For a point light, luminous power = 4 * pi * luminous intensity
-                            intensity: light.intensity() * core::f32::consts::PI * 4.0,
-                            range: light.range().unwrap_or(20.0),
-                            radius: 0.0,
-                            ..Default::default()
-                        });
+                        let mut entity = parent.spawn((
+                            PointLight {
+                                color: Color::srgb_from_array(light.color()),
+                                intensity: settings
+                                    .light_intensity_units
+                                    .to_lumens(light.intensity()),
+                                range: light
+                                    .range()
+                                    .map_or(20.0, |range| converter.distance(range)),
+                                radius: 0.0,
+                                ..Default::default()
+                            },
+                            converter.light_transform(),
+                        ));
                         if let Some(name) = light.name() {
                             entity.insert(Name::new(name.to_string()));
                         }
@@ -1660,18 +1886,24 @@ fn load_node(
                         inner_cone_angle,
                         outer_cone_angle,
                     } => {
-                        let mut entity = parent.spawn(SpotLight {
-                            color: Color::srgb_from_array(light.color()),
-                            // NOTE: KHR_punctual_lights defines the intensity units for spot lights in
-                            // candela (lm/sr) which is luminous intensity and we need luminous power.
-                            // For a spot light, we map luminous power = 4 * pi * luminous intensity
-                            intensity: light.intensity() * core::f32::consts::PI * 4.0,
-                            range: light.range().unwrap_or(20.0),
-                            radius: light.range().unwrap_or(0.0),
-                            inner_angle: inner_cone_angle,
-                            outer_angle: outer_cone_angle,
-                            ..Default::default()
-                        });
+                        let mut entity = parent.spawn((
+                            SpotLight {
+                                color: Color::srgb_from_array(light.color()),
+                                intensity: settings
+                                    .light_intensity_units
+                                    .to_lumens(light.intensity()),
+                                range: light
+                                    .range()
+                                    .map_or(20.0, |range| converter.distance(range)),
+                                radius: light
+                                    .range()
+                                    .map_or(0.0, |range| converter.distance(range)),
+                                inner_angle: inner_cone_angle,
+                                outer_angle: outer_cone_angle,
+                                ..Default::default()
+                            },
+                            converter.light_transform(),
+                        ));
                         if let Some(name) = light.name() {
                             entity.insert(Name::new(name.to_string()));
                         }
@@ -1693,6 +1925,7 @@ fn load_node(
                 root_load_context,
                 load_context,
                 settings,
+                converter,
                 node_index_to_entity_map,
                 entity_to_skin_index_map,
                 active_camera_found,
@@ -2809,4 +3042,48 @@ mod test {
         assert_eq!(skinned_node.children.len(), 2);
         assert_eq!(skinned_node.skin.as_ref(), Some(&gltf_root.skins[0]));
     }
+
+    #[test]
+    fn light_intensity_units() {
+        use crate::GltfLightIntensityUnits;
+
+        let photometric = GltfLightIntensityUnits::Photometric;
+        assert_eq!(photometric.to_lumens(1.0), core::f32::consts::PI * 4.0);
+        assert_eq!(photometric.to_lux(3.0), 3.0);
+        assert_eq!(GltfLightIntensityUnits::Lumens.to_lumens(800.0), 800.0);
+        let watts = GltfLightIntensityUnits::Watts {
+            luminous_efficacy: 683.0,
+        };
+        assert_eq!(watts.to_lumens(2.0), 1366.0);
+        assert_eq!(watts.to_lux(2.0), 1366.0);
+    }
+
+    #[test]
+    fn physical_camera_extras() {
+        use super::PhysicalCameraExtras;
+        use bevy_render::camera::{Exposure, PhysicalCameraParameters};
+
+        let extras: PhysicalCameraExtras =
+            serde_json::from_str(r#"{ "aperture_f_stops": 2.8, "sensitivity_iso": 400.0 }"#)
+                .unwrap();
+        let (exposure, depth_of_field) = extras.components();
+        let parameters = PhysicalCameraParameters {
+            aperture_f_stops: 2.8,
+            sensitivity_iso: 400.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            exposure.map(|exposure| exposure.ev100),
+            Some(Exposure::from_physical_camera(parameters).ev100)
+        );
+        assert!(depth_of_field.is_none());
+
+        let extras: PhysicalCameraExtras =
+            serde_json::from_str(r#"{ "focal_distance": 4.0, "name": "Camera" }"#).unwrap();
+        let (exposure, depth_of_field) = extras.components();
+        assert!(exposure.is_none());
+        let depth_of_field = depth_of_field.unwrap();
+        assert_eq!(depth_of_field.focal_distance, 4.0);
+        assert_eq!(depth_of_field.sensor_height, parameters.sensor_height);
+    }
 }
@@ -8,7 +8,7 @@ use super::ktx2::*;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 use bevy_asset::{Asset, RenderAssetUsages};
-use bevy_color::{Color, ColorToComponents, Gray, LinearRgba, Srgba, Xyza};
+use bevy_color::{Color, ColorSpace, ColorToComponents, Gray, LinearRgba, Srgba, Xyza};
 use bevy_math::{AspectRatio, UVec2, UVec3, Vec2};
 use core::hash::Hash;
 use serde::{Deserialize, Serialize};
@@ -920,6 +920,52 @@ impl Image {
             .map(|(dyn_img, is_srgb)| Self::from_dynamic(dyn_img, is_srgb, self.asset_usage))
     }
 
+    /// Returns the [`ColorSpace`] in which the texels of this image are sampled.
+    ///
+    /// sRGB formats are decoded to linear values by the GPU when sampled, so they are in
+    /// [`ColorSpace::Srgb`]. All other formats are read as is, and are in
+    /// [`ColorSpace::LinearSrgb`].
+    pub fn color_space(&self) -> ColorSpace {
+        if self.texture_descriptor.format.is_srgb() {
+            ColorSpace::Srgb
+        } else {
+            ColorSpace::LinearSrgb
+        }
+    }
+
+    /// Tags the texels of this image as being in `color_space`, without changing the data.
+    ///
+    /// This switches the texture format to its sRGB or linear counterpart, so that the GPU
+    /// decodes the texels accordingly. This is used to conform images that are loaded with the
+    /// wrong [`ImageLoaderSettings::is_srgb`](crate::ImageLoaderSettings::is_srgb), such as a
+    /// normal map loaded as sRGB.
+    ///
+    /// Returns an error if the format has no counterpart in `color_space`, or if `color_space`
+    /// can't be sampled by the GPU ([`ColorSpace::AcesCg`]).
+    pub fn set_color_space(&mut self, color_space: ColorSpace) -> Result<(), TextureAccessError> {
+        let format = self.texture_descriptor.format;
+        let new_format = match color_space {
+            ColorSpace::Srgb => format.add_srgb_suffix(),
+            ColorSpace::LinearSrgb => format.remove_srgb_suffix(),
+            ColorSpace::AcesCg => format,
+        };
+        if color_space == ColorSpace::AcesCg || new_format.is_srgb() == color_space.is_linear() {
+            return Err(TextureAccessError::UnsupportedTextureFormat(format));
+        }
+        self.texture_descriptor.format = new_format;
+        if let Some(view_format) = self
+            .texture_view_descriptor
+            .as_mut()
+            .and_then(|descriptor| descriptor.format.as_mut())
+        {
+            *view_format = match color_space {
+                ColorSpace::Srgb => view_format.add_srgb_suffix(),
+                _ => view_format.remove_srgb_suffix(),
+            };
+        }
+        Ok(())
+    }
+
     /// Load a bytes buffer in a [`Image`], according to type `image_type`, using the `image`
     /// crate
     pub fn from_buffer(
@@ -1168,7 +1214,7 @@ impl Image {
     }
 
     #[inline(always)]
-    fn get_color_at_internal(&self, coords: UVec3) -> Result<Color, TextureAccessError> {
+    pub(crate) fn get_color_at_internal(&self, coords: UVec3) -> Result<Color, TextureAccessError> {
         let Some(bytes) = self.pixel_bytes(coords) else {
             return Err(TextureAccessError::OutOfBounds {
                 x: coords.x,
@@ -1310,7 +1356,7 @@ impl Image {
     }
 
     #[inline(always)]
-    fn set_color_at_internal(
+    pub(crate) fn set_color_at_internal(
         &mut self,
         coords: UVec3,
         color: Color,
@@ -1505,6 +1551,8 @@ pub enum TextureAccessError {
     UnsupportedTextureFormat(TextureFormat),
     #[error("attempt to access texture with different dimension")]
     WrongDimension,
+    #[error("compressed texture format must be decoded first: {0:?}")]
+    CompressedTextureFormat(TextureFormat),
 }
 
 /// An error that occurs when loading a texture
@@ -1717,4 +1765,29 @@ mod test {
         image.set_color_at_3d(4, 9, 2, Color::WHITE).unwrap();
         assert!(matches!(image.get_color_at_3d(4, 9, 2), Ok(Color::WHITE)));
     }
+
+    #[test]
+    fn set_color_space() {
+        let mut image = Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        assert_eq!(image.color_space(), ColorSpace::Srgb);
+        image.set_color_space(ColorSpace::LinearSrgb).unwrap();
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert!(image.set_color_space(ColorSpace::AcesCg).is_err());
+
+        let mut image = Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[0; 16],
+            TextureFormat::Rgba32Float,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        assert!(image.set_color_space(ColorSpace::Srgb).is_err());
+        assert_eq!(image.color_space(), ColorSpace::LinearSrgb);
+    }
 }
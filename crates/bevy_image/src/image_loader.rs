@@ -92,6 +92,12 @@ pub enum ImageFormatSetting {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImageLoaderSettings {
     pub format: ImageFormatSetting,
+    /// Whether the texels of the image are in [`ColorSpace::Srgb`](bevy_color::ColorSpace::Srgb)
+    /// rather than [`ColorSpace::LinearSrgb`](bevy_color::ColorSpace::LinearSrgb).
+    ///
+    /// This should be false for images that don't store colors, such as normal maps. Materials
+    /// that tag their textures with a color space conform them after loading, see
+    /// [`Image::set_color_space`](crate::Image::set_color_space).
     pub is_srgb: bool,
     pub sampler: ImageSampler,
     pub asset_usage: RenderAssetUsages,
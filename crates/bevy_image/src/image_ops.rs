@@ -0,0 +1,500 @@
+use crate::{Image, TextureAccessError, Volume};
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_math::{ops, URect, UVec2, UVec3, Vec4};
+use core::f32::consts::PI;
+use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+/// The filter used to compute the pixels of an [`Image`] when it's resized with
+/// [`Image::resample`] or when its mipmaps are generated with [`Image::generate_mipmaps`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ImageFilter {
+    /// Takes the nearest pixel, keeping hard edges. Best suited for pixel art.
+    Nearest,
+    /// Interpolates linearly between pixels, and averages them when downscaling.
+    #[default]
+    Linear,
+    /// A cubic filter, sharper than [`ImageFilter::Linear`].
+    CatmullRom,
+    /// A windowed sinc filter, the sharpest, which may ring around hard edges.
+    Lanczos3,
+}
+
+impl ImageFilter {
+    /// The distance from the center beyond which the filter is zero, in source pixels when
+    /// upscaling.
+    fn support(self) -> f32 {
+        match self {
+            ImageFilter::Nearest => 0.5,
+            ImageFilter::Linear => 1.0,
+            ImageFilter::CatmullRom => 2.0,
+            ImageFilter::Lanczos3 => 3.0,
+        }
+    }
+
+    fn weight(self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            ImageFilter::Nearest => {
+                if x < 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ImageFilter::Linear => (1.0 - x).max(0.0),
+            ImageFilter::CatmullRom => {
+                if x < 1.0 {
+                    1.5 * x * x * x - 2.5 * x * x + 1.0
+                } else if x < 2.0 {
+                    -0.5 * x * x * x + 2.5 * x * x - 4.0 * x + 2.0
+                } else {
+                    0.0
+                }
+            }
+            ImageFilter::Lanczos3 => {
+                if x < 3.0 {
+                    sinc(x) * sinc(x / 3.0)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+fn sinc(x: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else {
+        ops::sin(PI * x) / (PI * x)
+    }
+}
+
+/// The contribution of source pixels to a destination pixel along one axis.
+struct Contribution {
+    start: usize,
+    weights: Vec<f32>,
+}
+
+/// Computes the [`Contribution`]s of `source_len` pixels to each of `destination_len` pixels.
+fn contributions(source_len: u32, destination_len: u32, filter: ImageFilter) -> Vec<Contribution> {
+    let scale = source_len as f32 / destination_len as f32;
+    // Widen the filter when downscaling, so that every source pixel contributes.
+    let filter_scale = scale.max(1.0);
+    let support = filter.support() * filter_scale;
+    (0..destination_len)
+        .map(|i| {
+            let center = (i as f32 + 0.5) * scale;
+            if filter == ImageFilter::Nearest {
+                return Contribution {
+                    start: (center as usize).min(source_len as usize - 1),
+                    weights: vec![1.0],
+                };
+            }
+            let start = ((center - support).floor().max(0.0) as usize).min(source_len as usize - 1);
+            let end = ((center + support).ceil() as usize).clamp(start + 1, source_len as usize);
+            let mut weights: Vec<f32> = (start..end)
+                .map(|j| filter.weight((j as f32 + 0.5 - center) / filter_scale))
+                .collect();
+            let sum: f32 = weights.iter().sum();
+            if sum != 0.0 {
+                weights.iter_mut().for_each(|weight| *weight /= sum);
+            }
+            Contribution { start, weights }
+        })
+        .collect()
+}
+
+/// Resamples premultiplied linear `pixels` of `size` to `new_size`, one axis after the other.
+fn resample_pixels(
+    pixels: &[Vec4],
+    size: UVec2,
+    new_size: UVec2,
+    filter: ImageFilter,
+) -> Vec<Vec4> {
+    let (width, new_width, new_height) =
+        (size.x as usize, new_size.x as usize, new_size.y as usize);
+
+    let horizontal = contributions(size.x, new_size.x, filter);
+    let mut rows = Vec::with_capacity(new_width * size.y as usize);
+    for y in 0..size.y as usize {
+        let row = &pixels[y * width..(y + 1) * width];
+        rows.extend(horizontal.iter().map(|contribution| {
+            contribution
+                .weights
+                .iter()
+                .zip(&row[contribution.start..])
+                .map(|(&weight, &pixel)| pixel * weight)
+                .sum::<Vec4>()
+        }));
+    }
+
+    let vertical = contributions(size.y, new_size.y, filter);
+    let mut result = Vec::with_capacity(new_width * new_height);
+    for contribution in &vertical {
+        result.extend((0..new_width).map(|x| {
+            contribution
+                .weights
+                .iter()
+                .enumerate()
+                .map(|(i, &weight)| rows[(contribution.start + i) * new_width + x] * weight)
+                .sum::<Vec4>()
+        }));
+    }
+    result
+}
+
+impl Image {
+    /// Returns the size of a pixel in bytes, or an error if the pixels can't be edited on the CPU.
+    fn editable_pixel_size(&self) -> Result<usize, TextureAccessError> {
+        let format = self.texture_descriptor.format;
+        if format.block_dimensions() != (1, 1) {
+            return Err(TextureAccessError::CompressedTextureFormat(format));
+        }
+        format
+            .block_copy_size(None)
+            .map(|size| size as usize)
+            .ok_or(TextureAccessError::UnsupportedTextureFormat(format))
+    }
+
+    /// Checks that this image is a 2D image with a single layer, and can be edited on the CPU.
+    fn check_editable_2d(&self) -> Result<usize, TextureAccessError> {
+        let pixel_size = self.editable_pixel_size()?;
+        if self.texture_descriptor.dimension != TextureDimension::D2
+            || self.texture_descriptor.size.depth_or_array_layers != 1
+        {
+            return Err(TextureAccessError::WrongDimension);
+        }
+        Ok(pixel_size)
+    }
+
+    /// Creates an empty image of `size` with the same format and settings as this one.
+    fn empty_like(&self, size: UVec2) -> Result<Image, TextureAccessError> {
+        let pixel_size = self.editable_pixel_size()?;
+        let mut texture_descriptor = self.texture_descriptor.clone();
+        texture_descriptor.size = Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        };
+        texture_descriptor.mip_level_count = 1;
+        Ok(Image {
+            data: vec![0; size.x as usize * size.y as usize * pixel_size],
+            texture_descriptor,
+            sampler: self.sampler.clone(),
+            texture_view_descriptor: self.texture_view_descriptor.clone(),
+            asset_usage: self.asset_usage,
+        })
+    }
+
+    /// Reads the pixels of the first mip level as linear colors with premultiplied alpha.
+    fn premultiplied_pixels(&self) -> Result<Vec<Vec4>, TextureAccessError> {
+        let mut pixels = Vec::with_capacity((self.width() * self.height()) as usize);
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let color = LinearRgba::from(self.get_color_at_internal(UVec3::new(x, y, 0))?);
+                let alpha = color.alpha;
+                pixels.push(color.to_vec4() * Vec4::new(alpha, alpha, alpha, 1.0));
+            }
+        }
+        Ok(pixels)
+    }
+
+    /// Writes linear colors with premultiplied alpha to the pixels of this image.
+    fn set_premultiplied_pixels(&mut self, pixels: &[Vec4]) -> Result<(), TextureAccessError> {
+        let width = self.width();
+        for (i, pixel) in pixels.iter().enumerate() {
+            let alpha = pixel.w.clamp(0.0, 1.0);
+            let color = if alpha > 0.0 {
+                pixel.truncate().extend(alpha) / Vec4::new(alpha, alpha, alpha, 1.0)
+            } else {
+                Vec4::ZERO
+            };
+            let coords = UVec3::new(i as u32 % width, i as u32 / width, 0);
+            self.set_color_at_internal(coords, Color::LinearRgba(LinearRgba::from_vec4(color)))?;
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of this 2D image resized to `size`, computing the new pixels with `filter`.
+    ///
+    /// Unlike [`Image::resize`], which only resizes the `data` buffer, this resamples the content
+    /// of the image. The pixels are filtered as linear colors with premultiplied alpha, so that
+    /// sRGB images don't darken and transparent pixels don't bleed into their neighbors. The
+    /// result has the same format as this image and no mipmaps.
+    ///
+    /// Returns an error if the image isn't a 2D image with a single layer, or if its format isn't
+    /// supported by [`Image::get_color_at`] and [`Image::set_color_at`]. Block-compressed images
+    /// have to be decoded first.
+    pub fn resample(&self, size: UVec2, filter: ImageFilter) -> Result<Image, TextureAccessError> {
+        self.check_editable_2d()?;
+        let size = size.max(UVec2::ONE);
+        let pixels = self.premultiplied_pixels()?;
+        let pixels = resample_pixels(&pixels, self.size(), size, filter);
+        let mut image = self.empty_like(size)?;
+        image.set_premultiplied_pixels(&pixels)?;
+        Ok(image)
+    }
+
+    /// Returns a copy of the `rect` region of this 2D image.
+    ///
+    /// The result has the same format as this image and no mipmaps. Returns an error if `rect`
+    /// isn't within the image, or for the same reasons as [`Image::blit`].
+    pub fn crop(&self, rect: URect) -> Result<Image, TextureAccessError> {
+        let mut image = self.empty_like(rect.size())?;
+        image.blit(self, rect, UVec2::ZERO)?;
+        Ok(image)
+    }
+
+    /// Copies the `source_rect` region of `source` to this 2D image, with its top left corner
+    /// at `position`.
+    ///
+    /// The pixels are copied as is if both images have the same format, or converted otherwise,
+    /// decoding and encoding sRGB as needed. Only the first mip level of this image is modified.
+    ///
+    /// Returns an error if the region isn't within both images, if either of them isn't a 2D
+    /// image with a single layer, or if a conversion between formats not supported by
+    /// [`Image::get_color_at`] and [`Image::set_color_at`] is needed. Block-compressed images
+    /// have to be decoded first.
+    pub fn blit(
+        &mut self,
+        source: &Image,
+        source_rect: URect,
+        position: UVec2,
+    ) -> Result<(), TextureAccessError> {
+        let pixel_size = self.check_editable_2d()?;
+        source.check_editable_2d()?;
+        let size = source_rect.size();
+        for (image, corner) in [(source, source_rect.max), (&*self, position + size)] {
+            if corner.x > image.width() || corner.y > image.height() {
+                return Err(TextureAccessError::OutOfBounds {
+                    x: corner.x,
+                    y: corner.y,
+                    z: 0,
+                });
+            }
+        }
+
+        if source.texture_descriptor.format == self.texture_descriptor.format {
+            let row_size = size.x as usize * pixel_size;
+            for y in 0..size.y {
+                let from = source
+                    .pixel_data_offset(UVec3::new(source_rect.min.x, source_rect.min.y + y, 0))
+                    .unwrap();
+                let to = self
+                    .pixel_data_offset(UVec3::new(position.x, position.y + y, 0))
+                    .unwrap();
+                self.data[to..to + row_size].copy_from_slice(&source.data[from..from + row_size]);
+            }
+            return Ok(());
+        }
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let color = source.get_color_at_internal(UVec3::new(
+                    source_rect.min.x + x,
+                    source_rect.min.y + y,
+                    0,
+                ))?;
+                self.set_color_at_internal(UVec3::new(position.x + x, position.y + y, 0), color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of this image converted to `format`.
+    ///
+    /// Unlike [`Image::convert`], this supports all the formats supported by
+    /// [`Image::get_color_at`] and [`Image::set_color_at`], and images of any dimension. The
+    /// colors are converted through [`Color`], so sRGB data is decoded and encoded as needed. The
+    /// result has no mipmaps.
+    ///
+    /// Block-compressed images have to be decoded first, and return an error.
+    pub fn try_convert(&self, format: TextureFormat) -> Result<Image, TextureAccessError> {
+        self.editable_pixel_size()?;
+        let mut image = Image {
+            texture_descriptor: self.texture_descriptor.clone(),
+            ..Default::default()
+        };
+        image.texture_descriptor.format = format;
+        image.texture_descriptor.mip_level_count = 1;
+        image.data = vec![0; self.texture_descriptor.size.volume() * image.editable_pixel_size()?];
+        image.sampler = self.sampler.clone();
+        image.texture_view_descriptor = self.texture_view_descriptor.clone();
+        image.asset_usage = self.asset_usage;
+
+        let size = self.texture_descriptor.size;
+        for z in 0..size.depth_or_array_layers {
+            for y in 0..size.height {
+                for x in 0..size.width {
+                    let coords = UVec3::new(x, y, z);
+                    image.set_color_at_internal(coords, self.get_color_at_internal(coords)?)?;
+                }
+            }
+        }
+        Ok(image)
+    }
+
+    /// Generates the full chain of mipmaps of this 2D image or 2D array image, down to 1×1
+    /// pixels, replacing the existing ones.
+    ///
+    /// Each level is resampled from the previous one with `filter`, see [`Image::resample`].
+    /// Returns an error if the image isn't a 2D image, or if its format isn't supported by
+    /// [`Image::get_color_at`] and [`Image::set_color_at`]. Block-compressed images have to be
+    /// decoded first.
+    pub fn generate_mipmaps(&mut self, filter: ImageFilter) -> Result<(), TextureAccessError> {
+        let pixel_size = self.editable_pixel_size()?;
+        if self.texture_descriptor.dimension != TextureDimension::D2 {
+            return Err(TextureAccessError::WrongDimension);
+        }
+
+        let size = self.texture_descriptor.size;
+        let layers = size.depth_or_array_layers as usize;
+        let level_count = 32 - size.width.max(size.height).leading_zeros();
+        // The data holds all the mip levels of each layer, one layer after the other.
+        let layer_stride = self.data.len() / layers;
+        let level_size = size.width as usize * size.height as usize * pixel_size;
+
+        let mut data = Vec::with_capacity(self.data.len() * 4 / 3);
+        for layer in 0..layers {
+            let mut level = self.empty_like(self.size())?;
+            level
+                .data
+                .copy_from_slice(&self.data[layer * layer_stride..][..level_size]);
+            data.extend_from_slice(&level.data);
+            for _ in 1..level_count {
+                let size = (level.size() / 2).max(UVec2::ONE);
+                level = level.resample(size, filter)?;
+                data.extend_from_slice(&level.data);
+            }
+        }
+
+        self.data = data;
+        self.texture_descriptor.mip_level_count = level_count;
+        if let Some(descriptor) = self.texture_view_descriptor.as_mut() {
+            descriptor.mip_level_count = None;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::RenderAssetUsages;
+    use wgpu::{TextureViewDescriptor, TextureViewDimension};
+
+    fn image(size: UVec2, format: TextureFormat) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &vec![0; format.block_copy_size(None).unwrap() as usize],
+            format,
+            RenderAssetUsages::all(),
+        )
+    }
+
+    #[test]
+    fn resample_averages_in_linear_space() {
+        let mut checker = image(UVec2::new(2, 2), TextureFormat::Rgba32Float);
+        for (x, y) in [(0, 0), (1, 1)] {
+            checker.set_color_at(x, y, Color::WHITE).unwrap();
+        }
+        for (x, y) in [(1, 0), (0, 1)] {
+            checker.set_color_at(x, y, Color::BLACK).unwrap();
+        }
+
+        let resampled = checker.resample(UVec2::ONE, ImageFilter::Linear).unwrap();
+        assert_eq!(resampled.size(), UVec2::ONE);
+        let color = LinearRgba::from(resampled.get_color_at(0, 0).unwrap());
+        assert!((color.red - 0.5).abs() < 1e-5);
+        assert!((color.alpha - 1.0).abs() < 1e-5);
+
+        // Upscaling with the nearest filter keeps the pixels.
+        let upscaled = checker
+            .resample(UVec2::new(4, 4), ImageFilter::Nearest)
+            .unwrap();
+        let color_at = |x, y| LinearRgba::from(upscaled.get_color_at(x, y).unwrap());
+        assert_eq!(color_at(1, 1), LinearRgba::WHITE);
+        assert_eq!(color_at(2, 1), LinearRgba::BLACK);
+    }
+
+    #[test]
+    fn crop_and_blit() {
+        let mut source = image(UVec2::new(4, 4), TextureFormat::Rgba8Unorm);
+        source
+            .set_color_at(2, 1, Color::linear_rgb(1.0, 0.0, 0.0))
+            .unwrap();
+
+        let cropped = source.crop(URect::new(2, 1, 4, 3)).unwrap();
+        assert_eq!(cropped.size(), UVec2::new(2, 2));
+        assert_eq!(
+            cropped.get_color_at(0, 0).unwrap(),
+            Color::linear_rgb(1.0, 0.0, 0.0)
+        );
+
+        // Blitting between formats converts the pixels.
+        let mut destination = image(UVec2::new(3, 3), TextureFormat::Rgba32Float);
+        destination
+            .blit(&cropped, URect::new(0, 0, 2, 2), UVec2::new(1, 1))
+            .unwrap();
+        assert_eq!(
+            destination.get_color_at(1, 1).unwrap(),
+            Color::linear_rgb(1.0, 0.0, 0.0)
+        );
+        assert!(matches!(
+            destination.blit(&cropped, URect::new(0, 0, 2, 2), UVec2::new(2, 2)),
+            Err(TextureAccessError::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn generate_mipmaps_of_array() {
+        let mut image = image(UVec2::new(4, 2), TextureFormat::R8Unorm);
+        image.reinterpret_stacked_2d_as_array(2);
+        image.data = vec![255, 255, 255, 255, 0, 0, 0, 0];
+        image.generate_mipmaps(ImageFilter::Linear).unwrap();
+
+        assert_eq!(image.texture_descriptor.mip_level_count, 3);
+        // Each layer holds 4×1, 2×1 and 1×1 pixels.
+        assert_eq!(
+            image.data,
+            vec![255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn convert_keeps_the_view() {
+        let mut cube = image(UVec2::new(1, 6), TextureFormat::Rgba8Unorm);
+        cube.reinterpret_stacked_2d_as_array(6);
+        cube.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        });
+
+        let converted = cube.try_convert(TextureFormat::Rgba32Float).unwrap();
+        assert_eq!(
+            converted.texture_view_descriptor.unwrap().dimension,
+            Some(TextureViewDimension::Cube)
+        );
+    }
+
+    #[test]
+    fn compressed_formats_are_rejected() {
+        let image = Image {
+            texture_descriptor: wgpu_types::TextureDescriptor {
+                format: TextureFormat::Bc1RgbaUnorm,
+                ..Image::default().texture_descriptor
+            },
+            ..Default::default()
+        };
+        assert!(matches!(
+            image.resample(UVec2::ONE, ImageFilter::Linear),
+            Err(TextureAccessError::CompressedTextureFormat(_))
+        ));
+    }
+}
@@ -25,10 +25,12 @@ pub mod gamepad;
 pub mod gestures;
 pub mod keyboard;
 pub mod mouse;
+mod test_app;
 pub mod touch;
 
 pub use axis::*;
 pub use button_input::*;
+pub use test_app::*;
 
 /// The input prelude.
 ///
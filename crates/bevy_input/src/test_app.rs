@@ -0,0 +1,123 @@
+use crate::{
+    keyboard::{Key, KeyCode, KeyboardInput, NativeKey},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    ButtonState,
+};
+use bevy_app::TestApp;
+use bevy_ecs::entity::Entity;
+use bevy_math::Vec2;
+
+/// Extends [`TestApp`] to inject input events, as if they were sent by the windowing backend.
+///
+/// The events are read by the [`InputPlugin`](crate::InputPlugin) during the next
+/// [`step`](TestApp::step), after which the [`ButtonInput`](crate::ButtonInput) resources
+/// reflect them. The events are sent for [`Entity::PLACEHOLDER`] instead of a window; use
+/// [`TestApp::send_event`] to send events for a specific window.
+pub trait TestAppInputExt {
+    /// Sends a [`KeyboardInput`] pressing `key_code`.
+    fn press_key(&mut self, key_code: KeyCode) -> &mut Self;
+
+    /// Sends a [`KeyboardInput`] releasing `key_code`.
+    fn release_key(&mut self, key_code: KeyCode) -> &mut Self;
+
+    /// Sends a [`MouseButtonInput`] pressing `button`.
+    fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self;
+
+    /// Sends a [`MouseButtonInput`] releasing `button`.
+    fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self;
+
+    /// Sends a [`MouseMotion`] moving the mouse by `delta`.
+    fn move_mouse(&mut self, delta: Vec2) -> &mut Self;
+
+    /// Sends a [`MouseWheel`] scrolling by `delta` lines.
+    fn scroll_mouse(&mut self, delta: Vec2) -> &mut Self;
+}
+
+impl TestAppInputExt for TestApp {
+    fn press_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_event(keyboard_input(key_code, ButtonState::Pressed))
+    }
+
+    fn release_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_event(keyboard_input(key_code, ButtonState::Released))
+    }
+
+    fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_event(MouseButtonInput {
+            button,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_event(MouseButtonInput {
+            button,
+            state: ButtonState::Released,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    fn move_mouse(&mut self, delta: Vec2) -> &mut Self {
+        self.send_event(MouseMotion { delta })
+    }
+
+    fn scroll_mouse(&mut self, delta: Vec2) -> &mut Self {
+        self.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: delta.x,
+            y: delta.y,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+}
+
+fn keyboard_input(key_code: KeyCode, state: ButtonState) -> KeyboardInput {
+    KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        keyboard::KeyCode,
+        mouse::{AccumulatedMouseMotion, MouseButton},
+        ButtonInput, InputPlugin, TestAppInputExt,
+    };
+    use bevy_app::TestApp;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn inject_input() {
+        let mut app = TestApp::new(InputPlugin);
+        app.press_key(KeyCode::Space)
+            .press_mouse_button(MouseButton::Left)
+            .move_mouse(Vec2::new(3.0, 4.0))
+            .step();
+        assert!(app
+            .resource::<ButtonInput<KeyCode>>()
+            .just_pressed(KeyCode::Space));
+        assert!(app
+            .resource::<ButtonInput<MouseButton>>()
+            .pressed(MouseButton::Left));
+        assert_eq!(
+            app.resource::<AccumulatedMouseMotion>().delta,
+            Vec2::new(3.0, 4.0)
+        );
+
+        app.step();
+        let keys = app.resource::<ButtonInput<KeyCode>>();
+        assert!(keys.pressed(KeyCode::Space) && !keys.just_pressed(KeyCode::Space));
+
+        app.release_key(KeyCode::Space).step();
+        assert!(app
+            .resource::<ButtonInput<KeyCode>>()
+            .just_released(KeyCode::Space));
+    }
+}
@@ -0,0 +1,285 @@
+use alloc::{collections::VecDeque, string::String, sync::Arc, vec::Vec};
+use core::fmt::{self, Write as _};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use bevy_app::App;
+use bevy_ecs::resource::Resource;
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+use crate::BoxedLayer;
+
+/// The default number of logs kept by a [`LogCapture`].
+pub const DEFAULT_LOG_CAPTURE_CAPACITY: usize = 1000;
+
+/// Captures the logs of the app in a bounded ring buffer, to display them in an in-game console.
+///
+/// Enable it with [`log_capture_layer`] as the [`LogPlugin::custom_layer`]:
+/// ```no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup, Update};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_log::{log_capture_layer, LogCapture, LogPlugin};
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins.set(LogPlugin {
+///             custom_layer: log_capture_layer,
+///             ..Default::default()
+///         }))
+///         .add_systems(Update, print_console)
+///         .run();
+/// }
+///
+/// fn print_console(capture: Res<LogCapture>, mut last_seen: Local<Option<u64>>) {
+///     for log in capture.logs_after(*last_seen) {
+///         println!("[{}] {}: {}", log.level, log.target, log.message);
+///         *last_seen = Some(log.id);
+///     }
+/// }
+/// ```
+///
+/// Only the logs passing the [`LogPlugin::filter`] reach the capture, which then applies its own
+/// [`LogCaptureFilter`], configurable at runtime. When the buffer is full, the oldest logs are
+/// dropped.
+///
+/// The resource can be cloned to be shared with other threads: all clones access the same
+/// buffer.
+///
+/// [`LogPlugin::custom_layer`]: crate::LogPlugin::custom_layer
+/// [`LogPlugin::filter`]: crate::LogPlugin::filter
+#[derive(Resource, Clone)]
+pub struct LogCapture {
+    state: Arc<Mutex<LogCaptureState>>,
+}
+
+struct LogCaptureState {
+    logs: VecDeque<CapturedLog>,
+    capacity: usize,
+    filter: LogCaptureFilter,
+    next_id: u64,
+}
+
+/// A log captured by a [`LogCapture`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedLog {
+    /// The id of the log, increasing with each captured log.
+    pub id: u64,
+    /// The level of the log.
+    pub level: Level,
+    /// The target of the log, usually the module path where it was emitted.
+    pub target: String,
+    /// The message of the log, followed by its other fields as `name=value`.
+    pub message: String,
+}
+
+/// Selects the logs captured by a [`LogCapture`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogCaptureFilter {
+    /// The most verbose level captured, for the targets without a specific level.
+    pub level: Level,
+    /// The most verbose level captured for the targets starting with the given prefix, like
+    /// `("bevy_render", Level::WARN)`. The longest matching prefix is used.
+    pub targets: Vec<(String, Level)>,
+}
+
+impl Default for LogCaptureFilter {
+    fn default() -> Self {
+        Self {
+            level: Level::INFO,
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl LogCaptureFilter {
+    /// Returns `true` if a log with the given `target` and `level` is captured.
+    pub fn is_enabled(&self, target: &str, level: &Level) -> bool {
+        let max_level = self
+            .targets
+            .iter()
+            .filter(|(prefix, _)| target.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(&self.level, |(_, level)| level);
+        level <= max_level
+    }
+}
+
+impl Default for LogCapture {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_CAPTURE_CAPACITY)
+    }
+}
+
+impl LogCapture {
+    /// Creates a capture keeping the last `capacity` logs.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(LogCaptureState {
+                logs: VecDeque::with_capacity(capacity),
+                capacity,
+                filter: LogCaptureFilter::default(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, LogCaptureState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the maximum number of logs kept.
+    pub fn capacity(&self) -> usize {
+        self.state().capacity
+    }
+
+    /// Sets the maximum number of logs kept, dropping the oldest logs if there are more.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut state = self.state();
+        state.capacity = capacity;
+        let excess = state.logs.len().saturating_sub(capacity);
+        state.logs.drain(..excess);
+    }
+
+    /// Returns the filter selecting the captured logs.
+    pub fn filter(&self) -> LogCaptureFilter {
+        self.state().filter.clone()
+    }
+
+    /// Sets the filter selecting the captured logs. The logs already captured are kept.
+    pub fn set_filter(&self, filter: LogCaptureFilter) {
+        self.state().filter = filter;
+    }
+
+    /// Returns the captured logs, from the oldest to the most recent.
+    pub fn logs(&self) -> Vec<CapturedLog> {
+        self.state().logs.iter().cloned().collect()
+    }
+
+    /// Returns the captured logs more recent than the log with the id `after`, from the oldest
+    /// to the most recent, or all of them if `after` is `None`.
+    pub fn logs_after(&self, after: Option<u64>) -> Vec<CapturedLog> {
+        let state = self.state();
+        let start = after.map_or(0, |after| state.logs.partition_point(|log| log.id <= after));
+        state.logs.range(start..).cloned().collect()
+    }
+
+    /// Returns the number of captured logs.
+    pub fn len(&self) -> usize {
+        self.state().logs.len()
+    }
+
+    /// Returns `true` if no log is captured.
+    pub fn is_empty(&self) -> bool {
+        self.state().logs.is_empty()
+    }
+
+    /// Removes the captured logs.
+    pub fn clear(&self) {
+        self.state().logs.clear();
+    }
+
+    /// Captures a log, if it passes the filter.
+    pub fn push(&self, level: Level, target: &str, message: String) {
+        let mut state = self.state();
+        if state.capacity == 0 || !state.filter.is_enabled(target, &level) {
+            return;
+        }
+        if state.logs.len() >= state.capacity {
+            state.logs.pop_front();
+        }
+        let id = state.next_id;
+        state.next_id += 1;
+        state.logs.push_back(CapturedLog {
+            id,
+            level,
+            target: target.into(),
+            message,
+        });
+    }
+
+    /// Returns a [`Layer`] capturing the logs in this buffer.
+    pub fn layer(&self) -> LogCaptureLayer {
+        LogCaptureLayer {
+            capture: self.clone(),
+        }
+    }
+}
+
+/// A [`Layer`] capturing logs in a [`LogCapture`].
+pub struct LogCaptureLayer {
+    capture: LogCapture,
+}
+
+impl<S: Subscriber> Layer<S> for LogCaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        // bevy_render::renderer logs a `tracy.frame_mark` event every frame, which isn't
+        // worth displaying.
+        if metadata.fields().field("tracy.frame_mark").is_some()
+            || !self
+                .capture
+                .state()
+                .filter
+                .is_enabled(metadata.target(), metadata.level())
+        {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        self.capture
+            .push(*metadata.level(), metadata.target(), visitor.finish());
+    }
+}
+
+/// Formats the fields of an event, with the message first.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(mut self) -> String {
+        if !self.fields.is_empty() {
+            if !self.message.is_empty() {
+                self.message.push(' ');
+            }
+            self.message.push_str(&self.fields);
+        }
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={value:?}", field.name());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+}
+
+/// A [`LogPlugin::custom_layer`](crate::LogPlugin::custom_layer) capturing the logs in a
+/// [`LogCapture`] resource.
+///
+/// The resource is inserted in the app if it doesn't exist, with a capacity of
+/// [`DEFAULT_LOG_CAPTURE_CAPACITY`] logs.
+pub fn log_capture_layer(app: &mut App) -> Option<BoxedLayer> {
+    let capture = app.world_mut().get_resource_or_init::<LogCapture>().clone();
+    Some(Box::new(capture.layer()))
+}
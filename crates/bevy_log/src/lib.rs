@@ -22,6 +22,7 @@ use core::error::Error;
 
 #[cfg(target_os = "android")]
 mod android_tracing;
+mod capture;
 mod once;
 
 #[cfg(feature = "trace_tracy_memory")]
@@ -46,6 +47,7 @@ pub mod prelude {
 }
 
 pub use bevy_utils::once;
+pub use capture::*;
 pub use tracing::{
     self, debug, debug_span, error, error_span, info, info_span, trace, trace_span, warn,
     warn_span, Level,
@@ -0,0 +1,452 @@
+use alloc::vec::Vec;
+
+use super::{Aabb3d, BoundingVolume, IntersectsVolume, RayCast3d};
+use crate::Vec3A;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+/// Identifies a volume inserted in an [`AabbTree3d`].
+///
+/// Identifiers are reused once their volume is removed from the tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash)
+)]
+pub struct AabbTreeId(u32);
+
+/// A dynamic bounding volume hierarchy of [`Aabb3d`]s, for broad-phase spatial queries.
+///
+/// Each volume inserted in the tree is associated with some data of type `T`, like the entity it bounds.
+/// Volumes can be moved and removed at any time, and queries only visit the branches of the tree
+/// overlapping the queried region, instead of testing every volume.
+///
+/// To avoid restructuring the tree each time a volume moves by a small amount, the tree stores
+/// the volumes grown by a [margin](AabbTree3d::with_margin), and only moves them in the tree once they
+/// leave this enlarged box. Queries still test the exact volumes.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_math::{bounding::{Aabb3d, AabbTree3d, BoundingSphere, RayCast3d}, Dir3A, Vec3A};
+/// let mut tree = AabbTree3d::default();
+/// let a = tree.insert(Aabb3d::new(Vec3A::ZERO, Vec3A::ONE), "a");
+/// let b = tree.insert(Aabb3d::new(Vec3A::new(10.0, 0.0, 0.0), Vec3A::ONE), "b");
+///
+/// let near_origin: Vec<_> = tree.query_volume(&BoundingSphere::new(Vec3A::ZERO, 2.0)).collect();
+/// assert_eq!(near_origin, [(a, &"a")]);
+///
+/// let ray = RayCast3d::new(Vec3A::new(20.0, 0.0, 0.0), Dir3A::NEG_X, 100.0);
+/// assert_eq!(tree.cast_ray(&ray), Some((b, &"b", 9.0)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct AabbTree3d<T> {
+    nodes: Vec<Node<T>>,
+    root: Option<u32>,
+    /// The head of the list of unused nodes.
+    free: Option<u32>,
+    len: usize,
+    margin: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    /// The bounds of the subtree, or the enlarged volume for leaves.
+    aabb: Aabb3d,
+    parent: Option<u32>,
+    kind: NodeKind<T>,
+}
+
+#[derive(Clone, Debug)]
+enum NodeKind<T> {
+    Leaf { aabb: Aabb3d, data: T },
+    Branch([u32; 2]),
+    Free { next: Option<u32> },
+}
+
+impl<T> Default for AabbTree3d<T> {
+    fn default() -> Self {
+        Self::with_margin(0.1)
+    }
+}
+
+impl<T> AabbTree3d<T> {
+    /// Creates an empty tree storing volumes grown by `margin` in each direction.
+    ///
+    /// A larger margin makes [`update`](Self::update) cheaper for moving volumes,
+    /// at the cost of less precise pruning during queries.
+    pub fn with_margin(margin: f32) -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: None,
+            free: None,
+            len: 0,
+            margin,
+        }
+    }
+
+    /// Returns the number of volumes in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the tree contains no volumes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all volumes from the tree.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+        self.free = None;
+        self.len = 0;
+    }
+
+    /// Inserts `aabb` in the tree, associated with `data`, and returns its identifier.
+    pub fn insert(&mut self, aabb: Aabb3d, data: T) -> AabbTreeId {
+        let leaf = self.allocate(Node {
+            aabb: aabb.grow(Vec3A::splat(self.margin)),
+            parent: None,
+            kind: NodeKind::Leaf { aabb, data },
+        });
+        self.attach(leaf);
+        self.len += 1;
+        AabbTreeId(leaf)
+    }
+
+    /// Moves the volume `id` to `aabb`.
+    ///
+    /// Returns `false` if `id` isn't in the tree.
+    pub fn update(&mut self, id: AabbTreeId, aabb: Aabb3d) -> bool {
+        let Some(Node {
+            aabb: enlarged,
+            kind: NodeKind::Leaf { aabb: exact, .. },
+            ..
+        }) = self.nodes.get_mut(id.0 as usize)
+        else {
+            return false;
+        };
+        *exact = aabb;
+        if enlarged.contains(&aabb) {
+            return true;
+        }
+        *enlarged = aabb.grow(Vec3A::splat(self.margin));
+        self.detach(id.0);
+        self.attach(id.0);
+        true
+    }
+
+    /// Removes the volume `id` from the tree and returns its data.
+    pub fn remove(&mut self, id: AabbTreeId) -> Option<T> {
+        if !matches!(self.node(id.0), Some(NodeKind::Leaf { .. })) {
+            return None;
+        }
+        self.detach(id.0);
+        self.len -= 1;
+        match self.release(id.0) {
+            NodeKind::Leaf { data, .. } => Some(data),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the data associated with the volume `id`.
+    pub fn get(&self, id: AabbTreeId) -> Option<&T> {
+        match self.node(id.0) {
+            Some(NodeKind::Leaf { data, .. }) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the data associated with the volume `id`.
+    pub fn get_mut(&mut self, id: AabbTreeId) -> Option<&mut T> {
+        match self.nodes.get_mut(id.0 as usize).map(|node| &mut node.kind) {
+            Some(NodeKind::Leaf { data, .. }) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the volume `id`.
+    pub fn aabb(&self, id: AabbTreeId) -> Option<Aabb3d> {
+        match self.node(id.0) {
+            Some(NodeKind::Leaf { aabb, .. }) => Some(*aabb),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all volumes in the tree, in an unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (AabbTreeId, Aabb3d, &T)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| match &node.kind {
+                NodeKind::Leaf { aabb, data } => Some((AabbTreeId(index as u32), *aabb, data)),
+                _ => None,
+            })
+    }
+
+    /// Returns an iterator over the volumes for which `intersects` returns `true`.
+    ///
+    /// `intersects` must return `true` for any box containing a volume that should be returned,
+    /// so that the branches of the tree that can't contain matches are skipped.
+    pub fn query<'a>(
+        &'a self,
+        mut intersects: impl FnMut(&Aabb3d) -> bool + 'a,
+    ) -> impl Iterator<Item = (AabbTreeId, &'a T)> + 'a {
+        let mut stack: Vec<u32> = self.root.into_iter().collect();
+        core::iter::from_fn(move || {
+            while let Some(index) = stack.pop() {
+                let node = &self.nodes[index as usize];
+                if !intersects(&node.aabb) {
+                    continue;
+                }
+                match &node.kind {
+                    NodeKind::Leaf { aabb, data } => {
+                        if intersects(aabb) {
+                            return Some((AabbTreeId(index), data));
+                        }
+                    }
+                    NodeKind::Branch(children) => stack.extend(children),
+                    NodeKind::Free { .. } => unreachable!(),
+                }
+            }
+            None
+        })
+    }
+
+    /// Returns an iterator over the volumes intersecting `volume`.
+    ///
+    /// This can be used with an [`Aabb3d`], a [`BoundingSphere`](super::BoundingSphere),
+    /// a [`RayCast3d`] or an [`AabbCast3d`](super::AabbCast3d).
+    pub fn query_volume<'a, V: IntersectsVolume<Aabb3d>>(
+        &'a self,
+        volume: &'a V,
+    ) -> impl Iterator<Item = (AabbTreeId, &'a T)> + 'a {
+        self.query(|aabb| volume.intersects(aabb))
+    }
+
+    /// Returns the first volume hit by `ray`, along with the distance to the hit.
+    pub fn cast_ray(&self, ray: &RayCast3d) -> Option<(AabbTreeId, &T, f32)> {
+        let mut closest = None;
+        let mut max = ray.max;
+        let mut stack: Vec<u32> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            if !ray
+                .aabb_intersection_at(&node.aabb)
+                .is_some_and(|distance| distance <= max)
+            {
+                continue;
+            }
+            match &node.kind {
+                NodeKind::Leaf { aabb, data } => {
+                    if let Some(distance) = ray.aabb_intersection_at(aabb) {
+                        if distance <= max {
+                            max = distance;
+                            closest = Some((AabbTreeId(index), data, distance));
+                        }
+                    }
+                }
+                NodeKind::Branch(children) => stack.extend(children),
+                NodeKind::Free { .. } => unreachable!(),
+            }
+        }
+        closest
+    }
+
+    fn node(&self, index: u32) -> Option<&NodeKind<T>> {
+        self.nodes.get(index as usize).map(|node| &node.kind)
+    }
+
+    fn allocate(&mut self, node: Node<T>) -> u32 {
+        match self.free {
+            Some(index) => {
+                let NodeKind::Free { next } = self.nodes[index as usize].kind else {
+                    unreachable!();
+                };
+                self.free = next;
+                self.nodes[index as usize] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                (self.nodes.len() - 1) as u32
+            }
+        }
+    }
+
+    fn release(&mut self, index: u32) -> NodeKind<T> {
+        let kind = core::mem::replace(
+            &mut self.nodes[index as usize].kind,
+            NodeKind::Free { next: self.free },
+        );
+        self.free = Some(index);
+        kind
+    }
+
+    /// Inserts the detached `leaf` in the tree, next to the node whose bounds grow the least.
+    fn attach(&mut self, leaf: u32) {
+        let Some(mut sibling) = self.root else {
+            self.root = Some(leaf);
+            self.nodes[leaf as usize].parent = None;
+            return;
+        };
+
+        let leaf_aabb = self.nodes[leaf as usize].aabb;
+        while let NodeKind::Branch(children) = self.nodes[sibling as usize].kind {
+            let aabb = self.nodes[sibling as usize].aabb;
+            let merged_area = aabb.merge(&leaf_aabb).visible_area();
+            // Pairing the leaf with this node creates a new parent of this size,
+            // while descending grows this node's bounds.
+            let cost = merged_area;
+            let inherited = merged_area - aabb.visible_area();
+            let child_cost = |child: u32| {
+                let child = &self.nodes[child as usize];
+                let merged_area = child.aabb.merge(&leaf_aabb).visible_area();
+                match child.kind {
+                    NodeKind::Leaf { .. } => merged_area + inherited,
+                    _ => merged_area - child.aabb.visible_area() + inherited,
+                }
+            };
+            let costs = children.map(child_cost);
+            if cost < costs[0] && cost < costs[1] {
+                break;
+            }
+            sibling = if costs[0] <= costs[1] {
+                children[0]
+            } else {
+                children[1]
+            };
+        }
+
+        let old_parent = self.nodes[sibling as usize].parent;
+        let parent = self.allocate(Node {
+            aabb: self.nodes[sibling as usize].aabb.merge(&leaf_aabb),
+            parent: old_parent,
+            kind: NodeKind::Branch([sibling, leaf]),
+        });
+        self.nodes[sibling as usize].parent = Some(parent);
+        self.nodes[leaf as usize].parent = Some(parent);
+        match old_parent {
+            Some(old_parent) => {
+                self.replace_child(old_parent, sibling, parent);
+                self.refit(old_parent);
+            }
+            None => self.root = Some(parent),
+        }
+    }
+
+    /// Removes `leaf` from the tree structure without releasing its node.
+    fn detach(&mut self, leaf: u32) {
+        let Some(parent) = self.nodes[leaf as usize].parent else {
+            self.root = None;
+            return;
+        };
+        let NodeKind::Branch(children) = self.nodes[parent as usize].kind else {
+            unreachable!();
+        };
+        let sibling = if children[0] == leaf {
+            children[1]
+        } else {
+            children[0]
+        };
+
+        let grandparent = self.nodes[parent as usize].parent;
+        self.nodes[sibling as usize].parent = grandparent;
+        match grandparent {
+            Some(grandparent) => {
+                self.replace_child(grandparent, parent, sibling);
+                self.refit(grandparent);
+            }
+            None => self.root = Some(sibling),
+        }
+        self.release(parent);
+        self.nodes[leaf as usize].parent = None;
+    }
+
+    fn replace_child(&mut self, parent: u32, old: u32, new: u32) {
+        if let NodeKind::Branch(children) = &mut self.nodes[parent as usize].kind {
+            for child in children {
+                if *child == old {
+                    *child = new;
+                }
+            }
+        }
+    }
+
+    /// Recomputes the bounds of `index` and its ancestors.
+    fn refit(&mut self, index: u32) {
+        let mut current = Some(index);
+        while let Some(index) = current {
+            let node = &self.nodes[index as usize];
+            let NodeKind::Branch([a, b]) = node.kind else {
+                unreachable!();
+            };
+            let parent = node.parent;
+            self.nodes[index as usize].aabb = self.nodes[a as usize]
+                .aabb
+                .merge(&self.nodes[b as usize].aabb);
+            current = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bounding::BoundingSphere, Dir3A};
+
+    fn unit_box(x: f32) -> Aabb3d {
+        Aabb3d::new(Vec3A::new(x, 0.0, 0.0), Vec3A::splat(0.5))
+    }
+
+    #[test]
+    fn insert_update_remove() {
+        let mut tree = AabbTree3d::default();
+        let ids: Vec<_> = (0..32)
+            .map(|i| tree.insert(unit_box(i as f32 * 2.0), i))
+            .collect();
+        assert_eq!(tree.len(), 32);
+
+        let sphere = BoundingSphere::new(Vec3A::new(10.0, 0.0, 0.0), 1.0);
+        let mut found: Vec<_> = tree.query_volume(&sphere).map(|(_, i)| *i).collect();
+        found.sort();
+        assert_eq!(found, [5]);
+
+        // Move volume 0 next to volume 5.
+        assert!(tree.update(ids[0], unit_box(10.5)));
+        let mut found: Vec<_> = tree.query_volume(&sphere).map(|(_, i)| *i).collect();
+        found.sort();
+        assert_eq!(found, [0, 5]);
+
+        assert_eq!(tree.remove(ids[5]), Some(5));
+        assert_eq!(tree.remove(ids[5]), None);
+        assert!(!tree.update(ids[5], unit_box(0.0)));
+        let found: Vec<_> = tree.query_volume(&sphere).map(|(_, i)| *i).collect();
+        assert_eq!(found, [0]);
+        assert_eq!(tree.len(), 31);
+        assert_eq!(tree.iter().count(), 31);
+
+        for id in ids {
+            tree.remove(id);
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.query(|_| true).count(), 0);
+    }
+
+    #[test]
+    fn cast_ray_returns_closest_hit() {
+        let mut tree = AabbTree3d::default();
+        for i in 0..16 {
+            tree.insert(unit_box(i as f32 * 2.0), i);
+        }
+        let ray = RayCast3d::new(Vec3A::new(-10.0, 0.0, 0.0), Dir3A::X, 100.0);
+        let (_, data, distance) = tree.cast_ray(&ray).unwrap();
+        assert_eq!((*data, distance), (0, 9.5));
+
+        let ray = RayCast3d::new(Vec3A::new(-10.0, 0.0, 0.0), Dir3A::X, 5.0);
+        assert!(tree.cast_ray(&ray).is_none());
+    }
+}
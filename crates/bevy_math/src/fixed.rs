@@ -0,0 +1,742 @@
+//! Fixed-point numbers and vectors, for computations that must give the same results on every platform.
+
+use core::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::{StableInterpolate, Vec2, Vec3, VectorSpace};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The number of fractional bits of a [`Fixed`].
+const FRAC_BITS: u32 = 32;
+
+/// A signed Q32.32 fixed-point number: 32 integer bits and 32 fractional bits, stored in an [`i64`].
+///
+/// Floating point operations may give slightly different results depending on the platform, compiler
+/// optimizations or instruction set, which breaks simulations that must stay in sync across machines,
+/// like lockstep multiplayer. Fixed-point arithmetic only uses integer operations, so its results are
+/// exactly reproducible.
+///
+/// A `Fixed` represents values in `[-2^31, 2^31)` with a precision of `2^-32`. Multiplication and division
+/// round toward negative infinity, and like integer arithmetic, operations that overflow panic in debug builds.
+///
+/// Converting from floats is deterministic, so floats can be used to describe constants and assets,
+/// as long as the simulation itself only uses fixed-point operations.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_math::Fixed;
+/// let speed = Fixed::from_int(3) / Fixed::from_int(2);
+/// let position = Fixed::from_f32(10.25) + speed * Fixed::from_int(2);
+/// assert_eq!(position, Fixed::from_f32(13.25));
+/// assert_eq!(position.to_f32(), 13.25);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+#[doc(alias = "fixed_point")]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// Zero.
+    pub const ZERO: Self = Self(0);
+    /// One.
+    pub const ONE: Self = Self(1 << FRAC_BITS);
+    /// Negative one.
+    pub const NEG_ONE: Self = Self(-1 << FRAC_BITS);
+    /// One half.
+    pub const HALF: Self = Self(1 << (FRAC_BITS - 1));
+    /// The smallest positive value, `2^-32`.
+    pub const EPSILON: Self = Self(1);
+    /// The smallest value, `-2^31`.
+    pub const MIN: Self = Self(i64::MIN);
+    /// The largest value, `2^31 - 2^-32`.
+    pub const MAX: Self = Self(i64::MAX);
+
+    /// Creates a `Fixed` from its raw representation, the value multiplied by `2^32`.
+    #[inline]
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw representation of this value, the value multiplied by `2^32`.
+    #[inline]
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Creates a `Fixed` from an integer.
+    #[inline]
+    pub const fn from_int(value: i32) -> Self {
+        Self((value as i64) << FRAC_BITS)
+    }
+
+    /// Creates a `Fixed` from an [`f32`], rounding toward zero.
+    ///
+    /// Values out of range saturate to [`Fixed::MIN`] and [`Fixed::MAX`], and `NaN` gives zero.
+    #[inline]
+    pub fn from_f32(value: f32) -> Self {
+        Self::from_f64(f64::from(value))
+    }
+
+    /// Creates a `Fixed` from an [`f64`], rounding toward zero.
+    ///
+    /// Values out of range saturate to [`Fixed::MIN`] and [`Fixed::MAX`], and `NaN` gives zero.
+    #[inline]
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * (1u64 << FRAC_BITS) as f64) as i64)
+    }
+
+    /// Converts this value to the nearest [`f32`].
+    #[inline]
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Converts this value to the nearest [`f64`].
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1u64 << FRAC_BITS) as f64
+    }
+
+    /// Returns the integer part of this value, rounding toward negative infinity.
+    #[inline]
+    pub const fn to_int(self) -> i32 {
+        (self.0 >> FRAC_BITS) as i32
+    }
+
+    /// Returns the absolute value of this value.
+    #[inline]
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Returns `-1`, `0` or `1` depending on the sign of this value.
+    #[inline]
+    pub const fn signum(self) -> Self {
+        Self::from_int(self.0.signum() as i32)
+    }
+
+    /// Returns the largest integer less than or equal to this value.
+    #[inline]
+    pub const fn floor(self) -> Self {
+        Self(self.0 & !(Self::ONE.0 - 1))
+    }
+
+    /// Returns the smallest integer greater than or equal to this value.
+    #[inline]
+    pub const fn ceil(self) -> Self {
+        Self(self.0 + (Self::ONE.0 - 1)).floor()
+    }
+
+    /// Returns the nearest integer to this value, rounding half-way cases toward positive infinity.
+    #[inline]
+    pub const fn round(self) -> Self {
+        Self(self.0 + Self::HALF.0).floor()
+    }
+
+    /// Returns the fractional part of this value, `self - self.floor()`, which is never negative.
+    #[inline]
+    pub const fn fract(self) -> Self {
+        Self(self.0 & (Self::ONE.0 - 1))
+    }
+
+    /// Returns the minimum of two values.
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+
+    /// Returns the maximum of two values.
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+
+    /// Restricts this value to the interval `[min, max]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`.
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Ord::clamp(self, min, max)
+    }
+
+    /// Returns the square root of this value, rounded toward zero.
+    ///
+    /// Negative values give zero.
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        // sqrt(x * 2^32) * 2^16 == sqrt(x) * 2^32
+        Self(isqrt((self.0 as u128) << FRAC_BITS) as i64)
+    }
+
+    /// Linearly interpolates between `self` and `rhs` using the parameter `t`.
+    ///
+    /// `t` is not clamped, so values outside of `[0, 1]` extrapolate.
+    #[inline]
+    pub fn lerp(self, rhs: Self, t: Self) -> Self {
+        self + (rhs - self) * t
+    }
+}
+
+/// Returns the square root of `value`, rounded down.
+fn isqrt(value: u128) -> u128 {
+    let mut result = 0;
+    let mut remainder = value;
+    // The largest power of four not greater than `value`.
+    let mut bit = 1 << ((127 - value.leading_zeros()) & !1);
+    while bit != 0 {
+        if remainder >= result + bit {
+            remainder -= result + bit;
+            result = (result >> 1) + bit;
+        } else {
+            result >>= 1;
+        }
+        bit >>= 2;
+    }
+    result
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl From<i32> for Fixed {
+    #[inline]
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl From<Fixed> for f32 {
+    #[inline]
+    fn from(value: Fixed) -> Self {
+        value.to_f32()
+    }
+}
+
+impl From<Fixed> for f64 {
+    #[inline]
+    fn from(value: Fixed) -> Self {
+        value.to_f64()
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let product = (self.0 as i128 * rhs.0 as i128) >> FRAC_BITS;
+        debug_assert!(
+            i64::try_from(product).is_ok(),
+            "attempt to multiply with overflow"
+        );
+        Self(product as i64)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        let dividend = (self.0 as i128) << FRAC_BITS;
+        let divisor = rhs.0 as i128;
+        let mut quotient = dividend / divisor;
+        // Integer division rounds toward zero.
+        if dividend % divisor != 0 && (dividend < 0) != (divisor < 0) {
+            quotient -= 1;
+        }
+        debug_assert!(
+            i64::try_from(quotient).is_ok(),
+            "attempt to divide with overflow"
+        );
+        Self(quotient as i64)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fixed {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fixed {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Fixed {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Mul<f32> for Fixed {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: f32) -> Self {
+        self * Self::from_f32(rhs)
+    }
+}
+
+impl Div<f32> for Fixed {
+    type Output = Self;
+    #[inline]
+    fn div(self, rhs: f32) -> Self {
+        self / Self::from_f32(rhs)
+    }
+}
+
+impl Sum for Fixed {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl VectorSpace for Fixed {
+    const ZERO: Self = Fixed::ZERO;
+
+    #[inline]
+    fn lerp(self, rhs: Self, t: f32) -> Self {
+        Fixed::lerp(self, rhs, Fixed::from_f32(t))
+    }
+}
+
+impl StableInterpolate for Fixed {
+    #[inline]
+    fn interpolate_stable(&self, other: &Self, t: f32) -> Self {
+        VectorSpace::lerp(*self, *other, t)
+    }
+}
+
+/// Implements the component-wise operations shared by the fixed-point vectors.
+macro_rules! impl_fixed_vec {
+    ($Vec:ident, $FloatVec:ident, $($field:ident),+) => {
+        impl $Vec {
+            /// All zeroes.
+            pub const ZERO: Self = Self::splat(Fixed::ZERO);
+            /// All ones.
+            pub const ONE: Self = Self::splat(Fixed::ONE);
+
+            /// Creates a vector with all elements set to `value`.
+            #[inline]
+            pub const fn splat(value: Fixed) -> Self {
+                Self { $($field: value),+ }
+            }
+
+            /// Creates a vector from a floating point vector, rounding each element toward zero.
+            ///
+            /// See [`Fixed::from_f32`].
+            #[inline]
+            pub fn from_vec(value: $FloatVec) -> Self {
+                Self { $($field: Fixed::from_f32(value.$field)),+ }
+            }
+
+            /// Converts this vector to the nearest floating point vector.
+            #[inline]
+            pub fn to_vec(self) -> $FloatVec {
+                $FloatVec::new($(self.$field.to_f32()),+)
+            }
+
+            /// Computes the dot product of `self` and `rhs`.
+            #[inline]
+            pub fn dot(self, rhs: Self) -> Fixed {
+                Fixed::ZERO $(+ self.$field * rhs.$field)+
+            }
+
+            /// Computes the squared length of this vector.
+            #[inline]
+            pub fn length_squared(self) -> Fixed {
+                self.dot(self)
+            }
+
+            /// Computes the length of this vector.
+            #[inline]
+            pub fn length(self) -> Fixed {
+                self.length_squared().sqrt()
+            }
+
+            /// Computes the distance between `self` and `rhs`.
+            #[inline]
+            pub fn distance(self, rhs: Self) -> Fixed {
+                (self - rhs).length()
+            }
+
+            /// Returns this vector scaled to a length of one, or zero if its length is zero.
+            #[inline]
+            pub fn normalize_or_zero(self) -> Self {
+                let length = self.length();
+                if length == Fixed::ZERO {
+                    Self::ZERO
+                } else {
+                    self / length
+                }
+            }
+
+            /// Returns a vector containing the minimum of each element of `self` and `rhs`.
+            #[inline]
+            pub fn min(self, rhs: Self) -> Self {
+                Self { $($field: self.$field.min(rhs.$field)),+ }
+            }
+
+            /// Returns a vector containing the maximum of each element of `self` and `rhs`.
+            #[inline]
+            pub fn max(self, rhs: Self) -> Self {
+                Self { $($field: self.$field.max(rhs.$field)),+ }
+            }
+
+            /// Returns a vector containing the absolute value of each element of `self`.
+            #[inline]
+            pub fn abs(self) -> Self {
+                Self { $($field: self.$field.abs()),+ }
+            }
+
+            /// Linearly interpolates between `self` and `rhs` using the parameter `t`.
+            ///
+            /// `t` is not clamped, so values outside of `[0, 1]` extrapolate.
+            #[inline]
+            pub fn lerp(self, rhs: Self, t: Fixed) -> Self {
+                self + (rhs - self) * t
+            }
+        }
+
+        impl From<$FloatVec> for $Vec {
+            #[inline]
+            fn from(value: $FloatVec) -> Self {
+                Self::from_vec(value)
+            }
+        }
+
+        impl From<$Vec> for $FloatVec {
+            #[inline]
+            fn from(value: $Vec) -> Self {
+                value.to_vec()
+            }
+        }
+
+        impl Add for $Vec {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $Vec {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<Fixed> for $Vec {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: Fixed) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Mul<$Vec> for Fixed {
+            type Output = $Vec;
+            #[inline]
+            fn mul(self, rhs: $Vec) -> $Vec {
+                rhs * self
+            }
+        }
+
+        impl Div<Fixed> for $Vec {
+            type Output = Self;
+            #[inline]
+            fn div(self, rhs: Fixed) -> Self {
+                Self { $($field: self.$field / rhs),+ }
+            }
+        }
+
+        impl Mul<f32> for $Vec {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: f32) -> Self {
+                self * Fixed::from_f32(rhs)
+            }
+        }
+
+        impl Div<f32> for $Vec {
+            type Output = Self;
+            #[inline]
+            fn div(self, rhs: f32) -> Self {
+                self / Fixed::from_f32(rhs)
+            }
+        }
+
+        impl Neg for $Vec {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $Vec {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $Vec {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign<Fixed> for $Vec {
+            #[inline]
+            fn mul_assign(&mut self, rhs: Fixed) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign<Fixed> for $Vec {
+            #[inline]
+            fn div_assign(&mut self, rhs: Fixed) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl Sum for $Vec {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, Add::add)
+            }
+        }
+
+        impl VectorSpace for $Vec {
+            const ZERO: Self = $Vec::ZERO;
+
+            #[inline]
+            fn lerp(self, rhs: Self, t: f32) -> Self {
+                $Vec::lerp(self, rhs, Fixed::from_f32(t))
+            }
+        }
+
+        impl StableInterpolate for $Vec {
+            #[inline]
+            fn interpolate_stable(&self, other: &Self, t: f32) -> Self {
+                VectorSpace::lerp(*self, *other, t)
+            }
+        }
+    };
+}
+
+/// A 2D vector of [`Fixed`] elements.
+///
+/// This is the fixed-point counterpart of [`Vec2`], for computations that must be deterministic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct FixedVec2 {
+    /// The x element of the vector.
+    pub x: Fixed,
+    /// The y element of the vector.
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+    /// Creates a new vector.
+    #[inline]
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        Self { x, y }
+    }
+
+    /// Computes the perpendicular dot product of `self` and `rhs`, the z element of their 3D cross product.
+    #[inline]
+    pub fn perp_dot(self, rhs: Self) -> Fixed {
+        self.x * rhs.y - self.y * rhs.x
+    }
+}
+
+impl_fixed_vec!(FixedVec2, Vec2, x, y);
+
+/// A 3D vector of [`Fixed`] elements.
+///
+/// This is the fixed-point counterpart of [`Vec3`], for computations that must be deterministic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct FixedVec3 {
+    /// The x element of the vector.
+    pub x: Fixed,
+    /// The y element of the vector.
+    pub y: Fixed,
+    /// The z element of the vector.
+    pub z: Fixed,
+}
+
+impl FixedVec3 {
+    /// Creates a new vector.
+    #[inline]
+    pub const fn new(x: Fixed, y: Fixed, z: Fixed) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Computes the cross product of `self` and `rhs`.
+    #[inline]
+    pub fn cross(self, rhs: Self) -> Self {
+        Self {
+            x: self.y * rhs.z - self.z * rhs.y,
+            y: self.z * rhs.x - self.x * rhs.z,
+            z: self.x * rhs.y - self.y * rhs.x,
+        }
+    }
+}
+
+impl_fixed_vec!(FixedVec3, Vec3, x, y, z);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_arithmetic() {
+        let a = Fixed::from_f32(2.5);
+        let b = Fixed::from_int(-4);
+        assert_eq!(a + b, Fixed::from_f32(-1.5));
+        assert_eq!(a - b, Fixed::from_f32(6.5));
+        assert_eq!(a * b, Fixed::from_int(-10));
+        // -1.6, rounded toward negative infinity.
+        assert_eq!((b / a).to_bits(), -6871947674);
+        assert_eq!(
+            Fixed::ONE / Fixed::from_int(3) * Fixed::from_int(3),
+            Fixed::ONE - Fixed::from_bits(1)
+        );
+        assert_eq!(Fixed::from_int(9).sqrt(), Fixed::from_int(3));
+        assert_eq!(Fixed::from_int(2).sqrt().to_bits(), 6074000999);
+        assert_eq!(Fixed::from_int(-3).sqrt(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn fixed_rounding() {
+        let value = Fixed::from_f32(-1.25);
+        assert_eq!(value.floor(), Fixed::from_int(-2));
+        assert_eq!(value.ceil(), Fixed::from_int(-1));
+        assert_eq!(value.round(), Fixed::from_int(-1));
+        assert_eq!(value.fract(), Fixed::from_f32(0.75));
+        assert_eq!(value.to_int(), -2);
+        assert_eq!(Fixed::from_f32(f32::NAN), Fixed::ZERO);
+        assert_eq!(Fixed::from_f32(1e20), Fixed::MAX);
+    }
+
+    #[test]
+    fn fixed_vectors() {
+        let a = FixedVec2::from(Vec2::new(3.0, 4.0));
+        assert_eq!(a.length(), Fixed::from_int(5));
+        assert_eq!(a.normalize_or_zero().to_vec(), Vec2::new(0.6, 0.8));
+        assert_eq!(FixedVec2::ZERO.normalize_or_zero(), FixedVec2::ZERO);
+
+        let x = FixedVec3::new(Fixed::ONE, Fixed::ZERO, Fixed::ZERO);
+        let y = FixedVec3::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO);
+        assert_eq!(
+            x.cross(y),
+            FixedVec3::new(Fixed::ZERO, Fixed::ZERO, Fixed::ONE)
+        );
+        assert_eq!(
+            Vec3::from(x + y * Fixed::from_int(2)),
+            Vec3::new(1.0, 2.0, 0.0)
+        );
+    }
+
+    #[cfg(feature = "curve")]
+    #[test]
+    fn fixed_curves() {
+        use crate::curve::{Curve, EaseFunction, EasingCurve};
+
+        let start = FixedVec2::ZERO;
+        let end = FixedVec2::new(Fixed::from_int(10), Fixed::from_int(-10));
+        let curve = EasingCurve::new(start, end, EaseFunction::Linear);
+        assert_eq!(
+            curve.sample(0.5),
+            Some(FixedVec2::new(Fixed::from_int(5), Fixed::from_int(-5)))
+        );
+        assert_eq!(start.interpolate_stable(&end, 1.0), end);
+    }
+}
@@ -0,0 +1,315 @@
+//! Boolean operations between polygons, based on the classification of split edges.
+//!
+//! All edges of the operands are split at their intersections. Each resulting fragment is then
+//! kept if the region produced by the operation lies on exactly one of its sides, and the kept
+//! fragments are chained into contours with that region on their left.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
+use core::iter;
+
+use super::predicates::{edges, orient2d_f64, segment_distance_f64, winding_number_f64};
+use crate::{DVec2, Vec2};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The distance below which points are considered to lie on an edge, relative to the magnitude of
+/// the coordinates of the operands.
+const TOLERANCE: f64 = 1e-6;
+
+/// A boolean operation between two [`ComplexPolygon`](super::ComplexPolygon)s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum BooleanOp {
+    /// The region covered by either polygon.
+    Union,
+    /// The region covered by both polygons.
+    Intersection,
+    /// The region covered by the first polygon but not by the second one.
+    Difference,
+    /// The region covered by exactly one of the polygons.
+    Xor,
+}
+
+impl BooleanOp {
+    /// Returns `true` if a point with the given winding numbers is covered by the result.
+    fn contains(self, subject: i32, clip: i32) -> bool {
+        let (subject, clip) = (subject != 0, clip != 0);
+        match self {
+            BooleanOp::Union => subject || clip,
+            BooleanOp::Intersection => subject && clip,
+            BooleanOp::Difference => subject && !clip,
+            BooleanOp::Xor => subject != clip,
+        }
+    }
+}
+
+/// Computes the boolean operation `op` between `subject` and `clip`.
+pub(super) fn boolean(subject: &[Vec<Vec2>], clip: &[Vec<Vec2>], op: BooleanOp) -> Vec<Vec<Vec2>> {
+    overlay(subject, clip, |subject, clip| op.contains(subject, clip))
+}
+
+/// Computes the contours of the region containing the points for which `inside` returns `true`,
+/// given their winding numbers with respect to `subject` and `clip`.
+pub(super) fn overlay(
+    subject: &[Vec<Vec2>],
+    clip: &[Vec<Vec2>],
+    inside: impl Fn(i32, i32) -> bool,
+) -> Vec<Vec<Vec2>> {
+    let edges: Vec<(Vec2, Vec2)> = subject
+        .iter()
+        .chain(clip)
+        .flat_map(|contour| edges(contour))
+        .filter(|(a, b)| a != b)
+        .collect();
+    if edges.is_empty() {
+        return Vec::new();
+    }
+    let scale = edges
+        .iter()
+        .flat_map(|(a, b)| [a.abs().max_element(), b.abs().max_element()])
+        .fold(1.0, f32::max);
+    let tolerance = scale as f64 * TOLERANCE;
+
+    // Split the edges at their intersections, including the ones between edges of the same
+    // operand, so that self-intersecting operands are handled as well.
+    let mut splits = vec![Vec::new(); edges.len()];
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            split_pair(&edges, i, j, tolerance, &mut splits);
+        }
+    }
+
+    let mut emitted = BTreeSet::new();
+    let mut segments = Vec::new();
+    for (&(a, b), points) in edges.iter().zip(&mut splits) {
+        let direction = (b - a).as_dvec2();
+        points.sort_by(|p, q| {
+            let p = (p.as_dvec2() - a.as_dvec2()).dot(direction);
+            let q = (q.as_dvec2() - a.as_dvec2()).dot(direction);
+            p.total_cmp(&q)
+        });
+        points.dedup();
+
+        let mut start = a;
+        for &end in points.iter().chain(iter::once(&b)) {
+            if end == start {
+                continue;
+            }
+            let segment = match classify(start, end, &edges, subject, clip, tolerance, &inside) {
+                (true, false) => (start, end),
+                (false, true) => (end, start),
+                _ => {
+                    start = end;
+                    continue;
+                }
+            };
+            // Coincident edges of both operands produce the same segment.
+            if emitted.insert((key(segment.0), key(segment.1))) {
+                segments.push(segment);
+            }
+            start = end;
+        }
+    }
+
+    build_contours(&segments, tolerance)
+}
+
+/// Records the points where the edges `i` and `j` intersect.
+fn split_pair(
+    edges: &[(Vec2, Vec2)],
+    i: usize,
+    j: usize,
+    tolerance: f64,
+    splits: &mut [Vec<Vec2>],
+) {
+    let (e, f) = (edges[i], edges[j]);
+    let (a, b, c, d) = (
+        e.0.as_dvec2(),
+        e.1.as_dvec2(),
+        f.0.as_dvec2(),
+        f.1.as_dvec2(),
+    );
+    if a.x.min(b.x) > c.x.max(d.x) + tolerance
+        || c.x.min(d.x) > a.x.max(b.x) + tolerance
+        || a.y.min(b.y) > c.y.max(d.y) + tolerance
+        || c.y.min(d.y) > a.y.max(b.y) + tolerance
+    {
+        return;
+    }
+
+    // Endpoints lying on the other edge split it, which covers both touching and collinear edges.
+    let mut touching = false;
+    for (point, edge, index) in [(e.0, f, j), (e.1, f, j), (f.0, e, i), (f.1, e, i)] {
+        if lies_within(point, edge, tolerance) {
+            splits[index].push(point);
+            touching = true;
+        }
+    }
+    if touching {
+        return;
+    }
+
+    let o0 = orient2d_f64(a, b, c);
+    let o1 = orient2d_f64(a, b, d);
+    let o2 = orient2d_f64(c, d, a);
+    let o3 = orient2d_f64(c, d, b);
+    if o0 * o1 < 0.0 && o2 * o3 < 0.0 {
+        let point = (a + (b - a) * (o2 / (o2 - o3))).as_vec2();
+        splits[i].push(point);
+        splits[j].push(point);
+    }
+}
+
+/// Returns `true` if `point` lies on `edge`, away from its endpoints.
+fn lies_within(point: Vec2, edge: (Vec2, Vec2), tolerance: f64) -> bool {
+    if point == edge.0 || point == edge.1 {
+        return false;
+    }
+    let (p, a, b) = (point.as_dvec2(), edge.0.as_dvec2(), edge.1.as_dvec2());
+    let t = (p - a).dot(b - a);
+    t > 0.0 && t < (b - a).length_squared() && segment_distance_f64(p, a, b) <= tolerance
+}
+
+/// Returns whether the region lies to the left and to the right of the segment from `start` to
+/// `end`.
+fn classify(
+    start: Vec2,
+    end: Vec2,
+    edges: &[(Vec2, Vec2)],
+    subject: &[Vec<Vec2>],
+    clip: &[Vec<Vec2>],
+    tolerance: f64,
+    inside: &impl Fn(i32, i32) -> bool,
+) -> (bool, bool) {
+    let (p, q) = (start.as_dvec2(), end.as_dvec2());
+    let midpoint = (p + q) * 0.5;
+    let length = p.distance(q);
+    let normal = (q - p).perp() / length;
+
+    // Probe on both sides, closer to the segment than to any edge not overlapping it.
+    let clearance = edges
+        .iter()
+        .map(|(a, b)| segment_distance_f64(midpoint, a.as_dvec2(), b.as_dvec2()))
+        .filter(|distance| *distance > tolerance)
+        .fold(f64::INFINITY, f64::min);
+    let offset = (0.5 * clearance).min(0.25 * length);
+
+    let side = |point: DVec2| {
+        inside(
+            winding_number_f64(point, subject),
+            winding_number_f64(point, clip),
+        )
+    };
+    (
+        side(midpoint + normal * offset),
+        side(midpoint - normal * offset),
+    )
+}
+
+/// Chains directed segments into closed contours.
+fn build_contours(segments: &[(Vec2, Vec2)], tolerance: f64) -> Vec<Vec<Vec2>> {
+    let mut outgoing: BTreeMap<_, Vec<usize>> = BTreeMap::new();
+    for (index, (start, _)) in segments.iter().enumerate() {
+        outgoing.entry(key(*start)).or_default().push(index);
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut contours = Vec::new();
+    for first in 0..segments.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let origin = segments[first].0;
+        let mut contour = vec![origin];
+        let mut current = first;
+        let closed = loop {
+            let (start, end) = segments[current];
+            if key(end) == key(origin) {
+                break true;
+            }
+            contour.push(end);
+
+            // Where several contours touch, take the sharpest left turn to keep them apart.
+            let direction = (end - start).as_dvec2();
+            let next = outgoing
+                .get(&key(end))
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&index| !used[index])
+                .max_by(|&i, &j| {
+                    let turn_i = turn(direction, segments[i]);
+                    let turn_j = turn(direction, segments[j]);
+                    turn_i.total_cmp(&turn_j)
+                });
+            let Some(next) = next else {
+                break false;
+            };
+            used[next] = true;
+            current = next;
+        };
+
+        if closed {
+            if let Some(contour) = simplify_contour(contour, tolerance) {
+                contours.push(contour);
+            }
+        }
+    }
+    contours
+}
+
+/// Returns a value increasing with the counterclockwise angle between `direction` and `segment`.
+fn turn(direction: DVec2, segment: (Vec2, Vec2)) -> f64 {
+    let direction = direction.normalize();
+    let next = (segment.1 - segment.0).as_dvec2().normalize();
+    let (cos, sin) = (direction.dot(next), direction.perp_dot(next));
+    if sin >= 0.0 {
+        1.0 - cos
+    } else {
+        cos - 1.0
+    }
+}
+
+/// Removes the vertices lying on the segment between their neighbors, and discards contours
+/// without area.
+fn simplify_contour(mut contour: Vec<Vec2>, tolerance: f64) -> Option<Vec<Vec2>> {
+    let mut i = 0;
+    while contour.len() >= 3 && i < contour.len() {
+        let n = contour.len();
+        let previous = contour[(i + n - 1) % n].as_dvec2();
+        let next = contour[(i + 1) % n].as_dvec2();
+        if segment_distance_f64(contour[i].as_dvec2(), previous, next) <= tolerance {
+            contour.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    let area = edges(&contour)
+        .map(|(a, b)| a.as_dvec2().perp_dot(b.as_dvec2()))
+        .sum::<f64>();
+    (contour.len() >= 3 && area * area > tolerance * tolerance).then_some(contour)
+}
+
+/// Returns a key identifying the position of `point`, treating both zeros as equal.
+fn key(point: Vec2) -> (u32, u32) {
+    let point = point + Vec2::ZERO;
+    (point.x.to_bits(), point.y.to_bits())
+}
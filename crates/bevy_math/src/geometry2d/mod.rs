@@ -0,0 +1,346 @@
+//! Algorithms on arbitrary 2D polygons: boolean operations, offsetting and triangulation.
+//!
+//! The algorithms operate on [`ComplexPolygon`]s, which can have several contours and holes,
+//! and are typically used to build colliders and meshes:
+//!
+//! ```
+//! # use bevy_math::{geometry2d::{ComplexPolygon, OffsetJoin}, prelude::*};
+//! let floor = ComplexPolygon::from(Rectangle::new(4.0, 1.0));
+//! let pillar = ComplexPolygon::from(Rectangle::new(1.0, 4.0));
+//!
+//! let shape = floor.union(&pillar).offset(0.1, OffsetJoin::Round { resolution: 16 });
+//! let triangulation = shape.triangulate();
+//! assert!(!triangulation.indices.is_empty());
+//! ```
+
+mod boolean;
+mod offset;
+mod predicates;
+mod triangulation;
+
+pub use boolean::BooleanOp;
+pub use offset::OffsetJoin;
+pub use predicates::{orient2d, segment_intersection, winding_number, SegmentIntersection};
+pub use triangulation::Triangulation;
+
+use alloc::vec::Vec;
+
+use crate::{
+    primitives::{BoxedPolygon, ConvexPolygon, Measured2d, Polygon, Primitive2d, Rectangle},
+    Vec2,
+};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A polygon made of any number of closed contours, which can describe several disjoint shapes
+/// with holes.
+///
+/// The polygon covers the points around which its contours have a non-zero [winding number],
+/// so contours may overlap or intersect themselves. The polygons returned by the operations of
+/// this type are normalized: their contours don't cross each other, outer contours are
+/// counterclockwise and holes are clockwise, so that the covered region is always on the left of
+/// the contours.
+///
+/// [winding number]: https://en.wikipedia.org/wiki/Winding_number
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ComplexPolygon {
+    /// The contours of the polygon, which are implicitly closed.
+    pub contours: Vec<Vec<Vec2>>,
+}
+impl Primitive2d for ComplexPolygon {}
+
+impl ComplexPolygon {
+    /// Creates a new [`ComplexPolygon`] from its contours, without changing their orientation.
+    pub fn new(contours: impl IntoIterator<Item = impl IntoIterator<Item = Vec2>>) -> Self {
+        Self {
+            contours: contours
+                .into_iter()
+                .map(|contour| contour.into_iter().collect())
+                .collect(),
+        }
+    }
+
+    /// Adds an outer contour to the polygon, making it counterclockwise.
+    pub fn with_outer(self, vertices: impl IntoIterator<Item = Vec2>) -> Self {
+        self.with_oriented_contour(vertices, true)
+    }
+
+    /// Adds a hole to the polygon, making it clockwise.
+    ///
+    /// The hole only removes area from the polygon where it overlaps a single outer contour.
+    pub fn with_hole(self, vertices: impl IntoIterator<Item = Vec2>) -> Self {
+        self.with_oriented_contour(vertices, false)
+    }
+
+    fn with_oriented_contour(
+        mut self,
+        vertices: impl IntoIterator<Item = Vec2>,
+        counterclockwise: bool,
+    ) -> Self {
+        let mut contour: Vec<Vec2> = vertices.into_iter().collect();
+        if (signed_area(&contour) > 0.0) != counterclockwise {
+            contour.reverse();
+        }
+        self.contours.push(contour);
+        self
+    }
+
+    /// Returns the winding number of the contours around `point`.
+    pub fn winding_number(&self, point: Vec2) -> i32 {
+        winding_number(point, &self.contours)
+    }
+
+    /// Returns `true` if the polygon covers `point`.
+    ///
+    /// Points on the boundary of the polygon may or may not be considered covered.
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.winding_number(point) != 0
+    }
+
+    /// Returns the normalized version of this polygon, which covers the same region with
+    /// contours that don't cross each other.
+    pub fn normalized(&self) -> Self {
+        self.boolean(&Self::default(), BooleanOp::Union)
+    }
+
+    /// Computes the boolean operation `op` between this polygon and `other`.
+    ///
+    /// This splits all edges at their intersections, which takes quadratic time in the total
+    /// number of edges. The intersections are computed in `f64`, and points closer to an edge
+    /// than a millionth of the magnitude of the coordinates are snapped to it.
+    pub fn boolean(&self, other: &Self, op: BooleanOp) -> Self {
+        Self {
+            contours: boolean::boolean(&self.contours, &other.contours, op),
+        }
+    }
+
+    /// Returns the region covered by either this polygon or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Union)
+    }
+
+    /// Returns the region covered by both this polygon and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+
+    /// Returns the region covered by this polygon but not by `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Difference)
+    }
+
+    /// Returns the region covered by exactly one of this polygon and `other`.
+    pub fn xor(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Xor)
+    }
+
+    /// Grows the polygon by `distance`, or shrinks it if `distance` is negative.
+    ///
+    /// The polygon must be normalized, as the contours are offset to the right of their edges.
+    /// Parts of the polygon thinner than twice a negative `distance` disappear, and the result
+    /// is normalized.
+    pub fn offset(&self, distance: f32, join: OffsetJoin) -> Self {
+        Self {
+            contours: offset::offset(&self.contours, distance, join),
+        }
+    }
+
+    /// Splits the polygon into triangles.
+    ///
+    /// The polygon must be normalized. Holes are connected to the smallest outer contour
+    /// containing them, and the resulting contours are triangulated by ear clipping, which takes
+    /// quadratic time in the number of vertices.
+    pub fn triangulate(&self) -> Triangulation {
+        triangulation::triangulate(&self.contours)
+    }
+}
+
+impl Measured2d for ComplexPolygon {
+    /// Get the total length of the contours of the polygon.
+    fn perimeter(&self) -> f32 {
+        self.contours
+            .iter()
+            .flat_map(|contour| predicates::edges(contour))
+            .map(|(a, b)| a.distance(b))
+            .sum()
+    }
+
+    /// Get the area covered by the polygon.
+    ///
+    /// This is the signed area enclosed by the contours, which is only the covered area if the
+    /// polygon is normalized.
+    fn area(&self) -> f32 {
+        self.contours
+            .iter()
+            .map(|contour| signed_area(contour))
+            .sum()
+    }
+}
+
+fn signed_area(contour: &[Vec2]) -> f32 {
+    predicates::edges(contour)
+        .map(|(a, b)| a.as_dvec2().perp_dot(b.as_dvec2()))
+        .sum::<f64>() as f32
+        * 0.5
+}
+
+impl<const N: usize> From<Polygon<N>> for ComplexPolygon {
+    fn from(polygon: Polygon<N>) -> Self {
+        Self::default().with_outer(polygon.vertices)
+    }
+}
+
+impl<const N: usize> From<ConvexPolygon<N>> for ComplexPolygon {
+    fn from(polygon: ConvexPolygon<N>) -> Self {
+        Self::default().with_outer(*polygon.vertices())
+    }
+}
+
+impl From<BoxedPolygon> for ComplexPolygon {
+    fn from(polygon: BoxedPolygon) -> Self {
+        Self::default().with_outer(polygon.vertices)
+    }
+}
+
+impl From<Rectangle> for ComplexPolygon {
+    fn from(rectangle: Rectangle) -> Self {
+        let Vec2 { x, y } = rectangle.half_size;
+        Self::new([[
+            Vec2::new(-x, -y),
+            Vec2::new(x, -y),
+            Vec2::new(x, y),
+            Vec2::new(-x, y),
+        ]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        primitives::{Triangle2d, WindingOrder},
+        vec2,
+    };
+    use approx::assert_relative_eq;
+
+    fn square(min: Vec2, size: f32) -> ComplexPolygon {
+        ComplexPolygon::new([[
+            min,
+            min + vec2(size, 0.0),
+            min + Vec2::splat(size),
+            min + vec2(0.0, size),
+        ]])
+    }
+
+    #[test]
+    fn boolean_operations() {
+        let a = square(Vec2::ZERO, 2.0);
+        let b = square(Vec2::ONE, 2.0);
+
+        assert_relative_eq!(a.union(&b).area(), 7.0, epsilon = 1e-5);
+        assert_relative_eq!(a.intersection(&b).area(), 1.0, epsilon = 1e-5);
+        assert_relative_eq!(a.difference(&b).area(), 3.0, epsilon = 1e-5);
+        assert_relative_eq!(a.xor(&b).area(), 6.0, epsilon = 1e-5);
+
+        let union = a.union(&b);
+        assert_eq!(union.contours.len(), 1);
+        assert_eq!(union.contours[0].len(), 8);
+        assert!(union.contains(vec2(2.5, 2.5)));
+        assert!(!union.contains(vec2(2.5, 0.5)));
+    }
+
+    #[test]
+    fn shared_edges_and_holes() {
+        // Adjacent squares merge into a single rectangle.
+        let union = square(Vec2::ZERO, 1.0).union(&square(Vec2::X, 1.0));
+        assert_eq!(union.contours.len(), 1);
+        assert_eq!(union.contours[0].len(), 4);
+        assert_relative_eq!(union.area(), 2.0, epsilon = 1e-5);
+
+        // Punching a hole produces a clockwise inner contour.
+        let frame = square(Vec2::ZERO, 3.0).difference(&square(Vec2::ONE, 1.0));
+        assert_eq!(frame.contours.len(), 2);
+        assert_relative_eq!(frame.area(), 8.0, epsilon = 1e-5);
+        assert!(!frame.contains(Vec2::splat(1.5)));
+        assert!(frame
+            .contours
+            .iter()
+            .any(|contour| signed_area(contour) < 0.0));
+    }
+
+    #[test]
+    fn normalize_self_intersecting() {
+        // A bowtie covers two triangles of opposite orientations.
+        let bowtie =
+            ComplexPolygon::new([[Vec2::ZERO, vec2(2.0, 2.0), vec2(2.0, 0.0), vec2(0.0, 2.0)]]);
+        let normalized = bowtie.normalized();
+        assert_eq!(normalized.contours.len(), 2);
+        assert_relative_eq!(normalized.area(), 2.0, epsilon = 1e-5);
+        for contour in &normalized.contours {
+            assert!(signed_area(contour) > 0.0);
+        }
+    }
+
+    #[test]
+    fn offsetting() {
+        let square = square(Vec2::ZERO, 2.0);
+
+        let grown = square.offset(0.5, OffsetJoin::Miter { limit: 2.0 });
+        assert_relative_eq!(grown.area(), 9.0, epsilon = 1e-4);
+
+        let beveled = square.offset(0.5, OffsetJoin::Bevel);
+        assert_relative_eq!(beveled.area(), 9.0 - 4.0 * 0.125, epsilon = 1e-4);
+
+        let rounded = square.offset(0.5, OffsetJoin::Round { resolution: 256 });
+        let expected = 4.0 + 4.0 * 2.0 * 0.5 + core::f32::consts::PI * 0.25;
+        assert_relative_eq!(rounded.area(), expected, epsilon = 1e-2);
+
+        let shrunk = square.offset(-0.5, OffsetJoin::default());
+        assert_relative_eq!(shrunk.area(), 1.0, epsilon = 1e-4);
+
+        // Shrinking by more than half of the width removes the polygon entirely.
+        assert!(square
+            .offset(-1.5, OffsetJoin::default())
+            .contours
+            .is_empty());
+    }
+
+    #[test]
+    fn triangulation_covers_polygon() {
+        let l_shape = ComplexPolygon::new([[
+            Vec2::ZERO,
+            vec2(2.0, 0.0),
+            vec2(2.0, 1.0),
+            vec2(1.0, 1.0),
+            vec2(1.0, 2.0),
+            vec2(0.0, 2.0),
+        ]]);
+        let triangulation = l_shape.triangulate();
+        assert_eq!(triangulation.indices.len(), 4 * 3);
+        let area: f32 = triangulation.triangles().map(|t| t.area()).sum();
+        assert_relative_eq!(area, 3.0, epsilon = 1e-5);
+
+        let frame = square(Vec2::ZERO, 3.0).difference(&square(Vec2::ONE, 1.0));
+        let triangulation = frame.triangulate();
+        assert_eq!(triangulation.indices.len(), 8 * 3);
+        let triangles: Vec<Triangle2d> = triangulation.triangles().collect();
+        let area: f32 = triangles.iter().map(Measured2d::area).sum();
+        assert_relative_eq!(area, 8.0, epsilon = 1e-5);
+        for triangle in &triangles {
+            assert_eq!(triangle.winding_order(), WindingOrder::CounterClockwise);
+        }
+    }
+}
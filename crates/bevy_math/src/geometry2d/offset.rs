@@ -0,0 +1,165 @@
+//! Offsetting of polygons.
+//!
+//! Each contour is offset edge by edge, with joins inserted between consecutive edges. The raw
+//! contours may overlap themselves or be inverted where the offset is larger than the features of
+//! the polygon, which is resolved by keeping the region they wind positively around.
+
+use alloc::vec::Vec;
+use core::f32::consts::{PI, TAU};
+
+use super::boolean::overlay;
+use crate::{ops, Rot2, Vec2};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// How the corners of a polygon are joined when it is offset with
+/// [`ComplexPolygon::offset`](super::ComplexPolygon::offset).
+///
+/// Joins only apply to corners where the offset edges move apart from each other, which are the
+/// convex corners when growing a polygon and the concave ones when shrinking it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum OffsetJoin {
+    /// Extends the offset edges until they meet.
+    ///
+    /// Corners whose extended edges would reach further than `limit` times the offset distance
+    /// from the original corner are beveled instead.
+    Miter {
+        /// The maximum distance of a mitered corner, relative to the offset distance.
+        limit: f32,
+    },
+    /// Connects the offset edges with a straight segment.
+    Bevel,
+    /// Connects the offset edges with a circular arc.
+    Round {
+        /// The number of segments a full circle would be split into.
+        resolution: u32,
+    },
+}
+
+impl Default for OffsetJoin {
+    /// Returns [`OffsetJoin::Miter`] with a limit of `2.0`.
+    fn default() -> Self {
+        OffsetJoin::Miter { limit: 2.0 }
+    }
+}
+
+/// Offsets `contours` by `distance` to the right of their edges.
+pub(super) fn offset(contours: &[Vec<Vec2>], distance: f32, join: OffsetJoin) -> Vec<Vec<Vec2>> {
+    let raw: Vec<Vec<Vec2>> = contours
+        .iter()
+        .map(|contour| offset_contour(contour, distance, join))
+        .filter(|contour| contour.len() >= 3)
+        .collect();
+    overlay(&raw, &[], |winding, _| winding > 0)
+}
+
+fn offset_contour(contour: &[Vec2], distance: f32, join: OffsetJoin) -> Vec<Vec2> {
+    let mut vertices: Vec<Vec2> = contour.to_vec();
+    vertices.dedup();
+    while vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+    let n = vertices.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    // The direction of the edge leaving each vertex.
+    let directions: Vec<Vec2> = (0..n)
+        .map(|i| (vertices[(i + 1) % n] - vertices[i]).normalize_or_zero())
+        .collect();
+
+    let mut result = Vec::with_capacity(2 * n);
+    for (i, &vertex) in vertices.iter().enumerate() {
+        let incoming = directions[(i + n - 1) % n];
+        let outgoing = directions[i];
+        let incoming_normal = right_normal(incoming);
+        let outgoing_normal = right_normal(outgoing);
+        let cross = incoming.perp_dot(outgoing);
+        let dot = incoming.dot(outgoing);
+
+        let aligned = ops::abs(cross) <= 1e-6;
+        if aligned && dot > 0.0 {
+            result.push(vertex + incoming_normal * distance);
+        } else if aligned || cross * distance > 0.0 {
+            push_join(
+                &mut result,
+                vertex,
+                incoming_normal,
+                outgoing_normal,
+                distance,
+                join,
+            );
+        } else {
+            // The offset edges overlap. Going through the original vertex keeps the winding of
+            // the small loop this creates consistent, so that it is removed afterwards.
+            result.push(vertex + incoming_normal * distance);
+            result.push(vertex);
+            result.push(vertex + outgoing_normal * distance);
+        }
+    }
+    result
+}
+
+/// Pushes the vertices joining two offset edges moving apart from each other.
+fn push_join(
+    result: &mut Vec<Vec2>,
+    vertex: Vec2,
+    incoming_normal: Vec2,
+    outgoing_normal: Vec2,
+    distance: f32,
+    join: OffsetJoin,
+) {
+    let cos = incoming_normal.dot(outgoing_normal);
+    match join {
+        OffsetJoin::Miter { limit } => {
+            // The miter extends `1 / cos(angle / 2)` times the distance from the vertex.
+            if 1.0 + cos > 2.0 / (limit * limit) {
+                let miter = (incoming_normal + outgoing_normal) / (1.0 + cos);
+                result.push(vertex + miter * distance);
+            } else {
+                result.push(vertex + incoming_normal * distance);
+                result.push(vertex + outgoing_normal * distance);
+            }
+        }
+        OffsetJoin::Bevel => {
+            result.push(vertex + incoming_normal * distance);
+            result.push(vertex + outgoing_normal * distance);
+        }
+        OffsetJoin::Round { resolution } => {
+            // Reversing edges are capped by a half circle on the side of the offset.
+            let angle = if cos <= -1.0 + 1e-6 {
+                ops::copysign(PI, distance)
+            } else {
+                incoming_normal.angle_to(outgoing_normal)
+            };
+            let step = TAU / resolution.max(3) as f32;
+            let segments = (ops::abs(angle) / step) as u32 + 1;
+            let rotation = Rot2::radians(angle / segments as f32);
+            let mut normal = incoming_normal;
+            result.push(vertex + normal * distance);
+            for _ in 0..segments {
+                normal = rotation * normal;
+                result.push(vertex + normal * distance);
+            }
+        }
+    }
+}
+
+/// Returns the normal to the right of `direction`.
+fn right_normal(direction: Vec2) -> Vec2 {
+    -direction.perp()
+}
@@ -0,0 +1,186 @@
+//! Geometric predicates shared by the 2D geometry algorithms.
+//!
+//! The predicates are evaluated in `f64`, which makes them exact for most `f32` inputs and keeps
+//! the rounding errors far below the precision of the inputs otherwise.
+
+use crate::{DVec2, Vec2};
+
+/// Returns twice the signed area of the triangle `abc`.
+///
+/// The result is positive if `a`, `b` and `c` are in counterclockwise order, negative if they
+/// are in clockwise order, and zero if they are collinear.
+#[inline]
+pub fn orient2d(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    orient2d_f64(a.as_dvec2(), b.as_dvec2(), c.as_dvec2()) as f32
+}
+
+#[inline]
+pub(super) fn orient2d_f64(a: DVec2, b: DVec2, c: DVec2) -> f64 {
+    (b - a).perp_dot(c - a)
+}
+
+/// Returns the winding number of `contours` around `point`.
+///
+/// Each counterclockwise contour around the point adds one to the winding number, and each
+/// clockwise contour around it subtracts one. Contours are implicitly closed.
+pub fn winding_number<C: AsRef<[Vec2]>>(point: Vec2, contours: &[C]) -> i32 {
+    winding_number_f64(point.as_dvec2(), contours)
+}
+
+pub(super) fn winding_number_f64<C: AsRef<[Vec2]>>(point: DVec2, contours: &[C]) -> i32 {
+    let mut winding = 0;
+    for contour in contours {
+        for (a, b) in edges(contour.as_ref()) {
+            let (a, b) = (a.as_dvec2(), b.as_dvec2());
+            if a.y <= point.y {
+                if b.y > point.y && orient2d_f64(a, b, point) > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= point.y && orient2d_f64(a, b, point) < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+    winding
+}
+
+/// Returns the distance between `point` and the segment `ab`.
+pub(super) fn segment_distance_f64(point: DVec2, a: DVec2, b: DVec2) -> f64 {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared == 0.0 {
+        return point.distance(a);
+    }
+    let t = ((point - a).dot(ab) / length_squared).clamp(0.0, 1.0);
+    point.distance(a + ab * t)
+}
+
+/// The intersection of two segments, as returned by [`segment_intersection`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SegmentIntersection {
+    /// The segments don't intersect.
+    None,
+    /// The segments intersect at a single point.
+    Point(Vec2),
+    /// The segments are collinear and overlap between two points.
+    Overlap(Vec2, Vec2),
+}
+
+/// Computes the intersection of the segments `a0 a1` and `b0 b1`.
+///
+/// Endpoints are considered part of the segments, so segments sharing an endpoint intersect.
+pub fn segment_intersection(a0: Vec2, a1: Vec2, b0: Vec2, b1: Vec2) -> SegmentIntersection {
+    let (p0, p1, q0, q1) = (a0.as_dvec2(), a1.as_dvec2(), b0.as_dvec2(), b1.as_dvec2());
+    let o0 = orient2d_f64(p0, p1, q0);
+    let o1 = orient2d_f64(p0, p1, q1);
+    let o2 = orient2d_f64(q0, q1, p0);
+    let o3 = orient2d_f64(q0, q1, p1);
+
+    if o0 == 0.0 && o1 == 0.0 {
+        // The segments are collinear, so project them onto their common line.
+        let direction = if p0 != p1 { p1 - p0 } else { q1 - q0 };
+        if direction == DVec2::ZERO {
+            return if a0 == b0 {
+                SegmentIntersection::Point(a0)
+            } else {
+                SegmentIntersection::None
+            };
+        }
+        let project = |point: Vec2| (point.as_dvec2() - p0).dot(direction);
+        let (a_min, a_max) = min_max(a0, a1, project);
+        let (b_min, b_max) = min_max(b0, b1, project);
+        let start = if project(a_min) >= project(b_min) {
+            a_min
+        } else {
+            b_min
+        };
+        let end = if project(a_max) <= project(b_max) {
+            a_max
+        } else {
+            b_max
+        };
+        return match project(start).partial_cmp(&project(end)) {
+            Some(core::cmp::Ordering::Less) => SegmentIntersection::Overlap(start, end),
+            Some(core::cmp::Ordering::Equal) => SegmentIntersection::Point(start),
+            _ => SegmentIntersection::None,
+        };
+    }
+
+    if o0 * o1 > 0.0 || o2 * o3 > 0.0 {
+        return SegmentIntersection::None;
+    }
+    if o0 == 0.0 {
+        return SegmentIntersection::Point(b0);
+    }
+    if o1 == 0.0 {
+        return SegmentIntersection::Point(b1);
+    }
+    if o2 == 0.0 {
+        return SegmentIntersection::Point(a0);
+    }
+    if o3 == 0.0 {
+        return SegmentIntersection::Point(a1);
+    }
+    let t = o2 / (o2 - o3);
+    SegmentIntersection::Point((p0 + (p1 - p0) * t).as_vec2())
+}
+
+fn min_max(a: Vec2, b: Vec2, project: impl Fn(Vec2) -> f64) -> (Vec2, Vec2) {
+    if project(a) <= project(b) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Returns the edges of a closed contour.
+pub(super) fn edges(contour: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    contour
+        .iter()
+        .zip(contour.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec2;
+
+    #[test]
+    fn orientation() {
+        let (a, b) = (Vec2::ZERO, Vec2::X);
+        assert!(orient2d(a, b, Vec2::Y) > 0.0);
+        assert!(orient2d(a, b, Vec2::NEG_Y) < 0.0);
+        assert_eq!(orient2d(a, b, vec2(3.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn winding() {
+        let square = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+        let reversed = [Vec2::Y, Vec2::ONE, Vec2::X, Vec2::ZERO];
+        assert_eq!(winding_number(Vec2::splat(0.5), &[square]), 1);
+        assert_eq!(winding_number(Vec2::splat(0.5), &[reversed]), -1);
+        assert_eq!(winding_number(Vec2::splat(0.5), &[square, square]), 2);
+        assert_eq!(winding_number(Vec2::splat(1.5), &[square]), 0);
+    }
+
+    #[test]
+    fn intersections() {
+        assert_eq!(
+            segment_intersection(Vec2::ZERO, Vec2::ONE, vec2(0.0, 1.0), vec2(1.0, 0.0)),
+            SegmentIntersection::Point(Vec2::splat(0.5))
+        );
+        assert_eq!(
+            segment_intersection(Vec2::ZERO, Vec2::X, vec2(0.5, 0.0), vec2(0.5, 1.0)),
+            SegmentIntersection::Point(vec2(0.5, 0.0))
+        );
+        assert_eq!(
+            segment_intersection(Vec2::ZERO, vec2(2.0, 0.0), vec2(3.0, 0.0), Vec2::X),
+            SegmentIntersection::Overlap(Vec2::X, vec2(2.0, 0.0))
+        );
+        assert_eq!(
+            segment_intersection(Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE),
+            SegmentIntersection::None
+        );
+    }
+}
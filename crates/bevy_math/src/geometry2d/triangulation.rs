@@ -0,0 +1,322 @@
+//! Triangulation of polygons with holes by ear clipping.
+//!
+//! Holes are first connected to the outer contour containing them through bridge edges, as in
+//! David Eberly's "Triangulation by Ear Clipping", and the resulting contour is then clipped ear
+//! by ear.
+
+use alloc::{vec, vec::Vec};
+
+use super::predicates::{orient2d_f64, winding_number_f64};
+use crate::{ops, primitives::Triangle2d, Vec2};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// The triangles covering a [`ComplexPolygon`](super::ComplexPolygon), as returned by
+/// [`ComplexPolygon::triangulate`](super::ComplexPolygon::triangulate).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default)
+)]
+pub struct Triangulation {
+    /// The vertices of the contours of the polygon, in order.
+    pub vertices: Vec<Vec2>,
+    /// The indices of the vertices of each triangle, in counterclockwise order.
+    pub indices: Vec<u32>,
+}
+
+impl Triangulation {
+    /// Returns an iterator over the triangles.
+    pub fn triangles(&self) -> impl Iterator<Item = Triangle2d> + '_ {
+        self.indices.chunks_exact(3).map(|triangle| {
+            Triangle2d::new(
+                self.vertices[triangle[0] as usize],
+                self.vertices[triangle[1] as usize],
+                self.vertices[triangle[2] as usize],
+            )
+        })
+    }
+}
+
+/// A contour of the polygon, with its repeated vertices removed.
+struct Ring {
+    indices: Vec<u32>,
+    points: Vec<Vec2>,
+    area: f64,
+}
+
+pub(super) fn triangulate(contours: &[Vec<Vec2>]) -> Triangulation {
+    let mut vertices = Vec::new();
+    let mut outers = Vec::new();
+    let mut holes = Vec::new();
+    for contour in contours {
+        let start = vertices.len() as u32;
+        vertices.extend_from_slice(contour);
+        let mut indices: Vec<u32> = (start..vertices.len() as u32).collect();
+        indices.dedup_by(|a, b| vertices[*a as usize] == vertices[*b as usize]);
+        while indices.len() > 1
+            && vertices[indices[0] as usize] == vertices[indices[indices.len() - 1] as usize]
+        {
+            indices.pop();
+        }
+        let points: Vec<Vec2> = indices.iter().map(|&i| vertices[i as usize]).collect();
+        let area = signed_area(&points);
+        if indices.len() < 3 || area == 0.0 {
+            continue;
+        }
+        let ring = Ring {
+            indices,
+            points,
+            area,
+        };
+        if area > 0.0 {
+            outers.push(ring);
+        } else {
+            holes.push(ring);
+        }
+    }
+
+    // Each hole belongs to the smallest outer contour containing it.
+    let mut outer_holes: Vec<Vec<&Ring>> = vec![Vec::new(); outers.len()];
+    for hole in &holes {
+        let parent = hole.points.iter().find_map(|point| {
+            outers
+                .iter()
+                .enumerate()
+                .filter(|(_, outer)| winding_number_f64(point.as_dvec2(), &[&outer.points]) != 0)
+                .min_by(|(_, a), (_, b)| a.area.total_cmp(&b.area))
+                .map(|(index, _)| index)
+        });
+        if let Some(parent) = parent {
+            outer_holes[parent].push(hole);
+        }
+    }
+
+    let mut indices = Vec::new();
+    for (outer, mut holes) in outers.iter().zip(outer_holes) {
+        let mut polygon = outer.indices.clone();
+        // Connecting the rightmost holes first keeps the bridges from crossing each other.
+        holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+        for hole in holes {
+            eliminate_hole(&vertices, &mut polygon, hole);
+        }
+        ear_clip(&vertices, &polygon, &mut indices);
+    }
+
+    Triangulation { vertices, indices }
+}
+
+fn signed_area(points: &[Vec2]) -> f64 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            points[i]
+                .as_dvec2()
+                .perp_dot(points[(i + 1) % n].as_dvec2())
+        })
+        .sum::<f64>()
+        * 0.5
+}
+
+fn max_x(ring: &Ring) -> f32 {
+    ring.points
+        .iter()
+        .map(|point| point.x)
+        .fold(f32::MIN, f32::max)
+}
+
+fn orient(a: Vec2, b: Vec2, c: Vec2) -> f64 {
+    orient2d_f64(a.as_dvec2(), b.as_dvec2(), c.as_dvec2())
+}
+
+/// Splices `hole` into `polygon` through a bridge from its rightmost vertex.
+fn eliminate_hole(vertices: &[Vec2], polygon: &mut Vec<u32>, hole: &Ring) {
+    let (start, _) = hole
+        .points
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.x.total_cmp(&b.x))
+        .unwrap();
+    let Some(bridge) = find_bridge(vertices, polygon, hole.points[start]) else {
+        return;
+    };
+
+    let mut spliced = Vec::with_capacity(polygon.len() + hole.indices.len() + 2);
+    spliced.extend_from_slice(&polygon[..=bridge]);
+    spliced.extend_from_slice(&hole.indices[start..]);
+    spliced.extend_from_slice(&hole.indices[..start]);
+    spliced.push(hole.indices[start]);
+    spliced.push(polygon[bridge]);
+    spliced.extend_from_slice(&polygon[bridge + 1..]);
+    *polygon = spliced;
+}
+
+/// Finds a vertex of `polygon` visible from `point`, which lies inside the polygon.
+fn find_bridge(vertices: &[Vec2], polygon: &[u32], point: Vec2) -> Option<usize> {
+    let n = polygon.len();
+    let at = |i: usize| vertices[polygon[i % n] as usize];
+
+    // Find the closest edge crossed by a ray from the point towards positive x. As the polygon is
+    // counterclockwise, the edges crossed from the inside go upwards.
+    let mut nearest_x = f32::INFINITY;
+    let mut bridge = None;
+    for i in 0..n {
+        let (a, b) = (at(i), at(i + 1));
+        if a.y > point.y || point.y > b.y || a.y == b.y {
+            continue;
+        }
+        let x = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x < point.x || x >= nearest_x {
+            continue;
+        }
+        nearest_x = x;
+        bridge = Some(if point.y == a.y {
+            i
+        } else if point.y == b.y {
+            (i + 1) % n
+        } else if a.x > b.x {
+            i
+        } else {
+            (i + 1) % n
+        });
+    }
+    let mut bridge = bridge?;
+
+    // Reflex vertices inside the triangle formed by the point, the crossing and the candidate may
+    // hide the candidate. The one closest to the ray is visible, so connect to it instead.
+    let crossing = Vec2::new(nearest_x, point.y);
+    let candidate = at(bridge);
+    if candidate != crossing {
+        let mut best_tangent = f32::INFINITY;
+        for i in 0..n {
+            let vertex = at(i);
+            if vertex == candidate
+                || vertex.x <= point.x
+                || orient(at(i + n - 1), vertex, at(i + 1)) >= 0.0
+                || !in_triangle(point, crossing, candidate, vertex)
+            {
+                continue;
+            }
+            let tangent = ops::abs(vertex.y - point.y) / (vertex.x - point.x);
+            if tangent < best_tangent || (tangent == best_tangent && vertex.x < at(bridge).x) {
+                best_tangent = tangent;
+                bridge = i;
+            }
+        }
+    }
+
+    // The vertex may appear several times after previous holes were connected to it, so pick the
+    // occurrence whose interior angle faces the point.
+    let target = at(bridge);
+    (0..n)
+        .filter(|&i| at(i) == target)
+        .find(|&i| in_sector(at(i + n - 1), at(i), at(i + 1), point))
+        .or(Some(bridge))
+}
+
+/// Returns `true` if `point` lies inside or on the boundary of the triangle `abc`, regardless of
+/// its orientation.
+fn in_triangle(a: Vec2, b: Vec2, c: Vec2, point: Vec2) -> bool {
+    let (d0, d1, d2) = (
+        orient(a, b, point),
+        orient(b, c, point),
+        orient(c, a, point),
+    );
+    (d0 >= 0.0 && d1 >= 0.0 && d2 >= 0.0) || (d0 <= 0.0 && d1 <= 0.0 && d2 <= 0.0)
+}
+
+/// Returns `true` if `point` lies in the interior angle of `vertex` in a counterclockwise polygon.
+fn in_sector(previous: Vec2, vertex: Vec2, next: Vec2, point: Vec2) -> bool {
+    let after_previous = orient(previous, vertex, point) >= 0.0;
+    let before_next = orient(vertex, next, point) >= 0.0;
+    if orient(previous, vertex, next) >= 0.0 {
+        after_previous && before_next
+    } else {
+        after_previous || before_next
+    }
+}
+
+/// Triangulates the counterclockwise `polygon` by ear clipping.
+fn ear_clip(vertices: &[Vec2], polygon: &[u32], indices: &mut Vec<u32>) {
+    let n = polygon.len();
+    if n < 3 {
+        return;
+    }
+    let at = |i: usize| vertices[polygon[i] as usize];
+    let mut next: Vec<usize> = (1..=n).map(|i| i % n).collect();
+    let mut previous: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+
+    let mut remaining = n;
+    let mut current = 0;
+    // The number of vertices visited since the last one was removed.
+    let mut stalled = 0;
+    while remaining > 3 {
+        let (a, b, c) = (previous[current], current, next[current]);
+        let orientation = orient(at(a), at(b), at(c));
+        let emit = if orientation == 0.0 {
+            // Collinear vertices don't form a triangle.
+            Some(false)
+        } else if orientation > 0.0
+            && (stalled >= remaining || is_ear(&at, &previous, &next, a, b, c))
+        {
+            // Without any ear in a whole pass, the polygon isn't simple. Clipping convex vertices
+            // anyway still guarantees progress.
+            Some(true)
+        } else if stalled >= 2 * remaining {
+            Some(false)
+        } else {
+            None
+        };
+
+        match emit {
+            Some(emit) => {
+                if emit {
+                    indices.extend_from_slice(&[polygon[a], polygon[b], polygon[c]]);
+                }
+                next[a] = c;
+                previous[c] = a;
+                remaining -= 1;
+                current = c;
+                stalled = 0;
+            }
+            None => {
+                current = c;
+                stalled += 1;
+            }
+        }
+    }
+
+    let (a, b, c) = (previous[current], current, next[current]);
+    if orient(at(a), at(b), at(c)) > 0.0 {
+        indices.extend_from_slice(&[polygon[a], polygon[b], polygon[c]]);
+    }
+}
+
+/// Returns `true` if no other vertex lies in the triangle `abc`.
+fn is_ear(
+    at: &impl Fn(usize) -> Vec2,
+    previous: &[usize],
+    next: &[usize],
+    a: usize,
+    b: usize,
+    c: usize,
+) -> bool {
+    let (pa, pb, pc) = (at(a), at(b), at(c));
+    let mut i = next[c];
+    while i != a {
+        let point = at(i);
+        // Only reflex vertices can lie inside an ear of a simple polygon.
+        if point != pa
+            && point != pb
+            && point != pc
+            && orient(at(previous[i]), point, at(next[i])) <= 0.0
+            && in_triangle(pa, pb, pc, point)
+        {
+            return false;
+        }
+        i = next[i];
+    }
+    true
+}
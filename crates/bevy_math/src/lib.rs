@@ -33,6 +33,7 @@ pub mod common_traits;
 mod compass;
 pub mod cubic_splines;
 mod direction;
+mod fixed;
 mod float_ord;
 mod isometry;
 pub mod ops;
@@ -44,6 +45,9 @@ mod rotation2d;
 #[cfg(feature = "curve")]
 pub mod curve;
 
+#[cfg(feature = "alloc")]
+pub mod geometry2d;
+
 #[cfg(feature = "rand")]
 pub mod sampling;
 
@@ -52,6 +56,7 @@ pub use aspect_ratio::AspectRatio;
 pub use common_traits::*;
 pub use compass::{CompassOctant, CompassQuadrant};
 pub use direction::*;
+pub use fixed::{Fixed, FixedVec2, FixedVec3};
 pub use float_ord::*;
 pub use isometry::{Isometry2d, Isometry3d};
 pub use ops::FloatPow;
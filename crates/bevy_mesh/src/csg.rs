@@ -0,0 +1,638 @@
+//! Constructive solid geometry: boolean operations between closed meshes.
+//!
+//! The operations are implemented with binary space partitioning (BSP) trees, which split the
+//! triangles of each mesh along the planes of the triangles of the other mesh. They run on the
+//! CPU, so they can be used at runtime, or ahead of time with the [`CsgSettings`] stored in the
+//! settings of an asset processor.
+//!
+//! ```
+//! # use bevy_math::primitives::{Cuboid, Sphere};
+//! # use bevy_mesh::{csg::CsgSettings, Mesh, Meshable};
+//! let block = Mesh::from(Cuboid::new(2.0, 2.0, 2.0));
+//! let hole = Sphere::new(1.2).mesh().ico(3).unwrap();
+//!
+//! let carved = block.subtract(&hole).unwrap();
+//! let rounded = block.intersect_with_settings(&hole, &CsgSettings::FLAT).unwrap();
+//! ```
+
+use crate::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues};
+use alloc::vec::Vec;
+use bevy_math::{Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A boolean operation between two meshes, see [`Mesh::csg`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum CsgOperation {
+    /// The volume inside either mesh.
+    Union,
+    /// The volume inside the first mesh but outside the second mesh.
+    Subtract,
+    /// The volume inside both meshes.
+    Intersect,
+}
+
+/// Settings for the boolean operations between meshes.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Default)]
+pub struct CsgSettings {
+    /// The distance from a plane under which a vertex is considered to lie on the plane.
+    ///
+    /// Larger values avoid slivers when faces of both meshes are almost coplanar, at the cost of
+    /// small displacements of the vertices close to the faces of the other mesh.
+    pub epsilon: f32,
+    /// The area under which triangles are considered degenerate and discarded, both from the
+    /// inputs and from the result.
+    pub min_triangle_area: f32,
+    /// If true, the normals of the result are the normals of its faces. Otherwise, they are
+    /// interpolated from the normals of the input meshes, which keeps smooth surfaces smooth.
+    ///
+    /// Face normals are always used for an input mesh without normals.
+    pub flat_normals: bool,
+}
+
+impl Default for CsgSettings {
+    fn default() -> Self {
+        Self {
+            epsilon: 1e-5,
+            min_triangle_area: 1e-10,
+            flat_normals: false,
+        }
+    }
+}
+
+impl CsgSettings {
+    /// The default settings, with the normals of the faces of the result.
+    pub const FLAT: Self = Self {
+        epsilon: 1e-5,
+        min_triangle_area: 1e-10,
+        flat_normals: true,
+    };
+}
+
+/// An error that occurred while computing a boolean operation between meshes.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgError {
+    #[error("Source mesh does not have primitive topology TriangleList")]
+    WrongTopology,
+
+    #[error("Source mesh lacks position data")]
+    MissingPositions,
+
+    #[error("Source mesh position data is not Float32x3")]
+    PositionsFormat,
+
+    #[error("Face index data references vertices that do not exist")]
+    BadIndices,
+}
+
+impl Mesh {
+    /// Computes a boolean operation between this mesh and `other`, with the default
+    /// [`CsgSettings`].
+    ///
+    /// Both meshes must be closed and use the [`TriangleList`](PrimitiveTopology::TriangleList)
+    /// topology. The result has positions, normals, and UVs if both meshes have UVs. Its faces are
+    /// not merged, so it has more triangles than needed: simplify it if it is used often.
+    pub fn csg(&self, other: &Mesh, operation: CsgOperation) -> Result<Mesh, CsgError> {
+        self.csg_with_settings(other, operation, &CsgSettings::default())
+    }
+
+    /// Computes a boolean operation between this mesh and `other`, see [`Mesh::csg`].
+    pub fn csg_with_settings(
+        &self,
+        other: &Mesh,
+        operation: CsgOperation,
+        settings: &CsgSettings,
+    ) -> Result<Mesh, CsgError> {
+        let with_uvs = self.contains_attribute(Mesh::ATTRIBUTE_UV_0)
+            && other.contains_attribute(Mesh::ATTRIBUTE_UV_0);
+        let mut a = Bsp::new(read_polygons(self, settings)?, settings);
+        let mut b = Bsp::new(read_polygons(other, settings)?, settings);
+
+        let polygons = match operation {
+            CsgOperation::Union => {
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.into_polygons());
+                a.into_polygons()
+            }
+            CsgOperation::Subtract => {
+                a.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.into_polygons());
+                a.invert();
+                a.into_polygons()
+            }
+            CsgOperation::Intersect => {
+                a.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                a.build(b.into_polygons());
+                a.invert();
+                a.into_polygons()
+            }
+        };
+
+        Ok(write_polygons(
+            self,
+            polygons,
+            with_uvs,
+            settings.flat_normals,
+            settings.min_triangle_area,
+        ))
+    }
+
+    /// Computes the union of this mesh and `other`, see [`Mesh::csg`].
+    pub fn union(&self, other: &Mesh) -> Result<Mesh, CsgError> {
+        self.csg(other, CsgOperation::Union)
+    }
+
+    /// Computes the union of this mesh and `other` with the given settings, see [`Mesh::csg`].
+    pub fn union_with_settings(
+        &self,
+        other: &Mesh,
+        settings: &CsgSettings,
+    ) -> Result<Mesh, CsgError> {
+        self.csg_with_settings(other, CsgOperation::Union, settings)
+    }
+
+    /// Subtracts `other` from this mesh, see [`Mesh::csg`].
+    pub fn subtract(&self, other: &Mesh) -> Result<Mesh, CsgError> {
+        self.csg(other, CsgOperation::Subtract)
+    }
+
+    /// Subtracts `other` from this mesh with the given settings, see [`Mesh::csg`].
+    pub fn subtract_with_settings(
+        &self,
+        other: &Mesh,
+        settings: &CsgSettings,
+    ) -> Result<Mesh, CsgError> {
+        self.csg_with_settings(other, CsgOperation::Subtract, settings)
+    }
+
+    /// Computes the intersection of this mesh and `other`, see [`Mesh::csg`].
+    pub fn intersect(&self, other: &Mesh) -> Result<Mesh, CsgError> {
+        self.csg(other, CsgOperation::Intersect)
+    }
+
+    /// Computes the intersection of this mesh and `other` with the given settings, see
+    /// [`Mesh::csg`].
+    pub fn intersect_with_settings(
+        &self,
+        other: &Mesh,
+        settings: &CsgSettings,
+    ) -> Result<Mesh, CsgError> {
+        self.csg_with_settings(other, CsgOperation::Intersect, settings)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Vertex {
+    position: Vec3,
+    normal: Vec3,
+    uv: Vec2,
+}
+
+impl Vertex {
+    fn lerp(&self, other: &Vertex, t: f32) -> Vertex {
+        Vertex {
+            position: self.position.lerp(other.position, t),
+            normal: self.normal.lerp(other.normal, t),
+            uv: self.uv.lerp(other.uv, t),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Plane {
+    normal: Vec3,
+    distance: f32,
+}
+
+impl Plane {
+    fn flip(&mut self) {
+        self.normal = -self.normal;
+        self.distance = -self.distance;
+    }
+}
+
+/// A convex polygon.
+#[derive(Clone)]
+struct Polygon {
+    vertices: Vec<Vertex>,
+    plane: Plane,
+}
+
+impl Polygon {
+    fn flip(&mut self) {
+        self.vertices.reverse();
+        for vertex in &mut self.vertices {
+            vertex.normal = -vertex.normal;
+        }
+        self.plane.flip();
+    }
+}
+
+const COPLANAR: u8 = 0;
+const FRONT: u8 = 1;
+const BACK: u8 = 2;
+const SPANNING: u8 = 3;
+
+/// A side of a plane.
+enum Side {
+    Front,
+    Back,
+}
+
+/// The parts of a polygon split by a plane.
+#[derive(Default)]
+struct SplitPolygon {
+    /// The polygon, if it lies on the plane and faces the same way.
+    coplanar_front: Option<Polygon>,
+    /// The polygon, if it lies on the plane and faces the other way.
+    coplanar_back: Option<Polygon>,
+    front: Option<Polygon>,
+    back: Option<Polygon>,
+}
+
+/// Splits `polygon` by `plane`.
+fn split_polygon(plane: &Plane, polygon: Polygon, epsilon: f32) -> SplitPolygon {
+    let classes: Vec<u8> = polygon
+        .vertices
+        .iter()
+        .map(|vertex| {
+            let distance = plane.normal.dot(vertex.position) - plane.distance;
+            if distance < -epsilon {
+                BACK
+            } else if distance > epsilon {
+                FRONT
+            } else {
+                COPLANAR
+            }
+        })
+        .collect();
+
+    match classes
+        .iter()
+        .fold(COPLANAR, |class, vertex| class | vertex)
+    {
+        COPLANAR if plane.normal.dot(polygon.plane.normal) > 0.0 => SplitPolygon {
+            coplanar_front: Some(polygon),
+            ..Default::default()
+        },
+        COPLANAR => SplitPolygon {
+            coplanar_back: Some(polygon),
+            ..Default::default()
+        },
+        FRONT => SplitPolygon {
+            front: Some(polygon),
+            ..Default::default()
+        },
+        BACK => SplitPolygon {
+            back: Some(polygon),
+            ..Default::default()
+        },
+        _ => {
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            let len = polygon.vertices.len();
+            for i in 0..len {
+                let j = (i + 1) % len;
+                let (vi, vj) = (polygon.vertices[i], polygon.vertices[j]);
+                if classes[i] != BACK {
+                    front.push(vi);
+                }
+                if classes[i] != FRONT {
+                    back.push(vi);
+                }
+                if classes[i] | classes[j] == SPANNING {
+                    let t = (plane.distance - plane.normal.dot(vi.position))
+                        / plane.normal.dot(vj.position - vi.position);
+                    let vertex = vi.lerp(&vj, t);
+                    front.push(vertex);
+                    back.push(vertex);
+                }
+            }
+            SplitPolygon {
+                front: (front.len() >= 3).then_some(Polygon {
+                    vertices: front,
+                    plane: polygon.plane,
+                }),
+                back: (back.len() >= 3).then_some(Polygon {
+                    vertices: back,
+                    plane: polygon.plane,
+                }),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct BspNode {
+    plane: Option<Plane>,
+    front: Option<usize>,
+    back: Option<usize>,
+    polygons: Vec<Polygon>,
+}
+
+/// A BSP tree, with the nodes stored in a [`Vec`] and traversed without recursion, so that large
+/// meshes can't overflow the stack.
+struct Bsp {
+    nodes: Vec<BspNode>,
+    epsilon: f32,
+}
+
+impl Bsp {
+    fn new(polygons: Vec<Polygon>, settings: &CsgSettings) -> Self {
+        let mut bsp = Self {
+            nodes: Vec::new(),
+            epsilon: settings.epsilon,
+        };
+        bsp.build(polygons);
+        bsp
+    }
+
+    /// Adds `polygons` to the tree.
+    fn build(&mut self, polygons: Vec<Polygon>) {
+        if polygons.is_empty() {
+            return;
+        }
+        if self.nodes.is_empty() {
+            self.nodes.push(BspNode::default());
+        }
+
+        let mut stack = alloc::vec![(0, polygons)];
+        while let Some((index, polygons)) = stack.pop() {
+            let plane = *self.nodes[index].plane.get_or_insert(polygons[0].plane);
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                let split = split_polygon(&plane, polygon, self.epsilon);
+                let node = &mut self.nodes[index];
+                node.polygons.extend(split.coplanar_front);
+                node.polygons.extend(split.coplanar_back);
+                front.extend(split.front);
+                back.extend(split.back);
+            }
+
+            if !front.is_empty() {
+                let child = self.child(index, Side::Front);
+                stack.push((child, front));
+            }
+            if !back.is_empty() {
+                let child = self.child(index, Side::Back);
+                stack.push((child, back));
+            }
+        }
+    }
+
+    /// Returns the child of a node on the given side, creating it if needed.
+    fn child(&mut self, index: usize, side: Side) -> usize {
+        let new = self.nodes.len();
+        let child = match side {
+            Side::Front => &mut self.nodes[index].front,
+            Side::Back => &mut self.nodes[index].back,
+        };
+        match child {
+            Some(child) => *child,
+            None => {
+                *child = Some(new);
+                self.nodes.push(BspNode::default());
+                new
+            }
+        }
+    }
+
+    /// Swaps the inside and the outside of the solid.
+    fn invert(&mut self) {
+        for node in &mut self.nodes {
+            for polygon in &mut node.polygons {
+                polygon.flip();
+            }
+            if let Some(plane) = &mut node.plane {
+                plane.flip();
+            }
+            core::mem::swap(&mut node.front, &mut node.back);
+        }
+    }
+
+    /// Removes the parts of `polygons` inside the solid.
+    fn clip_polygons(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        if self.nodes.is_empty() {
+            return polygons;
+        }
+
+        let mut clipped = Vec::new();
+        let mut stack = alloc::vec![(0, polygons)];
+        while let Some((index, polygons)) = stack.pop() {
+            let node = &self.nodes[index];
+            let Some(plane) = node.plane else {
+                clipped.extend(polygons);
+                continue;
+            };
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                let split = split_polygon(&plane, polygon, self.epsilon);
+                front.extend(split.coplanar_front);
+                front.extend(split.front);
+                back.extend(split.coplanar_back);
+                back.extend(split.back);
+            }
+
+            match node.front {
+                Some(child) => stack.push((child, front)),
+                None => clipped.extend(front),
+            }
+            // Polygons behind a leaf are inside the solid.
+            if let Some(child) = node.back {
+                stack.push((child, back));
+            }
+        }
+        clipped
+    }
+
+    /// Removes the parts of the polygons of this tree inside the solid of `other`.
+    fn clip_to(&mut self, other: &Bsp) {
+        for node in &mut self.nodes {
+            node.polygons = other.clip_polygons(core::mem::take(&mut node.polygons));
+        }
+    }
+
+    fn into_polygons(self) -> Vec<Polygon> {
+        self.nodes
+            .into_iter()
+            .flat_map(|node| node.polygons)
+            .collect()
+    }
+}
+
+fn read_polygons(mesh: &Mesh, settings: &CsgSettings) -> Result<Vec<Polygon>, CsgError> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Err(CsgError::WrongTopology);
+    }
+    let positions = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .ok_or(CsgError::MissingPositions)?
+        .as_float3()
+        .ok_or(CsgError::PositionsFormat)?;
+    let normals = mesh
+        .attribute(Mesh::ATTRIBUTE_NORMAL)
+        .and_then(VertexAttributeValues::as_float3)
+        .filter(|normals| normals.len() == positions.len());
+    let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+        Some(VertexAttributeValues::Float32x2(uvs)) if uvs.len() == positions.len() => Some(uvs),
+        _ => None,
+    };
+
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+
+    let mut polygons = Vec::with_capacity(indices.len() / 3);
+    for triangle in indices.chunks_exact(3) {
+        if triangle.iter().any(|index| *index >= positions.len()) {
+            return Err(CsgError::BadIndices);
+        }
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| Vec3::from(positions[i]));
+        let cross = (b - a).cross(c - a);
+        if cross.length() * 0.5 <= settings.min_triangle_area {
+            continue;
+        }
+        let normal = cross.normalize();
+
+        let vertices = triangle
+            .iter()
+            .map(|&index| Vertex {
+                position: Vec3::from(positions[index]),
+                normal: normals.map_or(normal, |normals| Vec3::from(normals[index])),
+                uv: uvs.map_or(Vec2::ZERO, |uvs| Vec2::from(uvs[index])),
+            })
+            .collect();
+        polygons.push(Polygon {
+            vertices,
+            plane: Plane {
+                normal,
+                distance: normal.dot(a),
+            },
+        });
+    }
+    Ok(polygons)
+}
+
+fn write_polygons(
+    source: &Mesh,
+    polygons: Vec<Polygon>,
+    with_uvs: bool,
+    flat_normals: bool,
+    min_triangle_area: f32,
+) -> Mesh {
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+
+    for polygon in polygons {
+        let start = positions.len() as u32;
+        for vertex in &polygon.vertices {
+            positions.push(vertex.position.to_array());
+            let normal = if flat_normals {
+                polygon.plane.normal
+            } else {
+                vertex
+                    .normal
+                    .try_normalize()
+                    .unwrap_or(polygon.plane.normal)
+            };
+            normals.push(normal.to_array());
+            uvs.push(vertex.uv.to_array());
+        }
+
+        // The polygons are convex, so they can be triangulated as fans.
+        for i in 1..polygon.vertices.len() as u32 - 1 {
+            let [a, b, c] = [0, i, i + 1].map(|index| polygon.vertices[index as usize].position);
+            if (b - a).cross(c - a).length() * 0.5 > min_triangle_area {
+                indices.extend([start, start + i, start + i + 1]);
+            }
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, source.asset_usage)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_indices(Indices::U32(indices));
+    if with_uvs {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    }
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::primitives::Cuboid;
+
+    fn volume(mesh: &Mesh) -> f32 {
+        mesh.triangles()
+            .unwrap()
+            .map(|triangle| {
+                let [a, b, c] = triangle.vertices;
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn overlapping_cubes() {
+        let a = Mesh::from(Cuboid::new(2.0, 2.0, 2.0));
+        let b = Mesh::from(Cuboid::new(2.0, 2.0, 2.0)).translated_by(Vec3::X);
+
+        let union = a.union(&b).unwrap();
+        assert!((volume(&union) - 12.0).abs() < 1e-3);
+        assert!(union.contains_attribute(Mesh::ATTRIBUTE_UV_0));
+
+        let subtracted = a.subtract(&b).unwrap();
+        assert!((volume(&subtracted) - 4.0).abs() < 1e-3);
+
+        let intersected = a.intersect_with_settings(&b, &CsgSettings::FLAT).unwrap();
+        assert!((volume(&intersected) - 4.0).abs() < 1e-3);
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            intersected.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("Mesh does not have a normal attribute");
+        };
+        assert!(normals
+            .iter()
+            .all(|normal| Vec3::from(*normal).abs().max_element() > 0.999));
+    }
+
+    #[test]
+    fn disjoint_cubes() {
+        let a = Mesh::from(Cuboid::new(1.0, 1.0, 1.0));
+        let b = Mesh::from(Cuboid::new(1.0, 1.0, 1.0)).translated_by(Vec3::splat(3.0));
+
+        assert!((volume(&a.union(&b).unwrap()) - 2.0).abs() < 1e-3);
+        assert!((volume(&a.subtract(&b).unwrap()) - 1.0).abs() < 1e-3);
+        assert_eq!(a.intersect(&b).unwrap().count_vertices(), 0);
+    }
+
+    #[test]
+    fn wrong_topology() {
+        let a = Mesh::from(Cuboid::new(1.0, 1.0, 1.0));
+        let b = Mesh::new(PrimitiveTopology::LineList, a.asset_usage);
+        assert_eq!(a.union(&b).unwrap_err(), CsgError::WrongTopology);
+    }
+}
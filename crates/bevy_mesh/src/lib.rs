@@ -4,7 +4,9 @@ extern crate alloc;
 extern crate core;
 
 mod conversions;
+pub mod csg;
 mod index;
+pub mod lightmap_uvs;
 mod mesh;
 mod mikktspace;
 pub mod morph;
@@ -0,0 +1,368 @@
+//! Generation of the second UV channel of meshes, used to apply lightmaps.
+//!
+//! Lightmaps need UVs in which no two triangles overlap, and in which each triangle covers an area
+//! of the texture proportional to its area in the world, which the UVs used by materials rarely
+//! provide. [`Mesh::generate_lightmap_uvs`] splits a mesh into charts, projects each chart onto the
+//! plane it faces the most, and packs the charts into a single texture.
+//!
+//! ```
+//! # use bevy_math::primitives::Cuboid;
+//! # use bevy_mesh::{lightmap_uvs::LightmapUvSettings, Mesh};
+//! let mut mesh = Mesh::from(Cuboid::new(2.0, 1.0, 1.0));
+//!
+//! let size = mesh.generate_lightmap_uvs(&LightmapUvSettings::default()).unwrap();
+//! assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_1).is_some());
+//! ```
+
+use crate::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues};
+use alloc::vec::Vec;
+use bevy_math::{ops, UVec2, Vec2, Vec3};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Settings for the generation of lightmap UVs, see [`Mesh::generate_lightmap_uvs`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Default)]
+pub struct LightmapUvSettings {
+    /// The number of lightmap texels per unit of length of the mesh.
+    ///
+    /// The default is `16.0`.
+    pub texels_per_unit: f32,
+    /// The number of texels left empty around each chart, so that the texels of the charts don't
+    /// bleed into each other when the lightmap is filtered.
+    ///
+    /// The default is `2`.
+    pub padding: u32,
+    /// The maximum width and height of the lightmap, in texels.
+    ///
+    /// If the charts don't fit at `texels_per_unit`, they are scaled down until they do.
+    ///
+    /// The default is `2048`.
+    pub max_size: u32,
+}
+
+impl Default for LightmapUvSettings {
+    fn default() -> Self {
+        Self {
+            texels_per_unit: 16.0,
+            padding: 2,
+            max_size: 2048,
+        }
+    }
+}
+
+/// An error generating the lightmap UVs of a mesh, see [`Mesh::generate_lightmap_uvs`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LightmapUvError {
+    /// Lightmap UVs can only be generated for triangle lists.
+    #[error("Lightmap UVs can only be generated for meshes with a `TriangleList` topology, found `{0:?}`")]
+    WrongTopology(PrimitiveTopology),
+    /// The mesh has no positions.
+    #[error("The mesh has no `Mesh::ATTRIBUTE_POSITION`")]
+    MissingPositions,
+    /// The positions of the mesh aren't in the expected format.
+    #[error("The `Mesh::ATTRIBUTE_POSITION` of the mesh isn't in the `Float32x3` format")]
+    PositionsFormat,
+}
+
+/// A group of connected triangles facing the same axis, laid out as a single island of the
+/// lightmap.
+struct Chart {
+    /// The axis the triangles are projected along, from 0 to 2.
+    axis: usize,
+    /// The minimum of the projected positions.
+    min: Vec2,
+    /// The size of the projected positions, in mesh units.
+    extent: Vec2,
+    /// The position of the chart in the lightmap, in texels.
+    offset: UVec2,
+}
+
+impl Chart {
+    fn project(&self, position: Vec3) -> Vec2 {
+        match self.axis {
+            0 => Vec2::new(position.z, position.y),
+            1 => Vec2::new(position.x, position.z),
+            _ => Vec2::new(position.x, position.y),
+        }
+    }
+
+    /// The size of the chart in texels, without padding, at `scale` texels per unit.
+    fn size(&self, scale: f32) -> UVec2 {
+        (self.extent * scale).ceil().as_uvec2() + UVec2::ONE
+    }
+}
+
+impl Mesh {
+    /// Generates the second UV channel of the mesh, [`Mesh::ATTRIBUTE_UV_1`], for a lightmap, and
+    /// returns the size of the lightmap in texels.
+    ///
+    /// The triangles are grouped into charts of connected triangles facing the same axis, which
+    /// are projected along that axis and packed without overlap. The vertices shared between
+    /// charts are duplicated, so the mesh has indices afterwards, and the attributes of the
+    /// vertices are preserved.
+    ///
+    /// The size of the lightmap may exceed [`LightmapUvSettings::max_size`] if the mesh has too
+    /// many charts to fit in it, even with a single texel each.
+    pub fn generate_lightmap_uvs(
+        &mut self,
+        settings: &LightmapUvSettings,
+    ) -> Result<UVec2, LightmapUvError> {
+        if self.primitive_topology() != PrimitiveTopology::TriangleList {
+            return Err(LightmapUvError::WrongTopology(self.primitive_topology()));
+        }
+        let positions: Vec<Vec3> = match self.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => {
+                positions.iter().copied().map(Vec3::from).collect()
+            }
+            Some(_) => return Err(LightmapUvError::PositionsFormat),
+            None => return Err(LightmapUvError::MissingPositions),
+        };
+        let indices: Vec<u32> = match self.indices() {
+            Some(indices) => indices.iter().map(|index| index as u32).collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        let triangle_count = indices.len() / 3;
+
+        // Vertices with the same position, which are often split for their normals or UVs, must be
+        // welded to find which triangles are connected.
+        let mut welded_ids = HashMap::<[u32; 3], u32>::default();
+        let welded: Vec<u32> = positions
+            .iter()
+            .map(|position| {
+                let key = position.to_array().map(f32::to_bits);
+                let next_id = welded_ids.len() as u32;
+                *welded_ids.entry(key).or_insert(next_id)
+            })
+            .collect();
+
+        // Each triangle is classified by the axis and the direction it faces the most.
+        let classes: Vec<usize> = indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+                let normal = (b - a).cross(c - a);
+                let abs = normal.abs();
+                let axis = if abs.x >= abs.y && abs.x >= abs.z {
+                    0
+                } else if abs.y >= abs.z {
+                    1
+                } else {
+                    2
+                };
+                axis * 2 + usize::from(normal[axis] < 0.0)
+            })
+            .collect();
+
+        // Triangles sharing an edge and facing the same axis belong to the same chart.
+        let mut parents: Vec<usize> = (0..triangle_count).collect();
+        let mut edges = HashMap::<(u32, u32, usize), usize>::default();
+        for (triangle, vertices) in indices.chunks_exact(3).enumerate() {
+            for i in 0..3 {
+                let a = welded[vertices[i] as usize];
+                let b = welded[vertices[(i + 1) % 3] as usize];
+                let key = (a.min(b), a.max(b), classes[triangle]);
+                match edges.get(&key) {
+                    Some(&other) => union(&mut parents, triangle, other),
+                    None => {
+                        edges.insert(key, triangle);
+                    }
+                }
+            }
+        }
+
+        let mut chart_ids = HashMap::<usize, usize>::default();
+        let mut charts = Vec::new();
+        let mut triangle_charts = Vec::with_capacity(triangle_count);
+        for (triangle, class) in classes.iter().enumerate() {
+            let root = find(&mut parents, triangle);
+            let chart = *chart_ids.entry(root).or_insert_with(|| {
+                charts.push(Chart {
+                    axis: class / 2,
+                    min: Vec2::splat(f32::MAX),
+                    extent: Vec2::splat(f32::MIN),
+                    offset: UVec2::ZERO,
+                });
+                charts.len() - 1
+            });
+            triangle_charts.push(chart);
+        }
+
+        // Compute the bounds of the charts, storing the maximum in `extent` until all triangles
+        // are visited.
+        for (vertices, &chart) in indices.chunks_exact(3).zip(&triangle_charts) {
+            let chart = &mut charts[chart];
+            for &vertex in vertices {
+                let projected = chart.project(positions[vertex as usize]);
+                chart.min = chart.min.min(projected);
+                chart.extent = chart.extent.max(projected);
+            }
+        }
+        for chart in &mut charts {
+            chart.extent -= chart.min;
+        }
+
+        let mut scale = settings.texels_per_unit.max(f32::EPSILON);
+        let size = loop {
+            let size = pack_charts(&mut charts, scale, settings.padding);
+            let fits = size.max_element() <= settings.max_size;
+            let smallest = charts.iter().all(|chart| chart.size(scale) == UVec2::ONE);
+            if fits || smallest {
+                break size;
+            }
+            scale *= 0.9 * settings.max_size as f32 / size.max_element() as f32;
+        };
+
+        // Split the vertices shared between charts, and compute their UVs.
+        let mut new_vertices = HashMap::<(u32, usize), u32>::default();
+        let mut remap = Vec::new();
+        let mut uvs = Vec::new();
+        let mut new_indices = Vec::with_capacity(indices.len());
+        for (vertices, &chart_index) in indices.chunks_exact(3).zip(&triangle_charts) {
+            let chart = &charts[chart_index];
+            for &vertex in vertices {
+                let new_vertex = *new_vertices
+                    .entry((vertex, chart_index))
+                    .or_insert_with(|| {
+                        let texel = (chart.project(positions[vertex as usize]) - chart.min) * scale
+                            + chart.offset.as_vec2()
+                            + 0.5;
+                        remap.push(vertex);
+                        uvs.push((texel / size.as_vec2()).to_array());
+                        remap.len() as u32 - 1
+                    });
+                new_indices.push(new_vertex);
+            }
+        }
+
+        self.insert_indices(Indices::U32(remap));
+        self.duplicate_vertices();
+        self.insert_indices(Indices::U32(new_indices));
+        self.insert_attribute(Mesh::ATTRIBUTE_UV_1, uvs);
+
+        Ok(size)
+    }
+}
+
+/// Places the charts in rows, from the tallest to the shortest, and returns the size of the
+/// lightmap.
+fn pack_charts(charts: &mut [Chart], scale: f32, padding: u32) -> UVec2 {
+    let padded_sizes: Vec<UVec2> = charts
+        .iter()
+        .map(|chart| chart.size(scale) + padding)
+        .collect();
+    let area: f32 = padded_sizes
+        .iter()
+        .map(|size| size.x as f32 * size.y as f32)
+        .sum();
+    let widest = padded_sizes.iter().map(|size| size.x).max().unwrap_or(0);
+    let width = (ops::sqrt(area) * 1.05).ceil() as u32;
+    let width = width.max(widest) + padding;
+
+    let mut order: Vec<usize> = (0..charts.len()).collect();
+    order.sort_by_key(|&chart| core::cmp::Reverse(padded_sizes[chart].y));
+
+    let mut cursor = UVec2::splat(padding);
+    let mut row_height = 0;
+    for chart in order {
+        let size = padded_sizes[chart];
+        if cursor.x + size.x > width {
+            cursor = UVec2::new(padding, cursor.y + row_height);
+            row_height = 0;
+        }
+        charts[chart].offset = cursor;
+        cursor.x += size.x;
+        row_height = row_height.max(size.y);
+    }
+
+    UVec2::new(width, cursor.y + row_height).max(UVec2::ONE)
+}
+
+fn find(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let a = find(parents, a);
+    let b = find(parents, b);
+    parents[a.max(b)] = a.min(b);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LightmapUvSettings;
+    use crate::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues};
+    use bevy_asset::RenderAssetUsages;
+    use bevy_math::{primitives::Cuboid, Vec2};
+
+    #[test]
+    fn cuboid_lightmap_uvs() {
+        let mut mesh = Mesh::from(Cuboid::new(2.0, 1.0, 0.5));
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION).cloned()
+        else {
+            panic!("The cuboid positions are missing");
+        };
+
+        let settings = LightmapUvSettings::default();
+        let size = mesh.generate_lightmap_uvs(&settings).unwrap();
+        assert!(size.max_element() <= settings.max_size);
+
+        // The faces of a cuboid are already split, so no vertex is added.
+        let Some(VertexAttributeValues::Float32x3(new_positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("The positions are missing");
+        };
+        assert_eq!(new_positions, &positions);
+
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_1)
+        else {
+            panic!("The lightmap UVs are missing");
+        };
+        assert!(uvs
+            .iter()
+            .all(|uv| (0.0..=1.0).contains(&uv[0]) && (0.0..=1.0).contains(&uv[1])));
+
+        // The texel density is preserved: the largest face is 2 by 1 units.
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        let largest = indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| Vec2::from(uvs[triangle[i]]) * size.as_vec2());
+                (b - a).perp_dot(c - a).abs() / 2.0
+            })
+            .fold(0.0, f32::max);
+        let expected = 2.0 * 1.0 / 2.0 * settings.texels_per_unit * settings.texels_per_unit;
+        assert!((largest - expected).abs() < 1.0);
+    }
+
+    #[test]
+    fn shared_vertices_are_split() {
+        // Two triangles sharing an edge, folded at a right angle.
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 0.0, -1.0],
+                [0.0, 1.0, 0.0],
+            ],
+        )
+        .with_inserted_indices(Indices::U32(vec![0, 1, 2, 0, 3, 1]));
+
+        mesh.generate_lightmap_uvs(&LightmapUvSettings::default())
+            .unwrap();
+        assert_eq!(mesh.count_vertices(), 6);
+    }
+}
@@ -5,6 +5,7 @@ use bevy_asset::RenderAssetUsages;
 
 use super::{Extrudable, MeshBuilder, Meshable};
 use bevy_math::{
+    geometry2d::{ComplexPolygon, Triangulation},
     ops,
     primitives::{
         Annulus, Capsule2d, Circle, CircularSector, CircularSegment, ConvexPolygon, Ellipse,
@@ -457,6 +458,82 @@ impl<const N: usize> From<ConvexPolygon<N>> for Mesh {
     }
 }
 
+/// A builder used for creating a [`Mesh`] with a [`ComplexPolygon`] shape.
+///
+/// The polygon must be normalized, which is the case of the polygons returned by the boolean
+/// operations and offsetting of [`ComplexPolygon`]. Texture coordinates map the bounding box of
+/// the polygon to the unit square.
+#[derive(Clone, Debug, Default, Reflect)]
+#[reflect(Default, Debug)]
+pub struct ComplexPolygonMeshBuilder {
+    /// The [`ComplexPolygon`] shape.
+    pub polygon: ComplexPolygon,
+}
+
+impl Meshable for ComplexPolygon {
+    type Output = ComplexPolygonMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        Self::Output {
+            polygon: self.clone(),
+        }
+    }
+}
+
+impl MeshBuilder for ComplexPolygonMeshBuilder {
+    fn build(&self) -> Mesh {
+        let Triangulation { vertices, indices } = self.polygon.triangulate();
+
+        let min = vertices.iter().copied().fold(Vec2::MAX, Vec2::min);
+        let max = vertices.iter().copied().fold(Vec2::MIN, Vec2::max);
+        let size = (max - min).max(Vec2::splat(f32::EPSILON));
+
+        let positions: Vec<_> = vertices
+            .iter()
+            .map(|vertex| [vertex.x, vertex.y, 0.0])
+            .collect();
+        let normals = vec![[0.0, 0.0, 1.0]; vertices.len()];
+        let uvs: Vec<_> = vertices
+            .iter()
+            .map(|vertex| {
+                let uv = (*vertex - min) / size;
+                [uv.x, 1.0 - uv.y]
+            })
+            .collect();
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+impl Extrudable for ComplexPolygonMeshBuilder {
+    fn perimeter(&self) -> Vec<PerimeterSegment> {
+        let mut start = 0;
+        self.polygon
+            .contours
+            .iter()
+            .filter_map(|contour| {
+                let end = start + contour.len() as u32;
+                let indices = (start..end).chain([start]).collect();
+                start = end;
+                (contour.len() >= 3).then_some(PerimeterSegment::Flat { indices })
+            })
+            .collect()
+    }
+}
+
+impl From<ComplexPolygon> for Mesh {
+    fn from(polygon: ComplexPolygon) -> Self {
+        polygon.mesh().build()
+    }
+}
+
 /// A builder used for creating a [`Mesh`] with a [`RegularPolygon`] shape.
 #[derive(Clone, Copy, Debug, Reflect)]
 #[reflect(Default, Debug)]
@@ -1175,7 +1252,12 @@ impl From<Capsule2d> for Mesh {
 
 #[cfg(test)]
 mod tests {
-    use bevy_math::{prelude::Annulus, primitives::RegularPolygon, FloatOrd};
+    use bevy_math::{
+        geometry2d::ComplexPolygon,
+        prelude::Annulus,
+        primitives::{Rectangle, RegularPolygon},
+        FloatOrd,
+    };
     use bevy_platform_support::collections::HashSet;
 
     use crate::{Mesh, MeshBuilder, Meshable, VertexAttributeValues};
@@ -1203,6 +1285,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_complex_polygon() {
+        let frame = ComplexPolygon::from(Rectangle::new(3.0, 3.0))
+            .difference(&ComplexPolygon::from(Rectangle::new(1.0, 1.0)));
+        let mesh = frame.mesh().build();
+
+        assert_eq!(
+            8,
+            count_distinct_positions(
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                    .unwrap()
+                    .as_float3()
+                    .unwrap()
+            )
+        );
+        // A square with a hole is covered by eight triangles.
+        assert_eq!(24, mesh.indices().unwrap().len());
+    }
+
     /// Sin/cos and multiplication computations result in numbers like 0.4999999.
     /// Round these to numbers we expect like 0.5.
     fn fix_floats<const N: usize>(points: &mut [[f32; N]]) {
@@ -1,6 +1,6 @@
 use crate::{Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
 use bevy_asset::RenderAssetUsages;
-use bevy_math::{primitives::Cuboid, Vec3};
+use bevy_math::{ops, primitives::Cuboid, Vec3};
 use bevy_reflect::prelude::*;
 
 /// A builder used for creating a [`Mesh`] with a [`Cuboid`] shape.
@@ -19,6 +19,19 @@ impl Default for CuboidMeshBuilder {
     }
 }
 
+impl CuboidMeshBuilder {
+    /// Creates a [`RoundedCuboidMeshBuilder`] with the size of this cuboid, and edges and corners
+    /// rounded with the given radius.
+    #[inline]
+    pub fn rounded(self, radius: f32) -> RoundedCuboidMeshBuilder {
+        RoundedCuboidMeshBuilder {
+            half_size: self.half_size,
+            radius,
+            ..Default::default()
+        }
+    }
+}
+
 impl MeshBuilder for CuboidMeshBuilder {
     fn build(&self) -> Mesh {
         let min = -self.half_size;
@@ -97,3 +110,143 @@ impl From<Cuboid> for Mesh {
         cuboid.mesh().build()
     }
 }
+
+/// A builder used for creating a [`Mesh`] with a [`Cuboid`] shape with rounded edges and corners.
+///
+/// Each face of the cuboid has its own UVs, covering the whole texture like a [`Cuboid`] mesh.
+#[derive(Clone, Copy, Debug, Reflect)]
+#[reflect(Default, Debug)]
+pub struct RoundedCuboidMeshBuilder {
+    /// Half of the width, height and depth of the cuboid, including the rounded edges.
+    pub half_size: Vec3,
+    /// The radius of the rounded edges and corners.
+    ///
+    /// It is clamped to the smallest half size. The default is `0.1`.
+    pub radius: f32,
+    /// The number of segments used for each rounded edge.
+    ///
+    /// The default is `4`.
+    pub resolution: u32,
+}
+
+impl Default for RoundedCuboidMeshBuilder {
+    /// Returns the default [`RoundedCuboidMeshBuilder`] with a width, height, and depth of `1.0`,
+    /// and a radius of `0.1`.
+    fn default() -> Self {
+        Self {
+            half_size: Vec3::splat(0.5),
+            radius: 0.1,
+            resolution: 4,
+        }
+    }
+}
+
+impl RoundedCuboidMeshBuilder {
+    /// Creates a new [`RoundedCuboidMeshBuilder`] from a full size and the radius of the rounded
+    /// edges and corners.
+    #[inline]
+    pub fn new(size: Vec3, radius: f32) -> Self {
+        Self {
+            half_size: size / 2.0,
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the number of segments used for each rounded edge.
+    #[inline]
+    pub const fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+}
+
+impl MeshBuilder for RoundedCuboidMeshBuilder {
+    fn build(&self) -> Mesh {
+        let radius = self.radius.clamp(0.0, self.half_size.min_element());
+        let inner = self.half_size - radius;
+        let resolution = if radius > 0.0 {
+            self.resolution.max(1)
+        } else {
+            0
+        };
+
+        // The coordinates of the vertices along an axis. Each face is a grid which is projected on
+        // the rounded cuboid. The rounded part of a face covers 45 degrees of the edge, split in
+        // segments of the same angle.
+        let coordinates = |inner: f32| {
+            let rounded: Vec<f32> = (0..=resolution)
+                .map(|i| {
+                    let angle = i as f32 / resolution.max(1) as f32 * core::f32::consts::FRAC_PI_4;
+                    inner + radius * ops::tan(angle)
+                })
+                .collect();
+            let skip = if inner > 0.0 { 0 } else { 1 };
+            rounded
+                .iter()
+                .rev()
+                .map(|coordinate| -coordinate)
+                .chain(rounded.iter().skip(skip).copied())
+                .collect::<Vec<f32>>()
+        };
+        let coordinates = [
+            coordinates(inner.x),
+            coordinates(inner.y),
+            coordinates(inner.z),
+        ];
+
+        // The normal and the U and V directions of each face, with the index of their axis.
+        let faces = [
+            (Vec3::Z, Vec3::X, Vec3::Y, [2, 0, 1]),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y, [2, 0, 1]),
+            (Vec3::X, Vec3::NEG_Z, Vec3::Y, [0, 2, 1]),
+            (Vec3::NEG_X, Vec3::Z, Vec3::Y, [0, 2, 1]),
+            (Vec3::Y, Vec3::X, Vec3::NEG_Z, [1, 0, 2]),
+            (Vec3::NEG_Y, Vec3::X, Vec3::Z, [1, 0, 2]),
+        ];
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = Vec::new();
+
+        for (face_normal, u, v, [normal_axis, u_axis, v_axis]) in faces {
+            let (u_coordinates, v_coordinates) = (&coordinates[u_axis], &coordinates[v_axis]);
+            let (u_last, v_last) = (u_coordinates.len() - 1, v_coordinates.len() - 1);
+            let start = positions.len() as u32;
+
+            for (j, v_coordinate) in v_coordinates.iter().enumerate() {
+                for (i, u_coordinate) in u_coordinates.iter().enumerate() {
+                    let point = face_normal * self.half_size[normal_axis]
+                        + u * *u_coordinate
+                        + v * *v_coordinate;
+                    let core = point.clamp(-inner, inner);
+                    let normal = (point - core).try_normalize().unwrap_or(face_normal);
+                    positions.push((core + normal * radius).to_array());
+                    normals.push(normal.to_array());
+                    uvs.push([i as f32 / u_last as f32, 1.0 - j as f32 / v_last as f32]);
+                }
+            }
+
+            let row = u_last as u32 + 1;
+            for j in 0..v_last as u32 {
+                for i in 0..u_last as u32 {
+                    let a = start + j * row + i;
+                    let b = a + 1;
+                    let c = b + row;
+                    let d = a + row;
+                    indices.extend([a, b, c, c, d, a]);
+                }
+            }
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
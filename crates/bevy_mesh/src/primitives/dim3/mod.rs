@@ -7,7 +7,9 @@ mod plane;
 mod sphere;
 mod tetrahedron;
 mod torus;
+mod torus_knot;
 pub(crate) mod triangle3d;
+mod tube;
 
 pub use capsule::*;
 pub use cone::*;
@@ -18,4 +20,6 @@ pub use plane::*;
 pub use sphere::*;
 pub use tetrahedron::*;
 pub use torus::*;
+pub use torus_knot::*;
 pub use triangle3d::*;
+pub use tube::*;
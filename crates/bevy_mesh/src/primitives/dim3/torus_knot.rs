@@ -0,0 +1,119 @@
+use crate::{Mesh, MeshBuilder, TubeCaps, TubeMeshBuilder};
+use bevy_math::{ops, Vec3};
+use bevy_reflect::prelude::*;
+
+/// A builder used for creating a [`Mesh`] with a torus knot shape: a tube winding around the ring
+/// of a torus.
+///
+/// The knot winds `p` times around the axis of the torus, which is the Y axis, and `q` times
+/// around its ring. When `p` and `q` are coprime, the knot is a single closed tube; a `(2, 3)` knot
+/// is a trefoil.
+#[derive(Clone, Copy, Debug, Reflect)]
+#[reflect(Default, Debug)]
+pub struct TorusKnotMeshBuilder {
+    /// The number of times the knot winds around the axis of the torus.
+    ///
+    /// The default is `2`.
+    pub p: u32,
+    /// The number of times the knot winds around the ring of the torus.
+    ///
+    /// The default is `3`.
+    pub q: u32,
+    /// The radius of the ring of the torus, from its center to the center of the tube.
+    ///
+    /// The default is `1.0`.
+    pub major_radius: f32,
+    /// The distance between the knot and the ring of the torus.
+    ///
+    /// The default is `0.4`.
+    pub minor_radius: f32,
+    /// The radius of the tube of the knot.
+    ///
+    /// The default is `0.15`.
+    pub tube_radius: f32,
+    /// The number of segments along the knot.
+    ///
+    /// The default is `128`.
+    pub tubular_resolution: u32,
+    /// The number of vertices used for each circular segment of the tube.
+    ///
+    /// The default is `16`.
+    pub radial_resolution: u32,
+}
+
+impl Default for TorusKnotMeshBuilder {
+    fn default() -> Self {
+        Self {
+            p: 2,
+            q: 3,
+            major_radius: 1.0,
+            minor_radius: 0.4,
+            tube_radius: 0.15,
+            tubular_resolution: 128,
+            radial_resolution: 16,
+        }
+    }
+}
+
+impl TorusKnotMeshBuilder {
+    /// Creates a new [`TorusKnotMeshBuilder`] winding `p` times around the axis of the torus and
+    /// `q` times around its ring.
+    #[inline]
+    pub fn new(p: u32, q: u32) -> Self {
+        Self {
+            p,
+            q,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the radius of the ring of the torus, and the distance between the knot and the ring.
+    #[inline]
+    pub const fn radii(mut self, major_radius: f32, minor_radius: f32) -> Self {
+        self.major_radius = major_radius;
+        self.minor_radius = minor_radius;
+        self
+    }
+
+    /// Sets the radius of the tube of the knot.
+    #[inline]
+    pub const fn tube_radius(mut self, tube_radius: f32) -> Self {
+        self.tube_radius = tube_radius;
+        self
+    }
+
+    /// Sets the number of segments along the knot.
+    #[inline]
+    pub const fn tubular_resolution(mut self, resolution: u32) -> Self {
+        self.tubular_resolution = resolution;
+        self
+    }
+
+    /// Sets the number of vertices used for each circular segment of the tube.
+    #[inline]
+    pub const fn radial_resolution(mut self, resolution: u32) -> Self {
+        self.radial_resolution = resolution;
+        self
+    }
+
+    /// Returns the point of the knot at `t`, which goes from `0.0` to `1.0` along the knot.
+    pub fn point(&self, t: f32) -> Vec3 {
+        let angle = t * core::f32::consts::TAU;
+        let (sin_p, cos_p) = ops::sin_cos(angle * self.p as f32);
+        let (sin_q, cos_q) = ops::sin_cos(angle * self.q as f32);
+        let radius = self.major_radius + self.minor_radius * cos_q;
+        Vec3::new(radius * cos_p, self.minor_radius * sin_q, radius * sin_p)
+    }
+}
+
+impl MeshBuilder for TorusKnotMeshBuilder {
+    fn build(&self) -> Mesh {
+        let segments = self.tubular_resolution.max(3);
+        let path = (0..segments).map(|i| self.point(i as f32 / segments as f32));
+        TubeMeshBuilder::new(path, self.tube_radius)
+            .resolution(self.radial_resolution)
+            .closed(true)
+            .caps(TubeCaps::None)
+            .build()
+    }
+}
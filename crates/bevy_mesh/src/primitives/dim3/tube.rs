@@ -0,0 +1,368 @@
+use crate::{Indices, Mesh, MeshBuilder, PrimitiveTopology};
+use bevy_asset::RenderAssetUsages;
+use bevy_math::{
+    curve::{Curve, CurveExt, ResamplingError},
+    ops, Quat, Vec3,
+};
+use bevy_reflect::prelude::*;
+
+/// The shape of the ends of an open tube.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum TubeCaps {
+    /// The ends are left open.
+    None,
+    /// The ends are closed by flat disks.
+    #[default]
+    Flat,
+    /// The ends are closed by hemispheres, making a capsule along the path.
+    Round,
+}
+
+/// A builder used for creating a [`Mesh`] with a tube following a path.
+///
+/// The tube is a circle swept along the path, with its orientation carried along the path without
+/// twisting. The U coordinate of the UVs goes around the tube, and the V coordinate along the path.
+///
+/// ```
+/// # use bevy_math::{vec3, curve::FunctionCurve, curve::Interval, ops};
+/// # use bevy_mesh::{Mesh, MeshBuilder, TubeCaps, TubeMeshBuilder};
+/// // A capsule bent at a right angle.
+/// let pipe: Mesh = TubeMeshBuilder::new(
+///     [vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(1.0, 1.0, 0.0)],
+///     0.1,
+/// )
+/// .caps(TubeCaps::Round)
+/// .build();
+///
+/// // A helix.
+/// let helix = FunctionCurve::new(Interval::new(0.0, 10.0).unwrap(), |t| {
+///     vec3(ops::cos(t), t * 0.1, ops::sin(t))
+/// });
+/// let spring = TubeMeshBuilder::from_curve(&helix, 100, 0.05).unwrap().build();
+/// ```
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default, Debug)]
+pub struct TubeMeshBuilder {
+    /// The points of the path followed by the center of the tube.
+    pub path: Vec<Vec3>,
+    /// The radius of the tube.
+    pub radius: f32,
+    /// The number of vertices used for each circular segment of the tube.
+    ///
+    /// The default is `16`.
+    pub resolution: u32,
+    /// Whether the path loops back to its first point.
+    ///
+    /// The default is `false`.
+    pub closed: bool,
+    /// The shape of the ends of the tube, if it isn't closed.
+    ///
+    /// The default is [`TubeCaps::Flat`].
+    pub caps: TubeCaps,
+}
+
+impl Default for TubeMeshBuilder {
+    fn default() -> Self {
+        Self {
+            path: Vec::new(),
+            radius: 0.5,
+            resolution: 16,
+            closed: false,
+            caps: TubeCaps::default(),
+        }
+    }
+}
+
+impl TubeMeshBuilder {
+    /// Creates a new [`TubeMeshBuilder`] following the given path, with the given radius.
+    #[inline]
+    pub fn new(path: impl IntoIterator<Item = Vec3>, radius: f32) -> Self {
+        Self {
+            path: path.into_iter().collect(),
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new [`TubeMeshBuilder`] following a curve, sampled at `samples` evenly spaced
+    /// points of its domain.
+    ///
+    /// If `samples` is less than 2 or if the curve has an unbounded domain, a
+    /// [`ResamplingError`] is returned.
+    pub fn from_curve(
+        curve: &impl Curve<Vec3>,
+        samples: usize,
+        radius: f32,
+    ) -> Result<Self, ResamplingError> {
+        Ok(Self::new(curve.samples(samples)?, radius))
+    }
+
+    /// Sets the number of vertices used for each circular segment of the tube.
+    #[inline]
+    pub const fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets whether the path loops back to its first point.
+    #[inline]
+    pub const fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Sets the shape of the ends of the tube, if it isn't closed.
+    #[inline]
+    pub const fn caps(mut self, caps: TubeCaps) -> Self {
+        self.caps = caps;
+        self
+    }
+}
+
+/// A circle of vertices of the tube.
+struct Ring {
+    center: Vec3,
+    tangent: Vec3,
+    normal: Vec3,
+    binormal: Vec3,
+    radius: f32,
+    /// The angle between the normals of the vertices and the plane of the ring, for round caps.
+    tilt: f32,
+    v: f32,
+}
+
+impl MeshBuilder for TubeMeshBuilder {
+    fn build(&self) -> Mesh {
+        let mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+
+        // Consecutive duplicate points have no tangent.
+        let mut path = self.path.clone();
+        path.dedup();
+        if self.closed && path.len() > 1 && path.first() == path.last() {
+            path.pop();
+        }
+        if path.len() < 2 {
+            return mesh
+                .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new())
+                .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, Vec::<[f32; 3]>::new())
+                .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, Vec::<[f32; 2]>::new())
+                .with_inserted_indices(Indices::U32(Vec::new()));
+        }
+
+        let len = path.len();
+        let resolution = self.resolution.max(3);
+        let point = |i: usize| path[i % len];
+
+        let tangents: Vec<Vec3> = (0..len)
+            .map(|i| {
+                let (previous, next) = if self.closed {
+                    (point(i + len - 1), point(i + 1))
+                } else {
+                    (path[i.saturating_sub(1)], path[(i + 1).min(len - 1)])
+                };
+                (next - previous)
+                    .try_normalize()
+                    .unwrap_or_else(|| (path[1] - path[0]).normalize())
+            })
+            .collect();
+
+        // Parallel transport of the normal along the path, rotating it as little as possible.
+        let mut frame_normals = Vec::with_capacity(len);
+        frame_normals.push(tangents[0].any_orthonormal_vector());
+        for i in 1..len {
+            let rotation = Quat::from_rotation_arc(tangents[i - 1], tangents[i]);
+            frame_normals.push((rotation * frame_normals[i - 1]).normalize());
+        }
+        if self.closed {
+            // Spread the twist between the last and the first frames along the path, so that the
+            // tube joins without a seam.
+            let end =
+                Quat::from_rotation_arc(tangents[len - 1], tangents[0]) * frame_normals[len - 1];
+            let start = frame_normals[0];
+            let twist = ops::atan2(tangents[0].dot(end.cross(start)), end.dot(start));
+            for (i, normal) in frame_normals.iter_mut().enumerate() {
+                let angle = twist * i as f32 / len as f32;
+                *normal = Quat::from_axis_angle(tangents[i], angle) * *normal;
+            }
+        }
+
+        let mut distances = Vec::with_capacity(len + 1);
+        distances.push(0.0);
+        for i in 1..=len {
+            let segment = if i < len || self.closed {
+                point(i).distance(point(i - 1))
+            } else {
+                0.0
+            };
+            distances.push(distances[i - 1] + segment);
+        }
+        let total_length = distances[if self.closed { len } else { len - 1 }].max(f32::EPSILON);
+
+        let ring = |i: usize, center: Vec3, radius: f32, tilt: f32| {
+            let index = i % len;
+            let tangent = tangents[index];
+            let normal = frame_normals[index];
+            Ring {
+                center,
+                tangent,
+                normal,
+                binormal: tangent.cross(normal),
+                radius,
+                tilt,
+                v: distances[i.min(len)] / total_length,
+            }
+        };
+
+        let round_cap_rings = (resolution / 4).max(1);
+        let round_cap = |i: usize, direction: f32| {
+            let (end, tangent, radius) = (path[i], tangents[i], self.radius);
+            (1..=round_cap_rings).map(move |k| {
+                let angle = k as f32 / round_cap_rings as f32 * core::f32::consts::FRAC_PI_2;
+                let (sin, cos) = ops::sin_cos(angle);
+                let center = end + tangent * direction * radius * sin;
+                (center, radius * cos, angle * direction)
+            })
+        };
+
+        let mut rings = Vec::new();
+        if !self.closed && self.caps == TubeCaps::Round {
+            let cap: Vec<_> = round_cap(0, -1.0).collect();
+            rings.extend(
+                cap.into_iter()
+                    .rev()
+                    .map(|(center, radius, tilt)| ring(0, center, radius, tilt)),
+            );
+        }
+        rings.extend((0..len).map(|i| ring(i, path[i], self.radius, 0.0)));
+        if self.closed {
+            rings.push(ring(len, path[0], self.radius, 0.0));
+        } else if self.caps == TubeCaps::Round {
+            rings.extend(
+                round_cap(len - 1, 1.0)
+                    .map(|(center, radius, tilt)| ring(len - 1, center, radius, tilt)),
+            );
+        }
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = Vec::new();
+
+        let row = resolution + 1;
+        for (r, ring) in rings.iter().enumerate() {
+            let (sin_tilt, cos_tilt) = ops::sin_cos(ring.tilt);
+            for j in 0..=resolution {
+                let angle = j as f32 / resolution as f32 * core::f32::consts::TAU;
+                let (sin, cos) = ops::sin_cos(angle);
+                let direction = ring.normal * cos + ring.binormal * sin;
+                positions.push((ring.center + direction * ring.radius).to_array());
+                normals.push((direction * cos_tilt + ring.tangent * sin_tilt).to_array());
+                uvs.push([j as f32 / resolution as f32, ring.v]);
+            }
+
+            if r > 0 {
+                let start = (r as u32 - 1) * row;
+                for j in 0..resolution {
+                    let a = start + j;
+                    let b = a + 1;
+                    let c = a + row;
+                    let d = c + 1;
+                    indices.extend([a, b, c, b, d, c]);
+                }
+            }
+        }
+
+        if !self.closed && self.caps == TubeCaps::Flat {
+            let first = &rings[0];
+            let last = &rings[rings.len() - 1];
+            for (ring, normal, flip) in [(first, -first.tangent, true), (last, last.tangent, false)]
+            {
+                let center = positions.len() as u32;
+                positions.push(ring.center.to_array());
+                normals.push(normal.to_array());
+                uvs.push([0.5, 0.5]);
+                for j in 0..=resolution {
+                    let angle = j as f32 / resolution as f32 * core::f32::consts::TAU;
+                    let (sin, cos) = ops::sin_cos(angle);
+                    let direction = ring.normal * cos + ring.binormal * sin;
+                    positions.push((ring.center + direction * ring.radius).to_array());
+                    normals.push(normal.to_array());
+                    uvs.push([0.5 + cos * 0.5, 0.5 - sin * 0.5]);
+                }
+                for j in 0..resolution {
+                    let (a, b) = (center + 1 + j, center + 2 + j);
+                    if flip {
+                        indices.extend([center, b, a]);
+                    } else {
+                        indices.extend([center, a, b]);
+                    }
+                }
+            }
+        }
+
+        mesh.with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::vec3;
+
+    #[test]
+    fn straight_tube() {
+        let mesh = TubeMeshBuilder::new([Vec3::ZERO, Vec3::Y, Vec3::Y * 2.0], 0.5)
+            .resolution(8)
+            .build();
+        // 3 rings and 2 caps of 9 vertices, and the centers of the caps.
+        assert_eq!(mesh.count_vertices(), 3 * 9 + 2 * 10);
+        assert_eq!(mesh.indices().unwrap().len(), (2 * 8 * 2 + 2 * 8) * 3);
+
+        // The vertices of the body are at the radius from the path, with outward normals.
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION).unwrap();
+        let normals = mesh.attribute(Mesh::ATTRIBUTE_NORMAL).unwrap();
+        for (position, normal) in positions
+            .as_float3()
+            .unwrap()
+            .iter()
+            .zip(normals.as_float3().unwrap())
+            .take(27)
+        {
+            let (position, normal) = (Vec3::from(*position), Vec3::from(*normal));
+            let offset = position.with_y(0.0);
+            assert!((offset.length() - 0.5).abs() < 1e-5);
+            assert!(offset.normalize().dot(normal) > 0.999);
+        }
+    }
+
+    #[test]
+    fn closed_tube_has_no_seam() {
+        let path = (0..16).map(|i| {
+            let (sin, cos) = ops::sin_cos(i as f32 / 16.0 * core::f32::consts::TAU);
+            vec3(cos, sin * 0.3, sin)
+        });
+        let mesh = TubeMeshBuilder::new(path, 0.1)
+            .resolution(6)
+            .closed(true)
+            .build();
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        // The last ring duplicates the first ring.
+        assert_eq!(positions.len(), 17 * 7);
+        for j in 0..7 {
+            let first = Vec3::from(positions[j]);
+            let last = Vec3::from(positions[16 * 7 + j]);
+            assert!(first.distance(last) < 1e-4);
+        }
+    }
+}
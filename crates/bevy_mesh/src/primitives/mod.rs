@@ -28,6 +28,9 @@ pub use dim3::*;
 mod extrusion;
 pub use extrusion::*;
 
+mod uv_projection;
+pub use uv_projection::*;
+
 use super::Mesh;
 
 /// A trait for shapes that can be turned into a [`Mesh`].
@@ -43,6 +46,17 @@ pub trait Meshable {
 pub trait MeshBuilder {
     /// Builds a [`Mesh`] based on the configuration in `self`.
     fn build(&self) -> Mesh;
+
+    /// Replaces the UVs of the built [`Mesh`] by a projection of its positions.
+    fn uv_projection(self, projection: UvProjection) -> UvProjectionMeshBuilder<Self>
+    where
+        Self: Sized,
+    {
+        UvProjectionMeshBuilder {
+            builder: self,
+            projection,
+        }
+    }
 }
 
 impl<T: MeshBuilder> From<T> for Mesh {
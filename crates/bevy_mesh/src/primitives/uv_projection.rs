@@ -0,0 +1,152 @@
+use crate::{Mesh, MeshBuilder, VertexAttributeValues};
+use bevy_math::{ops, Vec2, Vec3};
+use bevy_reflect::prelude::*;
+
+/// A mapping of positions to UV coordinates, replacing the UVs generated by a [`MeshBuilder`].
+///
+/// ```
+/// # use bevy_math::primitives::Cuboid;
+/// # use bevy_mesh::{Mesh, MeshBuilder, Meshable, UvProjection};
+/// // Tile a texture every unit on the faces of a long box.
+/// let wall: Mesh = Cuboid::new(10.0, 3.0, 0.2)
+///     .mesh()
+///     .uv_projection(UvProjection::Box { scale: 1.0 })
+///     .build();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub enum UvProjection {
+    /// Projects the positions on a plane: the UV coordinates are the dot products of the position
+    /// with the `u` and `v` vectors, so their lengths set the scale of the texture.
+    Planar {
+        /// The direction of increasing U coordinates.
+        u: Vec3,
+        /// The direction of increasing V coordinates.
+        v: Vec3,
+    },
+    /// Projects the positions on the plane of the axis closest to the normal of each vertex, like
+    /// the faces of a box, so that the texture isn't stretched on any face.
+    ///
+    /// Vertices shared by faces projected on different planes get the UVs of only one of them.
+    Box {
+        /// The number of times the texture repeats per unit of distance.
+        scale: f32,
+    },
+    /// Projects the positions on a sphere around the origin: the U coordinate goes around the
+    /// Y axis, and the V coordinate from the top to the bottom.
+    ///
+    /// Vertices on the seam, where U wraps around, aren't duplicated.
+    Spherical,
+}
+
+impl UvProjection {
+    /// Returns the UV coordinates of a vertex with the given position and normal.
+    pub fn project(&self, position: Vec3, normal: Vec3) -> Vec2 {
+        match *self {
+            UvProjection::Planar { u, v } => Vec2::new(position.dot(u), position.dot(v)),
+            UvProjection::Box { scale } => {
+                let abs = normal.abs();
+                let uv = if abs.x >= abs.y && abs.x >= abs.z {
+                    Vec2::new(-position.z * normal.x.signum(), -position.y)
+                } else if abs.y >= abs.z {
+                    Vec2::new(position.x, position.z * normal.y.signum())
+                } else {
+                    Vec2::new(position.x * normal.z.signum(), -position.y)
+                };
+                uv * scale
+            }
+            UvProjection::Spherical => {
+                let Some(direction) = position.try_normalize() else {
+                    return Vec2::splat(0.5);
+                };
+                Vec2::new(
+                    0.5 + ops::atan2(direction.x, direction.z) / core::f32::consts::TAU,
+                    ops::acos(direction.y.clamp(-1.0, 1.0)) / core::f32::consts::PI,
+                )
+            }
+        }
+    }
+}
+
+impl Mesh {
+    /// Replaces the UVs of this mesh by a projection of its positions.
+    ///
+    /// If the mesh has no normals, the positions are used as normals. Nothing is done if the mesh
+    /// has no positions, or if they aren't in the `Float32x3` format.
+    pub fn project_uvs(&mut self, projection: UvProjection) {
+        let Some(positions) = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+        else {
+            return;
+        };
+        let normals = self
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .filter(|normals| normals.len() == positions.len())
+            .unwrap_or(positions);
+
+        let uvs: Vec<[f32; 2]> = positions
+            .iter()
+            .zip(normals)
+            .map(|(position, normal)| {
+                projection
+                    .project(Vec3::from(*position), Vec3::from(*normal))
+                    .to_array()
+            })
+            .collect();
+        self.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    }
+
+    /// Consumes the mesh and returns a mesh with UVs projected from its positions.
+    ///
+    /// (Alternatively, you can use [`Mesh::project_uvs`] to mutate an existing mesh in-place)
+    #[must_use]
+    pub fn with_projected_uvs(mut self, projection: UvProjection) -> Self {
+        self.project_uvs(projection);
+        self
+    }
+}
+
+/// A [`MeshBuilder`] replacing the UVs of the meshes of another builder by a [`UvProjection`].
+///
+/// This is created by [`MeshBuilder::uv_projection`].
+#[derive(Clone, Debug, Reflect)]
+pub struct UvProjectionMeshBuilder<B> {
+    /// The builder of the projected mesh.
+    pub builder: B,
+    /// The projection of the positions to UV coordinates.
+    pub projection: UvProjection,
+}
+
+impl<B: MeshBuilder> MeshBuilder for UvProjectionMeshBuilder<B> {
+    fn build(&self) -> Mesh {
+        self.builder.build().with_projected_uvs(self.projection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Meshable;
+    use bevy_math::primitives::Cuboid;
+
+    #[test]
+    fn box_projection() {
+        let mesh = Cuboid::new(4.0, 2.0, 2.0)
+            .mesh()
+            .uv_projection(UvProjection::Box { scale: 0.5 })
+            .build();
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("Expected uvs f32x2");
+        };
+        // The front face spans 2 units of U and 1 unit of V.
+        let front = &uvs[0..4];
+        let (min, max) = front.iter().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), uv| (min.min(Vec2::from(*uv)), max.max(Vec2::from(*uv))),
+        );
+        assert_eq!(max - min, Vec2::new(2.0, 1.0));
+    }
+}
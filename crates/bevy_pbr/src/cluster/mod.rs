@@ -161,8 +161,9 @@ pub struct GpuClusterableObject {
     pub(crate) spot_light_tan_angle: f32,
     pub(crate) soft_shadow_size: f32,
     pub(crate) shadow_map_near_z: f32,
-    pub(crate) pad_a: f32,
-    pub(crate) pad_b: f32,
+    pub(crate) shadow_slope_bias: f32,
+    // The fraction of the shadow map that the light renders to, see `PointLight::shadow_map_resolution`
+    pub(crate) shadow_map_scale: f32,
 }
 
 pub enum GpuClusterableObjects {
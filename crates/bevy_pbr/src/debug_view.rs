@@ -0,0 +1,60 @@
+use bevy_asset::{weak_handle, Handle};
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{extract_component::ExtractComponent, render_resource::Shader};
+
+pub const DEBUG_VIEW_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3e4b8a2c-5f61-4d0e-9c77-8b1d2e6fa043");
+
+/// Add this component to a [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d) to replace the
+/// shading of meshes using the [`StandardMaterial`](crate::StandardMaterial) by a debug view, to
+/// diagnose issues with their content.
+///
+/// The mode is read from a uniform by the PBR shaders, so switching between modes doesn't
+/// specialize new pipelines, except for [`DebugView::Overdraw`] which blends the meshes together.
+///
+/// Debug views only apply to meshes rendered in the forward pass, not to deferred rendering.
+#[derive(Debug, Component, ExtractComponent, Reflect, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub enum DebugView {
+    /// The meshes are rendered normally.
+    #[default]
+    None,
+    /// Shows the world space normals, after normal mapping, remapped from `-1..1` to `0..1`.
+    Normals,
+    /// Shows the fractional part of the world position, as a color repeating every unit.
+    WorldPosition,
+    /// Shows a checker pattern of 8 by 8 cells over the `0..1` range of the first UV channel,
+    /// tinted by the UV coordinates to show their orientation.
+    ///
+    /// Meshes without UVs are shown in magenta.
+    UvChecker,
+    /// Colors the meshes by the mip level sampled from their base color texture, from red for the
+    /// full resolution to blue for the smallest mips. Magnified textures, with texels larger than
+    /// pixels, are darkened.
+    MipLevels,
+    /// Accumulates a constant color for each fragment drawn, ignoring the depth test, to show how
+    /// many times each pixel is shaded.
+    ///
+    /// This is the only mode specializing new pipelines.
+    Overdraw,
+    /// Colors the meshes by the shadow cascade of the first directional light they are in.
+    ShadowCascades,
+}
+
+impl DebugView {
+    /// The value of the mode in the shaders.
+    ///
+    /// NOTE: these values must be kept in sync with the constants in `debug_view.wgsl`.
+    pub(crate) fn shader_mode(self) -> u32 {
+        match self {
+            DebugView::None => 0,
+            DebugView::Normals => 1,
+            DebugView::WorldPosition => 2,
+            DebugView::UvChecker => 3,
+            DebugView::MipLevels => 4,
+            DebugView::Overdraw => 5,
+            DebugView::ShadowCascades => 6,
+        }
+    }
+}
@@ -22,6 +22,7 @@ use bevy_core_pipeline::{
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_image::BevyDefault as _;
 use bevy_render::{
+    camera::{ExtractedCamera, ViewRenderScale},
     extract_component::{
         ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
     },
@@ -155,6 +156,8 @@ impl ViewNode for DeferredOpaquePass3dPbrLightingNode {
         &'static ViewTarget,
         &'static DeferredLightingIdDepthTexture,
         &'static DeferredLightingPipeline,
+        &'static ExtractedCamera,
+        Option<&'static ViewRenderScale>,
     );
 
     fn run(
@@ -172,6 +175,8 @@ impl ViewNode for DeferredOpaquePass3dPbrLightingNode {
             target,
             deferred_lighting_id_depth_texture,
             deferred_lighting_pipeline,
+            camera,
+            render_scale,
         ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
@@ -213,6 +218,11 @@ impl ViewNode for DeferredOpaquePass3dPbrLightingNode {
             occlusion_query_set: None,
         });
 
+        // Only shade the part of the target the G-buffer was rendered to.
+        if let Some(viewport) = camera.main_pass_viewport(render_scale) {
+            render_pass.set_camera_viewport(&viewport);
+        }
+
         render_pass.set_render_pipeline(pipeline);
         render_pass.set_bind_group(
             0,
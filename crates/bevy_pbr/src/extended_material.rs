@@ -3,10 +3,10 @@ use bevy_ecs::system::SystemParamItem;
 use bevy_reflect::{impl_type_path, Reflect};
 use bevy_render::{
     alpha::AlphaMode,
-    mesh::MeshVertexBufferLayoutRef,
+    mesh::{MeshVertexAttribute, MeshVertexBufferLayoutRef},
     render_resource::{
         AsBindGroup, AsBindGroupError, BindGroupLayout, RenderPipelineDescriptor, Shader,
-        ShaderRef, SpecializedMeshPipelineError, UnpreparedBindGroup,
+        ShaderDefVal, ShaderRef, SpecializedMeshPipelineError, UnpreparedBindGroup,
     },
     renderer::RenderDevice,
 };
@@ -26,6 +26,23 @@ pub struct MaterialExtensionKey<E: MaterialExtension> {
     pub bind_group_data: E::Data,
 }
 
+/// The shader location of the first [`MaterialExtension::vertex_attributes`], after the locations
+/// used by the mesh pipeline and the prepass.
+pub const FIRST_EXTENSION_VERTEX_ATTRIBUTE_LOCATION: u32 = 8;
+
+/// A custom vertex attribute of the mesh, read by the vertex shader of a [`MaterialExtension`].
+///
+/// See [`MaterialExtension::vertex_attributes`].
+#[derive(Clone, Copy, Debug)]
+pub struct ExtensionVertexAttribute {
+    /// The attribute of the mesh.
+    pub attribute: MeshVertexAttribute,
+    /// The name of the shader def defined when the mesh has the attribute.
+    ///
+    /// The shader location of the attribute is defined as `{shader_def}_LOCATION`.
+    pub shader_def: &'static str,
+}
+
 /// A subset of the `Material` trait for defining extensions to a base `Material`, such as the builtin `StandardMaterial`.
 ///
 /// A user type implementing the trait should be used as the `E` generic param in an `ExtendedMaterial` struct.
@@ -42,6 +59,30 @@ pub trait MaterialExtension: Asset + AsBindGroup + Clone + Sized {
         ShaderRef::Default
     }
 
+    /// Returns the custom vertex attributes read by this material's vertex shaders, in addition to
+    /// the attributes of the base material.
+    ///
+    /// The attributes the mesh has are added to the vertex buffer layout of the pipelines, at
+    /// consecutive shader locations starting from [`FIRST_EXTENSION_VERTEX_ATTRIBUTE_LOCATION`].
+    /// For each of them, the shader def [`ExtensionVertexAttribute::shader_def`] is defined, along
+    /// with `{shader_def}_LOCATION` holding its location, so the vertex shader can declare it
+    /// without hardcoding the location:
+    ///
+    /// ```wgsl
+    /// struct Vertex {
+    ///     @builtin(instance_index) instance_index: u32,
+    ///     @location(0) position: vec3<f32>,
+    /// #ifdef WIND_WEIGHT
+    ///     @location(#{WIND_WEIGHT_LOCATION}) wind_weight: f32,
+    /// #endif
+    /// };
+    /// ```
+    ///
+    /// Meshes without an attribute still use the material, without its shader def.
+    fn vertex_attributes() -> &'static [ExtensionVertexAttribute] {
+        &[]
+    }
+
     // Returns this material’s AlphaMode. If None is returned, the base material alpha mode will be used.
     fn alpha_mode() -> Option<AlphaMode> {
         None
@@ -336,6 +377,8 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         };
         B::specialize(&base_pipeline, descriptor, layout, base_key)?;
 
+        add_extension_vertex_attributes(E::vertex_attributes(), descriptor, layout)?;
+
         // Call the extended material's specialize function afterwards
         let MaterialPipeline::<Self> {
             mesh_pipeline,
@@ -363,3 +406,34 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         )
     }
 }
+
+/// Adds the [`MaterialExtension::vertex_attributes`] the mesh has to the vertex buffer layout and
+/// the shader defs of the pipeline.
+fn add_extension_vertex_attributes(
+    attributes: &[ExtensionVertexAttribute],
+    descriptor: &mut RenderPipelineDescriptor,
+    layout: &MeshVertexBufferLayoutRef,
+) -> Result<(), SpecializedMeshPipelineError> {
+    let Some(vertex_buffer) = descriptor.vertex.buffers.first_mut() else {
+        return Ok(());
+    };
+    for (location, attribute) in (FIRST_EXTENSION_VERTEX_ATTRIBUTE_LOCATION..).zip(attributes) {
+        if !layout.0.contains(attribute.attribute.id) {
+            continue;
+        }
+        let attribute_layout = layout
+            .0
+            .get_layout(&[attribute.attribute.at_shader_location(location)])?;
+        vertex_buffer.attributes.extend(attribute_layout.attributes);
+
+        let shader_defs = [
+            ShaderDefVal::from(attribute.shader_def),
+            ShaderDefVal::UInt(format!("{}_LOCATION", attribute.shader_def), location),
+        ];
+        descriptor.vertex.shader_defs.extend(shader_defs.clone());
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader_defs.extend(shader_defs);
+        }
+    }
+    Ok(())
+}
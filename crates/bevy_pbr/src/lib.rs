@@ -10,6 +10,10 @@ extern crate alloc;
 
 #[cfg(feature = "meshlet")]
 mod meshlet;
+pub mod outline;
+pub mod particles;
+pub mod vertex_animation;
+pub mod wind;
 pub mod wireframe;
 
 /// Experimental features that are not yet finished. Please report any issues you encounter!
@@ -27,6 +31,7 @@ pub mod experimental {
 mod atmosphere;
 mod cluster;
 mod components;
+mod debug_view;
 pub mod decal;
 pub mod deferred;
 mod extended_material;
@@ -35,6 +40,8 @@ mod light;
 mod light_probe;
 mod lightmap;
 mod material;
+#[cfg(feature = "bevy_animation")]
+mod material_animation;
 mod material_bind_groups;
 mod mesh_material;
 mod parallax;
@@ -52,6 +59,7 @@ use bevy_color::{Color, LinearRgba};
 pub use atmosphere::*;
 pub use cluster::*;
 pub use components::*;
+pub use debug_view::*;
 pub use decal::clustered::ClusteredDecalPlugin;
 pub use extended_material::*;
 pub use fog::*;
@@ -59,6 +67,8 @@ pub use light::*;
 pub use light_probe::*;
 pub use lightmap::*;
 pub use material::*;
+#[cfg(feature = "bevy_animation")]
+pub use material_animation::{AnimatedMaterialField, MaterialAnimationPlugin, MaterialOverride};
 pub use mesh_material::*;
 pub use parallax::*;
 pub use pbr_material::*;
@@ -105,6 +115,13 @@ pub mod graph {
         EarlyPrepassBuildIndirectParameters,
         LatePrepassBuildIndirectParameters,
         MainBuildIndirectParameters,
+        /// Label for the outline pass.
+        Outline,
+        /// Label for the particle simulation pass.
+        ParticleSimulation,
+        /// Label for the pass that convolves the cubemaps captured by
+        /// reflection probes.
+        ReflectionProbeFilter,
     }
 }
 
@@ -123,7 +140,7 @@ use bevy_render::{
     render_graph::RenderGraph,
     render_resource::Shader,
     sync_component::SyncComponentPlugin,
-    texture::GpuImage,
+    texture::{GpuImage, TextureColorSpacePlugin},
     view::VisibilitySystems,
     ExtractSchedule, Render, RenderApp, RenderSet,
 };
@@ -154,6 +171,8 @@ pub const PBR_FUNCTIONS_HANDLE: Handle<Shader> =
 pub const PBR_AMBIENT_HANDLE: Handle<Shader> = weak_handle!("4a90b95b-112a-4a10-9145-7590d6f14260");
 pub const PARALLAX_MAPPING_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("6cf57d9f-222a-429a-bba4-55ba9586e1d4");
+pub const TRIPLANAR_MAPPING_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("cd456773-cfab-4df8-9c3c-53b21b1bb61c");
 pub const VIEW_TRANSFORMATIONS_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("ec047703-cde3-4876-94df-fed121544abb");
 pub const PBR_PREPASS_FUNCTIONS_SHADER_HANDLE: Handle<Shader> =
@@ -245,6 +264,12 @@ impl Plugin for PbrPlugin {
             "deferred/pbr_deferred_functions.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            DEBUG_VIEW_SHADER_HANDLE,
+            "render/debug_view.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(
             app,
             SHADOW_SAMPLING_HANDLE,
@@ -294,6 +319,12 @@ impl Plugin for PbrPlugin {
             "render/parallax_mapping.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            TRIPLANAR_MAPPING_SHADER_HANDLE,
+            "render/triplanar_mapping.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(
             app,
             VIEW_TRANSFORMATIONS_SHADER_HANDLE,
@@ -320,14 +351,20 @@ impl Plugin for PbrPlugin {
             .register_type::<DirectionalLightShadowMap>()
             .register_type::<NotShadowCaster>()
             .register_type::<NotShadowReceiver>()
+            .register_type::<StaticShadowCaster>()
+            .register_type::<CachedShadowMap>()
             .register_type::<PointLight>()
             .register_type::<PointLightShadowMap>()
             .register_type::<SpotLight>()
+            .register_type::<SoftShadowLimits>()
             .register_type::<ShadowFilteringMethod>()
+            .register_type::<DebugView>()
             .init_resource::<AmbientLight>()
             .init_resource::<GlobalVisibleClusterableObjects>()
             .init_resource::<DirectionalLightShadowMap>()
             .init_resource::<PointLightShadowMap>()
+            .init_resource::<SoftShadowLimits>()
+            .init_resource::<StaticShadowCasterChanges>()
             .register_type::<DefaultOpaqueRendererMethod>()
             .init_resource::<DefaultOpaqueRendererMethod>()
             .add_plugins((
@@ -343,6 +380,7 @@ impl Plugin for PbrPlugin {
                 FogPlugin,
                 ExtractResourcePlugin::<DefaultOpaqueRendererMethod>::default(),
                 ExtractComponentPlugin::<ShadowFilteringMethod>::default(),
+                ExtractComponentPlugin::<DebugView>::default(),
                 LightmapPlugin,
                 LightProbePlugin,
                 PbrProjectionPlugin,
@@ -355,10 +393,13 @@ impl Plugin for PbrPlugin {
             ))
             .add_plugins((
                 decal::ForwardDecalPlugin,
+                TextureColorSpacePlugin::<StandardMaterial>::default(),
                 SyncComponentPlugin::<DirectionalLight>::default(),
                 SyncComponentPlugin::<PointLight>::default(),
                 SyncComponentPlugin::<SpotLight>::default(),
                 ExtractComponentPlugin::<AmbientLight>::default(),
+                ExtractResourcePlugin::<StaticShadowCasterChanges>::default(),
+                ExtractResourcePlugin::<SoftShadowLimits>::default(),
             ))
             .add_plugins(AtmospherePlugin)
             .configure_sets(
@@ -424,9 +465,15 @@ impl Plugin for PbrPlugin {
                         // because that resets entity `ViewVisibility` for the first view
                         // which would override any results from this otherwise
                         .after(VisibilitySystems::CheckVisibility),
+                    track_static_shadow_caster_changes
+                        .after(TransformSystem::TransformPropagate)
+                        .after(SimulationLightSystems::CheckLightVisibility),
                 ),
             );
 
+        #[cfg(feature = "bevy_animation")]
+        app.add_plugins(MaterialAnimationPlugin::<StandardMaterial>::default());
+
         if self.add_default_deferred_lighting_plugin {
             app.add_plugins(DeferredPbrLightingPlugin);
         }
@@ -459,6 +506,7 @@ impl Plugin for PbrPlugin {
                 ),
             )
             .init_resource::<LightMeta>()
+            .init_resource::<ShadowCaches>()
             .init_resource::<RenderMaterialBindings>();
 
         render_app.world_mut().add_observer(add_light_view_entities);
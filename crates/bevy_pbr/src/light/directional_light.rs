@@ -100,6 +100,12 @@ pub struct DirectionalLight {
     #[cfg(feature = "experimental_pbr_pcss")]
     pub soft_shadow_size: Option<f32>,
 
+    /// Controls the blocker search of the soft shadows of this light.
+    ///
+    /// This only has an effect if soft shadows are enabled.
+    #[cfg(feature = "experimental_pbr_pcss")]
+    pub soft_shadow_blocker_search: SoftShadowBlockerSearch,
+
     /// Whether this directional light contributes diffuse lighting to meshes
     /// with lightmaps.
     ///
@@ -127,6 +133,27 @@ pub struct DirectionalLight {
     /// is scaled to the shadow map's texel size so that it is automatically
     /// adjusted to the orthographic projection.
     pub shadow_normal_bias: f32,
+
+    /// A bias applied along the direction to the light that grows with the
+    /// angle between the fragment's surface normal and that direction. It is
+    /// scaled to the shadow map's texel size like `shadow_normal_bias`.
+    ///
+    /// This removes the shadow acne of surfaces at grazing angles to the light
+    /// without pushing the shadows of surfaces facing the light away from their
+    /// casters.
+    pub shadow_slope_bias: f32,
+
+    /// The resolution of the shadow map of each cascade of this light, if lower
+    /// than [`DirectionalLightShadowMap::size`].
+    ///
+    /// Directional lights share one shadow map texture, so this doesn't save
+    /// memory, but lights whose shadows don't need much detail can render fewer
+    /// texels to save time. Values larger than
+    /// [`DirectionalLightShadowMap::size`] are clamped to it. Powers of two
+    /// keep the shadows stable as the camera moves.
+    ///
+    /// This only has an effect if shadows are enabled.
+    pub shadow_map_resolution: Option<u32>,
 }
 
 impl Default for DirectionalLight {
@@ -137,9 +164,13 @@ impl Default for DirectionalLight {
             shadows_enabled: false,
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            shadow_slope_bias: 0.0,
+            shadow_map_resolution: None,
             affects_lightmapped_mesh_diffuse: true,
             #[cfg(feature = "experimental_pbr_pcss")]
             soft_shadow_size: None,
+            #[cfg(feature = "experimental_pbr_pcss")]
+            soft_shadow_blocker_search: SoftShadowBlockerSearch::default(),
         }
     }
 }
@@ -31,6 +31,11 @@ mod spot_light;
 pub use spot_light::SpotLight;
 mod directional_light;
 pub use directional_light::DirectionalLight;
+mod shadow_cache;
+pub use shadow_cache::{
+    track_static_shadow_caster_changes, CachedShadowMap, StaticShadowCaster,
+    StaticShadowCasterChanges,
+};
 
 /// Constants for operating with the light units: lumens, and lux.
 pub mod light_consts {
@@ -120,6 +125,86 @@ impl Default for DirectionalLightShadowMap {
     }
 }
 
+/// Returns the resolution that a light with the given `shadow_map_resolution`
+/// override renders its shadow map at, in a shadow map texture of `size`
+/// texels.
+pub(crate) fn effective_shadow_map_resolution(
+    shadow_map_resolution: Option<u32>,
+    size: usize,
+) -> u32 {
+    let size = size as u32;
+    shadow_map_resolution.map_or(size, |resolution| resolution.clamp(1, size))
+}
+
+/// Controls the blocker search of the soft shadows of a light.
+///
+/// Percentage-closer soft shadows first search the shadow map around each
+/// fragment for the occluders, or *blockers*, that shadow it, and use their
+/// average depth to determine how wide the penumbra is. This controls how wide
+/// that search is and how many samples it takes.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub struct SoftShadowBlockerSearch {
+    /// The size of the search region, relative to the size of the light.
+    ///
+    /// Larger values find blockers further away from the fragment, which widens
+    /// the penumbras cast by small occluders, at the cost of more noise.
+    ///
+    /// This is clamped to the `0.0..16.0` range.
+    pub radius_scale: f32,
+
+    /// The number of shadow map samples taken by the search.
+    ///
+    /// This is clamped to the `1..=8` range, and to
+    /// [`SoftShadowLimits::max_blocker_search_samples`].
+    pub sample_count: u32,
+}
+
+impl SoftShadowBlockerSearch {
+    /// The maximum number of samples that a blocker search can take.
+    pub const MAX_SAMPLE_COUNT: u32 = 8;
+    /// The maximum value of [`SoftShadowBlockerSearch::radius_scale`].
+    pub const MAX_RADIUS_SCALE: f32 = 16.0;
+}
+
+impl Default for SoftShadowBlockerSearch {
+    fn default() -> Self {
+        Self {
+            radius_scale: 1.0,
+            sample_count: Self::MAX_SAMPLE_COUNT,
+        }
+    }
+}
+
+/// Caps the cost of the soft shadows of [`PointLight`]s, [`SpotLight`]s and
+/// [`DirectionalLight`]s.
+///
+/// Soft shadows are evaluated per light per fragment, so a fragment in a
+/// cluster crowded with soft shadow casting lights can be very expensive to
+/// shade. Once a fragment has evaluated the soft shadows of
+/// `max_lights_per_fragment` lights, the shadows of the remaining lights are
+/// filtered like hard shadows.
+#[derive(Resource, Clone, Copy, Debug, ExtractResource, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct SoftShadowLimits {
+    /// The maximum number of lights whose soft shadows are evaluated for a
+    /// single fragment.
+    pub max_lights_per_fragment: u32,
+
+    /// The maximum number of samples that the blocker search of any light
+    /// takes, regardless of its [`SoftShadowBlockerSearch::sample_count`].
+    pub max_blocker_search_samples: u32,
+}
+
+impl Default for SoftShadowLimits {
+    fn default() -> Self {
+        Self {
+            max_lights_per_fragment: 4,
+            max_blocker_search_samples: SoftShadowBlockerSearch::MAX_SAMPLE_COUNT,
+        }
+    }
+}
+
 /// Controls how cascaded shadow mapping works.
 /// Prefer using [`CascadeShadowConfigBuilder`] to construct an instance.
 ///
@@ -360,7 +445,10 @@ pub fn build_directional_light_cascades(
 
                     calculate_cascade(
                         corners,
-                        directional_light_shadow_map.size as f32,
+                        effective_shadow_map_resolution(
+                            directional_light.shadow_map_resolution,
+                            directional_light_shadow_map.size,
+                        ) as f32,
                         world_from_light,
                         camera_to_light_view,
                     )
@@ -1054,3 +1142,16 @@ pub fn check_point_light_mesh_visibility(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::effective_shadow_map_resolution;
+
+    #[test]
+    fn shadow_map_resolution_is_clamped() {
+        assert_eq!(effective_shadow_map_resolution(None, 2048), 2048);
+        assert_eq!(effective_shadow_map_resolution(Some(512), 2048), 512);
+        assert_eq!(effective_shadow_map_resolution(Some(4096), 2048), 2048);
+        assert_eq!(effective_shadow_map_resolution(Some(0), 2048), 1);
+    }
+}
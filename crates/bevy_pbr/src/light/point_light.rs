@@ -71,6 +71,12 @@ pub struct PointLight {
     #[cfg(feature = "experimental_pbr_pcss")]
     pub soft_shadows_enabled: bool,
 
+    /// Controls the blocker search of the soft shadows of this light.
+    ///
+    /// This only has an effect if soft shadows are enabled.
+    #[cfg(feature = "experimental_pbr_pcss")]
+    pub soft_shadow_blocker_search: SoftShadowBlockerSearch,
+
     /// Whether this point light contributes diffuse lighting to meshes with
     /// lightmaps.
     ///
@@ -95,6 +101,14 @@ pub struct PointLight {
     /// away.
     pub shadow_normal_bias: f32,
 
+    /// A bias applied along the direction to the light that grows with the angle between the
+    /// fragment's surface normal and that direction. It is scaled to the shadow map's texel size
+    /// like `shadow_normal_bias`.
+    ///
+    /// This removes the shadow acne of surfaces at grazing angles to the light without pushing
+    /// the shadows of surfaces facing the light away from their casters.
+    pub shadow_slope_bias: f32,
+
     /// The distance from the light to near Z plane in the shadow map.
     ///
     /// Objects closer than this distance to the light won't cast shadows.
@@ -102,6 +116,16 @@ pub struct PointLight {
     ///
     /// This only has an effect if shadows are enabled.
     pub shadow_map_near_z: f32,
+
+    /// The resolution of each face of the shadow map of this light, if lower than
+    /// [`PointLightShadowMap::size`].
+    ///
+    /// All point lights share one shadow map texture, so this doesn't save memory, but lights
+    /// that are small or far away can render fewer texels to save time. Values larger than
+    /// [`PointLightShadowMap::size`] are clamped to it.
+    ///
+    /// This only has an effect if shadows are enabled.
+    pub shadow_map_resolution: Option<u32>,
 }
 
 impl Default for PointLight {
@@ -118,9 +142,13 @@ impl Default for PointLight {
             affects_lightmapped_mesh_diffuse: true,
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            shadow_slope_bias: 0.0,
             shadow_map_near_z: Self::DEFAULT_SHADOW_MAP_NEAR_Z,
+            shadow_map_resolution: None,
             #[cfg(feature = "experimental_pbr_pcss")]
             soft_shadows_enabled: false,
+            #[cfg(feature = "experimental_pbr_pcss")]
+            soft_shadow_blocker_search: SoftShadowBlockerSearch::default(),
         }
     }
 }
@@ -0,0 +1,123 @@
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_render::{extract_resource::ExtractResource, mesh::Mesh3d, view::ViewVisibility};
+use bevy_transform::components::GlobalTransform;
+
+use super::NotShadowCaster;
+
+/// Add this component to a [`DirectionalLight`](crate::DirectionalLight),
+/// [`PointLight`](crate::PointLight) or [`SpotLight`](crate::SpotLight) with shadows enabled to
+/// cache the shadows of the [`StaticShadowCaster`]s.
+///
+/// The static casters are rendered into a cached shadow map, which is only refreshed when one of
+/// them changes or when the light moves. Every frame, the cached shadow map is copied into the
+/// shadow map of the light, and the other casters are rendered on top of it. This saves most of
+/// the cost of the shadows of mostly static scenes.
+///
+/// The cascades of a [`DirectionalLight`](crate::DirectionalLight) follow the camera, so their
+/// cached shadow maps are refreshed whenever the camera moves.
+///
+/// This isn't supported on WebGL 2, which can't copy depth textures, where it is ignored.
+#[derive(Debug, Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+pub struct CachedShadowMap;
+
+/// Add this component to a [`Mesh3d`] that doesn't move to render its shadows in the cached shadow
+/// maps of the lights with a [`CachedShadowMap`].
+///
+/// Any change to the transform, mesh or visibility of a static caster refreshes the cached shadow
+/// maps. Other changes, such as changes to the mesh asset or the material of the caster, aren't
+/// detected: call [`StaticShadowCasterChanges::invalidate`] to refresh the cached shadow maps
+/// after them.
+#[derive(Debug, Component, Reflect, Default)]
+#[reflect(Component, Default, Debug)]
+pub struct StaticShadowCaster;
+
+/// Tracks the changes to the [`StaticShadowCaster`]s, to refresh the cached shadow maps of the
+/// lights with a [`CachedShadowMap`].
+#[derive(Resource, Clone, Copy, Default, Debug, ExtractResource)]
+pub struct StaticShadowCasterChanges {
+    generation: u32,
+}
+
+impl StaticShadowCasterChanges {
+    /// Refreshes all cached shadow maps, for changes to the static casters which aren't detected
+    /// automatically.
+    pub fn invalidate(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// A counter incremented each time the static casters change.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// Invalidates the cached shadow maps when a [`StaticShadowCaster`] is added, removed, moved or
+/// changes visibility.
+pub fn track_static_shadow_caster_changes(
+    mut changes: ResMut<StaticShadowCasterChanges>,
+    changed_casters: Query<
+        (),
+        (
+            With<StaticShadowCaster>,
+            Or<(
+                Changed<StaticShadowCaster>,
+                Changed<GlobalTransform>,
+                Changed<ViewVisibility>,
+                Changed<Mesh3d>,
+                Changed<NotShadowCaster>,
+            )>,
+        ),
+    >,
+    mut removed_casters: RemovedComponents<StaticShadowCaster>,
+    mut removed_not_shadow_casters: RemovedComponents<NotShadowCaster>,
+) {
+    // Drain both readers, so that old removals aren't reported next frame.
+    let removed = removed_casters.read().count() + removed_not_shadow_casters.read().count();
+    if removed > 0 || !changed_casters.is_empty() {
+        changes.invalidate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+    use bevy_transform::components::GlobalTransform;
+
+    use super::{
+        track_static_shadow_caster_changes, StaticShadowCaster, StaticShadowCasterChanges,
+    };
+
+    #[test]
+    fn static_shadow_caster_changes() {
+        let mut world = World::new();
+        world.init_resource::<StaticShadowCasterChanges>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(track_static_shadow_caster_changes);
+        let generation = |world: &World| world.resource::<StaticShadowCasterChanges>().generation();
+
+        let caster = world
+            .spawn((StaticShadowCaster, GlobalTransform::default()))
+            .id();
+        world.spawn(GlobalTransform::default());
+        schedule.run(&mut world);
+        assert_eq!(generation(&world), 1);
+
+        schedule.run(&mut world);
+        assert_eq!(generation(&world), 1);
+
+        world
+            .entity_mut(caster)
+            .insert(GlobalTransform::from_xyz(1.0, 0.0, 0.0));
+        schedule.run(&mut world);
+        assert_eq!(generation(&world), 2);
+
+        world.despawn(caster);
+        schedule.run(&mut world);
+        assert_eq!(generation(&world), 3);
+
+        schedule.run(&mut world);
+        assert_eq!(generation(&world), 3);
+    }
+}
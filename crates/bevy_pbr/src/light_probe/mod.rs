@@ -41,13 +41,17 @@ use crate::{
     },
 };
 
-use self::irradiance_volume::IrradianceVolume;
+use self::{
+    irradiance_volume::IrradianceVolume,
+    reflection_probe_capture::{ReflectionProbeCaptureCamera, ReflectionProbeCapturePlugin},
+};
 
 pub const LIGHT_PROBE_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("e80a2ae6-1c5a-4d9a-a852-d66ff0e6bf7f");
 
 pub mod environment_map;
 pub mod irradiance_volume;
+pub mod reflection_probe_capture;
 
 /// The maximum number of each type of light probe that each view will consider.
 ///
@@ -191,6 +195,9 @@ struct LightProbeInfo<C>
 where
     C: LightProbeComponent,
 {
+    // The main world entity of the light probe.
+    entity: Entity,
+
     // The transform from world space to light probe space.
     light_from_world: Mat4,
 
@@ -365,7 +372,8 @@ impl Plugin for LightProbePlugin {
 
         app.register_type::<LightProbe>()
             .register_type::<EnvironmentMapLight>()
-            .register_type::<IrradianceVolume>();
+            .register_type::<IrradianceVolume>()
+            .add_plugins(ReflectionProbeCapturePlugin);
     }
 
     fn finish(&self, app: &mut App) {
@@ -417,9 +425,18 @@ fn gather_environment_map_uniform(
 /// to views, performing frustum culling and distance sorting in the process.
 fn gather_light_probes<C>(
     image_assets: Res<RenderAssets<GpuImage>>,
-    light_probe_query: Extract<Query<(&GlobalTransform, &C), With<LightProbe>>>,
+    light_probe_query: Extract<Query<(Entity, &GlobalTransform, &C), With<LightProbe>>>,
     view_query: Extract<
-        Query<(RenderEntity, &GlobalTransform, &Frustum, Option<&C>), With<Camera3d>>,
+        Query<
+            (
+                RenderEntity,
+                &GlobalTransform,
+                &Frustum,
+                Option<&C>,
+                Option<&ReflectionProbeCaptureCamera>,
+            ),
+            With<Camera3d>,
+        >,
     >,
     mut reflection_probes: Local<Vec<LightProbeInfo<C>>>,
     mut view_reflection_probes: Local<Vec<LightProbeInfo<C>>>,
@@ -436,13 +453,20 @@ fn gather_light_probes<C>(
     );
 
     // Build up the light probes uniform and the key table.
-    for (view_entity, view_transform, view_frustum, view_component) in view_query.iter() {
-        // Cull light probes outside the view frustum.
+    for (view_entity, view_transform, view_frustum, view_component, capture_camera) in
+        view_query.iter()
+    {
+        // Cull light probes outside the view frustum. The cameras capturing a
+        // reflection probe skip that probe, so that it doesn't reflect itself.
         view_reflection_probes.clear();
         view_reflection_probes.extend(
             reflection_probes
                 .iter()
-                .filter(|light_probe_info| light_probe_info.frustum_cull(view_frustum))
+                .filter(|light_probe_info| {
+                    light_probe_info.frustum_cull(view_frustum)
+                        && capture_camera
+                            .is_none_or(|camera| camera.probe != light_probe_info.entity)
+                })
                 .cloned(),
         );
 
@@ -612,10 +636,11 @@ where
     /// [`LightProbeInfo`]. This is done for every light probe in the scene
     /// every frame.
     fn new(
-        (light_probe_transform, environment_map): (&GlobalTransform, &C),
+        (entity, light_probe_transform, environment_map): (Entity, &GlobalTransform, &C),
         image_assets: &RenderAssets<GpuImage>,
     ) -> Option<LightProbeInfo<C>> {
         environment_map.id(image_assets).map(|id| LightProbeInfo {
+            entity,
             world_from_light: light_probe_transform.affine(),
             light_from_world: light_probe_transform.compute_matrix().inverse(),
             asset_id: id,
@@ -731,6 +756,7 @@ where
 {
     fn clone(&self) -> Self {
         Self {
+            entity: self.entity,
             light_from_world: self.light_from_world,
             world_from_light: self.world_from_light,
             intensity: self.intensity,
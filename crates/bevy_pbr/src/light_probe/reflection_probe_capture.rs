@@ -0,0 +1,1016 @@
+//! Reflection probes that capture their surroundings at runtime.
+//!
+//! A [`ReflectionProbeCapture`] renders the scene around a [`LightProbe`] into
+//! a cubemap with six cameras, one per face, and then convolves that cubemap
+//! into the diffuse and specular maps of the probe's [`EnvironmentMapLight`].
+//! The result is used by the regular environment map lighting path, exactly
+//! like a pre-baked cubemap would be.
+//!
+//! By default, a probe is captured once when it's spawned and then only when a
+//! [`ReflectionProbeCaptureRequest`] is added to it, but captures can also
+//! happen every frame or at a fixed interval. The six faces can be spread over
+//! six frames to amortize their cost. See [`ReflectionProbeUpdate`].
+//!
+//! The capture cameras are regular [`Camera3d`]s marked with a
+//! [`ReflectionProbeCaptureCamera`] component, so components such as a skybox
+//! or render layers can be added to them to control what gets captured. The
+//! capture cameras don't receive the reflections of their own probe, so that
+//! a capture doesn't feed back into the next one.
+//!
+//! Convolving the cubemap requires compute shaders and storage textures, so
+//! runtime captures aren't available on WebGL 2, where
+//! [`ReflectionProbeCapture`] does nothing.
+
+use core::{array, ops::Range, time::Duration};
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, AssetId, Assets, Handle, RenderAssetUsages};
+use bevy_core_pipeline::{
+    core_3d::Camera3d,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_ecs::{
+    component::{require, Component, HookContext},
+    entity::Entity,
+    query::{Added, Has},
+    reflect::ReflectComponent,
+    resource::Resource,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut},
+    world::{DeferredWorld, FromWorld, World},
+};
+use bevy_image::{Image, TextureFormatPixelInfo};
+use bevy_math::{UVec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{
+        Camera, CameraUpdateSystem, Exposure, ManualTextureView, ManualTextureViewHandle,
+        ManualTextureViews, PerspectiveProjection, Projection, RenderTarget,
+    },
+    graph::CameraDriverLabel,
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        binding_types::{sampler, texture_cube, texture_storage_2d_array, uniform_buffer},
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        CachedComputePipelineId, ComputePassDescriptor, ComputePipelineDescriptor, DownlevelFlags,
+        DynamicUniformBuffer, Extent3d, FilterMode, PipelineCache, Sampler, SamplerBindingType,
+        SamplerDescriptor, Shader, ShaderStages, ShaderType, StorageTextureAccess, Texture,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureView, TextureViewDescriptor, TextureViewDimension,
+    },
+    renderer::{RenderAdapter, RenderContext, RenderDevice, RenderQueue},
+    texture::GpuImage,
+    view::{Msaa, VisibilitySystems},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_time::Time;
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+use bevy_utils::{once, prelude::default};
+use tracing::warn;
+
+use crate::{
+    graph::NodePbr,
+    light_probe::{environment_map::EnvironmentMapLight, LightProbe},
+};
+
+pub const REFLECTION_PROBE_CAPTURE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("5b0d2a4e-8f61-4c3b-9a7e-2d6c1f0b8e93");
+
+/// The format of the captured cubemap and of the maps generated from it.
+const CAPTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The size of each face of the generated diffuse map.
+///
+/// Diffuse lighting varies slowly with the normal, so a small map is enough.
+const DIFFUSE_MAP_SIZE: u32 = 32;
+
+/// The number of samples taken for each texel of the specular map.
+const SPECULAR_SAMPLE_COUNT: u32 = 64;
+
+/// The number of samples taken for each texel of the diffuse map.
+const DIFFUSE_SAMPLE_COUNT: u32 = 128;
+
+/// The forward and up directions of the capture camera of each cubemap face.
+///
+/// The environment map shader negates the z coordinate of its sampling
+/// directions, so the cameras of the ±Z faces look along ∓Z.
+const CUBE_FACE_DIRECTIONS: [(Vec3, Vec3); 6] = [
+    (Vec3::X, Vec3::Y),
+    (Vec3::NEG_X, Vec3::Y),
+    (Vec3::Y, Vec3::Z),
+    (Vec3::NEG_Y, Vec3::NEG_Z),
+    (Vec3::NEG_Z, Vec3::Y),
+    (Vec3::Z, Vec3::Y),
+];
+
+/// Adds support for [`ReflectionProbeCapture`].
+///
+/// This plugin is added by the [`LightProbePlugin`](super::LightProbePlugin).
+pub struct ReflectionProbeCapturePlugin;
+
+/// Renders the surroundings of a [`LightProbe`] into the cubemaps of its
+/// [`EnvironmentMapLight`] at runtime.
+///
+/// The [`EnvironmentMapLight::diffuse_map`] and
+/// [`EnvironmentMapLight::specular_map`] of the probe are replaced with images
+/// generated from the capture, while its other fields, such as the intensity,
+/// are left untouched. The captured cubemap is aligned with the world axes, so
+/// [`EnvironmentMapLight::rotation`] should usually be left as the identity.
+///
+/// The capture is made from the position of the probe by six cameras, which
+/// are spawned as separate entities with a [`ReflectionProbeCaptureCamera`]
+/// component, and despawned along with this component.
+///
+/// Since pipelines are compiled asynchronously, a capture made during the first
+/// frames of the app may miss parts of the scene. Add a
+/// [`ReflectionProbeCaptureRequest`] to the probe to capture it again.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(
+    LightProbe,
+    EnvironmentMapLight(|| EnvironmentMapLight {
+        intensity: 1.0,
+        ..default()
+    })
+)]
+#[component(on_remove = release_reflection_probe_capture)]
+pub struct ReflectionProbeCapture {
+    /// The size of each face of the captured cubemap, in pixels.
+    ///
+    /// This is also the size of the generated specular map.
+    pub resolution: u32,
+
+    /// When the surroundings of the probe are captured.
+    pub update: ReflectionProbeUpdate,
+
+    /// Whether a capture renders a single face per frame instead of all six.
+    ///
+    /// This makes captures six times cheaper per frame, at the cost of taking
+    /// six frames to complete. The probe keeps the result of the previous
+    /// capture until the new one is complete.
+    pub time_sliced: bool,
+
+    /// The distance from the probe to the near plane of the capture cameras.
+    pub near: f32,
+
+    /// The distance from the probe to the far plane of the capture cameras.
+    pub far: f32,
+}
+
+impl Default for ReflectionProbeCapture {
+    fn default() -> Self {
+        Self {
+            resolution: 256,
+            update: ReflectionProbeUpdate::Once,
+            time_sliced: true,
+            near: 0.1,
+            far: 1000.0,
+        }
+    }
+}
+
+impl ReflectionProbeCapture {
+    /// Returns the projection of the capture cameras.
+    fn projection(&self) -> Projection {
+        Projection::Perspective(PerspectiveProjection {
+            fov: core::f32::consts::FRAC_PI_2,
+            aspect_ratio: 1.0,
+            near: self.near,
+            far: self.far,
+        })
+    }
+}
+
+/// When a [`ReflectionProbeCapture`] captures its surroundings.
+///
+/// Whatever the update mode, adding a [`ReflectionProbeCaptureRequest`] to the
+/// probe starts a new capture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum ReflectionProbeUpdate {
+    /// Capture once, when the probe is spawned.
+    #[default]
+    Once,
+    /// Capture continuously, starting a new capture as soon as the previous one
+    /// is complete.
+    ///
+    /// This renders the scene six times per capture, so it's only suitable for
+    /// a few probes with a small resolution.
+    EveryFrame,
+    /// Capture when at least the given duration of [`Time`] has elapsed since
+    /// the start of the previous capture.
+    Interval(Duration),
+    /// Only capture when a [`ReflectionProbeCaptureRequest`] is added to the
+    /// probe.
+    OnDemand,
+}
+
+/// Requests a new capture of a [`ReflectionProbeCapture`], whatever its
+/// [`ReflectionProbeUpdate`].
+///
+/// This component is removed from the probe when the capture starts.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct ReflectionProbeCaptureRequest;
+
+/// Marks one of the six cameras that render the faces of a
+/// [`ReflectionProbeCapture`].
+///
+/// These cameras are managed by the probe: their target, projection,
+/// transform and activity are overwritten, but other components can be added
+/// to them to control how the scene is captured.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct ReflectionProbeCaptureCamera {
+    /// The probe entity this camera captures the surroundings of.
+    pub probe: Entity,
+    /// The index of the cubemap face rendered by this camera, in the usual
+    /// `+X`, `-X`, `+Y`, `-Y`, `+Z`, `-Z` order.
+    pub face: usize,
+}
+
+/// The resources of a [`ReflectionProbeCapture`] and the progress of its
+/// current capture.
+#[derive(Component)]
+struct ReflectionProbeCaptureState {
+    /// The resolution the resources were created with.
+    resolution: u32,
+    /// The cubemap the cameras render to, with a full mip chain.
+    texture: Texture,
+    /// The cameras of each face.
+    cameras: [Entity; 6],
+    /// The render targets of the cameras of each face.
+    views: [ManualTextureViewHandle; 6],
+    /// The progress of the current capture.
+    progress: CaptureProgress,
+    /// The exposure of the capture cameras, which is removed from the
+    /// generated maps.
+    exposure: f32,
+}
+
+/// When a [`ReflectionProbeCapture`] was last captured, and which of its faces
+/// are rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct CaptureProgress {
+    /// The next face to render, if a capture is in progress.
+    next_face: Option<usize>,
+    /// The time at which the last capture started.
+    last_capture: Option<Duration>,
+    /// Whether a capture has been started at least once.
+    captured: bool,
+    /// Whether the last face of a capture is rendered this frame, so that the
+    /// cubemap should be convolved.
+    filter: bool,
+}
+
+impl CaptureProgress {
+    /// Starts a new capture at `now` if none is in progress and `update` or a
+    /// request calls for one, then returns the faces to render this frame.
+    fn advance(
+        &mut self,
+        update: ReflectionProbeUpdate,
+        time_sliced: bool,
+        requested: bool,
+        now: Duration,
+    ) -> Range<usize> {
+        self.filter = false;
+
+        if self.next_face.is_none() {
+            let scheduled = match update {
+                ReflectionProbeUpdate::Once => !self.captured,
+                ReflectionProbeUpdate::EveryFrame => true,
+                ReflectionProbeUpdate::Interval(interval) => self
+                    .last_capture
+                    .is_none_or(|last_capture| now.saturating_sub(last_capture) >= interval),
+                ReflectionProbeUpdate::OnDemand => false,
+            };
+            if scheduled || requested {
+                self.next_face = Some(0);
+                self.last_capture = Some(now);
+                self.captured = true;
+            }
+        }
+
+        let Some(face) = self.next_face else {
+            return 0..0;
+        };
+        let faces = if time_sliced { face..face + 1 } else { face..6 };
+        if faces.end == 6 {
+            self.next_face = None;
+            self.filter = true;
+        } else {
+            self.next_face = Some(faces.end);
+        }
+        faces
+    }
+}
+
+impl ReflectionProbeCaptureState {
+    /// Despawns the cameras of the capture and frees their render targets.
+    fn release(&self, commands: &mut Commands, manual_texture_views: &mut ManualTextureViews) {
+        for camera in self.cameras {
+            commands.entity(camera).try_despawn();
+        }
+        for view in &self.views {
+            manual_texture_views.remove(view);
+        }
+    }
+}
+
+/// Releases the resources of a [`ReflectionProbeCapture`] when it's removed.
+fn release_reflection_probe_capture(
+    mut world: DeferredWorld,
+    HookContext { entity, .. }: HookContext,
+) {
+    let Some(state) = world.get::<ReflectionProbeCaptureState>(entity) else {
+        return;
+    };
+    let (cameras, views) = (state.cameras, state.views);
+
+    if let Some(mut manual_texture_views) = world.get_resource_mut::<ManualTextureViews>() {
+        for view in &views {
+            manual_texture_views.remove(view);
+        }
+    }
+
+    let mut commands = world.commands();
+    for camera in cameras {
+        commands.entity(camera).try_despawn();
+    }
+    commands
+        .entity(entity)
+        .try_remove::<ReflectionProbeCaptureState>();
+}
+
+impl Plugin for ReflectionProbeCapturePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            REFLECTION_PROBE_CAPTURE_SHADER_HANDLE,
+            "reflection_probe_capture.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ReflectionProbeCapture>()
+            .register_type::<ReflectionProbeCaptureRequest>()
+            .register_type::<ReflectionProbeCaptureCamera>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        // This plugin is part of the `PbrPlugin`, so don't fail on platforms
+        // such as WebGL 2 which can't convolve the captures. Only warn if a
+        // capture is actually used there.
+        let render_adapter = render_app.world().resource::<RenderAdapter>();
+        if !render_adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::COMPUTE_SHADERS)
+            || !render_adapter
+                .get_texture_format_features(CAPTURE_FORMAT)
+                .allowed_usages
+                .contains(TextureUsages::STORAGE_BINDING)
+            || render_app
+                .world()
+                .resource::<RenderDevice>()
+                .limits()
+                .max_storage_textures_per_shader_stage
+                == 0
+        {
+            app.add_systems(PostUpdate, warn_unsupported_reflection_probe_captures);
+            return;
+        }
+
+        render_app
+            .init_resource::<ReflectionProbeFilterPipelines>()
+            .init_resource::<ReflectionProbeFilterJobs>()
+            .init_resource::<ReflectionProbeFilterDispatches>()
+            .add_systems(ExtractSchedule, extract_reflection_probe_filter_jobs)
+            .add_systems(
+                Render,
+                prepare_reflection_probe_filter_dispatches.in_set(RenderSet::PrepareBindGroups),
+            );
+
+        let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        graph.add_node(NodePbr::ReflectionProbeFilter, ReflectionProbeFilterNode);
+        graph.add_node_edge(CameraDriverLabel, NodePbr::ReflectionProbeFilter);
+
+        app.add_systems(
+            PostUpdate,
+            (
+                setup_reflection_probe_captures.before(CameraUpdateSystem),
+                schedule_reflection_probe_captures
+                    .after(TransformSystem::TransformPropagate)
+                    .before(VisibilitySystems::UpdateFrusta),
+            ),
+        );
+    }
+}
+
+/// Warns that [`ReflectionProbeCapture`]s aren't supported on this platform.
+fn warn_unsupported_reflection_probe_captures(probes: Query<(), Added<ReflectionProbeCapture>>) {
+    if !probes.is_empty() {
+        once!(warn!(
+            "ReflectionProbeCapture isn't supported on this platform: the GPU lacks support for compute shaders or storage textures."
+        ));
+    }
+}
+
+/// Creates the capture texture, the cameras and the environment maps of new
+/// [`ReflectionProbeCapture`]s, and recreates them when their resolution
+/// changes.
+fn setup_reflection_probe_captures(
+    mut commands: Commands,
+    mut probes: Query<(
+        Entity,
+        &ReflectionProbeCapture,
+        &mut EnvironmentMapLight,
+        Option<&ReflectionProbeCaptureState>,
+    )>,
+    render_device: Res<RenderDevice>,
+    mut manual_texture_views: ResMut<ManualTextureViews>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (entity, capture, mut environment_map, state) in &mut probes {
+        if let Some(state) = state {
+            if state.resolution == capture.resolution {
+                continue;
+            }
+            state.release(&mut commands, &mut manual_texture_views);
+        }
+
+        let resolution = capture.resolution.max(1);
+        let mip_level_count = resolution.ilog2() + 1;
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("reflection_probe_capture_texture"),
+            size: Extent3d {
+                width: resolution,
+                height: resolution,
+                depth_or_array_layers: 6,
+            },
+            mip_level_count,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: CAPTURE_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::STORAGE_BINDING,
+            view_formats: &[],
+        });
+
+        // Allocate the handles of the render targets downwards from the end of
+        // the range, so that they're unlikely to clash with the ones chosen by
+        // users.
+        let mut next_handle = u32::MAX;
+        let views = array::from_fn(|face| {
+            while manual_texture_views.contains_key(&ManualTextureViewHandle(next_handle)) {
+                next_handle -= 1;
+            }
+            let handle = ManualTextureViewHandle(next_handle);
+            let texture_view = texture.create_view(&TextureViewDescriptor {
+                label: Some("reflection_probe_capture_face_view"),
+                dimension: Some(TextureViewDimension::D2),
+                base_mip_level: 0,
+                mip_level_count: Some(1),
+                base_array_layer: face as u32,
+                array_layer_count: Some(1),
+                ..default()
+            });
+            manual_texture_views.insert(
+                handle,
+                ManualTextureView {
+                    texture_view,
+                    size: UVec2::splat(resolution),
+                    format: CAPTURE_FORMAT,
+                },
+            );
+            handle
+        });
+
+        let cameras = array::from_fn(|face| {
+            let (forward, up) = CUBE_FACE_DIRECTIONS[face];
+            commands
+                .spawn((
+                    Camera3d::default(),
+                    Camera {
+                        target: RenderTarget::TextureView(views[face]),
+                        hdr: true,
+                        is_active: false,
+                        order: -1,
+                        ..default()
+                    },
+                    capture.projection(),
+                    Tonemapping::None,
+                    DebandDither::Disabled,
+                    Msaa::Off,
+                    Transform::default().looking_to(forward, up),
+                    ReflectionProbeCaptureCamera {
+                        probe: entity,
+                        face,
+                    },
+                ))
+                .id()
+        });
+
+        environment_map.specular_map =
+            images.add(environment_map_image(resolution, mip_level_count));
+        environment_map.diffuse_map = images.add(environment_map_image(DIFFUSE_MAP_SIZE, 1));
+
+        commands.entity(entity).insert(ReflectionProbeCaptureState {
+            resolution: capture.resolution,
+            texture,
+            cameras,
+            views,
+            progress: CaptureProgress::default(),
+            exposure: Exposure::default().exposure(),
+        });
+    }
+}
+
+/// Returns a black cubemap image that can be written to by the filtering
+/// shaders.
+fn environment_map_image(size: u32, mip_level_count: u32) -> Image {
+    let texel_count: usize = (0..mip_level_count)
+        .map(|mip| ((size >> mip).max(1) as usize).pow(2))
+        .sum();
+
+    Image {
+        data: vec![0; texel_count * 6 * CAPTURE_FORMAT.pixel_size()],
+        texture_descriptor: TextureDescriptor {
+            label: Some("reflection_probe_environment_map"),
+            size: Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 6,
+            },
+            mip_level_count,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: CAPTURE_FORMAT,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::STORAGE_BINDING
+                | TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+        texture_view_descriptor: Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..default()
+        }),
+        asset_usage: RenderAssetUsages::RENDER_WORLD,
+        ..default()
+    }
+}
+
+/// Starts captures according to the [`ReflectionProbeUpdate`] of each probe,
+/// activates the cameras of the faces to render this frame and moves them to
+/// the position of their probe.
+fn schedule_reflection_probe_captures(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut probes: Query<(
+        Entity,
+        &ReflectionProbeCapture,
+        &mut ReflectionProbeCaptureState,
+        &GlobalTransform,
+        Has<ReflectionProbeCaptureRequest>,
+    )>,
+    mut cameras: Query<(
+        &mut Camera,
+        &mut Projection,
+        &mut Transform,
+        &mut GlobalTransform,
+        Option<&Exposure>,
+    )>,
+) {
+    let now = time.elapsed();
+
+    for (entity, capture, mut state, probe_transform, requested) in &mut probes {
+        // Requests made while a capture is in progress start a new one once
+        // it's complete.
+        if requested && state.progress.next_face.is_none() {
+            commands
+                .entity(entity)
+                .remove::<ReflectionProbeCaptureRequest>();
+        }
+        let faces = state
+            .progress
+            .advance(capture.update, capture.time_sliced, requested, now);
+
+        let cameras_of_probe = state.cameras;
+        for (face, camera_entity) in cameras_of_probe.into_iter().enumerate() {
+            let Ok((mut camera, mut projection, mut transform, mut global_transform, exposure)) =
+                cameras.get_mut(camera_entity)
+            else {
+                continue;
+            };
+
+            let is_active = faces.contains(&face);
+            if camera.is_active != is_active {
+                camera.is_active = is_active;
+            }
+            if !is_active {
+                continue;
+            }
+
+            if face == 0 {
+                state.exposure = exposure.copied().unwrap_or_default().exposure();
+            }
+            let expected_projection = capture.projection();
+            if !matches!(
+                (&*projection, &expected_projection),
+                (Projection::Perspective(current), Projection::Perspective(expected))
+                    if current.near == expected.near && current.far == expected.far
+            ) {
+                *projection = expected_projection;
+            }
+
+            let (forward, up) = CUBE_FACE_DIRECTIONS[face];
+            *transform =
+                Transform::from_translation(probe_transform.translation()).looking_to(forward, up);
+            *global_transform = GlobalTransform::from(*transform);
+        }
+    }
+}
+
+/// The capture of a probe to convolve this frame.
+struct ReflectionProbeFilterJob {
+    texture: Texture,
+    specular_map: AssetId<Image>,
+    diffuse_map: AssetId<Image>,
+    intensity: f32,
+}
+
+/// The captures whose last face is rendered this frame.
+#[derive(Resource, Default)]
+struct ReflectionProbeFilterJobs(Vec<ReflectionProbeFilterJob>);
+
+/// The settings of a single dispatch of the filtering shader.
+#[derive(Clone, Copy, ShaderType)]
+struct ReflectionProbeFilterSettings {
+    /// The GGX roughness (perceptual roughness squared) of the specular lobe.
+    roughness: f32,
+    /// The number of samples taken for each texel.
+    sample_count: u32,
+    /// The size of the first mip level of the source cubemap.
+    source_size: u32,
+    /// The size of the output mip level.
+    output_size: u32,
+    /// The factor the output is multiplied by.
+    intensity: f32,
+}
+
+/// A single dispatch of the filtering shader, writing to one mip level of a
+/// cubemap.
+struct ReflectionProbeFilterDispatch {
+    pipeline: CachedComputePipelineId,
+    bind_group: BindGroup,
+    settings_offset: u32,
+    output_size: u32,
+}
+
+/// The dispatches of the filtering shader to run this frame, in order.
+#[derive(Resource, Default)]
+struct ReflectionProbeFilterDispatches {
+    settings: DynamicUniformBuffer<ReflectionProbeFilterSettings>,
+    dispatches: Vec<ReflectionProbeFilterDispatch>,
+}
+
+/// The pipelines used to generate the mips of the captured cubemap and to
+/// convolve it into environment maps.
+#[derive(Resource)]
+struct ReflectionProbeFilterPipelines {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    downsample: CachedComputePipelineId,
+    filter_specular: CachedComputePipelineId,
+    filter_diffuse: CachedComputePipelineId,
+}
+
+impl FromWorld for ReflectionProbeFilterPipelines {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(
+            "reflection_probe_filter_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_cube(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    texture_storage_2d_array(CAPTURE_FORMAT, StorageTextureAccess::WriteOnly),
+                    uniform_buffer::<ReflectionProbeFilterSettings>(true),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("reflection_probe_filter_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let queue_pipeline = |entry_point: &'static str| {
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some(format!("reflection_probe_{entry_point}_pipeline").into()),
+                layout: vec![layout.clone()],
+                push_constant_ranges: vec![],
+                shader: REFLECTION_PROBE_CAPTURE_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: entry_point.into(),
+                zero_initialize_workgroup_memory: false,
+            })
+        };
+
+        let downsample = queue_pipeline("downsample");
+        let filter_specular = queue_pipeline("filter_specular");
+        let filter_diffuse = queue_pipeline("filter_diffuse");
+
+        Self {
+            layout,
+            sampler,
+            downsample,
+            filter_specular,
+            filter_diffuse,
+        }
+    }
+}
+
+/// Collects the captures to convolve this frame.
+fn extract_reflection_probe_filter_jobs(
+    mut jobs: ResMut<ReflectionProbeFilterJobs>,
+    probes: Extract<Query<(&ReflectionProbeCaptureState, &EnvironmentMapLight)>>,
+) {
+    jobs.0.clear();
+    jobs.0.extend(
+        probes
+            .iter()
+            .filter(|(state, _)| state.progress.filter)
+            .map(|(state, environment_map)| ReflectionProbeFilterJob {
+                texture: state.texture.clone(),
+                specular_map: environment_map.specular_map.id(),
+                diffuse_map: environment_map.diffuse_map.id(),
+                intensity: state.exposure.recip(),
+            }),
+    );
+}
+
+/// Creates the bind groups of the dispatches that generate the mips of the
+/// captured cubemaps and convolve them.
+fn prepare_reflection_probe_filter_dispatches(
+    jobs: Res<ReflectionProbeFilterJobs>,
+    pipelines: Res<ReflectionProbeFilterPipelines>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut dispatches: ResMut<ReflectionProbeFilterDispatches>,
+) {
+    let dispatches = &mut *dispatches;
+    dispatches.settings.clear();
+    dispatches.dispatches.clear();
+    if jobs.0.is_empty() {
+        return;
+    }
+
+    let mut pending = vec![];
+    for job in &jobs.0 {
+        let (Some(specular_map), Some(diffuse_map)) =
+            (images.get(job.specular_map), images.get(job.diffuse_map))
+        else {
+            continue;
+        };
+
+        // Generate the mips of the capture, which are sampled when convolving
+        // it to avoid aliasing.
+        let size = job.texture.width();
+        let mip_level_count = job.texture.mip_level_count();
+        for mip in 1..mip_level_count {
+            pending.push((
+                pipelines.downsample,
+                cube_view(&job.texture, mip - 1, 1),
+                storage_view(&job.texture, mip),
+                ReflectionProbeFilterSettings {
+                    roughness: 0.0,
+                    sample_count: 1,
+                    source_size: mip_size(size, mip - 1),
+                    output_size: mip_size(size, mip),
+                    intensity: 1.0,
+                },
+            ));
+        }
+
+        // Each mip of the specular map stores the reflection of surfaces whose
+        // perceptual roughness grows linearly with the mip level.
+        let source = cube_view(&job.texture, 0, mip_level_count);
+        let specular_mip_level_count = specular_map.mip_level_count;
+        for mip in 0..specular_mip_level_count {
+            let perceptual_roughness = if specular_mip_level_count > 1 {
+                mip as f32 / (specular_mip_level_count - 1) as f32
+            } else {
+                0.0
+            };
+            pending.push((
+                pipelines.filter_specular,
+                source.clone(),
+                storage_view(&specular_map.texture, mip),
+                ReflectionProbeFilterSettings {
+                    roughness: perceptual_roughness * perceptual_roughness,
+                    sample_count: SPECULAR_SAMPLE_COUNT,
+                    source_size: size,
+                    output_size: mip_size(specular_map.size.width, mip),
+                    intensity: job.intensity,
+                },
+            ));
+        }
+
+        pending.push((
+            pipelines.filter_diffuse,
+            source,
+            storage_view(&diffuse_map.texture, 0),
+            ReflectionProbeFilterSettings {
+                roughness: 1.0,
+                sample_count: DIFFUSE_SAMPLE_COUNT,
+                source_size: size,
+                output_size: diffuse_map.size.width,
+                intensity: job.intensity,
+            },
+        ));
+    }
+
+    let offsets: Vec<_> = pending
+        .iter()
+        .map(|(_, _, _, settings)| dispatches.settings.push(settings))
+        .collect();
+    dispatches
+        .settings
+        .write_buffer(&render_device, &render_queue);
+    let Some(settings_binding) = dispatches.settings.binding() else {
+        return;
+    };
+
+    for ((pipeline, source, output, settings), settings_offset) in pending.into_iter().zip(offsets)
+    {
+        let bind_group = render_device.create_bind_group(
+            "reflection_probe_filter_bind_group",
+            &pipelines.layout,
+            &BindGroupEntries::sequential((
+                &source,
+                &pipelines.sampler,
+                &output,
+                settings_binding.clone(),
+            )),
+        );
+        dispatches.dispatches.push(ReflectionProbeFilterDispatch {
+            pipeline,
+            bind_group,
+            settings_offset,
+            output_size: settings.output_size,
+        });
+    }
+}
+
+/// Returns the size of the given mip level of a texture of the given size.
+fn mip_size(size: u32, mip: u32) -> u32 {
+    (size >> mip).max(1)
+}
+
+/// Creates a cube view of `mip_level_count` mips of `texture`, starting at
+/// `base_mip_level`.
+fn cube_view(texture: &Texture, base_mip_level: u32, mip_level_count: u32) -> TextureView {
+    texture.create_view(&TextureViewDescriptor {
+        label: Some("reflection_probe_filter_source_view"),
+        dimension: Some(TextureViewDimension::Cube),
+        base_mip_level,
+        mip_level_count: Some(mip_level_count),
+        ..default()
+    })
+}
+
+/// Creates a view of a single mip of the six faces of `texture`, to be written
+/// to by a compute shader.
+fn storage_view(texture: &Texture, mip: u32) -> TextureView {
+    texture.create_view(&TextureViewDescriptor {
+        label: Some("reflection_probe_filter_output_view"),
+        dimension: Some(TextureViewDimension::D2Array),
+        base_mip_level: mip,
+        mip_level_count: Some(1),
+        ..default()
+    })
+}
+
+/// Generates the mips of the captured cubemaps and convolves them into the
+/// environment maps of their probes, once all cameras have rendered.
+struct ReflectionProbeFilterNode;
+
+impl Node for ReflectionProbeFilterNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let dispatches = world.resource::<ReflectionProbeFilterDispatches>();
+        if dispatches.dispatches.is_empty() {
+            return Ok(());
+        }
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let mut compute_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("reflection_probe_filter_pass"),
+                    timestamp_writes: None,
+                });
+
+        for dispatch in &dispatches.dispatches {
+            let Some(pipeline) = pipeline_cache.get_compute_pipeline(dispatch.pipeline) else {
+                continue;
+            };
+            let workgroup_count = dispatch.output_size.div_ceil(8);
+            compute_pass.set_pipeline(pipeline);
+            compute_pass.set_bind_group(0, &dispatch.bind_group, &[dispatch.settings_offset]);
+            compute_pass.dispatch_workgroups(workgroup_count, workgroup_count, 6);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::{CaptureProgress, ReflectionProbeUpdate};
+
+    /// Advances `progress` by a frame and returns the faces rendered and
+    /// whether the capture is convolved.
+    fn frame(
+        progress: &mut CaptureProgress,
+        update: ReflectionProbeUpdate,
+        time_sliced: bool,
+        requested: bool,
+        now: u64,
+    ) -> (core::ops::Range<usize>, bool) {
+        let faces = progress.advance(update, time_sliced, requested, Duration::from_secs(now));
+        (faces, progress.filter)
+    }
+
+    #[test]
+    fn scheduling() {
+        let mut progress = CaptureProgress::default();
+        let update = ReflectionProbeUpdate::Once;
+        assert_eq!(frame(&mut progress, update, false, false, 0), (0..6, true));
+        assert_eq!(frame(&mut progress, update, false, false, 1), (0..0, false));
+        assert_eq!(frame(&mut progress, update, false, true, 2), (0..6, true));
+        assert_eq!(frame(&mut progress, update, false, false, 3), (0..0, false));
+
+        let mut progress = CaptureProgress::default();
+        let update = ReflectionProbeUpdate::OnDemand;
+        assert_eq!(frame(&mut progress, update, false, false, 0), (0..0, false));
+        assert_eq!(frame(&mut progress, update, false, true, 1), (0..6, true));
+        assert_eq!(frame(&mut progress, update, false, false, 2), (0..0, false));
+
+        let mut progress = CaptureProgress::default();
+        let update = ReflectionProbeUpdate::Interval(Duration::from_secs(2));
+        assert_eq!(frame(&mut progress, update, false, false, 0), (0..6, true));
+        assert_eq!(frame(&mut progress, update, false, false, 1), (0..0, false));
+        assert_eq!(frame(&mut progress, update, false, false, 2), (0..6, true));
+
+        let mut progress = CaptureProgress::default();
+        let update = ReflectionProbeUpdate::EveryFrame;
+        for now in 0..3 {
+            assert_eq!(
+                frame(&mut progress, update, false, false, now),
+                (0..6, true)
+            );
+        }
+    }
+
+    #[test]
+    fn time_slicing() {
+        let mut progress = CaptureProgress::default();
+        let update = ReflectionProbeUpdate::OnDemand;
+        assert_eq!(frame(&mut progress, update, true, true, 0), (0..1, false));
+        for face in 1..5 {
+            // Requests made while a capture is in progress don't restart it.
+            let requested = face == 2;
+            assert_eq!(
+                frame(&mut progress, update, true, requested, face as u64),
+                (face..face + 1, false)
+            );
+        }
+        assert_eq!(frame(&mut progress, update, true, false, 5), (5..6, true));
+        assert_eq!(frame(&mut progress, update, true, false, 6), (0..0, false));
+        assert_eq!(progress.last_capture, Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,145 @@
+// Generates the mips of a cubemap captured by a reflection probe, and
+// convolves it into the specular and diffuse maps of the probe.
+//
+// Every entry point writes one texel of one mip level of the six faces of its
+// output, and samples the source cubemap at lower-resolution mips when its
+// samples are sparse, to avoid aliasing. This is "filtered importance
+// sampling" [1].
+//
+// [1]: https://developer.nvidia.com/gpugems/gpugems3/part-iii-rendering/chapter-20-gpu-based-importance-sampling
+
+#import bevy_render::maths::{PI, orthonormalize}
+
+struct ReflectionProbeFilterSettings {
+    roughness: f32,
+    sample_count: u32,
+    source_size: u32,
+    output_size: u32,
+    intensity: f32,
+}
+
+@group(0) @binding(0) var source: texture_cube<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var output: texture_storage_2d_array<rgba16float, write>;
+@group(0) @binding(3) var<uniform> settings: ReflectionProbeFilterSettings;
+
+// Returns the direction of the center of the output texel at `id`, where `id.z`
+// is the index of the cubemap face.
+fn texel_direction(id: vec3<u32>) -> vec3<f32> {
+    let st = (vec2<f32>(id.xy) + 0.5) / f32(settings.output_size) * 2.0 - 1.0;
+    let s = st.x;
+    let t = st.y;
+    switch id.z {
+        case 0u: { return normalize(vec3(1.0, -t, -s)); }
+        case 1u: { return normalize(vec3(-1.0, -t, s)); }
+        case 2u: { return normalize(vec3(s, 1.0, t)); }
+        case 3u: { return normalize(vec3(s, -1.0, -t)); }
+        case 4u: { return normalize(vec3(s, -t, 1.0)); }
+        default: { return normalize(vec3(-s, -t, -1.0)); }
+    }
+}
+
+// Returns a basis whose Z axis is `normal`.
+fn tangent_basis(normal: vec3<f32>) -> mat3x3<f32> {
+    let up = select(vec3(0.0, 1.0, 0.0), vec3(1.0, 0.0, 0.0), abs(normal.y) > 0.999);
+    return orthonormalize(normal, up);
+}
+
+// Returns the `i`th point of a Hammersley set of `count` points.
+fn hammersley(i: u32, count: u32) -> vec2<f32> {
+    return vec2(f32(i) / f32(count), f32(reverseBits(i)) * 2.3283064365386963e-10);
+}
+
+// Returns the mip level of the source cubemap whose texels cover about the
+// same solid angle as a sample drawn with the probability density `pdf`.
+fn sample_mip_level(pdf: f32) -> f32 {
+    let size = f32(settings.source_size);
+    let texel_solid_angle = 4.0 * PI / (6.0 * size * size);
+    let sample_solid_angle = 1.0 / (f32(settings.sample_count) * pdf + 0.0001);
+    return max(0.5 * log2(sample_solid_angle / texel_solid_angle) + 1.0, 0.0);
+}
+
+@compute
+@workgroup_size(8, 8, 1)
+fn downsample(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (any(id.xy >= vec2(settings.output_size))) {
+        return;
+    }
+
+    // The center of an output texel is the corner shared by four source
+    // texels, so a bilinear sample averages them.
+    let color = textureSampleLevel(source, source_sampler, texel_direction(id), 0.0);
+    textureStore(output, id.xy, id.z, color);
+}
+
+@compute
+@workgroup_size(8, 8, 1)
+fn filter_specular(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (any(id.xy >= vec2(settings.output_size))) {
+        return;
+    }
+
+    let N = texel_direction(id);
+
+    // Perfectly smooth surfaces reflect the capture as is.
+    if (settings.roughness == 0.0) {
+        let color = textureSampleLevel(source, source_sampler, N, 0.0).rgb;
+        textureStore(output, id.xy, id.z, vec4(color * settings.intensity, 1.0));
+        return;
+    }
+
+    // Importance sample the GGX distribution, assuming that the view and
+    // reflection directions are the normal.
+    let basis = tangent_basis(N);
+    let a2 = settings.roughness * settings.roughness;
+    var color = vec3(0.0);
+    var weight = 0.0;
+    for (var i = 0u; i < settings.sample_count; i += 1u) {
+        let xi = hammersley(i, settings.sample_count);
+        let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (a2 - 1.0) * xi.y));
+        let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+        let phi = 2.0 * PI * xi.x;
+        let H = basis * vec3(sin_theta * cos(phi), sin_theta * sin(phi), cos_theta);
+        let L = 2.0 * dot(N, H) * H - N;
+        let NdotL = dot(N, L);
+        if (NdotL <= 0.0) {
+            continue;
+        }
+
+        // The density of `L` is D(H) * NdotH / (4 * VdotH), which is D(H) / 4
+        // since V = N.
+        let d = cos_theta * cos_theta * (a2 - 1.0) + 1.0;
+        let D = a2 / (PI * d * d);
+        let mip_level = sample_mip_level(D * 0.25);
+        color += textureSampleLevel(source, source_sampler, L, mip_level).rgb * NdotL;
+        weight += NdotL;
+    }
+
+    color /= max(weight, 0.0001);
+    textureStore(output, id.xy, id.z, vec4(color * settings.intensity, 1.0));
+}
+
+@compute
+@workgroup_size(8, 8, 1)
+fn filter_diffuse(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (any(id.xy >= vec2(settings.output_size))) {
+        return;
+    }
+
+    // Importance sample the cosine-weighted hemisphere around the normal.
+    let N = texel_direction(id);
+    let basis = tangent_basis(N);
+    var color = vec3(0.0);
+    for (var i = 0u; i < settings.sample_count; i += 1u) {
+        let xi = hammersley(i, settings.sample_count);
+        let r = sqrt(xi.y);
+        let phi = 2.0 * PI * xi.x;
+        let NdotL = sqrt(1.0 - xi.y);
+        let L = basis * vec3(r * cos(phi), r * sin(phi), NdotL);
+        let mip_level = sample_mip_level(NdotL / PI);
+        color += textureSampleLevel(source, source_sampler, L, mip_level).rgb;
+    }
+
+    color /= f32(settings.sample_count);
+    textureStore(output, id.xy, id.z, vec4(color * settings.intensity, 1.0));
+}
@@ -0,0 +1,302 @@
+//! Computes the lighting of the texels of a lightmap by path tracing the [`BakeScene`].
+
+use core::f32::consts::{PI, TAU};
+
+use bevy_asset::RenderAssetUsages;
+use bevy_color::LinearRgba;
+use bevy_image::{Image, ImageSampler};
+use bevy_math::{ops, Affine3A, UVec2, Vec2, Vec3, Vec3A};
+use bevy_render::{
+    mesh::{Mesh, VertexAttributeValues},
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use super::scene::{BakeScene, RAY_EPSILON};
+
+/// The parameters of the path tracing of a lightmap.
+#[derive(Clone, Copy)]
+pub(super) struct IntegratorSettings {
+    pub(super) samples: u32,
+    pub(super) bounces: u32,
+    pub(super) dilation: u32,
+    pub(super) seed: u64,
+}
+
+/// A small PCG random number generator, to avoid depending on `rand` for the baker.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        let mut rng = Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ 0xd1b5_4a32_d192_ed03);
+        rng.next_u32();
+        rng
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        let state = self.0;
+        self.0 = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let xor_shifted = (((state >> 18) ^ state) >> 27) as u32;
+        xor_shifted.rotate_right((state >> 59) as u32)
+    }
+
+    /// Returns a number in `0.0..1.0`.
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+/// Returns a random direction in the hemisphere around `normal`, with a probability proportional
+/// to the cosine of its angle with the normal.
+fn cosine_sample_hemisphere(normal: Vec3A, rng: &mut Rng) -> Vec3A {
+    let radius_squared = rng.next_f32();
+    let radius = ops::sqrt(radius_squared);
+    let (sin, cos) = ops::sin_cos(rng.next_f32() * TAU);
+    let (tangent, bitangent) = normal.any_orthonormal_pair();
+    (tangent * (radius * cos)
+        + bitangent * (radius * sin)
+        + normal * ops::sqrt(1.0 - radius_squared))
+    .normalize()
+}
+
+/// Returns the radiance coming from `direction` to `origin`, after at most `bounces` bounces.
+fn trace_path(
+    scene: &BakeScene,
+    mut origin: Vec3A,
+    mut direction: Vec3A,
+    bounces: u32,
+    rng: &mut Rng,
+) -> Vec3 {
+    let mut radiance = Vec3::ZERO;
+    let mut throughput = Vec3::ONE;
+    for bounce in 1..=bounces {
+        let Some(hit) = scene.intersect(origin, direction, f32::INFINITY, false) else {
+            radiance += throughput * scene.sky;
+            break;
+        };
+        let triangle = scene.triangle(hit.triangle);
+        let [a, b, c] = triangle.positions;
+        // Back faces are treated as black, to avoid leaking light through closed meshes.
+        if (b - a).cross(c - a).dot(direction) >= 0.0 {
+            break;
+        }
+
+        let weights = Vec3A::new(
+            1.0 - hit.barycentric.x - hit.barycentric.y,
+            hit.barycentric.x,
+            hit.barycentric.y,
+        );
+        let position = origin + direction * hit.distance;
+        let normal = interpolate(triangle.normals, weights).normalize_or_zero();
+        let uv =
+            triangle.uvs[0] * weights.x + triangle.uvs[1] * weights.y + triangle.uvs[2] * weights.z;
+
+        let material = &scene.materials[triangle.material as usize];
+        radiance += throughput * material.emissive;
+        // A Lambertian surface reflects `albedo / PI` of its illuminance.
+        throughput *= material.albedo(uv);
+        radiance += throughput * scene.direct_illuminance(position, normal, |_| true) / PI;
+
+        if bounce == bounces || throughput.max_element() <= 0.0 {
+            break;
+        }
+        origin = position + normal * RAY_EPSILON;
+        direction = cosine_sample_hemisphere(normal, rng);
+    }
+    radiance
+}
+
+fn interpolate(values: [Vec3A; 3], weights: Vec3A) -> Vec3A {
+    values[0] * weights.x + values[1] * weights.y + values[2] * weights.z
+}
+
+/// A point of the surface of the mesh covered by a texel of the lightmap.
+struct TexelSurface {
+    position: Vec3A,
+    normal: Vec3A,
+}
+
+/// Finds the point of the mesh covered by each texel, using its lightmap UVs.
+fn rasterize(mesh: &Mesh, transform: &Affine3A, size: UVec2) -> Vec<Option<TexelSurface>> {
+    let mut texels: Vec<Option<TexelSurface>> = (0..size.x * size.y).map(|_| None).collect();
+    let (
+        Some(VertexAttributeValues::Float32x3(positions)),
+        Some(VertexAttributeValues::Float32x2(uvs)),
+    ) = (
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+        mesh.attribute(Mesh::ATTRIBUTE_UV_1),
+    )
+    else {
+        return texels;
+    };
+    let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(normals)) => Some(normals),
+        _ => None,
+    };
+    let normal_matrix = transform.matrix3.inverse().transpose();
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+
+    for triangle in indices.chunks_exact(3) {
+        let texel_positions = [0, 1, 2].map(|i| Vec2::from(uvs[triangle[i]]) * size.as_vec2());
+        let world_positions =
+            [0, 1, 2].map(|i| transform.transform_point3a(Vec3A::from(positions[triangle[i]])));
+        let face_normal = (world_positions[1] - world_positions[0])
+            .cross(world_positions[2] - world_positions[0])
+            .normalize_or_zero();
+        let world_normals = [0, 1, 2].map(|i| match normals {
+            Some(normals) => (normal_matrix * Vec3A::from(normals[triangle[i]]))
+                .try_normalize()
+                .unwrap_or(face_normal),
+            None => face_normal,
+        });
+
+        let [a, b, c] = texel_positions;
+        let area = (b - a).perp_dot(c - a);
+        if area.abs() < f32::EPSILON || face_normal == Vec3A::ZERO {
+            continue;
+        }
+        let min = a.min(b).min(c).floor().max(Vec2::ZERO).as_uvec2();
+        let max = a
+            .max(b)
+            .max(c)
+            .ceil()
+            .as_uvec2()
+            .min(size.saturating_sub(UVec2::ONE));
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let texel = &mut texels[(y * size.x + x) as usize];
+                if texel.is_some() {
+                    continue;
+                }
+                let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let weights = Vec3A::new(
+                    (c - b).perp_dot(center - b),
+                    (a - c).perp_dot(center - c),
+                    (b - a).perp_dot(center - a),
+                ) / area;
+                if weights.min_element() < 0.0 {
+                    continue;
+                }
+                *texel = Some(TexelSurface {
+                    position: interpolate(world_positions, weights),
+                    normal: interpolate(world_normals, weights)
+                        .try_normalize()
+                        .unwrap_or(face_normal),
+                });
+            }
+        }
+    }
+    texels
+}
+
+/// Fills the empty texels next to covered texels with the average of their neighbors, `passes`
+/// times, so that filtering the lightmap doesn't blend the charts with black.
+fn dilate(values: &mut [Option<Vec3>], size: UVec2, passes: u32) {
+    for _ in 0..passes {
+        let previous = values.to_vec();
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let index = (y * size.x + x) as usize;
+                if previous[index].is_some() {
+                    continue;
+                }
+                let mut sum = Vec3::ZERO;
+                let mut count = 0;
+                for neighbor_y in y.saturating_sub(1)..=(y + 1).min(size.y - 1) {
+                    for neighbor_x in x.saturating_sub(1)..=(x + 1).min(size.x - 1) {
+                        if let Some(value) = previous[(neighbor_y * size.x + neighbor_x) as usize] {
+                            sum += value;
+                            count += 1;
+                        }
+                    }
+                }
+                if count > 0 {
+                    values[index] = Some(sum / count as f32);
+                }
+            }
+        }
+    }
+}
+
+/// Bakes the lightmap of a mesh with lightmap UVs, placed in the scene with `transform`.
+///
+/// Each texel stores the illuminance of the surface divided by π, which is the radiance reflected
+/// by a white Lambertian surface, as expected by the shaders.
+pub(super) fn bake_lightmap(
+    scene: &BakeScene,
+    mesh: &Mesh,
+    transform: &Affine3A,
+    size: UVec2,
+    settings: &IntegratorSettings,
+) -> Image {
+    let texels = rasterize(mesh, transform, size);
+    let samples = settings.samples.max(1);
+
+    let mut values: Vec<Option<Vec3>> = texels
+        .iter()
+        .enumerate()
+        .map(|(index, texel)| {
+            let texel = texel.as_ref()?;
+            let mut rng = Rng::new(settings.seed ^ index as u64);
+            let origin = texel.position + texel.normal * RAY_EPSILON;
+
+            let mut indirect = Vec3::ZERO;
+            if settings.bounces > 0 {
+                for _ in 0..samples {
+                    let direction = cosine_sample_hemisphere(texel.normal, &mut rng);
+                    indirect += trace_path(scene, origin, direction, settings.bounces, &mut rng);
+                }
+            }
+            // With cosine weighted samples, the average radiance is the illuminance divided by π.
+            let direct = scene
+                .direct_illuminance(texel.position, texel.normal, |light| light.bakes_direct());
+            Some(indirect / samples as f32 + direct / PI)
+        })
+        .collect();
+    dilate(&mut values, size, settings.dilation);
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0; 8],
+        TextureFormat::Rgba16Float,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::linear();
+    for (index, value) in values.into_iter().enumerate() {
+        let value = value.unwrap_or(Vec3::ZERO);
+        let (x, y) = (index as u32 % size.x, index as u32 / size.x);
+        // The image was just created with a format supporting this.
+        let _ = image.set_color_at(x, y, LinearRgba::rgb(value.x, value.y, value.z).into());
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{UVec2, Vec3};
+
+    use super::dilate;
+
+    #[test]
+    fn dilation() {
+        let mut values = vec![None; 9];
+        values[4] = Some(Vec3::ONE);
+        dilate(&mut values, UVec2::new(3, 3), 1);
+        assert!(values.iter().all(|value| *value == Some(Vec3::ONE)));
+
+        let mut values = vec![None; 5];
+        values[0] = Some(Vec3::ONE);
+        dilate(&mut values, UVec2::new(5, 1), 2);
+        assert_eq!(values[2], Some(Vec3::ONE));
+        assert_eq!(values[3], None);
+    }
+}
@@ -0,0 +1,443 @@
+//! A CPU lightmap baker, which path traces the lighting of static meshes into [`Lightmap`]s.
+//!
+//! Add a [`BakeLightmap`] component to the meshes to bake, then send a [`BakeLightmaps`] event.
+//! The baker generates lightmap UVs for the meshes which don't have any, see
+//! [`Mesh::generate_lightmap_uvs`], and path traces the scene in the background on the
+//! [`AsyncComputeTaskPool`]. When all the lightmaps are baked, it replaces the meshes of the
+//! entities with the meshes with lightmap UVs, inserts their [`Lightmap`]s, and sends a
+//! [`LightmapsBaked`] event.
+//!
+//! The baked meshes and lightmap images are regular assets, which can be saved to be loaded
+//! instead of baking them again.
+//!
+//! The scene is made of the visible meshes which cast shadows, with the base color and emissive
+//! of their [`StandardMaterial`], and of the directional, point and spot lights. Their meshes and
+//! images must be kept in the main world, see
+//! [`RenderAssetUsages::MAIN_WORLD`](bevy_asset::RenderAssetUsages::MAIN_WORLD): those which
+//! aren't are ignored, or use the base color of their material. The indirect light of all lights
+//! is baked, while their direct light is only baked for the lights which don't affect lightmapped
+//! meshes at runtime, such as
+//! [`DirectionalLight::affects_lightmapped_mesh_diffuse`](crate::DirectionalLight::affects_lightmapped_mesh_diffuse).
+
+mod integrator;
+mod scene;
+
+use alloc::sync::Arc;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_asset::{AssetId, Assets, Handle};
+use bevy_color::{ColorToComponents, LinearRgba};
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_math::{ops, Rect, UVec2, Vec3A};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    mesh::{lightmap_uvs::LightmapUvSettings, Mesh, Mesh3d, VertexAttributeValues},
+    view::InheritedVisibility,
+};
+use bevy_tasks::{block_on, poll_once, AsyncComputeTaskPool, Task};
+use bevy_transform::components::GlobalTransform;
+use tracing::warn;
+
+use self::{
+    integrator::{bake_lightmap, IntegratorSettings},
+    scene::{BakeScene, SceneLight, SceneMaterial},
+};
+use crate::{
+    DirectionalLight, Lightmap, MeshMaterial3d, NotShadowCaster, PointLight, SpotLight,
+    StandardMaterial,
+};
+
+/// A plugin that bakes the [`Lightmap`]s of the entities with a [`BakeLightmap`] component when a
+/// [`BakeLightmaps`] event is sent.
+pub struct LightmapBakePlugin;
+
+impl Plugin for LightmapBakePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BakeLightmap>()
+            .add_event::<BakeLightmaps>()
+            .add_event::<LightmapsBaked>()
+            .add_systems(Update, (start_lightmap_bake, finish_lightmap_bake).chain());
+    }
+}
+
+/// Add this component to an entity with a [`Mesh3d`] to bake its [`Lightmap`] when a
+/// [`BakeLightmaps`] event is sent.
+///
+/// The entity should not move: the lightmap is only valid where it was baked.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct BakeLightmap;
+
+/// Settings of the lightmap baker, see [`BakeLightmaps`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub struct LightmapBakeSettings {
+    /// The settings of the lightmap UVs generated for the meshes which don't have any.
+    ///
+    /// The texel density applies to the meshes scaled by the transform of the first entity using
+    /// them. The padding is also filled with the lighting of the nearest texels, so that the
+    /// filtering of the lightmap doesn't darken the edges of the charts.
+    pub uvs: LightmapUvSettings,
+    /// The number of paths traced for each texel.
+    ///
+    /// The default is `128`.
+    pub samples: u32,
+    /// The number of times light bounces off surfaces before reaching the texels. `0` only bakes
+    /// direct lighting.
+    ///
+    /// The default is `3`.
+    pub bounces: u32,
+    /// The radiance of the rays which don't hit anything, in the same units as
+    /// [`StandardMaterial::emissive`].
+    ///
+    /// The default is [`LinearRgba::BLACK`].
+    pub sky: LinearRgba,
+}
+
+impl Default for LightmapBakeSettings {
+    fn default() -> Self {
+        Self {
+            uvs: LightmapUvSettings::default(),
+            samples: 128,
+            bounces: 3,
+            sky: LinearRgba::BLACK,
+        }
+    }
+}
+
+/// Send this event to bake the lightmaps of the entities with a [`BakeLightmap`] component.
+///
+/// It is ignored while a bake is in progress.
+#[derive(Event, Clone, Copy, Debug, Default)]
+pub struct BakeLightmaps {
+    /// The settings of the bake.
+    pub settings: LightmapBakeSettings,
+}
+
+/// Sent when the lightmaps requested by a [`BakeLightmaps`] event are baked and applied.
+#[derive(Event, Clone, Debug)]
+pub struct LightmapsBaked {
+    /// The entities which received a [`Lightmap`].
+    pub entities: Vec<Entity>,
+}
+
+/// The lightmaps being baked.
+#[derive(Resource)]
+struct LightmapBake {
+    tasks: Vec<LightmapBakeTask>,
+    baked: Vec<Entity>,
+}
+
+struct LightmapBakeTask {
+    entity: Entity,
+    /// The mesh with lightmap UVs to apply to the entity.
+    mesh: Handle<Mesh>,
+    task: Task<Image>,
+}
+
+/// A mesh with lightmap UVs, shared by all the entities baked with the same mesh.
+struct LightmapMesh {
+    mesh: Arc<Mesh>,
+    /// The handle of the mesh, or `None` if it's a new mesh which isn't added yet.
+    handle: Option<Handle<Mesh>>,
+    size: UVec2,
+}
+
+/// Gathers the scene and starts baking the lightmaps when a [`BakeLightmaps`] event is received.
+fn start_lightmap_bake(
+    mut commands: Commands,
+    mut events: EventReader<BakeLightmaps>,
+    bake: Option<Res<LightmapBake>>,
+    targets: Query<(Entity, &Mesh3d, &GlobalTransform), With<BakeLightmap>>,
+    occluders: Query<
+        (
+            &Mesh3d,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Option<&MeshMaterial3d<StandardMaterial>>,
+        ),
+        Without<NotShadowCaster>,
+    >,
+    lights: (
+        Query<(&DirectionalLight, &GlobalTransform, &InheritedVisibility)>,
+        Query<(&PointLight, &GlobalTransform, &InheritedVisibility)>,
+        Query<(&SpotLight, &GlobalTransform, &InheritedVisibility)>,
+    ),
+    mut meshes: ResMut<Assets<Mesh>>,
+    materials: Res<Assets<StandardMaterial>>,
+    images: Res<Assets<Image>>,
+) {
+    let Some(settings) = events.read().last().map(|event| event.settings) else {
+        return;
+    };
+    if bake.is_some() {
+        warn!("Ignoring a `BakeLightmaps` event, as lightmaps are already being baked");
+        return;
+    }
+
+    let (directional_lights, point_lights, spot_lights) = lights;
+    let scene_lights = directional_lights
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(light, transform, _)| SceneLight::Directional {
+            direction: transform.forward().as_vec3().into(),
+            illuminance: LinearRgba::from(light.color).to_vec3() * light.illuminance,
+            shadows: light.shadows_enabled,
+            bake_direct: !light.affects_lightmapped_mesh_diffuse,
+        })
+        .chain(
+            point_lights
+                .iter()
+                .filter(|(_, _, visibility)| visibility.get())
+                .map(|(light, transform, _)| SceneLight::Point {
+                    position: transform.translation_vec3a(),
+                    intensity: LinearRgba::from(light.color).to_vec3() * light.intensity
+                        / (4.0 * core::f32::consts::PI),
+                    inverse_range_squared: 1.0 / (light.range * light.range),
+                    shadows: light.shadows_enabled,
+                    bake_direct: !light.affects_lightmapped_mesh_diffuse,
+                    spot: None,
+                }),
+        )
+        .chain(
+            spot_lights
+                .iter()
+                .filter(|(_, _, visibility)| visibility.get())
+                .map(|(light, transform, _)| SceneLight::Point {
+                    position: transform.translation_vec3a(),
+                    intensity: LinearRgba::from(light.color).to_vec3() * light.intensity
+                        / (4.0 * core::f32::consts::PI),
+                    inverse_range_squared: 1.0 / (light.range * light.range),
+                    shadows: light.shadows_enabled,
+                    bake_direct: !light.affects_lightmapped_mesh_diffuse,
+                    spot: Some((
+                        transform.forward().as_vec3().into(),
+                        ops::cos(light.inner_angle),
+                        ops::cos(light.outer_angle),
+                    )),
+                }),
+        )
+        .collect();
+
+    // Gather the materials, sharing the textures between them.
+    let mut scene_materials = vec![SceneMaterial::DEFAULT];
+    let mut material_indices = HashMap::<AssetId<StandardMaterial>, u32>::default();
+    let mut textures = HashMap::<AssetId<Image>, Option<Arc<Image>>>::default();
+    let mut material_index = |material: Option<&MeshMaterial3d<StandardMaterial>>| {
+        let Some(material) = material else {
+            return 0;
+        };
+        *material_indices.entry(material.id()).or_insert_with(|| {
+            let Some(material) = materials.get(material) else {
+                return 0;
+            };
+            let base_color_texture = material.base_color_texture.as_ref().and_then(|texture| {
+                textures
+                    .entry(texture.id())
+                    .or_insert_with(|| images.get(texture).cloned().map(Arc::new))
+                    .clone()
+            });
+            scene_materials.push(SceneMaterial {
+                base_color: LinearRgba::from(material.base_color).to_vec3(),
+                base_color_texture,
+                emissive: material.emissive.to_vec3(),
+            });
+            scene_materials.len() as u32 - 1
+        })
+    };
+
+    let mut meshes_to_add = Vec::new();
+    for (mesh, transform, visibility, material) in &occluders {
+        if !visibility.get() {
+            continue;
+        }
+        if let Some(mesh) = meshes.get(mesh) {
+            meshes_to_add.push((mesh, transform.affine(), material_index(material)));
+        }
+    }
+    let mut scene = BakeScene::new(scene_materials, scene_lights, settings.sky.to_vec3());
+    for (mesh, transform, material) in meshes_to_add {
+        scene.add_mesh(mesh, &transform, material);
+    }
+    scene.build();
+    let scene = Arc::new(scene);
+
+    // Generate the lightmap UVs of the meshes to bake.
+    let mut lightmap_meshes = HashMap::<AssetId<Mesh>, LightmapMesh>::default();
+    for (entity, mesh, transform) in &targets {
+        if lightmap_meshes.contains_key(&mesh.id()) {
+            continue;
+        }
+        let Some(source) = meshes.get(mesh) else {
+            warn!(
+                "Can't bake the lightmap of {entity}, as its mesh isn't loaded in the main world"
+            );
+            continue;
+        };
+        if let Some(lightmap_mesh) = lightmap_mesh(source, mesh, transform, &settings.uvs) {
+            lightmap_meshes.insert(mesh.id(), lightmap_mesh);
+        } else {
+            warn!("Can't bake the lightmap of {entity}, as lightmap UVs can't be generated for its mesh");
+        }
+    }
+    for lightmap_mesh in lightmap_meshes.values_mut() {
+        if lightmap_mesh.handle.is_none() {
+            lightmap_mesh.handle = Some(meshes.add((*lightmap_mesh.mesh).clone()));
+        }
+    }
+
+    let task_pool = AsyncComputeTaskPool::get();
+    let tasks = targets
+        .iter()
+        .filter_map(|(entity, mesh, transform)| {
+            let lightmap_mesh = lightmap_meshes.get(&mesh.id())?;
+            let scene = scene.clone();
+            let mesh = lightmap_mesh.mesh.clone();
+            let size = lightmap_mesh.size;
+            let transform = transform.affine();
+            let settings = IntegratorSettings {
+                samples: settings.samples,
+                bounces: settings.bounces,
+                dilation: settings.uvs.padding,
+                seed: entity.to_bits(),
+            };
+            Some(LightmapBakeTask {
+                entity,
+                mesh: lightmap_mesh.handle.clone()?,
+                task: task_pool.spawn(async move {
+                    bake_lightmap(&scene, &mesh, &transform, size, &settings)
+                }),
+            })
+        })
+        .collect();
+    commands.insert_resource(LightmapBake {
+        tasks,
+        baked: Vec::new(),
+    });
+}
+
+/// Returns the mesh to bake with lightmap UVs, generating them if the mesh doesn't have any.
+fn lightmap_mesh(
+    source: &Mesh,
+    handle: &Mesh3d,
+    transform: &GlobalTransform,
+    settings: &LightmapUvSettings,
+) -> Option<LightmapMesh> {
+    let scale = transform.scale().abs().max_element();
+    if let Some(VertexAttributeValues::Float32x2(_)) = source.attribute(Mesh::ATTRIBUTE_UV_1) {
+        // Size the lightmap for the area of the existing UVs to be close to the texel density.
+        let area = surface_area(source) * scale * scale;
+        let side = (ops::sqrt(area) * settings.texels_per_unit).ceil() as u32;
+        return Some(LightmapMesh {
+            mesh: Arc::new(source.clone()),
+            handle: Some(handle.0.clone()),
+            size: UVec2::splat(side.clamp(1, settings.max_size)),
+        });
+    }
+
+    let mut mesh = source.clone();
+    let size = mesh
+        .generate_lightmap_uvs(&LightmapUvSettings {
+            texels_per_unit: settings.texels_per_unit * scale,
+            ..*settings
+        })
+        .ok()?;
+    Some(LightmapMesh {
+        mesh: Arc::new(mesh),
+        handle: None,
+        size,
+    })
+}
+
+fn surface_area(mesh: &Mesh) -> f32 {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return 0.0;
+    };
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+    indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3A::from(positions[triangle[i]]));
+            (b - a).cross(c - a).length() / 2.0
+        })
+        .sum()
+}
+
+/// Applies the lightmaps whose bake is complete, and sends a [`LightmapsBaked`] event once they
+/// all are.
+fn finish_lightmap_bake(
+    mut commands: Commands,
+    bake: Option<ResMut<LightmapBake>>,
+    mut images: ResMut<Assets<Image>>,
+    mut baked_events: EventWriter<LightmapsBaked>,
+) {
+    let Some(mut bake) = bake else {
+        return;
+    };
+    let LightmapBake { tasks, baked } = &mut *bake;
+    tasks.retain_mut(|task| {
+        let Some(image) = block_on(poll_once(&mut task.task)) else {
+            return true;
+        };
+        let image = images.add(image);
+        if let Some(mut entity) = commands.get_entity(task.entity) {
+            entity.insert((
+                Mesh3d(task.mesh.clone()),
+                Lightmap {
+                    image,
+                    uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+                    bicubic_sampling: false,
+                },
+            ));
+            baked.push(task.entity);
+        }
+        false
+    });
+
+    if tasks.is_empty() {
+        baked_events.send(LightmapsBaked {
+            entities: core::mem::take(baked),
+        });
+        commands.remove_resource::<LightmapBake>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{primitives::Cuboid, Vec3};
+    use bevy_render::mesh::{lightmap_uvs::LightmapUvSettings, Mesh, Mesh3d};
+    use bevy_transform::components::GlobalTransform;
+
+    use super::{lightmap_mesh, surface_area};
+
+    #[test]
+    fn lightmap_mesh_texel_density() {
+        let cube = Mesh::from(Cuboid::default());
+        assert!((surface_area(&cube) - 6.0).abs() < 1.0e-5);
+
+        let settings = LightmapUvSettings::default();
+        let small = lightmap_mesh(
+            &cube,
+            &Mesh3d::default(),
+            &GlobalTransform::IDENTITY,
+            &settings,
+        )
+        .unwrap();
+        let large = lightmap_mesh(
+            &cube,
+            &Mesh3d::default(),
+            &GlobalTransform::from_scale(Vec3::splat(4.0)),
+            &settings,
+        )
+        .unwrap();
+        assert!(large.size.x > small.size.x * 3);
+        assert!(small.mesh.attribute(Mesh::ATTRIBUTE_UV_1).is_some());
+    }
+}
@@ -0,0 +1,504 @@
+//! The scene traced by the lightmap baker: the triangles of the meshes in world space, organized
+//! in a bounding volume hierarchy, their materials, and the lights.
+
+use alloc::sync::Arc;
+use bevy_color::{ColorToComponents, LinearRgba};
+use bevy_image::Image;
+use bevy_math::{Affine3A, Vec2, Vec3, Vec3A};
+use bevy_render::mesh::{Mesh, PrimitiveTopology, VertexAttributeValues};
+
+/// The distance rays are offset from the surfaces they start from, to avoid hitting them.
+pub(super) const RAY_EPSILON: f32 = 1.0e-3;
+
+/// The maximum number of triangles in a leaf of the BVH.
+const MAX_LEAF_TRIANGLES: usize = 4;
+
+/// A triangle of the scene, in world space.
+pub(super) struct SceneTriangle {
+    pub(super) positions: [Vec3A; 3],
+    pub(super) normals: [Vec3A; 3],
+    pub(super) uvs: [Vec2; 3],
+    pub(super) material: u32,
+}
+
+/// The properties of a material that affect the light bounced off a surface.
+pub(super) struct SceneMaterial {
+    pub(super) base_color: Vec3,
+    pub(super) base_color_texture: Option<Arc<Image>>,
+    pub(super) emissive: Vec3,
+}
+
+impl SceneMaterial {
+    /// The material used by meshes without a [`StandardMaterial`](crate::StandardMaterial).
+    pub(super) const DEFAULT: Self = Self {
+        base_color: Vec3::ONE,
+        base_color_texture: None,
+        emissive: Vec3::ZERO,
+    };
+
+    /// Returns the diffuse albedo of the material at the given UV.
+    pub(super) fn albedo(&self, uv: Vec2) -> Vec3 {
+        let Some(texture) = self.base_color_texture.as_ref() else {
+            return self.base_color;
+        };
+        let size = texture.size();
+        let texel = (uv - uv.floor()) * size.as_vec2();
+        let texel = texel.as_uvec2().min(size - 1);
+        match texture.get_color_at(texel.x, texel.y) {
+            Ok(color) => self.base_color * LinearRgba::from(color).to_vec3(),
+            Err(_) => self.base_color,
+        }
+    }
+}
+
+/// A light of the scene, whose direct lighting is computed with shadow rays.
+pub(super) enum SceneLight {
+    Directional {
+        /// The direction the light travels in.
+        direction: Vec3A,
+        /// The color multiplied by the illuminance, in lux.
+        illuminance: Vec3,
+        shadows: bool,
+        /// Whether the direct light is baked, which is the case for lights which don't affect
+        /// the diffuse lighting of lightmapped meshes at runtime.
+        bake_direct: bool,
+    },
+    Point {
+        position: Vec3A,
+        /// The color multiplied by the luminous intensity, in candela.
+        intensity: Vec3,
+        inverse_range_squared: f32,
+        shadows: bool,
+        bake_direct: bool,
+        /// The direction and the cosines of the inner and outer angles of a spot light.
+        spot: Option<(Vec3A, f32, f32)>,
+    },
+}
+
+impl SceneLight {
+    /// Returns the direction towards the light from `position`, the distance to the light, and the
+    /// illuminance it provides to a surface facing it, ignoring shadows.
+    fn illuminance_at(&self, position: Vec3A) -> (Vec3A, f32, Vec3) {
+        match *self {
+            SceneLight::Directional {
+                direction,
+                illuminance,
+                ..
+            } => (-direction, f32::INFINITY, illuminance),
+            SceneLight::Point {
+                position: light_position,
+                intensity,
+                inverse_range_squared,
+                spot,
+                ..
+            } => {
+                let to_light = light_position - position;
+                let distance_squared = to_light.length_squared();
+                let direction = to_light.normalize_or_zero();
+                // The same falloff as the shaders, see `getDistanceAttenuation`.
+                let factor = distance_squared * inverse_range_squared;
+                let smooth_factor = (1.0 - factor * factor).clamp(0.0, 1.0);
+                let mut attenuation = smooth_factor * smooth_factor / distance_squared.max(1.0e-4);
+                if let Some((spot_direction, cos_inner, cos_outer)) = spot {
+                    let cos_angle = spot_direction.dot(-direction);
+                    let scale = 1.0 / (cos_inner - cos_outer).max(1.0e-4);
+                    let spot_attenuation = ((cos_angle - cos_outer) * scale).clamp(0.0, 1.0);
+                    attenuation *= spot_attenuation * spot_attenuation;
+                }
+                (direction, distance_squared.sqrt(), intensity * attenuation)
+            }
+        }
+    }
+
+    fn casts_shadows(&self) -> bool {
+        match *self {
+            SceneLight::Directional { shadows, .. } | SceneLight::Point { shadows, .. } => shadows,
+        }
+    }
+
+    pub(super) fn bakes_direct(&self) -> bool {
+        match *self {
+            SceneLight::Directional { bake_direct, .. } | SceneLight::Point { bake_direct, .. } => {
+                bake_direct
+            }
+        }
+    }
+}
+
+/// The intersection of a ray with a triangle of the scene.
+pub(super) struct Hit {
+    pub(super) distance: f32,
+    pub(super) triangle: u32,
+    /// The barycentric coordinates of the second and third vertices.
+    pub(super) barycentric: Vec2,
+}
+
+/// A node of the BVH: either two children, or a range of triangles.
+struct BvhNode {
+    min: Vec3A,
+    max: Vec3A,
+    /// The index of the first child, or of the first triangle of a leaf.
+    start: u32,
+    /// The number of triangles of a leaf, or 0 for an inner node.
+    count: u32,
+}
+
+/// The scene traced by the baker.
+pub(super) struct BakeScene {
+    triangles: Vec<SceneTriangle>,
+    nodes: Vec<BvhNode>,
+    pub(super) materials: Vec<SceneMaterial>,
+    pub(super) lights: Vec<SceneLight>,
+    /// The radiance of the rays that don't hit anything.
+    pub(super) sky: Vec3,
+}
+
+impl BakeScene {
+    pub(super) fn new(materials: Vec<SceneMaterial>, lights: Vec<SceneLight>, sky: Vec3) -> Self {
+        Self {
+            triangles: Vec::new(),
+            nodes: Vec::new(),
+            materials,
+            lights,
+            sky,
+        }
+    }
+
+    /// Adds the triangles of a mesh, transformed to world space.
+    ///
+    /// Meshes which aren't triangle lists, or which have no positions, are ignored.
+    pub(super) fn add_mesh(&mut self, mesh: &Mesh, transform: &Affine3A, material: u32) {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return;
+        };
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return;
+        }
+        let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) => Some(normals),
+            _ => None,
+        };
+        let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => Some(uvs),
+            _ => None,
+        };
+        let normal_matrix = transform.matrix3.inverse().transpose();
+
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+        for triangle in indices.chunks_exact(3) {
+            let positions =
+                [0, 1, 2].map(|i| transform.transform_point3a(Vec3A::from(positions[triangle[i]])));
+            let face_normal = (positions[1] - positions[0])
+                .cross(positions[2] - positions[0])
+                .normalize_or_zero();
+            if face_normal == Vec3A::ZERO {
+                continue;
+            }
+            let normals = [0, 1, 2].map(|i| match normals {
+                Some(normals) => (normal_matrix * Vec3A::from(normals[triangle[i]]))
+                    .try_normalize()
+                    .unwrap_or(face_normal),
+                None => face_normal,
+            });
+            let uvs = [0, 1, 2].map(|i| uvs.map_or(Vec2::ZERO, |uvs| Vec2::from(uvs[triangle[i]])));
+            self.triangles.push(SceneTriangle {
+                positions,
+                normals,
+                uvs,
+                material,
+            });
+        }
+    }
+
+    /// Builds the BVH, once all meshes are added.
+    pub(super) fn build(&mut self) {
+        self.nodes.clear();
+        if self.triangles.is_empty() {
+            return;
+        }
+        let mut centroids: Vec<Vec3A> = self
+            .triangles
+            .iter()
+            .map(|triangle| triangle.positions.iter().copied().sum::<Vec3A>() / 3.0)
+            .collect();
+
+        self.nodes.push(BvhNode {
+            min: Vec3A::ZERO,
+            max: Vec3A::ZERO,
+            start: 0,
+            count: self.triangles.len() as u32,
+        });
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let start = self.nodes[node_index].start as usize;
+            let end = start + self.nodes[node_index].count as usize;
+            let (min, max) = self.triangles[start..end].iter().fold(
+                (Vec3A::splat(f32::MAX), Vec3A::splat(f32::MIN)),
+                |(min, max), triangle| {
+                    triangle
+                        .positions
+                        .iter()
+                        .fold((min, max), |(min, max), &p| (min.min(p), max.max(p)))
+                },
+            );
+            self.nodes[node_index].min = min;
+            self.nodes[node_index].max = max;
+            if end - start <= MAX_LEAF_TRIANGLES {
+                continue;
+            }
+
+            // Split at the median of the centroids along the longest axis of their bounds.
+            let (centroid_min, centroid_max) = centroids[start..end].iter().fold(
+                (Vec3A::splat(f32::MAX), Vec3A::splat(f32::MIN)),
+                |(min, max), &c| (min.min(c), max.max(c)),
+            );
+            let extent = centroid_max - centroid_min;
+            let axis = if extent.x >= extent.y && extent.x >= extent.z {
+                0
+            } else if extent.y >= extent.z {
+                1
+            } else {
+                2
+            };
+            let mut order: Vec<usize> = (start..end).collect();
+            let middle = order.len() / 2;
+            order.select_nth_unstable_by(middle, |&a, &b| {
+                centroids[a][axis].total_cmp(&centroids[b][axis])
+            });
+            reorder(&mut self.triangles, &mut centroids, start, &order);
+
+            let left = self.nodes.len();
+            let middle = start + middle;
+            for (start, end) in [(start, middle), (middle, end)] {
+                self.nodes.push(BvhNode {
+                    min: Vec3A::ZERO,
+                    max: Vec3A::ZERO,
+                    start: start as u32,
+                    count: (end - start) as u32,
+                });
+            }
+            self.nodes[node_index].start = left as u32;
+            self.nodes[node_index].count = 0;
+            stack.extend([left, left + 1]);
+        }
+    }
+
+    pub(super) fn triangle(&self, index: u32) -> &SceneTriangle {
+        &self.triangles[index as usize]
+    }
+
+    /// Returns the closest intersection of the ray with the scene, closer than `max_distance`.
+    ///
+    /// If `any_hit` is true, returns the first intersection found instead, which is enough for
+    /// shadow rays.
+    pub(super) fn intersect(
+        &self,
+        origin: Vec3A,
+        direction: Vec3A,
+        max_distance: f32,
+        any_hit: bool,
+    ) -> Option<Hit> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let inverse_direction = direction.recip();
+        let mut closest: Option<Hit> = None;
+        let mut max_distance = max_distance;
+        let mut stack = vec![0u32];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index as usize];
+            if !intersect_bounds(node, origin, inverse_direction, max_distance) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.extend([node.start, node.start + 1]);
+                continue;
+            }
+            for index in node.start..node.start + node.count {
+                let triangle = &self.triangles[index as usize];
+                let Some((distance, barycentric)) =
+                    intersect_triangle(triangle, origin, direction, max_distance)
+                else {
+                    continue;
+                };
+                max_distance = distance;
+                closest = Some(Hit {
+                    distance,
+                    triangle: index,
+                    barycentric,
+                });
+                if any_hit {
+                    return closest;
+                }
+            }
+        }
+        closest
+    }
+
+    /// Returns the illuminance provided by the lights selected by `filter` to a surface at
+    /// `position` facing `normal`, including shadows.
+    pub(super) fn direct_illuminance(
+        &self,
+        position: Vec3A,
+        normal: Vec3A,
+        filter: impl Fn(&SceneLight) -> bool,
+    ) -> Vec3 {
+        let mut illuminance = Vec3::ZERO;
+        for light in &self.lights {
+            if !filter(light) {
+                continue;
+            }
+            let (direction, distance, light_illuminance) = light.illuminance_at(position);
+            let cos_theta = normal.dot(direction);
+            if cos_theta <= 0.0 || light_illuminance == Vec3::ZERO {
+                continue;
+            }
+            if light.casts_shadows() {
+                let origin = position + normal * RAY_EPSILON;
+                let max_distance = distance - 2.0 * RAY_EPSILON;
+                if self
+                    .intersect(origin, direction, max_distance, true)
+                    .is_some()
+                {
+                    continue;
+                }
+            }
+            illuminance += light_illuminance * cos_theta;
+        }
+        illuminance
+    }
+}
+
+/// Moves the triangles and centroids from `start` to the positions given by `order`.
+fn reorder(
+    triangles: &mut Vec<SceneTriangle>,
+    centroids: &mut [Vec3A],
+    start: usize,
+    order: &[usize],
+) {
+    let moved_centroids: Vec<Vec3A> = order.iter().map(|&i| centroids[i]).collect();
+    centroids[start..start + order.len()].copy_from_slice(&moved_centroids);
+
+    let mut slots: Vec<Option<SceneTriangle>> = triangles
+        .drain(start..start + order.len())
+        .map(Some)
+        .collect();
+    let tail = triangles.split_off(start);
+    triangles.extend(order.iter().map(|&i| slots[i - start].take().unwrap()));
+    triangles.extend(tail);
+}
+
+/// Tests the ray against the bounds of a node, with the slab method.
+fn intersect_bounds(
+    node: &BvhNode,
+    origin: Vec3A,
+    inverse_direction: Vec3A,
+    max_distance: f32,
+) -> bool {
+    let t0 = (node.min - origin) * inverse_direction;
+    let t1 = (node.max - origin) * inverse_direction;
+    let near = t0.min(t1).max_element().max(0.0);
+    let far = t0.max(t1).min_element().min(max_distance);
+    near <= far
+}
+
+/// Intersects the ray with a triangle, with the Möller–Trumbore algorithm, and returns the
+/// distance and barycentric coordinates of the intersection.
+fn intersect_triangle(
+    triangle: &SceneTriangle,
+    origin: Vec3A,
+    direction: Vec3A,
+    max_distance: f32,
+) -> Option<(f32, Vec2)> {
+    let [a, b, c] = triangle.positions;
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = direction.cross(edge2);
+    let determinant = edge1.dot(p);
+    if determinant.abs() < 1.0e-12 {
+        return None;
+    }
+    let inverse_determinant = determinant.recip();
+    let s = origin - a;
+    let u = s.dot(p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = direction.dot(q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = edge2.dot(q) * inverse_determinant;
+    (distance > 0.0 && distance < max_distance).then_some((distance, Vec2::new(u, v)))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{primitives::Cuboid, Affine3A, Vec3, Vec3A};
+    use bevy_render::mesh::Mesh;
+
+    use super::{BakeScene, SceneLight, SceneMaterial};
+
+    fn scene_with_cubes(count: usize) -> BakeScene {
+        let mut scene = BakeScene::new(vec![SceneMaterial::DEFAULT], Vec::new(), Vec3::ZERO);
+        let cube = Mesh::from(Cuboid::default());
+        for i in 0..count {
+            let transform = Affine3A::from_translation(Vec3::new(i as f32 * 2.0, 0.0, 0.0));
+            scene.add_mesh(&cube, &transform, 0);
+        }
+        scene.build();
+        scene
+    }
+
+    #[test]
+    fn ray_intersection() {
+        let scene = scene_with_cubes(16);
+
+        // Along the row of cubes, the ray hits the first cube.
+        let hit = scene
+            .intersect(Vec3A::new(-5.0, 0.0, 0.0), Vec3A::X, f32::INFINITY, false)
+            .unwrap();
+        assert!((hit.distance - 4.5).abs() < 1.0e-4);
+
+        // Between two cubes, the ray hits nothing.
+        assert!(scene
+            .intersect(Vec3A::new(1.0, -5.0, 0.0), Vec3A::Y, f32::INFINITY, false)
+            .is_none());
+
+        // Above the seventh cube, the ray hits its top face.
+        let hit = scene
+            .intersect(
+                Vec3A::new(12.0, 5.0, 0.0),
+                Vec3A::NEG_Y,
+                f32::INFINITY,
+                false,
+            )
+            .unwrap();
+        assert!((hit.distance - 4.5).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn shadowed_light() {
+        let mut scene = scene_with_cubes(1);
+        scene.lights.push(SceneLight::Directional {
+            direction: Vec3A::NEG_Y,
+            illuminance: Vec3::ONE,
+            shadows: true,
+            bake_direct: true,
+        });
+
+        // Above the cube, the light is unoccluded.
+        let lit = scene.direct_illuminance(Vec3A::new(0.0, 0.5, 0.0), Vec3A::Y, |_| true);
+        assert_eq!(lit, Vec3::ONE);
+
+        // Below the cube, the cube casts a shadow.
+        let shadowed = scene.direct_illuminance(Vec3A::new(0.0, -2.0, 0.0), Vec3A::Y, |_| true);
+        assert_eq!(shadowed, Vec3::ZERO);
+    }
+}
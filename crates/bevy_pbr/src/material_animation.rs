@@ -0,0 +1,326 @@
+//! Animation of the fields of materials with [`bevy_animation`].
+//!
+//! Materials are assets that are usually shared between many meshes, while animation curves
+//! write into the components of the animated entity. To animate a material, add a
+//! [`MaterialOverride`] next to the [`MeshMaterial3d`] of the animated entity: this gives the
+//! entity its own copy of the material, whose fields are animated with an
+//! [`AnimatedMaterialField`]:
+//!
+//! ```
+//! # use bevy_animation::{animation_curves::*, AnimationClip, AnimationTargetId};
+//! # use bevy_color::LinearRgba;
+//! # use bevy_ecs::name::Name;
+//! # use bevy_math::Vec2;
+//! # use bevy_pbr::{AnimatedMaterialField, StandardMaterial};
+//! # let animation_target_id = AnimationTargetId::from(&Name::new("Lamp"));
+//! let mut animation_clip = AnimationClip::default();
+//! animation_clip.add_curve_to_target(
+//!     animation_target_id,
+//!     AnimatableCurve::new(
+//!         AnimatedMaterialField::<StandardMaterial, LinearRgba>::new("emissive"),
+//!         AnimatableKeyframeCurve::new([
+//!             (0.0, LinearRgba::BLACK),
+//!             (1.0, LinearRgba::rgb(4.0, 3.0, 1.0)),
+//!         ])
+//!         .expect("Failed to create emissive curve"),
+//!     ),
+//! );
+//! animation_clip.add_curve_to_target(
+//!     animation_target_id,
+//!     AnimatableCurve::new(
+//!         AnimatedMaterialField::<StandardMaterial, Vec2>::new("uv_transform.translation"),
+//!         AnimatableKeyframeCurve::new([(0.0, Vec2::ZERO), (1.0, Vec2::new(0.5, 0.0))])
+//!             .expect("Failed to create UV offset curve"),
+//!     ),
+//! );
+//! ```
+
+use core::{any::TypeId, hash::BuildHasher, marker::PhantomData};
+
+use bevy_animation::{
+    animatable::Animatable,
+    animation_curves::{AnimatableProperty, EvaluatorId},
+    AnimationEntityMut, AnimationEvaluationError,
+};
+use bevy_app::{Animation, App, Plugin, PostUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_platform_support::hash::{FixedHasher, Hashed};
+use bevy_reflect::{ParsedPath, Reflect, ReflectPath};
+
+use crate::{Material, MeshMaterial3d};
+
+/// Makes the fields of materials of type `M` animatable with [`AnimatedMaterialField`], by
+/// managing the [`MaterialOverride<M>`] components.
+///
+/// This is added for [`StandardMaterial`](crate::StandardMaterial) by the
+/// [`PbrPlugin`](crate::PbrPlugin).
+pub struct MaterialAnimationPlugin<M>(PhantomData<fn() -> M>);
+
+impl<M> Default for MaterialAnimationPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Material + Reflect> Plugin for MaterialAnimationPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                init_material_overrides::<M>.before(Animation),
+                sync_material_overrides::<M>.after(Animation),
+            ),
+        )
+        .add_observer(restore_overridden_material::<M>);
+    }
+}
+
+/// A copy of the material of a [`MeshMaterial3d<M>`] owned by a single entity, so the material
+/// can be animated without affecting the other meshes sharing it.
+///
+/// Once the original material is loaded, it's copied into [`MaterialOverride::material`] and
+/// into a new material asset, which replaces the material of the [`MeshMaterial3d<M>`]. Changes
+/// to [`MaterialOverride::material`], such as the ones made by an [`AnimatedMaterialField`],
+/// are then written to that asset. Removing the component restores the original material.
+///
+/// Setting [`MaterialOverride::material`] before the component is inserted overrides the
+/// material with the given one instead of a copy of the original.
+#[derive(Component, Clone, Debug)]
+pub struct MaterialOverride<M: Material> {
+    /// The material of the entity, or `None` while the original material isn't loaded yet.
+    pub material: Option<M>,
+    /// The original material of the entity.
+    source: Option<Handle<M>>,
+    /// The material asset owned by the entity.
+    instance: Option<Handle<M>>,
+}
+
+impl<M: Material> Default for MaterialOverride<M> {
+    fn default() -> Self {
+        Self {
+            material: None,
+            source: None,
+            instance: None,
+        }
+    }
+}
+
+impl<M: Material> MaterialOverride<M> {
+    /// Creates a [`MaterialOverride`] replacing the material of the entity with `material`.
+    pub fn new(material: M) -> Self {
+        Self {
+            material: Some(material),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the material asset owned by the entity, once it's been created.
+    pub fn instance(&self) -> Option<&Handle<M>> {
+        self.instance.as_ref()
+    }
+}
+
+/// An [`AnimatableProperty`] animating a field of the [`MaterialOverride<M>`] of an entity.
+///
+/// The field is selected with a reflection path, as accepted by [`ParsedPath::parse`], such as
+/// `"emissive"` or `"uv_transform.translation"`, and must be of type `P`.
+pub struct AnimatedMaterialField<M, P> {
+    path: ParsedPath,
+    /// A pre-hashed (override-type-id, path-hash) pair, uniquely identifying a material field
+    evaluator_id: Hashed<(TypeId, usize)>,
+    marker: PhantomData<fn() -> (M, P)>,
+}
+
+impl<M, P> Clone for AnimatedMaterialField<M, P> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            evaluator_id: self.evaluator_id,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Material, P> AnimatedMaterialField<M, P> {
+    /// Creates a new [`AnimatedMaterialField`] animating the field of `M` at `path`.
+    ///
+    /// # Panics
+    /// If `path` isn't a valid reflection path.
+    pub fn new(path: &str) -> Self {
+        let path = ParsedPath::parse(path).expect("Material field path should be valid");
+        let path_hash = FixedHasher.hash_one(&path) as usize;
+        Self {
+            path,
+            evaluator_id: Hashed::new((TypeId::of::<MaterialOverride<M>>(), path_hash)),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M, P> AnimatableProperty for AnimatedMaterialField<M, P>
+where
+    M: Material + Reflect,
+    P: Animatable,
+{
+    type Property = P;
+
+    fn get_mut<'a>(
+        &self,
+        entity: &'a mut AnimationEntityMut,
+    ) -> Result<&'a mut P, AnimationEvaluationError> {
+        let material_override = entity
+            .get_mut::<MaterialOverride<M>>()
+            .ok_or_else(|| {
+                AnimationEvaluationError::ComponentNotPresent(TypeId::of::<MaterialOverride<M>>())
+            })?
+            .into_inner();
+        material_override
+            .material
+            .as_mut()
+            .and_then(|material| (&self.path).element_mut::<P>(material).ok())
+            .ok_or_else(|| AnimationEvaluationError::PropertyNotPresent(TypeId::of::<P>()))
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId {
+        EvaluatorId::ComponentField(&self.evaluator_id)
+    }
+}
+
+/// Gives each new [`MaterialOverride<M>`] its own material asset, once the original material is
+/// loaded.
+fn init_material_overrides<M: Material>(
+    mut overrides: Query<(&mut MaterialOverride<M>, &mut MeshMaterial3d<M>)>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    for (mut material_override, mut mesh_material) in &mut overrides {
+        if material_override.instance.is_some() {
+            continue;
+        }
+
+        let material = match material_override.material {
+            Some(ref material) => material.clone(),
+            None => match materials.get(&mesh_material.0) {
+                Some(material) => material.clone(),
+                None => continue,
+            },
+        };
+
+        let instance = materials.add(material.clone());
+        let material_override = &mut *material_override;
+        material_override.material = Some(material);
+        material_override.source = Some(mesh_material.0.clone());
+        material_override.instance = Some(instance.clone());
+        mesh_material.0 = instance;
+    }
+}
+
+/// Writes the changed [`MaterialOverride<M>`] components to their material assets.
+fn sync_material_overrides<M: Material>(
+    overrides: Query<&MaterialOverride<M>, Changed<MaterialOverride<M>>>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    for material_override in &overrides {
+        let (Some(material), Some(instance)) =
+            (&material_override.material, &material_override.instance)
+        else {
+            continue;
+        };
+        if let Some(asset) = materials.get_mut(instance) {
+            *asset = material.clone();
+        }
+    }
+}
+
+/// Points the [`MeshMaterial3d<M>`] of an entity back at its original material when its
+/// [`MaterialOverride<M>`] is removed.
+fn restore_overridden_material<M: Material>(
+    trigger: Trigger<OnRemove, MaterialOverride<M>>,
+    mut overrides: Query<(&MaterialOverride<M>, &mut MeshMaterial3d<M>)>,
+) {
+    let Ok((material_override, mut mesh_material)) = overrides.get_mut(trigger.target()) else {
+        return;
+    };
+    if let Some(ref source) = material_override.source {
+        mesh_material.0 = source.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardMaterial;
+    use bevy_color::{Color, LinearRgba};
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn material_override() {
+        let mut world = World::new();
+        world.init_resource::<Assets<StandardMaterial>>();
+        world.add_observer(restore_overridden_material::<StandardMaterial>);
+
+        let shared = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: Color::WHITE,
+                ..Default::default()
+            });
+        let overridden = world
+            .spawn((
+                MeshMaterial3d(shared.clone()),
+                MaterialOverride::<StandardMaterial>::default(),
+            ))
+            .id();
+        let other = world.spawn(MeshMaterial3d(shared.clone())).id();
+
+        world
+            .run_system_once(init_material_overrides::<StandardMaterial>)
+            .unwrap();
+        let instance = world
+            .get::<MaterialOverride<StandardMaterial>>(overridden)
+            .unwrap()
+            .instance()
+            .cloned()
+            .unwrap();
+        assert_ne!(instance, shared);
+        assert_eq!(
+            world
+                .get::<MeshMaterial3d<StandardMaterial>>(overridden)
+                .unwrap()
+                .0,
+            instance
+        );
+        assert_eq!(
+            world
+                .get::<MeshMaterial3d<StandardMaterial>>(other)
+                .unwrap()
+                .0,
+            shared
+        );
+
+        let field = AnimatedMaterialField::<StandardMaterial, LinearRgba>::new("emissive");
+        let mut material_override = world
+            .get_mut::<MaterialOverride<StandardMaterial>>(overridden)
+            .unwrap();
+        *(&field.path)
+            .element_mut::<LinearRgba>(material_override.material.as_mut().unwrap())
+            .unwrap() = LinearRgba::RED;
+        world
+            .run_system_once(sync_material_overrides::<StandardMaterial>)
+            .unwrap();
+        let materials = world.resource::<Assets<StandardMaterial>>();
+        assert_eq!(materials.get(&instance).unwrap().emissive, LinearRgba::RED);
+        assert_eq!(materials.get(&shared).unwrap().emissive, LinearRgba::BLACK);
+
+        world
+            .entity_mut(overridden)
+            .remove::<MaterialOverride<StandardMaterial>>();
+        assert_eq!(
+            world
+                .get::<MeshMaterial3d<StandardMaterial>>(overridden)
+                .unwrap()
+                .0,
+            shared
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use crate::Material;
 use bevy_asset::{AsAssetId, AssetId, Handle};
+use bevy_color::{Color, LinearRgba};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::Vec4;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use derive_more::derive::From;
 
@@ -65,3 +67,49 @@ impl<M: Material> AsAssetId for MeshMaterial3d<M> {
         self.id()
     }
 }
+
+/// Overrides of the material properties of a single mesh instance.
+///
+/// Giving each of many instances of a mesh a different color would otherwise require one material
+/// asset per instance, which prevents the instances from being batched together. The overrides
+/// are instead stored in the per-instance mesh data, next to the transform of the mesh, and
+/// replace the properties of the shared material when shading, so instances with different
+/// overrides are still drawn in a single batch.
+///
+/// [`StandardMaterial`](crate::StandardMaterial) applies the
+/// [`base_color`](Self::base_color) and [`emissive`](Self::emissive) overrides. Custom material
+/// shaders can read all the overrides from the `mesh` binding, and the
+/// [`custom`](Self::custom) values with `bevy_pbr::mesh_functions::get_custom_material_data`.
+///
+/// Overrides aren't applied to meshlets.
+///
+/// ```
+/// # use bevy_color::Color;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_pbr::{MeshMaterial3d, MeshMaterialOverrides, StandardMaterial};
+/// # use bevy_render::mesh::Mesh3d;
+/// # fn spawn(mut commands: Commands, mesh: Mesh3d, material: MeshMaterial3d<StandardMaterial>) {
+/// for i in 0..1000 {
+///     commands.spawn((
+///         mesh.clone(),
+///         material.clone(),
+///         MeshMaterialOverrides {
+///             base_color: Some(Color::hsl(i as f32 * 0.36, 0.8, 0.5)),
+///             ..Default::default()
+///         },
+///     ));
+/// }
+/// # }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct MeshMaterialOverrides {
+    /// Replaces the base color of the material, which is still multiplied by the base color
+    /// texture and vertex colors.
+    pub base_color: Option<Color>,
+    /// Replaces the emissive color of the material, which is still multiplied by the emissive
+    /// texture.
+    pub emissive: Option<LinearRgba>,
+    /// Arbitrary values for custom material shaders.
+    pub custom: [Vec4; 2],
+}
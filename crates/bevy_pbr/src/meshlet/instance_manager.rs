@@ -1,7 +1,7 @@
 use super::{meshlet_mesh_manager::MeshletMeshManager, MeshletMesh, MeshletMesh3d};
 use crate::{
-    Material, MeshFlags, MeshTransforms, MeshUniform, NotShadowCaster, NotShadowReceiver,
-    PreviousGlobalTransform, RenderMaterialBindings, RenderMaterialInstances,
+    Material, MeshFlags, MeshMaterialOverrides, MeshTransforms, MeshUniform, NotShadowCaster,
+    NotShadowReceiver, PreviousGlobalTransform, RenderMaterialBindings, RenderMaterialInstances,
     RenderMeshMaterialIds,
 };
 use bevy_asset::{AssetEvent, AssetServer, Assets, UntypedAssetId};
@@ -125,6 +125,7 @@ impl InstanceManager {
             None,
             None,
             None,
+            &MeshMaterialOverrides::default(),
         );
 
         // Append instance data
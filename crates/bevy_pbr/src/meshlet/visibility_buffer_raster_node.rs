@@ -2,11 +2,11 @@ use super::{
     pipelines::MeshletPipelines,
     resource_manager::{MeshletViewBindGroups, MeshletViewResources},
 };
-use crate::{LightEntity, ShadowView, ViewLightEntities};
+use crate::{LightEntity, ShadowView, StaticShadowView, ViewLightEntities};
 use bevy_color::LinearRgba;
 use bevy_core_pipeline::prepass::PreviousViewUniformOffset;
 use bevy_ecs::{
-    query::QueryState,
+    query::{QueryState, Without},
     world::{FromWorld, World},
 };
 use bevy_math::ops;
@@ -30,14 +30,18 @@ pub struct MeshletVisibilityBufferRasterPassNode {
         &'static MeshletViewResources,
         &'static ViewLightEntities,
     )>,
-    view_light_query: QueryState<(
-        &'static ShadowView,
-        &'static LightEntity,
-        &'static ViewUniformOffset,
-        &'static PreviousViewUniformOffset,
-        &'static MeshletViewBindGroups,
-        &'static MeshletViewResources,
-    )>,
+    // Meshlets aren't rendered in the cached shadow maps, only in the light views they are cached for
+    view_light_query: QueryState<
+        (
+            &'static ShadowView,
+            &'static LightEntity,
+            &'static ViewUniformOffset,
+            &'static PreviousViewUniformOffset,
+            &'static MeshletViewBindGroups,
+            &'static MeshletViewResources,
+        ),
+        Without<StaticShadowView>,
+    >,
 }
 
 impl FromWorld for MeshletVisibilityBufferRasterPassNode {
@@ -0,0 +1,1167 @@
+//! Screen-space outlines, for example to highlight selected entities.
+//!
+//! Outlined meshes are first rendered into a mask that stores their outline
+//! style. A jump flood then finds, for each pixel, the pixel of the mask whose
+//! outline reaches it, in a number of fullscreen passes that grows with the
+//! logarithm of the outline width. A last fullscreen pass blends the outlines
+//! over the image.
+
+use core::{iter, ops::Range};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_core_pipeline::{
+    core_3d::{
+        graph::{Core3d, Node3d},
+        Camera3d,
+    },
+    fullscreen_vertex_shader,
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::*,
+    query::{QueryItem, ROQueryItem},
+    system::{
+        lifetimeless::{Read, SRes},
+        SystemParamItem,
+    },
+};
+use bevy_image::BevyDefault as _;
+use bevy_math::{FloatOrd, Vec4};
+use bevy_platform_support::collections::{HashMap, HashSet};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    batching::{gpu_preprocessing::IndirectParametersBuffers, GetBatchData, GetFullBatchData},
+    camera::{Camera, ExtractedCamera},
+    diagnostic::RecordDiagnostics,
+    mesh::{Mesh3d, MeshVertexBufferLayoutRef, RenderMesh},
+    render_asset::RenderAssets,
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_phase::{
+        sort_phase_system, AddRenderCommand, CachedRenderPipelinePhaseItem, DrawFunctionId,
+        DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
+        SetItemPipeline, SortedPhaseItem, SortedRenderPhasePlugin, TrackedRenderPass,
+        ViewSortedRenderPhases,
+    },
+    render_resource::{
+        binding_types::{texture_2d, uniform_buffer},
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BlendComponent,
+        BlendFactor, BlendOperation, BlendState, CachedRenderPipelineId, ColorTargetState,
+        ColorWrites, CompareFunction, DynamicUniformBuffer, Extent3d, FragmentState, Operations,
+        PipelineCache, RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline,
+        RenderPipelineDescriptor, Shader, ShaderStages, ShaderType, SpecializedMeshPipeline,
+        SpecializedMeshPipelineError, SpecializedMeshPipelines, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, StoreOp, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureSampleType, TextureUsages, TextureView,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    sync_world::{MainEntity, MainEntityHashMap},
+    texture::{CachedTexture, ColorAttachment, TextureCache},
+    view::{
+        ExtractedView, Msaa, RenderVisibleEntities, RetainedViewEntity, ViewDepthTexture,
+        ViewTarget, ViewVisibility,
+    },
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use nonmax::NonMaxU32;
+use tracing::error;
+
+use crate::{
+    graph::NodePbr, DrawMesh, MeshPipeline, MeshPipelineKey, RenderMeshInstances, SetMeshBindGroup,
+    SetMeshViewBindGroup, ViewKeyCache,
+};
+
+const OUTLINE_MASK_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("6f0f3c5e-8b8e-4a51-9d55-2f4c1b7b2a61");
+const OUTLINE_JUMP_FLOOD_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3b8e5f14-9a27-4c6d-b1e0-58d2f7a94c3e");
+const OUTLINE_COMPOSITE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("c1d0a7e2-5d3b-4f0e-8f26-74b9e1a3c4d8");
+
+/// The largest [`Outline::width`], in pixels.
+///
+/// Each doubling of the widest outline on screen adds a fullscreen pass to the
+/// jump flood, so wider outlines are clamped to this value.
+pub const MAX_OUTLINE_WIDTH: f32 = 32.0;
+
+/// The format of the jump flood textures, which store the coordinates of the
+/// nearest pixel of the outline mask.
+const JUMP_FLOOD_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rg32Sint;
+
+/// Draws an [`Outline`] around meshes.
+///
+/// This plugin isn't part of the `DefaultPlugins`.
+#[derive(Debug, Default)]
+pub struct OutlinePlugin;
+
+impl Plugin for OutlinePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OUTLINE_MASK_SHADER_HANDLE,
+            "outline_mask.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            OUTLINE_JUMP_FLOOD_SHADER_HANDLE,
+            "outline_jump_flood.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            OUTLINE_COMPOSITE_SHADER_HANDLE,
+            "outline_composite.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Outline>()
+            .register_type::<OutlineMode>()
+            .add_plugins(SortedRenderPhasePlugin::<OutlineMask3d, OutlineBatchData>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<DrawFunctions<OutlineMask3d>>()
+            .init_resource::<RenderOutlines>()
+            .init_resource::<OutlineBuffers>()
+            .add_render_command::<OutlineMask3d, DrawOutlineMask>()
+            .add_systems(ExtractSchedule, (extract_outlines, extract_outline_phases))
+            .add_systems(
+                Render,
+                (
+                    queue_outlines.in_set(RenderSet::QueueMeshes),
+                    sort_phase_system::<OutlineMask3d>.in_set(RenderSet::PhaseSort),
+                    prepare_outline_pipelines.in_set(RenderSet::Prepare),
+                    prepare_outline_textures.in_set(RenderSet::PrepareResources),
+                    prepare_outline_buffers.in_set(RenderSet::PrepareBindGroups),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<OutlineNode>>(Core3d, NodePbr::Outline)
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::Tonemapping,
+                    NodePbr::Outline,
+                    Node3d::EndMainPassPostProcessing,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<OutlineMaskPipeline>()
+            .init_resource::<SpecializedMeshPipelines<OutlineMaskPipeline>>()
+            .init_resource::<OutlineJumpFloodPipeline>()
+            .init_resource::<OutlineCompositePipeline>()
+            .init_resource::<SpecializedRenderPipelines<OutlineCompositePipeline>>();
+    }
+}
+
+/// Draws an outline around the mesh of the entity it's attached to, as seen by 3D cameras.
+///
+/// Outlines are drawn in screen space after tonemapping, so their color isn't affected by
+/// exposure or tonemapping, and their width doesn't depend on the distance to the camera.
+/// The outlines of overlapping meshes are merged.
+///
+/// This requires the [`OutlinePlugin`] to be enabled.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct Outline {
+    /// The color of the outline. Its alpha sets the opacity of the outline.
+    pub color: Color,
+    /// The width of the outline in physical pixels, up to [`MAX_OUTLINE_WIDTH`].
+    pub width: f32,
+    /// Whether the outline is hidden behind other meshes.
+    pub mode: OutlineMode,
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            width: 3.0,
+            mode: OutlineMode::default(),
+        }
+    }
+}
+
+/// How an [`Outline`] interacts with the depth of the scene.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum OutlineMode {
+    /// Only the visible parts of the mesh are outlined.
+    #[default]
+    Occluded,
+    /// The whole mesh is outlined, even behind other meshes, like an X-ray.
+    AlwaysVisible,
+}
+
+/// The style of an outline, as written to the GPU.
+#[derive(Clone, Copy, Debug, PartialEq, ShaderType)]
+pub struct OutlineStyleUniform {
+    /// The color of the outline in linear RGBA.
+    pub color: Vec4,
+    /// The width of the outline in pixels.
+    pub width: f32,
+}
+
+/// The settings of a jump flood pass of the outlines, as written to the GPU.
+#[derive(Clone, Copy, Debug, Default, ShaderType)]
+pub struct OutlineJumpFloodUniform {
+    /// The distance in pixels to the neighbors whose seeds are compared.
+    pub step_size: i32,
+}
+
+/// The outline of a visible entity, in the render world.
+#[derive(Clone, Copy, Debug)]
+pub struct RenderOutline {
+    /// The index of the style of this outline in [`RenderOutlines::styles`].
+    pub style: u32,
+    /// Whether the outline is hidden behind other meshes.
+    pub mode: OutlineMode,
+}
+
+/// The outlines of the visible entities, extracted every frame.
+///
+/// Outlines that look the same share a style, so that they can be batched.
+#[derive(Resource, Default)]
+pub struct RenderOutlines {
+    /// The outline of each entity.
+    pub entities: MainEntityHashMap<RenderOutline>,
+    /// The distinct outline styles.
+    pub styles: Vec<OutlineStyleUniform>,
+    /// The width of the widest outline.
+    pub max_width: f32,
+}
+
+/// The GPU buffers holding the outline styles and the settings of the jump flood passes.
+#[derive(Resource, Default)]
+pub struct OutlineBuffers {
+    styles: DynamicUniformBuffer<OutlineStyleUniform>,
+    style_offsets: Vec<u32>,
+    style_bind_group: Option<BindGroup>,
+    jump_flood: DynamicUniformBuffer<OutlineJumpFloodUniform>,
+    jump_flood_offsets: Vec<u32>,
+}
+
+/// The outline textures of a view.
+///
+/// Both mask textures are resolved when MSAA is enabled. The color texture
+/// stores the premultiplied outline color, and the width texture stores the
+/// outline width in its red channel and the coverage of the mask in its green
+/// channel. The jump flood passes alternate between the two jump flood
+/// textures.
+#[derive(Component)]
+pub struct ViewOutlineTextures {
+    pub color: ColorAttachment,
+    pub width: ColorAttachment,
+    pub jump_flood: [CachedTexture; 2],
+}
+
+/// Identifies the outline composite pipeline used by a view.
+#[derive(Component, Deref, DerefMut)]
+pub struct OutlineCompositePipelineId(pub CachedRenderPipelineId);
+
+/// A mesh rendered into the outline mask.
+pub struct OutlineMask3d {
+    pub distance: f32,
+    pub pipeline: CachedRenderPipelineId,
+    pub entity: (Entity, MainEntity),
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub extra_index: PhaseItemExtraIndex,
+    /// Whether the mesh in question is indexed (uses an index buffer in
+    /// addition to its vertex buffer).
+    pub indexed: bool,
+}
+
+impl PhaseItem for OutlineMask3d {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity.0
+    }
+
+    fn main_entity(&self) -> MainEntity {
+        self.entity.1
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn extra_index(&self) -> PhaseItemExtraIndex {
+        self.extra_index.clone()
+    }
+
+    #[inline]
+    fn batch_range_and_extra_index_mut(&mut self) -> (&mut Range<u32>, &mut PhaseItemExtraIndex) {
+        (&mut self.batch_range, &mut self.extra_index)
+    }
+}
+
+impl SortedPhaseItem for OutlineMask3d {
+    // Back-to-front, so that the nearest outline wins where masks overlap.
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        FloatOrd(self.distance)
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        radsort::sort_by_key(items, |item| item.distance);
+    }
+
+    #[inline]
+    fn indexed(&self) -> bool {
+        self.indexed
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for OutlineMask3d {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+/// Batches the meshes of the outline mask like [`MeshPipeline`] does, without
+/// batching meshes with different outline styles together.
+pub struct OutlineBatchData;
+
+impl GetBatchData for OutlineBatchData {
+    type Param = (<MeshPipeline as GetBatchData>::Param, SRes<RenderOutlines>);
+    // The mesh comparison data, and the outline style index.
+    type CompareData = (<MeshPipeline as GetBatchData>::CompareData, u32);
+    type BufferData = <MeshPipeline as GetBatchData>::BufferData;
+
+    fn get_batch_data(
+        (mesh_param, outlines): &SystemParamItem<Self::Param>,
+        query_item: (Entity, MainEntity),
+    ) -> Option<(Self::BufferData, Option<Self::CompareData>)> {
+        let (buffer_data, compare_data) = MeshPipeline::get_batch_data(mesh_param, query_item)?;
+        let style = outlines.entities.get(&query_item.1)?.style;
+        Some((buffer_data, compare_data.map(|data| (data, style))))
+    }
+}
+
+impl GetFullBatchData for OutlineBatchData {
+    type BufferInputData = <MeshPipeline as GetFullBatchData>::BufferInputData;
+
+    fn get_binned_batch_data(
+        (mesh_param, _): &SystemParamItem<Self::Param>,
+        main_entity: MainEntity,
+    ) -> Option<Self::BufferData> {
+        MeshPipeline::get_binned_batch_data(mesh_param, main_entity)
+    }
+
+    fn get_index_and_compare_data(
+        (mesh_param, outlines): &SystemParamItem<Self::Param>,
+        main_entity: MainEntity,
+    ) -> Option<(NonMaxU32, Option<Self::CompareData>)> {
+        let (index, compare_data) =
+            MeshPipeline::get_index_and_compare_data(mesh_param, main_entity)?;
+        let style = outlines.entities.get(&main_entity)?.style;
+        Some((index, compare_data.map(|data| (data, style))))
+    }
+
+    fn get_binned_index(
+        (mesh_param, _): &SystemParamItem<Self::Param>,
+        main_entity: MainEntity,
+    ) -> Option<NonMaxU32> {
+        MeshPipeline::get_binned_index(mesh_param, main_entity)
+    }
+
+    fn write_batch_indirect_parameters_metadata(
+        mesh_index: u32,
+        indexed: bool,
+        base_output_index: u32,
+        batch_set_index: Option<NonMaxU32>,
+        indirect_parameters_buffers: &mut IndirectParametersBuffers,
+        indirect_parameters_offset: u32,
+    ) {
+        MeshPipeline::write_batch_indirect_parameters_metadata(
+            mesh_index,
+            indexed,
+            base_output_index,
+            batch_set_index,
+            indirect_parameters_buffers,
+            indirect_parameters_offset,
+        );
+    }
+}
+
+/// The draw commands of the outline mask.
+pub type DrawOutlineMask = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    SetOutlineStyleBindGroup<2>,
+    DrawMesh,
+);
+
+/// Binds the outline style of the entity being drawn.
+pub struct SetOutlineStyleBindGroup<const I: usize>;
+
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetOutlineStyleBindGroup<I> {
+    type Param = (SRes<RenderOutlines>, SRes<OutlineBuffers>);
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        (outlines, buffers): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let buffers = buffers.into_inner();
+        let Some(bind_group) = buffers.style_bind_group.as_ref() else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(&offset) = outlines
+            .into_inner()
+            .entities
+            .get(&item.main_entity())
+            .and_then(|outline| buffers.style_offsets.get(outline.style as usize))
+        else {
+            return RenderCommandResult::Skip;
+        };
+        pass.set_bind_group(I, bind_group, &[offset]);
+        RenderCommandResult::Success
+    }
+}
+
+/// The pipeline rendering meshes into the outline mask.
+#[derive(Resource)]
+pub struct OutlineMaskPipeline {
+    mesh_pipeline: MeshPipeline,
+    style_layout: BindGroupLayout,
+}
+
+/// Identifies a specific configuration of the outline mask pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OutlineMaskPipelineKey {
+    pub mesh_key: MeshPipelineKey,
+    pub mode: OutlineMode,
+}
+
+impl FromWorld for OutlineMaskPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let style_layout = render_device.create_bind_group_layout(
+            "outline_style_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                uniform_buffer::<OutlineStyleUniform>(true),
+            ),
+        );
+
+        Self {
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+            style_layout,
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for OutlineMaskPipeline {
+    type Key = OutlineMaskPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key.mesh_key, layout)?;
+        descriptor.label = Some("outline_mask_pipeline".into());
+        descriptor.layout.push(self.style_layout.clone());
+
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.shader = OUTLINE_MASK_SHADER_HANDLE;
+        fragment.targets = vec![
+            Some(ColorTargetState {
+                format: TextureFormat::Rgba16Float,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }),
+            Some(ColorTargetState {
+                format: TextureFormat::Rg16Float,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }),
+        ];
+
+        // The mask is drawn against the depth of the main pass, without changing it.
+        let depth_stencil = descriptor.depth_stencil.as_mut().unwrap();
+        depth_stencil.depth_write_enabled = false;
+        depth_stencil.depth_compare = match key.mode {
+            OutlineMode::Occluded => CompareFunction::GreaterEqual,
+            OutlineMode::AlwaysVisible => CompareFunction::Always,
+        };
+        descriptor.multisample.alpha_to_coverage_enabled = false;
+
+        Ok(descriptor)
+    }
+}
+
+/// The pipelines of the jump flood, which finds the pixel of the outline mask
+/// whose outline reaches each pixel.
+#[derive(Resource)]
+pub struct OutlineJumpFloodPipeline {
+    seed_layout: BindGroupLayout,
+    jump_flood_layout: BindGroupLayout,
+    seed_pipeline: CachedRenderPipelineId,
+    jump_flood_pipeline: CachedRenderPipelineId,
+}
+
+impl FromWorld for OutlineJumpFloodPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let seed_layout = render_device.create_bind_group_layout(
+            "outline_seed_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                // The outline width mask
+                texture_2d(TextureSampleType::Float { filterable: true }),
+            ),
+        );
+        let jump_flood_layout = render_device.create_bind_group_layout(
+            "outline_jump_flood_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // The outline width mask
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // The seeds of the previous pass
+                    texture_2d(TextureSampleType::Sint),
+                    uniform_buffer::<OutlineJumpFloodUniform>(true),
+                ),
+            ),
+        );
+
+        let pipeline =
+            |label: &'static str, layout: &BindGroupLayout, entry_point: &'static str| {
+                RenderPipelineDescriptor {
+                    label: Some(label.into()),
+                    layout: vec![layout.clone()],
+                    vertex: fullscreen_vertex_shader::fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader: OUTLINE_JUMP_FLOOD_SHADER_HANDLE,
+                        shader_defs: vec![],
+                        entry_point: entry_point.into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: JUMP_FLOOD_TEXTURE_FORMAT,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    push_constant_ranges: vec![],
+                    primitive: Default::default(),
+                    depth_stencil: None,
+                    multisample: Default::default(),
+                    zero_initialize_workgroup_memory: false,
+                }
+            };
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let seed_pipeline = pipeline_cache.queue_render_pipeline(pipeline(
+            "outline_seed_pipeline",
+            &seed_layout,
+            "seed",
+        ));
+        let jump_flood_pipeline = pipeline_cache.queue_render_pipeline(pipeline(
+            "outline_jump_flood_pipeline",
+            &jump_flood_layout,
+            "jump_flood",
+        ));
+
+        Self {
+            seed_layout,
+            jump_flood_layout,
+            seed_pipeline,
+            jump_flood_pipeline,
+        }
+    }
+}
+
+/// The pipeline blending the outlines over the image of a view.
+#[derive(Resource)]
+pub struct OutlineCompositePipeline {
+    layout: BindGroupLayout,
+}
+
+impl FromWorld for OutlineCompositePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "outline_composite_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // The seeds found by the jump flood
+                    texture_2d(TextureSampleType::Sint),
+                    // The outline color mask
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // The outline width mask
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                ),
+            ),
+        );
+
+        Self { layout }
+    }
+}
+
+impl SpecializedRenderPipeline for OutlineCompositePipeline {
+    // Whether the view is HDR.
+    type Key = bool;
+
+    fn specialize(&self, hdr: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("outline_composite_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_vertex_shader::fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: OUTLINE_COMPOSITE_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    // The outlines are premultiplied, and don't change the
+                    // alpha of the image.
+                    blend: Some(BlendState {
+                        color: BlendComponent {
+                            src_factor: BlendFactor::One,
+                            dst_factor: BlendFactor::OneMinusSrcAlpha,
+                            operation: BlendOperation::Add,
+                        },
+                        alpha: BlendComponent {
+                            src_factor: BlendFactor::Zero,
+                            dst_factor: BlendFactor::One,
+                            operation: BlendOperation::Add,
+                        },
+                    }),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            push_constant_ranges: vec![],
+            primitive: Default::default(),
+            depth_stencil: None,
+            multisample: Default::default(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+/// Extracts the outlines of the visible entities into [`RenderOutlines`].
+pub fn extract_outlines(
+    mut render_outlines: ResMut<RenderOutlines>,
+    mut style_indices: Local<HashMap<[u32; 5], u32>>,
+    outlines: Extract<Query<(Entity, &Outline, &ViewVisibility)>>,
+) {
+    let render_outlines = &mut *render_outlines;
+    render_outlines.entities.clear();
+    render_outlines.styles.clear();
+    render_outlines.max_width = 0.0;
+    style_indices.clear();
+
+    for (entity, outline, view_visibility) in &outlines {
+        let width = outline.width.clamp(0.0, MAX_OUTLINE_WIDTH);
+        if !view_visibility.get() || width == 0.0 {
+            continue;
+        }
+
+        let color = outline.color.to_linear().to_vec4();
+        let [r, g, b, a] = color.to_array().map(f32::to_bits);
+        let style = *style_indices
+            .entry([r, g, b, a, width.to_bits()])
+            .or_insert_with(|| {
+                render_outlines
+                    .styles
+                    .push(OutlineStyleUniform { color, width });
+                render_outlines.styles.len() as u32 - 1
+            });
+
+        render_outlines.max_width = render_outlines.max_width.max(width);
+        render_outlines.entities.insert(
+            entity.into(),
+            RenderOutline {
+                style,
+                mode: outline.mode,
+            },
+        );
+    }
+}
+
+/// Creates the outline mask phase of each active 3D camera.
+pub fn extract_outline_phases(
+    mut outline_phases: ResMut<ViewSortedRenderPhases<OutlineMask3d>>,
+    cameras_3d: Extract<Query<(Entity, &Camera), With<Camera3d>>>,
+    mut live_entities: Local<HashSet<RetainedViewEntity>>,
+) {
+    live_entities.clear();
+
+    for (main_entity, camera) in &cameras_3d {
+        if !camera.is_active {
+            continue;
+        }
+
+        // This is the main 3D camera, so use the first subview index (0).
+        let retained_view_entity = RetainedViewEntity::new(main_entity.into(), None, 0);
+        outline_phases.insert_or_clear(retained_view_entity);
+        live_entities.insert(retained_view_entity);
+    }
+
+    outline_phases.retain(|view_entity, _| live_entities.contains(view_entity));
+}
+
+/// Adds the visible outlined meshes to the outline mask phase of each view.
+pub fn queue_outlines(
+    draw_functions: Res<DrawFunctions<OutlineMask3d>>,
+    outline_mask_pipeline: Res<OutlineMaskPipeline>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<OutlineMaskPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_outlines: Res<RenderOutlines>,
+    render_meshes: Res<RenderAssets<RenderMesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    view_key_cache: Res<ViewKeyCache>,
+    mut outline_phases: ResMut<ViewSortedRenderPhases<OutlineMask3d>>,
+    views: Query<(&MainEntity, &ExtractedView, &RenderVisibleEntities)>,
+) {
+    if render_outlines.entities.is_empty() {
+        return;
+    }
+
+    let draw_function = draw_functions.read().id::<DrawOutlineMask>();
+
+    for (view_entity, view, visible_entities) in &views {
+        let Some(outline_phase) = outline_phases.get_mut(&view.retained_view_entity) else {
+            continue;
+        };
+        let Some(view_key) = view_key_cache.get(view_entity) else {
+            continue;
+        };
+
+        let rangefinder = view.rangefinder3d();
+        for (render_entity, visible_entity) in visible_entities.iter::<Mesh3d>() {
+            let Some(outline) = render_outlines.entities.get(visible_entity) else {
+                continue;
+            };
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(*visible_entity)
+            else {
+                continue;
+            };
+            let Some(mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+
+            let key = OutlineMaskPipelineKey {
+                mesh_key: *view_key | MeshPipelineKey::from_bits_retain(mesh.key_bits.bits()),
+                mode: outline.mode,
+            };
+            let pipeline = match pipelines.specialize(
+                &pipeline_cache,
+                &outline_mask_pipeline,
+                key,
+                &mesh.layout,
+            ) {
+                Ok(id) => id,
+                Err(err) => {
+                    error!("{}", err);
+                    continue;
+                }
+            };
+
+            outline_phase.add(OutlineMask3d {
+                distance: rangefinder.distance_translation(&mesh_instance.translation),
+                pipeline,
+                entity: (*render_entity, *visible_entity),
+                draw_function,
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::None,
+                indexed: mesh.indexed(),
+            });
+        }
+    }
+}
+
+/// Specializes the outline composite pipeline of each view.
+pub fn prepare_outline_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<OutlineCompositePipeline>>,
+    outline_composite_pipeline: Res<OutlineCompositePipeline>,
+    views: Query<(Entity, &ExtractedView), With<ViewDepthTexture>>,
+) {
+    for (entity, view) in &views {
+        let pipeline_id =
+            pipelines.specialize(&pipeline_cache, &outline_composite_pipeline, view.hdr);
+        commands
+            .entity(entity)
+            .insert(OutlineCompositePipelineId(pipeline_id));
+    }
+}
+
+/// Creates the outline textures of the views that draw outlines.
+pub fn prepare_outline_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    outline_phases: Res<ViewSortedRenderPhases<OutlineMask3d>>,
+    views: Query<(Entity, &ExtractedCamera, &ExtractedView, &Msaa)>,
+) {
+    for (entity, camera, view, msaa) in &views {
+        let Some(outline_phase) = outline_phases.get(&view.retained_view_entity) else {
+            continue;
+        };
+        let Some(physical_target_size) = camera.physical_target_size else {
+            continue;
+        };
+        if outline_phase.items.is_empty() {
+            continue;
+        }
+
+        let size = Extent3d {
+            width: physical_target_size.x,
+            height: physical_target_size.y,
+            depth_or_array_layers: 1,
+        };
+        let color = outline_mask_attachment(
+            &mut texture_cache,
+            &render_device,
+            "outline_color_mask",
+            TextureFormat::Rgba16Float,
+            size,
+            msaa,
+        );
+        let width = outline_mask_attachment(
+            &mut texture_cache,
+            &render_device,
+            "outline_width_mask",
+            TextureFormat::Rg16Float,
+            size,
+            msaa,
+        );
+        let jump_flood = ["outline_jump_flood_a", "outline_jump_flood_b"].map(|label| {
+            texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some(label),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: JUMP_FLOOD_TEXTURE_FORMAT,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+            )
+        });
+        commands.entity(entity).insert(ViewOutlineTextures {
+            color,
+            width,
+            jump_flood,
+        });
+    }
+}
+
+fn outline_mask_attachment(
+    texture_cache: &mut TextureCache,
+    render_device: &RenderDevice,
+    label: &'static str,
+    format: TextureFormat,
+    size: Extent3d,
+    msaa: &Msaa,
+) -> ColorAttachment {
+    let mut texture = |sample_count| {
+        texture_cache.get(
+            render_device,
+            TextureDescriptor {
+                label: Some(label),
+                size,
+                mip_level_count: 1,
+                sample_count,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        )
+    };
+    let resolved = texture(1);
+    let multisampled = (msaa.samples() > 1).then(|| texture(msaa.samples()));
+    ColorAttachment::new(resolved, multisampled, Some(LinearRgba::NONE))
+}
+
+/// Writes the outline styles and the settings of the jump flood passes to the GPU.
+pub fn prepare_outline_buffers(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    render_outlines: Res<RenderOutlines>,
+    outline_mask_pipeline: Res<OutlineMaskPipeline>,
+    mut outline_buffers: ResMut<OutlineBuffers>,
+) {
+    let OutlineBuffers {
+        styles,
+        style_offsets,
+        style_bind_group,
+        jump_flood,
+        jump_flood_offsets,
+    } = &mut *outline_buffers;
+
+    style_offsets.clear();
+    *style_bind_group = None;
+    jump_flood_offsets.clear();
+    if render_outlines.styles.is_empty() {
+        return;
+    }
+
+    {
+        let Some(mut writer) =
+            styles.get_writer(render_outlines.styles.len(), &render_device, &render_queue)
+        else {
+            return;
+        };
+        for style in &render_outlines.styles {
+            style_offsets.push(writer.write(style));
+        }
+    }
+
+    {
+        let steps = jump_flood_steps(render_outlines.max_width);
+        let Some(mut writer) = jump_flood.get_writer(steps.len(), &render_device, &render_queue)
+        else {
+            return;
+        };
+        for step_size in steps {
+            jump_flood_offsets.push(writer.write(&OutlineJumpFloodUniform { step_size }));
+        }
+    }
+
+    *style_bind_group = styles.binding().map(|binding| {
+        render_device.create_bind_group(
+            "outline_style_bind_group",
+            &outline_mask_pipeline.style_layout,
+            &BindGroupEntries::single(binding),
+        )
+    });
+}
+
+/// Returns the step sizes of the jump flood passes, which halve from the smallest
+/// power of two reaching past the outer edge of the widest outline down to one.
+///
+/// A last pass of step one fixes most of the pixels the jump flood gets wrong.
+fn jump_flood_steps(max_width: f32) -> Vec<i32> {
+    let reach = (max_width + 1.0).ceil() as i32;
+    iter::successors(Some((reach as u32).next_power_of_two() as i32), |step| {
+        (*step > 1).then_some(step / 2)
+    })
+    .chain([1])
+    .collect()
+}
+
+/// Renders the outline mask of a view, runs the jump flood over it, then blends
+/// the outlines over the image of the view.
+#[derive(Default)]
+pub struct OutlineNode;
+
+impl ViewNode for OutlineNode {
+    type ViewQuery = (
+        Read<ExtractedCamera>,
+        Read<ExtractedView>,
+        Read<ViewTarget>,
+        Read<ViewDepthTexture>,
+        Read<ViewOutlineTextures>,
+        Read<OutlineCompositePipelineId>,
+    );
+
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (camera, view, target, depth, textures, pipeline_id): QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.view_entity();
+
+        let Some(outline_phase) = world
+            .resource::<ViewSortedRenderPhases<OutlineMask3d>>()
+            .get(&view.retained_view_entity)
+        else {
+            return Ok(());
+        };
+        if outline_phase.items.is_empty() {
+            return Ok(());
+        }
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(composite_pipeline) = pipeline_cache.get_render_pipeline(**pipeline_id) else {
+            return Ok(());
+        };
+        let jump_flood_pipelines = world.resource::<OutlineJumpFloodPipeline>();
+        let (Some(seed_pipeline), Some(jump_flood_pipeline)) = (
+            pipeline_cache.get_render_pipeline(jump_flood_pipelines.seed_pipeline),
+            pipeline_cache.get_render_pipeline(jump_flood_pipelines.jump_flood_pipeline),
+        ) else {
+            return Ok(());
+        };
+        let outline_buffers = world.resource::<OutlineBuffers>();
+        let Some(jump_flood_uniforms) = outline_buffers.jump_flood.binding() else {
+            return Ok(());
+        };
+
+        let diagnostics = render_context.diagnostic_recorder();
+
+        {
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("outline_mask_pass"),
+                color_attachments: &[
+                    Some(textures.color.get_attachment()),
+                    Some(textures.width.get_attachment()),
+                ],
+                // The depth of the main pass is only read, but storing it
+                // keeps it intact for later passes.
+                depth_stencil_attachment: Some(depth.get_attachment(StoreOp::Store)),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            let pass_span = diagnostics.pass_span(&mut render_pass, "outline_mask_pass");
+
+            if let Some(viewport) = camera.viewport.as_ref() {
+                render_pass.set_camera_viewport(viewport);
+            }
+
+            if let Err(err) = outline_phase.render(&mut render_pass, world, view_entity) {
+                error!("Error encountered while rendering the outline mask phase {err:?}");
+            }
+
+            pass_span.end(&mut render_pass);
+        }
+
+        let render_device = render_context.render_device().clone();
+        let width_mask = &textures.width.texture.default_view;
+
+        let time_span =
+            diagnostics.time_span(render_context.command_encoder(), "outline_jump_flood");
+
+        let seed_bind_group = render_device.create_bind_group(
+            "outline_seed_bind_group",
+            &jump_flood_pipelines.seed_layout,
+            &BindGroupEntries::single(width_mask),
+        );
+        fullscreen_pass(
+            render_context,
+            "outline_seed_pass",
+            &textures.jump_flood[0].default_view,
+            seed_pipeline,
+            &seed_bind_group,
+            &[],
+        );
+
+        // Each pass reads the seeds of the previous one.
+        let jump_flood_bind_groups = [0, 1].map(|source| {
+            render_device.create_bind_group(
+                "outline_jump_flood_bind_group",
+                &jump_flood_pipelines.jump_flood_layout,
+                &BindGroupEntries::sequential((
+                    width_mask,
+                    &textures.jump_flood[source].default_view,
+                    jump_flood_uniforms.clone(),
+                )),
+            )
+        });
+        let mut source = 0;
+        for &offset in &outline_buffers.jump_flood_offsets {
+            fullscreen_pass(
+                render_context,
+                "outline_jump_flood_pass",
+                &textures.jump_flood[1 - source].default_view,
+                jump_flood_pipeline,
+                &jump_flood_bind_groups[source],
+                &[offset],
+            );
+            source = 1 - source;
+        }
+
+        time_span.end(render_context.command_encoder());
+
+        let composite_bind_group = render_device.create_bind_group(
+            "outline_composite_bind_group",
+            &world.resource::<OutlineCompositePipeline>().layout,
+            &BindGroupEntries::sequential((
+                &textures.jump_flood[source].default_view,
+                &textures.color.texture.default_view,
+                width_mask,
+            )),
+        );
+
+        // The outlines are blended over the image, so it doesn't need to be copied.
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("outline_composite_pass"),
+            color_attachments: &[Some(target.get_unsampled_color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        let pass_span = diagnostics.pass_span(&mut render_pass, "outline_composite_pass");
+
+        render_pass.set_render_pipeline(composite_pipeline);
+        render_pass.set_bind_group(0, &composite_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        pass_span.end(&mut render_pass);
+
+        Ok(())
+    }
+}
+
+/// Runs a fullscreen pass of the jump flood into `destination`.
+fn fullscreen_pass(
+    render_context: &mut RenderContext,
+    label: &'static str,
+    destination: &TextureView,
+    pipeline: &RenderPipeline,
+    bind_group: &BindGroup,
+    offsets: &[u32],
+) {
+    let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: destination,
+            resolve_target: None,
+            ops: Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_bind_group(0, bind_group, offsets);
+    render_pass.draw(0..3, 0..1);
+}
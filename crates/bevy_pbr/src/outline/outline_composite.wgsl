@@ -0,0 +1,33 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var seeds: texture_2d<i32>;
+@group(0) @binding(1) var color_mask: texture_2d<f32>;
+@group(0) @binding(2) var width_mask: texture_2d<f32>;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+
+    // The pixel of the mask whose outline reaches furthest past this pixel,
+    // found by the jump flood.
+    let seed = textureLoad(seeds, pixel, 0).xy;
+    if seed.x < 0 {
+        return vec4(0.0);
+    }
+
+    // The mask is resolved from MSAA, so its values are scaled by its
+    // coverage.
+    let width = textureLoad(width_mask, seed, 0).rg;
+    let outline_color = textureLoad(color_mask, seed, 0) / width.g;
+
+    // Antialias the outer edge of the outline.
+    let distance = length(vec2<f32>(pixel - seed));
+    let coverage = saturate(width.r / width.g - distance + 0.5);
+
+    // Don't draw the outline over the outlined meshes themselves.
+    let inside = textureLoad(width_mask, pixel, 0).g;
+
+    // The outline color is premultiplied, so scaling all of its channels keeps
+    // it premultiplied for the blend over the image.
+    return outline_color * coverage * (1.0 - inside);
+}
@@ -0,0 +1,61 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+// The seed of the pixels that no outline reaches.
+const NO_SEED: vec2<i32> = vec2(-1);
+
+struct OutlineJumpFlood {
+    step_size: i32,
+};
+
+@group(0) @binding(0) var width_mask: texture_2d<f32>;
+@group(0) @binding(1) var seeds: texture_2d<i32>;
+@group(0) @binding(2) var<uniform> settings: OutlineJumpFlood;
+
+// Returns the distance from `pixel` to the outer edge of the outline of `seed`,
+// which is negative inside the outline.
+fn outline_distance(pixel: vec2<i32>, seed: vec2<i32>) -> f32 {
+    // The mask is resolved from MSAA, so its values are scaled by its coverage.
+    let width = textureLoad(width_mask, seed, 0).rg;
+    return length(vec2<f32>(pixel - seed)) - width.r / width.g;
+}
+
+// Makes each pixel of the mask the seed of its own outline.
+@fragment
+fn seed(in: FullscreenVertexOutput) -> @location(0) vec2<i32> {
+    let pixel = vec2<i32>(in.position.xy);
+    if textureLoad(width_mask, pixel, 0).g > 0.0 {
+        return pixel;
+    }
+    return NO_SEED;
+}
+
+// Picks the seed whose outline reaches furthest past this pixel among the
+// seeds of the pixels `step_size` away.
+@fragment
+fn jump_flood(in: FullscreenVertexOutput) -> @location(0) vec2<i32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let size = vec2<i32>(textureDimensions(seeds));
+
+    var nearest = NO_SEED;
+    var nearest_distance = 0.0;
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let coords = pixel + vec2(x, y) * settings.step_size;
+            if any(coords < vec2(0)) || any(coords >= size) {
+                continue;
+            }
+
+            let seed = textureLoad(seeds, coords, 0).xy;
+            if seed.x < 0 {
+                continue;
+            }
+
+            let distance = outline_distance(pixel, seed);
+            if nearest.x < 0 || distance < nearest_distance {
+                nearest = seed;
+                nearest_distance = distance;
+            }
+        }
+    }
+    return nearest;
+}
@@ -0,0 +1,23 @@
+#import bevy_pbr::forward_io::VertexOutput
+
+struct OutlineStyle {
+    color: vec4<f32>,
+    width: f32,
+};
+
+@group(2) @binding(0) var<uniform> style: OutlineStyle;
+
+struct FragmentOutput {
+    // The premultiplied color of the outline.
+    @location(0) color: vec4<f32>,
+    // The width of the outline, and the coverage of the mask.
+    @location(1) width: vec2<f32>,
+};
+
+@fragment
+fn fragment(in: VertexOutput) -> FragmentOutput {
+    var out: FragmentOutput;
+    out.color = vec4(style.color.rgb * style.color.a, style.color.a);
+    out.width = vec2(style.width, 1.0);
+    return out;
+}
@@ -0,0 +1,546 @@
+//! GPU particles, simulated in a compute shader and drawn as billboards or meshes.
+//!
+//! The CPU only decides how many particles each [`ParticleEmitter`] spawns every frame. The
+//! particles themselves live in a ring buffer on the GPU, where a compute shader spawns, moves
+//! and optionally collides them with the depth buffer, before they're drawn in the transparent
+//! pass of every 3D camera.
+
+mod render;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_color::LinearRgba;
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_math::{curve::Interval, ops, Curve, Vec3, VectorSpace};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    mesh::Mesh, render_resource::Shader, sync_world::SyncToRenderWorld, view::Visibility,
+};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+const PARTICLE_TYPES_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("2e6c9a41-7d3f-4b85-a1e0-58c4f9b2d763");
+const PARTICLE_SIMULATE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("b81f0d52-3a6e-4c97-8d24-e7a5c1f0b349");
+const PARTICLE_RENDER_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("4c93e7a0-f215-4d68-b0c3-91d6a2e8f57b");
+
+/// The number of samples of the curves over the lifetime of particles sent to the GPU.
+///
+/// The [`ParticleCurve`]s of a [`ParticleEmitter`] are sampled at this many evenly spaced times,
+/// and linearly interpolated on the GPU.
+pub const PARTICLE_CURVE_SAMPLES: usize = 32;
+
+/// Simulates and draws the particles of [`ParticleEmitter`]s.
+///
+/// This requires compute shaders, so it doesn't do anything on WebGL 2.
+///
+/// This plugin isn't part of the `DefaultPlugins`.
+#[derive(Debug, Default)]
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            PARTICLE_TYPES_SHADER_HANDLE,
+            "particle_types.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            PARTICLE_SIMULATE_SHADER_HANDLE,
+            "particle_simulate.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            PARTICLE_RENDER_SHADER_HANDLE,
+            "particle_render.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ParticleEmitter>()
+            .register_type::<ParticleEmitterState>()
+            .register_type::<ParticleCurve<f32>>()
+            .register_type::<ParticleCurve<LinearRgba>>()
+            .add_systems(
+                PostUpdate,
+                tick_particle_emitters.in_set(ParticleSystems::TickEmitters),
+            );
+
+        render::build(app);
+    }
+
+    fn finish(&self, app: &mut App) {
+        render::finish(app);
+    }
+}
+
+/// System sets of the [`ParticlesPlugin`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParticleSystems {
+    /// Advances the [`ParticleEmitterState`] of every emitter, and decides how many particles it
+    /// spawns this frame.
+    TickEmitters,
+}
+
+/// Emits particles simulated on the GPU.
+///
+/// Every frame, the emitter spawns particles according to its [`spawn_rate`](Self::spawn_rate),
+/// at a random point of its [`shape`](Self::shape). The particles move in world space, and don't
+/// follow the emitter once spawned. Their speed, size and color change over their lifetime,
+/// following the curves of the emitter.
+///
+/// The emitter holds at most [`capacity`](Self::capacity) particles: when it spawns more, the
+/// oldest particles are replaced, even if they're still alive.
+///
+/// Emitters are drawn by every 3D camera, in the transparent pass, sorted by the distance of the
+/// emitter to the camera. They aren't frustum culled.
+///
+/// This requires the [`ParticlesPlugin`] to be enabled.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform, Visibility, SyncToRenderWorld, ParticleEmitterState)]
+pub struct ParticleEmitter {
+    /// The maximum number of particles alive at the same time.
+    pub capacity: u32,
+    /// The number of particles spawned per second, over the [`duration`](Self::duration) of the
+    /// emitter.
+    pub spawn_rate: ParticleCurve<f32>,
+    /// The duration of the emitter, in seconds, over which [`spawn_rate`](Self::spawn_rate) is
+    /// sampled.
+    pub duration: f32,
+    /// Whether the emitter starts over at the end of its [`duration`](Self::duration), or stops
+    /// spawning particles.
+    pub looping: bool,
+    /// Where the particles spawn, in the local space of the emitter.
+    pub shape: ParticleSpawnShape,
+    /// How long the particles live, in seconds.
+    pub lifetime: f32,
+    /// The random variation of the [`lifetime`](Self::lifetime) of each particle, as a fraction
+    /// of the lifetime.
+    pub lifetime_variation: f32,
+    /// The initial velocity of the particles, in the local space of the emitter.
+    pub velocity: Vec3,
+    /// The angle in radians between the [`velocity`](Self::velocity) and the direction of the
+    /// particles, which spawn in a cone of this half angle.
+    pub velocity_spread: f32,
+    /// The random variation of the initial speed of each particle, as a fraction of the speed.
+    pub speed_variation: f32,
+    /// The acceleration of the particles in world space, like gravity.
+    pub acceleration: Vec3,
+    /// How fast the particles slow down, as a fraction of their velocity per second.
+    pub drag: f32,
+    /// A multiplier of the speed of the particles, over their lifetime.
+    pub speed_over_lifetime: ParticleCurve<f32>,
+    /// The size of the particles in meters, over their lifetime.
+    pub size_over_lifetime: ParticleCurve<f32>,
+    /// The color of the particles, over their lifetime.
+    ///
+    /// The color is multiplied with the texture of billboards.
+    pub color_over_lifetime: ParticleCurve<LinearRgba>,
+    /// Whether the particles collide with the depth buffer.
+    pub collision: Option<ParticleCollision>,
+    /// How the particles are drawn.
+    pub render: ParticleRender,
+    /// How the particles are blended with the scene.
+    pub blend_mode: ParticleBlendMode,
+    /// The order the particles of the emitter are drawn in.
+    pub draw_order: ParticleDrawOrder,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            spawn_rate: ParticleCurve::Constant(100.0),
+            duration: 1.0,
+            looping: true,
+            shape: ParticleSpawnShape::default(),
+            lifetime: 2.0,
+            lifetime_variation: 0.0,
+            velocity: Vec3::Y,
+            velocity_spread: 0.0,
+            speed_variation: 0.0,
+            acceleration: Vec3::ZERO,
+            drag: 0.0,
+            speed_over_lifetime: ParticleCurve::Constant(1.0),
+            size_over_lifetime: ParticleCurve::Constant(0.1),
+            color_over_lifetime: ParticleCurve::Constant(LinearRgba::WHITE),
+            collision: None,
+            render: ParticleRender::default(),
+            blend_mode: ParticleBlendMode::default(),
+            draw_order: ParticleDrawOrder::default(),
+        }
+    }
+}
+
+/// Where the particles of a [`ParticleEmitter`] spawn, in its local space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum ParticleSpawnShape {
+    /// At the origin of the emitter.
+    #[default]
+    Point,
+    /// Anywhere in a sphere centered on the emitter.
+    Sphere {
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// Anywhere in a box centered on the emitter.
+    Box {
+        /// Half the size of the box along each axis.
+        half_size: Vec3,
+    },
+    /// Anywhere on a disc centered on the emitter, in its XZ plane.
+    Disc {
+        /// The radius of the disc.
+        radius: f32,
+    },
+}
+
+/// How particles collide with the scene.
+///
+/// Particles collide with what the camera simulating them sees: the depth prepass of the 3D
+/// camera with the lowest order. The camera needs a
+/// [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass) and [`Msaa::Off`], otherwise the
+/// particles go through the scene. Particles hidden from that camera, or offscreen, don't collide.
+///
+/// [`Msaa::Off`]: bevy_render::view::Msaa::Off
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub struct ParticleCollision {
+    /// The fraction of the speed of particles kept along the normal of the surface when they
+    /// bounce.
+    pub restitution: f32,
+    /// The fraction of the speed of particles along the surface lost when they bounce.
+    pub friction: f32,
+    /// How far behind the depth buffer particles collide, in meters.
+    ///
+    /// Particles further behind a surface are considered to be hidden by it, rather than
+    /// colliding with it.
+    pub thickness: f32,
+    /// Whether the particles die when they collide, instead of bouncing.
+    pub kill: bool,
+}
+
+impl Default for ParticleCollision {
+    fn default() -> Self {
+        Self {
+            restitution: 0.3,
+            friction: 0.1,
+            thickness: 0.5,
+            kill: false,
+        }
+    }
+}
+
+/// How the particles of a [`ParticleEmitter`] are drawn.
+///
+/// Particles are unlit, colored by the [`color_over_lifetime`](ParticleEmitter::color_over_lifetime)
+/// of the emitter.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum ParticleRender {
+    /// Square sprites facing the camera, with an optional texture.
+    Billboard {
+        /// The texture of the sprites, multiplied with the color of the particles.
+        texture: Option<Handle<Image>>,
+    },
+    /// A mesh per particle, scaled by the size of the particle.
+    ///
+    /// The mesh needs positions, and its first UVs are read if present. It doesn't rotate.
+    Mesh(Handle<Mesh>),
+}
+
+impl Default for ParticleRender {
+    fn default() -> Self {
+        Self::Billboard { texture: None }
+    }
+}
+
+/// How particles are blended with the scene.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum ParticleBlendMode {
+    /// The color of particles is blended over the scene, using their alpha.
+    #[default]
+    Alpha,
+    /// The color of particles, multiplied by their alpha, is added to the scene, for fire, sparks
+    /// or magic effects. The order particles are drawn in doesn't matter.
+    Additive,
+}
+
+/// The order the particles of a [`ParticleEmitter`] are drawn in.
+///
+/// Particles aren't sorted by distance to the camera, but by age, which is much cheaper. The
+/// emitters themselves are sorted by distance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum ParticleDrawOrder {
+    /// The oldest particles are drawn first, so the newest particles are on top. This suits
+    /// emitters whose particles move towards the camera, or stay in place.
+    #[default]
+    OldestFirst,
+    /// The newest particles are drawn first, so the oldest particles are on top. This suits
+    /// emitters whose particles move away from the camera, like smoke rising from a chimney.
+    NewestFirst,
+}
+
+/// A parameter of particles changing over time, like their color over their lifetime.
+///
+/// The curve is defined over `[0, 1]`, from the start to the end of the lifetime of particles, or
+/// of the duration of the emitter. Any [`Curve`] of `bevy_math`, like an
+/// [`EasingCurve`](bevy_math::curve::EasingCurve), can be turned into a particle curve with
+/// [`from_curve`](Self::from_curve).
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub enum ParticleCurve<T> {
+    /// The same value all the time.
+    Constant(T),
+    /// Values linearly interpolated between keyframes, sorted by time in `[0, 1]`. The first and
+    /// last values are held before and after the keyframes.
+    Keyframes(Vec<(f32, T)>),
+}
+
+impl<T: VectorSpace> ParticleCurve<T> {
+    /// Creates a curve interpolating between `keyframes`, given as `(time, value)` pairs with
+    /// times in `[0, 1]`.
+    ///
+    /// The keyframes are sorted by time, and the ones at a non-finite time are ignored. Without
+    /// any keyframe, the curve is always zero.
+    pub fn keyframes(keyframes: impl IntoIterator<Item = (f32, T)>) -> Self {
+        let mut keyframes: Vec<_> = keyframes
+            .into_iter()
+            .filter(|(time, _)| time.is_finite())
+            .collect();
+        keyframes.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self::Keyframes(keyframes)
+    }
+
+    /// Creates a curve linearly interpolating from `start` to `end`.
+    pub fn linear(start: T, end: T) -> Self {
+        Self::Keyframes(vec![(0.0, start), (1.0, end)])
+    }
+
+    /// Samples `curve` at `samples` evenly spaced times over `[0, 1]`, clamped to the domain of
+    /// `curve`, and creates a particle curve interpolating between these samples.
+    pub fn from_curve(curve: &impl Curve<T>, samples: usize) -> Self {
+        let last = samples.saturating_sub(1).max(1) as f32;
+        Self::Keyframes(
+            (0..samples.max(2))
+                .map(|index| {
+                    let time = index as f32 / last;
+                    (time, curve.sample_clamped(time))
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the value of the curve at `time`, clamped to `[0, 1]`.
+    pub fn sample_at(&self, time: f32) -> T {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Keyframes(keyframes) => {
+                let next = keyframes.partition_point(|(key_time, _)| *key_time < time);
+                match (
+                    next.checked_sub(1).map(|index| &keyframes[index]),
+                    keyframes.get(next),
+                ) {
+                    (None, None) => T::ZERO,
+                    (Some((_, value)), None) | (None, Some((_, value))) => *value,
+                    (Some((start_time, start)), Some((end_time, end))) => {
+                        let span = end_time - start_time;
+                        if span <= 0.0 {
+                            *end
+                        } else {
+                            start.lerp(*end, (time - start_time) / span)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Samples the curve at [`PARTICLE_CURVE_SAMPLES`] evenly spaced times, for the GPU.
+    fn sample_table(&self) -> [T; PARTICLE_CURVE_SAMPLES] {
+        let last = (PARTICLE_CURVE_SAMPLES - 1) as f32;
+        core::array::from_fn(|index| self.sample_at(index as f32 / last))
+    }
+}
+
+impl<T: VectorSpace> Curve<T> for ParticleCurve<T> {
+    fn domain(&self) -> Interval {
+        Interval::UNIT
+    }
+
+    fn sample_unchecked(&self, t: f32) -> T {
+        self.sample_at(t)
+    }
+}
+
+impl<T> From<T> for ParticleCurve<T> {
+    fn from(value: T) -> Self {
+        Self::Constant(value)
+    }
+}
+
+/// The playback state of a [`ParticleEmitter`], added automatically with it.
+///
+/// This tracks how long the emitter has been playing, and lets systems pause it, restart it or
+/// spawn a burst of particles.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct ParticleEmitterState {
+    /// Whether the emitter and its particles are frozen.
+    pub paused: bool,
+    /// How long the emitter has been playing, in seconds.
+    age: f32,
+    /// The fraction of a particle left to spawn from the previous frames.
+    spawn_accumulator: f32,
+    /// Particles to spawn on the next frame, on top of the spawn rate.
+    burst: u32,
+    /// The first slot of the ring buffer of particles written this frame.
+    spawn_start: u32,
+    /// The number of particles spawned this frame.
+    spawn_count: u32,
+    /// The total number of particles spawned, to seed their random values.
+    spawned: u32,
+    /// The time the particles advance by this frame, in seconds.
+    delta_time: f32,
+}
+
+impl ParticleEmitterState {
+    /// Returns how long the emitter has been playing, in seconds.
+    pub fn age(&self) -> f32 {
+        self.age
+    }
+
+    /// Returns the number of particles spawned this frame.
+    pub fn spawn_count(&self) -> u32 {
+        self.spawn_count
+    }
+
+    /// Spawns `count` particles on the next frame, on top of the spawn rate of the emitter.
+    pub fn burst(&mut self, count: u32) {
+        self.burst = self.burst.saturating_add(count);
+    }
+
+    /// Starts the emitter over from the beginning of its duration. The particles alive keep
+    /// living.
+    pub fn restart(&mut self) {
+        self.age = 0.0;
+        self.spawn_accumulator = 0.0;
+        self.paused = false;
+    }
+
+    /// Returns `true` if the emitter doesn't loop, has reached the end of its duration and all
+    /// its particles are dead.
+    pub fn is_finished(&self, emitter: &ParticleEmitter) -> bool {
+        !emitter.looping
+            && self.age >= emitter.duration + emitter.lifetime * (1.0 + emitter.lifetime_variation)
+    }
+
+    /// Advances the emitter by `delta` seconds, and decides how many particles it spawns.
+    fn tick(&mut self, emitter: &ParticleEmitter, delta: f32) {
+        let capacity = emitter.capacity.max(1);
+        self.spawn_start = (self.spawn_start + self.spawn_count) % capacity;
+        self.spawn_count = 0;
+        if self.paused {
+            self.delta_time = 0.0;
+            return;
+        }
+        self.delta_time = delta;
+
+        if emitter.looping || self.age < emitter.duration {
+            let time = if emitter.duration <= 0.0 {
+                0.0
+            } else if emitter.looping {
+                ops::rem_euclid(self.age / emitter.duration, 1.0)
+            } else {
+                self.age / emitter.duration
+            };
+            self.spawn_accumulator += emitter.spawn_rate.sample_at(time).max(0.0) * delta;
+        }
+        self.age += delta;
+
+        let spawned = ops::floor(self.spawn_accumulator);
+        self.spawn_accumulator -= spawned;
+        self.spawn_count = (spawned as u32).saturating_add(self.burst).min(capacity);
+        self.burst = 0;
+        self.spawned = self.spawned.wrapping_add(self.spawn_count);
+    }
+}
+
+/// Advances every [`ParticleEmitter`], and decides how many particles it spawns this frame.
+pub fn tick_particle_emitters(
+    time: Res<Time>,
+    mut emitters: Query<(&ParticleEmitter, &mut ParticleEmitterState)>,
+) {
+    for (emitter, mut state) in &mut emitters {
+        state.tick(emitter, time.delta_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curve_sampling() {
+        let curve = ParticleCurve::keyframes([(1.0, 4.0), (0.0, 0.0), (0.5, 1.0)]);
+        assert_eq!(curve.sample_at(-1.0), 0.0);
+        assert_eq!(curve.sample_at(0.25), 0.5);
+        assert_eq!(curve.sample_at(0.75), 2.5);
+        assert_eq!(curve.sample_at(2.0), 4.0);
+
+        let table = ParticleCurve::linear(0.0, 31.0).sample_table();
+        assert_eq!(table[0], 0.0);
+        assert!((table[10] - 10.0).abs() < 1e-4);
+        assert_eq!(table[PARTICLE_CURVE_SAMPLES - 1], 31.0);
+
+        let resampled = ParticleCurve::from_curve(&curve, 5);
+        assert_eq!(resampled.sample_at(0.75), 2.5);
+        assert_eq!(ParticleCurve::<f32>::keyframes([]).sample_at(0.5), 0.0);
+    }
+
+    #[test]
+    fn spawn_schedule() {
+        let emitter = ParticleEmitter {
+            capacity: 8,
+            spawn_rate: ParticleCurve::Constant(10.0),
+            duration: 1.0,
+            looping: false,
+            lifetime: 0.5,
+            ..Default::default()
+        };
+        let mut state = ParticleEmitterState::default();
+
+        state.tick(&emitter, 0.25);
+        assert_eq!((state.spawn_start, state.spawn_count), (0, 2));
+        state.tick(&emitter, 0.25);
+        assert_eq!((state.spawn_start, state.spawn_count), (2, 3));
+
+        // Bursts are capped by the capacity, and the ring buffer wraps around.
+        state.burst(20);
+        state.tick(&emitter, 0.0);
+        assert_eq!((state.spawn_start, state.spawn_count), (5, 8));
+        state.tick(&emitter, 0.25);
+        assert_eq!((state.spawn_start, state.spawn_count), (5, 2));
+
+        state.paused = true;
+        state.tick(&emitter, 0.25);
+        assert_eq!((state.spawn_count, state.delta_time), (0, 0.0));
+
+        // The emitter stops spawning at the end of its duration.
+        state.paused = false;
+        state.tick(&emitter, 0.25);
+        state.tick(&emitter, 0.25);
+        assert_eq!(state.spawn_count, 0);
+        assert!(!state.is_finished(&emitter));
+        state.tick(&emitter, 0.5);
+        assert!(state.is_finished(&emitter));
+    }
+}
@@ -0,0 +1,85 @@
+#import bevy_pbr::particle_types::{
+    Particle, ParticleEmitter, curve_sample, normalized_age, sample_packed_curve,
+}
+#import bevy_render::view::View
+
+@group(0) @binding(0) var<uniform> view: View;
+
+@group(1) @binding(0) var<storage, read> particles: array<Particle>;
+@group(1) @binding(1) var<uniform> emitter: ParticleEmitter;
+@group(1) @binding(2) var particle_texture: texture_2d<f32>;
+@group(1) @binding(3) var particle_sampler: sampler;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+#ifdef BILLBOARD
+    @builtin(vertex_index) vertex_index: u32,
+#else
+    @location(0) position: vec3<f32>,
+#ifdef VERTEX_UVS
+    @location(1) uv: vec2<f32>,
+#endif
+#endif
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+fn particle_color(t: f32) -> vec4<f32> {
+    let sample = curve_sample(t);
+    return mix(emitter.colors[sample.first], emitter.colors[sample.second], sample.blend);
+}
+
+// Each instance draws the particle of a slot of the ring buffer.
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Draw the slots from the oldest particle to the newest, or the other way around.
+    var slot = (emitter.draw_start + vertex.instance_index) % emitter.capacity;
+    if emitter.draw_backwards != 0u {
+        slot = (emitter.draw_start + emitter.capacity - 1u - vertex.instance_index) % emitter.capacity;
+    }
+    let particle = particles[slot];
+    if particle.age >= particle.lifetime {
+        // Collapse the triangles of dead particles, so they aren't rasterized.
+        out.position = vec4(0.0, 0.0, 0.0, 1.0);
+        return out;
+    }
+
+    let t = normalized_age(particle);
+    let size = sample_packed_curve(emitter.sizes, t);
+
+#ifdef BILLBOARD
+    var corners = array(
+        vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+        vec2(-1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0),
+    );
+    let corner = corners[vertex.vertex_index % 6u];
+    let right = view.world_from_view[0].xyz;
+    let up = view.world_from_view[1].xyz;
+    let world_position = particle.position + (right * corner.x + up * corner.y) * size * 0.5;
+    out.uv = corner * vec2(0.5, -0.5) + 0.5;
+#else
+    let world_position = particle.position + vertex.position * size;
+#ifdef VERTEX_UVS
+    out.uv = vertex.uv;
+#else
+    out.uv = vec2(0.5);
+#endif
+#endif
+
+    out.position = view.clip_from_world * vec4(world_position, 1.0);
+    out.color = particle_color(t);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = in.color * textureSample(particle_texture, particle_sampler, in.uv);
+    // Both blend modes expect premultiplied alpha.
+    return vec4(color.rgb * color.a, color.a);
+}
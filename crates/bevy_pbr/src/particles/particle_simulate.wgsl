@@ -0,0 +1,164 @@
+#import bevy_pbr::{
+    particle_types::{
+        COLLISION_KILL, COLLISION_NONE, SHAPE_BOX, SHAPE_DISC, SHAPE_SPHERE, Particle,
+        ParticleEmitter, normalized_age, sample_packed_curve,
+    },
+    utils::{rand_f, rand_u},
+}
+#import bevy_render::{maths::{PI_2, orthonormalize}, view::View}
+
+@group(0) @binding(0) var<uniform> view: View;
+#ifdef DEPTH_COLLISION
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+#endif
+
+@group(1) @binding(0) var<storage, read_write> particles: array<Particle>;
+@group(1) @binding(1) var<uniform> emitter: ParticleEmitter;
+
+// Returns a random point of the spawn shape, in the local space of the emitter.
+fn spawn_position(rng: ptr<function, u32>) -> vec3<f32> {
+    let size = emitter.shape_size;
+    if emitter.shape == SHAPE_SPHERE {
+        // Rejection sampling would diverge, so use the cube root of a uniform radius instead.
+        let direction = random_direction(rng);
+        return direction * size.x * pow(rand_f(rng), 1.0 / 3.0);
+    } else if emitter.shape == SHAPE_BOX {
+        return (vec3(rand_f(rng), rand_f(rng), rand_f(rng)) * 2.0 - 1.0) * size;
+    } else if emitter.shape == SHAPE_DISC {
+        let angle = rand_f(rng) * PI_2;
+        return vec3(cos(angle), 0.0, sin(angle)) * size.x * sqrt(rand_f(rng));
+    }
+    return vec3(0.0);
+}
+
+fn random_direction(rng: ptr<function, u32>) -> vec3<f32> {
+    let z = rand_f(rng) * 2.0 - 1.0;
+    let angle = rand_f(rng) * PI_2;
+    let radius = sqrt(max(1.0 - z * z, 0.0));
+    return vec3(radius * cos(angle), radius * sin(angle), z);
+}
+
+// Returns a random velocity in the cone around the velocity of the emitter, in its local space.
+fn spawn_velocity(rng: ptr<function, u32>) -> vec3<f32> {
+    let speed = length(emitter.velocity) * (1.0 + emitter.speed_variation * (rand_f(rng) * 2.0 - 1.0));
+    if speed == 0.0 {
+        return vec3(0.0);
+    }
+    let axis = normalize(emitter.velocity);
+    let cos_theta = mix(1.0, cos(emitter.velocity_spread), rand_f(rng));
+    let sin_theta = sqrt(max(1.0 - cos_theta * cos_theta, 0.0));
+    let phi = rand_f(rng) * PI_2;
+    let up = select(vec3(0.0, 1.0, 0.0), vec3(1.0, 0.0, 0.0), abs(axis.y) > 0.99);
+    let basis = orthonormalize(axis, up);
+    let direction = basis * vec3(sin_theta * cos(phi), sin_theta * sin(phi), cos_theta);
+    return direction * max(speed, 0.0);
+}
+
+fn spawn(offset: u32) -> Particle {
+    var rng = emitter.seed + offset;
+    rand_u(&rng);
+
+    var particle: Particle;
+    particle.position = (emitter.world_from_local * vec4(spawn_position(&rng), 1.0)).xyz;
+    particle.velocity = (emitter.world_from_local * vec4(spawn_velocity(&rng), 0.0)).xyz;
+    particle.age = 0.0;
+    particle.lifetime = emitter.lifetime * max(1.0 + emitter.lifetime_variation * (rand_f(&rng) * 2.0 - 1.0), 0.0);
+    return particle;
+}
+
+#ifdef DEPTH_COLLISION
+// Returns the world position of the surface seen by the view at `pixel`.
+fn surface_position(pixel: vec2<i32>, size: vec2<i32>) -> vec3<f32> {
+    let clamped = clamp(pixel, vec2(0), size - 1);
+    let depth = textureLoad(depth_texture, clamped, 0);
+    let uv = (vec2<f32>(clamped) + 0.5) / vec2<f32>(size);
+    let ndc = vec3(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth);
+    let world = view.world_from_clip * vec4(ndc, 1.0);
+    return world.xyz / world.w;
+}
+
+// Bounces or kills the particle if it went behind the depth buffer, close enough to the surface
+// to have hit it.
+fn collide(particle: ptr<function, Particle>) {
+    let clip = view.clip_from_world * vec4((*particle).position, 1.0);
+    if clip.w <= 0.0 {
+        return;
+    }
+    let ndc = clip.xyz / clip.w;
+    if any(abs(ndc.xy) >= vec2(1.0)) {
+        return;
+    }
+
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let pixel = vec2<i32>((ndc.xy * vec2(0.5, -0.5) + 0.5) * vec2<f32>(size));
+    let surface = surface_position(pixel, size);
+    let to_particle = (*particle).position - view.world_position;
+    let to_surface = surface - view.world_position;
+    let ray = normalize(to_particle);
+    let penetration = dot(to_particle, ray) - dot(to_surface, ray);
+    if penetration <= 0.0 || penetration > emitter.collision_thickness {
+        return;
+    }
+
+    if emitter.collision == COLLISION_KILL {
+        (*particle).age = (*particle).lifetime;
+        return;
+    }
+
+    // Reconstruct the normal of the surface from its neighbors, facing the view.
+    let right = surface_position(pixel + vec2(1, 0), size) - surface;
+    let down = surface_position(pixel + vec2(0, 1), size) - surface;
+    var normal = cross(right, down);
+    if dot(normal, normal) == 0.0 {
+        normal = -ray;
+    }
+    normal = normalize(normal);
+    if dot(normal, ray) > 0.0 {
+        normal = -normal;
+    }
+
+    let velocity = (*particle).velocity;
+    let normal_speed = dot(velocity, normal);
+    (*particle).position = surface + normal * 1e-3;
+    if normal_speed < 0.0 {
+        let tangent_velocity = velocity - normal * normal_speed;
+        (*particle).velocity = tangent_velocity * (1.0 - emitter.friction)
+            - normal * normal_speed * emitter.restitution;
+    }
+}
+#endif
+
+@compute @workgroup_size(64, 1, 1)
+fn simulate(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let index = global_id.x;
+    if index >= emitter.capacity {
+        return;
+    }
+
+    // The slots written this frame follow each other from `spawn_start`, wrapping around.
+    let offset = (index + emitter.capacity - emitter.spawn_start) % emitter.capacity;
+    if offset < emitter.spawn_count {
+        particles[index] = spawn(offset);
+        return;
+    }
+
+    var particle = particles[index];
+    if particle.age >= particle.lifetime {
+        return;
+    }
+
+    let dt = emitter.delta_time;
+    particle.velocity += emitter.acceleration * dt;
+    particle.velocity /= 1.0 + emitter.drag * dt;
+    let speed = sample_packed_curve(emitter.speeds, normalized_age(particle));
+    particle.position += particle.velocity * speed * dt;
+    particle.age += dt;
+
+#ifdef DEPTH_COLLISION
+    if emitter.collision != COLLISION_NONE {
+        collide(&particle);
+    }
+#endif
+
+    particles[index] = particle;
+}
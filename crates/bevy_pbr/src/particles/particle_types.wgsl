@@ -0,0 +1,80 @@
+#define_import_path bevy_pbr::particle_types
+
+// A slot of the ring buffer of particles of an emitter. Its size must match `PARTICLE_SIZE`.
+// Particles are dead when their age reaches their lifetime, and zeroed slots are dead.
+struct Particle {
+    position: vec3<f32>,
+    age: f32,
+    velocity: vec3<f32>,
+    lifetime: f32,
+}
+
+// Must match `PARTICLE_CURVE_SAMPLES`.
+const CURVE_SAMPLES: u32 = 32u;
+
+const SHAPE_SPHERE: u32 = 1u;
+const SHAPE_BOX: u32 = 2u;
+const SHAPE_DISC: u32 = 3u;
+
+const COLLISION_NONE: u32 = 0u;
+const COLLISION_KILL: u32 = 2u;
+
+struct ParticleEmitter {
+    world_from_local: mat4x4<f32>,
+    velocity: vec3<f32>,
+    velocity_spread: f32,
+    acceleration: vec3<f32>,
+    drag: f32,
+    shape_size: vec3<f32>,
+    shape: u32,
+    lifetime: f32,
+    lifetime_variation: f32,
+    speed_variation: f32,
+    delta_time: f32,
+    capacity: u32,
+    spawn_start: u32,
+    spawn_count: u32,
+    seed: u32,
+    collision: u32,
+    restitution: f32,
+    friction: f32,
+    collision_thickness: f32,
+    draw_start: u32,
+    draw_backwards: u32,
+    colors: array<vec4<f32>, CURVE_SAMPLES>,
+    // Four samples per vector.
+    sizes: array<vec4<f32>, 8>,
+    speeds: array<vec4<f32>, 8>,
+}
+
+// The two samples of a curve around a time, and the blend between them.
+struct CurveSample {
+    first: u32,
+    second: u32,
+    blend: f32,
+}
+
+// Returns the samples of the curves of an emitter around `t`, between 0 and 1.
+fn curve_sample(t: f32) -> CurveSample {
+    let position = clamp(t, 0.0, 1.0) * f32(CURVE_SAMPLES - 1u);
+    let first = min(u32(position), CURVE_SAMPLES - 2u);
+    return CurveSample(first, first + 1u, position - f32(first));
+}
+
+// Returns the sample `index` of a curve packed four samples per vector.
+fn packed_sample(table: array<vec4<f32>, 8>, index: u32) -> f32 {
+    // Copy the table to a variable, since arrays passed by value can't be indexed dynamically.
+    var samples = table;
+    return samples[index / 4u][index % 4u];
+}
+
+// Samples a curve packed four samples per vector at `t`.
+fn sample_packed_curve(table: array<vec4<f32>, 8>, t: f32) -> f32 {
+    let sample = curve_sample(t);
+    return mix(packed_sample(table, sample.first), packed_sample(table, sample.second), sample.blend);
+}
+
+// Returns the age of a particle, as a fraction of its lifetime.
+fn normalized_age(particle: Particle) -> f32 {
+    return particle.age / max(particle.lifetime, 1e-6);
+}
@@ -0,0 +1,877 @@
+//! Extraction, simulation and drawing of particles in the render world.
+
+use core::num::NonZero;
+
+use bevy_app::App;
+use bevy_asset::{AssetId, Handle};
+use bevy_color::ColorToComponents;
+use bevy_core_pipeline::{
+    core_3d::{
+        graph::{Core3d, Node3d},
+        Camera3d, Transparent3d, CORE_3D_DEPTH_FORMAT,
+    },
+    prepass::{
+        DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, ViewPrepassTextures,
+    },
+};
+use bevy_ecs::{
+    prelude::*,
+    query::{QueryItem, ROQueryItem},
+    system::{
+        lifetimeless::{Read, SRes},
+        SystemParamItem,
+    },
+};
+use bevy_image::{BevyDefault as _, Image};
+use bevy_math::{Mat4, Vec3, Vec4};
+use bevy_render::{
+    camera::ExtractedCamera,
+    mesh::{
+        allocator::MeshAllocator, Mesh, MeshVertexBufferLayoutRef, RenderMesh, RenderMeshBufferInfo,
+    },
+    render_asset::RenderAssets,
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+        RenderCommandResult, SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+    },
+    render_resource::{binding_types::*, *},
+    renderer::{RenderAdapter, RenderContext, RenderDevice, RenderQueue},
+    sync_world::{MainEntityHashMap, RenderEntity},
+    texture::{FallbackImage, GpuImage},
+    view::{
+        ExtractedView, InheritedVisibility, Msaa, ViewTarget, ViewUniform, ViewUniformOffset,
+        ViewUniforms,
+    },
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::GlobalTransform;
+use tracing::warn;
+
+use super::{
+    ParticleBlendMode, ParticleDrawOrder, ParticleEmitter, ParticleEmitterState, ParticleRender,
+    ParticleSpawnShape, PARTICLE_CURVE_SAMPLES, PARTICLE_RENDER_SHADER_HANDLE,
+    PARTICLE_SIMULATE_SHADER_HANDLE,
+};
+use crate::{graph::NodePbr, MeshPipeline, MeshPipelineKey, SetMeshViewBindGroup};
+
+/// The size of a particle in the buffers of emitters, as declared in `particle_types.wgsl`.
+const PARTICLE_SIZE: u64 = 32;
+
+/// The number of particles simulated by each workgroup of the simulation shader.
+const SIMULATION_WORKGROUP_SIZE: u32 = 64;
+
+pub(super) fn build(app: &mut App) {
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+
+    render_app
+        .init_resource::<ExtractedParticleEmitters>()
+        .init_resource::<ParticleBuffers>()
+        .init_resource::<ParticleSimulationView>()
+        .add_systems(ExtractSchedule, extract_particle_emitters);
+}
+
+pub(super) fn finish(app: &mut App) {
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+
+    if !render_app
+        .world()
+        .resource::<RenderAdapter>()
+        .get_downlevel_capabilities()
+        .flags
+        .contains(DownlevelFlags::COMPUTE_SHADERS)
+    {
+        warn!("ParticlesPlugin not loaded. GPU lacks support for compute shaders.");
+        return;
+    }
+
+    render_app
+        .init_resource::<ParticleSimulationPipeline>()
+        .init_resource::<SpecializedComputePipelines<ParticleSimulationPipeline>>()
+        .init_resource::<ParticlePipeline>()
+        .init_resource::<SpecializedRenderPipelines<ParticlePipeline>>()
+        .init_resource::<SpecializedMeshPipelines<ParticlePipeline>>()
+        .add_render_command::<Transparent3d, DrawParticles>()
+        .add_systems(
+            Render,
+            (
+                queue_particles.in_set(RenderSet::Queue),
+                prepare_particle_buffers.in_set(RenderSet::PrepareResources),
+                prepare_particle_bind_groups.in_set(RenderSet::PrepareBindGroups),
+            ),
+        )
+        .add_render_graph_node::<ViewNodeRunner<ParticleSimulationNode>>(
+            Core3d,
+            NodePbr::ParticleSimulation,
+        )
+        .add_render_graph_edges(
+            Core3d,
+            (
+                // END_PRE_PASSES -> PARTICLE_SIMULATION -> MAIN_PASS
+                Node3d::EndPrepasses,
+                NodePbr::ParticleSimulation,
+                Node3d::StartMainPass,
+            ),
+        );
+}
+
+/// The parameters of an emitter for this frame, as read by the shaders.
+#[derive(Clone, ShaderType)]
+struct ParticleEmitterUniform {
+    world_from_local: Mat4,
+    velocity: Vec3,
+    velocity_spread: f32,
+    acceleration: Vec3,
+    drag: f32,
+    shape_size: Vec3,
+    shape: u32,
+    lifetime: f32,
+    lifetime_variation: f32,
+    speed_variation: f32,
+    delta_time: f32,
+    capacity: u32,
+    spawn_start: u32,
+    spawn_count: u32,
+    seed: u32,
+    /// 0 without collisions, 1 to bounce and 2 to kill particles.
+    collision: u32,
+    restitution: f32,
+    friction: f32,
+    collision_thickness: f32,
+    /// The first slot drawn, and whether the slots are drawn backwards.
+    draw_start: u32,
+    draw_backwards: u32,
+    /// The color over the lifetime of particles.
+    colors: [Vec4; PARTICLE_CURVE_SAMPLES],
+    /// The size over the lifetime of particles, four samples per vector.
+    sizes: [Vec4; PARTICLE_CURVE_SAMPLES / 4],
+    /// The speed multiplier over the lifetime of particles, four samples per vector.
+    speeds: [Vec4; PARTICLE_CURVE_SAMPLES / 4],
+}
+
+impl ParticleEmitterUniform {
+    fn new(
+        emitter: &ParticleEmitter,
+        state: &ParticleEmitterState,
+        transform: &GlobalTransform,
+    ) -> Self {
+        let capacity = emitter.capacity.max(1);
+        let (shape, shape_size) = match emitter.shape {
+            ParticleSpawnShape::Point => (0, Vec3::ZERO),
+            ParticleSpawnShape::Sphere { radius } => (1, Vec3::splat(radius)),
+            ParticleSpawnShape::Box { half_size } => (2, half_size),
+            ParticleSpawnShape::Disc { radius } => (3, Vec3::splat(radius)),
+        };
+        let collision = emitter.collision.unwrap_or_default();
+        let pack = |table: [f32; PARTICLE_CURVE_SAMPLES]| {
+            core::array::from_fn(|index| Vec4::from_slice(&table[index * 4..]))
+        };
+
+        Self {
+            world_from_local: transform.compute_matrix(),
+            velocity: emitter.velocity,
+            velocity_spread: emitter.velocity_spread,
+            acceleration: emitter.acceleration,
+            drag: emitter.drag.max(0.0),
+            shape_size,
+            shape,
+            lifetime: emitter.lifetime.max(0.0),
+            lifetime_variation: emitter.lifetime_variation,
+            speed_variation: emitter.speed_variation,
+            delta_time: state.delta_time,
+            capacity,
+            spawn_start: state.spawn_start % capacity,
+            spawn_count: state.spawn_count.min(capacity),
+            seed: state.spawned.wrapping_sub(state.spawn_count),
+            collision: match emitter.collision {
+                None => 0,
+                Some(collision) if collision.kill => 2,
+                Some(_) => 1,
+            },
+            restitution: collision.restitution,
+            friction: collision.friction,
+            collision_thickness: collision.thickness,
+            draw_start: (state.spawn_start + state.spawn_count) % capacity,
+            draw_backwards: (emitter.draw_order == ParticleDrawOrder::NewestFirst) as u32,
+            colors: emitter
+                .color_over_lifetime
+                .sample_table()
+                .map(ColorToComponents::to_vec4),
+            sizes: pack(emitter.size_over_lifetime.sample_table()),
+            speeds: pack(emitter.speed_over_lifetime.sample_table()),
+        }
+    }
+}
+
+/// An emitter extracted to the render world.
+struct ExtractedParticleEmitter {
+    render_entity: Entity,
+    uniform: ParticleEmitterUniform,
+    translation: Vec3,
+    mesh: Option<AssetId<Mesh>>,
+    texture: Option<AssetId<Image>>,
+    blend_mode: ParticleBlendMode,
+    /// Whether the particles move this frame.
+    simulate: bool,
+}
+
+#[derive(Resource, Default)]
+struct ExtractedParticleEmitters {
+    emitters: MainEntityHashMap<ExtractedParticleEmitter>,
+}
+
+fn extract_particle_emitters(
+    mut extracted: ResMut<ExtractedParticleEmitters>,
+    emitters: Extract<
+        Query<(
+            Entity,
+            RenderEntity,
+            &ParticleEmitter,
+            &ParticleEmitterState,
+            &GlobalTransform,
+            &InheritedVisibility,
+        )>,
+    >,
+) {
+    extracted.emitters.clear();
+    for (entity, render_entity, emitter, state, transform, visibility) in &emitters {
+        if !visibility.get() {
+            continue;
+        }
+        let (mesh, texture) = match &emitter.render {
+            ParticleRender::Billboard { texture } => (None, texture.as_ref().map(Handle::id)),
+            ParticleRender::Mesh(mesh) => (Some(mesh.id()), None),
+        };
+        extracted.emitters.insert(
+            entity.into(),
+            ExtractedParticleEmitter {
+                render_entity,
+                uniform: ParticleEmitterUniform::new(emitter, state, transform),
+                translation: transform.translation(),
+                mesh,
+                texture,
+                blend_mode: emitter.blend_mode,
+                simulate: state.delta_time > 0.0 || state.spawn_count > 0,
+            },
+        );
+    }
+}
+
+/// The GPU data of an emitter.
+struct GpuParticleEmitter {
+    /// The ring buffer of particles.
+    particles: Buffer,
+    capacity: u32,
+    uniform_offset: u32,
+    simulate: bool,
+    mesh: Option<AssetId<Mesh>>,
+    texture: Option<AssetId<Image>>,
+    simulate_bind_group: Option<BindGroup>,
+    render_bind_group: Option<BindGroup>,
+}
+
+#[derive(Resource, Default)]
+struct ParticleBuffers {
+    uniforms: DynamicUniformBuffer<ParticleEmitterUniform>,
+    emitters: MainEntityHashMap<GpuParticleEmitter>,
+}
+
+fn prepare_particle_buffers(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    extracted: Res<ExtractedParticleEmitters>,
+    mut buffers: ResMut<ParticleBuffers>,
+) {
+    let buffers = &mut *buffers;
+    buffers
+        .emitters
+        .retain(|entity, _| extracted.emitters.contains_key(entity));
+    buffers.uniforms.clear();
+
+    for (entity, emitter) in &extracted.emitters {
+        let uniform_offset = buffers.uniforms.push(&emitter.uniform);
+        let capacity = emitter.uniform.capacity;
+        let gpu_emitter = buffers
+            .emitters
+            .entry(*entity)
+            .or_insert_with(|| GpuParticleEmitter {
+                particles: create_particle_buffer(&render_device, capacity),
+                capacity,
+                uniform_offset,
+                simulate: false,
+                mesh: None,
+                texture: None,
+                simulate_bind_group: None,
+                render_bind_group: None,
+            });
+        if gpu_emitter.capacity != capacity {
+            // The particles alive are lost, since they're in different slots now.
+            gpu_emitter.particles = create_particle_buffer(&render_device, capacity);
+            gpu_emitter.capacity = capacity;
+        }
+        gpu_emitter.uniform_offset = uniform_offset;
+        gpu_emitter.simulate = emitter.simulate;
+        gpu_emitter.mesh = emitter.mesh;
+        gpu_emitter.texture = emitter.texture;
+    }
+
+    buffers.uniforms.write_buffer(&render_device, &render_queue);
+}
+
+/// Creates the ring buffer of particles of an emitter, zeroed so all the particles are dead.
+fn create_particle_buffer(render_device: &RenderDevice, capacity: u32) -> Buffer {
+    render_device.create_buffer(&BufferDescriptor {
+        label: Some("particle_buffer"),
+        size: capacity as u64 * PARTICLE_SIZE,
+        usage: BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    })
+}
+
+/// The view whose depth the particles collide with, which simulates all the emitters.
+struct SimulationView {
+    view: Entity,
+    bind_group: BindGroup,
+    pipeline: CachedComputePipelineId,
+}
+
+#[derive(Resource, Default)]
+struct ParticleSimulationView(Option<SimulationView>);
+
+fn prepare_particle_bind_groups(
+    render_device: Res<RenderDevice>,
+    pipeline_cache: Res<PipelineCache>,
+    simulation_pipeline: Res<ParticleSimulationPipeline>,
+    mut simulation_pipelines: ResMut<SpecializedComputePipelines<ParticleSimulationPipeline>>,
+    particle_pipeline: Res<ParticlePipeline>,
+    mut buffers: ResMut<ParticleBuffers>,
+    mut simulation_view: ResMut<ParticleSimulationView>,
+    images: Res<RenderAssets<GpuImage>>,
+    fallback_image: Res<FallbackImage>,
+    view_uniforms: Res<ViewUniforms>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedCamera,
+            &Msaa,
+            Option<&ViewPrepassTextures>,
+        ),
+        With<Camera3d>,
+    >,
+) {
+    simulation_view.0 = None;
+    let buffers = &mut *buffers;
+    let (Some(uniforms), Some(view_uniforms)) =
+        (buffers.uniforms.binding(), view_uniforms.uniforms.binding())
+    else {
+        return;
+    };
+
+    for emitter in buffers.emitters.values_mut() {
+        emitter.simulate_bind_group = Some(render_device.create_bind_group(
+            "particle_simulate_bind_group",
+            &simulation_pipeline.emitter_layout,
+            &BindGroupEntries::sequential((
+                emitter.particles.as_entire_binding(),
+                uniforms.clone(),
+            )),
+        ));
+
+        let texture = emitter
+            .texture
+            .and_then(|texture| images.get(texture))
+            .unwrap_or(&fallback_image.d2);
+        emitter.render_bind_group = Some(render_device.create_bind_group(
+            "particle_render_bind_group",
+            &particle_pipeline.emitter_layout,
+            &BindGroupEntries::sequential((
+                emitter.particles.as_entire_binding(),
+                uniforms.clone(),
+                &texture.texture_view,
+                &particle_pipeline.sampler,
+            )),
+        ));
+    }
+
+    // The camera rendered first simulates the particles, before any camera draws them.
+    let Some((view, _, msaa, prepass_textures)) = views
+        .iter()
+        .min_by_key(|(entity, camera, ..)| (camera.order, *entity))
+    else {
+        return;
+    };
+    let depth = prepass_textures
+        .and_then(ViewPrepassTextures::depth_view)
+        .filter(|_| *msaa == Msaa::Off);
+    let bind_group = match depth {
+        Some(depth) => render_device.create_bind_group(
+            "particle_simulate_view_bind_group",
+            &simulation_pipeline.view_depth_layout,
+            &BindGroupEntries::sequential((view_uniforms, depth)),
+        ),
+        None => render_device.create_bind_group(
+            "particle_simulate_view_bind_group",
+            &simulation_pipeline.view_layout,
+            &BindGroupEntries::single(view_uniforms),
+        ),
+    };
+    let pipeline = simulation_pipelines.specialize(
+        &pipeline_cache,
+        &simulation_pipeline,
+        ParticleSimulationPipelineKey {
+            depth_collision: depth.is_some(),
+        },
+    );
+    simulation_view.0 = Some(SimulationView {
+        view,
+        bind_group,
+        pipeline,
+    });
+}
+
+/// The pipeline simulating particles.
+#[derive(Resource)]
+struct ParticleSimulationPipeline {
+    view_layout: BindGroupLayout,
+    view_depth_layout: BindGroupLayout,
+    emitter_layout: BindGroupLayout,
+}
+
+impl FromWorld for ParticleSimulationPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        Self {
+            view_layout: render_device.create_bind_group_layout(
+                "particle_simulate_view_bind_group_layout",
+                &BindGroupLayoutEntries::single(
+                    ShaderStages::COMPUTE,
+                    uniform_buffer::<ViewUniform>(true),
+                ),
+            ),
+            view_depth_layout: render_device.create_bind_group_layout(
+                "particle_simulate_view_depth_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (uniform_buffer::<ViewUniform>(true), texture_depth_2d()),
+                ),
+            ),
+            emitter_layout: render_device.create_bind_group_layout(
+                "particle_simulate_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (
+                        storage_buffer_sized(false, NonZero::<u64>::new(PARTICLE_SIZE)),
+                        uniform_buffer::<ParticleEmitterUniform>(true),
+                    ),
+                ),
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ParticleSimulationPipelineKey {
+    depth_collision: bool,
+}
+
+impl SpecializedComputePipeline for ParticleSimulationPipeline {
+    type Key = ParticleSimulationPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> ComputePipelineDescriptor {
+        let (view_layout, shader_defs) = if key.depth_collision {
+            (&self.view_depth_layout, vec!["DEPTH_COLLISION".into()])
+        } else {
+            (&self.view_layout, vec![])
+        };
+
+        ComputePipelineDescriptor {
+            label: Some("particle_simulate_pipeline".into()),
+            layout: vec![view_layout.clone(), self.emitter_layout.clone()],
+            push_constant_ranges: vec![],
+            shader: PARTICLE_SIMULATE_SHADER_HANDLE,
+            shader_defs,
+            entry_point: "simulate".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+/// Simulates the particles of all the emitters, in the view chosen to simulate them.
+#[derive(Default)]
+struct ParticleSimulationNode;
+
+impl ViewNode for ParticleSimulationNode {
+    type ViewQuery = Read<ViewUniformOffset>;
+
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        view_uniform_offset: QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let Some(simulation_view) = &world.resource::<ParticleSimulationView>().0 else {
+            return Ok(());
+        };
+        if simulation_view.view != graph.view_entity() {
+            return Ok(());
+        }
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_compute_pipeline(simulation_view.pipeline)
+        else {
+            return Ok(());
+        };
+
+        let mut compute_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("particle_simulate_pass"),
+                    timestamp_writes: None,
+                });
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.set_bind_group(
+            0,
+            &simulation_view.bind_group,
+            &[view_uniform_offset.offset],
+        );
+
+        for emitter in world.resource::<ParticleBuffers>().emitters.values() {
+            let Some(bind_group) = emitter.simulate_bind_group.as_ref() else {
+                continue;
+            };
+            if !emitter.simulate {
+                continue;
+            }
+            compute_pass.set_bind_group(1, bind_group, &[emitter.uniform_offset]);
+            compute_pass.dispatch_workgroups(
+                emitter.capacity.div_ceil(SIMULATION_WORKGROUP_SIZE),
+                1,
+                1,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The pipeline drawing particles.
+#[derive(Resource)]
+struct ParticlePipeline {
+    mesh_pipeline: MeshPipeline,
+    emitter_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for ParticlePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        Self {
+            emitter_layout: render_device.create_bind_group_layout(
+                "particle_render_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::VERTEX_FRAGMENT,
+                    (
+                        storage_buffer_read_only_sized(false, NonZero::<u64>::new(PARTICLE_SIZE)),
+                        uniform_buffer::<ParticleEmitterUniform>(true),
+                        texture_2d(TextureSampleType::Float { filterable: true }),
+                        sampler(SamplerBindingType::Filtering),
+                    ),
+                ),
+            ),
+            sampler: render_device.create_sampler(&SamplerDescriptor {
+                label: Some("particle_sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                mipmap_filter: FilterMode::Linear,
+                ..Default::default()
+            }),
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ParticlePipelineKey {
+    view_key: MeshPipelineKey,
+    blend_mode: ParticleBlendMode,
+}
+
+impl ParticlePipeline {
+    fn descriptor(
+        &self,
+        key: ParticlePipelineKey,
+        shader_defs: Vec<ShaderDefVal>,
+        buffers: Vec<VertexBufferLayout>,
+    ) -> RenderPipelineDescriptor {
+        let format = if key.view_key.contains(MeshPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        let blend = match key.blend_mode {
+            ParticleBlendMode::Alpha => BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            ParticleBlendMode::Additive => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            },
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("particle_render_pipeline".into()),
+            layout: vec![
+                self.mesh_pipeline
+                    .get_view_layout(key.view_key.into())
+                    .clone(),
+                self.emitter_layout.clone(),
+            ],
+            push_constant_ranges: vec![],
+            vertex: VertexState {
+                shader: PARTICLE_RENDER_SHADER_HANDLE,
+                shader_defs: shader_defs.clone(),
+                entry_point: "vertex".into(),
+                buffers,
+            },
+            fragment: Some(FragmentState {
+                shader: PARTICLE_RENDER_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(blend),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            // Particles are tested against the depth of the scene, but don't write to it.
+            depth_stencil: Some(DepthStencilState {
+                format: CORE_3D_DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            multisample: MultisampleState {
+                count: key.view_key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for ParticlePipeline {
+    type Key = ParticlePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        self.descriptor(key, vec!["BILLBOARD".into()], vec![])
+    }
+}
+
+impl SpecializedMeshPipeline for ParticlePipeline {
+    type Key = ParticlePipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut shader_defs = vec![];
+        let mut attributes = vec![Mesh::ATTRIBUTE_POSITION.at_shader_location(0)];
+        if layout.0.contains(Mesh::ATTRIBUTE_UV_0) {
+            shader_defs.push("VERTEX_UVS".into());
+            attributes.push(Mesh::ATTRIBUTE_UV_0.at_shader_location(1));
+        }
+        let vertex_buffer_layout = layout.0.get_layout(&attributes)?;
+        Ok(self.descriptor(key, shader_defs, vec![vertex_buffer_layout]))
+    }
+}
+
+fn queue_particles(
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    pipeline: Res<ParticlePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ParticlePipeline>>,
+    mut mesh_pipelines: ResMut<SpecializedMeshPipelines<ParticlePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    extracted: Res<ExtractedParticleEmitters>,
+    meshes: Res<RenderAssets<RenderMesh>>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    views: Query<(
+        &ExtractedView,
+        &Msaa,
+        (
+            Has<NormalPrepass>,
+            Has<DepthPrepass>,
+            Has<MotionVectorPrepass>,
+            Has<DeferredPrepass>,
+        ),
+    )>,
+) {
+    let draw_function = draw_functions.read().id::<DrawParticles>();
+
+    for (view, msaa, (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass)) in
+        &views
+    {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view.retained_view_entity)
+        else {
+            continue;
+        };
+
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
+            | MeshPipelineKey::from_hdr(view.hdr);
+        if normal_prepass {
+            view_key |= MeshPipelineKey::NORMAL_PREPASS;
+        }
+        if depth_prepass {
+            view_key |= MeshPipelineKey::DEPTH_PREPASS;
+        }
+        if motion_vector_prepass {
+            view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+        }
+        if deferred_prepass {
+            view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+        }
+
+        let rangefinder = view.rangefinder3d();
+        for (main_entity, emitter) in &extracted.emitters {
+            let key = ParticlePipelineKey {
+                view_key,
+                blend_mode: emitter.blend_mode,
+            };
+            let (pipeline, indexed) = match emitter.mesh {
+                None => (pipelines.specialize(&pipeline_cache, &pipeline, key), false),
+                Some(mesh) => {
+                    let Some(mesh) = meshes.get(mesh) else {
+                        continue;
+                    };
+                    match mesh_pipelines.specialize(&pipeline_cache, &pipeline, key, &mesh.layout) {
+                        Ok(pipeline) => (pipeline, mesh.indexed()),
+                        Err(err) => {
+                            warn!("Failed to specialize the particle pipeline: {err}");
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            transparent_phase.add(Transparent3d {
+                entity: (emitter.render_entity, *main_entity),
+                draw_function,
+                pipeline,
+                distance: rangefinder.distance_translation(&emitter.translation),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::None,
+                indexed,
+            });
+        }
+    }
+}
+
+type DrawParticles = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetParticleEmitterBindGroup<1>,
+    DrawParticleInstances,
+);
+
+struct SetParticleEmitterBindGroup<const I: usize>;
+
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetParticleEmitterBindGroup<I> {
+    type Param = SRes<ParticleBuffers>;
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        _view: ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        buffers: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(emitter) = buffers.into_inner().emitters.get(&item.main_entity()) else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(bind_group) = emitter.render_bind_group.as_ref() else {
+            return RenderCommandResult::Skip;
+        };
+        pass.set_bind_group(I, bind_group, &[emitter.uniform_offset]);
+        RenderCommandResult::Success
+    }
+}
+
+/// Draws a billboard or a mesh per slot of the ring buffer of particles. The vertex shader
+/// collapses the dead particles.
+struct DrawParticleInstances;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawParticleInstances {
+    type Param = (
+        SRes<ParticleBuffers>,
+        SRes<RenderAssets<RenderMesh>>,
+        SRes<MeshAllocator>,
+    );
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        _view: ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        (buffers, meshes, mesh_allocator): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(emitter) = buffers.into_inner().emitters.get(&item.main_entity()) else {
+            return RenderCommandResult::Skip;
+        };
+        let instances = 0..emitter.capacity;
+
+        let Some(mesh_id) = emitter.mesh else {
+            pass.draw(0..6, instances);
+            return RenderCommandResult::Success;
+        };
+        let mesh_allocator = mesh_allocator.into_inner();
+        let (Some(mesh), Some(vertex_buffer_slice)) = (
+            meshes.into_inner().get(mesh_id),
+            mesh_allocator.mesh_vertex_slice(&mesh_id),
+        ) else {
+            return RenderCommandResult::Skip;
+        };
+        pass.set_vertex_buffer(0, vertex_buffer_slice.buffer.slice(..));
+
+        match &mesh.buffer_info {
+            RenderMeshBufferInfo::Indexed {
+                index_format,
+                count,
+            } => {
+                let Some(index_buffer_slice) = mesh_allocator.mesh_index_slice(&mesh_id) else {
+                    return RenderCommandResult::Skip;
+                };
+                pass.set_index_buffer(index_buffer_slice.buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(
+                    index_buffer_slice.range.start..(index_buffer_slice.range.start + *count),
+                    vertex_buffer_slice.range.start as i32,
+                    instances,
+                );
+            }
+            RenderMeshBufferInfo::NonIndexed => {
+                pass.draw(vertex_buffer_slice.range, instances);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
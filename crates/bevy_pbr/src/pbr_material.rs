@@ -1,5 +1,5 @@
-use bevy_asset::Asset;
-use bevy_color::{Alpha, ColorToComponents};
+use bevy_asset::{Asset, AssetId};
+use bevy_color::{Alpha, ColorSpace, ColorToComponents};
 use bevy_math::{Affine2, Affine3, Mat2, Mat3, Vec2, Vec3, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
@@ -23,6 +23,31 @@ pub enum UvChannel {
     Uv1,
 }
 
+/// The model used to shade a [`StandardMaterial`], selected with [`StandardMaterial::shading_model`].
+///
+/// The default is [`ShadingModel::Standard`].
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum ShadingModel {
+    /// Shades the surface of opaque objects, or of volumes when light is transmitted through them.
+    #[default]
+    Standard,
+    /// Shades thin surfaces lit from both sides, letting light through them, such as leaves, grass
+    /// blades, paper or lampshades.
+    ///
+    /// The back faces are lit as if their normals were flipped, like with
+    /// [`StandardMaterial::double_sided`], including when using normal maps. Light reaching the
+    /// other side is transmitted through the surface, in the amount set by
+    /// [`StandardMaterial::diffuse_transmission`] and tinted by
+    /// [`StandardMaterial::diffuse_transmission_color`], regardless of
+    /// [`StandardMaterial::thickness`]. The transmitted light is reduced by the light reflected
+    /// specularly by the surface, so that the surface never emits more light than it receives.
+    ///
+    /// To avoid culling the back faces, set [`StandardMaterial::cull_mode`] to `None`.
+    #[doc(alias = "foliage")]
+    ThinTranslucent,
+}
+
 /// A material with "standard" properties used in PBR lighting.
 /// Standard property values with pictures here:
 /// <https://google.github.io/filament/Material%20Properties.pdf>.
@@ -64,6 +89,7 @@ pub struct StandardMaterial {
     #[texture(1)]
     #[sampler(2)]
     #[dependency]
+    #[reflect(@ColorSpace::Srgb)]
     pub base_color_texture: Option<Handle<Image>>,
 
     // Use a color for user friendliness even though we technically don't use the alpha channel
@@ -115,6 +141,7 @@ pub struct StandardMaterial {
     #[texture(3)]
     #[sampler(4)]
     #[dependency]
+    #[reflect(@ColorSpace::Srgb)]
     pub emissive_texture: Option<Handle<Image>>,
 
     /// Linear perceptual roughness, clamped to `[0.089, 1.0]` in the shader.
@@ -168,6 +195,7 @@ pub struct StandardMaterial {
     #[texture(5)]
     #[sampler(6)]
     #[dependency]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub metallic_roughness_texture: Option<Handle<Image>>,
 
     /// Specular intensity for non-metals on a linear scale of `[0.0, 1.0]`.
@@ -218,6 +246,15 @@ pub struct StandardMaterial {
     #[doc(alias = "translucency")]
     pub diffuse_transmission: f32,
 
+    /// The color of the light transmitted _diffusely_ through the material, which multiplies
+    /// [`StandardMaterial::base_color`].
+    ///
+    /// Thin translucent surfaces often transmit light of a different hue than they reflect, such as
+    /// leaves, which look more yellow when lit from behind.
+    ///
+    /// Defaults to [`Color::WHITE`].
+    pub diffuse_transmission_color: Color,
+
     /// The UV channel to use for the [`StandardMaterial::diffuse_transmission_texture`].
     ///
     /// Defaults to [`UvChannel::Uv0`].
@@ -232,6 +269,7 @@ pub struct StandardMaterial {
     #[cfg_attr(feature = "pbr_transmission_textures", texture(19))]
     #[cfg_attr(feature = "pbr_transmission_textures", sampler(20))]
     #[cfg(feature = "pbr_transmission_textures")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub diffuse_transmission_texture: Option<Handle<Image>>,
 
     /// The amount of light transmitted _specularly_ through the material (i.e. via refraction).
@@ -273,6 +311,7 @@ pub struct StandardMaterial {
     #[cfg_attr(feature = "pbr_transmission_textures", texture(15))]
     #[cfg_attr(feature = "pbr_transmission_textures", sampler(16))]
     #[cfg(feature = "pbr_transmission_textures")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub specular_transmission_texture: Option<Handle<Image>>,
 
     /// Thickness of the volume beneath the material surface.
@@ -302,6 +341,7 @@ pub struct StandardMaterial {
     #[cfg_attr(feature = "pbr_transmission_textures", texture(17))]
     #[cfg_attr(feature = "pbr_transmission_textures", sampler(18))]
     #[cfg(feature = "pbr_transmission_textures")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub thickness_texture: Option<Handle<Image>>,
 
     /// The [index of refraction](https://en.wikipedia.org/wiki/Refractive_index) of the material.
@@ -388,6 +428,7 @@ pub struct StandardMaterial {
     #[texture(9)]
     #[sampler(10)]
     #[dependency]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub normal_map_texture: Option<Handle<Image>>,
 
     /// Normal map textures authored for DirectX have their y-component flipped. Set this to flip
@@ -412,6 +453,7 @@ pub struct StandardMaterial {
     #[texture(7)]
     #[sampler(8)]
     #[dependency]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub occlusion_texture: Option<Handle<Image>>,
 
     /// The UV channel to use for the [`StandardMaterial::specular_texture`].
@@ -439,6 +481,7 @@ pub struct StandardMaterial {
     #[texture(27)]
     #[sampler(28)]
     #[cfg(feature = "pbr_specular_textures")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub specular_texture: Option<Handle<Image>>,
 
     /// The UV channel to use for the
@@ -460,6 +503,7 @@ pub struct StandardMaterial {
     #[cfg(feature = "pbr_specular_textures")]
     #[texture(29)]
     #[sampler(30)]
+    #[reflect(@ColorSpace::Srgb)]
     pub specular_tint_texture: Option<Handle<Image>>,
 
     /// An extra thin translucent layer on top of the main PBR layer. This is
@@ -485,6 +529,7 @@ pub struct StandardMaterial {
     #[cfg_attr(feature = "pbr_multi_layer_material_textures", texture(21))]
     #[cfg_attr(feature = "pbr_multi_layer_material_textures", sampler(22))]
     #[cfg(feature = "pbr_multi_layer_material_textures")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub clearcoat_texture: Option<Handle<Image>>,
 
     /// The roughness of the clearcoat material. This is specified in exactly
@@ -510,6 +555,7 @@ pub struct StandardMaterial {
     #[cfg_attr(feature = "pbr_multi_layer_material_textures", texture(23))]
     #[cfg_attr(feature = "pbr_multi_layer_material_textures", sampler(24))]
     #[cfg(feature = "pbr_multi_layer_material_textures")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub clearcoat_roughness_texture: Option<Handle<Image>>,
 
     /// The UV channel to use for the [`StandardMaterial::clearcoat_normal_texture`].
@@ -532,6 +578,7 @@ pub struct StandardMaterial {
     #[cfg_attr(feature = "pbr_multi_layer_material_textures", texture(25))]
     #[cfg_attr(feature = "pbr_multi_layer_material_textures", sampler(26))]
     #[cfg(feature = "pbr_multi_layer_material_textures")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub clearcoat_normal_texture: Option<Handle<Image>>,
 
     /// Increases the roughness along a specific direction, so that the specular
@@ -602,8 +649,80 @@ pub struct StandardMaterial {
     #[cfg_attr(feature = "pbr_anisotropy_texture", texture(13))]
     #[cfg_attr(feature = "pbr_anisotropy_texture", sampler(14))]
     #[cfg(feature = "pbr_anisotropy_texture")]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub anisotropy_texture: Option<Handle<Image>>,
 
+    /// A texture adding small-scale color variation to the base color, usually
+    /// tiled much more densely than the
+    /// [`StandardMaterial::base_color_texture`] with the
+    /// [`StandardMaterial::detail_uv_transform`], so that large surfaces don't
+    /// look blurry up close.
+    ///
+    /// The base color is multiplied by the color of this texture, scaled so
+    /// that sRGB mid-gray (0.5) leaves it unchanged: darker texels darken the
+    /// base color and brighter texels brighten it.
+    #[texture(31)]
+    #[sampler(32)]
+    #[dependency]
+    #[reflect(@ColorSpace::Srgb)]
+    pub detail_albedo_texture: Option<Handle<Image>>,
+
+    /// A normal map adding small-scale details on top of the
+    /// [`StandardMaterial::normal_map_texture`], usually tiled much more
+    /// densely with the [`StandardMaterial::detail_uv_transform`].
+    ///
+    /// It's in the same format as the
+    /// [`StandardMaterial::normal_map_texture`], and its y axis is flipped
+    /// along with it by [`StandardMaterial::flip_normal_map_y`]. Unless
+    /// [`StandardMaterial::triplanar_mapping`] is enabled, the mesh must have
+    /// tangents for the detail normal map to have any effect.
+    ///
+    /// As this is a non-color map, it must not be loaded as sRGB.
+    #[texture(33)]
+    #[sampler(34)]
+    #[dependency]
+    #[reflect(@ColorSpace::LinearSrgb)]
+    pub detail_normal_map_texture: Option<Handle<Image>>,
+
+    /// The transform applied to the UVs corresponding to `ATTRIBUTE_UV_0` on
+    /// the mesh before sampling the detail textures, independently of
+    /// [`StandardMaterial::uv_transform`].
+    ///
+    /// For example, `Affine2::from_scale(Vec2::splat(8.0))` repeats the
+    /// details 8 times per UV unit. With
+    /// [`StandardMaterial::triplanar_mapping`], this transforms the world
+    /// position used to project the detail textures instead.
+    ///
+    /// Defaults to identity.
+    pub detail_uv_transform: Affine2,
+
+    /// Projects the base color, emissive, metallic-roughness, occlusion,
+    /// normal map and detail textures along the three world axes, and blends
+    /// the projections according to the surface normal, instead of sampling
+    /// them with the mesh UVs.
+    ///
+    /// This is useful for meshes without good UVs, such as cliffs or
+    /// procedurally generated rocks. The world position, transformed by
+    /// [`StandardMaterial::uv_transform`], is used as texture coordinates, so
+    /// the textures tile once per world unit by default. Normal maps don't
+    /// need mesh tangents when triplanar mapping is enabled, but the mesh
+    /// still needs UVs for the textures to be sampled.
+    ///
+    /// Triplanar mapping samples each texture three times. It isn't supported
+    /// for meshlets, which fall back to UVs, and it doesn't apply to the
+    /// other textures of the material, nor to the alpha of masked materials
+    /// in the depth prepass.
+    ///
+    /// Defaults to `false`.
+    pub triplanar_mapping: bool,
+
+    /// How sharply the projections of [`StandardMaterial::triplanar_mapping`]
+    /// transition into each other where the surface isn't aligned with a
+    /// world axis. Higher values result in narrower transitions.
+    ///
+    /// Defaults to 4.0.
+    pub triplanar_blend_sharpness: f32,
+
     /// Support two-sided lighting by automatically flipping the normals for "back" faces
     /// within the PBR lighting shader.
     ///
@@ -612,6 +731,11 @@ pub struct StandardMaterial {
     /// which can be done via `cull_mode`.
     pub double_sided: bool,
 
+    /// The model used to shade the material.
+    ///
+    /// Defaults to [`ShadingModel::Standard`].
+    pub shading_model: ShadingModel,
+
     /// Whether to cull the "front", "back" or neither side of a mesh.
     /// If set to `None`, the two sides of the mesh are visible.
     ///
@@ -706,6 +830,7 @@ pub struct StandardMaterial {
     #[texture(11)]
     #[sampler(12)]
     #[dependency]
+    #[reflect(@ColorSpace::LinearSrgb)]
     pub depth_map: Option<Handle<Image>>,
 
     /// How deep the offset introduced by the depth map should be.
@@ -841,6 +966,7 @@ impl Default for StandardMaterial {
             // <https://google.github.io/filament/Material%20Properties.pdf>
             reflectance: 0.5,
             diffuse_transmission: 0.0,
+            diffuse_transmission_color: Color::WHITE,
             #[cfg(feature = "pbr_transmission_textures")]
             diffuse_transmission_channel: UvChannel::Uv0,
             #[cfg(feature = "pbr_transmission_textures")]
@@ -891,8 +1017,14 @@ impl Default for StandardMaterial {
             anisotropy_channel: UvChannel::Uv0,
             #[cfg(feature = "pbr_anisotropy_texture")]
             anisotropy_texture: None,
+            detail_albedo_texture: None,
+            detail_normal_map_texture: None,
+            detail_uv_transform: Affine2::IDENTITY,
+            triplanar_mapping: false,
+            triplanar_blend_sharpness: 4.0,
             flip_normal_map_y: false,
             double_sided: false,
+            shading_model: ShadingModel::Standard,
             cull_mode: Some(Face::Back),
             unlit: false,
             fog_enabled: true,
@@ -959,6 +1091,7 @@ bitflags::bitflags! {
         const ANISOTROPY_TEXTURE         = 1 << 17;
         const SPECULAR_TEXTURE           = 1 << 18;
         const SPECULAR_TINT_TEXTURE      = 1 << 19;
+        const TWO_COMPONENT_DETAIL_NORMAL_MAP = 1 << 20;
         const ALPHA_MODE_RESERVED_BITS   = Self::ALPHA_MODE_MASK_BITS << Self::ALPHA_MODE_SHIFT_BITS; // ← Bitmask reserving bits for the `AlphaMode`
         const ALPHA_MODE_OPAQUE          = 0 << Self::ALPHA_MODE_SHIFT_BITS;                          // ← Values are just sequential values bitshifted into
         const ALPHA_MODE_MASK            = 1 << Self::ALPHA_MODE_SHIFT_BITS;                          //   the bitmask, and can range from 0 to 7.
@@ -988,8 +1121,13 @@ pub struct StandardMaterialUniform {
     pub emissive: Vec4,
     /// Color white light takes after traveling through the attenuation distance underneath the material surface
     pub attenuation_color: Vec4,
+    /// Color of the light diffusely transmitted through the material, multiplying the base color
+    pub diffuse_transmission_color: Vec4,
     /// The transform applied to the UVs corresponding to `ATTRIBUTE_UV_0` on the mesh before sampling. Default is identity.
     pub uv_transform: Mat3,
+    /// The transform applied to the UVs corresponding to `ATTRIBUTE_UV_0` on the mesh before sampling the detail
+    /// textures. Default is identity.
+    pub detail_uv_transform: Mat3,
     /// Specular intensity for non-metals on a linear scale of [0.0, 1.0]
     /// defaults to 0.5 which is mapped to 4% reflectance in the shader
     pub reflectance: Vec3,
@@ -1031,6 +1169,8 @@ pub struct StandardMaterialUniform {
     pub max_relief_mapping_search_steps: u32,
     /// ID for specifying which deferred lighting pass should be used for rendering this material, if any.
     pub deferred_lighting_pass_id: u32,
+    /// How sharply the projections of triplanar mapping transition into each other.
+    pub triplanar_blend_sharpness: f32,
 }
 
 impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
@@ -1051,7 +1191,7 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
         if self.occlusion_texture.is_some() {
             flags |= StandardMaterialFlags::OCCLUSION_TEXTURE;
         }
-        if self.double_sided {
+        if self.double_sided || self.shading_model == ShadingModel::ThinTranslucent {
             flags |= StandardMaterialFlags::DOUBLE_SIDED;
         }
         if self.unlit {
@@ -1109,22 +1249,18 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
         let has_normal_map = self.normal_map_texture.is_some();
         if has_normal_map {
             let normal_map_id = self.normal_map_texture.as_ref().map(Handle::id).unwrap();
-            if let Some(texture) = images.get(normal_map_id) {
-                match texture.texture_format {
-                    // All 2-component unorm formats
-                    TextureFormat::Rg8Unorm
-                    | TextureFormat::Rg16Unorm
-                    | TextureFormat::Bc5RgUnorm
-                    | TextureFormat::EacRg11Unorm => {
-                        flags |= StandardMaterialFlags::TWO_COMPONENT_NORMAL_MAP;
-                    }
-                    _ => {}
-                }
+            if is_two_component_normal_map(images, normal_map_id) {
+                flags |= StandardMaterialFlags::TWO_COMPONENT_NORMAL_MAP;
             }
-            if self.flip_normal_map_y {
-                flags |= StandardMaterialFlags::FLIP_NORMAL_MAP_Y;
+        }
+        if let Some(detail_normal_map) = &self.detail_normal_map_texture {
+            if is_two_component_normal_map(images, detail_normal_map.id()) {
+                flags |= StandardMaterialFlags::TWO_COMPONENT_DETAIL_NORMAL_MAP;
             }
         }
+        if (has_normal_map || self.detail_normal_map_texture.is_some()) && self.flip_normal_map_y {
+            flags |= StandardMaterialFlags::FLIP_NORMAL_MAP_Y;
+        }
         // NOTE: 0.5 is from the glTF default - do we want this?
         let mut alpha_cutoff = 0.5;
         match self.alpha_mode {
@@ -1170,6 +1306,7 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             attenuation_color: LinearRgba::from(self.attenuation_color)
                 .to_f32_array()
                 .into(),
+            diffuse_transmission_color: LinearRgba::from(self.diffuse_transmission_color).to_vec4(),
             flags: flags.bits(),
             alpha_cutoff,
             parallax_depth_scale: self.parallax_depth_scale,
@@ -1178,10 +1315,26 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             max_relief_mapping_search_steps: self.parallax_mapping_method.max_steps(),
             deferred_lighting_pass_id: self.deferred_lighting_pass_id as u32,
             uv_transform: self.uv_transform.into(),
+            detail_uv_transform: self.detail_uv_transform.into(),
+            triplanar_blend_sharpness: self.triplanar_blend_sharpness,
         }
     }
 }
 
+/// Returns true if the normal map image `id` only stores the x and y components of the normals.
+fn is_two_component_normal_map(images: &RenderAssets<GpuImage>, id: AssetId<Image>) -> bool {
+    images.get(id).is_some_and(|texture| {
+        matches!(
+            texture.texture_format,
+            // All 2-component unorm formats
+            TextureFormat::Rg8Unorm
+                | TextureFormat::Rg16Unorm
+                | TextureFormat::Bc5RgUnorm
+                | TextureFormat::EacRg11Unorm
+        )
+    })
+}
+
 bitflags! {
     /// The pipeline key for `StandardMaterial`, packed into 64 bits.
     #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -1209,6 +1362,10 @@ bitflags! {
         const CLEARCOAT_NORMAL_UV      = 0x100000;
         const SPECULAR_UV              = 0x200000;
         const SPECULAR_TINT_UV         = 0x400000;
+        const DETAIL_ALBEDO            = 0x800000;
+        const DETAIL_NORMAL_MAP        = 0x1000000;
+        const TRIPLANAR                = 0x2000000;
+        const THIN_TRANSLUCENT         = 0x4000000;
         const DEPTH_BIAS               = 0xffffffff_00000000;
     }
 }
@@ -1297,6 +1454,20 @@ impl From<&StandardMaterial> for StandardMaterialKey {
             material.normal_map_channel != UvChannel::Uv0,
         );
 
+        key.set(
+            StandardMaterialKey::DETAIL_ALBEDO,
+            material.detail_albedo_texture.is_some(),
+        );
+        key.set(
+            StandardMaterialKey::DETAIL_NORMAL_MAP,
+            material.detail_normal_map_texture.is_some(),
+        );
+        key.set(StandardMaterialKey::TRIPLANAR, material.triplanar_mapping);
+        key.set(
+            StandardMaterialKey::THIN_TRANSLUCENT,
+            material.shading_model == ShadingModel::ThinTranslucent,
+        );
+
         #[cfg(feature = "pbr_anisotropy_texture")]
         {
             key.set(
@@ -1355,14 +1526,17 @@ impl Material for StandardMaterial {
     #[inline]
     fn opaque_render_method(&self) -> OpaqueRendererMethod {
         match self.opaque_render_method {
-            // For now, diffuse transmission doesn't work under deferred rendering as we don't pack
-            // the required data into the GBuffer. If this material is set to `Auto`, we report it as
-            // `Forward` so that it's rendered correctly, even when the `DefaultOpaqueRendererMethod`
-            // is set to `Deferred`.
+            // For now, diffuse transmission and thin translucent shading don't work under deferred
+            // rendering as we don't pack the required data into the GBuffer. If this material is set
+            // to `Auto`, we report it as `Forward` so that it's rendered correctly, even when the
+            // `DefaultOpaqueRendererMethod` is set to `Deferred`.
             //
             // If the developer explicitly sets the `OpaqueRendererMethod` to `Deferred`, we assume
             // they know what they're doing and don't override it.
-            OpaqueRendererMethod::Auto if self.diffuse_transmission > 0.0 => {
+            OpaqueRendererMethod::Auto
+                if self.diffuse_transmission > 0.0
+                    || self.shading_model == ShadingModel::ThinTranslucent =>
+            {
                 OpaqueRendererMethod::Forward
             }
             other => other,
@@ -1498,6 +1672,26 @@ impl Material for StandardMaterial {
                     StandardMaterialKey::SPECULAR_TINT_UV,
                     "STANDARD_MATERIAL_SPECULAR_TINT_UV_B",
                 ),
+                (
+                    StandardMaterialKey::DETAIL_ALBEDO,
+                    "STANDARD_MATERIAL_DETAIL_ALBEDO",
+                ),
+                (
+                    StandardMaterialKey::DETAIL_NORMAL_MAP,
+                    "STANDARD_MATERIAL_DETAIL_NORMAL_MAP",
+                ),
+                (
+                    StandardMaterialKey::DETAIL_ALBEDO | StandardMaterialKey::DETAIL_NORMAL_MAP,
+                    "STANDARD_MATERIAL_DETAIL_TEXTURES",
+                ),
+                (
+                    StandardMaterialKey::TRIPLANAR,
+                    "STANDARD_MATERIAL_TRIPLANAR",
+                ),
+                (
+                    StandardMaterialKey::THIN_TRANSLUCENT,
+                    "STANDARD_MATERIAL_THIN_TRANSLUCENT",
+                ),
             ] {
                 if key.bind_group_data.intersects(flags) {
                     shader_defs.push(shader_def.into());
@@ -0,0 +1,98 @@
+#define_import_path bevy_pbr::debug_view
+
+#import bevy_pbr::{
+    mesh_view_bindings as view_bindings,
+    pbr_types::PbrInput,
+    shadows::cascade_debug_visualization,
+}
+
+#import bevy_render::{
+    color_operations::hsv_to_rgb,
+    maths::PI_2,
+}
+
+// NOTE: these values must be kept in sync with `DebugView::shader_mode` in bevy_pbr/src/debug_view.rs
+const DEBUG_VIEW_NONE: u32 = 0u;
+const DEBUG_VIEW_NORMALS: u32 = 1u;
+const DEBUG_VIEW_WORLD_POSITION: u32 = 2u;
+const DEBUG_VIEW_UV_CHECKER: u32 = 3u;
+const DEBUG_VIEW_MIP_LEVELS: u32 = 4u;
+const DEBUG_VIEW_OVERDRAW: u32 = 5u;
+const DEBUG_VIEW_SHADOW_CASCADES: u32 = 6u;
+
+const MIP_LEVEL_COLORS: f32 = 8.0;
+
+// Returns the color of the fragment in the active debug view of the camera, or `color` if there
+// is none.
+//
+// `uv` is the first UV channel of the mesh, if `has_uv` is true, and `texture_size` is the size of
+// the texture whose mip levels are shown.
+fn apply_debug_view(
+    pbr_input: PbrInput,
+    uv: vec2<f32>,
+    has_uv: bool,
+    texture_size: vec2<f32>,
+    color: vec4<f32>,
+) -> vec4<f32> {
+    // Derivatives must be computed in uniform control flow, so before branching.
+    let texel = uv * texture_size;
+    let texel_dx = dpdx(texel);
+    let texel_dy = dpdy(texel);
+
+    let mode = view_bindings::lights.debug_view_mode;
+    if mode == DEBUG_VIEW_NONE {
+        return color;
+    }
+
+    if mode == DEBUG_VIEW_NORMALS {
+        return vec4(pbr_input.N * 0.5 + 0.5, 1.0);
+    }
+
+    if mode == DEBUG_VIEW_WORLD_POSITION {
+        return vec4(fract(pbr_input.world_position.xyz), 1.0);
+    }
+
+    if mode == DEBUG_VIEW_UV_CHECKER {
+        if !has_uv {
+            return vec4(1.0, 0.0, 1.0, 1.0);
+        }
+        let cell = vec2<i32>(floor(uv * 8.0));
+        let checker = f32((cell.x + cell.y) & 1);
+        let tint = vec3(fract(uv), 0.5);
+        return vec4(mix(tint * 0.25, tint, checker), 1.0);
+    }
+
+    if mode == DEBUG_VIEW_MIP_LEVELS {
+        if !has_uv {
+            return vec4(1.0, 0.0, 1.0, 1.0);
+        }
+        let level = 0.5 * log2(max(dot(texel_dx, texel_dx), dot(texel_dy, texel_dy)));
+        // From red for the mip 0 to blue for the mip 6 and above.
+        let hue = clamp(level, 0.0, MIP_LEVEL_COLORS - 2.0) / MIP_LEVEL_COLORS * PI_2;
+        var level_color = hsv_to_rgb(vec3(hue, 1.0, 1.0));
+        if level < 0.0 {
+            level_color *= 0.35;
+        }
+        return vec4(level_color, 1.0);
+    }
+
+    if mode == DEBUG_VIEW_OVERDRAW {
+        // Each fragment is added to the previous ones by the blending of the pipeline.
+        return vec4(0.1, 0.03, 0.01, 1.0);
+    }
+
+    if mode == DEBUG_VIEW_SHADOW_CASCADES {
+        if view_bindings::lights.n_directional_lights == 0u {
+            return color;
+        }
+        let view_z = dot(vec4<f32>(
+            view_bindings::view.view_from_world[0].z,
+            view_bindings::view.view_from_world[1].z,
+            view_bindings::view.view_from_world[2].z,
+            view_bindings::view.view_from_world[3].z
+        ), pbr_input.world_position);
+        return vec4(cascade_debug_visualization(color.rgb, 0u, view_z), color.a);
+    }
+
+    return color;
+}
@@ -1,6 +1,7 @@
 use self::assign::ClusterableObjectType;
 use crate::material_bind_groups::MaterialBindGroupAllocator;
 use crate::*;
+use alloc::sync::Arc;
 use bevy_asset::UntypedAssetId;
 use bevy_color::ColorToComponents;
 use bevy_core_pipeline::core_3d::{Camera3d, CORE_3D_DEPTH_FORMAT};
@@ -17,7 +18,7 @@ use bevy_math::{ops, Mat4, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_platform_support::collections::{HashMap, HashSet};
 use bevy_render::{
     batching::gpu_preprocessing::{GpuPreprocessingMode, GpuPreprocessingSupport},
-    camera::SortedCameras,
+    camera::{SortedCameras, ViewRenderScale},
     mesh::allocator::MeshAllocator,
     view::{NoIndirectDrawing, RetainedViewEntity},
 };
@@ -40,7 +41,12 @@ use bevy_render::{
 };
 use bevy_transform::{components::GlobalTransform, prelude::Transform};
 use bevy_utils::default;
-use core::{hash::Hash, marker::PhantomData, ops::Range};
+use core::{
+    hash::Hash,
+    marker::PhantomData,
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
 #[cfg(feature = "trace")]
 use tracing::info_span;
 use tracing::{error, warn};
@@ -56,12 +62,18 @@ pub struct ExtractedPointLight {
     pub shadows_enabled: bool,
     pub shadow_depth_bias: f32,
     pub shadow_normal_bias: f32,
+    pub shadow_slope_bias: f32,
     pub shadow_map_near_z: f32,
+    /// the resolution of the shadow map of this light, see [`PointLight::shadow_map_resolution`]
+    pub shadow_map_resolution: u32,
     pub spot_light_angles: Option<(f32, f32)>,
     pub volumetric: bool,
     pub soft_shadows_enabled: bool,
+    pub soft_shadow_blocker_search: SoftShadowBlockerSearch,
     /// whether this point light contributes diffuse light to lightmapped meshes
     pub affects_lightmapped_mesh_diffuse: bool,
+    /// whether the shadows of the static casters are cached, see [`CachedShadowMap`]
+    pub cached_shadow_map: bool,
 }
 
 #[derive(Component, Debug)]
@@ -76,11 +88,18 @@ pub struct ExtractedDirectionalLight {
     pub affects_lightmapped_mesh_diffuse: bool,
     pub shadow_depth_bias: f32,
     pub shadow_normal_bias: f32,
+    pub shadow_slope_bias: f32,
+    /// the resolution of the shadow map of each cascade of this light, see
+    /// [`DirectionalLight::shadow_map_resolution`]
+    pub shadow_map_resolution: u32,
     pub cascade_shadow_config: CascadeShadowConfig,
     pub cascades: EntityHashMap<Vec<Cascade>>,
     pub frusta: EntityHashMap<Vec<Frustum>>,
     pub render_layers: RenderLayers,
     pub soft_shadow_size: Option<f32>,
+    pub soft_shadow_blocker_search: SoftShadowBlockerSearch,
+    /// whether the shadows of the static casters are cached, see [`CachedShadowMap`]
+    pub cached_shadow_map: bool,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_view_types.wgsl!
@@ -91,11 +110,54 @@ bitflags::bitflags! {
         const SPOT_LIGHT_Y_NEGATIVE             = 1 << 1;
         const VOLUMETRIC                        = 1 << 2;
         const AFFECTS_LIGHTMAPPED_MESH_DIFFUSE  = 1 << 3;
+        const BLOCKER_SEARCH_SAMPLE_COUNT_RESERVED_BITS = Self::BLOCKER_SEARCH_SAMPLE_COUNT_MASK_BITS << Self::BLOCKER_SEARCH_SAMPLE_COUNT_SHIFT_BITS;
+        const BLOCKER_SEARCH_RADIUS_SCALE_RESERVED_BITS = Self::BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS << Self::BLOCKER_SEARCH_RADIUS_SCALE_SHIFT_BITS;
         const NONE                              = 0;
         const UNINITIALIZED                     = 0xFFFF;
     }
 }
 
+impl PointLightFlags {
+    const BLOCKER_SEARCH_SAMPLE_COUNT_MASK_BITS: u32 = 0b1111;
+    const BLOCKER_SEARCH_SAMPLE_COUNT_SHIFT_BITS: u32 = 4;
+
+    // The radius scale is stored as a 16-bit fixed-point number in the
+    // `0.0..SoftShadowBlockerSearch::MAX_RADIUS_SCALE` range.
+    const BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS: u32 = 0xFFFF;
+    const BLOCKER_SEARCH_RADIUS_SCALE_SHIFT_BITS: u32 = 16;
+
+    fn from_soft_shadow_blocker_search(
+        blocker_search: &SoftShadowBlockerSearch,
+        soft_shadow_limits: &SoftShadowLimits,
+    ) -> Self {
+        let sample_count = blocker_search_sample_count(blocker_search, soft_shadow_limits);
+        let radius_scale = (blocker_search
+            .radius_scale
+            .clamp(0.0, SoftShadowBlockerSearch::MAX_RADIUS_SCALE)
+            / SoftShadowBlockerSearch::MAX_RADIUS_SCALE
+            * Self::BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS as f32)
+            .round() as u32;
+        Self::from_bits_retain(
+            ((sample_count & Self::BLOCKER_SEARCH_SAMPLE_COUNT_MASK_BITS)
+                << Self::BLOCKER_SEARCH_SAMPLE_COUNT_SHIFT_BITS)
+                | ((radius_scale & Self::BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS)
+                    << Self::BLOCKER_SEARCH_RADIUS_SCALE_SHIFT_BITS),
+        )
+    }
+}
+
+/// Returns the number of samples that the blocker search of a light takes,
+/// within the limits of [`SoftShadowLimits`].
+fn blocker_search_sample_count(
+    blocker_search: &SoftShadowBlockerSearch,
+    soft_shadow_limits: &SoftShadowLimits,
+) -> u32 {
+    let max_sample_count = soft_shadow_limits
+        .max_blocker_search_samples
+        .clamp(1, SoftShadowBlockerSearch::MAX_SAMPLE_COUNT);
+    blocker_search.sample_count.clamp(1, max_sample_count)
+}
+
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
 pub struct GpuDirectionalCascade {
     clip_from_world: Mat4,
@@ -112,6 +174,11 @@ pub struct GpuDirectionalLight {
     soft_shadow_size: f32,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    shadow_slope_bias: f32,
+    // The fraction of the shadow map that each cascade renders to, see `DirectionalLight::shadow_map_resolution`.
+    shadow_map_scale: f32,
+    blocker_search_sample_count: u32,
+    blocker_search_radius_scale: f32,
     num_cascades: u32,
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
@@ -144,6 +211,9 @@ pub struct GpuLights {
     // offset from spot light's light index to spot light's shadow map index
     spot_light_shadowmap_offset: i32,
     ambient_light_affects_lightmapped_meshes: u32,
+    debug_view_mode: u32,
+    // the maximum number of lights whose soft shadows are evaluated per fragment, see `SoftShadowLimits`
+    max_soft_shadow_lights: u32,
 }
 
 // NOTE: When running bevy on Adreno GPU chipsets in WebGL, any value above 1 will result in a crash
@@ -165,6 +235,16 @@ pub const MAX_CASCADES_PER_LIGHT: usize = 4;
 #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
 pub const MAX_CASCADES_PER_LIGHT: usize = 1;
 
+// NOTE: WebGL 2 can't copy depth textures, which the cached shadow maps are copied with.
+#[cfg(any(
+    not(feature = "webgl"),
+    not(target_arch = "wasm32"),
+    feature = "webgpu"
+))]
+const SHADOW_CACHING_SUPPORTED: bool = true;
+#[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+const SHADOW_CACHING_SUPPORTED: bool = false;
+
 #[derive(Resource, Clone)]
 pub struct ShadowSamplers {
     pub point_light_comparison_sampler: Sampler,
@@ -225,6 +305,7 @@ pub fn extract_lights(
             &ViewVisibility,
             &CubemapFrusta,
             Option<&VolumetricLight>,
+            Has<CachedShadowMap>,
         )>,
     >,
     spot_lights: Extract<
@@ -237,6 +318,7 @@ pub fn extract_lights(
             &ViewVisibility,
             &Frustum,
             Option<&VolumetricLight>,
+            Has<CachedShadowMap>,
         )>,
     >,
     directional_lights: Extract<
@@ -253,6 +335,7 @@ pub fn extract_lights(
                 &ViewVisibility,
                 Option<&RenderLayers>,
                 Option<&VolumetricLight>,
+                Has<CachedShadowMap>,
             ),
             Without<SpotLight>,
         >,
@@ -269,15 +352,6 @@ pub fn extract_lights(
     if directional_light_shadow_map.is_changed() {
         commands.insert_resource(directional_light_shadow_map.clone());
     }
-    // This is the point light shadow map texel size for one face of the cube as a distance of 1.0
-    // world unit from the light.
-    // point_light_texel_size = 2.0 * 1.0 * tan(PI / 4.0) / cube face width in texels
-    // PI / 4.0 is half the cube face fov, tan(PI / 4.0) = 1.0, so this simplifies to:
-    // point_light_texel_size = 2.0 / cube face width in texels
-    // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
-    // https://catlikecoding.com/unity/tutorials/custom-srp/point-and-spot-shadows/
-    let point_light_texel_size = 2.0 / point_light_shadow_map.size as f32;
-
     let mut point_lights_values = Vec::with_capacity(*previous_point_lights_len);
     for entity in global_point_lights.iter().copied() {
         let Ok((
@@ -289,6 +363,7 @@ pub fn extract_lights(
             view_visibility,
             frusta,
             volumetric_light,
+            cached_shadow_map,
         )) = point_lights.get(entity)
         else {
             continue;
@@ -305,6 +380,19 @@ pub fn extract_lights(
                 .unwrap(),
         };
 
+        let shadow_map_resolution = effective_shadow_map_resolution(
+            point_light.shadow_map_resolution,
+            point_light_shadow_map.size,
+        );
+        // This is the point light shadow map texel size for one face of the cube as a distance of 1.0
+        // world unit from the light.
+        // point_light_texel_size = 2.0 * 1.0 * tan(PI / 4.0) / cube face width in texels
+        // PI / 4.0 is half the cube face fov, tan(PI / 4.0) = 1.0, so this simplifies to:
+        // point_light_texel_size = 2.0 / cube face width in texels
+        // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
+        // https://catlikecoding.com/unity/tutorials/custom-srp/point-and-spot-shadows/
+        let point_light_texel_size = 2.0 / shadow_map_resolution as f32;
+
         let extracted_point_light = ExtractedPointLight {
             color: point_light.color.into(),
             // NOTE: Map from luminous power in lumens to luminous intensity in lumens per steradian
@@ -320,7 +408,9 @@ pub fn extract_lights(
             shadow_normal_bias: point_light.shadow_normal_bias
                 * point_light_texel_size
                 * core::f32::consts::SQRT_2,
+            shadow_slope_bias: point_light.shadow_slope_bias * point_light_texel_size,
             shadow_map_near_z: point_light.shadow_map_near_z,
+            shadow_map_resolution,
             spot_light_angles: None,
             volumetric: volumetric_light.is_some(),
             affects_lightmapped_mesh_diffuse: point_light.affects_lightmapped_mesh_diffuse,
@@ -328,6 +418,11 @@ pub fn extract_lights(
             soft_shadows_enabled: point_light.soft_shadows_enabled,
             #[cfg(not(feature = "experimental_pbr_pcss"))]
             soft_shadows_enabled: false,
+            #[cfg(feature = "experimental_pbr_pcss")]
+            soft_shadow_blocker_search: point_light.soft_shadow_blocker_search,
+            #[cfg(not(feature = "experimental_pbr_pcss"))]
+            soft_shadow_blocker_search: SoftShadowBlockerSearch::default(),
+            cached_shadow_map: cached_shadow_map && SHADOW_CACHING_SUPPORTED,
         };
         point_lights_values.push((
             render_entity,
@@ -353,6 +448,7 @@ pub fn extract_lights(
             view_visibility,
             frustum,
             volumetric_light,
+            cached_shadow_map,
         )) = spot_lights.get(entity)
         {
             if !view_visibility.get() {
@@ -361,8 +457,11 @@ pub fn extract_lights(
             let render_visible_entities =
                 create_render_visible_mesh_entities(&mapper, visible_entities);
 
-            let texel_size =
-                2.0 * ops::tan(spot_light.outer_angle) / directional_light_shadow_map.size as f32;
+            let shadow_map_resolution = effective_shadow_map_resolution(
+                spot_light.shadow_map_resolution,
+                directional_light_shadow_map.size,
+            );
+            let texel_size = 2.0 * ops::tan(spot_light.outer_angle) / shadow_map_resolution as f32;
 
             spot_lights_values.push((
                 render_entity,
@@ -385,7 +484,9 @@ pub fn extract_lights(
                         shadow_normal_bias: spot_light.shadow_normal_bias
                             * texel_size
                             * core::f32::consts::SQRT_2,
+                        shadow_slope_bias: spot_light.shadow_slope_bias * texel_size,
                         shadow_map_near_z: spot_light.shadow_map_near_z,
+                        shadow_map_resolution,
                         spot_light_angles: Some((spot_light.inner_angle, spot_light.outer_angle)),
                         volumetric: volumetric_light.is_some(),
                         affects_lightmapped_mesh_diffuse: spot_light
@@ -394,6 +495,11 @@ pub fn extract_lights(
                         soft_shadows_enabled: spot_light.soft_shadows_enabled,
                         #[cfg(not(feature = "experimental_pbr_pcss"))]
                         soft_shadows_enabled: false,
+                        #[cfg(feature = "experimental_pbr_pcss")]
+                        soft_shadow_blocker_search: spot_light.soft_shadow_blocker_search,
+                        #[cfg(not(feature = "experimental_pbr_pcss"))]
+                        soft_shadow_blocker_search: SoftShadowBlockerSearch::default(),
+                        cached_shadow_map: cached_shadow_map && SHADOW_CACHING_SUPPORTED,
                     },
                     render_visible_entities,
                     *frustum,
@@ -417,6 +523,7 @@ pub fn extract_lights(
         view_visibility,
         maybe_layers,
         volumetric_light,
+        cached_shadow_map,
     ) in &directional_lights
     {
         if !view_visibility.get() {
@@ -473,15 +580,25 @@ pub fn extract_lights(
                     soft_shadow_size: directional_light.soft_shadow_size,
                     #[cfg(not(feature = "experimental_pbr_pcss"))]
                     soft_shadow_size: None,
+                    #[cfg(feature = "experimental_pbr_pcss")]
+                    soft_shadow_blocker_search: directional_light.soft_shadow_blocker_search,
+                    #[cfg(not(feature = "experimental_pbr_pcss"))]
+                    soft_shadow_blocker_search: SoftShadowBlockerSearch::default(),
                     shadows_enabled: directional_light.shadows_enabled,
                     shadow_depth_bias: directional_light.shadow_depth_bias,
                     // The factor of SQRT_2 is for the worst-case diagonal offset
                     shadow_normal_bias: directional_light.shadow_normal_bias
                         * core::f32::consts::SQRT_2,
+                    shadow_slope_bias: directional_light.shadow_slope_bias,
+                    shadow_map_resolution: effective_shadow_map_resolution(
+                        directional_light.shadow_map_resolution,
+                        directional_light_shadow_map.size,
+                    ),
                     cascade_shadow_config: cascade_config.clone(),
                     cascades: extracted_cascades,
                     frusta: extracted_frusta,
                     render_layers: maybe_layers.unwrap_or_default().clone(),
+                    cached_shadow_map: cached_shadow_map && SHADOW_CACHING_SUPPORTED,
                 },
                 RenderCascadesVisibleEntities {
                     entities: cascade_visible_entities,
@@ -643,7 +760,7 @@ pub struct LightMeta {
     pub view_gpu_lights: DynamicUniformBuffer<GpuLights>,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub enum LightEntity {
     Directional {
         light_entity: Entity,
@@ -657,6 +774,197 @@ pub enum LightEntity {
         light_entity: Entity,
     },
 }
+
+/// Marks the light view rendering the [`StaticShadowCaster`]s of a light view of a light with a
+/// [`CachedShadowMap`] into its cached shadow map.
+///
+/// Only the static casters are queued in this view, and only when the cached shadow map is
+/// refreshed. The other casters are queued in the light view it is cached for.
+#[derive(Component)]
+pub struct StaticShadowView {
+    /// Whether the cached shadow map is rendered this frame.
+    pub refresh: bool,
+    incomplete: Arc<AtomicBool>,
+}
+
+impl StaticShadowView {
+    /// Refreshes the cached shadow map again next frame, because a static caster couldn't be
+    /// queued this frame, e.g. because its pipeline is still compiling.
+    pub fn mark_incomplete(&self) {
+        self.incomplete.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The cached shadow map of a light view of a light with a [`CachedShadowMap`].
+///
+/// The [`StaticShadowView`] renders the static casters into `cache_texture` if `refresh` is set,
+/// then the cached shadow map is copied into the layer `array_layer` of `shadow_texture`, before
+/// the light view renders the other casters on top of it.
+#[derive(Component)]
+pub struct CachedShadowView {
+    pub static_view: Entity,
+    pub refresh: bool,
+    pub cache_texture: Texture,
+    pub shadow_texture: Texture,
+    pub array_layer: u32,
+}
+
+/// The cached shadow maps of the light views of the lights with a [`CachedShadowMap`], with the
+/// [`StaticShadowView`]s rendering into them.
+#[derive(Resource, Default)]
+pub struct ShadowCaches {
+    caches: HashMap<RetainedViewEntity, ShadowCache>,
+    live_caches: HashSet<RetainedViewEntity>,
+}
+
+struct ShadowCache {
+    static_view_entity: Entity,
+    texture: Texture,
+    texture_view: TextureView,
+    /// The projection of the light view when the cached shadow map was last rendered.
+    clip_from_world: Mat4,
+    /// The generation of the [`StaticShadowCasterChanges`] when the cached shadow map was last
+    /// rendered.
+    generation: u32,
+    incomplete: Arc<AtomicBool>,
+}
+
+/// A light view of a light with a [`CachedShadowMap`], collected by [`prepare_lights`] to create
+/// its [`StaticShadowView`].
+struct CachedLightView {
+    view_light_entity: Entity,
+    retained_view_entity: RetainedViewEntity,
+    viewport: UVec4,
+    world_from_view: GlobalTransform,
+    clip_from_view: Mat4,
+    clip_from_world: Option<Mat4>,
+    frustum: Frustum,
+    light_entity: LightEntity,
+    pass_name: String,
+    shadow_texture: Texture,
+    array_layer: u32,
+}
+
+/// Set in the subview index of the [`StaticShadowView`]s, to tell them apart from the light views
+/// they are cached for.
+const STATIC_SHADOW_SUBVIEW_INDEX_BIT: u32 = 1 << 31;
+
+impl ShadowCaches {
+    /// Prepares the cached shadow map of a light view, and the [`StaticShadowView`] rendering into
+    /// it, which is returned with its retained view entity.
+    fn prepare_view(
+        &mut self,
+        commands: &mut Commands,
+        render_device: &RenderDevice,
+        generation: u32,
+        view: CachedLightView,
+    ) -> (Entity, RetainedViewEntity) {
+        let width = view.shadow_texture.width();
+        let height = view.shadow_texture.height();
+        let clip_from_world = view.clip_from_world.unwrap_or_else(|| {
+            view.clip_from_view * view.world_from_view.compute_matrix().inverse()
+        });
+
+        self.live_caches.insert(view.retained_view_entity);
+        let cache = self.caches.get(&view.retained_view_entity);
+        if cache.is_none_or(|cache| cache.texture.width() != width) {
+            let static_view_entity = cache.map_or_else(
+                || commands.spawn_empty().id(),
+                |cache| cache.static_view_entity,
+            );
+            let texture = render_device.create_texture(&TextureDescriptor {
+                label: Some("cached_shadow_map_texture"),
+                size: Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: CORE_3D_DEPTH_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            let texture_view = texture.create_view(&TextureViewDescriptor {
+                label: Some("cached_shadow_map_texture_view"),
+                ..default()
+            });
+            self.caches.insert(
+                view.retained_view_entity,
+                ShadowCache {
+                    static_view_entity,
+                    texture,
+                    texture_view,
+                    clip_from_world,
+                    generation,
+                    // The new cached shadow map has to be rendered.
+                    incomplete: Arc::new(AtomicBool::new(true)),
+                },
+            );
+        }
+
+        let cache = self.caches.get_mut(&view.retained_view_entity).unwrap();
+        let refresh = cache.incomplete.swap(false, Ordering::Relaxed)
+            || cache.clip_from_world != clip_from_world
+            || cache.generation != generation;
+        cache.clip_from_world = clip_from_world;
+        cache.generation = generation;
+
+        let retained_view_entity = RetainedViewEntity {
+            subview_index: view.retained_view_entity.subview_index
+                | STATIC_SHADOW_SUBVIEW_INDEX_BIT,
+            ..view.retained_view_entity
+        };
+
+        commands.entity(cache.static_view_entity).insert((
+            ShadowView {
+                depth_attachment: DepthAttachment::new(cache.texture_view.clone(), Some(0.0)),
+                pass_name: format!("{} static", view.pass_name),
+            },
+            ExtractedView {
+                retained_view_entity,
+                viewport: view.viewport,
+                world_from_view: view.world_from_view,
+                clip_from_view: view.clip_from_view,
+                clip_from_world: view.clip_from_world,
+                hdr: false,
+                color_grading: Default::default(),
+            },
+            view.frustum,
+            view.light_entity,
+            StaticShadowView {
+                refresh,
+                incomplete: cache.incomplete.clone(),
+            },
+        ));
+
+        commands
+            .entity(view.view_light_entity)
+            .insert(CachedShadowView {
+                static_view: cache.static_view_entity,
+                refresh,
+                cache_texture: cache.texture.clone(),
+                shadow_texture: view.shadow_texture,
+                array_layer: view.array_layer,
+            });
+
+        (cache.static_view_entity, retained_view_entity)
+    }
+
+    /// Drops the cached shadow maps of the light views which weren't prepared this frame.
+    fn retain_live(&mut self, commands: &mut Commands) {
+        let live_caches = core::mem::take(&mut self.live_caches);
+        self.caches.retain(|retained_view_entity, cache| {
+            let live = live_caches.contains(retained_view_entity);
+            if !live {
+                despawn_entities(commands, vec![cache.static_view_entity]);
+            }
+            live
+        });
+    }
+}
+
 pub fn calculate_cluster_factors(
     near: f32,
     far: f32,
@@ -721,13 +1029,22 @@ pub fn prepare_lights(
             Option<&RenderLayers>,
             Has<NoIndirectDrawing>,
             Option<&AmbientLight>,
+            Option<&DebugView>,
+            Option<&ViewRenderScale>,
         ),
         With<Camera3d>,
     >,
     ambient_light: Res<AmbientLight>,
-    point_light_shadow_map: Res<PointLightShadowMap>,
-    directional_light_shadow_map: Res<DirectionalLightShadowMap>,
-    mut shadow_render_phases: ResMut<ViewBinnedRenderPhases<Shadow>>,
+    (point_light_shadow_map, directional_light_shadow_map, soft_shadow_limits): (
+        Res<PointLightShadowMap>,
+        Res<DirectionalLightShadowMap>,
+        Res<SoftShadowLimits>,
+    ),
+    (mut shadow_render_phases, mut shadow_caches, static_shadow_caster_changes): (
+        ResMut<ViewBinnedRenderPhases<Shadow>>,
+        ResMut<ShadowCaches>,
+        Res<StaticShadowCasterChanges>,
+    ),
     (
         mut max_directional_lights_warning_emitted,
         mut max_cascades_per_light_warning_emitted,
@@ -916,6 +1233,19 @@ pub fn prepare_lights(
             flags |= PointLightFlags::AFFECTS_LIGHTMAPPED_MESH_DIFFUSE;
         }
 
+        if light.soft_shadows_enabled {
+            flags |= PointLightFlags::from_soft_shadow_blocker_search(
+                &light.soft_shadow_blocker_search,
+                &soft_shadow_limits,
+            );
+        }
+
+        // Spot lights render to the directional light shadow map.
+        let shadow_map_size = match light.spot_light_angles {
+            Some(_) => directional_light_shadow_map.size,
+            None => point_light_shadow_map.size,
+        };
+
         let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
             Some((inner, outer)) => {
                 let light_direction = light.transform.forward();
@@ -960,10 +1290,10 @@ pub fn prepare_lights(
             flags: flags.bits(),
             shadow_depth_bias: light.shadow_depth_bias,
             shadow_normal_bias: light.shadow_normal_bias,
+            shadow_slope_bias: light.shadow_slope_bias,
             shadow_map_near_z: light.shadow_map_near_z,
+            shadow_map_scale: light.shadow_map_resolution as f32 / shadow_map_size as f32,
             spot_light_tan_angle,
-            pad_a: 0.0,
-            pad_b: 0.0,
             soft_shadow_size: if light.soft_shadows_enabled {
                 light.radius
             } else {
@@ -1017,6 +1347,17 @@ pub fn prepare_lights(
             soft_shadow_size: light.soft_shadow_size.unwrap_or_default(),
             shadow_depth_bias: light.shadow_depth_bias,
             shadow_normal_bias: light.shadow_normal_bias,
+            shadow_slope_bias: light.shadow_slope_bias,
+            shadow_map_scale: light.shadow_map_resolution as f32
+                / directional_light_shadow_map.size as f32,
+            blocker_search_sample_count: blocker_search_sample_count(
+                &light.soft_shadow_blocker_search,
+                &soft_shadow_limits,
+            ),
+            blocker_search_radius_scale: light
+                .soft_shadow_blocker_search
+                .radius_scale
+                .clamp(0.0, SoftShadowBlockerSearch::MAX_RADIUS_SCALE),
             num_cascades: num_cascades as u32,
             cascades_overlap_proportion: light.cascade_shadow_config.overlap_proportion,
             depth_texture_base_index: num_directional_cascades_enabled as u32,
@@ -1051,7 +1392,10 @@ pub fn prepare_lights(
             dimension: TextureDimension::D2,
             format: CORE_3D_DEPTH_FORMAT,
             label: Some("point_light_shadow_map_texture"),
-            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            // The cached shadow maps are copied into the shadow maps
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST,
             view_formats: &[],
         },
     );
@@ -1102,7 +1446,10 @@ pub fn prepare_lights(
             dimension: TextureDimension::D2,
             format: CORE_3D_DEPTH_FORMAT,
             label: Some("directional_light_shadow_map_texture"),
-            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            // The cached shadow maps are copied into the shadow maps
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST,
             view_formats: &[],
         },
     );
@@ -1139,6 +1486,8 @@ pub fn prepare_lights(
         maybe_layers,
         no_indirect_drawing,
         maybe_ambient_override,
+        debug_view,
+        render_scale,
     ) in sorted_cameras
         .0
         .iter()
@@ -1146,6 +1495,7 @@ pub fn prepare_lights(
     {
         live_views.insert(entity);
         let mut view_lights = Vec::new();
+        let mut cached_light_views = Vec::new();
 
         let gpu_preprocessing_mode = gpu_preprocessing_support.min(if !no_indirect_drawing {
             GpuPreprocessingMode::Culling
@@ -1161,6 +1511,12 @@ pub fn prepare_lights(
             is_orthographic,
         );
 
+        // The clusters are looked up from the fragment coordinates of the main passes, which cover
+        // a smaller viewport when the view has a render scale.
+        let main_pass_size = render_scale.map_or(extracted_view.viewport.zw(), |render_scale| {
+            render_scale.main_pass_size
+        });
+
         let n_clusters = clusters.dimensions.x * clusters.dimensions.y * clusters.dimensions.z;
         let ambient_light = maybe_ambient_override.unwrap_or(&ambient_light);
         let mut gpu_lights = GpuLights {
@@ -1168,8 +1524,8 @@ pub fn prepare_lights(
             ambient_color: Vec4::from_slice(&LinearRgba::from(ambient_light.color).to_f32_array())
                 * ambient_light.brightness,
             cluster_factors: Vec4::new(
-                clusters.dimensions.x as f32 / extracted_view.viewport.z as f32,
-                clusters.dimensions.y as f32 / extracted_view.viewport.w as f32,
+                clusters.dimensions.x as f32 / main_pass_size.x as f32,
+                clusters.dimensions.y as f32 / main_pass_size.y as f32,
                 cluster_factors_zw.x,
                 cluster_factors_zw.y,
             ),
@@ -1183,6 +1539,8 @@ pub fn prepare_lights(
                 - point_light_count as i32,
             ambient_light_affects_lightmapped_meshes: ambient_light.affects_lightmapped_meshes
                 as u32,
+            debug_view_mode: debug_view.map_or(0, |debug_view| debug_view.shader_mode()),
+            max_soft_shadow_lights: soft_shadow_limits.max_lights_per_fragment,
         };
 
         // TODO: this should select lights based on relevance to the view instead of the first ones that show up in a query
@@ -1250,7 +1608,9 @@ pub fn prepare_lights(
                                     array_layer_count: Some(1u32),
                                 });
 
-                        DepthAttachment::new(depth_texture_view, Some(0.0))
+                        // The cached shadow map is copied into the shadow map instead of clearing it
+                        let clear_value = (!light.cached_shadow_map).then_some(0.0);
+                        DepthAttachment::new(depth_texture_view, clear_value)
                     })
                     .clone();
 
@@ -1260,24 +1620,30 @@ pub fn prepare_lights(
                     face_index as u32,
                 );
 
+                let pass_name = format!(
+                    "shadow pass point light {} {}",
+                    light_index,
+                    face_index_to_name(face_index)
+                );
+                // Lights with a lower shadow map resolution only render to the top left corner
+                // of their shadow map.
+                let viewport = UVec4::new(
+                    0,
+                    0,
+                    light.shadow_map_resolution,
+                    light.shadow_map_resolution,
+                );
+                let world_from_view = view_translation * *view_rotation;
+
                 commands.entity(view_light_entity).insert((
                     ShadowView {
                         depth_attachment,
-                        pass_name: format!(
-                            "shadow pass point light {} {}",
-                            light_index,
-                            face_index_to_name(face_index)
-                        ),
+                        pass_name: pass_name.clone(),
                     },
                     ExtractedView {
                         retained_view_entity,
-                        viewport: UVec4::new(
-                            0,
-                            0,
-                            point_light_shadow_map.size as u32,
-                            point_light_shadow_map.size as u32,
-                        ),
-                        world_from_view: view_translation * *view_rotation,
+                        viewport,
+                        world_from_view,
                         clip_from_world: None,
                         clip_from_view: cube_face_projection,
                         hdr: false,
@@ -1302,6 +1668,29 @@ pub fn prepare_lights(
                         .insert_or_clear(retained_view_entity, gpu_preprocessing_mode);
                     live_shadow_mapping_lights.insert(retained_view_entity);
                 }
+
+                if first && light.cached_shadow_map {
+                    cached_light_views.push(CachedLightView {
+                        view_light_entity,
+                        retained_view_entity,
+                        viewport,
+                        world_from_view,
+                        clip_from_view: cube_face_projection,
+                        clip_from_world: None,
+                        frustum: *frustum,
+                        light_entity: LightEntity::Point {
+                            light_entity,
+                            face_index,
+                        },
+                        pass_name,
+                        shadow_texture: point_light_depth_texture.texture.clone(),
+                        array_layer: base_array_layer,
+                    });
+                } else {
+                    commands
+                        .entity(view_light_entity)
+                        .remove::<CachedShadowView>();
+                }
             }
         }
 
@@ -1352,7 +1741,9 @@ pub fn prepare_lights(
                         },
                     );
 
-                    DepthAttachment::new(depth_texture_view, Some(0.0))
+                    // The cached shadow map is copied into the shadow map instead of clearing it
+                    let clear_value = (!light.cached_shadow_map).then_some(0.0);
+                    DepthAttachment::new(depth_texture_view, clear_value)
                 })
                 .clone();
 
@@ -1365,19 +1756,22 @@ pub fn prepare_lights(
             let retained_view_entity =
                 RetainedViewEntity::new(*light_main_entity, Some(camera_main_entity.into()), 0);
 
+            let pass_name = format!("shadow pass spot light {light_index}");
+            let viewport = UVec4::new(
+                0,
+                0,
+                light.shadow_map_resolution,
+                light.shadow_map_resolution,
+            );
+
             commands.entity(view_light_entity).insert((
                 ShadowView {
                     depth_attachment,
-                    pass_name: format!("shadow pass spot light {light_index}"),
+                    pass_name: pass_name.clone(),
                 },
                 ExtractedView {
                     retained_view_entity,
-                    viewport: UVec4::new(
-                        0,
-                        0,
-                        directional_light_shadow_map.size as u32,
-                        directional_light_shadow_map.size as u32,
-                    ),
+                    viewport,
                     world_from_view: spot_world_from_view,
                     clip_from_view: spot_projection,
                     clip_from_world: None,
@@ -1399,6 +1793,26 @@ pub fn prepare_lights(
                 shadow_render_phases.insert_or_clear(retained_view_entity, gpu_preprocessing_mode);
                 live_shadow_mapping_lights.insert(retained_view_entity);
             }
+
+            if first && light.cached_shadow_map {
+                cached_light_views.push(CachedLightView {
+                    view_light_entity,
+                    retained_view_entity,
+                    viewport,
+                    world_from_view: spot_world_from_view,
+                    clip_from_view: spot_projection,
+                    clip_from_world: None,
+                    frustum: *spot_light_frustum.unwrap(),
+                    light_entity: LightEntity::Spot { light_entity },
+                    pass_name,
+                    shadow_texture: directional_light_depth_texture.texture.clone(),
+                    array_layer: base_array_layer,
+                });
+            } else {
+                commands
+                    .entity(view_light_entity)
+                    .remove::<CachedShadowView>();
+            }
         }
 
         // directional lights
@@ -1487,8 +1901,11 @@ pub fn prepare_lights(
                 // NOTE: For point and spotlights, we reuse the same depth attachment for all views.
                 // However, for directional lights, we want a new depth attachment for each view,
                 // so that the view is cleared for each view.
-                let depth_attachment = DepthAttachment::new(depth_texture_view, Some(0.0));
+                // The cached shadow map is copied into the shadow map instead of clearing it.
+                let clear_value = (!light.cached_shadow_map).then_some(0.0);
+                let depth_attachment = DepthAttachment::new(depth_texture_view, clear_value);
 
+                let array_layer = directional_depth_texture_array_index;
                 directional_depth_texture_array_index += 1;
 
                 let mut frustum = *frustum;
@@ -1502,21 +1919,23 @@ pub fn prepare_lights(
                     cascade_index as u32,
                 );
 
+                let pass_name =
+                    format!("shadow pass directional light {light_index} cascade {cascade_index}");
+                let viewport = UVec4::new(
+                    0,
+                    0,
+                    light.shadow_map_resolution,
+                    light.shadow_map_resolution,
+                );
+
                 commands.entity(view_light_entity).insert((
                     ShadowView {
                         depth_attachment,
-                        pass_name: format!(
-                            "shadow pass directional light {light_index} cascade {cascade_index}"
-                        ),
+                        pass_name: pass_name.clone(),
                     },
                     ExtractedView {
                         retained_view_entity,
-                        viewport: UVec4::new(
-                            0,
-                            0,
-                            directional_light_shadow_map.size as u32,
-                            directional_light_shadow_map.size as u32,
-                        ),
+                        viewport,
                         world_from_view: GlobalTransform::from(cascade.world_from_cascade),
                         clip_from_view: cascade.clip_from_cascade,
                         clip_from_world: Some(cascade.clip_from_world),
@@ -1541,7 +1960,51 @@ pub fn prepare_lights(
                 // TODO: Implement GPU culling for shadow passes.
                 shadow_render_phases.insert_or_clear(retained_view_entity, gpu_preprocessing_mode);
                 live_shadow_mapping_lights.insert(retained_view_entity);
+
+                if light.cached_shadow_map {
+                    cached_light_views.push(CachedLightView {
+                        view_light_entity,
+                        retained_view_entity,
+                        viewport,
+                        world_from_view: GlobalTransform::from(cascade.world_from_cascade),
+                        clip_from_view: cascade.clip_from_cascade,
+                        clip_from_world: Some(cascade.clip_from_world),
+                        frustum,
+                        light_entity: LightEntity::Directional {
+                            light_entity,
+                            cascade_index,
+                        },
+                        pass_name,
+                        shadow_texture: directional_light_depth_texture.texture.clone(),
+                        array_layer,
+                    });
+                } else {
+                    commands
+                        .entity(view_light_entity)
+                        .remove::<CachedShadowView>();
+                }
+            }
+        }
+
+        // Render the static casters of the lights with cached shadow maps in separate views
+        for cached_light_view in cached_light_views {
+            let (static_view_entity, retained_view_entity) = shadow_caches.prepare_view(
+                &mut commands,
+                &render_device,
+                static_shadow_caster_changes.generation(),
+                cached_light_view,
+            );
+
+            if !matches!(gpu_preprocessing_mode, GpuPreprocessingMode::Culling) {
+                commands
+                    .entity(static_view_entity)
+                    .insert(NoIndirectDrawing);
             }
+
+            view_lights.push(static_view_entity);
+
+            shadow_render_phases.insert_or_clear(retained_view_entity, gpu_preprocessing_mode);
+            live_shadow_mapping_lights.insert(retained_view_entity);
         }
 
         commands.entity(entity).insert((
@@ -1570,6 +2033,7 @@ pub fn prepare_lights(
     }
 
     shadow_render_phases.retain(|entity, _| live_shadow_mapping_lights.contains(entity));
+    shadow_caches.retain_live(&mut commands);
 }
 
 fn despawn_entities(commands: &mut Commands, entities: Vec<Entity>) {
@@ -1833,8 +2297,14 @@ pub fn queue_shadows<M: Material>(
     mut shadow_render_phases: ResMut<ViewBinnedRenderPhases<Shadow>>,
     gpu_preprocessing_support: Res<GpuPreprocessingSupport>,
     mesh_allocator: Res<MeshAllocator>,
+    pipeline_cache: Res<PipelineCache>,
     view_lights: Query<(Entity, &ViewLightEntities), With<ExtractedView>>,
-    view_light_entities: Query<(&LightEntity, &ExtractedView)>,
+    view_light_entities: Query<(
+        &LightEntity,
+        &ExtractedView,
+        Option<&StaticShadowView>,
+        Has<CachedShadowView>,
+    )>,
     point_light_entities: Query<&RenderCubemapVisibleEntities, With<ExtractedPointLight>>,
     directional_light_entities: Query<
         &RenderCascadesVisibleEntities,
@@ -1842,17 +2312,22 @@ pub fn queue_shadows<M: Material>(
     >,
     spot_light_entities: Query<&RenderVisibleMeshEntities, With<ExtractedPointLight>>,
     specialized_material_pipeline_cache: Res<SpecializedShadowMaterialPipelineCache<M>>,
+    render_material_instances: Res<RenderMaterialInstances<M>>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
     let draw_shadow_mesh = shadow_draw_functions.read().id::<DrawPrepass<M>>();
     for (entity, view_lights) in &view_lights {
         for view_light_entity in view_lights.lights.iter().copied() {
-            let Ok((light_entity, extracted_view_light)) =
+            let Ok((light_entity, extracted_view_light, static_shadow_view, cached_shadow_view)) =
                 view_light_entities.get(view_light_entity)
             else {
                 continue;
             };
+            // The static casters are only queued when the cached shadow map is refreshed
+            if static_shadow_view.is_some_and(|static_shadow_view| !static_shadow_view.refresh) {
+                continue;
+            }
             let Some(shadow_phase) =
                 shadow_render_phases.get_mut(&extracted_view_light.retained_view_entity)
             else {
@@ -1884,11 +2359,6 @@ pub fn queue_shadows<M: Material>(
             };
 
             for (entity, main_entity) in visible_entities.iter().copied() {
-                let Some((_, pipeline_id)) =
-                    specialized_material_pipeline_cache.get(&(view_light_entity, main_entity))
-                else {
-                    continue;
-                };
                 let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(main_entity)
                 else {
                     continue;
@@ -1899,12 +2369,40 @@ pub fn queue_shadows<M: Material>(
                 {
                     continue;
                 }
+                // Lights with a cached shadow map render their static casters in a separate view
+                let static_shadow_caster = mesh_instance
+                    .flags
+                    .contains(RenderMeshInstanceFlags::STATIC_SHADOW_CASTER);
+                if (static_shadow_view.is_some() && !static_shadow_caster)
+                    || (cached_shadow_view && static_shadow_caster)
+                {
+                    continue;
+                }
 
+                let pipeline_id = specialized_material_pipeline_cache
+                    .get(&(view_light_entity, main_entity))
+                    .map(|(_, pipeline_id)| *pipeline_id);
                 let (vertex_slab, index_slab) =
                     mesh_allocator.mesh_slabs(&mesh_instance.mesh_asset_id);
 
+                // The cached shadow map must be rendered again once all of its casters using this
+                // material can be drawn
+                if let Some(static_shadow_view) = static_shadow_view {
+                    if render_material_instances.contains_key(&main_entity)
+                        && (pipeline_id
+                            .is_none_or(|id| pipeline_cache.get_render_pipeline(id).is_none())
+                            || vertex_slab.is_none())
+                    {
+                        static_shadow_view.mark_incomplete();
+                    }
+                }
+
+                let Some(pipeline_id) = pipeline_id else {
+                    continue;
+                };
+
                 let batch_set_key = ShadowBatchSetKey {
-                    pipeline: *pipeline_id,
+                    pipeline: pipeline_id,
                     draw_function: draw_shadow_mesh,
                     vertex_slab: vertex_slab.unwrap_or_default(),
                     index_slab,
@@ -2044,7 +2542,15 @@ impl CachedRenderPipelinePhaseItem for Shadow {
 
 pub struct ShadowPassNode {
     main_view_query: QueryState<Read<ViewLightEntities>>,
-    view_light_query: QueryState<(Read<ShadowView>, Read<ExtractedView>)>,
+    view_light_query: QueryState<
+        (
+            Read<ShadowView>,
+            Read<ExtractedView>,
+            Option<Read<CachedShadowView>>,
+        ),
+        Without<StaticShadowView>,
+    >,
+    static_view_query: QueryState<(Read<ShadowView>, Read<ExtractedView>), With<StaticShadowView>>,
 }
 
 impl ShadowPassNode {
@@ -2052,6 +2558,7 @@ impl ShadowPassNode {
         Self {
             main_view_query: QueryState::new(world),
             view_light_query: QueryState::new(world),
+            static_view_query: QueryState::new(world),
         }
     }
 }
@@ -2060,6 +2567,7 @@ impl Node for ShadowPassNode {
     fn update(&mut self, world: &mut World) {
         self.main_view_query.update_archetypes(world);
         self.view_light_query.update_archetypes(world);
+        self.static_view_query.update_archetypes(world);
     }
 
     fn run<'w>(
@@ -2080,8 +2588,9 @@ impl Node for ShadowPassNode {
         let time_span = diagnostics.time_span(render_context.command_encoder(), "shadows");
 
         if let Ok(view_lights) = self.main_view_query.get_manual(world, view_entity) {
+            // The static views are rendered along with the light views they are cached for
             for view_light_entity in view_lights.lights.iter().copied() {
-                let Ok((view_light, extracted_light_view)) =
+                let Ok((view_light, extracted_light_view, cached_shadow_view)) =
                     self.view_light_query.get_manual(world, view_light_entity)
                 else {
                     continue;
@@ -2093,6 +2602,23 @@ impl Node for ShadowPassNode {
                     continue;
                 };
 
+                let static_view = cached_shadow_view.and_then(|cached_shadow_view| {
+                    let (static_view_light, extracted_static_view) = self
+                        .static_view_query
+                        .get_manual(world, cached_shadow_view.static_view)
+                        .ok()?;
+                    let static_shadow_phase =
+                        shadow_render_phases.get(&extracted_static_view.retained_view_entity)?;
+                    Some((
+                        cached_shadow_view,
+                        static_view_light,
+                        extracted_static_view.viewport,
+                        static_shadow_phase,
+                    ))
+                });
+
+                let viewport = extracted_light_view.viewport;
+
                 let depth_stencil_attachment =
                     Some(view_light.depth_attachment.get_attachment(StoreOp::Store));
 
@@ -2105,6 +2631,63 @@ impl Node for ShadowPassNode {
                             label: Some("shadow_pass_command_encoder"),
                         });
 
+                    if let Some((
+                        cached_shadow_view,
+                        static_view_light,
+                        static_viewport,
+                        static_shadow_phase,
+                    )) = static_view
+                    {
+                        if cached_shadow_view.refresh {
+                            let render_pass =
+                                command_encoder.begin_render_pass(&RenderPassDescriptor {
+                                    label: Some(&static_view_light.pass_name),
+                                    color_attachments: &[],
+                                    depth_stencil_attachment: Some(
+                                        static_view_light
+                                            .depth_attachment
+                                            .get_attachment(StoreOp::Store),
+                                    ),
+                                    timestamp_writes: None,
+                                    occlusion_query_set: None,
+                                });
+
+                            let mut render_pass =
+                                TrackedRenderPass::new(&render_device, render_pass);
+                            set_shadow_viewport(&mut render_pass, static_viewport);
+                            let pass_span = diagnostics
+                                .pass_span(&mut render_pass, static_view_light.pass_name.clone());
+
+                            if let Err(err) = static_shadow_phase.render(
+                                &mut render_pass,
+                                world,
+                                cached_shadow_view.static_view,
+                            ) {
+                                error!(
+                                    "Error encountered while rendering the static shadow phase {err:?}"
+                                );
+                            }
+
+                            pass_span.end(&mut render_pass);
+                        }
+
+                        // Start from the cached shadow map, the other casters are rendered on top
+                        command_encoder.copy_texture_to_texture(
+                            cached_shadow_view.cache_texture.as_image_copy(),
+                            ImageCopyTexture {
+                                texture: &cached_shadow_view.shadow_texture,
+                                mip_level: 0,
+                                origin: Origin3d {
+                                    x: 0,
+                                    y: 0,
+                                    z: cached_shadow_view.array_layer,
+                                },
+                                aspect: TextureAspect::All,
+                            },
+                            cached_shadow_view.cache_texture.size(),
+                        );
+                    }
+
                     let render_pass = command_encoder.begin_render_pass(&RenderPassDescriptor {
                         label: Some(&view_light.pass_name),
                         color_attachments: &[],
@@ -2114,6 +2697,7 @@ impl Node for ShadowPassNode {
                     });
 
                     let mut render_pass = TrackedRenderPass::new(&render_device, render_pass);
+                    set_shadow_viewport(&mut render_pass, viewport);
                     let pass_span =
                         diagnostics.pass_span(&mut render_pass, view_light.pass_name.clone());
 
@@ -2135,3 +2719,82 @@ impl Node for ShadowPassNode {
         Ok(())
     }
 }
+
+/// Restricts a shadow pass to the viewport of its light view, which is smaller
+/// than the shadow map for lights with a lower shadow map resolution.
+fn set_shadow_viewport(render_pass: &mut TrackedRenderPass, viewport: UVec4) {
+    render_pass.set_viewport(
+        viewport.x as f32,
+        viewport.y as f32,
+        viewport.z as f32,
+        viewport.w as f32,
+        0.0,
+        1.0,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PointLightFlags, SoftShadowBlockerSearch, SoftShadowLimits};
+
+    /// Unpacks the blocker search of a light from its flags, like `shadows.wgsl` does.
+    fn unpack_blocker_search(flags: PointLightFlags) -> (u32, f32) {
+        let bits = flags.bits();
+        let sample_count = (bits >> PointLightFlags::BLOCKER_SEARCH_SAMPLE_COUNT_SHIFT_BITS)
+            & PointLightFlags::BLOCKER_SEARCH_SAMPLE_COUNT_MASK_BITS;
+        let radius_scale = (bits >> PointLightFlags::BLOCKER_SEARCH_RADIUS_SCALE_SHIFT_BITS)
+            & PointLightFlags::BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS;
+        (
+            sample_count,
+            radius_scale as f32 / PointLightFlags::BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS as f32
+                * SoftShadowBlockerSearch::MAX_RADIUS_SCALE,
+        )
+    }
+
+    #[test]
+    fn blocker_search_flags_round_trip() {
+        let limits = SoftShadowLimits::default();
+        let flags = PointLightFlags::from_soft_shadow_blocker_search(
+            &SoftShadowBlockerSearch {
+                radius_scale: 2.5,
+                sample_count: 5,
+            },
+            &limits,
+        );
+        // The blocker search doesn't overwrite the other flags.
+        assert!(!flags.intersects(
+            PointLightFlags::SHADOWS_ENABLED
+                | PointLightFlags::SPOT_LIGHT_Y_NEGATIVE
+                | PointLightFlags::VOLUMETRIC
+                | PointLightFlags::AFFECTS_LIGHTMAPPED_MESH_DIFFUSE
+        ));
+        let (sample_count, radius_scale) = unpack_blocker_search(flags);
+        assert_eq!(sample_count, 5);
+        assert!((radius_scale - 2.5).abs() < 1e-3);
+
+        // Values out of range are clamped, including to the limits.
+        let flags = PointLightFlags::from_soft_shadow_blocker_search(
+            &SoftShadowBlockerSearch {
+                radius_scale: 100.0,
+                sample_count: 100,
+            },
+            &SoftShadowLimits {
+                max_blocker_search_samples: 3,
+                ..limits
+            },
+        );
+        assert_eq!(
+            unpack_blocker_search(flags),
+            (3, SoftShadowBlockerSearch::MAX_RADIUS_SCALE)
+        );
+
+        let flags = PointLightFlags::from_soft_shadow_blocker_search(
+            &SoftShadowBlockerSearch {
+                radius_scale: -1.0,
+                sample_count: 0,
+            },
+            &limits,
+        );
+        assert_eq!(unpack_blocker_search(flags), (1, 0.0));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::material_bind_groups::{MaterialBindGroupIndex, MaterialBindGroupSlot};
 use allocator::MeshAllocator;
 use bevy_asset::{load_internal_asset, AssetId, UntypedAssetId};
+use bevy_color::ColorToComponents;
 use bevy_core_pipeline::{
     core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
     deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
@@ -161,19 +162,24 @@ impl Plugin for MeshRenderPlugin {
             return;
         }
 
-        app.add_systems(
-            PostUpdate,
-            (no_automatic_skin_batching, no_automatic_morph_batching),
-        )
-        .add_plugins((
-            BinnedRenderPhasePlugin::<Opaque3d, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<AlphaMask3d, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<Shadow, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<Opaque3dDeferred, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<AlphaMask3dDeferred, MeshPipeline>::default(),
-            SortedRenderPhasePlugin::<Transmissive3d, MeshPipeline>::default(),
-            SortedRenderPhasePlugin::<Transparent3d, MeshPipeline>::default(),
-        ));
+        app.register_type::<MeshMaterialOverrides>()
+            .add_systems(
+                PostUpdate,
+                (
+                    no_automatic_skin_batching,
+                    no_automatic_morph_batching,
+                    mark_meshes_as_changed_if_their_material_overrides_changed.ambiguous_with_all(),
+                ),
+            )
+            .add_plugins((
+                BinnedRenderPhasePlugin::<Opaque3d, MeshPipeline>::default(),
+                BinnedRenderPhasePlugin::<AlphaMask3d, MeshPipeline>::default(),
+                BinnedRenderPhasePlugin::<Shadow, MeshPipeline>::default(),
+                BinnedRenderPhasePlugin::<Opaque3dDeferred, MeshPipeline>::default(),
+                BinnedRenderPhasePlugin::<AlphaMask3dDeferred, MeshPipeline>::default(),
+                SortedRenderPhasePlugin::<Transmissive3d, MeshPipeline>::default(),
+                SortedRenderPhasePlugin::<Transparent3d, MeshPipeline>::default(),
+            ));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -336,6 +342,7 @@ pub fn check_views_need_specialization(
             Has<RenderViewLightProbes<IrradianceVolume>>,
         ),
         Has<OrderIndependentTransparencySettings>,
+        Option<&DebugView>,
     )>,
     ticks: SystemChangeTick,
 ) {
@@ -354,6 +361,7 @@ pub fn check_views_need_specialization(
         distance_fog,
         (has_environment_maps, has_irradiance_volumes),
         has_oit,
+        debug_view,
     ) in views.iter_mut()
     {
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
@@ -426,6 +434,9 @@ pub fn check_views_need_specialization(
         if distance_fog {
             view_key |= MeshPipelineKey::DISTANCE_FOG;
         }
+        if debug_view == Some(&DebugView::Overdraw) {
+            view_key |= MeshPipelineKey::DEBUG_VIEW_OVERDRAW;
+        }
         if let Some(camera_3d) = camera_3d {
             view_key |= screen_space_specular_transmission_pipeline_key(
                 camera_3d.screen_space_specular_transmission_quality,
@@ -485,6 +496,16 @@ pub struct MeshUniform {
     /// Low 16 bits: index of the material inside the bind group data.
     /// High 16 bits: index of the lightmap in the binding array.
     pub material_and_lightmap_bind_group_slot: u32,
+    /// The base color of the [`MeshMaterialOverrides`], in linear RGBA.
+    ///
+    /// This is only used if [`MeshFlags::BASE_COLOR_OVERRIDE`] is set.
+    pub base_color_override: Vec4,
+    /// The emissive color of the [`MeshMaterialOverrides`], in linear RGBA.
+    ///
+    /// This is only used if [`MeshFlags::EMISSIVE_OVERRIDE`] is set.
+    pub emissive_override: Vec4,
+    /// The [`MeshMaterialOverrides::custom`] values.
+    pub custom_material_data: [Vec4; 2],
 }
 
 /// Information that has to be transferred from CPU to GPU in order to produce
@@ -545,6 +566,12 @@ pub struct MeshInputUniform {
     pub pad_a: u32,
     /// Padding.
     pub pad_b: u32,
+    /// The base color of the [`MeshMaterialOverrides`], in linear RGBA.
+    pub base_color_override: Vec4,
+    /// The emissive color of the [`MeshMaterialOverrides`], in linear RGBA.
+    pub emissive_override: Vec4,
+    /// The [`MeshMaterialOverrides::custom`] values.
+    pub custom_material_data: [Vec4; 2],
 }
 
 /// Information about each mesh instance needed to cull it on GPU.
@@ -578,6 +605,7 @@ impl MeshUniform {
         maybe_lightmap: Option<(LightmapSlotIndex, Rect)>,
         current_skin_index: Option<u32>,
         previous_skin_index: Option<u32>,
+        material_overrides: &MeshMaterialOverrides,
     ) -> Self {
         let (local_from_world_transpose_a, local_from_world_transpose_b) =
             mesh_transforms.world_from_local.inverse_transpose_3x3();
@@ -598,10 +626,26 @@ impl MeshUniform {
             previous_skin_index: previous_skin_index.unwrap_or(u32::MAX),
             material_and_lightmap_bind_group_slot: u32::from(material_bind_group_slot)
                 | ((lightmap_bind_group_slot as u32) << 16),
+            base_color_override: material_overrides.base_color_override(),
+            emissive_override: material_overrides.emissive_override(),
+            custom_material_data: material_overrides.custom,
         }
     }
 }
 
+impl MeshMaterialOverrides {
+    /// Returns the base color override in linear RGBA, or zero if there's none.
+    fn base_color_override(&self) -> Vec4 {
+        self.base_color
+            .map_or(Vec4::ZERO, |color| color.to_linear().to_vec4())
+    }
+
+    /// Returns the emissive override in linear RGBA, or zero if there's none.
+    fn emissive_override(&self) -> Vec4 {
+        self.emissive.map_or(Vec4::ZERO, ColorToComponents::to_vec4)
+    }
+}
+
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_types.wgsl!
 bitflags::bitflags! {
     /// Various flags and tightly-packed values on a mesh.
@@ -614,6 +658,10 @@ bitflags::bitflags! {
         ///
         /// This will be `u16::MAX` if this mesh has no LOD.
         const LOD_INDEX_MASK              = (1 << 16) - 1;
+        /// The mesh has a [`MeshMaterialOverrides::base_color`].
+        const BASE_COLOR_OVERRIDE         = 1 << 26;
+        /// The mesh has a [`MeshMaterialOverrides::emissive`].
+        const EMISSIVE_OVERRIDE           = 1 << 27;
         /// Disables frustum culling for this mesh.
         ///
         /// This corresponds to the
@@ -636,6 +684,7 @@ impl MeshFlags {
         no_frustum_culling: bool,
         not_shadow_receiver: bool,
         transmitted_receiver: bool,
+        material_overrides: &MeshMaterialOverrides,
     ) -> MeshFlags {
         let mut mesh_flags = if not_shadow_receiver {
             MeshFlags::empty()
@@ -648,6 +697,12 @@ impl MeshFlags {
         if transmitted_receiver {
             mesh_flags |= MeshFlags::TRANSMITTED_SHADOW_RECEIVER;
         }
+        if material_overrides.base_color.is_some() {
+            mesh_flags |= MeshFlags::BASE_COLOR_OVERRIDE;
+        }
+        if material_overrides.emissive.is_some() {
+            mesh_flags |= MeshFlags::EMISSIVE_OVERRIDE;
+        }
         if transform.affine().matrix3.determinant().is_sign_positive() {
             mesh_flags |= MeshFlags::SIGN_DETERMINANT_MODEL_3X3;
         }
@@ -683,6 +738,9 @@ bitflags::bitflags! {
         /// The mesh had morph targets last frame and so they should be taken
         /// into account for motion vector computation.
         const HAS_PREVIOUS_MORPH      = 1 << 4;
+        /// The mesh is a [`StaticShadowCaster`], rendered in the cached shadow
+        /// maps of the lights with a [`CachedShadowMap`].
+        const STATIC_SHADOW_CASTER    = 1 << 5;
     }
 }
 
@@ -698,6 +756,8 @@ pub struct RenderMeshInstanceCpu {
     ///
     /// This will be written into the [`MeshUniform`] at the appropriate time.
     pub transforms: MeshTransforms,
+    /// The per-instance material overrides of the mesh.
+    pub material_overrides: MeshMaterialOverrides,
 }
 
 /// CPU data that the render world needs to keep for each entity that contains a
@@ -757,6 +817,8 @@ pub struct RenderMeshInstanceGpuBuilder {
     pub previous_input_index: Option<NonMaxU32>,
     /// Various flags.
     pub mesh_flags: MeshFlags,
+    /// The per-instance material overrides of the mesh.
+    pub material_overrides: MeshMaterialOverrides,
 }
 
 /// The per-thread queues used during [`extract_meshes_for_gpu_building`].
@@ -809,10 +871,15 @@ impl RenderMeshInstanceShared {
         previous_transform: Option<&PreviousGlobalTransform>,
         mesh: &Mesh3d,
         not_shadow_caster: bool,
+        static_shadow_caster: bool,
         no_automatic_batching: bool,
     ) -> Self {
         let mut mesh_instance_flags = RenderMeshInstanceFlags::empty();
         mesh_instance_flags.set(RenderMeshInstanceFlags::SHADOW_CASTER, !not_shadow_caster);
+        mesh_instance_flags.set(
+            RenderMeshInstanceFlags::STATIC_SHADOW_CASTER,
+            static_shadow_caster,
+        );
         mesh_instance_flags.set(
             RenderMeshInstanceFlags::AUTOMATIC_BATCHING,
             !no_automatic_batching,
@@ -1162,6 +1229,9 @@ impl RenderMeshInstanceGpuBuilder {
             ) | ((lightmap_slot as u32) << 16),
             pad_a: 0,
             pad_b: 0,
+            base_color_override: self.material_overrides.base_color_override(),
+            emissive_override: self.material_overrides.emissive_override(),
+            custom_material_data: self.material_overrides.custom,
         };
 
         // Did the last frame contain this entity as well?
@@ -1300,8 +1370,10 @@ pub fn extract_meshes_for_cpu_building(
             Has<NotShadowReceiver>,
             Has<TransmittedShadowReceiver>,
             Has<NotShadowCaster>,
+            Has<StaticShadowCaster>,
             Has<NoAutomaticBatching>,
             Has<VisibilityRange>,
+            Option<&MeshMaterialOverrides>,
         )>,
     >,
 ) {
@@ -1318,12 +1390,15 @@ pub fn extract_meshes_for_cpu_building(
             not_shadow_receiver,
             transmitted_receiver,
             not_shadow_caster,
+            static_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            material_overrides,
         )| {
             if !view_visibility.get() {
                 return;
             }
+            let material_overrides = material_overrides.copied().unwrap_or_default();
 
             let mut lod_index = None;
             if visibility_range {
@@ -1336,12 +1411,14 @@ pub fn extract_meshes_for_cpu_building(
                 no_frustum_culling,
                 not_shadow_receiver,
                 transmitted_receiver,
+                &material_overrides,
             );
 
             let shared = RenderMeshInstanceShared::from_components(
                 previous_transform,
                 mesh,
                 not_shadow_caster,
+                static_shadow_caster,
                 no_automatic_batching,
             );
 
@@ -1357,6 +1434,7 @@ pub fn extract_meshes_for_cpu_building(
                             .into(),
                         flags: mesh_flags.bits(),
                     },
+                    material_overrides,
                     shared,
                 },
             ));
@@ -1406,8 +1484,10 @@ pub fn extract_meshes_for_gpu_building(
                 Has<NotShadowReceiver>,
                 Has<TransmittedShadowReceiver>,
                 Has<NotShadowCaster>,
+                Has<StaticShadowCaster>,
                 Has<NoAutomaticBatching>,
                 Has<VisibilityRange>,
+                Option<&MeshMaterialOverrides>,
             ),
             Or<(
                 Changed<ViewVisibility>,
@@ -1420,6 +1500,7 @@ pub fn extract_meshes_for_gpu_building(
                 Changed<NotShadowReceiver>,
                 Changed<TransmittedShadowReceiver>,
                 Changed<NotShadowCaster>,
+                Changed<StaticShadowCaster>,
                 Changed<NoAutomaticBatching>,
                 Changed<VisibilityRange>,
             )>,
@@ -1463,13 +1544,16 @@ pub fn extract_meshes_for_gpu_building(
             not_shadow_receiver,
             transmitted_receiver,
             not_shadow_caster,
+            static_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            material_overrides,
         )| {
             if !view_visibility.get() {
                 queue.remove(entity.into(), any_gpu_culling);
                 return;
             }
+            let material_overrides = material_overrides.copied().unwrap_or_default();
 
             let mut lod_index = None;
             if visibility_range {
@@ -1482,12 +1566,14 @@ pub fn extract_meshes_for_gpu_building(
                 no_frustum_culling,
                 not_shadow_receiver,
                 transmitted_receiver,
+                &material_overrides,
             );
 
             let shared = RenderMeshInstanceShared::from_components(
                 previous_transform,
                 mesh,
                 not_shadow_caster,
+                static_shadow_caster,
                 no_automatic_batching,
             );
 
@@ -1512,6 +1598,7 @@ pub fn extract_meshes_for_gpu_building(
                 lightmap_uv_rect,
                 mesh_flags,
                 previous_input_index,
+                material_overrides,
             };
 
             queue.push(
@@ -1538,6 +1625,27 @@ pub fn extract_meshes_for_gpu_building(
     }
 }
 
+/// Marks meshes whose [`MeshMaterialOverrides`] changed or were removed as
+/// changed.
+///
+/// The overrides are written into the [`MeshInputUniform`], so
+/// [`extract_meshes_for_gpu_building`] must re-extract these meshes, which it
+/// does for meshes whose [`Mesh3d`] changed.
+fn mark_meshes_as_changed_if_their_material_overrides_changed(
+    mut meshes_query: Query<&mut Mesh3d>,
+    changed_overrides_query: Query<Entity, Changed<MeshMaterialOverrides>>,
+    mut removed_overrides_query: RemovedComponents<MeshMaterialOverrides>,
+) {
+    for entity in changed_overrides_query
+        .iter()
+        .chain(removed_overrides_query.read())
+    {
+        if let Ok(mut mesh) = meshes_query.get_mut(entity) {
+            mesh.set_changed();
+        }
+    }
+}
+
 /// A system that sets the [`RenderMeshInstanceFlags`] for each mesh based on
 /// whether the previous frame had skins and/or morph targets.
 ///
@@ -1840,6 +1948,7 @@ impl GetBatchData for MeshPipeline {
                 maybe_lightmap.map(|lightmap| (lightmap.slot_index, lightmap.uv_rect)),
                 current_skin_index,
                 previous_skin_index,
+                &mesh_instance.material_overrides,
             ),
             mesh_instance.should_batch().then_some((
                 material_bind_group_index.group,
@@ -1907,6 +2016,7 @@ impl GetFullBatchData for MeshPipeline {
             maybe_lightmap.map(|lightmap| (lightmap.slot_index, lightmap.uv_rect)),
             current_skin_index,
             previous_skin_index,
+            &mesh_instance.material_overrides,
         ))
     }
 
@@ -1994,7 +2104,8 @@ bitflags::bitflags! {
         const HAS_PREVIOUS_MORPH                = 1 << 19;
         const OIT_ENABLED                       = 1 << 20;
         const DISTANCE_FOG                      = 1 << 21;
-        const LAST_FLAG                         = Self::DISTANCE_FOG.bits();
+        const DEBUG_VIEW_OVERDRAW               = 1 << 22;
+        const LAST_FLAG                         = Self::DEBUG_VIEW_OVERDRAW.bits();
 
         // Bitfields
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
@@ -2266,7 +2377,21 @@ impl SpecializedMeshPipeline for MeshPipeline {
         let (label, blend, depth_write_enabled);
         let pass = key.intersection(MeshPipelineKey::BLEND_RESERVED_BITS);
         let (mut is_opaque, mut alpha_to_coverage_enabled) = (false, false);
-        if key.contains(MeshPipelineKey::OIT_ENABLED) && pass == MeshPipelineKey::BLEND_ALPHA {
+        if key.contains(MeshPipelineKey::DEBUG_VIEW_OVERDRAW) {
+            label = "overdraw_debug_view_mesh_pipeline".into();
+            // Every fragment adds its color to the previous ones, so that the brightness of a
+            // pixel shows the number of fragments shaded for it
+            blend = Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent::OVER,
+            });
+            depth_write_enabled = false;
+        } else if key.contains(MeshPipelineKey::OIT_ENABLED) && pass == MeshPipelineKey::BLEND_ALPHA
+        {
             label = "oit_mesh_pipeline".into();
             // TODO tail blending would need alpha blending
             blend = None;
@@ -2520,7 +2645,12 @@ impl SpecializedMeshPipeline for MeshPipeline {
             depth_stencil: Some(DepthStencilState {
                 format: CORE_3D_DEPTH_FORMAT,
                 depth_write_enabled,
-                depth_compare: CompareFunction::GreaterEqual,
+                // The overdraw debug view counts the hidden fragments too
+                depth_compare: if key.contains(MeshPipelineKey::DEBUG_VIEW_OVERDRAW) {
+                    CompareFunction::Always
+                } else {
+                    CompareFunction::GreaterEqual
+                },
                 stencil: StencilState {
                     front: StencilFaceState::IGNORE,
                     back: StencilFaceState::IGNORE,
@@ -3066,11 +3196,51 @@ impl<P: PhaseItem> RenderCommand<P> for DrawMesh {
 
 #[cfg(test)]
 mod tests {
-    use super::MeshPipelineKey;
+    use super::{MeshFlags, MeshMaterialOverrides, MeshPipelineKey};
+    use bevy_color::{Color, LinearRgba};
+    use bevy_math::Vec4;
+    use bevy_transform::components::GlobalTransform;
+
     #[test]
     fn mesh_key_msaa_samples() {
         for i in [1, 2, 4, 8, 16, 32, 64, 128] {
             assert_eq!(MeshPipelineKey::from_msaa_samples(i).msaa_samples(), i);
         }
     }
+
+    #[test]
+    fn mesh_material_overrides() {
+        let flags = |material_overrides| {
+            MeshFlags::from_components(
+                &GlobalTransform::IDENTITY,
+                None,
+                false,
+                false,
+                false,
+                &material_overrides,
+            )
+        };
+
+        let none = flags(MeshMaterialOverrides::default());
+        assert!(!none.intersects(MeshFlags::BASE_COLOR_OVERRIDE | MeshFlags::EMISSIVE_OVERRIDE));
+
+        let material_overrides = MeshMaterialOverrides {
+            base_color: Some(Color::WHITE),
+            ..Default::default()
+        };
+        assert!(flags(material_overrides).contains(MeshFlags::BASE_COLOR_OVERRIDE));
+        assert!(!flags(material_overrides).contains(MeshFlags::EMISSIVE_OVERRIDE));
+        assert_eq!(material_overrides.base_color_override(), Vec4::ONE);
+        assert_eq!(material_overrides.emissive_override(), Vec4::ZERO);
+
+        let material_overrides = MeshMaterialOverrides {
+            emissive: Some(LinearRgba::rgb(2.0, 1.0, 0.0)),
+            ..Default::default()
+        };
+        assert!(flags(material_overrides).contains(MeshFlags::EMISSIVE_OVERRIDE));
+        assert_eq!(
+            material_overrides.emissive_override(),
+            Vec4::new(2.0, 1.0, 0.0, 1.0)
+        );
+    }
 }
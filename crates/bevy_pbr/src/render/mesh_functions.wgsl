@@ -7,7 +7,11 @@
         VISIBILITY_RANGE_UNIFORM_BUFFER_SIZE
     },
     mesh_bindings::mesh,
-    mesh_types::MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT,
+    mesh_types::{
+        MESH_FLAGS_BASE_COLOR_OVERRIDE_BIT,
+        MESH_FLAGS_EMISSIVE_OVERRIDE_BIT,
+        MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT,
+    },
     view_transformations::position_world_to_clip,
 }
 #import bevy_render::maths::{affine3_to_square, mat2x4_f32_to_mat3x3_unpack}
@@ -97,6 +101,33 @@ fn mesh_tangent_local_to_world(world_from_local: mat4x4<f32>, vertex_tangent: ve
     }
 }
 
+// Returns the base color override of the mesh instance if it has one, or the
+// base color of its material otherwise.
+fn override_base_color(instance_index: u32, base_color: vec4<f32>) -> vec4<f32> {
+    if ((mesh[instance_index].flags & MESH_FLAGS_BASE_COLOR_OVERRIDE_BIT) != 0u) {
+        return mesh[instance_index].base_color_override;
+    }
+    return base_color;
+}
+
+// Returns the emissive override of the mesh instance if it has one, or the
+// emissive color of its material otherwise.
+//
+// The alpha channel of the emissive color of `StandardMaterial` holds its
+// exposure weight, which is kept.
+fn override_emissive(instance_index: u32, emissive: vec4<f32>) -> vec4<f32> {
+    if ((mesh[instance_index].flags & MESH_FLAGS_EMISSIVE_OVERRIDE_BIT) != 0u) {
+        return vec4(mesh[instance_index].emissive_override.rgb, emissive.a);
+    }
+    return emissive;
+}
+
+// Returns one of the custom values of the `MeshMaterialOverrides` of the mesh
+// instance.
+fn get_custom_material_data(instance_index: u32, index: u32) -> vec4<f32> {
+    return mesh[instance_index].custom_material_data[index];
+}
+
 #endif  // MESHLET_MESH_MATERIAL_PASS
 
 // Returns an appropriate dither level for the current mesh instance.
@@ -345,4 +345,9 @@ fn main(@builtin(global_invocation_id) global_invocation_id: vec3<u32>) {
     output[mesh_output_index].previous_skin_index = current_input[input_index].previous_skin_index;
     output[mesh_output_index].material_and_lightmap_bind_group_slot =
         current_input[input_index].material_and_lightmap_bind_group_slot;
+    output[mesh_output_index].base_color_override =
+        current_input[input_index].base_color_override;
+    output[mesh_output_index].emissive_override = current_input[input_index].emissive_override;
+    output[mesh_output_index].custom_material_data =
+        current_input[input_index].custom_material_data;
 }
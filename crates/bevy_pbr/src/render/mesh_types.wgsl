@@ -22,6 +22,11 @@ struct Mesh {
     // Low 16 bits: index of the material inside the bind group data.
     // High 16 bits: index of the lightmap in the binding array.
     material_and_lightmap_bind_group_slot: u32,
+    // The `MeshMaterialOverrides` of the mesh. The base color and emissive
+    // overrides are only set if the corresponding mesh flags are set.
+    base_color_override: vec4<f32>,
+    emissive_override: vec4<f32>,
+    custom_material_data: array<vec4<f32>, 2>,
 };
 
 #ifdef SKINNED
@@ -38,6 +43,10 @@ struct MorphWeights {
 
 // [2^0, 2^16)
 const MESH_FLAGS_VISIBILITY_RANGE_INDEX_BITS: u32 = 65535u;
+// 2^26
+const MESH_FLAGS_BASE_COLOR_OVERRIDE_BIT: u32 = 67108864u;
+// 2^27
+const MESH_FLAGS_EMISSIVE_OVERRIDE_BIT: u32 = 134217728u;
 // 2^28
 const MESH_FLAGS_NO_FRUSTUM_CULLING_BIT: u32 = 268435456u;
 // 2^29
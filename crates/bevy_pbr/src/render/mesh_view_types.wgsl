@@ -13,14 +13,21 @@ struct ClusterableObject {
     spot_light_tan_angle: f32,
     soft_shadow_size: f32,
     shadow_map_near_z: f32,
-    texture_index: u32,
-    pad: f32,
+    shadow_slope_bias: f32,
+    // The fraction of the shadow map that the light renders to
+    shadow_map_scale: f32,
 };
 
 const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32                    = 1u;
 const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32                  = 2u;
 const POINT_LIGHT_FLAGS_VOLUMETRIC_BIT: u32                         = 4u;
 const POINT_LIGHT_FLAGS_AFFECTS_LIGHTMAPPED_MESH_DIFFUSE_BIT: u32   = 8u;
+const POINT_LIGHT_FLAGS_BLOCKER_SEARCH_SAMPLE_COUNT_MASK_BITS: u32  = 15u;
+const POINT_LIGHT_FLAGS_BLOCKER_SEARCH_SAMPLE_COUNT_SHIFT_BITS: u32 = 4u;
+const POINT_LIGHT_FLAGS_BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS: u32  = 65535u;
+const POINT_LIGHT_FLAGS_BLOCKER_SEARCH_RADIUS_SCALE_SHIFT_BITS: u32 = 16u;
+// This must match `SoftShadowBlockerSearch::MAX_RADIUS_SCALE` on the Rust side.
+const MAX_BLOCKER_SEARCH_RADIUS_SCALE: f32                          = 16.0;
 
 struct DirectionalCascade {
     clip_from_world: mat4x4<f32>,
@@ -37,6 +44,11 @@ struct DirectionalLight {
     soft_shadow_size: f32,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
+    shadow_slope_bias: f32,
+    // The fraction of the shadow map that each cascade renders to
+    shadow_map_scale: f32,
+    blocker_search_sample_count: u32,
+    blocker_search_radius_scale: f32,
     num_cascades: u32,
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
@@ -66,8 +78,11 @@ struct Lights {
     cluster_factors: vec4<f32>,
     n_directional_lights: u32,
     spot_light_shadowmap_offset: i32,
-    environment_map_smallest_specular_mip_level: u32,
-    environment_map_intensity: f32,
+    ambient_light_affects_lightmapped_meshes: u32,
+    // NOTE: the values of the modes are defined in bevy_pbr/src/render/debug_view.wgsl
+    debug_view_mode: u32,
+    // The maximum number of lights whose soft shadows are evaluated per fragment
+    max_soft_shadow_lights: u32,
 };
 
 struct Fog {
@@ -12,7 +12,10 @@
 }
 #else
 #import bevy_pbr::{
+    debug_view::apply_debug_view,
     forward_io::{VertexOutput, FragmentOutput},
+    mesh_bindings::mesh,
+    pbr_bindings,
     pbr_functions,
     pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
     pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT,
@@ -88,6 +91,24 @@ fn fragment(
     // apply in-shader post processing (fog, alpha-premultiply, and also tonemapping, debanding if the camera is non-hdr)
     // note this does not include fullscreen postprocessing effects like bloom.
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+
+    // replace the color by the debug view of the camera, if any
+#ifdef VERTEX_UVS_A
+#ifdef BINDLESS
+#ifdef MESHLET_MESH_MATERIAL_PASS
+    let slot = in.material_bind_group_slot;
+#else   // MESHLET_MESH_MATERIAL_PASS
+    let slot = mesh[in.instance_index].material_and_lightmap_bind_group_slot & 0xffffu;
+#endif  // MESHLET_MESH_MATERIAL_PASS
+    let base_color_texture_size =
+        vec2<f32>(textureDimensions(pbr_bindings::base_color_texture[slot]));
+#else   // BINDLESS
+    let base_color_texture_size = vec2<f32>(textureDimensions(pbr_bindings::base_color_texture));
+#endif  // BINDLESS
+    out.color = apply_debug_view(pbr_input, in.uv, true, base_color_texture_size, out.color);
+#else
+    out.color = apply_debug_view(pbr_input, vec2(0.0), false, vec2(1.0), out.color);
+#endif
 #endif
 
 #ifdef OIT_ENABLED
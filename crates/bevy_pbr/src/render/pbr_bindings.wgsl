@@ -91,3 +91,15 @@
 @group(2) @binding(30) var specular_tint_sampler: sampler;
 #endif  // BINDLESS
 #endif  // PBR_SPECULAR_TEXTURES_SUPPORTED
+
+#ifdef BINDLESS
+@group(2) @binding(31) var detail_albedo_texture: binding_array<texture_2d<f32>, 16>;
+@group(2) @binding(32) var detail_albedo_sampler: binding_array<sampler, 16>;
+@group(2) @binding(33) var detail_normal_map_texture: binding_array<texture_2d<f32>, 16>;
+@group(2) @binding(34) var detail_normal_map_sampler: binding_array<sampler, 16>;
+#else   // BINDLESS
+@group(2) @binding(31) var detail_albedo_texture: texture_2d<f32>;
+@group(2) @binding(32) var detail_albedo_sampler: sampler;
+@group(2) @binding(33) var detail_normal_map_texture: texture_2d<f32>;
+@group(2) @binding(34) var detail_normal_map_sampler: sampler;
+#endif  // BINDLESS
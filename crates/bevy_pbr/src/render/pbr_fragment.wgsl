@@ -4,6 +4,7 @@
     pbr_functions,
     pbr_functions::SampleBias,
     pbr_bindings,
+    mesh_functions,
     pbr_types,
     prepass_utils,
     lighting,
@@ -11,8 +12,21 @@
     mesh_view_bindings::view,
     parallax_mapping::parallaxed_uv,
     lightmap::lightmap,
+    triplanar_mapping,
 }
 
+// Triplanar mapping relies on the screen space derivatives of the world
+// position, which aren't available when shading meshlets.
+#ifdef STANDARD_MATERIAL_TRIPLANAR
+#ifndef MESHLET_MESH_MATERIAL_PASS
+#define TRIPLANAR_MAPPING
+#endif  // MESHLET_MESH_MATERIAL_PASS
+#endif  // STANDARD_MATERIAL_TRIPLANAR
+
+// The factor applied to detail albedo texels, the inverse of sRGB mid-gray
+// (0.5) in linear space.
+const DETAIL_ALBEDO_SCALE: f32 = 4.5947938;
+
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
 #import bevy_pbr::mesh_view_bindings::screen_space_ambient_occlusion_texture
 #import bevy_pbr::ssao_utils::ssao_multibounce
@@ -77,15 +91,19 @@ fn pbr_input_from_standard_material(
     let slot = mesh[in.instance_index].material_and_lightmap_bind_group_slot & 0xffffu;
 #endif  // MESHLET_MESH_MATERIAL_PASS
     let flags = pbr_bindings::material[slot].flags;
-    let base_color = pbr_bindings::material[slot].base_color;
+    var base_color = pbr_bindings::material[slot].base_color;
     let deferred_lighting_pass_id = pbr_bindings::material[slot].deferred_lighting_pass_id;
 #else   // BINDLESS
     let slot = mesh[in.instance_index].material_and_lightmap_bind_group_slot & 0xffffu;
     let flags = pbr_bindings::material.flags;
-    let base_color = pbr_bindings::material.base_color;
+    var base_color = pbr_bindings::material.base_color;
     let deferred_lighting_pass_id = pbr_bindings::material.deferred_lighting_pass_id;
 #endif
 
+#ifndef MESHLET_MESH_MATERIAL_PASS
+    base_color = mesh_functions::override_base_color(in.instance_index, base_color);
+#endif  // MESHLET_MESH_MATERIAL_PASS
+
     let double_sided = (flags & pbr_types::STANDARD_MATERIAL_FLAGS_DOUBLE_SIDED_BIT) != 0u;
 
     var pbr_input: pbr_types::PbrInput = pbr_input_from_vertex_output(in, is_front, double_sided);
@@ -125,6 +143,39 @@ fn pbr_input_from_standard_material(
     var uv_b = uv;
 #endif
 
+#ifdef TRIPLANAR_MAPPING
+#ifdef BINDLESS
+    let triplanar_blend_sharpness = pbr_bindings::material[slot].triplanar_blend_sharpness;
+#else   // BINDLESS
+    let triplanar_blend_sharpness = pbr_bindings::material.triplanar_blend_sharpness;
+#endif  // BINDLESS
+    let triplanar = triplanar_mapping::triplanar_coords(
+        in.world_position.xyz,
+        pbr_input.world_normal,
+        uv_transform,
+        triplanar_blend_sharpness,
+    );
+#endif  // TRIPLANAR_MAPPING
+
+#ifdef STANDARD_MATERIAL_DETAIL_TEXTURES
+#ifdef BINDLESS
+    let detail_uv_transform = pbr_bindings::material[slot].detail_uv_transform;
+#else   // BINDLESS
+    let detail_uv_transform = pbr_bindings::material.detail_uv_transform;
+#endif  // BINDLESS
+#ifdef TRIPLANAR_MAPPING
+    let detail_triplanar = triplanar_mapping::triplanar_coords(
+        in.world_position.xyz,
+        pbr_input.world_normal,
+        detail_uv_transform,
+        triplanar_blend_sharpness,
+    );
+#else   // TRIPLANAR_MAPPING
+    // TODO: Transforming UVs mean we need to apply derivative chain rule for meshlet mesh material pass
+    let detail_uv = (detail_uv_transform * vec3(in.uv, 1.0)).xy;
+#endif  // TRIPLANAR_MAPPING
+#endif  // STANDARD_MATERIAL_DETAIL_TEXTURES
+
 #ifdef VERTEX_TANGENTS
     if ((flags & pbr_types::STANDARD_MATERIAL_FLAGS_DEPTH_MAP_BIT) != 0u) {
         let V = pbr_input.V;
@@ -182,6 +233,19 @@ fn pbr_input_from_standard_material(
 
     if ((flags & pbr_types::STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u) {
         pbr_input.material.base_color *=
+#ifdef TRIPLANAR_MAPPING
+            triplanar_mapping::triplanar_sample(
+#ifdef BINDLESS
+                pbr_bindings::base_color_texture[slot],
+                pbr_bindings::base_color_sampler[slot],
+#else   // BINDLESS
+                pbr_bindings::base_color_texture,
+                pbr_bindings::base_color_sampler,
+#endif  // BINDLESS
+                triplanar,
+                bias.mip_bias,
+            );
+#else   // TRIPLANAR_MAPPING
 #ifdef MESHLET_MESH_MATERIAL_PASS
             textureSampleGrad(
 #else   // MESHLET_MESH_MATERIAL_PASS
@@ -206,6 +270,7 @@ fn pbr_input_from_standard_material(
                 bias.mip_bias,
 #endif  // MESHLET_MESH_MATERIAL_PASS
         );
+#endif  // TRIPLANAR_MAPPING
 
 #ifdef ALPHA_TO_COVERAGE
     // Sharpen alpha edges.
@@ -226,6 +291,51 @@ fn pbr_input_from_standard_material(
 #endif // ALPHA_TO_COVERAGE
 
     }
+
+#ifdef STANDARD_MATERIAL_DETAIL_ALBEDO
+    let detail_albedo =
+#ifdef TRIPLANAR_MAPPING
+        triplanar_mapping::triplanar_sample(
+#ifdef BINDLESS
+            pbr_bindings::detail_albedo_texture[slot],
+            pbr_bindings::detail_albedo_sampler[slot],
+#else   // BINDLESS
+            pbr_bindings::detail_albedo_texture,
+            pbr_bindings::detail_albedo_sampler,
+#endif  // BINDLESS
+            detail_triplanar,
+            bias.mip_bias,
+        ).rgb;
+#else   // TRIPLANAR_MAPPING
+#ifdef MESHLET_MESH_MATERIAL_PASS
+        textureSampleGrad(
+#else   // MESHLET_MESH_MATERIAL_PASS
+        textureSampleBias(
+#endif  // MESHLET_MESH_MATERIAL_PASS
+#ifdef BINDLESS
+            pbr_bindings::detail_albedo_texture[slot],
+            pbr_bindings::detail_albedo_sampler[slot],
+#else   // BINDLESS
+            pbr_bindings::detail_albedo_texture,
+            pbr_bindings::detail_albedo_sampler,
+#endif  // BINDLESS
+            detail_uv,
+#ifdef MESHLET_MESH_MATERIAL_PASS
+            bias.ddx_uv,
+            bias.ddy_uv,
+#else   // MESHLET_MESH_MATERIAL_PASS
+            bias.mip_bias,
+#endif  // MESHLET_MESH_MATERIAL_PASS
+        ).rgb;
+#endif  // TRIPLANAR_MAPPING
+    // Scale the detail color so that sRGB mid-gray leaves the base color
+    // unchanged.
+    pbr_input.material.base_color = vec4(
+        pbr_input.material.base_color.rgb * detail_albedo * DETAIL_ALBEDO_SCALE,
+        pbr_input.material.base_color.a,
+    );
+#endif  // STANDARD_MATERIAL_DETAIL_ALBEDO
+
 #endif // VERTEX_UVS
 
     pbr_input.material.flags = flags;
@@ -235,11 +345,13 @@ fn pbr_input_from_standard_material(
 #ifdef BINDLESS
         pbr_input.material.ior = pbr_bindings::material[slot].ior;
         pbr_input.material.attenuation_color = pbr_bindings::material[slot].attenuation_color;
+        pbr_input.material.diffuse_transmission_color = pbr_bindings::material[slot].diffuse_transmission_color;
         pbr_input.material.attenuation_distance = pbr_bindings::material[slot].attenuation_distance;
         pbr_input.material.alpha_cutoff = pbr_bindings::material[slot].alpha_cutoff;
 #else   // BINDLESS
         pbr_input.material.ior = pbr_bindings::material.ior;
         pbr_input.material.attenuation_color = pbr_bindings::material.attenuation_color;
+        pbr_input.material.diffuse_transmission_color = pbr_bindings::material.diffuse_transmission_color;
         pbr_input.material.attenuation_distance = pbr_bindings::material.attenuation_distance;
         pbr_input.material.alpha_cutoff = pbr_bindings::material.alpha_cutoff;
 #endif  // BINDLESS
@@ -325,10 +437,26 @@ fn pbr_input_from_standard_material(
 #else   // BINDLESS
         var emissive: vec4<f32> = pbr_bindings::material.emissive;
 #endif  // BINDLESS
+#ifndef MESHLET_MESH_MATERIAL_PASS
+        emissive = mesh_functions::override_emissive(in.instance_index, emissive);
+#endif  // MESHLET_MESH_MATERIAL_PASS
 
 #ifdef VERTEX_UVS
         if ((flags & pbr_types::STANDARD_MATERIAL_FLAGS_EMISSIVE_TEXTURE_BIT) != 0u) {
             emissive = vec4<f32>(emissive.rgb *
+#ifdef TRIPLANAR_MAPPING
+                triplanar_mapping::triplanar_sample(
+#ifdef BINDLESS
+                    pbr_bindings::emissive_texture[slot],
+                    pbr_bindings::emissive_sampler[slot],
+#else   // BINDLESS
+                    pbr_bindings::emissive_texture,
+                    pbr_bindings::emissive_sampler,
+#endif  // BINDLESS
+                    triplanar,
+                    bias.mip_bias,
+                ).rgb,
+#else   // TRIPLANAR_MAPPING
 #ifdef MESHLET_MESH_MATERIAL_PASS
                 textureSampleGrad(
 #else   // MESHLET_MESH_MATERIAL_PASS
@@ -353,6 +481,7 @@ fn pbr_input_from_standard_material(
                     bias.mip_bias,
 #endif  // MESHLET_MESH_MATERIAL_PASS
                 ).rgb,
+#endif  // TRIPLANAR_MAPPING
             emissive.a);
         }
 #endif
@@ -371,6 +500,19 @@ fn pbr_input_from_standard_material(
 #ifdef VERTEX_UVS
         if ((flags & pbr_types::STANDARD_MATERIAL_FLAGS_METALLIC_ROUGHNESS_TEXTURE_BIT) != 0u) {
             let metallic_roughness =
+#ifdef TRIPLANAR_MAPPING
+                triplanar_mapping::triplanar_sample(
+#ifdef BINDLESS
+                    pbr_bindings::metallic_roughness_texture[slot],
+                    pbr_bindings::metallic_roughness_sampler[slot],
+#else   // BINDLESS
+                    pbr_bindings::metallic_roughness_texture,
+                    pbr_bindings::metallic_roughness_sampler,
+#endif  // BINDLESS
+                    triplanar,
+                    bias.mip_bias,
+                );
+#else   // TRIPLANAR_MAPPING
 #ifdef MESHLET_MESH_MATERIAL_PASS
                 textureSampleGrad(
 #else   // MESHLET_MESH_MATERIAL_PASS
@@ -395,6 +537,7 @@ fn pbr_input_from_standard_material(
                     bias.mip_bias,
 #endif  // MESHLET_MESH_MATERIAL_PASS
                 );
+#endif  // TRIPLANAR_MAPPING
             // Sampling from GLTF standard channels for now
             metallic *= metallic_roughness.b;
             perceptual_roughness *= metallic_roughness.g;
@@ -612,6 +755,19 @@ fn pbr_input_from_standard_material(
 #ifdef VERTEX_UVS
         if ((flags & pbr_types::STANDARD_MATERIAL_FLAGS_OCCLUSION_TEXTURE_BIT) != 0u) {
             diffuse_occlusion *= 
+#ifdef TRIPLANAR_MAPPING
+                triplanar_mapping::triplanar_sample(
+#ifdef BINDLESS
+                    pbr_bindings::occlusion_texture[slot],
+                    pbr_bindings::occlusion_sampler[slot],
+#else   // BINDLESS
+                    pbr_bindings::occlusion_texture,
+                    pbr_bindings::occlusion_sampler,
+#endif  // BINDLESS
+                    triplanar,
+                    bias.mip_bias,
+                ).r;
+#else   // TRIPLANAR_MAPPING
 #ifdef MESHLET_MESH_MATERIAL_PASS
                 textureSampleGrad(
 #else   // MESHLET_MESH_MATERIAL_PASS
@@ -636,6 +792,7 @@ fn pbr_input_from_standard_material(
                     bias.mip_bias,
 #endif  // MESHLET_MESH_MATERIAL_PASS
                 ).r;
+#endif  // TRIPLANAR_MAPPING
         }
 #endif
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
@@ -656,10 +813,53 @@ fn pbr_input_from_standard_material(
         pbr_input.clearcoat_N = pbr_input.N;
 
 #ifdef VERTEX_UVS
+
+#ifdef TRIPLANAR_MAPPING
+
+        // Triplanar normal mapping projects the normal maps along the world
+        // axes, so it doesn't need tangents.
+#ifdef STANDARD_MATERIAL_NORMAL_MAP
+        pbr_input.N = triplanar_mapping::triplanar_normal(
+#ifdef BINDLESS
+            pbr_bindings::normal_map_texture[slot],
+            pbr_bindings::normal_map_sampler[slot],
+#else   // BINDLESS
+            pbr_bindings::normal_map_texture,
+            pbr_bindings::normal_map_sampler,
+#endif  // BINDLESS
+            triplanar,
+            bias.mip_bias,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_NORMAL_MAP) != 0u,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u,
+            pbr_input.N,
+        );
+#endif  // STANDARD_MATERIAL_NORMAL_MAP
+
+#ifdef STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+        pbr_input.N = triplanar_mapping::triplanar_normal(
+#ifdef BINDLESS
+            pbr_bindings::detail_normal_map_texture[slot],
+            pbr_bindings::detail_normal_map_sampler[slot],
+#else   // BINDLESS
+            pbr_bindings::detail_normal_map_texture,
+            pbr_bindings::detail_normal_map_sampler,
+#endif  // BINDLESS
+            detail_triplanar,
+            bias.mip_bias,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_DETAIL_NORMAL_MAP) != 0u,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u,
+            pbr_input.N,
+        );
+#endif  // STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+
+#endif  // TRIPLANAR_MAPPING
+
 #ifdef VERTEX_TANGENTS
 
         let TBN = pbr_functions::calculate_tbn_mikktspace(pbr_input.world_normal, in.world_tangent);
 
+#ifndef TRIPLANAR_MAPPING
+
 #ifdef STANDARD_MATERIAL_NORMAL_MAP
 
         let Nt =
@@ -692,6 +892,44 @@ fn pbr_input_from_standard_material(
 
 #endif  // STANDARD_MATERIAL_NORMAL_MAP
 
+#ifdef STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+
+        let detail_Nt =
+#ifdef MESHLET_MESH_MATERIAL_PASS
+            textureSampleGrad(
+#else   // MESHLET_MESH_MATERIAL_PASS
+            textureSampleBias(
+#endif  // MESHLET_MESH_MATERIAL_PASS
+#ifdef BINDLESS
+                pbr_bindings::detail_normal_map_texture[slot],
+                pbr_bindings::detail_normal_map_sampler[slot],
+#else   // BINDLESS
+                pbr_bindings::detail_normal_map_texture,
+                pbr_bindings::detail_normal_map_sampler,
+#endif  // BINDLESS
+                detail_uv,
+#ifdef MESHLET_MESH_MATERIAL_PASS
+                bias.ddx_uv,
+                bias.ddy_uv,
+#else   // MESHLET_MESH_MATERIAL_PASS
+                bias.mip_bias,
+#endif  // MESHLET_MESH_MATERIAL_PASS
+            ).rgb;
+
+        pbr_input.N = pbr_functions::apply_detail_normal_mapping(
+            TBN,
+            pbr_input.N,
+            pbr_functions::unpack_normal_map(
+                (flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_DETAIL_NORMAL_MAP) != 0u,
+                (flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u,
+                detail_Nt,
+            ),
+        );
+
+#endif  // STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+
+#endif  // TRIPLANAR_MAPPING
+
 #ifdef STANDARD_MATERIAL_CLEARCOAT
 
         // Note: `KHR_materials_clearcoat` specifies that, if there's no
@@ -741,6 +979,13 @@ fn pbr_input_from_standard_material(
 #endif  // VERTEX_TANGENTS
 #endif  // VERTEX_UVS
 
+#ifdef STANDARD_MATERIAL_THIN_TRANSLUCENT
+        // The geometric normal isn't flipped for back faces when normal mapping is possible, but
+        // thin translucent surfaces need it to face the viewer to bias shadows and transmit light
+        // from the correct side.
+        pbr_input.world_normal = select(-in.world_normal, in.world_normal, is_front);
+#endif  // STANDARD_MATERIAL_THIN_TRANSLUCENT
+
         // Take anisotropy into account.
         //
         // This code comes from the `KHR_materials_anisotropy` spec:
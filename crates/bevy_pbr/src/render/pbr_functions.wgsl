@@ -167,6 +167,23 @@ fn calculate_tbn_mikktspace(world_normal: vec3<f32>, world_tangent: vec4<f32>) -
     return mat3x3(T, B, N);
 }
 
+// Unpacks the tangent-space normal `in_Nt` sampled from a normal map.
+fn unpack_normal_map(two_component: bool, flip_y: bool, in_Nt: vec3<f32>) -> vec3<f32> {
+    var Nt: vec3<f32>;
+    if two_component {
+        // Only use the xy components and derive z for 2-component normal maps.
+        Nt = vec3<f32>(in_Nt.rg * 2.0 - 1.0, 0.0);
+        Nt.z = sqrt(1.0 - Nt.x * Nt.x - Nt.y * Nt.y);
+    } else {
+        Nt = in_Nt * 2.0 - 1.0;
+    }
+    // Normal maps authored for DirectX require flipping the y component
+    if flip_y {
+        Nt.y = -Nt.y;
+    }
+    return Nt;
+}
+
 fn apply_normal_mapping(
     standard_material_flags: u32,
     TBN: mat3x3<f32>,
@@ -180,18 +197,11 @@ fn apply_normal_mapping(
     var N = TBN[2];
 
     // Nt is the tangent-space normal.
-    var Nt = in_Nt;
-    if (standard_material_flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_NORMAL_MAP) != 0u {
-        // Only use the xy components and derive z for 2-component normal maps.
-        Nt = vec3<f32>(Nt.rg * 2.0 - 1.0, 0.0);
-        Nt.z = sqrt(1.0 - Nt.x * Nt.x - Nt.y * Nt.y);
-    } else {
-        Nt = Nt * 2.0 - 1.0;
-    }
-    // Normal maps authored for DirectX require flipping the y component
-    if (standard_material_flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u {
-        Nt.y = -Nt.y;
-    }
+    var Nt = unpack_normal_map(
+        (standard_material_flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_NORMAL_MAP) != 0u,
+        (standard_material_flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u,
+        in_Nt,
+    );
 
     if double_sided && !is_front {
         Nt = -Nt;
@@ -207,6 +217,17 @@ fn apply_normal_mapping(
     return normalize(N);
 }
 
+// Perturbs the normal `N`, which may already be normal mapped, with the
+// tangent-space normal `Nt` unpacked from a detail normal map.
+//
+// The tangent frame of `TBN` is rebuilt around `N` so that the details follow
+// the shape of the surface described by the main normal map.
+fn apply_detail_normal_mapping(TBN: mat3x3<f32>, N: vec3<f32>, Nt: vec3<f32>) -> vec3<f32> {
+    let T = normalize(TBN[0] - N * dot(N, TBN[0]));
+    let B = normalize(TBN[1] - N * dot(N, TBN[1]) - T * dot(T, TBN[1]));
+    return normalize(Nt.x * T + Nt.y * B + Nt.z * N);
+}
+
 #ifdef STANDARD_MATERIAL_ANISOTROPY
 
 // Modifies the normal to achieve a better approximate direction from the
@@ -322,14 +343,21 @@ fn apply_pbr_lighting(
         diffuse_transmission
     );
 
-    // Diffuse transmissive strength is inversely related to metallicity and specular transmission, but directly related to diffuse transmission
-    let diffuse_transmissive_color = output_color.rgb * (1.0 - metallic) * (1.0 - specular_transmission) * diffuse_transmission;
+    let F0 = calculate_F0(output_color.rgb, metallic, reflectance);
+    let F_ab = lighting::F_AB(perceptual_roughness, NdotV);
 
+    // Diffuse transmissive strength is inversely related to metallicity and specular transmission, but directly related to diffuse transmission
+    var diffuse_transmissive_color = output_color.rgb * in.material.diffuse_transmission_color.rgb * (1.0 - metallic) * (1.0 - specular_transmission) * diffuse_transmission;
+
+#ifdef STANDARD_MATERIAL_THIN_TRANSLUCENT
+    // Thin surfaces have no volume to scatter light in: the light is transmitted at the surface itself,
+    // minus the part reflected specularly, so that reflection and transmission never exceed the incoming light
+    diffuse_transmissive_color *= 1.0 - (F0 * F_ab.x + F_ab.y);
+    let diffuse_transmissive_lobe_world_position = in.world_position;
+#else   // STANDARD_MATERIAL_THIN_TRANSLUCENT
     // Calculate the world position of the second Lambertian lobe used for diffuse transmission, by subtracting material thickness
     let diffuse_transmissive_lobe_world_position = in.world_position - vec4<f32>(in.world_normal, 0.0) * thickness;
-
-    let F0 = calculate_F0(output_color.rgb, metallic, reflectance);
-    let F_ab = lighting::F_AB(perceptual_roughness, NdotV);
+#endif  // STANDARD_MATERIAL_THIN_TRANSLUCENT
 
     var direct_light: vec3<f32> = vec3<f32>(0.0);
 
@@ -8,8 +8,17 @@
     prepass_io,
     mesh_bindings::mesh,
     mesh_view_bindings::view,
+    triplanar_mapping,
 }
 
+// Triplanar mapping relies on the screen space derivatives of the world
+// position, which aren't available when shading meshlets.
+#ifdef STANDARD_MATERIAL_TRIPLANAR
+#ifndef MESHLET_MESH_MATERIAL_PASS
+#define TRIPLANAR_MAPPING
+#endif  // MESHLET_MESH_MATERIAL_PASS
+#endif  // STANDARD_MATERIAL_TRIPLANAR
+
 #ifdef MESHLET_MESH_MATERIAL_PASS
 #import bevy_pbr::meshlet_visibility_buffer_resolve::resolve_vertex_output
 #endif
@@ -67,15 +76,6 @@ fn fragment(
         var normal = world_normal;
 
 #ifdef VERTEX_UVS
-#ifdef VERTEX_TANGENTS
-#ifdef STANDARD_MATERIAL_NORMAL_MAP
-
-// TODO: Transforming UVs mean we need to apply derivative chain rule for meshlet mesh material pass
-#ifdef STANDARD_MATERIAL_NORMAL_MAP_UV_B
-        let uv = (uv_transform * vec3(in.uv_b, 1.0)).xy;
-#else
-        let uv = (uv_transform * vec3(in.uv, 1.0)).xy;
-#endif
 
         // Fill in the sample bias so we can sample from textures.
         var bias: SampleBias;
@@ -86,6 +86,80 @@ fn fragment(
         bias.mip_bias = view.mip_bias;
 #endif  // MESHLET_MESH_MATERIAL_PASS
 
+#ifdef STANDARD_MATERIAL_DETAIL_TEXTURES
+#ifdef BINDLESS
+        let detail_uv_transform = pbr_bindings::material[slot].detail_uv_transform;
+#else   // BINDLESS
+        let detail_uv_transform = pbr_bindings::material.detail_uv_transform;
+#endif  // BINDLESS
+#endif  // STANDARD_MATERIAL_DETAIL_TEXTURES
+
+#ifdef TRIPLANAR_MAPPING
+
+#ifdef BINDLESS
+        let triplanar_blend_sharpness = pbr_bindings::material[slot].triplanar_blend_sharpness;
+#else   // BINDLESS
+        let triplanar_blend_sharpness = pbr_bindings::material.triplanar_blend_sharpness;
+#endif  // BINDLESS
+
+#ifdef STANDARD_MATERIAL_NORMAL_MAP
+        normal = triplanar_mapping::triplanar_normal(
+#ifdef BINDLESS
+            pbr_bindings::normal_map_texture[slot],
+            pbr_bindings::normal_map_sampler[slot],
+#else   // BINDLESS
+            pbr_bindings::normal_map_texture,
+            pbr_bindings::normal_map_sampler,
+#endif  // BINDLESS
+            triplanar_mapping::triplanar_coords(
+                in.world_position.xyz,
+                world_normal,
+                uv_transform,
+                triplanar_blend_sharpness,
+            ),
+            bias.mip_bias,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_NORMAL_MAP) != 0u,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u,
+            normal,
+        );
+#endif  // STANDARD_MATERIAL_NORMAL_MAP
+
+#ifdef STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+        normal = triplanar_mapping::triplanar_normal(
+#ifdef BINDLESS
+            pbr_bindings::detail_normal_map_texture[slot],
+            pbr_bindings::detail_normal_map_sampler[slot],
+#else   // BINDLESS
+            pbr_bindings::detail_normal_map_texture,
+            pbr_bindings::detail_normal_map_sampler,
+#endif  // BINDLESS
+            triplanar_mapping::triplanar_coords(
+                in.world_position.xyz,
+                world_normal,
+                detail_uv_transform,
+                triplanar_blend_sharpness,
+            ),
+            bias.mip_bias,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_DETAIL_NORMAL_MAP) != 0u,
+            (flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u,
+            normal,
+        );
+#endif  // STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+
+#else   // TRIPLANAR_MAPPING
+#ifdef VERTEX_TANGENTS
+
+        let TBN = pbr_functions::calculate_tbn_mikktspace(normal, in.world_tangent);
+
+#ifdef STANDARD_MATERIAL_NORMAL_MAP
+
+// TODO: Transforming UVs mean we need to apply derivative chain rule for meshlet mesh material pass
+#ifdef STANDARD_MATERIAL_NORMAL_MAP_UV_B
+        let uv = (uv_transform * vec3(in.uv_b, 1.0)).xy;
+#else
+        let uv = (uv_transform * vec3(in.uv, 1.0)).xy;
+#endif
+
         let Nt =
 #ifdef MESHLET_MESH_MATERIAL_PASS
             textureSampleGrad(
@@ -107,7 +181,6 @@ fn fragment(
                 bias.mip_bias,
 #endif  // MESHLET_MESH_MATERIAL_PASS
             ).rgb;
-        let TBN = pbr_functions::calculate_tbn_mikktspace(normal, in.world_tangent);
 
         normal = pbr_functions::apply_normal_mapping(
             flags,
@@ -118,7 +191,47 @@ fn fragment(
         );
 
 #endif  // STANDARD_MATERIAL_NORMAL_MAP
+
+#ifdef STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+
+        // TODO: Transforming UVs mean we need to apply derivative chain rule for meshlet mesh material pass
+        let detail_uv = (detail_uv_transform * vec3(in.uv, 1.0)).xy;
+        let detail_Nt =
+#ifdef MESHLET_MESH_MATERIAL_PASS
+            textureSampleGrad(
+#else   // MESHLET_MESH_MATERIAL_PASS
+            textureSampleBias(
+#endif  // MESHLET_MESH_MATERIAL_PASS
+#ifdef BINDLESS
+                pbr_bindings::detail_normal_map_texture[slot],
+                pbr_bindings::detail_normal_map_sampler[slot],
+#else   // BINDLESS
+                pbr_bindings::detail_normal_map_texture,
+                pbr_bindings::detail_normal_map_sampler,
+#endif  // BINDLESS
+                detail_uv,
+#ifdef MESHLET_MESH_MATERIAL_PASS
+                bias.ddx_uv,
+                bias.ddy_uv,
+#else   // MESHLET_MESH_MATERIAL_PASS
+                bias.mip_bias,
+#endif  // MESHLET_MESH_MATERIAL_PASS
+            ).rgb;
+
+        normal = pbr_functions::apply_detail_normal_mapping(
+            TBN,
+            normal,
+            pbr_functions::unpack_normal_map(
+                (flags & pbr_types::STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_DETAIL_NORMAL_MAP) != 0u,
+                (flags & pbr_types::STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y) != 0u,
+                detail_Nt,
+            ),
+        );
+
+#endif  // STANDARD_MATERIAL_DETAIL_NORMAL_MAP
+
 #endif  // VERTEX_TANGENTS
+#endif  // TRIPLANAR_MAPPING
 #endif  // VERTEX_UVS
 
         out.normal = vec4(normal * 0.5 + vec3(0.5), 1.0);
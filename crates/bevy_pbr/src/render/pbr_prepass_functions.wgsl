@@ -4,6 +4,7 @@
     prepass_io::VertexOutput,
     prepass_bindings::previous_view_uniforms,
     mesh_bindings::mesh,
+    mesh_functions,
     mesh_view_bindings::view,
     pbr_bindings,
     pbr_types,
@@ -22,6 +23,9 @@ fn prepass_alpha_discard(in: VertexOutput) {
 #else   // BINDLESS
     var output_color: vec4<f32> = pbr_bindings::material.base_color;
 #endif  // BINDLESS
+#ifndef MESHLET_MESH_MATERIAL_PASS
+    output_color = mesh_functions::override_base_color(in.instance_index, output_color);
+#endif  // MESHLET_MESH_MATERIAL_PASS
 
 #ifdef VERTEX_UVS
 #ifdef STANDARD_MATERIAL_BASE_COLOR_UV_B
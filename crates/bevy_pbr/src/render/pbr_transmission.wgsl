@@ -57,11 +57,19 @@ fn specular_transmissive_light(world_position: vec4<f32>, frag_coord: vec3<f32>,
     return F * specular_transmissive_color * mix(transmitted_environment_light_specular, background_color.rgb, background_color.a);
 }
 
+// Converts a position within the viewport, from (0, 0) to (1, 1), to UVs in the view transmission
+// texture, which the main passes only cover part of when they're rendered at a lower resolution.
+fn transmission_texture_uv(offset_position: vec2<f32>) -> vec2<f32> {
+    let texture_size = vec2<f32>(textureDimensions(view_bindings::view_transmission_texture));
+    let viewport = view_bindings::view.viewport;
+    return (viewport.xy + offset_position * viewport.zw) / texture_size;
+}
+
 fn fetch_transmissive_background_non_rough(offset_position: vec2<f32>, frag_coord: vec3<f32>) -> vec4<f32> {
     var background_color = textureSampleLevel(
         view_bindings::view_transmission_texture,
         view_bindings::view_transmission_sampler,
-        offset_position,
+        transmission_texture_uv(offset_position),
         0.0
     );
 
@@ -161,7 +169,7 @@ fn fetch_transmissive_background(offset_position: vec2<f32>, frag_coord: vec3<f3
         var sample = textureSampleLevel(
             view_bindings::view_transmission_texture,
             view_bindings::view_transmission_sampler,
-            modified_offset_position,
+            transmission_texture_uv(modified_offset_position),
             0.0
         );
 
@@ -217,35 +217,28 @@ fn sample_shadow_map_jimenez_fourteen(
 // to the search size, to provide a sample pattern in a similar manner to the
 // cubemap sampling approach we use for PCF.
 //
-// `search_size` is the size of the search region in texels.
+// `search_size` is the size of the search region in texels, and `sample_count`
+// is the number of sample points used, up to 8.
 fn search_for_blockers_in_shadow_map(
     light_local: vec2<f32>,
     depth: f32,
     array_index: i32,
     texel_size: f32,
     search_size: f32,
+    sample_count: u32,
 ) -> f32 {
     let shadow_map_size = vec2<f32>(textureDimensions(view_bindings::directional_shadow_textures));
     let uv_offset_scale = search_size / (texel_size * shadow_map_size);
 
-    let offset0 = D3D_SAMPLE_POINT_POSITIONS[0] * uv_offset_scale;
-    let offset1 = D3D_SAMPLE_POINT_POSITIONS[1] * uv_offset_scale;
-    let offset2 = D3D_SAMPLE_POINT_POSITIONS[2] * uv_offset_scale;
-    let offset3 = D3D_SAMPLE_POINT_POSITIONS[3] * uv_offset_scale;
-    let offset4 = D3D_SAMPLE_POINT_POSITIONS[4] * uv_offset_scale;
-    let offset5 = D3D_SAMPLE_POINT_POSITIONS[5] * uv_offset_scale;
-    let offset6 = D3D_SAMPLE_POINT_POSITIONS[6] * uv_offset_scale;
-    let offset7 = D3D_SAMPLE_POINT_POSITIONS[7] * uv_offset_scale;
+    // Copy the sample positions to a variable so that they can be indexed
+    // dynamically.
+    var sample_positions = D3D_SAMPLE_POINT_POSITIONS;
 
     var sum = vec2(0.0);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset0, depth, array_index);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset1, depth, array_index);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset2, depth, array_index);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset3, depth, array_index);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset4, depth, array_index);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset5, depth, array_index);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset6, depth, array_index);
-    sum += search_for_blockers_in_shadow_map_hardware(light_local + offset7, depth, array_index);
+    for (var i = 0u; i < min(sample_count, 8u); i += 1u) {
+        let offset = sample_positions[i] * uv_offset_scale;
+        sum += search_for_blockers_in_shadow_map_hardware(light_local + offset, depth, array_index);
+    }
 
     if (sum.y == 0.0) {
         return 0.0;
@@ -281,16 +274,27 @@ fn sample_shadow_map(light_local: vec2<f32>, depth: f32, array_index: i32, texel
 //
 // A good overview of the technique:
 // <https://medium.com/@varunm100/soft-shadows-for-mobile-ar-9e8da2e6f4ba>
+//
+// `blocker_search_sample_count` and `blocker_search_radius_scale` control the
+// blocker search, see `SoftShadowBlockerSearch`.
 fn sample_shadow_map_pcss(
     light_local: vec2<f32>,
     depth: f32,
     array_index: i32,
     texel_size: f32,
     light_size: f32,
+    blocker_search_sample_count: u32,
+    blocker_search_radius_scale: f32,
 ) -> f32 {
     // Determine the average Z value of the closest blocker.
     let z_blocker = search_for_blockers_in_shadow_map(
-        light_local, depth, array_index, texel_size, light_size);
+        light_local,
+        depth,
+        array_index,
+        texel_size,
+        light_size * blocker_search_radius_scale,
+        blocker_search_sample_count,
+    );
 
     // Don't let the blur size go below 0.5, or shadows will look unacceptably aliased.
     let blur_size = max((z_blocker - depth) * light_size / depth, 0.5);
@@ -309,24 +313,61 @@ fn sample_shadow_map_pcss(
 #endif  // SHADOW_FILTER_METHOD_TEMPORAL
 }
 
+// Remaps a direction in the shadow cubemap of a point light to the top left
+// `scale` by `scale` corner of the cube face that it points to, which is where
+// lights with a lower shadow map resolution render their shadows.
+fn shadow_cubemap_viewport_direction(light_local: vec3<f32>, scale: f32) -> vec3<f32> {
+    if (scale >= 1.0) {
+        return light_local;
+    }
+
+    // The texture coordinates of each face are the minor axes of the direction
+    // divided by its major axis, with signs that depend on the face. Shrink
+    // them toward the top left corner of the face, where they're -1.
+    let abs_light_local = abs(light_local);
+    let major_axis_magnitude = max(abs_light_local.x, max(abs_light_local.y, abs_light_local.z));
+    let offset = (1.0 - scale) * major_axis_magnitude;
+    if (abs_light_local.x >= abs_light_local.y && abs_light_local.x >= abs_light_local.z) {
+        return vec3(
+            light_local.x,
+            light_local.y * scale + offset,
+            light_local.z * scale + sign(light_local.x) * offset,
+        );
+    }
+    if (abs_light_local.y >= abs_light_local.z) {
+        return vec3(
+            light_local.x * scale - offset,
+            light_local.y,
+            light_local.z * scale - sign(light_local.y) * offset,
+        );
+    }
+    return vec3(
+        light_local.x * scale - sign(light_local.z) * offset,
+        light_local.y * scale + offset,
+        light_local.z,
+    );
+}
+
 // NOTE: Due to the non-uniform control flow in `shadows::fetch_point_shadow`,
 // we must use the Level variant of textureSampleCompare to avoid undefined
 // behavior due to some of the fragments in a quad (2x2 fragments) being
 // processed not being sampled, and this messing with mip-mapping functionality.
 // The shadow maps have no mipmaps so Level just samples from LOD 0.
 fn sample_shadow_cubemap_hardware(light_local: vec3<f32>, depth: f32, light_id: u32) -> f32 {
+    let direction = shadow_cubemap_viewport_direction(
+        light_local, view_bindings::clusterable_objects.data[light_id].shadow_map_scale);
 #ifdef NO_CUBE_ARRAY_TEXTURES_SUPPORT
     return textureSampleCompare(
         view_bindings::point_shadow_textures,
         view_bindings::point_shadow_textures_comparison_sampler,
-        light_local,
+        direction,
         depth
     );
 #else
     return textureSampleCompareLevel(
         view_bindings::point_shadow_textures,
         view_bindings::point_shadow_textures_comparison_sampler,
-        light_local,
+        direction,
         i32(light_id),
         depth
     );
@@ -348,17 +389,19 @@ fn search_for_blockers_in_shadow_cubemap_hardware(
 
 #ifdef PCSS_SAMPLERS_AVAILABLE
 
+    let direction = shadow_cubemap_viewport_direction(
+        light_local, view_bindings::clusterable_objects.data[light_id].shadow_map_scale);
 #ifdef NO_CUBE_ARRAY_TEXTURES_SUPPORT
     let sampled_depth = textureSample(
         view_bindings::point_shadow_textures,
         view_bindings::point_shadow_textures_linear_sampler,
-        light_local,
+        direction,
     );
 #else
     let sampled_depth = textureSample(
         view_bindings::point_shadow_textures,
         view_bindings::point_shadow_textures_linear_sampler,
-        light_local,
+        direction,
         i32(light_id),
     );
 #endif
@@ -543,13 +586,15 @@ fn sample_shadow_cubemap(
 // This follows the logic in `sample_shadow_cubemap_gaussian`, but uses linear
 // sampling instead of percentage-closer filtering.
 //
-// The `scale` parameter represents the size of the light.
+// The `scale` parameter represents the size of the search region, and
+// `sample_count` is the number of sample points used, up to 8.
 fn search_for_blockers_in_shadow_cubemap(
     light_local: vec3<f32>,
     depth: f32,
     scale: f32,
     distance_to_light: f32,
     light_id: u32,
+    sample_count: u32,
 ) -> f32 {
     // Create an orthonormal basis so we can apply a 2D sampling pattern to a
     // cubemap.
@@ -559,23 +604,15 @@ fn search_for_blockers_in_shadow_cubemap(
     }
     let basis = orthonormalize(light_local, up) * scale * distance_to_light;
 
+    // Copy the sample positions to a variable so that they can be indexed
+    // dynamically.
+    var sample_positions = D3D_SAMPLE_POINT_POSITIONS;
+
     var sum: vec2<f32> = vec2(0.0);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[0], basis[0], basis[1], light_local, depth, light_id);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[1], basis[0], basis[1], light_local, depth, light_id);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[2], basis[0], basis[1], light_local, depth, light_id);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[3], basis[0], basis[1], light_local, depth, light_id);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[4], basis[0], basis[1], light_local, depth, light_id);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[5], basis[0], basis[1], light_local, depth, light_id);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[6], basis[0], basis[1], light_local, depth, light_id);
-    sum += search_for_blockers_in_shadow_cubemap_at_offset(
-        D3D_SAMPLE_POINT_POSITIONS[7], basis[0], basis[1], light_local, depth, light_id);
+    for (var i = 0u; i < min(sample_count, 8u); i += 1u) {
+        sum += search_for_blockers_in_shadow_cubemap_at_offset(
+            sample_positions[i], basis[0], basis[1], light_local, depth, light_id);
+    }
 
     if (sum.y == 0.0) {
         return 0.0;
@@ -588,15 +625,26 @@ fn search_for_blockers_in_shadow_cubemap(
 //
 // A good overview of the technique:
 // <https://medium.com/@varunm100/soft-shadows-for-mobile-ar-9e8da2e6f4ba>
+//
+// `blocker_search_sample_count` and `blocker_search_radius_scale` control the
+// blocker search, see `SoftShadowBlockerSearch`.
 fn sample_shadow_cubemap_pcss(
     light_local: vec3<f32>,
     distance_to_light: f32,
     depth: f32,
     light_id: u32,
     light_size: f32,
+    blocker_search_sample_count: u32,
+    blocker_search_radius_scale: f32,
 ) -> f32 {
     let z_blocker = search_for_blockers_in_shadow_cubemap(
-        light_local, depth, light_size, distance_to_light, light_id);
+        light_local,
+        depth,
+        light_size * blocker_search_radius_scale,
+        distance_to_light,
+        light_id,
+        blocker_search_sample_count,
+    );
 
     // Don't let the blur size go below 0.5, or shadows will look unacceptably aliased.
     let blur_size = max((z_blocker - depth) * light_size / depth, 0.5);
@@ -1,7 +1,14 @@
 #define_import_path bevy_pbr::shadows
 
 #import bevy_pbr::{
-    mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    mesh_view_types::{
+        MAX_BLOCKER_SEARCH_RADIUS_SCALE,
+        POINT_LIGHT_FLAGS_BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS,
+        POINT_LIGHT_FLAGS_BLOCKER_SEARCH_RADIUS_SCALE_SHIFT_BITS,
+        POINT_LIGHT_FLAGS_BLOCKER_SEARCH_SAMPLE_COUNT_MASK_BITS,
+        POINT_LIGHT_FLAGS_BLOCKER_SEARCH_SAMPLE_COUNT_SHIFT_BITS,
+        POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    },
     mesh_view_bindings as view_bindings,
     shadow_sampling::{
         SPOT_SHADOW_TEXEL_SIZE, sample_shadow_cubemap, sample_shadow_cubemap_pcss,
@@ -16,6 +23,49 @@
 
 const flip_z: vec3<f32> = vec3<f32>(1.0, 1.0, -1.0);
 
+// Clamps the tangent used by the slope bias, which is infinite for surfaces
+// parallel to the direction to the light.
+const MAX_SHADOW_SLOPE: f32 = 10.0;
+
+// The number of lights whose soft shadows this fragment has evaluated so far.
+var<private> soft_shadow_light_count: u32 = 0u;
+
+// Returns true if this fragment can evaluate the soft shadows of one more
+// light, and counts that light. Otherwise, the light's shadows should be
+// filtered like hard shadows.
+//
+// This caps the cost of soft shadows in clusters with many lights, see
+// `SoftShadowLimits`.
+fn claim_soft_shadow_evaluation() -> bool {
+    if (soft_shadow_light_count >= view_bindings::lights.max_soft_shadow_lights) {
+        return false;
+    }
+    soft_shadow_light_count += 1u;
+    return true;
+}
+
+// Returns the tangent of the angle between the surface normal and the direction
+// to the light, which scales the slope bias.
+fn shadow_slope(surface_normal: vec3<f32>, direction_to_light: vec3<f32>) -> f32 {
+    let cos_theta = saturate(dot(surface_normal, direction_to_light));
+    let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+    return min(sin_theta / max(cos_theta, 1e-4), MAX_SHADOW_SLOPE);
+}
+
+// Returns the number of samples and the radius scale of the blocker search of
+// the soft shadows of a point or spot light.
+fn point_light_blocker_search(flags: u32) -> vec2<f32> {
+    let sample_count = (flags >> POINT_LIGHT_FLAGS_BLOCKER_SEARCH_SAMPLE_COUNT_SHIFT_BITS) &
+        POINT_LIGHT_FLAGS_BLOCKER_SEARCH_SAMPLE_COUNT_MASK_BITS;
+    let radius_scale = (flags >> POINT_LIGHT_FLAGS_BLOCKER_SEARCH_RADIUS_SCALE_SHIFT_BITS) &
+        POINT_LIGHT_FLAGS_BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS;
+    return vec2(
+        f32(sample_count),
+        f32(radius_scale) / f32(POINT_LIGHT_FLAGS_BLOCKER_SEARCH_RADIUS_SCALE_MASK_BITS) *
+            MAX_BLOCKER_SEARCH_RADIUS_SCALE
+    );
+}
+
 fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
     let light = &view_bindings::clusterable_objects.data[light_id];
 
@@ -25,11 +75,14 @@ fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: v
     let surface_to_light_abs = abs(surface_to_light);
     let distance_to_light = max(surface_to_light_abs.x, max(surface_to_light_abs.y, surface_to_light_abs.z));
 
-    // The normal bias here is already scaled by the texel size at 1 world unit from the light.
-    // The texel size increases proportionally with distance from the light so multiplying by
-    // distance to light scales the normal bias to the texel size at the fragment distance.
+    // The normal and slope biases here are already scaled by the texel size at 1 world unit from
+    // the light. The texel size increases proportionally with distance from the light so
+    // multiplying by distance to light scales them to the texel size at the fragment distance.
+    let direction_to_light = normalize(surface_to_light.xyz);
     let normal_offset = (*light).shadow_normal_bias * distance_to_light * surface_normal.xyz;
-    let depth_offset = (*light).shadow_depth_bias * normalize(surface_to_light.xyz);
+    let slope_bias = (*light).shadow_slope_bias * distance_to_light *
+        shadow_slope(surface_normal, direction_to_light);
+    let depth_offset = ((*light).shadow_depth_bias + slope_bias) * direction_to_light;
     let offset_position = frag_position.xyz + normal_offset + depth_offset;
 
     // similar largest-absolute-axis trick as above, but now with the offset fragment position
@@ -47,13 +100,16 @@ fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: v
     // If soft shadows are enabled, use the PCSS path. Cubemaps assume a
     // left-handed coordinate space, so we have to flip the z-axis when
     // sampling.
-    if ((*light).soft_shadow_size > 0.0) {
+    if ((*light).soft_shadow_size > 0.0 && claim_soft_shadow_evaluation()) {
+        let blocker_search = point_light_blocker_search((*light).flags);
         return sample_shadow_cubemap_pcss(
             frag_ls * flip_z,
             distance_to_light,
             depth,
             light_id,
             (*light).soft_shadow_size,
+            u32(blocker_search.x),
+            blocker_search.y,
         );
     }
 
@@ -83,9 +139,12 @@ fn fetch_spot_shadow(
     // view matrix z_axis is the reverse of transform.forward()
     let fwd = -spot_dir;
     let distance_to_light = dot(fwd, surface_to_light);
+    let direction_to_light = normalize(surface_to_light);
+    let slope_bias = (*light).shadow_slope_bias * distance_to_light *
+        shadow_slope(surface_normal, direction_to_light);
     let offset_position =
         -surface_to_light
-        + ((*light).shadow_depth_bias * normalize(surface_to_light))
+        + (((*light).shadow_depth_bias + slope_bias) * direction_to_light)
         + (surface_normal.xyz * (*light).shadow_normal_bias) * distance_to_light;
 
     // the construction of the up and right vectors needs to precisely mirror the code
@@ -109,16 +168,25 @@ fn fetch_spot_shadow(
     // to get ndc coordinates
     let f_div_minus_z = 1.0 / ((*light).spot_light_tan_angle * -projected_position.z);
     let shadow_xy_ndc = projected_position.xy * f_div_minus_z;
-    // convert to uv coordinates
-    let shadow_uv = shadow_xy_ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
+    // convert to uv coordinates, in the part of the shadow map that the light renders to
+    let shadow_uv = (shadow_xy_ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5)) *
+        (*light).shadow_map_scale;
 
     let depth = near_z / -projected_position.z;
 
     // If soft shadows are enabled, use the PCSS path.
     let array_index = i32(light_id) + view_bindings::lights.spot_light_shadowmap_offset;
-    if ((*light).soft_shadow_size > 0.0) {
+    if ((*light).soft_shadow_size > 0.0 && claim_soft_shadow_evaluation()) {
+        let blocker_search = point_light_blocker_search((*light).flags);
         return sample_shadow_map_pcss(
-            shadow_uv, depth, array_index, SPOT_SHADOW_TEXEL_SIZE, (*light).soft_shadow_size);
+            shadow_uv,
+            depth,
+            array_index,
+            SPOT_SHADOW_TEXEL_SIZE,
+            (*light).soft_shadow_size,
+            u32(blocker_search.x),
+            blocker_search.y,
+        );
     }
 
     return sample_shadow_map(shadow_uv, depth, array_index, SPOT_SHADOW_TEXEL_SIZE);
@@ -168,18 +236,23 @@ fn world_to_directional_light_local(
     return vec4(light_local, depth, 1.0);
 }
 
+// Samples a cascade of a directional light, using the PCSS path if
+// `soft_shadows` is true.
 fn sample_directional_cascade(
     light_id: u32,
     cascade_index: u32,
     frag_position: vec4<f32>,
     surface_normal: vec3<f32>,
+    soft_shadows: bool,
 ) -> f32 {
     let light = &view_bindings::lights.directional_lights[light_id];
     let cascade = &(*light).cascades[cascade_index];
 
-    // The normal bias is scaled to the texel size.
+    // The normal and slope biases are scaled to the texel size.
     let normal_offset = (*light).shadow_normal_bias * (*cascade).texel_size * surface_normal.xyz;
-    let depth_offset = (*light).shadow_depth_bias * (*light).direction_to_light.xyz;
+    let slope_bias = (*light).shadow_slope_bias * (*cascade).texel_size *
+        shadow_slope(surface_normal, (*light).direction_to_light.xyz);
+    let depth_offset = ((*light).shadow_depth_bias + slope_bias) * (*light).direction_to_light.xyz;
     let offset_position = vec4<f32>(frag_position.xyz + normal_offset + depth_offset, frag_position.w);
 
     let light_local = world_to_directional_light_local(light_id, cascade_index, offset_position);
@@ -187,16 +260,24 @@ fn sample_directional_cascade(
         return 1.0;
     }
 
+    // The cascade only covers the part of the shadow map that the light renders to.
+    let shadow_uv = light_local.xy * (*light).shadow_map_scale;
     let array_index = i32((*light).depth_texture_base_index + cascade_index);
     let texel_size = (*cascade).texel_size;
 
-    // If soft shadows are enabled, use the PCSS path.
-    if ((*light).soft_shadow_size > 0.0) {
+    if (soft_shadows) {
         return sample_shadow_map_pcss(
-            light_local.xy, light_local.z, array_index, texel_size, (*light).soft_shadow_size);
+            shadow_uv,
+            light_local.z,
+            array_index,
+            texel_size,
+            (*light).soft_shadow_size,
+            (*light).blocker_search_sample_count,
+            (*light).blocker_search_radius_scale,
+        );
     }
 
-    return sample_shadow_map(light_local.xy, light_local.z, array_index, texel_size);
+    return sample_shadow_map(shadow_uv, light_local.z, array_index, texel_size);
 }
 
 fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>, view_z: f32) -> f32 {
@@ -207,7 +288,11 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
         return 1.0;
     }
 
-    var shadow = sample_directional_cascade(light_id, cascade_index, frag_position, surface_normal);
+    // Both cascades of a blend count as a single soft shadow evaluation.
+    let soft_shadows = (*light).soft_shadow_size > 0.0 && claim_soft_shadow_evaluation();
+
+    var shadow = sample_directional_cascade(
+        light_id, cascade_index, frag_position, surface_normal, soft_shadows);
 
     // Blend with the next cascade, if there is one.
     let next_cascade_index = cascade_index + 1u;
@@ -215,7 +300,8 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
         let this_far_bound = (*light).cascades[cascade_index].far_bound;
         let next_near_bound = (1.0 - (*light).cascades_overlap_proportion) * this_far_bound;
         if (-view_z >= next_near_bound) {
-            let next_shadow = sample_directional_cascade(light_id, next_cascade_index, frag_position, surface_normal);
+            let next_shadow = sample_directional_cascade(
+                light_id, next_cascade_index, frag_position, surface_normal, soft_shadows);
             shadow = mix(shadow, next_shadow, (-view_z - next_near_bound) / (this_far_bound - next_near_bound));
         }
     }
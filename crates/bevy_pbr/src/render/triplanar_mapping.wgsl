@@ -0,0 +1,89 @@
+#define_import_path bevy_pbr::triplanar_mapping
+
+#import bevy_pbr::pbr_functions::unpack_normal_map
+
+// The coordinates of a texture projected along the three world axes.
+//
+// Each projection uses the world position, transformed by a UV transform, as
+// texture coordinates. The V axis of the side projections points down so that
+// textures aren't upside down on vertical surfaces.
+struct TriplanarCoords {
+    // Projection along the X axis, with U along +Z and V along -Y.
+    uv_x: vec2<f32>,
+    // Projection along the Y axis, with U along +X and V along +Z.
+    uv_y: vec2<f32>,
+    // Projection along the Z axis, with U along +X and V along -Y.
+    uv_z: vec2<f32>,
+    // The contribution of each projection, summing to 1.
+    weights: vec3<f32>,
+}
+
+fn triplanar_coords(
+    world_position: vec3<f32>,
+    world_normal: vec3<f32>,
+    uv_transform: mat3x3<f32>,
+    blend_sharpness: f32,
+) -> TriplanarCoords {
+    var coords: TriplanarCoords;
+    coords.uv_x = (uv_transform * vec3(world_position.z, -world_position.y, 1.0)).xy;
+    coords.uv_y = (uv_transform * vec3(world_position.x, world_position.z, 1.0)).xy;
+    coords.uv_z = (uv_transform * vec3(world_position.x, -world_position.y, 1.0)).xy;
+
+    // Raising the weights to a power narrows the regions where the projections
+    // blend into each other.
+    let weights = pow(abs(world_normal), vec3(blend_sharpness));
+    coords.weights = weights / max(weights.x + weights.y + weights.z, 0.0001);
+    return coords;
+}
+
+// Samples a texture with the three projections of `coords` and blends the
+// results.
+fn triplanar_sample(
+    texture: texture_2d<f32>,
+    texture_sampler: sampler,
+    coords: TriplanarCoords,
+    mip_bias: f32,
+) -> vec4<f32> {
+    return textureSampleBias(texture, texture_sampler, coords.uv_x, mip_bias) * coords.weights.x +
+        textureSampleBias(texture, texture_sampler, coords.uv_y, mip_bias) * coords.weights.y +
+        textureSampleBias(texture, texture_sampler, coords.uv_z, mip_bias) * coords.weights.z;
+}
+
+// Perturbs the world space normal `N` with a normal map sampled with the three
+// projections of `coords`.
+//
+// The tangent space normal of each projection is applied in the frame of the
+// projection's U and V axes, and the perturbations are blended together. This
+// is the "UDN" blend from Ben Golus's "Normal Mapping for a Triplanar Shader":
+// <https://bgolus.medium.com/normal-mapping-for-a-triplanar-shader-10bf39dca05a>
+fn triplanar_normal(
+    normal_map: texture_2d<f32>,
+    normal_map_sampler: sampler,
+    coords: TriplanarCoords,
+    mip_bias: f32,
+    two_component: bool,
+    flip_y: bool,
+    N: vec3<f32>,
+) -> vec3<f32> {
+    let Nt_x = unpack_normal_map(
+        two_component,
+        flip_y,
+        textureSampleBias(normal_map, normal_map_sampler, coords.uv_x, mip_bias).rgb,
+    );
+    let Nt_y = unpack_normal_map(
+        two_component,
+        flip_y,
+        textureSampleBias(normal_map, normal_map_sampler, coords.uv_y, mip_bias).rgb,
+    );
+    let Nt_z = unpack_normal_map(
+        two_component,
+        flip_y,
+        textureSampleBias(normal_map, normal_map_sampler, coords.uv_z, mip_bias).rgb,
+    );
+
+    let perturbation =
+        vec3(0.0, -Nt_x.y, Nt_x.x) * coords.weights.x +
+        vec3(Nt_y.x, 0.0, Nt_y.y) * coords.weights.y +
+        vec3(Nt_z.x, -Nt_z.y, 0.0) * coords.weights.z;
+    return normalize(N + perturbation);
+}
@@ -0,0 +1,423 @@
+//! Vertex animation textures (VAT), to play back simulations baked in a DCC
+//! tool, like cloth or fluids, without simulating or skinning them at runtime.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, Asset, Assets, Handle};
+use bevy_ecs::system::{Res, ResMut};
+use bevy_image::Image;
+use bevy_math::{ops, UVec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    mesh::MeshVertexBufferLayoutRef,
+    render_resource::{
+        AsBindGroup, RenderPipelineDescriptor, Shader, ShaderRef, ShaderType,
+        SpecializedMeshPipelineError,
+    },
+};
+use bevy_time::Time;
+
+use crate::{
+    ExtendedMaterial, Material, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
+    MaterialPlugin, StandardMaterial,
+};
+
+const VERTEX_ANIMATION_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3b8c2f4e-6a1d-4e7b-9c05-8d2f1a6e4b93");
+const VERTEX_ANIMATION_PREPASS_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("a4e91d27-0c6f-4b3a-8e52-f17b9d3c6a08");
+const VERTEX_ANIMATION_FUNCTIONS_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("5d07b6c1-92e4-4f8a-b3d6-0e4a8c1f7b25");
+
+/// Plays back [`VertexAnimation`]s on meshes with a [`VertexAnimationMaterial<StandardMaterial>`].
+///
+/// This plugin isn't part of the `DefaultPlugins`.
+#[derive(Debug, Default)]
+pub struct VertexAnimationPlugin;
+
+impl Plugin for VertexAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            VERTEX_ANIMATION_FUNCTIONS_SHADER_HANDLE,
+            "vertex_animation_functions.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VERTEX_ANIMATION_SHADER_HANDLE,
+            "vertex_animation.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VERTEX_ANIMATION_PREPASS_SHADER_HANDLE,
+            "vertex_animation_prepass.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<VertexAnimation>()
+            .register_type::<VertexAnimationRepeat>()
+            .add_plugins(MaterialPlugin::<VertexAnimationMaterial<StandardMaterial>>::default())
+            .add_systems(PostUpdate, advance_vertex_animations::<StandardMaterial>);
+    }
+}
+
+/// Type alias for an extended material with a [`VertexAnimation`] extension.
+///
+/// The [`VertexAnimationPlugin`] sets up this material for [`StandardMaterial`]. For other base
+/// materials, add the [`MaterialPlugin`] for this material and the [`advance_vertex_animations`]
+/// system for the base material.
+#[expect(type_alias_bounds, reason = "Type alias generics not yet stable")]
+pub type VertexAnimationMaterial<B: Material> = ExtendedMaterial<B, VertexAnimation>;
+
+/// Material extension playing back a vertex animation texture (VAT).
+///
+/// A vertex animation texture stores the position of every vertex of a mesh at every frame of an
+/// animation, as baked by tools like Houdini or Blender. Each texel stores one vertex at one frame:
+/// texel `frame * vertex_count + vertex` is found by reading the texture row by row, so frames can
+/// wrap across rows. The vertices are in the order of the vertex buffer of the mesh, so the mesh
+/// must be exported alongside the texture and not be modified.
+///
+/// - The [`positions`](Self::positions) texture stores the offset of each vertex from its position
+///   in the mesh, in the local space of the mesh.
+/// - The optional [`normals`](Self::normals) texture stores the normal of each vertex, which
+///   replaces the normal of the mesh.
+///
+/// Both textures must use a float format that isn't sRGB, like
+/// [`TextureFormat::Rgba32Float`](bevy_render::render_resource::TextureFormat::Rgba32Float),
+/// and only their first three channels are used. Consecutive frames are blended, and the
+/// vertex shader used in prepasses and shadows is animated too, so motion vectors are correct.
+///
+/// The animation replaces the vertex shader of the base material, and doesn't support morph targets.
+/// The animation of a material is shared by all the meshes using it.
+#[derive(Asset, AsBindGroup, Reflect, Clone, Debug)]
+#[reflect(Default, Debug)]
+#[uniform(300, VertexAnimationUniform)]
+#[bind_group_data(VertexAnimationKey)]
+pub struct VertexAnimation {
+    /// The offsets of the vertices at each frame.
+    #[texture(301, filterable = false)]
+    pub positions: Handle<Image>,
+    /// The normals of the vertices at each frame, if they change during the animation.
+    #[texture(302, filterable = false)]
+    pub normals: Option<Handle<Image>>,
+    /// The number of vertices in the mesh.
+    pub vertex_count: u32,
+    /// The number of frames in the animation.
+    pub frame_count: u32,
+    /// The number of frames played per second, at a [`speed`](Self::speed) of `1.0`.
+    pub frames_per_second: f32,
+    /// How fast the animation plays. Negative values play it backwards.
+    pub speed: f32,
+    /// What happens when the animation reaches its end.
+    pub repeat: VertexAnimationRepeat,
+    /// Whether the animation is paused.
+    pub paused: bool,
+    /// The current time in the animation, in seconds.
+    pub time: f32,
+    /// The time in the animation on the previous update, for motion vectors.
+    #[reflect(ignore)]
+    previous_time: f32,
+}
+
+/// What a [`VertexAnimation`] does when it reaches its end.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum VertexAnimationRepeat {
+    /// Stop on the last frame.
+    Once,
+    /// Start again from the first frame, blending the last frame into the first one.
+    #[default]
+    Loop,
+    /// Play the animation backwards, then forwards again.
+    PingPong,
+}
+
+impl Default for VertexAnimation {
+    fn default() -> Self {
+        Self {
+            positions: Handle::default(),
+            normals: None,
+            vertex_count: 0,
+            frame_count: 1,
+            frames_per_second: 24.0,
+            speed: 1.0,
+            repeat: VertexAnimationRepeat::default(),
+            paused: false,
+            time: 0.0,
+            previous_time: 0.0,
+        }
+    }
+}
+
+impl VertexAnimation {
+    /// Creates a looping animation of `frame_count` frames of a mesh with `vertex_count` vertices,
+    /// played at 24 frames per second.
+    pub fn new(positions: Handle<Image>, vertex_count: u32, frame_count: u32) -> Self {
+        Self {
+            positions,
+            vertex_count,
+            frame_count,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the texture storing the normals of the vertices at each frame.
+    pub fn with_normals(mut self, normals: Handle<Image>) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    /// Sets the number of frames played per second.
+    pub fn with_frames_per_second(mut self, frames_per_second: f32) -> Self {
+        self.frames_per_second = frames_per_second;
+        self
+    }
+
+    /// Sets what happens when the animation reaches its end.
+    pub fn with_repeat(mut self, repeat: VertexAnimationRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Resumes the animation.
+    pub fn play(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pauses the animation on its current frame.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Jumps to `time` in the animation, in seconds.
+    pub fn seek(&mut self, time: f32) {
+        self.time = time;
+        // Don't produce motion vectors across the jump.
+        self.previous_time = time;
+    }
+
+    /// Jumps back to the start of the animation, and plays it.
+    pub fn restart(&mut self) {
+        self.seek(0.0);
+        self.play();
+    }
+
+    /// Returns the current frame of the animation. The fractional part is the blend
+    /// towards the next frame.
+    pub fn frame(&self) -> f32 {
+        self.frame_at(self.time)
+    }
+
+    /// Returns `true` if the animation plays [`Once`](VertexAnimationRepeat::Once) and
+    /// has reached its end, in the direction it's playing.
+    pub fn is_finished(&self) -> bool {
+        if self.repeat != VertexAnimationRepeat::Once {
+            return false;
+        }
+        let frame = self.time * self.frames_per_second;
+        if self.speed < 0.0 {
+            frame <= 0.0
+        } else {
+            frame >= self.last_frame() as f32
+        }
+    }
+
+    /// Advances the animation by `delta` seconds, scaled by its [`speed`](Self::speed), unless
+    /// it's paused or finished.
+    pub fn advance(&mut self, delta: f32) {
+        self.previous_time = self.time;
+        if self.paused || self.is_finished() {
+            return;
+        }
+        self.time += delta * self.speed;
+        if self.repeat == VertexAnimationRepeat::Once && self.frames_per_second > 0.0 {
+            self.time = self
+                .time
+                .clamp(0.0, self.last_frame() as f32 / self.frames_per_second);
+        }
+    }
+
+    /// Returns `true` if [`advance`](Self::advance) would change the animation.
+    fn needs_advance(&self) -> bool {
+        self.previous_time != self.time || !(self.paused || self.is_finished())
+    }
+
+    fn last_frame(&self) -> u32 {
+        self.frame_count.saturating_sub(1)
+    }
+
+    fn frame_at(&self, time: f32) -> f32 {
+        let last = self.last_frame() as f32;
+        let frame = time * self.frames_per_second;
+        match self.repeat {
+            VertexAnimationRepeat::Once => frame.clamp(0.0, last),
+            VertexAnimationRepeat::Loop => ops::rem_euclid(frame, self.frame_count.max(1) as f32),
+            VertexAnimationRepeat::PingPong => {
+                if last == 0.0 {
+                    return 0.0;
+                }
+                let frame = ops::rem_euclid(frame, 2.0 * last);
+                if frame > last {
+                    2.0 * last - frame
+                } else {
+                    frame
+                }
+            }
+        }
+    }
+
+    /// Returns the two frames to blend at `time`, and the blend factor between them.
+    fn frames_at(&self, time: f32) -> (UVec2, f32) {
+        let frame = self.frame_at(time);
+        let current = (ops::floor(frame) as u32).min(self.last_frame());
+        let next = if self.repeat == VertexAnimationRepeat::Loop {
+            (current + 1) % self.frame_count.max(1)
+        } else {
+            (current + 1).min(self.last_frame())
+        };
+        (UVec2::new(current, next), frame - current as f32)
+    }
+}
+
+/// The GPU representation of a [`VertexAnimation`].
+#[derive(Clone, Copy, Default, ShaderType)]
+pub struct VertexAnimationUniform {
+    /// The two frames to blend.
+    pub frames: UVec2,
+    /// The blend factor from the first frame to the second.
+    pub blend: f32,
+    /// The two frames blended on the previous update.
+    pub previous_frames: UVec2,
+    /// The blend factor on the previous update.
+    pub previous_blend: f32,
+    /// The number of vertices in the mesh.
+    pub vertex_count: u32,
+}
+
+impl From<&VertexAnimation> for VertexAnimationUniform {
+    fn from(animation: &VertexAnimation) -> Self {
+        let (frames, blend) = animation.frames_at(animation.time);
+        let (previous_frames, previous_blend) = animation.frames_at(animation.previous_time);
+        Self {
+            frames,
+            blend,
+            previous_frames,
+            previous_blend,
+            vertex_count: animation.vertex_count,
+        }
+    }
+}
+
+/// The pipeline key of a [`VertexAnimation`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VertexAnimationKey {
+    normals: bool,
+}
+
+impl From<&VertexAnimation> for VertexAnimationKey {
+    fn from(animation: &VertexAnimation) -> Self {
+        Self {
+            normals: animation.normals.is_some(),
+        }
+    }
+}
+
+impl MaterialExtension for VertexAnimation {
+    fn vertex_shader() -> ShaderRef {
+        VERTEX_ANIMATION_SHADER_HANDLE.into()
+    }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        VERTEX_ANIMATION_PREPASS_SHADER_HANDLE.into()
+    }
+
+    fn deferred_vertex_shader() -> ShaderRef {
+        VERTEX_ANIMATION_PREPASS_SHADER_HANDLE.into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if key.bind_group_data.normals {
+            descriptor
+                .vertex
+                .shader_defs
+                .push("VERTEX_ANIMATION_NORMALS".into());
+        }
+        Ok(())
+    }
+}
+
+/// Advances the [`VertexAnimation`] of every [`VertexAnimationMaterial`] with the base material `B`.
+pub fn advance_vertex_animations<B: Material>(
+    time: Res<Time>,
+    mut materials: ResMut<Assets<VertexAnimationMaterial<B>>>,
+) {
+    // Only mutate the materials that change, so that the others aren't prepared again.
+    let playing: Vec<_> = materials
+        .iter()
+        .filter(|(_, material)| material.extension.needs_advance())
+        .map(|(id, _)| id)
+        .collect();
+    for id in playing {
+        if let Some(material) = materials.get_mut(id) {
+            material.extension.advance(time.delta_secs());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation(repeat: VertexAnimationRepeat) -> VertexAnimation {
+        VertexAnimation::new(Handle::default(), 100, 5)
+            .with_frames_per_second(10.0)
+            .with_repeat(repeat)
+    }
+
+    #[test]
+    fn frames_follow_repeat_mode() {
+        let looping = animation(VertexAnimationRepeat::Loop);
+        assert_eq!(looping.frames_at(0.125), (UVec2::new(1, 2), 0.25));
+        // The last frame blends into the first one.
+        assert_eq!(looping.frames_at(0.4375), (UVec2::new(4, 0), 0.375));
+        assert_eq!(looping.frames_at(0.625), (UVec2::new(1, 2), 0.25));
+
+        let once = animation(VertexAnimationRepeat::Once);
+        assert_eq!(once.frames_at(0.4375), (UVec2::new(4, 4), 0.0));
+        assert_eq!(once.frames_at(10.0), (UVec2::new(4, 4), 0.0));
+
+        let ping_pong = animation(VertexAnimationRepeat::PingPong);
+        assert_eq!(ping_pong.frames_at(0.25), (UVec2::new(2, 3), 0.5));
+        assert_eq!(ping_pong.frames_at(0.5625), (UVec2::new(2, 3), 0.375));
+        assert_eq!(ping_pong.frames_at(0.75), (UVec2::new(0, 1), 0.5));
+    }
+
+    #[test]
+    fn playback_controls() {
+        let mut once = animation(VertexAnimationRepeat::Once);
+        once.advance(0.25);
+        assert_eq!(once.frame(), 2.5);
+        once.pause();
+        once.advance(0.25);
+        assert_eq!(once.frame(), 2.5);
+        // The previous time caught up, so paused animations aren't updated anymore.
+        assert!(!once.needs_advance());
+
+        once.play();
+        once.advance(1.0);
+        assert!(once.is_finished());
+        assert_eq!(once.frame(), 4.0);
+        once.advance(1.0);
+        assert!(!once.needs_advance());
+
+        once.restart();
+        assert_eq!(once.frame(), 0.0);
+        assert!(once.needs_advance());
+    }
+}
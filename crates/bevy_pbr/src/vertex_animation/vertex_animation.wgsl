@@ -0,0 +1,94 @@
+#import bevy_pbr::{
+    mesh_functions,
+    skinning,
+    forward_io::{Vertex, VertexOutput},
+    vertex_animation,
+    view_transformations::position_world_to_clip,
+}
+
+// Like the vertex shader of `mesh.wgsl`, with the vertices moved by the animation. Morph targets
+// aren't supported.
+@vertex
+fn vertex(
+    vertex_in: Vertex,
+#ifndef MORPH_TARGETS
+    @builtin(vertex_index) vertex_index: u32,
+#endif
+) -> VertexOutput {
+    var out: VertexOutput;
+    var vertex = vertex_in;
+
+#ifdef MORPH_TARGETS
+    let vertex_index = vertex_in.index;
+#endif
+    let local_vertex_index = vertex_animation::local_vertex_index(vertex_in.instance_index, vertex_index);
+
+#ifdef VERTEX_POSITIONS
+    vertex.position += vertex_animation::position_offset(local_vertex_index);
+#endif
+#ifdef VERTEX_NORMALS
+#ifdef VERTEX_ANIMATION_NORMALS
+    vertex.normal = vertex_animation::animated_normal(local_vertex_index);
+#endif
+#endif
+
+    let mesh_world_from_local = mesh_functions::get_world_from_local(vertex_in.instance_index);
+
+#ifdef SKINNED
+    var world_from_local = skinning::skin_model(
+        vertex.joint_indices,
+        vertex.joint_weights,
+        vertex_in.instance_index
+    );
+#else
+    // Use vertex_in.instance_index instead of vertex.instance_index to work around a wgpu dx12 bug.
+    // See https://github.com/gfx-rs/naga/issues/2416 .
+    var world_from_local = mesh_world_from_local;
+#endif
+
+#ifdef VERTEX_NORMALS
+#ifdef SKINNED
+    out.world_normal = skinning::skin_normals(world_from_local, vertex.normal);
+#else
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(
+        vertex.normal,
+        vertex_in.instance_index
+    );
+#endif
+#endif
+
+#ifdef VERTEX_POSITIONS
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+#endif
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent,
+        vertex_in.instance_index
+    );
+#endif
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex_in.instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        vertex_in.instance_index, mesh_world_from_local[3]);
+#endif
+
+    return out;
+}
@@ -0,0 +1,49 @@
+#define_import_path bevy_pbr::vertex_animation
+
+#import bevy_pbr::mesh_bindings::mesh
+
+struct VertexAnimation {
+    frames: vec2<u32>,
+    blend: f32,
+    previous_frames: vec2<u32>,
+    previous_blend: f32,
+    vertex_count: u32,
+}
+
+@group(2) @binding(300) var<uniform> animation: VertexAnimation;
+@group(2) @binding(301) var positions: texture_2d<f32>;
+@group(2) @binding(302) var normals: texture_2d<f32>;
+
+// Returns the index of the vertex in the mesh, from its index in the vertex buffer.
+fn local_vertex_index(instance_index: u32, vertex_index: u32) -> u32 {
+    return vertex_index - mesh[instance_index].first_vertex_index;
+}
+
+// Loads the texel of `vertex` at `frame`. Frames are stored one after the other, and wrap
+// across rows of the texture.
+fn load_texel(texture: texture_2d<f32>, frame: u32, vertex: u32) -> vec3<f32> {
+    let width = textureDimensions(texture).x;
+    let index = frame * animation.vertex_count + vertex;
+    return textureLoad(texture, vec2(index % width, index / width), 0).xyz;
+}
+
+fn blend_frames(texture: texture_2d<f32>, frames: vec2<u32>, blend: f32, vertex: u32) -> vec3<f32> {
+    let a = load_texel(texture, frames.x, vertex);
+    let b = load_texel(texture, frames.y, vertex);
+    return mix(a, b, blend);
+}
+
+// Returns the offset of `vertex` from its position in the mesh, at the current time.
+fn position_offset(vertex: u32) -> vec3<f32> {
+    return blend_frames(positions, animation.frames, animation.blend, vertex);
+}
+
+// Returns the offset of `vertex` from its position in the mesh, on the previous frame.
+fn previous_position_offset(vertex: u32) -> vec3<f32> {
+    return blend_frames(positions, animation.previous_frames, animation.previous_blend, vertex);
+}
+
+// Returns the normal of `vertex` at the current time.
+fn animated_normal(vertex: u32) -> vec3<f32> {
+    return normalize(blend_frames(normals, animation.frames, animation.blend, vertex));
+}
@@ -0,0 +1,118 @@
+#import bevy_pbr::{
+    mesh_functions,
+    prepass_io::{Vertex, VertexOutput},
+    skinning,
+    vertex_animation,
+    view_transformations::position_world_to_clip,
+}
+
+// Like the vertex shader of `prepass.wgsl`, with the vertices moved by the animation. This is
+// used for the prepasses, the deferred prepass and shadows. Morph targets aren't supported.
+@vertex
+fn vertex(
+    vertex_in: Vertex,
+#ifndef MORPH_TARGETS
+    @builtin(vertex_index) vertex_index: u32,
+#endif
+) -> VertexOutput {
+    var out: VertexOutput;
+    var vertex = vertex_in;
+
+#ifdef MORPH_TARGETS
+    let vertex_index = vertex_in.index;
+#endif
+    let local_vertex_index = vertex_animation::local_vertex_index(vertex_in.instance_index, vertex_index);
+
+    vertex.position += vertex_animation::position_offset(local_vertex_index);
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#ifdef VERTEX_ANIMATION_NORMALS
+    vertex.normal = vertex_animation::animated_normal(local_vertex_index);
+#endif // VERTEX_ANIMATION_NORMALS
+#endif // NORMAL_PREPASS_OR_DEFERRED_PREPASS
+
+    let mesh_world_from_local = mesh_functions::get_world_from_local(vertex_in.instance_index);
+
+#ifdef SKINNED
+    var world_from_local = skinning::skin_model(
+        vertex.joint_indices,
+        vertex.joint_weights,
+        vertex_in.instance_index
+    );
+#else // SKINNED
+    var world_from_local = mesh_world_from_local;
+#endif // SKINNED
+
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.unclipped_depth = out.position.z;
+    out.position.z = min(out.position.z, 1.0); // Clamp depth to avoid clipping
+#endif // UNCLIPPED_DEPTH_ORTHO_EMULATION
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif // VERTEX_UVS_A
+
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif // VERTEX_UVS_B
+
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#ifdef SKINNED
+    out.world_normal = skinning::skin_normals(world_from_local, vertex.normal);
+#else // SKINNED
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(
+        vertex.normal,
+        vertex_in.instance_index
+    );
+#endif // SKINNED
+
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent,
+        vertex_in.instance_index
+    );
+#endif // VERTEX_TANGENTS
+#endif // NORMAL_PREPASS_OR_DEFERRED_PREPASS
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+    // The vertex moves with the animation too, so start from where the animation put it last frame.
+    var prev_vertex = vertex_in;
+    prev_vertex.position += vertex_animation::previous_position_offset(local_vertex_index);
+
+#ifdef SKINNED
+#ifdef HAS_PREVIOUS_SKIN
+    let prev_model = skinning::skin_prev_model(
+        prev_vertex.joint_indices,
+        prev_vertex.joint_weights,
+        vertex_in.instance_index
+    );
+#else   // HAS_PREVIOUS_SKIN
+    let prev_model = mesh_functions::get_previous_world_from_local(vertex_in.instance_index);
+#endif  // HAS_PREVIOUS_SKIN
+#else   // SKINNED
+    let prev_model = mesh_functions::get_previous_world_from_local(vertex_in.instance_index);
+#endif  // SKINNED
+
+    out.previous_world_position = mesh_functions::mesh_position_local_to_world(
+        prev_model,
+        vec4<f32>(prev_vertex.position, 1.0)
+    );
+#endif // MOTION_VECTOR_PREPASS
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex_in.instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        vertex_in.instance_index, mesh_world_from_local[3]);
+#endif  // VISIBILITY_RANGE_DITHER
+
+    return out;
+}
@@ -285,8 +285,8 @@ fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
             if (local_light_attenuation != 0.0) {
                 let cascade = &(*light).cascades[cascade_index];
                 let array_index = i32((*light).depth_texture_base_index + cascade_index);
-                local_light_attenuation =
-                    sample_shadow_map_hardware(light_local.xy, light_local.z, array_index);
+                local_light_attenuation = sample_shadow_map_hardware(
+                    light_local.xy * (*light).shadow_map_scale, light_local.z, array_index);
             }
 
             if (local_light_attenuation != 0.0) {
@@ -471,8 +471,9 @@ fn fetch_spot_shadow_without_normal(light_id: u32, frag_position: vec4<f32>) ->
     // to get ndc coordinates
     let f_div_minus_z = 1.0 / ((*light).spot_light_tan_angle * -projected_position.z);
     let shadow_xy_ndc = projected_position.xy * f_div_minus_z;
-    // convert to uv coordinates
-    let shadow_uv = shadow_xy_ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
+    // convert to uv coordinates, in the part of the shadow map that the light renders to
+    let shadow_uv = (shadow_xy_ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5)) *
+        (*light).shadow_map_scale;
 
     // 0.1 must match POINT_LIGHT_NEAR_Z
     let depth = 0.1 / -projected_position.z;
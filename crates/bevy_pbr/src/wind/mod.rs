@@ -0,0 +1,298 @@
+//! Wind swaying vegetation, like grass, leaves and branches, in the vertex shader.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, Asset, Assets, Handle};
+use bevy_ecs::{
+    reflect::ReflectResource,
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    mesh::MeshVertexAttribute,
+    render_resource::{AsBindGroup, Shader, ShaderRef, ShaderType, VertexFormat},
+};
+use bevy_time::Time;
+
+use crate::{
+    ExtendedMaterial, ExtensionVertexAttribute, Material, MaterialExtension, MaterialPlugin,
+    StandardMaterial,
+};
+
+const WIND_SHADER_HANDLE: Handle<Shader> = weak_handle!("c6e2a8d4-1f7b-4e93-a05c-3b9d6f2e8a17");
+const WIND_PREPASS_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("0f4b7e19-8c3a-4d62-b5e1-a7d92c6f3e40");
+const WIND_FUNCTIONS_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("7a91c3e5-2d8f-4b06-9e47-d1f5a0b8c263");
+
+/// Sways the meshes with a [`WindMaterial<StandardMaterial>`] in the [`Wind`].
+///
+/// This plugin isn't part of the `DefaultPlugins`.
+#[derive(Debug, Default)]
+pub struct WindPlugin;
+
+impl Plugin for WindPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            WIND_FUNCTIONS_SHADER_HANDLE,
+            "wind_functions.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(app, WIND_SHADER_HANDLE, "wind.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            WIND_PREPASS_SHADER_HANDLE,
+            "wind_prepass.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Wind>()
+            .register_type::<WindSway>()
+            .init_resource::<Wind>()
+            .add_plugins(MaterialPlugin::<WindMaterial<StandardMaterial>>::default())
+            .add_systems(PostUpdate, update_wind_materials::<StandardMaterial>);
+    }
+}
+
+/// The per-vertex weight of the [`WindSway`], as a `Float32` between `0.0` and `1.0`.
+///
+/// Vertices with a weight of `0.0` stay in place, like the base of a trunk or the root of a blade
+/// of grass, while vertices with a weight of `1.0` move the most, like the tips of leaves.
+pub const ATTRIBUTE_WIND_WEIGHT: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_WindWeight", 1_871_204_553, VertexFormat::Float32);
+
+/// The wind blowing on every [`WindMaterial`].
+///
+/// The wind bends the meshes in its [`direction`](Self::direction), with a slow sway around the
+/// bent position. Gusts travel through the scene in the direction of the wind, and bend the
+/// meshes they pass further.
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[reflect(Resource, Default, Debug, PartialEq)]
+pub struct Wind {
+    /// The direction the wind blows towards, in world space. It doesn't need to be normalized.
+    pub direction: Vec3,
+    /// How far the wind bends the vertices with a weight of `1.0`, in meters.
+    pub strength: f32,
+    /// How many times per second the meshes sway back and forth.
+    pub sway_frequency: f32,
+    /// How much further gusts bend the vertices with a weight of `1.0`, in meters.
+    pub gust_strength: f32,
+    /// How fast gusts travel through the scene, in meters per second.
+    pub gust_speed: f32,
+    /// The distance between gusts, in meters.
+    pub gust_scale: f32,
+}
+
+impl Default for Wind {
+    fn default() -> Self {
+        Self {
+            direction: Vec3::X,
+            strength: 0.1,
+            sway_frequency: 0.5,
+            gust_strength: 0.15,
+            gust_speed: 5.0,
+            gust_scale: 8.0,
+        }
+    }
+}
+
+impl Wind {
+    /// No wind at all.
+    pub const CALM: Self = Self {
+        direction: Vec3::X,
+        strength: 0.0,
+        sway_frequency: 0.0,
+        gust_strength: 0.0,
+        gust_speed: 0.0,
+        gust_scale: 1.0,
+    };
+
+    /// Returns `true` if the wind doesn't move anything.
+    pub fn is_calm(&self) -> bool {
+        self.strength == 0.0 && self.gust_strength == 0.0
+    }
+}
+
+/// Type alias for an extended material with a [`WindSway`] extension.
+///
+/// The [`WindPlugin`] sets up this material for [`StandardMaterial`]. For other base materials,
+/// add the [`MaterialPlugin`] for this material and the [`update_wind_materials`] system for the
+/// base material.
+#[expect(type_alias_bounds, reason = "Type alias generics not yet stable")]
+pub type WindMaterial<B: Material> = ExtendedMaterial<B, WindSway>;
+
+/// Material extension swaying the vertices of meshes in the [`Wind`].
+///
+/// How much each vertex moves is its weight, multiplied by the [`flexibility`](Self::flexibility)
+/// of the material. The weight is read from the [`ATTRIBUTE_WIND_WEIGHT`] of the mesh, or else
+/// from the red channel of its vertex colors, as exported by most vegetation tools. Meshes with
+/// neither sway as a whole, which suits small leaves and flowers.
+///
+/// All the vertices of a mesh sway together, out of phase with the meshes around them. The same
+/// displacement is applied in the prepasses, the deferred prepass and shadows, so shadows follow
+/// the meshes and motion vectors are correct.
+///
+/// The sway replaces the vertex shader of the base material, and doesn't support morph targets.
+#[derive(Asset, AsBindGroup, Reflect, Clone, Debug)]
+#[reflect(Default, Debug)]
+#[uniform(400, WindSwayUniform)]
+pub struct WindSway {
+    /// How much the vertices with a weight of `1.0` follow the wind.
+    pub flexibility: f32,
+    /// The wind on the last update.
+    #[reflect(ignore)]
+    wind: Wind,
+    /// The time of the last update, in seconds.
+    #[reflect(ignore)]
+    time: f32,
+    /// The time of the update before, for motion vectors.
+    #[reflect(ignore)]
+    previous_time: f32,
+}
+
+impl Default for WindSway {
+    fn default() -> Self {
+        Self {
+            flexibility: 1.0,
+            wind: Wind::CALM,
+            time: 0.0,
+            previous_time: 0.0,
+        }
+    }
+}
+
+impl WindSway {
+    /// Creates a wind sway with the given [`flexibility`](Self::flexibility).
+    pub fn new(flexibility: f32) -> Self {
+        Self {
+            flexibility,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the wind and the time of the sway.
+    pub fn update(&mut self, wind: &Wind, time: f32) {
+        self.wind.clone_from(wind);
+        self.previous_time = self.time;
+        self.time = time;
+    }
+
+    /// Returns `true` if [`update`](Self::update) would change the sway.
+    ///
+    /// In a calm wind, the vertices stay in place whatever the time.
+    fn needs_update(&self, wind: &Wind) -> bool {
+        self.wind != *wind || !wind.is_calm()
+    }
+}
+
+/// The GPU representation of a [`WindSway`].
+#[derive(Clone, Copy, Default, ShaderType)]
+pub struct WindSwayUniform {
+    /// The normalized direction of the wind.
+    pub direction: Vec3,
+    /// How far the wind bends the vertices, in meters.
+    pub strength: f32,
+    /// How many times per second the meshes sway back and forth.
+    pub sway_frequency: f32,
+    /// How much further gusts bend the vertices, in meters.
+    pub gust_strength: f32,
+    /// How fast gusts travel, in meters per second.
+    pub gust_speed: f32,
+    /// The distance between gusts, in meters.
+    pub gust_scale: f32,
+    /// How much the vertices follow the wind.
+    pub flexibility: f32,
+    /// The time of the sway, in seconds.
+    pub time: f32,
+    /// The time of the sway on the previous update, in seconds.
+    pub previous_time: f32,
+}
+
+impl From<&WindSway> for WindSwayUniform {
+    fn from(sway: &WindSway) -> Self {
+        let wind = &sway.wind;
+        Self {
+            direction: wind.direction.normalize_or_zero(),
+            strength: wind.strength,
+            sway_frequency: wind.sway_frequency,
+            gust_strength: wind.gust_strength,
+            gust_speed: wind.gust_speed,
+            gust_scale: wind.gust_scale.max(f32::EPSILON),
+            flexibility: sway.flexibility,
+            time: sway.time,
+            previous_time: sway.previous_time,
+        }
+    }
+}
+
+impl MaterialExtension for WindSway {
+    fn vertex_shader() -> ShaderRef {
+        WIND_SHADER_HANDLE.into()
+    }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        WIND_PREPASS_SHADER_HANDLE.into()
+    }
+
+    fn deferred_vertex_shader() -> ShaderRef {
+        WIND_PREPASS_SHADER_HANDLE.into()
+    }
+
+    fn vertex_attributes() -> &'static [ExtensionVertexAttribute] {
+        &[ExtensionVertexAttribute {
+            attribute: ATTRIBUTE_WIND_WEIGHT,
+            shader_def: "WIND_WEIGHT",
+        }]
+    }
+}
+
+/// Updates the [`WindSway`] of every [`WindMaterial`] with the base material `B` to the [`Wind`].
+pub fn update_wind_materials<B: Material>(
+    time: Res<Time>,
+    wind: Res<Wind>,
+    mut materials: ResMut<Assets<WindMaterial<B>>>,
+) {
+    // Only mutate the materials that change, so that the others aren't prepared again.
+    let swaying: Vec<_> = materials
+        .iter()
+        .filter(|(_, material)| material.extension.needs_update(&wind))
+        .map(|(id, _)| id)
+        .collect();
+    for id in swaying {
+        if let Some(material) = materials.get_mut(id) {
+            material
+                .extension
+                .update(&wind, time.elapsed_secs_wrapped());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calm_wind_stops_updates() {
+        let wind = Wind {
+            direction: Vec3::new(0.0, 0.0, 2.0),
+            ..Default::default()
+        };
+        let mut sway = WindSway::new(0.5);
+        assert!(sway.needs_update(&wind));
+        sway.update(&wind, 1.0);
+        sway.update(&wind, 1.5);
+        assert!(sway.needs_update(&wind));
+
+        let uniform = WindSwayUniform::from(&sway);
+        assert_eq!(uniform.direction, Vec3::Z);
+        assert_eq!(uniform.flexibility, 0.5);
+        assert_eq!((uniform.previous_time, uniform.time), (1.0, 1.5));
+
+        // The wind calms down, and the vertices move back once.
+        assert!(sway.needs_update(&Wind::CALM));
+        sway.update(&Wind::CALM, 2.0);
+        assert!(!sway.needs_update(&Wind::CALM));
+    }
+}
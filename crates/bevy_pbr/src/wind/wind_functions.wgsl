@@ -0,0 +1,52 @@
+#define_import_path bevy_pbr::wind
+
+#import bevy_render::maths::PI_2
+
+struct WindSway {
+    direction: vec3<f32>,
+    strength: f32,
+    sway_frequency: f32,
+    gust_strength: f32,
+    gust_speed: f32,
+    gust_scale: f32,
+    flexibility: f32,
+    time: f32,
+    previous_time: f32,
+}
+
+@group(2) @binding(400) var<uniform> wind: WindSway;
+
+fn hash(x: f32) -> f32 {
+    return fract(sin(x * 127.1) * 43758.5453);
+}
+
+// Smooth noise between 0 and 1, changing once per unit of `x`.
+fn gust_noise(x: f32) -> f32 {
+    let cell = floor(x);
+    return mix(hash(cell), hash(cell + 1.0), smoothstep(0.0, 1.0, fract(x)));
+}
+
+// Returns the displacement in world space of a vertex with `weight`, of a mesh at `origin`, at
+// `time`. All the vertices of a mesh share the same origin, so they sway together.
+fn displacement(origin: vec3<f32>, weight: f32, time: f32) -> vec3<f32> {
+    // Offsets the sway of neighbouring meshes, so they don't move in lockstep.
+    let phase = dot(origin.xz, vec2(0.37, 0.61));
+    let sway = 0.75 + 0.25 * sin(PI_2 * wind.sway_frequency * time + phase);
+
+    // Gusts travel in the direction of the wind.
+    let along = dot(origin, wind.direction);
+    let gust = gust_noise((along - time * wind.gust_speed) / wind.gust_scale);
+
+    let bend = wind.strength * sway + wind.gust_strength * gust;
+    return wind.direction * (bend * weight * wind.flexibility);
+}
+
+// The displacement of the vertex when the material was last updated.
+fn current_displacement(origin: vec3<f32>, weight: f32) -> vec3<f32> {
+    return displacement(origin, weight, wind.time);
+}
+
+// The displacement of the vertex on the update before, for motion vectors.
+fn previous_displacement(origin: vec3<f32>, weight: f32) -> vec3<f32> {
+    return displacement(origin, weight, wind.previous_time);
+}
@@ -0,0 +1,143 @@
+#import bevy_pbr::{
+    mesh_functions,
+    prepass_io::VertexOutput,
+    skinning,
+    view_transformations::position_world_to_clip,
+    wind,
+}
+
+// The vertex of `prepass_io`, with the weight of the wind.
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+#ifdef VERTEX_UVS_A
+    @location(1) uv: vec2<f32>,
+#endif
+#ifdef VERTEX_UVS_B
+    @location(2) uv_b: vec2<f32>,
+#endif
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    @location(3) normal: vec3<f32>,
+#ifdef VERTEX_TANGENTS
+    @location(4) tangent: vec4<f32>,
+#endif
+#endif // NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#ifdef SKINNED
+    @location(5) joint_indices: vec4<u32>,
+    @location(6) joint_weights: vec4<f32>,
+#endif
+#ifdef VERTEX_COLORS
+    @location(7) color: vec4<f32>,
+#endif
+#ifdef WIND_WEIGHT
+    @location(#{WIND_WEIGHT_LOCATION}) wind_weight: f32,
+#endif
+}
+
+fn wind_weight(vertex: Vertex) -> f32 {
+#ifdef WIND_WEIGHT
+    return vertex.wind_weight;
+#else ifdef VERTEX_COLORS
+    return vertex.color.r;
+#else
+    return 1.0;
+#endif
+}
+
+// Like the vertex shader of `prepass.wgsl`, with the vertices moved by the wind. This is used
+// for the prepasses, the deferred prepass and shadows, so they match the main pass. Morph targets
+// aren't supported.
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let weight = wind_weight(vertex);
+    let mesh_world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+
+#ifdef SKINNED
+    var world_from_local = skinning::skin_model(
+        vertex.joint_indices,
+        vertex.joint_weights,
+        vertex.instance_index
+    );
+#else // SKINNED
+    var world_from_local = mesh_world_from_local;
+#endif // SKINNED
+
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.position, 1.0));
+    out.world_position += vec4(wind::current_displacement(mesh_world_from_local[3].xyz, weight), 0.0);
+    out.position = position_world_to_clip(out.world_position.xyz);
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.unclipped_depth = out.position.z;
+    out.position.z = min(out.position.z, 1.0); // Clamp depth to avoid clipping
+#endif // UNCLIPPED_DEPTH_ORTHO_EMULATION
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif // VERTEX_UVS_A
+
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif // VERTEX_UVS_B
+
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#ifdef SKINNED
+    out.world_normal = skinning::skin_normals(world_from_local, vertex.normal);
+#else // SKINNED
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(
+        vertex.normal,
+        vertex.instance_index
+    );
+#endif // SKINNED
+
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent,
+        vertex.instance_index
+    );
+#endif // VERTEX_TANGENTS
+#endif // NORMAL_PREPASS_OR_DEFERRED_PREPASS
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+#ifdef SKINNED
+#ifdef HAS_PREVIOUS_SKIN
+    let prev_model = skinning::skin_prev_model(
+        vertex.joint_indices,
+        vertex.joint_weights,
+        vertex.instance_index
+    );
+#else   // HAS_PREVIOUS_SKIN
+    let prev_model = mesh_functions::get_previous_world_from_local(vertex.instance_index);
+#endif  // HAS_PREVIOUS_SKIN
+#else   // SKINNED
+    let prev_model = mesh_functions::get_previous_world_from_local(vertex.instance_index);
+#endif  // SKINNED
+
+    // The vertex moves with the wind too, so start from where the wind put it last frame.
+    let prev_mesh_world_from_local = mesh_functions::get_previous_world_from_local(vertex.instance_index);
+    out.previous_world_position = mesh_functions::mesh_position_local_to_world(
+        prev_model,
+        vec4<f32>(vertex.position, 1.0)
+    );
+    out.previous_world_position += vec4(
+        wind::previous_displacement(prev_mesh_world_from_local[3].xyz, weight),
+        0.0
+    );
+#endif // MOTION_VECTOR_PREPASS
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        vertex.instance_index, mesh_world_from_local[3]);
+#endif  // VISIBILITY_RANGE_DITHER
+
+    return out;
+}
@@ -39,7 +39,7 @@ use bevy_reflect::Reflect;
 ///
 /// This includes the most common types in this module, re-exported for your convenience.
 pub mod prelude {
-    pub use super::{ray::RayMap, HitData, PointerHits};
+    pub use super::{ray::RayMap, HitData, HitRegion, PointerHits};
     pub use crate::{
         pointer::{PointerId, PointerLocation},
         PickSet, Pickable,
@@ -109,6 +109,10 @@ pub struct HitData {
     pub position: Option<Vec3>,
     /// The normal vector of the hit test, if the data is available from the backend.
     pub normal: Option<Vec3>,
+    /// The region of the entity that was hit, if the data is available from the backend.
+    ///
+    /// This is reported by the UI backend, for example to resize or drag panels from their edges.
+    pub region: Option<HitRegion>,
 }
 
 impl HitData {
@@ -119,8 +123,111 @@ impl HitData {
             depth,
             position,
             normal,
+            region: None,
+        }
+    }
+
+    /// Sets the [`HitRegion`] of the entity that was hit.
+    pub fn with_region(mut self, region: HitRegion) -> Self {
+        self.region = Some(region);
+        self
+    }
+}
+
+/// The region of a rectangular entity, such as a UI node, that was hit: one of its edges or corners,
+/// or the content area they surround.
+///
+/// How thick the edges are is up to the backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum HitRegion {
+    /// The area inside of the edges.
+    Content,
+    /// The top edge, away from the corners.
+    Top,
+    /// The bottom edge, away from the corners.
+    Bottom,
+    /// The left edge, away from the corners.
+    Left,
+    /// The right edge, away from the corners.
+    Right,
+    /// The corner where the top and left edges meet.
+    TopLeft,
+    /// The corner where the top and right edges meet.
+    TopRight,
+    /// The corner where the bottom and left edges meet.
+    BottomLeft,
+    /// The corner where the bottom and right edges meet.
+    BottomRight,
+}
+
+impl HitRegion {
+    /// Returns the region at the intersection of the edges that were hit.
+    ///
+    /// If the opposite edges of an axis were both hit, neither is taken into account.
+    pub fn from_edges(left: bool, top: bool, right: bool, bottom: bool) -> Self {
+        let horizontal = (left != right).then_some(left);
+        let vertical = (top != bottom).then_some(top);
+        match (horizontal, vertical) {
+            (None, None) => HitRegion::Content,
+            (None, Some(true)) => HitRegion::Top,
+            (None, Some(false)) => HitRegion::Bottom,
+            (Some(true), None) => HitRegion::Left,
+            (Some(false), None) => HitRegion::Right,
+            (Some(true), Some(true)) => HitRegion::TopLeft,
+            (Some(false), Some(true)) => HitRegion::TopRight,
+            (Some(true), Some(false)) => HitRegion::BottomLeft,
+            (Some(false), Some(false)) => HitRegion::BottomRight,
         }
     }
+
+    /// Returns `true` if this is the content area.
+    pub fn is_content(self) -> bool {
+        self == HitRegion::Content
+    }
+
+    /// Returns `true` if this is one of the corners.
+    pub fn is_corner(self) -> bool {
+        matches!(
+            self,
+            HitRegion::TopLeft
+                | HitRegion::TopRight
+                | HitRegion::BottomLeft
+                | HitRegion::BottomRight
+        )
+    }
+
+    /// Returns `true` if this region touches the left edge, including the left corners.
+    pub fn is_left(self) -> bool {
+        matches!(
+            self,
+            HitRegion::Left | HitRegion::TopLeft | HitRegion::BottomLeft
+        )
+    }
+
+    /// Returns `true` if this region touches the right edge, including the right corners.
+    pub fn is_right(self) -> bool {
+        matches!(
+            self,
+            HitRegion::Right | HitRegion::TopRight | HitRegion::BottomRight
+        )
+    }
+
+    /// Returns `true` if this region touches the top edge, including the top corners.
+    pub fn is_top(self) -> bool {
+        matches!(
+            self,
+            HitRegion::Top | HitRegion::TopLeft | HitRegion::TopRight
+        )
+    }
+
+    /// Returns `true` if this region touches the bottom edge, including the bottom corners.
+    pub fn is_bottom(self) -> bool {
+        matches!(
+            self,
+            HitRegion::Bottom | HitRegion::BottomLeft | HitRegion::BottomRight
+        )
+    }
 }
 
 pub mod ray {
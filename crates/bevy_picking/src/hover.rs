@@ -56,6 +56,21 @@ type OverMap = HashMap<PointerId, LayerMap>;
 #[derive(Debug, Deref, DerefMut, Default, Resource)]
 pub struct HoverMap(pub HashMap<PointerId, HashMap<Entity, HitData>>);
 
+impl HoverMap {
+    /// Returns `true` if the pointer is hovering the entity.
+    pub fn is_hovered(&self, pointer_id: PointerId, entity: Entity) -> bool {
+        self.get(&pointer_id)
+            .is_some_and(|hovered| hovered.contains_key(&entity))
+    }
+
+    /// Iterates over the entities hovered by the pointer, in no particular order.
+    pub fn hovered(&self, pointer_id: PointerId) -> impl Iterator<Item = Entity> + '_ {
+        self.get(&pointer_id)
+            .into_iter()
+            .flat_map(|hovered| hovered.keys().copied())
+    }
+}
+
 /// The previous state of the hover map, used to track changes to hover state.
 #[derive(Debug, Deref, DerefMut, Default, Resource)]
 pub struct PreviousHoverMap(pub HashMap<PointerId, HashMap<Entity, HitData>>);
@@ -165,6 +165,7 @@ pub mod input;
 #[cfg(feature = "bevy_mesh_picking_backend")]
 pub mod mesh_picking;
 pub mod pointer;
+pub mod virtual_pointer;
 pub mod window;
 
 use bevy_app::{prelude::*, PluginGroupBuilder};
@@ -276,8 +277,9 @@ pub enum PickSet {
     Last,
 }
 
-/// One plugin that contains the [`PointerInputPlugin`](input::PointerInputPlugin), [`PickingPlugin`]
-/// and the [`InteractionPlugin`], this is probably the plugin that will be most used.
+/// One plugin that contains the [`PointerInputPlugin`](input::PointerInputPlugin), [`PickingPlugin`],
+/// the [`InteractionPlugin`] and the [`VirtualPointerPlugin`](virtual_pointer::VirtualPointerPlugin),
+/// this is probably the plugin that will be most used.
 ///
 /// Note: for any of these plugins to work, they require a picking backend to be active,
 /// The picking backend is responsible to turn an input, into a [`crate::backend::PointerHits`]
@@ -291,6 +293,7 @@ impl PluginGroup for DefaultPickingPlugins {
             .add(input::PointerInputPlugin::default())
             .add(PickingPlugin::default())
             .add(InteractionPlugin)
+            .add(virtual_pointer::VirtualPointerPlugin)
     }
 }
 
@@ -392,7 +395,8 @@ impl Plugin for PickingPlugin {
             .register_type::<pointer::PointerLocation>()
             .register_type::<pointer::PointerPress>()
             .register_type::<pointer::PointerInteraction>()
-            .register_type::<backend::ray::RayId>();
+            .register_type::<backend::ray::RayId>()
+            .register_type::<backend::HitRegion>();
     }
 }
 
@@ -0,0 +1,345 @@
+//! Pointers driven programmatically, such as gamepad-controlled cursors, tutorial playback or
+//! integration tests.
+//!
+//! A [`VirtualPointer`] is a pointer entity with its own [`PointerId`], which isn't tied to a mouse
+//! or touch screen. It's moved and clicked with the [`VirtualPointers`] system parameter, which
+//! sends the same [`PointerInput`] events as the built-in inputs, so virtual pointers hover, click
+//! and drag entities like any other pointer.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::Vec2;
+//! # use bevy_picking::{
+//! #     pointer::{Location, PointerButton},
+//! #     virtual_pointer::{VirtualPointer, VirtualPointers},
+//! # };
+//! # use bevy_render::camera::NormalizedRenderTarget;
+//! #[derive(Resource)]
+//! struct TutorialPointer(Entity);
+//!
+//! fn spawn_tutorial_pointer(mut commands: Commands) {
+//!     let pointer = commands.spawn(VirtualPointer::default()).id();
+//!     commands.insert_resource(TutorialPointer(pointer));
+//! }
+//!
+//! fn click_play_button(
+//!     tutorial_pointer: Res<TutorialPointer>,
+//!     mut pointers: VirtualPointers,
+//!     window: Single<Entity, With<bevy_window::PrimaryWindow>>,
+//! ) {
+//!     let location = Location {
+//!         target: NormalizedRenderTarget::Window(
+//!             bevy_window::WindowRef::Primary.normalize(Some(*window)).unwrap(),
+//!         ),
+//!         position: Vec2::new(400.0, 300.0),
+//!     };
+//!     pointers.move_to(tutorial_pointer.0, location).unwrap();
+//!     pointers.click(tutorial_pointer.0, PointerButton::Primary).unwrap();
+//! }
+//! # bevy_ecs::system::assert_is_system(spawn_tutorial_pointer);
+//! # bevy_ecs::system::assert_is_system(click_play_button);
+//! ```
+//!
+//! Adding a [`GamepadPointer`] to a virtual pointer moves it with a stick of a gamepad, and
+//! presses its buttons with the buttons of the gamepad.
+//!
+//! In tests, the results of the interactions can be checked with the [`HoverMap`], the
+//! [`PointerInteraction`] of the pointer, or the [`Pointer`](crate::events::Pointer) events,
+//! once the picking systems in [`PreUpdate`] have run:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::{events::{Click, Pointer}, hover::HoverMap, pointer::PointerId};
+//! fn assert_clicked(world: &mut World, pointer_id: PointerId, button: Entity) {
+//!     assert!(world.resource::<HoverMap>().is_hovered(pointer_id, button));
+//!     let clicks = world.resource::<Events<Pointer<Click>>>();
+//!     assert!(clicks
+//!         .iter_current_update_events()
+//!         .any(|click| click.pointer_id == pointer_id && click.target == button));
+//! }
+//! ```
+//!
+//! [`HoverMap`]: crate::hover::HoverMap
+//! [`PointerInteraction`]: crate::pointer::PointerInteraction
+
+use bevy_app::prelude::*;
+use bevy_ecs::{entity::EntityBorrow, prelude::*, system::SystemParam};
+use bevy_input::gamepad::{Gamepad, GamepadButton};
+use bevy_math::Vec2;
+use bevy_reflect::prelude::*;
+use bevy_render::camera::NormalizedRenderTarget;
+use bevy_time::Time;
+use bevy_window::Window;
+use core::fmt;
+use uuid::Uuid;
+
+use crate::{
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
+    PickSet,
+};
+
+/// Adds support for [`GamepadPointer`]s.
+///
+/// [`VirtualPointer`]s and [`VirtualPointers`] work without this plugin.
+#[derive(Default)]
+pub struct VirtualPointerPlugin;
+
+impl Plugin for VirtualPointerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(First, move_gamepad_pointers.in_set(PickSet::Input))
+            .register_type::<VirtualPointer>()
+            .register_type::<GamepadPointer>();
+    }
+}
+
+/// A pointer driven programmatically with [`VirtualPointers`].
+///
+/// Spawning this component creates a new pointer, with a unique [`PointerId::Custom`] id.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(PointerId(|| PointerId::Custom(Uuid::new_v4())))]
+pub struct VirtualPointer {
+    location: Option<Location>,
+}
+
+impl VirtualPointer {
+    /// Returns the location the pointer was last moved to, if any.
+    ///
+    /// This is updated as soon as the pointer is moved, while its
+    /// [`PointerLocation`](crate::pointer::PointerLocation) is only updated once the
+    /// [`PointerInput`] events are processed.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+/// A [`SystemParam`] to move and press the buttons of [`VirtualPointer`]s.
+///
+/// Each method sends a [`PointerInput`] event, which is processed in [`PreUpdate`]. Sending the
+/// events in [`First`], in [`PickSet::Input`], lets them be processed in the same frame.
+///
+/// The methods return an error if `pointer` isn't a [`VirtualPointer`].
+#[derive(SystemParam)]
+pub struct VirtualPointers<'w, 's> {
+    pointers: Query<'w, 's, (&'static PointerId, &'static mut VirtualPointer)>,
+    events: EventWriter<'w, PointerInput>,
+}
+
+impl VirtualPointers<'_, '_> {
+    /// Moves the pointer to `location`.
+    pub fn move_to(
+        &mut self,
+        pointer: Entity,
+        location: Location,
+    ) -> Result<(), NotAVirtualPointer> {
+        let (pointer_id, mut virtual_pointer) = self
+            .pointers
+            .get_mut(pointer)
+            .map_err(|_| NotAVirtualPointer(pointer))?;
+        let delta = match &virtual_pointer.location {
+            Some(previous) if previous.target == location.target => {
+                location.position - previous.position
+            }
+            _ => Vec2::ZERO,
+        };
+        virtual_pointer.location = Some(location.clone());
+        self.events.send(PointerInput::new(
+            *pointer_id,
+            location,
+            PointerAction::Move { delta },
+        ));
+        Ok(())
+    }
+
+    /// Moves the pointer by `delta`, on the render target it's on.
+    ///
+    /// This does nothing if the pointer was never moved to a location with
+    /// [`move_to`](Self::move_to).
+    pub fn move_by(&mut self, pointer: Entity, delta: Vec2) -> Result<(), NotAVirtualPointer> {
+        let (_, virtual_pointer) = self
+            .pointers
+            .get(pointer)
+            .map_err(|_| NotAVirtualPointer(pointer))?;
+        let Some(mut location) = virtual_pointer.location.clone() else {
+            return Ok(());
+        };
+        location.position += delta;
+        self.move_to(pointer, location)
+    }
+
+    /// Presses a button of the pointer, at its current location.
+    ///
+    /// This does nothing if the pointer was never moved to a location.
+    pub fn press(
+        &mut self,
+        pointer: Entity,
+        button: PointerButton,
+    ) -> Result<(), NotAVirtualPointer> {
+        self.send(pointer, PointerAction::Press(button))
+    }
+
+    /// Releases a button of the pointer, at its current location.
+    ///
+    /// This does nothing if the pointer was never moved to a location.
+    pub fn release(
+        &mut self,
+        pointer: Entity,
+        button: PointerButton,
+    ) -> Result<(), NotAVirtualPointer> {
+        self.send(pointer, PointerAction::Release(button))
+    }
+
+    /// Presses and releases a button of the pointer, at its current location.
+    ///
+    /// This does nothing if the pointer was never moved to a location.
+    pub fn click(
+        &mut self,
+        pointer: Entity,
+        button: PointerButton,
+    ) -> Result<(), NotAVirtualPointer> {
+        self.press(pointer, button)?;
+        self.release(pointer, button)
+    }
+
+    /// Cancels the current interactions of the pointer, such as drags.
+    ///
+    /// This does nothing if the pointer was never moved to a location.
+    pub fn cancel(&mut self, pointer: Entity) -> Result<(), NotAVirtualPointer> {
+        self.send(pointer, PointerAction::Cancel)
+    }
+
+    fn send(&mut self, pointer: Entity, action: PointerAction) -> Result<(), NotAVirtualPointer> {
+        let (pointer_id, virtual_pointer) = self
+            .pointers
+            .get(pointer)
+            .map_err(|_| NotAVirtualPointer(pointer))?;
+        if let Some(location) = virtual_pointer.location.clone() {
+            self.events
+                .send(PointerInput::new(*pointer_id, location, action));
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`VirtualPointers`] when an entity isn't a [`VirtualPointer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotAVirtualPointer(pub Entity);
+
+impl fmt::Display for NotAVirtualPointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "entity {} isn't a virtual pointer", self.0)
+    }
+}
+
+impl core::error::Error for NotAVirtualPointer {}
+
+/// Controls a [`VirtualPointer`] with a gamepad.
+///
+/// The stick sets the velocity of the pointer, which stays within the window it's on. The buttons
+/// of the gamepad press the buttons of the pointer. The pointer must be moved to its initial
+/// location with [`VirtualPointers::move_to`].
+///
+/// This requires the [`VirtualPointerPlugin`].
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug)]
+#[require(VirtualPointer)]
+pub struct GamepadPointer {
+    /// The entity of the [`Gamepad`] controlling the pointer.
+    pub gamepad: Entity,
+    /// The stick moving the pointer.
+    pub stick: GamepadPointerStick,
+    /// The speed of the pointer when the stick is fully tilted, in logical pixels per second.
+    pub speed: f32,
+    /// The tilt of the stick below which the pointer doesn't move, between 0 and 1.
+    pub dead_zone: f32,
+    /// The gamepad button pressing [`PointerButton::Primary`].
+    pub primary_button: GamepadButton,
+    /// The gamepad button pressing [`PointerButton::Secondary`].
+    pub secondary_button: GamepadButton,
+}
+
+impl GamepadPointer {
+    /// Creates a [`GamepadPointer`] controlled by `gamepad`, with the default settings.
+    pub fn new(gamepad: Entity) -> Self {
+        Self {
+            gamepad,
+            stick: GamepadPointerStick::default(),
+            speed: 800.0,
+            dead_zone: 0.1,
+            primary_button: GamepadButton::South,
+            secondary_button: GamepadButton::East,
+        }
+    }
+
+    /// Returns the velocity of the pointer for a tilt of the stick, in logical pixels per second.
+    ///
+    /// The tilt is rescaled so the speed grows from 0 at the edge of the dead zone, and the Y axis
+    /// is flipped, since the Y axis of a stick points up, while the one of the pointer points down.
+    pub fn velocity(&self, stick: Vec2) -> Vec2 {
+        let tilt = stick.length().min(1.0);
+        if tilt <= self.dead_zone {
+            return Vec2::ZERO;
+        }
+        let speed = (tilt - self.dead_zone) / (1.0 - self.dead_zone) * self.speed;
+        Vec2::new(stick.x, -stick.y).normalize_or_zero() * speed
+    }
+}
+
+/// The stick of a gamepad moving a [`GamepadPointer`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum GamepadPointerStick {
+    /// The left stick.
+    #[default]
+    Left,
+    /// The right stick.
+    Right,
+}
+
+/// Moves the [`GamepadPointer`]s with their gamepads, and presses their buttons.
+pub fn move_gamepad_pointers(
+    gamepad_pointers: Query<(Entity, &GamepadPointer)>,
+    gamepads: Query<&Gamepad>,
+    windows: Query<&Window>,
+    time: Res<Time>,
+    mut pointers: VirtualPointers,
+) {
+    for (entity, gamepad_pointer) in &gamepad_pointers {
+        let Ok(gamepad) = gamepads.get(gamepad_pointer.gamepad) else {
+            continue;
+        };
+        let Ok((_, virtual_pointer)) = pointers.pointers.get(entity) else {
+            continue;
+        };
+        let Some(mut location) = virtual_pointer.location.clone() else {
+            continue;
+        };
+
+        let stick = match gamepad_pointer.stick {
+            GamepadPointerStick::Left => gamepad.left_stick(),
+            GamepadPointerStick::Right => gamepad.right_stick(),
+        };
+        let velocity = gamepad_pointer.velocity(stick);
+        if velocity != Vec2::ZERO {
+            location.position += velocity * time.delta_secs();
+            if let NormalizedRenderTarget::Window(window) = &location.target {
+                if let Ok(window) = windows.get(window.entity()) {
+                    location.position = location.position.clamp(Vec2::ZERO, window.size());
+                }
+            }
+            let _ = pointers.move_to(entity, location);
+        }
+
+        for (gamepad_button, button) in [
+            (gamepad_pointer.primary_button, PointerButton::Primary),
+            (gamepad_pointer.secondary_button, PointerButton::Secondary),
+        ] {
+            if gamepad.just_pressed(gamepad_button) {
+                let _ = pointers.press(entity, button);
+            }
+            if gamepad.just_released(gamepad_button) {
+                let _ = pointers.release(entity, button);
+            }
+        }
+    }
+}
@@ -481,6 +481,7 @@ impl<'a> ReflectMeta<'a> {
             where_clause_options,
             None,
             Option::<core::iter::Empty<&Type>>::None,
+            None,
         )
     }
 
@@ -571,6 +572,7 @@ impl<'a> ReflectStruct<'a> {
             where_clause_options,
             self.serialization_data(),
             Some(self.active_types().iter()),
+            None,
         )
     }
 
@@ -714,6 +716,7 @@ impl<'a> ReflectEnum<'a> {
             where_clause_options,
             None,
             Some(self.active_fields().map(StructField::reflected_type)),
+            crate::enum_utility::impl_variant_default_data(self),
         )
     }
 
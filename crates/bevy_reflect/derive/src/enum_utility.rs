@@ -2,8 +2,8 @@ use crate::{
     derive_data::ReflectEnum, derive_data::StructField, field_attributes::DefaultBehavior,
     ident::ident_or_index,
 };
-use bevy_macro_utils::fq_std::{FQDefault, FQOption};
-use proc_macro2::{Ident, TokenStream};
+use bevy_macro_utils::fq_std::{FQBox, FQDefault, FQOption};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
 
 pub(crate) struct EnumVariantOutputData {
@@ -275,3 +275,126 @@ impl<'a> VariantBuilder for TryApplyVariantBuilder<'a> {
         }
     }
 }
+
+/// Generates the enum variant output data needed to build the `ReflectVariantDefault` type data.
+pub(crate) struct DefaultVariantBuilder<'a> {
+    reflect_enum: &'a ReflectEnum<'a>,
+}
+
+impl<'a> DefaultVariantBuilder<'a> {
+    pub fn new(reflect_enum: &'a ReflectEnum) -> Self {
+        Self { reflect_enum }
+    }
+}
+
+impl<'a> VariantBuilder for DefaultVariantBuilder<'a> {
+    fn reflect_enum(&self) -> &ReflectEnum {
+        self.reflect_enum
+    }
+
+    fn access_field(&self, registry: &Ident, field: VariantField) -> TokenStream {
+        let bevy_reflect_path = self.reflect_enum.meta().bevy_reflect_path();
+        let field_ty = field.field.reflected_type();
+
+        quote! {
+            #registry
+                .get_type_data::<#bevy_reflect_path::std_traits::ReflectDefault>(
+                    ::core::any::TypeId::of::<#field_ty>()
+                )
+                .map(#bevy_reflect_path::std_traits::ReflectDefault::default)
+        }
+    }
+
+    fn unwrap_field(&self, field: VariantField) -> TokenStream {
+        let alias = field.alias;
+        quote!(#alias?)
+    }
+
+    fn construct_field(&self, field: VariantField) -> TokenStream {
+        let bevy_reflect_path = self.reflect_enum.meta().bevy_reflect_path();
+        let field_ty = field.field.reflected_type();
+        let alias = field.alias;
+
+        quote! {
+            <#field_ty as #bevy_reflect_path::FromReflect>::from_reflect(
+                #bevy_reflect_path::PartialReflect::as_partial_reflect(&*#alias)
+            )?
+        }
+    }
+
+    fn on_active_field(&self, registry: &Ident, field: VariantField) -> TokenStream {
+        let bevy_reflect_path = self.reflect_enum.meta().bevy_reflect_path();
+        let field_ty = field.field.reflected_type();
+
+        // The default of the field takes precedence over the default of its type.
+        let construction = match &field.field.attrs.default {
+            DefaultBehavior::Func(path) => quote! { #path() },
+            DefaultBehavior::Default => quote! { #FQDefault::default() },
+            DefaultBehavior::Required => {
+                let alias = field.alias;
+                let field_accessor = self.access_field(registry, field);
+                let field_unwrapper = self.unwrap_field(field);
+                let field_constructor = self.construct_field(field);
+
+                quote! {{
+                    let #alias = #field_accessor;
+                    let #alias = #field_unwrapper;
+                    #field_constructor
+                }}
+            }
+        };
+
+        if field.field.attrs().remote.is_some() {
+            quote! {
+                <#field_ty as #bevy_reflect_path::ReflectRemote>::into_remote(#construction)
+            }
+        } else {
+            construction
+        }
+    }
+}
+
+/// Generates the registration of the `ReflectVariantDefault` type data, constructing each variant
+/// with the default values of its fields.
+///
+/// Returns `None` if the enum doesn't derive `FromReflect`, which the construction relies on.
+pub(crate) fn impl_variant_default_data(reflect_enum: &ReflectEnum) -> Option<TokenStream> {
+    if !reflect_enum.meta().from_reflect().should_auto_derive() {
+        return None;
+    }
+
+    let bevy_reflect_path = reflect_enum.meta().bevy_reflect_path();
+    let registry = Ident::new("__registry", Span::call_site());
+
+    let EnumVariantOutputData {
+        variant_constructors,
+        ..
+    } = DefaultVariantBuilder::new(reflect_enum).build(&registry);
+
+    let constructor = if variant_constructors.is_empty() {
+        quote!(|_, _| #FQOption::None)
+    } else {
+        let variant_indices = 0..variant_constructors.len();
+        let variant_constructors = if reflect_enum.meta().is_remote_wrapper() {
+            quote!(#(#variant_indices => Self(#variant_constructors),)*)
+        } else {
+            quote!(#(#variant_indices => #variant_constructors,)*)
+        };
+
+        quote! {
+            |__index, #registry| {
+                let value: Self = match __index {
+                    #variant_constructors
+                    _ => return #FQOption::None,
+                };
+                #FQOption::Some(#FQBox::new(value))
+            }
+        }
+    };
+
+    Some(quote! {
+        registration.insert::<#bevy_reflect_path::ReflectVariantDefault>(
+            #bevy_reflect_path::ReflectVariantDefault::new(#constructor)
+        );
+    })
+}
@@ -151,6 +151,7 @@ fn match_reflect_impls(ast: DeriveInput, source: ReflectImplSource) -> TokenStre
 /// * `ReflectFromReflect` (unless opting out of `FromReflect`)
 /// * `SerializationData`
 /// * `ReflectFromPtr`
+/// * `ReflectVariantDefault` for enums (unless opting out of `FromReflect`)
 ///
 /// ### Special Identifiers
 ///
@@ -195,7 +196,8 @@ fn match_reflect_impls(ast: DeriveInput, source: ReflectImplSource) -> TokenStre
 /// This is useful for when a type can't or shouldn't implement `FromReflect`,
 /// or if a manual implementation is desired.
 ///
-/// Note that in the latter case, `ReflectFromReflect` will no longer be automatically registered.
+/// Note that in the latter case, `ReflectFromReflect` and `ReflectVariantDefault` will no longer be
+/// automatically registered.
 ///
 /// ## `#[reflect(type_path = false)]`
 ///
@@ -13,6 +13,7 @@ pub(crate) fn impl_get_type_registration<'a>(
     where_clause_options: &WhereClauseOptions,
     serialization_data: Option<&SerializationDataDef>,
     type_dependencies: Option<impl Iterator<Item = &'a Type>>,
+    variant_default_data: Option<proc_macro2::TokenStream>,
 ) -> proc_macro2::TokenStream {
     let type_path = meta.type_path();
     let bevy_reflect_path = meta.bevy_reflect_path();
@@ -53,6 +54,7 @@ pub(crate) fn impl_get_type_registration<'a>(
                 registration.insert::<#bevy_reflect_path::ReflectFromPtr>(#bevy_reflect_path::FromType::<Self>::from_type());
                 #from_reflect_data
                 #serialization_data
+                #variant_default_data
                 #(registration.insert::<#registration_data>(#bevy_reflect_path::FromType::<Self>::from_type());)*
                 registration
             }
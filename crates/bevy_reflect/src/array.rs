@@ -23,10 +23,10 @@ use core::{
 /// this trait does not make any guarantees that the implementor's elements
 /// are homogeneous (i.e. all the same type).
 ///
-/// This trait has a blanket implementation over Rust arrays of up to 32 items.
-/// This implementation can technically contain more than 32,
-/// but the blanket [`GetTypeRegistration`] is only implemented up to the 32
-/// item limit due to a [limitation] on [`Deserialize`].
+/// This trait has a blanket implementation over Rust arrays of any length,
+/// along with [`FromReflect`], [`Typed`] and [`GetTypeRegistration`].
+/// Arrays are serialized through reflection rather than with [`Deserialize`],
+/// so they aren't subject to its [limitation] to 32 items either.
 ///
 /// # Example
 ///
@@ -44,6 +44,8 @@ use core::{
 /// [reflection]: crate
 /// [`List`]: crate::List
 /// [type-erasing]: https://doc.rust-lang.org/book/ch17-02-trait-objects.html
+/// [`FromReflect`]: crate::FromReflect
+/// [`Typed`]: crate::Typed
 /// [`GetTypeRegistration`]: crate::GetTypeRegistration
 /// [limitation]: https://github.com/serde-rs/serde/issues/1937
 /// [`Deserialize`]: ::serde::Deserialize
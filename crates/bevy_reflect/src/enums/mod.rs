@@ -1,11 +1,13 @@
 mod dynamic_enum;
 mod enum_trait;
 mod helpers;
+mod variant_default;
 mod variants;
 
 pub use dynamic_enum::*;
 pub use enum_trait::*;
 pub use helpers::*;
+pub use variant_default::*;
 pub use variants::*;
 
 #[cfg(test)]
@@ -691,4 +693,75 @@ mod tests {
             "expected TestEnum::C{{value: 123}} != TestEnum::C2{{value: 1.23}}"
         );
     }
+
+    #[test]
+    fn enum_should_construct_default_variants() {
+        #[derive(Reflect, Debug, PartialEq)]
+        struct NoDefault(usize);
+
+        fn default_value() -> usize {
+            123
+        }
+
+        #[derive(Reflect, Debug, PartialEq)]
+        enum TestEnum {
+            A,
+            B(usize, #[reflect(default = "default_value")] usize),
+            C { value: f32, flag: bool },
+            D(NoDefault),
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<TestEnum>();
+        let variant_default = registry
+            .get_type_data::<ReflectVariantDefault>(core::any::TypeId::of::<TestEnum>())
+            .unwrap();
+
+        let default_variant = |index| {
+            variant_default
+                .default_variant(index, &registry)
+                .map(|value| value.take::<TestEnum>().unwrap())
+        };
+        assert_eq!(Some(TestEnum::A), default_variant(0));
+        assert_eq!(Some(TestEnum::B(0, 123)), default_variant(1));
+        assert_eq!(
+            Some(TestEnum::C {
+                value: 0.0,
+                flag: false
+            }),
+            default_variant(2)
+        );
+        assert_eq!(None, default_variant(3));
+        assert_eq!(None, default_variant(4));
+
+        let mut value = TestEnum::B(1, 2);
+        variant_default
+            .set_variant(&mut value, 1, &registry)
+            .unwrap();
+        assert_eq!(TestEnum::B(1, 2), value);
+        variant_default
+            .set_variant(&mut value, 2, &registry)
+            .unwrap();
+        assert_eq!(
+            TestEnum::C {
+                value: 0.0,
+                flag: false
+            },
+            value
+        );
+        assert!(matches!(
+            variant_default.set_variant(&mut value, 3, &registry),
+            Err(VariantDefaultError::NoDefault { index: 3, .. })
+        ));
+        assert_eq!(2, value.variant_index());
+        assert_eq!(
+            2,
+            TestEnum::type_info()
+                .as_enum()
+                .unwrap()
+                .variant_at(2)
+                .unwrap()
+                .field_len()
+        );
+    }
 }
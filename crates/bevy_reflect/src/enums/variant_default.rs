@@ -0,0 +1,108 @@
+use crate::{ApplyError, PartialReflect, Reflect, ReflectRef, TypeRegistry};
+use alloc::boxed::Box;
+use thiserror::Error;
+
+/// Type data constructing the variants of an enum, with default values for their fields.
+///
+/// This is registered by `#[derive(Reflect)]` for enums which derive [`FromReflect`]. Each field of
+/// the constructed variant takes its `#[reflect(default)]` value if it has one, or else the value
+/// from the [`ReflectDefault`] registered for its type. A variant can't be constructed if one of
+/// its fields has neither.
+///
+/// Along with [`EnumInfo`], this allows to list the variants of an enum and to switch between them
+/// dynamically, for example from a dropdown in an editor.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Enum, Reflect, ReflectVariantDefault, TypeRegistry};
+/// #[derive(Reflect, Debug, PartialEq)]
+/// enum Shape {
+///     Circle { radius: f32 },
+///     Square(f32),
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Shape>();
+/// let variant_default = registry.get_type_data::<ReflectVariantDefault>(core::any::TypeId::of::<Shape>()).unwrap();
+///
+/// let square = variant_default.default_variant(1, &registry).unwrap();
+/// assert_eq!(square.downcast_ref::<Shape>(), Some(&Shape::Square(0.0)));
+///
+/// let mut shape = Shape::Square(2.0);
+/// variant_default.set_variant(&mut shape, 0, &registry).unwrap();
+/// assert_eq!(shape, Shape::Circle { radius: 0.0 });
+/// assert_eq!(shape.variant_index(), 0);
+/// ```
+///
+/// [`FromReflect`]: crate::FromReflect
+/// [`ReflectDefault`]: crate::std_traits::ReflectDefault
+/// [`EnumInfo`]: crate::EnumInfo
+#[derive(Clone)]
+pub struct ReflectVariantDefault {
+    default_variant: fn(usize, &TypeRegistry) -> Option<Box<dyn Reflect>>,
+}
+
+/// An error returned by [`ReflectVariantDefault::set_variant`].
+#[derive(Debug, Error)]
+pub enum VariantDefaultError {
+    /// The variant doesn't exist, or one of its fields has no default value.
+    #[error("variant at index {index} of `{type_path}` can't be constructed with default values")]
+    NoDefault {
+        /// The type path of the enum.
+        type_path: Box<str>,
+        /// The index of the variant.
+        index: usize,
+    },
+    /// The constructed variant couldn't be applied to the value.
+    #[error(transparent)]
+    Apply(#[from] ApplyError),
+}
+
+impl ReflectVariantDefault {
+    /// Creates the type data from a function constructing the variant at the given index.
+    ///
+    /// This is used by `#[derive(Reflect)]`, and can be used for manual [`Enum`] implementations.
+    ///
+    /// [`Enum`]: crate::Enum
+    pub fn new(default_variant: fn(usize, &TypeRegistry) -> Option<Box<dyn Reflect>>) -> Self {
+        Self { default_variant }
+    }
+
+    /// Returns the variant at `index`, with default values for its fields.
+    ///
+    /// Returns `None` if there is no variant at `index`, or if one of its fields has no default
+    /// value.
+    pub fn default_variant(
+        &self,
+        index: usize,
+        registry: &TypeRegistry,
+    ) -> Option<Box<dyn Reflect>> {
+        (self.default_variant)(index, registry)
+    }
+
+    /// Switches `value` to the variant at `index`, with default values for its fields.
+    ///
+    /// If `value` already is this variant, it is left unchanged.
+    pub fn set_variant(
+        &self,
+        value: &mut dyn PartialReflect,
+        index: usize,
+        registry: &TypeRegistry,
+    ) -> Result<(), VariantDefaultError> {
+        if let ReflectRef::Enum(value) = value.reflect_ref() {
+            if value.variant_index() == index {
+                return Ok(());
+            }
+        }
+
+        let variant = self.default_variant(index, registry).ok_or_else(|| {
+            VariantDefaultError::NoDefault {
+                type_path: value.reflect_type_path().into(),
+                index,
+            }
+        })?;
+        value.try_apply(variant.as_partial_reflect())?;
+        Ok(())
+    }
+}
@@ -113,6 +113,15 @@ impl VariantInfo {
         }
     }
 
+    /// Returns the number of fields of this variant.
+    pub fn field_len(&self) -> usize {
+        match self {
+            Self::Struct(info) => info.field_len(),
+            Self::Tuple(info) => info.field_len(),
+            Self::Unit(_) => 0,
+        }
+    }
+
     impl_custom_attribute_methods!(
         self,
         match self {
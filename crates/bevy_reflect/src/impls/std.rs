@@ -1486,8 +1486,11 @@ impl<T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration, const N: usiz
 {
     fn from_reflect(reflect: &dyn PartialReflect) -> Option<Self> {
         let ref_array = reflect.reflect_ref().as_array().ok()?;
+        if ref_array.len() != N {
+            return None;
+        }
 
-        let mut temp_vec = Vec::with_capacity(ref_array.len());
+        let mut temp_vec = Vec::with_capacity(N);
 
         for field in ref_array.iter() {
             temp_vec.push(T::from_reflect(field)?);
@@ -2442,7 +2445,7 @@ crate::func::macros::impl_function_traits!(&'static Location<'static>);
 mod tests {
     use crate::{
         self as bevy_reflect, Enum, FromReflect, PartialReflect, Reflect, ReflectSerialize,
-        TypeInfo, TypeRegistry, Typed, VariantInfo, VariantType,
+        TypeInfo, TypePath, TypeRegistry, Typed, VariantInfo, VariantType,
     };
     use alloc::{collections::BTreeMap, string::String, vec};
     use bevy_platform_support::collections::HashMap;
@@ -2661,6 +2664,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn large_array_should_reflect() {
+        let array = [7_u16; 100];
+
+        let TypeInfo::Array(info) = <[u16; 100]>::type_info() else {
+            panic!("expected `TypeInfo::Array`");
+        };
+        assert_eq!(info.capacity(), 100);
+        assert_eq!(<[u16; 100]>::type_path(), "[u16; 100]");
+
+        let dynamic = array.clone_value();
+        assert_eq!(<[u16; 100]>::from_reflect(&*dynamic), Some(array));
+        assert_eq!(<[u16; 99]>::from_reflect(&*dynamic), None);
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<[u16; 100]>();
+        assert!(registry.contains(core::any::TypeId::of::<[u16; 100]>()));
+    }
+
     #[test]
     fn nonzero_usize_impl_reflect_from_reflect() {
         let a: &dyn PartialReflect = &core::num::NonZero::<usize>::new(42).unwrap();
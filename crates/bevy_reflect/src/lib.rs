@@ -575,6 +575,7 @@ mod map;
 mod path;
 mod reflect;
 mod reflectable;
+mod registry_snapshot;
 mod remote;
 mod set;
 mod struct_trait;
@@ -610,6 +611,7 @@ pub mod std_traits;
 #[cfg(feature = "debug_stack")]
 mod type_info_stack;
 pub mod utility;
+pub mod validation;
 
 /// The reflect prelude.
 ///
@@ -639,6 +641,7 @@ pub use map::*;
 pub use path::*;
 pub use reflect::*;
 pub use reflectable::*;
+pub use registry_snapshot::*;
 pub use remote::*;
 pub use set::*;
 pub use struct_trait::*;
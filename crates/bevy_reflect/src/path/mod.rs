@@ -8,6 +8,9 @@ mod parse;
 pub use parse::ParseError;
 use parse::PathParser;
 
+mod query;
+pub use query::*;
+
 use crate::{PartialReflect, Reflect};
 use alloc::vec::Vec;
 use core::fmt;
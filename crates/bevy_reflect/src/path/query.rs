@@ -0,0 +1,498 @@
+//! Queries matching several elements of a reflected value, with wildcards and filters.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::cmp::Ordering;
+use thiserror::Error;
+
+use super::{ParsedPath, ReflectPath, ReflectPathError};
+use crate::{PartialReflect, ReflectMut, ReflectRef};
+
+/// An error returned when parsing a [`PathQuery`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PathQueryError<'a> {
+    /// A part of the query without wildcards or filters isn't a valid path.
+    #[error(transparent)]
+    InvalidPath(ReflectPathError<'a>),
+
+    /// A filter wasn't closed by `)]`.
+    #[error("the filter at offset {0} wasn't closed, reached end of query before finding a `)]`")]
+    UnclosedFilter(usize),
+
+    /// A filter couldn't be parsed.
+    #[error("invalid filter `{filter}`: {reason}")]
+    InvalidFilter {
+        /// The content of the filter.
+        filter: &'a str,
+        /// Why the filter is invalid.
+        reason: &'static str,
+    },
+}
+
+impl<'a> From<ReflectPathError<'a>> for PathQueryError<'a> {
+    fn from(value: ReflectPathError<'a>) -> Self {
+        PathQueryError::InvalidPath(value)
+    }
+}
+
+/// A path matching any number of elements within a reflected value.
+///
+/// Unlike a [`ParsedPath`], which targets a single element, a query may contain wildcards
+/// and filters, making it useful for tooling that needs to inspect or edit many values at once.
+///
+/// # Syntax
+///
+/// A query uses the syntax described in [`GetPath`](super::GetPath), extended with:
+/// - `[*]`: every element of a list or array, or every value of a map.
+/// - `.*`: every field of a struct, tuple struct, tuple or enum variant.
+/// - `[?(@.path)]`: the elements matched by `[*]` for which `.path` exists, and is `true` if it's a `bool`.
+/// - `[?(@.path <op> <literal>)]`: the elements matched by `[*]` for which `.path` compares to the literal.
+///   `<op>` is one of `==`, `!=`, `<`, `<=`, `>` or `>=`, and the literal is a number, a quoted string,
+///   or `true` or `false`. Use `@` alone to compare the element itself.
+///
+/// Elements for which the rest of the query can't be resolved, such as a list element
+/// missing a field, are skipped rather than causing an error.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{PathQuery, Reflect};
+/// #[derive(Reflect)]
+/// struct Shop {
+///     items: Vec<Item>,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Item {
+///     price: f32,
+///     on_sale: bool,
+/// }
+///
+/// let mut shop = Shop {
+///     items: vec![
+///         Item { price: 10.0, on_sale: true },
+///         Item { price: 25.0, on_sale: false },
+///         Item { price: 40.0, on_sale: true },
+///     ],
+/// };
+///
+/// let prices = PathQuery::parse("items[*].price").unwrap();
+/// assert_eq!(prices.iter(&shop).count(), 3);
+///
+/// // Apply a discount to the items on sale.
+/// let on_sale = PathQuery::parse("items[?(@.on_sale)].price").unwrap();
+/// on_sale.for_each_mut(&mut shop, |price| {
+///     *price.try_downcast_mut::<f32>().unwrap() *= 0.5;
+/// });
+///
+/// let cheap = PathQuery::parse("items[?(@.price < 15)]").unwrap();
+/// assert_eq!(cheap.iter(&shop).count(), 1);
+/// assert_eq!(shop.items[2].price, 20.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathQuery(Vec<QuerySegment>);
+
+#[derive(Clone, Debug, PartialEq)]
+enum QuerySegment {
+    Path(ParsedPath),
+    Elements,
+    Fields,
+    Filter(Predicate),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Predicate {
+    path: ParsedPath,
+    comparison: Option<(Comparison, Literal)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Literal {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl PathQuery {
+    /// Parses a [`PathQuery`] from a string.
+    ///
+    /// See the [type-level documentation](PathQuery) for the syntax.
+    pub fn parse(query: &str) -> Result<Self, PathQueryError> {
+        let mut segments = Vec::new();
+        let mut rest = query;
+        while !rest.is_empty() {
+            if let Some(remaining) = rest.strip_prefix("[*]") {
+                segments.push(QuerySegment::Elements);
+                rest = remaining;
+            } else if let Some(remaining) = rest.strip_prefix(".*") {
+                segments.push(QuerySegment::Fields);
+                rest = remaining;
+            } else if let Some(remaining) = rest.strip_prefix("[?(") {
+                let offset = query.len() - rest.len();
+                let end =
+                    find_filter_end(remaining).ok_or(PathQueryError::UnclosedFilter(offset))?;
+                segments.push(QuerySegment::Filter(Predicate::parse(&remaining[..end])?));
+                rest = &remaining[end + 2..];
+            } else {
+                let end = ["[*]", ".*", "[?("]
+                    .iter()
+                    .filter_map(|special| rest.find(special))
+                    .min()
+                    .unwrap_or(rest.len());
+                segments.push(QuerySegment::Path(ParsedPath::parse(&rest[..end])?));
+                rest = &rest[end..];
+            }
+        }
+        Ok(Self(segments))
+    }
+
+    /// Returns an iterator over the elements of `root` matched by this query.
+    pub fn iter<'r>(
+        &self,
+        root: &'r dyn PartialReflect,
+    ) -> impl Iterator<Item = &'r dyn PartialReflect> {
+        let mut matches = vec![root];
+        for segment in &self.0 {
+            matches = matches
+                .into_iter()
+                .flat_map(|value| segment.apply(value))
+                .collect();
+        }
+        matches.into_iter()
+    }
+
+    /// Calls `f` on every element of `root` matched by this query, and returns the number of matches.
+    ///
+    /// Reflected values can't hand out several mutable borrows of their elements at once,
+    /// so the matches are visited one at a time rather than returned as an iterator.
+    pub fn for_each_mut(
+        &self,
+        root: &mut dyn PartialReflect,
+        mut f: impl FnMut(&mut dyn PartialReflect),
+    ) -> usize {
+        visit_mut(&self.0, root, &mut f)
+    }
+}
+
+impl QuerySegment {
+    fn apply<'r>(&self, value: &'r dyn PartialReflect) -> Vec<&'r dyn PartialReflect> {
+        match self {
+            Self::Path(path) => path.reflect_element(value).into_iter().collect(),
+            Self::Elements => elements(value),
+            Self::Filter(predicate) => elements(value)
+                .into_iter()
+                .filter(|element| predicate.matches(*element))
+                .collect(),
+            Self::Fields => match value.reflect_ref() {
+                ReflectRef::Struct(value) => value.iter_fields().collect(),
+                ReflectRef::TupleStruct(value) => value.iter_fields().collect(),
+                ReflectRef::Tuple(value) => value.iter_fields().collect(),
+                ReflectRef::Enum(value) => value.iter_fields().map(|field| field.value()).collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+/// Returns the elements of a list or array, or the values of a map.
+fn elements(value: &dyn PartialReflect) -> Vec<&dyn PartialReflect> {
+    match value.reflect_ref() {
+        ReflectRef::List(list) => list.iter().collect(),
+        ReflectRef::Array(array) => array.iter().collect(),
+        ReflectRef::Map(map) => map.iter().map(|(_, value)| value).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn visit_mut(
+    segments: &[QuerySegment],
+    value: &mut dyn PartialReflect,
+    f: &mut dyn FnMut(&mut dyn PartialReflect),
+) -> usize {
+    let Some((segment, rest)) = segments.split_first() else {
+        f(value);
+        return 1;
+    };
+
+    if let QuerySegment::Path(path) = segment {
+        return match path.reflect_element_mut(value) {
+            Ok(element) => visit_mut(rest, element, f),
+            Err(_) => 0,
+        };
+    }
+
+    let mut count = 0;
+    let mut visit = |element: &mut dyn PartialReflect| {
+        let matches = match segment {
+            QuerySegment::Filter(predicate) => predicate.matches(element),
+            _ => true,
+        };
+        if matches {
+            count += visit_mut(rest, element, f);
+        }
+    };
+
+    match (segment, value.reflect_mut()) {
+        (QuerySegment::Elements | QuerySegment::Filter(_), ReflectMut::List(list)) => {
+            for index in 0..list.len() {
+                visit(list.get_mut(index).unwrap());
+            }
+        }
+        (QuerySegment::Elements | QuerySegment::Filter(_), ReflectMut::Array(array)) => {
+            for index in 0..array.len() {
+                visit(array.get_mut(index).unwrap());
+            }
+        }
+        (QuerySegment::Elements | QuerySegment::Filter(_), ReflectMut::Map(map)) => {
+            for index in 0..map.len() {
+                visit(map.get_at_mut(index).unwrap().1);
+            }
+        }
+        (QuerySegment::Fields, ReflectMut::Struct(value)) => {
+            for index in 0..value.field_len() {
+                visit(value.field_at_mut(index).unwrap());
+            }
+        }
+        (QuerySegment::Fields, ReflectMut::TupleStruct(value)) => {
+            for index in 0..value.field_len() {
+                visit(value.field_mut(index).unwrap());
+            }
+        }
+        (QuerySegment::Fields, ReflectMut::Tuple(value)) => {
+            for index in 0..value.field_len() {
+                visit(value.field_mut(index).unwrap());
+            }
+        }
+        (QuerySegment::Fields, ReflectMut::Enum(value)) => {
+            for index in 0..value.field_len() {
+                visit(value.field_at_mut(index).unwrap());
+            }
+        }
+        _ => {}
+    }
+    count
+}
+
+/// Returns the offset of the `)]` closing a filter, ignoring those within quoted strings.
+fn find_filter_end(filter: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, char) in filter.char_indices() {
+        match quote {
+            Some(q) if char == q => quote = None,
+            None if char == '\'' || char == '"' => quote = Some(char),
+            None if filter[index..].starts_with(")]") => return Some(index),
+            Some(_) | None => {}
+        }
+    }
+    None
+}
+
+impl Predicate {
+    fn parse(filter: &str) -> Result<Self, PathQueryError> {
+        let invalid = |reason| PathQueryError::InvalidFilter { filter, reason };
+
+        let body = filter
+            .trim()
+            .strip_prefix('@')
+            .ok_or(invalid("expected the filter to start with `@`"))?;
+        let Some(op_start) = body.find(['=', '!', '<', '>']) else {
+            return Ok(Self {
+                path: ParsedPath::parse(body.trim())?,
+                comparison: None,
+            });
+        };
+
+        let path = ParsedPath::parse(body[..op_start].trim())?;
+        let operators = [
+            ("==", Comparison::Eq),
+            ("!=", Comparison::Ne),
+            ("<=", Comparison::Le),
+            (">=", Comparison::Ge),
+            ("<", Comparison::Lt),
+            (">", Comparison::Gt),
+        ];
+        let (literal, comparison) = operators
+            .iter()
+            .find_map(|(op, comparison)| {
+                body[op_start..]
+                    .strip_prefix(op)
+                    .map(|literal| (literal.trim(), *comparison))
+            })
+            .ok_or(invalid(
+                "expected one of `==`, `!=`, `<`, `<=`, `>` or `>=`",
+            ))?;
+
+        let literal = match literal {
+            "true" => Literal::Bool(true),
+            "false" => Literal::Bool(false),
+            _ => {
+                let quoted = literal
+                    .strip_prefix('\'')
+                    .and_then(|literal| literal.strip_suffix('\''))
+                    .or_else(|| {
+                        literal
+                            .strip_prefix('"')
+                            .and_then(|literal| literal.strip_suffix('"'))
+                    });
+                match quoted {
+                    Some(string) => Literal::String(string.into()),
+                    None => Literal::Number(
+                        literal
+                            .parse()
+                            .map_err(|_| invalid("expected a number, a string, or a boolean"))?,
+                    ),
+                }
+            }
+        };
+
+        Ok(Self {
+            path,
+            comparison: Some((comparison, literal)),
+        })
+    }
+
+    fn matches(&self, element: &dyn PartialReflect) -> bool {
+        let Ok(value) = self.path.reflect_element(element) else {
+            return false;
+        };
+        let Some((comparison, literal)) = &self.comparison else {
+            return value.try_downcast_ref::<bool>().copied().unwrap_or(true);
+        };
+        let ordering = match literal {
+            Literal::Bool(literal) => value
+                .try_downcast_ref::<bool>()
+                .map(|value| value.cmp(literal)),
+            Literal::Number(literal) => as_f64(value).and_then(|value| value.partial_cmp(literal)),
+            Literal::String(literal) => as_str(value).map(|value| value.cmp(literal.as_str())),
+        };
+        ordering.is_some_and(|ordering| match comparison {
+            Comparison::Eq => ordering == Ordering::Equal,
+            Comparison::Ne => ordering != Ordering::Equal,
+            Comparison::Lt => ordering == Ordering::Less,
+            Comparison::Le => ordering != Ordering::Greater,
+            Comparison::Gt => ordering == Ordering::Greater,
+            Comparison::Ge => ordering != Ordering::Less,
+        })
+    }
+}
+
+fn as_f64(value: &dyn PartialReflect) -> Option<f64> {
+    macro_rules! downcast {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return Some(*value as f64);
+                }
+            )*
+        };
+    }
+    downcast!(f32, f64, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    None
+}
+
+fn as_str(value: &dyn PartialReflect) -> Option<&str> {
+    value
+        .try_downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| value.try_downcast_ref::<&'static str>().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, Reflect};
+    use bevy_platform_support::collections::HashMap;
+
+    #[derive(Reflect)]
+    struct Scene {
+        nodes: Vec<Node>,
+        tags: HashMap<String, Node>,
+    }
+
+    #[derive(Reflect)]
+    struct Node {
+        name: String,
+        enabled: bool,
+        weight: u32,
+    }
+
+    fn node(name: &str, enabled: bool, weight: u32) -> Node {
+        Node {
+            name: name.into(),
+            enabled,
+            weight,
+        }
+    }
+
+    fn scene() -> Scene {
+        Scene {
+            nodes: vec![node("a", true, 1), node("b", false, 5), node("c", true, 10)],
+            tags: [("player".into(), node("p", true, 3))]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn names(query: &str, scene: &Scene) -> Vec<String> {
+        PathQuery::parse(query)
+            .unwrap()
+            .iter(scene)
+            .map(|name| name.try_downcast_ref::<String>().unwrap().clone())
+            .collect()
+    }
+
+    #[test]
+    fn wildcards_and_filters() {
+        let scene = scene();
+        assert_eq!(names("nodes[*].name", &scene), ["a", "b", "c"]);
+        assert_eq!(names("nodes[?(@.enabled)].name", &scene), ["a", "c"]);
+        assert_eq!(names("nodes[?(@.weight >= 5)].name", &scene), ["b", "c"]);
+        assert_eq!(names("nodes[?(@.name != 'b')].name", &scene), ["a", "c"]);
+        assert_eq!(names("nodes[?(@.name == \"c\")].name", &scene), ["c"]);
+        assert_eq!(names("tags[*].name", &scene), ["p"]);
+        assert_eq!(names("nodes[1].name", &scene), ["b"]);
+        let fields = PathQuery::parse("nodes[0].*").unwrap();
+        assert_eq!(fields.iter(&scene).count(), 3);
+    }
+
+    #[test]
+    fn for_each_mut_edits_matches() {
+        let mut scene = scene();
+        let query = PathQuery::parse("nodes[?(@.weight < 10)].enabled").unwrap();
+        let count = query.for_each_mut(&mut scene, |enabled| {
+            *enabled.try_downcast_mut::<bool>().unwrap() = false;
+        });
+        assert_eq!(count, 2);
+        let enabled: Vec<bool> = scene.nodes.iter().map(|node| node.enabled).collect();
+        assert_eq!(enabled, [false, false, true]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            PathQuery::parse("nodes[?(@.enabled"),
+            Err(PathQueryError::UnclosedFilter(5))
+        );
+        assert!(matches!(
+            PathQuery::parse("nodes[?(.enabled)]"),
+            Err(PathQueryError::InvalidFilter { .. })
+        ));
+        assert!(matches!(
+            PathQuery::parse("nodes[?(@.weight > heavy)]"),
+            Err(PathQueryError::InvalidFilter { .. })
+        ));
+        assert!(matches!(
+            PathQuery::parse("nodes[x]"),
+            Err(PathQueryError::InvalidPath(_))
+        ));
+    }
+}
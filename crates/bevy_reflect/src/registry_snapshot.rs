@@ -0,0 +1,516 @@
+use crate::{
+    attributes::CustomAttributes, std_traits::ReflectDefault, GenericInfo, NamedField,
+    ReflectDeserialize, ReflectFromReflect, ReflectSerialize, TypeData, TypeInfo, TypeRegistration,
+    TypeRegistry, UnnamedField, VariantInfo,
+};
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::any::TypeId;
+use serde::{Deserialize, Serialize};
+
+/// An owned copy of the types of a [`TypeRegistry`], which can be serialized.
+///
+/// A snapshot describes each registered type like its [`TypeInfo`]: its paths, its docs, its
+/// fields or variants, its generics and its custom attributes. Unlike the registry, it doesn't
+/// require the types to be linked, so a snapshot taken by a game can be deserialized by another
+/// process, like an editor, to learn the layout of the types it receives over the
+/// [Bevy Remote Protocol].
+///
+/// A snapshot only describes types. It can't deserialize values by itself: values are still
+/// serialized and deserialized with the [`TypeRegistry`] of the process that links the types.
+///
+/// Types are referred to by their [type path].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypePath, TypeRegistry, TypeRegistrySnapshot, TypeKindSnapshot};
+/// #[derive(Reflect)]
+/// struct Player {
+///     name: String,
+///     health: f32,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Player>();
+///
+/// let snapshot = TypeRegistrySnapshot::new(&registry);
+/// let json = serde_json::to_string(&snapshot).unwrap();
+///
+/// // In another process, which doesn't know about `Player`.
+/// let snapshot: TypeRegistrySnapshot = serde_json::from_str(&json).unwrap();
+/// let player = snapshot.get(Player::type_path()).unwrap();
+/// let TypeKindSnapshot::Struct { fields } = &player.kind else {
+///     panic!("expected a struct");
+/// };
+/// assert_eq!(fields[1].name.as_deref(), Some("health"));
+/// assert_eq!(fields[1].type_path, "f32");
+/// ```
+///
+/// [type path]: crate::TypePath::type_path
+/// [Bevy Remote Protocol]: https://docs.rs/bevy_remote
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeRegistrySnapshot {
+    /// The registered types, sorted by type path.
+    pub types: Vec<TypeSnapshot>,
+}
+
+impl TypeRegistrySnapshot {
+    /// Takes a snapshot of all the types of the `registry`.
+    ///
+    /// The [type data] this crate defines is recorded in the [`type_data`] of each type, as
+    /// `Default`, `Serialize`, `Deserialize` and `FromReflect`. Other type data can be recorded with
+    /// [`with_type_data`](Self::with_type_data).
+    ///
+    /// [type data]: TypeData
+    /// [`type_data`]: TypeSnapshot::type_data
+    pub fn new(registry: &TypeRegistry) -> Self {
+        let mut types: Vec<_> = registry.iter().map(TypeSnapshot::new).collect();
+        types.sort_by(|a, b| a.type_path.cmp(&b.type_path));
+        Self { types }
+    }
+
+    /// Records the type data `D` of the registered types under the given `name`.
+    pub fn with_type_data<D: TypeData>(mut self, registry: &TypeRegistry, name: &str) -> Self {
+        for snapshot in &mut self.types {
+            let has_data = registry
+                .get_with_type_path(&snapshot.type_path)
+                .is_some_and(TypeRegistration::contains::<D>);
+            if has_data && !snapshot.type_data.iter().any(|data| data == name) {
+                snapshot.type_data.push(name.to_owned());
+            }
+        }
+        self
+    }
+
+    /// Returns the type with the given [type path], if it's in the snapshot.
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn get(&self, type_path: &str) -> Option<&TypeSnapshot> {
+        self.types
+            .binary_search_by(|snapshot| snapshot.type_path.as_str().cmp(type_path))
+            .ok()
+            .map(|index| &self.types[index])
+    }
+
+    /// Returns the types with the given [short type path].
+    ///
+    /// There are multiple types if their short type paths are ambiguous.
+    ///
+    /// [short type path]: crate::TypePath::short_type_path
+    pub fn get_with_short_type_path<'a>(
+        &'a self,
+        short_type_path: &'a str,
+    ) -> impl Iterator<Item = &'a TypeSnapshot> {
+        self.types
+            .iter()
+            .filter(move |snapshot| snapshot.short_type_path == short_type_path)
+    }
+}
+
+/// An owned copy of the [`TypeInfo`] of a registered type, in a [`TypeRegistrySnapshot`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TypeSnapshot {
+    /// The [type path](crate::TypePath::type_path) of the type.
+    pub type_path: String,
+    /// The [short type path](crate::TypePath::short_type_path) of the type.
+    pub short_type_path: String,
+    /// The name of the type, without its module path or generics.
+    pub type_ident: Option<String>,
+    /// The name of the crate the type is in.
+    pub crate_name: Option<String>,
+    /// The path to the module the type is in.
+    pub module_path: Option<String>,
+    /// The docs of the type, when the `documentation` feature is enabled.
+    pub docs: Option<String>,
+    /// The shape of the type.
+    pub kind: TypeKindSnapshot,
+    /// The generic parameters of the type.
+    pub generics: Vec<GenericSnapshot>,
+    /// The custom attributes of the type.
+    pub custom_attributes: Vec<CustomAttributeSnapshot>,
+    /// The names of the [type data](TypeData) registered for the type.
+    pub type_data: Vec<String>,
+}
+
+impl TypeSnapshot {
+    /// Takes a snapshot of a registered type.
+    pub fn new(registration: &TypeRegistration) -> Self {
+        let info = registration.type_info();
+        let path_table = info.type_path_table();
+        let (kind, custom_attributes) = match info {
+            TypeInfo::Struct(info) => (
+                TypeKindSnapshot::Struct {
+                    fields: info.iter().map(FieldSnapshot::named).collect(),
+                },
+                snapshot_attributes(info.custom_attributes()),
+            ),
+            TypeInfo::TupleStruct(info) => (
+                TypeKindSnapshot::TupleStruct {
+                    fields: info.iter().map(FieldSnapshot::unnamed).collect(),
+                },
+                snapshot_attributes(info.custom_attributes()),
+            ),
+            TypeInfo::Tuple(info) => (
+                TypeKindSnapshot::Tuple {
+                    fields: info.iter().map(FieldSnapshot::unnamed).collect(),
+                },
+                Vec::new(),
+            ),
+            TypeInfo::List(info) => (
+                TypeKindSnapshot::List {
+                    item: info.item_ty().path().to_owned(),
+                },
+                Vec::new(),
+            ),
+            TypeInfo::Array(info) => (
+                TypeKindSnapshot::Array {
+                    item: info.item_ty().path().to_owned(),
+                    capacity: info.capacity(),
+                },
+                Vec::new(),
+            ),
+            TypeInfo::Map(info) => (
+                TypeKindSnapshot::Map {
+                    key: info.key_ty().path().to_owned(),
+                    value: info.value_ty().path().to_owned(),
+                },
+                Vec::new(),
+            ),
+            TypeInfo::Set(info) => (
+                TypeKindSnapshot::Set {
+                    value: info.value_ty().path().to_owned(),
+                },
+                Vec::new(),
+            ),
+            TypeInfo::Enum(info) => (
+                TypeKindSnapshot::Enum {
+                    variants: info.iter().map(VariantSnapshot::new).collect(),
+                },
+                snapshot_attributes(info.custom_attributes()),
+            ),
+            TypeInfo::Opaque(_) => (TypeKindSnapshot::Opaque, Vec::new()),
+        };
+
+        let known_type_data: [(TypeId, &str); 4] = [
+            (TypeId::of::<ReflectDefault>(), "Default"),
+            (TypeId::of::<ReflectSerialize>(), "Serialize"),
+            (TypeId::of::<ReflectDeserialize>(), "Deserialize"),
+            (TypeId::of::<ReflectFromReflect>(), "FromReflect"),
+        ];
+
+        Self {
+            type_path: path_table.path().to_owned(),
+            short_type_path: path_table.short_path().to_owned(),
+            type_ident: path_table.ident().map(ToOwned::to_owned),
+            crate_name: path_table.crate_name().map(ToOwned::to_owned),
+            module_path: path_table.module_path().map(ToOwned::to_owned),
+            docs: type_docs(info),
+            kind,
+            generics: info.generics().iter().map(GenericSnapshot::new).collect(),
+            custom_attributes,
+            type_data: known_type_data
+                .into_iter()
+                .filter(|(id, _)| registration.contains_by_id(*id))
+                .map(|(_, name)| name.to_owned())
+                .collect(),
+        }
+    }
+}
+
+/// The shape of a type in a [`TypeSnapshot`], like its [`ReflectKind`](crate::ReflectKind).
+///
+/// Other types are referred to by their [type path](crate::TypePath::type_path).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TypeKindSnapshot {
+    /// A struct with named fields.
+    Struct {
+        /// The fields of the struct.
+        fields: Vec<FieldSnapshot>,
+    },
+    /// A tuple struct.
+    TupleStruct {
+        /// The fields of the tuple struct.
+        fields: Vec<FieldSnapshot>,
+    },
+    /// A tuple.
+    Tuple {
+        /// The fields of the tuple.
+        fields: Vec<FieldSnapshot>,
+    },
+    /// A list of items.
+    List {
+        /// The type of the items.
+        item: String,
+    },
+    /// An array with a fixed number of items.
+    Array {
+        /// The type of the items.
+        item: String,
+        /// The number of items.
+        capacity: usize,
+    },
+    /// A map from keys to values.
+    Map {
+        /// The type of the keys.
+        key: String,
+        /// The type of the values.
+        value: String,
+    },
+    /// A set of values.
+    Set {
+        /// The type of the values.
+        value: String,
+    },
+    /// An enum.
+    Enum {
+        /// The variants of the enum.
+        variants: Vec<VariantSnapshot>,
+    },
+    /// A type whose structure isn't reflected, like primitives.
+    Opaque,
+}
+
+/// A field of a struct, tuple struct, tuple or enum variant in a [`TypeSnapshot`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FieldSnapshot {
+    /// The name of the field, or `None` for the fields of tuples, which are identified by their
+    /// position.
+    pub name: Option<String>,
+    /// The type path of the field.
+    pub type_path: String,
+    /// The docs of the field, when the `documentation` feature is enabled.
+    pub docs: Option<String>,
+    /// The custom attributes of the field.
+    pub custom_attributes: Vec<CustomAttributeSnapshot>,
+}
+
+impl FieldSnapshot {
+    fn named(field: &NamedField) -> Self {
+        Self {
+            name: Some(field.name().to_owned()),
+            type_path: field.type_path().to_owned(),
+            #[cfg(feature = "documentation")]
+            docs: field.docs().map(ToOwned::to_owned),
+            #[cfg(not(feature = "documentation"))]
+            docs: None,
+            custom_attributes: snapshot_attributes(field.custom_attributes()),
+        }
+    }
+
+    fn unnamed(field: &UnnamedField) -> Self {
+        Self {
+            name: None,
+            type_path: field.type_path().to_owned(),
+            #[cfg(feature = "documentation")]
+            docs: field.docs().map(ToOwned::to_owned),
+            #[cfg(not(feature = "documentation"))]
+            docs: None,
+            custom_attributes: snapshot_attributes(field.custom_attributes()),
+        }
+    }
+}
+
+/// A variant of an enum in a [`TypeSnapshot`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VariantSnapshot {
+    /// The name of the variant.
+    pub name: String,
+    /// The shape of the variant.
+    pub kind: VariantKindSnapshot,
+    /// The fields of the variant, empty for unit variants.
+    pub fields: Vec<FieldSnapshot>,
+    /// The docs of the variant, when the `documentation` feature is enabled.
+    pub docs: Option<String>,
+    /// The custom attributes of the variant.
+    pub custom_attributes: Vec<CustomAttributeSnapshot>,
+}
+
+impl VariantSnapshot {
+    fn new(variant: &VariantInfo) -> Self {
+        let (kind, fields) = match variant {
+            VariantInfo::Struct(info) => (
+                VariantKindSnapshot::Struct,
+                info.iter().map(FieldSnapshot::named).collect(),
+            ),
+            VariantInfo::Tuple(info) => (
+                VariantKindSnapshot::Tuple,
+                info.iter().map(FieldSnapshot::unnamed).collect(),
+            ),
+            VariantInfo::Unit(_) => (VariantKindSnapshot::Unit, Vec::new()),
+        };
+
+        Self {
+            name: variant.name().to_owned(),
+            kind,
+            fields,
+            #[cfg(feature = "documentation")]
+            docs: variant.docs().map(ToOwned::to_owned),
+            #[cfg(not(feature = "documentation"))]
+            docs: None,
+            custom_attributes: snapshot_attributes(variant.custom_attributes()),
+        }
+    }
+}
+
+/// The shape of a [`VariantSnapshot`], like its [`VariantType`](crate::VariantType).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VariantKindSnapshot {
+    /// A variant with named fields.
+    Struct,
+    /// A variant with unnamed fields.
+    Tuple,
+    /// A variant without fields.
+    Unit,
+}
+
+/// A generic parameter of a type in a [`TypeSnapshot`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenericSnapshot {
+    /// The name of the parameter.
+    pub name: String,
+    /// The type path of the type the parameter is set to, or of the type of a const parameter.
+    pub type_path: String,
+    /// Whether this is a const parameter.
+    pub is_const: bool,
+}
+
+impl GenericSnapshot {
+    fn new(info: &GenericInfo) -> Self {
+        Self {
+            name: info.name().to_string(),
+            type_path: info.type_path().to_owned(),
+            is_const: info.is_const(),
+        }
+    }
+}
+
+/// A custom attribute in a [`TypeSnapshot`].
+///
+/// The value of the attribute is only available as text, since its type may not be known by the
+/// process reading the snapshot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomAttributeSnapshot {
+    /// The type path of the attribute.
+    pub type_path: String,
+    /// The [`Debug`](core::fmt::Debug) representation of the value of the attribute.
+    pub value: String,
+}
+
+fn snapshot_attributes(attributes: &CustomAttributes) -> Vec<CustomAttributeSnapshot> {
+    let mut snapshots: Vec<_> = attributes
+        .iter()
+        .map(|(_, value)| CustomAttributeSnapshot {
+            type_path: value.reflect_type_path().to_owned(),
+            value: format!("{value:?}"),
+        })
+        .collect();
+    // Attributes are stored by type id, in no particular order.
+    snapshots.sort_by(|a, b| a.type_path.cmp(&b.type_path));
+    snapshots
+}
+
+#[cfg(feature = "documentation")]
+fn type_docs(info: &TypeInfo) -> Option<String> {
+    info.docs().map(ToOwned::to_owned)
+}
+
+#[cfg(not(feature = "documentation"))]
+fn type_docs(_info: &TypeInfo) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, Reflect};
+    use alloc::vec;
+
+    #[derive(Reflect, Default)]
+    #[reflect(Default, where T: Default)]
+    struct Slider<T> {
+        #[reflect(@0.1_f32)]
+        value: f32,
+        label: T,
+    }
+
+    #[derive(Reflect)]
+    enum Shape {
+        Point,
+        Circle(f32),
+        Rect { width: f32, height: f32 },
+    }
+
+    #[test]
+    fn snapshot_registry() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Slider<String>>();
+        registry.register::<Shape>();
+        registry.register::<Vec<Shape>>();
+
+        let snapshot = TypeRegistrySnapshot::new(&registry);
+        let paths: Vec<_> = snapshot.types.iter().map(|ty| &ty.type_path).collect();
+        assert!(paths.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let slider = snapshot
+            .get_with_short_type_path("Slider<String>")
+            .next()
+            .unwrap();
+        assert_eq!(slider.type_ident.as_deref(), Some("Slider"));
+        assert_eq!(slider.type_data, ["Default", "FromReflect"]);
+        assert_eq!(
+            slider.generics,
+            [GenericSnapshot {
+                name: "T".into(),
+                type_path: "alloc::string::String".into(),
+                is_const: false,
+            }]
+        );
+        let TypeKindSnapshot::Struct { fields } = &slider.kind else {
+            panic!("expected a struct");
+        };
+        assert_eq!(fields[0].name.as_deref(), Some("value"));
+        assert_eq!(
+            fields[0].custom_attributes,
+            [CustomAttributeSnapshot {
+                type_path: "f32".into(),
+                value: "0.1".into(),
+            }]
+        );
+        assert!(snapshot.get(&fields[1].type_path).is_some());
+
+        let shape = snapshot.get(&format!("{}::Shape", module_path!())).unwrap();
+        let TypeKindSnapshot::Enum { variants } = &shape.kind else {
+            panic!("expected an enum");
+        };
+        let kinds: Vec<_> = variants.iter().map(|variant| variant.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                VariantKindSnapshot::Unit,
+                VariantKindSnapshot::Tuple,
+                VariantKindSnapshot::Struct
+            ]
+        );
+        assert_eq!(variants[2].fields[1].name.as_deref(), Some("height"));
+
+        let shapes = snapshot
+            .get_with_short_type_path("Vec<Shape>")
+            .next()
+            .unwrap();
+        assert_eq!(
+            shapes.kind,
+            TypeKindSnapshot::List {
+                item: shape.type_path.clone()
+            }
+        );
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let deserialized: TypeRegistrySnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, snapshot);
+    }
+}
@@ -1,7 +1,9 @@
 pub use deserialize_with_registry::*;
 pub use deserializer::*;
+pub use path::*;
 pub use processor::*;
 pub use registrations::*;
+pub use validating::*;
 
 mod arrays;
 mod deserialize_with_registry;
@@ -12,6 +14,7 @@ mod helpers;
 mod lists;
 mod maps;
 mod options;
+mod path;
 mod processor;
 mod registration_utils;
 mod registrations;
@@ -21,6 +24,7 @@ mod structs;
 mod tuple_structs;
 mod tuple_utils;
 mod tuples;
+mod validating;
 
 #[cfg(test)]
 mod tests {
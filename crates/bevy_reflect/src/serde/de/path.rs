@@ -0,0 +1,230 @@
+use crate::{
+    serde::{de::error_utils::make_custom_error, TypedReflectDeserializer},
+    Access, ApplyError, NamedField, OffsetAccess, ParsedPath, PartialReflect, ReflectPath,
+    TypeInfo, TypeRegistration, TypeRegistry, UnnamedField, VariantInfo,
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::{any::TypeId, fmt, fmt::Formatter};
+use serde::de::{DeserializeSeed, Error, SeqAccess, Visitor};
+use thiserror::Error;
+
+/// A value deserialized by a [`ReflectPathDeserializer`], along with the path it should be
+/// applied at.
+#[derive(Debug)]
+pub struct ReflectPatch {
+    /// The path of the value, relative to the root type of the [`ReflectPathDeserializer`].
+    pub path: ParsedPath,
+    /// The deserialized value.
+    ///
+    /// Like for a [`TypedReflectDeserializer`], this is usually a dynamic representation
+    /// of the type at `path`.
+    pub value: Box<dyn PartialReflect>,
+}
+
+impl ReflectPatch {
+    /// Applies the value of this patch to the element at its path within `root`.
+    ///
+    /// See [`PartialReflect::try_apply`] for how the value is applied.
+    pub fn apply(&self, root: &mut dyn PartialReflect) -> Result<(), ReflectPatchError> {
+        self.path
+            .reflect_element_mut(root)
+            .map_err(|error| ReflectPatchError::InvalidPath(error.to_string()))?
+            .try_apply(self.value.as_partial_reflect())?;
+        Ok(())
+    }
+}
+
+/// An error returned by [`ReflectPatch::apply`].
+#[derive(Error, Debug)]
+pub enum ReflectPatchError {
+    /// The path of the patch couldn't be found in the value it was applied to.
+    ///
+    /// Contains the message of the [`ReflectPathError`](crate::ReflectPathError).
+    #[error("{0}")]
+    InvalidPath(String),
+
+    /// The value of the patch couldn't be applied to the element at its path.
+    #[error(transparent)]
+    InvalidValue(#[from] ApplyError),
+}
+
+/// A deserializer for a value at a path within a reflected type whose [`TypeRegistration`]
+/// is known.
+///
+/// This is the deserializer counterpart to [`ReflectPathSerializer`].
+///
+/// # Input
+///
+/// This deserializer expects a tuple of two elements: the path of the value, as a string,
+/// and the serialized value. The type of the value is found by following the path through
+/// the [`TypeInfo`] of the root type, so every type along the path must be registered.
+///
+/// Accessing a field of an enum by name or index requires the field to have the same type
+/// in every variant which has it.
+///
+/// # Output
+///
+/// This deserializer will return a [`ReflectPatch`], which can be applied to a value of the
+/// root type with [`ReflectPatch::apply`].
+///
+/// # Example
+///
+/// ```
+/// # use core::any::TypeId;
+/// # use serde::de::DeserializeSeed;
+/// # use bevy_reflect::prelude::*;
+/// # use bevy_reflect::{TypeRegistry, serde::ReflectPathDeserializer};
+/// #[derive(Reflect)]
+/// struct Player {
+///   inventory: Inventory,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Inventory {
+///   items: Vec<Item>,
+/// }
+///
+/// #[derive(Reflect, PartialEq, Debug)]
+/// struct Item {
+///   count: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+///
+/// let mut player = Player {
+///   inventory: Inventory {
+///     items: vec![Item { count: 1 }, Item { count: 5 }],
+///   },
+/// };
+///
+/// let input = r#"(".inventory.items[1]", (count: 3))"#;
+///
+/// let registration = registry.get(TypeId::of::<Player>()).unwrap();
+///
+/// let mut deserializer = ron::Deserializer::from_str(input).unwrap();
+/// let reflect_deserializer = ReflectPathDeserializer::new(registration, &registry);
+/// let patch = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+///
+/// patch.apply(&mut player).unwrap();
+/// assert_eq!(player.inventory.items[1], Item { count: 3 });
+/// ```
+///
+/// [`ReflectPathSerializer`]: crate::serde::ReflectPathSerializer
+pub struct ReflectPathDeserializer<'a> {
+    registration: &'a TypeRegistration,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectPathDeserializer<'a> {
+    /// Creates a deserializer for values at paths within the type of `registration`.
+    pub fn new(registration: &'a TypeRegistration, registry: &'a TypeRegistry) -> Self {
+        Self {
+            registration,
+            registry,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ReflectPathDeserializer<'_> {
+    type Value = ReflectPatch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct ReflectPathVisitor<'a> {
+            registration: &'a TypeRegistration,
+            registry: &'a TypeRegistry,
+        }
+
+        impl<'de> Visitor<'de> for ReflectPathVisitor<'_> {
+            type Value = ReflectPatch;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter
+                    .write_str("tuple containing the path and the value of a reflected element")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let path = seq
+                    .next_element::<String>()?
+                    .ok_or_else(|| Error::invalid_length(0, &self))?;
+                let path = ParsedPath::parse(&path).map_err(make_custom_error)?;
+
+                let mut registration = self.registration;
+                for OffsetAccess { access, .. } in &path.0 {
+                    let type_path = registration.type_info().type_path();
+                    let type_id =
+                        field_type_id(registration.type_info(), access).ok_or_else(|| {
+                            make_custom_error(format_args!(
+                                "`{access}` doesn't access a single type of field in `{type_path}`"
+                            ))
+                        })?;
+                    registration = self.registry.get(type_id).ok_or_else(|| {
+                        make_custom_error(format_args!(
+                            "no registration found for the type of `{access}` in `{type_path}`"
+                        ))
+                    })?;
+                }
+
+                let value = seq
+                    .next_element_seed(TypedReflectDeserializer::new(registration, self.registry))?
+                    .ok_or_else(|| Error::invalid_length(1, &self))?;
+
+                Ok(ReflectPatch { path, value })
+            }
+        }
+
+        deserializer.deserialize_tuple(
+            2,
+            ReflectPathVisitor {
+                registration: self.registration,
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+/// Returns the [`TypeId`] of the element accessed by `access` in the type of `info`.
+///
+/// For enums, the element must have the same type in all the variants which have it.
+fn field_type_id(info: &TypeInfo, access: &Access) -> Option<TypeId> {
+    match (info, access) {
+        (TypeInfo::Struct(info), Access::Field(name)) => info.field(name).map(NamedField::type_id),
+        (TypeInfo::Struct(info), &Access::FieldIndex(index)) => {
+            info.field_at(index).map(NamedField::type_id)
+        }
+        (TypeInfo::TupleStruct(info), &Access::TupleIndex(index)) => {
+            info.field_at(index).map(UnnamedField::type_id)
+        }
+        (TypeInfo::Tuple(info), &Access::TupleIndex(index)) => {
+            info.field_at(index).map(UnnamedField::type_id)
+        }
+        (TypeInfo::List(info), Access::ListIndex(_)) => Some(info.item_ty().id()),
+        (TypeInfo::Array(info), Access::ListIndex(_)) => Some(info.item_ty().id()),
+        (TypeInfo::Enum(info), access) => {
+            let mut type_ids = info.iter().filter_map(|variant| match (variant, access) {
+                (VariantInfo::Struct(variant), Access::Field(name)) => {
+                    variant.field(name).map(NamedField::type_id)
+                }
+                (VariantInfo::Struct(variant), &Access::FieldIndex(index)) => {
+                    variant.field_at(index).map(NamedField::type_id)
+                }
+                (VariantInfo::Tuple(variant), &Access::TupleIndex(index)) => {
+                    variant.field_at(index).map(UnnamedField::type_id)
+                }
+                _ => None,
+            });
+            let type_id = type_ids.next()?;
+            type_ids.all(|other| other == type_id).then_some(type_id)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{validation::validate, PartialReflect, TypeRegistry};
+use alloc::boxed::Box;
+use serde::de::{DeserializeSeed, Error};
+
+/// A deserializer [validating] the values deserialized by another reflection deserializer.
+///
+/// This wraps a [`ReflectDeserializer`] or a [`TypedReflectDeserializer`], and fails if the
+/// deserialized value is rejected by one of the [`ValidateAttribute`]s attached to its types,
+/// fields or variants. The error message lists every rejected value, with its path.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry, validation::Range};
+/// # use bevy_reflect::serde::{TypedReflectDeserializer, ValidatingDeserializer};
+/// # use serde::de::DeserializeSeed;
+/// #[derive(Reflect)]
+/// struct Volume {
+///     #[reflect(@Range(0.0..=1.0))]
+///     level: f32,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Volume>();
+///
+/// let input = "(level: 2.0)";
+/// let mut deserializer = ron::Deserializer::from_str(input).unwrap();
+/// let reflect_deserializer = ValidatingDeserializer::new(
+///     TypedReflectDeserializer::of::<Volume>(&registry),
+///     &registry,
+/// );
+/// let error = reflect_deserializer.deserialize(&mut deserializer).unwrap_err();
+/// assert!(error.to_string().contains("`.level`"));
+/// ```
+///
+/// [validating]: crate::validation
+/// [`ReflectDeserializer`]: crate::serde::ReflectDeserializer
+/// [`TypedReflectDeserializer`]: crate::serde::TypedReflectDeserializer
+/// [`ValidateAttribute`]: crate::validation::ValidateAttribute
+pub struct ValidatingDeserializer<'a, S> {
+    deserializer: S,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, S> ValidatingDeserializer<'a, S> {
+    /// Creates a deserializer validating the values deserialized by `deserializer`, with the
+    /// validators registered in `registry`.
+    pub fn new(deserializer: S, registry: &'a TypeRegistry) -> Self {
+        Self {
+            deserializer,
+            registry,
+        }
+    }
+}
+
+impl<'de, S> DeserializeSeed<'de> for ValidatingDeserializer<'_, S>
+where
+    S: DeserializeSeed<'de, Value = Box<dyn PartialReflect>>,
+{
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = self.deserializer.deserialize(deserializer)?;
+        validate(value.as_ref(), self.registry).map_err(D::Error::custom)?;
+        Ok(value)
+    }
+}
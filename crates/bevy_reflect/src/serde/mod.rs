@@ -11,8 +11,9 @@ mod tests {
     use super::*;
     use crate::{
         self as bevy_reflect, type_registry::TypeRegistry, DynamicStruct, DynamicTupleStruct,
-        FromReflect, PartialReflect, Reflect, Struct,
+        FromReflect, ParsedPath, PartialReflect, Reflect, Struct,
     };
+    use alloc::{string::ToString, vec, vec::Vec};
     use serde::de::DeserializeSeed;
 
     #[test]
@@ -139,6 +140,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialization_large_const_generic_array() {
+        #[derive(Debug, Reflect, PartialEq)]
+        struct Grid<const N: usize> {
+            cells: [u8; N],
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Grid<64>>();
+
+        let mut grid = Grid { cells: [0; 64] };
+        grid.cells[63] = 1;
+
+        let serializer = ReflectSerializer::new(&grid, &registry);
+        let serialized = ron::ser::to_string(&serializer).unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = ReflectDeserializer::new(&registry);
+        let deserialized = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let received =
+            <Grid<64> as FromReflect>::from_reflect(deserialized.as_partial_reflect()).unwrap();
+        assert_eq!(grid, received);
+    }
+
     #[test]
     #[should_panic(
         expected = "cannot serialize dynamic value without represented type: `bevy_reflect::DynamicStruct`"
@@ -183,6 +209,75 @@ mod tests {
             .unwrap());
     }
 
+    #[test]
+    fn should_roundtrip_value_at_path() {
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Player {
+            items: Vec<Item>,
+        }
+
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Item {
+            Sword { damage: u32 },
+            Shield { damage: u32, armor: u32 },
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        let registration = registry.get(core::any::TypeId::of::<Player>()).unwrap();
+
+        let source = Player {
+            items: vec![
+                Item::Sword { damage: 1 },
+                Item::Shield {
+                    damage: 2,
+                    armor: 3,
+                },
+            ],
+        };
+        let mut target = Player {
+            items: vec![Item::Sword { damage: 0 }, Item::Sword { damage: 0 }],
+        };
+
+        for (path, expected) in [
+            ("items[1]", r#"(".items[1]",Shield(damage:2,armor:3))"#),
+            ("items[0].damage", r#"(".items[0].damage",1)"#),
+        ] {
+            let path = ParsedPath::parse(path).unwrap();
+            let serializer = ReflectPathSerializer::new(&source, &path, &registry);
+            let result = ron::ser::to_string(&serializer).unwrap();
+            assert_eq!(expected, result);
+
+            let mut deserializer = ron::de::Deserializer::from_str(&result).unwrap();
+            let patch = ReflectPathDeserializer::new(registration, &registry)
+                .deserialize(&mut deserializer)
+                .unwrap();
+            assert_eq!(path.to_string(), patch.path.to_string());
+            patch.apply(&mut target).unwrap();
+        }
+        assert_eq!(source, target);
+
+        // `armor` only exists in one variant, so its type is known.
+        let mut deserializer = ron::de::Deserializer::from_str(r#"(".items[1].armor",5)"#).unwrap();
+        let patch = ReflectPathDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        patch.apply(&mut target).unwrap();
+        assert_eq!(
+            target.items[1],
+            Item::Shield {
+                damage: 2,
+                armor: 5
+            }
+        );
+
+        // A path that doesn't exist in the root type can't be deserialized.
+        let mut deserializer = ron::de::Deserializer::from_str(r#"(".health",5)"#).unwrap();
+        assert!(ReflectPathDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .is_err());
+    }
+
     mod type_data {
         use super::*;
         use crate::from_reflect::FromReflect;
@@ -1,3 +1,4 @@
+pub use path::*;
 pub use processor::*;
 pub use serializable::*;
 pub use serialize_with_registry::*;
@@ -9,6 +10,7 @@ mod enums;
 mod error_utils;
 mod lists;
 mod maps;
+mod path;
 mod processor;
 mod serializable;
 mod serialize_with_registry;
@@ -0,0 +1,95 @@
+use crate::{
+    serde::{ser::error_utils::make_custom_error, TypedReflectSerializer},
+    ParsedPath, PartialReflect, ReflectPath, TypeRegistry,
+};
+use alloc::string::ToString;
+use serde::{ser::SerializeTuple, Serialize, Serializer};
+
+/// A serializer for the value at a [`ParsedPath`] within a reflected value.
+///
+/// This is useful to send a small part of a larger value, such as a single item of an
+/// inventory, without serializing the rest of it.
+///
+/// This is the serializer counterpart to [`ReflectPathDeserializer`].
+///
+/// # Output
+///
+/// This serializer will output a tuple of two elements: the path, as a string, and the
+/// value at that path, serialized like a [`TypedReflectSerializer`] would.
+/// Since the type of the value is determined by the type of the root and the path,
+/// the type path of the value isn't included.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::prelude::*;
+/// # use bevy_reflect::{ParsedPath, TypeRegistry, serde::ReflectPathSerializer};
+/// #[derive(Reflect)]
+/// struct Player {
+///   inventory: Inventory,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Inventory {
+///   items: Vec<Item>,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Item {
+///   count: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+///
+/// let player = Player {
+///   inventory: Inventory {
+///     items: vec![Item { count: 1 }, Item { count: 5 }],
+///   },
+/// };
+///
+/// let path = ParsedPath::parse("inventory.items[1]").unwrap();
+/// let reflect_serializer = ReflectPathSerializer::new(&player, &path, &registry);
+/// let output = ron::to_string(&reflect_serializer).unwrap();
+///
+/// assert_eq!(output, r#"(".inventory.items[1]",(count:5))"#);
+/// ```
+///
+/// [`ReflectPathDeserializer`]: crate::serde::ReflectPathDeserializer
+pub struct ReflectPathSerializer<'a> {
+    root: &'a dyn PartialReflect,
+    path: &'a ParsedPath,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectPathSerializer<'a> {
+    /// Creates a serializer for the value at `path` within `root`.
+    pub fn new(
+        root: &'a dyn PartialReflect,
+        path: &'a ParsedPath,
+        registry: &'a TypeRegistry,
+    ) -> Self {
+        Self {
+            root,
+            path,
+            registry,
+        }
+    }
+}
+
+impl Serialize for ReflectPathSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let value = self
+            .path
+            .reflect_element(self.root)
+            .map_err(make_custom_error)?;
+
+        let mut state = serializer.serialize_tuple(2)?;
+        state.serialize_element(&self.path.to_string())?;
+        state.serialize_element(&TypedReflectSerializer::new(value, self.registry))?;
+        state.end()
+    }
+}
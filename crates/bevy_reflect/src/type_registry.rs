@@ -96,7 +96,8 @@ impl TypeRegistry {
         }
     }
 
-    /// Create a type registry with default registrations for primitive types.
+    /// Create a type registry with default registrations for primitive types, and for the
+    /// built-in [validation](crate::validation) attributes.
     pub fn new() -> Self {
         let mut registry = Self::empty();
         registry.register::<bool>();
@@ -116,6 +117,7 @@ impl TypeRegistry {
         registry.register::<f32>();
         registry.register::<f64>();
         registry.register::<String>();
+        registry.register::<crate::validation::Range>();
         registry
     }
 
@@ -0,0 +1,458 @@
+//! Validation of reflected values against their [custom attributes].
+//!
+//! A custom attribute can validate the values it is attached to by implementing
+//! [`ValidateAttribute`] and registering [`ReflectValidateAttribute`] in the [`TypeRegistry`].
+//! [`validate`] then checks a value, and all the values it contains, against the attributes of
+//! their types, fields and variants.
+//!
+//! ```
+//! # use bevy_reflect::{Reflect, TypeRegistry, validation::{validate, Range}};
+//! #[derive(Reflect)]
+//! struct Volume {
+//!     #[reflect(@Range(0.0..=1.0))]
+//!     level: f32,
+//! }
+//!
+//! let registry = TypeRegistry::new();
+//! assert!(validate(&Volume { level: 0.5 }, &registry).is_ok());
+//!
+//! let errors = validate(&Volume { level: 2.0 }, &registry).unwrap_err();
+//! assert_eq!(errors.errors[0].path, ".level");
+//! ```
+//!
+//! Validation can also be enforced when deserializing reflected values, with
+//! [`ValidatingDeserializer`].
+//!
+//! [custom attributes]: crate::attributes::CustomAttributes
+//! [`ValidatingDeserializer`]: crate::serde::ValidatingDeserializer
+
+use crate::{
+    self as bevy_reflect, attributes::CustomAttributes, FromType, NamedField, PartialReflect,
+    Reflect, ReflectRef, TypeInfo, TypeRegistry, UnnamedField, VariantInfo,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, ops::RangeInclusive};
+
+/// A custom attribute checking the values it is attached to.
+///
+/// Register [`ReflectValidateAttribute`] for the attribute type, with `#[reflect(ValidateAttribute)]`,
+/// so that [`validate`] finds it.
+///
+/// ```
+/// # use bevy_reflect::{PartialReflect, Reflect, TypeRegistry};
+/// # use bevy_reflect::validation::{validate, ReflectValidateAttribute, ValidateAttribute};
+/// #[derive(Reflect)]
+/// #[reflect(ValidateAttribute)]
+/// struct NotEmpty;
+///
+/// impl ValidateAttribute for NotEmpty {
+///     fn validate(&self, value: &dyn PartialReflect) -> Result<(), String> {
+///         match value.try_downcast_ref::<String>() {
+///             Some(value) if value.is_empty() => Err("the string is empty".to_string()),
+///             _ => Ok(()),
+///         }
+///     }
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Player {
+///     #[reflect(@NotEmpty)]
+///     name: String,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<NotEmpty>();
+/// assert!(validate(&Player { name: String::new() }, &registry).is_err());
+/// ```
+pub trait ValidateAttribute: Reflect {
+    /// Checks a value this attribute is attached to, returning a description of the problem if
+    /// the value is invalid.
+    fn validate(&self, value: &dyn PartialReflect) -> Result<(), String>;
+}
+
+/// Type data for attributes implementing [`ValidateAttribute`].
+#[derive(Clone)]
+pub struct ReflectValidateAttribute {
+    get: fn(&dyn Reflect) -> Option<&dyn ValidateAttribute>,
+}
+
+impl ReflectValidateAttribute {
+    /// Downcasts an attribute to [`ValidateAttribute`], if it has the type of this registration.
+    pub fn get<'a>(&self, attribute: &'a dyn Reflect) -> Option<&'a dyn ValidateAttribute> {
+        (self.get)(attribute)
+    }
+}
+
+impl<T: ValidateAttribute> FromType<T> for ReflectValidateAttribute {
+    fn from_type() -> Self {
+        Self {
+            get: |attribute| {
+                attribute
+                    .downcast_ref::<T>()
+                    .map(|attribute| attribute as &dyn ValidateAttribute)
+            },
+        }
+    }
+}
+
+/// A custom attribute restricting numbers to a range of values.
+///
+/// The bounds are [`f64`], so that `@Range(0.0..=1.0)` can be attached to fields of any primitive
+/// number type. Values which aren't primitive numbers are invalid.
+///
+/// This attribute is registered by [`TypeRegistry::new`].
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[reflect(ValidateAttribute, Debug, PartialEq)]
+pub struct Range(pub RangeInclusive<f64>);
+
+impl ValidateAttribute for Range {
+    fn validate(&self, value: &dyn PartialReflect) -> Result<(), String> {
+        let Some(number) = as_f64(value) else {
+            return Err(format!(
+                "expected a number, found `{}`",
+                value.reflect_type_path()
+            ));
+        };
+        if self.0.contains(&number) {
+            Ok(())
+        } else {
+            Err(format!(
+                "{number} is out of the range {}..={}",
+                self.0.start(),
+                self.0.end()
+            ))
+        }
+    }
+}
+
+fn as_f64(value: &dyn PartialReflect) -> Option<f64> {
+    macro_rules! downcast {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return Some(*value as f64);
+                }
+            )*
+        };
+    }
+
+    if let Some(value) = value.try_downcast_ref::<f64>() {
+        return Some(*value);
+    }
+    downcast!(f32, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+    None
+}
+
+/// A value rejected by a [`ValidateAttribute`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The path to the value from the validated value, in the [`ReflectPath`] syntax.
+    ///
+    /// This is empty if the validated value itself is invalid.
+    ///
+    /// [`ReflectPath`]: crate::ReflectPath
+    pub path: String,
+    /// The type path of the attribute which rejected the value.
+    pub attribute: String,
+    /// Why the value was rejected.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "invalid value: {}", self.message)
+        } else {
+            write!(f, "invalid value at `{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+impl core::error::Error for ValidationError {}
+
+/// The errors returned by [`validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationErrors {
+    /// The values which were rejected, in the order they were visited.
+    pub errors: Vec<ValidationError>,
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ValidationErrors {}
+
+/// Checks `value`, and all the values it contains, against the [`ValidateAttribute`]s attached to
+/// their types, fields and variants.
+///
+/// The attributes are looked up in `registry` by their [`ReflectValidateAttribute`] type data;
+/// other attributes are ignored. Dynamic values are validated against the attributes of the type
+/// they represent, if any.
+pub fn validate(
+    value: &dyn PartialReflect,
+    registry: &TypeRegistry,
+) -> Result<(), ValidationErrors> {
+    let mut validator = Validator {
+        registry,
+        path: String::new(),
+        errors: ValidationErrors::default(),
+    };
+    validator.visit(value, None);
+
+    if validator.errors.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.errors)
+    }
+}
+
+struct Validator<'a> {
+    registry: &'a TypeRegistry,
+    path: String,
+    errors: ValidationErrors,
+}
+
+impl Validator<'_> {
+    /// Validates `value`, with the attributes of the field containing it.
+    fn visit(&mut self, value: &dyn PartialReflect, field_attributes: Option<&CustomAttributes>) {
+        let type_info = value.get_represented_type_info();
+        if let Some(attributes) = field_attributes {
+            self.check(value, attributes);
+        }
+        match type_info {
+            Some(TypeInfo::Struct(info)) => self.check(value, info.custom_attributes()),
+            Some(TypeInfo::TupleStruct(info)) => self.check(value, info.custom_attributes()),
+            Some(TypeInfo::Enum(info)) => self.check(value, info.custom_attributes()),
+            _ => {}
+        }
+
+        match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                let info = type_info.and_then(|info| info.as_struct().ok());
+                for (index, field) in value.iter_fields().enumerate() {
+                    let Some(name) = value.name_at(index) else {
+                        continue;
+                    };
+                    let attributes = info
+                        .and_then(|info| info.field(name))
+                        .map(NamedField::custom_attributes);
+                    self.visit_at(&format!(".{name}"), field, attributes);
+                }
+            }
+            ReflectRef::TupleStruct(value) => {
+                let info = type_info.and_then(|info| info.as_tuple_struct().ok());
+                for (index, field) in value.iter_fields().enumerate() {
+                    let attributes = info
+                        .and_then(|info| info.field_at(index))
+                        .map(UnnamedField::custom_attributes);
+                    self.visit_at(&format!(".{index}"), field, attributes);
+                }
+            }
+            ReflectRef::Tuple(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    self.visit_at(&format!(".{index}"), field, None);
+                }
+            }
+            ReflectRef::List(value) => {
+                for (index, item) in value.iter().enumerate() {
+                    self.visit_at(&format!("[{index}]"), item, None);
+                }
+            }
+            ReflectRef::Array(value) => {
+                for (index, item) in value.iter().enumerate() {
+                    self.visit_at(&format!("[{index}]"), item, None);
+                }
+            }
+            ReflectRef::Map(value) => {
+                for (key, item) in value.iter() {
+                    self.visit_at(&format!("[{key:?}]"), item, None);
+                }
+            }
+            ReflectRef::Set(value) => {
+                for item in value.iter() {
+                    self.visit_at(&format!("[{item:?}]"), item, None);
+                }
+            }
+            ReflectRef::Enum(value) => {
+                let variant = type_info
+                    .and_then(|info| info.as_enum().ok())
+                    .and_then(|info| info.variant(value.variant_name()));
+                if let Some(variant) = variant {
+                    self.check(value.as_partial_reflect(), variant.custom_attributes());
+                }
+                for (index, field) in value.iter_fields().enumerate() {
+                    let (path, attributes) = match (field.name(), variant) {
+                        (Some(name), Some(VariantInfo::Struct(info))) => (
+                            format!(".{name}"),
+                            info.field(name).map(NamedField::custom_attributes),
+                        ),
+                        (Some(name), _) => (format!(".{name}"), None),
+                        (None, Some(VariantInfo::Tuple(info))) => (
+                            format!(".{index}"),
+                            info.field_at(index).map(UnnamedField::custom_attributes),
+                        ),
+                        (None, _) => (format!(".{index}"), None),
+                    };
+                    self.visit_at(&path, field.value(), attributes);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_at(
+        &mut self,
+        segment: &str,
+        value: &dyn PartialReflect,
+        field_attributes: Option<&CustomAttributes>,
+    ) {
+        let len = self.path.len();
+        self.path.push_str(segment);
+        self.visit(value, field_attributes);
+        self.path.truncate(len);
+    }
+
+    fn check(&mut self, value: &dyn PartialReflect, attributes: &CustomAttributes) {
+        for (type_id, attribute) in attributes.iter() {
+            let Some(validator) = self
+                .registry
+                .get_type_data::<ReflectValidateAttribute>(*type_id)
+                .and_then(|data| data.get(attribute))
+            else {
+                continue;
+            };
+            if let Err(message) = validator.validate(value) {
+                self.errors.errors.push(ValidationError {
+                    path: self.path.clone(),
+                    attribute: attribute.reflect_type_path().to_string(),
+                    message,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        serde::{ReflectDeserializer, ReflectSerializer, ValidatingDeserializer},
+        TypePath,
+    };
+    use alloc::{vec, vec::Vec};
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect)]
+    #[reflect(ValidateAttribute)]
+    struct Even;
+
+    impl ValidateAttribute for Even {
+        fn validate(&self, value: &dyn PartialReflect) -> Result<(), String> {
+            match value.try_downcast_ref::<u32>() {
+                Some(value) if value % 2 == 0 => Ok(()),
+                _ => Err("expected an even number".to_string()),
+            }
+        }
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Settings {
+        #[reflect(@Range(0.0..=1.0))]
+        volume: f32,
+        #[reflect(@Even)]
+        count: u32,
+        channels: Vec<Channel>,
+        mode: Mode,
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Channel(#[reflect(@Range(-10.0..=10.0))] i32);
+
+    #[derive(Reflect, Debug, PartialEq)]
+    enum Mode {
+        Off,
+        Fixed(#[reflect(@Range(1.0..=4.0))] u8),
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Even>();
+        registry.register::<Settings>();
+        registry
+    }
+
+    #[test]
+    fn should_validate_nested_values() {
+        let registry = registry();
+
+        let valid = Settings {
+            volume: 0.5,
+            count: 2,
+            channels: vec![Channel(-10), Channel(3)],
+            mode: Mode::Fixed(4),
+        };
+        assert_eq!(Ok(()), validate(&valid, &registry));
+        assert_eq!(Ok(()), validate(&Mode::Off, &registry));
+
+        let invalid = Settings {
+            volume: 1.5,
+            count: 3,
+            channels: vec![Channel(0), Channel(11)],
+            mode: Mode::Fixed(0),
+        };
+        let errors = validate(&invalid, &registry).unwrap_err();
+        let paths = errors
+            .errors
+            .iter()
+            .map(|error| error.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![".volume", ".count", ".channels[1].0", ".mode.0"],
+            paths
+        );
+        assert_eq!(errors.errors[0].attribute, Range::type_path());
+        assert_eq!(errors.errors[1].message, "expected an even number");
+
+        // Without the registration of its validator, an attribute is ignored.
+        let errors = validate(&invalid, &TypeRegistry::new()).unwrap_err();
+        assert_eq!(3, errors.errors.len());
+    }
+
+    #[test]
+    fn should_validate_when_deserializing() {
+        let registry = registry();
+
+        let value = Settings {
+            volume: 2.0,
+            count: 2,
+            channels: Vec::new(),
+            mode: Mode::Off,
+        };
+        let serialized = ron::to_string(&ReflectSerializer::new(&value, &registry)).unwrap();
+
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert!(validate(deserialized.as_ref(), &registry).is_err());
+
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let error = ValidatingDeserializer::new(ReflectDeserializer::new(&registry), &registry)
+            .deserialize(&mut deserializer)
+            .unwrap_err();
+        assert!(error.to_string().contains("invalid value at `.volume`"));
+    }
+}
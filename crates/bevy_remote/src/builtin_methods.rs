@@ -19,7 +19,7 @@ use bevy_reflect::{
     prelude::ReflectDefault,
     serde::{ReflectSerializer, TypedReflectDeserializer},
     GetPath as _, NamedField, OpaqueInfo, PartialReflect, ReflectDeserialize, ReflectSerialize,
-    TypeInfo, TypeRegistration, TypeRegistry, VariantInfo,
+    TypeInfo, TypeRegistration, TypeRegistry, TypeRegistrySnapshot, VariantInfo,
 };
 use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
 use serde_json::{json, Map, Value};
@@ -62,6 +62,9 @@ pub const BRP_LIST_AND_WATCH_METHOD: &str = "bevy/list+watch";
 /// The method path for a `bevy/registry/schema` request.
 pub const BRP_REGISTRY_SCHEMA_METHOD: &str = "bevy/registry/schema";
 
+/// The method path for a `bevy/registry/snapshot` request.
+pub const BRP_REGISTRY_SNAPSHOT_METHOD: &str = "bevy/registry/snapshot";
+
 /// `bevy/get`: Retrieves one or more components from the entity with the given
 /// ID.
 ///
@@ -978,6 +981,17 @@ pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> Br
     serde_json::to_value(schemas).map_err(BrpError::internal)
 }
 
+/// Handles a `bevy/registry/snapshot` request (export all registry types) coming from a client.
+pub fn export_registry_snapshot(In(_params): In<Option<Value>>, world: &World) -> BrpResult {
+    let types = world.resource::<AppTypeRegistry>();
+    let types = types.read();
+    let snapshot = TypeRegistrySnapshot::new(&types)
+        .with_type_data::<ReflectComponent>(&types, "Component")
+        .with_type_data::<ReflectResource>(&types, "Resource");
+
+    serde_json::to_value(snapshot).map_err(BrpError::internal)
+}
+
 /// Exports schema info for a given type
 fn export_type(reg: &TypeRegistration) -> (String, JsonSchemaBevyType) {
     let t = reg.type_info();
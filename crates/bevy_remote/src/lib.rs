@@ -6,6 +6,10 @@
 //! over HTTP. These *remote clients* can inspect and alter the state of the
 //! entity-component system.
 //!
+//! With the `websocket` feature, the `RemoteWebSocketPlugin` accepts connections over
+//! WebSocket instead, which suits watching requests, and the `RemoteWebSocketClientPlugin`
+//! lets apps targeting WASM connect to a remote tool.
+//!
 //! The Bevy Remote Protocol is based on the JSON-RPC 2.0 protocol.
 //!
 //! ## Request objects
@@ -274,6 +278,14 @@
 //! - `removed`: An array of fully-qualified type names of components removed from the entity
 //!   in the last tick.
 //!
+//! ### bevy/registry/snapshot
+//!
+//! Export all the registered types, so that a process which doesn't link the app can learn the
+//! layout of the values it reads and writes with the other methods.
+//!
+//! `result`: A [`TypeRegistrySnapshot`](bevy_reflect::TypeRegistrySnapshot) of the types, with the
+//! `Component` and `Resource` type data recorded.
+//!
 //!
 //! ## Custom methods
 //!
@@ -337,6 +349,8 @@ use std::sync::RwLock;
 pub mod builtin_methods;
 #[cfg(feature = "http")]
 pub mod http;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 const CHANNEL_SIZE: usize = 16;
 
@@ -428,6 +442,10 @@ impl Default for RemotePlugin {
                 builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
                 builtin_methods::export_registry_types,
             )
+            .with_method(
+                builtin_methods::BRP_REGISTRY_SNAPSHOT_METHOD,
+                builtin_methods::export_registry_snapshot,
+            )
             .with_method(
                 builtin_methods::BRP_MUTATE_COMPONENT_METHOD,
                 builtin_methods::process_remote_mutate_component_request,
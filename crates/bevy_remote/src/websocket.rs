@@ -0,0 +1,477 @@
+//! The BRP transport using JSON-RPC over WebSocket.
+//!
+//! Adding the [`RemoteWebSocketPlugin`] to your [`App`] causes Bevy to accept WebSocket
+//! connections (by default, on port 15703) while your app is running. Since browsers can't accept
+//! connections, apps targeting WASM instead connect to a remote tool with the
+//! [`RemoteWebSocketClientPlugin`], and answer the requests the tool sends over the connection.
+//!
+//! Each text message is a JSON-RPC request, or a batch of requests, and is answered with a text
+//! message holding the response, or the batch of responses. The same methods as over HTTP are
+//! available. Watching methods, like `bevy/get+watch`, send a response each time the watched
+//! values change, with the id of the request, until the connection closes.
+
+use crate::{
+    error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpResult, BrpSender,
+};
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::system::Res;
+use serde_json::Value;
+
+#[cfg(not(target_family = "wasm"))]
+use native::connect;
+#[cfg(not(target_family = "wasm"))]
+pub use native::{RemoteWebSocketPlugin, DEFAULT_WEBSOCKET_ADDR};
+#[cfg(target_family = "wasm")]
+use web::connect;
+
+/// The default port that Bevy will listen on for WebSocket connections.
+///
+/// This is the port after the default port of the HTTP transport.
+pub const DEFAULT_WEBSOCKET_PORT: u16 = 15703;
+
+/// Add this plugin to your [`App`] to connect to a remote tool over WebSocket, and let it inspect
+/// and modify entities. It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// Unlike with the `RemoteWebSocketPlugin`, the app is the one connecting, which lets tools like
+/// browser-based inspectors work with apps running in a browser. The tool sends requests over the
+/// connection, and the app sends the responses back.
+///
+/// Only unencrypted `ws://` URLs are supported outside of browsers. The app doesn't reconnect if
+/// the connection closes.
+pub struct RemoteWebSocketClientPlugin {
+    /// The URL of the tool to connect to.
+    url: String,
+}
+
+impl RemoteWebSocketClientPlugin {
+    /// Creates a plugin connecting to the tool at the given URL, like `ws://127.0.0.1:15703`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl Plugin for RemoteWebSocketClientPlugin {
+    fn build(&self, app: &mut App) {
+        let url = self.url.clone();
+        app.add_systems(Startup, move |request_sender: Res<BrpSender>| {
+            connect(&url, request_sender.clone());
+        });
+    }
+}
+
+/// A request sent to the world, waiting for its results.
+struct PendingRequest {
+    id: Option<Value>,
+    results: Receiver<BrpResult>,
+    /// Whether the request watches values, and gets a result each time they change.
+    watch: bool,
+}
+
+/// A helper function for the Bevy Remote Protocol server that handles a message of a client, and
+/// sends the responses to `outgoing`.
+async fn process_message(
+    message: String,
+    request_sender: Sender<BrpMessage>,
+    outgoing: Sender<String>,
+) -> anyhow::Result<()> {
+    match serde_json::from_str(&message) {
+        Ok(BrpBatch::Single(request)) => match send_request(request, &request_sender).await {
+            Ok(request) => {
+                // Watching requests get a result each time the watched values change, until the
+                // connection closes.
+                while let Ok(result) = request.results.recv().await {
+                    let response = BrpResponse::new(request.id.clone(), result);
+                    outgoing.send(serde_json::to_string(&response)?).await?;
+                }
+            }
+            Err(response) => outgoing.send(serde_json::to_string(&response)?).await?,
+        },
+        Ok(BrpBatch::Batch(requests)) => {
+            let mut responses = Vec::new();
+            for request in requests {
+                let response = match send_request(request, &request_sender).await {
+                    Ok(PendingRequest {
+                        id, watch: true, ..
+                    }) => BrpResponse::new(
+                        id,
+                        Err(BrpError {
+                            code: error_codes::INVALID_REQUEST,
+                            message: "Streaming can not be used in batch requests".to_string(),
+                            data: None,
+                        }),
+                    ),
+                    Ok(request) => BrpResponse::new(request.id, request.results.recv().await?),
+                    Err(response) => response,
+                };
+                responses.push(response);
+            }
+            outgoing.send(serde_json::to_string(&responses)?).await?;
+        }
+        Err(err) => {
+            let response = BrpResponse::new(
+                None,
+                Err(BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: err.to_string(),
+                    data: None,
+                }),
+            );
+            outgoing.send(serde_json::to_string(&response)?).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends a single request of a client to the world, or returns the error response if the request
+/// is invalid.
+async fn send_request(
+    request: Value,
+    request_sender: &Sender<BrpMessage>,
+) -> Result<PendingRequest, BrpResponse> {
+    // Reach in and get the request ID early so that we can report it even when parsing fails.
+    let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+    let request: BrpRequest = match serde_json::from_value(request) {
+        Ok(v) => v,
+        Err(err) => {
+            return Err(BrpResponse::new(
+                id,
+                Err(BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: err.to_string(),
+                    data: None,
+                }),
+            ));
+        }
+    };
+
+    if request.jsonrpc != "2.0" {
+        return Err(BrpResponse::new(
+            id,
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: String::from("JSON-RPC request requires `\"jsonrpc\": \"2.0\"`"),
+                data: None,
+            }),
+        ));
+    }
+
+    let watch = request.method.contains("+watch");
+    let size = if watch { 8 } else { 1 };
+    let (result_sender, result_receiver) = async_channel::bounded(size);
+
+    let _ = request_sender
+        .send(BrpMessage {
+            method: request.method,
+            params: request.params,
+            sender: result_sender,
+        })
+        .await;
+
+    Ok(PendingRequest {
+        id: request.id,
+        results: result_receiver,
+        watch,
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod native {
+    use super::{process_message, DEFAULT_WEBSOCKET_PORT};
+    use crate::{BrpMessage, BrpSender};
+    use anyhow::{anyhow, Result as AnyhowResult};
+    use async_channel::Sender;
+    use async_io::Async;
+    use async_tungstenite::{
+        tungstenite::{
+            handshake::server::{ErrorResponse, Request, Response},
+            http::{header::ORIGIN, StatusCode, Uri},
+            Error as WsError, Message,
+        },
+        WebSocketStream,
+    };
+    use bevy_app::{App, Plugin, Startup};
+    use bevy_ecs::system::Res;
+    use bevy_tasks::{
+        futures_lite::{future, StreamExt},
+        IoTaskPool,
+    };
+    use core::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::{
+        net::{TcpListener, TcpStream, ToSocketAddrs},
+        sync::Arc,
+    };
+
+    /// The default host address that Bevy will use for its WebSocket server.
+    pub const DEFAULT_WEBSOCKET_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+    /// Add this plugin to your [`App`] to allow remote connections over WebSocket to inspect and
+    /// modify entities. It requires the [`RemotePlugin`](crate::RemotePlugin).
+    ///
+    /// This BRP transport cannot be used when targeting WASM, where the
+    /// [`RemoteWebSocketClientPlugin`](super::RemoteWebSocketClientPlugin) connects to the remote
+    /// tool instead.
+    ///
+    /// Browsers don't apply the same-origin policy to WebSockets, so any web page could otherwise
+    /// connect to the app. Handshakes with an `Origin` header are refused, unless the origin was
+    /// allowed with [`RemoteWebSocketPlugin::with_allowed_origin`]. Tools outside of browsers don't
+    /// send the header, and can always connect.
+    ///
+    /// The defaults are:
+    /// - [`DEFAULT_WEBSOCKET_ADDR`] : 127.0.0.1.
+    /// - [`DEFAULT_WEBSOCKET_PORT`] : 15703.
+    /// - No allowed origins.
+    pub struct RemoteWebSocketPlugin {
+        /// The address that Bevy will bind to.
+        address: IpAddr,
+        /// The port that Bevy will listen on.
+        port: u16,
+        /// The origins of the web pages allowed to connect.
+        allowed_origins: Vec<String>,
+    }
+
+    impl Default for RemoteWebSocketPlugin {
+        fn default() -> Self {
+            Self {
+                address: DEFAULT_WEBSOCKET_ADDR,
+                port: DEFAULT_WEBSOCKET_PORT,
+                allowed_origins: Vec::new(),
+            }
+        }
+    }
+
+    impl Plugin for RemoteWebSocketPlugin {
+        fn build(&self, app: &mut App) {
+            let address = SocketAddr::new(self.address, self.port);
+            let allowed_origins: Arc<[String]> = self.allowed_origins.clone().into();
+            app.add_systems(Startup, move |request_sender: Res<BrpSender>| {
+                IoTaskPool::get()
+                    .spawn(listen(
+                        address,
+                        allowed_origins.clone(),
+                        request_sender.clone(),
+                    ))
+                    .detach();
+            });
+        }
+    }
+
+    impl RemoteWebSocketPlugin {
+        /// Set the IP address that the server will use.
+        #[must_use]
+        pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+            self.address = address.into();
+            self
+        }
+
+        /// Set the remote port that the server will listen on.
+        #[must_use]
+        pub fn with_port(mut self, port: u16) -> Self {
+            self.port = port;
+            self
+        }
+
+        /// Allow the web pages of the given origin, like `http://localhost:8080`, to connect.
+        ///
+        /// This is needed for browser-based inspectors, since handshakes sent by a browser are
+        /// refused by default.
+        #[must_use]
+        pub fn with_allowed_origin(mut self, origin: impl Into<String>) -> Self {
+            self.allowed_origins.push(origin.into());
+            self
+        }
+    }
+
+    /// The Bevy Remote Protocol WebSocket server main loop.
+    async fn listen(
+        address: SocketAddr,
+        allowed_origins: Arc<[String]>,
+        request_sender: Sender<BrpMessage>,
+    ) -> AnyhowResult<()> {
+        let listener = Async::<TcpListener>::bind(address)?;
+        loop {
+            let (client, _) = listener.accept().await?;
+
+            let allowed_origins = allowed_origins.clone();
+            let request_sender = request_sender.clone();
+            IoTaskPool::get()
+                .spawn(async move {
+                    let callback = |request: &Request, response: Response| {
+                        check_origin(request, &allowed_origins).map(|()| response)
+                    };
+                    let Ok(socket) = async_tungstenite::accept_hdr_async(client, callback).await
+                    else {
+                        return;
+                    };
+                    let _ = handle_connection(socket, request_sender).await;
+                })
+                .detach();
+        }
+    }
+
+    /// Refuses the handshake of a web page whose origin wasn't allowed.
+    ///
+    /// Requests without an `Origin` header don't come from a browser, and are accepted.
+    fn check_origin(request: &Request, allowed_origins: &[String]) -> Result<(), ErrorResponse> {
+        let Some(origin) = request.headers().get(ORIGIN) else {
+            return Ok(());
+        };
+        let allowed = origin
+            .to_str()
+            .is_ok_and(|origin| allowed_origins.iter().any(|allowed| allowed == origin));
+        if allowed {
+            return Ok(());
+        }
+
+        let mut response = ErrorResponse::new(Some("Origin not allowed".to_owned()));
+        *response.status_mut() = StatusCode::FORBIDDEN;
+        Err(response)
+    }
+
+    /// Connects to the remote tool at `url`, and handles its requests.
+    pub(super) fn connect(url: &str, request_sender: Sender<BrpMessage>) {
+        let url = url.to_owned();
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = connect_main(url, request_sender).await;
+            })
+            .detach();
+    }
+
+    async fn connect_main(url: String, request_sender: Sender<BrpMessage>) -> AnyhowResult<()> {
+        let uri: Uri = url.parse()?;
+        if uri.scheme_str() != Some("ws") {
+            return Err(anyhow!("Only `ws://` URLs are supported, got {url}"));
+        }
+        let host = uri.host().ok_or_else(|| anyhow!("Missing host in {url}"))?;
+        let address = (host, uri.port_u16().unwrap_or(80))
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("Failed to resolve {host}"))?;
+
+        let stream = Async::<TcpStream>::connect(address).await?;
+        let (socket, _) = async_tungstenite::client_async(url, stream).await?;
+        handle_connection(socket, request_sender).await
+    }
+
+    /// Handles the requests of a connection until it closes.
+    async fn handle_connection(
+        mut socket: WebSocketStream<Async<TcpStream>>,
+        request_sender: Sender<BrpMessage>,
+    ) -> AnyhowResult<()> {
+        let (outgoing_sender, outgoing_receiver) = async_channel::unbounded::<String>();
+
+        loop {
+            // Wait for either a message from the client, or a response to send back. The channel
+            // never closes, since `outgoing_sender` is alive.
+            let event = future::or(async { Event::Incoming(socket.next().await) }, async {
+                Event::Outgoing(outgoing_receiver.recv().await.ok())
+            })
+            .await;
+
+            match event {
+                Event::Outgoing(Some(response)) => socket.send(Message::text(response)).await?,
+                Event::Incoming(Some(Ok(Message::Text(text)))) => {
+                    IoTaskPool::get()
+                        .spawn(process_message(
+                            text.as_str().to_owned(),
+                            request_sender.clone(),
+                            outgoing_sender.clone(),
+                        ))
+                        .detach();
+                }
+                Event::Incoming(Some(Ok(Message::Close(_))) | None) => return Ok(()),
+                Event::Incoming(Some(Err(err))) => return Err(err.into()),
+                // Pings are answered by the socket, and other messages are ignored.
+                Event::Incoming(Some(Ok(_))) | Event::Outgoing(None) => {}
+            }
+        }
+    }
+
+    enum Event {
+        Incoming(Option<Result<Message, WsError>>),
+        Outgoing(Option<String>),
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn handshake(origin: Option<&str>) -> Request {
+            let mut request = Request::builder()
+                .uri("ws://127.0.0.1:15703")
+                .header("Upgrade", "websocket")
+                .header("Connection", "Upgrade")
+                .header("Sec-WebSocket-Version", "13")
+                .header("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==");
+            if let Some(origin) = origin {
+                request = request.header(ORIGIN, origin);
+            }
+            request.body(()).unwrap()
+        }
+
+        #[test]
+        fn refuse_foreign_origins() {
+            let allowed = ["http://localhost:8080".to_owned()];
+
+            // Tools outside of browsers don't send an origin.
+            assert!(check_origin(&handshake(None), &[]).is_ok());
+            assert!(check_origin(&handshake(Some("http://localhost:8080")), &allowed).is_ok());
+
+            let refused = check_origin(&handshake(Some("https://example.com")), &allowed);
+            assert_eq!(refused.unwrap_err().status(), StatusCode::FORBIDDEN);
+            assert!(check_origin(&handshake(Some("http://localhost:8080")), &[]).is_err());
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod web {
+    use super::process_message;
+    use crate::BrpMessage;
+    use async_channel::Sender;
+    use bevy_tasks::IoTaskPool;
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{MessageEvent, WebSocket};
+
+    /// Connects to the remote tool at `url` with the WebSocket API of the browser, and handles its
+    /// requests.
+    pub(super) fn connect(url: &str, request_sender: Sender<BrpMessage>) {
+        let Ok(socket) = WebSocket::new(url) else {
+            return;
+        };
+        let (outgoing_sender, outgoing_receiver) = async_channel::unbounded::<String>();
+
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            // Binary messages aren't part of the protocol.
+            let Some(text) = event.data().as_string() else {
+                return;
+            };
+            IoTaskPool::get()
+                .spawn(process_message(
+                    text,
+                    request_sender.clone(),
+                    outgoing_sender.clone(),
+                ))
+                .detach();
+        });
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        // The socket calls the closure for as long as it's open.
+        on_message.forget();
+
+        IoTaskPool::get()
+            .spawn(async move {
+                while let Ok(response) = outgoing_receiver.recv().await {
+                    // Stop once the connection closes, which drops the pending requests.
+                    if socket.ready_state() != WebSocket::OPEN
+                        || socket.send_with_str(&response).is_err()
+                    {
+                        break;
+                    }
+                }
+            })
+            .detach();
+    }
+}
@@ -3,19 +3,25 @@ mod camera_driver_node;
 mod clear_color;
 mod manual_texture_view;
 mod projection;
+mod render_scale;
+mod shake;
 
 pub use camera::*;
 pub use camera_driver_node::*;
 pub use clear_color::*;
 pub use manual_texture_view::*;
 pub use projection::*;
+pub use render_scale::*;
+pub use shake::*;
 
 use crate::{
     extract_component::ExtractComponentPlugin, extract_resource::ExtractResourcePlugin,
-    render_graph::RenderGraph, ExtractSchedule, Render, RenderApp, RenderSet,
+    render_graph::RenderGraph, view::VisibilitySystems, ExtractSchedule, Render, RenderApp,
+    RenderSet,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate, Update};
 use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_transform::TransformSystem;
 
 #[derive(Default)]
 pub struct CameraPlugin;
@@ -29,6 +35,9 @@ impl Plugin for CameraPlugin {
             .register_type::<Exposure>()
             .register_type::<TemporalJitter>()
             .register_type::<MipBias>()
+            .register_type::<CameraShake>()
+            .register_type::<RenderScale>()
+            .register_type::<DynamicRenderScale>()
             .init_resource::<ManualTextureViews>()
             .init_resource::<ClearColor>()
             .add_plugins((
@@ -36,12 +45,19 @@ impl Plugin for CameraPlugin {
                 ExtractResourcePlugin::<ManualTextureViews>::default(),
                 ExtractResourcePlugin::<ClearColor>::default(),
                 ExtractComponentPlugin::<CameraMainTextureUsages>::default(),
-            ));
+            ))
+            .add_systems(
+                PostUpdate,
+                apply_camera_shake
+                    .after(TransformSystem::TransformPropagate)
+                    .before(VisibilitySystems::UpdateFrusta),
+            )
+            .add_systems(Update, update_dynamic_render_scales);
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<SortedCameras>()
-                .add_systems(ExtractSchedule, extract_cameras)
+                .add_systems(ExtractSchedule, (extract_cameras, extract_render_scales))
                 .add_systems(Render, sort_cameras.in_set(RenderSet::ManageViews));
             let camera_driver_node = CameraDriverNode::new(render_app.world_mut());
             let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
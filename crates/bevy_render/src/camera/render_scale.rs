@@ -0,0 +1,255 @@
+use core::time::Duration;
+
+use super::{Camera, ExtractedCamera, Viewport};
+use crate::{sync_world::RenderEntity, Extract};
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::{Real, Time};
+
+/// Renders the main passes of a 3D camera at a fraction of the resolution of its viewport.
+///
+/// The scale applies to both axes: a scale of `0.5` renders a quarter of the pixels. The main
+/// passes, from the prepasses to the transparent pass, render to the top left of the
+/// viewport, and the result is upscaled to the whole viewport before post-processing. By default
+/// this upscaling is bilinear, but it can be replaced by other upscalers, see
+/// `CustomMainPassUpscaler` in `bevy_core_pipeline`.
+///
+/// Add [`DynamicRenderScale`] to the camera to adjust the scale from the frame time.
+///
+/// The viewport of the view uniform is the viewport of the main passes, so shaders converting
+/// fragment coordinates, such as clustered lighting, deferred lighting and transmission, see the
+/// scaled resolution. Screen space effects sampling the depth or prepass textures after the main passes, like
+/// temporal anti-aliasing, aren't aware of the render scale yet.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct RenderScale(pub f32);
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl RenderScale {
+    /// Returns the size the main passes are rendered at, for a viewport of `viewport_size`.
+    pub fn main_pass_size(&self, viewport_size: UVec2) -> UVec2 {
+        let scale = self.0.clamp(0.0, 1.0);
+        (viewport_size.as_vec2() * scale)
+            .round()
+            .as_uvec2()
+            .clamp(UVec2::ONE, viewport_size.max(UVec2::ONE))
+    }
+}
+
+/// Adjusts the [`RenderScale`] of a camera so that frames take [`target_frame_time`](Self::target_frame_time).
+///
+/// The frame time is measured with [`Time<Real>`], and smoothed over several frames. When it
+/// is too long, the scale decreases down to [`min_scale`](Self::min_scale), and when it is short
+/// enough, the scale increases up to [`max_scale`](Self::max_scale). The rendering cost is
+/// assumed to be proportional to the number of pixels rendered.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(RenderScale)]
+pub struct DynamicRenderScale {
+    /// The frame time to reach.
+    pub target_frame_time: Duration,
+    /// The smallest scale to render at.
+    pub min_scale: f32,
+    /// The largest scale to render at. It takes precedence if it's smaller than
+    /// [`min_scale`](Self::min_scale).
+    pub max_scale: f32,
+    /// The fraction of the difference with the ideal scale that is corrected each frame,
+    /// between `0.0` and `1.0`.
+    pub adjustment_rate: f32,
+    /// The relative difference with the target frame time under which the scale isn't adjusted,
+    /// to avoid oscillations.
+    pub tolerance: f32,
+    #[reflect(ignore)]
+    smoothed_frame_time: Option<f32>,
+}
+
+impl Default for DynamicRenderScale {
+    fn default() -> Self {
+        Self::from_target_fps(60.0)
+    }
+}
+
+impl DynamicRenderScale {
+    /// Returns the settings adjusting the scale to render `fps` frames per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` isn't a positive, finite number.
+    pub fn from_target_fps(fps: f64) -> Self {
+        assert!(
+            fps > 0.0 && fps.is_finite(),
+            "the target frame rate must be positive and finite, got {fps}"
+        );
+        Self {
+            target_frame_time: Duration::from_secs_f64(1.0 / fps),
+            min_scale: 0.5,
+            max_scale: 1.0,
+            adjustment_rate: 0.1,
+            tolerance: 0.1,
+            smoothed_frame_time: None,
+        }
+    }
+
+    /// Returns the frame time, smoothed over the last frames.
+    pub fn smoothed_frame_time(&self) -> Option<Duration> {
+        self.smoothed_frame_time.map(Duration::from_secs_f32)
+    }
+
+    /// Accounts for a frame which took `frame_time`, and returns the new scale.
+    fn adjust(&mut self, scale: f32, frame_time: Duration) -> f32 {
+        let frame_time = frame_time.as_secs_f32();
+        let smoothed = match self.smoothed_frame_time {
+            Some(smoothed) => smoothed + (frame_time - smoothed) * 0.1,
+            None => frame_time,
+        };
+        self.smoothed_frame_time = Some(smoothed);
+
+        let target = self.target_frame_time.as_secs_f32();
+        if smoothed <= 0.0 || (smoothed - target).abs() <= target * self.tolerance {
+            return self.clamp_scale(scale);
+        }
+        // The cost is proportional to the number of pixels, which is proportional to the square
+        // of the scale.
+        let ideal = scale * (target / smoothed).sqrt();
+        let scale = scale + (ideal - scale) * self.adjustment_rate.clamp(0.0, 1.0);
+        self.clamp_scale(scale)
+    }
+
+    /// Restricts `scale` to the configured range, without panicking on an invalid range.
+    fn clamp_scale(&self, scale: f32) -> f32 {
+        scale.max(self.min_scale).min(self.max_scale)
+    }
+}
+
+/// Adjusts the [`RenderScale`] of the cameras with [`DynamicRenderScale`].
+pub fn update_dynamic_render_scales(
+    time: Res<Time<Real>>,
+    mut cameras: Query<(&mut RenderScale, &mut DynamicRenderScale)>,
+) {
+    if time.delta().is_zero() {
+        return;
+    }
+    for (mut render_scale, mut dynamic) in &mut cameras {
+        let scale = dynamic.adjust(render_scale.0, time.delta());
+        render_scale.set_if_neq(RenderScale(scale));
+    }
+}
+
+/// The [`RenderScale`] of a view, in the render world.
+///
+/// Only present on views whose main passes are rendered at a lower resolution than their
+/// viewport. The `viewport` of the view uniform of these views has the size of the main passes.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ViewRenderScale {
+    /// The scale the main passes are rendered at.
+    pub scale: f32,
+    /// The size the main passes are rendered at.
+    pub main_pass_size: UVec2,
+}
+
+/// Extracts the [`RenderScale`] of the active cameras into [`ViewRenderScale`]s, and removes
+/// them from the views rendered at full resolution.
+pub fn extract_render_scales(
+    mut commands: Commands,
+    cameras: Extract<Query<(RenderEntity, &Camera, Option<&RenderScale>)>>,
+) {
+    for (render_entity, camera, render_scale) in &cameras {
+        let view_render_scale = camera
+            .physical_viewport_size()
+            .zip(render_scale)
+            .filter(|_| camera.is_active)
+            .and_then(|(viewport_size, render_scale)| {
+                let main_pass_size = render_scale.main_pass_size(viewport_size);
+                (main_pass_size != viewport_size).then_some(ViewRenderScale {
+                    scale: render_scale.0,
+                    main_pass_size,
+                })
+            });
+
+        match view_render_scale {
+            Some(view_render_scale) => {
+                commands.entity(render_entity).insert(view_render_scale);
+            }
+            None => {
+                commands.entity(render_entity).remove::<ViewRenderScale>();
+            }
+        }
+    }
+}
+
+impl ExtractedCamera {
+    /// Returns the viewport the main passes of the camera render to, which is smaller than the
+    /// viewport of the camera if it has a [`ViewRenderScale`].
+    pub fn main_pass_viewport(&self, render_scale: Option<&ViewRenderScale>) -> Option<Viewport> {
+        let Some(render_scale) = render_scale else {
+            return self.viewport.clone();
+        };
+        let mut viewport = self.viewport.clone().unwrap_or_default();
+        viewport.physical_size = render_scale.main_pass_size;
+        Some(viewport)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_pass_size() {
+        assert_eq!(
+            RenderScale(0.5).main_pass_size(UVec2::new(1920, 1080)),
+            UVec2::new(960, 540)
+        );
+        assert_eq!(
+            RenderScale(0.0).main_pass_size(UVec2::new(1920, 1080)),
+            UVec2::ONE
+        );
+        assert_eq!(
+            RenderScale(2.0).main_pass_size(UVec2::new(1920, 1080)),
+            UVec2::new(1920, 1080)
+        );
+    }
+
+    #[test]
+    fn dynamic_render_scale() {
+        let mut dynamic = DynamicRenderScale {
+            adjustment_rate: 1.0,
+            ..DynamicRenderScale::from_target_fps(50.0)
+        };
+
+        // Frames taking 4 times the target need half the pixels on each axis.
+        let scale = dynamic.adjust(1.0, Duration::from_millis(80));
+        assert!((scale - 0.5).abs() < 1e-4);
+
+        // Within the tolerance, the scale doesn't change.
+        let mut dynamic = DynamicRenderScale::from_target_fps(50.0);
+        assert_eq!(dynamic.adjust(0.8, Duration::from_millis(21)), 0.8);
+
+        // Short frames increase the scale, up to the maximum.
+        let mut dynamic = DynamicRenderScale {
+            adjustment_rate: 1.0,
+            ..DynamicRenderScale::from_target_fps(50.0)
+        };
+        assert_eq!(dynamic.adjust(0.6, Duration::from_millis(5)), 1.0);
+
+        // An inverted range doesn't panic, and the maximum wins.
+        let mut dynamic = DynamicRenderScale {
+            min_scale: 0.9,
+            max_scale: 0.7,
+            ..DynamicRenderScale::from_target_fps(50.0)
+        };
+        assert_eq!(dynamic.adjust(0.8, Duration::from_millis(20)), 0.7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_target_fps() {
+        DynamicRenderScale::from_target_fps(0.0);
+    }
+}
@@ -0,0 +1,210 @@
+use bevy_ecs::prelude::*;
+use bevy_math::{ops, EulerRot, Quat, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// Shakes a camera, for example when an explosion happens nearby.
+///
+/// The strength of the shake is driven by its [`trauma`](Self::trauma), a value between `0.0` and `1.0`
+/// that gameplay code increases with [`CameraShake::add_trauma`] and that decays over time.
+/// The amplitude of the shake is `trauma` raised to [`trauma_exponent`](Self::trauma_exponent),
+/// so that small amounts of trauma give subtle shakes.
+///
+/// Each axis of the translation and rotation is offset by its own channel of smooth noise, sampled
+/// at [`frequency`](Self::frequency). The offsets are applied to the [`GlobalTransform`] of the camera
+/// after transform propagation, on top of the transform computed from the hierarchy. This means the shake
+/// composes with any camera rig driving the [`Transform`], and never accumulates in it.
+/// Children of the camera aren't shaken.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::camera::CameraShake;
+/// fn on_explosion(mut cameras: Query<&mut CameraShake>) {
+///     for mut shake in &mut cameras {
+///         shake.add_trauma(0.5);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct CameraShake {
+    /// The current strength of the shake, between `0.0` and `1.0`.
+    pub trauma: f32,
+    /// How much [`trauma`](Self::trauma) decreases each second.
+    pub trauma_decay: f32,
+    /// The exponent applied to [`trauma`](Self::trauma) to compute the amplitude of the shake.
+    pub trauma_exponent: f32,
+    /// The largest translation offset along each axis, reached at full amplitude.
+    pub max_translation: Vec3,
+    /// The largest rotation offset around each axis in radians, reached at full amplitude.
+    ///
+    /// The rotation is applied as yaw (around Y), pitch (around X) and roll (around Z).
+    pub max_rotation: Vec3,
+    /// How many times per second the noise changes direction, on average.
+    pub frequency: f32,
+    /// The seed of the noise. Cameras with different seeds shake differently.
+    pub seed: u32,
+    /// The time at which the noise is sampled.
+    #[reflect(ignore)]
+    time: f32,
+    /// The transform computed by propagation, and the shaken transform that replaced it.
+    #[reflect(ignore)]
+    applied: Option<(GlobalTransform, GlobalTransform)>,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            trauma_decay: 1.0,
+            trauma_exponent: 2.0,
+            max_translation: Vec3::splat(0.1),
+            max_rotation: Vec3::new(0.05, 0.05, 0.1),
+            frequency: 15.0,
+            seed: 0,
+            time: 0.0,
+            applied: None,
+        }
+    }
+}
+
+impl CameraShake {
+    /// Increases the [`trauma`](Self::trauma) by `amount`, up to `1.0`.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Returns the current amplitude of the shake, between `0.0` and `1.0`.
+    pub fn amplitude(&self) -> f32 {
+        ops::powf(self.trauma.clamp(0.0, 1.0), self.trauma_exponent)
+    }
+
+    /// Returns the translation and rotation offsets of the shake at its current time.
+    pub fn offsets(&self) -> (Vec3, Quat) {
+        let amplitude = self.amplitude();
+        let t = self.time * self.frequency;
+        let channel = |index: u32| noise(self.seed.wrapping_add(index), t) * amplitude;
+        let translation = Vec3::new(channel(0), channel(1), channel(2)) * self.max_translation;
+        let rotation = Vec3::new(channel(3), channel(4), channel(5)) * self.max_rotation;
+        (
+            translation,
+            Quat::from_euler(EulerRot::YXZ, rotation.y, rotation.x, rotation.z),
+        )
+    }
+}
+
+/// Applies [`CameraShake`] to the [`GlobalTransform`] of cameras, and decays their trauma.
+///
+/// This runs after transform propagation, and before frusta are updated.
+pub fn apply_camera_shake(
+    time: Res<Time>,
+    mut cameras: Query<(&mut CameraShake, &mut GlobalTransform)>,
+) {
+    for (mut shake, mut transform) in &mut cameras {
+        let shake = &mut *shake;
+        // If the transform wasn't recomputed by propagation since the last shake, start from the
+        // transform it had before being shaken.
+        let base = match shake.applied {
+            Some((base, shaken)) if shaken == *transform => base,
+            _ => *transform,
+        };
+
+        if shake.trauma <= 0.0 {
+            if shake.applied.take().is_some() {
+                *transform = base;
+            }
+            continue;
+        }
+
+        shake.time += time.delta_secs();
+        let (translation, rotation) = shake.offsets();
+        shake.trauma = (shake.trauma - shake.trauma_decay * time.delta_secs()).max(0.0);
+
+        let (scale, base_rotation, base_translation) = base.to_scale_rotation_translation();
+        let shaken = GlobalTransform::from(Transform {
+            translation: base_translation + base_rotation * translation,
+            rotation: base_rotation * rotation,
+            scale,
+        });
+        *transform = shaken;
+        shake.applied = Some((base, shaken));
+    }
+}
+
+/// Returns one-dimensional gradient noise at `x`, between `-1.0` and `1.0`.
+fn noise(seed: u32, x: f32) -> f32 {
+    let cell = ops::floor(x);
+    let t = x - cell;
+    let cell = cell as i32;
+    let gradient = |cell: i32| {
+        let hash = hash(seed ^ (cell as u32).wrapping_mul(0x9e37_79b9));
+        hash as f32 / u32::MAX as f32 * 2.0 - 1.0
+    };
+    let start = gradient(cell) * t;
+    let end = gradient(cell + 1) * (t - 1.0);
+    let fade = t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    // One-dimensional gradient noise lies within [-0.5, 0.5].
+    (start + (end - start) * fade) * 2.0
+}
+
+fn hash(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, PostUpdate};
+    use core::time::Duration;
+
+    #[test]
+    fn noise_is_bounded_and_smooth() {
+        for i in 0..1000 {
+            let x = i as f32 * 0.013;
+            let value = noise(7, x);
+            assert!((-1.0..=1.0).contains(&value));
+            assert!((noise(7, x + 0.001) - value).abs() < 0.02);
+        }
+        assert_eq!(noise(7, 3.0), 0.0);
+    }
+
+    #[test]
+    fn shake_is_additive_and_decays() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(PostUpdate, apply_camera_shake);
+        let base = GlobalTransform::from_xyz(1.0, 2.0, 3.0);
+        let camera = app
+            .world_mut()
+            .spawn((
+                CameraShake {
+                    trauma: 1.0,
+                    trauma_decay: 0.5,
+                    ..Default::default()
+                },
+                base,
+            ))
+            .id();
+
+        // The trauma reaches zero on the fourth update, and the shake is removed on the fifth.
+        for _ in 0..5 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(520));
+            app.update();
+            let transform = app.world().get::<GlobalTransform>(camera).unwrap();
+            // The offsets never accumulate.
+            assert!(transform.translation().distance(base.translation()) <= 0.2);
+        }
+
+        let shake = app.world().get::<CameraShake>(camera).unwrap();
+        assert_eq!(shake.trauma, 0.0);
+        assert_eq!(app.world().get::<GlobalTransform>(camera), Some(&base));
+    }
+}
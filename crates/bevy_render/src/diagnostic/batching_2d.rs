@@ -0,0 +1,228 @@
+use alloc::sync::Arc;
+use std::sync::Mutex;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::AssetId;
+use bevy_derive::Deref;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::{
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_image::Image;
+use bevy_platform_support::collections::HashMap;
+
+use crate::RenderApp;
+
+/// Reports how many draw calls the 2D render phases issue, and why consecutive items could not be
+/// batched together.
+///
+/// Sprites are only batched while consecutive items in the transparent 2D phase use the same
+/// texture, and meshes while they use the same material and mesh. Each time a new draw call is
+/// started, the [`BatchBreakCause`] is recorded by the 2D renderer, and the statistics of the
+/// last rendered frame are available in the [`Batching2dReport`] resource. The texture switches
+/// are also counted by pair of textures, which tells which textures would gain the most from
+/// being packed into a texture atlas.
+///
+/// To access the diagnostics, you can use the [`DiagnosticsStore`](bevy_diagnostic::DiagnosticsStore) resource,
+/// or add [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin).
+#[derive(Default)]
+pub struct Batching2dDiagnosticsPlugin;
+
+impl Plugin for Batching2dDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let tracker = Batching2dTracker::default();
+        app.insert_resource(tracker.clone())
+            .init_resource::<Batching2dReport>()
+            .register_diagnostic(Diagnostic::new(Self::DRAW_CALLS))
+            .register_diagnostic(Diagnostic::new(Self::TEXTURE_SWITCHES))
+            .register_diagnostic(Diagnostic::new(Self::Z_ORDER_INTERLEAVES))
+            .register_diagnostic(Diagnostic::new(Self::MATERIAL_CHANGES))
+            .register_diagnostic(Diagnostic::new(Self::OTHER_BREAKS))
+            .add_systems(PreUpdate, update_batching_2d_report);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(tracker);
+        }
+    }
+}
+
+impl Batching2dDiagnosticsPlugin {
+    /// The number of draw calls issued by the transparent 2D phase of all views.
+    pub const DRAW_CALLS: DiagnosticPath =
+        DiagnosticPath::const_new("render/batching_2d/draw_calls");
+    /// The number of batches broken because two consecutive sprites used different textures.
+    pub const TEXTURE_SWITCHES: DiagnosticPath =
+        DiagnosticPath::const_new("render/batching_2d/texture_switches");
+    /// The number of batches broken because an item of another kind, such as a mesh between two
+    /// sprites, was sorted between them.
+    pub const Z_ORDER_INTERLEAVES: DiagnosticPath =
+        DiagnosticPath::const_new("render/batching_2d/z_order_interleaves");
+    /// The number of batches broken because two consecutive meshes used different materials.
+    pub const MATERIAL_CHANGES: DiagnosticPath =
+        DiagnosticPath::const_new("render/batching_2d/material_changes");
+    /// The number of batches broken for any other reason, such as two consecutive meshes using
+    /// different mesh assets, or automatic batching being disabled.
+    pub const OTHER_BREAKS: DiagnosticPath =
+        DiagnosticPath::const_new("render/batching_2d/other_breaks");
+}
+
+/// The reason why a 2D phase item could not be added to the batch of the previous item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BatchBreakCause {
+    /// The item is a sprite using a different texture than the previous sprite.
+    TextureSwitch,
+    /// The item is drawn by a different draw function than the previous item, for example a
+    /// sprite following a mesh.
+    ZOrderInterleave,
+    /// The item is a mesh using a different material than the previous mesh.
+    MaterialChange,
+    /// The item can't be batched for another reason.
+    Other,
+}
+
+/// The batching statistics of the 2D render phases for a single frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Batching2dStats {
+    /// The number of draw calls issued.
+    pub draw_calls: u32,
+    /// The number of batches broken by a [`BatchBreakCause::TextureSwitch`].
+    pub texture_switches: u32,
+    /// The number of batches broken by a [`BatchBreakCause::ZOrderInterleave`].
+    pub z_order_interleaves: u32,
+    /// The number of batches broken by a [`BatchBreakCause::MaterialChange`].
+    pub material_changes: u32,
+    /// The number of batches broken by a [`BatchBreakCause::Other`].
+    pub other_breaks: u32,
+    /// The number of texture switches between each pair of textures, in either direction.
+    ///
+    /// The smallest id of each pair comes first.
+    pub texture_pairs: HashMap<(AssetId<Image>, AssetId<Image>), u32>,
+}
+
+impl Batching2dStats {
+    /// Records a draw call which continues no previous batch, because of `cause`.
+    ///
+    /// The first draw call of a phase has no cause.
+    pub fn record_draw(&mut self, cause: Option<BatchBreakCause>) {
+        self.draw_calls += 1;
+        match cause {
+            Some(BatchBreakCause::TextureSwitch) => self.texture_switches += 1,
+            Some(BatchBreakCause::ZOrderInterleave) => self.z_order_interleaves += 1,
+            Some(BatchBreakCause::MaterialChange) => self.material_changes += 1,
+            Some(BatchBreakCause::Other) => self.other_breaks += 1,
+            None => {}
+        }
+    }
+
+    /// Records a draw call for a sprite using the texture `to`, following a sprite using `from`.
+    pub fn record_texture_switch(&mut self, from: AssetId<Image>, to: AssetId<Image>) {
+        self.record_draw(Some(BatchBreakCause::TextureSwitch));
+        *self
+            .texture_pairs
+            .entry((from.min(to), from.max(to)))
+            .or_default() += 1;
+    }
+
+    /// The number of draw calls which continue no previous batch, regardless of the cause.
+    pub fn batch_breaks(&self) -> u32 {
+        self.texture_switches + self.z_order_interleaves + self.material_changes + self.other_breaks
+    }
+
+    /// Returns the pairs of textures by number of texture switches, from most to least frequent.
+    pub fn texture_pairs_by_count(&self) -> Vec<((AssetId<Image>, AssetId<Image>), u32)> {
+        let mut pairs: Vec<_> = self
+            .texture_pairs
+            .iter()
+            .map(|(pair, count)| (*pair, *count))
+            .collect();
+        pairs.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        pairs
+    }
+}
+
+/// Collects the [`Batching2dStats`] recorded by the 2D renderer.
+///
+/// This is shared between the main world and the render world, and only present if the
+/// [`Batching2dDiagnosticsPlugin`] was added. Renderers should skip collecting the statistics
+/// when it is missing.
+#[derive(Resource, Clone, Default)]
+pub struct Batching2dTracker(Arc<Mutex<Option<Batching2dStats>>>);
+
+impl Batching2dTracker {
+    /// Replaces the statistics with the ones of the frame that was just prepared.
+    pub fn submit(&self, stats: Batching2dStats) {
+        *self.0.lock().unwrap() = Some(stats);
+    }
+
+    fn take(&self) -> Option<Batching2dStats> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// The batching statistics of the last frame rendered, updated by the [`Batching2dDiagnosticsPlugin`].
+#[derive(Resource, Clone, Debug, Default, Deref)]
+pub struct Batching2dReport(pub Batching2dStats);
+
+/// Updates the [`Batching2dReport`] and the diagnostics when the render world submitted a frame.
+fn update_batching_2d_report(
+    tracker: Res<Batching2dTracker>,
+    mut report: ResMut<Batching2dReport>,
+    mut diagnostics: Diagnostics,
+) {
+    let Some(stats) = tracker.take() else {
+        return;
+    };
+
+    diagnostics.add_measurement(&Batching2dDiagnosticsPlugin::DRAW_CALLS, || {
+        stats.draw_calls as f64
+    });
+    diagnostics.add_measurement(&Batching2dDiagnosticsPlugin::TEXTURE_SWITCHES, || {
+        stats.texture_switches as f64
+    });
+    diagnostics.add_measurement(&Batching2dDiagnosticsPlugin::Z_ORDER_INTERLEAVES, || {
+        stats.z_order_interleaves as f64
+    });
+    diagnostics.add_measurement(&Batching2dDiagnosticsPlugin::MATERIAL_CHANGES, || {
+        stats.material_changes as f64
+    });
+    diagnostics.add_measurement(&Batching2dDiagnosticsPlugin::OTHER_BREAKS, || {
+        stats.other_breaks as f64
+    });
+    report.0 = stats;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::uuid::Uuid;
+
+    #[test]
+    fn texture_switches_are_counted_by_pair() {
+        let a = AssetId::<Image>::Uuid {
+            uuid: Uuid::from_u128(1),
+        };
+        let b = AssetId::<Image>::Uuid {
+            uuid: Uuid::from_u128(2),
+        };
+        let c = AssetId::<Image>::Uuid {
+            uuid: Uuid::from_u128(3),
+        };
+
+        let mut stats = Batching2dStats::default();
+        stats.record_draw(None);
+        stats.record_texture_switch(a, b);
+        stats.record_texture_switch(b, a);
+        stats.record_draw(Some(BatchBreakCause::ZOrderInterleave));
+        stats.record_texture_switch(c, a);
+
+        assert_eq!(stats.draw_calls, 5);
+        assert_eq!(stats.texture_switches, 3);
+        assert_eq!(stats.z_order_interleaves, 1);
+        assert_eq!(stats.batch_breaks(), 4);
+        assert_eq!(
+            stats.texture_pairs_by_count(),
+            vec![((a, b), 2), ((a, c), 1)]
+        );
+    }
+}
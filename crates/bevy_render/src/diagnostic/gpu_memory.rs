@@ -0,0 +1,420 @@
+use alloc::sync::Arc;
+use core::{
+    any::TypeId,
+    cell::Cell,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::sync::Mutex;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{AssetId, UntypedAssetId};
+use bevy_diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+use bevy_ecs::{
+    resource::Resource,
+    system::{Local, Res, ResMut},
+};
+use bevy_platform_support::{collections::HashMap, time::Instant};
+use disqualified::ShortName;
+use tracing::warn;
+
+use crate::{render_asset::RenderAsset, renderer::RenderDevice, RenderApp};
+
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Tracks the GPU memory allocated by the renderer, and reports it as diagnostics.
+///
+/// Buffers and textures created through the [`RenderDevice`] are counted until they are dropped.
+/// Allocations made while preparing a [`RenderAsset`] are also attributed to that asset, so that
+/// the memory used by each asset type, and by each individual asset, can be inspected in the
+/// [`GpuMemoryReport`] resource.
+///
+/// The sizes are estimates: they don't include the padding and alignment added by drivers, nor
+/// memory allocated directly through `wgpu`.
+///
+/// To access the diagnostics, you can use the [`DiagnosticsStore`] resource,
+/// or add [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin).
+#[derive(Default)]
+pub struct GpuMemoryDiagnosticsPlugin {
+    /// The number of bytes of GPU memory above which a warning is logged.
+    ///
+    /// This can be changed at runtime with the [`GpuMemoryBudget`] resource.
+    pub budget: Option<u64>,
+}
+
+impl Plugin for GpuMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let tracker = GpuMemoryTracker::default();
+        app.insert_resource(tracker.clone())
+            .insert_resource(GpuMemoryBudget {
+                max_bytes: self.budget,
+            })
+            .init_resource::<GpuMemoryReport>()
+            .add_systems(PreUpdate, update_gpu_memory_report);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(tracker);
+        }
+    }
+}
+
+impl GpuMemoryDiagnosticsPlugin {
+    /// The GPU memory allocated for buffers, in MiB.
+    pub const BUFFERS: DiagnosticPath = DiagnosticPath::const_new("render/gpu_memory/buffers");
+    /// The GPU memory allocated for textures, in MiB.
+    pub const TEXTURES: DiagnosticPath = DiagnosticPath::const_new("render/gpu_memory/textures");
+    /// The GPU memory allocated for buffers and textures, in MiB.
+    pub const TOTAL: DiagnosticPath = DiagnosticPath::const_new("render/gpu_memory/total");
+
+    /// The path of the diagnostic recording the GPU memory allocated by the render asset `A`, in MiB.
+    pub fn asset_type_path<A: RenderAsset>() -> DiagnosticPath {
+        Self::asset_type_path_from_name(core::any::type_name::<A>())
+    }
+
+    fn asset_type_path_from_name(name: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!("render/gpu_memory/assets/{}", ShortName(name)))
+    }
+}
+
+/// The number of bytes of GPU memory above which the [`GpuMemoryDiagnosticsPlugin`] logs a warning.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct GpuMemoryBudget {
+    /// The budget in bytes, or `None` to never warn.
+    pub max_bytes: Option<u64>,
+}
+
+/// The GPU memory allocated by the renderer, updated every frame by the [`GpuMemoryDiagnosticsPlugin`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct GpuMemoryReport {
+    /// The bytes allocated for buffers.
+    pub buffer_bytes: u64,
+    /// The bytes allocated for textures.
+    pub texture_bytes: u64,
+    /// The memory allocated by each render asset type, from largest to smallest.
+    pub asset_types: Vec<GpuMemoryAssetType>,
+}
+
+impl GpuMemoryReport {
+    /// The bytes allocated for buffers and textures.
+    pub fn total_bytes(&self) -> u64 {
+        self.buffer_bytes + self.texture_bytes
+    }
+
+    /// Returns the memory allocated by the render asset `A`, if any.
+    pub fn asset_type<A: RenderAsset>(&self) -> Option<&GpuMemoryAssetType> {
+        self.asset_types
+            .iter()
+            .find(|asset_type| asset_type.type_id == TypeId::of::<A>())
+    }
+
+    /// Returns the bytes allocated when preparing the asset `id` of the render asset `A`.
+    pub fn asset_bytes<A: RenderAsset>(&self, id: impl Into<AssetId<A::SourceAsset>>) -> u64 {
+        self.asset_type::<A>()
+            .and_then(|asset_type| asset_type.assets.get(&id.into().untyped()))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// The GPU memory allocated by a render asset type, as reported in the [`GpuMemoryReport`].
+#[derive(Clone, Debug)]
+pub struct GpuMemoryAssetType {
+    type_id: TypeId,
+    /// The type name of the render asset.
+    pub name: &'static str,
+    /// The bytes allocated by all assets of this type.
+    pub bytes: u64,
+    /// The bytes allocated when preparing each asset of this type.
+    pub assets: HashMap<UntypedAssetId, u64>,
+}
+
+/// Attributes the allocations made while preparing render assets to these assets.
+///
+/// This is shared between the main world and the render world, and only present if the
+/// [`GpuMemoryDiagnosticsPlugin`] was added.
+#[derive(Resource, Clone, Default)]
+pub struct GpuMemoryTracker(Arc<Mutex<TrackedAssets>>);
+
+#[derive(Default)]
+struct TrackedAssets {
+    asset_types: HashMap<TypeId, GpuMemoryAssetType>,
+    changed: bool,
+}
+
+impl GpuMemoryTracker {
+    /// Records that `bytes` were allocated while preparing the asset `id`.
+    pub(crate) fn record<A: RenderAsset>(&self, id: AssetId<A::SourceAsset>, bytes: u64) {
+        if bytes == 0 {
+            self.remove::<A>(id);
+            return;
+        }
+        let mut tracked = self.0.lock().unwrap();
+        let asset_type = tracked
+            .asset_types
+            .entry(TypeId::of::<A>())
+            .or_insert_with(|| GpuMemoryAssetType {
+                type_id: TypeId::of::<A>(),
+                name: core::any::type_name::<A>(),
+                bytes: 0,
+                assets: HashMap::default(),
+            });
+        let previous = asset_type.assets.insert(id.untyped(), bytes).unwrap_or(0);
+        asset_type.bytes = asset_type.bytes - previous + bytes;
+        tracked.changed = true;
+    }
+
+    /// Forgets the memory allocated for the asset `id`, which was unloaded.
+    pub(crate) fn remove<A: RenderAsset>(&self, id: AssetId<A::SourceAsset>) {
+        let mut tracked = self.0.lock().unwrap();
+        let Some(asset_type) = tracked.asset_types.get_mut(&TypeId::of::<A>()) else {
+            return;
+        };
+        if let Some(bytes) = asset_type.assets.remove(&id.untyped()) {
+            asset_type.bytes -= bytes;
+            tracked.changed = true;
+        }
+    }
+}
+
+/// Counts the GPU memory allocated through a [`RenderDevice`].
+#[derive(Debug, Default)]
+pub(crate) struct GpuMemoryCounters {
+    buffer_bytes: AtomicU64,
+    texture_bytes: AtomicU64,
+}
+
+thread_local! {
+    /// The bytes allocated on this thread, used to attribute allocations to the asset being prepared.
+    static ALLOCATED_ON_THREAD: Cell<u64> = const { Cell::new(0) };
+}
+
+impl GpuMemoryCounters {
+    pub(crate) fn allocate_buffer(self: &Arc<Self>, bytes: u64) -> Arc<GpuAllocation> {
+        self.allocate(bytes, false)
+    }
+
+    pub(crate) fn allocate_texture(
+        self: &Arc<Self>,
+        desc: &wgpu::TextureDescriptor,
+    ) -> Arc<GpuAllocation> {
+        self.allocate(texture_bytes(desc), true)
+    }
+
+    fn allocate(self: &Arc<Self>, bytes: u64, texture: bool) -> Arc<GpuAllocation> {
+        self.counter(texture).fetch_add(bytes, Ordering::Relaxed);
+        ALLOCATED_ON_THREAD.with(|allocated| allocated.set(allocated.get() + bytes));
+        Arc::new(GpuAllocation {
+            counters: self.clone(),
+            bytes,
+            texture,
+        })
+    }
+
+    fn counter(&self, texture: bool) -> &AtomicU64 {
+        if texture {
+            &self.texture_bytes
+        } else {
+            &self.buffer_bytes
+        }
+    }
+
+    pub(crate) fn buffer_bytes(&self) -> u64 {
+        self.buffer_bytes.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn texture_bytes(&self) -> u64 {
+        self.texture_bytes.load(Ordering::Relaxed)
+    }
+}
+
+/// Keeps a buffer or texture counted in the [`GpuMemoryCounters`] until it is dropped.
+#[derive(Debug)]
+pub(crate) struct GpuAllocation {
+    counters: Arc<GpuMemoryCounters>,
+    bytes: u64,
+    texture: bool,
+}
+
+impl Drop for GpuAllocation {
+    fn drop(&mut self) {
+        self.counters
+            .counter(self.texture)
+            .fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// Returns the number of bytes allocated on the current thread so far.
+///
+/// The difference between two calls is the memory allocated by the code that ran in between.
+pub(crate) fn allocated_on_thread() -> u64 {
+    ALLOCATED_ON_THREAD.with(Cell::get)
+}
+
+/// Estimates the memory used by a texture, including all its mip levels and samples.
+fn texture_bytes(desc: &wgpu::TextureDescriptor) -> u64 {
+    let (block_width, block_height) = desc.format.block_dimensions();
+    // Depth and stencil formats may not have a defined size, assume 4 bytes per texel.
+    let block_size = desc.format.block_copy_size(None).unwrap_or(4) as u64;
+    let bytes: u64 = (0..desc.mip_level_count)
+        .filter_map(|level| desc.mip_level_size(level))
+        .map(|size| {
+            size.width.div_ceil(block_width) as u64
+                * size.height.div_ceil(block_height) as u64
+                * size.depth_or_array_layers as u64
+                * block_size
+        })
+        .sum();
+    bytes * desc.sample_count as u64
+}
+
+/// Updates the [`GpuMemoryReport`] and the diagnostics, and warns when the [`GpuMemoryBudget`] is exceeded.
+fn update_gpu_memory_report(
+    render_device: Option<Res<RenderDevice>>,
+    tracker: Res<GpuMemoryTracker>,
+    budget: Res<GpuMemoryBudget>,
+    mut report: ResMut<GpuMemoryReport>,
+    mut store: ResMut<DiagnosticsStore>,
+    mut over_budget: Local<bool>,
+) {
+    let Some(render_device) = render_device else {
+        return;
+    };
+
+    report.buffer_bytes = render_device.allocated_buffer_bytes();
+    report.texture_bytes = render_device.allocated_texture_bytes();
+    {
+        let mut tracked = tracker.0.lock().unwrap();
+        if tracked.changed {
+            tracked.changed = false;
+            tracked
+                .asset_types
+                .retain(|_, asset_type| !asset_type.assets.is_empty());
+            report.asset_types = tracked.asset_types.values().cloned().collect();
+            report
+                .asset_types
+                .sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.name.cmp(b.name)));
+        }
+    }
+
+    let time = Instant::now();
+    let mut add_measurement = |path: DiagnosticPath, bytes: u64| {
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path.clone()).with_suffix(" MiB"));
+        }
+        store
+            .get_mut(&path)
+            .unwrap()
+            .add_measurement(DiagnosticMeasurement {
+                time,
+                value: bytes as f64 / MIB,
+            });
+    };
+    add_measurement(GpuMemoryDiagnosticsPlugin::BUFFERS, report.buffer_bytes);
+    add_measurement(GpuMemoryDiagnosticsPlugin::TEXTURES, report.texture_bytes);
+    add_measurement(GpuMemoryDiagnosticsPlugin::TOTAL, report.total_bytes());
+    for asset_type in &report.asset_types {
+        add_measurement(
+            GpuMemoryDiagnosticsPlugin::asset_type_path_from_name(asset_type.name),
+            asset_type.bytes,
+        );
+    }
+
+    // Only warn when the budget starts being exceeded, rather than every frame.
+    let exceeded = budget
+        .max_bytes
+        .is_some_and(|max_bytes| report.total_bytes() > max_bytes);
+    if exceeded && !*over_budget {
+        let largest = report
+            .asset_types
+            .iter()
+            .take(3)
+            .map(|asset_type| {
+                format!(
+                    "{} ({:.1} MiB)",
+                    ShortName(asset_type.name),
+                    asset_type.bytes as f64 / MIB
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        warn!(
+            "GPU memory usage ({:.1} MiB) exceeds the budget of {:.1} MiB. Largest asset types: {}",
+            report.total_bytes() as f64 / MIB,
+            budget.max_bytes.unwrap_or(0) as f64 / MIB,
+            largest
+        );
+    }
+    *over_budget = exceeded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::GpuImage;
+    use bevy_asset::uuid::Uuid;
+    use bevy_image::Image;
+    use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+    #[test]
+    fn tracker_totals() {
+        let tracker = GpuMemoryTracker::default();
+        let a = AssetId::<Image>::Uuid {
+            uuid: Uuid::from_u128(1),
+        };
+        let b = AssetId::<Image>::Uuid {
+            uuid: Uuid::from_u128(2),
+        };
+        let total = || {
+            let tracked = tracker.0.lock().unwrap();
+            let asset_type = &tracked.asset_types[&TypeId::of::<GpuImage>()];
+            (asset_type.bytes, asset_type.assets.len())
+        };
+
+        tracker.record::<GpuImage>(a, 100);
+        tracker.record::<GpuImage>(b, 50);
+        assert_eq!(total(), (150, 2));
+
+        // Preparing an asset again replaces its previous allocation.
+        tracker.record::<GpuImage>(a, 30);
+        assert_eq!(total(), (80, 2));
+
+        tracker.remove::<GpuImage>(b);
+        assert_eq!(total(), (30, 1));
+
+        // Removing an untracked asset does nothing.
+        tracker.remove::<GpuImage>(b);
+        assert_eq!(total(), (30, 1));
+
+        // Recording no allocation forgets the asset.
+        tracker.record::<GpuImage>(a, 0);
+        assert_eq!(total(), (0, 0));
+    }
+
+    #[test]
+    fn texture_bytes_include_mips_and_samples() {
+        let mut desc = wgpu::TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 3,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        // 4x4 + 2x2 + 1x1 texels of 4 bytes.
+        assert_eq!(texture_bytes(&desc), 84);
+
+        desc.mip_level_count = 1;
+        desc.sample_count = 4;
+        assert_eq!(texture_bytes(&desc), 256);
+
+        // 4x4 texels are a single 8 byte block.
+        desc.sample_count = 1;
+        desc.format = TextureFormat::Bc1RgbaUnorm;
+        assert_eq!(texture_bytes(&desc), 8);
+    }
+}
@@ -17,7 +17,7 @@ use wgpu::{
 
 use crate::renderer::{RenderDevice, WgpuWrapper};
 
-use super::RecordDiagnostics;
+use super::{RecordDiagnostics, RenderDiagnosticsPlugin};
 
 // buffer offset must be divisible by 256, so this constant must be divisible by 32 (=256/8)
 const MAX_TIMESTAMP_QUERIES: u32 = 256;
@@ -68,7 +68,10 @@ impl DiagnosticsRecorder {
     }
 
     /// Begins recording diagnostics for a new frame.
-    pub fn begin_frame(&mut self) {
+    ///
+    /// The `encoder` should be the first one submitted in the frame, as the GPU frame time is
+    /// measured from it.
+    pub fn begin_frame(&mut self, encoder: &mut CommandEncoder) {
         let internal = &mut self.0;
         let mut idx = 0;
         while idx < internal.submitted_frames.len() {
@@ -81,7 +84,7 @@ impl DiagnosticsRecorder {
             }
         }
 
-        self.current_frame_mut().begin();
+        self.current_frame_mut().begin(encoder);
     }
 
     /// Copies data from [`QuerySet`]'s to a [`Buffer`], after which it can be downloaded to CPU.
@@ -154,6 +157,8 @@ struct SpanRecord {
 }
 
 struct FrameData {
+    frame_begin_timestamp_index: Option<u32>,
+    frame_end_timestamp_index: Option<u32>,
     timestamps_query_set: Option<QuerySet>,
     num_timestamps: u32,
     supports_timestamps_inside_passes: bool,
@@ -220,6 +225,8 @@ impl FrameData {
         };
 
         FrameData {
+            frame_begin_timestamp_index: None,
+            frame_end_timestamp_index: None,
             timestamps_query_set,
             num_timestamps: 0,
             supports_timestamps_inside_passes: features
@@ -240,12 +247,14 @@ impl FrameData {
         }
     }
 
-    fn begin(&mut self) {
+    fn begin(&mut self, encoder: &mut CommandEncoder) {
         self.num_timestamps = 0;
         self.num_pipeline_statistics = 0;
         self.path_components.clear();
         self.open_spans.clear();
         self.closed_spans.clear();
+        self.frame_begin_timestamp_index = self.write_timestamp(encoder, false);
+        self.frame_end_timestamp_index = None;
     }
 
     fn write_timestamp(
@@ -387,6 +396,11 @@ impl FrameData {
     }
 
     fn resolve(&mut self, encoder: &mut CommandEncoder) {
+        // The frame ends with the commands resolving the queries.
+        if self.frame_begin_timestamp_index.is_some() {
+            self.frame_end_timestamp_index = self.write_timestamp(encoder, false);
+        }
+
         let Some(resolve_buffer) = &self.resolve_buffer else {
             return;
         };
@@ -487,6 +501,23 @@ impl FrameData {
 
         let mut diagnostics = Vec::new();
 
+        let elapsed_ms = |begin: u32, end: u32| {
+            let begin = timestamps[begin as usize] as f64;
+            let end = timestamps[end as usize] as f64;
+            (end - begin) * (timestamp_period_ns as f64) / 1e6
+        };
+
+        if let (Some(begin), Some(end)) = (
+            self.frame_begin_timestamp_index,
+            self.frame_end_timestamp_index,
+        ) {
+            diagnostics.push(RenderDiagnostic {
+                path: RenderDiagnosticsPlugin::GPU_FRAME_TIME,
+                suffix: "ms",
+                value: elapsed_ms(begin, end),
+            });
+        }
+
         for span in &self.closed_spans {
             if let (Some(begin), Some(end)) = (span.begin_instant, span.end_instant) {
                 diagnostics.push(RenderDiagnostic {
@@ -498,14 +529,10 @@ impl FrameData {
 
             if let (Some(begin), Some(end)) = (span.begin_timestamp_index, span.end_timestamp_index)
             {
-                let begin = timestamps[begin as usize] as f64;
-                let end = timestamps[end as usize] as f64;
-                let value = (end - begin) * (timestamp_period_ns as f64) / 1e6;
-
                 diagnostics.push(RenderDiagnostic {
                     path: self.diagnostic_path(&span.path_range, "elapsed_gpu"),
                     suffix: "ms",
-                    value,
+                    value: elapsed_ms(begin, end),
                 });
             }
 
@@ -2,12 +2,26 @@
 //!
 //! For more info, see [`RenderDiagnosticsPlugin`].
 
+mod batching_2d;
+mod gpu_memory;
 pub(crate) mod internal;
 
+pub use batching_2d::{
+    BatchBreakCause, Batching2dDiagnosticsPlugin, Batching2dReport, Batching2dStats,
+    Batching2dTracker,
+};
+pub(crate) use gpu_memory::{allocated_on_thread, GpuAllocation, GpuMemoryCounters};
+pub use gpu_memory::{
+    GpuMemoryAssetType, GpuMemoryBudget, GpuMemoryDiagnosticsPlugin, GpuMemoryReport,
+    GpuMemoryTracker,
+};
+
 use alloc::{borrow::Cow, sync::Arc};
 use core::marker::PhantomData;
 
 use bevy_app::{App, Plugin, PreUpdate};
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::system::{Local, Res};
 
 use crate::RenderApp;
 
@@ -40,17 +54,44 @@ use super::{RenderDevice, RenderQueue};
 ///     time_span.end(render_context.command_encoder());
 ///     ```
 ///
+/// The plugin also records the standard diagnostics [`GPU_FRAME_TIME`](Self::GPU_FRAME_TIME),
+/// the time the GPU took to execute all the commands of a frame, and
+/// [`ADAPTER_MEMORY_ALLOCATED`](Self::ADAPTER_MEMORY_ALLOCATED) and
+/// [`ADAPTER_MEMORY_RESERVED`](Self::ADAPTER_MEMORY_RESERVED), the GPU memory used by the backend.
+///
 /// # Supported platforms
 /// Timestamp queries and pipeline statistics are currently supported only on Vulkan and DX12.
 /// On other platforms (Metal, WebGPU, WebGL2) only CPU time will be recorded.
+///
+/// The adapter memory is currently only reported by DX12.
 #[derive(Default)]
 pub struct RenderDiagnosticsPlugin;
 
+impl RenderDiagnosticsPlugin {
+    /// The time the GPU took to execute the commands submitted for a frame, in milliseconds.
+    pub const GPU_FRAME_TIME: DiagnosticPath = DiagnosticPath::const_new("render/gpu_frame_time");
+    /// The GPU memory allocated by the backend for buffers and textures, in MiB.
+    pub const ADAPTER_MEMORY_ALLOCATED: DiagnosticPath =
+        DiagnosticPath::const_new("render/adapter_memory/allocated");
+    /// The GPU memory reserved by the backend, including the unused parts of its memory blocks,
+    /// in MiB.
+    pub const ADAPTER_MEMORY_RESERVED: DiagnosticPath =
+        DiagnosticPath::const_new("render/adapter_memory/reserved");
+}
+
 impl Plugin for RenderDiagnosticsPlugin {
     fn build(&self, app: &mut App) {
         let render_diagnostics_mutex = RenderDiagnosticsMutex::default();
         app.insert_resource(render_diagnostics_mutex.clone())
-            .add_systems(PreUpdate, sync_diagnostics);
+            .register_diagnostic(Diagnostic::new(Self::GPU_FRAME_TIME).with_suffix("ms"))
+            .register_diagnostic(
+                Diagnostic::new(Self::ADAPTER_MEMORY_ALLOCATED).with_suffix(" MiB"),
+            )
+            .register_diagnostic(Diagnostic::new(Self::ADAPTER_MEMORY_RESERVED).with_suffix(" MiB"))
+            .add_systems(
+                PreUpdate,
+                (sync_diagnostics, update_adapter_memory_diagnostics),
+            );
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.insert_resource(render_diagnostics_mutex);
@@ -68,6 +109,30 @@ impl Plugin for RenderDiagnosticsPlugin {
     }
 }
 
+/// Records the GPU memory used by the backend, if it reports it.
+fn update_adapter_memory_diagnostics(
+    render_device: Option<Res<RenderDevice>>,
+    mut diagnostics: Diagnostics,
+    mut unsupported: Local<bool>,
+) {
+    const MIB: f64 = 1024.0 * 1024.0;
+
+    let Some(render_device) = render_device.filter(|_| !*unsupported) else {
+        return;
+    };
+    let Some(report) = render_device.wgpu_device().generate_allocator_report() else {
+        *unsupported = true;
+        return;
+    };
+
+    diagnostics.add_measurement(&RenderDiagnosticsPlugin::ADAPTER_MEMORY_ALLOCATED, || {
+        report.total_allocated_bytes as f64 / MIB
+    });
+    diagnostics.add_measurement(&RenderDiagnosticsPlugin::ADAPTER_MEMORY_RESERVED, || {
+        report.total_reserved_bytes as f64 / MIB
+    });
+}
+
 /// Allows recording diagnostic spans.
 pub trait RecordDiagnostics: Send + Sync {
     /// Begin a time span, which will record elapsed CPU and GPU time.
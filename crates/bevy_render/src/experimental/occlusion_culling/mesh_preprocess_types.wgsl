@@ -21,6 +21,10 @@ struct MeshInput {
     material_and_lightmap_bind_group_slot: u32,
     pad_a: u32,
     pad_b: u32,
+    // The per-instance material overrides, copied as is to the `Mesh`.
+    base_color_override: vec4<f32>,
+    emissive_override: vec4<f32>,
+    custom_material_data: array<vec4<f32>, 2>,
 }
 
 // The `wgpu` indirect parameters structure. This is a union of two structures.
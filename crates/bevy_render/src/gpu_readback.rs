@@ -354,6 +354,26 @@ pub(crate) const fn get_aligned_size(extent: Extent3d, pixel_size: u32) -> u32 {
     extent.height * align_byte_size(extent.width * pixel_size) * extent.depth_or_array_layers
 }
 
+/// Removes the padding added to each row of an image of `extent` read back from a buffer laid out
+/// with [`layout_data`].
+pub(crate) fn remove_row_padding(data: &mut Vec<u8>, extent: Extent3d, pixel_size: usize) {
+    let initial_row_bytes = extent.width as usize * pixel_size;
+    let buffered_row_bytes = align_byte_size(extent.width * pixel_size as u32) as usize;
+    if initial_row_bytes == buffered_row_bytes {
+        return;
+    }
+
+    let rows = extent.height as usize * extent.depth_or_array_layers as usize;
+    for row in 1..rows {
+        let take_offset = row * buffered_row_bytes;
+        data.copy_within(
+            take_offset..take_offset + initial_row_bytes,
+            row * initial_row_bytes,
+        );
+    }
+    data.truncate(initial_row_bytes * rows);
+}
+
 /// Get a [`ImageDataLayout`] aligned such that the image can be copied into a buffer.
 pub(crate) fn layout_data(extent: Extent3d, format: TextureFormat) -> ImageDataLayout {
     ImageDataLayout {
@@ -41,6 +41,7 @@ pub mod render_phase;
 pub mod render_resource;
 pub mod renderer;
 pub mod settings;
+pub mod spatial_index;
 pub mod storage;
 pub mod sync_component;
 pub mod sync_world;
@@ -90,7 +91,7 @@ use crate::{
     mesh::{MeshPlugin, MorphPlugin, RenderMesh},
     render_asset::prepare_assets,
     render_resource::{PipelineCache, Shader, ShaderLoader},
-    renderer::{render_system, RenderInstance, WgpuWrapper},
+    renderer::{render_system, AvailableRenderAdapters, RenderInstance, WgpuWrapper},
     settings::RenderCreation,
     storage::StoragePlugin,
     view::{ViewPlugin, WindowRenderPlugin},
@@ -100,7 +101,7 @@ use bevy_app::{App, AppLabel, Plugin, SubApp};
 use bevy_asset::{load_internal_asset, weak_handle, AssetApp, AssetServer, Handle};
 use bevy_ecs::{prelude::*, schedule::ScheduleLabel};
 use core::ops::{Deref, DerefMut};
-use std::sync::Mutex;
+use std::{path::PathBuf, sync::Mutex};
 use tracing::debug;
 
 /// Contains the default Bevy rendering backend based on wgpu.
@@ -123,6 +124,11 @@ pub struct RenderPlugin {
     /// This is a debugging feature that may reduce performance. It primarily
     /// exists for the `occlusion_culling` example.
     pub allow_copies_from_indirect_parameters: bool,
+    /// If set, the driver's cache of compiled pipelines is saved in this directory and loaded on the
+    /// next run, which can make startup faster on some drivers.
+    ///
+    /// See [`PipelineCache::load_driver_cache`].
+    pub pipeline_cache_directory: Option<PathBuf>,
 }
 
 /// The systems sets of the default [`App`] rendering schedule.
@@ -409,24 +415,47 @@ impl Plugin for RenderPlugin {
             let RenderResources(device, queue, adapter_info, render_adapter, instance) =
                 future_render_resources.0.lock().unwrap().take().unwrap();
 
+            #[cfg(not(target_arch = "wasm32"))]
+            let adapters = instance
+                .enumerate_adapters(wgpu::Backends::all())
+                .iter()
+                .map(wgpu::Adapter::get_info)
+                .collect();
+            #[cfg(target_arch = "wasm32")]
+            let adapters = vec![wgpu::AdapterInfo::clone(&adapter_info)];
+            let available_adapters = AvailableRenderAdapters::new(adapters, &adapter_info);
+            for adapter in available_adapters.iter() {
+                debug!(
+                    "Available wgpu adapter: {} ({:?}, {:?})",
+                    adapter.name, adapter.device_type, adapter.backend
+                );
+            }
+
             app.insert_resource(device.clone())
                 .insert_resource(queue.clone())
                 .insert_resource(adapter_info.clone())
-                .insert_resource(render_adapter.clone());
+                .insert_resource(render_adapter.clone())
+                .insert_resource(available_adapters.clone());
+
+            let mut pipeline_cache = PipelineCache::new(
+                device.clone(),
+                render_adapter.clone(),
+                self.synchronous_pipeline_compilation,
+            );
+            if let Some(directory) = &self.pipeline_cache_directory {
+                pipeline_cache.load_driver_cache(directory, &adapter_info);
+            }
 
             let render_app = app.sub_app_mut(RenderApp);
 
             render_app
                 .insert_resource(instance)
-                .insert_resource(PipelineCache::new(
-                    device.clone(),
-                    render_adapter.clone(),
-                    self.synchronous_pipeline_compilation,
-                ))
+                .insert_resource(pipeline_cache)
                 .insert_resource(device)
                 .insert_resource(queue)
                 .insert_resource(render_adapter)
                 .insert_resource(adapter_info)
+                .insert_resource(available_adapters)
                 .add_systems(
                     Render,
                     (|mut bpf: ResMut<RenderAssetBytesPerFrame>| {
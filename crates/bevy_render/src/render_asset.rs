@@ -1,11 +1,13 @@
 use crate::{
-    render_resource::AsBindGroupError, ExtractSchedule, MainWorld, Render, RenderApp, RenderSet,
+    diagnostic::{allocated_on_thread, GpuMemoryTracker},
+    render_resource::AsBindGroupError,
+    ExtractSchedule, MainWorld, Render, RenderApp, RenderSet,
 };
 use bevy_app::{App, Plugin, SubApp};
 pub use bevy_asset::RenderAssetUsages;
 use bevy_asset::{Asset, AssetEvent, AssetId, Assets};
 use bevy_ecs::{
-    prelude::{Commands, EventReader, IntoSystemConfigs, ResMut, Resource},
+    prelude::{Commands, EventReader, IntoSystemConfigs, Res, ResMut, Resource},
     schedule::{SystemConfigs, SystemSet},
     system::{StaticSystemParam, SystemParam, SystemParamItem, SystemState},
     world::{FromWorld, Mut},
@@ -309,6 +311,7 @@ pub fn prepare_assets<A: RenderAsset>(
     mut prepare_next_frame: ResMut<PrepareNextFrameAssets<A>>,
     param: StaticSystemParam<<A as RenderAsset>::Param>,
     mut bpf: ResMut<RenderAssetBytesPerFrame>,
+    gpu_memory: Option<Res<GpuMemoryTracker>>,
 ) {
     let mut wrote_asset_count = 0;
 
@@ -334,8 +337,12 @@ pub fn prepare_assets<A: RenderAsset>(
             0
         };
 
+        let allocated = allocated_on_thread();
         match A::prepare_asset(extracted_asset, id, &mut param) {
             Ok(prepared_asset) => {
+                if let Some(gpu_memory) = &gpu_memory {
+                    gpu_memory.record::<A>(id, allocated_on_thread() - allocated);
+                }
                 render_assets.insert(id, prepared_asset);
                 bpf.write_bytes(write_bytes);
                 wrote_asset_count += 1;
@@ -355,6 +362,9 @@ pub fn prepare_assets<A: RenderAsset>(
     for removed in extracted_assets.removed.drain() {
         render_assets.remove(removed);
         A::unload_asset(removed, &mut param);
+        if let Some(gpu_memory) = &gpu_memory {
+            gpu_memory.remove::<A>(removed);
+        }
     }
 
     for (id, extracted_asset) in extracted_assets.extracted.drain(..) {
@@ -373,8 +383,12 @@ pub fn prepare_assets<A: RenderAsset>(
             0
         };
 
+        let allocated = allocated_on_thread();
         match A::prepare_asset(extracted_asset, id, &mut param) {
             Ok(prepared_asset) => {
+                if let Some(gpu_memory) = &gpu_memory {
+                    gpu_memory.record::<A>(id, allocated_on_thread() - allocated);
+                }
                 render_assets.insert(id, prepared_asset);
                 bpf.write_bytes(write_bytes);
                 wrote_asset_count += 1;
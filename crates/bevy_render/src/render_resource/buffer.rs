@@ -1,4 +1,5 @@
 use crate::define_atomic_id;
+use crate::diagnostic::GpuAllocation;
 use crate::renderer::WgpuWrapper;
 use alloc::sync::Arc;
 use core::ops::{Bound, Deref, RangeBounds};
@@ -10,6 +11,7 @@ pub struct Buffer {
     id: BufferId,
     value: Arc<WgpuWrapper<wgpu::Buffer>>,
     size: wgpu::BufferAddress,
+    allocation: Option<Arc<GpuAllocation>>,
 }
 
 impl Buffer {
@@ -42,6 +44,12 @@ impl Buffer {
     pub fn unmap(&self) {
         self.value.unmap();
     }
+
+    /// Counts the memory of this buffer as allocated until it is dropped.
+    pub(crate) fn with_allocation(mut self, allocation: Arc<GpuAllocation>) -> Self {
+        self.allocation = Some(allocation);
+        self
+    }
 }
 
 impl From<wgpu::Buffer> for Buffer {
@@ -50,6 +58,7 @@ impl From<wgpu::Buffer> for Buffer {
             id: BufferId::new(),
             size: value.size(),
             value: Arc::new(WgpuWrapper::new(value)),
+            allocation: None,
         }
     }
 }
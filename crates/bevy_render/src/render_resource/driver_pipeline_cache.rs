@@ -0,0 +1,164 @@
+use crate::renderer::{RenderDevice, WgpuWrapper};
+use alloc::{sync::Arc, vec::Vec};
+use bevy_tasks::{futures::check_ready, IoTaskPool, Task};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, warn};
+use wgpu::{AdapterInfo, Features, PipelineCacheDescriptor};
+
+/// The driver's cache of compiled pipelines, persisted to disk between runs.
+///
+/// Compiling shaders into pipelines is handled by the driver, and can make the first frames of an app
+/// slow on some platforms. When the backend supports it, the compiled pipelines are kept in a cache whose
+/// data is saved to disk by the [`IoTaskPool`], so that the next run can skip most of the compilation.
+pub(crate) struct DriverPipelineCache {
+    pub(crate) cache: Arc<WgpuWrapper<wgpu::PipelineCache>>,
+    path: PathBuf,
+    /// Whether pipelines were created since the cache was last saved.
+    dirty: bool,
+    /// The task writing the cache to disk, which returns the number of bytes written.
+    save_task: Option<Task<io::Result<usize>>>,
+    pub(crate) stats: DriverPipelineCacheStats,
+}
+
+impl DriverPipelineCache {
+    /// Loads the cache of the adapter described by `adapter_info` from `directory`.
+    ///
+    /// Returns `None` if the device or backend doesn't support pipeline caching.
+    pub(crate) fn load(
+        device: &RenderDevice,
+        adapter_info: &AdapterInfo,
+        directory: &Path,
+    ) -> Option<Self> {
+        if !device.features().contains(Features::PIPELINE_CACHE) {
+            debug!("Pipeline caching is not supported by the render device");
+            return None;
+        }
+        // The key identifies the adapter. The driver version is checked when the data is loaded.
+        let Some(key) = wgpu::util::pipeline_cache_key(adapter_info) else {
+            debug!(
+                "Pipeline caching is not supported by the {:?} backend",
+                adapter_info.backend
+            );
+            return None;
+        };
+        let path = directory.join(key);
+
+        let data = match fs::read(&path) {
+            Ok(data) => Some(data),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => {
+                warn!(
+                    "Failed to read the pipeline cache at {}: {err}",
+                    path.display()
+                );
+                None
+            }
+        };
+
+        // SAFETY: The data was produced by `PipelineCache::get_data` for an adapter with the same key.
+        // With `fallback` set, data from another driver version or that is corrupted is discarded.
+        let cache = unsafe {
+            device
+                .wgpu_device()
+                .create_pipeline_cache(&PipelineCacheDescriptor {
+                    label: Some("bevy_pipeline_cache"),
+                    data: data.as_deref(),
+                    fallback: true,
+                })
+        };
+
+        let loaded_bytes = data.map_or(0, |data| data.len());
+        debug!(
+            "Loaded {loaded_bytes} bytes of pipeline cache from {}",
+            path.display()
+        );
+        Some(Self {
+            cache: Arc::new(WgpuWrapper::new(cache)),
+            path,
+            dirty: false,
+            save_task: None,
+            stats: DriverPipelineCacheStats {
+                loaded_bytes,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Records that a pipeline was created using the cache.
+    pub(crate) fn pipeline_created(&mut self) {
+        self.stats.pipelines_created += 1;
+        self.dirty = true;
+    }
+
+    /// Starts writing the cache to disk if pipelines were created since it was last saved.
+    ///
+    /// The cache is written by the [`IoTaskPool`], one save at a time. If a save is still running,
+    /// the next one starts on a later call.
+    pub(crate) fn save_if_dirty(&mut self) {
+        if let Some(task) = &mut self.save_task {
+            let Some(result) = check_ready(task) else {
+                return;
+            };
+            self.save_task = None;
+            match result {
+                Ok(saved_bytes) => {
+                    self.stats.saved_bytes = saved_bytes;
+                    debug!(
+                        "Saved {saved_bytes} bytes of pipeline cache to {}",
+                        self.path.display()
+                    );
+                }
+                Err(err) => warn!(
+                    "Failed to save the pipeline cache to {}: {err}",
+                    self.path.display()
+                ),
+            }
+        }
+
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+
+        let Some(data) = self.cache.get_data() else {
+            return;
+        };
+        let path = self.path.clone();
+        self.save_task =
+            Some(IoTaskPool::get().spawn(async move { write_atomically(&path, data) }));
+    }
+}
+
+/// Writes `data` to a temporary file before moving it to `path`, so that an interrupted write
+/// doesn't leave a truncated cache behind. Returns the number of bytes written.
+fn write_atomically(path: &Path, data: Vec<u8>) -> io::Result<usize> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, &data)?;
+    fs::rename(&temp_path, path)?;
+    Ok(data.len())
+}
+
+/// Statistics about the persistent pipeline cache of the driver.
+///
+/// Drivers don't report whether an individual pipeline was found in the cache. Pipelines created
+/// after a non-empty cache was loaded will usually skip compilation, unless their shaders or the
+/// driver changed since the cache was saved.
+///
+/// See [`PipelineCache::driver_cache_stats`](super::PipelineCache::driver_cache_stats).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DriverPipelineCacheStats {
+    /// The size of the cache data loaded from disk at startup, or `0` if there was none.
+    pub loaded_bytes: usize,
+    /// The number of pipelines created with the cache.
+    pub pipelines_created: u32,
+    /// The size of the cache data last saved to disk, or `0` if it wasn't saved yet.
+    ///
+    /// Saving happens in the background, so this is only updated once the write completed.
+    pub saved_bytes: usize,
+}
@@ -5,6 +5,7 @@ mod bind_group_layout;
 mod bind_group_layout_entries;
 mod buffer;
 mod buffer_vec;
+mod driver_pipeline_cache;
 mod gpu_array_buffer;
 mod pipeline;
 mod pipeline_cache;
@@ -21,6 +22,7 @@ pub use bind_group_layout::*;
 pub use bind_group_layout_entries::*;
 pub use buffer::*;
 pub use buffer_vec::*;
+pub use driver_pipeline_cache::*;
 pub use gpu_array_buffer::*;
 pub use pipeline::*;
 pub use pipeline_cache::*;
@@ -16,7 +16,10 @@ use bevy_tasks::Task;
 use bevy_utils::default;
 use core::{future::Future, hash::Hash, mem, ops::Deref};
 use naga::valid::Capabilities;
-use std::sync::{Mutex, PoisonError};
+use std::{
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
 use thiserror::Error;
 use tracing::{debug, error};
 #[cfg(feature = "shader_format_spirv")]
@@ -456,6 +459,7 @@ pub struct PipelineCache {
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on macOS, wasm, or without the `multi_threaded` feature.
     synchronous_pipeline_compilation: bool,
+    driver_cache: Option<DriverPipelineCache>,
 }
 
 impl PipelineCache {
@@ -483,9 +487,29 @@ impl PipelineCache {
             new_pipelines: default(),
             pipelines: default(),
             synchronous_pipeline_compilation,
+            driver_cache: None,
         }
     }
 
+    /// Loads the driver's cache of compiled pipelines from `directory`, and saves it back whenever
+    /// new pipelines have been created.
+    ///
+    /// This can make the startup of later runs faster. It requires the [`Features::PIPELINE_CACHE`]
+    /// device feature, and does nothing on backends that don't support pipeline caching.
+    ///
+    /// This is called by the [`RenderPlugin`](crate::RenderPlugin) if its `pipeline_cache_directory` is set,
+    /// and must be called before any pipeline is created for them to be cached.
+    pub fn load_driver_cache(&mut self, directory: &Path, adapter_info: &WgpuAdapterInfo) {
+        self.driver_cache = DriverPipelineCache::load(&self.device, adapter_info, directory);
+    }
+
+    /// Returns statistics about the driver's persistent pipeline cache, or `None` if it isn't in use.
+    ///
+    /// See [`PipelineCache::load_driver_cache`].
+    pub fn driver_cache_stats(&self) -> Option<DriverPipelineCacheStats> {
+        self.driver_cache.as_ref().map(|cache| cache.stats)
+    }
+
     /// Get the state of a cached render pipeline.
     ///
     /// See [`PipelineCache::queue_render_pipeline()`].
@@ -558,7 +582,12 @@ impl PipelineCache {
         let state = &mut self.pipelines[id.0].state;
         if let CachedPipelineState::Creating(task) = state {
             *state = match bevy_tasks::block_on(task) {
-                Ok(p) => CachedPipelineState::Ok(p),
+                Ok(p) => {
+                    if let Some(driver_cache) = &mut self.driver_cache {
+                        driver_cache.pipeline_created();
+                    }
+                    CachedPipelineState::Ok(p)
+                }
                 Err(e) => CachedPipelineState::Err(e),
             };
         }
@@ -666,6 +695,7 @@ impl PipelineCache {
         let device = self.device.clone();
         let shader_cache = self.shader_cache.clone();
         let layout_cache = self.layout_cache.clone();
+        let driver_cache = self.driver_cache.as_ref().map(|cache| cache.cache.clone());
 
         create_pipeline_task(
             async move {
@@ -758,7 +788,9 @@ impl PipelineCache {
                             // TODO: Should this be the same as the vertex compilation options?
                             compilation_options,
                         }),
-                    cache: None,
+                    cache: driver_cache
+                        .as_ref()
+                        .map(|cache| -> &wgpu::PipelineCache { cache }),
                 };
 
                 Ok(Pipeline::RenderPipeline(
@@ -777,6 +809,7 @@ impl PipelineCache {
         let device = self.device.clone();
         let shader_cache = self.shader_cache.clone();
         let layout_cache = self.layout_cache.clone();
+        let driver_cache = self.driver_cache.as_ref().map(|cache| cache.cache.clone());
 
         create_pipeline_task(
             async move {
@@ -817,7 +850,9 @@ impl PipelineCache {
                         zero_initialize_workgroup_memory: descriptor
                             .zero_initialize_workgroup_memory,
                     },
-                    cache: None,
+                    cache: driver_cache
+                        .as_ref()
+                        .map(|cache| -> &wgpu::PipelineCache { cache }),
                 };
 
                 Ok(Pipeline::ComputePipeline(
@@ -855,6 +890,13 @@ impl PipelineCache {
         }
 
         self.pipelines = pipelines;
+
+        // Save the driver cache once all pending pipelines are compiled, rather than after each one.
+        if self.waiting_pipelines.is_empty() {
+            if let Some(driver_cache) = &mut self.driver_cache {
+                driver_cache.save_if_dirty();
+            }
+        }
     }
 
     fn process_pipeline(&mut self, cached_pipeline: &mut CachedPipeline, id: usize) {
@@ -868,11 +910,20 @@ impl PipelineCache {
                         self.start_create_compute_pipeline(id, *descriptor.clone())
                     }
                 };
+                // Pipelines compiled synchronously are ready right away.
+                if let (CachedPipelineState::Ok(_), Some(driver_cache)) =
+                    (&cached_pipeline.state, &mut self.driver_cache)
+                {
+                    driver_cache.pipeline_created();
+                }
             }
 
             CachedPipelineState::Creating(ref mut task) => {
                 match bevy_tasks::futures::check_ready(task) {
                     Some(Ok(pipeline)) => {
+                        if let Some(driver_cache) = &mut self.driver_cache {
+                            driver_cache.pipeline_created();
+                        }
                         cached_pipeline.state = CachedPipelineState::Ok(pipeline);
                         return;
                     }
@@ -1,4 +1,5 @@
 use crate::define_atomic_id;
+use crate::diagnostic::GpuAllocation;
 use crate::renderer::WgpuWrapper;
 use alloc::sync::Arc;
 use bevy_derive::{Deref, DerefMut};
@@ -24,6 +25,7 @@ define_atomic_id!(TextureId);
 pub struct Texture {
     id: TextureId,
     value: Arc<WgpuWrapper<wgpu::Texture>>,
+    allocation: Option<Arc<GpuAllocation>>,
 }
 
 impl Texture {
@@ -37,6 +39,12 @@ impl Texture {
     pub fn create_view(&self, desc: &wgpu::TextureViewDescriptor) -> TextureView {
         TextureView::from(self.value.create_view(desc))
     }
+
+    /// Counts the memory of this texture as allocated until it is dropped.
+    pub(crate) fn with_allocation(mut self, allocation: Arc<GpuAllocation>) -> Self {
+        self.allocation = Some(allocation);
+        self
+    }
 }
 
 impl From<wgpu::Texture> for Texture {
@@ -44,6 +52,7 @@ impl From<wgpu::Texture> for Texture {
         Texture {
             id: TextureId::new(),
             value: Arc::new(WgpuWrapper::new(value)),
+            allocation: None,
         }
     }
 }
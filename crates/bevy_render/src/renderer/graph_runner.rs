@@ -73,9 +73,13 @@ impl RenderGraphRunner {
         world: &World,
         finalizer: impl FnOnce(&mut wgpu::CommandEncoder),
     ) -> Result<Option<DiagnosticsRecorder>, RenderGraphRunnerError> {
-        if let Some(recorder) = &mut diagnostics_recorder {
-            recorder.begin_frame();
-        }
+        // The diagnostics begin in their own command buffer, submitted before the others.
+        let begin_frame_commands = diagnostics_recorder.as_mut().map(|recorder| {
+            let mut command_encoder =
+                render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            recorder.begin_frame(&mut command_encoder);
+            command_encoder.finish()
+        });
 
         let mut render_context = RenderContext::new(
             render_device,
@@ -83,6 +87,9 @@ impl RenderGraphRunner {
             adapter.get_info(),
             diagnostics_recorder,
         );
+        if let Some(commands) = begin_frame_commands {
+            render_context.add_command_buffer(commands);
+        }
         Self::run_graph(graph, None, &mut render_context, world, &[], None)?;
         finalizer(render_context.command_encoder());
 
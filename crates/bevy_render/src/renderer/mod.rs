@@ -13,7 +13,7 @@ use crate::{
     render_graph::RenderGraph,
     render_phase::TrackedRenderPass,
     render_resource::RenderPassDescriptor,
-    settings::{WgpuSettings, WgpuSettingsPriority},
+    settings::{AdapterSelection, WgpuSettings, WgpuSettingsPriority},
     view::{ExtractedWindows, ViewTarget},
 };
 use alloc::sync::Arc;
@@ -49,6 +49,7 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
         world,
         |encoder| {
             crate::view::screenshot::submit_screenshot_commands(world, encoder);
+            crate::view::frame_capture::submit_frame_capture_commands(world, encoder);
             crate::gpu_readback::submit_readback_commands(world, encoder);
         },
     );
@@ -107,6 +108,7 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
     }
 
     crate::view::screenshot::collect_screenshots(world);
+    crate::view::frame_capture::collect_frame_captures(world);
 
     // update the time and send it to the app world
     let time_sender = world.resource::<TimeSender>();
@@ -180,6 +182,107 @@ pub struct RenderInstance(pub Arc<WgpuWrapper<Instance>>);
 #[derive(Resource, Clone, Deref, DerefMut)]
 pub struct RenderAdapterInfo(pub WgpuWrapper<AdapterInfo>);
 
+/// The adapters available to the renderer, for example to let users pick their GPU in a settings
+/// menu through [`WgpuSettings::adapter_selection`].
+///
+/// On the web, where adapters can't be enumerated, this only contains the adapter in use.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AvailableRenderAdapters {
+    adapters: Vec<AdapterInfo>,
+    selected: Option<usize>,
+}
+
+impl AvailableRenderAdapters {
+    /// Creates the list of `adapters`, where `selected` is the adapter in use by the renderer.
+    pub fn new(adapters: Vec<AdapterInfo>, selected: &AdapterInfo) -> Self {
+        let selected = adapters.iter().position(|adapter| adapter == selected);
+        Self { adapters, selected }
+    }
+
+    /// Iterates over the available adapters.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &AdapterInfo> {
+        self.adapters.iter()
+    }
+
+    /// Returns the index of the adapter in use by the renderer, if it's in the list.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Returns the adapter in use by the renderer, if it's in the list.
+    pub fn selected(&self) -> Option<&AdapterInfo> {
+        self.selected.map(|index| &self.adapters[index])
+    }
+}
+
+/// Lists the adapters which can be used with `settings`, without initializing the renderer.
+///
+/// This can be used to offer a choice of [`AdapterSelection`] before starting the app. Once the
+/// renderer is initialized, prefer the [`AvailableRenderAdapters`] resource.
+///
+/// Returns an empty list on the web, where adapters can't be enumerated.
+pub fn enumerate_adapters(settings: &WgpuSettings) -> Vec<AdapterInfo> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let Some(backends) = settings.backends else {
+            return Vec::new();
+        };
+        let instance = Instance::new(wgpu::InstanceDescriptor {
+            backends,
+            dx12_shader_compiler: settings.dx12_shader_compiler.clone(),
+            flags: settings.instance_flags,
+            gles_minor_version: settings.gles3_minor_version,
+        });
+        instance
+            .enumerate_adapters(backends)
+            .iter()
+            .map(Adapter::get_info)
+            .collect()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = settings;
+        Vec::new()
+    }
+}
+
+/// Selects the adapter matching [`WgpuSettings::adapter_selection`], falling back to the one
+/// requested with `request_adapter_options`.
+async fn select_adapter(
+    instance: &Instance,
+    options: &WgpuSettings,
+    request_adapter_options: &RequestAdapterOptions<'_, '_>,
+) -> Option<Adapter> {
+    if options.adapter_selection != AdapterSelection::Automatic {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let adapter = instance
+                .enumerate_adapters(wgpu::Backends::all())
+                .into_iter()
+                .filter(|adapter| {
+                    request_adapter_options
+                        .compatible_surface
+                        .is_none_or(|surface| adapter.is_surface_supported(surface))
+                })
+                .find(|adapter| options.adapter_selection.matches(&adapter.get_info()));
+            if adapter.is_some() {
+                return adapter;
+            }
+            warn!(
+                "No compatible adapter matches {:?}, falling back to automatic adapter selection",
+                options.adapter_selection
+            );
+        }
+        #[cfg(target_arch = "wasm32")]
+        warn!(
+            "Adapter selection policies are not supported on the web, \
+             falling back to automatic adapter selection"
+        );
+    }
+
+    instance.request_adapter(request_adapter_options).await
+}
+
 const GPU_NOT_FOUND_ERROR_MESSAGE: &str = if cfg!(target_os = "linux") {
     "Unable to find a GPU! Make sure you have installed required drivers! For extra information, see: https://github.com/bevyengine/bevy/blob/latest/docs/linux_dependencies.md"
 } else {
@@ -193,8 +296,7 @@ pub async fn initialize_renderer(
     options: &WgpuSettings,
     request_adapter_options: &RequestAdapterOptions<'_, '_>,
 ) -> (RenderDevice, RenderQueue, RenderAdapterInfo, RenderAdapter) {
-    let adapter = instance
-        .request_adapter(request_adapter_options)
+    let adapter = select_adapter(instance, options, request_adapter_options)
         .await
         .expect(GPU_NOT_FOUND_ERROR_MESSAGE);
 
@@ -1,4 +1,5 @@
 use super::RenderQueue;
+use crate::diagnostic::GpuMemoryCounters;
 use crate::render_resource::{
     BindGroup, BindGroupLayout, Buffer, ComputePipeline, RawRenderPipelineDescriptor,
     RenderPipeline, Sampler, Texture,
@@ -15,6 +16,7 @@ use wgpu::{
 #[derive(Resource, Clone)]
 pub struct RenderDevice {
     device: Arc<WgpuWrapper<wgpu::Device>>,
+    memory: Arc<GpuMemoryCounters>,
 }
 
 impl From<wgpu::Device> for RenderDevice {
@@ -25,7 +27,24 @@ impl From<wgpu::Device> for RenderDevice {
 
 impl RenderDevice {
     pub fn new(device: Arc<WgpuWrapper<wgpu::Device>>) -> Self {
-        Self { device }
+        Self {
+            device,
+            memory: Default::default(),
+        }
+    }
+
+    /// The number of bytes of the buffers created through this device that are still alive.
+    ///
+    /// See [`GpuMemoryDiagnosticsPlugin`](crate::diagnostic::GpuMemoryDiagnosticsPlugin).
+    pub fn allocated_buffer_bytes(&self) -> u64 {
+        self.memory.buffer_bytes()
+    }
+
+    /// An estimate of the number of bytes of the textures created through this device that are still alive.
+    ///
+    /// See [`GpuMemoryDiagnosticsPlugin`](crate::diagnostic::GpuMemoryDiagnosticsPlugin).
+    pub fn allocated_texture_bytes(&self) -> u64 {
+        self.memory.texture_bytes()
     }
 
     /// List all [`Features`](wgpu::Features) that may be used with this device.
@@ -165,13 +184,15 @@ impl RenderDevice {
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &wgpu::BufferDescriptor) -> Buffer {
         let wgpu_buffer = self.device.create_buffer(desc);
-        Buffer::from(wgpu_buffer)
+        let allocation = self.memory.allocate_buffer(wgpu_buffer.size());
+        Buffer::from(wgpu_buffer).with_allocation(allocation)
     }
 
     /// Creates a [`Buffer`] and initializes it with the specified data.
     pub fn create_buffer_with_data(&self, desc: &wgpu::util::BufferInitDescriptor) -> Buffer {
         let wgpu_buffer = self.device.create_buffer_init(desc);
-        Buffer::from(wgpu_buffer)
+        let allocation = self.memory.allocate_buffer(wgpu_buffer.size());
+        Buffer::from(wgpu_buffer).with_allocation(allocation)
     }
 
     /// Creates a new [`Texture`] and initializes it with the specified data.
@@ -188,7 +209,8 @@ impl RenderDevice {
         let wgpu_texture =
             self.device
                 .create_texture_with_data(render_queue.as_ref(), desc, order, data);
-        Texture::from(wgpu_texture)
+        let allocation = self.memory.allocate_texture(desc);
+        Texture::from(wgpu_texture).with_allocation(allocation)
     }
 
     /// Creates a new [`Texture`].
@@ -196,7 +218,8 @@ impl RenderDevice {
     /// `desc` specifies the general format of the texture.
     pub fn create_texture(&self, desc: &wgpu::TextureDescriptor) -> Texture {
         let wgpu_texture = self.device.create_texture(desc);
-        Texture::from(wgpu_texture)
+        let allocation = self.memory.allocate_texture(desc);
+        Texture::from(wgpu_texture).with_allocation(allocation)
     }
 
     /// Creates a new [`Sampler`].
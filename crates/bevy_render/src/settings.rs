@@ -5,8 +5,8 @@ use alloc::borrow::Cow;
 use std::path::PathBuf;
 
 pub use wgpu::{
-    Backends, Dx12Compiler, Features as WgpuFeatures, Gles3MinorVersion, InstanceFlags,
-    Limits as WgpuLimits, MemoryHints, PowerPreference,
+    AdapterInfo, Backends, DeviceType, Dx12Compiler, Features as WgpuFeatures, Gles3MinorVersion,
+    InstanceFlags, Limits as WgpuLimits, MemoryHints, PowerPreference,
 };
 
 /// Configures the priority used when automatically configuring the features/limits of `wgpu`.
@@ -20,6 +20,45 @@ pub enum WgpuSettingsPriority {
     WebGL2,
 }
 
+/// Configures which adapter (usually a GPU) is used for rendering.
+///
+/// This is mostly useful on laptops with both an integrated and a discrete GPU, where the
+/// adapter picked from [`WgpuSettings::power_preference`] may not be the expected one.
+/// Use [`AvailableRenderAdapters`](crate::renderer::AvailableRenderAdapters) or
+/// [`enumerate_adapters`](crate::renderer::enumerate_adapters) to list the adapters to choose from.
+///
+/// If no compatible adapter matches the policy, the adapter is selected as with
+/// [`AdapterSelection::Automatic`]. Policies other than [`AdapterSelection::Automatic`] are
+/// ignored on the web, where adapters can't be enumerated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum AdapterSelection {
+    /// Let `wgpu` select an adapter matching [`WgpuSettings::power_preference`].
+    #[default]
+    Automatic,
+    /// Prefer an adapter of this type, such as [`DeviceType::DiscreteGpu`] or
+    /// [`DeviceType::IntegratedGpu`].
+    DeviceType(DeviceType),
+    /// Prefer an adapter whose name contains this string, ignoring case.
+    Name(Cow<'static, str>),
+    /// Prefer an adapter with this PCI vendor ID, such as `0x10DE` for NVIDIA, `0x1002` for AMD
+    /// or `0x8086` for Intel.
+    Vendor(u32),
+}
+
+impl AdapterSelection {
+    /// Returns `true` if the adapter described by `info` matches this policy.
+    ///
+    /// Every adapter matches [`AdapterSelection::Automatic`].
+    pub fn matches(&self, info: &AdapterInfo) -> bool {
+        match self {
+            AdapterSelection::Automatic => true,
+            AdapterSelection::DeviceType(device_type) => info.device_type == *device_type,
+            AdapterSelection::Name(name) => info.name.to_lowercase().contains(&name.to_lowercase()),
+            AdapterSelection::Vendor(vendor) => info.vendor == *vendor,
+        }
+    }
+}
+
 /// Provides configuration for renderer initialization. Use [`RenderDevice::features`](RenderDevice::features),
 /// [`RenderDevice::limits`](RenderDevice::limits), and the [`RenderAdapterInfo`]
 /// resource to get runtime information about the actual adapter, backend, features, and limits.
@@ -34,6 +73,8 @@ pub struct WgpuSettings {
     pub device_label: Option<Cow<'static, str>>,
     pub backends: Option<Backends>,
     pub power_preference: PowerPreference,
+    /// The policy used to select the adapter, taking precedence over `power_preference`.
+    pub adapter_selection: AdapterSelection,
     pub priority: WgpuSettingsPriority,
     /// The features to ensure are enabled regardless of what the adapter/backend supports.
     /// Setting these explicitly may cause renderer initialization to fail.
@@ -76,6 +117,8 @@ impl Default for WgpuSettings {
         let power_preference =
             wgpu::util::power_preference_from_env().unwrap_or(PowerPreference::HighPerformance);
 
+        let adapter_selection = adapter_selection_from_env().unwrap_or_default();
+
         let priority = settings_priority_from_env().unwrap_or(WgpuSettingsPriority::Functionality);
 
         let limits = if cfg!(all(
@@ -114,6 +157,7 @@ impl Default for WgpuSettings {
             device_label: Default::default(),
             backends,
             power_preference,
+            adapter_selection,
             priority,
             features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
             disabled_features: None,
@@ -191,3 +235,36 @@ pub fn settings_priority_from_env() -> Option<WgpuSettingsPriority> {
         },
     )
 }
+
+/// Get an adapter selection policy from the environment variable `WGPU_ADAPTER_NAME`,
+/// selecting the adapter whose name contains its value.
+pub fn adapter_selection_from_env() -> Option<AdapterSelection> {
+    let name = std::env::var("WGPU_ADAPTER_NAME").ok()?;
+    Some(AdapterSelection::Name(name.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AdapterInfo, AdapterSelection, DeviceType};
+
+    #[test]
+    fn adapter_selection_matches() {
+        let info = AdapterInfo {
+            name: "NVIDIA GeForce RTX 3060 Laptop GPU".into(),
+            vendor: 0x10DE,
+            device: 0x2560,
+            device_type: DeviceType::DiscreteGpu,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend: wgpu::Backend::Vulkan,
+        };
+
+        assert!(AdapterSelection::Automatic.matches(&info));
+        assert!(AdapterSelection::DeviceType(DeviceType::DiscreteGpu).matches(&info));
+        assert!(!AdapterSelection::DeviceType(DeviceType::IntegratedGpu).matches(&info));
+        assert!(AdapterSelection::Name("geforce rtx".into()).matches(&info));
+        assert!(!AdapterSelection::Name("Intel".into()).matches(&info));
+        assert!(AdapterSelection::Vendor(0x10DE).matches(&info));
+        assert!(!AdapterSelection::Vendor(0x8086).matches(&info));
+    }
+}
@@ -0,0 +1,219 @@
+//! A broad-phase spatial index of the entities with an [`Aabb`], for gameplay queries.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{entity::hash_map::EntityHashMap, prelude::*};
+use bevy_math::{
+    bounding::{
+        Aabb3d, AabbTree3d, AabbTreeId, BoundingSphere, BoundingVolume, IntersectsVolume, RayCast3d,
+    },
+    Affine3A, Ray3d,
+};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{
+    primitives::{Aabb, Frustum},
+    view::VisibilitySystems,
+};
+
+/// Adds the [`SpatialIndex`] resource, kept up to date with the world-space bounds of all entities
+/// with an [`Aabb`] and a [`GlobalTransform`].
+///
+/// This plugin isn't part of the `DefaultPlugins`.
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>().add_systems(
+            PostUpdate,
+            update_spatial_index
+                .after(TransformSystem::TransformPropagate)
+                .after(VisibilitySystems::CalculateBounds),
+        );
+    }
+}
+
+/// Finds the entities in a region of space, without testing every entity.
+///
+/// The index contains the world-space bounds of every entity with an [`Aabb`] and a [`GlobalTransform`],
+/// and is updated by [`update_spatial_index`] in [`PostUpdate`]. Queries only test the bounding boxes,
+/// so they are meant as a broad phase before more precise tests.
+///
+/// Added by the [`SpatialIndexPlugin`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{bounding::BoundingSphere, Dir3, Ray3d, Vec3};
+/// # use bevy_render::spatial_index::SpatialIndex;
+/// fn explode(index: Res<SpatialIndex>) {
+///     let blast = BoundingSphere::new(Vec3::ZERO, 5.0);
+///     for entity in index.in_sphere(&blast) {
+///         // Apply damage to `entity`...
+///     }
+///
+///     let ray = Ray3d::new(Vec3::ZERO, Dir3::X);
+///     if let Some((entity, distance)) = index.cast_ray(ray, 100.0) {
+///         // `entity` is in the line of fire...
+///     }
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct SpatialIndex {
+    tree: AabbTree3d<Entity>,
+    ids: EntityHashMap<AabbTreeId>,
+}
+
+impl SpatialIndex {
+    /// Returns the world-space bounds of `entity`, if it's indexed.
+    pub fn aabb(&self, entity: Entity) -> Option<Aabb3d> {
+        self.tree.aabb(*self.ids.get(&entity)?)
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Returns `true` if no entity is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Returns the first entity hit by `ray` within `max_distance`, along with the distance to the hit.
+    pub fn cast_ray(&self, ray: Ray3d, max_distance: f32) -> Option<(Entity, f32)> {
+        self.tree
+            .cast_ray(&RayCast3d::from_ray(ray, max_distance))
+            .map(|(_, entity, distance)| (*entity, distance))
+    }
+
+    /// Returns the entities hit by `ray` within `max_distance`, in an unspecified order.
+    pub fn ray_hits(&self, ray: Ray3d, max_distance: f32) -> impl Iterator<Item = Entity> + '_ {
+        let ray = RayCast3d::from_ray(ray, max_distance);
+        self.tree
+            .query(move |aabb| ray.intersects(aabb))
+            .map(|(_, entity)| *entity)
+    }
+
+    /// Returns the entities whose bounds intersect `aabb`.
+    pub fn in_aabb<'a>(&'a self, aabb: &'a Aabb3d) -> impl Iterator<Item = Entity> + 'a {
+        self.tree.query_volume(aabb).map(|(_, entity)| *entity)
+    }
+
+    /// Returns the entities whose bounds intersect `sphere`.
+    pub fn in_sphere<'a>(
+        &'a self,
+        sphere: &'a BoundingSphere,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        self.tree.query_volume(sphere).map(|(_, entity)| *entity)
+    }
+
+    /// Returns the entities whose bounds intersect `frustum`, like the entities seen by a camera.
+    pub fn in_frustum<'a>(&'a self, frustum: &'a Frustum) -> impl Iterator<Item = Entity> + 'a {
+        self.tree
+            .query(|aabb| {
+                let aabb = Aabb {
+                    center: aabb.center(),
+                    half_extents: aabb.half_size(),
+                };
+                frustum.intersects_obb(&aabb, &Affine3A::IDENTITY, true, true)
+            })
+            .map(|(_, entity)| *entity)
+    }
+
+    /// Returns the underlying tree, for custom queries.
+    pub fn tree(&self) -> &AabbTree3d<Entity> {
+        &self.tree
+    }
+
+    fn insert_or_update(&mut self, entity: Entity, aabb: Aabb3d) {
+        match self.ids.get(&entity) {
+            Some(&id) => {
+                self.tree.update(id, aabb);
+            }
+            None => {
+                let id = self.tree.insert(aabb, entity);
+                self.ids.insert(entity, id);
+            }
+        }
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(id) = self.ids.remove(&entity) {
+            self.tree.remove(id);
+        }
+    }
+}
+
+/// Computes the world-space box bounding `aabb` transformed by `transform`.
+fn world_aabb(aabb: &Aabb, transform: &GlobalTransform) -> Aabb3d {
+    let affine = transform.affine();
+    let half_size = affine.matrix3.x_axis.abs() * aabb.half_extents.x
+        + affine.matrix3.y_axis.abs() * aabb.half_extents.y
+        + affine.matrix3.z_axis.abs() * aabb.half_extents.z;
+    Aabb3d::new(affine.transform_point3a(aabb.center), half_size)
+}
+
+/// Updates the [`SpatialIndex`] with the entities whose [`Aabb`] or [`GlobalTransform`] changed.
+pub fn update_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    changed: Query<
+        (Entity, &Aabb, &GlobalTransform),
+        Or<(Changed<Aabb>, Changed<GlobalTransform>)>,
+    >,
+    mut removed_aabbs: RemovedComponents<Aabb>,
+    mut removed_transforms: RemovedComponents<GlobalTransform>,
+) {
+    for entity in removed_aabbs.read().chain(removed_transforms.read()) {
+        index.remove(entity);
+    }
+    for (entity, aabb, transform) in &changed {
+        index.insert_or_update(entity, world_aabb(aabb, transform));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{Dir3, Vec3, Vec3A};
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn spatial_index_tracks_entities() {
+        let mut app = App::new();
+        app.add_plugins(SpatialIndexPlugin);
+        let aabb = Aabb {
+            center: Vec3A::ZERO,
+            half_extents: Vec3A::ONE,
+        };
+        let near = app
+            .world_mut()
+            .spawn((aabb, GlobalTransform::from_xyz(2.0, 0.0, 0.0)))
+            .id();
+        let far = app
+            .world_mut()
+            .spawn((
+                aabb,
+                GlobalTransform::from(
+                    Transform::from_xyz(20.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+                ),
+            ))
+            .id();
+        app.update();
+
+        let index = app.world().resource::<SpatialIndex>();
+        assert_eq!(index.len(), 2);
+        let sphere = BoundingSphere::new(Vec3::ZERO, 1.5);
+        assert_eq!(index.in_sphere(&sphere).collect::<Vec<_>>(), [near]);
+        let ray = Ray3d::new(Vec3::new(30.0, 0.0, 0.0), Dir3::NEG_X);
+        assert_eq!(index.cast_ray(ray, 100.0), Some((far, 8.0)));
+
+        app.world_mut().entity_mut(near).remove::<Aabb>();
+        app.world_mut()
+            .entity_mut(far)
+            .insert(GlobalTransform::from_xyz(0.0, 0.0, 0.0));
+        app.update();
+
+        let index = app.world().resource::<SpatialIndex>();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.in_sphere(&sphere).collect::<Vec<_>>(), [far]);
+    }
+}
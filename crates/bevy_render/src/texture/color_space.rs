@@ -0,0 +1,126 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{Asset, AssetEvent, AssetEvents, AssetId, Assets, Handle};
+use bevy_color::ColorSpace;
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{Struct, Typed};
+use tracing::warn;
+
+/// Conforms the textures of the assets of type `A` to the color space their fields are tagged
+/// with.
+///
+/// Fields of type `Option<Handle<Image>>` can be tagged with a [`ColorSpace`] reflection
+/// attribute. Whenever an asset is added or modified, the images of its tagged fields are
+/// reinterpreted with [`Image::set_color_space`] once they are loaded, so a normal map loaded
+/// as sRGB is sampled as linear data, and a base color texture loaded as linear is decoded from
+/// sRGB:
+///
+/// ```
+/// # use bevy_asset::{Asset, Handle};
+/// # use bevy_color::ColorSpace;
+/// # use bevy_image::Image;
+/// # use bevy_reflect::Reflect;
+/// #[derive(Asset, Reflect)]
+/// struct TerrainMaterial {
+///     #[reflect(@ColorSpace::Srgb)]
+///     albedo: Option<Handle<Image>>,
+///     #[reflect(@ColorSpace::LinearSrgb)]
+///     normal_map: Option<Handle<Image>>,
+/// }
+/// ```
+///
+/// Images in formats without an sRGB counterpart, such as HDR float images, are left untouched.
+/// Images shared by fields with different color spaces keep the first one they were conformed
+/// to, and a warning is logged.
+pub struct TextureColorSpacePlugin<A>(PhantomData<fn() -> A>);
+
+impl<A> Default for TextureColorSpacePlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Asset + Struct + Typed> Plugin for TextureColorSpacePlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            conform_texture_color_spaces::<A>.after(AssetEvents),
+        );
+    }
+}
+
+/// Returns the indices of the fields of `A` tagged with a [`ColorSpace`], with their color space.
+fn tagged_fields<A: Struct + Typed>() -> impl Iterator<Item = (usize, ColorSpace)> {
+    A::type_info()
+        .as_struct()
+        .into_iter()
+        .flat_map(|info| info.iter().enumerate())
+        .filter_map(|(index, field)| Some((index, *field.get_attribute::<ColorSpace>()?)))
+}
+
+/// The images waiting to be conformed to a color space, and the color spaces the images were
+/// already conformed to.
+#[derive(Default)]
+struct TextureColorSpaces {
+    pending: HashMap<AssetId<Image>, ColorSpace>,
+    conformed: HashMap<AssetId<Image>, ColorSpace>,
+}
+
+fn conform_texture_color_spaces<A: Asset + Struct + Typed>(
+    mut events: EventReader<AssetEvent<A>>,
+    assets: Res<Assets<A>>,
+    mut images: ResMut<Assets<Image>>,
+    mut state: Local<TextureColorSpaces>,
+) {
+    let state = &mut *state;
+    for event in events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        let Some(asset) = assets.get(*id) else {
+            continue;
+        };
+        for (index, color_space) in tagged_fields::<A>() {
+            let Some(Some(handle)) = asset
+                .field_at(index)
+                .and_then(|field| field.try_downcast_ref::<Option<Handle<Image>>>())
+            else {
+                continue;
+            };
+            let image_id = handle.id();
+            match state.conformed.get(&image_id) {
+                Some(&conformed) if conformed != color_space => warn!(
+                    "Texture {image_id} is used as {color_space:?} by a field of {}, but was already conformed to {conformed:?}",
+                    A::type_info().type_path()
+                ),
+                Some(_) => {}
+                None => {
+                    state.pending.insert(image_id, color_space);
+                }
+            }
+        }
+    }
+
+    state.pending.retain(|&image_id, &mut color_space| {
+        let Some(image) = images.get(image_id) else {
+            // Retry once the image is loaded.
+            return true;
+        };
+        let format = image.texture_descriptor.format;
+        // Formats without an sRGB counterpart, such as float formats, always store linear data.
+        let reinterpretable = format.add_srgb_suffix() != format.remove_srgb_suffix();
+        if reinterpretable && image.color_space() != color_space {
+            let Some(image) = images.get_mut(image_id) else {
+                return false;
+            };
+            if let Err(error) = image.set_color_space(color_space) {
+                warn!("Failed to conform texture {image_id} to {color_space:?}: {error}");
+            }
+        }
+        state.conformed.insert(image_id, color_space);
+        false
+    });
+}
@@ -1,3 +1,4 @@
+mod color_space;
 mod fallback_image;
 mod gpu_image;
 mod texture_attachment;
@@ -9,6 +10,7 @@ use bevy_image::CompressedImageSaver;
 #[cfg(feature = "hdr")]
 use bevy_image::HdrTextureLoader;
 use bevy_image::{CompressedImageFormats, Image, ImageLoader, ImageSamplerDescriptor};
+pub use color_space::*;
 pub use fallback_image::*;
 pub use gpu_image::*;
 pub use texture_attachment::*;
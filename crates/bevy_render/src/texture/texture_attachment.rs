@@ -1,5 +1,8 @@
 use super::CachedTexture;
-use crate::render_resource::{TextureFormat, TextureView};
+use crate::{
+    render_resource::{TextureFormat, TextureView},
+    view::ActiveHdrOutput,
+};
 use alloc::sync::Arc;
 use bevy_color::LinearRgba;
 use core::sync::atomic::{AtomicBool, Ordering};
@@ -126,6 +129,8 @@ impl DepthAttachment {
 pub struct OutputColorAttachment {
     pub view: TextureView,
     pub format: TextureFormat,
+    /// The HDR output of the window this texture is presented to, if it is active.
+    pub hdr_output: Option<ActiveHdrOutput>,
     is_first_call: Arc<AtomicBool>,
 }
 
@@ -134,10 +139,17 @@ impl OutputColorAttachment {
         Self {
             view,
             format,
+            hdr_output: None,
             is_first_call: Arc::new(AtomicBool::new(true)),
         }
     }
 
+    /// Sets the HDR output the texture is encoded for.
+    pub fn with_hdr_output(mut self, hdr_output: Option<ActiveHdrOutput>) -> Self {
+        self.hdr_output = hdr_output;
+        self
+    }
+
     /// Get this texture view as an attachment. The attachment will be cleared with a value of
     /// the provided `clear_color` if this is the first time calling this function, otherwise it
     /// will be loaded.
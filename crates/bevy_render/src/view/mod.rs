@@ -8,7 +8,7 @@ pub use window::*;
 use crate::{
     camera::{
         CameraMainTextureUsages, ClearColor, ClearColorConfig, Exposure, ExtractedCamera,
-        ManualTextureViews, MipBias, NormalizedRenderTarget, TemporalJitter,
+        ManualTextureViews, MipBias, NormalizedRenderTarget, TemporalJitter, ViewRenderScale,
     },
     experimental::occlusion_culling::OcclusionCulling,
     extract_component::ExtractComponentPlugin,
@@ -31,7 +31,7 @@ use bevy_color::LinearRgba;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::prelude::*;
 use bevy_image::BevyDefault as _;
-use bevy_math::{mat3, vec2, vec3, Mat3, Mat4, UVec4, Vec2, Vec3, Vec4, Vec4Swizzles};
+use bevy_math::{mat3, vec2, vec3, FloatExt, Mat3, Mat4, UVec4, Vec2, Vec3, Vec4, Vec4Swizzles};
 use bevy_platform_support::collections::{hash_map::Entry, HashMap};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render_macros::ExtractComponent;
@@ -518,6 +518,50 @@ impl ColorGrading {
     pub fn all_sections_mut(&mut self) -> impl Iterator<Item = &mut ColorGradingSection> {
         [&mut self.shadows, &mut self.midtones, &mut self.highlights].into_iter()
     }
+
+    /// Linearly interpolates every color grading value between `self` and
+    /// `rhs`, based on the value `t`.
+    ///
+    /// When `t` is `0.0`, the result will be equal to `self`. When `t` is
+    /// `1.0`, the result will be equal to `rhs`.
+    pub fn lerp(&self, rhs: &ColorGrading, t: f32) -> ColorGrading {
+        ColorGrading {
+            global: self.global.lerp(&rhs.global, t),
+            shadows: self.shadows.lerp(&rhs.shadows, t),
+            midtones: self.midtones.lerp(&rhs.midtones, t),
+            highlights: self.highlights.lerp(&rhs.highlights, t),
+        }
+    }
+}
+
+impl ColorGradingGlobal {
+    /// Linearly interpolates every value between `self` and `rhs`, based on
+    /// the value `t`.
+    pub fn lerp(&self, rhs: &ColorGradingGlobal, t: f32) -> ColorGradingGlobal {
+        ColorGradingGlobal {
+            exposure: self.exposure.lerp(rhs.exposure, t),
+            temperature: self.temperature.lerp(rhs.temperature, t),
+            tint: self.tint.lerp(rhs.tint, t),
+            hue: self.hue.lerp(rhs.hue, t),
+            post_saturation: self.post_saturation.lerp(rhs.post_saturation, t),
+            midtones_range: self.midtones_range.start.lerp(rhs.midtones_range.start, t)
+                ..self.midtones_range.end.lerp(rhs.midtones_range.end, t),
+        }
+    }
+}
+
+impl ColorGradingSection {
+    /// Linearly interpolates every value between `self` and `rhs`, based on
+    /// the value `t`.
+    pub fn lerp(&self, rhs: &ColorGradingSection, t: f32) -> ColorGradingSection {
+        ColorGradingSection {
+            saturation: self.saturation.lerp(rhs.saturation, t),
+            contrast: self.contrast.lerp(rhs.contrast, t),
+            gamma: self.gamma.lerp(rhs.gamma, t),
+            gain: self.gain.lerp(rhs.gain, t),
+            lift: self.lift.lerp(rhs.lift, t),
+        }
+    }
 }
 
 #[derive(Clone, ShaderType)]
@@ -827,6 +871,12 @@ impl ViewTarget {
         self.out_texture.format
     }
 
+    /// The HDR output the final texture this view will render to is encoded for, if any.
+    #[inline]
+    pub fn out_texture_hdr_output(&self) -> Option<ActiveHdrOutput> {
+        self.out_texture.hdr_output
+    }
+
     /// This will start a new "post process write", which assumes that the caller
     /// will write the [`PostProcessWrite`]'s `source` to the `destination`.
     ///
@@ -888,6 +938,7 @@ pub fn prepare_view_uniforms(
         Option<&Frustum>,
         Option<&TemporalJitter>,
         Option<&MipBias>,
+        Option<&ViewRenderScale>,
     )>,
 ) {
     let view_iter = views.iter();
@@ -899,8 +950,23 @@ pub fn prepare_view_uniforms(
     else {
         return;
     };
-    for (entity, extracted_camera, extracted_view, frustum, temporal_jitter, mip_bias) in &views {
-        let viewport = extracted_view.viewport.as_vec4();
+    for (
+        entity,
+        extracted_camera,
+        extracted_view,
+        frustum,
+        temporal_jitter,
+        mip_bias,
+        render_scale,
+    ) in &views
+    {
+        let mut viewport = extracted_view.viewport;
+        // The main passes, which use this uniform, render to a smaller viewport.
+        if let Some(render_scale) = render_scale {
+            viewport.z = render_scale.main_pass_size.x;
+            viewport.w = render_scale.main_pass_size.y;
+        }
+        let viewport = viewport.as_vec4();
         let unjittered_projection = extracted_view.clip_from_view;
         let mut clip_from_view = unjittered_projection;
 
@@ -979,7 +1045,14 @@ pub fn prepare_view_attachments(
                     .cloned()
                     .zip(target.get_texture_format(&windows, &images, &manual_texture_views))
                     .map(|(view, format)| {
+                        let hdr_output = match target {
+                            NormalizedRenderTarget::Window(window_ref) => windows
+                                .get(&window_ref.entity())
+                                .and_then(ExtractedWindow::active_hdr_output),
+                            _ => None,
+                        };
                         OutputColorAttachment::new(view.clone(), format.add_srgb_suffix())
+                            .with_hdr_output(hdr_output)
                     })
                 else {
                     continue;
@@ -0,0 +1,767 @@
+//! Continuous capture of the frames rendered to a [`RenderTarget`].
+
+use super::{
+    screenshot::{prepare_screenshots, RenderScreenshotTargets, ScreenshotToScreenPipeline},
+    ExtractedWindows,
+};
+use crate::{
+    camera::{ManualTextureViews, NormalizedRenderTarget, RenderTarget},
+    gpu_readback,
+    prelude::Shader,
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_resource::{
+        binding_types::texture_2d, BindGroup, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, Buffer, BufferUsages, CachedRenderPipelineId, FragmentState,
+        PipelineCache, RenderPipelineDescriptor, ShaderDefVal, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, Texture, TextureUsages, TextureView, VertexState,
+    },
+    renderer::RenderDevice,
+    texture::{GpuImage, OutputColorAttachment},
+    view::{prepare_view_attachments, prepare_view_targets, ViewTargetAttachments, WindowSurfaces},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use alloc::{borrow::Cow, sync::Arc};
+use bevy_app::{Plugin, PostUpdate, Update};
+use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{entity::hash_map::EntityHashMap, prelude::*};
+use bevy_image::{Image, TextureFormatPixelInfo};
+use bevy_reflect::Reflect;
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_time::{Real, Time};
+use bevy_utils::default;
+use bevy_window::{PrimaryWindow, WindowRef};
+use core::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use std::sync::{
+    mpsc::{Receiver, Sender},
+    Mutex,
+};
+use tracing::{error, warn};
+use wgpu::{CommandEncoder, Extent3d, TextureFormat};
+
+/// Triggered on a [`FrameCapture`] entity each time a frame has been captured.
+#[derive(Event, Reflect, Debug)]
+#[reflect(Debug)]
+pub struct FrameCaptured {
+    /// The captured frame, downscaled according to [`FrameCapture::downscale`].
+    pub image: Image,
+    /// The number of frames which were due to be captured since the previous one, but were
+    /// dropped because all the readback buffers were still in use.
+    pub dropped_frames: u32,
+}
+
+/// A component that continuously captures the frames rendered to a [`RenderTarget`], for example
+/// to stream them or make replay thumbnails.
+///
+/// Each captured frame triggers [`FrameCaptured`] on the entity, so an observer should be added
+/// to it to handle the frames. The capture goes on until the component is removed.
+///
+/// Unlike a [`Screenshot`](super::screenshot::Screenshot), the frames are copied through a ring of
+/// [`FrameCapture::buffer_count`] readback buffers which are reused from frame to frame. When they
+/// are all still waiting for the GPU, because the CPU is running ahead of it, the frame isn't
+/// captured, so that capturing never stalls rendering. A [`Screenshot`](super::screenshot::Screenshot)
+/// of the same target also takes precedence over the capture for that frame.
+///
+/// # Usage
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::view::frame_capture::{FrameCapture, FrameCaptured};
+/// # use core::time::Duration;
+/// fn start_capture(mut commands: Commands) {
+///     commands
+///         .spawn(
+///             FrameCapture::primary_window()
+///                 .with_downscale(4)
+///                 .with_interval(Duration::from_secs_f32(1.0 / 30.0)),
+///         )
+///         .observe(|trigger: Trigger<FrameCaptured>| {
+///             let size = trigger.event().image.size();
+///             // Encode or stream the frame here.
+///         });
+/// }
+/// ```
+#[derive(Component, Clone, Reflect, Debug)]
+#[reflect(Component, Debug)]
+#[require(FrameCaptureTimer)]
+pub struct FrameCapture {
+    /// The render target to capture.
+    pub target: RenderTarget,
+    /// The factor by which the width and height of the frames are divided, by averaging blocks
+    /// of `downscale` × `downscale` pixels.
+    ///
+    /// Defaults to 1, capturing the frames at full resolution.
+    pub downscale: u32,
+    /// The minimum time between two captured frames, measured in real time.
+    ///
+    /// Defaults to [`Duration::ZERO`], capturing every frame.
+    pub interval: Duration,
+    /// The number of readback buffers, which is the maximum number of frames waiting to be copied
+    /// to the CPU at the same time.
+    ///
+    /// Defaults to 3.
+    pub buffer_count: usize,
+}
+
+impl FrameCapture {
+    /// Captures the frames rendered to the provided render target.
+    pub fn new(target: RenderTarget) -> Self {
+        Self {
+            target,
+            downscale: 1,
+            interval: Duration::ZERO,
+            buffer_count: 3,
+        }
+    }
+
+    /// Captures the frames rendered to the provided window entity.
+    pub fn window(window: Entity) -> Self {
+        Self::new(RenderTarget::Window(WindowRef::Entity(window)))
+    }
+
+    /// Captures the frames rendered to the primary window, if one exists.
+    pub fn primary_window() -> Self {
+        Self::new(RenderTarget::Window(WindowRef::Primary))
+    }
+
+    /// Sets the [`FrameCapture::downscale`] factor.
+    pub fn with_downscale(mut self, downscale: u32) -> Self {
+        self.downscale = downscale;
+        self
+    }
+
+    /// Sets the minimum [`FrameCapture::interval`] between two captured frames.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the number of readback buffers.
+    pub fn with_buffer_count(mut self, buffer_count: usize) -> Self {
+        self.buffer_count = buffer_count;
+        self
+    }
+}
+
+/// Tracks when a [`FrameCapture`] should capture the next frame.
+#[derive(Component, Default)]
+struct FrameCaptureTimer {
+    elapsed: Duration,
+    due: bool,
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct CapturedFrames(Arc<Mutex<Receiver<(Entity, FrameCaptured)>>>);
+
+#[derive(Resource, Deref, DerefMut)]
+struct RenderCapturedFramesSender(Sender<(Entity, FrameCaptured)>);
+
+struct ExtractedFrameCapture {
+    target: NormalizedRenderTarget,
+    downscale: u32,
+    buffer_count: usize,
+    due: bool,
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct RenderFrameCaptures(EntityHashMap<ExtractedFrameCapture>);
+
+/// The GPU resources of a [`FrameCapture`], kept from frame to frame.
+struct FrameCaptureState {
+    target: NormalizedRenderTarget,
+    /// The texture the frame is rendered to instead of the render target.
+    texture: Texture,
+    texture_view: TextureView,
+    /// Copies `texture` back to the render target.
+    blit_bind_group: BindGroup,
+    blit_pipeline_id: CachedRenderPipelineId,
+    downscaled: Option<DownscaledTexture>,
+    buffers: Vec<ReadbackBuffer>,
+    format: TextureFormat,
+    size: Extent3d,
+    downscale: u32,
+    /// The buffer the frame is copied to this frame, if it is captured.
+    pending: Option<usize>,
+    dropped_frames: u32,
+}
+
+struct DownscaledTexture {
+    texture: Texture,
+    view: TextureView,
+    bind_group: BindGroup,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+struct ReadbackBuffer {
+    buffer: Buffer,
+    /// Set while the buffer is waiting to be copied to the CPU.
+    in_flight: Arc<AtomicBool>,
+}
+
+impl FrameCaptureState {
+    /// The size of the captured frames.
+    fn capture_size(&self) -> Extent3d {
+        downscaled_size(self.size, self.downscale)
+    }
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+struct RenderFrameCaptureStates(EntityHashMap<FrameCaptureState>);
+
+fn downscaled_size(size: Extent3d, downscale: u32) -> Extent3d {
+    Extent3d {
+        width: (size.width / downscale).max(1),
+        height: (size.height / downscale).max(1),
+        depth_or_array_layers: 1,
+    }
+}
+
+fn update_frame_capture_timers(
+    time: Res<Time<Real>>,
+    mut captures: Query<(&FrameCapture, &mut FrameCaptureTimer)>,
+) {
+    for (capture, mut timer) in &mut captures {
+        timer.elapsed += time.delta();
+        // Restart from zero rather than carrying the remainder over, so that a slow frame
+        // doesn't make the following ones capture in a burst.
+        timer.due = timer.elapsed >= capture.interval;
+        if timer.due {
+            timer.elapsed = Duration::ZERO;
+        }
+    }
+}
+
+fn trigger_frame_captures(mut commands: Commands, captured_frames: Res<CapturedFrames>) {
+    let captured_frames = captured_frames.lock().unwrap();
+    while let Ok((entity, captured)) = captured_frames.try_recv() {
+        if commands.get_entity(entity).is_some() {
+            commands.trigger_targets(captured, entity);
+        }
+    }
+}
+
+fn extract_frame_captures(
+    mut captures: ResMut<RenderFrameCaptures>,
+    primary_window: Extract<Query<Entity, With<PrimaryWindow>>>,
+    query: Extract<Query<(Entity, &FrameCapture, &FrameCaptureTimer)>>,
+) {
+    captures.clear();
+    let primary_window = primary_window.iter().next();
+    for (entity, capture, timer) in &query {
+        let Some(target) = capture.target.normalize(primary_window) else {
+            warn!(
+                "Unknown render target for frame capture, skipping: {:?}",
+                capture.target
+            );
+            continue;
+        };
+        captures.insert(
+            entity,
+            ExtractedFrameCapture {
+                target,
+                downscale: capture.downscale.max(1),
+                buffer_count: capture.buffer_count.max(1),
+                due: timer.due,
+            },
+        );
+    }
+}
+
+fn prepare_frame_captures(
+    captures: Res<RenderFrameCaptures>,
+    mut states: ResMut<RenderFrameCaptureStates>,
+    screenshot_targets: Res<RenderScreenshotTargets>,
+    window_surfaces: Res<WindowSurfaces>,
+    images: Res<RenderAssets<GpuImage>>,
+    manual_texture_views: Res<ManualTextureViews>,
+    render_device: Res<RenderDevice>,
+    blit_pipeline: Res<ScreenshotToScreenPipeline>,
+    downscale_pipeline: Res<FrameCaptureDownscalePipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    mut blit_pipelines: ResMut<SpecializedRenderPipelines<ScreenshotToScreenPipeline>>,
+    mut downscale_pipelines: ResMut<SpecializedRenderPipelines<FrameCaptureDownscalePipeline>>,
+    mut view_target_attachments: ResMut<ViewTargetAttachments>,
+) {
+    states.retain(|entity, _| captures.contains_key(entity));
+
+    for (entity, capture) in captures.iter() {
+        if let Some(state) = states.get_mut(entity) {
+            state.pending = None;
+        }
+        if !capture.due {
+            continue;
+        }
+
+        let Some((size, format)) = target_size_and_format(
+            &capture.target,
+            &window_surfaces,
+            &images,
+            &manual_texture_views,
+        ) else {
+            continue;
+        };
+
+        let up_to_date = states.get(entity).is_some_and(|state| {
+            state.target == capture.target
+                && state.size == size
+                && state.format == format
+                && state.downscale == capture.downscale
+                && state.buffers.len() == capture.buffer_count
+        });
+        if !up_to_date {
+            let state = prepare_frame_capture_state(
+                capture,
+                size,
+                format,
+                &render_device,
+                &blit_pipeline,
+                &downscale_pipeline,
+                &pipeline_cache,
+                &mut blit_pipelines,
+                &mut downscale_pipelines,
+            );
+            states.insert(*entity, state);
+        }
+        let state = states.get_mut(entity).unwrap();
+
+        // A screenshot of the same target already redirects its output this frame.
+        if screenshot_targets
+            .values()
+            .any(|target| *target == capture.target)
+        {
+            state.dropped_frames += 1;
+            continue;
+        }
+        let pipelines_ready = pipeline_cache
+            .get_render_pipeline(state.blit_pipeline_id)
+            .is_some()
+            && state.downscaled.as_ref().is_none_or(|downscaled| {
+                pipeline_cache
+                    .get_render_pipeline(downscaled.pipeline_id)
+                    .is_some()
+            });
+        if !pipelines_ready {
+            continue;
+        }
+        let Some(index) = state
+            .buffers
+            .iter()
+            .position(|buffer| !buffer.in_flight.load(Ordering::Acquire))
+        else {
+            state.dropped_frames += 1;
+            continue;
+        };
+        state.buffers[index]
+            .in_flight
+            .store(true, Ordering::Release);
+        state.pending = Some(index);
+
+        view_target_attachments.insert(
+            capture.target.clone(),
+            OutputColorAttachment::new(state.texture_view.clone(), format.add_srgb_suffix()),
+        );
+    }
+}
+
+/// Returns the size and format of the texture views rendered to `target`.
+fn target_size_and_format(
+    target: &NormalizedRenderTarget,
+    window_surfaces: &WindowSurfaces,
+    images: &RenderAssets<GpuImage>,
+    manual_texture_views: &ManualTextureViews,
+) -> Option<(Extent3d, TextureFormat)> {
+    match target {
+        NormalizedRenderTarget::Window(window) => {
+            let surface_data = window_surfaces.surfaces.get(&window.entity())?;
+            let size = Extent3d {
+                width: surface_data.configuration.width,
+                height: surface_data.configuration.height,
+                ..default()
+            };
+            Some((size, surface_data.configuration.format.add_srgb_suffix()))
+        }
+        NormalizedRenderTarget::Image(image) => {
+            let gpu_image = images.get(&image.handle)?;
+            Some((gpu_image.size, gpu_image.texture_format))
+        }
+        NormalizedRenderTarget::TextureView(texture_view) => {
+            let manual_texture_view = manual_texture_views.get(texture_view)?;
+            let size = Extent3d {
+                width: manual_texture_view.size.x,
+                height: manual_texture_view.size.y,
+                ..default()
+            };
+            Some((size, manual_texture_view.format))
+        }
+    }
+}
+
+fn prepare_frame_capture_state(
+    capture: &ExtractedFrameCapture,
+    size: Extent3d,
+    format: TextureFormat,
+    render_device: &RenderDevice,
+    blit_pipeline: &ScreenshotToScreenPipeline,
+    downscale_pipeline: &FrameCaptureDownscalePipeline,
+    pipeline_cache: &PipelineCache,
+    blit_pipelines: &mut SpecializedRenderPipelines<ScreenshotToScreenPipeline>,
+    downscale_pipelines: &mut SpecializedRenderPipelines<FrameCaptureDownscalePipeline>,
+) -> FrameCaptureState {
+    let texture = render_device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("frame-capture-rendertarget"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: TextureUsages::RENDER_ATTACHMENT
+            | TextureUsages::COPY_SRC
+            | TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let texture_view = texture.create_view(&Default::default());
+    let blit_bind_group = render_device.create_bind_group(
+        "frame-capture-to-screen-bind-group",
+        &blit_pipeline.bind_group_layout,
+        &BindGroupEntries::single(&texture_view),
+    );
+    let blit_pipeline_id = blit_pipelines.specialize(pipeline_cache, blit_pipeline, format);
+
+    let capture_size = downscaled_size(size, capture.downscale);
+    let downscaled = (capture.downscale > 1).then(|| {
+        let texture = render_device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("frame-capture-downscaled"),
+            size: capture_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&Default::default());
+        let bind_group = render_device.create_bind_group(
+            "frame-capture-downscale-bind-group",
+            &downscale_pipeline.bind_group_layout,
+            &BindGroupEntries::single(&texture_view),
+        );
+        let pipeline_id = downscale_pipelines.specialize(
+            pipeline_cache,
+            downscale_pipeline,
+            FrameCaptureDownscalePipelineKey {
+                format,
+                downscale: capture.downscale,
+            },
+        );
+        DownscaledTexture {
+            texture,
+            view,
+            bind_group,
+            pipeline_id,
+        }
+    });
+
+    let buffers = (0..capture.buffer_count)
+        .map(|_| ReadbackBuffer {
+            buffer: render_device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("frame-capture-transfer-buffer"),
+                size: gpu_readback::get_aligned_size(capture_size, format.pixel_size() as u32)
+                    as u64,
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            in_flight: Arc::new(AtomicBool::new(false)),
+        })
+        .collect();
+
+    FrameCaptureState {
+        target: capture.target.clone(),
+        texture,
+        texture_view,
+        blit_bind_group,
+        blit_pipeline_id,
+        downscaled,
+        buffers,
+        format,
+        size,
+        downscale: capture.downscale,
+        pending: None,
+        dropped_frames: 0,
+    }
+}
+
+/// A plugin that enables [`FrameCapture`].
+pub struct FrameCapturePlugin;
+
+const FRAME_CAPTURE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("4f0b2a8e-6d37-4c1e-9a55-2e8c1f7d93b6");
+
+impl Plugin for FrameCapturePlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(PostUpdate, update_frame_capture_timers)
+            .add_systems(Update, trigger_frame_captures)
+            .register_type::<FrameCapture>()
+            .register_type::<FrameCaptured>();
+
+        load_internal_asset!(
+            app,
+            FRAME_CAPTURE_SHADER_HANDLE,
+            "frame_capture.wgsl",
+            Shader::from_wgsl
+        );
+    }
+
+    fn finish(&self, app: &mut bevy_app::App) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        app.insert_resource(CapturedFrames(Arc::new(Mutex::new(rx))));
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(RenderCapturedFramesSender(tx))
+                .init_resource::<RenderFrameCaptures>()
+                .init_resource::<RenderFrameCaptureStates>()
+                .init_resource::<FrameCaptureDownscalePipeline>()
+                .init_resource::<SpecializedRenderPipelines<FrameCaptureDownscalePipeline>>()
+                .add_systems(ExtractSchedule, extract_frame_captures)
+                .add_systems(
+                    Render,
+                    prepare_frame_captures
+                        .after(prepare_view_attachments)
+                        .after(prepare_screenshots)
+                        .before(prepare_view_targets)
+                        .in_set(RenderSet::ManageViews),
+                );
+        }
+    }
+}
+
+/// Averages blocks of pixels of the captured frames when [`FrameCapture::downscale`] is set.
+#[derive(Resource)]
+pub struct FrameCaptureDownscalePipeline {
+    pub bind_group_layout: BindGroupLayout,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameCaptureDownscalePipelineKey {
+    pub format: TextureFormat,
+    pub downscale: u32,
+}
+
+impl FromWorld for FrameCaptureDownscalePipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let device = render_world.resource::<RenderDevice>();
+
+        let bind_group_layout = device.create_bind_group_layout(
+            "frame-capture-downscale-bgl",
+            &BindGroupLayoutEntries::single(
+                wgpu::ShaderStages::FRAGMENT,
+                texture_2d(wgpu::TextureSampleType::Float { filterable: false }),
+            ),
+        );
+
+        Self { bind_group_layout }
+    }
+}
+
+impl SpecializedRenderPipeline for FrameCaptureDownscalePipeline {
+    type Key = FrameCaptureDownscalePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = vec![ShaderDefVal::UInt("DOWNSCALE_FACTOR".into(), key.downscale)];
+        RenderPipelineDescriptor {
+            label: Some(Cow::Borrowed("frame-capture-downscale")),
+            layout: vec![self.bind_group_layout.clone()],
+            vertex: VertexState {
+                buffers: vec![],
+                shader_defs: shader_defs.clone(),
+                entry_point: Cow::Borrowed("vs_main"),
+                shader: FRAME_CAPTURE_SHADER_HANDLE,
+            },
+            primitive: wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: Default::default(),
+            fragment: Some(FragmentState {
+                shader: FRAME_CAPTURE_SHADER_HANDLE,
+                entry_point: Cow::Borrowed("fs_main"),
+                shader_defs,
+                targets: vec![Some(wgpu::ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            push_constant_ranges: Vec::new(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+pub(crate) fn submit_frame_capture_commands(world: &World, encoder: &mut CommandEncoder) {
+    let states = world.resource::<RenderFrameCaptureStates>();
+    let pipelines = world.resource::<PipelineCache>();
+    let windows = world.resource::<ExtractedWindows>();
+    let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+    let manual_texture_views = world.resource::<ManualTextureViews>();
+
+    for state in states.values() {
+        let Some(index) = state.pending else {
+            continue;
+        };
+
+        // Downscale the frame first, then copy it to the readback buffer.
+        let source = match &state.downscaled {
+            Some(downscaled) => {
+                let Some(pipeline) = pipelines.get_render_pipeline(downscaled.pipeline_id) else {
+                    continue;
+                };
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("frame_capture_downscale_pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &downscaled.view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &downscaled.bind_group, &[]);
+                pass.draw(0..3, 0..1);
+                &downscaled.texture
+            }
+            None => &state.texture,
+        };
+        let extent = state.capture_size();
+        encoder.copy_texture_to_buffer(
+            source.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &state.buffers[index].buffer,
+                layout: gpu_readback::layout_data(extent, state.format),
+            },
+            extent,
+        );
+
+        // Then show the frame on the render target, as it was rendered to the capture texture.
+        let Some(texture_view) =
+            state
+                .target
+                .get_texture_view(windows, gpu_images, manual_texture_views)
+        else {
+            continue;
+        };
+        if let Some(pipeline) = pipelines.get_render_pipeline(state.blit_pipeline_id) {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("frame_capture_to_screen_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: texture_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &state.blit_bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+}
+
+pub(crate) fn collect_frame_captures(world: &mut World) {
+    #[cfg(feature = "trace")]
+    let _span = tracing::info_span!("collect_frame_captures").entered();
+
+    let sender = world.resource::<RenderCapturedFramesSender>().0.clone();
+    let mut states = world.resource_mut::<RenderFrameCaptureStates>();
+
+    for (entity, state) in states.iter_mut() {
+        let Some(index) = state.pending.take() else {
+            continue;
+        };
+        let entity = *entity;
+        let sender = sender.clone();
+        let size = state.capture_size();
+        let texture_format = state.format;
+        let pixel_size = texture_format.pixel_size();
+        let dropped_frames = core::mem::take(&mut state.dropped_frames);
+        let buffer = state.buffers[index].buffer.clone();
+        let in_flight = state.buffers[index].in_flight.clone();
+
+        let finish = async move {
+            let (tx, rx) = async_channel::bounded(1);
+            let buffer_slice = buffer.slice(..);
+            // The polling for this map call is done every frame when the command queue is submitted.
+            buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+                if let Err(err) = &result {
+                    error!("Failed to map frame capture buffer: {err}");
+                }
+                tx.try_send(result.is_ok()).unwrap();
+            });
+            if !rx.recv().await.unwrap() {
+                in_flight.store(false, Ordering::Release);
+                return;
+            }
+            let data = buffer_slice.get_mapped_range();
+            // we immediately move the data to CPU memory to release the buffer for the next captures
+            let mut result = Vec::from(&*data);
+            drop(data);
+            buffer.unmap();
+            in_flight.store(false, Ordering::Release);
+
+            gpu_readback::remove_row_padding(&mut result, size, pixel_size);
+            let captured = FrameCaptured {
+                image: Image::new(
+                    size,
+                    wgpu::TextureDimension::D2,
+                    result,
+                    texture_format,
+                    RenderAssetUsages::RENDER_WORLD,
+                ),
+                dropped_frames,
+            };
+            if let Err(e) = sender.send((entity, captured)) {
+                error!("Failed to send captured frame: {}", e);
+            }
+        };
+
+        AsyncComputeTaskPool::get().spawn(finish).detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downscaled_size_is_never_empty() {
+        let size = Extent3d {
+            width: 1920,
+            height: 1081,
+            depth_or_array_layers: 1,
+        };
+        assert_eq!(
+            downscaled_size(size, 4),
+            Extent3d {
+                width: 480,
+                height: 270,
+                depth_or_array_layers: 1,
+            }
+        );
+        assert_eq!(downscaled_size(size, 4096).width, 1);
+        assert_eq!(downscaled_size(size, 4096).height, 1);
+    }
+}
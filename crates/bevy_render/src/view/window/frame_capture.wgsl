@@ -0,0 +1,31 @@
+// This vertex shader will create a triangle that will cover the entire screen
+// with minimal effort, avoiding the need for a vertex buffer etc.
+@vertex
+fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let x = f32((in_vertex_index & 1u) << 2u);
+    let y = f32((in_vertex_index & 2u) << 1u);
+    return vec4<f32>(x - 1.0, y - 1.0, 0.0, 1.0);
+}
+
+@group(0) @binding(0) var t: texture_2d<f32>;
+
+// Averages the block of DOWNSCALE_FACTOR × DOWNSCALE_FACTOR source texels covered by this pixel.
+@fragment
+fn fs_main(@builtin(position) pos: vec4<f32>) -> @location(0) vec4<f32> {
+    let factor = i32(#{DOWNSCALE_FACTOR}u);
+    let origin = vec2<i32>(floor(pos.xy)) * factor;
+    let source_size = vec2<i32>(textureDimensions(t));
+
+    var sum = vec4<f32>(0.0);
+    var count = 0.0;
+    for (var y = 0; y < factor; y += 1) {
+        for (var x = 0; x < factor; x += 1) {
+            let coords = origin + vec2<i32>(x, y);
+            if all(coords < source_size) {
+                sum += textureLoad(t, coords, 0i);
+                count += 1.0;
+            }
+        }
+    }
+    return sum / max(count, 1.0);
+}
@@ -8,7 +8,8 @@ use bevy_ecs::{entity::hash_map::EntityHashMap, prelude::*};
 use bevy_platform_support::collections::HashSet;
 use bevy_utils::default;
 use bevy_window::{
-    CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosing,
+    CompositeAlphaMode, HdrOutput, HdrOutputMode, PresentMode, PrimaryWindow, RawHandleWrapper,
+    Window, WindowClosing,
 };
 use core::{
     num::NonZero,
@@ -19,15 +20,17 @@ use wgpu::{
     SurfaceConfiguration, SurfaceTargetUnsafe, TextureFormat, TextureUsages, TextureViewDescriptor,
 };
 
+pub mod frame_capture;
 pub mod screenshot;
 
+use frame_capture::FrameCapturePlugin;
 use screenshot::{ScreenshotPlugin, ScreenshotToScreenPipeline};
 
 pub struct WindowRenderPlugin;
 
 impl Plugin for WindowRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(ScreenshotPlugin);
+        app.add_plugins((ScreenshotPlugin, FrameCapturePlugin));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -68,9 +71,23 @@ pub struct ExtractedWindow {
     pub size_changed: bool,
     pub present_mode_changed: bool,
     pub alpha_mode: CompositeAlphaMode,
+    /// The HDR output requested by the window.
+    pub hdr_output: HdrOutput,
+    pub hdr_output_mode_changed: bool,
+    /// The HDR encoding of the swap chain texture, if HDR output is active.
+    pub hdr_output_format: Option<HdrOutputFormat>,
 }
 
 impl ExtractedWindow {
+    /// Returns the HDR output of the window, if it is active.
+    pub fn active_hdr_output(&self) -> Option<ActiveHdrOutput> {
+        self.hdr_output_format.map(|format| ActiveHdrOutput {
+            format,
+            paper_white_nits: self.hdr_output.paper_white_nits.round().max(1.0) as u32,
+            peak_nits: self.hdr_output.peak_nits.round().max(1.0) as u32,
+        })
+    }
+
     fn set_swapchain_texture(&mut self, frame: wgpu::SurfaceTexture) {
         let texture_view_descriptor = TextureViewDescriptor {
             format: Some(frame.texture.format().add_srgb_suffix()),
@@ -83,6 +100,59 @@ impl ExtractedWindow {
     }
 }
 
+/// The HDR encoding of the swap chain texture of a window.
+///
+/// There is no HDR10 (PQ) encoding: `wgpu` can't configure the color space of a surface, and
+/// only picks the extended linear sRGB color space for `Rgba16Float` surfaces on Vulkan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HdrOutputFormat {
+    /// Linear values with the sRGB primaries, where `1.0` is 80 nits.
+    ScRgb,
+}
+
+impl HdrOutputFormat {
+    /// The texture format of the swap chain for this encoding.
+    pub fn texture_format(&self) -> TextureFormat {
+        match self {
+            HdrOutputFormat::ScRgb => TextureFormat::Rgba16Float,
+        }
+    }
+
+    /// Returns `true` if a surface of the `backend` supporting `formats` displays this encoding
+    /// correctly.
+    ///
+    /// `wgpu` doesn't expose the color spaces of surfaces, so this relies on how backends pick
+    /// them. Only Vulkan surfaces list `Rgba16Float` solely when they support the extended linear
+    /// sRGB color space, which the platform only offers when the display can show HDR colors.
+    /// Other backends list it regardless of the display, or don't present it as scRGB.
+    fn is_supported(&self, formats: &[TextureFormat], backend: wgpu::Backend) -> bool {
+        match self {
+            HdrOutputFormat::ScRgb => {
+                backend == wgpu::Backend::Vulkan && formats.contains(&self.texture_format())
+            }
+        }
+    }
+}
+
+/// The active HDR output of a window, see [`ExtractedWindow::active_hdr_output`].
+///
+/// Brightnesses are rounded to whole nits so that they can be used in pipeline keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ActiveHdrOutput {
+    pub format: HdrOutputFormat,
+    /// The brightness of SDR white, in nits.
+    pub paper_white_nits: u32,
+    /// The maximum brightness of the display, in nits.
+    pub peak_nits: u32,
+}
+
+impl ActiveHdrOutput {
+    /// The ratio of the peak brightness to the brightness of SDR white, at least `1.0`.
+    pub fn headroom(&self) -> f32 {
+        (self.peak_nits as f32 / self.paper_white_nits as f32).max(1.0)
+    }
+}
+
 #[derive(Default, Resource)]
 pub struct ExtractedWindows {
     pub primary: Option<Entity>,
@@ -133,6 +203,9 @@ fn extract_windows(
             swap_chain_texture_format: None,
             present_mode_changed: false,
             alpha_mode: window.composite_alpha_mode,
+            hdr_output: window.hdr_output,
+            hdr_output_mode_changed: false,
+            hdr_output_format: None,
         });
 
         // NOTE: Drop the swap chain frame here
@@ -141,6 +214,9 @@ fn extract_windows(
             || new_height != extracted_window.physical_height;
         extracted_window.present_mode_changed =
             window.present_mode != extracted_window.present_mode;
+        extracted_window.hdr_output_mode_changed =
+            window.hdr_output.mode != extracted_window.hdr_output.mode;
+        extracted_window.hdr_output = window.hdr_output;
 
         if extracted_window.size_changed {
             debug!(
@@ -177,6 +253,9 @@ struct SurfaceData {
     // TODO: what lifetime should this be?
     surface: WgpuWrapper<wgpu::Surface<'static>>,
     configuration: SurfaceConfiguration,
+    /// The texture formats supported by the surface.
+    formats: Vec<TextureFormat>,
+    hdr_output_format: Option<HdrOutputFormat>,
 }
 
 #[derive(Resource, Default)]
@@ -276,6 +355,7 @@ pub fn prepare_windows(
             }
         }
         window.swap_chain_texture_format = Some(surface_data.configuration.format);
+        window.hdr_output_format = surface_data.hdr_output_format;
     }
 }
 
@@ -287,6 +367,7 @@ pub fn need_surface_configuration(
         if !window_surfaces.configured_windows.contains(&window.entity)
             || window.size_changed
             || window.present_mode_changed
+            || window.hdr_output_mode_changed
         {
             return true;
         }
@@ -300,6 +381,51 @@ pub fn need_surface_configuration(
 // has to wait for the cpu to finish to start on the next frame.
 const DEFAULT_DESIRED_MAXIMUM_FRAME_LATENCY: u32 = 2;
 
+/// Selects the format of a surface of the `backend` supporting `formats`, and its HDR encoding if
+/// any.
+///
+/// HDR formats are only selected if requested by `hdr_output` and supported by the surface and
+/// its display. Otherwise, sRGB formats are preferred, falling back to the first available format
+/// if no sRGB formats are available.
+fn select_surface_format(
+    formats: &[TextureFormat],
+    hdr_output: HdrOutputMode,
+    backend: wgpu::Backend,
+) -> (TextureFormat, Option<HdrOutputFormat>) {
+    let hdr_format = match hdr_output {
+        HdrOutputMode::Disabled => None,
+        HdrOutputMode::ScRgb => Some(HdrOutputFormat::ScRgb),
+    };
+    if let Some(hdr_format) = hdr_format {
+        if hdr_format.is_supported(formats, backend) {
+            return (hdr_format.texture_format(), Some(hdr_format));
+        }
+        warn!(
+            "HDR output was requested, but the window surface or its display doesn't support it."
+        );
+    }
+
+    let mut format = *formats.first().expect("No supported formats for surface");
+    for available_format in formats {
+        // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
+        if *available_format == TextureFormat::Rgba8UnormSrgb
+            || *available_format == TextureFormat::Bgra8UnormSrgb
+        {
+            format = *available_format;
+            break;
+        }
+    }
+    (format, None)
+}
+
+fn surface_view_formats(format: TextureFormat) -> Vec<TextureFormat> {
+    if format.add_srgb_suffix() != format {
+        vec![format.add_srgb_suffix()]
+    } else {
+        vec![]
+    }
+}
+
 /// Creates window surfaces.
 pub fn create_surfaces(
     // By accessing a NonSend resource, we tell the scheduler to put this system on the main thread,
@@ -332,19 +458,11 @@ pub fn create_surfaces(
                 };
                 let caps = surface.get_capabilities(&render_adapter);
                 let formats = caps.formats;
-                // For future HDR output support, we'll need to request a format that supports HDR,
-                // but as of wgpu 0.15 that is not yet supported.
-                // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
-                let mut format = *formats.first().expect("No supported formats for surface");
-                for available_format in formats {
-                    // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
-                    if available_format == TextureFormat::Rgba8UnormSrgb
-                        || available_format == TextureFormat::Bgra8UnormSrgb
-                    {
-                        format = available_format;
-                        break;
-                    }
-                }
+                let (format, hdr_output_format) = select_surface_format(
+                    &formats,
+                    window.hdr_output.mode,
+                    render_adapter.get_info().backend,
+                );
 
                 let configuration = SurfaceConfiguration {
                     format,
@@ -374,11 +492,7 @@ pub fn create_surfaces(
                         }
                         CompositeAlphaMode::Inherit => wgpu::CompositeAlphaMode::Inherit,
                     },
-                    view_formats: if !format.is_srgb() {
-                        vec![format.add_srgb_suffix()]
-                    } else {
-                        vec![]
-                    },
+                    view_formats: surface_view_formats(format),
                 };
 
                 render_device.configure_surface(&surface, &configuration);
@@ -386,10 +500,22 @@ pub fn create_surfaces(
                 SurfaceData {
                     surface: WgpuWrapper::new(surface),
                     configuration,
+                    formats,
+                    hdr_output_format,
                 }
             });
 
-        if window.size_changed || window.present_mode_changed {
+        if window.size_changed || window.present_mode_changed || window.hdr_output_mode_changed {
+            if window.hdr_output_mode_changed {
+                let (format, hdr_output_format) = select_surface_format(
+                    &data.formats,
+                    window.hdr_output.mode,
+                    render_adapter.get_info().backend,
+                );
+                data.configuration.format = format;
+                data.configuration.view_formats = surface_view_formats(format);
+                data.hdr_output_format = hdr_output_format;
+            }
             data.configuration.width = window.physical_width;
             data.configuration.height = window.physical_height;
             data.configuration.present_mode = match window.present_mode {
@@ -406,3 +532,60 @@ pub fn create_surfaces(
         window_surfaces.configured_windows.insert(window.entity);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{select_surface_format, HdrOutputFormat};
+    use bevy_window::HdrOutputMode;
+    use wgpu::{Backend, TextureFormat};
+
+    const SDR_FORMATS: [TextureFormat; 2] =
+        [TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb];
+    const HDR_FORMATS: [TextureFormat; 3] = [
+        TextureFormat::Bgra8Unorm,
+        TextureFormat::Bgra8UnormSrgb,
+        TextureFormat::Rgba16Float,
+    ];
+
+    #[test]
+    fn sdr_formats() {
+        assert_eq!(
+            select_surface_format(&HDR_FORMATS, HdrOutputMode::Disabled, Backend::Vulkan),
+            (TextureFormat::Bgra8UnormSrgb, None)
+        );
+        // Without sRGB formats, the first one is used.
+        assert_eq!(
+            select_surface_format(
+                &[TextureFormat::Rgba8Unorm, TextureFormat::Bgra8Unorm],
+                HdrOutputMode::Disabled,
+                Backend::Vulkan
+            ),
+            (TextureFormat::Rgba8Unorm, None)
+        );
+    }
+
+    #[test]
+    fn scrgb_output() {
+        assert_eq!(
+            select_surface_format(&HDR_FORMATS, HdrOutputMode::ScRgb, Backend::Vulkan),
+            (TextureFormat::Rgba16Float, Some(HdrOutputFormat::ScRgb))
+        );
+        // The surface or its display doesn't support the extended linear sRGB color space.
+        assert_eq!(
+            select_surface_format(&SDR_FORMATS, HdrOutputMode::ScRgb, Backend::Vulkan),
+            (TextureFormat::Bgra8UnormSrgb, None)
+        );
+        // These backends list `Rgba16Float` whatever the display supports.
+        for backend in [
+            Backend::Dx12,
+            Backend::Metal,
+            Backend::Gl,
+            Backend::BrowserWebGpu,
+        ] {
+            assert_eq!(
+                select_surface_format(&HDR_FORMATS, HdrOutputMode::ScRgb, backend),
+                (TextureFormat::Bgra8UnormSrgb, None)
+            );
+        }
+    }
+}
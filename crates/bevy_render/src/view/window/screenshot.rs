@@ -101,7 +101,7 @@ impl Screenshot {
     }
 }
 
-struct ScreenshotPreparedState {
+pub(crate) struct ScreenshotPreparedState {
     pub texture: Texture,
     pub buffer: Buffer,
     pub bind_group: BindGroup,
@@ -113,10 +113,10 @@ struct ScreenshotPreparedState {
 pub struct CapturedScreenshots(pub Arc<Mutex<Receiver<(Entity, Image)>>>);
 
 #[derive(Resource, Deref, DerefMut, Default)]
-struct RenderScreenshotTargets(EntityHashMap<NormalizedRenderTarget>);
+pub(crate) struct RenderScreenshotTargets(EntityHashMap<NormalizedRenderTarget>);
 
 #[derive(Resource, Deref, DerefMut, Default)]
-struct RenderScreenshotsPrepared(EntityHashMap<ScreenshotPreparedState>);
+pub(crate) struct RenderScreenshotsPrepared(EntityHashMap<ScreenshotPreparedState>);
 
 #[derive(Resource, Deref, DerefMut)]
 struct RenderScreenshotsSender(Sender<(Entity, Image)>);
@@ -251,7 +251,7 @@ fn extract_screenshots(
     system_state.apply(&mut main_world);
 }
 
-fn prepare_screenshots(
+pub(crate) fn prepare_screenshots(
     targets: Res<RenderScreenshotTargets>,
     mut prepared: ResMut<RenderScreenshotsPrepared>,
     window_surfaces: Res<WindowSurfaces>,
@@ -655,22 +655,15 @@ pub(crate) fn collect_screenshots(world: &mut World) {
             let mut result = Vec::from(&*data);
             drop(data);
 
-            if result.len() != ((width * height) as usize * pixel_size) {
-                // Our buffer has been padded because we needed to align to a multiple of 256.
-                // We remove this padding here
-                let initial_row_bytes = width as usize * pixel_size;
-                let buffered_row_bytes =
-                    gpu_readback::align_byte_size(width * pixel_size as u32) as usize;
-
-                let mut take_offset = buffered_row_bytes;
-                let mut place_offset = initial_row_bytes;
-                for _ in 1..height {
-                    result.copy_within(take_offset..take_offset + buffered_row_bytes, place_offset);
-                    take_offset += buffered_row_bytes;
-                    place_offset += initial_row_bytes;
-                }
-                result.truncate(initial_row_bytes * height as usize);
-            }
+            gpu_readback::remove_row_padding(
+                &mut result,
+                Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                pixel_size,
+            );
 
             if let Err(e) = sender.send((
                 entity,
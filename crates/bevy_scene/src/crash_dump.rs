@@ -0,0 +1,44 @@
+use crate::DynamicScene;
+use bevy_app::{App, OnCrash, Plugin};
+use bevy_ecs::{reflect::AppTypeRegistry, world::World};
+use log::{error, info};
+use std::path::{Path, PathBuf};
+
+/// Saves the world to a scene file when the app crashes, to help investigating the crash.
+///
+/// The scene is written in the [`OnCrash`] schedule, which requires the
+/// [`CrashHandlerPlugin`](bevy_app::CrashHandlerPlugin). Only the components and resources whose
+/// types are registered in the [`AppTypeRegistry`] are saved, and the scene can be loaded like
+/// any other scene.
+pub struct SceneCrashDumpPlugin {
+    /// The path of the scene file.
+    pub path: PathBuf,
+}
+
+impl Default for SceneCrashDumpPlugin {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("crash_dump.scn.ron"),
+        }
+    }
+}
+
+impl Plugin for SceneCrashDumpPlugin {
+    fn build(&self, app: &mut App) {
+        let path = self.path.clone();
+        app.add_systems(OnCrash, move |world: &mut World| dump_scene(world, &path));
+    }
+}
+
+fn dump_scene(world: &World, path: &Path) {
+    let scene = DynamicScene::from_world(world);
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let result = scene
+        .serialize(&registry)
+        .map_err(|err| err.to_string())
+        .and_then(|serialized| std::fs::write(path, serialized).map_err(|err| err.to_string()));
+    match result {
+        Ok(()) => info!("Saved the world to {}", path.display()),
+        Err(err) => error!("Failed to save the world to {}: {err}", path.display()),
+    }
+}
@@ -3,13 +3,16 @@ use bevy_asset::Asset;
 use bevy_ecs::reflect::ReflectResource;
 use bevy_ecs::{
     entity::{hash_map::EntityHashMap, Entity, SceneEntityMapper},
+    persistent_id::{PersistentEntityId, PersistentEntityIds},
     reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
     world::World,
 };
-use bevy_reflect::{PartialReflect, TypePath, TypeRegistry};
+use bevy_reflect::{
+    FromReflect, PartialReflect, TypeInfo, TypePath, TypeRegistration, TypeRegistry,
+};
 
 #[cfg(feature = "serialize")]
-use crate::serde::SceneSerializer;
+use crate::serde::{SceneSerializer, SceneSerializerOptions};
 #[cfg(feature = "serialize")]
 use serde::Serialize;
 
@@ -39,6 +42,20 @@ pub struct DynamicEntity {
     pub components: Vec<Box<dyn PartialReflect>>,
 }
 
+impl DynamicEntity {
+    /// Returns the component `T` of the entity, if it has one which can be converted to `T`.
+    pub fn get_component<T: FromReflect>(&self) -> Option<T> {
+        self.components
+            .iter()
+            .find(|component| {
+                component
+                    .get_represented_type_info()
+                    .is_some_and(TypeInfo::is::<T>)
+            })
+            .and_then(|component| T::from_reflect(component.as_partial_reflect()))
+    }
+}
+
 impl DynamicScene {
     /// Create a new dynamic scene from a given scene.
     pub fn from_scene(scene: &Scene) -> Self {
@@ -53,6 +70,24 @@ impl DynamicScene {
             .build()
     }
 
+    /// Maps the entities of the scene to the entities of `world` holding the same
+    /// [`PersistentEntityId`].
+    ///
+    /// Passing the map to [`write_to_world`](Self::write_to_world) updates these entities instead
+    /// of spawning new ones, and remaps the references to them, for example to load a saved game
+    /// into a running world. The map is empty if the world has no [`PersistentEntityIds`] index.
+    pub fn persistent_entity_map(&self, world: &World) -> EntityHashMap<Entity> {
+        let Some(persistent_ids) = world.get_resource::<PersistentEntityIds>() else {
+            return EntityHashMap::default();
+        };
+        let scene_ids = self
+            .entities
+            .iter()
+            .filter_map(|scene_entity| Some((scene_entity.entity, scene_entity.get_component()?)))
+            .collect::<Vec<(Entity, PersistentEntityId)>>();
+        persistent_ids.entity_map(scene_ids.iter().map(|(entity, id)| (*entity, id)))
+    }
+
     /// Write the resources, the dynamic entities, and their corresponding components to the given world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
@@ -85,71 +120,25 @@ impl DynamicScene {
 
             // Apply/ add each component to the given entity.
             for component in &scene_entity.components {
-                let mut component = component.clone_value();
-                let type_info = component.get_represented_type_info().ok_or_else(|| {
-                    SceneSpawnError::NoRepresentedType {
-                        type_path: component.reflect_type_path().to_string(),
-                    }
-                })?;
-                let registration = type_registry.get(type_info.type_id()).ok_or_else(|| {
-                    SceneSpawnError::UnregisteredButReflectedType {
-                        type_path: type_info.type_path().to_string(),
-                    }
-                })?;
-                let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        SceneSpawnError::UnregisteredComponent {
-                            type_path: type_info.type_path().to_string(),
-                        }
-                    })?;
-
-                // If this component references entities in the scene, update
-                // them to the entities in the world.
-                if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
-                    SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
-                        map_entities.map_entities(component.as_partial_reflect_mut(), mapper);
-                    });
-                }
-
-                reflect_component.apply_or_insert(
-                    &mut world.entity_mut(entity),
+                write_component(
+                    world,
+                    entity,
                     component.as_partial_reflect(),
+                    entity_map,
                     &type_registry,
-                );
+                )?;
             }
         }
 
         // Insert resources after all entities have been added to the world.
         // This ensures the entities are available for the resources to reference during mapping.
         for resource in &self.resources {
-            let mut resource = resource.clone_value();
-            let type_info = resource.get_represented_type_info().ok_or_else(|| {
-                SceneSpawnError::NoRepresentedType {
-                    type_path: resource.reflect_type_path().to_string(),
-                }
-            })?;
-            let registration = type_registry.get(type_info.type_id()).ok_or_else(|| {
-                SceneSpawnError::UnregisteredButReflectedType {
-                    type_path: type_info.type_path().to_string(),
-                }
-            })?;
-            let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
-                SceneSpawnError::UnregisteredResource {
-                    type_path: type_info.type_path().to_string(),
-                }
-            })?;
-
-            // If this component references entities in the scene, update
-            // them to the entities in the world.
-            if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
-                SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
-                    map_entities.map_entities(resource.as_partial_reflect_mut(), mapper);
-                });
-            }
-
-            // If the world already contains an instance of the given resource
-            // just apply the (possibly) new value, otherwise insert the resource
-            reflect_resource.apply_or_insert(world, resource.as_partial_reflect(), &type_registry);
+            write_resource(
+                world,
+                resource.as_partial_reflect(),
+                entity_map,
+                &type_registry,
+            )?;
         }
 
         Ok(())
@@ -169,6 +158,144 @@ impl DynamicScene {
         self.write_to_world_with(world, entity_map, &registry)
     }
 
+    /// Writes the changes from the `previous` version of this scene to the given world, which
+    /// holds an instance of the `previous` scene mapped by `entity_map`.
+    ///
+    /// Only the entities, components and resources that were added or changed since the
+    /// `previous` scene are written, and the ones that were removed from it are despawned or
+    /// removed. Components whose value didn't change in the scene keep their current value in the
+    /// world, and the entities and components that aren't part of the scene are left untouched.
+    /// Values which can't be compared with [`PartialReflect::reflect_partial_eq`] are always
+    /// written.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered
+    /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
+    /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::prelude::Resource) trait.
+    pub fn write_changes_to_world_with(
+        &self,
+        previous: &DynamicScene,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = type_registry.read();
+        let scene_entities = self.entity_index();
+        let previous_entities = previous.entity_index();
+
+        // Despawn the entities which were removed from the scene.
+        for previous_entity in &previous.entities {
+            if !scene_entities.contains_key(&previous_entity.entity) {
+                if let Some(entity) = entity_map.remove(&previous_entity.entity) {
+                    if let Ok(entity_mut) = world.get_entity_mut(entity) {
+                        entity_mut.despawn();
+                    }
+                }
+            }
+        }
+
+        // Spawn the entities which were added to the scene, so that they can be referenced.
+        for scene_entity in &self.entities {
+            entity_map
+                .entry(scene_entity.entity)
+                .or_insert_with(|| world.spawn_empty().id());
+        }
+
+        for scene_entity in &self.entities {
+            let entity = *entity_map
+                .get(&scene_entity.entity)
+                .expect("should have previously spawned an empty entity");
+            // The entity may have been despawned at runtime.
+            if world.get_entity(entity).is_err() {
+                continue;
+            }
+            let previous_components = previous_entities
+                .get(&scene_entity.entity)
+                .map(|previous_entity| previous_entity.components.as_slice())
+                .unwrap_or_default();
+
+            for previous_component in previous_components {
+                if find_value(&scene_entity.components, previous_component.as_ref()).is_none() {
+                    let registration = registration(previous_component.as_ref(), &type_registry)?;
+                    let reflect_component =
+                        registration.data::<ReflectComponent>().ok_or_else(|| {
+                            SceneSpawnError::UnregisteredComponent {
+                                type_path: registration.type_info().type_path().to_string(),
+                            }
+                        })?;
+                    reflect_component.remove(&mut world.entity_mut(entity));
+                }
+            }
+
+            for component in &scene_entity.components {
+                if !is_unchanged(component.as_ref(), previous_components) {
+                    write_component(
+                        world,
+                        entity,
+                        component.as_partial_reflect(),
+                        entity_map,
+                        &type_registry,
+                    )?;
+                }
+            }
+        }
+
+        for previous_resource in &previous.resources {
+            if find_value(&self.resources, previous_resource.as_ref()).is_none() {
+                let registration = registration(previous_resource.as_ref(), &type_registry)?;
+                let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+                    SceneSpawnError::UnregisteredResource {
+                        type_path: registration.type_info().type_path().to_string(),
+                    }
+                })?;
+                reflect_resource.remove(world);
+            }
+        }
+
+        for resource in &self.resources {
+            if !is_unchanged(resource.as_ref(), &previous.resources) {
+                write_resource(
+                    world,
+                    resource.as_partial_reflect(),
+                    entity_map,
+                    &type_registry,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a copy of this scene, holding clones of its components and resources.
+    pub fn clone_dynamic(&self) -> DynamicScene {
+        DynamicScene {
+            resources: self
+                .resources
+                .iter()
+                .map(|resource| resource.clone_value())
+                .collect(),
+            entities: self
+                .entities
+                .iter()
+                .map(|scene_entity| DynamicEntity {
+                    entity: scene_entity.entity,
+                    components: scene_entity
+                        .components
+                        .iter()
+                        .map(|component| component.clone_value())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the entities of the scene, keyed by their identifier.
+    fn entity_index(&self) -> EntityHashMap<&DynamicEntity> {
+        self.entities
+            .iter()
+            .map(|scene_entity| (scene_entity.entity, scene_entity))
+            .collect()
+    }
+
     // TODO: move to AssetSaver when it is implemented
     /// Serialize this dynamic scene into the official Bevy scene format (`.scn` / `.scn.ron`).
     ///
@@ -181,6 +308,122 @@ impl DynamicScene {
     pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into the official Bevy scene format, with the given
+    /// [`SceneSerializerOptions`].
+    ///
+    /// With [`SceneSerializerOptions::HUMAN_FRIENDLY`], entities are identified by their name
+    /// instead of their [`Entity`] id, and fields equal to their default value are omitted, which
+    /// makes the scene easier to edit by hand and to merge in version control.
+    #[cfg(feature = "serialize")]
+    pub fn serialize_with_options(
+        &self,
+        registry: &TypeRegistry,
+        options: SceneSerializerOptions,
+    ) -> Result<String, ron::Error> {
+        serialize_ron(SceneSerializer::new(self, registry).with_options(options))
+    }
+}
+
+/// Returns the registration of the type represented by the reflected `value`.
+fn registration<'a>(
+    value: &dyn PartialReflect,
+    type_registry: &'a TypeRegistry,
+) -> Result<&'a TypeRegistration, SceneSpawnError> {
+    let type_info =
+        value
+            .get_represented_type_info()
+            .ok_or_else(|| SceneSpawnError::NoRepresentedType {
+                type_path: value.reflect_type_path().to_string(),
+            })?;
+    type_registry.get(type_info.type_id()).ok_or_else(|| {
+        SceneSpawnError::UnregisteredButReflectedType {
+            type_path: type_info.type_path().to_string(),
+        }
+    })
+}
+
+/// Returns the value among `values` which represents the same type as `value`.
+fn find_value<'a>(
+    values: &'a [Box<dyn PartialReflect>],
+    value: &dyn PartialReflect,
+) -> Option<&'a dyn PartialReflect> {
+    let type_info = value.get_represented_type_info()?;
+    values.iter().map(AsRef::as_ref).find(|other| {
+        other
+            .get_represented_type_info()
+            .is_some_and(|other| other.type_id() == type_info.type_id())
+    })
+}
+
+/// Returns `true` if `previous_values` hold a value equal to `value`.
+fn is_unchanged(value: &dyn PartialReflect, previous_values: &[Box<dyn PartialReflect>]) -> bool {
+    find_value(previous_values, value)
+        .and_then(|previous| previous.reflect_partial_eq(value))
+        .unwrap_or(false)
+}
+
+/// Applies the reflected `component` to the `entity` of the world, or inserts it, after mapping
+/// the scene entities it references.
+fn write_component(
+    world: &mut World,
+    entity: Entity,
+    component: &dyn PartialReflect,
+    entity_map: &mut EntityHashMap<Entity>,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    let registration = registration(component, type_registry)?;
+    let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredComponent {
+            type_path: registration.type_info().type_path().to_string(),
+        }
+    })?;
+    let mut component = component.clone_value();
+
+    // If this component references entities in the scene, update
+    // them to the entities in the world.
+    if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+        SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
+            map_entities.map_entities(component.as_partial_reflect_mut(), mapper);
+        });
+    }
+
+    reflect_component.apply_or_insert(
+        &mut world.entity_mut(entity),
+        component.as_partial_reflect(),
+        type_registry,
+    );
+    Ok(())
+}
+
+/// Applies the reflected `resource` to the world, or inserts it, after mapping the scene entities
+/// it references.
+fn write_resource(
+    world: &mut World,
+    resource: &dyn PartialReflect,
+    entity_map: &mut EntityHashMap<Entity>,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    let registration = registration(resource, type_registry)?;
+    let reflect_resource = registration.data::<ReflectResource>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredResource {
+            type_path: registration.type_info().type_path().to_string(),
+        }
+    })?;
+    let mut resource = resource.clone_value();
+
+    // If this resource references entities in the scene, update
+    // them to the entities in the world.
+    if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+        SceneEntityMapper::world_scope(entity_map, world, |_, mapper| {
+            map_entities.map_entities(resource.as_partial_reflect_mut(), mapper);
+        });
+    }
+
+    // If the world already contains an instance of the given resource
+    // just apply the (possibly) new value, otherwise insert the resource
+    reflect_resource.apply_or_insert(world, resource.as_partial_reflect(), type_registry);
+    Ok(())
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -204,6 +447,7 @@ mod tests {
             VisitEntitiesMut,
         },
         hierarchy::ChildOf,
+        persistent_id::{PersistentEntityId, PersistentEntityIds},
         reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
         resource::Resource,
         world::World,
@@ -332,6 +576,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn persistent_entity_map_updates_existing_entities() {
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Health>();
+            registry.register::<PersistentEntityId>();
+        }
+
+        let mut source_world = World::new();
+        source_world.insert_resource(type_registry.clone());
+        let id = PersistentEntityId::new();
+        source_world.spawn((id, Health(5)));
+        source_world.spawn(Health(10));
+        let scene = DynamicScene::from_world(&source_world);
+
+        let mut world = World::new();
+        world.insert_resource(type_registry);
+        world.init_index::<PersistentEntityId>();
+        let existing = world.spawn((id, Health(100))).id();
+
+        let mut entity_map = scene.persistent_entity_map(&world);
+        assert_eq!(entity_map.len(), 1);
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        // The entity with the same id is updated, the other one is spawned.
+        assert_eq!(world.get::<Health>(existing), Some(&Health(5)));
+        assert_eq!(world.query::<&Health>().iter(&world).count(), 2);
+        assert_eq!(
+            world.resource::<PersistentEntityIds>().get_all(&id),
+            &[existing]
+        );
+    }
+
     // Regression test for https://github.com/bevyengine/bevy/issues/14300
     // Fails before the fix in https://github.com/bevyengine/bevy/pull/15405
     #[test]
@@ -14,6 +14,8 @@
 extern crate alloc;
 
 mod components;
+#[cfg(all(feature = "serialize", not(target_arch = "wasm32")))]
+mod crash_dump;
 mod dynamic_scene;
 mod dynamic_scene_builder;
 mod scene;
@@ -27,8 +29,10 @@ pub mod serde;
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy scenes.
 pub use bevy_asset::ron;
 
-use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::{persistent_id::PersistentEntityId, schedule::IntoSystemConfigs};
 pub use components::*;
+#[cfg(all(feature = "serialize", not(target_arch = "wasm32")))]
+pub use crash_dump::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
 pub use scene::*;
@@ -63,8 +67,12 @@ impl Plugin for ScenePlugin {
             .init_resource::<SceneSpawner>()
             .register_type::<SceneRoot>()
             .register_type::<DynamicSceneRoot>()
+            .register_type::<PersistentEntityId>()
             .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
 
+        // Used by `DynamicScene::persistent_entity_map` to find the entities of saved scenes.
+        app.world_mut().init_index::<PersistentEntityId>();
+
         // Register component hooks for DynamicSceneRoot
         app.world_mut()
             .register_component_hooks::<DynamicSceneRoot>()
@@ -39,6 +39,25 @@ pub struct InstanceInfo {
     pub entity_map: EntityHashMap<Entity>,
 }
 
+/// How the [`SceneSpawner`] updates the instances of a [`DynamicScene`] when the scene is
+/// modified, for example when its file is hot-reloaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SceneReloadMode {
+    /// Writes every entity, component and resource of the modified scene to its instances,
+    /// overwriting the changes made to them at runtime.
+    #[default]
+    Overwrite,
+    /// Only writes the entities, components and resources that changed in the scene, and
+    /// despawns or removes the ones that were removed from it.
+    ///
+    /// Components whose value didn't change in the scene keep their runtime value, and entities
+    /// and components added at runtime are kept. This requires the [`SceneSpawner`] to keep a copy
+    /// of each spawned dynamic scene, to compare it with the modified scene.
+    ///
+    /// See [`DynamicScene::write_changes_to_world_with`].
+    Incremental,
+}
+
 /// Unique id identifying a scene instance.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Reflect)]
 #[reflect(Debug, PartialEq, Hash)]
@@ -69,6 +88,9 @@ impl InstanceId {
 /// - [`spawn_as_child`](Self::spawn_as_child)
 /// - [`despawn`](Self::despawn)
 /// - [`despawn_instance`](Self::despawn_instance)
+///
+/// Instances of dynamic scenes are updated when their scene is modified, as set by the
+/// [`SceneReloadMode`].
 #[derive(Default, Resource)]
 pub struct SceneSpawner {
     pub(crate) spawned_dynamic_scenes: HashMap<AssetId<DynamicScene>, HashSet<InstanceId>>,
@@ -79,6 +101,9 @@ pub struct SceneSpawner {
     scenes_to_despawn: Vec<AssetId<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    reload_mode: SceneReloadMode,
+    /// The spawned dynamic scenes, as they were before being modified.
+    scene_snapshots: HashMap<AssetId<DynamicScene>, DynamicScene>,
 }
 
 /// Errors that can occur when spawning a scene.
@@ -136,6 +161,22 @@ pub enum SceneSpawnError {
 }
 
 impl SceneSpawner {
+    /// Returns how the instances of dynamic scenes are updated when their scene is modified.
+    pub fn reload_mode(&self) -> SceneReloadMode {
+        self.reload_mode
+    }
+
+    /// Sets how the instances of dynamic scenes are updated when their scene is modified.
+    ///
+    /// Scenes spawned before switching to [`SceneReloadMode::Incremental`] are overwritten on
+    /// their next modification, and updated incrementally afterwards.
+    pub fn set_reload_mode(&mut self, reload_mode: SceneReloadMode) {
+        self.reload_mode = reload_mode;
+        if reload_mode == SceneReloadMode::Overwrite {
+            self.scene_snapshots.clear();
+        }
+    }
+
     /// Schedule the spawn of a new instance of the provided dynamic scene.
     pub fn spawn_dynamic(&mut self, id: impl Into<Handle<DynamicScene>>) -> InstanceId {
         let instance_id = InstanceId::new();
@@ -189,7 +230,9 @@ impl SceneSpawner {
         world: &mut World,
         id: impl Into<AssetId<DynamicScene>>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&id.into()) {
+        let id = id.into();
+        self.scene_snapshots.remove(&id);
+        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&id) {
             for instance_id in instance_ids {
                 self.despawn_instance_sync(world, &instance_id);
             }
@@ -222,6 +265,7 @@ impl SceneSpawner {
             .insert(instance_id, InstanceInfo { entity_map });
         let spawned = self.spawned_dynamic_scenes.entry(id).or_default();
         spawned.insert(instance_id);
+        self.snapshot_scene(world, id);
         Ok(instance_id)
     }
 
@@ -281,17 +325,65 @@ impl SceneSpawner {
         scene_ids: &[AssetId<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
         for id in scene_ids {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(id) {
-                for instance_id in spawned_instances {
-                    if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+            let Some(spawned_instances) = self.spawned_dynamic_scenes.get(id) else {
+                continue;
+            };
+            let previous = self.scene_snapshots.remove(id);
+            for instance_id in spawned_instances {
+                if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                    if let Some(previous) = &previous {
+                        Self::update_dynamic_internal(
+                            world,
+                            *id,
+                            previous,
+                            &mut instance_info.entity_map,
+                        )?;
+                    } else {
                         Self::spawn_dynamic_internal(world, *id, &mut instance_info.entity_map)?;
                     }
                 }
             }
+            self.snapshot_scene(world, *id);
         }
         Ok(())
     }
 
+    fn update_dynamic_internal(
+        world: &mut World,
+        id: AssetId<DynamicScene>,
+        previous: &DynamicScene,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) -> Result<(), SceneSpawnError> {
+        world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            let scene = scenes
+                .get(id)
+                .ok_or(SceneSpawnError::NonExistentScene { id })?;
+
+            scene.write_changes_to_world_with(
+                previous,
+                world,
+                entity_map,
+                &world.resource::<AppTypeRegistry>().clone(),
+            )
+        })
+    }
+
+    /// Keeps a copy of the dynamic scene to compare it with its next version, if the scene is
+    /// updated incrementally and isn't copied yet.
+    ///
+    /// An existing copy is kept, since the scene may have been modified since, and the instances
+    /// spawned before still need to be updated.
+    fn snapshot_scene(&mut self, world: &World, id: AssetId<DynamicScene>) {
+        if self.reload_mode != SceneReloadMode::Incremental
+            || self.scene_snapshots.contains_key(&id)
+        {
+            return;
+        }
+        if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(id) {
+            self.scene_snapshots.insert(id, scene.clone_dynamic());
+        }
+    }
+
     /// Immediately despawns all scenes scheduled for despawn by despawning their instances.
     pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
         let scenes_to_despawn = core::mem::take(&mut self.scenes_to_despawn);
@@ -327,6 +419,7 @@ impl SceneSpawner {
                         .entry(handle.id())
                         .or_insert_with(HashSet::default);
                     spawned.insert(instance_id);
+                    self.snapshot_scene(world, handle.id());
 
                     // Scenes with parents need more setup before they are ready.
                     // See `set_scene_instance_parent_sync()`.
@@ -652,6 +745,67 @@ mod tests {
         assert_eq!(old_a, new_a);
     }
 
+    #[test]
+    fn incremental_reload() {
+        #[derive(Reflect, Component, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct B(usize);
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<A>();
+        atr.write().register::<B>();
+        let mut scene_world = World::default();
+        scene_world.insert_resource(atr.clone());
+        let kept = scene_world.spawn((A(1), B(1))).id();
+        let removed = scene_world.spawn(A(2)).id();
+
+        let mut world = World::default();
+        world.insert_resource(atr);
+        world.insert_resource(Assets::<DynamicScene>::default());
+        let scene = DynamicScene::from_world(&scene_world);
+        let scene_id = world.resource_mut::<Assets<DynamicScene>>().add(scene);
+
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner.set_reload_mode(SceneReloadMode::Incremental);
+        let instance_id = scene_spawner
+            .spawn_dynamic_sync(&mut world, &scene_id)
+            .unwrap();
+        let entity_map = &scene_spawner.spawned_instances[&instance_id].entity_map;
+        let (kept_entity, removed_entity) = (entity_map[&kept], entity_map[&removed]);
+
+        // Change the instance at runtime.
+        world.get_mut::<A>(kept_entity).unwrap().0 = 10;
+        world.entity_mut(kept_entity).insert(ComponentF);
+
+        // Change a component in the scene, remove an entity and add another one.
+        scene_world.entity_mut(kept).insert(B(2));
+        scene_world.despawn(removed);
+        let added = scene_world.spawn(A(3)).id();
+        let scene = DynamicScene::from_world(&scene_world);
+        world
+            .resource_mut::<Assets<DynamicScene>>()
+            .insert(&scene_id, scene);
+        scene_spawner
+            .update_spawned_scenes(&mut world, &[scene_id.id()])
+            .unwrap();
+
+        // Only the changes of the scene are written.
+        assert_eq!(world.get::<A>(kept_entity), Some(&A(10)));
+        assert_eq!(world.get::<B>(kept_entity), Some(&B(2)));
+        assert!(world.entity(kept_entity).contains::<ComponentF>());
+        assert!(world.get_entity(removed_entity).is_err());
+        let entity_map = &scene_spawner.spawned_instances[&instance_id].entity_map;
+        assert!(!entity_map.contains_key(&removed));
+        assert_eq!(world.get::<A>(entity_map[&added]), Some(&A(3)));
+
+        // Overwriting the instance writes every component again.
+        scene_spawner.set_reload_mode(SceneReloadMode::Overwrite);
+        scene_spawner
+            .update_spawned_scenes(&mut world, &[scene_id.id()])
+            .unwrap();
+        assert_eq!(world.get::<A>(kept_entity), Some(&A(1)));
+    }
+
     #[derive(Component, Reflect, Default)]
     #[reflect(Component)]
     struct ComponentF;
@@ -1,18 +1,26 @@
 //! `serde` serialization and deserialization implementation for Bevy scenes.
 
 use crate::{DynamicEntity, DynamicScene};
-use bevy_ecs::entity::Entity;
-use bevy_platform_support::collections::HashSet;
+use alloc::borrow::Cow;
+use bevy_ecs::{
+    entity::{hash_map::EntityHashMap, Entity},
+    name::Name,
+    persistent_id::PersistentEntityId,
+};
+use bevy_platform_support::collections::{HashMap, HashSet};
 use bevy_reflect::{
     serde::{
-        ReflectDeserializer, TypeRegistrationDeserializer, TypedReflectDeserializer,
+        ReflectDeserializer, ReflectDeserializerProcessor, ReflectSerializerProcessor,
+        SerializationData, TypeRegistrationDeserializer, TypedReflectDeserializer,
         TypedReflectSerializer,
     },
-    PartialReflect, ReflectFromReflect, TypeRegistry,
+    std_traits::ReflectDefault,
+    PartialReflect, ReflectFromReflect, ReflectRef, ReflectSerialize, TypeInfo, TypeRegistration,
+    TypeRegistry,
 };
-use core::fmt::Formatter;
+use core::{any::TypeId, cell::RefCell, fmt::Formatter};
 use serde::{
-    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Unexpected, Visitor},
     ser::{SerializeMap, SerializeStruct},
     Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -29,6 +37,36 @@ pub const ENTITY_STRUCT: &str = "Entity";
 /// Name of the serialized component field in an entity struct.
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
+/// Options changing how a [`SceneSerializer`] serializes a scene, to make it easier to read, edit
+/// and merge in version control.
+///
+/// Both options rely on the format being self-describing, like RON or JSON: scenes serialized
+/// with them can't be deserialized from non-self-describing formats like `postcard` or `bincode`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SceneSerializerOptions {
+    /// Identify entities by a stable key instead of their [`Entity`] id.
+    ///
+    /// Entities are keyed by their [`PersistentEntityId`] if they have one, otherwise by their [`Name`],
+    /// and are sorted by key. References to entities in components and resources are serialized
+    /// as the key of the entity, and resolved back to the deserialized entity on load. See
+    /// [`SceneEntityKeys`] for the details.
+    pub stable_entity_ids: bool,
+    /// Omit the fields of struct components and resources which are equal to the same field in
+    /// the [`Default`] value of the type.
+    ///
+    /// This only applies to types registering [`ReflectDefault`]. The omitted fields are filled
+    /// from the default value when the scene is deserialized.
+    pub omit_defaults: bool,
+}
+
+impl SceneSerializerOptions {
+    /// The options producing the most concise and stable scenes.
+    pub const HUMAN_FRIENDLY: Self = Self {
+        stable_entity_ids: true,
+        omit_defaults: true,
+    };
+}
+
 /// Serializer for a [`DynamicScene`].
 ///
 /// Helper object defining Bevy's serialize format for a [`DynamicScene`] and implementing
@@ -59,6 +97,8 @@ pub struct SceneSerializer<'a> {
     pub scene: &'a DynamicScene,
     /// The type registry containing the types present in the scene.
     pub registry: &'a TypeRegistry,
+    /// The options changing how the scene is serialized.
+    pub options: SceneSerializerOptions,
 }
 
 impl<'a> SceneSerializer<'a> {
@@ -69,7 +109,17 @@ impl<'a> SceneSerializer<'a> {
     ///
     /// [`World`]: bevy_ecs::world::World
     pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistry) -> Self {
-        SceneSerializer { scene, registry }
+        SceneSerializer {
+            scene,
+            registry,
+            options: SceneSerializerOptions::default(),
+        }
+    }
+
+    /// Sets the [`SceneSerializerOptions`] used to serialize the scene.
+    pub fn with_options(mut self, options: SceneSerializerOptions) -> Self {
+        self.options = options;
+        self
     }
 }
 
@@ -78,12 +128,19 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: Serializer,
     {
+        let entity_keys = self
+            .options
+            .stable_entity_ids
+            .then(|| SceneEntityKeys::new(&self.scene.entities));
+
         let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
         state.serialize_field(
             SCENE_RESOURCES,
             &SceneMapSerializer {
                 entries: &self.scene.resources,
                 registry: self.registry,
+                entity_keys: entity_keys.as_ref(),
+                omit_defaults: self.options.omit_defaults,
             },
         )?;
         state.serialize_field(
@@ -91,18 +148,73 @@ impl<'a> Serialize for SceneSerializer<'a> {
             &EntitiesSerializer {
                 entities: &self.scene.entities,
                 registry: self.registry,
+                entity_keys: entity_keys.as_ref(),
+                omit_defaults: self.options.omit_defaults,
             },
         )?;
         state.end()
     }
 }
 
+/// The stable keys of the entities of a scene serialized with
+/// [`SceneSerializerOptions::stable_entity_ids`].
+///
+/// Each entity is keyed by the [`PersistentEntityId`] it has, or by its [`Name`]. Entities with
+/// neither, and entities outside of the scene, are keyed by their [`Entity`] id, like `"12v1"`.
+/// When several entities would have the same key, a suffix is added to the key of the later
+/// ones, like `"Door#2"`.
+#[derive(Debug, Default, Clone)]
+pub struct SceneEntityKeys(EntityHashMap<String>);
+
+impl SceneEntityKeys {
+    /// Computes the keys of the `entities` of a scene.
+    pub fn new(entities: &[DynamicEntity]) -> Self {
+        let mut used = <HashSet<String>>::default();
+        let mut keys = EntityHashMap::default();
+        for entity in entities {
+            let base = entity
+                .get_component::<PersistentEntityId>()
+                .map(|id| id.0.to_string())
+                .or_else(|| {
+                    entity
+                        .get_component::<Name>()
+                        .map(|name| name.as_str().to_string())
+                })
+                .unwrap_or_else(|| entity.entity.to_string());
+
+            let mut key = base.clone();
+            let mut suffix = 2;
+            while used.contains(&key) {
+                key = format!("{base}#{suffix}");
+                suffix += 1;
+            }
+            used.insert(key.clone());
+            keys.insert(entity.entity, key);
+        }
+        Self(keys)
+    }
+
+    /// Returns the key of `entity`.
+    pub fn get(&self, entity: Entity) -> Cow<'_, str> {
+        match self.0.get(&entity) {
+            Some(key) => Cow::Borrowed(key),
+            None => Cow::Owned(entity.to_string()),
+        }
+    }
+}
+
 /// Handles serialization of multiple entities as a map of entity id to serialized entity.
 pub struct EntitiesSerializer<'a> {
     /// The entities to serialize.
     pub entities: &'a [DynamicEntity],
     /// Type registry in which the component types used by the entities are registered.
     pub registry: &'a TypeRegistry,
+    /// The keys identifying the entities, if they are serialized with
+    /// [`SceneSerializerOptions::stable_entity_ids`].
+    pub entity_keys: Option<&'a SceneEntityKeys>,
+    /// Whether to omit the fields equal to their default value, see
+    /// [`SceneSerializerOptions::omit_defaults`].
+    pub omit_defaults: bool,
 }
 
 impl<'a> Serialize for EntitiesSerializer<'a> {
@@ -111,12 +223,36 @@ impl<'a> Serialize for EntitiesSerializer<'a> {
         S: Serializer,
     {
         let mut state = serializer.serialize_map(Some(self.entities.len()))?;
-        for entity in self.entities {
+        let Some(entity_keys) = self.entity_keys else {
+            for entity in self.entities {
+                state.serialize_entry(
+                    &entity.entity,
+                    &EntitySerializer {
+                        entity,
+                        registry: self.registry,
+                        entity_keys: None,
+                        omit_defaults: self.omit_defaults,
+                    },
+                )?;
+            }
+            return state.end();
+        };
+
+        // Sorting by key keeps the order of the entities stable between saves.
+        let mut entities = self
+            .entities
+            .iter()
+            .map(|entity| (entity_keys.get(entity.entity), entity))
+            .collect::<Vec<_>>();
+        entities.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (key, entity) in entities {
             state.serialize_entry(
-                &entity.entity,
+                key.as_ref(),
                 &EntitySerializer {
                     entity,
                     registry: self.registry,
+                    entity_keys: Some(entity_keys),
+                    omit_defaults: self.omit_defaults,
                 },
             )?;
         }
@@ -130,6 +266,12 @@ pub struct EntitySerializer<'a> {
     pub entity: &'a DynamicEntity,
     /// Type registry in which the component types used by the entity are registered.
     pub registry: &'a TypeRegistry,
+    /// The keys identifying the entities, if they are serialized with
+    /// [`SceneSerializerOptions::stable_entity_ids`].
+    pub entity_keys: Option<&'a SceneEntityKeys>,
+    /// Whether to omit the fields equal to their default value, see
+    /// [`SceneSerializerOptions::omit_defaults`].
+    pub omit_defaults: bool,
 }
 
 impl<'a> Serialize for EntitySerializer<'a> {
@@ -143,6 +285,8 @@ impl<'a> Serialize for EntitySerializer<'a> {
             &SceneMapSerializer {
                 entries: &self.entity.components,
                 registry: self.registry,
+                entity_keys: self.entity_keys,
+                omit_defaults: self.omit_defaults,
             },
         )?;
         state.end()
@@ -161,6 +305,12 @@ pub struct SceneMapSerializer<'a> {
     pub entries: &'a [Box<dyn PartialReflect>],
     /// Type registry in which the types used in `entries` are registered.
     pub registry: &'a TypeRegistry,
+    /// The keys identifying the entities, if they are serialized with
+    /// [`SceneSerializerOptions::stable_entity_ids`].
+    pub entity_keys: Option<&'a SceneEntityKeys>,
+    /// Whether to omit the fields equal to their default value, see
+    /// [`SceneSerializerOptions::omit_defaults`].
+    pub omit_defaults: bool,
 }
 
 impl<'a> Serialize for SceneMapSerializer<'a> {
@@ -184,10 +334,119 @@ impl<'a> Serialize for SceneMapSerializer<'a> {
             entries
         };
 
+        let processor = EntityKeyProcessor {
+            entity_keys: self.entity_keys,
+        };
         for (type_path, partial_reflect) in sorted_entries {
             state.serialize_entry(
                 type_path,
-                &TypedReflectSerializer::new(partial_reflect, self.registry),
+                &SceneValueSerializer {
+                    value: partial_reflect,
+                    registry: self.registry,
+                    processor: &processor,
+                    omit_defaults: self.omit_defaults,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Serializes the references to entities as their key in [`SceneEntityKeys`].
+struct EntityKeyProcessor<'a> {
+    entity_keys: Option<&'a SceneEntityKeys>,
+}
+
+impl ReflectSerializerProcessor for EntityKeyProcessor<'_> {
+    fn try_serialize<S>(
+        &self,
+        value: &dyn PartialReflect,
+        _registry: &TypeRegistry,
+        serializer: S,
+    ) -> Result<Result<S::Ok, S>, S::Error>
+    where
+        S: Serializer,
+    {
+        match (self.entity_keys, value.try_downcast_ref::<Entity>()) {
+            (Some(entity_keys), Some(entity)) => {
+                serializer.serialize_str(&entity_keys.get(*entity)).map(Ok)
+            }
+            _ => Ok(Err(serializer)),
+        }
+    }
+}
+
+/// Serializes a component or resource, omitting the fields equal to their default value if
+/// `omit_defaults` is set.
+struct SceneValueSerializer<'a> {
+    value: &'a dyn PartialReflect,
+    registry: &'a TypeRegistry,
+    processor: &'a EntityKeyProcessor<'a>,
+    omit_defaults: bool,
+}
+
+impl SceneValueSerializer<'_> {
+    /// Returns the type name of the value and its serialized fields which differ from its default
+    /// value, if the value is a struct with a registered [`ReflectDefault`] and without a custom
+    /// [`ReflectSerialize`] implementation.
+    fn non_default_fields(
+        &self,
+    ) -> Option<(&'static str, Vec<(&'static str, &dyn PartialReflect)>)> {
+        let (ReflectRef::Struct(value), Some(TypeInfo::Struct(info))) = (
+            self.value.reflect_ref(),
+            self.value.get_represented_type_info(),
+        ) else {
+            return None;
+        };
+        let registration = self
+            .registry
+            .get(info.type_id())
+            .filter(|registration| !registration.contains::<ReflectSerialize>())?;
+        let default = registration.data::<ReflectDefault>()?.default();
+        let ReflectRef::Struct(default) = default.reflect_ref() else {
+            return None;
+        };
+        let serialization_data = registration.data::<SerializationData>();
+
+        let fields = (0..info.field_len())
+            .filter(|&index| !serialization_data.is_some_and(|data| data.is_field_skipped(index)))
+            .filter_map(|index| {
+                let name = info.field_at(index)?.name();
+                let field = value.field(name)?;
+                let is_default = default
+                    .field(name)
+                    .and_then(|default| field.reflect_partial_eq(default))
+                    .unwrap_or(false);
+                (!is_default).then_some((name, field))
+            })
+            .collect();
+        Some((info.type_path_table().ident()?, fields))
+    }
+}
+
+impl Serialize for SceneValueSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Some((name, fields)) = self
+            .omit_defaults
+            .then(|| self.non_default_fields())
+            .flatten()
+        else {
+            return TypedReflectSerializer::with_processor(
+                self.value,
+                self.registry,
+                self.processor,
+            )
+            .serialize(serializer);
+        };
+
+        let mut state = serializer.serialize_struct(name, fields.len())?;
+        for (field_name, field) in fields {
+            state.serialize_field(
+                field_name,
+                &TypedReflectSerializer::with_processor(field, self.registry, self.processor),
             )?;
         }
         state.end()
@@ -207,6 +466,159 @@ enum EntityField {
     Components,
 }
 
+/// Resolves the identifiers of the entities of a serialized scene to the [`Entity`] ids of the
+/// deserialized [`DynamicScene`].
+///
+/// Entities are identified by their [`Entity`] id, or by their key in scenes serialized with
+/// [`SceneSerializerOptions::stable_entity_ids`]. Each key is resolved to a new [`Entity`] id the
+/// first time it is encountered, so that components can reference entities which are
+/// deserialized later in the scene.
+#[derive(Debug, Default)]
+pub struct SceneEntityIds {
+    state: RefCell<SceneEntityIdsState>,
+}
+
+#[derive(Debug, Default)]
+struct SceneEntityIdsState {
+    keys: HashMap<String, Entity>,
+    key_indices: HashSet<u32>,
+    id_indices: HashSet<u32>,
+    next_index: u32,
+}
+
+impl SceneEntityIds {
+    /// Returns the entity identified by `key`.
+    pub fn resolve_key(&self, key: &str) -> Entity {
+        let mut state = self.state.borrow_mut();
+        if let Some(&entity) = state.keys.get(key) {
+            return entity;
+        }
+
+        let state = &mut *state;
+        while state.id_indices.contains(&state.next_index) {
+            state.next_index += 1;
+        }
+        let entity = Entity::from_raw(state.next_index);
+        state.next_index += 1;
+        state.key_indices.insert(entity.index());
+        state.keys.insert(key.to_string(), entity);
+        entity
+    }
+
+    /// Returns the entity identified by the [`Entity`] id `entity`.
+    ///
+    /// Returns `None` if the index of `entity` is already used by an entity identified by a key.
+    pub fn resolve_id(&self, entity: Entity) -> Option<Entity> {
+        let mut state = self.state.borrow_mut();
+        if state.key_indices.contains(&entity.index()) {
+            return None;
+        }
+        state.id_indices.insert(entity.index());
+        Some(entity)
+    }
+}
+
+/// Deserializes an entity identifier, which is either an [`Entity`] id or a key.
+///
+/// Keys can only be deserialized from self-describing formats.
+struct EntityIdDeserializer<'a> {
+    entity_ids: &'a SceneEntityIds,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityIdDeserializer<'a> {
+    type Value = Entity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(EntityIdVisitor {
+                entity_ids: self.entity_ids,
+            })
+        } else {
+            let entity = Entity::deserialize(deserializer)?;
+            EntityIdVisitor {
+                entity_ids: self.entity_ids,
+            }
+            .resolve_id(entity)
+        }
+    }
+}
+
+struct EntityIdVisitor<'a> {
+    entity_ids: &'a SceneEntityIds,
+}
+
+impl<'a> EntityIdVisitor<'a> {
+    fn resolve_id<E: Error>(&self, entity: Entity) -> Result<Entity, E> {
+        self.entity_ids.resolve_id(entity).ok_or_else(|| {
+            Error::custom(format_args!(
+                "entity id `{entity}` is already used by an entity identified by a key"
+            ))
+        })
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for EntityIdVisitor<'a> {
+    type Value = Entity;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entity id or key")
+    }
+
+    fn visit_u64<E>(self, bits: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let entity = Entity::try_from_bits(bits)
+            .map_err(|_| Error::invalid_value(Unexpected::Unsigned(bits), &self))?;
+        self.resolve_id(entity)
+    }
+
+    fn visit_i64<E>(self, bits: i64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let bits = u64::try_from(bits)
+            .map_err(|_| Error::invalid_value(Unexpected::Signed(bits), &self))?;
+        self.visit_u64(bits)
+    }
+
+    fn visit_str<E>(self, key: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(self.entity_ids.resolve_key(key))
+    }
+}
+
+/// Deserializes the references to entities with [`SceneEntityIds`].
+struct EntityIdProcessor<'a> {
+    entity_ids: &'a SceneEntityIds,
+}
+
+impl ReflectDeserializerProcessor for EntityIdProcessor<'_> {
+    fn try_deserialize<'de, D>(
+        &mut self,
+        registration: &TypeRegistration,
+        _registry: &TypeRegistry,
+        deserializer: D,
+    ) -> Result<Result<Box<dyn PartialReflect>, D>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if registration.type_id() != TypeId::of::<Entity>() {
+            return Ok(Err(deserializer));
+        }
+        let entity = EntityIdDeserializer {
+            entity_ids: self.entity_ids,
+        }
+        .deserialize(deserializer)?;
+        Ok(Ok(Box::new(entity)))
+    }
+}
+
 /// Handles scene deserialization.
 pub struct SceneDeserializer<'a> {
     /// Type registry in which the components and resources types used in the scene to deserialize are registered.
@@ -220,11 +632,13 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: Deserializer<'de>,
     {
+        let entity_ids = SceneEntityIds::default();
         deserializer.deserialize_struct(
             SCENE_STRUCT,
             &[SCENE_RESOURCES, SCENE_ENTITIES],
             SceneVisitor {
                 type_registry: self.type_registry,
+                entity_ids: &entity_ids,
             },
         )
     }
@@ -232,6 +646,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
 
 struct SceneVisitor<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub entity_ids: &'a SceneEntityIds,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
@@ -248,12 +663,14 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
         let resources = seq
             .next_element_seed(SceneMapDeserializer {
                 registry: self.type_registry,
+                entity_ids: self.entity_ids,
             })?
             .ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?;
 
         let entities = seq
             .next_element_seed(SceneEntitiesDeserializer {
                 type_registry: self.type_registry,
+                entity_ids: self.entity_ids,
             })?
             .ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
 
@@ -277,6 +694,7 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
                     }
                     resources = Some(map.next_value_seed(SceneMapDeserializer {
                         registry: self.type_registry,
+                        entity_ids: self.entity_ids,
                     })?);
                 }
                 SceneField::Entities => {
@@ -285,6 +703,7 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
                     }
                     entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
                         type_registry: self.type_registry,
+                        entity_ids: self.entity_ids,
                     })?);
                 }
             }
@@ -304,6 +723,8 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
 pub struct SceneEntitiesDeserializer<'a> {
     /// Type registry in which the component types used by the entities to deserialize are registered.
     pub type_registry: &'a TypeRegistry,
+    /// Resolves the identifiers of the entities to deserialize.
+    pub entity_ids: &'a SceneEntityIds,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
@@ -315,12 +736,14 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
     {
         deserializer.deserialize_map(SceneEntitiesVisitor {
             type_registry: self.type_registry,
+            entity_ids: self.entity_ids,
         })
     }
 }
 
 struct SceneEntitiesVisitor<'a> {
     pub type_registry: &'a TypeRegistry,
+    pub entity_ids: &'a SceneEntityIds,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
@@ -335,10 +758,13 @@ impl<'a, 'de> Visitor<'de> for SceneEntitiesVisitor<'a> {
         A: MapAccess<'de>,
     {
         let mut entities = Vec::new();
-        while let Some(entity) = map.next_key::<Entity>()? {
+        while let Some(entity) = map.next_key_seed(EntityIdDeserializer {
+            entity_ids: self.entity_ids,
+        })? {
             let entity = map.next_value_seed(SceneEntityDeserializer {
                 entity,
                 type_registry: self.type_registry,
+                entity_ids: self.entity_ids,
             })?;
             entities.push(entity);
         }
@@ -353,6 +779,8 @@ pub struct SceneEntityDeserializer<'a> {
     pub entity: Entity,
     /// Type registry in which the component types used by the entity to deserialize are registered.
     pub type_registry: &'a TypeRegistry,
+    /// Resolves the identifiers of the entities referenced by the components to deserialize.
+    pub entity_ids: &'a SceneEntityIds,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
@@ -368,6 +796,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
             SceneEntityVisitor {
                 entity: self.entity,
                 registry: self.type_registry,
+                entity_ids: self.entity_ids,
             },
         )
     }
@@ -376,6 +805,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
 struct SceneEntityVisitor<'a> {
     pub entity: Entity,
     pub registry: &'a TypeRegistry,
+    pub entity_ids: &'a SceneEntityIds,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
@@ -392,6 +822,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
         let components = seq
             .next_element_seed(SceneMapDeserializer {
                 registry: self.registry,
+                entity_ids: self.entity_ids,
             })?
             .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
 
@@ -415,6 +846,7 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 
                     components = Some(map.next_value_seed(SceneMapDeserializer {
                         registry: self.registry,
+                        entity_ids: self.entity_ids,
                     })?);
                 }
             }
@@ -431,9 +863,15 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
 }
 
 /// Handles deserialization of a sequence of values with unique types.
+///
+/// The fields omitted from struct values, like the ones serialized with
+/// [`SceneSerializerOptions::omit_defaults`], are filled from their [`Default`] value if the type
+/// registers [`ReflectDefault`].
 pub struct SceneMapDeserializer<'a> {
     /// Type registry in which the types of the values to deserialize are registered.
     pub registry: &'a TypeRegistry,
+    /// Resolves the identifiers of the entities referenced by the values to deserialize.
+    pub entity_ids: &'a SceneEntityIds,
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for SceneMapDeserializer<'a> {
@@ -445,12 +883,14 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneMapDeserializer<'a> {
     {
         deserializer.deserialize_map(SceneMapVisitor {
             registry: self.registry,
+            entity_ids: self.entity_ids,
         })
     }
 }
 
 struct SceneMapVisitor<'a> {
     pub registry: &'a TypeRegistry,
+    pub entity_ids: &'a SceneEntityIds,
 }
 
 impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
@@ -464,8 +904,14 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     where
         A: SeqAccess<'de>,
     {
+        let mut processor = EntityIdProcessor {
+            entity_ids: self.entity_ids,
+        };
         let mut dynamic_properties = Vec::new();
-        while let Some(entity) = seq.next_element_seed(ReflectDeserializer::new(self.registry))? {
+        while let Some(entity) = seq.next_element_seed(ReflectDeserializer::with_processor(
+            self.registry,
+            &mut processor,
+        ))? {
             dynamic_properties.push(entity);
         }
 
@@ -476,6 +922,9 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     where
         A: MapAccess<'de>,
     {
+        let mut processor = EntityIdProcessor {
+            entity_ids: self.entity_ids,
+        };
         let mut added = <HashSet<_>>::default();
         let mut entries = Vec::new();
         while let Some(registration) =
@@ -488,16 +937,23 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
                 )));
             }
 
-            let value =
-                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?;
+            let value = map.next_value_seed(TypedReflectDeserializer::with_processor(
+                registration,
+                self.registry,
+                &mut processor,
+            ))?;
 
-            // Attempt to convert using FromReflect.
-            let value = self
-                .registry
-                .get(registration.type_id())
-                .and_then(|tr| tr.data::<ReflectFromReflect>())
+            // Attempt to convert using FromReflect, or by applying the value to the default value
+            // of the type when some of its fields were omitted.
+            let value = registration
+                .data::<ReflectFromReflect>()
                 .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
                 .map(PartialReflect::into_partial_reflect)
+                .or_else(|| {
+                    let mut default = registration.data::<ReflectDefault>()?.default();
+                    default.try_apply(value.as_partial_reflect()).ok()?;
+                    Some(default.into_partial_reflect())
+                })
                 .unwrap_or(value);
 
             entries.push(value);
@@ -511,17 +967,18 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
 mod tests {
     use crate::{
         ron,
-        serde::{SceneDeserializer, SceneSerializer},
+        serde::{SceneDeserializer, SceneSerializer, SceneSerializerOptions},
         DynamicScene, DynamicSceneBuilder,
     };
     use bevy_ecs::{
         entity::{hash_map::EntityHashMap, Entity, VisitEntities, VisitEntitiesMut},
+        name::Name,
         prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
         query::{With, Without},
         reflect::{AppTypeRegistry, ReflectMapEntities},
         world::FromWorld,
     };
-    use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+    use bevy_reflect::{std_traits::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
     use bincode::Options;
     use serde::{de::DeserializeSeed, Deserialize, Serialize};
     use std::io::BufReader;
@@ -594,6 +1051,24 @@ mod tests {
         }
     }
 
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, Default, PartialEq)]
+    struct MySettings {
+        enabled: bool,
+        speed: f32,
+        label: String,
+    }
+
+    impl Default for MySettings {
+        fn default() -> Self {
+            Self {
+                enabled: true,
+                speed: 1.0,
+                label: "default".to_string(),
+            }
+        }
+    }
+
     fn create_world() -> World {
         let mut world = World::new();
         let registry = AppTypeRegistry::default();
@@ -612,6 +1087,8 @@ mod tests {
             registry.register::<MyEntityRef>();
             registry.register::<Entity>();
             registry.register::<MyResource>();
+            registry.register::<MySettings>();
+            registry.register::<Name>();
         }
         world.insert_resource(registry);
         world
@@ -727,6 +1204,89 @@ mod tests {
         assert_eq!(1, dst_world.query::<&Baz>().iter(&dst_world).count());
     }
 
+    #[test]
+    fn should_serialize_with_stable_ids_and_without_defaults() {
+        let mut world = create_world();
+
+        let door = world
+            .spawn((
+                Name::new("Door"),
+                MySettings {
+                    speed: 2.0,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        let lever = world.spawn((Name::new("Lever"), MyEntityRef(door))).id();
+        let other_door = world.spawn((Name::new("Door"), Foo(123))).id();
+
+        world.insert_resource(MyResource { foo: 123 });
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities([door, lever, other_door].into_iter())
+            .extract_resources()
+            .build();
+
+        let expected = r#"(
+  resources: {
+    "bevy_scene::serde::tests::MyResource": (
+      foo: 123,
+    ),
+  },
+  entities: {
+    "Door": (
+      components: {
+        "bevy_ecs::name::Name": "Door",
+        "bevy_scene::serde::tests::MySettings": (
+          speed: 2.0,
+        ),
+      },
+    ),
+    "Door#2": (
+      components: {
+        "bevy_ecs::name::Name": "Door",
+        "bevy_scene::serde::tests::Foo": (123),
+      },
+    ),
+    "Lever": (
+      components: {
+        "bevy_ecs::name::Name": "Lever",
+        "bevy_scene::serde::tests::MyEntityRef": ("Door"),
+      },
+    ),
+  },
+)"#;
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let output = scene
+            .serialize_with_options(&registry, SceneSerializerOptions::HUMAN_FRIENDLY)
+            .unwrap();
+        assert_eq!(expected, output);
+
+        let mut deserializer = ron::de::Deserializer::from_str(&output).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry,
+        };
+        let deserialized_scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let mut map = EntityHashMap::default();
+        let mut dst_world = create_world();
+        deserialized_scene
+            .write_to_world(&mut dst_world, &mut map)
+            .unwrap();
+
+        // The reference is resolved by key, and the omitted fields are filled from the default.
+        let &MyEntityRef(target) = dst_world.query::<&MyEntityRef>().single(&dst_world);
+        assert_eq!(
+            dst_world.get::<MySettings>(target),
+            Some(&MySettings {
+                speed: 2.0,
+                ..Default::default()
+            })
+        );
+        assert_eq!(dst_world.get::<Name>(target).unwrap().as_str(), "Door");
+        assert_eq!(3, dst_world.query::<&Name>().iter(&dst_world).count());
+    }
+
     fn roundtrip_ron(world: &World) -> (DynamicScene, DynamicScene) {
         let scene = DynamicScene::from_world(world);
         let registry = world.resource::<AppTypeRegistry>().read();
@@ -15,6 +15,8 @@ mod mesh2d;
 mod picking_backend;
 mod render;
 mod sprite;
+mod sprite_animation;
+mod sprite_material;
 mod texture_slice;
 
 /// The sprite prelude.
@@ -24,8 +26,10 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         sprite::{Sprite, SpriteImageMode},
+        sprite_animation::{SpriteAnimationClip, SpriteAnimationPlayer, SpriteAnimationRepeat},
+        sprite_material::{MaterialSprite, SpriteMaterial},
         texture_slice::{BorderRect, SliceScaleMode, TextureSlice, TextureSlicer},
-        ColorMaterial, MeshMaterial2d, ScalingMode,
+        ColorMaterial, MeshMaterial2d, ScalingMode, SpriteMaterialPlugin,
     };
 }
 
@@ -34,14 +38,17 @@ pub use mesh2d::*;
 pub use picking_backend::*;
 pub use render::*;
 pub use sprite::*;
+pub use sprite_animation::*;
+pub use sprite_material::*;
 pub use texture_slice::*;
 
 use bevy_app::prelude::*;
-use bevy_asset::{load_internal_asset, weak_handle, AssetEvents, Assets, Handle};
+use bevy_asset::{load_internal_asset, weak_handle, AssetApp, AssetEvents, Assets, Handle};
 use bevy_core_pipeline::core_2d::Transparent2d;
 use bevy_ecs::prelude::*;
 use bevy_image::{prelude::*, TextureAtlasPlugin};
 use bevy_render::{
+    diagnostic::Batching2dTracker,
     mesh::{Mesh, Mesh2d, MeshAabb},
     primitives::Aabb,
     render_phase::AddRenderCommand,
@@ -78,12 +85,17 @@ pub const SPRITE_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("ed996613-54c0-49bd-81be-1c2d1a0d03c2");
 pub const SPRITE_VIEW_BINDINGS_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("43947210-8df6-459a-8f2a-12f350d174cc");
+pub const SPRITE_BINDINGS_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("9b5755f6-8e54-4c1e-95c6-f5e3d2ba288f");
+pub const SPRITE_VERTEX_OUTPUT_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("e7e05ebc-3cfb-46cc-a982-e96c92e89884");
 
 /// System set for sprite rendering.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum SpriteSystem {
     ExtractSprites,
     ComputeSlices,
+    AnimateSprites,
 }
 
 impl Plugin for SpritePlugin {
@@ -100,6 +112,18 @@ impl Plugin for SpritePlugin {
             "render/sprite_view_bindings.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            SPRITE_BINDINGS_SHADER_HANDLE,
+            "render/sprite_bindings.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SPRITE_VERTEX_OUTPUT_SHADER_HANDLE,
+            "render/sprite_vertex_output.wgsl",
+            Shader::from_wgsl
+        );
 
         if !app.is_plugin_added::<TextureAtlasPlugin>() {
             app.add_plugins(TextureAtlasPlugin);
@@ -110,10 +134,17 @@ impl Plugin for SpritePlugin {
             .register_type::<TextureSlicer>()
             .register_type::<Anchor>()
             .register_type::<Mesh2d>()
+            .init_asset::<SpriteAnimationClip>()
+            .register_asset_reflect::<SpriteAnimationClip>()
+            .register_type::<SpriteAnimationPlayer>()
             .add_plugins((Mesh2dRenderPlugin, ColorMaterialPlugin))
             .add_systems(
                 PostUpdate,
                 (
+                    animate_sprites
+                        .in_set(SpriteSystem::AnimateSprites)
+                        .before(VisibilitySystems::CalculateBounds)
+                        .before(SpriteSystem::ComputeSlices),
                     calculate_bounds_2d.in_set(VisibilitySystems::CalculateBounds),
                     (
                         compute_slices_on_asset_event.before(AssetEvents),
@@ -123,6 +154,9 @@ impl Plugin for SpritePlugin {
                 ),
             );
 
+        #[cfg(feature = "serialize")]
+        app.init_asset_loader::<SpriteAnimationClipLoader>();
+
         #[cfg(feature = "bevy_sprite_picking_backend")]
         if self.add_picking {
             app.add_plugins(SpritePickingPlugin);
@@ -151,6 +185,9 @@ impl Plugin for SpritePlugin {
                             .ambiguous_with(queue_material2d_meshes::<ColorMaterial>),
                         prepare_sprite_image_bind_groups.in_set(RenderSet::PrepareBindGroups),
                         prepare_sprite_view_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                        record_batching_2d_stats
+                            .in_set(RenderSet::Cleanup)
+                            .run_if(resource_exists::<Batching2dTracker>),
                     ),
                 );
         };
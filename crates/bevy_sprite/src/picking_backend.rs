@@ -1,6 +1,7 @@
 //! A [`bevy_picking`] backend for sprites. Works for simple sprites and sprite atlases. Works for
-//! sprites with arbitrary transforms. Picking is done based on sprite bounds, not visible pixels.
-//! This means a partially transparent sprite is pickable even in its transparent areas.
+//! sprites with arbitrary transforms. By default, picking ignores the pixels of a sprite whose
+//! alpha is below a threshold, see [`SpritePickingMode`]. Sprites can also be picked with a custom
+//! shape instead, with the [`SpritePickingShape`] component.
 
 use crate::Sprite;
 use bevy_app::prelude::*;
@@ -32,6 +33,63 @@ pub enum SpritePickingMode {
     AlphaThreshold(f32),
 }
 
+/// The shape used to pick a sprite, overriding [`SpritePickingSettings::picking_mode`].
+///
+/// Shapes are given in the local space of the sprite's entity, where the origin is the
+/// entity's translation, like the sprite itself. The sprite is picked when the pointer is in the
+/// shape, even outside of the sprite's bounds.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug)]
+pub enum SpritePickingShape {
+    /// Picks the sprite with this [`SpritePickingMode`] instead of the one of the settings, for
+    /// example to use a different alpha threshold.
+    Mode(SpritePickingMode),
+    /// Picks the sprite within a circle.
+    Circle {
+        /// The center of the circle.
+        center: Vec2,
+        /// The radius of the circle.
+        radius: f32,
+    },
+    /// Picks the sprite within a polygon, given by its vertices in order. The polygon may be
+    /// concave, and is closed between its last and first vertices.
+    Polygon(Vec<Vec2>),
+}
+
+impl SpritePickingShape {
+    /// Returns `true` if `point` is within the circle or polygon of this shape.
+    ///
+    /// Returns `None` for [`SpritePickingShape::Mode`], which depends on the sprite.
+    pub fn contains(&self, point: Vec2) -> Option<bool> {
+        match self {
+            SpritePickingShape::Mode(_) => None,
+            SpritePickingShape::Circle { center, radius } => {
+                Some(point.distance_squared(*center) <= radius * radius)
+            }
+            SpritePickingShape::Polygon(vertices) => Some(polygon_contains(vertices, point)),
+        }
+    }
+}
+
+/// Returns `true` if `point` is in the polygon with `vertices`, with the even-odd rule.
+fn polygon_contains(vertices: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    let Some(mut previous) = vertices.last().copied() else {
+        return false;
+    };
+    for &vertex in vertices {
+        if (vertex.y > point.y) != (previous.y > point.y)
+            && point.x
+                < vertex.x
+                    + (previous.x - vertex.x) * (point.y - vertex.y) / (previous.y - vertex.y)
+        {
+            inside = !inside;
+        }
+        previous = vertex;
+    }
+    inside
+}
+
 /// Runtime settings for the [`SpritePickingPlugin`].
 #[derive(Resource, Reflect)]
 #[reflect(Resource, Default)]
@@ -67,6 +125,7 @@ impl Plugin for SpritePickingPlugin {
                 SpritePickingCamera,
                 SpritePickingMode,
                 SpritePickingSettings,
+                SpritePickingShape,
             )>()
             .add_systems(PreUpdate, sprite_picking.in_set(PickSet::Backend));
     }
@@ -90,16 +149,17 @@ fn sprite_picking(
         &Sprite,
         &GlobalTransform,
         Option<&Pickable>,
+        Option<&SpritePickingShape>,
         &ViewVisibility,
     )>,
     mut output: EventWriter<PointerHits>,
 ) {
     let mut sorted_sprites: Vec<_> = sprite_query
         .iter()
-        .filter_map(|(entity, sprite, transform, pickable, vis)| {
+        .filter_map(|(entity, sprite, transform, pickable, shape, vis)| {
             let marker_requirement = !settings.require_markers || pickable.is_some();
             if !transform.affine().is_nan() && vis.get() && marker_requirement {
-                Some((entity, sprite, transform, pickable))
+                Some((entity, sprite, transform, pickable, shape))
             } else {
                 None
             }
@@ -107,7 +167,7 @@ fn sprite_picking(
         .collect();
 
     // radsort is a stable radix sort that performed better than `slice::sort_by_key`
-    radsort::sort_by_key(&mut sorted_sprites, |(_, _, transform, _)| {
+    radsort::sort_by_key(&mut sorted_sprites, |(_, _, transform, _, _)| {
         -transform.translation().z
     });
 
@@ -149,7 +209,7 @@ fn sprite_picking(
         let picks: Vec<(Entity, HitData)> = sorted_sprites
             .iter()
             .copied()
-            .filter_map(|(entity, sprite, sprite_transform, pickable)| {
+            .filter_map(|(entity, sprite, sprite_transform, pickable, shape)| {
                 if blocked {
                     return None;
                 }
@@ -180,38 +240,22 @@ fn sprite_picking(
                     .lerp(cursor_end_sprite, lerp_factor)
                     .xy();
 
-                let Ok(cursor_pixel_space) = sprite.compute_pixel_space_point(
-                    cursor_pos_sprite,
-                    &images,
-                    &texture_atlas_layout,
-                ) else {
-                    return None;
-                };
-
-                // Since the pixel space coordinate is `Ok`, we know the cursor is in the bounds of
-                // the sprite.
-
-                let cursor_in_valid_pixels_of_sprite = 'valid_pixel: {
-                    match settings.picking_mode {
-                        SpritePickingMode::AlphaThreshold(cutoff) => {
-                            let Some(image) = images.get(&sprite.image) else {
-                                // [`Sprite::from_color`] returns a defaulted handle.
-                                // This handle doesn't return a valid image, so returning false here would make picking "color sprites" impossible
-                                break 'valid_pixel true;
-                            };
-                            // grab pixel and check alpha
-                            let Ok(color) = image.get_color_at(
-                                cursor_pixel_space.x as u32,
-                                cursor_pixel_space.y as u32,
-                            ) else {
-                                // We don't know how to interpret the pixel.
-                                break 'valid_pixel false;
-                            };
-                            // Check the alpha is above the cutoff.
-                            color.alpha() > cutoff
-                        }
-                        SpritePickingMode::BoundingBox => true,
-                    }
+                let cursor_in_valid_pixels_of_sprite = match shape {
+                    Some(SpritePickingShape::Mode(mode)) => sprite_contains_point(
+                        sprite,
+                        cursor_pos_sprite,
+                        *mode,
+                        &images,
+                        &texture_atlas_layout,
+                    ),
+                    Some(shape) => shape.contains(cursor_pos_sprite).unwrap_or_default(),
+                    None => sprite_contains_point(
+                        sprite,
+                        cursor_pos_sprite,
+                        settings.picking_mode,
+                        &images,
+                        &texture_atlas_layout,
+                    ),
                 };
 
                 blocked = cursor_in_valid_pixels_of_sprite
@@ -244,3 +288,75 @@ fn sprite_picking(
         output.send(PointerHits::new(*pointer, picks, order));
     }
 }
+
+/// Returns `true` if the point `cursor_pos_sprite`, in the sprite's local frame, is in the bounds
+/// of `sprite`, on a pixel that is valid for `mode`.
+fn sprite_contains_point(
+    sprite: &Sprite,
+    cursor_pos_sprite: Vec2,
+    mode: SpritePickingMode,
+    images: &Assets<Image>,
+    texture_atlas_layout: &Assets<TextureAtlasLayout>,
+) -> bool {
+    let Ok(cursor_pixel_space) =
+        sprite.compute_pixel_space_point(cursor_pos_sprite, images, texture_atlas_layout)
+    else {
+        return false;
+    };
+
+    // Since the pixel space coordinate is `Ok`, we know the cursor is in the bounds of
+    // the sprite.
+    match mode {
+        SpritePickingMode::AlphaThreshold(cutoff) => {
+            let Some(image) = images.get(&sprite.image) else {
+                // [`Sprite::from_color`] returns a defaulted handle.
+                // This handle doesn't return a valid image, so returning false here would make picking "color sprites" impossible
+                return true;
+            };
+            // grab pixel and check alpha
+            let Ok(color) =
+                image.get_color_at(cursor_pixel_space.x as u32, cursor_pixel_space.y as u32)
+            else {
+                // We don't know how to interpret the pixel.
+                return false;
+            };
+            // Check the alpha is above the cutoff.
+            color.alpha() > cutoff
+        }
+        SpritePickingMode::BoundingBox => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picking_shape_contains() {
+        let circle = SpritePickingShape::Circle {
+            center: Vec2::new(1.0, 0.0),
+            radius: 2.0,
+        };
+        assert_eq!(circle.contains(Vec2::new(2.5, 0.0)), Some(true));
+        assert_eq!(circle.contains(Vec2::new(-1.5, 0.0)), Some(false));
+
+        // An L shape, which is concave.
+        let polygon = SpritePickingShape::Polygon(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ]);
+        assert_eq!(polygon.contains(Vec2::new(0.5, 1.5)), Some(true));
+        assert_eq!(polygon.contains(Vec2::new(1.5, 0.5)), Some(true));
+        assert_eq!(polygon.contains(Vec2::new(1.5, 1.5)), Some(false));
+        assert_eq!(polygon.contains(Vec2::new(-0.5, 0.5)), Some(false));
+
+        assert_eq!(
+            SpritePickingShape::Mode(SpritePickingMode::BoundingBox).contains(Vec2::ZERO),
+            None
+        );
+    }
+}
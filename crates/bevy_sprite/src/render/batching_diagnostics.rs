@@ -0,0 +1,111 @@
+use bevy_asset::AssetId;
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_ecs::system::Res;
+use bevy_image::Image;
+use bevy_render::{
+    diagnostic::{BatchBreakCause, Batching2dStats, Batching2dTracker},
+    render_phase::{DrawFunctionId, PhaseItem, ViewSortedRenderPhases},
+};
+
+use crate::{ExtractedSprites, Material2dBindGroupId, RenderMesh2dInstances};
+
+/// What a draw call of the transparent 2D phase draws, as far as batching is concerned.
+#[derive(Clone, Copy)]
+enum DrawKind {
+    Sprite(AssetId<Image>),
+    Mesh(Material2dBindGroupId),
+    Other(DrawFunctionId),
+}
+
+/// Submits the [`Batching2dStats`] of the transparent 2D phases of all views to the
+/// [`Batching2dTracker`].
+///
+/// This walks the phase items the same way they are rendered, and compares each draw call with
+/// the previous one to find out why they couldn't be batched.
+pub fn record_batching_2d_stats(
+    tracker: Res<Batching2dTracker>,
+    phases: Res<ViewSortedRenderPhases<Transparent2d>>,
+    extracted_sprites: Res<ExtractedSprites>,
+    mesh_instances: Res<RenderMesh2dInstances>,
+) {
+    let mut stats = Batching2dStats::default();
+    for phase in phases.values() {
+        let mut previous = None;
+        let mut index = 0;
+        while index < phase.items.len() {
+            let item = &phase.items[index];
+            let batch_len = item.batch_range().len();
+            if batch_len == 0 {
+                index += 1;
+                continue;
+            }
+            index += batch_len;
+
+            let draw = if let Some(sprite) = extracted_sprites.sprites.get(&item.entity) {
+                DrawKind::Sprite(sprite.image_handle_id)
+            } else if let Some(mesh_instance) = mesh_instances.get(&item.main_entity()) {
+                DrawKind::Mesh(mesh_instance.material_bind_group_id)
+            } else {
+                DrawKind::Other(item.draw_function())
+            };
+            record_draw(&mut stats, previous, draw);
+            previous = Some(draw);
+        }
+    }
+    tracker.submit(stats);
+}
+
+fn record_draw(stats: &mut Batching2dStats, previous: Option<DrawKind>, draw: DrawKind) {
+    let Some(previous) = previous else {
+        stats.record_draw(None);
+        return;
+    };
+    let cause = match (previous, draw) {
+        (DrawKind::Sprite(from), DrawKind::Sprite(to)) if from != to => {
+            stats.record_texture_switch(from, to);
+            return;
+        }
+        (DrawKind::Mesh(from), DrawKind::Mesh(to)) if from != to => BatchBreakCause::MaterialChange,
+        (DrawKind::Other(from), DrawKind::Other(to)) if from != to => {
+            BatchBreakCause::ZOrderInterleave
+        }
+        (DrawKind::Sprite(_), DrawKind::Sprite(_))
+        | (DrawKind::Mesh(_), DrawKind::Mesh(_))
+        | (DrawKind::Other(_), DrawKind::Other(_)) => {
+            // The same texture or material was used: the meshes differ, batching was disabled
+            // for them, or they were separated by an item that didn't draw.
+            BatchBreakCause::Other
+        }
+        _ => BatchBreakCause::ZOrderInterleave,
+    };
+    stats.record_draw(Some(cause));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::uuid::Uuid;
+
+    #[test]
+    fn batch_breaks_are_classified() {
+        let texture = |n| AssetId::<Image>::Uuid {
+            uuid: Uuid::from_u128(n),
+        };
+        let sprite = |n| DrawKind::Sprite(texture(n));
+        let mesh = DrawKind::Mesh(Material2dBindGroupId(None));
+
+        let mut stats = Batching2dStats::default();
+        let draws = [sprite(1), sprite(2), mesh, mesh, sprite(2), sprite(1)];
+        let mut previous = None;
+        for draw in draws {
+            record_draw(&mut stats, previous, draw);
+            previous = Some(draw);
+        }
+
+        assert_eq!(stats.draw_calls, 6);
+        assert_eq!(stats.texture_switches, 2);
+        assert_eq!(stats.z_order_interleaves, 2);
+        assert_eq!(stats.other_breaks, 1);
+        assert_eq!(stats.texture_pairs.get(&(texture(1), texture(2))), Some(&2));
+    }
+}
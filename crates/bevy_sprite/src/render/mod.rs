@@ -1,5 +1,11 @@
 use core::ops::Range;
 
+mod batching_diagnostics;
+mod sprite_material_pipeline;
+
+pub use batching_diagnostics::*;
+pub use sprite_material_pipeline::*;
+
 use crate::{ComputedTextureSlices, ScalingMode, Sprite, SPRITE_SHADER_HANDLE};
 use bevy_asset::{AssetEvent, AssetId, Assets};
 use bevy_color::{ColorToComponents, LinearRgba};
@@ -44,7 +50,7 @@ use bevy_transform::components::GlobalTransform;
 use bytemuck::{Pod, Zeroable};
 use fixedbitset::FixedBitSet;
 
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct SpritePipeline {
     view_layout: BindGroupLayout,
     material_layout: BindGroupLayout,
@@ -179,6 +185,42 @@ impl SpritePipelineKey {
             SpritePipelineKey::NONE
         }
     }
+
+    /// Returns the key of the sprite pipeline for a view.
+    pub fn from_view(
+        view: &ExtractedView,
+        msaa: &Msaa,
+        tonemapping: Option<&Tonemapping>,
+        dither: Option<&DebandDither>,
+    ) -> Self {
+        let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
+        let mut view_key = SpritePipelineKey::from_hdr(view.hdr) | msaa_key;
+
+        if !view.hdr {
+            if let Some(tonemapping) = tonemapping {
+                view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
+                view_key |= match tonemapping {
+                    Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
+                    Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
+                    Tonemapping::ReinhardLuminance => {
+                        SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
+                    }
+                    Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
+                    Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
+                    Tonemapping::SomewhatBoringDisplayTransform => {
+                        SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
+                    }
+                    Tonemapping::TonyMcMapface => SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE,
+                    Tonemapping::BlenderFilmic => SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC,
+                };
+            }
+            if let Some(DebandDither::Enabled) = dither {
+                view_key |= SpritePipelineKey::DEBAND_DITHER;
+            }
+        }
+
+        view_key
+    }
 }
 
 impl SpecializedRenderPipeline for SpritePipeline {
@@ -462,6 +504,70 @@ impl SpriteInstance {
             i_uv_offset_scale: uv_offset_scale.to_array(),
         }
     }
+
+    /// Computes the instance data of a sprite drawn with an image of size `image_size`.
+    fn new(extracted_sprite: &ExtractedSprite, image_size: Vec2) -> Self {
+        // By default, the size of the quad is the size of the texture
+        let mut quad_size = image_size;
+
+        // Texture size is the size of the image
+        let mut texture_size = image_size;
+
+        // If a rect is specified, adjust UVs and the size of the quad
+        let mut uv_offset_scale = if let Some(rect) = extracted_sprite.rect {
+            let rect_size = rect.size();
+            quad_size = rect_size;
+            // Update texture size to the rect size
+            // It will help scale properly only portion of the image
+            texture_size = rect_size;
+            Vec4::new(
+                rect.min.x / image_size.x,
+                rect.max.y / image_size.y,
+                rect_size.x / image_size.x,
+                -rect_size.y / image_size.y,
+            )
+        } else {
+            Vec4::new(0.0, 1.0, 1.0, -1.0)
+        };
+
+        // Override the size if a custom one is specified
+        if let Some(custom_size) = extracted_sprite.custom_size {
+            quad_size = custom_size;
+        }
+
+        // Used for translation of the quad if `TextureScale::Fit...` is specified.
+        let mut quad_translation = Vec2::ZERO;
+
+        // Scales the texture based on the `texture_scale` field.
+        if let Some(scaling_mode) = extracted_sprite.scaling_mode {
+            apply_scaling(
+                scaling_mode,
+                texture_size,
+                &mut quad_size,
+                &mut quad_translation,
+                &mut uv_offset_scale,
+            );
+        }
+
+        if extracted_sprite.flip_x {
+            uv_offset_scale.x += uv_offset_scale.z;
+            uv_offset_scale.z *= -1.0;
+        }
+        if extracted_sprite.flip_y {
+            uv_offset_scale.y += uv_offset_scale.w;
+            uv_offset_scale.w *= -1.0;
+        }
+
+        let transform = extracted_sprite.transform.affine()
+            * Affine3A::from_scale_rotation_translation(
+                quad_size.extend(1.0),
+                Quat::IDENTITY,
+                ((quad_size + quad_translation) * (-extracted_sprite.anchor - Vec2::splat(0.5)))
+                    .extend(0.0),
+            );
+
+        Self::from(&transform, &extracted_sprite.color, &uv_offset_scale)
+    }
 }
 
 #[derive(Resource)]
@@ -498,6 +604,25 @@ pub struct ImageBindGroups {
     values: HashMap<AssetId<Image>, BindGroup>,
 }
 
+impl ImageBindGroups {
+    /// Creates the bind group of an image, if it doesn't exist yet.
+    fn insert(
+        &mut self,
+        image_handle_id: AssetId<Image>,
+        gpu_image: &GpuImage,
+        render_device: &RenderDevice,
+        sprite_pipeline: &SpritePipeline,
+    ) {
+        self.values.entry(image_handle_id).or_insert_with(|| {
+            render_device.create_bind_group(
+                "sprite_material_bind_group",
+                &sprite_pipeline.material_layout,
+                &BindGroupEntries::sequential((&gpu_image.texture_view, &gpu_image.sampler)),
+            )
+        });
+    }
+}
+
 pub fn queue_sprites(
     mut view_entities: Local<FixedBitSet>,
     draw_functions: Res<DrawFunctions<Transparent2d>>,
@@ -522,32 +647,7 @@ pub fn queue_sprites(
             continue;
         };
 
-        let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
-        let mut view_key = SpritePipelineKey::from_hdr(view.hdr) | msaa_key;
-
-        if !view.hdr {
-            if let Some(tonemapping) = tonemapping {
-                view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
-                view_key |= match tonemapping {
-                    Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
-                    Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
-                    Tonemapping::ReinhardLuminance => {
-                        SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
-                    }
-                    Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
-                    Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
-                    Tonemapping::SomewhatBoringDisplayTransform => {
-                        SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
-                    }
-                    Tonemapping::TonyMcMapface => SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE,
-                    Tonemapping::BlenderFilmic => SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC,
-                };
-            }
-            if let Some(DebandDither::Enabled) = dither {
-                view_key |= SpritePipelineKey::DEBAND_DITHER;
-            }
-        }
-
+        let view_key = SpritePipelineKey::from_view(view, msaa, tonemapping, dither);
         let pipeline = pipelines.specialize(&pipeline_cache, &sprite_pipeline, view_key);
 
         view_entities.clear();
@@ -679,19 +779,12 @@ pub fn prepare_sprite_image_bind_groups(
 
                 batch_image_size = gpu_image.size_2d().as_vec2();
                 batch_image_handle = extracted_sprite.image_handle_id;
-                image_bind_groups
-                    .values
-                    .entry(batch_image_handle)
-                    .or_insert_with(|| {
-                        render_device.create_bind_group(
-                            "sprite_material_bind_group",
-                            &sprite_pipeline.material_layout,
-                            &BindGroupEntries::sequential((
-                                &gpu_image.texture_view,
-                                &gpu_image.sampler,
-                            )),
-                        )
-                    });
+                image_bind_groups.insert(
+                    batch_image_handle,
+                    gpu_image,
+                    &render_device,
+                    &sprite_pipeline,
+                );
 
                 batch_item_index = item_index;
                 current_batch = Some(batches.entry((*retained_view, item.entity())).insert(
@@ -702,74 +795,10 @@ pub fn prepare_sprite_image_bind_groups(
                 ));
             }
 
-            // By default, the size of the quad is the size of the texture
-            let mut quad_size = batch_image_size;
-
-            // Texture size is the size of the image
-            let mut texture_size = batch_image_size;
-
-            // If a rect is specified, adjust UVs and the size of the quad
-            let mut uv_offset_scale = if let Some(rect) = extracted_sprite.rect {
-                let rect_size = rect.size();
-                quad_size = rect_size;
-                // Update texture size to the rect size
-                // It will help scale properly only portion of the image
-                texture_size = rect_size;
-                Vec4::new(
-                    rect.min.x / batch_image_size.x,
-                    rect.max.y / batch_image_size.y,
-                    rect_size.x / batch_image_size.x,
-                    -rect_size.y / batch_image_size.y,
-                )
-            } else {
-                Vec4::new(0.0, 1.0, 1.0, -1.0)
-            };
-
-            // Override the size if a custom one is specified
-            if let Some(custom_size) = extracted_sprite.custom_size {
-                quad_size = custom_size;
-            }
-
-            // Used for translation of the quad if `TextureScale::Fit...` is specified.
-            let mut quad_translation = Vec2::ZERO;
-
-            // Scales the texture based on the `texture_scale` field.
-            if let Some(scaling_mode) = extracted_sprite.scaling_mode {
-                apply_scaling(
-                    scaling_mode,
-                    texture_size,
-                    &mut quad_size,
-                    &mut quad_translation,
-                    &mut uv_offset_scale,
-                );
-            }
-
-            if extracted_sprite.flip_x {
-                uv_offset_scale.x += uv_offset_scale.z;
-                uv_offset_scale.z *= -1.0;
-            }
-            if extracted_sprite.flip_y {
-                uv_offset_scale.y += uv_offset_scale.w;
-                uv_offset_scale.w *= -1.0;
-            }
-
-            let transform = extracted_sprite.transform.affine()
-                * Affine3A::from_scale_rotation_translation(
-                    quad_size.extend(1.0),
-                    Quat::IDENTITY,
-                    ((quad_size + quad_translation)
-                        * (-extracted_sprite.anchor - Vec2::splat(0.5)))
-                    .extend(0.0),
-                );
-
             // Store the vertex data and add the item to the render phase
             sprite_meta
                 .sprite_instance_buffer
-                .push(SpriteInstance::from(
-                    &transform,
-                    &extracted_sprite.color,
-                    &uv_offset_scale,
-                ));
+                .push(SpriteInstance::new(extracted_sprite, batch_image_size));
 
             transparent_phase.items[batch_item_index]
                 .batch_range_mut()
@@ -7,7 +7,11 @@
     view::View,
 }
 
-#import bevy_sprite::sprite_view_bindings::view
+#import bevy_sprite::{
+    sprite_bindings::{sprite_texture, sprite_sampler},
+    sprite_vertex_output::SpriteVertexOutput,
+    sprite_view_bindings::view,
+}
 
 struct VertexInput {
     @builtin(vertex_index) index: u32,
@@ -21,15 +25,9 @@ struct VertexInput {
     @location(4) i_uv_offset_scale: vec4<f32>,
 }
 
-struct VertexOutput {
-    @builtin(position) clip_position: vec4<f32>,
-    @location(0) uv: vec2<f32>,
-    @location(1) @interpolate(flat) color: vec4<f32>,
-};
-
 @vertex
-fn vertex(in: VertexInput) -> VertexOutput {
-    var out: VertexOutput;
+fn vertex(in: VertexInput) -> SpriteVertexOutput {
+    var out: SpriteVertexOutput;
 
     let vertex_position = vec3<f32>(
         f32(in.index & 0x1u),
@@ -48,11 +46,8 @@ fn vertex(in: VertexInput) -> VertexOutput {
     return out;
 }
 
-@group(1) @binding(0) var sprite_texture: texture_2d<f32>;
-@group(1) @binding(1) var sprite_sampler: sampler;
-
 @fragment
-fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+fn fragment(in: SpriteVertexOutput) -> @location(0) vec4<f32> {
     var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
 
 #ifdef TONEMAP_IN_SHADER
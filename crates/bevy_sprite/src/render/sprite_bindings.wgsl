@@ -0,0 +1,4 @@
+#define_import_path bevy_sprite::sprite_bindings
+
+@group(1) @binding(0) var sprite_texture: texture_2d<f32>;
+@group(1) @binding(1) var sprite_sampler: sampler;
@@ -0,0 +1,555 @@
+use core::{hash::Hash, marker::PhantomData, ops::Range};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AssetApp, AssetId, AssetServer, Handle};
+use bevy_core_pipeline::{
+    core_2d::Transparent2d,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    prelude::*,
+    query::ROQueryItem,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_image::Image;
+use bevy_math::{FloatOrd, Vec2};
+use bevy_platform_support::collections::HashMap;
+use bevy_render::{
+    render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+        RenderCommandResult, SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    sync_world::MainEntity,
+    texture::GpuImage,
+    view::{ExtractedView, Msaa, RenderVisibleEntities, RetainedViewEntity},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use fixedbitset::FixedBitSet;
+
+use super::{
+    prepare_sprite_image_bind_groups, queue_sprites, ExtractedSprite, ExtractedSprites,
+    ImageBindGroups, SetSpriteViewBindGroup, SpriteInstance, SpriteMeta, SpritePipeline,
+    SpritePipelineKey,
+};
+use crate::{MaterialSprite, Sprite, SpriteMaterial, SpriteMaterialKey, SpriteSystem};
+
+/// Adds the necessary ECS resources and render logic to enable rendering sprites using the given
+/// [`SpriteMaterial`] asset type.
+pub struct SpriteMaterialPlugin<M: SpriteMaterial>(PhantomData<M>);
+
+impl<M: SpriteMaterial> Default for SpriteMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: SpriteMaterial> Plugin for SpriteMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        app.init_asset::<M>()
+            .register_type::<MaterialSprite<M>>()
+            .add_plugins(RenderAssetPlugin::<PreparedSpriteMaterial<M>>::default());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<Transparent2d, DrawSpriteMaterial<M>>()
+                .init_resource::<ExtractedMaterialSprites<M>>()
+                .init_resource::<SpriteMaterialMeta<M>>()
+                .init_resource::<SpriteMaterialBatches<M>>()
+                .init_resource::<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>()
+                .add_systems(
+                    ExtractSchedule,
+                    extract_material_sprites::<M>.after(SpriteSystem::ExtractSprites),
+                )
+                .add_systems(
+                    Render,
+                    (
+                        queue_material_sprites::<M>
+                            .in_set(RenderSet::Queue)
+                            .ambiguous_with(queue_sprites),
+                        prepare_material_sprites::<M>
+                            .in_set(RenderSet::PrepareBindGroups)
+                            .after(prepare_sprite_image_bind_groups),
+                    ),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<SpriteMaterialPipeline<M>>();
+        }
+    }
+}
+
+/// Render pipeline data for a given [`SpriteMaterial`]
+#[derive(Resource)]
+pub struct SpriteMaterialPipeline<M: SpriteMaterial> {
+    pub sprite_pipeline: SpritePipeline,
+    pub material_layout: BindGroupLayout,
+    pub vertex_shader: Option<Handle<Shader>>,
+    pub fragment_shader: Option<Handle<Shader>>,
+    marker: PhantomData<M>,
+}
+
+impl<M: SpriteMaterial> SpecializedRenderPipeline for SpriteMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = SpriteMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = self.sprite_pipeline.specialize(key.sprite_key);
+        if let Some(vertex_shader) = &self.vertex_shader {
+            descriptor.vertex.shader = vertex_shader.clone();
+        }
+
+        if let Some(fragment_shader) = &self.fragment_shader {
+            descriptor.fragment.as_mut().unwrap().shader = fragment_shader.clone();
+        }
+
+        descriptor.layout.push(self.material_layout.clone());
+        descriptor.label = Some("sprite_material_pipeline".into());
+
+        M::specialize(&mut descriptor, key);
+
+        descriptor
+    }
+}
+
+impl<M: SpriteMaterial> FromWorld for SpriteMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+
+        SpriteMaterialPipeline {
+            sprite_pipeline: world.resource::<SpritePipeline>().clone(),
+            material_layout: M::bind_group_layout(render_device),
+            vertex_shader: match M::vertex_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            fragment_shader: match M::fragment_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+pub struct PreparedSpriteMaterial<M: SpriteMaterial> {
+    pub bindings: BindingResources,
+    pub bind_group: BindGroup,
+    pub key: M::Data,
+}
+
+impl<M: SpriteMaterial> RenderAsset for PreparedSpriteMaterial<M> {
+    type SourceAsset = M;
+
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<SpriteMaterialPipeline<M>>,
+        M::Param,
+    );
+
+    fn prepare_asset(
+        material: Self::SourceAsset,
+        _: AssetId<Self::SourceAsset>,
+        (render_device, pipeline, ref mut material_param): &mut SystemParamItem<Self::Param>,
+    ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
+        match material.as_bind_group(&pipeline.material_layout, render_device, material_param) {
+            Ok(prepared) => Ok(PreparedSpriteMaterial {
+                bindings: prepared.bindings,
+                bind_group: prepared.bind_group,
+                key: prepared.data,
+            }),
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                Err(PrepareAssetError::RetryNextUpdate(material))
+            }
+            Err(other) => Err(PrepareAssetError::AsBindGroupError(other)),
+        }
+    }
+}
+
+pub struct ExtractedMaterialSprite<M: SpriteMaterial> {
+    pub sprite: ExtractedSprite,
+    pub material: AssetId<M>,
+}
+
+/// The sprites drawn with a [`SpriteMaterial`] of type `M`.
+///
+/// These are moved out of [`ExtractedSprites`] during extraction, so that they aren't also drawn
+/// by the default sprite pipeline.
+#[derive(Resource)]
+pub struct ExtractedMaterialSprites<M: SpriteMaterial> {
+    pub sprites: HashMap<(Entity, MainEntity), ExtractedMaterialSprite<M>>,
+}
+
+impl<M: SpriteMaterial> Default for ExtractedMaterialSprites<M> {
+    fn default() -> Self {
+        Self {
+            sprites: Default::default(),
+        }
+    }
+}
+
+pub fn extract_material_sprites<M: SpriteMaterial>(
+    mut extracted_sprites: ResMut<ExtractedSprites>,
+    mut extracted_material_sprites: ResMut<ExtractedMaterialSprites<M>>,
+    mut materials: Local<HashMap<MainEntity, AssetId<M>>>,
+    material_sprite_query: Extract<Query<(Entity, &MaterialSprite<M>)>>,
+) {
+    extracted_material_sprites.sprites.clear();
+
+    materials.clear();
+    materials.extend(
+        material_sprite_query
+            .iter()
+            .map(|(entity, material)| (entity.into(), material.id())),
+    );
+    if materials.is_empty() {
+        return;
+    }
+
+    // Sliced sprites are extracted as several sprites sharing the main entity of the `Sprite`,
+    // so they are matched by main entity.
+    extracted_material_sprites.sprites.extend(
+        extracted_sprites
+            .sprites
+            .extract_if(|(_, main_entity), _| materials.contains_key(main_entity))
+            .map(|(key, sprite)| {
+                let material = materials[&key.1];
+                (key, ExtractedMaterialSprite { sprite, material })
+            }),
+    );
+}
+
+pub fn queue_material_sprites<M: SpriteMaterial>(
+    mut view_entities: Local<FixedBitSet>,
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    sprite_material_pipeline: Res<SpriteMaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SpriteMaterialPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_materials: Res<RenderAssets<PreparedSpriteMaterial<M>>>,
+    extracted_sprites: Res<ExtractedMaterialSprites<M>>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut views: Query<(
+        &RenderVisibleEntities,
+        &ExtractedView,
+        &Msaa,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+    )>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    if extracted_sprites.sprites.is_empty() {
+        return;
+    }
+
+    let draw_function = draw_functions.read().id::<DrawSpriteMaterial<M>>();
+
+    for (visible_entities, view, msaa, tonemapping, dither) in &mut views {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view.retained_view_entity)
+        else {
+            continue;
+        };
+
+        let view_key = SpritePipelineKey::from_view(view, msaa, tonemapping, dither);
+
+        view_entities.clear();
+        view_entities.extend(
+            visible_entities
+                .iter::<Sprite>()
+                .map(|(_, e)| e.index() as usize),
+        );
+
+        for ((entity, main_entity), extracted_sprite) in extracted_sprites.sprites.iter() {
+            let index = extracted_sprite
+                .sprite
+                .original_entity
+                .unwrap_or(*entity)
+                .index();
+
+            if !view_entities.contains(index as usize) {
+                continue;
+            }
+
+            let Some(material) = render_materials.get(extracted_sprite.material) else {
+                continue;
+            };
+
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &sprite_material_pipeline,
+                SpriteMaterialKey {
+                    sprite_key: view_key,
+                    bind_group_data: material.key.clone(),
+                },
+            );
+
+            // These items will be sorted by depth with other phase items
+            let sort_key = FloatOrd(extracted_sprite.sprite.transform.translation().z);
+
+            transparent_phase.add(Transparent2d {
+                draw_function,
+                pipeline,
+                entity: (*entity, *main_entity),
+                sort_key,
+                // `batch_range` is calculated in `prepare_material_sprites`
+                batch_range: 0..0,
+                extra_index: PhaseItemExtraIndex::None,
+                indexed: true,
+            });
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SpriteMaterialMeta<M: SpriteMaterial> {
+    sprite_instance_buffer: RawBufferVec<SpriteInstance>,
+    marker: PhantomData<M>,
+}
+
+impl<M: SpriteMaterial> Default for SpriteMaterialMeta<M> {
+    fn default() -> Self {
+        Self {
+            sprite_instance_buffer: RawBufferVec::new(BufferUsages::VERTEX),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct SpriteMaterialBatches<M: SpriteMaterial>(
+    HashMap<(RetainedViewEntity, Entity), SpriteMaterialBatch<M>>,
+);
+
+impl<M: SpriteMaterial> Default for SpriteMaterialBatches<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// Consecutive sprites sharing the same image and [`SpriteMaterial`], drawn in a single call.
+pub struct SpriteMaterialBatch<M: SpriteMaterial> {
+    image_handle_id: AssetId<Image>,
+    material: AssetId<M>,
+    range: Range<u32>,
+}
+
+pub fn prepare_material_sprites<M: SpriteMaterial>(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut sprite_material_meta: ResMut<SpriteMaterialMeta<M>>,
+    sprite_pipeline: Res<SpritePipeline>,
+    mut image_bind_groups: ResMut<ImageBindGroups>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    extracted_sprites: Res<ExtractedMaterialSprites<M>>,
+    mut phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    mut batches: ResMut<SpriteMaterialBatches<M>>,
+) {
+    batches.clear();
+    sprite_material_meta.sprite_instance_buffer.clear();
+
+    if extracted_sprites.sprites.is_empty() {
+        return;
+    }
+
+    let mut index = 0;
+
+    for (retained_view, transparent_phase) in phases.iter_mut() {
+        let mut current_batch = None;
+        let mut batch_item_index = 0;
+        let mut batch_image_size = Vec2::ZERO;
+        let mut batch_image_handle = AssetId::invalid();
+        let mut batch_material = AssetId::invalid();
+
+        for item_index in 0..transparent_phase.items.len() {
+            let item = &transparent_phase.items[item_index];
+            let Some(extracted_sprite) = extracted_sprites.sprites.get(&item.entity) else {
+                // Other phase items must be drawn between the batches to respect draw order.
+                batch_image_handle = AssetId::invalid();
+                continue;
+            };
+
+            if batch_image_handle != extracted_sprite.sprite.image_handle_id
+                || batch_material != extracted_sprite.material
+            {
+                let Some(gpu_image) = gpu_images.get(extracted_sprite.sprite.image_handle_id)
+                else {
+                    continue;
+                };
+
+                batch_image_size = gpu_image.size_2d().as_vec2();
+                batch_image_handle = extracted_sprite.sprite.image_handle_id;
+                batch_material = extracted_sprite.material;
+                image_bind_groups.insert(
+                    batch_image_handle,
+                    gpu_image,
+                    &render_device,
+                    &sprite_pipeline,
+                );
+
+                batch_item_index = item_index;
+                current_batch = Some(batches.entry((*retained_view, item.entity())).insert(
+                    SpriteMaterialBatch {
+                        image_handle_id: batch_image_handle,
+                        material: batch_material,
+                        range: index..index,
+                    },
+                ));
+            }
+
+            sprite_material_meta
+                .sprite_instance_buffer
+                .push(SpriteInstance::new(
+                    &extracted_sprite.sprite,
+                    batch_image_size,
+                ));
+
+            transparent_phase.items[batch_item_index]
+                .batch_range_mut()
+                .end += 1;
+            current_batch.as_mut().unwrap().get_mut().range.end += 1;
+            index += 1;
+        }
+    }
+    sprite_material_meta
+        .sprite_instance_buffer
+        .write_buffer(&render_device, &render_queue);
+}
+
+/// [`RenderCommand`] for rendering sprites with a [`SpriteMaterial`].
+pub type DrawSpriteMaterial<M> = (
+    SetItemPipeline,
+    SetSpriteViewBindGroup<0>,
+    SetSpriteMaterialTextureBindGroup<M, 1>,
+    SetSpriteMaterialBindGroup<M, 2>,
+    DrawSpriteMaterialBatch<M>,
+);
+
+pub struct SetSpriteMaterialTextureBindGroup<M: SpriteMaterial, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: SpriteMaterial, const I: usize> RenderCommand<P>
+    for SetSpriteMaterialTextureBindGroup<M, I>
+{
+    type Param = (SRes<ImageBindGroups>, SRes<SpriteMaterialBatches<M>>);
+    type ViewQuery = Read<ExtractedView>;
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        view: ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<()>,
+        (image_bind_groups, batches): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(batch) = batches
+            .into_inner()
+            .get(&(view.retained_view_entity, item.entity()))
+        else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(image_bind_group) = image_bind_groups
+            .into_inner()
+            .values
+            .get(&batch.image_handle_id)
+        else {
+            return RenderCommandResult::Skip;
+        };
+
+        pass.set_bind_group(I, image_bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct SetSpriteMaterialBindGroup<M: SpriteMaterial, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: SpriteMaterial, const I: usize> RenderCommand<P>
+    for SetSpriteMaterialBindGroup<M, I>
+{
+    type Param = (
+        SRes<RenderAssets<PreparedSpriteMaterial<M>>>,
+        SRes<SpriteMaterialBatches<M>>,
+    );
+    type ViewQuery = Read<ExtractedView>;
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        view: ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<()>,
+        (materials, batches): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(batch) = batches
+            .into_inner()
+            .get(&(view.retained_view_entity, item.entity()))
+        else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(material) = materials.into_inner().get(batch.material) else {
+            return RenderCommandResult::Skip;
+        };
+
+        pass.set_bind_group(I, &material.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct DrawSpriteMaterialBatch<M: SpriteMaterial>(PhantomData<M>);
+impl<P: PhaseItem, M: SpriteMaterial> RenderCommand<P> for DrawSpriteMaterialBatch<M> {
+    type Param = (
+        SRes<SpriteMeta>,
+        SRes<SpriteMaterialMeta<M>>,
+        SRes<SpriteMaterialBatches<M>>,
+    );
+    type ViewQuery = Read<ExtractedView>;
+    type ItemQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        view: ROQueryItem<'w, Self::ViewQuery>,
+        _entity: Option<()>,
+        (sprite_meta, sprite_material_meta, batches): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(batch) = batches
+            .into_inner()
+            .get(&(view.retained_view_entity, item.entity()))
+        else {
+            return RenderCommandResult::Skip;
+        };
+
+        // The index buffer is shared with the default sprite pipeline.
+        pass.set_index_buffer(
+            sprite_meta
+                .into_inner()
+                .sprite_index_buffer
+                .buffer()
+                .unwrap()
+                .slice(..),
+            0,
+            IndexFormat::Uint32,
+        );
+        pass.set_vertex_buffer(
+            0,
+            sprite_material_meta
+                .into_inner()
+                .sprite_instance_buffer
+                .buffer()
+                .unwrap()
+                .slice(..),
+        );
+        pass.draw_indexed(0..6, 0, batch.range.clone());
+        RenderCommandResult::Success
+    }
+}
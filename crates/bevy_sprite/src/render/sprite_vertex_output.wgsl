@@ -0,0 +1,12 @@
+#define_import_path bevy_sprite::sprite_vertex_output
+
+// The vertex output of the sprite vertex shader, used as the input of the
+// fragment shaders of sprite materials.
+struct SpriteVertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // The UV of the sprite texture, with the atlas rect, flipping and slicing
+    // already applied.
+    @location(0) uv: vec2<f32>,
+    // The color of the `Sprite`.
+    @location(1) @interpolate(flat) color: vec4<f32>,
+};
@@ -0,0 +1,450 @@
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_time::Time;
+
+use crate::Sprite;
+
+/// A flipbook animation, showing a sequence of sections of a [`TextureAtlas`](bevy_image::TextureAtlas).
+///
+/// Clips are played by a [`SpriteAnimationPlayer`], which sets the index of the texture atlas
+/// of its [`Sprite`].
+///
+/// With the `serialize` feature, clips can be loaded from `.sprite_anim.ron` files, and are
+/// hot-reloaded like other assets:
+///
+/// ```ron
+/// (
+///     frames: [
+///         (index: 0, duration: 0.1),
+///         (index: 1, duration: 0.1, events: ["footstep"]),
+///         (index: 2, duration: 0.2),
+///     ],
+///     repeat: Loop,
+/// )
+/// ```
+#[derive(Asset, Clone, Debug, Default, Reflect)]
+#[reflect(Default, Debug)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SpriteAnimationClip {
+    /// The frames of the clip, in order.
+    pub frames: Vec<SpriteAnimationFrame>,
+    /// What happens when the clip reaches its last frame.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub repeat: SpriteAnimationRepeat,
+}
+
+/// A frame of a [`SpriteAnimationClip`].
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SpriteAnimationFrame {
+    /// The index of the section of the texture atlas shown during this frame.
+    pub index: usize,
+    /// How long the frame is shown, in seconds.
+    pub duration: f32,
+    /// The names of the [`SpriteAnimationEvent`]s triggered when this frame is reached.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub events: Vec<String>,
+}
+
+/// What a [`SpriteAnimationClip`] does when it reaches its last frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum SpriteAnimationRepeat {
+    /// Stop on the last frame, and trigger [`SpriteAnimationFinished`].
+    Once,
+    /// Start again from the first frame.
+    #[default]
+    Loop,
+    /// Play the frames backwards down to the first frame, then forwards again.
+    PingPong,
+}
+
+impl SpriteAnimationClip {
+    /// Creates a looping clip showing each atlas index of `indices` in turn, at `fps` frames per second.
+    pub fn from_indices(indices: impl IntoIterator<Item = usize>, fps: f32) -> Self {
+        Self {
+            frames: indices
+                .into_iter()
+                .map(|index| SpriteAnimationFrame {
+                    index,
+                    duration: 1.0 / fps,
+                    events: Vec::new(),
+                })
+                .collect(),
+            repeat: SpriteAnimationRepeat::default(),
+        }
+    }
+
+    /// Sets what happens when the clip reaches its last frame.
+    pub fn with_repeat(mut self, repeat: SpriteAnimationRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Adds an event named `name`, triggered when the frame at position `frame` in the clip is reached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` is out of bounds.
+    pub fn with_event(mut self, frame: usize, name: impl Into<String>) -> Self {
+        self.frames[frame].events.push(name.into());
+        self
+    }
+
+    /// Returns the duration of a single pass through the frames of the clip, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Returns the position of the frame following `frame` and whether the clip then plays
+    /// backwards, or `None` if the clip ends on `frame`.
+    fn next_frame(&self, frame: usize, reversed: bool) -> Option<(usize, bool)> {
+        let last = self.frames.len().checked_sub(1)?;
+        match self.repeat {
+            SpriteAnimationRepeat::Once => (frame < last).then_some((frame + 1, false)),
+            SpriteAnimationRepeat::Loop => Some((if frame < last { frame + 1 } else { 0 }, false)),
+            SpriteAnimationRepeat::PingPong if last == 0 => Some((0, false)),
+            SpriteAnimationRepeat::PingPong => match (reversed, frame) {
+                (false, frame) if frame < last => Some((frame + 1, false)),
+                (true, frame) if frame > 0 => Some((frame - 1, true)),
+                (false, frame) => Some((frame - 1, true)),
+                (true, frame) => Some((frame + 1, false)),
+            },
+        }
+    }
+}
+
+/// Plays a [`SpriteAnimationClip`] on the [`Sprite`] of its entity.
+///
+/// The index of the [`TextureAtlas`](bevy_image::TextureAtlas) of the sprite is updated by
+/// [`animate_sprites`] in [`PostUpdate`](bevy_app::PostUpdate). Sprites without a texture atlas
+/// aren't changed, but the clip is still played and its events triggered.
+///
+/// The events of the clip trigger a [`SpriteAnimationEvent`] on the entity of the player,
+/// which can be observed:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_sprite::{SpriteAnimationEvent, SpriteAnimationPlayer};
+/// fn setup(mut commands: Commands) {
+///     commands
+///         .spawn(SpriteAnimationPlayer::default())
+///         .observe(|trigger: Trigger<SpriteAnimationEvent>| {
+///             if trigger.name == "footstep" {
+///                 // Play a sound...
+///             }
+///         });
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct SpriteAnimationPlayer {
+    clip: Handle<SpriteAnimationClip>,
+    /// How fast the clip is played. Must not be negative.
+    pub speed: f32,
+    /// Whether the clip is paused.
+    pub paused: bool,
+    /// The position of the current frame in the clip.
+    frame: usize,
+    /// How long the current frame has been shown, in seconds.
+    elapsed: f32,
+    /// Whether a [`PingPong`](SpriteAnimationRepeat::PingPong) clip is playing backwards.
+    reversed: bool,
+    /// Whether the current frame was shown and its events triggered.
+    started: bool,
+    finished: bool,
+}
+
+impl Default for SpriteAnimationPlayer {
+    fn default() -> Self {
+        Self {
+            clip: Handle::default(),
+            speed: 1.0,
+            paused: false,
+            frame: 0,
+            elapsed: 0.0,
+            reversed: false,
+            started: false,
+            finished: false,
+        }
+    }
+}
+
+impl SpriteAnimationPlayer {
+    /// Creates a player for `clip`, starting from its first frame.
+    pub fn new(clip: Handle<SpriteAnimationClip>) -> Self {
+        Self {
+            clip,
+            ..Default::default()
+        }
+    }
+
+    /// Sets how fast the clip is played.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Returns the clip being played.
+    pub fn clip(&self) -> &Handle<SpriteAnimationClip> {
+        &self.clip
+    }
+
+    /// Plays `clip` from its first frame.
+    pub fn set_clip(&mut self, clip: Handle<SpriteAnimationClip>) {
+        self.clip = clip;
+        self.restart();
+    }
+
+    /// Resumes the clip.
+    pub fn play(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pauses the clip on its current frame.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Plays the clip again from its first frame.
+    pub fn restart(&mut self) {
+        self.seek_to_frame(0);
+        self.paused = false;
+    }
+
+    /// Jumps to the frame at position `frame` in the clip. Its events are triggered on the next update.
+    pub fn seek_to_frame(&mut self, frame: usize) {
+        self.frame = frame;
+        self.elapsed = 0.0;
+        self.reversed = false;
+        self.started = false;
+        self.finished = false;
+    }
+
+    /// Returns the position of the current frame in the clip.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns `true` if the clip plays [`Once`](SpriteAnimationRepeat::Once) and reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances the player by `delta` seconds through `clip`, calling `reached` with the position
+    /// of each frame reached. Returns `true` if the clip finished during this update.
+    fn advance(
+        &mut self,
+        clip: &SpriteAnimationClip,
+        delta: f32,
+        mut reached: impl FnMut(usize),
+    ) -> bool {
+        if clip.frames.is_empty() {
+            return false;
+        }
+        // The clip may have been reloaded with fewer frames.
+        if self.frame >= clip.frames.len() {
+            self.seek_to_frame(0);
+        }
+        if !self.started {
+            self.started = true;
+            reached(self.frame);
+        }
+        if self.paused || self.finished {
+            return false;
+        }
+        // Clips without duration never leave their frame.
+        if clip.repeat != SpriteAnimationRepeat::Once && clip.duration() <= 0.0 {
+            return false;
+        }
+
+        self.elapsed += delta * self.speed.max(0.0);
+        loop {
+            let duration = clip.frames[self.frame].duration;
+            if self.elapsed < duration {
+                return false;
+            }
+            let Some((frame, reversed)) = clip.next_frame(self.frame, self.reversed) else {
+                self.elapsed = duration;
+                self.finished = true;
+                return true;
+            };
+            self.elapsed -= duration;
+            self.frame = frame;
+            self.reversed = reversed;
+            reached(frame);
+        }
+    }
+}
+
+/// Triggered on the entity of a [`SpriteAnimationPlayer`] when it reaches a frame with events.
+#[derive(Event, Clone, Debug)]
+pub struct SpriteAnimationEvent {
+    /// The name of the event, from [`SpriteAnimationFrame::events`].
+    pub name: String,
+    /// The position of the frame in the clip.
+    pub frame: usize,
+}
+
+/// Triggered on the entity of a [`SpriteAnimationPlayer`] when its clip plays
+/// [`Once`](SpriteAnimationRepeat::Once) and reaches its end.
+#[derive(Event, Clone, Debug)]
+pub struct SpriteAnimationFinished;
+
+/// Advances every [`SpriteAnimationPlayer`], and updates the texture atlas index of its [`Sprite`].
+pub fn animate_sprites(
+    mut commands: Commands,
+    time: Res<Time>,
+    clips: Res<Assets<SpriteAnimationClip>>,
+    mut players: Query<(Entity, &mut SpriteAnimationPlayer, Option<&mut Sprite>)>,
+) {
+    for (entity, mut player, sprite) in &mut players {
+        let Some(clip) = clips.get(&player.clip) else {
+            continue;
+        };
+
+        let finished = player.advance(clip, time.delta_secs(), |frame| {
+            for name in &clip.frames[frame].events {
+                commands.entity(entity).trigger(SpriteAnimationEvent {
+                    name: name.clone(),
+                    frame,
+                });
+            }
+        });
+        if finished {
+            commands.entity(entity).trigger(SpriteAnimationFinished);
+        }
+
+        let Some(mut sprite) = sprite else {
+            continue;
+        };
+        let Some(index) = clip.frames.get(player.frame).map(|frame| frame.index) else {
+            continue;
+        };
+        // Only mutate the sprite when its index changes, so that it isn't extracted again.
+        if sprite
+            .texture_atlas
+            .as_ref()
+            .is_some_and(|atlas| atlas.index != index)
+        {
+            if let Some(atlas) = sprite.texture_atlas.as_mut() {
+                atlas.index = index;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod loader {
+    use bevy_asset::{io::Reader, AssetLoader, LoadContext};
+    use thiserror::Error;
+
+    use super::SpriteAnimationClip;
+
+    /// Asset loader for a [`SpriteAnimationClip`] (`.sprite_anim.ron`).
+    #[derive(Debug, Default)]
+    pub struct SpriteAnimationClipLoader;
+
+    /// Possible errors that can be produced by [`SpriteAnimationClipLoader`].
+    #[non_exhaustive]
+    #[derive(Debug, Error)]
+    pub enum SpriteAnimationClipLoaderError {
+        /// An [IO Error](std::io::Error)
+        #[error("Error while trying to read the sprite animation file: {0}")]
+        Io(#[from] std::io::Error),
+        /// A [RON Error](ron::error::SpannedError)
+        #[error("Could not parse RON: {0}")]
+        RonSpannedError(#[from] ron::error::SpannedError),
+    }
+
+    impl AssetLoader for SpriteAnimationClipLoader {
+        type Asset = SpriteAnimationClip;
+        type Settings = ();
+        type Error = SpriteAnimationClipLoaderError;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &(),
+            _load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(ron::de::from_bytes(&bytes)?)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["sprite_anim.ron"]
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+pub use loader::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(
+        clip: &SpriteAnimationClip,
+        player: &mut SpriteAnimationPlayer,
+        delta: f32,
+    ) -> Vec<usize> {
+        let mut reached = Vec::new();
+        player.advance(clip, delta, |frame| reached.push(frame));
+        reached
+    }
+
+    #[test]
+    fn frames_follow_repeat_mode() {
+        let clip = SpriteAnimationClip::from_indices([4, 5, 6], 4.0);
+        let mut player = SpriteAnimationPlayer::default();
+        assert_eq!(play(&clip, &mut player, 0.0), [0]);
+        // A long update reaches every frame in between.
+        assert_eq!(play(&clip, &mut player, 1.0), [1, 2, 0, 1]);
+
+        let clip = clip.with_repeat(SpriteAnimationRepeat::PingPong);
+        let mut player = SpriteAnimationPlayer::default().with_speed(2.0);
+        assert_eq!(
+            play(&clip, &mut player, 1.25),
+            [0, 1, 2, 1, 0, 1, 2, 1, 0, 1, 2]
+        );
+
+        let clip = clip.with_repeat(SpriteAnimationRepeat::Once);
+        let mut player = SpriteAnimationPlayer::default();
+        assert_eq!(play(&clip, &mut player, 0.5), [0, 1, 2]);
+        assert!(!player.is_finished());
+        assert!(player.advance(&clip, 0.25, |_| {}));
+        assert!(player.is_finished());
+        assert!(play(&clip, &mut player, 1.0).is_empty());
+        assert_eq!(player.frame(), 2);
+    }
+
+    #[test]
+    fn reloaded_clip_restarts_player() {
+        let clip = SpriteAnimationClip::from_indices(0..4, 1.0);
+        let mut player = SpriteAnimationPlayer::default();
+        play(&clip, &mut player, 3.5);
+        assert_eq!(player.frame(), 3);
+
+        let clip = SpriteAnimationClip::from_indices(0..2, 1.0);
+        assert_eq!(play(&clip, &mut player, 0.5), [0]);
+        assert_eq!(player.frame(), 0);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{Sprite, SpritePipelineKey};
+use bevy_asset::{AsAssetId, Asset, AssetId, Handle};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    component::{require, Component},
+    reflect::ReflectComponent,
+};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, ShaderRef};
+use core::hash::Hash;
+use derive_more::derive::From;
+
+/// Materials are used alongside [`SpriteMaterialPlugin`](crate::SpriteMaterialPlugin) and
+/// [`MaterialSprite`] to render [`Sprite`]s with custom shader logic.
+///
+/// Unlike a [`Material2d`](crate::Material2d) on a [`Mesh2d`](bevy_render::mesh::Mesh2d), a
+/// [`SpriteMaterial`] keeps everything a [`Sprite`] supports: its image, texture atlas, `rect`,
+/// flipping, anchor, color and image mode (including slicing and tiling) are all applied as usual,
+/// and the material's shader is only responsible for shading the result.
+///
+/// `SpriteMaterials` must implement [`AsBindGroup`] to define how data will be transferred to the GPU and bound in shaders.
+/// [`AsBindGroup`] can be derived, which makes generating bindings straightforward. See the [`AsBindGroup`] docs for details.
+///
+/// Materials must also implement [`Asset`] so they can be treated as such.
+///
+/// # Example
+///
+/// Here is a simple [`SpriteMaterial`] implementation. The [`AsBindGroup`] derive has many features. To see what else is available,
+/// check out the [`AsBindGroup`] documentation.
+/// ```
+/// # use bevy_sprite::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # use bevy_render::render_resource::{AsBindGroup, ShaderRef};
+/// # use bevy_color::LinearRgba;
+/// # use bevy_asset::{AssetServer, Assets, Asset};
+///
+/// #[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+/// pub struct OutlineMaterial {
+///     // Uniform bindings must implement `ShaderType`, which will be used to convert the value to
+///     // its shader-compatible equivalent. Most core math types already implement `ShaderType`.
+///     #[uniform(0)]
+///     outline_color: LinearRgba,
+/// }
+///
+/// // All functions on `SpriteMaterial` have default impls. You only need to implement the
+/// // functions that are relevant for your material.
+/// impl SpriteMaterial for OutlineMaterial {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/outline_material.wgsl".into()
+///     }
+/// }
+///
+/// // Spawn a sprite using `OutlineMaterial`.
+/// fn setup(mut commands: Commands, mut materials: ResMut<Assets<OutlineMaterial>>, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         Sprite::from_image(asset_server.load("player.png")),
+///         MaterialSprite(materials.add(OutlineMaterial {
+///             outline_color: LinearRgba::WHITE,
+///         })),
+///     ));
+/// }
+/// ```
+///
+/// In WGSL shaders, the material's bindings are in bind group 2. Bind group 0 is bound to the
+/// view, and bind group 1 to the image of the sprite, which can be imported from
+/// `bevy_sprite::sprite_bindings`. Fragment shaders take a `SpriteVertexOutput`, imported from
+/// `bevy_sprite::sprite_vertex_output`, whose `uv` already accounts for the atlas, flipping and
+/// slicing of the sprite:
+///
+/// ```wgsl
+/// #import bevy_sprite::{
+///     sprite_bindings::{sprite_texture, sprite_sampler},
+///     sprite_vertex_output::SpriteVertexOutput,
+/// }
+///
+/// @group(2) @binding(0) var<uniform> outline_color: vec4<f32>;
+///
+/// @fragment
+/// fn fragment(in: SpriteVertexOutput) -> @location(0) vec4<f32> {
+///     let color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+///     return mix(outline_color, color, color.a);
+/// }
+/// ```
+pub trait SpriteMaterial: AsBindGroup + Asset + Clone + Sized {
+    /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default
+    /// sprite vertex shader will be used.
+    fn vertex_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the default
+    /// sprite fragment shader will be used.
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Customizes the default [`RenderPipelineDescriptor`], which is the one of the sprite
+    /// pipeline with this material's shaders and bind group layout.
+    #[expect(
+        unused_variables,
+        reason = "The parameters here are intentionally unused by the default implementation; however, putting underscores here will result in the underscores being copied by rust-analyzer's tab completion."
+    )]
+    #[inline]
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: SpriteMaterialKey<Self>) {}
+}
+
+/// The key used to specialize the pipeline of a [`SpriteMaterial`].
+pub struct SpriteMaterialKey<M: SpriteMaterial> {
+    pub sprite_key: SpritePipelineKey,
+    pub bind_group_data: M::Data,
+}
+
+impl<M: SpriteMaterial> Eq for SpriteMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: SpriteMaterial> PartialEq for SpriteMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.sprite_key == other.sprite_key && self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: SpriteMaterial> Clone for SpriteMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            sprite_key: self.sprite_key,
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: SpriteMaterial> Hash for SpriteMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.sprite_key.hash(state);
+        self.bind_group_data.hash(state);
+    }
+}
+
+/// A [material](SpriteMaterial) used to render the [`Sprite`] of an entity, instead of the
+/// default sprite shader.
+///
+/// The sprite isn't drawn until the material is loaded.
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect, PartialEq, Eq, From)]
+#[reflect(Component, Default)]
+#[require(Sprite)]
+pub struct MaterialSprite<M: SpriteMaterial>(pub Handle<M>);
+
+impl<M: SpriteMaterial> Default for MaterialSprite<M> {
+    fn default() -> Self {
+        Self(Handle::default())
+    }
+}
+
+impl<M: SpriteMaterial> From<MaterialSprite<M>> for AssetId<M> {
+    fn from(material: MaterialSprite<M>) -> Self {
+        material.id()
+    }
+}
+
+impl<M: SpriteMaterial> From<&MaterialSprite<M>> for AssetId<M> {
+    fn from(material: &MaterialSprite<M>) -> Self {
+        material.id()
+    }
+}
+
+impl<M: SpriteMaterial> AsAssetId for MaterialSprite<M> {
+    type Asset = M;
+
+    fn as_asset_id(&self) -> AssetId<Self::Asset> {
+        self.id()
+    }
+}
@@ -1,12 +1,19 @@
 use bevy_app::{App, MainScheduleOrder, Plugin, PreStartup, PreUpdate, SubApp};
-use bevy_ecs::{event::Events, schedule::IntoSystemConfigs, world::FromWorld};
+use bevy_ecs::{
+    event::Events,
+    schedule::IntoSystemConfigs,
+    system::{In, IntoSystem},
+    world::FromWorld,
+};
 use bevy_utils::once;
 use log::warn;
 
 use crate::{
     state::{
-        setup_state_transitions_in_world, ComputedStates, FreelyMutableState, NextState, State,
-        StateTransition, StateTransitionEvent, StateTransitionSteps, States, SubStates,
+        run_transition_guards, setup_state_transitions_in_world, ApplyStateTransition,
+        ComputedStates, FreelyMutableState, NextState, PendingTransition, State, StateTransition,
+        StateTransitionEvent, StateTransitionGuards, StateTransitionHistory, StateTransitionSteps,
+        States, SubStates, TransitionGuardDecision,
     },
     state_scoped::clear_state_scoped_entities,
 };
@@ -57,6 +64,46 @@ pub trait AppExtStates {
     /// This method is idempotent: it has no effect when called again using the same generic type.
     fn add_sub_state<S: SubStates>(&mut self) -> &mut Self;
 
+    /// Adds a transition guard for the state `S`, which can cancel or redirect the transitions
+    /// queued in [`NextState<S>`].
+    ///
+    /// Guards are systems taking the [`PendingTransition`] as input. They run in the order they
+    /// were added, during the [`StateTransition`](struct@StateTransition) schedule and before the
+    /// transition is applied, so the [`OnExit`](crate::state::OnExit) schedule of a cancelled
+    /// transition never runs. Each guard sees the redirections of the previous ones.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_state::prelude::*;
+    /// # #[derive(States, Clone, PartialEq, Eq, Hash, Debug, Default)]
+    /// # enum GameState { #[default] Paused, InGame }
+    /// # #[derive(Resource)]
+    /// # struct Saving(bool);
+    /// fn block_leaving_pause_while_saving(
+    ///     In(transition): In<PendingTransition<GameState>>,
+    ///     saving: Res<Saving>,
+    /// ) -> TransitionGuardDecision<GameState> {
+    ///     if transition.exited == GameState::Paused && saving.0 {
+    ///         TransitionGuardDecision::Cancel
+    ///     } else {
+    ///         TransitionGuardDecision::Allow
+    ///     }
+    /// }
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(bevy_state::app::StatesPlugin);
+    /// app.init_state::<GameState>()
+    ///     .add_state_transition_guard(block_leaving_pause_while_saving);
+    /// ```
+    ///
+    /// Guards only run for transitions queued in [`NextState<S>`]: they don't apply to
+    /// [`SubStates`] being added or removed when their source states change.
+    fn add_state_transition_guard<S: FreelyMutableState, M>(
+        &mut self,
+        guard: impl IntoSystem<In<PendingTransition<S>>, TransitionGuardDecision<S>, M>,
+    ) -> &mut Self;
+
     /// Enable state-scoped entity clearing for state `S`.
     ///
     /// If the [`States`] trait was derived with the `#[states(scoped_entities)]` attribute, it
@@ -100,6 +147,7 @@ impl AppExtStates for SubApp {
         if !self.world().contains_resource::<State<S>>() {
             self.init_resource::<State<S>>()
                 .init_resource::<NextState<S>>()
+                .init_resource::<StateTransitionHistory<S>>()
                 .add_event::<StateTransitionEvent<S>>();
             let schedule = self.get_schedule_mut(StateTransition).expect(
                 "The `StateTransition` schedule is missing. Did you forget to add StatesPlugin or DefaultPlugins before calling init_state?"
@@ -126,6 +174,7 @@ impl AppExtStates for SubApp {
         if !self.world().contains_resource::<State<S>>() {
             self.insert_resource::<State<S>>(State::new(state.clone()))
                 .init_resource::<NextState<S>>()
+                .init_resource::<StateTransitionHistory<S>>()
                 .add_event::<StateTransitionEvent<S>>();
             let schedule = self.get_schedule_mut(StateTransition).expect(
                 "The `StateTransition` schedule is missing. Did you forget to add StatesPlugin or DefaultPlugins before calling insert_state?"
@@ -214,6 +263,25 @@ impl AppExtStates for SubApp {
         self
     }
 
+    fn add_state_transition_guard<S: FreelyMutableState, M>(
+        &mut self,
+        guard: impl IntoSystem<In<PendingTransition<S>>, TransitionGuardDecision<S>, M>,
+    ) -> &mut Self {
+        if !self.world().contains_resource::<StateTransitionGuards<S>>() {
+            self.init_resource::<StateTransitionGuards<S>>();
+            self.add_systems(
+                StateTransition,
+                run_transition_guards::<S>
+                    .in_set(StateTransitionSteps::DependentTransitions)
+                    .before(ApplyStateTransition::<S>::default()),
+            );
+        }
+        self.world_mut()
+            .resource_mut::<StateTransitionGuards<S>>()
+            .add(guard);
+        self
+    }
+
     fn enable_state_scoped_entities<S: States>(&mut self) -> &mut Self {
         if !self
             .world()
@@ -276,6 +344,14 @@ impl AppExtStates for App {
         self
     }
 
+    fn add_state_transition_guard<S: FreelyMutableState, M>(
+        &mut self,
+        guard: impl IntoSystem<In<PendingTransition<S>>, TransitionGuardDecision<S>, M>,
+    ) -> &mut Self {
+        self.main_mut().add_state_transition_guard(guard);
+        self
+    }
+
     fn enable_state_scoped_entities<S: States>(&mut self) -> &mut Self {
         self.main_mut().enable_state_scoped_entities::<S>();
         self
@@ -315,13 +391,22 @@ impl Plugin for StatesPlugin {
 
 #[cfg(test)]
 mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
     use crate::{
         self as bevy_state,
         app::StatesPlugin,
-        state::{State, StateTransition, StateTransitionEvent},
+        state::{
+            NextState, OnExit, PendingTransition, State, StateTransition, StateTransitionEvent,
+            StateTransitionHistory, TransitionGuardDecision,
+        },
     };
     use bevy_app::App;
-    use bevy_ecs::event::Events;
+    use bevy_ecs::{
+        event::Events,
+        resource::Resource,
+        system::{In, ResMut},
+    };
     use bevy_state_macros::States;
 
     use super::AppExtStates;
@@ -373,4 +458,118 @@ mod tests {
         assert_eq!(last.exited, None);
         assert_eq!(last.entered, Some(TestState::C));
     }
+
+    #[derive(Resource, Default)]
+    struct ExitedA(bool);
+
+    fn cancel_leaving_a(
+        In(transition): In<PendingTransition<TestState>>,
+    ) -> TransitionGuardDecision<TestState> {
+        if transition.exited == TestState::A {
+            TransitionGuardDecision::Cancel
+        } else {
+            TransitionGuardDecision::Allow
+        }
+    }
+
+    fn redirect_b_to_c(
+        In(transition): In<PendingTransition<TestState>>,
+    ) -> TransitionGuardDecision<TestState> {
+        if transition.entered == TestState::B {
+            TransitionGuardDecision::Redirect(TestState::C)
+        } else {
+            TransitionGuardDecision::Allow
+        }
+    }
+
+    #[test]
+    fn transition_guard_can_cancel_transition() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin);
+        app.init_state::<TestState>()
+            .init_resource::<ExitedA>()
+            .add_systems(OnExit(TestState::A), |mut exited: ResMut<ExitedA>| {
+                exited.0 = true;
+            })
+            .add_state_transition_guard(cancel_leaving_a);
+
+        let world = app.world_mut();
+        world.run_schedule(StateTransition);
+        world.insert_resource(NextState::Pending(TestState::B));
+        world.run_schedule(StateTransition);
+
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::A);
+        assert!(matches!(
+            world.resource::<NextState<TestState>>(),
+            NextState::Unchanged
+        ));
+        assert!(!world.resource::<ExitedA>().0);
+
+        let record = world
+            .resource::<StateTransitionHistory<TestState>>()
+            .last()
+            .unwrap();
+        assert!(record.is_cancelled());
+        assert_eq!(record.exited, TestState::A);
+        assert_eq!(record.requested, TestState::B);
+        assert!(record.guard.as_ref().unwrap().contains("cancel_leaving_a"));
+    }
+
+    #[test]
+    fn transition_guards_see_previous_redirections() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin);
+        app.insert_state(TestState::B)
+            .add_state_transition_guard(redirect_b_to_c)
+            .add_state_transition_guard(|In(transition): In<PendingTransition<TestState>>| {
+                assert_eq!(transition.entered, TestState::C);
+                TransitionGuardDecision::Redirect(TestState::A)
+            });
+
+        let world = app.world_mut();
+        world.run_schedule(StateTransition);
+        world.insert_resource(NextState::Pending(TestState::B));
+        world.run_schedule(StateTransition);
+
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::A);
+        let record = world
+            .resource::<StateTransitionHistory<TestState>>()
+            .last()
+            .unwrap();
+        assert!(record.is_redirected());
+        assert_eq!(record.exited, TestState::B);
+        assert_eq!(record.requested, TestState::B);
+        assert_eq!(record.entered, Some(TestState::A));
+    }
+
+    #[test]
+    fn state_transition_history_keeps_last_transitions() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin);
+        app.init_state::<TestState>()
+            .insert_resource(StateTransitionHistory::<TestState>::new(2));
+
+        let world = app.world_mut();
+        world.run_schedule(StateTransition);
+        for state in [TestState::B, TestState::C, TestState::A] {
+            world.insert_resource(NextState::Pending(state));
+            world.run_schedule(StateTransition);
+        }
+
+        let history = world.resource::<StateTransitionHistory<TestState>>();
+        assert_eq!(history.len(), 2);
+        let transitions = history
+            .iter()
+            .map(|record| (record.exited.clone(), record.entered.clone().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            [(TestState::B, TestState::C), (TestState::C, TestState::A)]
+        );
+        let display = history.to_string();
+        let lines = display.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("B -> C"));
+        assert!(lines[1].starts_with("C -> A"));
+    }
 }
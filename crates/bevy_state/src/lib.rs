@@ -60,6 +60,10 @@ pub mod state_scoped;
 /// Provides [`App`](bevy_app::App) and [`SubApp`](bevy_app::SubApp) with methods for registering
 /// state-scoped events.
 pub mod state_scoped_events;
+#[cfg(feature = "bevy_app")]
+/// Provides [`StateScopedResources`](crate::state_scoped_resources::StateScopedResources) and
+/// [`App`](bevy_app::App) methods for registering state-scoped resources.
+pub mod state_scoped_resources;
 
 #[cfg(feature = "bevy_reflect")]
 /// Provides definitions for the basic traits required by the state system
@@ -71,7 +75,11 @@ pub mod reflect;
 pub mod prelude {
     #[cfg(feature = "bevy_app")]
     #[doc(hidden)]
-    pub use crate::{app::AppExtStates, state_scoped_events::StateScopedEventsAppExt};
+    pub use crate::{
+        app::AppExtStates,
+        state_scoped_events::StateScopedEventsAppExt,
+        state_scoped_resources::{StateScopedResources, StateScopedResourcesAppExt},
+    };
 
     #[cfg(feature = "bevy_reflect")]
     #[doc(hidden)]
@@ -83,8 +91,9 @@ pub mod prelude {
         condition::*,
         state::{
             last_transition, ComputedStates, EnterSchedules, ExitSchedules, NextState, OnEnter,
-            OnExit, OnTransition, State, StateSet, StateTransition, StateTransitionEvent, States,
-            SubStates, TransitionSchedules,
+            OnExit, OnTransition, PendingTransition, State, StateSet, StateTransition,
+            StateTransitionEvent, StateTransitionHistory, States, SubStates,
+            TransitionGuardDecision, TransitionSchedules,
         },
         state_scoped::StateScoped,
     };
@@ -1,3 +1,5 @@
+#[cfg(feature = "track_location")]
+use bevy_ecs::change_detection::DetectChanges;
 use bevy_ecs::{
     event::EventWriter,
     prelude::Schedule,
@@ -5,7 +7,10 @@ use bevy_ecs::{
     system::{Commands, IntoSystem, ResMut},
 };
 
-use super::{states::States, take_next_state, transitions::*, NextState, State};
+use super::{
+    states::States, take_next_state, transitions::*, NextState, State, StateTransitionGuards,
+    StateTransitionHistory, StateTransitionRecord,
+};
 
 /// This trait allows a state to be mutated directly using the [`NextState<S>`](crate::state::NextState) resource.
 ///
@@ -51,12 +56,32 @@ fn apply_state_transition<S: FreelyMutableState>(
     commands: Commands,
     current_state: Option<ResMut<State<S>>>,
     next_state: Option<ResMut<NextState<S>>>,
+    history: Option<ResMut<StateTransitionHistory<S>>>,
+    guards: Option<ResMut<StateTransitionGuards<S>>>,
 ) {
+    #[cfg(feature = "track_location")]
+    let source = next_state.as_ref().map(DetectChanges::changed_by);
+    #[cfg(not(feature = "track_location"))]
+    let source = None;
     let Some(next_state) = take_next_state(next_state) else {
         return;
     };
     let Some(current_state) = current_state else {
         return;
     };
+    let redirection = guards.and_then(|mut guards| guards.redirection.take());
+    if let Some(mut history) = history {
+        let (requested, guard) = match redirection {
+            Some((requested, guard)) => (requested, Some(guard)),
+            None => (next_state.clone(), None),
+        };
+        history.push(StateTransitionRecord {
+            exited: current_state.get().clone(),
+            requested,
+            entered: Some(next_state.clone()),
+            guard,
+            source,
+        });
+    }
     internal_apply_state_transition(event, commands, Some(current_state), Some(next_state));
 }
@@ -4,6 +4,8 @@ mod resources;
 mod state_set;
 mod states;
 mod sub_states;
+mod transition_guards;
+mod transition_history;
 mod transitions;
 
 pub use bevy_state_macros::*;
@@ -13,6 +15,8 @@ pub use resources::*;
 pub use state_set::*;
 pub use states::*;
 pub use sub_states::*;
+pub use transition_guards::*;
+pub use transition_history::*;
 pub use transitions::*;
 
 #[cfg(test)]
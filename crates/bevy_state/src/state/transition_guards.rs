@@ -0,0 +1,150 @@
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+
+#[cfg(feature = "track_location")]
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::{
+    change_detection::{DetectChangesMut, Mut},
+    resource::Resource,
+    system::{BoxedSystem, In, IntoSystem},
+    world::World,
+};
+use log::debug;
+
+use super::{
+    freely_mutable_state::FreelyMutableState,
+    resources::{NextState, State},
+    states::States,
+    transition_history::{StateTransitionHistory, StateTransitionRecord},
+};
+
+/// A transition of `S` queued in [`NextState<S>`], passed to the transition guards of `S`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTransition<S: States> {
+    /// The current state.
+    pub exited: S,
+    /// The state that will be entered, including the redirections of the previous guards.
+    pub entered: S,
+}
+
+/// What a transition guard does with a [`PendingTransition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransitionGuardDecision<S: States> {
+    /// Let the transition happen, unless a later guard decides otherwise.
+    Allow,
+    /// Cancel the transition, as if [`NextState::reset`] was called. The remaining guards don't run.
+    Cancel,
+    /// Enter this state instead. The remaining guards see the new target state.
+    Redirect(S),
+}
+
+struct TransitionGuard<S: States> {
+    system: BoxedSystem<In<PendingTransition<S>>, TransitionGuardDecision<S>>,
+    initialized: bool,
+}
+
+/// The transition guards of the state `S`, run in the order they were added.
+#[derive(Resource)]
+pub(crate) struct StateTransitionGuards<S: FreelyMutableState> {
+    guards: Vec<TransitionGuard<S>>,
+    /// The state requested before a guard redirected the transition, and the name of that guard.
+    pub(crate) redirection: Option<(S, Cow<'static, str>)>,
+}
+
+impl<S: FreelyMutableState> Default for StateTransitionGuards<S> {
+    fn default() -> Self {
+        Self {
+            guards: Vec::new(),
+            redirection: None,
+        }
+    }
+}
+
+impl<S: FreelyMutableState> StateTransitionGuards<S> {
+    pub(crate) fn add<M>(
+        &mut self,
+        guard: impl IntoSystem<In<PendingTransition<S>>, TransitionGuardDecision<S>, M>,
+    ) {
+        self.guards.push(TransitionGuard {
+            system: Box::new(IntoSystem::into_system(guard)),
+            initialized: false,
+        });
+    }
+}
+
+/// Runs the transition guards of `S` on the transition queued in [`NextState<S>`], if any.
+///
+/// Cancelled transitions are recorded in the [`StateTransitionHistory<S>`] here, while redirected
+/// ones are recorded once applied.
+pub(crate) fn run_transition_guards<S: FreelyMutableState>(world: &mut World) {
+    let Some(next_state) = world.get_resource_ref::<NextState<S>>() else {
+        return;
+    };
+    let NextState::Pending(requested) = &*next_state else {
+        return;
+    };
+    let requested = requested.clone();
+    #[cfg(feature = "track_location")]
+    let source = Some(next_state.changed_by());
+    #[cfg(not(feature = "track_location"))]
+    let source = None;
+    let Some(exited) = world
+        .get_resource::<State<S>>()
+        .map(|state| state.get().clone())
+    else {
+        return;
+    };
+
+    world.resource_scope(|world, mut guards: Mut<StateTransitionGuards<S>>| {
+        let guards = guards.bypass_change_detection();
+        let mut transition = PendingTransition {
+            exited,
+            entered: requested.clone(),
+        };
+        let mut redirected_by = None;
+        for guard in &mut guards.guards {
+            if !guard.initialized {
+                guard.system.initialize(world);
+                guard.initialized = true;
+            }
+            match guard.system.run(transition.clone(), world) {
+                TransitionGuardDecision::Allow => {}
+                TransitionGuardDecision::Cancel => {
+                    let name = guard.system.name();
+                    debug!(
+                        "transition guard `{}` cancelled transition {:?} -> {:?}",
+                        name, transition.exited, transition.entered
+                    );
+                    world.resource_mut::<NextState<S>>().reset();
+                    if let Some(mut history) = world.get_resource_mut::<StateTransitionHistory<S>>()
+                    {
+                        history.push(StateTransitionRecord {
+                            exited: transition.exited,
+                            requested,
+                            entered: None,
+                            guard: Some(name),
+                            source,
+                        });
+                    }
+                    return;
+                }
+                TransitionGuardDecision::Redirect(state) => {
+                    let name = guard.system.name();
+                    debug!(
+                        "transition guard `{}` redirected transition {:?} -> {:?} to {:?}",
+                        name, transition.exited, transition.entered, state
+                    );
+                    transition.entered = state;
+                    redirected_by = Some(name);
+                }
+            }
+        }
+
+        if let Some(name) = redirected_by {
+            // Bypass change detection to keep the location of the code that queued the transition.
+            *world
+                .resource_mut::<NextState<S>>()
+                .bypass_change_detection() = NextState::Pending(transition.entered);
+            guards.redirection = Some((requested, name));
+        }
+    });
+}
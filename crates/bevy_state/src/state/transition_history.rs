@@ -0,0 +1,167 @@
+use alloc::{borrow::Cow, collections::VecDeque};
+use core::{fmt, panic::Location};
+
+use bevy_ecs::resource::Resource;
+
+use super::states::States;
+
+/// A transition of `S` that was requested through [`NextState<S>`](super::NextState),
+/// as recorded in the [`StateTransitionHistory<S>`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateTransitionRecord<S: States> {
+    /// The state that was current when the transition was requested.
+    pub exited: S,
+    /// The state that was queued in [`NextState<S>`](super::NextState).
+    pub requested: S,
+    /// The state that was entered, or `None` if a transition guard cancelled the transition.
+    ///
+    /// This differs from [`requested`](Self::requested) when a guard redirected the transition.
+    pub entered: Option<S>,
+    /// The name of the last transition guard that cancelled or redirected the transition, if any.
+    pub guard: Option<Cow<'static, str>>,
+    /// The location of the code that queued the transition.
+    ///
+    /// This is only recorded when the `track_location` feature is enabled.
+    pub source: Option<&'static Location<'static>>,
+}
+
+impl<S: States> StateTransitionRecord<S> {
+    /// Returns `true` if a transition guard cancelled the transition.
+    pub fn is_cancelled(&self) -> bool {
+        self.entered.is_none()
+    }
+
+    /// Returns `true` if a transition guard redirected the transition to another state.
+    pub fn is_redirected(&self) -> bool {
+        self.entered
+            .as_ref()
+            .is_some_and(|entered| *entered != self.requested)
+    }
+}
+
+impl<S: States> fmt::Display for StateTransitionRecord<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.entered {
+            Some(entered) if self.is_redirected() => write!(
+                f,
+                "{:?} -> {:?} (redirected from {:?})",
+                self.exited, entered, self.requested
+            )?,
+            Some(entered) => write!(f, "{:?} -> {:?}", self.exited, entered)?,
+            None => write!(f, "{:?} -> {:?} (cancelled)", self.exited, self.requested)?,
+        }
+        if let Some(guard) = &self.guard {
+            write!(f, " by `{guard}`")?;
+        }
+        if let Some(source) = self.source {
+            write!(f, ", queued at {source}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The most recent transitions of the state `S` requested through [`NextState<S>`](super::NextState),
+/// oldest first.
+///
+/// This includes the transitions cancelled by a transition guard, and is mostly useful for
+/// debugging which code triggered each state change. Enable the `track_location` feature to
+/// record where each transition was queued.
+///
+/// The history is added when installing a state with `init_state` or `insert_state`, and its
+/// [`Display`](fmt::Display) implementation prints one transition per line:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_state::prelude::*;
+/// # #[derive(States, Clone, PartialEq, Eq, Hash, Debug, Default)]
+/// # enum GameState { #[default] Menu, InGame }
+/// fn print_history(history: Res<StateTransitionHistory<GameState>>) {
+///     println!("{}", *history);
+/// }
+/// ```
+///
+/// Transitions of [`SubStates`](super::SubStates) and [`ComputedStates`](super::ComputedStates)
+/// are not recorded.
+#[derive(Resource, Debug, Clone)]
+pub struct StateTransitionHistory<S: States> {
+    records: VecDeque<StateTransitionRecord<S>>,
+    capacity: usize,
+}
+
+impl<S: States> StateTransitionHistory<S> {
+    /// The number of transitions kept by [`StateTransitionHistory::default`].
+    pub const DEFAULT_CAPACITY: usize = 32;
+
+    /// Creates an empty history which keeps the last `capacity` transitions.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the number of transitions kept by this history.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the number of transitions kept by this history, dropping the oldest ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Returns the number of transitions in this history.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no transition was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the most recent transition, if any.
+    pub fn last(&self) -> Option<&StateTransitionRecord<S>> {
+        self.records.back()
+    }
+
+    /// Iterates over the recorded transitions, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &StateTransitionRecord<S>> + '_ {
+        self.records.iter()
+    }
+
+    /// Removes all the recorded transitions.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    pub(crate) fn push(&mut self, record: StateTransitionRecord<S>) {
+        self.records.push_back(record);
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        while self.records.len() > self.capacity {
+            self.records.pop_front();
+        }
+    }
+}
+
+impl<S: States> Default for StateTransitionHistory<S> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<S: States> fmt::Display for StateTransitionHistory<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, record) in self.records.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{record}")?;
+        }
+        Ok(())
+    }
+}
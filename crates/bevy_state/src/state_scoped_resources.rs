@@ -0,0 +1,290 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+
+#[cfg(feature = "bevy_reflect")]
+use alloc::string::String;
+use bevy_app::{App, SubApp};
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::{AppTypeRegistry, ReflectResource};
+use bevy_ecs::{
+    event::EventReader, resource::Resource, schedule::IntoSystemConfigs, system::Commands,
+    world::World,
+};
+use bevy_platform_support::collections::HashMap;
+#[cfg(feature = "bevy_reflect")]
+use log::warn;
+
+use crate::state::{StateTransition, StateTransitionEvent, StateTransitionSteps, States};
+
+fn remove_resource<R: Resource>(w: &mut World) {
+    w.remove_resource::<R>();
+}
+
+/// Resources and values whose lifetime is bound to a variant of the state `S`.
+///
+/// When exiting a state, the resources registered for it are removed from the world, and the
+/// values held for it are dropped. This is mostly useful for asset handles: holding the handles
+/// of a level here lets their assets unload as soon as the level is left.
+///
+/// Resources are registered with [`StateScopedResourcesAppExt`], and values can be held at any
+/// time with [`StateScopedResources::hold`]:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_state::prelude::*;
+/// # use bevy_state::state_scoped_resources::StateScopedResources;
+/// # #[derive(States, Clone, PartialEq, Eq, Hash, Debug, Default)]
+/// # enum GameState { #[default] Loading, InGame }
+/// # struct Handle;
+/// # fn load(_path: &str) -> Handle { Handle }
+/// fn load_level(mut scoped: ResMut<StateScopedResources<GameState>>) {
+///     // The handle is dropped when exiting `GameState::InGame`.
+///     scoped.hold(GameState::InGame, load("levels/forest.glb"));
+/// }
+/// ```
+///
+/// Note that the cleanup is ordered ambiguously relative to [`StateScoped`](crate::prelude::StateScoped) entity
+/// cleanup and the [`OnExit`](crate::prelude::OnExit) schedule for the exited state. All of these occur within
+/// schedule [`StateTransition`] and system set [`StateTransitionSteps::ExitSchedules`].
+#[derive(Resource)]
+pub struct StateScopedResources<S: States> {
+    cleanup_fns: HashMap<S, Vec<fn(&mut World)>>,
+    #[cfg(feature = "bevy_reflect")]
+    reflected: HashMap<S, Vec<String>>,
+    held: HashMap<S, Vec<Box<dyn Any + Send + Sync>>>,
+}
+
+impl<S: States> StateScopedResources<S> {
+    /// Registers the resource `R` to be removed when exiting `state`.
+    pub fn add_resource<R: Resource>(&mut self, state: S) {
+        self.cleanup_fns
+            .entry(state)
+            .or_default()
+            .push(remove_resource::<R>);
+    }
+
+    /// Registers the resource with the type path `type_path` to be removed when exiting `state`.
+    ///
+    /// The resource type must be registered in the [`AppTypeRegistry`] with [`ReflectResource`]
+    /// type data by the time the state is exited.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn add_reflect_resource(&mut self, state: S, type_path: impl Into<String>) {
+        self.reflected
+            .entry(state)
+            .or_default()
+            .push(type_path.into());
+    }
+
+    /// Holds `value` until exiting `state`, then drops it.
+    ///
+    /// Unlike registered resources, held values are only dropped once: holding a value for a
+    /// state that is exited and entered again needs to be done on each enter.
+    pub fn hold(&mut self, state: S, value: impl Any + Send + Sync) {
+        self.held.entry(state).or_default().push(Box::new(value));
+    }
+
+    fn cleanup(&mut self, w: &mut World, state: &S) {
+        if let Some(fns) = self.cleanup_fns.get(state) {
+            for callback in fns {
+                (*callback)(w);
+            }
+        }
+        #[cfg(feature = "bevy_reflect")]
+        if let Some(type_paths) = self.reflected.get(state) {
+            remove_reflect_resources(w, type_paths);
+        }
+        self.held.remove(state);
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn remove_reflect_resources(w: &mut World, type_paths: &[String]) {
+    let Some(registry) = w.get_resource::<AppTypeRegistry>().cloned() else {
+        warn!("State scoped resources were registered by type path, but there is no `AppTypeRegistry`.");
+        return;
+    };
+    let registry = registry.read();
+    for type_path in type_paths {
+        match registry
+            .get_with_type_path(type_path)
+            .and_then(|registration| registration.data::<ReflectResource>())
+        {
+            Some(reflect_resource) => reflect_resource.remove(w),
+            None => warn!(
+                "State scoped resource `{}` isn't registered with `ReflectResource` type data.",
+                type_path
+            ),
+        }
+    }
+}
+
+impl<S: States> Default for StateScopedResources<S> {
+    fn default() -> Self {
+        Self {
+            cleanup_fns: HashMap::default(),
+            #[cfg(feature = "bevy_reflect")]
+            reflected: HashMap::default(),
+            held: HashMap::default(),
+        }
+    }
+}
+
+/// Removes the resources and drops the values of [`StateScopedResources<S>`] registered for the
+/// exited state, if any.
+pub fn clear_state_scoped_resources<S: States>(
+    mut c: Commands,
+    mut transitions: EventReader<StateTransitionEvent<S>>,
+) {
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    if transition.entered == transition.exited {
+        return;
+    }
+    let Some(exited) = transition.exited.clone() else {
+        return;
+    };
+
+    c.queue(move |w: &mut World| {
+        w.resource_scope::<StateScopedResources<S>, ()>(|w, mut resources| {
+            resources.cleanup(w, &exited);
+        });
+    });
+}
+
+fn init_state_scoped_resources<S: States>(app: &mut SubApp) {
+    if !app.world().contains_resource::<StateScopedResources<S>>() {
+        app.init_resource::<StateScopedResources<S>>();
+        // Like state scoped entities, this runs for every exited variant of the state, unlike
+        // `OnExit`.
+        app.add_systems(
+            StateTransition,
+            clear_state_scoped_resources::<S>.in_set(StateTransitionSteps::ExitSchedules),
+        );
+    }
+}
+
+fn add_state_scoped_resource_impl<R: Resource, S: States>(app: &mut SubApp, state: S) {
+    init_state_scoped_resources::<S>(app);
+    app.world_mut()
+        .resource_mut::<StateScopedResources<S>>()
+        .add_resource::<R>(state);
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn add_reflect_state_scoped_resource_impl<S: States>(
+    app: &mut SubApp,
+    state: S,
+    type_path: String,
+) {
+    init_state_scoped_resources::<S>(app);
+    app.world_mut()
+        .resource_mut::<StateScopedResources<S>>()
+        .add_reflect_resource(state, type_path);
+}
+
+/// Extension trait for [`App`] adding methods for registering state scoped resources.
+///
+/// See [`StateScopedResources`] for more details.
+pub trait StateScopedResourcesAppExt {
+    /// Adds a [`Resource`] that is automatically removed when leaving the specified `state`.
+    ///
+    /// Removing a resource drops it, along with any asset handle it contains.
+    fn add_state_scoped_resource<R: Resource>(&mut self, state: impl States) -> &mut Self;
+
+    /// Adds the [`Resource`] with the type path `type_path`, which is automatically removed
+    /// when leaving the specified `state`.
+    ///
+    /// The resource type must be registered with [`ReflectResource`] type data.
+    #[cfg(feature = "bevy_reflect")]
+    fn add_reflect_state_scoped_resource(
+        &mut self,
+        state: impl States,
+        type_path: impl Into<String>,
+    ) -> &mut Self;
+}
+
+impl StateScopedResourcesAppExt for App {
+    fn add_state_scoped_resource<R: Resource>(&mut self, state: impl States) -> &mut Self {
+        self.main_mut().add_state_scoped_resource::<R>(state);
+        self
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    fn add_reflect_state_scoped_resource(
+        &mut self,
+        state: impl States,
+        type_path: impl Into<String>,
+    ) -> &mut Self {
+        self.main_mut()
+            .add_reflect_state_scoped_resource(state, type_path);
+        self
+    }
+}
+
+impl StateScopedResourcesAppExt for SubApp {
+    fn add_state_scoped_resource<R: Resource>(&mut self, state: impl States) -> &mut Self {
+        add_state_scoped_resource_impl::<R, _>(self, state);
+        self
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    fn add_reflect_state_scoped_resource(
+        &mut self,
+        state: impl States,
+        type_path: impl Into<String>,
+    ) -> &mut Self {
+        add_reflect_state_scoped_resource_impl(self, state, type_path.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use bevy_app::App;
+    use bevy_ecs::resource::Resource;
+    use bevy_state_macros::States;
+
+    use super::{StateScopedResources, StateScopedResourcesAppExt};
+    use crate::{
+        self as bevy_state,
+        app::{AppExtStates, StatesPlugin},
+        state::{NextState, StateTransition},
+    };
+
+    #[derive(States, Default, PartialEq, Eq, Hash, Debug, Clone)]
+    enum TestState {
+        #[default]
+        A,
+        B,
+    }
+
+    #[derive(Resource)]
+    struct LevelAssets;
+
+    #[test]
+    fn state_scoped_resources_are_cleared_on_exit() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_state::<TestState>()
+            .add_state_scoped_resource::<LevelAssets>(TestState::A);
+        app.world_mut().run_schedule(StateTransition);
+
+        let handle = Arc::new(());
+        app.insert_resource(LevelAssets);
+        app.world_mut()
+            .resource_mut::<StateScopedResources<TestState>>()
+            .hold(TestState::A, handle.clone());
+        assert_eq!(Arc::strong_count(&handle), 2);
+
+        app.world_mut()
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::B);
+        app.world_mut().run_schedule(StateTransition);
+
+        assert!(!app.world().contains_resource::<LevelAssets>());
+        assert_eq!(Arc::strong_count(&handle), 1);
+    }
+}
@@ -0,0 +1,446 @@
+use alloc::{boxed::Box, vec::Vec};
+use bevy_platform_support::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+use core::{any::Any, fmt, panic::AssertUnwindSafe};
+use futures_lite::FutureExt;
+
+use crate::{futures::check_ready, AsyncComputeTaskPool, Task};
+
+/// Identifies a job submitted to a [`JobGraph`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct JobId(u64);
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "job {}", self.0)
+    }
+}
+
+/// The state of a job in a [`JobGraph`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JobStatus {
+    /// The job waits for its dependencies to complete.
+    Waiting,
+    /// The job is running on the [`AsyncComputeTaskPool`].
+    Running,
+    /// The job returned its output, which can be retrieved with [`JobGraph::take_output`].
+    Completed,
+    /// The job was canceled with [`JobGraph::cancel`], or one of its dependencies didn't complete.
+    Canceled,
+    /// The job panicked.
+    Failed,
+}
+
+impl JobStatus {
+    /// Returns `true` if the job won't run anymore.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Canceled | Self::Failed)
+    }
+}
+
+/// A notification that a job of a [`JobGraph`] finished, returned by [`JobGraph::update`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct JobFinished {
+    /// The job which finished.
+    pub job: JobId,
+    /// Whether the job [completed](JobStatus::Completed), was [canceled](JobStatus::Canceled),
+    /// or [failed](JobStatus::Failed).
+    pub status: JobStatus,
+}
+
+/// Passed to a running job, to report its progress and check whether it was canceled.
+#[derive(Clone, Default)]
+pub struct JobProgress(Arc<JobProgressState>);
+
+#[derive(Default)]
+struct JobProgressState {
+    /// The bits of an `f32` between 0 and 1.
+    progress: AtomicU32,
+    canceled: AtomicBool,
+}
+
+impl JobProgress {
+    /// Reports the progress of the job, from 0 to 1.
+    pub fn set(&self, progress: f32) {
+        self.0
+            .progress
+            .store(progress.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Returns the last progress reported by the job, from 0 to 1.
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.0.progress.load(Ordering::Relaxed))
+    }
+
+    /// Returns `true` if the job was canceled with [`JobGraph::cancel`].
+    ///
+    /// Long-running jobs should check this regularly and return early when it's `true`: their
+    /// output is discarded anyway.
+    pub fn is_canceled(&self) -> bool {
+        self.0.canceled.load(Ordering::Relaxed)
+    }
+}
+
+type JobOutput = Box<dyn Any + Send>;
+type JobFn = Box<dyn FnOnce(&JobProgress) -> JobOutput + Send>;
+
+/// The parts of a job which aren't `Sync`.
+enum JobPayload {
+    Waiting(JobFn),
+    Running(Task<Result<JobOutput, Box<dyn Any + Send>>>),
+    Completed(JobOutput),
+    None,
+}
+
+struct Job {
+    status: JobStatus,
+    progress: JobProgress,
+    /// The number of dependencies which haven't completed yet.
+    pending_dependencies: usize,
+    dependents: Vec<JobId>,
+    // Only accessed through `&mut self`, so the lock is never contended.
+    payload: Mutex<JobPayload>,
+}
+
+impl Job {
+    fn payload(&mut self) -> &mut JobPayload {
+        self.payload
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A graph of jobs running on the [`AsyncComputeTaskPool`], each starting once the jobs it depends
+/// on are completed.
+///
+/// Unlike [`TaskPool::scope`](crate::TaskPool::scope), which blocks until all of its tasks are
+/// done, jobs can span many frames: [`JobGraph::update`] should be called regularly, usually once
+/// per frame, to start the jobs whose dependencies are completed and to collect the finished ones.
+///
+/// Jobs report their progress and check whether they were canceled through the [`JobProgress`]
+/// they are given.
+///
+/// ```
+/// # use bevy_tasks::{AsyncComputeTaskPool, JobGraph, TaskPool};
+/// # AsyncComputeTaskPool::get_or_init(TaskPool::new);
+/// let mut jobs = JobGraph::new();
+/// let heights = jobs.submit(&[], |progress| {
+///     let mut heights = Vec::new();
+///     for i in 0..100 {
+///         if progress.is_canceled() {
+///             break;
+///         }
+///         heights.push(i as f32);
+///         progress.set(i as f32 / 100.0);
+///     }
+///     heights
+/// });
+/// let mesh = jobs.submit(&[heights], |_| 42);
+///
+/// // Usually once per frame:
+/// for finished in jobs.update() {
+///     println!("{} finished: {:?}", finished.job, finished.status);
+/// }
+/// if let Some(mesh) = jobs.take_output::<i32>(mesh) {
+///     // ...
+/// }
+/// ```
+#[derive(Default)]
+pub struct JobGraph {
+    jobs: HashMap<JobId, Job>,
+    next_id: u64,
+    finished: Vec<JobFinished>,
+}
+
+impl JobGraph {
+    /// Creates an empty job graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submits a job, which starts once all of its `dependencies` are completed.
+    ///
+    /// Dependencies which were removed from the graph are considered completed. If a dependency is
+    /// canceled or fails, the job is canceled.
+    pub fn submit<T: Send + 'static>(
+        &mut self,
+        dependencies: &[JobId],
+        job: impl FnOnce(&JobProgress) -> T + Send + 'static,
+    ) -> JobId {
+        let id = JobId(self.next_id);
+        self.next_id += 1;
+
+        let mut pending_dependencies = 0;
+        let mut canceled = false;
+        for dependency in dependencies {
+            let Some(dependency) = self.jobs.get_mut(dependency) else {
+                continue;
+            };
+            match dependency.status {
+                JobStatus::Waiting | JobStatus::Running => {
+                    pending_dependencies += 1;
+                    dependency.dependents.push(id);
+                }
+                JobStatus::Completed => {}
+                JobStatus::Canceled | JobStatus::Failed => canceled = true,
+            }
+        }
+
+        self.jobs.insert(
+            id,
+            Job {
+                status: JobStatus::Waiting,
+                progress: JobProgress::default(),
+                pending_dependencies,
+                dependents: Vec::new(),
+                payload: Mutex::new(JobPayload::Waiting(Box::new(move |progress| {
+                    Box::new(job(progress))
+                }))),
+            },
+        );
+
+        if canceled {
+            self.cancel(id);
+        } else if pending_dependencies == 0 {
+            self.start(id);
+        }
+        id
+    }
+
+    /// Returns the status of a job, or `None` if it was removed from the graph.
+    pub fn status(&self, job: JobId) -> Option<JobStatus> {
+        self.jobs.get(&job).map(|job| job.status)
+    }
+
+    /// Returns the last progress reported by a job, from 0 to 1, or `None` if it was removed from
+    /// the graph.
+    pub fn progress(&self, job: JobId) -> Option<f32> {
+        self.jobs.get(&job).map(|job| job.progress.get())
+    }
+
+    /// Iterates over the jobs of the graph with their status.
+    pub fn iter(&self) -> impl Iterator<Item = (JobId, JobStatus)> + '_ {
+        self.jobs.iter().map(|(id, job)| (*id, job.status))
+    }
+
+    /// Returns the number of jobs in the graph, including the finished jobs which weren't removed.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns `true` if the graph has no jobs.
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Cancels a job and the jobs depending on it.
+    ///
+    /// A waiting job is canceled immediately, while a running job is only notified through
+    /// [`JobProgress::is_canceled`], and is reported as canceled by [`JobGraph::update`] once it
+    /// returns.
+    pub fn cancel(&mut self, job: JobId) {
+        let mut to_cancel = Vec::from([job]);
+        while let Some(id) = to_cancel.pop() {
+            let Some(job) = self.jobs.get_mut(&id) else {
+                continue;
+            };
+            job.progress.0.canceled.store(true, Ordering::Relaxed);
+            // Running jobs finish on their own, but nothing depending on them may start anymore.
+            to_cancel.append(&mut job.dependents);
+            if job.status != JobStatus::Waiting {
+                continue;
+            }
+            job.status = JobStatus::Canceled;
+            *job.payload() = JobPayload::None;
+            self.finished.push(JobFinished {
+                job: id,
+                status: JobStatus::Canceled,
+            });
+        }
+    }
+
+    /// Removes a completed job from the graph and returns its output, if it's of type `T`.
+    pub fn take_output<T: 'static>(&mut self, job: JobId) -> Option<T> {
+        let entry = self.jobs.get_mut(&job)?;
+        let JobPayload::Completed(output) = core::mem::replace(entry.payload(), JobPayload::None)
+        else {
+            return None;
+        };
+        match output.downcast::<T>() {
+            Ok(output) => {
+                self.jobs.remove(&job);
+                Some(*output)
+            }
+            Err(output) => {
+                *entry.payload() = JobPayload::Completed(output);
+                None
+            }
+        }
+    }
+
+    /// Removes a finished job from the graph, dropping its output.
+    ///
+    /// Returns `false` if the job isn't finished, in which case it's not removed.
+    pub fn remove(&mut self, job: JobId) -> bool {
+        if !self.status(job).is_some_and(JobStatus::is_finished) {
+            return false;
+        }
+        self.jobs.remove(&job);
+        true
+    }
+
+    /// Collects the jobs which finished since the last call and starts the jobs depending on them.
+    ///
+    /// Returns the jobs which finished since the last call, including the canceled jobs which
+    /// weren't running.
+    pub fn update(&mut self) -> impl Iterator<Item = JobFinished> + '_ {
+        let running: Vec<JobId> = self
+            .jobs
+            .iter()
+            .filter(|(_, job)| job.status == JobStatus::Running)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in running {
+            let job = self.jobs.get_mut(&id).unwrap();
+            let JobPayload::Running(task) = job.payload() else {
+                continue;
+            };
+            let Some(result) = check_ready(task) else {
+                continue;
+            };
+
+            let status = match result {
+                Ok(_) if job.progress.is_canceled() => {
+                    *job.payload() = JobPayload::None;
+                    JobStatus::Canceled
+                }
+                Ok(output) => {
+                    *job.payload() = JobPayload::Completed(output);
+                    JobStatus::Completed
+                }
+                Err(_) => {
+                    *job.payload() = JobPayload::None;
+                    JobStatus::Failed
+                }
+            };
+            job.status = status;
+            let dependents = core::mem::take(&mut job.dependents);
+            self.finished.push(JobFinished { job: id, status });
+
+            for dependent in dependents {
+                if status != JobStatus::Completed {
+                    self.cancel(dependent);
+                    continue;
+                }
+                let Some(dependent_job) = self.jobs.get_mut(&dependent) else {
+                    continue;
+                };
+                dependent_job.pending_dependencies -= 1;
+                if dependent_job.pending_dependencies == 0 {
+                    self.start(dependent);
+                }
+            }
+        }
+
+        self.finished.drain(..)
+    }
+
+    fn start(&mut self, id: JobId) {
+        let Some(job) = self.jobs.get_mut(&id) else {
+            return;
+        };
+        let JobPayload::Waiting(run) = core::mem::replace(job.payload(), JobPayload::None) else {
+            return;
+        };
+        let progress = job.progress.clone();
+        let task = AsyncComputeTaskPool::get()
+            .spawn(AssertUnwindSafe(async move { run(&progress) }).catch_unwind());
+        job.status = JobStatus::Running;
+        *job.payload() = JobPayload::Running(task);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputeTaskPool, IoTaskPool, TaskPool};
+
+    fn init_task_pools() {
+        ComputeTaskPool::get_or_init(TaskPool::new);
+        AsyncComputeTaskPool::get_or_init(TaskPool::new);
+        IoTaskPool::get_or_init(TaskPool::new);
+    }
+
+    fn run_until_finished(jobs: &mut JobGraph, job: JobId) -> Vec<JobFinished> {
+        let mut finished = Vec::new();
+        while !jobs.status(job).unwrap().is_finished() {
+            crate::tick_global_task_pools_on_main_thread();
+            finished.extend(jobs.update());
+            std::thread::yield_now();
+        }
+        finished
+    }
+
+    #[test]
+    fn dependencies() {
+        init_task_pools();
+        let mut jobs = JobGraph::new();
+
+        let first = jobs.submit(&[], |progress| {
+            progress.set(0.5);
+            1
+        });
+        let second = jobs.submit(&[first], |_| 2);
+        assert_eq!(jobs.status(second), Some(JobStatus::Waiting));
+
+        let finished = run_until_finished(&mut jobs, second);
+        assert_eq!(
+            finished,
+            [
+                JobFinished {
+                    job: first,
+                    status: JobStatus::Completed
+                },
+                JobFinished {
+                    job: second,
+                    status: JobStatus::Completed
+                }
+            ]
+        );
+        assert_eq!(jobs.progress(first), Some(0.5));
+        assert_eq!(jobs.take_output::<u32>(first), None);
+        assert_eq!(jobs.take_output::<i32>(first), Some(1));
+        assert_eq!(jobs.take_output::<i32>(second), Some(2));
+        assert!(jobs.is_empty());
+    }
+
+    #[test]
+    fn cancel() {
+        init_task_pools();
+        let mut jobs = JobGraph::new();
+
+        let running = jobs.submit(&[], JobProgress::is_canceled);
+        let waiting = jobs.submit(&[running], |_| ());
+        let failing = jobs.submit(&[], |_| panic!("job failed"));
+        let after_failing = jobs.submit(&[failing], |_| ());
+
+        jobs.cancel(running);
+        assert_eq!(jobs.status(running), Some(JobStatus::Running));
+        assert_eq!(jobs.status(waiting), Some(JobStatus::Canceled));
+
+        run_until_finished(&mut jobs, running);
+        run_until_finished(&mut jobs, after_failing);
+        assert_eq!(jobs.status(running), Some(JobStatus::Canceled));
+        assert_eq!(jobs.take_output::<bool>(running), None);
+        assert_eq!(jobs.status(failing), Some(JobStatus::Failed));
+        assert_eq!(jobs.status(after_failing), Some(JobStatus::Canceled));
+        assert!(jobs.remove(failing));
+        assert_eq!(jobs.status(failing), None);
+    }
+}
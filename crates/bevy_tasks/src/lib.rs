@@ -85,6 +85,11 @@ pub use futures_lite::future::poll_once;
 mod iter;
 pub use iter::ParallelIterator;
 
+#[cfg(feature = "std")]
+mod job_graph;
+#[cfg(feature = "std")]
+pub use job_graph::{JobFinished, JobGraph, JobId, JobProgress, JobStatus};
+
 pub use futures_lite;
 
 /// The tasks prelude.
@@ -42,6 +42,8 @@ mod pipeline;
 mod text;
 mod text2d;
 mod text_access;
+mod text_cache;
+mod text_cursor;
 
 pub use bounds::*;
 pub use error::*;
@@ -54,6 +56,7 @@ pub use pipeline::*;
 pub use text::*;
 pub use text2d::*;
 pub use text_access::*;
+pub use text_cache::*;
 
 /// The text prelude.
 ///
@@ -61,8 +64,8 @@ pub use text_access::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        Font, JustifyText, LineBreak, Text2d, Text2dReader, Text2dWriter, TextColor, TextError,
-        TextFont, TextLayout, TextSpan,
+        Font, JustifyText, LineBreak, Text2d, Text2dReader, Text2dWriter, TextColor,
+        TextDropShadow, TextError, TextFont, TextGradient, TextLayout, TextOutline, TextSpan,
     };
 }
 
@@ -110,6 +113,9 @@ impl Plugin for TextPlugin {
             .register_type::<TextFont>()
             .register_type::<LineHeight>()
             .register_type::<TextColor>()
+            .register_type::<TextOutline>()
+            .register_type::<TextDropShadow>()
+            .register_type::<TextGradient>()
             .register_type::<TextSpan>()
             .register_type::<TextBounds>()
             .register_type::<TextLayout>()
@@ -138,7 +144,13 @@ impl Plugin for TextPlugin {
                     .in_set(Update2dText)
                     .after(Animation),
             )
-            .add_systems(Last, trim_cosmic_cache);
+            .add_systems(
+                Last,
+                (
+                    trim_cosmic_cache,
+                    (evict_modified_fonts, trim_text_caches).chain(),
+                ),
+            );
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.add_systems(
@@ -16,8 +16,10 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use cosmic_text::{Attrs, Buffer, Family, Metrics, Shaping, Wrap};
 
 use crate::{
-    error::TextError, ComputedTextBlock, Font, FontAtlasSets, FontSmoothing, JustifyText,
-    LineBreak, PositionedGlyph, TextBounds, TextEntity, TextFont, TextLayout, YAxisOrientation,
+    error::TextError,
+    text_cache::{GlyphRun, GlyphRunKey, ShapingKey, TextCaches},
+    ComputedTextBlock, Font, FontAtlasSets, FontSmoothing, JustifyText, LineBreak, PositionedGlyph,
+    TextBounds, TextEntity, TextFont, TextLayout, YAxisOrientation,
 };
 
 /// A wrapper resource around a [`cosmic_text::FontSystem`]
@@ -73,12 +75,17 @@ pub struct TextPipeline {
     spans_buffer: Vec<(usize, &'static str, &'static TextFont, FontFaceInfo)>,
     /// Buffered vec for collecting info for glyph assembly.
     glyph_info: Vec<(AssetId<Font>, FontSmoothing)>,
+    /// Shaping results and glyph runs reused across frames and entities.
+    pub(crate) caches: TextCaches,
 }
 
 impl TextPipeline {
     /// Utilizes [`cosmic_text::Buffer`] to shape and layout text
     ///
     /// Negative or 0.0 font sizes will not be laid out.
+    ///
+    /// Text that was recently shaped with the same content, fonts and wrapping configuration, by any entity,
+    /// is copied from a cache instead of being shaped again. See [`TextPipeline::cache_stats`].
     pub fn update_buffer<'a>(
         &mut self,
         fonts: &Assets<Font>,
@@ -90,7 +97,36 @@ impl TextPipeline {
         computed: &mut ComputedTextBlock,
         font_system: &mut CosmicFontSystem,
     ) -> Result<(), TextError> {
+        self.shape(
+            fonts,
+            text_spans,
+            linebreak,
+            justify,
+            bounds,
+            scale_factor,
+            computed,
+            font_system,
+        )
+        .map(|_| ())
+    }
+
+    /// Shapes text into the buffer of `computed`, reusing a cached result if the same text was shaped
+    /// with the same configuration recently.
+    ///
+    /// Returns the key identifying the shaped text.
+    fn shape<'a>(
+        &mut self,
+        fonts: &Assets<Font>,
+        text_spans: impl Iterator<Item = (Entity, usize, &'a str, &'a TextFont, Color)>,
+        linebreak: LineBreak,
+        justify: JustifyText,
+        bounds: TextBounds,
+        scale_factor: f64,
+        computed: &mut ComputedTextBlock,
+        font_system: &mut CosmicFontSystem,
+    ) -> Result<ShapingKey, TextError> {
         let font_system = &mut font_system.0;
+        let mut key = ShapingKey::new(linebreak, justify, bounds, scale_factor);
 
         // Collect span information into a vec. This is necessary because font loading requires mut access
         // to FontSystem, which the cosmic-text Buffer also needs.
@@ -107,6 +143,7 @@ impl TextPipeline {
         for (span_index, (entity, depth, span, text_font, color)) in text_spans.enumerate() {
             // Save this span entity in the computed text block.
             computed.entities.push(TextEntity { entity, depth });
+            key.push_span(span, text_font);
 
             if span.is_empty() {
                 continue;
@@ -149,6 +186,23 @@ impl TextPipeline {
             spans.push((span_index, span, text_font, face_info, color));
         }
 
+        let frame = self.caches.frame();
+        if let Some(buffer) = self.caches.shaping.get(&key, frame, |_| true) {
+            computed.buffer.clone_from(buffer);
+
+            spans.clear();
+            self.spans_buffer = spans
+                .into_iter()
+                .map(
+                    |_| -> (usize, &'static str, &'static TextFont, FontFaceInfo) {
+                        unreachable!()
+                    },
+                )
+                .collect();
+
+            return Ok(key);
+        }
+
         let mut metrics = Metrics::new(font_size, line_height).scale(scale_factor as f32);
         // Metrics of 0.0 cause `Buffer::set_metrics` to panic. We hack around this by 'falling
         // through' to call `Buffer::set_rich_text` with zero spans so any cached text will be cleared without
@@ -202,6 +256,10 @@ impl TextPipeline {
             buffer.set_size(font_system, Some(dimensions.x), bounds.height);
         }
 
+        self.caches
+            .shaping
+            .insert(key.clone(), computed.buffer.clone(), frame);
+
         // Recover the spans buffer.
         spans.clear();
         self.spans_buffer = spans
@@ -209,7 +267,7 @@ impl TextPipeline {
             .map(|_| -> (usize, &'static str, &'static TextFont, FontFaceInfo) { unreachable!() })
             .collect();
 
-        Ok(())
+        Ok(key)
     }
 
     /// Queues text for rendering
@@ -245,7 +303,7 @@ impl TextPipeline {
             glyph_info.push((text_font.font.id(), text_font.font_smoothing));
         });
 
-        let update_result = self.update_buffer(
+        let shape_result = self.shape(
             fonts,
             text_spans,
             layout.linebreak,
@@ -255,9 +313,32 @@ impl TextPipeline {
             computed,
             font_system,
         );
-        if let Err(err) = update_result {
+        let shaping_key = match shape_result {
+            Ok(key) => key,
+            Err(err) => {
+                self.glyph_info = glyph_info;
+                return Err(err);
+            }
+        };
+
+        // Reuse the glyphs of identical text, as long as the atlases they were placed in still exist.
+        let glyph_run_key = GlyphRunKey::new(
+            shaping_key,
+            glyph_info.iter().map(|(_, font_smoothing)| *font_smoothing),
+            &y_axis_orientation,
+        );
+        let frame = self.caches.frame();
+        if let Some(run) = self.caches.glyph_runs.get(&glyph_run_key, frame, |run| {
+            run.glyphs.iter().all(|glyph| {
+                font_atlas_sets
+                    .sets
+                    .contains_key(&glyph_info[glyph.span_index].0)
+            })
+        }) {
+            layout_info.glyphs.extend_from_slice(&run.glyphs);
+            layout_info.size = run.size;
             self.glyph_info = glyph_info;
-            return Err(err);
+            return Ok(());
         }
 
         let buffer = &mut computed.buffer;
@@ -342,6 +423,14 @@ impl TextPipeline {
         result?;
 
         layout_info.size = box_size;
+        self.caches.glyph_runs.insert(
+            glyph_run_key,
+            GlyphRun {
+                glyphs: layout_info.glyphs.clone(),
+                size: box_size,
+            },
+            frame,
+        );
         Ok(())
     }
 
@@ -8,6 +8,7 @@ use bevy_asset::Handle;
 use bevy_color::Color;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_math::Vec2;
 use bevy_reflect::prelude::*;
 use bevy_utils::once;
 use cosmic_text::{Buffer, Metrics};
@@ -397,6 +398,84 @@ impl TextColor {
     pub const WHITE: Self = TextColor(Color::WHITE);
 }
 
+/// Draws an outline around the glyphs of this text section.
+///
+/// Like [`TextColor`], this can be set on the root text entity and on each [`TextSpan`].
+/// Text effects are currently only rendered for UI text.
+#[derive(Component, Copy, Clone, Debug, Reflect, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct TextOutline {
+    /// Width of the outline in logical pixels.
+    pub width: f32,
+    /// Color of the outline.
+    pub color: Color,
+}
+
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            width: 1.,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// Draws a drop shadow behind the glyphs of this text section.
+///
+/// Like [`TextColor`], this can be set on the root text entity and on each [`TextSpan`].
+/// The shadow also covers the [`TextOutline`] of the section, if any.
+/// Text effects are currently only rendered for UI text.
+#[derive(Component, Copy, Clone, Debug, Reflect, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct TextDropShadow {
+    /// Displacement of the shadow in logical pixels.
+    pub offset: Vec2,
+    /// Blur radius of the shadow in logical pixels. With a value of zero the shadow is sharp.
+    pub blur: f32,
+    /// Color of the shadow.
+    pub color: Color,
+}
+
+impl Default for TextDropShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::splat(2.),
+            blur: 2.,
+            color: Color::linear_rgba(0., 0., 0., 0.75),
+        }
+    }
+}
+
+/// Fills the glyphs of this text section with a vertical gradient, from the top to the bottom of
+/// the text block, instead of their [`TextColor`].
+///
+/// Like [`TextColor`], this can be set on the root text entity and on each [`TextSpan`].
+/// Text effects are currently only rendered for UI text.
+#[derive(Component, Copy, Clone, Debug, Reflect, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct TextGradient {
+    /// Color at the top of the text block.
+    pub top: Color,
+    /// Color at the bottom of the text block.
+    pub bottom: Color,
+}
+
+impl TextGradient {
+    /// Creates a vertical gradient from `top` to `bottom`.
+    pub fn new(top: impl Into<Color>, bottom: impl Into<Color>) -> Self {
+        Self {
+            top: top.into(),
+            bottom: bottom.into(),
+        }
+    }
+}
+
+impl Default for TextGradient {
+    fn default() -> Self {
+        Self::new(Color::WHITE, Color::BLACK)
+    }
+}
+
 /// Determines how lines will be broken when preventing text from running out of bounds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
 #[reflect(Serialize, Deserialize)]
@@ -0,0 +1,380 @@
+use bevy_app::prelude::*;
+use bevy_asset::{AssetEvent, AssetId};
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_platform_support::collections::HashMap;
+use core::hash::Hash;
+use smallvec::SmallVec;
+
+use crate::{
+    CosmicBuffer, Font, FontSmoothing, JustifyText, LineBreak, PositionedGlyph, TextBounds,
+    TextFont, TextPipeline, YAxisOrientation,
+};
+
+/// The number of frames a cached shaping result or glyph run is kept without being used.
+pub const TEXT_CACHE_MAX_AGE: u32 = 60;
+
+/// Identifies the input of a shaping pass.
+///
+/// Text color is deliberately not part of the key: it doesn't affect shaping, and glyph colors are
+/// looked up from the span entities when rendering.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ShapingKey {
+    /// The text of all spans, concatenated.
+    text: String,
+    spans: SmallVec<[ShapingSpanKey; 1]>,
+    linebreak: LineBreak,
+    justify: JustifyText,
+    /// The bits of the bounds' width and height.
+    bounds: [Option<u32>; 2],
+    /// The bits of the scale factor.
+    scale_factor: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ShapingSpanKey {
+    /// The end of the span in [`ShapingKey::text`].
+    end: usize,
+    font: AssetId<Font>,
+    font_size: u32,
+    line_height: u32,
+}
+
+impl ShapingKey {
+    pub(crate) fn new(
+        linebreak: LineBreak,
+        justify: JustifyText,
+        bounds: TextBounds,
+        scale_factor: f64,
+    ) -> Self {
+        Self {
+            text: String::new(),
+            spans: SmallVec::new(),
+            linebreak,
+            justify,
+            bounds: [
+                bounds.width.map(f32::to_bits),
+                bounds.height.map(f32::to_bits),
+            ],
+            scale_factor: scale_factor.to_bits(),
+        }
+    }
+
+    /// Adds a span to the key. Every span of the block must be pushed, including empty ones,
+    /// so that span indices stored in the shaped buffer stay valid.
+    pub(crate) fn push_span(&mut self, span: &str, text_font: &TextFont) {
+        self.text.push_str(span);
+        self.spans.push(ShapingSpanKey {
+            end: self.text.len(),
+            font: text_font.font.id(),
+            font_size: text_font.font_size.to_bits(),
+            line_height: text_font.line_height.eval(text_font.font_size).to_bits(),
+        });
+    }
+
+    /// Returns `true` if any span of the key uses the font `id`.
+    fn uses_font(&self, id: AssetId<Font>) -> bool {
+        self.spans.iter().any(|span| span.font == id)
+    }
+}
+
+/// Identifies a run of positioned glyphs, produced from a shaped buffer.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct GlyphRunKey {
+    shaping: ShapingKey,
+    font_smoothing: SmallVec<[FontSmoothing; 1]>,
+    top_to_bottom: bool,
+}
+
+impl GlyphRunKey {
+    pub(crate) fn new(
+        shaping: ShapingKey,
+        font_smoothing: impl Iterator<Item = FontSmoothing>,
+        y_axis_orientation: &YAxisOrientation,
+    ) -> Self {
+        Self {
+            shaping,
+            font_smoothing: font_smoothing.collect(),
+            top_to_bottom: matches!(y_axis_orientation, YAxisOrientation::TopToBottom),
+        }
+    }
+}
+
+/// Positioned glyphs of a text block, ready to be copied into a [`TextLayoutInfo`](crate::TextLayoutInfo).
+pub(crate) struct GlyphRun {
+    pub(crate) glyphs: Vec<PositionedGlyph>,
+    pub(crate) size: Vec2,
+}
+
+/// A map whose entries are discarded once they haven't been used for [`TEXT_CACHE_MAX_AGE`] frames.
+pub(crate) struct AgedCache<K, V> {
+    entries: HashMap<K, (V, u32)>,
+    hits: u32,
+    misses: u32,
+}
+
+impl<K, V> Default for AgedCache<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::default(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> AgedCache<K, V> {
+    /// Returns the entry for `key` if there is one and `is_valid` accepts it, marking it as used in `frame`.
+    pub(crate) fn get(&mut self, key: &K, frame: u32, is_valid: impl Fn(&V) -> bool) -> Option<&V> {
+        match self.entries.get_mut(key) {
+            Some((value, last_used)) if is_valid(value) => {
+                *last_used = frame;
+                self.hits += 1;
+                Some(value)
+            }
+            _ => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: K, value: V, frame: u32) {
+        self.entries.insert(key, (value, frame));
+    }
+
+    fn trim(&mut self, frame: u32) {
+        self.entries
+            .retain(|_, (_, last_used)| frame.wrapping_sub(*last_used) <= TEXT_CACHE_MAX_AGE);
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        self.entries.retain(|key, _| keep(key));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the hits and misses since the last call, and resets them.
+    fn take_counts(&mut self) -> (u32, u32) {
+        (
+            core::mem::take(&mut self.hits),
+            core::mem::take(&mut self.misses),
+        )
+    }
+}
+
+/// Shaping results and glyph runs shared by all text entities, owned by the [`TextPipeline`].
+#[derive(Default)]
+pub(crate) struct TextCaches {
+    frame: u32,
+    pub(crate) shaping: AgedCache<ShapingKey, CosmicBuffer>,
+    pub(crate) glyph_runs: AgedCache<GlyphRunKey, GlyphRun>,
+    stats: TextCacheStats,
+}
+
+impl TextCaches {
+    pub(crate) fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Records the statistics of the current frame and discards entries that are no longer used.
+    fn end_frame(&mut self) {
+        let (shaping_hits, shaping_misses) = self.shaping.take_counts();
+        let (glyph_run_hits, glyph_run_misses) = self.glyph_runs.take_counts();
+        self.shaping.trim(self.frame);
+        self.glyph_runs.trim(self.frame);
+        self.stats = TextCacheStats {
+            shaping_hits,
+            shaping_misses,
+            shaping_entries: self.shaping.entries.len(),
+            glyph_run_hits,
+            glyph_run_misses,
+            glyph_run_entries: self.glyph_runs.entries.len(),
+        };
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.shaping.clear();
+        self.glyph_runs.clear();
+    }
+
+    /// Discards the entries that use the font `id`.
+    fn remove_font(&mut self, id: AssetId<Font>) {
+        self.shaping.retain(|key| !key.uses_font(id));
+        self.glyph_runs.retain(|key| !key.shaping.uses_font(id));
+    }
+}
+
+/// Statistics about the text caches of the [`TextPipeline`] during the last complete frame.
+///
+/// Text with the same content, fonts, sizes and wrapping configuration is only shaped once,
+/// and text laid out identically reuses the same positioned glyphs, even across entities.
+///
+/// See [`TextPipeline::cache_stats`], and [`TextCacheDiagnosticsPlugin`] to record these as diagnostics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextCacheStats {
+    /// The number of text blocks whose shaping was reused.
+    pub shaping_hits: u32,
+    /// The number of text blocks that had to be shaped.
+    pub shaping_misses: u32,
+    /// The number of shaped text blocks kept in the cache.
+    pub shaping_entries: usize,
+    /// The number of text blocks whose positioned glyphs were reused.
+    pub glyph_run_hits: u32,
+    /// The number of text blocks whose glyphs had to be positioned.
+    pub glyph_run_misses: u32,
+    /// The number of glyph runs kept in the cache.
+    pub glyph_run_entries: usize,
+}
+
+impl TextPipeline {
+    /// Returns statistics about the shaping and glyph run caches during the last complete frame.
+    pub fn cache_stats(&self) -> TextCacheStats {
+        self.caches.stats
+    }
+
+    /// Discards all cached shaping results and glyph runs.
+    pub fn clear_caches(&mut self) {
+        self.caches.clear();
+    }
+}
+
+/// Discards the cached shaping results and glyph runs of fonts that were modified or removed, so
+/// that hot-reloaded fonts are shaped again.
+pub(crate) fn evict_modified_fonts(
+    mut text_pipeline: ResMut<TextPipeline>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+) {
+    for event in font_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            text_pipeline.caches.remove_font(*id);
+        }
+    }
+}
+
+/// Records the cache statistics of the current frame and discards stale cache entries.
+pub(crate) fn trim_text_caches(mut text_pipeline: ResMut<TextPipeline>) {
+    text_pipeline.caches.end_frame();
+}
+
+/// Adds diagnostics for the hits and misses of the [`TextPipeline`] caches to an App.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct TextCacheDiagnosticsPlugin;
+
+impl Plugin for TextCacheDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::SHAPING_CACHE_HITS))
+            .register_diagnostic(Diagnostic::new(Self::SHAPING_CACHE_MISSES))
+            .register_diagnostic(Diagnostic::new(Self::GLYPH_RUN_CACHE_HITS))
+            .register_diagnostic(Diagnostic::new(Self::GLYPH_RUN_CACHE_MISSES))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl TextCacheDiagnosticsPlugin {
+    /// The number of text blocks whose shaping was reused per frame.
+    pub const SHAPING_CACHE_HITS: DiagnosticPath =
+        DiagnosticPath::const_new("text/shaping_cache_hits");
+    /// The number of text blocks shaped per frame.
+    pub const SHAPING_CACHE_MISSES: DiagnosticPath =
+        DiagnosticPath::const_new("text/shaping_cache_misses");
+    /// The number of text blocks whose positioned glyphs were reused per frame.
+    pub const GLYPH_RUN_CACHE_HITS: DiagnosticPath =
+        DiagnosticPath::const_new("text/glyph_run_cache_hits");
+    /// The number of text blocks whose glyphs were positioned per frame.
+    pub const GLYPH_RUN_CACHE_MISSES: DiagnosticPath =
+        DiagnosticPath::const_new("text/glyph_run_cache_misses");
+
+    /// Records the [`TextCacheStats`] of the last frame.
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, text_pipeline: Res<TextPipeline>) {
+        let stats = text_pipeline.cache_stats();
+        diagnostics.add_measurement(&Self::SHAPING_CACHE_HITS, || stats.shaping_hits as f64);
+        diagnostics.add_measurement(&Self::SHAPING_CACHE_MISSES, || stats.shaping_misses as f64);
+        diagnostics.add_measurement(&Self::GLYPH_RUN_CACHE_HITS, || stats.glyph_run_hits as f64);
+        diagnostics.add_measurement(&Self::GLYPH_RUN_CACHE_MISSES, || {
+            stats.glyph_run_misses as f64
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::weak_handle;
+
+    #[test]
+    fn shaping_key_separates_spans() {
+        let font = TextFont::default();
+        let mut a = ShapingKey::new(
+            LineBreak::default(),
+            JustifyText::default(),
+            TextBounds::UNBOUNDED,
+            1.0,
+        );
+        let mut b = a.clone();
+        a.push_span("ab", &font);
+        a.push_span("c", &font);
+        b.push_span("a", &font);
+        b.push_span("bc", &font);
+        assert!(a != b);
+
+        let mut c = ShapingKey::new(
+            LineBreak::default(),
+            JustifyText::default(),
+            TextBounds::UNBOUNDED,
+            2.0,
+        );
+        c.push_span("ab", &font);
+        c.push_span("c", &font);
+        assert!(a != c);
+    }
+
+    #[test]
+    fn unused_entries_are_trimmed() {
+        let mut cache = AgedCache::<u32, u32>::default();
+        cache.insert(1, 10, 0);
+        cache.insert(2, 20, 0);
+        assert_eq!(cache.get(&1, 0, |_| true), Some(&10));
+        assert_eq!(cache.get(&3, 0, |_| true), None);
+        assert_eq!(cache.get(&2, 0, |_| false), None);
+        assert_eq!(cache.take_counts(), (1, 2));
+
+        assert_eq!(cache.get(&1, TEXT_CACHE_MAX_AGE, |_| true), Some(&10));
+        cache.trim(TEXT_CACHE_MAX_AGE + 1);
+        assert_eq!(cache.entries.len(), 1);
+        assert!(cache.entries.contains_key(&1));
+    }
+
+    #[test]
+    fn modified_fonts_are_evicted() {
+        let font = TextFont::default();
+        let other_font = TextFont {
+            font: weak_handle!("2b4e8d1f-6c3a-4f97-a5e2-9d0b7c18f364"),
+            ..Default::default()
+        };
+        let mut a = ShapingKey::new(
+            LineBreak::default(),
+            JustifyText::default(),
+            TextBounds::UNBOUNDED,
+            1.0,
+        );
+        let mut b = a.clone();
+        a.push_span("a", &font);
+        b.push_span("b", &other_font);
+
+        let mut caches = TextCaches::default();
+        caches.shaping.insert(a.clone(), CosmicBuffer::default(), 0);
+        caches.shaping.insert(b.clone(), CosmicBuffer::default(), 0);
+        caches.remove_font(font.font.id());
+        assert!(!caches.shaping.entries.contains_key(&a));
+        assert!(caches.shaping.entries.contains_key(&b));
+    }
+}
@@ -0,0 +1,113 @@
+//! Maps between the positions of a laid out [`ComputedTextBlock`] and the byte offsets of its text,
+//! to implement text editing.
+
+use core::ops::Range;
+
+use bevy_math::{Rect, Vec2};
+use cosmic_text::{Affinity, Cursor, LayoutRun};
+
+use crate::ComputedTextBlock;
+
+/// Positions in this module are in physical pixels, relative to the top left corner of the text
+/// block, with the y axis pointing down.
+///
+/// Byte offsets index the text of the whole block: the text of its spans concatenated, in the
+/// order of [`ComputedTextBlock::entities`].
+impl ComputedTextBlock {
+    /// Returns the byte offset in the text of the block closest to `position`, in physical pixels
+    /// relative to the top left corner of the text.
+    ///
+    /// Returns `None` if the text was not laid out yet.
+    pub fn hit(&self, position: Vec2) -> Option<usize> {
+        let cursor = self.buffer.hit(position.x, position.y)?;
+        Some(self.byte_offset(cursor))
+    }
+
+    /// Returns the rectangle of a caret placed at the byte offset `byte` in the text of the block,
+    /// with a width of zero and the height of its line.
+    ///
+    /// Returns `None` if the text was not laid out yet.
+    pub fn caret_rect(&self, byte: usize) -> Option<Rect> {
+        let cursor = self.cursor(byte);
+        let mut line_end = None;
+        for run in self.buffer.layout_runs() {
+            if run.line_i != cursor.line {
+                continue;
+            }
+            let caret = |x| {
+                Rect::from_corners(
+                    Vec2::new(x, run.line_top),
+                    Vec2::new(x, run.line_top + run.line_height),
+                )
+            };
+            if run.glyphs.is_empty() {
+                return Some(caret(0.0));
+            }
+            // A caret between two wrapped runs is placed at the start of the second run.
+            for glyph in run.glyphs {
+                let (start_x, end_x) = if run.rtl {
+                    (glyph.x + glyph.w, glyph.x)
+                } else {
+                    (glyph.x, glyph.x + glyph.w)
+                };
+                if glyph.start == cursor.index {
+                    return Some(caret(start_x));
+                }
+                if glyph.end == cursor.index {
+                    line_end = Some(caret(end_x));
+                }
+            }
+        }
+        line_end
+    }
+
+    /// Returns the rectangles covering the text between the byte offsets of `range`, one for
+    /// each laid out line crossed by the range.
+    pub fn selection_rects(&self, range: Range<usize>) -> impl Iterator<Item = Rect> + '_ {
+        let start = Cursor {
+            affinity: Affinity::After,
+            ..self.cursor(range.start)
+        };
+        let end = Cursor {
+            affinity: Affinity::Before,
+            ..self.cursor(range.end)
+        };
+        let runs = (range.start < range.end).then(|| self.buffer.layout_runs());
+        runs.into_iter()
+            .flatten()
+            .filter_map(move |run: LayoutRun| {
+                let (x, width) = run.highlight(start, end)?;
+                (width > 0.0).then(|| {
+                    Rect::from_corners(
+                        Vec2::new(x, run.line_top),
+                        Vec2::new(x + width, run.line_top + run.line_height),
+                    )
+                })
+            })
+    }
+
+    /// Converts a byte offset in the text of the block to a line and an offset in that line.
+    fn cursor(&self, byte: usize) -> Cursor {
+        let lines = &self.buffer.lines;
+        let mut line_start = 0;
+        for (line_i, line) in lines.iter().enumerate() {
+            let len = line.text().len();
+            if byte <= line_start + len || line_i + 1 == lines.len() {
+                return Cursor::new(line_i, byte.saturating_sub(line_start).min(len));
+            }
+            line_start += len + line.ending().as_str().len();
+        }
+        Cursor::new(0, 0)
+    }
+
+    /// Converts a line and an offset in that line to a byte offset in the text of the block.
+    fn byte_offset(&self, cursor: Cursor) -> usize {
+        self.buffer
+            .lines
+            .iter()
+            .take(cursor.line)
+            .map(|line| line.text().len() + line.ending().as_str().len())
+            .sum::<usize>()
+            + cursor.index
+    }
+}
@@ -0,0 +1,344 @@
+use bevy_app::App;
+use bevy_ecs::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, IntoSystemConfigs, ScheduleLabel, SystemSet},
+    world::World,
+};
+use bevy_platform_support::collections::HashMap;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+use core::time::Duration;
+
+use crate::time::Time;
+
+/// The clock of a schedule group, following the clock of the schedule running the group.
+///
+/// A specialization of the [`Time`] structure. **For method documentation, see
+/// [`Time<Group>#impl-Time<Group>`].**
+///
+/// A schedule group is a schedule run by a system of another schedule, its parent, which is
+/// added with [`ScheduleGroupAppExt::add_schedule_group`]. Groups can be nested: the parent of a
+/// group can itself be a group. The clocks of all groups are stored in the [`ScheduleGroups`]
+/// resource, and the clock of a group is set as the generic [`Time`] resource while the group
+/// runs, so that its systems see the time elapsed since the group last ran.
+///
+/// A group without a [`timestep()`](Time::timestep) runs every time its parent runs, with the
+/// same [`delta()`](Time::delta) as its parent. A group with a timestep runs at most once per
+/// run of its parent, when at least one timestep has elapsed since it last ran on average, and
+/// reports a `delta()` equal to the time elapsed since it last ran. Unlike
+/// [`Time<Fixed>`](crate::Fixed), missed runs aren't caught up, which makes groups suitable for
+/// logic that only needs to run at a lower rate than the frame rate, like AI.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::{prelude::*, schedule::ScheduleLabel};
+/// # use bevy_time::prelude::*;
+/// # use bevy_time::{Group, RunScheduleGroup, ScheduleGroupAppExt};
+/// #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Ai;
+///
+/// # #[derive(Resource)]
+/// # struct Paused;
+/// fn build(app: &mut App) {
+///     app.add_schedule_group(Update, Ai, Time::<Group>::from_hz(10.0))
+///         .configure_sets(
+///             Update,
+///             RunScheduleGroup::new(Ai).run_if(not(resource_exists::<Paused>)),
+///         )
+///         .add_systems(Ai, think);
+/// }
+///
+/// fn think(_time: Res<Time>) {
+///     // `_time.delta()` is the time elapsed since the `Ai` group last ran, around 100ms.
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect))]
+pub struct Group {
+    timestep: Option<Duration>,
+    overstep: Duration,
+    pending: Duration,
+}
+
+impl Time<Group> {
+    /// Returns a new clock for a group running every `timestep`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestep` is zero.
+    pub fn from_duration(timestep: Duration) -> Self {
+        let mut ret = Self::default();
+        ret.set_timestep(timestep);
+        ret
+    }
+
+    /// Returns a new clock for a group running every `seconds`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seconds` is zero, negative or not finite.
+    pub fn from_seconds(seconds: f64) -> Self {
+        let mut ret = Self::default();
+        ret.set_timestep_seconds(seconds);
+        ret
+    }
+
+    /// Returns a new clock for a group running `hz` times per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    pub fn from_hz(hz: f64) -> Self {
+        let mut ret = Self::default();
+        ret.set_timestep_hz(hz);
+        ret
+    }
+
+    /// Returns the amount of time the group waits between runs, if it is rate limited.
+    #[inline]
+    pub fn timestep(&self) -> Option<Duration> {
+        self.context().timestep
+    }
+
+    /// Sets the amount of time the group waits between runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestep` is zero.
+    #[inline]
+    pub fn set_timestep(&mut self, timestep: Duration) {
+        assert_ne!(
+            timestep,
+            Duration::ZERO,
+            "attempted to set group timestep to zero"
+        );
+        self.context_mut().timestep = Some(timestep);
+    }
+
+    /// Sets the amount of time the group waits between runs, in seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seconds` is zero, negative or not finite.
+    #[inline]
+    pub fn set_timestep_seconds(&mut self, seconds: f64) {
+        assert!(
+            seconds.is_sign_positive(),
+            "seconds less than or equal to zero"
+        );
+        assert!(seconds.is_finite(), "seconds is infinite");
+        self.set_timestep(Duration::from_secs_f64(seconds));
+    }
+
+    /// Sets the number of times per second the group runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    #[inline]
+    pub fn set_timestep_hz(&mut self, hz: f64) {
+        assert!(hz.is_sign_positive(), "Hz less than or equal to zero");
+        assert!(hz.is_finite(), "Hz is infinite");
+        self.set_timestep_seconds(1.0 / hz);
+    }
+
+    /// Removes the timestep of the group, so that it runs every time its parent runs.
+    #[inline]
+    pub fn clear_timestep(&mut self) {
+        let context = self.context_mut();
+        context.timestep = None;
+        context.overstep = Duration::ZERO;
+    }
+
+    /// Returns the amount of time accumulated toward the next run of the group.
+    #[inline]
+    pub fn overstep(&self) -> Duration {
+        self.context().overstep
+    }
+
+    /// Accumulates `delta` from the parent clock, and advances the clock if the group should
+    /// run.
+    fn tick(&mut self, delta: Duration) -> bool {
+        let context = self.context_mut();
+        context.pending += delta;
+        if let Some(timestep) = context.timestep {
+            context.overstep += delta;
+            let Some(overstep) = context.overstep.checked_sub(timestep) else {
+                return false;
+            };
+            // Missed runs are dropped rather than caught up on the next runs of the parent.
+            context.overstep = if overstep < timestep {
+                overstep
+            } else {
+                Duration::from_nanos((overstep.as_nanos() % timestep.as_nanos()) as u64)
+            };
+        }
+        let pending = core::mem::take(&mut context.pending);
+        self.advance_by(pending);
+        true
+    }
+}
+
+/// The clocks of the schedule groups, added with [`ScheduleGroupAppExt::add_schedule_group`].
+#[derive(Resource, Debug, Default)]
+pub struct ScheduleGroups {
+    clocks: HashMap<InternedScheduleLabel, Time<Group>>,
+}
+
+impl ScheduleGroups {
+    /// Returns the clock of the schedule group `label`.
+    pub fn get(&self, label: impl ScheduleLabel) -> Option<&Time<Group>> {
+        self.clocks.get(&label.intern())
+    }
+
+    /// Returns the clock of the schedule group `label` mutably, to change its timestep.
+    pub fn get_mut(&mut self, label: impl ScheduleLabel) -> Option<&mut Time<Group>> {
+        self.clocks.get_mut(&label.intern())
+    }
+
+    /// Inserts the clock of the schedule group `label`, returning the previous one if any.
+    pub fn insert(&mut self, label: impl ScheduleLabel, time: Time<Group>) -> Option<Time<Group>> {
+        self.clocks.insert(label.intern(), time)
+    }
+}
+
+/// The system set of the system running a schedule group in its parent schedule.
+///
+/// Run conditions added to this set with [`App::configure_sets`] are the run conditions of the
+/// group. When they prevent the group from running, its clock doesn't advance.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunScheduleGroup(pub InternedScheduleLabel);
+
+impl RunScheduleGroup {
+    /// Returns the set of the system running the schedule group `label`.
+    pub fn new(label: impl ScheduleLabel) -> Self {
+        Self(label.intern())
+    }
+}
+
+/// Runs the schedule group `label` if its clock allows it, with its clock set as the generic
+/// [`Time`] resource.
+fn run_schedule_group(world: &mut World, label: InternedScheduleLabel) {
+    let parent_time = *world.resource::<Time>();
+    let Some(time) = world
+        .get_resource_mut::<ScheduleGroups>()
+        .and_then(|mut groups| {
+            let time = groups.clocks.get_mut(&label)?;
+            time.tick(parent_time.delta()).then_some(*time)
+        })
+    else {
+        return;
+    };
+
+    *world.resource_mut::<Time>() = time.as_generic();
+    let _ = world.try_run_schedule(label);
+    *world.resource_mut::<Time>() = parent_time;
+}
+
+/// Extension trait for [`App`] adding methods for schedule groups.
+pub trait ScheduleGroupAppExt {
+    /// Adds the schedule `group`, run by a system of the schedule `parent` following the clock
+    /// `time`.
+    ///
+    /// The system running the group is in the [`RunScheduleGroup`] set of the group. See
+    /// [`Time<Group>`](Group) for more details.
+    fn add_schedule_group(
+        &mut self,
+        parent: impl ScheduleLabel,
+        group: impl ScheduleLabel,
+        time: Time<Group>,
+    ) -> &mut Self;
+}
+
+impl ScheduleGroupAppExt for App {
+    fn add_schedule_group(
+        &mut self,
+        parent: impl ScheduleLabel,
+        group: impl ScheduleLabel,
+        time: Time<Group>,
+    ) -> &mut Self {
+        let group = group.intern();
+        self.init_schedule(group);
+        self.world_mut()
+            .get_resource_or_init::<ScheduleGroups>()
+            .insert(group, time);
+        self.add_systems(
+            parent,
+            (move |world: &mut World| run_schedule_group(world, group))
+                .in_set(RunScheduleGroup(group)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+    use bevy_app::Update;
+    use bevy_ecs::system::{Res, ResMut};
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Ai;
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Slow;
+
+    #[derive(Resource, Default)]
+    struct Deltas {
+        ai: Vec<Duration>,
+        slow: Vec<Duration>,
+    }
+
+    #[test]
+    fn test_tick() {
+        let mut time = Time::<Group>::from_seconds(2.0);
+
+        assert!(!time.tick(Duration::from_secs(1)));
+        assert_eq!(time.delta(), Duration::ZERO);
+        assert_eq!(time.overstep(), Duration::from_secs(1));
+
+        assert!(time.tick(Duration::from_secs(1)));
+        assert_eq!(time.delta(), Duration::from_secs(2));
+        assert_eq!(time.elapsed(), Duration::from_secs(2));
+        assert_eq!(time.overstep(), Duration::ZERO);
+
+        // Only runs once, and drops the missed run.
+        assert!(time.tick(Duration::from_secs(5)));
+        assert_eq!(time.delta(), Duration::from_secs(5));
+        assert_eq!(time.overstep(), Duration::from_secs(1));
+
+        time.clear_timestep();
+        assert!(time.tick(Duration::from_secs(1)));
+        assert_eq!(time.delta(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_nested_groups() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Deltas>()
+            .add_schedule_group(Update, Ai, Time::<Group>::from_hz(2.0))
+            .add_schedule_group(Ai, Slow, Time::<Group>::from_hz(1.0))
+            .add_systems(Ai, |time: Res<Time>, mut deltas: ResMut<Deltas>| {
+                deltas.ai.push(time.delta());
+            })
+            .add_systems(Slow, |time: Res<Time>, mut deltas: ResMut<Deltas>| {
+                deltas.slow.push(time.delta());
+            });
+
+        for _ in 0..4 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(250));
+            app.update();
+        }
+
+        let deltas = app.world().resource::<Deltas>();
+        assert_eq!(deltas.ai, vec![Duration::from_millis(500); 2]);
+        assert_eq!(deltas.slow, vec![Duration::from_secs(1)]);
+        assert_eq!(
+            app.world().resource::<Time>().delta(),
+            Duration::from_millis(250)
+        );
+    }
+}
@@ -15,15 +15,19 @@ extern crate alloc;
 /// Common run conditions
 pub mod common_conditions;
 mod fixed;
+mod group;
 mod real;
 mod stopwatch;
+mod test_app;
 mod time;
 mod timer;
 mod virt;
 
 pub use fixed::*;
+pub use group::*;
 pub use real::*;
 pub use stopwatch::*;
+pub use test_app::*;
 pub use time::*;
 pub use timer::*;
 pub use virt::*;
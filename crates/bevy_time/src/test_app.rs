@@ -0,0 +1,58 @@
+use crate::TimeUpdateStrategy;
+use bevy_app::TestApp;
+use core::time::Duration;
+
+/// Extends [`TestApp`] to control the passage of time.
+pub trait TestAppTimeExt {
+    /// Makes [`Time`](crate::Time) advance by exactly `delta` every frame, instead of following
+    /// the system clock.
+    ///
+    /// As always, time doesn't advance during the first frame of the app. The
+    /// [`Virtual`](crate::Virtual) time still clamps `delta` to its maximum delta, and is scaled
+    /// by its relative speed.
+    fn set_frame_delta(&mut self, delta: Duration) -> &mut Self;
+
+    /// Runs as many frames as needed for `duration` to pass, with the delta set by
+    /// [`set_frame_delta`](Self::set_frame_delta).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frame delta wasn't set.
+    fn step_for(&mut self, duration: Duration) -> &mut Self;
+}
+
+impl TestAppTimeExt for TestApp {
+    fn set_frame_delta(&mut self, delta: Duration) -> &mut Self {
+        self.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+        self
+    }
+
+    fn step_for(&mut self, duration: Duration) -> &mut Self {
+        let Some(TimeUpdateStrategy::ManualDuration(delta)) =
+            self.world().get_resource::<TimeUpdateStrategy>()
+        else {
+            panic!("`TestAppTimeExt::step_for` requires a frame delta set with `set_frame_delta`");
+        };
+        let frames = duration.as_nanos().div_ceil(delta.as_nanos().max(1));
+        self.step_frames(frames.try_into().unwrap_or(u32::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TestAppTimeExt, Time, TimePlugin};
+    use bevy_app::TestApp;
+    use core::time::Duration;
+
+    #[test]
+    fn fixed_frame_delta() {
+        let mut app = TestApp::new(TimePlugin);
+        app.set_frame_delta(Duration::from_millis(10)).step();
+        assert_eq!(app.resource::<Time>().elapsed(), Duration::ZERO);
+
+        app.step_for(Duration::from_millis(95));
+        let time = app.resource::<Time>();
+        assert_eq!(time.delta(), Duration::from_millis(10));
+        assert_eq!(time.elapsed(), Duration::from_millis(100));
+    }
+}
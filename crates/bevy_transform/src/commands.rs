@@ -2,7 +2,14 @@
 //! while preserving [`GlobalTransform`].
 
 use crate::prelude::{GlobalTransform, Transform};
-use bevy_ecs::{entity::Entity, hierarchy::ChildOf, system::EntityCommands, world::EntityWorldMut};
+use alloc::{vec, vec::Vec};
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::{ChildOf, Children},
+    system::EntityCommands,
+    world::{EntityWorldMut, World},
+};
+use bevy_math::Affine3A;
 
 /// Collection of methods similar to the built-in parenting methods on [`EntityWorldMut`] and [`EntityCommands`], but preserving each
 /// entity's [`GlobalTransform`].
@@ -78,3 +85,222 @@ impl BuildChildrenTransformExt for EntityWorldMut<'_> {
         self
     }
 }
+
+/// Collection of methods on [`EntityWorldMut`] and [`EntityCommands`] to move an entity in world
+/// space, by updating its [`Transform`] and the [`GlobalTransform`] of its descendants.
+///
+/// The [`GlobalTransform`]s are updated right away, so that they can be read before the next
+/// transform propagation. Since they're computed from the current [`GlobalTransform`] of the entity
+/// and of its parent, the ones modified since the last propagation aren't taken into account.
+/// Entities without a [`GlobalTransform`] are left untouched.
+///
+/// To change the parent of an entity while preserving its [`GlobalTransform`], see
+/// [`BuildChildrenTransformExt::set_parent_in_place`].
+pub trait TransformHierarchyExt {
+    /// Moves this entity to `transform` in world space, along with its descendants.
+    ///
+    /// Note that the updates will only execute the next time commands are applied
+    /// (during [`ApplyDeferred`](bevy_ecs::schedule::ApplyDeferred)) when using [`EntityCommands`].
+    fn set_global_transform(&mut self, transform: GlobalTransform) -> &mut Self;
+
+    /// Moves this entity to `transform` in world space, while its children keep their
+    /// [`GlobalTransform`], by updating their [`Transform`] too.
+    ///
+    /// Note that the updates will only execute the next time commands are applied
+    /// (during [`ApplyDeferred`](bevy_ecs::schedule::ApplyDeferred)) when using [`EntityCommands`].
+    fn set_global_transform_preserving_children(&mut self, transform: GlobalTransform)
+        -> &mut Self;
+
+    /// Applies `delta` to this entity in world space, along with its descendants.
+    ///
+    /// The new [`GlobalTransform`] of each entity of the subtree is `delta` applied after the
+    /// current one, so a translation moves the subtree along the world axes, and a rotation turns
+    /// it around the world origin. The subtree is traversed once.
+    ///
+    /// Note that the updates will only execute the next time commands are applied
+    /// (during [`ApplyDeferred`](bevy_ecs::schedule::ApplyDeferred)) when using [`EntityCommands`].
+    fn apply_global_delta(&mut self, delta: Transform) -> &mut Self;
+}
+
+impl TransformHierarchyExt for EntityCommands<'_> {
+    fn set_global_transform(&mut self, transform: GlobalTransform) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.set_global_transform(transform);
+        })
+    }
+
+    fn set_global_transform_preserving_children(
+        &mut self,
+        transform: GlobalTransform,
+    ) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.set_global_transform_preserving_children(transform);
+        })
+    }
+
+    fn apply_global_delta(&mut self, delta: Transform) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.apply_global_delta(delta);
+        })
+    }
+}
+
+impl TransformHierarchyExt for EntityWorldMut<'_> {
+    fn set_global_transform(&mut self, transform: GlobalTransform) -> &mut Self {
+        let Some(current) = self.get::<GlobalTransform>() else {
+            return self;
+        };
+        let delta = transform.affine() * current.affine().inverse();
+        let root = self.id();
+        self.world_scope(|world| transform_subtree(world, root, delta));
+        self
+    }
+
+    fn set_global_transform_preserving_children(
+        &mut self,
+        transform: GlobalTransform,
+    ) -> &mut Self {
+        let entity = self.id();
+        self.world_scope(|world| {
+            if update_global_transform(world, entity, transform).is_none() {
+                return;
+            }
+            let Some(children) = world.get::<Children>(entity) else {
+                return;
+            };
+            for child in children.iter().copied().collect::<Vec<_>>() {
+                // Recompute the `Transform` of the child relative to the new parent transform.
+                if let Some(&child_global) = world.get::<GlobalTransform>(child) {
+                    update_global_transform(world, child, child_global);
+                }
+            }
+        });
+        self
+    }
+
+    fn apply_global_delta(&mut self, delta: Transform) -> &mut Self {
+        let root = self.id();
+        self.world_scope(|world| transform_subtree(world, root, delta.compute_affine()));
+        self
+    }
+}
+
+/// Sets the [`GlobalTransform`] of `entity`, and updates its [`Transform`] to match.
+fn update_global_transform(
+    world: &mut World,
+    entity: Entity,
+    transform: GlobalTransform,
+) -> Option<()> {
+    let parent = world
+        .get::<ChildOf>(entity)
+        .and_then(|child_of| world.get::<GlobalTransform>(child_of.get()))
+        .copied();
+    *world.get_mut::<Transform>(entity)? = match parent {
+        Some(parent) => transform.reparented_to(&parent),
+        None => transform.compute_transform(),
+    };
+    *world.get_mut::<GlobalTransform>(entity)? = transform;
+    Some(())
+}
+
+/// Applies `delta` to the [`GlobalTransform`] of `root` and of all its descendants, and updates
+/// the [`Transform`] of `root` to match.
+///
+/// The descendants keep their [`Transform`], since they move along with `root`.
+fn transform_subtree(world: &mut World, root: Entity, delta: Affine3A) {
+    let Some(&root_global) = world.get::<GlobalTransform>(root) else {
+        return;
+    };
+    if update_global_transform(
+        world,
+        root,
+        GlobalTransform::from(delta * root_global.affine()),
+    )
+    .is_none()
+    {
+        return;
+    }
+
+    let mut stack = vec![root];
+    while let Some(entity) = stack.pop() {
+        if entity != root {
+            if let Some(mut global) = world.get_mut::<GlobalTransform>(entity) {
+                *global = GlobalTransform::from(delta * global.affine());
+            }
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{Quat, Vec3};
+    use core::f32::consts::FRAC_PI_2;
+
+    fn spawn_hierarchy(world: &mut World) -> (Entity, Entity, Entity) {
+        let parent = Transform::from_xyz(1.0, 0.0, 0.0);
+        let child = Transform::from_xyz(0.0, 1.0, 0.0);
+        let grandchild = Transform::from_xyz(0.0, 0.0, 1.0);
+        let parent_global = GlobalTransform::from(parent);
+        let child_global = parent_global * child;
+        let parent = world.spawn((parent, parent_global)).id();
+        let child = world.spawn((child, child_global, ChildOf(parent))).id();
+        let grandchild = world
+            .spawn((grandchild, child_global * grandchild, ChildOf(child)))
+            .id();
+        (parent, child, grandchild)
+    }
+
+    fn translation(world: &World, entity: Entity) -> Vec3 {
+        world.get::<GlobalTransform>(entity).unwrap().translation()
+    }
+
+    #[test]
+    fn apply_global_delta_moves_subtree() {
+        let mut world = World::new();
+        let (parent, child, grandchild) = spawn_hierarchy(&mut world);
+
+        world
+            .entity_mut(child)
+            .apply_global_delta(Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2)));
+
+        // The child turned around the world origin, carrying the grandchild with it.
+        assert!(translation(&world, parent).abs_diff_eq(Vec3::X, 1e-5));
+        assert!(translation(&world, child).abs_diff_eq(Vec3::new(-1.0, 1.0, 0.0), 1e-5));
+        assert!(translation(&world, grandchild).abs_diff_eq(Vec3::new(-1.0, 1.0, 1.0), 1e-5));
+        let child_transform = world.get::<Transform>(child).unwrap();
+        assert!(child_transform
+            .translation
+            .abs_diff_eq(Vec3::new(-2.0, 1.0, 0.0), 1e-5));
+        assert_eq!(
+            *world.get::<Transform>(grandchild).unwrap(),
+            Transform::from_xyz(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn set_global_transform_preserving_children() {
+        let mut world = World::new();
+        let (parent, child, grandchild) = spawn_hierarchy(&mut world);
+
+        world
+            .entity_mut(parent)
+            .set_global_transform(GlobalTransform::from_xyz(2.0, 0.0, 0.0));
+        assert!(translation(&world, grandchild).abs_diff_eq(Vec3::new(2.0, 1.0, 1.0), 1e-5));
+
+        world
+            .entity_mut(parent)
+            .set_global_transform_preserving_children(GlobalTransform::from_xyz(0.0, 0.0, 0.0));
+        assert!(translation(&world, parent).abs_diff_eq(Vec3::ZERO, 1e-5));
+        assert!(translation(&world, child).abs_diff_eq(Vec3::new(2.0, 1.0, 0.0), 1e-5));
+        assert!(world
+            .get::<Transform>(child)
+            .unwrap()
+            .translation
+            .abs_diff_eq(Vec3::new(2.0, 1.0, 0.0), 1e-5));
+        assert!(translation(&world, grandchild).abs_diff_eq(Vec3::new(2.0, 1.0, 1.0), 1e-5));
+    }
+}
@@ -43,7 +43,7 @@ pub mod prelude {
     #[cfg(feature = "bevy-support")]
     #[doc(hidden)]
     pub use crate::{
-        commands::BuildChildrenTransformExt,
+        commands::{BuildChildrenTransformExt, TransformHierarchyExt},
         helper::TransformHelper,
         plugins::{TransformPlugin, TransformSystem},
         traits::TransformPoint,
@@ -13,5 +13,6 @@
 //! as they may not be fully supported, functional or stable.
 
 mod ghost_hierarchy;
+pub mod text_input;
 
 pub use ghost_hierarchy::*;
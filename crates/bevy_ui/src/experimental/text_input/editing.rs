@@ -0,0 +1,307 @@
+//! The editing operations of a [`TextInput`](super::TextInput), independent of the UI.
+
+use core::ops::Range;
+
+use super::TextInputState;
+
+/// The kinds of characters separating words when moving or deleting by words.
+#[derive(PartialEq, Eq)]
+enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_whitespace() {
+            Self::Whitespace
+        } else if c.is_alphanumeric() || c == '_' {
+            Self::Word
+        } else {
+            Self::Punctuation
+        }
+    }
+}
+
+fn previous_char_boundary(text: &str, index: usize) -> usize {
+    text[..index]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+fn next_char_boundary(text: &str, index: usize) -> usize {
+    text[index..]
+        .chars()
+        .next()
+        .map_or(index, |c| index + c.len_utf8())
+}
+
+/// Returns the start of the word before `index`, skipping the whitespace before it.
+fn previous_word_boundary(text: &str, index: usize) -> usize {
+    let mut chars = text[..index].char_indices().rev().peekable();
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    let Some(&(_, first)) = chars.peek() else {
+        return 0;
+    };
+    let class = CharClass::of(first);
+    let mut start = index;
+    while let Some((i, _)) = chars.next_if(|(_, c)| CharClass::of(*c) == class) {
+        start = i;
+    }
+    start
+}
+
+/// Returns the end of the word after `index`, and of the whitespace after it.
+fn next_word_boundary(text: &str, index: usize) -> usize {
+    let mut chars = text[index..].char_indices().peekable();
+    if let Some(&(_, first)) = chars.peek() {
+        let class = CharClass::of(first);
+        while chars.next_if(|(_, c)| CharClass::of(*c) == class).is_some() {}
+    }
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    chars.peek().map_or(text.len(), |(i, _)| index + i)
+}
+
+fn line_start(text: &str, index: usize) -> usize {
+    text[..index].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(text: &str, index: usize) -> usize {
+    text[index..].find('\n').map_or(text.len(), |i| index + i)
+}
+
+/// Removes the characters that can't be inserted in the text, and replaces the line breaks with
+/// spaces when `multiline` is false.
+pub(super) fn sanitize(text: &str, multiline: bool) -> String {
+    text.replace("\r\n", "\n")
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' if !multiline => Some(' '),
+            '\r' => Some('\n'),
+            '\n' | '\t' => Some(c),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+impl TextInputState {
+    /// Moves the cursor to `cursor`, moving the anchor of the selection with it unless `extend`
+    /// is true.
+    ///
+    /// `cursor` must be on a char boundary of the value of the input.
+    pub fn set_cursor(&mut self, cursor: usize, extend: bool) {
+        self.cursor = cursor;
+        if !extend {
+            self.anchor = cursor;
+        }
+    }
+
+    /// Selects the whole `value`.
+    pub fn select_all(&mut self, value: &str) {
+        self.anchor = 0;
+        self.cursor = value.len();
+    }
+
+    /// Clamps the cursor and the anchor of the selection to `value`, after it was changed.
+    pub(super) fn clamp(&mut self, value: &str) {
+        let clamp = |index: usize| {
+            let mut index = index.min(value.len());
+            while !value.is_char_boundary(index) {
+                index -= 1;
+            }
+            index
+        };
+        self.cursor = clamp(self.cursor);
+        self.anchor = clamp(self.anchor);
+    }
+
+    /// Replaces the selection with `text`, truncated to keep at most `max_length` chars in `value`.
+    ///
+    /// Returns true if `value` changed.
+    pub(super) fn insert(
+        &mut self,
+        value: &mut String,
+        text: &str,
+        max_length: Option<usize>,
+    ) -> bool {
+        if text.is_empty() {
+            return false;
+        }
+        let selection = self.selection();
+        let text = match max_length {
+            Some(max_length) => {
+                let kept = value.chars().count() - value[selection.clone()].chars().count();
+                let available = max_length.saturating_sub(kept);
+                text.char_indices()
+                    .nth(available)
+                    .map_or(text, |(end, _)| &text[..end])
+            }
+            None => text,
+        };
+        if text.is_empty() && selection.is_empty() {
+            return false;
+        }
+        value.replace_range(selection.clone(), text);
+        self.set_cursor(selection.start + text.len(), false);
+        true
+    }
+
+    /// Deletes the selected text.
+    ///
+    /// Returns true if `value` changed.
+    pub(super) fn delete_selection(&mut self, value: &mut String) -> bool {
+        let selection = self.selection();
+        if selection.is_empty() {
+            return false;
+        }
+        value.replace_range(selection.clone(), "");
+        self.set_cursor(selection.start, false);
+        true
+    }
+
+    /// Deletes the selection, or the char or word before the cursor if nothing is selected.
+    ///
+    /// Returns true if `value` changed.
+    pub(super) fn delete_backward(&mut self, value: &mut String, word: bool) -> bool {
+        if self.cursor == self.anchor {
+            self.anchor = if word {
+                previous_word_boundary(value, self.cursor)
+            } else {
+                previous_char_boundary(value, self.cursor)
+            };
+        }
+        self.delete_selection(value)
+    }
+
+    /// Deletes the selection, or the char or word after the cursor if nothing is selected.
+    ///
+    /// Returns true if `value` changed.
+    pub(super) fn delete_forward(&mut self, value: &mut String, word: bool) -> bool {
+        if self.cursor == self.anchor {
+            self.anchor = if word {
+                next_word_boundary(value, self.cursor)
+            } else {
+                next_char_boundary(value, self.cursor)
+            };
+        }
+        self.delete_selection(value)
+    }
+
+    /// Moves the cursor by a char or a word, forward or backward.
+    ///
+    /// Without `extend`, a selection collapses to its edge in the direction of the move instead.
+    pub(super) fn move_horizontally(
+        &mut self,
+        value: &str,
+        forward: bool,
+        word: bool,
+        extend: bool,
+    ) {
+        let selection = self.selection();
+        let cursor = match (forward, word) {
+            _ if !extend && !word && !selection.is_empty() => {
+                if forward {
+                    selection.end
+                } else {
+                    selection.start
+                }
+            }
+            (true, true) => next_word_boundary(value, self.cursor),
+            (true, false) => next_char_boundary(value, self.cursor),
+            (false, true) => previous_word_boundary(value, self.cursor),
+            (false, false) => previous_char_boundary(value, self.cursor),
+        };
+        self.set_cursor(cursor, extend);
+    }
+
+    /// Moves the cursor to the start or the end of its line, or of the whole value if `whole`
+    /// is true.
+    pub(super) fn move_to_edge(&mut self, value: &str, end: bool, whole: bool, extend: bool) {
+        let cursor = match (end, whole) {
+            (true, true) => value.len(),
+            (false, true) => 0,
+            (true, false) => line_end(value, self.cursor),
+            (false, false) => line_start(value, self.cursor),
+        };
+        self.set_cursor(cursor, extend);
+    }
+
+    /// Returns the byte range of the selected text, which is empty when nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// Returns the selected text of `value`.
+    pub fn selected_text<'a>(&self, value: &'a str) -> &'a str {
+        value.get(self.selection()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(cursor: usize, anchor: usize) -> TextInputState {
+        let mut state = TextInputState::default();
+        state.set_cursor(anchor, false);
+        state.set_cursor(cursor, true);
+        state
+    }
+
+    #[test]
+    fn word_boundaries() {
+        let text = "hello,  wörld foo";
+        assert_eq!(previous_word_boundary(text, text.len()), 15);
+        assert_eq!(previous_word_boundary(text, 14), 8);
+        assert_eq!(previous_word_boundary(text, 8), 5);
+        assert_eq!(previous_word_boundary(text, 5), 0);
+        assert_eq!(next_word_boundary(text, 0), 5);
+        assert_eq!(next_word_boundary(text, 5), 8);
+        assert_eq!(next_word_boundary(text, 8), 15);
+        assert_eq!(next_word_boundary(text, 15), text.len());
+    }
+
+    #[test]
+    fn insert_and_delete() {
+        let mut value = String::from("héllo");
+        let mut state = selected(3, 3);
+
+        assert!(state.delete_backward(&mut value, false));
+        assert_eq!((value.as_str(), state.cursor), ("hllo", 1));
+        assert!(state.insert(&mut value, "ey", None));
+        assert_eq!((value.as_str(), state.cursor), ("heyllo", 3));
+
+        state.move_to_edge(&value, true, false, true);
+        assert_eq!(state.selected_text(&value), "llo");
+        assert!(state.insert(&mut value, "!!!", Some(5)));
+        assert_eq!(value, "hey!!");
+
+        state.move_horizontally(&value, false, true, false);
+        assert!(state.delete_forward(&mut value, true));
+        assert_eq!(value, "hey");
+        assert!(!state.delete_forward(&mut value, false));
+    }
+
+    #[test]
+    fn collapse_selection() {
+        let value = "one\ntwo";
+        let mut state = selected(1, 6);
+        state.move_horizontally(value, false, false, false);
+        assert_eq!(state.selection(), 1..1);
+
+        let mut state = selected(6, 6);
+        state.move_to_edge(value, false, false, false);
+        assert_eq!(state.cursor, 4);
+        state.move_to_edge(value, false, true, true);
+        assert_eq!(state.selection(), 0..4);
+    }
+
+    #[test]
+    fn sanitize_line_breaks() {
+        assert_eq!(sanitize("a\r\nb\u{7}", true), "a\nb");
+        assert_eq!(sanitize("a\r\nb\nc", false), "a b c");
+    }
+}
@@ -0,0 +1,861 @@
+//! An editable text field, built on `bevy_text`.
+//!
+//! A node with the [`TextInput`] component edits the text of its [`TextInputValue`] while it has
+//! the [`InputFocus`]. It supports single-line and multi-line editing, a blinking caret, selection
+//! with the mouse and the keyboard, key repeat, IME composition, and cutting, copying and pasting
+//! with the [`Clipboard`]. Editing triggers a [`TextInputChange`] event on the node, and pressing
+//! enter triggers a [`TextInputSubmit`] event.
+//!
+//! The node lays out its text in child nodes, listed in its [`TextInputParts`], which scroll to
+//! keep the caret visible. The text is styled by the [`TextFont`] and [`TextColor`] of the node,
+//! and the caret, selection and placeholder by its [`TextInputStyle`].
+//!
+//! The widgets are enabled by adding the [`TextInputPlugin`].
+
+mod editing;
+
+use crate::{
+    widget::{self, Text},
+    BackgroundColor, ComputedNode, FlexDirection, FocusPolicy, Interaction, Node, Overflow,
+    PositionType, RelativeCursorPosition, ScrollPosition, UiSystem, Val,
+};
+use bevy_app::prelude::*;
+use bevy_color::Color;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{component::HookContext, prelude::*, world::DeferredWorld};
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    ButtonInput, ButtonState, InputSystem,
+};
+use bevy_input_focus::{tab_navigation::TabIndex, InputFocus};
+use bevy_math::{ops, Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::Visibility;
+use bevy_text::{ComputedTextBlock, LineBreak, TextColor, TextFont, TextLayout};
+use bevy_time::{Real, Time};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{
+    Clipboard, ClipboardContent, ClipboardContentKind, ClipboardError, ClipboardPasted,
+    ClipboardRequestId, Ime, PrimaryWindow, Window,
+};
+use editing::sanitize;
+use tracing::warn;
+
+/// Adds support for [`TextInput`] nodes.
+///
+/// This plugin is not added by the [`UiPlugin`](crate::UiPlugin): it requires the resources of
+/// the `InputPlugin`, the `WindowPlugin` and the `TimePlugin`, which are part of the
+/// `DefaultPlugins`.
+#[derive(Default)]
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputFocus>()
+            .register_type::<TextInput>()
+            .register_type::<TextInputMode>()
+            .register_type::<TextInputValue>()
+            .register_type::<TextInputState>()
+            .register_type::<TextInputStyle>()
+            .register_type::<TextInputParts>()
+            .add_systems(
+                PreUpdate,
+                (text_input_pointer_system, text_input_keyboard_system)
+                    .chain()
+                    .after(InputSystem)
+                    .after(UiSystem::Focus),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    sync_text_input_text.before(UiSystem::Prepare),
+                    update_text_input_visuals
+                        .in_set(UiSystem::PostLayout)
+                        .after(widget::text_system),
+                ),
+            );
+    }
+}
+
+/// Whether a [`TextInput`] edits a single line or several lines of text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum TextInputMode {
+    /// A single line of text, which scrolls horizontally. Line breaks are replaced with spaces,
+    /// and pressing enter submits the input.
+    #[default]
+    SingleLine,
+    /// Several lines of text, wrapped to the width of the node, which scroll vertically. Pressing
+    /// enter inserts a line break, and pressing enter with the shortcut modifier (control, or
+    /// command on macOS) submits the input.
+    MultiLine,
+}
+
+/// Makes a UI node an editable text field. See the [module docs](self) for more details.
+///
+/// The node is focused when clicked, and can be reached with tab navigation through its
+/// [`TabIndex`]. Unless its [`Node::overflow`] is set, the node scrolls its overflowing text.
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[component(on_add = on_add_text_input)]
+#[require(
+    Node,
+    TextInputValue,
+    TextInputState,
+    TextInputStyle,
+    TextFont,
+    TextColor,
+    Interaction,
+    FocusPolicy(|| FocusPolicy::Block),
+    TabIndex
+)]
+pub struct TextInput {
+    /// Whether the input edits a single line or several lines of text.
+    ///
+    /// This can't be changed after the input is spawned.
+    pub mode: TextInputMode,
+    /// The maximum number of chars of the value, or `None` for no limit.
+    pub max_length: Option<usize>,
+    /// The text displayed with the [`TextInputStyle::placeholder_color`] while the value is empty.
+    pub placeholder: String,
+}
+
+impl TextInput {
+    /// Creates a single-line text input.
+    pub fn single_line() -> Self {
+        Self::default()
+    }
+
+    /// Creates a multi-line text input.
+    pub fn multi_line() -> Self {
+        Self {
+            mode: TextInputMode::MultiLine,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the maximum number of chars of the value.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Sets the text displayed while the value is empty.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+}
+
+/// The text edited by a [`TextInput`].
+///
+/// Setting the value replaces the text of the input, keeping the cursor where it is when
+/// possible.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq, Deref, DerefMut, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct TextInputValue(pub String);
+
+/// The position of the cursor and the selection of a [`TextInput`], and the text being composed
+/// with an input method.
+///
+/// Offsets are in bytes into the [`TextInputValue`], and always on char boundaries.
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct TextInputState {
+    cursor: usize,
+    anchor: usize,
+    preedit: String,
+    preedit_cursor: usize,
+    #[reflect(ignore)]
+    pending_paste: Option<ClipboardRequestId>,
+    /// The [`Time<Real>`] elapsed seconds when the cursor last moved, to restart the caret blink.
+    last_activity: f32,
+}
+
+impl TextInputState {
+    /// Returns the byte offset of the cursor in the value of the input.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the text being composed with an input method, if any.
+    ///
+    /// The text is displayed at the cursor but isn't part of the value until it is committed.
+    pub fn preedit(&self) -> Option<&str> {
+        (!self.preedit.is_empty()).then_some(self.preedit.as_str())
+    }
+}
+
+/// The colors and sizes of the decorations of a [`TextInput`].
+///
+/// The text itself is styled by the [`TextFont`] and [`TextColor`] of the input node.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct TextInputStyle {
+    /// The color of the caret.
+    pub caret_color: Color,
+    /// The width of the caret, in logical pixels.
+    pub caret_width: f32,
+    /// The color drawn behind the selected text.
+    pub selection_color: Color,
+    /// The color of the placeholder text.
+    pub placeholder_color: Color,
+    /// The duration of a blink of the caret in seconds, or zero to disable blinking.
+    pub caret_blink_period: f32,
+}
+
+impl Default for TextInputStyle {
+    fn default() -> Self {
+        Self {
+            caret_color: Color::WHITE,
+            caret_width: 2.,
+            selection_color: Color::srgba(0.25, 0.45, 0.9, 0.6),
+            placeholder_color: Color::srgba(1., 1., 1., 0.4),
+            caret_blink_period: 1.,
+        }
+    }
+}
+
+/// The child nodes of a [`TextInput`], spawned when it is added.
+///
+/// They can be queried to customize the look of the input, for example to round the corners of
+/// the caret.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub struct TextInputParts {
+    /// The node scrolled within the input, containing the other parts.
+    pub content: Entity,
+    /// The [`Text`] node displaying the value, or the placeholder.
+    pub text: Entity,
+    /// The node of the caret.
+    pub caret: Entity,
+    /// The parent of the nodes drawn behind the selected text, and under the text being composed.
+    pub selection: Entity,
+}
+
+/// Marks the caret node of a [`TextInput`].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct TextInputCaret;
+
+/// Marks the nodes drawn behind the selected text of a [`TextInput`], and under the text being
+/// composed.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct TextInputSelection;
+
+/// Marks the [`Text`] node displaying the value of a [`TextInput`].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct TextInputText;
+
+/// Triggered on a [`TextInput`] when its value is edited by the user.
+///
+/// This event bubbles up the hierarchy, like pointer events.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub struct TextInputChange {
+    /// The new value of the input.
+    pub value: String,
+}
+
+impl Event for TextInputChange {
+    type Traversal = &'static ChildOf;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+/// Triggered on a [`TextInput`] when the user presses enter, or enter with the shortcut modifier
+/// in a multi-line input.
+///
+/// This event bubbles up the hierarchy, like pointer events.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub struct TextInputSubmit {
+    /// The submitted value of the input.
+    pub value: String,
+}
+
+impl Event for TextInputSubmit {
+    type Traversal = &'static ChildOf;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+fn on_add_text_input(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(mode) = world.get::<TextInput>(entity).map(|input| input.mode) else {
+        return;
+    };
+    if let Some(mut node) = world.get_mut::<Node>(entity) {
+        if node.overflow == Overflow::visible() {
+            node.overflow = match mode {
+                TextInputMode::SingleLine => Overflow::scroll_x(),
+                TextInputMode::MultiLine => Overflow::scroll_y(),
+            };
+        }
+    }
+
+    let (width, min_width, linebreak) = match mode {
+        TextInputMode::SingleLine => (Val::Auto, Val::Percent(100.), LineBreak::NoWrap),
+        TextInputMode::MultiLine => (Val::Percent(100.), Val::Auto, LineBreak::WordBoundary),
+    };
+    let mut commands = world.commands();
+    let content = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                flex_shrink: 0.,
+                width,
+                min_width,
+                min_height: Val::Percent(100.),
+                ..Default::default()
+            },
+            ChildOf(entity),
+        ))
+        .id();
+    // The children are drawn in order: the selection behind the text, and the caret above it.
+    let selection = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.),
+                top: Val::Px(0.),
+                ..Default::default()
+            },
+            ChildOf(content),
+        ))
+        .id();
+    let text = commands
+        .spawn((
+            TextInputText,
+            Text::default(),
+            TextLayout::new_with_linebreak(linebreak),
+            RelativeCursorPosition::default(),
+            ChildOf(content),
+        ))
+        .id();
+    let caret = commands
+        .spawn((
+            TextInputCaret,
+            Node {
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            },
+            Visibility::Hidden,
+            ChildOf(content),
+        ))
+        .id();
+    commands.entity(entity).insert(TextInputParts {
+        content,
+        text,
+        caret,
+        selection,
+    });
+}
+
+/// Focuses a [`TextInput`] when it is pressed, and moves its cursor or selects its text with the
+/// mouse.
+///
+/// Pressing the mouse outside of the focused input removes the [`InputFocus`].
+pub fn text_input_pointer_system(
+    mut focus: ResMut<InputFocus>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    mut inputs: Query<(
+        Entity,
+        Ref<Interaction>,
+        &TextInputValue,
+        &mut TextInputState,
+        &TextInputParts,
+    )>,
+    texts: Query<(&ComputedTextBlock, &ComputedNode, &RelativeCursorPosition)>,
+) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for (entity, interaction, value, mut state, parts) in &mut inputs {
+        let just_pressed = interaction.is_changed() && *interaction == Interaction::Pressed;
+        if just_pressed {
+            if focus.0 != Some(entity) {
+                focus.0 = Some(entity);
+            }
+        } else if *interaction != Interaction::Pressed {
+            if mouse.just_pressed(MouseButton::Left) && focus.0 == Some(entity) {
+                focus.0 = None;
+            }
+            continue;
+        }
+
+        // The pointer can't move the cursor while text is being composed.
+        if !state.preedit.is_empty() {
+            continue;
+        }
+        let Ok((block, text_node, relative_position)) = texts.get(parts.text) else {
+            continue;
+        };
+        let Some(position) = relative_position.normalized else {
+            continue;
+        };
+        // The placeholder is displayed while the value is empty.
+        let hit = if value.is_empty() {
+            Some(0)
+        } else {
+            block.hit(position * text_node.size())
+        };
+        let Some(cursor) = hit.filter(|&cursor| value.is_char_boundary(cursor)) else {
+            continue;
+        };
+        // Dragging the pointer extends the selection started when it was pressed.
+        let extend = !just_pressed || shift;
+        if state.cursor != cursor || (!extend && state.anchor != cursor) {
+            state.set_cursor(cursor, extend);
+            state.last_activity = time.elapsed_secs();
+        }
+    }
+}
+
+/// Edits the focused [`TextInput`] with the keyboard, the input method and the [`Clipboard`].
+///
+/// Shortcuts use the control key, or the command key on macOS. Moving and deleting by words uses
+/// the control key, or the option key on macOS.
+pub fn text_input_keyboard_system(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut ime_events: EventReader<Ime>,
+    mut pasted_events: EventReader<ClipboardPasted>,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<InputFocus>,
+    mut clipboard: ResMut<Clipboard>,
+    time: Res<Time<Real>>,
+    mut inputs: Query<(
+        &TextInput,
+        &mut TextInputValue,
+        &mut TextInputState,
+        &TextInputParts,
+    )>,
+    blocks: Query<&ComputedTextBlock>,
+    mut commands: Commands,
+) {
+    let Some((entity, Ok((input, mut value, mut state, parts)))) =
+        focus.0.map(|entity| (entity, inputs.get_mut(entity)))
+    else {
+        keyboard_events.clear();
+        ime_events.clear();
+        pasted_events.clear();
+        return;
+    };
+    if keyboard_events.is_empty() && ime_events.is_empty() && pasted_events.is_empty() {
+        return;
+    }
+
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let (shortcut, word) = if cfg!(target_os = "macos") {
+        let command = keys.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]);
+        (command, alt)
+    } else {
+        // Control and alt are pressed together to type with AltGr.
+        (ctrl && !alt, ctrl)
+    };
+
+    let multiline = input.mode == TextInputMode::MultiLine;
+    let previous_state = state.clone();
+    let state_mut = state.bypass_change_detection();
+    let value_mut = &mut value.bypass_change_detection().0;
+    let mut edited = false;
+    let mut submitted = false;
+    // Some platforms send the text committed by the input method in keyboard events too.
+    let mut ime_committed = false;
+
+    for event in ime_events.read() {
+        match event {
+            Ime::Preedit {
+                value: preedit,
+                cursor,
+                ..
+            } => {
+                // The composed text replaces the selection.
+                if state_mut.preedit.is_empty() && !preedit.is_empty() {
+                    edited |= state_mut.delete_selection(value_mut);
+                }
+                state_mut.preedit.clone_from(preedit);
+                state_mut.preedit_cursor = cursor.map_or(preedit.len(), |(start, _)| start);
+            }
+            Ime::Commit {
+                value: committed, ..
+            } => {
+                state_mut.preedit.clear();
+                let committed = sanitize(committed, multiline);
+                edited |= state_mut.insert(value_mut, &committed, input.max_length);
+                ime_committed = true;
+            }
+            Ime::Disabled { .. } => state_mut.preedit.clear(),
+            Ime::Enabled { .. } => {}
+        }
+    }
+
+    for pasted in pasted_events.read() {
+        if state_mut.pending_paste != Some(pasted.request) {
+            continue;
+        }
+        state_mut.pending_paste = None;
+        match &pasted.result {
+            Ok(ClipboardContent::Text(text)) => {
+                let text = sanitize(text, multiline);
+                edited |= state_mut.insert(value_mut, &text, input.max_length);
+            }
+            Ok(_) | Err(ClipboardError::ContentNotAvailable) => {}
+            Err(error) => warn!("Couldn't paste in the text input: {error}"),
+        }
+    }
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed || !state_mut.preedit.is_empty() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(character) if shortcut => match character.to_lowercase().as_str() {
+                "a" => state_mut.select_all(value_mut),
+                key @ ("c" | "x") => {
+                    let selected = state_mut.selected_text(value_mut);
+                    if selected.is_empty() {
+                        continue;
+                    }
+                    if let Err(error) = clipboard.set_text(selected) {
+                        warn!("Couldn't copy the text input selection: {error}");
+                        continue;
+                    }
+                    if key == "x" {
+                        edited |= state_mut.delete_selection(value_mut);
+                    }
+                }
+                "v" => {
+                    state_mut.pending_paste =
+                        Some(clipboard.request_paste(ClipboardContentKind::Text));
+                }
+                _ => {}
+            },
+            Key::Backspace => edited |= state_mut.delete_backward(value_mut, word),
+            Key::Delete => edited |= state_mut.delete_forward(value_mut, word),
+            Key::ArrowLeft => state_mut.move_horizontally(value_mut, false, word, shift),
+            Key::ArrowRight => state_mut.move_horizontally(value_mut, true, word, shift),
+            Key::ArrowUp | Key::ArrowDown => {
+                let down = event.logical_key == Key::ArrowDown;
+                // The text block displays the value, unless it is empty.
+                let target = blocks
+                    .get(parts.text)
+                    .ok()
+                    .filter(|_| !value_mut.is_empty())
+                    .and_then(|block| {
+                        let caret = block.caret_rect(state_mut.cursor)?;
+                        let y = if down {
+                            caret.max.y + caret.height() / 2.
+                        } else {
+                            caret.min.y - caret.height() / 2.
+                        };
+                        block.hit(Vec2::new(caret.min.x, y)).filter(|_| y >= 0.)
+                    })
+                    .filter(|&cursor| {
+                        cursor != state_mut.cursor && value_mut.is_char_boundary(cursor)
+                    });
+                // Moving past the first or the last line moves to the start or the end.
+                match target {
+                    Some(cursor) => state_mut.set_cursor(cursor, shift),
+                    None => state_mut.move_to_edge(value_mut, down, true, shift),
+                }
+            }
+            Key::Home => state_mut.move_to_edge(value_mut, false, shortcut, shift),
+            Key::End => state_mut.move_to_edge(value_mut, true, shortcut, shift),
+            Key::Enter if multiline && !shortcut => {
+                edited |= state_mut.insert(value_mut, "\n", input.max_length);
+            }
+            Key::Enter => submitted = true,
+            // Tab moves the focus.
+            Key::Tab => {}
+            _ if shortcut || ime_committed => {}
+            _ => {
+                if let Some(text) = &event.text {
+                    let text = sanitize(text, multiline);
+                    edited |= state_mut.insert(value_mut, &text, input.max_length);
+                }
+            }
+        }
+    }
+
+    if edited {
+        value.set_changed();
+        commands.trigger_targets(
+            TextInputChange {
+                value: value.0.clone(),
+            },
+            entity,
+        );
+    }
+    if submitted {
+        commands.trigger_targets(
+            TextInputSubmit {
+                value: value.0.clone(),
+            },
+            entity,
+        );
+    }
+    if *state != previous_state {
+        state.last_activity = time.elapsed_secs();
+    }
+}
+
+/// Updates the [`Text`] node of each [`TextInput`] from its value, placeholder, font and color.
+pub fn sync_text_input_text(
+    mut inputs: Query<(
+        Ref<TextInput>,
+        Ref<TextInputValue>,
+        &mut TextInputState,
+        Ref<TextInputStyle>,
+        Ref<TextFont>,
+        Ref<TextColor>,
+        &TextInputParts,
+    )>,
+    mut texts: Query<
+        (&mut Text, &mut TextFont, &mut TextColor, &mut TextLayout),
+        (With<TextInputText>, Without<TextInput>),
+    >,
+) {
+    for (input, value, mut state, style, font, color, parts) in &mut inputs {
+        if value.is_changed() {
+            state.bypass_change_detection().clamp(&value);
+        }
+        let Ok((mut text, mut text_font, mut text_color, mut layout)) = texts.get_mut(parts.text)
+        else {
+            continue;
+        };
+
+        let placeholder = value.is_empty() && state.preedit.is_empty();
+        if input.is_changed() || value.is_changed() || state.is_changed() {
+            let display = if placeholder {
+                input.placeholder.clone()
+            } else {
+                let mut display = value.0.clone();
+                display.insert_str(state.cursor, &state.preedit);
+                display
+            };
+            text.set_if_neq(Text(display));
+        }
+        let color = if placeholder {
+            style.placeholder_color
+        } else {
+            color.0
+        };
+        text_color.set_if_neq(TextColor(color));
+        if font.is_changed() {
+            *text_font = font.clone();
+        }
+        let linebreak = match input.mode {
+            TextInputMode::SingleLine => LineBreak::NoWrap,
+            TextInputMode::MultiLine => LineBreak::WordBoundary,
+        };
+        if layout.linebreak != linebreak {
+            layout.linebreak = linebreak;
+        }
+    }
+}
+
+/// Places the caret and the selection of each [`TextInput`] over its laid out text, blinks the
+/// caret, and scrolls the focused input to keep its caret visible.
+///
+/// The primary window receives input method events while a text input is focused, with the
+/// candidate window placed under the caret.
+pub fn update_text_input_visuals(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    focus: Res<InputFocus>,
+    mut ime_enabled: Local<bool>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut inputs: Query<(
+        Entity,
+        Ref<TextInputValue>,
+        Ref<TextInputState>,
+        &TextInputStyle,
+        &TextInputParts,
+        &ComputedNode,
+        &mut ScrollPosition,
+    )>,
+    texts: Query<
+        (
+            Ref<ComputedTextBlock>,
+            &ComputedNode,
+            &GlobalTransform,
+            &TextColor,
+        ),
+        With<TextInputText>,
+    >,
+    mut carets: Query<
+        (&mut Node, &mut BackgroundColor, &mut Visibility),
+        (With<TextInputCaret>, Without<TextInputSelection>),
+    >,
+    mut highlights: Query<
+        (&mut Node, &mut BackgroundColor),
+        (With<TextInputSelection>, Without<TextInputCaret>),
+    >,
+    children: Query<&Children>,
+) {
+    // `None` when no input is focused, `Some(None)` while the focused input isn't laid out yet.
+    let mut ime_position = None;
+    for (entity, value, state, style, parts, input_node, mut scroll) in &mut inputs {
+        let Ok((block, text_node, transform, text_color)) = texts.get(parts.text) else {
+            continue;
+        };
+        let focused = focus.0 == Some(entity);
+        let scale = text_node.inverse_scale_factor();
+        let to_logical = |rect: Rect| Rect {
+            min: rect.min * scale,
+            max: rect.max * scale,
+        };
+
+        // Offsets into the displayed text, which includes the composed text or the placeholder.
+        let preedit = !state.preedit.is_empty();
+        let (caret, selection) = if preedit {
+            let caret = state.cursor + state.preedit_cursor;
+            (caret, state.cursor..state.cursor)
+        } else if value.is_empty() {
+            (0, 0..0)
+        } else {
+            (state.cursor, state.selection())
+        };
+        let caret = block.caret_rect(caret);
+
+        if let Ok((mut node, mut color, mut visibility)) = carets.get_mut(parts.caret) {
+            let period = style.caret_blink_period;
+            let blink_on = period <= 0.
+                || ops::rem_euclid(time.elapsed_secs() - state.last_activity, period) < period / 2.;
+            let visible = focused && blink_on && caret.is_some() && selection.is_empty();
+            visibility.set_if_neq(if visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+            color.set_if_neq(BackgroundColor(style.caret_color));
+            if let Some(caret) = caret {
+                let caret = to_logical(caret);
+                let left = (caret.min.x - style.caret_width / 2.).max(0.);
+                place_node(
+                    &mut node,
+                    Rect::new(left, caret.min.y, left + style.caret_width, caret.max.y),
+                );
+            }
+        }
+
+        let mut rects = Vec::new();
+        if focused {
+            rects.extend(
+                block
+                    .selection_rects(selection)
+                    .map(|rect| (to_logical(rect), style.selection_color)),
+            );
+        }
+        if preedit {
+            // The composed text is underlined.
+            let composed = state.cursor..state.cursor + state.preedit.len();
+            rects.extend(block.selection_rects(composed).map(|rect| {
+                let rect = to_logical(rect);
+                let underline = Rect::new(rect.min.x, rect.max.y - 1., rect.max.x, rect.max.y);
+                (underline, text_color.0)
+            }));
+        }
+        let existing = children.get(parts.selection).map_or(&[][..], |c| &**c);
+        for (index, &(rect, color)) in rects.iter().enumerate() {
+            match existing
+                .get(index)
+                .and_then(|&entity| highlights.get_mut(entity).ok())
+            {
+                Some((mut node, mut background)) => {
+                    place_node(&mut node, rect);
+                    background.set_if_neq(BackgroundColor(color));
+                }
+                None => {
+                    commands.spawn((
+                        TextInputSelection,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(rect.min.x),
+                            top: Val::Px(rect.min.y),
+                            width: Val::Px(rect.width()),
+                            height: Val::Px(rect.height()),
+                            ..Default::default()
+                        },
+                        BackgroundColor(color),
+                        ChildOf(parts.selection),
+                    ));
+                }
+            }
+        }
+        for &surplus in existing.iter().skip(rects.len()) {
+            commands.entity(surplus).despawn();
+        }
+
+        if !focused {
+            continue;
+        }
+        ime_position = Some(caret.map(|caret| {
+            let top_left = transform.translation().truncate() - text_node.size() / 2.;
+            (top_left + Vec2::new(caret.min.x, caret.max.y)) * scale
+        }));
+
+        let Some(caret) =
+            caret.filter(|_| state.is_changed() || value.is_changed() || block.is_changed())
+        else {
+            continue;
+        };
+        // Scroll the content of the input to keep the caret visible.
+        let caret = to_logical(caret);
+        let inset = input_node.content_inset();
+        let visible = (input_node.size()
+            - Vec2::new(inset.left + inset.right, inset.top + inset.bottom))
+            * input_node.inverse_scale_factor();
+        let mut offset = Vec2::from(&*scroll);
+        if caret.min.x < offset.x {
+            offset.x = caret.min.x;
+        } else if caret.max.x + style.caret_width > offset.x + visible.x {
+            offset.x = caret.max.x + style.caret_width - visible.x;
+        }
+        if caret.min.y < offset.y {
+            offset.y = caret.min.y;
+        } else if caret.max.y > offset.y + visible.y {
+            offset.y = caret.max.y - visible.y;
+        }
+        if offset != Vec2::from(&*scroll) {
+            *scroll = offset.into();
+        }
+    }
+
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    match ime_position {
+        Some(position) => {
+            if !window.ime_enabled {
+                window.ime_enabled = true;
+            }
+            if let Some(position) = position.filter(|&position| window.ime_position != position) {
+                window.ime_position = position;
+            }
+            *ime_enabled = true;
+        }
+        None if *ime_enabled => {
+            window.ime_enabled = false;
+            *ime_enabled = false;
+        }
+        None => {}
+    }
+}
+
+/// Moves an absolutely positioned `node` over `rect`, in logical pixels, without triggering
+/// change detection when it is already there.
+fn place_node(node: &mut Mut<Node>, rect: Rect) {
+    let left = Val::Px(rect.min.x);
+    let top = Val::Px(rect.min.y);
+    let width = Val::Px(rect.width());
+    let height = Val::Px(rect.height());
+    if node.left != left || node.top != top || node.width != width || node.height != height {
+        node.left = left;
+        node.top = top;
+        node.width = width;
+        node.height = height;
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
-    CalculatedClip, ComputedNode, DefaultUiCamera, ResolvedBorderRadius, UiStack, UiTargetCamera,
+    modal::ModalStack, CalculatedClip, ComputedNode, DefaultUiCamera, ResolvedBorderRadius,
+    UiIntegerScale, UiStack, UiTargetCamera,
 };
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::{Entity, EntityBorrow},
-    prelude::{Component, With},
+    prelude::{ChildOf, Component, With},
     query::QueryData,
     reflect::ReflectComponent,
     system::{Local, Query, Res},
@@ -149,7 +150,7 @@ pub struct NodeQuery {
 /// Entities with a hidden [`InheritedVisibility`] are always treated as released.
 pub fn ui_focus_system(
     mut state: Local<State>,
-    camera_query: Query<(Entity, &Camera)>,
+    camera_query: Query<(Entity, &Camera, Option<&UiIntegerScale>)>,
     default_ui_camera: DefaultUiCamera,
     primary_window: Query<Entity, With<PrimaryWindow>>,
     windows: Query<&Window>,
@@ -157,6 +158,8 @@ pub fn ui_focus_system(
     touches_input: Res<Touches>,
     ui_stack: Res<UiStack>,
     mut node_query: Query<NodeQuery>,
+    modal_stack: Option<Res<ModalStack>>,
+    parents: Query<&ChildOf>,
 ) {
     let primary_window = primary_window.iter().next();
 
@@ -188,7 +191,7 @@ pub fn ui_focus_system(
 
     let camera_cursor_positions: HashMap<Entity, Vec2> = camera_query
         .iter()
-        .filter_map(|(entity, camera)| {
+        .filter_map(|(entity, camera, integer_scale)| {
             // Interactions are only supported for cameras rendering to a window.
             let Some(NormalizedRenderTarget::Window(window_ref)) =
                 camera.target.normalize(primary_window)
@@ -208,7 +211,11 @@ pub fn ui_focus_system(
                         .first_pressed_position()
                         .map(|pos| pos * window.scale_factor())
                 })
-                .map(|cursor_position| (entity, cursor_position - viewport_position))
+                .map(|cursor_position| {
+                    let position = (cursor_position - viewport_position)
+                        / UiIntegerScale::get(integer_scale) as f32;
+                    (entity, position)
+                })
         })
         .collect();
 
@@ -275,12 +282,18 @@ pub fn ui_focus_system(
 
             let contains_cursor = relative_cursor_position_component.mouse_over()
                 && cursor_position.is_some_and(|point| {
-                    pick_rounded_rect(
-                        *point - node_rect.center(),
-                        node_rect.size(),
-                        node.node.border_radius,
-                    )
-                });
+                    node.calculated_clip
+                        .is_none_or(|clip| clip.contains(*point))
+                        && pick_rounded_rect(
+                            *point - node_rect.center(),
+                            node_rect.size(),
+                            node.node.border_radius,
+                        )
+                })
+                // Nodes below the top modal can't be interacted with.
+                && !modal_stack
+                    .as_ref()
+                    .is_some_and(|stack| stack.blocks(*entity, &parents));
 
             // Save the relative cursor position to the correct component
             if let Some(mut node_relative_cursor_position_component) = node.relative_cursor_position
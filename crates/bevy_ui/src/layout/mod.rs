@@ -1,7 +1,7 @@
 use crate::{
     experimental::{UiChildren, UiRootNodes},
     BorderRadius, ComputedNode, ContentSize, DefaultUiCamera, Display, LayoutConfig, Node, Outline,
-    OverflowAxis, ScrollPosition, UiScale, UiTargetCamera, Val,
+    OverflowAxis, ScrollPosition, UiIntegerScale, UiScale, UiScaleOverrides, UiTargetCamera, Val,
 };
 use bevy_ecs::{
     entity::{hash_map::EntityHashMap, hash_set::EntityHashSet},
@@ -96,8 +96,11 @@ pub fn ui_layout_system(
     mut commands: Commands,
     mut buffers: Local<UiLayoutSystemBuffers>,
     primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
-    camera_data: (Query<(Entity, &Camera)>, DefaultUiCamera),
-    ui_scale: Res<UiScale>,
+    camera_data: (
+        Query<(Entity, &Camera, Option<&UiIntegerScale>)>,
+        DefaultUiCamera,
+    ),
+    (ui_scale, ui_scale_overrides): (Res<UiScale>, Res<UiScaleOverrides>),
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut resize_events: EventReader<bevy_window::WindowResized>,
     mut ui_surface: ResMut<UiSurface>,
@@ -139,19 +142,20 @@ pub fn ui_layout_system(
 
     resized_windows.clear();
     resized_windows.extend(resize_events.read().map(|event| event.window));
-    let mut calculate_camera_layout_info = |camera: &Camera| {
-        let size = camera.physical_viewport_size().unwrap_or(UVec2::ZERO);
-        let scale_factor = camera.target_scaling_factor().unwrap_or(1.0);
-        let camera_target = camera
-            .target
-            .normalize(primary_window.get_single().map(|(e, _)| e).ok());
+    let mut calculate_camera_layout_info = |camera: &Camera, integer_scale: u32| {
+        // With an integer scale, the UI is laid out in pixels covering several physical pixels.
+        let size = camera.physical_viewport_size().unwrap_or(UVec2::ZERO) / integer_scale;
+        let scale_factor = camera.target_scaling_factor().unwrap_or(1.0) / integer_scale as f32;
+        let primary_window = primary_window.get_single().map(|(e, _)| e).ok();
+        let camera_target = camera.target.normalize(primary_window);
         let resized = matches!(camera_target,
           Some(NormalizedRenderTarget::Window(window_ref)) if resized_windows.contains(&window_ref.entity())
         );
         CameraLayoutInfo {
             size,
             resized,
-            scale_factor: scale_factor * ui_scale.0,
+            scale_factor: scale_factor
+                * ui_scale_overrides.for_camera(camera, primary_window, &ui_scale),
             root_nodes: interned_root_nodes.pop().unwrap_or_default(),
         }
     };
@@ -164,7 +168,7 @@ pub fn ui_layout_system(
         .for_each(|(entity, _, _, target_camera)| {
             match camera_with_default(target_camera) {
                 Some(camera_entity) => {
-                    let Ok((_, camera)) = cameras.get(camera_entity) else {
+                    let Ok((_, camera, integer_scale)) = cameras.get(camera_entity) else {
                         once!(warn!(
                             "UiTargetCamera (of root UI node {entity}) is pointing to a camera {} which doesn't exist",
                             camera_entity
@@ -173,7 +177,9 @@ pub fn ui_layout_system(
                     };
                     let layout_info = camera_layout_info
                         .entry(camera_entity)
-                        .or_insert_with(|| calculate_camera_layout_info(camera));
+                        .or_insert_with(|| {
+                            calculate_camera_layout_info(camera, UiIntegerScale::get(integer_scale))
+                        });
                     layout_info.root_nodes.push(entity);
                 }
                 None => {
@@ -207,6 +213,7 @@ pub fn ui_layout_system(
                 if camera.resized
                     || !scale_factor_events.is_empty()
                     || ui_scale.is_changed()
+                    || ui_scale_overrides.is_changed()
                     || node.is_changed()
                     || content_size
                         .as_ref()
@@ -229,7 +236,7 @@ pub fn ui_layout_system(
     ui_surface.remove_camera_entities(removed_components.removed_cameras.read());
 
     // update camera children
-    for (camera_id, _) in cameras.iter() {
+    for (camera_id, ..) in cameras.iter() {
         let root_nodes =
             if let Some(CameraLayoutInfo { root_nodes, .. }) = camera_layout_info.get(&camera_id) {
                 root_nodes.iter().cloned()
@@ -486,7 +493,7 @@ mod tests {
 
     use crate::{
         layout::ui_surface::UiSurface, prelude::*, ui_layout_system,
-        update::update_target_camera_system, ContentSize, LayoutContext,
+        update::update_target_camera_system, ContentSize, LayoutContext, UiScaleOverrides,
     };
 
     // these window dimensions are easy to convert to and from percentage values
@@ -496,6 +503,7 @@ mod tests {
     fn setup_ui_test_world() -> (World, Schedule) {
         let mut world = World::new();
         world.init_resource::<UiScale>();
+        world.init_resource::<UiScaleOverrides>();
         world.init_resource::<UiSurface>();
         world.init_resource::<Events<WindowScaleFactorChanged>>();
         world.init_resource::<Events<WindowResized>>();
@@ -571,6 +579,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ui_integer_scale_lays_out_in_ui_pixels() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+        let camera = world
+            .query_filtered::<Entity, With<Camera2d>>()
+            .single(&world);
+        world.entity_mut(camera).insert(UiIntegerScale(4));
+
+        let ui_root = world
+            .spawn(Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                ..default()
+            })
+            .id();
+        let ui_child = world
+            .spawn(Node {
+                width: Val::Px(200.),
+                height: Val::Px(20.),
+                ..default()
+            })
+            .id();
+        world.entity_mut(ui_root).add_child(ui_child);
+
+        ui_schedule.run(&mut world);
+
+        // Each UI pixel covers 4 by 4 physical pixels, so nodes keep their apparent size.
+        let root_node = world.get::<ComputedNode>(ui_root).unwrap();
+        assert_eq!(root_node.size, Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT) / 4.);
+        assert_eq!(root_node.inverse_scale_factor, 4.);
+        let child_node = world.get::<ComputedNode>(ui_child).unwrap();
+        assert_eq!(child_node.size, Vec2::new(50., 5.));
+    }
+
     #[test]
     fn ui_surface_tracks_ui_entities() {
         let (mut world, mut ui_schedule) = setup_ui_test_world();
@@ -1144,6 +1186,7 @@ mod tests {
     fn no_camera_ui() {
         let mut world = World::new();
         world.init_resource::<UiScale>();
+        world.init_resource::<UiScaleOverrides>();
         world.init_resource::<UiSurface>();
         world.init_resource::<Events<WindowScaleFactorChanged>>();
         world.init_resource::<Events<WindowResized>>();
@@ -11,12 +11,16 @@
 //! This UI is laid out with the Flexbox and CSS Grid layout models (see <https://cssreference.io/flexbox/>)
 
 pub mod measurement;
+pub mod modal;
+pub mod navigation;
 pub mod ui_material;
 pub mod update;
 pub mod widget;
 
 #[cfg(feature = "bevy_ui_picking_backend")]
 pub mod picking_backend;
+#[cfg(feature = "style_sheet")]
+pub mod style_sheet;
 
 use bevy_derive::{Deref, DerefMut};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -29,6 +33,7 @@ mod geometry;
 mod layout;
 mod render;
 mod stack;
+mod transition;
 mod ui_node;
 
 pub use focus::*;
@@ -36,6 +41,7 @@ pub use geometry::*;
 pub use layout::*;
 pub use measurement::*;
 pub use render::*;
+pub use transition::*;
 pub use ui_material::*;
 pub use ui_node::*;
 
@@ -65,9 +71,12 @@ pub mod prelude {
 }
 
 use bevy_app::{prelude::*, Animation};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{entity::hash_map::EntityHashMap, prelude::*};
 use bevy_input::InputSystem;
-use bevy_render::{camera::CameraUpdateSystem, RenderApp};
+use bevy_render::{
+    camera::{Camera, CameraUpdateSystem, NormalizedRenderTarget},
+    RenderApp,
+};
 use bevy_transform::TransformSystem;
 use layout::ui_surface::UiSurface;
 use stack::ui_stack_system;
@@ -131,6 +140,39 @@ impl Default for UiScale {
     }
 }
 
+/// Per-window overrides of [`UiScale`], keyed by window entity.
+///
+/// UI rendered to a window listed here uses its scale instead of [`UiScale`]. In both cases, the scale
+/// is multiplied by the scale factor of the window, so UI keeps the same apparent size when a window
+/// is moved to a monitor with a different DPI.
+#[derive(Debug, Default, Clone, Reflect, Resource, Deref, DerefMut)]
+#[reflect(Resource, Debug, Default)]
+pub struct UiScaleOverrides(pub EntityHashMap<f32>);
+
+impl UiScaleOverrides {
+    /// Returns the UI scale of `window`: its override if there is one, or `ui_scale` otherwise.
+    pub fn get_or(&self, window: Option<Entity>, ui_scale: &UiScale) -> f32 {
+        window
+            .and_then(|window| self.0.get(&window))
+            .copied()
+            .unwrap_or(ui_scale.0)
+    }
+
+    /// Returns the UI scale of the window `camera` renders to.
+    pub(crate) fn for_camera(
+        &self,
+        camera: &Camera,
+        primary_window: Option<Entity>,
+        ui_scale: &UiScale,
+    ) -> f32 {
+        let window = match camera.target.normalize(primary_window) {
+            Some(NormalizedRenderTarget::Window(window_ref)) => Some(window_ref.entity()),
+            _ => None,
+        };
+        self.get_or(window, ui_scale)
+    }
+}
+
 // Marks systems that can be ambiguous with [`widget::text_system`] if the `bevy_text` feature is enabled.
 // See https://github.com/bevyengine/bevy/pull/11391 for more details.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -143,6 +185,7 @@ impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UiSurface>()
             .init_resource::<UiScale>()
+            .init_resource::<UiScaleOverrides>()
             .init_resource::<UiStack>()
             .register_type::<BackgroundColor>()
             .register_type::<CalculatedClip>()
@@ -158,6 +201,7 @@ impl Plugin for UiPlugin {
             .register_type::<ImageNodeSize>()
             .register_type::<UiRect>()
             .register_type::<UiScale>()
+            .register_type::<UiScaleOverrides>()
             .register_type::<BorderColor>()
             .register_type::<BorderRadius>()
             .register_type::<BoxShadow>()
@@ -167,7 +211,11 @@ impl Plugin for UiPlugin {
             .register_type::<Outline>()
             .register_type::<BoxShadowSamples>()
             .register_type::<UiAntiAlias>()
+            .register_type::<UiPixelSnap>()
+            .register_type::<UiIntegerScale>()
             .register_type::<TextShadow>()
+            .register_type::<UiTransition>()
+            .register_type::<UiTransitionExit>()
             .configure_sets(
                 PostUpdate,
                 (
@@ -204,6 +252,7 @@ impl Plugin for UiPlugin {
                     .ambiguous_with(ui_layout_system)
                     .in_set(AmbiguousWithTextSystem),
                 update_clipping_system.after(TransformSystem::TransformPropagate),
+                animate_ui_transitions.before(UiSystem::Prepare),
                 // Potential conflicts: `Assets<Image>`
                 // They run independently since `widget::image_node_system` will only ever observe
                 // its own ImageNode, and `widget::text_system` & `bevy_text::update_text2d_layout`
@@ -221,6 +270,11 @@ impl Plugin for UiPlugin {
             app.add_plugins(picking_backend::UiPickingPlugin);
         }
 
+        app.add_plugins(modal::UiModalPlugin);
+
+        #[cfg(feature = "style_sheet")]
+        app.add_plugins(style_sheet::StyleSheetPlugin);
+
         if !self.enable_rendering {
             return;
         }
@@ -0,0 +1,238 @@
+//! Modal UI nodes, like dialogs, which take over the input while they are open.
+//!
+//! A node with the [`Modal`] component:
+//! - is rendered above all the other UI nodes, on a dedicated layer set with its [`GlobalZIndex`],
+//! - traps the [`InputFocus`] within its descendants, both for tab navigation and for directional
+//!   navigation with the [`UiNavigationPlugin`](crate::navigation::UiNavigationPlugin),
+//! - blocks pointer interactions with everything below it, including the nodes outside of it and
+//!   the entities picked by other picking backends,
+//! - restores the focus it took when it is removed or despawned.
+//!
+//! Modals can be stacked: only the most recently opened modal, on top of the [`ModalStack`], is
+//! interactive.
+
+use crate::{
+    navigation::{AutoDirectionalNavigation, NavigationContainer, NavigationPolicy},
+    FocusPolicy, GlobalZIndex, Node,
+};
+use bevy_app::prelude::*;
+use bevy_ecs::{component::HookContext, prelude::*, world::DeferredWorld};
+use bevy_input_focus::{
+    tab_navigation::{TabGroup, TabIndex},
+    InputFocus, InputFocusSet,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::GlobalTransform;
+
+/// Adds support for [`Modal`] nodes.
+///
+/// This plugin is added by the [`UiPlugin`](crate::UiPlugin).
+#[derive(Default)]
+pub struct UiModalPlugin;
+
+impl Plugin for UiModalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModalStack>()
+            .register_type::<Modal>()
+            .add_systems(
+                PreUpdate,
+                trap_modal_focus
+                    .after(InputFocusSet::Dispatch)
+                    .after(crate::UiSystem::Focus),
+            );
+    }
+}
+
+/// Makes a UI node and its descendants modal, like a dialog.
+///
+/// Pointers outside of the node hit the node itself, so observing `Pointer<Click>` events
+/// targeting it can be used to close the modal when clicking outside of it. See the
+/// [module docs](self) for more details.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[component(on_add = on_add_modal, on_remove = on_remove_modal)]
+#[require(
+    Node,
+    GlobalZIndex(|| GlobalZIndex(Modal::GLOBAL_Z_INDEX)),
+    FocusPolicy(|| FocusPolicy::Block),
+    TabGroup(|| TabGroup { order: 0, modal: true }),
+    NavigationContainer(|| NavigationContainer(NavigationPolicy::Contain))
+)]
+pub struct Modal;
+
+impl Modal {
+    /// The [`GlobalZIndex`] of the first modal opened. Each modal opened on top of another one is
+    /// one index above it.
+    pub const GLOBAL_Z_INDEX: i32 = i32::MAX / 2;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ModalEntry {
+    entity: Entity,
+    previous_focus: Option<Entity>,
+}
+
+/// The open [`Modal`] nodes, in the order they were opened.
+#[derive(Resource, Debug, Default)]
+pub struct ModalStack {
+    modals: Vec<ModalEntry>,
+}
+
+impl ModalStack {
+    /// Returns the most recently opened modal, which is the only interactive one.
+    pub fn top(&self) -> Option<Entity> {
+        self.modals.last().map(|modal| modal.entity)
+    }
+
+    /// Returns the open modals, from the first opened to the most recently opened.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.modals.iter().map(|modal| modal.entity)
+    }
+
+    /// Returns `true` if no modal is open.
+    pub fn is_empty(&self) -> bool {
+        self.modals.is_empty()
+    }
+
+    /// Returns `true` if `entity` is blocked by the top modal, because it isn't the modal or
+    /// one of its descendants.
+    pub fn blocks(&self, entity: Entity, parents: &Query<&ChildOf>) -> bool {
+        self.top()
+            .is_some_and(|modal| !is_in_subtree(parents, modal, entity))
+    }
+}
+
+fn is_in_subtree(parents: &Query<&ChildOf>, root: Entity, entity: Entity) -> bool {
+    entity == root
+        || parents
+            .iter_ancestors(entity)
+            .any(|ancestor| ancestor == root)
+}
+
+fn on_add_modal(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let previous_focus = world.get_resource::<InputFocus>().and_then(|focus| focus.0);
+    let Some(top) = world.get_resource::<ModalStack>().map(ModalStack::top) else {
+        return;
+    };
+    let z_index = top
+        .and_then(|top| world.get::<GlobalZIndex>(top))
+        .map_or(Modal::GLOBAL_Z_INDEX, |z_index| z_index.0.saturating_add(1));
+    if let Some(mut global_z_index) = world.get_mut::<GlobalZIndex>(entity) {
+        global_z_index.0 = z_index;
+    }
+    world.resource_mut::<ModalStack>().modals.push(ModalEntry {
+        entity,
+        previous_focus,
+    });
+}
+
+fn on_remove_modal(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(mut stack) = world.get_resource_mut::<ModalStack>() else {
+        return;
+    };
+    let Some(index) = stack.modals.iter().position(|modal| modal.entity == entity) else {
+        return;
+    };
+    let removed = stack.modals.remove(index);
+    if let Some(above) = stack.modals.get_mut(index) {
+        // The modal above was opened from this one, so it restores the focus this one took.
+        above.previous_focus = removed.previous_focus;
+        return;
+    }
+
+    let previous_focus = removed
+        .previous_focus
+        .filter(|previous| world.entities().contains(*previous));
+    if let Some(mut focus) = world.get_resource_mut::<InputFocus>() {
+        focus.0 = previous_focus;
+    }
+}
+
+/// Moves the [`InputFocus`] into the top [`Modal`] when it is outside of it.
+///
+/// The focus moves to the focusable descendant of the modal with the lowest [`TabIndex`], or to
+/// the descendant with [`AutoDirectionalNavigation`] at the top left. If the modal has no
+/// focusable descendant, the modal itself is focused.
+pub fn trap_modal_focus(
+    stack: Res<ModalStack>,
+    focus: Option<ResMut<InputFocus>>,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    focusables: Query<
+        (Option<&TabIndex>, &GlobalTransform),
+        Or<(With<TabIndex>, With<AutoDirectionalNavigation>)>,
+    >,
+) {
+    let (Some(modal), Some(mut focus)) = (stack.top(), focus) else {
+        return;
+    };
+    if focus
+        .0
+        .is_some_and(|focused| is_in_subtree(&parents, modal, focused))
+    {
+        return;
+    }
+
+    let first = children
+        .iter_descendants(modal)
+        .filter_map(|entity| {
+            let (tab_index, transform) = focusables.get(entity).ok()?;
+            // Negative tab indices aren't reachable with tab navigation.
+            let order = match tab_index {
+                Some(TabIndex(index)) if *index < 0 => return None,
+                Some(TabIndex(index)) => *index,
+                None => i32::MAX,
+            };
+            Some((entity, order, transform.translation()))
+        })
+        .min_by(|(_, a_order, a), (_, b_order, b)| {
+            a_order
+                .cmp(b_order)
+                .then(a.y.total_cmp(&b.y))
+                .then(a.x.total_cmp(&b.x))
+        });
+    focus.0 = Some(first.map_or(modal, |(entity, ..)| entity));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn modal_traps_and_restores_focus() {
+        let mut world = World::new();
+        world.init_resource::<ModalStack>();
+        let button = world.spawn(TabIndex(0)).id();
+        world.insert_resource(InputFocus(Some(button)));
+
+        let modal = world.spawn(Modal).id();
+        let ok = world
+            .spawn((TabIndex(1), GlobalTransform::default(), ChildOf(modal)))
+            .id();
+        world.spawn((TabIndex(-1), GlobalTransform::default(), ChildOf(modal)));
+        assert_eq!(
+            world.get::<GlobalZIndex>(modal).unwrap().0,
+            Modal::GLOBAL_Z_INDEX
+        );
+
+        world.run_system_once(trap_modal_focus).unwrap();
+        assert_eq!(world.resource::<InputFocus>().0, Some(ok));
+
+        // A modal opened from another one is on top of it, and is focused itself without
+        // focusable descendants.
+        let confirm = world.spawn(Modal).id();
+        assert_eq!(
+            world.get::<GlobalZIndex>(confirm).unwrap().0,
+            Modal::GLOBAL_Z_INDEX + 1
+        );
+        world.run_system_once(trap_modal_focus).unwrap();
+        assert_eq!(world.resource::<InputFocus>().0, Some(confirm));
+
+        world.despawn(confirm);
+        assert_eq!(world.resource::<InputFocus>().0, Some(ok));
+        world.despawn(modal);
+        assert_eq!(world.resource::<InputFocus>().0, Some(button));
+        assert!(world.resource::<ModalStack>().is_empty());
+    }
+}
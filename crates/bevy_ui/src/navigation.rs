@@ -0,0 +1,472 @@
+//! Directional navigation between UI nodes, with a gamepad or the keyboard.
+//!
+//! The [`UiNavigationPlugin`] resolves the neighbors of the nodes with
+//! [`AutoDirectionalNavigation`] from their layout, and stores them in the
+//! [`DirectionalNavigationMap`] of [`bevy_input_focus`]. The neighbors of a node are the closest
+//! nodes in each [`CompassOctant`], unless overridden with [`NavigationOverrides`]. A
+//! [`NavigationContainer`] can keep the navigation within its descendants, or wrap around at its
+//! edges.
+//!
+//! The plugin also moves the [`InputFocus`] when the keys or gamepad buttons of
+//! [`DirectionalNavigationBindings`] are pressed, and triggers [`FocusGained`] and [`FocusLost`]
+//! on the entities whose focus changed. Like pointer events from `bevy_picking`, these events
+//! bubble up the hierarchy. Tab navigation is handled by [`TabNavigationPlugin`], which is added
+//! along with this plugin.
+
+use crate::ComputedNode;
+use bevy_app::prelude::*;
+use bevy_ecs::{entity::hash_set::EntityHashSet, prelude::*};
+use bevy_input::{
+    gamepad::{Gamepad, GamepadButton},
+    keyboard::KeyCode,
+    ButtonInput, InputSystem,
+};
+use bevy_input_focus::{
+    directional_navigation::{
+        DirectionalNavigation, DirectionalNavigationError, DirectionalNavigationMap, NavNeighbors,
+    },
+    tab_navigation::TabNavigationPlugin,
+    InputFocus, InputFocusVisible,
+};
+use bevy_math::{CompassOctant, Dir2, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::InheritedVisibility;
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use smallvec::SmallVec;
+
+/// Adds directional navigation between UI nodes.
+///
+/// This requires the [`InputDispatchPlugin`](bevy_input_focus::InputDispatchPlugin) and the
+/// [`DirectionalNavigationPlugin`](bevy_input_focus::directional_navigation::DirectionalNavigationPlugin).
+/// See the [module docs](self) for more details.
+#[derive(Default)]
+pub struct UiNavigationPlugin;
+
+impl Plugin for UiNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<TabNavigationPlugin>() {
+            app.add_plugins(TabNavigationPlugin);
+        }
+
+        app.init_resource::<DirectionalNavigationBindings>()
+            .register_type::<AutoDirectionalNavigation>()
+            .register_type::<NavigationOverrides>()
+            .register_type::<NavigationContainer>()
+            .register_type::<NavigationPolicy>()
+            .register_type::<DirectionalNavigationBindings>()
+            .add_systems(
+                PreUpdate,
+                navigate_with_input
+                    .after(InputSystem)
+                    .before(crate::modal::trap_modal_focus),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_directional_navigation_map
+                        .after(TransformSystem::TransformPropagate)
+                        .after(crate::UiSystem::Layout),
+                    send_focus_events,
+                ),
+            );
+    }
+}
+
+/// Marks a UI node as a target of directional navigation, with neighbors resolved from its layout.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct AutoDirectionalNavigation;
+
+/// Explicit neighbors of a node with [`AutoDirectionalNavigation`], which take precedence over
+/// the neighbors resolved from its layout.
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct NavigationOverrides(pub NavNeighbors);
+
+impl NavigationOverrides {
+    /// Sets the neighbor of the node in the direction `octant`.
+    pub fn with(mut self, octant: CompassOctant, entity: Entity) -> Self {
+        self.0.set(octant, entity);
+        self
+    }
+}
+
+/// How directional navigation behaves at the edges of a [`NavigationContainer`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum NavigationPolicy {
+    /// Navigation can leave the container to reach any other node.
+    #[default]
+    Escape,
+    /// Navigation stays within the descendants of the container, and stops at its edges.
+    Contain,
+    /// Navigation stays within the descendants of the container, and wraps around to the
+    /// opposite edge when there is no node in a direction.
+    Wrap,
+}
+
+/// Sets the [`NavigationPolicy`] of the descendants of a node with [`AutoDirectionalNavigation`].
+///
+/// The policy applying to a node is the one of its closest container whose policy isn't
+/// [`NavigationPolicy::Escape`].
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct NavigationContainer(pub NavigationPolicy);
+
+/// The inputs moving the [`InputFocus`] between nodes with [`AutoDirectionalNavigation`].
+///
+/// By default, the arrow keys and the directional pad of any gamepad are bound to the cardinal
+/// directions.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct DirectionalNavigationBindings {
+    /// The keys bound to each direction.
+    pub keys: Vec<(KeyCode, CompassOctant)>,
+    /// The gamepad buttons bound to each direction.
+    pub gamepad_buttons: Vec<(GamepadButton, CompassOctant)>,
+}
+
+impl Default for DirectionalNavigationBindings {
+    fn default() -> Self {
+        Self {
+            keys: vec![
+                (KeyCode::ArrowUp, CompassOctant::North),
+                (KeyCode::ArrowRight, CompassOctant::East),
+                (KeyCode::ArrowDown, CompassOctant::South),
+                (KeyCode::ArrowLeft, CompassOctant::West),
+            ],
+            gamepad_buttons: vec![
+                (GamepadButton::DPadUp, CompassOctant::North),
+                (GamepadButton::DPadRight, CompassOctant::East),
+                (GamepadButton::DPadDown, CompassOctant::South),
+                (GamepadButton::DPadLeft, CompassOctant::West),
+            ],
+        }
+    }
+}
+
+/// Triggered on an entity when it gains the [`InputFocus`].
+///
+/// This event bubbles up the hierarchy, like pointer events.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub struct FocusGained {
+    /// The entity that previously had the focus, if any.
+    pub previous: Option<Entity>,
+}
+
+impl Event for FocusGained {
+    type Traversal = &'static ChildOf;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+/// Triggered on an entity when it loses the [`InputFocus`].
+///
+/// This event bubbles up the hierarchy, like pointer events.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub struct FocusLost {
+    /// The entity that now has the focus, if any.
+    pub next: Option<Entity>,
+}
+
+impl Event for FocusLost {
+    type Traversal = &'static ChildOf;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+/// A node taking part in directional navigation, in a y-up space.
+#[derive(Debug, Clone)]
+struct NavNode {
+    entity: Entity,
+    center: Vec2,
+    /// The closest container whose policy isn't [`NavigationPolicy::Escape`], if any.
+    scope: Option<(Entity, NavigationPolicy)>,
+    /// All the containers of the node.
+    containers: SmallVec<[Entity; 4]>,
+}
+
+impl NavNode {
+    fn is_candidate_from(&self, from: &NavNode) -> bool {
+        self.entity != from.entity
+            && from
+                .scope
+                .is_none_or(|(scope, _)| self.containers.contains(&scope))
+    }
+}
+
+/// Returns the neighbors of `from` among `nodes`, resolved from their positions.
+fn resolve_neighbors(nodes: &[NavNode], from: &NavNode) -> NavNeighbors {
+    let mut neighbors = NavNeighbors::EMPTY;
+    for index in 0..8 {
+        let Some(octant) = CompassOctant::from_index(index) else {
+            continue;
+        };
+        let direction = Dir2::from(octant);
+        // Candidates must be within 67.5° of cardinal directions, and 45° of diagonal ones.
+        let max_slope = if index % 2 == 0 { 2.414 } else { 1.0 };
+        // Scores a candidate by its distance along the direction, with the distance across it
+        // counting more so that aligned nodes are preferred.
+        let score = |node: &NavNode| {
+            let offset = node.center - from.center;
+            let along = offset.dot(*direction);
+            let across = (offset - *direction * along).length();
+            (along, across, along + 2.0 * across)
+        };
+
+        let closest = nodes
+            .iter()
+            .filter(|node| node.is_candidate_from(from))
+            .filter_map(|node| {
+                let (along, across, score) = score(node);
+                (along > 0.0 && across < along * max_slope).then_some((node.entity, score))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let neighbor = closest.or_else(|| {
+            if !matches!(from.scope, Some((_, NavigationPolicy::Wrap))) {
+                return None;
+            }
+            // Wrap around to the node the farthest behind, preferring aligned nodes.
+            nodes
+                .iter()
+                .filter(|node| node.is_candidate_from(from))
+                .filter_map(|node| {
+                    let (along, _, score) = score(node);
+                    (along < 0.0).then_some((node.entity, score))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        });
+
+        if let Some((entity, _)) = neighbor {
+            neighbors.set(octant, entity);
+        }
+    }
+    neighbors
+}
+
+/// Resolves the neighbors of the nodes with [`AutoDirectionalNavigation`] and stores them in the
+/// [`DirectionalNavigationMap`].
+///
+/// Neighbors are only resolved again when a navigable node or a container changed.
+pub fn update_directional_navigation_map(
+    mut map: ResMut<DirectionalNavigationMap>,
+    nodes: Query<
+        (
+            Entity,
+            &ComputedNode,
+            &GlobalTransform,
+            Option<&InheritedVisibility>,
+            Option<&NavigationOverrides>,
+        ),
+        With<AutoDirectionalNavigation>,
+    >,
+    changed: Query<
+        (),
+        Or<(
+            (
+                With<AutoDirectionalNavigation>,
+                Or<(
+                    Changed<ComputedNode>,
+                    Changed<GlobalTransform>,
+                    Changed<InheritedVisibility>,
+                    Added<AutoDirectionalNavigation>,
+                    Changed<NavigationOverrides>,
+                )>,
+            ),
+            Changed<NavigationContainer>,
+        )>,
+    >,
+    mut removed: RemovedComponents<AutoDirectionalNavigation>,
+    containers: Query<&NavigationContainer>,
+    parents: Query<&ChildOf>,
+) {
+    let removed: EntityHashSet = removed.read().collect();
+    if !removed.is_empty() {
+        map.remove_multiple(removed);
+    } else if changed.is_empty() {
+        return;
+    }
+
+    let mut hidden = Vec::new();
+    let nav_nodes: Vec<NavNode> = nodes
+        .iter()
+        .filter_map(|(entity, node, transform, visibility, _)| {
+            if node.is_empty() || visibility.is_some_and(|visibility| !visibility.get()) {
+                hidden.push(entity);
+                return None;
+            }
+            let mut scope = None;
+            let mut node_containers = SmallVec::new();
+            for ancestor in parents.iter_ancestors(entity) {
+                if let Ok(NavigationContainer(policy)) = containers.get(ancestor) {
+                    node_containers.push(ancestor);
+                    if scope.is_none() && *policy != NavigationPolicy::Escape {
+                        scope = Some((ancestor, *policy));
+                    }
+                }
+            }
+            let translation = transform.translation();
+            Some(NavNode {
+                entity,
+                // UI nodes are laid out in a y-down space.
+                center: Vec2::new(translation.x, -translation.y),
+                scope,
+                containers: node_containers,
+            })
+        })
+        .collect();
+
+    for entity in hidden {
+        map.remove(entity);
+    }
+
+    for nav_node in &nav_nodes {
+        let mut neighbors = resolve_neighbors(&nav_nodes, nav_node);
+        if let Ok((.., Some(overrides))) = nodes.get(nav_node.entity) {
+            for (neighbor, override_neighbor) in neighbors
+                .neighbors
+                .iter_mut()
+                .zip(overrides.0.neighbors.iter())
+            {
+                if override_neighbor.is_some() {
+                    *neighbor = *override_neighbor;
+                }
+            }
+        }
+        map.neighbors.insert(nav_node.entity, neighbors);
+    }
+}
+
+/// Moves the [`InputFocus`] when the inputs of [`DirectionalNavigationBindings`] are pressed.
+///
+/// When the focus isn't on a navigable node, the node at the top left is focused instead.
+pub fn navigate_with_input(
+    mut navigation: DirectionalNavigation,
+    mut visible: ResMut<InputFocusVisible>,
+    bindings: Res<DirectionalNavigationBindings>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<&Gamepad>,
+    nodes: Query<(Entity, &GlobalTransform), With<AutoDirectionalNavigation>>,
+) {
+    let key_directions = bindings
+        .keys
+        .iter()
+        .filter(|(key, _)| keys.as_ref().is_some_and(|keys| keys.just_pressed(*key)))
+        .map(|(_, direction)| *direction);
+    let button_directions = bindings
+        .gamepad_buttons
+        .iter()
+        .filter(|(button, _)| gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)))
+        .map(|(_, direction)| *direction);
+    let Some(direction) = key_directions.chain(button_directions).next() else {
+        return;
+    };
+
+    match navigation.navigate(direction) {
+        Ok(_) => visible.0 = true,
+        Err(DirectionalNavigationError::NoNeighborInDirection { current_focus, .. })
+            if navigation.map.get_neighbors(current_focus).is_some() => {}
+        Err(_) => {
+            let top_left = nodes.iter().min_by(|(_, a), (_, b)| {
+                let (a, b) = (a.translation(), b.translation());
+                a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+            });
+            if let Some((entity, _)) = top_left {
+                navigation.focus.set(entity);
+                visible.0 = true;
+            }
+        }
+    }
+}
+
+/// Triggers [`FocusGained`] and [`FocusLost`] when the [`InputFocus`] changes.
+pub fn send_focus_events(
+    mut commands: Commands,
+    focus: Option<Res<InputFocus>>,
+    mut previous: Local<Option<Entity>>,
+    entities: Query<Entity>,
+) {
+    let Some(focus) = focus else {
+        return;
+    };
+    if !focus.is_changed() || focus.0 == *previous {
+        return;
+    }
+
+    if let Some(previous) = previous.filter(|previous| entities.contains(*previous)) {
+        commands.trigger_targets(FocusLost { next: focus.0 }, previous);
+    }
+    if let Some(next) = focus.0 {
+        commands.trigger_targets(
+            FocusGained {
+                previous: *previous,
+            },
+            next,
+        );
+    }
+    *previous = focus.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(world: &mut World, policy: Option<(Entity, NavigationPolicy)>) -> Vec<NavNode> {
+        // A 3x2 grid, in a y-up space.
+        (0..6)
+            .map(|i| NavNode {
+                entity: world.spawn_empty().id(),
+                center: Vec2::new((i % 3) as f32 * 100.0, -((i / 3) as f32) * 50.0),
+                scope: policy,
+                containers: policy.map(|(container, _)| container).into_iter().collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolve_grid_neighbors() {
+        let mut world = World::new();
+        let nodes = grid(&mut world, None);
+
+        let neighbors = resolve_neighbors(&nodes, &nodes[1]);
+        assert_eq!(neighbors.get(CompassOctant::West), Some(nodes[0].entity));
+        assert_eq!(neighbors.get(CompassOctant::East), Some(nodes[2].entity));
+        assert_eq!(neighbors.get(CompassOctant::South), Some(nodes[4].entity));
+        assert_eq!(neighbors.get(CompassOctant::North), None);
+
+        let neighbors = resolve_neighbors(&nodes, &nodes[2]);
+        assert_eq!(neighbors.get(CompassOctant::East), None);
+        assert_eq!(
+            neighbors.get(CompassOctant::SouthWest),
+            Some(nodes[4].entity)
+        );
+    }
+
+    #[test]
+    fn resolve_wrapping_neighbors() {
+        let mut world = World::new();
+        let container = world.spawn_empty().id();
+        let mut nodes = grid(&mut world, Some((container, NavigationPolicy::Wrap)));
+
+        let neighbors = resolve_neighbors(&nodes, &nodes[2]);
+        assert_eq!(neighbors.get(CompassOctant::East), Some(nodes[0].entity));
+        assert_eq!(neighbors.get(CompassOctant::North), Some(nodes[5].entity));
+
+        // A node outside of the container can't be reached from the inside.
+        nodes.push(NavNode {
+            entity: world.spawn_empty().id(),
+            center: Vec2::new(300.0, 0.0),
+            scope: None,
+            containers: SmallVec::new(),
+        });
+        let neighbors = resolve_neighbors(&nodes, &nodes[2]);
+        assert_eq!(neighbors.get(CompassOctant::East), Some(nodes[0].entity));
+
+        // But the container can be entered from the outside.
+        let neighbors = resolve_neighbors(&nodes, &nodes[6]);
+        assert_eq!(neighbors.get(CompassOctant::West), Some(nodes[2].entity));
+    }
+}
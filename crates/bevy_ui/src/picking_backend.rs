@@ -21,12 +21,14 @@
 
 #![deny(missing_docs)]
 
-use crate::{focus::pick_rounded_rect, prelude::*, UiStack};
+use crate::{focus::pick_rounded_rect, modal::ModalStack, prelude::*, UiStack};
 use bevy_app::prelude::*;
 use bevy_ecs::{prelude::*, query::QueryData};
 use bevy_math::{Rect, Vec2};
 use bevy_platform_support::collections::HashMap;
+use bevy_reflect::prelude::*;
 use bevy_render::prelude::*;
+use bevy_sprite::BorderRect;
 use bevy_transform::prelude::*;
 use bevy_window::PrimaryWindow;
 
@@ -37,10 +39,21 @@ use bevy_picking::backend::prelude::*;
 pub struct UiPickingPlugin;
 impl Plugin for UiPickingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PreUpdate, ui_picking.in_set(PickSet::Backend));
+        app.register_type::<PickingEdgeThickness>()
+            .add_systems(PreUpdate, ui_picking.in_set(PickSet::Backend));
     }
 }
 
+/// The thickness of the edges of a UI node, in logical pixels, used to report which [`HitRegion`]
+/// of the node is hit in its [`HitData`].
+///
+/// Without this component, the edges of a node are its borders, see [`Node::border`]. This is useful
+/// for resizable panels, where the edges used to resize the panel are usually thicker than its
+/// visible border.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub struct PickingEdgeThickness(pub f32);
+
 /// Main query from bevy's `ui_focus_system`
 #[derive(QueryData)]
 #[query_data(mutable)]
@@ -52,6 +65,7 @@ pub struct NodeQuery {
     calculated_clip: Option<&'static CalculatedClip>,
     inherited_visibility: Option<&'static InheritedVisibility>,
     target_camera: Option<&'static UiTargetCamera>,
+    edge_thickness: Option<&'static PickingEdgeThickness>,
 }
 
 /// Computes the UI node entities under each pointer.
@@ -60,11 +74,18 @@ pub struct NodeQuery {
 /// we need for determining picking.
 pub fn ui_picking(
     pointers: Query<(&PointerId, &PointerLocation)>,
-    camera_query: Query<(Entity, &Camera, Has<IsDefaultUiCamera>)>,
+    camera_query: Query<(
+        Entity,
+        &Camera,
+        Has<IsDefaultUiCamera>,
+        Option<&UiIntegerScale>,
+    )>,
     default_ui_camera: DefaultUiCamera,
     primary_window: Query<Entity, With<PrimaryWindow>>,
     ui_stack: Res<UiStack>,
     node_query: Query<NodeQuery>,
+    modal_stack: Option<Res<ModalStack>>,
+    parents: Query<&ChildOf>,
     mut output: EventWriter<PointerHits>,
 ) {
     // For each camera, the pointer and its position
@@ -81,7 +102,7 @@ pub fn ui_picking(
         // cameras. We want to ensure we return all cameras with a matching target.
         for camera in camera_query
             .iter()
-            .map(|(entity, camera, _)| {
+            .map(|(entity, camera, ..)| {
                 (
                     entity,
                     camera.target.normalize(primary_window.get_single().ok()),
@@ -91,7 +112,7 @@ pub fn ui_picking(
             .filter(|(_entity, target)| target == &pointer_location.target)
             .map(|(cam_entity, _target)| cam_entity)
         {
-            let Ok((_, camera_data, _)) = camera_query.get(camera) else {
+            let Ok((_, camera_data, _, integer_scale)) = camera_query.get(camera) else {
                 continue;
             };
             let mut pointer_pos =
@@ -99,6 +120,7 @@ pub fn ui_picking(
             if let Some(viewport) = camera_data.physical_viewport_rect() {
                 pointer_pos -= viewport.min.as_vec2();
             }
+            pointer_pos /= UiIntegerScale::get(integer_scale) as f32;
             pointer_pos_by_camera
                 .entry(camera)
                 .or_default()
@@ -165,6 +187,9 @@ pub fn ui_picking(
             if visible_rect
                 .normalize(node_rect)
                 .contains(relative_cursor_position)
+                && node
+                    .calculated_clip
+                    .is_none_or(|clip| clip.contains(*cursor_position))
                 && pick_rounded_rect(
                     *cursor_position - node_rect.center(),
                     node_rect.size(),
@@ -179,6 +204,34 @@ pub fn ui_picking(
         }
     }
 
+    // While a modal is open, only its nodes can be hovered, and the pointers on its camera that
+    // aren't over any of them hit the modal itself, blocking everything below.
+    if let Some((stack, modal)) = modal_stack
+        .as_deref()
+        .and_then(|stack| Some(stack).zip(stack.top()))
+    {
+        for hovered_nodes in hit_nodes.values_mut() {
+            hovered_nodes.retain(|node| !stack.blocks(*node, &parents));
+        }
+        let modal_camera = node_query.get(modal).ok().and_then(|node| {
+            node.target_camera
+                .map(UiTargetCamera::entity)
+                .or(default_camera_entity)
+        });
+        if let Some(camera) = modal_camera {
+            for pointer_id in pointer_pos_by_camera
+                .get(&camera)
+                .into_iter()
+                .flat_map(|pointers| pointers.keys())
+            {
+                let hovered_nodes = hit_nodes.entry((camera, *pointer_id)).or_default();
+                if hovered_nodes.is_empty() {
+                    hovered_nodes.push(modal);
+                }
+            }
+        }
+    }
+
     for ((camera, pointer), hovered_nodes) in hit_nodes.iter() {
         // As soon as a node with a `Block` focus policy is detected, the iteration will stop on it
         // because it "captures" the interaction.
@@ -194,7 +247,25 @@ pub fn ui_picking(
                 continue;
             };
 
-            picks.push((node.entity, HitData::new(camera_entity, depth, None, None)));
+            let mut hit = HitData::new(camera_entity, depth, None, None);
+            if let Some(position) = pointer_pos_by_camera
+                .get(camera)
+                .and_then(|pointers| pointers.get(pointer))
+            {
+                let node_rect = Rect::from_center_size(
+                    node.global_transform.translation().truncate(),
+                    node.node.size(),
+                );
+                let edges = node.edge_thickness.map_or(node.node.border(), |thickness| {
+                    BorderRect::all(thickness.0 / node.node.inverse_scale_factor())
+                });
+                hit = hit.with_region(hit_region(
+                    *position - node_rect.min,
+                    node_rect.size(),
+                    edges,
+                ));
+            }
+            picks.push((node.entity, hit));
 
             if let Some(pickable) = node.pickable {
                 // If an entity has a `Pickable` component, we will use that as the source of truth.
@@ -211,10 +282,52 @@ pub fn ui_picking(
 
         let order = camera_query
             .get(*camera)
-            .map(|(_, cam, _)| cam.order)
+            .map(|(_, cam, ..)| cam.order)
             .unwrap_or_default() as f32
             + 0.5; // bevy ui can run on any camera, it's a special case
 
         output.send(PointerHits::new(*pointer, picks, order));
     }
 }
+
+/// Returns the region of a node of the given `size` containing `point`, relative to the top-left
+/// corner of the node.
+fn hit_region(point: Vec2, size: Vec2, edges: BorderRect) -> HitRegion {
+    HitRegion::from_edges(
+        point.x < edges.left,
+        point.y < edges.top,
+        point.x >= size.x - edges.right,
+        point.y >= size.y - edges.bottom,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hit_region;
+    use bevy_math::Vec2;
+    use bevy_picking::backend::HitRegion;
+    use bevy_sprite::BorderRect;
+
+    #[test]
+    fn hit_regions() {
+        let size = Vec2::new(100., 50.);
+        let edges = BorderRect::all(5.);
+        let region = |x, y| hit_region(Vec2::new(x, y), size, edges);
+
+        assert_eq!(region(50., 25.), HitRegion::Content);
+        assert_eq!(region(50., 2.), HitRegion::Top);
+        assert_eq!(region(50., 48.), HitRegion::Bottom);
+        assert_eq!(region(2., 25.), HitRegion::Left);
+        assert_eq!(region(98., 25.), HitRegion::Right);
+        assert_eq!(region(2., 2.), HitRegion::TopLeft);
+        assert_eq!(region(98., 2.), HitRegion::TopRight);
+        assert_eq!(region(2., 48.), HitRegion::BottomLeft);
+        assert_eq!(region(98., 48.), HitRegion::BottomRight);
+
+        // Without edges, the whole node is content
+        assert_eq!(
+            hit_region(Vec2::ZERO, size, BorderRect::ZERO),
+            HitRegion::Content
+        );
+    }
+}
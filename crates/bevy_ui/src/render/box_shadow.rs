@@ -18,7 +18,7 @@ use bevy_ecs::{
     },
 };
 use bevy_image::BevyDefault as _;
-use bevy_math::{vec2, FloatOrd, Mat4, Rect, Vec2, Vec3Swizzles, Vec4Swizzles};
+use bevy_math::{vec2, FloatOrd, Mat4, Vec2, Vec3Swizzles, Vec4Swizzles};
 use bevy_render::sync_world::MainEntity;
 use bevy_render::RenderApp;
 use bevy_render::{
@@ -33,7 +33,10 @@ use bevy_render::{
 use bevy_transform::prelude::GlobalTransform;
 use bytemuck::{Pod, Zeroable};
 
-use super::{stack_z_offsets, UiCameraMap, UiCameraView, QUAD_INDICES, QUAD_VERTEX_POSITIONS};
+use super::{
+    axis_aligned_scale, clip_positions_diff, gpu_clip_vertex_data, is_clipped_out, split_clip,
+    stack_z_offsets, UiCameraMap, UiCameraView, QUAD_INDICES, QUAD_VERTEX_POSITIONS,
+};
 
 pub const BOX_SHADOW_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("d2991ecd-134f-4f82-adf5-0fcc86f02227");
@@ -87,6 +90,8 @@ struct BoxShadowVertex {
     radius: [f32; 4],
     blur: f32,
     bounds: [f32; 2],
+    clip: [f32; 4],
+    clip_point: [f32; 2],
 }
 
 #[derive(Component)]
@@ -162,6 +167,10 @@ impl SpecializedRenderPipeline for BoxShadowPipeline {
                 VertexFormat::Float32,
                 // outer size
                 VertexFormat::Float32x2,
+                // clip rect
+                VertexFormat::Float32x4,
+                // position in the space of the clip rect
+                VertexFormat::Float32x2,
             ],
         );
         let shader_defs = vec![ShaderDefVal::UInt(
@@ -218,7 +227,7 @@ pub struct ExtractedBoxShadow {
     pub stack_index: u32,
     pub transform: Mat4,
     pub bounds: Vec2,
-    pub clip: Option<Rect>,
+    pub clip: Option<CalculatedClip>,
     pub extracted_camera_entity: Entity,
     pub color: LinearRgba,
     pub radius: ResolvedBorderRadius,
@@ -318,7 +327,7 @@ pub fn extract_shadows(
                 transform: transform.compute_matrix() * Mat4::from_translation(offset.extend(0.)),
                 color: drop_shadow.color.into(),
                 bounds: shadow_size + 6. * blur_radius,
-                clip: clip.map(|clip| clip.clip),
+                clip: clip.copied(),
                 extracted_camera_entity,
                 radius,
                 blur_radius,
@@ -431,26 +440,12 @@ pub fn prepare_shadows(
                         .map(|pos| (box_shadow.transform * (pos * rect_size).extend(1.)).xyz());
 
                     // Calculate the effect of clipping
-                    // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
-                    let positions_diff = if let Some(clip) = box_shadow.clip {
-                        [
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[0].x, 0.),
-                                f32::max(clip.min.y - positions[0].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[1].x, 0.),
-                                f32::max(clip.min.y - positions[1].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[2].x, 0.),
-                                f32::min(clip.max.y - positions[2].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[3].x, 0.),
-                                f32::min(clip.max.y - positions[3].y, 0.),
-                            ),
-                        ]
+                    // Moving the corners of the shadow only works when both the shadow and the clip
+                    // are axis-aligned, otherwise the clip is applied in the fragment shader
+                    let axis_aligned_scale = axis_aligned_scale(&box_shadow.transform);
+                    let (cpu_clip, gpu_clip) = split_clip(box_shadow.clip, axis_aligned_scale);
+                    let mut positions_diff = if let Some(clip) = cpu_clip {
+                        clip_positions_diff(&positions, clip)
                     } else {
                         [Vec2::ZERO; 4]
                     };
@@ -462,21 +457,22 @@ pub fn prepare_shadows(
                         positions[3] + positions_diff[3].extend(0.),
                     ];
 
-                    let transformed_rect_size = box_shadow.transform.transform_vector3(rect_size);
-
-                    // Don't try to cull nodes that have a rotation
-                    // In a rotation around the Z-axis, this value is 0.0 for an angle of 0.0 or π
-                    // In those two cases, the culling check can proceed normally as corners will be on
-                    // horizontal / vertical lines
-                    // For all other angles, bypass the culling check
-                    // This does not properly handles all rotations on all axis
-                    if box_shadow.transform.x_axis[1] == 0.0 {
-                        // Cull nodes that are completely clipped
-                        if positions_diff[0].x - positions_diff[1].x >= transformed_rect_size.x
-                            || positions_diff[1].y - positions_diff[2].y >= transformed_rect_size.y
-                        {
+                    // The clipped distances in the local space of the shadow
+                    let scale = axis_aligned_scale.unwrap_or(Vec2::ONE);
+                    for diff in &mut positions_diff {
+                        *diff /= scale;
+                    }
+
+                    if let Some(clip) = gpu_clip {
+                        // Cull shadows whose bounding rect is completely clipped
+                        if is_clipped_out(&positions, clip) {
                             continue;
                         }
+                    } else if positions_diff[0].x - positions_diff[1].x >= rect_size.x
+                        || positions_diff[1].y - positions_diff[2].y >= rect_size.y
+                    {
+                        // Cull shadows that are completely clipped
+                        continue;
                     }
 
                     let radius = [
@@ -503,6 +499,7 @@ pub fn prepare_shadows(
                     ]
                     .map(|pos| pos / box_shadow.bounds);
 
+                    let (clip, clip_points) = gpu_clip_vertex_data(gpu_clip, &positions_clipped);
                     for i in 0..4 {
                         ui_meta.vertices.push(BoxShadowVertex {
                             position: positions_clipped[i].into(),
@@ -512,6 +509,8 @@ pub fn prepare_shadows(
                             radius,
                             blur: box_shadow.blur_radius,
                             bounds: rect_size.xy().into(),
+                            clip,
+                            clip_point: clip_points[i],
                         });
                     }
 
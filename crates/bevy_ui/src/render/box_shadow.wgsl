@@ -13,6 +13,10 @@ struct BoxShadowVertexOutput {
     @location(2) @interpolate(flat) size: vec2<f32>,
     @location(3) @interpolate(flat) radius: vec4<f32>,    
     @location(4) @interpolate(flat) blur: f32,
+    // x: min x, y: min y, z: max x, w: max y.
+    @location(5) @interpolate(flat) clip: vec4<f32>,
+    // Position in the space of the clip rect.
+    @location(6) clip_point: vec2<f32>,
 }
 
 fn gaussian(x: f32, sigma: f32) -> f32 {
@@ -75,6 +79,8 @@ fn vertex(
     @location(4) radius: vec4<f32>,
     @location(5) blur: f32,
     @location(6) bounds: vec2<f32>,
+    @location(7) clip: vec4<f32>,
+    @location(8) clip_point: vec2<f32>,
 ) -> BoxShadowVertexOutput {
     var out: BoxShadowVertexOutput;
     out.position = view.clip_from_world * vec4(vertex_position, 1.0);
@@ -83,15 +89,27 @@ fn vertex(
     out.size = size;
     out.radius = radius;
     out.blur = blur;
+    out.clip = clip;
+    out.clip_point = clip_point;
     return out;
 }
 
+// Returns the visible part of the fragment, when the shadow or the rect it's clipped to is rotated
+// and can't be clipped by moving the corners of the shadow. Unclipped shadows have a clip rect
+// containing the whole shadow.
+fn clip_coverage(in: BoxShadowVertexOutput) -> f32 {
+    // Distances from the clip rect edges, in pixels, positive outside of the clip rect.
+    let pixel_size = max(fwidth(in.clip_point), vec2(1e-4));
+    let distance = max(in.clip.xy - in.clip_point, in.clip_point - in.clip.zw) / pixel_size;
+    return saturate(0.5 - max(distance.x, distance.y));
+}
+
 @fragment
 fn fragment(
     in: BoxShadowVertexOutput,
 ) -> @location(0) vec4<f32> {
     let g = in.color.a * roundedBoxShadow(-0.5 * in.size, 0.5 * in.size, in.point, max(in.blur, 0.01), in.radius);
-    return vec4(in.color.rgb, g);
+    return vec4(in.color.rgb, g * clip_coverage(in));
 }
 
 
@@ -94,9 +94,7 @@ pub fn extract_debug_overlay(
                 min: Vec2::ZERO,
                 max: uinode.size,
             },
-            clip: maybe_clip
-                .filter(|_| !debug_options.show_clipped)
-                .map(|clip| clip.clip),
+            clip: maybe_clip.filter(|_| !debug_options.show_clipped).copied(),
             image: AssetId::default(),
             extracted_camera_entity,
             item: ExtractedUiItem::Node {
@@ -0,0 +1,100 @@
+//! Renders the UI of cameras with a [`UiIntegerScale`](crate::UiIntegerScale) to an offscreen
+//! texture, which is upscaled to the viewport of the camera.
+
+use bevy_core_pipeline::blit::{BlitPipeline, BlitPipelineKey};
+use bevy_ecs::prelude::*;
+use bevy_image::BevyDefault as _;
+use bevy_math::UVec2;
+use bevy_render::{
+    render_resource::{
+        BlendState, CachedRenderPipelineId, Extent3d, PipelineCache, SpecializedRenderPipelines,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::RenderDevice,
+    texture::{CachedTexture, TextureCache},
+    view::{ExtractedView, ViewTarget},
+};
+
+use super::UiViewTarget;
+
+/// A render-world component that lives on the UI view of a camera with a
+/// [`UiIntegerScale`](crate::UiIntegerScale) greater than `1`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ExtractedUiIntegerScale {
+    /// The number of physical pixels covered by each UI pixel, along each axis.
+    pub scale: u32,
+    /// The size of the UI view, in UI pixels.
+    pub size: UVec2,
+    /// The position of the viewport of the camera in its target, in physical pixels.
+    pub viewport_position: UVec2,
+}
+
+/// The offscreen texture the UI view is rendered to, and the pipeline upscaling it to the
+/// viewport of the camera.
+#[derive(Component)]
+pub struct UiIntegerScaleTarget {
+    pub texture: CachedTexture,
+    pub pipeline: CachedRenderPipelineId,
+}
+
+pub fn prepare_ui_integer_scale_targets(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    pipeline_cache: Res<PipelineCache>,
+    blit_pipeline: Res<BlitPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    ui_views: Query<(
+        Entity,
+        &ExtractedView,
+        &ExtractedUiIntegerScale,
+        &UiViewTarget,
+    )>,
+    view_targets: Query<&ViewTarget>,
+) {
+    for (entity, view, integer_scale, ui_view_target) in &ui_views {
+        let Ok(view_target) = view_targets.get(ui_view_target.0) else {
+            continue;
+        };
+
+        // The format the UI pipelines render to.
+        let format = if view.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ui_integer_scale_texture"),
+                size: Extent3d {
+                    width: integer_scale.size.x,
+                    height: integer_scale.size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        // The UI blends its colors over a transparent texture, which leaves them premultiplied.
+        let pipeline = pipelines.specialize(
+            &pipeline_cache,
+            &blit_pipeline,
+            BlitPipelineKey {
+                texture_format: view_target.main_texture_format(),
+                blend_state: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                samples: 1,
+                hdr_output: None,
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(UiIntegerScaleTarget { texture, pipeline });
+    }
+}
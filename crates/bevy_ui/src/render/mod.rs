@@ -1,4 +1,5 @@
 pub mod box_shadow;
+mod integer_scale;
 mod pipeline;
 mod render_pass;
 mod ui_material_pipeline;
@@ -10,18 +11,19 @@ mod debug_overlay;
 use crate::widget::ImageNode;
 use crate::{
     BackgroundColor, BorderColor, BoxShadowSamples, CalculatedClip, ComputedNode, DefaultUiCamera,
-    Outline, ResolvedBorderRadius, TextShadow, UiAntiAlias, UiTargetCamera,
+    Outline, ResolvedBorderRadius, TextShadow, UiAntiAlias, UiIntegerScale, UiPixelSnap,
+    UiTargetCamera,
 };
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, weak_handle, AssetEvent, AssetId, Assets, Handle};
-use bevy_color::{Alpha, ColorToComponents, LinearRgba};
+use bevy_color::{Alpha, ColorToComponents, LinearRgba, Mix};
 use bevy_core_pipeline::core_2d::graph::{Core2d, Node2d};
 use bevy_core_pipeline::core_3d::graph::{Core3d, Node3d};
-use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
+use bevy_core_pipeline::{blit::BlitPipeline, core_2d::Camera2d, core_3d::Camera3d};
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemParam;
 use bevy_image::prelude::*;
-use bevy_math::{FloatOrd, Mat4, Rect, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4Swizzles};
+use bevy_math::{FloatOrd, Mat4, Rect, UVec2, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4Swizzles};
 use bevy_render::render_graph::{NodeRunError, RenderGraphContext};
 use bevy_render::render_phase::ViewSortedRenderPhases;
 use bevy_render::renderer::RenderContext;
@@ -51,12 +53,16 @@ pub use debug_overlay::UiDebugOptions;
 
 use crate::{Display, Node};
 use bevy_platform_support::collections::{HashMap, HashSet};
-use bevy_text::{ComputedTextBlock, PositionedGlyph, TextColor, TextLayoutInfo};
+use bevy_text::{
+    ComputedTextBlock, PositionedGlyph, TextColor, TextDropShadow, TextGradient, TextLayoutInfo,
+    TextOutline,
+};
 use bevy_transform::components::GlobalTransform;
 use box_shadow::BoxShadowPlugin;
 use bytemuck::{Pod, Zeroable};
 use core::ops::Range;
 use graph::{NodeUi, SubGraphUi};
+pub use integer_scale::*;
 pub use pipeline::*;
 pub use render_pass::*;
 pub use ui_material_pipeline::*;
@@ -157,6 +163,9 @@ pub fn build_ui_render(app: &mut App) {
         .add_systems(
             Render,
             (
+                prepare_ui_integer_scale_targets
+                    .in_set(RenderSet::PrepareResources)
+                    .run_if(resource_exists::<BlitPipeline>),
                 queue_uinodes.in_set(RenderSet::Queue),
                 sort_phase_system::<TransparentUi>.in_set(RenderSet::PhaseSort),
                 prepare_uinodes.in_set(RenderSet::PrepareBindGroups),
@@ -200,7 +209,7 @@ pub struct ExtractedUiNode {
     pub color: LinearRgba,
     pub rect: Rect,
     pub image: AssetId<Image>,
-    pub clip: Option<Rect>,
+    pub clip: Option<CalculatedClip>,
     /// Render world entity of the extracted camera corresponding to this node's target camera.
     pub extracted_camera_entity: Entity,
     pub item: ExtractedUiItem,
@@ -230,7 +239,9 @@ pub enum ExtractedUiItem {
         node_type: NodeType,
         transform: Mat4,
     },
-    /// A contiguous sequence of text glyphs from the same section
+    /// A contiguous sequence of text glyphs using the same texture atlas
+    ///
+    /// The colors of the glyphs are stored in each [`ExtractedGlyph`].
     Glyphs {
         /// Indices into [`ExtractedUiNodes::glyphs`]
         range: Range<usize>,
@@ -240,6 +251,23 @@ pub enum ExtractedUiItem {
 pub struct ExtractedGlyph {
     pub transform: Mat4,
     pub rect: Rect,
+    /// Color of the top of the glyph.
+    pub color: LinearRgba,
+    /// Color of the bottom of the glyph, which differs from `color` for text with a
+    /// [`TextGradient`].
+    pub bottom_color: LinearRgba,
+    pub effect: GlyphEffect,
+}
+
+/// An effect drawn from the shape of a glyph instead of the glyph itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GlyphEffect {
+    #[default]
+    None,
+    /// The glyph dilated by this width in physical pixels, for [`TextOutline`].
+    Outline(f32),
+    /// The glyph blurred by this radius in physical pixels, for [`TextDropShadow`].
+    Shadow(f32),
 }
 
 #[derive(Resource, Default)]
@@ -369,7 +397,7 @@ pub fn extract_uinode_background_colors(
                 min: Vec2::ZERO,
                 max: uinode.size,
             },
-            clip: clip.map(|clip| clip.clip),
+            clip: clip.copied(),
             image: AssetId::default(),
             extracted_camera_entity,
             item: ExtractedUiItem::Node {
@@ -453,7 +481,7 @@ pub fn extract_uinode_images(
             stack_index: uinode.stack_index,
             color: image.color.into(),
             rect,
-            clip: clip.map(|clip| clip.clip),
+            clip: clip.copied(),
             image: image.image.id(),
             extracted_camera_entity,
             item: ExtractedUiItem::Node {
@@ -522,7 +550,7 @@ pub fn extract_uinode_borders(
                         ..Default::default()
                     },
                     image,
-                    clip: maybe_clip.map(|clip| clip.clip),
+                    clip: maybe_clip.copied(),
                     extracted_camera_entity,
                     item: ExtractedUiItem::Node {
                         atlas_scaling: None,
@@ -555,7 +583,7 @@ pub fn extract_uinode_borders(
                     ..Default::default()
                 },
                 image,
-                clip: maybe_clip.map(|clip| clip.clip),
+                clip: maybe_clip.copied(),
                 extracted_camera_entity,
                 item: ExtractedUiItem::Node {
                     transform: global_transform.compute_matrix(),
@@ -621,6 +649,8 @@ pub fn extract_ui_camera_view(
                 &Camera,
                 Option<&UiAntiAlias>,
                 Option<&BoxShadowSamples>,
+                Option<&UiPixelSnap>,
+                Option<&UiIntegerScale>,
             ),
             Or<(With<Camera2d>, With<Camera3d>)>,
         >,
@@ -629,22 +659,34 @@ pub fn extract_ui_camera_view(
 ) {
     live_entities.clear();
 
-    for (main_entity, render_entity, camera, ui_anti_alias, shadow_samples) in &query {
+    for (
+        main_entity,
+        render_entity,
+        camera,
+        ui_anti_alias,
+        shadow_samples,
+        pixel_snap,
+        integer_scale,
+    ) in &query
+    {
         // ignore inactive cameras
         if !camera.is_active {
             commands
                 .get_entity(render_entity)
                 .expect("Camera entity wasn't synced.")
-                .remove::<(UiCameraView, UiAntiAlias, BoxShadowSamples)>();
+                .remove::<(UiCameraView, UiAntiAlias, BoxShadowSamples, UiPixelSnap)>();
             continue;
         }
 
         if let Some(physical_viewport_rect) = camera.physical_viewport_rect() {
+            // With an integer scale, the UI is rendered to a smaller offscreen texture.
+            let integer_scale = UiIntegerScale::get(integer_scale);
+            let ui_size = physical_viewport_rect.size() / integer_scale;
             // use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection
             let projection_matrix = Mat4::orthographic_rh(
                 0.0,
-                physical_viewport_rect.width() as f32,
-                physical_viewport_rect.height() as f32,
+                ui_size.x as f32,
+                ui_size.y as f32,
                 0.0,
                 0.0,
                 UI_CAMERA_FAR,
@@ -653,30 +695,38 @@ pub fn extract_ui_camera_view(
             // main 3D or 2D camera, which will have subview index 0.
             let retained_view_entity =
                 RetainedViewEntity::new(main_entity.into(), None, UI_CAMERA_SUBVIEW);
+            let viewport = if integer_scale > 1 {
+                UVec4::from((UVec2::ZERO, ui_size))
+            } else {
+                UVec4::from((physical_viewport_rect.min, physical_viewport_rect.size()))
+            };
             // Creates the UI view.
-            let ui_camera_view = commands
-                .spawn((
-                    ExtractedView {
-                        retained_view_entity,
-                        clip_from_view: projection_matrix,
-                        world_from_view: GlobalTransform::from_xyz(
-                            0.0,
-                            0.0,
-                            UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
-                        ),
-                        clip_from_world: None,
-                        hdr: camera.hdr,
-                        viewport: UVec4::from((
-                            physical_viewport_rect.min,
-                            physical_viewport_rect.size(),
-                        )),
-                        color_grading: Default::default(),
-                    },
-                    // Link to the main camera view.
-                    UiViewTarget(render_entity),
-                    TemporaryRenderEntity,
-                ))
-                .id();
+            let mut ui_camera_view = commands.spawn((
+                ExtractedView {
+                    retained_view_entity,
+                    clip_from_view: projection_matrix,
+                    world_from_view: GlobalTransform::from_xyz(
+                        0.0,
+                        0.0,
+                        UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
+                    ),
+                    clip_from_world: None,
+                    hdr: camera.hdr,
+                    viewport,
+                    color_grading: Default::default(),
+                },
+                // Link to the main camera view.
+                UiViewTarget(render_entity),
+                TemporaryRenderEntity,
+            ));
+            if integer_scale > 1 && ui_size.cmpgt(UVec2::ZERO).all() {
+                ui_camera_view.insert(ExtractedUiIntegerScale {
+                    scale: integer_scale,
+                    size: ui_size,
+                    viewport_position: physical_viewport_rect.min,
+                });
+            }
+            let ui_camera_view = ui_camera_view.id();
 
             let mut entity_commands = commands
                 .get_entity(render_entity)
@@ -689,6 +739,10 @@ pub fn extract_ui_camera_view(
             if let Some(shadow_samples) = shadow_samples {
                 entity_commands.insert(*shadow_samples);
             }
+            match pixel_snap {
+                Some(pixel_snap) => entity_commands.insert(*pixel_snap),
+                None => entity_commands.remove::<UiPixelSnap>(),
+            };
             transparent_render_phases.insert_or_clear(retained_view_entity);
 
             live_entities.insert(retained_view_entity);
@@ -698,6 +752,15 @@ pub fn extract_ui_camera_view(
     transparent_render_phases.retain(|entity, _| live_entities.contains(entity));
 }
 
+/// The glyphs of a text node are drawn in layers: first the shadows, then the outlines, and then
+/// the glyphs themselves.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TextLayer {
+    Shadow,
+    Outline,
+    Fill,
+}
+
 pub fn extract_text_sections(
     mut commands: Commands,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
@@ -714,12 +777,16 @@ pub fn extract_text_sections(
             &TextLayoutInfo,
         )>,
     >,
-    text_styles: Extract<Query<&TextColor>>,
+    text_styles: Extract<
+        Query<(
+            &TextColor,
+            Option<&TextGradient>,
+            Option<&TextOutline>,
+            Option<&TextDropShadow>,
+        )>,
+    >,
     camera_map: Extract<UiCameraMap>,
 ) {
-    let mut start = extracted_uinodes.glyphs.len();
-    let mut end = start + 1;
-
     let mut camera_mapper = camera_map.get_mapper();
     for (
         entity,
@@ -743,62 +810,116 @@ pub fn extract_text_sections(
 
         let transform = global_transform.affine()
             * bevy_math::Affine3A::from_translation((-0.5 * uinode.size()).extend(0.));
-
-        let mut color = LinearRgba::WHITE;
-        let mut current_span = usize::MAX;
-
-        for (
-            i,
-            PositionedGlyph {
+        let scale_factor = uinode.inverse_scale_factor().recip();
+
+        // Glyphs using the same atlas are batched together, whatever their section or layer
+        let mut start = extracted_uinodes.glyphs.len();
+        let mut run: Option<(AssetId<Image>, Rect)> = None;
+        for layer in [TextLayer::Shadow, TextLayer::Outline, TextLayer::Fill] {
+            let mut current_span = usize::MAX;
+            let mut style = None;
+            for PositionedGlyph {
                 position,
+                size,
                 atlas_info,
                 span_index,
                 ..
-            },
-        ) in text_layout_info.glyphs.iter().enumerate()
-        {
-            if *span_index != current_span {
-                color = text_styles
-                    .get(
-                        computed_block
-                            .entities()
-                            .get(*span_index)
-                            .map(|t| t.entity)
-                            .unwrap_or(Entity::PLACEHOLDER),
-                    )
-                    .map(|text_color| LinearRgba::from(text_color.0))
-                    .unwrap_or_default();
-                current_span = *span_index;
-            }
+            } in &text_layout_info.glyphs
+            {
+                if *span_index != current_span {
+                    style = computed_block
+                        .entities()
+                        .get(*span_index)
+                        .and_then(|text_entity| text_styles.get(text_entity.entity).ok());
+                    current_span = *span_index;
+                }
 
-            let rect = texture_atlases
-                .get(&atlas_info.texture_atlas)
-                .unwrap()
-                .textures[atlas_info.location.glyph_index]
-                .as_rect();
-            extracted_uinodes.glyphs.push(ExtractedGlyph {
-                transform: transform * Mat4::from_translation(position.extend(0.)),
-                rect,
-            });
+                let (color, bottom_color, effect, offset) = match layer {
+                    TextLayer::Shadow => {
+                        let Some(shadow) = style.and_then(|(.., shadow)| shadow) else {
+                            continue;
+                        };
+                        let color = LinearRgba::from(shadow.color);
+                        let blur = shadow.blur.max(0.) * scale_factor;
+                        let offset = shadow.offset * scale_factor;
+                        (color, color, GlyphEffect::Shadow(blur), offset)
+                    }
+                    TextLayer::Outline => {
+                        let Some(outline) = style.and_then(|(_, _, outline, _)| outline) else {
+                            continue;
+                        };
+                        let color = LinearRgba::from(outline.color);
+                        let width = outline.width.max(0.) * scale_factor;
+                        (color, color, GlyphEffect::Outline(width), Vec2::ZERO)
+                    }
+                    TextLayer::Fill => {
+                        let color = style
+                            .map(|(text_color, ..)| LinearRgba::from(text_color.0))
+                            .unwrap_or_default();
+                        let (top, bottom) = match style.and_then(|(_, gradient, ..)| gradient) {
+                            Some(gradient) => {
+                                // The gradient spans the whole text block
+                                let top = LinearRgba::from(gradient.top);
+                                let bottom = LinearRgba::from(gradient.bottom);
+                                let height = text_layout_info.size.y.max(f32::EPSILON);
+                                let top_t = (position.y - 0.5 * size.y) / height;
+                                let bottom_t = (position.y + 0.5 * size.y) / height;
+                                (top.mix(&bottom, top_t), top.mix(&bottom, bottom_t))
+                            }
+                            None => (color, color),
+                        };
+                        (top, bottom, GlyphEffect::None, Vec2::ZERO)
+                    }
+                };
+
+                let image = atlas_info.texture.id();
+                if let Some((run_image, run_rect)) = run {
+                    if run_image != image {
+                        let end = extracted_uinodes.glyphs.len();
+                        extracted_uinodes.uinodes.push(ExtractedUiNode {
+                            render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                            stack_index: uinode.stack_index,
+                            color: LinearRgba::WHITE,
+                            image: run_image,
+                            clip: clip.copied(),
+                            extracted_camera_entity,
+                            rect: run_rect,
+                            item: ExtractedUiItem::Glyphs { range: start..end },
+                            main_entity: entity.into(),
+                        });
+                        start = end;
+                    }
+                }
 
-            if text_layout_info.glyphs.get(i + 1).is_none_or(|info| {
-                info.span_index != current_span || info.atlas_info.texture != atlas_info.texture
-            }) {
-                extracted_uinodes.uinodes.push(ExtractedUiNode {
-                    render_entity: commands.spawn(TemporaryRenderEntity).id(),
-                    stack_index: uinode.stack_index,
-                    color,
-                    image: atlas_info.texture.id(),
-                    clip: clip.map(|clip| clip.clip),
-                    extracted_camera_entity,
+                let rect = texture_atlases
+                    .get(&atlas_info.texture_atlas)
+                    .unwrap()
+                    .textures[atlas_info.location.glyph_index]
+                    .as_rect();
+                extracted_uinodes.glyphs.push(ExtractedGlyph {
+                    transform: transform * Mat4::from_translation((*position + offset).extend(0.)),
                     rect,
-                    item: ExtractedUiItem::Glyphs { range: start..end },
-                    main_entity: entity.into(),
+                    color,
+                    bottom_color,
+                    effect,
                 });
-                start = end;
+                run = Some((image, rect));
             }
+        }
 
-            end += 1;
+        if let Some((run_image, run_rect)) = run {
+            let end = extracted_uinodes.glyphs.len();
+            extracted_uinodes.uinodes.push(ExtractedUiNode {
+                render_entity: commands.spawn(TemporaryRenderEntity).id(),
+                stack_index: uinode.stack_index,
+                color: LinearRgba::WHITE,
+                image: run_image,
+                clip: clip.copied(),
+                extracted_camera_entity,
+                rect: run_rect,
+                item: ExtractedUiItem::Glyphs { range: start..end },
+                main_entity: entity.into(),
+            });
         }
     }
 }
@@ -878,6 +999,9 @@ pub fn extract_text_shadows(
             extracted_uinodes.glyphs.push(ExtractedGlyph {
                 transform: transform * Mat4::from_translation(position.extend(0.)),
                 rect,
+                color: shadow.color.into(),
+                bottom_color: shadow.color.into(),
+                effect: GlyphEffect::None,
             });
 
             if text_layout_info.glyphs.get(i + 1).is_none_or(|info| {
@@ -888,7 +1012,7 @@ pub fn extract_text_shadows(
                     stack_index: uinode.stack_index,
                     color: shadow.color.into(),
                     image: atlas_info.texture.id(),
-                    clip: clip.map(|clip| clip.clip),
+                    clip: clip.copied(),
                     extracted_camera_entity,
                     rect,
                     item: ExtractedUiItem::Glyphs { range: start..end },
@@ -921,6 +1045,11 @@ struct UiVertex {
     pub size: [f32; 2],
     /// Position relative to the center of the UI node.
     pub point: [f32; 2],
+    /// Clip rect applied in the fragment shader when the [`shader_flags::CLIPPED`] flag is set.
+    /// Ordering: min x, min y, max x, max y.
+    pub clip: [f32; 4],
+    /// Position in the space of the clip rect.
+    pub clip_point: [f32; 2],
 }
 
 #[derive(Resource)]
@@ -963,6 +1092,14 @@ pub mod shader_flags {
     /// Ordering: top left, top right, bottom right, bottom left.
     pub const CORNERS: [u32; 4] = [0, 2, 2 | 4, 4];
     pub const BORDER: u32 = 8;
+    /// Clip the node in the fragment shader, when it or its clip is rotated.
+    pub const CLIPPED: u32 = 16;
+    /// Draw the outline of a glyph, with the width and the glyph rect in the atlas stored in the
+    /// `radius` and `border` attributes.
+    pub const GLYPH_OUTLINE: u32 = 32;
+    /// Draw the blurred shadow of a glyph, with the blur radius and the glyph rect in the atlas
+    /// stored in the `radius` and `border` attributes.
+    pub const GLYPH_SHADOW: u32 = 64;
 }
 
 pub fn queue_uinodes(
@@ -1018,6 +1155,133 @@ pub fn queue_uinodes(
     }
 }
 
+/// Returns the scale of `transform` if it maps the node to an axis-aligned rect without flipping it.
+pub(crate) fn axis_aligned_scale(transform: &Mat4) -> Option<Vec2> {
+    (transform.x_axis.y == 0.
+        && transform.y_axis.x == 0.
+        && transform.x_axis.x > 0.
+        && transform.y_axis.y > 0.)
+        .then(|| Vec2::new(transform.x_axis.x, transform.y_axis.y))
+}
+
+/// Splits `clip` into the clip applied by moving the corners of a node, and the clip applied in
+/// the fragment shader.
+pub(crate) fn split_clip(
+    clip: Option<CalculatedClip>,
+    axis_aligned_scale: Option<Vec2>,
+) -> (Option<Rect>, Option<CalculatedClip>) {
+    match clip {
+        Some(clip) if clip.is_axis_aligned() && axis_aligned_scale.is_some() => {
+            (Some(clip.clip), None)
+        }
+        clip => (None, clip),
+    }
+}
+
+/// Returns how much each of the corners of an axis-aligned node must move to be inside `clip`.
+pub(crate) fn clip_positions_diff(positions: &[Vec3; 4], clip: Rect) -> [Vec2; 4] {
+    [
+        Vec2::new(
+            f32::max(clip.min.x - positions[0].x, 0.),
+            f32::max(clip.min.y - positions[0].y, 0.),
+        ),
+        Vec2::new(
+            f32::min(clip.max.x - positions[1].x, 0.),
+            f32::max(clip.min.y - positions[1].y, 0.),
+        ),
+        Vec2::new(
+            f32::min(clip.max.x - positions[2].x, 0.),
+            f32::min(clip.max.y - positions[2].y, 0.),
+        ),
+        Vec2::new(
+            f32::max(clip.min.x - positions[3].x, 0.),
+            f32::min(clip.max.y - positions[3].y, 0.),
+        ),
+    ]
+}
+
+/// Returns `true` if the bounding rect of the corners of a node is outside `clip`.
+pub(crate) fn is_clipped_out(positions: &[Vec3; 4], clip: CalculatedClip) -> bool {
+    let bounds = positions.iter().fold(Rect::EMPTY, |bounds, position| {
+        bounds.union_point(position.xy())
+    });
+    bounds.intersect(clip.clip).is_empty()
+}
+
+/// Returns the clip rect and the clip space positions of the corners of a node clipped in the
+/// fragment shader.
+///
+/// Without a clip, the returned clip rect contains all the returned positions, so that shaders
+/// that don't check [`shader_flags::CLIPPED`] don't clip anything.
+pub(crate) fn gpu_clip_vertex_data(
+    clip: Option<CalculatedClip>,
+    positions: &[Vec3; 4],
+) -> ([f32; 4], [[f32; 2]; 4]) {
+    // Infinite bounds, from nodes which only clip along one axis, aren't reliably supported by GPUs
+    const MAX_CLIP_EXTENT: f32 = 1e9;
+    let Some(clip) = clip else {
+        return (
+            [
+                -MAX_CLIP_EXTENT,
+                -MAX_CLIP_EXTENT,
+                MAX_CLIP_EXTENT,
+                MAX_CLIP_EXTENT,
+            ],
+            [[0.; 2]; 4],
+        );
+    };
+    let min = clip.local_clip.min.max(Vec2::splat(-MAX_CLIP_EXTENT));
+    let max = clip.local_clip.max.min(Vec2::splat(MAX_CLIP_EXTENT));
+    (
+        [min.x, min.y, max.x, max.y],
+        positions.map(|position| clip.local_from_ui.transform_point2(position.xy()).into()),
+    )
+}
+
+/// Clips the convex polygon `points`, in UI coordinates, to the rotated rect of `clip`.
+///
+/// This is used instead of clipping in the fragment shader when the shader can't be changed.
+pub(crate) fn clip_polygon(points: &[Vec2], clip: CalculatedClip) -> Vec<Vec2> {
+    let mut polygon: Vec<Vec2> = points
+        .iter()
+        .map(|&point| clip.local_from_ui.transform_point2(point))
+        .collect();
+    // Sutherland-Hodgman, against each edge of the clip rect in turn
+    let edges = [
+        (0, clip.local_clip.min.x, -1.),
+        (1, clip.local_clip.min.y, -1.),
+        (0, clip.local_clip.max.x, 1.),
+        (1, clip.local_clip.max.y, 1.),
+    ];
+    for (axis, bound, sign) in edges {
+        let outside = |point: Vec2| sign * (point[axis] - bound) > 0.;
+        let input = core::mem::take(&mut polygon);
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+            if outside(current) != outside(previous) {
+                let t = (bound - previous[axis]) / (current[axis] - previous[axis]);
+                polygon.push(previous.lerp(current, t));
+            }
+            if !outside(current) {
+                polygon.push(current);
+            }
+        }
+    }
+    let ui_from_local = clip.local_from_ui.inverse();
+    polygon
+        .into_iter()
+        .map(|point| ui_from_local.transform_point2(point))
+        .collect()
+}
+
+/// Moves the corners of a quad so that its first corner lies on a whole pixel, keeping its size.
+fn snap_to_pixels(positions: &mut [Vec3; 4]) {
+    let offset = positions[0].xy().round() - positions[0].xy();
+    for position in positions {
+        *position += offset.extend(0.);
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ImageNodeBindGroups {
     pub values: HashMap<AssetId<Image>, BindGroup>,
@@ -1035,6 +1299,7 @@ pub fn prepare_uinodes(
     gpu_images: Res<RenderAssets<GpuImage>>,
     mut phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
     events: Res<SpriteAssetEvents>,
+    pixel_snaps: Query<&UiPixelSnap>,
     mut previous_len: Local<usize>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
@@ -1140,6 +1405,9 @@ pub fn prepare_uinodes(
                             continue;
                         }
                     }
+                    let pixel_snap = pixel_snaps
+                        .get(extracted_uinode.extracted_camera_entity)
+                        .is_ok_and(|pixel_snap| *pixel_snap == UiPixelSnap::On);
                     match &extracted_uinode.item {
                         ExtractedUiItem::Node {
                             atlas_scaling,
@@ -1161,31 +1429,22 @@ pub fn prepare_uinodes(
                             let rect_size = uinode_rect.size().extend(1.0);
 
                             // Specify the corners of the node
-                            let positions = QUAD_VERTEX_POSITIONS
+                            let mut positions = QUAD_VERTEX_POSITIONS
                                 .map(|pos| (*transform * (pos * rect_size).extend(1.)).xyz());
+                            let axis_aligned_scale = axis_aligned_scale(transform);
+                            // Snapping a rotated node would make it jitter while it rotates
+                            if pixel_snap && axis_aligned_scale.is_some() {
+                                snap_to_pixels(&mut positions);
+                            }
                             let points = QUAD_VERTEX_POSITIONS.map(|pos| pos.xy() * rect_size.xy());
 
                             // Calculate the effect of clipping
-                            // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
-                            let mut positions_diff = if let Some(clip) = extracted_uinode.clip {
-                                [
-                                    Vec2::new(
-                                        f32::max(clip.min.x - positions[0].x, 0.),
-                                        f32::max(clip.min.y - positions[0].y, 0.),
-                                    ),
-                                    Vec2::new(
-                                        f32::min(clip.max.x - positions[1].x, 0.),
-                                        f32::max(clip.min.y - positions[1].y, 0.),
-                                    ),
-                                    Vec2::new(
-                                        f32::min(clip.max.x - positions[2].x, 0.),
-                                        f32::min(clip.max.y - positions[2].y, 0.),
-                                    ),
-                                    Vec2::new(
-                                        f32::max(clip.min.x - positions[3].x, 0.),
-                                        f32::min(clip.max.y - positions[3].y, 0.),
-                                    ),
-                                ]
+                            // Moving the corners of the node only works when both the node and the clip are
+                            // axis-aligned, otherwise the clip is applied in the fragment shader
+                            let (cpu_clip, gpu_clip) =
+                                split_clip(extracted_uinode.clip, axis_aligned_scale);
+                            let mut positions_diff = if let Some(clip) = cpu_clip {
+                                clip_positions_diff(&positions, clip)
                             } else {
                                 [Vec2::ZERO; 4]
                             };
@@ -1197,6 +1456,12 @@ pub fn prepare_uinodes(
                                 positions[3] + positions_diff[3].extend(0.),
                             ];
 
+                            // The clipped distances in the local space of the node
+                            let scale = axis_aligned_scale.unwrap_or(Vec2::ONE);
+                            for diff in &mut positions_diff {
+                                *diff /= scale;
+                            }
+
                             let points = [
                                 points[0] + positions_diff[0],
                                 points[1] + positions_diff[1],
@@ -1204,23 +1469,14 @@ pub fn prepare_uinodes(
                                 points[3] + positions_diff[3],
                             ];
 
-                            let transformed_rect_size = transform.transform_vector3(rect_size);
-
-                            // Don't try to cull nodes that have a rotation
-                            // In a rotation around the Z-axis, this value is 0.0 for an angle of 0.0 or π
-                            // In those two cases, the culling check can proceed normally as corners will be on
-                            // horizontal / vertical lines
-                            // For all other angles, bypass the culling check
-                            // This does not properly handles all rotations on all axis
-                            if transform.x_axis[1] == 0.0 {
-                                // Cull nodes that are completely clipped
-                                if positions_diff[0].x - positions_diff[1].x
-                                    >= transformed_rect_size.x
-                                    || positions_diff[1].y - positions_diff[2].y
-                                        >= transformed_rect_size.y
-                                {
+                            if let Some(clip) = gpu_clip {
+                                // Cull nodes whose bounding rect is completely clipped
+                                if is_clipped_out(&positions, clip) {
                                     continue;
                                 }
+                            } else if points[0].x >= points[1].x || points[1].y >= points[2].y {
+                                // Cull nodes that are completely clipped
+                                continue;
                             }
                             let uvs = if flags == shader_flags::UNTEXTURED {
                                 [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]
@@ -1271,7 +1527,12 @@ pub fn prepare_uinodes(
                             if *node_type == NodeType::Border {
                                 flags |= shader_flags::BORDER;
                             }
+                            if gpu_clip.is_some() {
+                                flags |= shader_flags::CLIPPED;
+                            }
 
+                            let (clip, clip_points) =
+                                gpu_clip_vertex_data(gpu_clip, &positions_clipped);
                             for i in 0..4 {
                                 ui_meta.vertices.push(UiVertex {
                                     position: positions_clipped[i].into(),
@@ -1287,6 +1548,8 @@ pub fn prepare_uinodes(
                                     border: [border.left, border.top, border.right, border.bottom],
                                     size: rect_size.xy().into(),
                                     point: points[i].into(),
+                                    clip,
+                                    clip_point: clip_points[i],
                                 });
                             }
 
@@ -1304,37 +1567,33 @@ pub fn prepare_uinodes(
 
                             let atlas_extent = image.size_2d().as_vec2();
 
-                            let color = extracted_uinode.color.to_f32_array();
                             for glyph in &extracted_uinodes.glyphs[range.clone()] {
-                                let glyph_rect = glyph.rect;
-                                let size = glyph.rect.size();
+                                // Outlines and shadows extend past the glyph
+                                let (effect_flags, effect_radius) = match glyph.effect {
+                                    GlyphEffect::None => (0, 0.),
+                                    GlyphEffect::Outline(width) => {
+                                        (shader_flags::GLYPH_OUTLINE, width)
+                                    }
+                                    GlyphEffect::Shadow(blur) => (shader_flags::GLYPH_SHADOW, blur),
+                                };
+                                let glyph_rect = glyph.rect.inflate(effect_radius);
+                                let size = glyph_rect.size();
 
                                 let rect_size = glyph_rect.size().extend(1.0);
 
                                 // Specify the corners of the glyph
-                                let positions = QUAD_VERTEX_POSITIONS.map(|pos| {
+                                let mut positions = QUAD_VERTEX_POSITIONS.map(|pos| {
                                     (glyph.transform * (pos * rect_size).extend(1.)).xyz()
                                 });
+                                let axis_aligned_scale = axis_aligned_scale(&glyph.transform);
+                                if pixel_snap && axis_aligned_scale.is_some() {
+                                    snap_to_pixels(&mut positions);
+                                }
 
-                                let positions_diff = if let Some(clip) = extracted_uinode.clip {
-                                    [
-                                        Vec2::new(
-                                            f32::max(clip.min.x - positions[0].x, 0.),
-                                            f32::max(clip.min.y - positions[0].y, 0.),
-                                        ),
-                                        Vec2::new(
-                                            f32::min(clip.max.x - positions[1].x, 0.),
-                                            f32::max(clip.min.y - positions[1].y, 0.),
-                                        ),
-                                        Vec2::new(
-                                            f32::min(clip.max.x - positions[2].x, 0.),
-                                            f32::min(clip.max.y - positions[2].y, 0.),
-                                        ),
-                                        Vec2::new(
-                                            f32::max(clip.min.x - positions[3].x, 0.),
-                                            f32::min(clip.max.y - positions[3].y, 0.),
-                                        ),
-                                    ]
+                                let (cpu_clip, gpu_clip) =
+                                    split_clip(extracted_uinode.clip, axis_aligned_scale);
+                                let mut positions_diff = if let Some(clip) = cpu_clip {
+                                    clip_positions_diff(&positions, clip)
                                 } else {
                                     [Vec2::ZERO; 4]
                                 };
@@ -1346,47 +1605,77 @@ pub fn prepare_uinodes(
                                     positions[3] + positions_diff[3].extend(0.),
                                 ];
 
-                                // cull nodes that are completely clipped
-                                let transformed_rect_size =
-                                    glyph.transform.transform_vector3(rect_size);
-                                if positions_diff[0].x - positions_diff[1].x
-                                    >= transformed_rect_size.x.abs()
-                                    || positions_diff[1].y - positions_diff[2].y
-                                        >= transformed_rect_size.y.abs()
+                                // The clipped distances in the local space of the glyph
+                                let scale = axis_aligned_scale.unwrap_or(Vec2::ONE);
+                                for diff in &mut positions_diff {
+                                    *diff /= scale;
+                                }
+
+                                let mut flags = shader_flags::TEXTURED | effect_flags;
+                                if let Some(clip) = gpu_clip {
+                                    // cull glyphs whose bounding rect is completely clipped
+                                    if is_clipped_out(&positions, clip) {
+                                        continue;
+                                    }
+                                    flags |= shader_flags::CLIPPED;
+                                } else if positions_diff[0].x - positions_diff[1].x >= size.x
+                                    || positions_diff[1].y - positions_diff[2].y >= size.y
                                 {
+                                    // cull glyphs that are completely clipped
                                     continue;
                                 }
 
                                 let uvs = [
                                     Vec2::new(
-                                        glyph.rect.min.x + positions_diff[0].x,
-                                        glyph.rect.min.y + positions_diff[0].y,
+                                        glyph_rect.min.x + positions_diff[0].x,
+                                        glyph_rect.min.y + positions_diff[0].y,
                                     ),
                                     Vec2::new(
-                                        glyph.rect.max.x + positions_diff[1].x,
-                                        glyph.rect.min.y + positions_diff[1].y,
+                                        glyph_rect.max.x + positions_diff[1].x,
+                                        glyph_rect.min.y + positions_diff[1].y,
                                     ),
                                     Vec2::new(
-                                        glyph.rect.max.x + positions_diff[2].x,
-                                        glyph.rect.max.y + positions_diff[2].y,
+                                        glyph_rect.max.x + positions_diff[2].x,
+                                        glyph_rect.max.y + positions_diff[2].y,
                                     ),
                                     Vec2::new(
-                                        glyph.rect.min.x + positions_diff[3].x,
-                                        glyph.rect.max.y + positions_diff[3].y,
+                                        glyph_rect.min.x + positions_diff[3].x,
+                                        glyph_rect.max.y + positions_diff[3].y,
                                     ),
                                 ]
                                 .map(|pos| pos / atlas_extent);
 
+                                // Effects sample the atlas around the glyph, but only inside of its rect
+                                let (radius, border) = if effect_flags == 0 {
+                                    ([0.0; 4], [0.0; 4])
+                                } else {
+                                    let radius = effect_radius / atlas_extent;
+                                    let min = glyph.rect.min / atlas_extent;
+                                    let max = glyph.rect.max / atlas_extent;
+                                    ([radius.x, radius.y, 0., 0.], [min.x, min.y, max.x, max.y])
+                                };
+                                let colors = [
+                                    glyph.color,
+                                    glyph.color,
+                                    glyph.bottom_color,
+                                    glyph.bottom_color,
+                                ]
+                                .map(LinearRgba::to_f32_array);
+
+                                let (clip, clip_points) =
+                                    gpu_clip_vertex_data(gpu_clip, &positions_clipped);
                                 for i in 0..4 {
                                     ui_meta.vertices.push(UiVertex {
                                         position: positions_clipped[i].into(),
                                         uv: uvs[i].into(),
-                                        color,
-                                        flags: shader_flags::TEXTURED | shader_flags::CORNERS[i],
-                                        radius: [0.0; 4],
-                                        border: [0.0; 4],
+                                        color: colors[i],
+                                        flags: flags | shader_flags::CORNERS[i],
+                                        radius,
+                                        border,
                                         size: size.into(),
                                         point: [0.0; 2],
+                                        clip,
+                                        clip_point: clip_points[i],
                                     });
                                 }
 
@@ -1414,3 +1703,107 @@ pub fn prepare_uinodes(
     }
     extracted_uinodes.clear();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        axis_aligned_scale, clip_polygon, gpu_clip_vertex_data, is_clipped_out, split_clip,
+    };
+    use crate::CalculatedClip;
+    use bevy_math::{Affine2, Mat4, Rect, Vec2};
+    use core::f32::consts::FRAC_PI_4;
+
+    /// A 100x100 clip centered on the origin, rotated by 45 degrees.
+    fn rotated_clip() -> CalculatedClip {
+        let ui_from_local = Affine2::from_angle(FRAC_PI_4);
+        let local_clip = Rect::new(-50., -50., 50., 50.);
+        let clip = [
+            local_clip.min,
+            Vec2::new(local_clip.max.x, local_clip.min.y),
+            local_clip.max,
+            Vec2::new(local_clip.min.x, local_clip.max.y),
+        ]
+        .into_iter()
+        .fold(Rect::EMPTY, |clip, corner| {
+            clip.union_point(ui_from_local.transform_point2(corner))
+        });
+        CalculatedClip {
+            clip,
+            local_clip,
+            local_from_ui: ui_from_local.inverse(),
+        }
+    }
+
+    /// The corners of a 100x100 node centered on `center`.
+    fn node_corners(center: Vec2) -> [Vec2; 4] {
+        [
+            Vec2::new(-50., -50.),
+            Vec2::new(50., -50.),
+            Vec2::new(50., 50.),
+            Vec2::new(-50., 50.),
+        ]
+        .map(|corner| corner + center)
+    }
+
+    #[test]
+    fn rotated_clip_is_applied_in_the_fragment_shader() {
+        let clip = rotated_clip();
+        let axis_aligned_clip = CalculatedClip::new(Rect::new(-50., -50., 50., 50.));
+        let rotation = Mat4::from_rotation_z(0.3);
+
+        // Only axis-aligned clips of axis-aligned nodes are applied by moving the corners.
+        assert_eq!(
+            split_clip(Some(axis_aligned_clip), axis_aligned_scale(&Mat4::IDENTITY)),
+            (Some(axis_aligned_clip.clip), None)
+        );
+        assert_eq!(
+            split_clip(Some(axis_aligned_clip), axis_aligned_scale(&rotation)),
+            (None, Some(axis_aligned_clip))
+        );
+        assert_eq!(
+            split_clip(Some(clip), axis_aligned_scale(&Mat4::IDENTITY)),
+            (None, Some(clip))
+        );
+
+        let positions = node_corners(Vec2::ZERO).map(|corner| corner.extend(0.));
+        assert!(!is_clipped_out(&positions, clip));
+        let (clip_rect, clip_points) = gpu_clip_vertex_data(Some(clip), &positions);
+        assert_eq!(clip_rect, [-50., -50., 50., 50.]);
+        // The corners of the node stick out of the rotated clip.
+        for point in clip_points {
+            let point = Vec2::from(point);
+            assert!(!clip.local_clip.contains(point));
+            assert!((point.length() - 50. * core::f32::consts::SQRT_2).abs() < 1e-3);
+        }
+
+        let positions = node_corners(Vec2::new(200., 0.)).map(|corner| corner.extend(0.));
+        assert!(is_clipped_out(&positions, clip));
+
+        // Without a clip, nothing is clipped.
+        let (clip_rect, clip_points) = gpu_clip_vertex_data(None, &positions);
+        let clip_rect = Rect::new(clip_rect[0], clip_rect[1], clip_rect[2], clip_rect[3]);
+        assert!(clip_points
+            .iter()
+            .all(|&point| clip_rect.contains(Vec2::from(point))));
+    }
+
+    #[test]
+    fn clip_polygon_to_rotated_clip() {
+        let clip = rotated_clip();
+
+        // The rotated clip cuts the corners of the node, leaving an octagon.
+        let polygon = clip_polygon(&node_corners(Vec2::ZERO), clip);
+        assert_eq!(polygon.len(), 8);
+        for point in polygon {
+            assert!(Rect::new(-50., -50., 50., 50.)
+                .inflate(1e-3)
+                .contains(point));
+            assert!(clip
+                .local_clip
+                .inflate(1e-3)
+                .contains(clip.local_from_ui.transform_point2(point)));
+        }
+
+        assert!(clip_polygon(&node_corners(Vec2::new(200., 0.)), clip).is_empty());
+    }
+}
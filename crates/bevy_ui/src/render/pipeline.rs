@@ -74,6 +74,10 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x2,
                 // position relative to the center
                 VertexFormat::Float32x2,
+                // clip rect
+                VertexFormat::Float32x4,
+                // position in the space of the clip rect
+                VertexFormat::Float32x2,
             ],
         );
         let shader_defs = if key.anti_alias {
@@ -1,7 +1,11 @@
 use core::ops::Range;
 
-use super::{ImageNodeBindGroups, UiBatch, UiMeta, UiViewTarget};
+use super::{
+    ExtractedUiIntegerScale, ImageNodeBindGroups, UiBatch, UiIntegerScaleTarget, UiMeta,
+    UiViewTarget,
+};
 use crate::UiCameraView;
+use bevy_core_pipeline::blit::BlitPipeline;
 use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem},
@@ -12,7 +16,10 @@ use bevy_render::{
     camera::ExtractedCamera,
     render_graph::*,
     render_phase::*,
-    render_resource::{CachedRenderPipelineId, RenderPassDescriptor},
+    render_resource::{
+        BindGroupEntries, CachedRenderPipelineId, LoadOp, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor, StoreOp,
+    },
     renderer::*,
     view::*,
 };
@@ -22,6 +29,10 @@ pub struct UiPassNode {
     ui_view_query: QueryState<(&'static ExtractedView, &'static UiViewTarget)>,
     ui_view_target_query: QueryState<(&'static ViewTarget, &'static ExtractedCamera)>,
     ui_camera_view_query: QueryState<&'static UiCameraView>,
+    ui_integer_scale_query: QueryState<(
+        &'static ExtractedUiIntegerScale,
+        &'static UiIntegerScaleTarget,
+    )>,
 }
 
 impl UiPassNode {
@@ -30,6 +41,7 @@ impl UiPassNode {
             ui_view_query: world.query_filtered(),
             ui_view_target_query: world.query(),
             ui_camera_view_query: world.query(),
+            ui_integer_scale_query: world.query(),
         }
     }
 }
@@ -39,6 +51,7 @@ impl Node for UiPassNode {
         self.ui_view_query.update_archetypes(world);
         self.ui_view_target_query.update_archetypes(world);
         self.ui_camera_view_query.update_archetypes(world);
+        self.ui_integer_scale_query.update_archetypes(world);
     }
 
     fn run(
@@ -87,19 +100,72 @@ impl Node for UiPassNode {
         } else {
             input_view_entity
         };
+        // With an integer scale, the UI is rendered to an offscreen texture, and upscaled to the
+        // viewport of the camera afterwards.
+        let integer_scale = self
+            .ui_integer_scale_query
+            .get_manual(world, input_view_entity)
+            .ok();
+        let color_attachment = match integer_scale {
+            Some((_, scale_target)) => RenderPassColorAttachment {
+                view: &scale_target.texture.default_view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Default::default()),
+                    store: StoreOp::Store,
+                },
+            },
+            None => target.get_unsampled_color_attachment(),
+        };
         let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
             label: Some("ui_pass"),
-            color_attachments: &[Some(target.get_unsampled_color_attachment())],
+            color_attachments: &[Some(color_attachment)],
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
         });
-        if let Some(viewport) = camera.viewport.as_ref() {
+        if let (None, Some(viewport)) = (integer_scale, camera.viewport.as_ref()) {
             render_pass.set_camera_viewport(viewport);
         }
         if let Err(err) = transparent_phase.render(&mut render_pass, world, view_entity) {
             error!("Error encountered while rendering the ui phase {err:?}");
         }
+        drop(render_pass);
+
+        let Some((integer_scale, scale_target)) = integer_scale else {
+            return Ok(());
+        };
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(scale_target.pipeline)
+        else {
+            return Ok(());
+        };
+        let blit_pipeline = world.resource::<BlitPipeline>();
+        let bind_group = render_context.render_device().create_bind_group(
+            "ui_integer_scale_bind_group",
+            &blit_pipeline.texture_bind_group,
+            &BindGroupEntries::sequential((
+                &scale_target.texture.default_view,
+                &blit_pipeline.sampler,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("ui_integer_scale_pass"),
+            color_attachments: &[Some(target.get_unsampled_color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        // Cover the viewport with whole UI pixels, so that each texel is upscaled to the same
+        // number of physical pixels by the nearest sampler.
+        let position = integer_scale.viewport_position.as_vec2();
+        let size = (integer_scale.size * integer_scale.scale).as_vec2();
+        render_pass.set_viewport(position.x, position.y, size.x, size.y, 0.0, 1.0);
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
 
         Ok(())
     }
@@ -1,9 +1,12 @@
-#import bevy_render::view::View
+#import bevy_render::{maths::PI, view::View}
 
 const TEXTURED = 1u;
 const RIGHT_VERTEX = 2u;
 const BOTTOM_VERTEX = 4u;
 const BORDER: u32 = 8u;
+const CLIPPED: u32 = 16u;
+const GLYPH_OUTLINE: u32 = 32u;
+const GLYPH_SHADOW: u32 = 64u;
 
 fn enabled(flags: u32, mask: u32) -> bool {
     return (flags & mask) != 0u;
@@ -22,6 +25,11 @@ struct VertexOutput {
 
     // Position relative to the center of the rectangle.
     @location(6) point: vec2<f32>,
+
+    // x: min x, y: min y, z: max x, w: max y.
+    @location(7) @interpolate(flat) clip: vec4<f32>,
+    // Position in the space of the clip rect.
+    @location(8) clip_point: vec2<f32>,
     @builtin(position) position: vec4<f32>,
 };
 
@@ -39,6 +47,8 @@ fn vertex(
     @location(5) border: vec4<f32>,
     @location(6) size: vec2<f32>,
     @location(7) point: vec2<f32>,
+    @location(8) clip: vec4<f32>,
+    @location(9) clip_point: vec2<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
@@ -49,6 +59,8 @@ fn vertex(
     out.size = size;
     out.border = border;
     out.point = point;
+    out.clip = clip;
+    out.clip_point = clip_point;
 
     return out;
 }
@@ -172,13 +184,68 @@ fn draw_background(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     return vec4(color.rgb, saturate(color.a * t));
 }
 
+// Returns the coverage of a glyph at `uv`, or zero outside of `glyph_rect`, the rect of the glyph in
+// its atlas.
+fn glyph_coverage(uv: vec2<f32>, glyph_rect: vec4<f32>) -> f32 {
+    let inside = all(uv >= glyph_rect.xy) && all(uv <= glyph_rect.zw);
+    return select(0.0, textureSampleLevel(sprite_texture, sprite_sampler, uv, 0.0).a, inside);
+}
+
+// Returns the coverage of the glyph dilated by the outline width, for `TextOutline`.
+//
+// For glyphs, `radius.xy` is the width of the outline and `border` is the rect of the glyph, both in
+// uv space.
+fn glyph_outline_coverage(in: VertexOutput) -> f32 {
+    var coverage = glyph_coverage(in.uv, in.border);
+    for (var i = 0u; i < 16u; i += 1u) {
+        let angle = f32(i) * PI / 8.0;
+        let offset = vec2(cos(angle), sin(angle)) * in.radius.xy;
+        coverage = max(coverage, glyph_coverage(in.uv + offset, in.border));
+        coverage = max(coverage, glyph_coverage(in.uv + 0.5 * offset, in.border));
+    }
+    return coverage;
+}
+
+// Returns the coverage of the glyph blurred by the shadow blur radius, for `TextDropShadow`.
+//
+// For glyphs, `radius.xy` is the blur radius and `border` is the rect of the glyph, both in uv space.
+fn glyph_shadow_coverage(in: VertexOutput) -> f32 {
+    // Binomial weights, approximating a gaussian blur.
+    var weights = array<f32, 5>(1.0, 4.0, 6.0, 4.0, 1.0);
+    var coverage = 0.0;
+    for (var y = 0u; y < 5u; y += 1u) {
+        for (var x = 0u; x < 5u; x += 1u) {
+            let offset = (vec2(f32(x), f32(y)) - 2.0) * 0.5 * in.radius.xy;
+            coverage += weights[x] * weights[y] * glyph_coverage(in.uv + offset, in.border);
+        }
+    }
+    return coverage / 256.0;
+}
+
+// Returns the visible part of the fragment, when the node or the rect it's clipped to is rotated
+// and can't be clipped by moving the corners of the node.
+fn clip_coverage(in: VertexOutput) -> f32 {
+    // Distances from the clip rect edges, in pixels, positive outside of the clip rect.
+    // Derivatives must be computed in uniform control flow, so this is done before checking the flag.
+    let pixel_size = max(fwidth(in.clip_point), vec2(1e-4));
+    let distance = max(in.clip.xy - in.clip_point, in.clip_point - in.clip.zw) / pixel_size;
+    let coverage = saturate(0.5 - max(distance.x, distance.y));
+    return select(1.0, coverage, enabled(in.flags, CLIPPED));
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
 
-    if enabled(in.flags, BORDER) {
-        return draw(in, texture_color);
+    var color: vec4<f32>;
+    if enabled(in.flags, GLYPH_OUTLINE) {
+        color = vec4(in.color.rgb, in.color.a * glyph_outline_coverage(in));
+    } else if enabled(in.flags, GLYPH_SHADOW) {
+        color = vec4(in.color.rgb, in.color.a * glyph_shadow_coverage(in));
+    } else if enabled(in.flags, BORDER) {
+        color = draw(in, texture_color);
     } else {
-        return draw_background(in, texture_color);
+        color = draw_background(in, texture_color);
     }
+    return vec4(color.rgb, color.a * clip_coverage(in));
 }
@@ -11,7 +11,7 @@ use bevy_ecs::{
     },
 };
 use bevy_image::BevyDefault as _;
-use bevy_math::{FloatOrd, Mat4, Rect, Vec2, Vec4Swizzles};
+use bevy_math::{FloatOrd, Mat4, Rect, Vec2, Vec3Swizzles, Vec4Swizzles};
 use bevy_render::sync_world::{MainEntity, TemporaryRenderEntity};
 use bevy_render::{
     extract_component::ExtractComponentPlugin,
@@ -342,7 +342,7 @@ pub struct ExtractedUiMaterialNode<M: UiMaterial> {
     pub border: BorderRect,
     pub border_radius: ResolvedBorderRadius,
     pub material: AssetId<M>,
-    pub clip: Option<Rect>,
+    pub clip: Option<CalculatedClip>,
     // Camera to render this UI node to. By the time it is extracted,
     // it is defaulted to a single camera if only one exists.
     // Nodes with ambiguous camera will be ignored.
@@ -411,7 +411,7 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
             },
             border: computed_node.border(),
             border_radius: computed_node.border_radius(),
-            clip: clip.map(|clip| clip.clip),
+            clip: clip.copied(),
             extracted_camera_entity,
             main_entity: entity.into(),
         });
@@ -481,25 +481,58 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
                         (extracted_uinode.transform * (pos * rect_size).extend(1.0)).xyz()
                     });
 
-                    let positions_diff = if let Some(clip) = extracted_uinode.clip {
-                        [
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[0].x, 0.),
-                                f32::max(clip.min.y - positions[0].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[1].x, 0.),
-                                f32::max(clip.min.y - positions[1].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[2].x, 0.),
-                                f32::min(clip.max.y - positions[2].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[3].x, 0.),
-                                f32::min(clip.max.y - positions[3].y, 0.),
-                            ),
-                        ]
+                    let size = extracted_uinode.rect.size().into();
+                    let radius = [
+                        extracted_uinode.border_radius.top_left,
+                        extracted_uinode.border_radius.top_right,
+                        extracted_uinode.border_radius.bottom_right,
+                        extracted_uinode.border_radius.bottom_left,
+                    ];
+                    let border = [
+                        extracted_uinode.border.left,
+                        extracted_uinode.border.top,
+                        extracted_uinode.border.right,
+                        extracted_uinode.border.bottom,
+                    ];
+
+                    // Moving the corners of the node only works when both the node and the clip are
+                    // axis-aligned, otherwise the node is clipped to a polygon
+                    let axis_aligned_scale = axis_aligned_scale(&extracted_uinode.transform);
+                    let (cpu_clip, gpu_clip) =
+                        split_clip(extracted_uinode.clip, axis_aligned_scale);
+
+                    if let Some(clip) = gpu_clip {
+                        // The fragment shader of the material can't apply the clip, so the
+                        // geometry of the node is clipped instead
+                        let polygon = clip_polygon(&positions.map(Vec3Swizzles::xy), clip);
+                        if polygon.len() < 3 {
+                            continue;
+                        }
+                        let node_from_ui = extracted_uinode.transform.inverse();
+                        let z = positions[0].z;
+                        for i in 1..polygon.len() - 1 {
+                            for point in [polygon[0], polygon[i], polygon[i + 1]] {
+                                let position = point.extend(z);
+                                let uv = node_from_ui.transform_point3(position).xy()
+                                    / rect_size.xy()
+                                    + 0.5;
+                                ui_meta.vertices.push(UiMaterialVertex {
+                                    position: position.into(),
+                                    uv: uv.into(),
+                                    size,
+                                    radius,
+                                    border,
+                                });
+                            }
+                        }
+                        index += 3 * (polygon.len() - 2) as u32;
+                        existing_batch.unwrap().1.range.end = index;
+                        ui_phase.items[batch_item_index].batch_range_mut().end += 1;
+                        continue;
+                    }
+
+                    let mut positions_diff = if let Some(clip) = cpu_clip {
+                        clip_positions_diff(&positions, clip)
                     } else {
                         [Vec2::ZERO; 4]
                     };
@@ -511,22 +544,17 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
                         positions[3] + positions_diff[3].extend(0.),
                     ];
 
-                    let transformed_rect_size =
-                        extracted_uinode.transform.transform_vector3(rect_size);
-
-                    // Don't try to cull nodes that have a rotation
-                    // In a rotation around the Z-axis, this value is 0.0 for an angle of 0.0 or π
-                    // In those two cases, the culling check can proceed normally as corners will be on
-                    // horizontal / vertical lines
-                    // For all other angles, bypass the culling check
-                    // This does not properly handles all rotations on all axis
-                    if extracted_uinode.transform.x_axis[1] == 0.0 {
-                        // Cull nodes that are completely clipped
-                        if positions_diff[0].x - positions_diff[1].x >= transformed_rect_size.x
-                            || positions_diff[1].y - positions_diff[2].y >= transformed_rect_size.y
-                        {
-                            continue;
-                        }
+                    // The clipped distances in the local space of the node
+                    let scale = axis_aligned_scale.unwrap_or(Vec2::ONE);
+                    for diff in &mut positions_diff {
+                        *diff /= scale;
+                    }
+
+                    // Cull nodes that are completely clipped
+                    if positions_diff[0].x - positions_diff[1].x >= rect_size.x
+                        || positions_diff[1].y - positions_diff[2].y >= rect_size.y
+                    {
+                        continue;
                     }
                     let uvs = [
                         Vec2::new(
@@ -552,19 +580,9 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
                         ui_meta.vertices.push(UiMaterialVertex {
                             position: positions_clipped[i].into(),
                             uv: uvs[i].into(),
-                            size: extracted_uinode.rect.size().into(),
-                            radius: [
-                                extracted_uinode.border_radius.top_left,
-                                extracted_uinode.border_radius.top_right,
-                                extracted_uinode.border_radius.bottom_right,
-                                extracted_uinode.border_radius.bottom_left,
-                            ],
-                            border: [
-                                extracted_uinode.border.left,
-                                extracted_uinode.border.top,
-                                extracted_uinode.border.right,
-                                extracted_uinode.border.bottom,
-                            ],
+                            size,
+                            radius,
+                            border,
                         });
                     }
 
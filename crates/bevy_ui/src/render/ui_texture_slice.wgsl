@@ -40,6 +40,11 @@ struct UiVertexOutput {
     // x, y = top, left corner of the atlas rect
     // z, w = bottom, right corner of the atlas rect
     @location(5) @interpolate(flat) atlas_rect: vec4<f32>,
+
+    // x: min x, y: min y, z: max x, w: max y.
+    @location(6) @interpolate(flat) clip: vec4<f32>,
+    // Position in the space of the clip rect.
+    @location(7) clip_point: vec2<f32>,
     @builtin(position) position: vec4<f32>,
 }
 
@@ -52,6 +57,8 @@ fn vertex(
     @location(4) target_slices: vec4<f32>,
     @location(5) repeat: vec4<f32>,
     @location(6) atlas_rect: vec4<f32>,
+    @location(7) clip: vec4<f32>,
+    @location(8) clip_point: vec2<f32>,
 ) -> UiVertexOutput {
     var out: UiVertexOutput;
     out.uv = vertex_uv;
@@ -61,6 +68,8 @@ fn vertex(
     out.target_slices = target_slices;
     out.repeat = repeat;
     out.atlas_rect = atlas_rect;
+    out.clip = clip;
+    out.clip_point = clip_point;
     return out;
 }
 
@@ -115,6 +124,16 @@ fn map_uvs_to_slice(
     return vec2(x, y);
 }
 
+// Returns the visible part of the fragment, when the node or the rect it's clipped to is rotated
+// and can't be clipped by moving the corners of the node. Unclipped nodes have a clip rect
+// containing the whole node.
+fn clip_coverage(in: UiVertexOutput) -> f32 {
+    // Distances from the clip rect edges, in pixels, positive outside of the clip rect.
+    let pixel_size = max(fwidth(in.clip_point), vec2(1e-4));
+    let distance = max(in.clip.xy - in.clip_point, in.clip_point - in.clip.zw) / pixel_size;
+    return saturate(0.5 - max(distance.x, distance.y));
+}
+
 @fragment
 fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     // map the target uvs to slice coords
@@ -123,5 +142,6 @@ fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     // map the slice coords to texture coords
     let atlas_uv = in.atlas_rect.xy + uv * (in.atlas_rect.zw - in.atlas_rect.xy);
 
-    return in.color * textureSample(sprite_texture, sprite_sampler, atlas_uv);
+    let color = in.color * textureSample(sprite_texture, sprite_sampler, atlas_uv);
+    return vec4(color.rgb, color.a * clip_coverage(in));
 }
@@ -82,6 +82,11 @@ struct UiTextureSliceVertex {
     pub border: [f32; 4],
     pub repeat: [f32; 4],
     pub atlas: [f32; 4],
+    /// Clip rect applied in the fragment shader.
+    /// Ordering: min x, min y, max x, max y.
+    pub clip: [f32; 4],
+    /// Position in the space of the clip rect.
+    pub clip_point: [f32; 2],
 }
 
 #[derive(Component)]
@@ -175,6 +180,10 @@ impl SpecializedRenderPipeline for UiTextureSlicePipeline {
                 VertexFormat::Float32x4,
                 // normalized texture atlas rect (left, top, right, bottom)
                 VertexFormat::Float32x4,
+                // clip rect
+                VertexFormat::Float32x4,
+                // position in the space of the clip rect
+                VertexFormat::Float32x2,
             ],
         );
         let shader_defs = Vec::new();
@@ -229,7 +238,7 @@ pub struct ExtractedUiTextureSlice {
     pub rect: Rect,
     pub atlas_rect: Option<Rect>,
     pub image: AssetId<Image>,
-    pub clip: Option<Rect>,
+    pub clip: Option<CalculatedClip>,
     pub extracted_camera_entity: Entity,
     pub color: LinearRgba,
     pub image_scale_mode: SpriteImageMode,
@@ -319,7 +328,7 @@ pub fn extract_ui_texture_slices(
                 min: Vec2::ZERO,
                 max: uinode.size,
             },
-            clip: clip.map(|clip| clip.clip),
+            clip: clip.copied(),
             image: image.image.id(),
             extracted_camera_entity,
             image_scale_mode,
@@ -515,26 +524,12 @@ pub fn prepare_ui_slices(
                         .map(|pos| (texture_slices.transform * (pos * rect_size).extend(1.)).xyz());
 
                     // Calculate the effect of clipping
-                    // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
-                    let positions_diff = if let Some(clip) = texture_slices.clip {
-                        [
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[0].x, 0.),
-                                f32::max(clip.min.y - positions[0].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[1].x, 0.),
-                                f32::max(clip.min.y - positions[1].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[2].x, 0.),
-                                f32::min(clip.max.y - positions[2].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[3].x, 0.),
-                                f32::min(clip.max.y - positions[3].y, 0.),
-                            ),
-                        ]
+                    // Moving the corners of the node only works when both the node and the clip are
+                    // axis-aligned, otherwise the clip is applied in the fragment shader
+                    let axis_aligned_scale = axis_aligned_scale(&texture_slices.transform);
+                    let (cpu_clip, gpu_clip) = split_clip(texture_slices.clip, axis_aligned_scale);
+                    let mut positions_diff = if let Some(clip) = cpu_clip {
+                        clip_positions_diff(&positions, clip)
                     } else {
                         [Vec2::ZERO; 4]
                     };
@@ -546,22 +541,22 @@ pub fn prepare_ui_slices(
                         positions[3] + positions_diff[3].extend(0.),
                     ];
 
-                    let transformed_rect_size =
-                        texture_slices.transform.transform_vector3(rect_size);
+                    // The clipped distances in the local space of the node
+                    let scale = axis_aligned_scale.unwrap_or(Vec2::ONE);
+                    for diff in &mut positions_diff {
+                        *diff /= scale;
+                    }
 
-                    // Don't try to cull nodes that have a rotation
-                    // In a rotation around the Z-axis, this value is 0.0 for an angle of 0.0 or π
-                    // In those two cases, the culling check can proceed normally as corners will be on
-                    // horizontal / vertical lines
-                    // For all other angles, bypass the culling check
-                    // This does not properly handles all rotations on all axis
-                    if texture_slices.transform.x_axis[1] == 0.0 {
-                        // Cull nodes that are completely clipped
-                        if positions_diff[0].x - positions_diff[1].x >= transformed_rect_size.x
-                            || positions_diff[1].y - positions_diff[2].y >= transformed_rect_size.y
-                        {
+                    if let Some(clip) = gpu_clip {
+                        // Cull nodes whose bounding rect is completely clipped
+                        if is_clipped_out(&positions, clip) {
                             continue;
                         }
+                    } else if positions_diff[0].x - positions_diff[1].x >= rect_size.x
+                        || positions_diff[1].y - positions_diff[2].y >= rect_size.y
+                    {
+                        // Cull nodes that are completely clipped
+                        continue;
                     }
                     let flags = if texture_slices.image != AssetId::default() {
                         shader_flags::TEXTURED
@@ -624,6 +619,7 @@ pub fn prepare_ui_slices(
                         &texture_slices.image_scale_mode,
                     );
 
+                    let (clip, clip_points) = gpu_clip_vertex_data(gpu_clip, &positions_clipped);
                     for i in 0..4 {
                         ui_meta.vertices.push(UiTextureSliceVertex {
                             position: positions_clipped[i].into(),
@@ -633,6 +629,8 @@ pub fn prepare_ui_slices(
                             border,
                             repeat,
                             atlas,
+                            clip,
+                            clip_point: clip_points[i],
                         });
                     }
 
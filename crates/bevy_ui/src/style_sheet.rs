@@ -0,0 +1,755 @@
+//! Style sheets that apply reflected component values to UI node trees.
+//!
+//! See [`StyleSheet`] for the file format.
+
+use crate::{animate_ui_transitions, Node, UiSystem};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{
+    io::Reader, Asset, AssetApp, AssetEvent, AssetId, AssetLoader, Assets, Handle, LoadContext,
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    system::SystemState,
+};
+use bevy_platform_support::collections::HashSet;
+use bevy_reflect::{
+    serde::TypedReflectDeserializer, std_traits::ReflectDefault, GetPath, ParsedPath,
+    PartialReflect, Reflect, TypePath, TypeRegistration, TypeRegistry, TypeRegistryArc,
+};
+use core::{any::TypeId, fmt};
+use serde::de::{DeserializeSeed, Error as _, MapAccess, SeqAccess, Visitor};
+use thiserror::Error;
+use tracing::warn;
+
+/// Adds support for [`StyleSheet`] assets applied through the [`UiStyleSheet`] component.
+pub struct StyleSheetPlugin;
+
+impl Plugin for StyleSheetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StyleSheet>()
+            .init_asset_loader::<StyleSheetLoader>()
+            .register_type::<UiStyleSheet>()
+            .add_systems(
+                PostUpdate,
+                apply_style_sheets
+                    .before(UiSystem::Prepare)
+                    .before(animate_ui_transitions),
+            );
+    }
+}
+
+/// Applies a [`StyleSheet`] to this entity and all of its descendants.
+///
+/// Matching rules are applied when this component is added or changed, when the style sheet
+/// is (re)loaded, and to every [`Node`] spawned in the tree afterwards.
+///
+/// If several ancestors of an entity have a [`UiStyleSheet`], the outermost sheet is applied first,
+/// so the sheets closer to the entity take precedence.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct UiStyleSheet(pub Handle<StyleSheet>);
+
+/// A list of rules that set reflected component values on the UI entities matching a selector.
+///
+/// Style sheets are written in RON, with the `.style.ron` extension:
+///
+/// ```ron
+/// (
+///     rules: [
+///         (
+///             selector: ".Button",
+///             properties: {
+///                 "Node.width": Px(150.0),
+///                 "Node.padding": (left: Px(8.0), right: Px(8.0), top: Px(4.0), bottom: Px(4.0)),
+///                 "BackgroundColor": (Srgba((red: 0.15, green: 0.15, blue: 0.15, alpha: 1.0))),
+///             },
+///         ),
+///         (
+///             selector: "#menu > .Button",
+///             properties: {
+///                 "Node.margin.top": Px(10.0),
+///             },
+///         ),
+///     ],
+/// )
+/// ```
+///
+/// Selectors support a limited subset of CSS:
+/// - `*` matches any entity.
+/// - `#title` matches entities with a [`Name`] equal to `title`.
+/// - `.Button` matches entities with the `Button` component. Components can be written with their short
+///   or full type path, and must be registered with [`ReflectComponent`].
+/// - Simple selectors can be combined, such as `.Button#ok`.
+/// - `a b` matches `b` if it is a descendant of `a`, and `a > b` matches `b` if it is a child of `a`.
+///
+/// Property keys are a component type, optionally followed by a [reflection path](bevy_reflect::GetPath)
+/// to one of its fields. A key without a path replaces the whole component. A key with a path only changes
+/// that field, inserting the [default](ReflectDefault) value of the component first if it is missing.
+///
+/// Rules are applied in order, so later rules override earlier ones. Removing a property from a style sheet
+/// does not revert the value it previously set.
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct StyleSheet {
+    /// The rules of this style sheet, in the order they are applied.
+    pub rules: Vec<StyleRule>,
+}
+
+impl StyleSheet {
+    /// Parses a style sheet from RON, resolving component types with `registry`.
+    pub fn from_ron(ron: &str, registry: &TypeRegistry) -> Result<Self, ron::error::SpannedError> {
+        let mut deserializer = ron::de::Deserializer::from_str(ron)?;
+        StyleSheetDeserializer { registry }
+            .deserialize(&mut deserializer)
+            .map_err(|e| deserializer.span_error(e))
+    }
+
+    /// Applies every rule matching `entity` to it.
+    pub fn apply(&self, world: &mut World, entity: Entity, registry: &TypeRegistry) {
+        for rule in &self.rules {
+            if rule.selector.matches(world, entity) {
+                for property in &rule.properties {
+                    property.apply(world, entity, registry);
+                }
+            }
+        }
+    }
+}
+
+/// A [`Selector`] and the properties applied to the entities it matches.
+#[derive(Debug)]
+pub struct StyleRule {
+    /// The entities this rule applies to.
+    pub selector: Selector,
+    /// The values applied to the matching entities.
+    pub properties: Vec<StyleProperty>,
+}
+
+/// A reflected component value, or a field of one, set by a [`StyleRule`].
+pub struct StyleProperty {
+    component: TypeId,
+    field: Option<ParsedPath>,
+    value: Box<dyn PartialReflect>,
+}
+
+impl fmt::Debug for StyleProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StyleProperty")
+            .field("component", &self.component)
+            .field("field", &self.field)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl StyleProperty {
+    /// Sets this property on `entity`.
+    pub fn apply(&self, world: &mut World, entity: Entity, registry: &TypeRegistry) {
+        let Some(registration) = registry.get(self.component) else {
+            return;
+        };
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            return;
+        };
+        let Ok(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+
+        let Some(field) = self.field.as_ref() else {
+            reflect_component.apply_or_insert(&mut entity, &*self.value, registry);
+            return;
+        };
+
+        if !reflect_component.contains(&entity) {
+            let Some(reflect_default) = registration.data::<ReflectDefault>() else {
+                return;
+            };
+            let default = reflect_default.default();
+            reflect_component.insert(&mut entity, default.as_partial_reflect(), registry);
+        }
+        let Some(mut component) = reflect_component.reflect_mut(&mut entity) else {
+            return;
+        };
+        let result = match component.reflect_path_mut(field) {
+            Ok(target) => target.try_apply(&*self.value).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            warn!(
+                "Could not apply style property `{}.{field}`: {e}",
+                registration.type_info().type_path_table().short_path()
+            );
+        }
+    }
+}
+
+/// Selects entities by [`Name`], components and hierarchy, using a subset of the CSS selector syntax.
+///
+/// See [`StyleSheet`] for the supported syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    /// The compound selectors, from the outermost ancestor to the matched entity.
+    /// The combinator of the first compound is ignored.
+    compounds: Vec<(Combinator, CompoundSelector)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CompoundSelector {
+    name: Option<String>,
+    components: Vec<TypeId>,
+}
+
+/// An error produced when parsing a [`Selector`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SelectorError {
+    /// The selector doesn't contain anything to match.
+    #[error("selector is empty")]
+    Empty,
+    /// The selector contains a character that is not allowed at its position.
+    #[error("unexpected character `{0}` in selector")]
+    UnexpectedCharacter(char),
+    /// A `>` combinator is not between two compound selectors.
+    #[error("dangling `>` in selector")]
+    DanglingCombinator,
+    /// A `.` is not followed by a registered component.
+    #[error("`{0}` is not a registered component")]
+    UnknownComponent(String),
+}
+
+impl Selector {
+    /// Parses a selector, resolving the components it uses with `registry`.
+    pub fn parse(selector: &str, registry: &TypeRegistry) -> Result<Self, SelectorError> {
+        let mut compounds = Vec::new();
+        let mut combinator = Combinator::Descendant;
+        let mut chars = selector.chars().peekable();
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+                continue;
+            }
+            if c == '>' {
+                if compounds.is_empty() || combinator == Combinator::Child {
+                    return Err(SelectorError::DanglingCombinator);
+                }
+                combinator = Combinator::Child;
+                chars.next();
+                continue;
+            }
+
+            let mut compound = CompoundSelector::default();
+            let mut empty = true;
+            while let Some(&c) = chars.peek() {
+                match c {
+                    '*' => {
+                        chars.next();
+                    }
+                    '#' | '.' => {
+                        chars.next();
+                        let mut ident = String::new();
+                        while let Some(&c) = chars.peek() {
+                            if c.is_alphanumeric() || matches!(c, '_' | '-' | ':') {
+                                ident.push(c);
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                        if ident.is_empty() {
+                            return Err(SelectorError::UnexpectedCharacter(c));
+                        }
+                        if c == '#' {
+                            compound.name = Some(ident);
+                        } else {
+                            compound
+                                .components
+                                .push(component_type_id(&ident, registry)?);
+                        }
+                    }
+                    c if c.is_whitespace() || c == '>' => break,
+                    c => return Err(SelectorError::UnexpectedCharacter(c)),
+                }
+                empty = false;
+            }
+            if empty {
+                return Err(SelectorError::Empty);
+            }
+            compounds.push((combinator, compound));
+            combinator = Combinator::Descendant;
+        }
+
+        if combinator == Combinator::Child {
+            return Err(SelectorError::DanglingCombinator);
+        }
+        if compounds.is_empty() {
+            return Err(SelectorError::Empty);
+        }
+        Ok(Self { compounds })
+    }
+
+    /// Returns `true` if `entity` is matched by this selector.
+    pub fn matches(&self, world: &World, entity: Entity) -> bool {
+        self.matches_compounds(world, &self.compounds, entity)
+    }
+
+    fn matches_compounds(
+        &self,
+        world: &World,
+        compounds: &[(Combinator, CompoundSelector)],
+        entity: Entity,
+    ) -> bool {
+        let Some(((combinator, compound), rest)) = compounds.split_last() else {
+            return true;
+        };
+        if !compound.matches(world, entity) {
+            return false;
+        }
+        if rest.is_empty() {
+            return true;
+        }
+
+        let mut ancestor = parent(world, entity);
+        while let Some(current) = ancestor {
+            if self.matches_compounds(world, rest, current) {
+                return true;
+            }
+            if *combinator == Combinator::Child {
+                return false;
+            }
+            ancestor = parent(world, current);
+        }
+        false
+    }
+}
+
+impl CompoundSelector {
+    fn matches(&self, world: &World, entity: Entity) -> bool {
+        let Ok(entity) = world.get_entity(entity) else {
+            return false;
+        };
+        if let Some(name) = self.name.as_deref() {
+            if entity.get::<Name>().is_none_or(|n| n.as_str() != name) {
+                return false;
+            }
+        }
+        self.components
+            .iter()
+            .all(|&type_id| entity.contains_type_id(type_id))
+    }
+}
+
+fn parent(world: &World, entity: Entity) -> Option<Entity> {
+    world
+        .get_entity(entity)
+        .ok()?
+        .get::<ChildOf>()
+        .map(ChildOf::get)
+}
+
+/// Finds the registration of a component from its short or full type path.
+fn component_registration<'a>(
+    type_path: &str,
+    registry: &'a TypeRegistry,
+) -> Option<&'a TypeRegistration> {
+    registry
+        .get_with_short_type_path(type_path)
+        .or_else(|| registry.get_with_type_path(type_path))
+        .filter(|registration| registration.data::<ReflectComponent>().is_some())
+}
+
+fn component_type_id(type_path: &str, registry: &TypeRegistry) -> Result<TypeId, SelectorError> {
+    component_registration(type_path, registry)
+        .map(TypeRegistration::type_id)
+        .ok_or_else(|| SelectorError::UnknownComponent(type_path.to_string()))
+}
+
+/// Resolves a property key into the component it sets, the optional path of the field it sets,
+/// and the registration of the value's type.
+fn resolve_property<'a>(
+    key: &str,
+    registry: &'a TypeRegistry,
+) -> Result<(TypeId, Option<ParsedPath>, &'a TypeRegistration), String> {
+    let (type_path, field) = match key.split_once('.') {
+        Some((type_path, field)) => (type_path, Some(field)),
+        None => (key, None),
+    };
+    let registration = component_registration(type_path, registry)
+        .ok_or_else(|| format!("`{type_path}` is not a registered component"))?;
+    let Some(field) = field else {
+        return Ok((registration.type_id(), None, registration));
+    };
+
+    let path = ParsedPath::parse(field).map_err(|e| e.to_string())?;
+    let default = registration
+        .data::<ReflectDefault>()
+        .ok_or_else(|| format!("`{type_path}` must implement `ReflectDefault` to set its fields"))?
+        .default();
+    let field_registration = GetPath::reflect_path(&*default, &path)
+        .map_err(|e| e.to_string())?
+        .get_represented_type_info()
+        .and_then(|info| registry.get(info.type_id()))
+        .ok_or_else(|| format!("the type of `{key}` is not registered"))?;
+    Ok((registration.type_id(), Some(path), field_registration))
+}
+
+/// Loads [`StyleSheet`] assets from `.style.ron` files.
+#[derive(Debug)]
+pub struct StyleSheetLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for StyleSheetLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        StyleSheetLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`StyleSheetLoader`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum StyleSheetLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the style sheet file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    /// The file is not valid UTF-8.
+    #[error("Style sheet is not valid UTF-8: {0}")]
+    Utf8(#[from] core::str::Utf8Error),
+}
+
+impl AssetLoader for StyleSheetLoader {
+    type Asset = StyleSheet;
+    type Settings = ();
+    type Error = StyleSheetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = core::str::from_utf8(&bytes)?;
+        Ok(StyleSheet::from_ron(text, &self.type_registry.read())?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["style.ron"]
+    }
+}
+
+struct StyleSheetDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for StyleSheetDeserializer<'_> {
+    type Value = StyleSheet;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("StyleSheet", &["rules"], self)
+    }
+}
+
+impl<'de> Visitor<'de> for StyleSheetDeserializer<'_> {
+    type Value = StyleSheet;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a style sheet")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut rules = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "rules" => {
+                    rules = Some(map.next_value_seed(RulesDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                _ => return Err(A::Error::unknown_field(&key, &["rules"])),
+            }
+        }
+        Ok(StyleSheet {
+            rules: rules.ok_or_else(|| A::Error::missing_field("rules"))?,
+        })
+    }
+}
+
+struct RulesDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for RulesDeserializer<'_> {
+    type Value = Vec<StyleRule>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RulesDeserializer<'_> {
+    type Value = Vec<StyleRule>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of style rules")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut rules = Vec::new();
+        while let Some(rule) = seq.next_element_seed(RuleDeserializer {
+            registry: self.registry,
+        })? {
+            rules.push(rule);
+        }
+        Ok(rules)
+    }
+}
+
+struct RuleDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for RuleDeserializer<'_> {
+    type Value = StyleRule;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("StyleRule", &["selector", "properties"], self)
+    }
+}
+
+impl<'de> Visitor<'de> for RuleDeserializer<'_> {
+    type Value = StyleRule;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a style rule")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut selector = None;
+        let mut properties = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "selector" => {
+                    let text = map.next_value::<String>()?;
+                    selector =
+                        Some(Selector::parse(&text, self.registry).map_err(A::Error::custom)?);
+                }
+                "properties" => {
+                    properties = Some(map.next_value_seed(PropertiesDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                _ => return Err(A::Error::unknown_field(&key, &["selector", "properties"])),
+            }
+        }
+        Ok(StyleRule {
+            selector: selector.ok_or_else(|| A::Error::missing_field("selector"))?,
+            properties: properties.unwrap_or_default(),
+        })
+    }
+}
+
+struct PropertiesDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for PropertiesDeserializer<'_> {
+    type Value = Vec<StyleProperty>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PropertiesDeserializer<'_> {
+    type Value = Vec<StyleProperty>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of style properties")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut properties = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let (component, field, registration) =
+                resolve_property(&key, self.registry).map_err(A::Error::custom)?;
+            let value =
+                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?;
+            properties.push(StyleProperty {
+                component,
+                field,
+                value,
+            });
+        }
+        Ok(properties)
+    }
+}
+
+/// Applies [`StyleSheet`]s to the entities under a [`UiStyleSheet`].
+pub fn apply_style_sheets(
+    world: &mut World,
+    state: &mut SystemState<(
+        EventReader<AssetEvent<StyleSheet>>,
+        Query<(Entity, Ref<UiStyleSheet>)>,
+        Query<Entity, Added<Node>>,
+    )>,
+) {
+    let (mut events, roots, added_nodes) = state.get(world);
+
+    let mut changed_sheets = HashSet::<AssetId<StyleSheet>>::default();
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            changed_sheets.insert(*id);
+        }
+    }
+    let restyled_roots: Vec<Entity> = roots
+        .iter()
+        .filter(|(_, sheet)| sheet.is_changed() || changed_sheets.contains(&sheet.id()))
+        .map(|(entity, _)| entity)
+        .collect();
+    let added_nodes: Vec<Entity> = added_nodes.iter().collect();
+
+    let mut targets = Vec::new();
+    let mut visited = HashSet::<Entity>::default();
+    for root in restyled_roots {
+        let mut stack = vec![root];
+        while let Some(entity) = stack.pop() {
+            if !visited.insert(entity) {
+                continue;
+            }
+            targets.push(entity);
+            if let Some(children) = world.get::<Children>(entity) {
+                stack.extend(children.iter());
+            }
+        }
+    }
+    targets.extend(
+        added_nodes
+            .into_iter()
+            .filter(|entity| visited.insert(*entity)),
+    );
+    if targets.is_empty() {
+        return;
+    }
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    world.resource_scope(|world, sheets: Mut<Assets<StyleSheet>>| {
+        for entity in targets {
+            // Collect the style sheets of the entity and its ancestors, outermost first.
+            let mut chain = Vec::new();
+            let mut current = Some(entity);
+            while let Some(ancestor) = current {
+                if let Some(sheet) = world.get::<UiStyleSheet>(ancestor) {
+                    chain.push(sheet.id());
+                }
+                current = parent(world, ancestor);
+            }
+            for id in chain.into_iter().rev() {
+                if let Some(sheet) = sheets.get(id) {
+                    sheet.apply(world, entity, &registry);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BackgroundColor, Val};
+    use bevy_color::Color;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component, Default)]
+    struct Menu;
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Node>();
+        registry.register::<BackgroundColor>();
+        registry.register::<Menu>();
+        registry
+    }
+
+    #[test]
+    fn parse_selectors() {
+        let registry = registry();
+        assert!(Selector::parse("*", &registry).is_ok());
+        assert!(Selector::parse("#title", &registry).is_ok());
+        assert!(Selector::parse(".Menu > .Node#ok", &registry).is_ok());
+        assert!(Selector::parse(".Menu .Node", &registry).is_ok());
+        assert_eq!(Selector::parse("", &registry), Err(SelectorError::Empty));
+        assert_eq!(
+            Selector::parse(".Menu >", &registry),
+            Err(SelectorError::DanglingCombinator)
+        );
+        assert_eq!(
+            Selector::parse(".Missing", &registry),
+            Err(SelectorError::UnknownComponent("Missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn apply_matching_rules() {
+        let registry = registry();
+        let sheet = StyleSheet::from_ron(
+            r#"(
+                rules: [
+                    (
+                        selector: ".Menu > #item",
+                        properties: {
+                            "Node.width": Px(40.0),
+                            "BackgroundColor": (Srgba((red: 1.0, green: 0.0, blue: 0.0, alpha: 1.0))),
+                        },
+                    ),
+                ],
+            )"#,
+            &registry,
+        )
+        .unwrap();
+
+        let mut world = World::new();
+        let menu = world.spawn((Menu, Node::default())).id();
+        let item = world
+            .spawn((Node::default(), Name::new("item"), ChildOf(menu)))
+            .id();
+        let other = world.spawn((Node::default(), Name::new("item"))).id();
+
+        sheet.apply(&mut world, item, &registry);
+        sheet.apply(&mut world, other, &registry);
+
+        assert_eq!(world.get::<Node>(item).unwrap().width, Val::Px(40.0));
+        assert_eq!(
+            world.get::<BackgroundColor>(item).unwrap().0,
+            Color::srgb(1.0, 0.0, 0.0)
+        );
+        assert_eq!(world.get::<Node>(other).unwrap().width, Val::Auto);
+        assert!(world.get::<BackgroundColor>(other).is_none());
+    }
+}
@@ -0,0 +1,451 @@
+//! Animated transitions between values of UI style properties.
+
+use crate::{BackgroundColor, BorderColor, Node, Val};
+use bevy_color::{Color, Mix};
+use bevy_ecs::prelude::*;
+use bevy_math::{
+    curve::{Curve, EaseFunction, EasingCurve},
+    FloatExt,
+};
+use bevy_reflect::prelude::*;
+use bevy_time::Time;
+use core::time::Duration;
+
+/// A style property of a UI node that can be animated by a [`UiTransition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum TransitionProperty {
+    /// [`Node::width`]
+    Width,
+    /// [`Node::height`]
+    Height,
+    /// [`Node::left`], which translates positioned nodes horizontally.
+    Left,
+    /// [`Node::top`], which translates positioned nodes vertically.
+    Top,
+    /// The color of the [`BackgroundColor`] component.
+    BackgroundColor,
+    /// The color of the [`BorderColor`] component.
+    BorderColor,
+}
+
+/// A value of a [`TransitionProperty`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub enum TransitionValue {
+    /// A value of [`TransitionProperty::Width`].
+    Width(Val),
+    /// A value of [`TransitionProperty::Height`].
+    Height(Val),
+    /// A value of [`TransitionProperty::Left`].
+    Left(Val),
+    /// A value of [`TransitionProperty::Top`].
+    Top(Val),
+    /// A value of [`TransitionProperty::BackgroundColor`].
+    BackgroundColor(Color),
+    /// A value of [`TransitionProperty::BorderColor`].
+    BorderColor(Color),
+}
+
+impl TransitionValue {
+    /// The property this value belongs to.
+    pub const fn property(&self) -> TransitionProperty {
+        match self {
+            TransitionValue::Width(_) => TransitionProperty::Width,
+            TransitionValue::Height(_) => TransitionProperty::Height,
+            TransitionValue::Left(_) => TransitionProperty::Left,
+            TransitionValue::Top(_) => TransitionProperty::Top,
+            TransitionValue::BackgroundColor(_) => TransitionProperty::BackgroundColor,
+            TransitionValue::BorderColor(_) => TransitionProperty::BorderColor,
+        }
+    }
+
+    /// Interpolates between `self` and `other`.
+    ///
+    /// [`Val`]s are only interpolated if they have the same unit, otherwise `other` is returned.
+    /// Values of different properties are never interpolated.
+    pub fn interpolate(&self, other: &Self, t: f32) -> Self {
+        use TransitionValue::*;
+        match (*self, *other) {
+            (Width(a), Width(b)) => Width(interpolate_val(a, b, t)),
+            (Height(a), Height(b)) => Height(interpolate_val(a, b, t)),
+            (Left(a), Left(b)) => Left(interpolate_val(a, b, t)),
+            (Top(a), Top(b)) => Top(interpolate_val(a, b, t)),
+            (BackgroundColor(a), BackgroundColor(b)) => BackgroundColor(a.mix(&b, t)),
+            (BorderColor(a), BorderColor(b)) => BorderColor(a.mix(&b, t)),
+            _ => *other,
+        }
+    }
+}
+
+fn interpolate_val(a: Val, b: Val, t: f32) -> Val {
+    match (a, b) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(a.lerp(b, t)),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(a.lerp(b, t)),
+        (Val::Vw(a), Val::Vw(b)) => Val::Vw(a.lerp(b, t)),
+        (Val::Vh(a), Val::Vh(b)) => Val::Vh(a.lerp(b, t)),
+        (Val::VMin(a), Val::VMin(b)) => Val::VMin(a.lerp(b, t)),
+        (Val::VMax(a), Val::VMax(b)) => Val::VMax(a.lerp(b, t)),
+        _ => b,
+    }
+}
+
+/// How a single [`TransitionProperty`] is animated.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub struct PropertyTransition {
+    /// The animated property.
+    pub property: TransitionProperty,
+    /// How long the property takes to reach a new value.
+    pub duration: Duration,
+    /// The easing curve of the animation.
+    pub ease: EaseFunction,
+}
+
+/// Animates changes to the style properties of a UI node.
+///
+/// When a listed property is changed, for example by setting [`Node::width`], the node animates from
+/// its currently displayed value to the new one instead of jumping to it.
+///
+/// Nodes can also animate in when they are spawned, from the values given with [`UiTransition::with_enter`],
+/// and animate out before being despawned, by inserting [`UiTransitionExit`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// # use bevy_ui::{TransitionProperty, TransitionValue, UiTransition};
+/// # use bevy_color::palettes::css::{BLACK, RED};
+/// # use bevy_math::curve::EaseFunction;
+/// # use core::time::Duration;
+/// fn spawn_menu(mut commands: Commands) {
+///     commands.spawn((
+///         Node {
+///             width: Val::Px(200.0),
+///             ..Default::default()
+///         },
+///         BackgroundColor(RED.into()),
+///         UiTransition::default()
+///             .with(TransitionProperty::Width, Duration::from_millis(250), EaseFunction::CubicOut)
+///             .with(TransitionProperty::BackgroundColor, Duration::from_millis(250), EaseFunction::Linear)
+///             .with_enter(TransitionValue::Width(Val::Px(0.0)))
+///             .with_exit(TransitionValue::BackgroundColor(BLACK.into())),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct UiTransition {
+    /// The animated properties.
+    pub transitions: Vec<PropertyTransition>,
+    /// Values the node starts from when it is spawned, animating to the values it was spawned with.
+    pub enter: Vec<TransitionValue>,
+    /// Values the node animates to when [`UiTransitionExit`] is inserted, before being despawned.
+    pub exit: Vec<TransitionValue>,
+    #[reflect(ignore)]
+    active: Vec<ActiveTransition>,
+}
+
+impl UiTransition {
+    /// Animates `property` over `duration` with the `ease` curve.
+    pub fn with(
+        mut self,
+        property: TransitionProperty,
+        duration: Duration,
+        ease: EaseFunction,
+    ) -> Self {
+        self.transitions.push(PropertyTransition {
+            property,
+            duration,
+            ease,
+        });
+        self
+    }
+
+    /// Starts the animated property of `value` at `value` when the node is spawned.
+    pub fn with_enter(mut self, value: TransitionValue) -> Self {
+        self.enter.push(value);
+        self
+    }
+
+    /// Animates the property of `value` to `value` when the node exits.
+    pub fn with_exit(mut self, value: TransitionValue) -> Self {
+        self.exit.push(value);
+        self
+    }
+
+    /// Returns `true` if any property is currently animating.
+    pub fn is_animating(&self) -> bool {
+        self.active.iter().any(|active| !active.is_finished())
+    }
+}
+
+/// Plays the exit transitions of a node with a [`UiTransition`], then despawns it along with its descendants.
+///
+/// Nodes without a [`UiTransition`], or without exit values, are despawned right away.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct UiTransitionExit;
+
+#[derive(Debug, Clone)]
+struct ActiveTransition {
+    from: TransitionValue,
+    to: TransitionValue,
+    /// The last value written to the node, used to detect changes made by other systems.
+    current: TransitionValue,
+    elapsed: Duration,
+    duration: Duration,
+    ease: EaseFunction,
+}
+
+impl ActiveTransition {
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    fn retarget(&mut self, to: TransitionValue) {
+        self.from = self.current;
+        self.to = to;
+        self.elapsed = Duration::ZERO;
+    }
+
+    fn advance(&mut self, delta: Duration) -> TransitionValue {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+        let t = EasingCurve::new(0.0, 1.0, self.ease).sample_clamped(progress);
+        self.current = self.from.interpolate(&self.to, t);
+        self.current
+    }
+}
+
+fn read_value(
+    property: TransitionProperty,
+    node: &Node,
+    background_color: Option<&BackgroundColor>,
+    border_color: Option<&BorderColor>,
+) -> Option<TransitionValue> {
+    Some(match property {
+        TransitionProperty::Width => TransitionValue::Width(node.width),
+        TransitionProperty::Height => TransitionValue::Height(node.height),
+        TransitionProperty::Left => TransitionValue::Left(node.left),
+        TransitionProperty::Top => TransitionValue::Top(node.top),
+        TransitionProperty::BackgroundColor => {
+            TransitionValue::BackgroundColor(background_color?.0)
+        }
+        TransitionProperty::BorderColor => TransitionValue::BorderColor(border_color?.0),
+    })
+}
+
+fn write_value(
+    value: TransitionValue,
+    node: &mut Mut<Node>,
+    background_color: Option<&mut Mut<BackgroundColor>>,
+    border_color: Option<&mut Mut<BorderColor>>,
+) {
+    match value {
+        TransitionValue::Width(val) => node.width = val,
+        TransitionValue::Height(val) => node.height = val,
+        TransitionValue::Left(val) => node.left = val,
+        TransitionValue::Top(val) => node.top = val,
+        TransitionValue::BackgroundColor(color) => {
+            if let Some(background_color) = background_color {
+                background_color.0 = color;
+            }
+        }
+        TransitionValue::BorderColor(color) => {
+            if let Some(border_color) = border_color {
+                border_color.0 = color;
+            }
+        }
+    }
+}
+
+/// Animates the properties of nodes with a [`UiTransition`], and despawns exiting nodes once
+/// their transitions finish.
+pub fn animate_ui_transitions(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut UiTransition,
+        &mut Node,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<Ref<UiTransitionExit>>,
+    )>,
+    exiting_without_transition: Query<Entity, (With<UiTransitionExit>, Without<UiTransition>)>,
+) {
+    for entity in &exiting_without_transition {
+        commands.entity(entity).despawn();
+    }
+
+    let delta = time.delta();
+    for (entity, mut transition, mut node, mut background_color, mut border_color, exit) in
+        &mut query
+    {
+        let entered = transition.is_added();
+        let exit_started = exit.as_ref().is_some_and(Ref::is_added);
+        let transition = &mut *transition;
+
+        for config in &transition.transitions {
+            let Some(value) = read_value(
+                config.property,
+                &node,
+                background_color.as_deref(),
+                border_color.as_deref(),
+            ) else {
+                continue;
+            };
+
+            let index = match transition
+                .active
+                .iter()
+                .position(|active| active.to.property() == config.property)
+            {
+                Some(index) => index,
+                None => {
+                    let from = entered
+                        .then(|| {
+                            transition
+                                .enter
+                                .iter()
+                                .find(|enter| enter.property() == config.property)
+                        })
+                        .flatten()
+                        .copied()
+                        .unwrap_or(value);
+                    transition.active.push(ActiveTransition {
+                        from,
+                        to: value,
+                        current: from,
+                        elapsed: if from == value {
+                            config.duration
+                        } else {
+                            Duration::ZERO
+                        },
+                        duration: config.duration,
+                        ease: config.ease,
+                    });
+                    transition.active.len() - 1
+                }
+            };
+
+            let active = &mut transition.active[index];
+            active.duration = config.duration;
+            active.ease = config.ease;
+            if value != active.current {
+                // Another system set a new value, animate towards it from the displayed value.
+                active.retarget(value);
+            }
+            if exit_started {
+                if let Some(exit) = transition
+                    .exit
+                    .iter()
+                    .find(|exit| exit.property() == config.property)
+                {
+                    active.retarget(*exit);
+                }
+            }
+
+            if !active.is_finished() || active.current != value {
+                let value = active.advance(delta);
+                write_value(
+                    value,
+                    &mut node,
+                    background_color.as_mut(),
+                    border_color.as_mut(),
+                );
+            }
+        }
+
+        if exit.is_some() && !transition.is_animating() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Update;
+
+    /// Creates a world running the transitions in the same system every update, so that added
+    /// components are only detected once.
+    fn new_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let mut schedule = Schedule::new(Update);
+        schedule.add_systems(animate_ui_transitions);
+        world.add_schedule(schedule);
+        world
+    }
+
+    fn run(world: &mut World, delta: Duration) {
+        world.resource_mut::<Time>().advance_by(delta);
+        world.run_schedule(Update);
+    }
+
+    #[test]
+    fn transition_animates_changes() {
+        let mut world = new_world();
+        let entity = world
+            .spawn((
+                Node {
+                    width: Val::Px(0.0),
+                    ..Default::default()
+                },
+                UiTransition::default().with(
+                    TransitionProperty::Width,
+                    Duration::from_secs(1),
+                    EaseFunction::Linear,
+                ),
+            ))
+            .id();
+
+        run(&mut world, Duration::ZERO);
+        assert_eq!(world.get::<Node>(entity).unwrap().width, Val::Px(0.0));
+
+        world.get_mut::<Node>(entity).unwrap().width = Val::Px(100.0);
+        run(&mut world, Duration::from_millis(500));
+        assert_eq!(world.get::<Node>(entity).unwrap().width, Val::Px(50.0));
+
+        run(&mut world, Duration::from_millis(500));
+        assert_eq!(world.get::<Node>(entity).unwrap().width, Val::Px(100.0));
+        assert!(!world.get::<UiTransition>(entity).unwrap().is_animating());
+    }
+
+    #[test]
+    fn transition_enter_and_exit() {
+        let mut world = new_world();
+        let entity = world
+            .spawn((
+                Node {
+                    height: Val::Px(40.0),
+                    ..Default::default()
+                },
+                UiTransition::default()
+                    .with(
+                        TransitionProperty::Height,
+                        Duration::from_secs(1),
+                        EaseFunction::Linear,
+                    )
+                    .with_enter(TransitionValue::Height(Val::Px(0.0)))
+                    .with_exit(TransitionValue::Height(Val::Px(0.0))),
+            ))
+            .id();
+
+        run(&mut world, Duration::ZERO);
+        assert_eq!(world.get::<Node>(entity).unwrap().height, Val::Px(0.0));
+        run(&mut world, Duration::from_secs(1));
+        assert_eq!(world.get::<Node>(entity).unwrap().height, Val::Px(40.0));
+
+        world.entity_mut(entity).insert(UiTransitionExit);
+        run(&mut world, Duration::from_millis(500));
+        assert_eq!(world.get::<Node>(entity).unwrap().height, Val::Px(20.0));
+        run(&mut world, Duration::from_millis(500));
+        assert!(world.get_entity(entity).is_err());
+    }
+}
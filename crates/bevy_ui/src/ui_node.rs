@@ -2,7 +2,7 @@ use crate::{FocusPolicy, UiRect, Val};
 use bevy_color::Color;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{prelude::*, system::SystemParam};
-use bevy_math::{vec4, Rect, Vec2, Vec4Swizzles};
+use bevy_math::{vec4, Affine2, Rect, Vec2, Vec4Swizzles};
 use bevy_reflect::prelude::*;
 use bevy_render::{
     camera::{Camera, RenderTarget},
@@ -2134,11 +2134,43 @@ impl Outline {
 }
 
 /// The calculated clip of the node
-#[derive(Component, Default, Copy, Clone, Debug, Reflect)]
-#[reflect(Component, Default, Debug)]
+///
+/// When the clipping ancestor is rotated, the clip is a rotated rect: `local_clip`, in the space
+/// given by `local_from_ui`. Otherwise `local_clip` is the same as `clip`.
+#[derive(Component, Default, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
 pub struct CalculatedClip {
-    /// The rect of the clip
+    /// The rect of the clip, or its bounding rect if the clip is rotated
     pub clip: Rect,
+    /// The rect of the clip in the local space of the clipping node
+    pub local_clip: Rect,
+    /// Transforms UI coordinates to the local space of `local_clip`
+    pub local_from_ui: Affine2,
+}
+
+impl CalculatedClip {
+    /// Creates an axis-aligned clip.
+    pub const fn new(clip: Rect) -> Self {
+        Self {
+            clip,
+            local_clip: clip,
+            local_from_ui: Affine2::IDENTITY,
+        }
+    }
+
+    /// Returns `true` if the clip is an axis-aligned rect, which is always the case unless the
+    /// clipping node is rotated.
+    pub fn is_axis_aligned(&self) -> bool {
+        self.local_from_ui == Affine2::IDENTITY
+    }
+
+    /// Returns `true` if `point`, in UI coordinates, is inside the clip.
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.clip.contains(point)
+            && self
+                .local_clip
+                .contains(self.local_from_ui.transform_point2(point))
+    }
 }
 
 /// Indicates that this [`Node`] entity's front-to-back ordering is not controlled solely
@@ -2737,6 +2769,81 @@ pub enum UiAntiAlias {
     Off,
 }
 
+/// Marker for controlling whether the Ui of a camera is snapped to its physical pixels.
+/// By default, Ui isn't snapped.
+///
+/// When snapping, the nodes and text glyphs are moved to start on a whole physical pixel, so that
+/// their edges aren't blurred across two pixels on fractional scale factors or transforms. This
+/// applies to the backgrounds, borders, images and text of nodes.
+///
+/// ```
+/// use bevy_core_pipeline::prelude::*;
+/// use bevy_ecs::prelude::*;
+/// use bevy_ui::prelude::*;
+///
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         Camera2d,
+///         // This will cause all Ui in this camera to be snapped to pixels
+///         UiPixelSnap::On,
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Default, Debug, Reflect, Eq, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub enum UiPixelSnap {
+    /// UI will render at its exact position
+    #[default]
+    Off,
+    /// UI will render snapped to whole physical pixels
+    On,
+}
+
+/// Renders the Ui of a camera at a lower resolution, upscaled by an integer factor.
+///
+/// With a scale of `n`, each Ui pixel covers `n` by `n` physical pixels. The Ui is laid out and
+/// rendered to an offscreen texture `n` times smaller than the viewport of the camera, which is
+/// upscaled without filtering and drawn over the output of the camera. This gives crisp
+/// pixel-art interfaces, combined with [`UiAntiAlias::Off`] and fonts without smoothing.
+///
+/// The Ui keeps its apparent size: a [`Val::Px`] still spans the same number of physical pixels.
+/// Set the [`UiScale`](crate::UiScale) to `n` divided by the scale factor of the window to make
+/// each logical pixel match one Ui pixel. When the viewport isn't a multiple of `n`, its last
+/// few rows and columns aren't covered by the Ui.
+///
+/// A scale of `0` or `1` renders the Ui normally.
+///
+/// ```
+/// use bevy_core_pipeline::prelude::*;
+/// use bevy_ecs::prelude::*;
+/// use bevy_ui::prelude::*;
+///
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         Camera2d,
+///         // Each Ui pixel covers 3 by 3 physical pixels
+///         UiIntegerScale(3),
+///         UiAntiAlias::Off,
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Reflect, Eq, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct UiIntegerScale(pub u32);
+
+impl Default for UiIntegerScale {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl UiIntegerScale {
+    /// Returns the scale of the Ui of a camera with the given `UiIntegerScale`, at least `1`.
+    pub fn get(integer_scale: Option<&Self>) -> u32 {
+        integer_scale.map_or(1, |integer_scale| integer_scale.0.max(1))
+    }
+}
+
 /// Number of shadow samples.
 /// A larger value will result in higher quality shadows.
 /// Default is 4, values higher than ~10 offer diminishing returns.
@@ -11,7 +11,7 @@ use bevy_ecs::{
     query::{Changed, With},
     system::{Commands, Query},
 };
-use bevy_math::Rect;
+use bevy_math::{Affine2, Mat2, Rect, Vec2, Vec3Swizzles};
 use bevy_platform_support::collections::HashSet;
 use bevy_sprite::BorderRect;
 use bevy_transform::components::GlobalTransform;
@@ -49,7 +49,7 @@ fn update_clipping(
         Option<&mut CalculatedClip>,
     )>,
     entity: Entity,
-    mut maybe_inherited_clip: Option<Rect>,
+    mut maybe_inherited_clip: Option<CalculatedClip>,
 ) {
     let Ok((node, computed_node, global_transform, maybe_calculated_clip)) =
         node_query.get_mut(entity)
@@ -59,17 +59,15 @@ fn update_clipping(
 
     // If `display` is None, clip the entire node and all its descendants by replacing the inherited clip with a default rect (which is empty)
     if node.display == Display::None {
-        maybe_inherited_clip = Some(Rect::default());
+        maybe_inherited_clip = Some(CalculatedClip::default());
     }
 
     // Update this node's CalculatedClip component
     if let Some(mut calculated_clip) = maybe_calculated_clip {
         if let Some(inherited_clip) = maybe_inherited_clip {
             // Replace the previous calculated clip with the inherited clipping rect
-            if calculated_clip.clip != inherited_clip {
-                *calculated_clip = CalculatedClip {
-                    clip: inherited_clip,
-                };
+            if *calculated_clip != inherited_clip {
+                *calculated_clip = inherited_clip;
             }
         } else {
             // No inherited clipping rect, remove the component
@@ -77,9 +75,7 @@ fn update_clipping(
         }
     } else if let Some(inherited_clip) = maybe_inherited_clip {
         // No previous calculated clip, add a new CalculatedClip component with the inherited clipping rect
-        commands.entity(entity).try_insert(CalculatedClip {
-            clip: inherited_clip,
-        });
+        commands.entity(entity).try_insert(inherited_clip);
     }
 
     // Calculate new clip rectangle for children nodes
@@ -95,10 +91,9 @@ fn update_clipping(
         // of nested `Overflow::Hidden` nodes. If parent `clip` is not
         // defined, use the current node's clip.
 
-        let mut clip_rect = Rect::from_center_size(
-            global_transform.translation().truncate(),
-            computed_node.size(),
-        );
+        // The clip rect is computed in the local space of the node, centered on its origin, so that
+        // it follows the rotation and scale of the node.
+        let mut clip_rect = Rect::from_center_size(Vec2::ZERO, computed_node.size());
 
         // Content isn't clipped at the edges of the node but at the edges of the region specified by [`Node::overflow_clip_margin`].
         //
@@ -126,7 +121,12 @@ fn update_clipping(
             clip_rect.min.y = -f32::INFINITY;
             clip_rect.max.y = f32::INFINITY;
         }
-        Some(maybe_inherited_clip.map_or(clip_rect, |c| c.intersect(clip_rect)))
+        let affine = global_transform.affine();
+        let ui_from_node = Affine2::from_mat2_translation(
+            Mat2::from_cols(affine.matrix3.x_axis.xy(), affine.matrix3.y_axis.xy()),
+            affine.translation.xy(),
+        );
+        Some(node_clip(ui_from_node, clip_rect, maybe_inherited_clip))
     };
 
     for child in ui_children.iter_ui_children(entity) {
@@ -134,6 +134,87 @@ fn update_clipping(
     }
 }
 
+/// Tolerance used to consider the linear part of a transform axis-aligned.
+const AXIS_ALIGNED_EPSILON: f32 = 1e-5;
+
+/// Returns the clip of the children of a node clipping its content to `node_rect`, in the local
+/// space of the node, intersected with the clip inherited from its ancestors.
+///
+/// When the node and the ancestor clipping its content are rotated differently, their clips can't
+/// be intersected exactly, so the inherited clip is approximated by its bounding rect.
+fn node_clip(
+    ui_from_node: Affine2,
+    node_rect: Rect,
+    inherited_clip: Option<CalculatedClip>,
+) -> CalculatedClip {
+    // A node scaled to zero doesn't show any of its content.
+    if ui_from_node.matrix2.determinant() == 0. {
+        return CalculatedClip::default();
+    }
+
+    let clip = transform_rect(ui_from_node, node_rect);
+    let mut node_clip = if is_axis_aligned(ui_from_node.matrix2) {
+        CalculatedClip::new(clip)
+    } else {
+        CalculatedClip {
+            clip,
+            local_clip: node_rect,
+            local_from_ui: ui_from_node.inverse(),
+        }
+    };
+
+    let Some(inherited_clip) = inherited_clip else {
+        return node_clip;
+    };
+
+    node_clip.clip = node_clip.clip.intersect(inherited_clip.clip);
+    let local_from_inherited = node_clip.local_from_ui * inherited_clip.local_from_ui.inverse();
+    let inherited_rect = if is_axis_aligned(local_from_inherited.matrix2) {
+        transform_rect(local_from_inherited, inherited_clip.local_clip)
+    } else {
+        transform_rect(node_clip.local_from_ui, inherited_clip.clip)
+    };
+    node_clip.local_clip = node_clip.local_clip.intersect(inherited_rect);
+    node_clip
+}
+
+/// Returns `true` if `matrix` maps axis-aligned rects to axis-aligned rects.
+fn is_axis_aligned(matrix: Mat2) -> bool {
+    let aligned = matrix.x_axis.y.abs() < AXIS_ALIGNED_EPSILON
+        && matrix.y_axis.x.abs() < AXIS_ALIGNED_EPSILON;
+    let swapped = matrix.x_axis.x.abs() < AXIS_ALIGNED_EPSILON
+        && matrix.y_axis.y.abs() < AXIS_ALIGNED_EPSILON;
+    aligned || swapped
+}
+
+/// Returns the bounding rect of `rect` transformed by `affine`.
+///
+/// Unlike transforming the corners of `rect`, this supports rects which are infinite along an axis.
+fn transform_rect(affine: Affine2, rect: Rect) -> Rect {
+    // Interval arithmetic, where the terms of the linear part close to zero are ignored so that
+    // infinite bounds don't leak into the other axis.
+    let scale = |factor: f32, min: f32, max: f32| {
+        if factor.abs() < AXIS_ALIGNED_EPSILON {
+            Vec2::ZERO
+        } else if factor > 0. {
+            Vec2::new(factor * min, factor * max)
+        } else {
+            Vec2::new(factor * max, factor * min)
+        }
+    };
+    let matrix = affine.matrix2;
+    let x = scale(matrix.x_axis.x, rect.min.x, rect.max.x)
+        + scale(matrix.y_axis.x, rect.min.y, rect.max.y)
+        + affine.translation.x;
+    let y = scale(matrix.x_axis.y, rect.min.x, rect.max.x)
+        + scale(matrix.y_axis.y, rect.min.y, rect.max.y)
+        + affine.translation.y;
+    Rect {
+        min: Vec2::new(x.x, y.x),
+        max: Vec2::new(x.y, y.y),
+    }
+}
+
 pub fn update_target_camera_system(
     mut commands: Commands,
     changed_root_nodes_query: Query<
@@ -216,3 +297,81 @@ fn update_children_target_camera(
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{node_clip, transform_rect};
+    use crate::CalculatedClip;
+    use bevy_math::{Affine2, Rect, Vec2};
+    use core::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn scaled_node_clip() {
+        let ui_from_node =
+            Affine2::from_scale_angle_translation(Vec2::splat(2.), 0., Vec2::new(100., 50.));
+        let clip = node_clip(
+            ui_from_node,
+            Rect::from_center_size(Vec2::ZERO, Vec2::new(10., 20.)),
+            None,
+        );
+        assert_eq!(
+            clip,
+            CalculatedClip::new(Rect::from_center_size(
+                Vec2::new(100., 50.),
+                Vec2::new(20., 40.)
+            ))
+        );
+    }
+
+    #[test]
+    fn rotated_node_clip() {
+        let ui_from_node = Affine2::from_angle_translation(FRAC_PI_4, Vec2::new(100., 100.));
+        let clip = node_clip(
+            ui_from_node,
+            Rect::from_center_size(Vec2::ZERO, Vec2::splat(20.)),
+            None,
+        );
+        assert!(!clip.is_axis_aligned());
+        assert!(clip.contains(Vec2::new(100., 100.)));
+        assert!(clip.contains(Vec2::new(100., 113.)));
+        // Inside the bounding rect of the clip, but outside the clip itself
+        assert!(clip.clip.contains(Vec2::new(112., 112.)));
+        assert!(!clip.contains(Vec2::new(112., 112.)));
+    }
+
+    #[test]
+    fn nested_rotated_node_clip() {
+        let ui_from_parent = Affine2::from_angle(FRAC_PI_4);
+        let parent_clip = node_clip(
+            ui_from_parent,
+            Rect::from_center_size(Vec2::ZERO, Vec2::splat(100.)),
+            None,
+        );
+
+        // The child is rotated with its parent, and offset along the parent's x axis
+        let ui_from_child = ui_from_parent * Affine2::from_translation(Vec2::new(40., 0.));
+        let child_clip = node_clip(
+            ui_from_child,
+            Rect::from_center_size(Vec2::ZERO, Vec2::splat(40.)),
+            Some(parent_clip),
+        );
+        let expected = Rect::new(-20., -20., 10., 20.);
+        assert!(child_clip.local_clip.min.abs_diff_eq(expected.min, 1e-3));
+        assert!(child_clip.local_clip.max.abs_diff_eq(expected.max, 1e-3));
+    }
+
+    #[test]
+    fn transform_infinite_rect() {
+        let rect = Rect {
+            min: Vec2::new(-10., -f32::INFINITY),
+            max: Vec2::new(10., f32::INFINITY),
+        };
+        let scaled = transform_rect(Affine2::from_scale(Vec2::new(2., 3.)), rect);
+        assert_eq!(scaled.min, Vec2::new(-20., -f32::INFINITY));
+        assert_eq!(scaled.max, Vec2::new(20., f32::INFINITY));
+
+        let rotated = transform_rect(Affine2::from_angle(FRAC_PI_4), rect);
+        assert!(!rotated.min.is_nan() && !rotated.max.is_nan());
+        assert!(rotated.contains(Vec2::new(1000., -1000.)));
+    }
+}
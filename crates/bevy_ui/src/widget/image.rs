@@ -1,4 +1,7 @@
-use crate::{ContentSize, Measure, MeasureArgs, Node, NodeMeasure, UiScale};
+use crate::{
+    ContentSize, DefaultUiCamera, Measure, MeasureArgs, Node, NodeMeasure, UiIntegerScale, UiScale,
+    UiScaleOverrides,
+};
 use bevy_asset::{Assets, Handle};
 use bevy_color::Color;
 use bevy_ecs::prelude::*;
@@ -255,18 +258,25 @@ type UpdateImageFilter = (With<Node>, Without<crate::prelude::Text>);
 /// Updates content size of the node based on the image provided
 pub fn update_image_content_size_system(
     mut previous_combined_scale_factor: Local<f32>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    windows: Query<(Entity, &Window), With<PrimaryWindow>>,
     ui_scale: Res<UiScale>,
+    ui_scale_overrides: Res<UiScaleOverrides>,
+    default_ui_camera: DefaultUiCamera,
+    integer_scales: Query<&UiIntegerScale>,
     textures: Res<Assets<Image>>,
 
     atlases: Res<Assets<TextureAtlasLayout>>,
     mut query: Query<(&mut ContentSize, Ref<ImageNode>, &mut ImageNodeSize), UpdateImageFilter>,
 ) {
-    let combined_scale_factor = windows
-        .get_single()
-        .map(|window| window.resolution.scale_factor())
-        .unwrap_or(1.)
-        * ui_scale.0;
+    let integer_scale = default_ui_camera
+        .get()
+        .and_then(|camera| integer_scales.get(camera).ok());
+    let combined_scale_factor = match windows.get_single() {
+        Ok((entity, window)) => {
+            window.resolution.scale_factor() * ui_scale_overrides.get_or(Some(entity), &ui_scale)
+        }
+        Err(_) => ui_scale.0,
+    } / UiIntegerScale::get(integer_scale) as f32;
 
     for (mut content_size, image, mut image_size) in &mut query {
         if !matches!(image.image_mode, NodeImageMode::Auto)
@@ -1,6 +1,6 @@
 use crate::{
     ComputedNode, ContentSize, DefaultUiCamera, FixedMeasure, Measure, MeasureArgs, Node,
-    NodeMeasure, UiScale, UiTargetCamera,
+    NodeMeasure, UiIntegerScale, UiScale, UiScaleOverrides, UiTargetCamera,
 };
 use bevy_asset::Assets;
 use bevy_color::Color;
@@ -24,6 +24,7 @@ use bevy_text::{
     TextBounds, TextColor, TextError, TextFont, TextLayout, TextLayoutInfo, TextMeasureInfo,
     TextPipeline, TextReader, TextRoot, TextSpanAccess, TextWriter, YAxisOrientation,
 };
+use bevy_window::PrimaryWindow;
 use taffy::style::AvailableSpace;
 use tracing::error;
 
@@ -242,7 +243,8 @@ fn create_text_measure<'a>(
 /// A `Measure` is used by the UI's layout algorithm to determine the appropriate amount of space
 /// to provide for the text given the fonts, the text itself and the constraints of the layout.
 ///
-/// * Measures are regenerated if the target camera's scale factor (or primary window if no specific target) or [`UiScale`] is changed.
+/// * Measures are regenerated if the target camera's scale factor (or primary window if no specific target), [`UiScale`],
+///     [`UiScaleOverrides`] or [`UiIntegerScale`] is changed.
 /// * Changes that only modify the colors of a `Text` do not require a new `Measure`. This system
 ///     is only able to detect that a `Text` component has changed and will regenerate the `Measure` on
 ///     color changes. This can be expensive, particularly for large blocks of text, and the [`bypass_change_detection`](bevy_ecs::change_detection::DetectChangesMut::bypass_change_detection)
@@ -251,9 +253,11 @@ pub fn measure_text_system(
     mut scale_factors_buffer: Local<EntityHashMap<f32>>,
     mut last_scale_factors: Local<EntityHashMap<f32>>,
     fonts: Res<Assets<Font>>,
-    camera_query: Query<&Camera>,
+    camera_query: Query<(&Camera, Option<&UiIntegerScale>)>,
     default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
+    ui_scale_overrides: Res<UiScaleOverrides>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
     mut text_query: Query<
         (
             Entity,
@@ -272,6 +276,7 @@ pub fn measure_text_system(
     scale_factors_buffer.clear();
 
     let default_camera_entity = default_ui_camera.get();
+    let primary_window = primary_window.get_single().ok();
 
     for (entity, block, content_size, text_flags, computed, maybe_camera) in &mut text_query {
         let Some(camera_entity) = maybe_camera
@@ -283,14 +288,20 @@ pub fn measure_text_system(
 
         let scale_factor = match scale_factors_buffer.entry(camera_entity) {
             Entry::Occupied(entry) => *entry.get(),
-            Entry::Vacant(entry) => *entry.insert(
-                camera_query
-                    .get(camera_entity)
-                    .ok()
-                    .and_then(Camera::target_scaling_factor)
-                    .unwrap_or(1.0)
-                    * ui_scale.0,
-            ),
+            Entry::Vacant(entry) => {
+                let camera = camera_query.get(camera_entity).ok();
+                let integer_scale =
+                    camera.map_or(1, |(_, integer_scale)| UiIntegerScale::get(integer_scale));
+                *entry.insert(
+                    camera
+                        .and_then(|(camera, _)| camera.target_scaling_factor())
+                        .unwrap_or(1.0)
+                        * camera.map_or(ui_scale.0, |(camera, _)| {
+                            ui_scale_overrides.for_camera(camera, primary_window, &ui_scale)
+                        })
+                        / integer_scale as f32,
+                )
+            }
         };
 
         // Note: the ComputedTextBlock::needs_rerender bool is cleared in create_text_measure().
@@ -0,0 +1,325 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+use bevy_ecs::prelude::*;
+use bevy_utils::synccell::SyncCell;
+use thiserror::Error;
+
+/// The kind of content stored in the [`Clipboard`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClipboardContentKind {
+    /// Plain text.
+    Text,
+    /// An image, see [`ClipboardImage`].
+    Image,
+}
+
+/// Content that can be stored in the [`Clipboard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardContent {
+    /// Plain text.
+    Text(String),
+    /// An image.
+    Image(ClipboardImage),
+}
+
+impl ClipboardContent {
+    /// Returns the kind of this content.
+    pub fn kind(&self) -> ClipboardContentKind {
+        match self {
+            ClipboardContent::Text(_) => ClipboardContentKind::Text,
+            ClipboardContent::Image(_) => ClipboardContentKind::Image,
+        }
+    }
+}
+
+/// An image stored in the [`Clipboard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+    /// The pixels of the image, row by row from the top, in RGBA order with 8 bits per channel.
+    pub data: Vec<u8>,
+}
+
+/// An error returned when accessing the [`Clipboard`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// The clipboard is empty, or doesn't contain content of the requested kind.
+    #[error("the clipboard doesn't contain content of the requested kind")]
+    ContentNotAvailable,
+    /// The [`ClipboardBackend`] doesn't support content of this kind.
+    #[error("the clipboard backend doesn't support {0:?} content")]
+    Unsupported(ClipboardContentKind),
+    /// The [`ClipboardBackend`] can only read the clipboard asynchronously, for example on the
+    /// web. Use [`Clipboard::request_paste`] instead.
+    #[error("the clipboard can only be read asynchronously, use `Clipboard::request_paste`")]
+    AsyncOnly,
+    /// The platform returned an error.
+    #[error("the clipboard couldn't be accessed: {0}")]
+    Platform(String),
+}
+
+/// Identifies a paste requested with [`Clipboard::request_paste`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClipboardRequestId(u64);
+
+impl fmt::Display for ClipboardRequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An event sent when the content requested with [`Clipboard::request_paste`] was read.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardPasted {
+    /// The request returned by [`Clipboard::request_paste`].
+    pub request: ClipboardRequestId,
+    /// The content that was read from the clipboard.
+    pub result: Result<ClipboardContent, ClipboardError>,
+}
+
+/// The platform-specific implementation of the [`Clipboard`].
+///
+/// Windowing backends such as `bevy_winit` install a backend for the current platform with
+/// [`Clipboard::set_backend`]. Without one, the [`Clipboard`] uses a [`MemoryClipboard`],
+/// which is only shared within the app.
+pub trait ClipboardBackend: Send + 'static {
+    /// Reads the text stored in the clipboard.
+    fn get_text(&mut self) -> Result<String, ClipboardError>;
+
+    /// Replaces the content of the clipboard with `text`.
+    fn set_text(&mut self, text: String) -> Result<(), ClipboardError>;
+
+    /// Reads the image stored in the clipboard.
+    fn get_image(&mut self) -> Result<ClipboardImage, ClipboardError> {
+        Err(ClipboardError::Unsupported(ClipboardContentKind::Image))
+    }
+
+    /// Replaces the content of the clipboard with `image`.
+    fn set_image(&mut self, _image: ClipboardImage) -> Result<(), ClipboardError> {
+        Err(ClipboardError::Unsupported(ClipboardContentKind::Image))
+    }
+
+    /// Starts reading content of the given `kind` for the paste `request`.
+    ///
+    /// Returns the content if it could be read right away, or `None` if it will be returned
+    /// later by [`ClipboardBackend::poll_pastes`]. By default, the content is read right away.
+    fn start_paste(
+        &mut self,
+        request: ClipboardRequestId,
+        kind: ClipboardContentKind,
+    ) -> Option<Result<ClipboardContent, ClipboardError>> {
+        let _ = request;
+        Some(match kind {
+            ClipboardContentKind::Text => self.get_text().map(ClipboardContent::Text),
+            ClipboardContentKind::Image => self.get_image().map(ClipboardContent::Image),
+        })
+    }
+
+    /// Adds the pastes started by [`ClipboardBackend::start_paste`] that completed since the
+    /// last call. This is called once per frame by the [`clipboard_paste_system`].
+    fn poll_pastes(&mut self, pasted: &mut Vec<ClipboardPasted>) {
+        let _ = pasted;
+    }
+}
+
+/// A [`ClipboardBackend`] storing the content in memory, without access to the clipboard of the
+/// platform.
+///
+/// This is the default backend of the [`Clipboard`], used when no windowing backend provides one.
+#[derive(Debug, Default)]
+pub struct MemoryClipboard {
+    content: Option<ClipboardContent>,
+}
+
+impl ClipboardBackend for MemoryClipboard {
+    fn get_text(&mut self) -> Result<String, ClipboardError> {
+        match &self.content {
+            Some(ClipboardContent::Text(text)) => Ok(text.clone()),
+            _ => Err(ClipboardError::ContentNotAvailable),
+        }
+    }
+
+    fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+        self.content = Some(ClipboardContent::Text(text));
+        Ok(())
+    }
+
+    fn get_image(&mut self) -> Result<ClipboardImage, ClipboardError> {
+        match &self.content {
+            Some(ClipboardContent::Image(image)) => Ok(image.clone()),
+            _ => Err(ClipboardError::ContentNotAvailable),
+        }
+    }
+
+    fn set_image(&mut self, image: ClipboardImage) -> Result<(), ClipboardError> {
+        self.content = Some(ClipboardContent::Image(image));
+        Ok(())
+    }
+}
+
+/// Access to the clipboard of the platform, to implement copy and paste.
+///
+/// Text can be read and written on all platforms with a [`ClipboardBackend`], and images where
+/// the platform supports them. Some platforms, like the web, can only read the clipboard
+/// asynchronously: use [`Clipboard::request_paste`], which works on all platforms, and read the
+/// content from the [`ClipboardPasted`] events.
+///
+/// `bevy_winit` only provides the backend of the platform with its `clipboard` feature, which
+/// isn't enabled by default. Otherwise, the [`MemoryClipboard`] is used, and the content is only
+/// shared within the app.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::{Clipboard, ClipboardContent, ClipboardContentKind, ClipboardPasted};
+/// fn copy(mut clipboard: ResMut<Clipboard>) {
+///     if let Err(error) = clipboard.set_text("Hello") {
+///         log::warn!("Couldn't copy: {error}");
+///     }
+/// }
+///
+/// fn paste(mut clipboard: ResMut<Clipboard>) {
+///     clipboard.request_paste(ClipboardContentKind::Text);
+/// }
+///
+/// fn read_pasted(mut pasted: EventReader<ClipboardPasted>) {
+///     for pasted in pasted.read() {
+///         if let Ok(ClipboardContent::Text(text)) = &pasted.result {
+///             log::info!("Pasted {text}");
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(copy);
+/// # bevy_ecs::system::assert_is_system(paste);
+/// # bevy_ecs::system::assert_is_system(read_pasted);
+/// ```
+#[derive(Resource)]
+pub struct Clipboard {
+    backend: SyncCell<Box<dyn ClipboardBackend>>,
+    next_request: u64,
+    pasted: Vec<ClipboardPasted>,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::with_backend(MemoryClipboard::default())
+    }
+}
+
+impl Clipboard {
+    /// Creates a [`Clipboard`] using the given `backend`.
+    pub fn with_backend(backend: impl ClipboardBackend) -> Self {
+        Self {
+            backend: SyncCell::new(Box::new(backend)),
+            next_request: 0,
+            pasted: Vec::new(),
+        }
+    }
+
+    /// Replaces the [`ClipboardBackend`] of this clipboard.
+    ///
+    /// The pastes that the previous backend didn't complete yet are dropped.
+    pub fn set_backend(&mut self, backend: impl ClipboardBackend) {
+        *self.backend.get() = Box::new(backend);
+    }
+
+    /// Reads the text stored in the clipboard.
+    ///
+    /// Returns [`ClipboardError::AsyncOnly`] on platforms where the clipboard can only be read
+    /// with [`Clipboard::request_paste`].
+    pub fn get_text(&mut self) -> Result<String, ClipboardError> {
+        self.backend.get().get_text()
+    }
+
+    /// Replaces the content of the clipboard with `text`.
+    pub fn set_text(&mut self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        self.backend.get().set_text(text.into())
+    }
+
+    /// Reads the image stored in the clipboard.
+    ///
+    /// Returns [`ClipboardError::AsyncOnly`] on platforms where the clipboard can only be read
+    /// with [`Clipboard::request_paste`].
+    pub fn get_image(&mut self) -> Result<ClipboardImage, ClipboardError> {
+        self.backend.get().get_image()
+    }
+
+    /// Replaces the content of the clipboard with `image`.
+    pub fn set_image(&mut self, image: ClipboardImage) -> Result<(), ClipboardError> {
+        self.backend.get().set_image(image)
+    }
+
+    /// Requests to read content of the given `kind` from the clipboard.
+    ///
+    /// The content is sent in a [`ClipboardPasted`] event with the returned id, by the
+    /// [`clipboard_paste_system`] in [`PreUpdate`](bevy_app::PreUpdate) of the next frame,
+    /// or later on platforms where the clipboard is read asynchronously.
+    pub fn request_paste(&mut self, kind: ClipboardContentKind) -> ClipboardRequestId {
+        let request = ClipboardRequestId(self.next_request);
+        self.next_request += 1;
+        if let Some(result) = self.backend.get().start_paste(request, kind) {
+            self.pasted.push(ClipboardPasted { request, result });
+        }
+        request
+    }
+}
+
+/// Sends the [`ClipboardPasted`] events of the pastes requested with [`Clipboard::request_paste`]
+/// that completed.
+///
+/// This system is added by the [`WindowPlugin`](crate::WindowPlugin) in [`PreUpdate`](bevy_app::PreUpdate).
+pub fn clipboard_paste_system(
+    mut clipboard: ResMut<Clipboard>,
+    mut pasted_events: EventWriter<ClipboardPasted>,
+) {
+    let clipboard = clipboard.bypass_change_detection();
+    clipboard.backend.get().poll_pastes(&mut clipboard.pasted);
+    if !clipboard.pasted.is_empty() {
+        pasted_events.send_batch(clipboard.pasted.drain(..));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{event::Events, system::RunSystemOnce};
+
+    #[test]
+    fn memory_clipboard_paste() {
+        let mut world = World::new();
+        world.init_resource::<Events<ClipboardPasted>>();
+        let mut clipboard = Clipboard::default();
+
+        assert_eq!(
+            clipboard.get_text(),
+            Err(ClipboardError::ContentNotAvailable)
+        );
+        clipboard.set_text("copied").unwrap();
+        assert_eq!(clipboard.get_text().as_deref(), Ok("copied"));
+
+        let text_request = clipboard.request_paste(ClipboardContentKind::Text);
+        let image_request = clipboard.request_paste(ClipboardContentKind::Image);
+        world.insert_resource(clipboard);
+        world.run_system_once(clipboard_paste_system).unwrap();
+
+        let events = world.resource::<Events<ClipboardPasted>>();
+        let mut cursor = events.get_cursor();
+        let pasted: Vec<_> = cursor.read(events).cloned().collect();
+        assert_eq!(
+            pasted,
+            [
+                ClipboardPasted {
+                    request: text_request,
+                    result: Ok(ClipboardContent::Text("copied".into())),
+                },
+                ClipboardPasted {
+                    request: image_request,
+                    result: Err(ClipboardError::ContentNotAvailable),
+                },
+            ]
+        );
+    }
+}
@@ -21,6 +21,7 @@ use alloc::sync::Arc;
 
 use bevy_platform_support::sync::Mutex;
 
+mod clipboard;
 mod event;
 mod monitor;
 mod raw_handle;
@@ -33,6 +34,7 @@ pub use crate::raw_handle::*;
 #[cfg(target_os = "android")]
 pub use android_activity;
 
+pub use clipboard::*;
 pub use event::*;
 pub use monitor::*;
 pub use system::*;
@@ -118,7 +120,11 @@ impl Plugin for WindowPlugin {
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
             .add_event::<WindowThemeChanged>()
-            .add_event::<AppLifecycle>();
+            .add_event::<AppLifecycle>()
+            .add_event::<ClipboardPasted>();
+
+        app.init_resource::<Clipboard>()
+            .add_systems(PreUpdate, clipboard_paste_system);
 
         if let Some(primary_window) = &self.primary_window {
             app.world_mut().spawn(primary_window.clone()).insert((
@@ -167,7 +173,9 @@ impl Plugin for WindowPlugin {
         // Register window descriptor and related types
         #[cfg(feature = "bevy_reflect")]
         app.register_type::<Window>()
-            .register_type::<PrimaryWindow>();
+            .register_type::<PrimaryWindow>()
+            .register_type::<HdrOutput>()
+            .register_type::<HdrOutputMode>();
     }
 }
 
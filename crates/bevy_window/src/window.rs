@@ -177,6 +177,10 @@ pub struct Window {
     pub name: Option<String>,
     /// How the alpha channel of textures should be handled while compositing.
     pub composite_alpha_mode: CompositeAlphaMode,
+    /// Whether and how the window outputs HDR colors, on displays that support it.
+    ///
+    /// Changing this field during runtime reconfigures the surface of the window.
+    pub hdr_output: HdrOutput,
     /// The limits of the window's logical size
     /// (found in its [`resolution`](WindowResolution)) when resizing.
     pub resize_constraints: WindowResizeConstraints,
@@ -445,6 +449,7 @@ impl Default for Window {
             resolution: Default::default(),
             internal: Default::default(),
             composite_alpha_mode: Default::default(),
+            hdr_output: Default::default(),
             resize_constraints: Default::default(),
             ime_enabled: Default::default(),
             ime_position: Default::default(),
@@ -566,6 +571,31 @@ impl Window {
         self.resolution.scale_factor()
     }
 
+    /// Converts a position in logical pixels of this window to the logical pixels of `other`,
+    /// so that both refer to the same point on the screen.
+    ///
+    /// This accounts for the position and the scale factor of both windows, which can differ
+    /// when they are on monitors with different DPI.
+    ///
+    /// Returns `None` if the position of either window is not known, see [`WindowPosition::At`].
+    /// Window decorations are not accounted for.
+    pub fn logical_position_to(&self, position: Vec2, other: &Window) -> Option<Vec2> {
+        let (WindowPosition::At(origin), WindowPosition::At(other_origin)) =
+            (self.position, other.position)
+        else {
+            return None;
+        };
+        let physical = origin.as_vec2() + position * self.scale_factor();
+        Some((physical - other_origin.as_vec2()) / other.scale_factor())
+    }
+
+    /// Converts a size in logical pixels of this window to the logical pixels of `other`,
+    /// so that both cover the same number of physical pixels.
+    #[inline]
+    pub fn logical_size_to(&self, size: Vec2, other: &Window) -> Vec2 {
+        size * self.scale_factor() / other.scale_factor()
+    }
+
     /// The cursor position in this window in logical pixels.
     ///
     /// Returns `None` if the cursor is outside the window area.
@@ -1262,6 +1292,78 @@ pub enum CompositeAlphaMode {
     Inherit = 4,
 }
 
+/// Configures the HDR output of a [`Window`].
+///
+/// When HDR output is enabled and supported by the surface of the window, cameras rendering to
+/// the window with HDR enabled are tonemapped to the range of the display instead of the SDR
+/// range, and the final image is encoded for the display. Whether HDR output is active can be
+/// checked in the render world, on the `ExtractedWindow` of the window.
+///
+/// Only scRGB output on Vulkan is currently supported. `wgpu` doesn't expose the color spaces of
+/// surfaces, so HDR10 (PQ) output can't be configured, and other backends fall back to SDR.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct HdrOutput {
+    /// The HDR encoding the window uses, if supported.
+    pub mode: HdrOutputMode,
+    /// The brightness of SDR white, in nits.
+    ///
+    /// Colors with a value of `1.0` after tonemapping, as well as all the SDR content like UI, are
+    /// displayed at this brightness. Defaults to 203 nits, the reference white of ITU-R BT.2408.
+    pub paper_white_nits: f32,
+    /// The maximum brightness of the display, in nits.
+    ///
+    /// HDR cameras are tonemapped up to this brightness. Defaults to 1000 nits.
+    pub peak_nits: f32,
+}
+
+impl Default for HdrOutput {
+    fn default() -> Self {
+        Self {
+            mode: HdrOutputMode::default(),
+            paper_white_nits: 203.0,
+            peak_nits: 1000.0,
+        }
+    }
+}
+
+/// The HDR encoding a [`Window`] uses, see [`HdrOutput`].
+///
+/// The window falls back to SDR output when its surface or display doesn't support the encoding.
+///
+/// ## Platform-specific
+///
+/// `wgpu` picks the color space of the surface from its texture format, without exposing it. HDR
+/// output is only enabled where the supported surface formats reflect the color spaces the display
+/// can show, which is currently only with the Vulkan backend.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum HdrOutputMode {
+    /// The window always outputs SDR colors.
+    #[default]
+    Disabled,
+    /// Uses scRGB, a linear encoding with the sRGB primaries where `1.0` is 80 nits, if supported.
+    ScRgb,
+}
+
 /// Defines the way a [`Window`] is displayed.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
@@ -1435,6 +1537,37 @@ mod tests {
         );
     }
 
+    // Checks that positions are converted between windows with different positions and scale factors.
+    #[test]
+    fn logical_position_between_windows() {
+        let low_dpi = Window {
+            position: WindowPosition::At(IVec2::new(0, 0)),
+            resolution: WindowResolution::new(800., 600.),
+            ..Default::default()
+        };
+        let high_dpi = Window {
+            position: WindowPosition::At(IVec2::new(800, 0)),
+            resolution: WindowResolution::new(800., 600.).with_scale_factor_override(2.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            high_dpi.logical_position_to(Vec2::new(10., 20.), &low_dpi),
+            Some(Vec2::new(820., 40.))
+        );
+        assert_eq!(
+            low_dpi.logical_position_to(Vec2::new(820., 40.), &high_dpi),
+            Some(Vec2::new(10., 20.))
+        );
+        assert_eq!(
+            high_dpi.logical_size_to(Vec2::new(10., 20.), &low_dpi),
+            Vec2::new(20., 40.)
+        );
+
+        let unplaced = Window::default();
+        assert!(unplaced.logical_position_to(Vec2::ZERO, &low_dpi).is_none());
+    }
+
     // Checks that `Window::physical_cursor_position` returns `None` if the cursor position is not
     // within the bounds of the window.
     #[test]
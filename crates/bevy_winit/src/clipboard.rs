@@ -0,0 +1,146 @@
+//! Backends giving the [`Clipboard`] access to the clipboard of the platform.
+
+use bevy_app::{App, Plugin};
+use bevy_window::Clipboard;
+
+/// Installs the [`ClipboardBackend`](bevy_window::ClipboardBackend) of the current platform in
+/// the [`Clipboard`].
+pub(crate) struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        let mut clipboard = app.world_mut().get_resource_or_init::<Clipboard>();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        match arboard::Clipboard::new() {
+            Ok(platform) => clipboard.set_backend(native::ArboardClipboard(platform)),
+            Err(error) => tracing::warn!(
+                "Couldn't access the clipboard, falling back to a clipboard local to the app: {error}"
+            ),
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        clipboard.set_backend(web::WebClipboard::default());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use alloc::borrow::Cow;
+
+    use bevy_window::{ClipboardBackend, ClipboardError, ClipboardImage};
+
+    pub(super) struct ArboardClipboard(pub(super) arboard::Clipboard);
+
+    fn convert_error(error: arboard::Error) -> ClipboardError {
+        match error {
+            arboard::Error::ContentNotAvailable => ClipboardError::ContentNotAvailable,
+            error => ClipboardError::Platform(error.to_string()),
+        }
+    }
+
+    impl ClipboardBackend for ArboardClipboard {
+        fn get_text(&mut self) -> Result<String, ClipboardError> {
+            self.0.get_text().map_err(convert_error)
+        }
+
+        fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+            self.0.set_text(text).map_err(convert_error)
+        }
+
+        fn get_image(&mut self) -> Result<ClipboardImage, ClipboardError> {
+            let image = self.0.get_image().map_err(convert_error)?;
+            Ok(ClipboardImage {
+                width: image.width as u32,
+                height: image.height as u32,
+                data: image.bytes.into_owned(),
+            })
+        }
+
+        fn set_image(&mut self, image: ClipboardImage) -> Result<(), ClipboardError> {
+            self.0
+                .set_image(arboard::ImageData {
+                    width: image.width as usize,
+                    height: image.height as usize,
+                    bytes: Cow::Owned(image.data),
+                })
+                .map_err(convert_error)
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use bevy_window::{
+        ClipboardBackend, ClipboardContent, ClipboardContentKind, ClipboardError, ClipboardPasted,
+        ClipboardRequestId,
+    };
+    use crossbeam_channel::{Receiver, Sender};
+    use wasm_bindgen_futures::JsFuture;
+
+    /// Uses the asynchronous clipboard API of the browser, which only supports text.
+    pub(super) struct WebClipboard {
+        sender: Sender<ClipboardPasted>,
+        receiver: Receiver<ClipboardPasted>,
+    }
+
+    impl Default for WebClipboard {
+        fn default() -> Self {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            Self { sender, receiver }
+        }
+    }
+
+    fn web_clipboard() -> Result<web_sys::Clipboard, ClipboardError> {
+        web_sys::window()
+            .map(|window| window.navigator().clipboard())
+            .ok_or_else(|| ClipboardError::Platform("no window is available".into()))
+    }
+
+    impl ClipboardBackend for WebClipboard {
+        fn get_text(&mut self) -> Result<String, ClipboardError> {
+            Err(ClipboardError::AsyncOnly)
+        }
+
+        fn set_text(&mut self, text: String) -> Result<(), ClipboardError> {
+            let promise = web_clipboard()?.write_text(&text);
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(error) = JsFuture::from(promise).await {
+                    tracing::warn!("Couldn't write to the clipboard: {error:?}");
+                }
+            });
+            Ok(())
+        }
+
+        fn start_paste(
+            &mut self,
+            request: ClipboardRequestId,
+            kind: ClipboardContentKind,
+        ) -> Option<Result<ClipboardContent, ClipboardError>> {
+            if kind != ClipboardContentKind::Text {
+                return Some(Err(ClipboardError::Unsupported(kind)));
+            }
+            let promise = match web_clipboard() {
+                Ok(clipboard) => clipboard.read_text(),
+                Err(error) => return Some(Err(error)),
+            };
+            let sender = self.sender.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = match JsFuture::from(promise).await {
+                    Ok(text) => text
+                        .as_string()
+                        .map(ClipboardContent::Text)
+                        .ok_or(ClipboardError::ContentNotAvailable),
+                    Err(error) => Err(ClipboardError::Platform(format!("{error:?}"))),
+                };
+                // The receiver is only dropped with the clipboard, when the pastes are ignored.
+                let _ = sender.send(ClipboardPasted { request, result });
+            });
+            None
+        }
+
+        fn poll_pastes(&mut self, pasted: &mut Vec<ClipboardPasted>) {
+            pasted.extend(self.receiver.try_iter());
+        }
+    }
+}
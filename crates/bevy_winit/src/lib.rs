@@ -43,6 +43,8 @@ use crate::{
 };
 
 pub mod accessibility;
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod converters;
 pub mod cursor;
 #[cfg(feature = "custom_cursor")]
@@ -139,6 +141,8 @@ impl<T: Event> Plugin for WinitPlugin<T> {
 
         app.add_plugins(AccessKitPlugin);
         app.add_plugins(cursor::CursorPlugin);
+        #[cfg(feature = "clipboard")]
+        app.add_plugins(clipboard::ClipboardPlugin);
 
         let event_loop = event_loop_builder
             .build()
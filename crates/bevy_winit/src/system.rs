@@ -9,7 +9,8 @@ use bevy_ecs::{
 use bevy_input::keyboard::KeyboardFocusLost;
 use bevy_window::{
     ClosingWindow, Monitor, PrimaryMonitor, RawHandleWrapper, VideoMode, Window, WindowClosed,
-    WindowClosing, WindowCreated, WindowFocused, WindowMode, WindowResized, WindowWrapper,
+    WindowClosing, WindowCreated, WindowFocused, WindowMode, WindowResized,
+    WindowScaleFactorChanged, WindowWrapper,
 };
 use tracing::{error, info, warn};
 
@@ -263,6 +264,7 @@ pub(crate) fn changed_windows(
     winit_windows: NonSendMut<WinitWindows>,
     monitors: Res<WinitMonitors>,
     mut window_resized: EventWriter<WindowResized>,
+    mut window_scale_factor_changed: EventWriter<WindowScaleFactorChanged>,
 ) {
     for (entity, mut window, mut cache) in &mut changed_windows {
         let Some(winit_window) = winit_windows.get_window(entity) else {
@@ -342,6 +344,15 @@ pub(crate) fn changed_windows(
             let scale_factor = window.scale_factor();
             let cached_scale_factor = cache.window.scale_factor();
 
+            // The OS scale factor is reported by `react_to_scale_factor_change`, so only changes
+            // to `scale_factor_override` are left to report here.
+            if scale_factor != cached_scale_factor {
+                window_scale_factor_changed.send(WindowScaleFactorChanged {
+                    window: entity,
+                    scale_factor: scale_factor as f64,
+                });
+            }
+
             // Check and update `winit`'s physical size only if the window is not maximized
             if scale_factor != cached_scale_factor && !winit_window.is_maximized() {
                 let logical_size =
@@ -26,7 +26,8 @@ fn setup(mut commands: Commands) {
         Hold 'Up' or 'Down' to change the line width of round gizmos\n\
         Press '1' / '2' to toggle the visibility of straight / round gizmos\n\
         Press 'U' / 'I' to cycle through line styles\n\
-        Press 'J' / 'K' to cycle through line joins",
+        Press 'J' / 'K' to cycle through line joins\n\
+        Press 'C' to cycle through the line caps of straight gizmos",
         ),
         Node {
             position_type: PositionType::Absolute,
@@ -157,6 +158,13 @@ fn update_config(
             GizmoLineJoint::None => GizmoLineJoint::Bevel,
         };
     }
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        config.line.cap = match config.line.cap {
+            GizmoLineCap::Butt => GizmoLineCap::Square,
+            GizmoLineCap::Square => GizmoLineCap::Round,
+            GizmoLineCap::Round => GizmoLineCap::Butt,
+        };
+    }
 
     let (my_config, _) = config_store.config_mut::<MyRoundGizmos>();
     if keyboard.pressed(KeyCode::ArrowUp) {
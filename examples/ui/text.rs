@@ -62,6 +62,12 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 font_size: 42.0,
                 ..default()
             },
+            // Like `TextColor`, text effects are set per section, so these only apply to "FPS: ".
+            TextOutline {
+                width: 2.0,
+                color: Color::BLACK,
+            },
+            TextDropShadow::default(),
         ))
         .with_child((
             TextSpan::default(),